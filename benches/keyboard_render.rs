@@ -0,0 +1,111 @@
+//! Keyboard widget frame preparation on a full-size board.
+//!
+//! Compares recomputing every key's draw data (what each frame did before the
+//! render cache) with reusing the cached data, and the same for whole frames.
+//!
+//! Run with `cargo bench --bench keyboard_render`.
+
+// criterion_group! generates undocumented public functions
+#![allow(missing_docs)]
+
+use criterion::{criterion_group, criterion_main, Criterion};
+use ratatui::{backend::TestBackend, layout::Rect, Terminal};
+
+use lazyqmk::config::Config;
+use lazyqmk::models::{
+    Category, KeyDefinition, KeyGeometry, KeyboardGeometry, Layer, Layout, Position, RgbColor,
+    VisualLayoutMapping,
+};
+use lazyqmk::tui::keyboard::KeyRenderCache;
+use lazyqmk::tui::{AppState, KeyboardWidget};
+
+const ROWS: u8 = 6;
+const COLS: u8 = 18;
+const KEYS: usize = 104;
+
+/// 104 keys on a 6×18 grid, with categories, color overrides and tap-hold
+/// keycodes mixed in.
+fn full_size_state() -> AppState {
+    let mut geometry = KeyboardGeometry::new("bench", "LAYOUT_fullsize_ansi", ROWS, COLS);
+    let mut layout = Layout::new("Full size").unwrap();
+    layout.categories = vec![
+        Category::new("nav", "Navigation", RgbColor::new(0, 200, 255)).unwrap(),
+        Category::new("mods", "Modifiers", RgbColor::new(255, 128, 0)).unwrap(),
+    ];
+    let mut layer = Layer::new(0, "Base", RgbColor::new(255, 255, 255)).unwrap();
+
+    let positions = (0..ROWS)
+        .flat_map(|row| (0..COLS).map(move |col| Position::new(row, col)))
+        .take(KEYS);
+    for (index, pos) in positions.enumerate() {
+        geometry.add_key(KeyGeometry::new(
+            (pos.row, pos.col),
+            u8::try_from(index).unwrap(),
+            f32::from(pos.col),
+            f32::from(pos.row),
+        ));
+        let key = match index % 4 {
+            0 => KeyDefinition::at(pos).keycode("KC_A").category("nav"),
+            1 => KeyDefinition::at(pos)
+                .keycode("LCTL_T(KC_S)")
+                .category("mods"),
+            2 => KeyDefinition::at(pos)
+                .keycode("KC_ENTER")
+                .color(RgbColor::new(200, 0, 100)),
+            _ => KeyDefinition::at(pos).keycode("LT(1, KC_SPC)"),
+        };
+        layer.add_key(key).unwrap();
+    }
+    layout.add_layer(layer).unwrap();
+    layout
+        .add_layer(Layer::new(1, "Nav", RgbColor::new(0, 0, 255)).unwrap())
+        .unwrap();
+
+    let mapping = VisualLayoutMapping::build(&geometry);
+    AppState::new(layout, None, geometry, mapping, Config::default()).unwrap()
+}
+
+fn frame_prep(c: &mut Criterion) {
+    let state = full_size_state();
+    let inner_area = Rect::new(1, 1, 200, 60);
+    let mut group = c.benchmark_group("frame_prep_104_keys");
+
+    group.bench_function("uncached", |b| {
+        let mut cache = KeyRenderCache::default();
+        b.iter(|| {
+            cache.invalidate();
+            cache.prepare(0, inner_area, &state, 0);
+        });
+    });
+    group.bench_function("cached", |b| {
+        let mut cache = KeyRenderCache::default();
+        b.iter(|| cache.prepare(0, inner_area, &state, 0));
+    });
+    group.finish();
+}
+
+fn full_frame(c: &mut Criterion) {
+    let state = full_size_state();
+    let mut terminal = Terminal::new(TestBackend::new(202, 62)).unwrap();
+    let mut group = c.benchmark_group("render_frame_104_keys");
+
+    group.bench_function("uncached", |b| {
+        b.iter(|| {
+            state.key_render_cache.borrow_mut().invalidate();
+            terminal
+                .draw(|f| KeyboardWidget::render(f, f.area(), &state))
+                .unwrap();
+        });
+    });
+    group.bench_function("cached", |b| {
+        b.iter(|| {
+            terminal
+                .draw(|f| KeyboardWidget::render(f, f.area(), &state))
+                .unwrap();
+        });
+    });
+    group.finish();
+}
+
+criterion_group!(benches, frame_prep, full_frame);
+criterion_main!(benches);
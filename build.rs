@@ -0,0 +1,72 @@
+//! Build script that merges the vendored QMK keycode snapshot.
+//!
+//! Reads `src/keycode_db/qmk/keycodes_<version>_<group>.hjson`, merges the
+//! files in version order (later versions override earlier ones for the same
+//! keycode value), and writes `$OUT_DIR/qmk_keycodes.json`, which is embedded
+//! into `KeycodeDb`.
+
+use std::collections::BTreeMap;
+use std::env;
+use std::fs;
+use std::path::{Path, PathBuf};
+
+const SNAPSHOT_DIR: &str = "src/keycode_db/qmk";
+
+#[derive(serde::Deserialize)]
+struct KeycodeFile {
+    #[serde(default)]
+    keycodes: BTreeMap<String, serde_json::Value>,
+}
+
+/// Parses "keycodes_0.0.3_quantum.hjson" into ([0, 0, 3], path).
+fn versioned_file(path: &Path) -> Option<(Vec<u32>, PathBuf)> {
+    let name = path.file_name()?.to_str()?;
+    let rest = name.strip_prefix("keycodes_")?.strip_suffix(".hjson")?;
+    let (version, _group) = rest.split_once('_')?;
+    let parts = version
+        .split('.')
+        .map(str::parse)
+        .collect::<Result<Vec<u32>, _>>()
+        .ok()?;
+    Some((parts, path.to_path_buf()))
+}
+
+fn main() {
+    println!("cargo:rerun-if-changed={SNAPSHOT_DIR}");
+
+    let mut files: Vec<(Vec<u32>, PathBuf)> = fs::read_dir(SNAPSHOT_DIR)
+        .expect("keycode snapshot directory missing")
+        .filter_map(Result::ok)
+        .filter_map(|entry| versioned_file(&entry.path()))
+        .collect();
+    files.sort();
+
+    let mut merged: BTreeMap<String, serde_json::Value> = BTreeMap::new();
+    for (_, path) in &files {
+        println!("cargo:rerun-if-changed={}", path.display());
+        let content = fs::read_to_string(path).expect("failed to read keycode snapshot");
+        let file: KeycodeFile = json5::from_str(&content)
+            .unwrap_or_else(|e| panic!("failed to parse {}: {e}", path.display()));
+        for (value, mut keycode) in file.keycodes {
+            keycode["value"] = serde_json::Value::String(value.clone());
+            merged.insert(value, keycode);
+        }
+    }
+
+    let version = files.last().map_or_else(String::new, |(parts, _)| {
+        parts
+            .iter()
+            .map(u32::to_string)
+            .collect::<Vec<_>>()
+            .join(".")
+    });
+
+    let output = serde_json::json!({
+        "version": version,
+        "keycodes": merged.into_values().collect::<Vec<_>>(),
+    });
+
+    let out_path =
+        PathBuf::from(env::var("OUT_DIR").expect("OUT_DIR not set")).join("qmk_keycodes.json");
+    fs::write(out_path, output.to_string()).expect("failed to write qmk_keycodes.json");
+}
@@ -1,5 +1,215 @@
+use crate::app::startup_profile::StartupProfile;
+use crate::app::variant_resolution;
+use crate::keycode_db::KeycodeDb;
+use crate::services::geometry::{GeometryContext, GeometryResult};
 use crate::{config, models, services, tui};
-use anyhow::Result;
+use anyhow::{Context, Result};
+use std::path::PathBuf;
+use std::sync::mpsc;
+use std::thread;
+use std::time::{Duration, Instant};
+
+/// How `lazyqmk edit` opens a layout.
+#[derive(Debug, Clone, Default)]
+pub struct EditOptions {
+    /// Layout variant to use if the layout's variant no longer exists
+    pub assume_variant: Option<String>,
+    /// Print a startup timing breakdown on exit
+    pub profile_startup: bool,
+    /// Layer shown when the editor opens
+    pub layer: Option<usize>,
+    /// Key selected when the editor opens
+    pub position: Option<models::Position>,
+    /// Discard edits and refuse to save
+    pub read_only: bool,
+    /// QMK firmware path used instead of the configured one
+    pub qmk_path: Option<PathBuf>,
+    /// Start builds without checking the build environment
+    pub skip_env_check: bool,
+}
+
+/// Opens a layout file in the editor.
+///
+/// Geometry is built on a worker thread while the keycode database loads and
+/// the terminal shows the layout name with a spinner. Parsed `info.json` data
+/// is cached in the config directory, so reopening a layout skips JSON5
+/// parsing. With `profile_startup`, a timing breakdown is printed on exit.
+pub fn open_layout_file(path: PathBuf, options: &EditOptions) -> Result<()> {
+    let assume_variant = options.assume_variant.as_deref();
+    let mut profile = StartupProfile::start();
+
+    let mut layout = profile.measure("layout parse", || services::LayoutService::load(&path))?;
+    let mut config = profile.measure("config load", || config::Config::for_layout(&path))?;
+    if let Some(qmk_path) = &options.qmk_path {
+        config.paths.qmk_firmware = Some(qmk_path.clone());
+    }
+    if let Ok(paths) = crate::paths::AppPaths::current() {
+        config.paths.info_cache = Some(paths.info_cache_dir());
+    }
+
+    // Try to build proper geometry from QMK if config is available
+    let pending = if config.paths.qmk_firmware.is_some() {
+        // Get layout variant from metadata
+        let layout_variant = layout.metadata.layout_variant.clone()
+            .ok_or_else(|| anyhow::anyhow!("Layout variant not specified in layout metadata - layout may be from an older version"))?;
+        Some((
+            spawn_geometry_build(&config, &layout.metadata, &layout_variant),
+            layout_variant,
+        ))
+    } else {
+        None
+    };
+
+    let keycode_db = profile
+        .measure("keycode DB load", KeycodeDb::load)
+        .context("Failed to load keycode database")?;
+    let mut terminal = tui::setup_terminal()?;
+
+    let mut status = None;
+    let (geometry, mapping) = if let Some((receiver, layout_variant)) = pending {
+        let theme = tui::Theme::from_mode(config.ui.theme_mode);
+        // The loading screen is the first frame the user sees
+        let waited = tui::loading::wait_with_spinner(
+            &mut terminal,
+            &theme,
+            &layout.metadata.name,
+            "Loading keyboard geometry...",
+            &receiver,
+            config.ui.reduced_motion,
+            |render| profile.mark_first_frame(render),
+        );
+        let (attempt, duration) = match waited {
+            Ok(result) => result,
+            Err(e) => {
+                tui::restore_terminal(terminal)?;
+                return Err(e);
+            }
+        };
+        profile.record("geometry build", duration);
+
+        match attempt {
+            Ok(result) => (result.geometry, result.mapping),
+            Err(e)
+                if e.downcast_ref::<services::geometry::MissingVariantError>()
+                    .is_some() =>
+            {
+                // The variant dialog needs the terminal to itself
+                tui::restore_terminal(terminal)?;
+                let resolved = variant_resolution::resolve_geometry_attempt(
+                    &config,
+                    &mut layout,
+                    &layout_variant,
+                    assume_variant,
+                    Err(e),
+                )?;
+                let Some(resolved) = resolved else {
+                    println!("Layout not opened.");
+                    return Ok(());
+                };
+                status = resolved.status_message(&layout);
+                terminal = tui::setup_terminal()?;
+                (resolved.geometry, resolved.mapping)
+            }
+            Err(e) if assume_variant.is_some() => {
+                tui::restore_terminal(terminal)?;
+                return Err(e);
+            }
+            Err(_) => {
+                // Fall back to minimal geometry on error
+                let geo_result = services::geometry::build_minimal_geometry();
+                (geo_result.geometry, geo_result.mapping)
+            }
+        }
+    } else {
+        // No QMK path configured, use minimal geometry
+        let geo_result = services::geometry::build_minimal_geometry();
+        (geo_result.geometry, geo_result.mapping)
+    };
+
+    // Initialize TUI
+    let mut app_state = match tui::AppState::with_keycode_db(
+        layout,
+        Some(path),
+        geometry,
+        mapping,
+        config,
+        keycode_db,
+    ) {
+        Ok(state) => state,
+        Err(e) => {
+            tui::restore_terminal(terminal)?;
+            return Err(e);
+        }
+    };
+    app_state.startup_profile = Some(profile);
+    app_state.skip_env_check = options.skip_env_check;
+
+    // Adjust layers to match geometry (ensures keys match visual positions).
+    // Keys outside the geometry are not dropped silently: the editor asks first.
+    let adjusted = if options.read_only {
+        app_state.adjust_layers_to_geometry().map(|_| ())
+    } else {
+        app_state.check_geometry_on_load()
+    };
+    if let Err(e) = adjusted {
+        tui::restore_terminal(terminal)?;
+        return Err(e);
+    }
+
+    if let Err(e) = app_state.focus(options.layer, options.position) {
+        tui::restore_terminal(terminal)?;
+        return Err(e);
+    }
+
+    if options.read_only {
+        app_state.set_read_only();
+    } else if let Some(status) = status {
+        // A replaced variant is kept only once the user saves
+        app_state.mark_dirty();
+        app_state.log_action("Replace variant", status);
+    }
+
+    // Run main TUI loop (restores the terminal when done)
+    let result = tui::run_editor(&mut app_state, terminal);
+    if options.profile_startup {
+        if let Some(profile) = &app_state.startup_profile {
+            eprint!("{}", profile.report());
+        }
+        let cache = &app_state.validation_cache;
+        eprintln!(
+            "Validation cache: {} hit(s), {} miss(es)",
+            cache.hits(),
+            cache.misses()
+        );
+        eprintln!(
+            "Key render cache: {} full rebuild(s)",
+            app_state.key_render_cache.borrow().rebuilds()
+        );
+    }
+    result
+}
+
+/// Builds geometry on a worker thread, sending the result and its duration.
+fn spawn_geometry_build(
+    config: &config::Config,
+    metadata: &models::LayoutMetadata,
+    layout_variant: &str,
+) -> mpsc::Receiver<(Result<GeometryResult>, Duration)> {
+    let (sender, receiver) = mpsc::channel();
+    let config = config.clone();
+    let metadata = metadata.clone();
+    let layout_variant = layout_variant.to_string();
+    thread::spawn(move || {
+        let start = Instant::now();
+        let context = GeometryContext {
+            config: &config,
+            metadata: &metadata,
+        };
+        let result = services::geometry::build_geometry_for_layout(context, &layout_variant);
+        let _ = sender.send((result, start.elapsed()));
+    });
+    receiver
+}
 
 /// Creates a default layout from QMK keyboard info and launches the editor
 pub fn launch_editor_with_default_layout(
@@ -8,44 +218,25 @@ pub fn launch_editor_with_default_layout(
     layout_variant: &str,
     layout_file_name: &str,
 ) -> Result<()> {
-    // Create a default layout with the user-specified name
-    let mut layout = models::Layout::new(layout_file_name)?;
-
-    // Set keyboard in metadata for geometry building
-    layout.metadata.keyboard = Some(keyboard.to_string());
-
-    // Build geometry using the centralized geometry service
-    let geo_context = services::geometry::GeometryContext {
+    // Create a blank layout from the keyboard's defaults
+    let services::new_layout::NewLayout {
+        layout,
+        geometry,
+        mapping,
+    } = services::new_layout::create(
         config,
-        metadata: &layout.metadata,
-    };
-
-    let geo_result = services::geometry::build_geometry_for_layout(geo_context, layout_variant)?;
-    let geometry = geo_result.geometry;
-    let mapping = geo_result.mapping;
-
-    // Sanitize the layout name for use as keymap directory name
-    // This must be done BEFORE setting metadata to avoid conflicts with QMK's built-in keymaps
-    let sanitized_name = layout_file_name
-        .replace('/', "_")
-        .replace('\\', "_")
-        .replace(':', "_")
-        .replace(' ', "_")
-        .to_lowercase();
-
-    // Update the layout metadata with the resolved variant path
-    layout.metadata.keyboard = Some(geo_result.variant_path);
-    layout.metadata.layout_variant = Some(layout_variant.to_string());
-    // Use sanitized layout name as keymap name to avoid conflicts with default keymaps
-    layout.metadata.keymap_name = Some(sanitized_name.clone());
-    layout.metadata.output_format = Some("uf2".to_string());
-
-    // Add a default base layer with KC_TRNS for all positions
-    let base_layer = create_default_layer(0, "Base", &mapping)?;
-    layout.add_layer(base_layer)?;
+        services::new_layout::NewLayoutOptions {
+            keyboard,
+            variant: layout_variant,
+            name: layout_file_name,
+            keymap: None,
+            layer_count: None,
+        },
+    )?;
+    let sanitized_name = services::new_layout::sanitize_name(layout_file_name);
 
     // Create save path using the user-specified layout name
-    let layouts_dir = config::Config::config_dir()?.join("layouts");
+    let layouts_dir = crate::paths::layouts_dir()?;
     std::fs::create_dir_all(&layouts_dir)?;
 
     let layout_path = layouts_dir.join(format!("{}.md", sanitized_name));
@@ -56,47 +247,20 @@ pub fn launch_editor_with_default_layout(
     println!("Layout saved to: {}", layout_path.display());
     println!();
 
+    // Preselect this variant for the keyboard's next layout
+    let mut config = config.clone();
+    config.remember_variant(keyboard, layout_variant);
+    if let Err(e) = config.save() {
+        eprintln!("Warning: failed to remember the layout variant: {e}");
+    }
+
     // Initialize TUI with the generated layout
-    let mut terminal = tui::setup_terminal()?;
-    let mut app_state =
-        tui::AppState::new(layout, Some(layout_path), geometry, mapping, config.clone())?;
+    let terminal = tui::setup_terminal()?;
+    let mut app_state = tui::AppState::new(layout, Some(layout_path), geometry, mapping, config)?;
 
     // Layout is clean since we just saved it
     app_state.dirty = false;
 
-    // Run main TUI loop
-    let result = tui::run_tui(&mut app_state, &mut terminal);
-
-    // Restore terminal
-    tui::restore_terminal(terminal)?;
-
-    // Check for errors
-    result?;
-
-    Ok(())
-}
-
-/// Creates a default layer with KC_TRNS for all key positions
-pub fn create_default_layer(
-    number: u8,
-    name: &str,
-    mapping: &models::VisualLayoutMapping,
-) -> Result<models::Layer> {
-    use models::layer::KeyDefinition;
-    use models::ColorPalette;
-
-    // Use the color palette's default layer color (Gray-500)
-    let palette = ColorPalette::load().unwrap_or_default();
-    let default_color = palette.default_layer_color();
-
-    let mut layer = models::Layer::new(number, name.to_string(), default_color)?;
-
-    // Add KC_TRNS for each visual position in the mapping
-    // This ensures keys use visual positions (not matrix positions) for proper rendering
-    for pos in mapping.get_all_visual_positions() {
-        let key = KeyDefinition::new(pos, "KC_TRNS".to_string());
-        layer.add_key(key);
-    }
-
-    Ok(layer)
+    // Run main TUI loop (restores the terminal when done)
+    tui::run_editor(&mut app_state, terminal)
 }
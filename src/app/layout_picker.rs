@@ -6,7 +6,7 @@ use crate::tui::component::Component;
 use crate::tui::layout_picker::{LayoutPicker, LayoutPickerEvent};
 use crate::{config, services, tui};
 
-use super::onboarding;
+use super::{onboarding, variant_resolution};
 
 /// Runs the layout picker to choose between creating new or loading existing layouts
 pub fn run_layout_picker_terminal(config: &config::Config) -> Result<()> {
@@ -43,50 +43,51 @@ pub fn run_layout_picker_terminal(config: &config::Config) -> Result<()> {
                             println!();
 
                             // Load the selected layout
-                            let layout = services::LayoutService::load(&path)?;
+                            let mut layout = services::LayoutService::load(&path)?;
 
                             // Get layout variant from layout metadata
                             let layout_variant =
-                                layout.metadata.layout_variant.as_ref().ok_or_else(|| {
+                                layout.metadata.layout_variant.clone().ok_or_else(|| {
                                     anyhow::anyhow!(
                                         "Layout variant not specified in layout metadata"
                                     )
                                 })?;
 
-                            // Build geometry using the centralized geometry service
-                            let geo_context = services::geometry::GeometryContext {
+                            // Build geometry, letting the user pick a replacement
+                            // if the variant no longer exists
+                            let Some(resolved) = variant_resolution::resolve_geometry(
                                 config,
-                                metadata: &layout.metadata,
+                                &mut layout,
+                                &layout_variant,
+                                None,
+                            )?
+                            else {
+                                println!("Layout selection cancelled.");
+                                return Ok(());
                             };
-
-                            let geo_result = services::geometry::build_geometry_for_layout(
-                                geo_context,
-                                layout_variant,
-                            )?;
-                            let geometry = geo_result.geometry;
-                            let mapping = geo_result.mapping;
+                            let status = resolved.status_message(&layout);
 
                             // Re-initialize terminal for editor
-                            let mut terminal = tui::setup_terminal()?;
+                            let terminal = tui::setup_terminal()?;
                             let mut app_state = tui::AppState::new(
                                 layout,
                                 Some(path),
-                                geometry,
-                                mapping,
+                                resolved.geometry,
+                                resolved.mapping,
                                 config.clone(),
                             )?;
 
                             // Adjust layers to match geometry (ensures keys match visual positions)
                             app_state.adjust_layers_to_geometry()?;
 
-                            // Run main TUI loop
-                            let result = tui::run_tui(&mut app_state, &mut terminal);
-
-                            // Restore terminal
-                            tui::restore_terminal(terminal)?;
+                            // A replaced variant is kept only once the user saves
+                            if let Some(status) = status {
+                                app_state.mark_dirty();
+                                app_state.log_action("Replace variant", status);
+                            }
 
-                            // Check for errors
-                            result?;
+                            // Run main TUI loop (restores the terminal when done)
+                            tui::run_editor(&mut app_state, terminal)?;
                             return Ok(());
                         }
                         LayoutPickerEvent::Cancelled => {
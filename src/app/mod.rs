@@ -12,6 +12,12 @@ pub mod layout_picker;
 
 pub mod onboarding;
 
+/// Timing of startup phases for `--profile-startup`
+pub mod startup_profile;
+
+/// Recovery for layouts whose layout variant no longer exists
+pub mod variant_resolution;
+
 // Re-export commonly used functions for convenience
 pub use layout_picker::run_layout_picker_terminal;
 pub use onboarding::run_onboarding_wizard_terminal;
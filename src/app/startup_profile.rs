@@ -0,0 +1,188 @@
+//! Timing of the editor's startup phases (`lazyqmk --profile-startup`).
+//!
+//! Phases are measured individually; geometry is built on a worker thread
+//! while the keycode database loads, so phase durations can add up to more
+//! than the time to the first frame.
+
+use std::fmt::Write as _;
+use std::time::{Duration, Instant};
+
+/// Target time from launch to the first rendered frame.
+pub const FIRST_FRAME_BUDGET: Duration = Duration::from_millis(300);
+
+/// Durations of startup phases, in the order they finished.
+#[derive(Debug, Clone)]
+pub struct StartupProfile {
+    started: Instant,
+    phases: Vec<(&'static str, Duration)>,
+    first_frame: Option<Duration>,
+}
+
+impl StartupProfile {
+    /// Starts timing at the current instant.
+    #[must_use]
+    pub fn start() -> Self {
+        Self {
+            started: Instant::now(),
+            phases: Vec::new(),
+            first_frame: None,
+        }
+    }
+
+    /// Runs `f` and records how long it took as phase `name`.
+    pub fn measure<T>(&mut self, name: &'static str, f: impl FnOnce() -> T) -> T {
+        let start = Instant::now();
+        let result = f();
+        self.record(name, start.elapsed());
+        result
+    }
+
+    /// Records a phase measured elsewhere (e.g., on a worker thread).
+    pub fn record(&mut self, name: &'static str, duration: Duration) {
+        self.phases.push((name, duration));
+    }
+
+    /// Records the first rendered frame; later calls are ignored.
+    pub fn mark_first_frame(&mut self, render: Duration) {
+        if self.first_frame.is_none() {
+            self.record("first render", render);
+            self.first_frame = Some(self.started.elapsed());
+        }
+    }
+
+    /// Whether the first frame was rendered within [`FIRST_FRAME_BUDGET`].
+    #[must_use]
+    pub fn within_budget(&self) -> bool {
+        self.first_frame
+            .is_some_and(|first_frame| first_frame <= FIRST_FRAME_BUDGET)
+    }
+
+    /// Human-readable breakdown, one phase per line.
+    #[must_use]
+    pub fn report(&self) -> String {
+        let mut report = String::from("Startup profile:\n");
+        for (name, duration) in &self.phases {
+            let _ = writeln!(report, "  {name:<16} {:>8.1} ms", millis(*duration));
+        }
+        match self.first_frame {
+            Some(first_frame) => {
+                let _ = writeln!(
+                    report,
+                    "  {:<16} {:>8.1} ms (budget {} ms{})",
+                    "first frame",
+                    millis(first_frame),
+                    FIRST_FRAME_BUDGET.as_millis(),
+                    if self.within_budget() {
+                        ""
+                    } else {
+                        ", exceeded"
+                    }
+                );
+            }
+            None => report.push_str("  first frame      not rendered\n"),
+        }
+        report
+    }
+}
+
+fn millis(duration: Duration) -> f64 {
+    duration.as_secs_f64() * 1000.0
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::config::Config;
+    use crate::models::Layout;
+    use crate::parser::keyboard_json;
+    use crate::services::geometry::{self, GeometryContext};
+    use crate::tui::AppState;
+    use ratatui::backend::TestBackend;
+    use ratatui::Terminal;
+    use std::path::PathBuf;
+
+    #[test]
+    fn test_report_lists_phases_and_budget() {
+        let mut profile = StartupProfile::start();
+        let value = profile.measure("config load", || 42);
+        profile.record("geometry build", Duration::from_millis(12));
+        assert_eq!(value, 42);
+        assert!(!profile.within_budget());
+        assert!(profile.report().contains("not rendered"));
+
+        profile.mark_first_frame(Duration::from_millis(3));
+        // Only the first frame counts
+        profile.mark_first_frame(Duration::from_millis(50));
+        assert!(profile.within_budget());
+
+        let report = profile.report();
+        assert!(report.contains("first render          3.0 ms"));
+        assert!(!report.contains("50.0 ms"));
+        assert!(report.contains("config load"));
+        assert!(report.contains("geometry build       12.0 ms"));
+        assert!(report.contains("budget 300 ms"));
+    }
+
+    /// Runs the editor's startup phases against the mock QMK tree with a warm
+    /// info.json cache and checks the geometry comes from the cache.
+    ///
+    /// The first frame budget is wall-clock time and isn't asserted here;
+    /// `lazyqmk --profile-startup` reports it.
+    #[test]
+    fn test_cached_startup_reads_info_json_from_cache() {
+        let qmk_path = PathBuf::from(env!("CARGO_MANIFEST_DIR")).join("tests/fixtures/mock_qmk");
+        let cache = tempfile::TempDir::new().unwrap();
+
+        let mut config = Config::default();
+        config.paths.qmk_firmware = Some(qmk_path.clone());
+        config.paths.info_cache = Some(cache.path().to_path_buf());
+        let mut layout = Layout::new("Startup").unwrap();
+        layout.metadata.keyboard = Some("crkbd".to_string());
+        let context = || GeometryContext {
+            config: &config,
+            metadata: &layout.metadata,
+        };
+        // Warm the cache, as a previous session would have
+        geometry::build_geometry_for_layout(context(), "LAYOUT_split_3x6_3").unwrap();
+        let entry_path = cache.path().join("crkbd.json");
+        let mut entry: serde_json::Value =
+            serde_json::from_str(&std::fs::read_to_string(&entry_path).unwrap()).unwrap();
+        // Mark the entry, so only a cache hit returns this name
+        entry["info"]["keyboard_name"] = "from cache".into();
+        std::fs::write(&entry_path, entry.to_string()).unwrap();
+
+        let mut profile = StartupProfile::start();
+        let result = profile.measure("geometry build", || {
+            geometry::build_geometry_for_layout(context(), "LAYOUT_split_3x6_3")
+        });
+        let result = result.unwrap();
+        let keycode_db = profile
+            .measure("keycode DB load", crate::keycode_db::KeycodeDb::load)
+            .unwrap();
+        let state = AppState::with_keycode_db(
+            layout.clone(),
+            None,
+            result.geometry,
+            result.mapping,
+            config.clone(),
+            keycode_db,
+        )
+        .unwrap();
+
+        let mut terminal = Terminal::new(TestBackend::new(160, 50)).unwrap();
+        let render = Instant::now();
+        terminal
+            .draw(|f| crate::tui::render_frame(f, &state))
+            .unwrap();
+        profile.mark_first_frame(render.elapsed());
+        assert!(!profile.report().contains("not rendered"));
+
+        let info =
+            keyboard_json::parse_keyboard_info_json_cached(&qmk_path, "crkbd", Some(cache.path()))
+                .unwrap();
+        assert_eq!(info.keyboard_name.as_deref(), Some("from cache"));
+        // Without a cache directory the files are parsed again
+        let info = keyboard_json::parse_keyboard_info_json(&qmk_path, "crkbd").unwrap();
+        assert_ne!(info.keyboard_name.as_deref(), Some("from cache"));
+    }
+}
@@ -0,0 +1,163 @@
+//! Recovery for layouts whose layout variant no longer exists.
+//!
+//! When a keyboard's layout variant is renamed upstream, building geometry
+//! fails with [`MissingVariantError`]. Instead of aborting, the user picks a
+//! replacement (interactively, or with `--assume-variant`) and the layout's
+//! layers are fitted to the new geometry before the editor opens.
+
+use anyhow::{bail, Result};
+use crossterm::event::{self, Event};
+use std::time::Duration;
+
+use crate::models::{KeyboardGeometry, Layout, VisualLayoutMapping};
+use crate::services::geometry::{
+    adjust_layout_to_mapping, build_geometry_for_layout, GeometryAdjustReport, GeometryContext,
+    GeometryResult, MissingVariantError,
+};
+use crate::tui::component::Component;
+use crate::tui::variant_conflict::{VariantConflictDialog, VariantConflictEvent};
+use crate::{config, tui};
+
+/// Geometry for a layout, after resolving a missing variant if needed.
+#[derive(Debug)]
+pub struct ResolvedGeometry {
+    /// Keyboard geometry
+    pub geometry: KeyboardGeometry,
+    /// Visual layout mapping
+    pub mapping: VisualLayoutMapping,
+    /// Set when a replacement variant was chosen: the changes made to fit it
+    pub adjustment: Option<GeometryAdjustReport>,
+}
+
+impl ResolvedGeometry {
+    /// Status message describing the replacement, if one was made.
+    #[must_use]
+    pub fn status_message(&self, layout: &Layout) -> Option<String> {
+        let report = self.adjustment.as_ref()?;
+        let variant = layout.metadata.layout_variant.as_deref().unwrap_or("?");
+        Some(format!(
+            "Switched to {variant}: {} - save to keep the new variant",
+            report.summary()
+        ))
+    }
+}
+
+/// Builds geometry for `layout`, resolving a missing layout variant.
+///
+/// If the variant exists, the layout is untouched. Otherwise the replacement
+/// is taken from `assume_variant` or picked in a TUI dialog; the layout's
+/// metadata is updated in memory and its layers are fitted to the new
+/// geometry. Nothing is written to disk.
+///
+/// Returns `Ok(None)` if the user cancelled the dialog.
+///
+/// # Errors
+///
+/// Returns an error if geometry cannot be built for reasons other than a
+/// missing variant, or if `assume_variant` is not a layout of the keyboard.
+pub fn resolve_geometry(
+    config: &config::Config,
+    layout: &mut Layout,
+    layout_variant: &str,
+    assume_variant: Option<&str>,
+) -> Result<Option<ResolvedGeometry>> {
+    let context = GeometryContext {
+        config,
+        metadata: &layout.metadata,
+    };
+    let attempt = build_geometry_for_layout(context, layout_variant);
+    resolve_geometry_attempt(config, layout, layout_variant, assume_variant, attempt)
+}
+
+/// Like [`resolve_geometry`], starting from an already attempted build of
+/// `layout_variant` (e.g., one run on a worker thread during startup).
+///
+/// # Errors
+///
+/// Same as [`resolve_geometry`].
+pub fn resolve_geometry_attempt(
+    config: &config::Config,
+    layout: &mut Layout,
+    layout_variant: &str,
+    assume_variant: Option<&str>,
+    attempt: Result<GeometryResult>,
+) -> Result<Option<ResolvedGeometry>> {
+    let error = match attempt {
+        Ok(result) => {
+            return Ok(Some(ResolvedGeometry {
+                geometry: result.geometry,
+                mapping: result.mapping,
+                adjustment: None,
+            }))
+        }
+        Err(e) => e,
+    };
+    let Some(conflict) = error.downcast_ref::<MissingVariantError>() else {
+        return Err(error);
+    };
+
+    let replacement = if let Some(name) = assume_variant {
+        if conflict.candidate(name).is_none() {
+            bail!("--assume-variant '{name}' is not a layout of this keyboard. {conflict}");
+        }
+        name.to_string()
+    } else {
+        let layout_keys = layout.layers.first().map_or(0, |layer| layer.keys.len());
+        match prompt_for_variant(conflict.clone(), layout_keys)? {
+            Some(name) => name,
+            None => return Ok(None),
+        }
+    };
+
+    let context = GeometryContext {
+        config,
+        metadata: &layout.metadata,
+    };
+    let result = build_geometry_for_layout(context, &replacement)?;
+
+    layout.metadata.layout_variant = Some(replacement.clone());
+    layout.metadata.keyboard = Some(result.variant_path);
+    let report = adjust_layout_to_mapping(layout, &result.mapping);
+
+    println!("Layout variant '{layout_variant}' replaced by '{replacement}'");
+    println!("  {}", report.summary());
+    for (layer, position, keycode) in &report.removed_assigned {
+        println!(
+            "  - removed {keycode} from layer {layer} ({}, {})",
+            position.row, position.col
+        );
+    }
+    println!();
+
+    Ok(Some(ResolvedGeometry {
+        geometry: result.geometry,
+        mapping: result.mapping,
+        adjustment: Some(report),
+    }))
+}
+
+/// Shows the variant conflict dialog and returns the chosen variant.
+fn prompt_for_variant(conflict: MissingVariantError, layout_keys: usize) -> Result<Option<String>> {
+    let mut terminal = tui::setup_terminal()?;
+    let mut dialog = VariantConflictDialog::new(conflict, layout_keys);
+
+    loop {
+        let theme = tui::Theme::detect();
+
+        terminal.draw(|f| {
+            dialog.render(f, f.area(), &theme);
+        })?;
+
+        if event::poll(Duration::from_millis(100))? {
+            if let Event::Key(key) = event::read()? {
+                if let Some(event) = dialog.handle_input(key) {
+                    tui::restore_terminal(terminal)?;
+                    return Ok(match event {
+                        VariantConflictEvent::Selected(name) => Some(name),
+                        VariantConflictEvent::Cancelled => None,
+                    });
+                }
+            }
+        }
+    }
+}
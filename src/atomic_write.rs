@@ -0,0 +1,195 @@
+//! Crash-safe file writes.
+//!
+//! Every file LazyQMK writes (layouts, templates, config, generated firmware
+//! sources, exports) goes through [`write`] or [`write_with`]. Content is
+//! written to a temporary file in the target's directory, flushed to disk, and
+//! renamed over the target, so a crash or a full disk leaves either the old
+//! file or the new one — never a truncated mix.
+
+use anyhow::{Context, Result};
+use std::fs::{self, OpenOptions};
+use std::io::{self, Write};
+use std::path::{Path, PathBuf};
+use std::sync::atomic::{AtomicU32, Ordering};
+
+/// Counter that keeps temp file names unique within the process.
+static TEMP_COUNTER: AtomicU32 = AtomicU32::new(0);
+
+/// How many times a rename over an existing file is retried on Windows.
+#[cfg(windows)]
+const RENAME_ATTEMPTS: u32 = 5;
+
+/// Atomically replaces `path` with `contents`.
+///
+/// # Errors
+///
+/// Returns an error if the temp file cannot be created, written, synced, or
+/// renamed over `path`. The original file is left untouched in every case.
+pub fn write(path: &Path, contents: impl AsRef<[u8]>) -> Result<()> {
+    write_with(path, |file| file.write_all(contents.as_ref()))
+}
+
+/// Atomically replaces `path` with whatever `write_contents` writes.
+///
+/// The callback receives the temp file. If it fails, the temp file is removed
+/// and `path` keeps its previous content. Permissions of an existing target
+/// are copied to the new file.
+///
+/// # Errors
+///
+/// Returns the callback's error, or any error creating, syncing, or renaming
+/// the temp file.
+pub fn write_with<F>(path: &Path, write_contents: F) -> Result<()>
+where
+    F: FnOnce(&mut dyn Write) -> io::Result<()>,
+{
+    let temp_path = temp_path_for(path);
+    let result =
+        write_temp(&temp_path, path, write_contents).and_then(|()| replace(&temp_path, path));
+    if result.is_err() {
+        let _ = fs::remove_file(&temp_path);
+    }
+    result
+}
+
+/// Writes and syncs the temp file, copying permissions from `target`.
+fn write_temp<F>(temp_path: &Path, target: &Path, write_contents: F) -> Result<()>
+where
+    F: FnOnce(&mut dyn Write) -> io::Result<()>,
+{
+    let mut file = OpenOptions::new()
+        .write(true)
+        .create_new(true)
+        .open(temp_path)
+        .with_context(|| format!("Failed to create temporary file: {}", temp_path.display()))?;
+
+    write_contents(&mut file)
+        .and_then(|()| file.flush())
+        .with_context(|| format!("Failed to write to temporary file: {}", temp_path.display()))?;
+
+    if let Ok(metadata) = fs::metadata(target) {
+        file.set_permissions(metadata.permissions())
+            .with_context(|| format!("Failed to copy permissions of {}", target.display()))?;
+    }
+
+    file.sync_all()
+        .with_context(|| format!("Failed to sync temporary file: {}", temp_path.display()))
+}
+
+/// Renames the temp file over `target` and syncs the containing directory.
+fn replace(temp_path: &Path, target: &Path) -> Result<()> {
+    rename_over(temp_path, target)
+        .with_context(|| format!("Failed to rename temporary file to: {}", target.display()))?;
+    sync_parent_dir(target);
+    Ok(())
+}
+
+#[cfg(not(windows))]
+fn rename_over(from: &Path, to: &Path) -> io::Result<()> {
+    fs::rename(from, to)
+}
+
+/// Windows briefly locks files that are being scanned or indexed, which makes
+/// replacing an existing file fail with "access denied", so retry a few times.
+#[cfg(windows)]
+fn rename_over(from: &Path, to: &Path) -> io::Result<()> {
+    let mut attempt = 1;
+    loop {
+        match fs::rename(from, to) {
+            Err(e) if e.kind() == io::ErrorKind::PermissionDenied && attempt < RENAME_ATTEMPTS => {
+                std::thread::sleep(std::time::Duration::from_millis(20 * u64::from(attempt)));
+                attempt += 1;
+            }
+            result => return result,
+        }
+    }
+}
+
+/// Makes the rename itself durable. Best effort: not all filesystems allow
+/// opening a directory for syncing.
+#[cfg(unix)]
+fn sync_parent_dir(path: &Path) {
+    let parent = match path.parent() {
+        Some(parent) if !parent.as_os_str().is_empty() => parent,
+        _ => Path::new("."),
+    };
+    if let Ok(dir) = fs::File::open(parent) {
+        let _ = dir.sync_all();
+    }
+}
+
+#[cfg(not(unix))]
+const fn sync_parent_dir(_path: &Path) {}
+
+/// Temp file path next to `path`, e.g. `.layout.md.1234.0.tmp`.
+fn temp_path_for(path: &Path) -> PathBuf {
+    let name = path
+        .file_name()
+        .map_or_else(|| "file".into(), |name| name.to_string_lossy());
+    let unique = TEMP_COUNTER.fetch_add(1, Ordering::Relaxed);
+    path.with_file_name(format!(".{name}.{}.{unique}.tmp", std::process::id()))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tempfile::TempDir;
+
+    fn leftover_temp_files(dir: &Path) -> usize {
+        fs::read_dir(dir)
+            .unwrap()
+            .filter(|entry| {
+                entry
+                    .as_ref()
+                    .unwrap()
+                    .file_name()
+                    .to_string_lossy()
+                    .ends_with(".tmp")
+            })
+            .count()
+    }
+
+    #[test]
+    fn test_write_replaces_content() {
+        let temp = TempDir::new().unwrap();
+        let path = temp.path().join("layout.md");
+
+        write(&path, "first").unwrap();
+        write(&path, "second").unwrap();
+
+        assert_eq!(fs::read_to_string(&path).unwrap(), "second");
+        assert_eq!(leftover_temp_files(temp.path()), 0);
+    }
+
+    #[test]
+    fn test_failure_mid_write_leaves_original_untouched() {
+        let temp = TempDir::new().unwrap();
+        let path = temp.path().join("layout.md");
+        fs::write(&path, "original content").unwrap();
+
+        let result = write_with(&path, |writer| {
+            writer.write_all(b"partial")?;
+            Err(io::Error::new(io::ErrorKind::StorageFull, "disk full"))
+        });
+
+        assert!(result.is_err());
+        assert_eq!(fs::read_to_string(&path).unwrap(), "original content");
+        assert_eq!(leftover_temp_files(temp.path()), 0);
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn test_write_preserves_permissions() {
+        use std::os::unix::fs::PermissionsExt;
+
+        let temp = TempDir::new().unwrap();
+        let path = temp.path().join("config.toml");
+        fs::write(&path, "old").unwrap();
+        fs::set_permissions(&path, fs::Permissions::from_mode(0o600)).unwrap();
+
+        write(&path, "new").unwrap();
+
+        let mode = fs::metadata(&path).unwrap().permissions().mode();
+        assert_eq!(mode & 0o777, 0o600);
+    }
+}
@@ -22,7 +22,7 @@ use tracing::info;
 use tracing_subscriber::{layer::SubscriberExt, util::SubscriberInitExt};
 
 use lazyqmk::config::Config;
-use lazyqmk::web;
+use lazyqmk::web::{self, auth::AuthOptions};
 
 /// LazyQMK Web Server - REST API for the layout editor
 #[derive(Parser, Debug)]
@@ -76,8 +76,9 @@ BUILDING FROM SOURCE:
 
 SECURITY:
   By default, the server binds to 127.0.0.1 (localhost only) for security.
-  To expose the server to your local network:
-    lazyqmk-web --host 0.0.0.0
+  To expose the server to your local network, an access token is required:
+    LAZYQMK_AUTH_TOKEN=<secret> lazyqmk-web --host 0.0.0.0
+  Open the editor as http://<host>:<port>/?token=<secret> on the other device.
   
   For production deployment with HTTPS and authentication, see:
     docs/WEB_DEPLOYMENT.md
@@ -92,6 +93,17 @@ struct Args {
     #[arg(long, default_value = "127.0.0.1")]
     host: String,
 
+    /// Access token required for API requests.
+    /// Mandatory when binding to a non-loopback address; can also be set
+    /// through the LAZYQMK_AUTH_TOKEN environment variable
+    #[arg(long)]
+    auth_token: Option<String>,
+
+    /// Allow binding to a non-loopback address without an access token
+    /// (e.g., inside a container, behind port mapping)
+    #[arg(long)]
+    allow_unauthenticated: bool,
+
     /// Workspace directory containing layout files.
     /// Defaults to the platform-specific layouts directory:
     /// - Linux: ~/.config/LazyQMK/layouts/
@@ -103,11 +115,16 @@ struct Args {
     /// Enable verbose logging
     #[arg(short, long)]
     verbose: bool,
+
+    /// Hide layout names in request logs by logging route templates
+    /// (e.g. /api/layouts/{filename}) instead of request paths
+    #[arg(long)]
+    redact_logs: bool,
 }
 
 /// Gets the default layouts directory, creating it if it doesn't exist.
 fn get_default_layouts_dir() -> anyhow::Result<PathBuf> {
-    let layouts_dir = Config::config_dir()?.join("layouts");
+    let layouts_dir = lazyqmk::paths::layouts_dir()?;
 
     // Create directory if it doesn't exist
     if !layouts_dir.exists() {
@@ -138,7 +155,7 @@ async fn main() -> anyhow::Result<()> {
 
     // Determine workspace root:
     // 1. Use --workspace if provided
-    // 2. Otherwise, use Config::config_dir()/layouts (same as TUI)
+    // 2. Otherwise, use the layouts directory (same as TUI)
     let workspace_root = match args.workspace {
         Some(path) => path,
         None => get_default_layouts_dir()?,
@@ -149,6 +166,8 @@ async fn main() -> anyhow::Result<()> {
     // Build socket address
     let addr: SocketAddr = format!("{}:{}", args.host, args.port).parse()?;
 
+    let auth = AuthOptions::from_args(args.auth_token, args.allow_unauthenticated);
+
     // Start the server
-    web::run_server(config, workspace_root, addr).await
+    web::run_server(config, workspace_root, addr, auth, args.redact_logs).await
 }
@@ -0,0 +1,567 @@
+//! Batch apply command for scripted layout edits.
+//!
+//! Applies an ordered list of operations from a JSON file to a layout in one
+//! pass. All operations are validated before anything is changed, and the
+//! layout file is only rewritten (atomically) if every operation succeeds.
+//! See `docs/APPLY_FORMAT.md` for the operation schema.
+
+use crate::cli::category::validate_and_parse_hex;
+use crate::cli::common::{is_stdio, read_layout, write_layout, CliError, CliResult};
+use crate::keycode_db::KeycodeDb;
+use crate::models::{Category, KeyDefinition, Layer, Layout, Position, RgbColor};
+use clap::Args;
+use serde::{Deserialize, Serialize};
+use std::fs;
+use std::path::PathBuf;
+
+/// Apply a batch of operations to a layout
+#[derive(Debug, Clone, Args)]
+pub struct ApplyArgs {
+    /// Path to layout markdown file (`-` reads from stdin)
+    #[arg(short, long, value_name = "FILE")]
+    pub layout: PathBuf,
+
+    /// Path to JSON file containing an ordered list of operations
+    #[arg(long, value_name = "FILE")]
+    pub ops: PathBuf,
+
+    /// Where to write the updated layout (defaults to --layout; `-` writes to stdout)
+    #[arg(short, long, value_name = "FILE")]
+    pub output: Option<PathBuf>,
+
+    /// Output per-operation results as JSON
+    #[arg(long)]
+    pub json: bool,
+}
+
+/// A single layout operation.
+#[derive(Debug, Clone, PartialEq, Eq, Deserialize)]
+#[serde(tag = "op", rename_all = "snake_case", deny_unknown_fields)]
+pub enum Operation {
+    /// Set the keycode (and optionally label and color) of a key
+    SetKey {
+        /// Layer index
+        layer: usize,
+        /// Visual row
+        row: u8,
+        /// Visual column
+        col: u8,
+        /// QMK keycode
+        keycode: String,
+        /// Optional display label
+        #[serde(default)]
+        label: Option<String>,
+        /// Optional color override (#RRGGBB or #RGB)
+        #[serde(default)]
+        color: Option<String>,
+    },
+    /// Reset a key to `KC_TRNS`
+    ClearKey {
+        /// Layer index
+        layer: usize,
+        /// Visual row
+        row: u8,
+        /// Visual column
+        col: u8,
+    },
+    /// Set the default color of a layer
+    SetLayerColor {
+        /// Layer index
+        layer: usize,
+        /// Color (#RRGGBB or #RGB)
+        color: String,
+    },
+    /// Append a new layer filled with `KC_TRNS`
+    AddLayer {
+        /// Layer name
+        name: String,
+        /// Default color (#RRGGBB or #RGB), white if omitted
+        #[serde(default)]
+        color: Option<String>,
+    },
+    /// Update layout metadata fields (omitted fields are left unchanged)
+    SetMetadata {
+        /// Layout name
+        #[serde(default)]
+        name: Option<String>,
+        /// Layout description
+        #[serde(default)]
+        description: Option<String>,
+        /// Author
+        #[serde(default)]
+        author: Option<String>,
+        /// Tags (replaces existing tags)
+        #[serde(default)]
+        tags: Option<Vec<String>>,
+    },
+    /// Add a new category
+    AddCategory {
+        /// Category ID (kebab-case)
+        id: String,
+        /// Category name
+        name: String,
+        /// Color (#RRGGBB or #RGB)
+        color: String,
+    },
+    /// Assign a category to a key (with row/col) or a whole layer (without)
+    AssignCategory {
+        /// Layer index
+        layer: usize,
+        /// Visual row (requires `col`)
+        #[serde(default)]
+        row: Option<u8>,
+        /// Visual column (requires `row`)
+        #[serde(default)]
+        col: Option<u8>,
+        /// Category ID, or null to clear the assignment
+        category: Option<String>,
+    },
+}
+
+impl Operation {
+    /// Checks the operation in isolation (keycodes, colors, field formats).
+    ///
+    /// Checks that depend on the layout (layer indices, positions, category
+    /// references) happen when the operation is applied.
+    fn validate(&self, keycode_db: &KeycodeDb) -> Result<(), String> {
+        match self {
+            Self::SetKey { keycode, color, .. } => {
+                if !keycode_db.is_valid(keycode) {
+                    return Err(format!("Invalid keycode '{keycode}'"));
+                }
+                if let Some(color) = color {
+                    validate_and_parse_hex(color)?;
+                }
+            }
+            Self::SetLayerColor { color, .. } => {
+                validate_and_parse_hex(color)?;
+            }
+            Self::AddLayer { name, color } => {
+                if name.trim().is_empty() {
+                    return Err("Layer name cannot be empty".to_string());
+                }
+                if let Some(color) = color {
+                    validate_and_parse_hex(color)?;
+                }
+            }
+            Self::SetMetadata { name, .. } => {
+                if name.as_ref().is_some_and(|n| n.trim().is_empty()) {
+                    return Err("Layout name cannot be empty".to_string());
+                }
+            }
+            Self::AddCategory { id, name, color } => {
+                let color = validate_and_parse_hex(color)?;
+                Category::new(id, name, color).map_err(|e| format!("Invalid category: {e}"))?;
+            }
+            Self::AssignCategory { row, col, .. } => {
+                if row.is_some() != col.is_some() {
+                    return Err("'row' and 'col' must be given together".to_string());
+                }
+            }
+            Self::ClearKey { .. } => {}
+        }
+        Ok(())
+    }
+
+    /// Applies the operation to `layout`, returning a short description.
+    fn apply(&self, layout: &mut Layout) -> Result<String, String> {
+        match self {
+            Self::SetKey {
+                layer,
+                row,
+                col,
+                keycode,
+                label,
+                color,
+            } => {
+                let color = color.as_deref().map(validate_and_parse_hex).transpose()?;
+                let key = key_mut(layout, *layer, *row, *col)?;
+                key.keycode.clone_from(keycode);
+                if label.is_some() {
+                    key.label.clone_from(label);
+                }
+                if color.is_some() {
+                    key.color_override = color;
+                }
+                Ok(format!("Set layer {layer} ({row}, {col}) to {keycode}"))
+            }
+            Self::ClearKey { layer, row, col } => {
+                key_mut(layout, *layer, *row, *col)?.keycode = "KC_TRNS".to_string();
+                Ok(format!("Cleared layer {layer} ({row}, {col})"))
+            }
+            Self::SetLayerColor { layer, color } => {
+                let color = validate_and_parse_hex(color)?;
+                layer_mut(layout, *layer)?.default_color = color;
+                Ok(format!("Set layer {layer} color to {}", color.to_hex()))
+            }
+            Self::AddLayer { name, color } => {
+                let color = color
+                    .as_deref()
+                    .map(validate_and_parse_hex)
+                    .transpose()?
+                    .unwrap_or_else(|| RgbColor::new(255, 255, 255));
+                let number =
+                    u8::try_from(layout.layers.len()).map_err(|_| "Too many layers".to_string())?;
+                let mut new_layer = Layer::new(number, name, color).map_err(|e| e.to_string())?;
+                if let Some(base) = layout.layers.first() {
+                    for key in &base.keys {
+                        new_layer
+                            .add_key(KeyDefinition::new(key.position, "KC_TRNS"))
+                            .map_err(|e| e.to_string())?;
+                    }
+                }
+                layout.add_layer(new_layer).map_err(|e| e.to_string())?;
+                Ok(format!("Added layer {number} '{name}'"))
+            }
+            Self::SetMetadata {
+                name,
+                description,
+                author,
+                tags,
+            } => {
+                let metadata = &mut layout.metadata;
+                let mut changed = Vec::new();
+                if let Some(name) = name {
+                    metadata.name.clone_from(name);
+                    changed.push("name");
+                }
+                if let Some(description) = description {
+                    metadata.description.clone_from(description);
+                    changed.push("description");
+                }
+                if let Some(author) = author {
+                    metadata.author.clone_from(author);
+                    changed.push("author");
+                }
+                if let Some(tags) = tags {
+                    metadata.tags.clone_from(tags);
+                    changed.push("tags");
+                }
+                Ok(format!("Updated metadata: {}", changed.join(", ")))
+            }
+            Self::AddCategory { id, name, color } => {
+                let color = validate_and_parse_hex(color)?;
+                let category = Category::new(id, name, color).map_err(|e| e.to_string())?;
+                layout.add_category(category).map_err(|e| e.to_string())?;
+                Ok(format!("Added category '{id}'"))
+            }
+            Self::AssignCategory {
+                layer,
+                row,
+                col,
+                category,
+            } => {
+                if let Some(id) = category {
+                    if !layout.categories.iter().any(|c| &c.id == id) {
+                        return Err(format!("Category '{id}' not found"));
+                    }
+                }
+                let target = category.as_deref().unwrap_or("none");
+                if let (Some(row), Some(col)) = (row, col) {
+                    key_mut(layout, *layer, *row, *col)?
+                        .category_id
+                        .clone_from(category);
+                    Ok(format!(
+                        "Assigned category {target} to layer {layer} ({row}, {col})"
+                    ))
+                } else {
+                    layer_mut(layout, *layer)?.category_id.clone_from(category);
+                    Ok(format!("Assigned category {target} to layer {layer}"))
+                }
+            }
+        }
+    }
+}
+
+/// Looks up a layer by index.
+fn layer_mut(layout: &mut Layout, index: usize) -> Result<&mut Layer, String> {
+    let count = layout.layers.len();
+    layout
+        .get_layer_mut(index)
+        .ok_or_else(|| format!("Layer {index} does not exist (layout has {count} layers)"))
+}
+
+/// Looks up a key by layer index and visual position.
+fn key_mut(
+    layout: &mut Layout,
+    layer: usize,
+    row: u8,
+    col: u8,
+) -> Result<&mut KeyDefinition, String> {
+    layer_mut(layout, layer)?
+        .get_key_mut(Position::new(row, col))
+        .ok_or_else(|| format!("No key at ({row}, {col}) on layer {layer}"))
+}
+
+/// Outcome of a single operation.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "snake_case")]
+enum OpStatus {
+    /// Operation applied
+    Ok,
+    /// Operation failed validation or could not be applied
+    Error,
+    /// Operation not applied because an earlier operation failed
+    Skipped,
+}
+
+// JSON response types
+#[derive(Debug, Serialize)]
+struct OpResult {
+    index: usize,
+    op: String,
+    status: OpStatus,
+    message: String,
+}
+
+#[derive(Debug, Serialize)]
+struct ApplyResponse {
+    success: bool,
+    applied: usize,
+    total: usize,
+    results: Vec<OpResult>,
+}
+
+impl ApplyArgs {
+    /// Execute the apply command
+    pub fn execute(&self) -> CliResult<()> {
+        let output = self.output_path();
+        // Keep stdout clean for the layout when piping
+        let to_stdout = is_stdio(&output);
+        let report = |line: &str| {
+            if to_stdout {
+                eprintln!("{line}");
+            } else {
+                println!("{line}");
+            }
+        };
+
+        let layout = read_layout(&self.layout)?;
+
+        let ops_content = fs::read_to_string(&self.ops).map_err(|e| {
+            CliError::io(format!(
+                "Failed to read operations file {}: {e}",
+                self.ops.display()
+            ))
+        })?;
+
+        let keycode_db = KeycodeDb::load()
+            .map_err(|e| CliError::io(format!("Failed to load keycode database: {e}")))?;
+
+        let (layout, response) = apply_operations(layout, &ops_content, &keycode_db)?;
+
+        if response.success {
+            // Each operation is checked alone; the result must still hold together
+            layout.validate_invariants().map_err(|e| {
+                CliError::validation(format!("No changes written: invalid result: {e}"))
+            })?;
+            write_layout(&layout, &output)?;
+        }
+
+        if self.json {
+            report(
+                &serde_json::to_string_pretty(&response)
+                    .map_err(|e| CliError::io(format!("Failed to serialize JSON: {e}")))?,
+            );
+        } else {
+            for result in &response.results {
+                let prefix = match result.status {
+                    OpStatus::Ok => "✓",
+                    OpStatus::Error => "✗",
+                    OpStatus::Skipped => "-",
+                };
+                report(&format!(
+                    "  {prefix} [{}] {}: {}",
+                    result.index, result.op, result.message
+                ));
+            }
+            if response.success {
+                let target = if to_stdout {
+                    "stdout".to_string()
+                } else {
+                    output.display().to_string()
+                };
+                report(&format!(
+                    "\nApplied {} operation(s) to {target}",
+                    response.applied
+                ));
+            }
+        }
+
+        if !response.success {
+            return Err(CliError::validation(
+                "No changes written: one or more operations failed",
+            ));
+        }
+
+        Ok(())
+    }
+
+    /// Resolves where the updated layout is written
+    fn output_path(&self) -> PathBuf {
+        self.output.clone().unwrap_or_else(|| self.layout.clone())
+    }
+}
+
+/// Parses, validates, and applies all operations to `layout`.
+///
+/// Validation of every operation happens before any is applied. If any
+/// operation fails, the returned response has `success: false` and the
+/// returned layout must be discarded.
+fn apply_operations(
+    mut layout: Layout,
+    ops_content: &str,
+    keycode_db: &KeycodeDb,
+) -> CliResult<(Layout, ApplyResponse)> {
+    let raw_ops: Vec<serde_json::Value> = serde_json::from_str(ops_content).map_err(|e| {
+        CliError::validation(format!(
+            "Operations file must be a JSON array of operations: {e}"
+        ))
+    })?;
+
+    // Phase 1: parse and validate every operation up front
+    let parsed: Vec<(String, Result<Operation, String>)> = raw_ops
+        .into_iter()
+        .map(|value| {
+            let op_name = value
+                .get("op")
+                .and_then(serde_json::Value::as_str)
+                .unwrap_or("unknown")
+                .to_string();
+            let op = serde_json::from_value::<Operation>(value)
+                .map_err(|e| e.to_string())
+                .and_then(|op| op.validate(keycode_db).map(|()| op));
+            (op_name, op)
+        })
+        .collect();
+
+    let total = parsed.len();
+    let mut results = Vec::with_capacity(total);
+    let mut failed = parsed.iter().any(|(_, op)| op.is_err());
+
+    // Phase 2: apply in order, stopping at the first failure
+    for (index, (op_name, op)) in parsed.into_iter().enumerate() {
+        let (status, message) = match op {
+            Err(e) => (OpStatus::Error, e),
+            Ok(_) if failed => (
+                OpStatus::Skipped,
+                "Not applied because another operation failed".to_string(),
+            ),
+            Ok(op) => match op.apply(&mut layout) {
+                Ok(message) => (OpStatus::Ok, message),
+                Err(e) => {
+                    failed = true;
+                    (OpStatus::Error, e)
+                }
+            },
+        };
+        results.push(OpResult {
+            index,
+            op: op_name,
+            status,
+            message,
+        });
+    }
+
+    let applied = results.iter().filter(|r| r.status == OpStatus::Ok).count();
+    let success = !failed;
+    Ok((
+        layout,
+        ApplyResponse {
+            success,
+            applied: if success { applied } else { 0 },
+            total,
+            results,
+        },
+    ))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn test_layout() -> Layout {
+        let mut layout = Layout::new("Apply Test").unwrap();
+        let mut layer = Layer::new(0, "Base", RgbColor::new(255, 255, 255)).unwrap();
+        layer
+            .add_key(KeyDefinition::new(Position::new(0, 0), "KC_A"))
+            .unwrap();
+        layer
+            .add_key(KeyDefinition::new(Position::new(0, 1), "KC_B"))
+            .unwrap();
+        layout.add_layer(layer).unwrap();
+        layout
+    }
+
+    #[test]
+    fn test_apply_all_operations() {
+        let db = KeycodeDb::load().unwrap();
+        let ops = r##"[
+            {"op": "set_key", "layer": 0, "row": 0, "col": 0, "keycode": "KC_Q", "color": "#F00"},
+            {"op": "clear_key", "layer": 0, "row": 0, "col": 1},
+            {"op": "set_layer_color", "layer": 0, "color": "#00FF00"},
+            {"op": "add_layer", "name": "Nav"},
+            {"op": "set_metadata", "name": "Renamed", "tags": ["a"]},
+            {"op": "add_category", "id": "nav", "name": "Navigation", "color": "#0000FF"},
+            {"op": "assign_category", "layer": 1, "category": "nav"},
+            {"op": "assign_category", "layer": 0, "row": 0, "col": 0, "category": "nav"}
+        ]"##;
+
+        let (layout, response) = apply_operations(test_layout(), ops, &db).unwrap();
+        assert!(response.success);
+        assert_eq!(response.applied, 8);
+
+        let base = &layout.layers[0];
+        assert_eq!(base.keys[0].keycode, "KC_Q");
+        assert_eq!(base.keys[0].color_override, Some(RgbColor::new(255, 0, 0)));
+        assert_eq!(base.keys[0].category_id.as_deref(), Some("nav"));
+        assert_eq!(base.keys[1].keycode, "KC_TRNS");
+        assert_eq!(base.default_color, RgbColor::new(0, 255, 0));
+
+        assert_eq!(layout.layers.len(), 2);
+        assert_eq!(layout.layers[1].keys.len(), 2);
+        assert_eq!(layout.layers[1].category_id.as_deref(), Some("nav"));
+        assert_eq!(layout.metadata.name, "Renamed");
+        assert_eq!(layout.metadata.tags, vec!["a"]);
+    }
+
+    #[test]
+    fn test_validation_failure_applies_nothing() {
+        let db = KeycodeDb::load().unwrap();
+        let ops = r#"[
+            {"op": "set_key", "layer": 0, "row": 0, "col": 0, "keycode": "KC_Q"},
+            {"op": "set_key", "layer": 0, "row": 0, "col": 1, "keycode": "NOT_A_KEY"},
+            {"op": "bogus"}
+        ]"#;
+
+        let (_, response) = apply_operations(test_layout(), ops, &db).unwrap();
+        assert!(!response.success);
+        assert_eq!(response.applied, 0);
+        assert_eq!(response.results[0].status, OpStatus::Skipped);
+        assert_eq!(response.results[1].status, OpStatus::Error);
+        assert_eq!(response.results[2].status, OpStatus::Error);
+    }
+
+    #[test]
+    fn test_apply_failure_skips_remaining() {
+        let db = KeycodeDb::load().unwrap();
+        let ops = r#"[
+            {"op": "clear_key", "layer": 0, "row": 0, "col": 0},
+            {"op": "clear_key", "layer": 5, "row": 0, "col": 0},
+            {"op": "clear_key", "layer": 0, "row": 0, "col": 1}
+        ]"#;
+
+        let (_, response) = apply_operations(test_layout(), ops, &db).unwrap();
+        assert!(!response.success);
+        assert_eq!(response.results[0].status, OpStatus::Ok);
+        assert_eq!(response.results[1].status, OpStatus::Error);
+        assert!(response.results[1].message.contains("Layer 5"));
+        assert_eq!(response.results[2].status, OpStatus::Skipped);
+    }
+
+    #[test]
+    fn test_ops_must_be_array() {
+        let db = KeycodeDb::load().unwrap();
+        assert!(apply_operations(test_layout(), r#"{"op": "clear_key"}"#, &db).is_err());
+    }
+}
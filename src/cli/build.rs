@@ -0,0 +1,234 @@
+//! Build command: generate firmware files and compile them with `qmk compile`
+//! or `make` (`build.backend` in the config).
+
+use crate::cli::common::{
+    check_keyboard_mismatch, load_config, read_layout, record_history, CliError, CliResult,
+};
+use crate::config::{BuildBackend, Config};
+use crate::firmware::{environment, preflight};
+use crate::firmware::{
+    Backend, BuildState, BuildStatus, BuildTarget, FirmwareGenerator, FirmwareValidator,
+};
+use crate::keycode_db::KeycodeDb;
+use crate::models::Layout;
+use crate::services::geometry;
+use crate::services::history::{HistoryEntry, HistoryOperation};
+use clap::Args;
+use std::path::{Path, PathBuf};
+use std::time::Duration;
+
+/// Generate firmware files into the QMK tree and compile them
+#[derive(Debug, Clone, Args)]
+pub struct BuildArgs {
+    /// Path to layout markdown file (`-` reads from stdin)
+    #[arg(short, long, value_name = "FILE")]
+    pub layout: PathBuf,
+
+    /// Path to QMK firmware repository (defaults to the configured path)
+    #[arg(long, value_name = "PATH")]
+    pub qmk_path: Option<PathBuf>,
+
+    /// Keyboard to build, overriding the layout metadata and config
+    #[arg(long, value_name = "KEYBOARD")]
+    pub keyboard: Option<String>,
+
+    /// Keymap name to build, overriding the layout metadata and config
+    #[arg(long, value_name = "NAME")]
+    pub keymap: Option<String>,
+
+    /// Controller converter passed as `CONVERT_TO` (e.g., "rp2040_ce"),
+    /// overriding the layout metadata and config
+    #[arg(long, value_name = "CONVERTER")]
+    pub converter: Option<String>,
+
+    /// Build backend: auto, make or qmk (defaults to `build.backend` in the
+    /// config)
+    #[arg(long, value_name = "BACKEND")]
+    pub backend: Option<BuildBackend>,
+
+    /// Print the resolved keyboard/keymap and the compile command without building
+    #[arg(long)]
+    pub dry_run: bool,
+
+    /// Don't check for the toolchain, qmk CLI and QMK submodules before
+    /// building
+    #[arg(long)]
+    pub skip_env_check: bool,
+
+    /// Build even if the layout's keyboard differs from `--keyboard` or
+    /// `build.keyboard` in the config
+    #[arg(long)]
+    pub allow_keyboard_mismatch: bool,
+}
+
+impl BuildArgs {
+    /// Execute the build command.
+    ///
+    /// The keyboard and keymap come from `--keyboard/--keymap`, then the
+    /// layout metadata, then `build.keyboard`/`build.keymap` in the config
+    /// (keymap finally defaults to `default`).
+    pub fn execute(&self) -> CliResult<()> {
+        let layout = read_layout(&self.layout)?;
+        let mut config = load_config(&self.layout)?;
+        if let Some(qmk_path) = &self.qmk_path {
+            config.paths.qmk_firmware = Some(qmk_path.clone());
+        }
+        if let Some(backend) = self.backend {
+            config.build.backend = backend;
+        }
+
+        let target = BuildTarget::resolve(
+            self.keyboard.as_deref(),
+            self.keymap.as_deref(),
+            self.converter.as_deref(),
+            &layout.metadata,
+            &config.build,
+        )
+        .map_err(|e| CliError::validation(e.to_string()))?;
+        check_keyboard_mismatch(
+            &layout,
+            self.keyboard.as_deref(),
+            &config,
+            &preflight::keymap_target_path(&config, &target),
+            self.allow_keyboard_mismatch,
+        )?;
+
+        println!("Keyboard: {} ({})", target.keyboard, target.keyboard_source);
+        println!("Keymap: {} ({})", target.keymap, target.keymap_source);
+        if let (Some(converter), Some(source)) = (&target.converter, target.converter_source) {
+            println!("Converter: {converter} ({source})");
+        }
+        if let Some(warning) = target.converter_warning() {
+            eprintln!("Warning: {warning}");
+        }
+        // `auto` falls back to make without a QMK tree to check
+        let backend = Backend::resolve(
+            config.build.backend,
+            config
+                .paths
+                .qmk_firmware
+                .as_deref()
+                .unwrap_or(Path::new("")),
+        );
+        println!("Backend: {backend} ({})", config.build.backend.name());
+        println!("Command: {}", target.compile_command(backend));
+        if self.dry_run {
+            return Ok(());
+        }
+
+        let mut entry = HistoryEntry::new(HistoryOperation::Build)
+            .with_qmk_tree(config.paths.qmk_firmware.as_deref());
+        let result = Self::build(layout, &config, &target, self.skip_env_check, &mut entry);
+        record_history(&self.layout, entry, &result);
+        result
+    }
+
+    /// Validates, generates and compiles `layout`, recording validation
+    /// warnings and the firmware size into `entry`.
+    ///
+    /// Unless `skip_env_check` is set, fails before doing anything if the
+    /// build environment is incomplete.
+    fn build(
+        mut layout: Layout,
+        config: &Config,
+        target: &BuildTarget,
+        skip_env_check: bool,
+        entry: &mut HistoryEntry,
+    ) -> CliResult<()> {
+        let qmk_path = config.paths.qmk_firmware.clone().ok_or_else(|| {
+            CliError::validation("QMK firmware path not configured. Use --qmk-path")
+        })?;
+        if !skip_env_check {
+            environment::ensure_build_environment(
+                &qmk_path,
+                Backend::resolve(config.build.backend, &qmk_path),
+                &target.keyboard,
+                target.converter.as_deref(),
+            )
+            .map_err(|e| {
+                CliError::validation(format!(
+                    "{e}\n(run `lazyqmk doctor` for a full check, or pass --skip-env-check)"
+                ))
+            })?;
+        }
+        target.apply_to(&mut layout.metadata);
+
+        let layout_variant = layout
+            .metadata
+            .layout_variant
+            .clone()
+            .ok_or_else(|| CliError::validation("Layout variant not set in layout metadata"))?;
+        let geo_context = geometry::GeometryContext {
+            config,
+            metadata: &layout.metadata,
+        };
+        let geo_result = geometry::build_geometry_for_layout(geo_context, &layout_variant)
+            .map_err(|e| CliError::io(format!("Failed to build geometry: {e}")))?;
+        let keycode_db = KeycodeDb::load()
+            .map_err(|e| CliError::io(format!("Failed to load keycode database: {e}")))?;
+
+        let report = FirmwareValidator::new(
+            &layout,
+            &geo_result.geometry,
+            &geo_result.mapping,
+            &keycode_db,
+        )
+        .validate()
+        .map_err(|e| CliError::io(format!("Validation failed: {e}")))?;
+        entry.warnings = report.warnings.len();
+        if !report.is_valid() {
+            return Err(CliError::validation(format!(
+                "Layout validation failed:\n{}",
+                report.format_message()
+            )));
+        }
+
+        let generator = FirmwareGenerator::new(
+            &layout,
+            &geo_result.geometry,
+            &geo_result.mapping,
+            config,
+            &keycode_db,
+        );
+        generator
+            .generate()
+            .map_err(|e| CliError::io(format!("Generation failed: {e}")))?;
+
+        let mut build = BuildState::new();
+        build.backend = config.build.backend;
+        build
+            .start_build(
+                qmk_path,
+                target.keyboard.clone(),
+                target.keymap.clone(),
+                target.converter.clone(),
+                None,
+            )
+            .map_err(|e| CliError::io(format!("Failed to start build: {e}")))?;
+
+        let mut printed = 0;
+        while build.receiver.is_some() {
+            if !build.poll() {
+                std::thread::sleep(Duration::from_millis(50));
+            }
+            for (_, line) in &build.log_lines[printed..] {
+                println!("{line}");
+            }
+            printed = build.log_lines.len();
+        }
+
+        if build.status == BuildStatus::Success {
+            entry.firmware_size = build.size;
+            match &target.converter {
+                Some(converter) => println!("✓ Built {target} (CONVERT_TO={converter})"),
+                None => println!("✓ Built {target}"),
+            }
+            Ok(())
+        } else {
+            Err(CliError::io(format!(
+                "Build failed: {}",
+                build.last_message
+            )))
+        }
+    }
+}
@@ -1,9 +1,10 @@
 //! Category management commands for layouts.
 //!
-//! Provides commands to list, add, and delete categories in a layout file.
+//! Provides commands to list, add, delete, and recolor categories in a
+//! layout file.
 
 use crate::cli::common::{CliError, CliResult};
-use crate::models::{Category, RgbColor};
+use crate::models::{Category, ColorPalette, PaletteKind, RgbColor};
 use crate::services::LayoutService;
 use clap::{Args, Subcommand};
 use regex::Regex;
@@ -27,6 +28,8 @@ pub enum CategoryCommand {
     Add(AddCategoryArgs),
     /// Remove a category from a layout
     Delete(DeleteCategoryArgs),
+    /// Recolor all categories from a built-in palette
+    Colorize(ColorizeCategoriesArgs),
 }
 
 /// List all categories in a layout
@@ -77,6 +80,18 @@ pub struct DeleteCategoryArgs {
     pub force: bool,
 }
 
+/// Recolor all categories from a built-in palette
+#[derive(Debug, Clone, Args)]
+pub struct ColorizeCategoriesArgs {
+    /// Path to layout markdown file
+    #[arg(short, long, value_name = "FILE")]
+    pub layout: PathBuf,
+
+    /// Palette: tailwind, okabe-ito, or viridis (the last two are colorblind-safe)
+    #[arg(long, value_name = "NAME", default_value = "okabe-ito")]
+    pub palette: PaletteKind,
+}
+
 // JSON response types
 #[derive(Debug, Serialize)]
 struct CategoryItem {
@@ -98,16 +113,22 @@ impl CategoryArgs {
             CategoryCommand::List(args) => args.execute(),
             CategoryCommand::Add(args) => args.execute(),
             CategoryCommand::Delete(args) => args.execute(),
+            CategoryCommand::Colorize(args) => args.execute(),
         }
     }
+
+    /// Whether the subcommand was asked for JSON output
+    #[must_use]
+    pub const fn json_output(&self) -> bool {
+        matches!(&self.command, CategoryCommand::List(args) if args.json)
+    }
 }
 
 impl ListCategoriesArgs {
     /// Execute the list command
     pub fn execute(&self) -> CliResult<()> {
         // Load layout
-        let layout = LayoutService::load(&self.layout)
-            .map_err(|e| CliError::io(format!("Failed to load layout: {e}")))?;
+        let layout = LayoutService::load(&self.layout)?;
 
         // Convert categories to response format
         let categories = layout
@@ -149,8 +170,7 @@ impl AddCategoryArgs {
     /// Execute the add command
     pub fn execute(&self) -> CliResult<()> {
         // Load layout
-        let mut layout = LayoutService::load(&self.layout)
-            .map_err(|e| CliError::io(format!("Failed to load layout: {e}")))?;
+        let mut layout = LayoutService::load(&self.layout)?;
 
         // Validate hex color format
         let color = validate_and_parse_hex(&self.color).map_err(CliError::validation)?;
@@ -185,8 +205,7 @@ impl DeleteCategoryArgs {
     /// Execute the delete command
     pub fn execute(&self) -> CliResult<()> {
         // Load layout
-        let mut layout = LayoutService::load(&self.layout)
-            .map_err(|e| CliError::io(format!("Failed to load layout: {e}")))?;
+        let mut layout = LayoutService::load(&self.layout)?;
 
         // Check if category exists
         if !layout.categories.iter().any(|c| c.id == self.id) {
@@ -221,8 +240,54 @@ impl DeleteCategoryArgs {
     }
 }
 
+impl ColorizeCategoriesArgs {
+    /// Execute the colorize command
+    ///
+    /// Categories are assigned the palette's primary colors in order, wrapping
+    /// around if there are more categories than colors.
+    pub fn execute(&self) -> CliResult<()> {
+        let mut layout = LayoutService::load(&self.layout)?;
+
+        if layout.categories.is_empty() {
+            println!("No categories defined.");
+            return Ok(());
+        }
+
+        let colors = ColorPalette::for_kind(self.palette).primary_colors();
+        if colors.is_empty() {
+            return Err(CliError::io(format!(
+                "Palette '{}' has no colors",
+                self.palette
+            )));
+        }
+        if layout.categories.len() > colors.len() {
+            eprintln!(
+                "Warning: {} categories but palette '{}' has {} colors; colors will repeat",
+                layout.categories.len(),
+                self.palette,
+                colors.len()
+            );
+        }
+
+        for (category, color) in layout.categories.iter_mut().zip(colors.iter().cycle()) {
+            category.set_color(*color);
+            println!("  {:<20} {}", category.id, color.to_hex());
+        }
+
+        LayoutService::save(&layout, &self.layout)
+            .map_err(|e| CliError::io(format!("Failed to save layout: {e}")))?;
+
+        println!(
+            "Recolored {} categories with the {} palette.",
+            layout.categories.len(),
+            self.palette.label()
+        );
+        Ok(())
+    }
+}
+
 /// Validates hex color format (#RRGGBB or #RGB) and returns RgbColor
-fn validate_and_parse_hex(color: &str) -> Result<RgbColor, String> {
+pub fn validate_and_parse_hex(color: &str) -> Result<RgbColor, String> {
     // Match #RRGGBB or #RGB format
     let hex_regex = Regex::new(r"^#([0-9A-Fa-f]{6}|[0-9A-Fa-f]{3})$")
         .map_err(|_| "Failed to create hex regex".to_string())?;
@@ -334,7 +399,7 @@ mod tests {
 
         let mut key = KeyDefinition::new(Position::new(0, 0), "KC_A");
         key = key.with_category("navigation");
-        layer.add_key(key);
+        layer.add_key(key).unwrap();
 
         layout.add_layer(layer).unwrap();
 
@@ -1,7 +1,16 @@
 //! Common types and utilities for CLI commands.
 
+use crate::config::Config;
+use crate::firmware::preflight;
+use crate::firmware::validator::{ValidationError, ValidationWarning};
+use crate::models::Layout;
+use crate::parser::{layout::parse_markdown_layout_str, template_gen::generate_markdown};
+use crate::services::history::{HistoryEntry, HistoryService, DEFAULT_HISTORY_RETENTION};
+use crate::services::{LayoutLoadError, LayoutService};
 use serde::Serialize;
 use std::fmt;
+use std::io::{IsTerminal, Read, Write};
+use std::path::Path;
 
 /// CLI result type with proper exit codes.
 pub type CliResult<T> = Result<T, CliError>;
@@ -15,17 +24,45 @@ pub enum ExitCode {
     ValidationError = 1,
     /// I/O or system error (2)
     IoError = 2,
+    /// Success, but with validation warnings (3)
+    SuccessWithWarnings = 3,
 }
 
 impl ExitCode {
     /// Convert to i32 for process::exit
     #[must_use]
-    #[allow(dead_code)] // Used for custom exit code handling if needed in future
     pub const fn as_i32(self) -> i32 {
         self as i32
     }
 }
 
+/// What went wrong, as a stable name scripts can match on.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum ErrorKind {
+    /// An input file does not exist
+    NotFound,
+    /// An input file exists but is not a valid layout
+    Parse,
+    /// The input is invalid or the request can't be satisfied
+    Validation,
+    /// Reading, writing or running something failed
+    Io,
+}
+
+impl ErrorKind {
+    /// Name used in JSON (`error.kind`) and the text prefix.
+    #[must_use]
+    pub const fn as_str(self) -> &'static str {
+        match self {
+            Self::NotFound => "not_found",
+            Self::Parse => "parse",
+            Self::Validation => "validation",
+            Self::Io => "io",
+        }
+    }
+}
+
 /// CLI error with exit code.
 #[derive(Debug)]
 pub struct CliError {
@@ -33,6 +70,8 @@ pub struct CliError {
     pub message: String,
     /// Exit code
     pub exit_code: ExitCode,
+    /// Kind of failure
+    pub kind: ErrorKind,
 }
 
 impl CliError {
@@ -42,6 +81,7 @@ impl CliError {
         Self {
             message: message.into(),
             exit_code: ExitCode::ValidationError,
+            kind: ErrorKind::Validation,
         }
     }
 
@@ -51,8 +91,44 @@ impl CliError {
         Self {
             message: message.into(),
             exit_code: ExitCode::IoError,
+            kind: ErrorKind::Io,
         }
     }
+
+    /// Creates an error for a missing input file (exit code 2).
+    #[must_use]
+    pub fn not_found(message: impl Into<String>) -> Self {
+        Self {
+            kind: ErrorKind::NotFound,
+            ..Self::io(message)
+        }
+    }
+
+    /// Creates an error for a malformed input file (exit code 2).
+    #[must_use]
+    pub fn parse(message: impl Into<String>) -> Self {
+        Self {
+            kind: ErrorKind::Parse,
+            ..Self::io(message)
+        }
+    }
+
+    /// Prints the error to stderr and returns its exit code.
+    ///
+    /// With `json`, prints `{"error": {"kind": ..., "message": ...}}` so
+    /// scripts can tell failures apart without matching on the message.
+    #[must_use]
+    pub fn report(&self, json: bool) -> ExitCode {
+        if json {
+            let error = serde_json::json!({
+                "error": { "kind": self.kind, "message": self.message }
+            });
+            eprintln!("{error}");
+        } else {
+            eprintln!("Error: [{}] {}", self.kind.as_str(), self.message);
+        }
+        self.exit_code
+    }
 }
 
 impl fmt::Display for CliError {
@@ -61,6 +137,17 @@ impl fmt::Display for CliError {
     }
 }
 
+impl From<LayoutLoadError> for CliError {
+    fn from(err: LayoutLoadError) -> Self {
+        let message = err.to_string();
+        match err {
+            LayoutLoadError::NotFound { .. } => Self::not_found(message),
+            LayoutLoadError::Parse { .. } => Self::parse(message),
+            LayoutLoadError::Io { .. } => Self::io(message),
+        }
+    }
+}
+
 impl std::error::Error for CliError {}
 
 impl From<anyhow::Error> for CliError {
@@ -76,6 +163,139 @@ impl From<std::io::Error> for CliError {
     }
 }
 
+/// Path argument meaning "read from stdin" or "write to stdout".
+pub const STDIO_PATH: &str = "-";
+
+/// Returns true if `path` is the `-` placeholder for stdin/stdout.
+#[must_use]
+pub fn is_stdio(path: &Path) -> bool {
+    path.as_os_str() == STDIO_PATH
+}
+
+/// Loads the configuration for working on the layout at `path`: the user
+/// config with the layout's project file and the environment merged in.
+///
+/// A layout read from stdin picks up the project file of the current
+/// directory.
+pub fn load_config(path: &Path) -> CliResult<Config> {
+    let start = if is_stdio(path) { Path::new(".") } else { path };
+    Config::for_layout(start)
+        .map_err(|e| CliError::validation(format!("Failed to load configuration: {e}")))
+}
+
+/// Loads a layout from a file, or from stdin when `path` is `-`.
+///
+/// Reading from stdin is rejected when stdin is a terminal, since the
+/// command would otherwise block waiting for input.
+pub fn read_layout(path: &Path) -> CliResult<Layout> {
+    if !is_stdio(path) {
+        return Ok(LayoutService::load(path)?);
+    }
+
+    let mut stdin = std::io::stdin();
+    if stdin.is_terminal() {
+        return Err(CliError::validation(
+            "Refusing to read layout from an interactive terminal. Pipe a layout into stdin or pass a file path",
+        ));
+    }
+
+    let mut content = String::new();
+    stdin
+        .read_to_string(&mut content)
+        .map_err(|e| CliError::io(format!("Failed to read layout from stdin: {e}")))?;
+
+    parse_markdown_layout_str(&content)
+        .map_err(|e| CliError::parse(format!("Failed to load layout from stdin: {e:#}")))
+}
+
+/// Appends a generate or build run to the history of the layout at `path`.
+///
+/// Layouts read from stdin have no history. Failing to write the history
+/// only prints a warning, so it never fails the command itself.
+pub fn record_history<T>(path: &Path, mut entry: HistoryEntry, result: &CliResult<T>) {
+    if is_stdio(path) {
+        return;
+    }
+    if let Err(e) = result {
+        entry.fail(&e.message);
+    }
+    if let Err(e) = HistoryService::record(path, &entry, DEFAULT_HISTORY_RETENTION) {
+        eprintln!("Warning: Failed to record history: {e}");
+    }
+}
+
+/// Fails when the layout's keyboard differs from the configured build
+/// keyboard, unless `allow` (`--allow-keyboard-mismatch`) is set, in which
+/// case only a warning is printed.
+pub fn check_keyboard_mismatch(
+    layout: &Layout,
+    keyboard_override: Option<&str>,
+    config: &Config,
+    target_path: &Path,
+    allow: bool,
+) -> CliResult<()> {
+    let Some(mismatch) = preflight::check_keyboard_target(
+        &layout.metadata,
+        keyboard_override,
+        &config.build,
+        target_path,
+    ) else {
+        return Ok(());
+    };
+    if allow {
+        eprintln!("Warning: {mismatch}");
+        return Ok(());
+    }
+    Err(CliError::validation(format!(
+        "{mismatch}\n(pass --allow-keyboard-mismatch to continue anyway)"
+    )))
+}
+
+/// Saves a layout to a file, or to stdout when `path` is `-`.
+pub fn write_layout(layout: &Layout, path: &Path) -> CliResult<()> {
+    if !is_stdio(path) {
+        return LayoutService::save(layout, path)
+            .map_err(|e| CliError::io(format!("Failed to save layout: {e}")));
+    }
+
+    let markdown = generate_markdown(layout)
+        .map_err(|e| CliError::io(format!("Failed to generate layout markdown: {e}")))?;
+    let mut stdout = std::io::stdout().lock();
+    stdout
+        .write_all(markdown.as_bytes())
+        .and_then(|()| stdout.flush())
+        .map_err(|e| CliError::io(format!("Failed to write layout to stdout: {e}")))
+}
+
+/// Matches `text` against a pattern where `*` is any run and `?` any character.
+///
+/// Used for file name globs and keycode filters (e.g., `LT(*`).
+#[must_use]
+pub fn wildcard_match(pattern: &str, text: &str) -> bool {
+    let pattern: Vec<char> = pattern.chars().collect();
+    let text: Vec<char> = text.chars().collect();
+    let (mut p, mut t) = (0, 0);
+    let mut backtrack: Option<(usize, usize)> = None;
+
+    while t < text.len() {
+        if p < pattern.len() && (pattern[p] == '?' || pattern[p] == text[t]) {
+            p += 1;
+            t += 1;
+        } else if p < pattern.len() && pattern[p] == '*' {
+            backtrack = Some((p, t));
+            p += 1;
+        } else if let Some((star_p, star_t)) = backtrack {
+            p = star_p + 1;
+            t = star_t + 1;
+            backtrack = Some((star_p, star_t + 1));
+        } else {
+            return false;
+        }
+    }
+
+    pattern[p..].iter().all(|&c| c == '*')
+}
+
 /// JSON response for validation commands.
 #[derive(Debug, Clone, Serialize)]
 pub struct ValidationResponse {
@@ -97,6 +317,38 @@ pub struct ValidationMessage {
     /// Optional location context
     #[serde(skip_serializing_if = "Option::is_none")]
     pub location: Option<ValidationLocation>,
+    /// How to resolve the issue
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub suggestion: Option<String>,
+    /// Rule ID for `lazyqmk fix --rule`, if the issue has a quick fix
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub rule: Option<&'static str>,
+}
+
+impl ValidationMessage {
+    /// Converts a validator error.
+    #[must_use]
+    pub fn from_error(error: &ValidationError) -> Self {
+        Self {
+            severity: "error".to_string(),
+            message: error.message.clone(),
+            location: ValidationLocation::new(error.layer, error.row, error.col),
+            suggestion: error.suggestion.clone(),
+            rule: None,
+        }
+    }
+
+    /// Converts a validator warning.
+    #[must_use]
+    pub fn from_warning(warning: &ValidationWarning) -> Self {
+        Self {
+            severity: "warning".to_string(),
+            message: warning.message.clone(),
+            location: ValidationLocation::new(warning.layer, warning.row, warning.col),
+            suggestion: warning.suggestion.clone(),
+            rule: warning.rule,
+        }
+    }
 }
 
 /// Location context for a validation message.
@@ -108,6 +360,20 @@ pub struct ValidationLocation {
     pub position: ValidationPosition,
 }
 
+impl ValidationLocation {
+    /// Builds a location if the layer, row, and column are all known.
+    #[must_use]
+    pub const fn new(layer: Option<usize>, row: Option<u8>, col: Option<u8>) -> Option<Self> {
+        match (layer, row, col) {
+            (Some(layer), Some(row), Some(col)) => Some(Self {
+                layer,
+                position: ValidationPosition { row, col },
+            }),
+            _ => None,
+        }
+    }
+}
+
 /// Position coordinates.
 #[derive(Debug, Clone, Serialize)]
 pub struct ValidationPosition {
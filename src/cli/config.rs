@@ -1,10 +1,12 @@
 //! Configuration management CLI commands.
 
 use crate::cli::common::{CliError, CliResult};
-use crate::config::{Config, ThemeMode};
+use crate::config::{BuildBackend, Config, KeyboardDefaults, ThemeMode, ValueOrigin};
+use crate::firmware::{converter, paths};
 use clap::{Args, Subcommand};
 use serde::Serialize;
-use std::path::PathBuf;
+use std::collections::BTreeMap;
+use std::path::{Path, PathBuf};
 
 /// Configuration management commands
 #[derive(Args, Debug)]
@@ -18,7 +20,7 @@ enum ConfigCommand {
     /// Display current configuration
     Show(ConfigShowArgs),
     /// Set configuration values
-    Set(ConfigSetArgs),
+    Set(Box<ConfigSetArgs>),
 }
 
 /// Display current configuration
@@ -27,6 +29,11 @@ pub struct ConfigShowArgs {
     /// Output as JSON
     #[arg(long)]
     json: bool,
+
+    /// Show the configuration for this layout file or directory, including
+    /// its project file (default: current directory)
+    #[arg(long, value_name = "PATH")]
+    layout: Option<PathBuf>,
 }
 
 /// Set configuration values
@@ -40,9 +47,50 @@ pub struct ConfigSetArgs {
     #[arg(long, value_name = "DIR")]
     output_dir: Option<PathBuf>,
 
-    /// Theme mode (auto, light, or dark)
+    /// Theme mode (auto, light, dark, or high-contrast)
     #[arg(long, value_name = "MODE")]
     theme: Option<String>,
+
+    /// Directory with keymap.c/config.h/rules.mk template overrides
+    #[arg(long, value_name = "DIR")]
+    template_dir: Option<PathBuf>,
+
+    /// Keyboard to build for layouts that don't set one
+    #[arg(long, value_name = "KEYBOARD")]
+    build_keyboard: Option<String>,
+
+    /// Keymap name to build for layouts that don't set one
+    #[arg(long, value_name = "NAME")]
+    build_keymap: Option<String>,
+
+    /// Controller converter (`CONVERT_TO`) for layouts that don't set one
+    #[arg(long, value_name = "CONVERTER")]
+    build_converter: Option<String>,
+
+    /// Build backend: auto (qmk CLI when available, else make), make, or qmk
+    #[arg(long, value_name = "BACKEND")]
+    build_backend: Option<BuildBackend>,
+
+    /// Shared tap dance library file used by the TUI's library import
+    #[arg(long, value_name = "FILE")]
+    library: Option<PathBuf>,
+
+    /// Keyboard whose new-layout defaults the options below set (e.g., crkbd)
+    #[arg(long, value_name = "KEYBOARD")]
+    keyboard: Option<String>,
+
+    /// Layout variant preselected for the keyboard's new layouts
+    #[arg(long, value_name = "NAME", requires = "keyboard")]
+    default_variant: Option<String>,
+
+    /// Keymap name pattern for the keyboard's new layouts; {user}, {layout}
+    /// and {keyboard} are replaced (e.g., "{user}_{layout}")
+    #[arg(long, value_name = "PATTERN", requires = "keyboard")]
+    keymap_pattern: Option<String>,
+
+    /// Number of layers the keyboard's new layouts start with
+    #[arg(long, value_name = "N", requires = "keyboard")]
+    layer_count: Option<u8>,
 }
 
 /// JSON-serializable configuration for output
@@ -51,22 +99,44 @@ struct ConfigOutput {
     paths: PathsOutput,
     build: BuildOutput,
     ui: UiOutput,
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    lint_disable: Vec<String>,
+    /// New-layout defaults by keyboard
+    #[serde(skip_serializing_if = "BTreeMap::is_empty")]
+    keyboards: BTreeMap<String, KeyboardDefaults>,
+    /// Project file merged over the user config
+    #[serde(skip_serializing_if = "Option::is_none")]
+    project_file: Option<String>,
+    /// Settings not from the user config, by key: "project" or "environment"
+    #[serde(skip_serializing_if = "BTreeMap::is_empty")]
+    sources: BTreeMap<&'static str, ValueOrigin>,
 }
 
 #[derive(Serialize, Debug)]
 struct PathsOutput {
     #[serde(skip_serializing_if = "Option::is_none")]
     qmk_firmware: Option<String>,
+    library: String,
 }
 
 #[derive(Serialize, Debug)]
 struct BuildOutput {
     output_dir: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    template_dir: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    keyboard: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    keymap: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    converter: Option<String>,
+    backend: &'static str,
 }
 
 #[derive(Serialize, Debug)]
 struct UiOutput {
     theme: String,
+    transparent_keys: &'static str,
 }
 
 impl ConfigArgs {
@@ -82,7 +152,11 @@ impl ConfigArgs {
 impl ConfigShowArgs {
     /// Execute show command
     pub fn execute(&self) -> CliResult<()> {
-        let config = Config::load()
+        let mut config = Config::load()
+            .map_err(|e| CliError::validation(format!("Failed to load configuration: {}", e)))?;
+        let start = self.layout.as_deref().unwrap_or_else(|| Path::new("."));
+        config
+            .apply_layers(start)
             .map_err(|e| CliError::validation(format!("Failed to load configuration: {}", e)))?;
 
         if self.json {
@@ -99,9 +173,21 @@ impl ConfigSetArgs {
     /// Execute set command
     pub fn execute(&self) -> CliResult<()> {
         // At least one argument must be provided
-        if self.qmk_path.is_none() && self.output_dir.is_none() && self.theme.is_none() {
+        if self.qmk_path.is_none()
+            && self.output_dir.is_none()
+            && self.theme.is_none()
+            && self.template_dir.is_none()
+            && self.build_keyboard.is_none()
+            && self.build_keymap.is_none()
+            && self.build_converter.is_none()
+            && self.build_backend.is_none()
+            && self.library.is_none()
+            && self.default_variant.is_none()
+            && self.keymap_pattern.is_none()
+            && self.layer_count.is_none()
+        {
             return Err(CliError::validation(
-                "At least one configuration option must be specified: --qmk-path, --output-dir, or --theme"
+                "At least one configuration option must be specified: --qmk-path, --output-dir, --theme, --template-dir, --build-keyboard, --build-keymap, --build-converter, --build-backend, --library, or --keyboard with --default-variant, --keymap-pattern or --layer-count"
             ));
         }
 
@@ -148,18 +234,71 @@ impl ConfigSetArgs {
             config.build.output_dir.clone_from(path);
         }
 
+        // Validate and apply template_dir if provided
+        if let Some(path) = &self.template_dir {
+            if !path.is_dir() {
+                return Err(CliError::validation(format!(
+                    "Template directory does not exist: {}",
+                    path.display()
+                )));
+            }
+
+            config.build.template_dir = Some(path.clone());
+        }
+
+        // Validate and apply build fallbacks if provided
+        if let Some(keyboard) = &self.build_keyboard {
+            paths::validate_keyboard_path(keyboard)
+                .map_err(|e| CliError::validation(e.to_string()))?;
+            config.build.keyboard = Some(keyboard.clone());
+        }
+        if let Some(keymap) = &self.build_keymap {
+            paths::validate_keymap_name(keymap).map_err(|e| CliError::validation(e.to_string()))?;
+            config.build.keymap = Some(keymap.clone());
+        }
+        if let Some(name) = &self.build_converter {
+            converter::validate_converter_name(name)
+                .map_err(|e| CliError::validation(e.to_string()))?;
+            if let Some(warning) = converter::converter_warning(name) {
+                eprintln!("Warning: {warning}");
+            }
+            config.build.converter = Some(name.clone());
+        }
+        if let Some(backend) = self.build_backend {
+            config.build.backend = backend;
+        }
+
+        if let Some(path) = &self.library {
+            config.paths.library = Some(path.clone());
+        }
+
+        // Apply per-keyboard defaults if provided
+        if let Some(keyboard) = &self.keyboard {
+            paths::validate_keyboard_path(keyboard)
+                .map_err(|e| CliError::validation(e.to_string()))?;
+            if self.layer_count == Some(0) {
+                return Err(CliError::validation("--layer-count must be at least 1"));
+            }
+            let defaults = config.keyboard_defaults_mut(keyboard);
+            if let Some(variant) = &self.default_variant {
+                defaults.default_variant = Some(variant.clone());
+            }
+            if let Some(pattern) = &self.keymap_pattern {
+                defaults.keymap_pattern = Some(pattern.clone());
+            }
+            if let Some(count) = self.layer_count {
+                defaults.layer_count = Some(count);
+            }
+        }
+
         // Validate and apply theme if provided
         if let Some(theme_str) = &self.theme {
-            let theme = match theme_str.to_lowercase().as_str() {
-                "auto" => ThemeMode::Auto,
-                "light" => ThemeMode::Light,
-                "dark" => ThemeMode::Dark,
-                _ => {
-                    return Err(CliError::validation(
-                        "Invalid theme mode. Must be 'auto', 'light', or 'dark'".to_string(),
-                    ))
-                }
-            };
+            let theme = parse_theme_mode(theme_str).ok_or_else(|| {
+                CliError::validation(
+                    "Invalid theme mode. Must be 'auto', 'light', 'dark', or 'high-contrast'"
+                        .to_string(),
+                )
+            })?;
             config.ui.theme_mode = theme;
         }
 
@@ -183,13 +322,32 @@ fn output_json(config: &Config) -> CliResult<()> {
                 .qmk_firmware
                 .as_ref()
                 .map(|p| p.to_string_lossy().to_string()),
+            library: library_path(config)?,
         },
         build: BuildOutput {
             output_dir: config.build.output_dir.to_string_lossy().to_string(),
+            template_dir: config
+                .build
+                .template_dir
+                .as_ref()
+                .map(|p| p.to_string_lossy().to_string()),
+            keyboard: config.build.keyboard.clone(),
+            keymap: config.build.keymap.clone(),
+            converter: config.build.converter.clone(),
+            backend: config.build.backend.name(),
         },
         ui: UiOutput {
-            theme: format!("{:?}", config.ui.theme_mode).to_lowercase(),
+            theme: theme_mode_name(config.ui.theme_mode).to_string(),
+            transparent_keys: config.ui.transparent_keys.name(),
         },
+        lint_disable: config.lint.disable.clone(),
+        keyboards: config.keyboards.clone(),
+        project_file: config
+            .layers
+            .project_file
+            .as_ref()
+            .map(|p| p.to_string_lossy().to_string()),
+        sources: config.layers.origins.clone(),
     };
 
     let json = serde_json::to_string_pretty(&output)
@@ -199,30 +357,126 @@ fn output_json(config: &Config) -> CliResult<()> {
     Ok(())
 }
 
+/// Configured or default shared library file
+fn library_path(config: &Config) -> CliResult<String> {
+    config
+        .library_path()
+        .map(|p| p.to_string_lossy().to_string())
+        .map_err(|e| CliError::io(format!("Failed to locate library file: {e}")))
+}
+
 /// Output configuration in human-readable format
 fn output_human_readable(config: &Config) {
+    // Marks values set by the project file or the environment
+    let from = |key: &str| {
+        config
+            .origin(key)
+            .map(|origin| format!(" ({})", origin.label()))
+            .unwrap_or_default()
+    };
+
     println!("LazyQMK Configuration");
     println!("====================");
     println!();
 
+    if let Some(project_file) = &config.layers.project_file {
+        println!("Project file: {}", project_file.display());
+        println!();
+    }
+
     println!("Paths:");
     if let Some(qmk_path) = &config.paths.qmk_firmware {
-        println!("  QMK Firmware: {}", qmk_path.display());
+        println!(
+            "  QMK Firmware: {}{}",
+            qmk_path.display(),
+            from("paths.qmk_firmware")
+        );
     } else {
         println!("  QMK Firmware: (not configured)");
     }
+    match library_path(config) {
+        Ok(library) => println!("  Library: {library}{}", from("paths.library")),
+        Err(e) => println!("  Library: ({e})"),
+    }
     println!();
 
     println!("Build:");
-    println!("  Output Directory: {}", config.build.output_dir.display());
+    println!(
+        "  Output Directory: {}{}",
+        config.build.output_dir.display(),
+        from("build.output_dir")
+    );
+    if let Some(template_dir) = &config.build.template_dir {
+        println!(
+            "  Template Directory: {}{}",
+            template_dir.display(),
+            from("build.template_dir")
+        );
+    }
+    if let Some(keyboard) = &config.build.keyboard {
+        println!("  Fallback Keyboard: {keyboard}{}", from("build.keyboard"));
+    }
+    if let Some(keymap) = &config.build.keymap {
+        println!("  Fallback Keymap: {keymap}{}", from("build.keymap"));
+    }
+    if let Some(converter) = &config.build.converter {
+        println!("  Converter: {converter}{}", from("build.converter"));
+    }
+    println!("  Backend: {}", config.build.backend.name());
     println!();
 
+    if !config.lint.disable.is_empty() {
+        println!("Lint:");
+        println!(
+            "  Disabled Rules: {}{}",
+            config.lint.disable.join(", "),
+            from("lint.disable")
+        );
+        println!();
+    }
+
     println!("UI:");
-    println!(
-        "  Theme Mode: {}",
-        format!("{:?}", config.ui.theme_mode).to_lowercase()
-    );
+    println!("  Theme Mode: {}", theme_mode_name(config.ui.theme_mode));
+    println!("  Transparent Keys: {}", config.ui.transparent_keys.name());
     println!();
+
+    if !config.keyboards.is_empty() {
+        println!("Keyboard Defaults:");
+        for (keyboard, defaults) in &config.keyboards {
+            println!("  {keyboard}:");
+            if let Some(variant) = &defaults.default_variant {
+                println!("    Default Variant: {variant}");
+            }
+            if let Some(pattern) = &defaults.keymap_pattern {
+                println!("    Keymap Pattern: {pattern}");
+            }
+            if let Some(count) = defaults.layer_count {
+                println!("    Layer Count: {count}");
+            }
+        }
+        println!();
+    }
+}
+
+/// Parses a `--theme` value, case-insensitively
+fn parse_theme_mode(value: &str) -> Option<ThemeMode> {
+    match value.to_lowercase().as_str() {
+        "auto" => Some(ThemeMode::Auto),
+        "light" => Some(ThemeMode::Light),
+        "dark" => Some(ThemeMode::Dark),
+        "high-contrast" | "high_contrast" => Some(ThemeMode::HighContrast),
+        _ => None,
+    }
+}
+
+/// Theme mode as written on the command line
+const fn theme_mode_name(mode: ThemeMode) -> &'static str {
+    match mode {
+        ThemeMode::Auto => "auto",
+        ThemeMode::Light => "light",
+        ThemeMode::Dark => "dark",
+        ThemeMode::HighContrast => "high-contrast",
+    }
 }
 
 #[cfg(test)]
@@ -259,4 +513,21 @@ mod tests {
             ThemeMode::Dark
         );
     }
+
+    #[test]
+    fn test_parse_theme_mode_round_trips() {
+        for mode in [
+            ThemeMode::Auto,
+            ThemeMode::Light,
+            ThemeMode::Dark,
+            ThemeMode::HighContrast,
+        ] {
+            assert_eq!(parse_theme_mode(theme_mode_name(mode)), Some(mode));
+        }
+        assert_eq!(
+            parse_theme_mode("High_Contrast"),
+            Some(ThemeMode::HighContrast)
+        );
+        assert_eq!(parse_theme_mode("sepia"), None);
+    }
 }
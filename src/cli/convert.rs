@@ -0,0 +1,73 @@
+//! Convert command: rewrite a layout in another file format.
+
+use crate::cli::common::{CliError, CliResult};
+use crate::services::layout_format::{self, LayoutFormat, FORMATS};
+use crate::services::LayoutService;
+use clap::Args;
+use std::path::PathBuf;
+
+/// Convert a layout file to another format
+#[derive(Debug, Clone, Args)]
+pub struct ConvertArgs {
+    /// Layout file to read (format detected from extension or content)
+    #[arg(value_name = "INPUT")]
+    pub input: PathBuf,
+
+    /// File to write (format chosen by extension: .md or .lazyqmk.json)
+    #[arg(value_name = "OUTPUT")]
+    pub output: PathBuf,
+
+    /// Output format, overriding the extension (markdown, json)
+    #[arg(long, value_name = "FORMAT")]
+    pub to: Option<String>,
+
+    /// Overwrite OUTPUT if it already exists
+    #[arg(long)]
+    pub force: bool,
+}
+
+impl ConvertArgs {
+    /// Execute the convert command.
+    pub fn execute(&self) -> CliResult<()> {
+        let output_format = self.output_format()?;
+
+        if self.output.exists() && !self.force {
+            return Err(CliError::validation(format!(
+                "{} already exists. Use --force to overwrite",
+                self.output.display()
+            )));
+        }
+
+        let input_format = layout_format::format_for_load(&self.input);
+        let layout = LayoutService::load(&self.input)?;
+
+        LayoutService::save_as(&layout, &self.output, output_format).map_err(|e| {
+            CliError::io(format!(
+                "Failed to save layout {}: {e}",
+                self.output.display()
+            ))
+        })?;
+
+        println!(
+            "Converted {} ({}) -> {} ({})",
+            self.input.display(),
+            input_format.name(),
+            self.output.display(),
+            output_format.name()
+        );
+        Ok(())
+    }
+
+    fn output_format(&self) -> CliResult<&'static dyn LayoutFormat> {
+        let Some(name) = &self.to else {
+            return Ok(layout_format::format_for_save(&self.output));
+        };
+        layout_format::find_format(name).ok_or_else(|| {
+            let known: Vec<_> = FORMATS.iter().map(|format| format.name()).collect();
+            CliError::validation(format!(
+                "Unknown format '{name}'. Expected one of: {}",
+                known.join(", ")
+            ))
+        })
+    }
+}
@@ -0,0 +1,153 @@
+//! Doctor command: check that firmware builds can run.
+
+use crate::cli::common::{load_config, CliError, CliResult};
+use crate::firmware::environment::{self, EnvCheck, EnvProblem, Toolchain};
+use crate::firmware::Backend;
+use clap::Args;
+use serde::Serialize;
+use std::path::{Path, PathBuf};
+
+/// Check the QMK tree, build backend (qmk CLI or make), compiler toolchains
+/// and QMK submodules
+#[derive(Debug, Clone, Args)]
+pub struct DoctorArgs {
+    /// Path to QMK firmware repository (defaults to the configured path)
+    #[arg(long, value_name = "PATH")]
+    pub qmk_path: Option<PathBuf>,
+
+    /// Check only the toolchain this keyboard needs (defaults to
+    /// `build.keyboard` in the config; all toolchains without one)
+    #[arg(long, value_name = "KEYBOARD")]
+    pub keyboard: Option<String>,
+
+    /// Controller converter the keyboard is built for (e.g. "rp2040_ce")
+    #[arg(long, value_name = "CONVERTER")]
+    pub converter: Option<String>,
+
+    /// Output as JSON
+    #[arg(long)]
+    pub json: bool,
+}
+
+/// One check in `doctor --json`.
+#[derive(Debug, Serialize)]
+struct CheckOutput<'a> {
+    /// What was checked
+    name: &'a str,
+    /// Whether the check passed
+    ok: bool,
+    /// Whether a failure stops builds
+    required: bool,
+    /// What was found, or what is missing
+    detail: String,
+    /// How to fix a failure
+    #[serde(skip_serializing_if = "Option::is_none")]
+    fix: Option<&'a str>,
+}
+
+/// JSON output of `doctor --json`.
+#[derive(Debug, Serialize)]
+struct DoctorResponse<'a> {
+    /// Whether every required check passed
+    ok: bool,
+    /// Build backend builds would use (e.g. "make")
+    backend: String,
+    /// Checks in the order they ran
+    checks: Vec<CheckOutput<'a>>,
+}
+
+impl DoctorArgs {
+    /// Execute the doctor command.
+    pub fn execute(&self) -> CliResult<()> {
+        let config = load_config(Path::new("."))?;
+        let qmk_path = self
+            .qmk_path
+            .clone()
+            .or_else(|| config.paths.qmk_firmware.clone());
+        let keyboard = self
+            .keyboard
+            .clone()
+            .or_else(|| config.build.keyboard.clone());
+        let converter = self
+            .converter
+            .clone()
+            .or_else(|| config.build.converter.clone());
+
+        // With a keyboard only its toolchain matters; without one every
+        // toolchain is listed, but a missing one isn't a failure
+        let needed = match (&qmk_path, &keyboard) {
+            (Some(qmk_path), Some(keyboard)) => {
+                environment::toolchain_for_build(qmk_path, keyboard, converter.as_deref())
+            }
+            _ => None,
+        };
+        let toolchains = needed.map_or(Toolchain::ALL.to_vec(), |toolchain| vec![toolchain]);
+
+        let tree = qmk_path.as_deref().unwrap_or_else(|| Path::new(""));
+        let backend = Backend::resolve(config.build.backend, tree);
+        let mut checks = environment::check_environment(tree, backend, &toolchains);
+        if qmk_path.is_none() {
+            checks[0].result = Err(EnvProblem {
+                message: "QMK firmware path not configured".to_string(),
+                fix: "lazyqmk config set --qmk-path <DIR>".to_string(),
+            });
+        }
+        let is_required = |check: &EnvCheck| {
+            needed.is_some() || !toolchains.iter().any(|t| t.to_string() == check.name)
+        };
+        let failures = checks
+            .iter()
+            .filter(|check| check.result.is_err() && is_required(check))
+            .count();
+
+        if self.json {
+            let response = DoctorResponse {
+                ok: failures == 0,
+                backend: backend.to_string(),
+                checks: checks
+                    .iter()
+                    .map(|check| CheckOutput {
+                        name: &check.name,
+                        ok: check.result.is_ok(),
+                        required: is_required(check),
+                        detail: match &check.result {
+                            Ok(found) => found.clone(),
+                            Err(problem) => problem.message.clone(),
+                        },
+                        fix: check.result.as_ref().err().map(|p| p.fix.as_str()),
+                    })
+                    .collect(),
+            };
+            println!(
+                "{}",
+                serde_json::to_string_pretty(&response)
+                    .map_err(|e| CliError::io(format!("Failed to serialize JSON: {e}")))?
+            );
+        } else {
+            println!("Build backend: {backend} ({})", config.build.backend.name());
+            for check in &checks {
+                match &check.result {
+                    Ok(found) => println!("✓ {}: {found}", check.name),
+                    Err(problem) if is_required(check) => {
+                        println!("✗ {}: {}", check.name, problem.message);
+                        println!("    To fix: {}", problem.fix);
+                    }
+                    Err(problem) => println!(
+                        "- {}: not found (install with `{}` to build these keyboards)",
+                        check.name, problem.fix
+                    ),
+                }
+            }
+        }
+
+        if failures == 0 {
+            if !self.json {
+                println!();
+                println!("Ready to build");
+            }
+            Ok(())
+        } else {
+            Err(CliError::validation(format!("{failures} problem(s) found")))
+        }
+    }
+}
@@ -1,20 +1,18 @@
-//! Export command for generating markdown documentation.
+//! Export command for generating markdown documentation and layer data.
 
-use crate::cli::common::{CliError, CliResult};
-use crate::config::Config;
+use crate::atomic_write;
+use crate::cli::common::{is_stdio, load_config, read_layout, CliError, CliResult};
 use crate::export;
 use crate::keycode_db::KeycodeDb;
 use crate::models::Layout;
 use crate::services::geometry;
-use crate::services::LayoutService;
 use clap::Args;
-use std::fs;
 use std::path::PathBuf;
 
-/// Export keyboard layout to markdown documentation
+/// Export keyboard layout to markdown documentation or layer data for other tools
 #[derive(Debug, Clone, Args)]
 pub struct ExportArgs {
-    /// Path to layout markdown file
+    /// Path to layout markdown file (`-` reads from stdin)
     #[arg(short, long, value_name = "FILE")]
     pub layout: PathBuf,
 
@@ -22,10 +20,20 @@ pub struct ExportArgs {
     #[arg(long, value_name = "PATH")]
     pub qmk_path: PathBuf,
 
-    /// Output path for markdown file (defaults to [layout_name]_export_[date].md)
+    /// Output path (defaults to [layout_name]_export_[date].md, .yaml or .json; `-` writes to stdout)
     #[arg(short, long, value_name = "FILE")]
     pub output: Option<PathBuf>,
 
+    /// Output format: markdown, keymap-drawer (YAML for keymap-drawer), or
+    /// layers-json (resolved keys of every layer)
+    #[arg(long, value_name = "FORMAT", default_value = "markdown")]
+    pub format: String,
+
+    /// Leave transparent keys out of the markdown diagrams (also on when
+    /// `ui.transparent_keys` is "hidden")
+    #[arg(long)]
+    pub skip_transparent: bool,
+
     /// QMK layout variant (auto-detected from metadata if omitted)
     #[arg(long, value_name = "NAME")]
     pub layout_name: Option<String>,
@@ -34,12 +42,21 @@ pub struct ExportArgs {
 impl ExportArgs {
     /// Execute the export command
     pub fn execute(&self) -> CliResult<()> {
+        if !matches!(
+            self.format.as_str(),
+            "markdown" | "keymap-drawer" | "layers-json"
+        ) {
+            return Err(CliError::validation(format!(
+                "Invalid format '{}'. Must be 'markdown', 'keymap-drawer', or 'layers-json'",
+                self.format
+            )));
+        }
+
         // Load layout
-        let layout = LayoutService::load(&self.layout)
-            .map_err(|e| CliError::io(format!("Failed to load layout: {e}")))?;
+        let layout = read_layout(&self.layout)?;
 
         // Build config with QMK path
-        let mut config = Config::load().unwrap_or_default();
+        let mut config = load_config(&self.layout)?;
         config.paths.qmk_firmware = Some(self.qmk_path.clone());
 
         // Determine layout variant
@@ -61,21 +78,36 @@ impl ExportArgs {
 
         let geo_result = geometry::build_geometry_for_layout(geo_context, &layout_variant)
             .map_err(|e| CliError::io(format!("Failed to build geometry: {e}")))?;
-        let geometry = geo_result.geometry;
 
-        // Load keycode database (needed for tap dance docs)
+        // Load keycode database (needed for legends and tap dance docs)
         let keycode_db = KeycodeDb::load()
             .map_err(|e| CliError::io(format!("Failed to load keycode database: {e}")))?;
 
-        // Generate markdown content using export module
-        let markdown = export::export_to_markdown(&layout, &geometry, &keycode_db)
-            .map_err(|e| CliError::io(format!("Failed to generate markdown: {e}")))?;
+        // Generate content using export module
+        let content = match self.format.as_str() {
+            "keymap-drawer" => {
+                export::export_keymap_drawer(&layout, &geo_result.mapping, &keycode_db)
+            }
+            "layers-json" => export::export_layers_json(&layout, &geo_result.mapping, &keycode_db),
+            _ => export::export_to_markdown(
+                &layout,
+                &geo_result.geometry,
+                &keycode_db,
+                self.skip_transparent || config.ui.transparent_keys.skips_in_exports(),
+            ),
+        }
+        .map_err(|e| CliError::io(format!("Failed to generate {}: {e}", self.format)))?;
 
         // Determine output path
         let output_path = self.get_output_path(&layout);
 
+        if is_stdio(&output_path) {
+            print!("{content}");
+            return Ok(());
+        }
+
         // Write to file
-        fs::write(&output_path, markdown)
+        atomic_write::write(&output_path, content)
             .map_err(|e| CliError::io(format!("Failed to write output file: {e}")))?;
 
         println!("✓ Exported layout to: {}", output_path.display());
@@ -89,11 +121,16 @@ impl ExportArgs {
             return path.clone();
         }
 
-        // Auto-generate filename: [layout_name]_export_[date].md
+        // Auto-generate filename: [layout_name]_export_[date].[ext]
         let date = chrono::Local::now().format("%Y-%m-%d");
         let layout_name = layout.metadata.name.replace(' ', "_").to_lowercase();
+        let extension = match self.format.as_str() {
+            "keymap-drawer" => "yaml",
+            "layers-json" => "json",
+            _ => "md",
+        };
 
-        PathBuf::from(format!("{}_export_{}.md", layout_name, date))
+        PathBuf::from(format!("{}_export_{}.{extension}", layout_name, date))
     }
 }
 
@@ -108,6 +145,8 @@ mod tests {
             qmk_path: PathBuf::from("/qmk"),
             output: None,
             layout_name: None,
+            format: "markdown".to_string(),
+            skip_transparent: false,
         };
 
         let layout = Layout::new("My Test Layout").unwrap();
@@ -126,6 +165,8 @@ mod tests {
             qmk_path: PathBuf::from("/qmk"),
             output: Some(custom_path.clone()),
             layout_name: None,
+            format: "markdown".to_string(),
+            skip_transparent: false,
         };
 
         let layout = Layout::new("Test").unwrap();
@@ -0,0 +1,70 @@
+//! Fix command: apply a validator quick fix to layout files.
+
+use crate::cli::common::{CliError, CliResult};
+use crate::services::quick_fix::QuickFix;
+use crate::services::LayoutService;
+use clap::Args;
+use std::path::PathBuf;
+
+/// Apply the quick fix for a validation rule
+#[derive(Debug, Clone, Args)]
+pub struct FixArgs {
+    /// Rule whose warnings to fix (e.g., "base-layer-trns", "deprecated-keycode")
+    #[arg(long, value_name = "RULE")]
+    pub rule: String,
+
+    /// Layout markdown files to fix in place
+    #[arg(value_name = "FILES", required = true)]
+    pub layouts: Vec<PathBuf>,
+
+    /// Report what would change without writing any file
+    #[arg(long)]
+    pub dry_run: bool,
+}
+
+impl FixArgs {
+    /// Execute the fix command.
+    ///
+    /// All files are loaded before any is written, so a parse error in one
+    /// file leaves every file untouched.
+    pub fn execute(&self) -> CliResult<()> {
+        let fix = QuickFix::from_rule(&self.rule).ok_or_else(|| {
+            let known: Vec<_> = QuickFix::ALL.iter().map(|fix| fix.rule()).collect();
+            CliError::validation(format!(
+                "Unknown rule '{}'. Expected one of: {}",
+                self.rule,
+                known.join(", ")
+            ))
+        })?;
+
+        let mut layouts = Vec::with_capacity(self.layouts.len());
+        for path in &self.layouts {
+            let layout = LayoutService::load(path)?;
+            layouts.push((path, layout));
+        }
+
+        let mut total = 0;
+        for (path, layout) in &mut layouts {
+            let changes = fix.apply(layout);
+            println!("{}: {} change(s)", path.display(), changes.len());
+            for change in &changes {
+                println!("  {change}");
+            }
+            total += changes.len();
+
+            if !changes.is_empty() && !self.dry_run {
+                LayoutService::save(layout, path).map_err(|e| {
+                    CliError::io(format!("Failed to save layout {}: {e}", path.display()))
+                })?;
+            }
+        }
+
+        let verb = if self.dry_run { "Would fix" } else { "Fixed" };
+        println!(
+            "{verb} {total} change(s) across {} file(s): {}",
+            self.layouts.len(),
+            fix.description()
+        );
+        Ok(())
+    }
+}
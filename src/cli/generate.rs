@@ -1,61 +1,209 @@
 //! Generate command for firmware files.
+//!
+//! Exits 0 when clean, 3 when files were generated but validation reported
+//! warnings, 1 on validation failure and 2 on I/O failure. Every file is
+//! rendered before any is written and unchanged files are not rewritten. If a
+//! write fails, the files written before it stay, the failed file and the
+//! ones after it keep their previous copies, and the error names them.
 
-use crate::cli::common::{CliError, CliResult};
-use crate::config::Config;
+use crate::atomic_write;
+use crate::cli::common::{
+    check_keyboard_mismatch, load_config, read_layout, record_history, CliError, CliResult,
+    ExitCode, ValidationMessage,
+};
 use crate::firmware::generator::FirmwareGenerator;
+use crate::firmware::templates;
+use crate::firmware::validator::ValidationReport;
 use crate::keycode_db::KeycodeDb;
+use crate::models::Layout;
 use crate::services::geometry;
-use crate::services::LayoutService;
+use crate::services::history::{HistoryEntry, HistoryOperation};
 use clap::Args;
-use std::path::PathBuf;
+use serde::Serialize;
+use std::path::{Path, PathBuf};
+use std::time::Instant;
 
 /// Generate QMK firmware files from a layout
 #[derive(Debug, Clone, Args)]
+#[allow(clippy::struct_excessive_bools)] // Independent command-line flags
 pub struct GenerateArgs {
-    /// Path to layout markdown file
-    #[arg(short, long, value_name = "FILE")]
-    pub layout: PathBuf,
+    /// Path to layout markdown file (`-` reads from stdin)
+    #[arg(
+        short,
+        long,
+        value_name = "FILE",
+        required_unless_present = "dump_templates"
+    )]
+    pub layout: Option<PathBuf>,
 
     /// Path to QMK firmware repository
-    #[arg(long, value_name = "PATH")]
-    pub qmk_path: PathBuf,
+    #[arg(long, value_name = "PATH", required_unless_present = "dump_templates")]
+    pub qmk_path: Option<PathBuf>,
 
     /// Output directory for generated files
-    #[arg(short, long, value_name = "DIR")]
-    pub out_dir: PathBuf,
+    #[arg(
+        short,
+        long,
+        value_name = "DIR",
+        required_unless_present = "dump_templates"
+    )]
+    pub out_dir: Option<PathBuf>,
 
     /// QMK layout variant (auto-detected from metadata if omitted)
     #[arg(long, value_name = "NAME")]
     pub layout_name: Option<String>,
 
-    /// Output format: keymap, config, or all
+    /// Output format: keymap, config, rules, json-keymap, or all
+    /// (json-keymap writes a QMK Configurator keymap.json)
     #[arg(long, value_name = "TYPE", default_value = "all")]
     pub format: String,
 
+    /// Also write keymap.json alongside the requested files
+    #[arg(long)]
+    pub with_json_keymap: bool,
+
     /// Use stable timestamps/UUIDs for deterministic output (for testing)
     #[arg(long)]
     pub deterministic: bool,
+
+    /// Print a JSON summary (validation, per-file status, timing)
+    #[arg(long)]
+    pub json: bool,
+
+    /// Generate even if the layout's keyboard differs from `build.keyboard`
+    /// in the config
+    #[arg(long)]
+    pub allow_keyboard_mismatch: bool,
+
+    /// Write the embedded keymap.c/config.h/rules.mk templates to DIR and exit
+    #[arg(long, value_name = "DIR", conflicts_with = "layout")]
+    pub dump_templates: Option<PathBuf>,
+}
+
+/// What happened to one generated file.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "snake_case")]
+enum FileStatus {
+    /// File was written with new content
+    Written,
+    /// File already had identical content and was left untouched
+    Unchanged,
+    /// File was not requested by `--format`
+    Skipped,
+    /// Writing the file failed; any previous copy is left as it was
+    Failed,
+    /// Not attempted because an earlier file failed; any previous copy is
+    /// left as it was
+    NotWritten,
+}
+
+/// Per-file entry in the generate summary.
+#[derive(Debug, Clone, Serialize)]
+struct GeneratedFile {
+    /// File name (e.g., "keymap.c")
+    name: &'static str,
+    /// Full output path
+    path: PathBuf,
+    /// Write status
+    status: FileStatus,
+    /// Why the write failed (`failed` only)
+    #[serde(skip_serializing_if = "Option::is_none")]
+    error: Option<String>,
+}
+
+/// Validation results included in the generate summary.
+#[derive(Debug, Clone, Serialize)]
+struct GenerateValidation {
+    /// Whether validation passed (no errors)
+    valid: bool,
+    /// Errors and warnings
+    messages: Vec<ValidationMessage>,
+}
+
+/// JSON summary printed by `generate --json`.
+#[derive(Debug, Clone, Serialize)]
+struct GenerateSummary {
+    /// "success", "warnings" or "failed"
+    status: &'static str,
+    /// Process exit code for this run
+    exit_code: i32,
+    /// Validation results
+    validation: GenerateValidation,
+    /// Output directory
+    output_dir: PathBuf,
+    /// Per-file write status
+    files: Vec<GeneratedFile>,
+    /// Wall-clock time of the run in milliseconds
+    duration_ms: u64,
 }
 
 impl GenerateArgs {
-    /// Execute the generate command
-    pub fn execute(&self) -> CliResult<()> {
+    /// Execute the generate command.
+    ///
+    /// Returns [`ExitCode::SuccessWithWarnings`] when files were generated but
+    /// validation reported warnings.
+    pub fn execute(&self) -> CliResult<ExitCode> {
+        let started = Instant::now();
+
+        if let Some(dir) = &self.dump_templates {
+            let written = templates::dump_embedded(dir)
+                .map_err(|e| CliError::io(format!("Failed to dump templates: {e}")))?;
+            println!("✓ Wrote {} templates", written.len());
+            println!("  Output: {}", dir.display());
+            println!("  Set build.template_dir in config.toml to use them");
+            return Ok(ExitCode::Success);
+        }
+
+        let (Some(layout_path), Some(qmk_path), Some(out_dir)) =
+            (&self.layout, &self.qmk_path, &self.out_dir)
+        else {
+            return Err(CliError::validation(
+                "--layout, --qmk-path and --out-dir are required",
+            ));
+        };
+
         // Validate format
-        if !matches!(self.format.as_str(), "keymap" | "config" | "all") {
+        if !matches!(
+            self.format.as_str(),
+            "keymap" | "config" | "rules" | "json-keymap" | "all"
+        ) {
             return Err(CliError::validation(format!(
-                "Invalid format '{}'. Must be 'keymap', 'config', or 'all'",
+                "Invalid format '{}'. Must be 'keymap', 'config', 'rules', 'json-keymap', or 'all'",
                 self.format
             )));
         }
 
         // Load layout
-        let layout = LayoutService::load(&self.layout)
-            .map_err(|e| CliError::io(format!("Failed to load layout: {e}")))?;
+        let layout = read_layout(layout_path)?;
+
+        let mut entry = HistoryEntry::new(HistoryOperation::Generate).with_qmk_tree(Some(qmk_path));
+        let result = self.generate(layout, qmk_path, out_dir, started, &mut entry);
+        record_history(layout_path, entry, &result);
+        result
+    }
 
+    /// Validates `layout` and writes the requested files, counting
+    /// validation warnings into `entry`.
+    fn generate(
+        &self,
+        layout: Layout,
+        qmk_path: &Path,
+        out_dir: &Path,
+        started: Instant,
+        entry: &mut HistoryEntry,
+    ) -> CliResult<ExitCode> {
         // Build config with QMK path
-        let mut config = Config::load().unwrap_or_default();
-        config.paths.qmk_firmware = Some(self.qmk_path.clone());
-        config.build.output_dir.clone_from(&self.out_dir);
+        let layout_path = self.layout.as_deref().unwrap_or_else(|| Path::new("."));
+        let mut config = load_config(layout_path)?;
+        config.paths.qmk_firmware = Some(qmk_path.to_path_buf());
+        config.build.output_dir = out_dir.to_path_buf();
+        check_keyboard_mismatch(
+            &layout,
+            None,
+            &config,
+            out_dir,
+            self.allow_keyboard_mismatch,
+        )?;
 
         // Determine layout variant
         let layout_variant = self
@@ -93,8 +241,19 @@ impl GenerateArgs {
         let report = validator
             .validate()
             .map_err(|e| CliError::io(format!("Validation failed: {e}")))?;
+        let validation = validation_summary(&report);
+        entry.warnings = report.warnings.len();
 
         if !report.is_valid() {
+            if self.json {
+                self.print_summary(
+                    ExitCode::ValidationError,
+                    validation,
+                    out_dir,
+                    Vec::new(),
+                    started,
+                )?;
+            }
             return Err(CliError::validation(format!(
                 "Layout validation failed:\n{}",
                 report.format_message()
@@ -102,84 +261,190 @@ impl GenerateArgs {
         }
 
         // Create output directory
-        std::fs::create_dir_all(&self.out_dir)
+        std::fs::create_dir_all(out_dir)
             .map_err(|e| CliError::io(format!("Failed to create output directory: {e}")))?;
 
         // Generate files
         let generator = FirmwareGenerator::new(&layout, &geometry, &mapping, &config, &keycode_db);
+        let wants = |format: &str| match format {
+            // keymap.json is opt-in, so `all` keeps producing the C sources only
+            "json-keymap" => self.format == format || self.with_json_keymap,
+            _ => self.format == "all" || self.format == format,
+        };
 
-        match self.format.as_str() {
-            "all" => {
-                // Generate both files
-                let keymap_c = generator
-                    .generate_keymap_c()
-                    .map_err(|e| CliError::io(format!("Failed to generate keymap.c: {e}")))?;
-                let config_h = generator
-                    .generate_merged_config_h()
-                    .map_err(|e| CliError::io(format!("Failed to generate config.h: {e}")))?;
-
-                // Apply deterministic transformations if requested
-                let keymap_c = if self.deterministic {
-                    normalize_for_deterministic(&keymap_c)
-                } else {
-                    keymap_c
-                };
-                let config_h = if self.deterministic {
-                    normalize_for_deterministic(&config_h)
-                } else {
-                    config_h
-                };
-
-                // Write files
-                std::fs::write(self.out_dir.join("keymap.c"), keymap_c)
-                    .map_err(|e| CliError::io(format!("Failed to write keymap.c: {e}")))?;
-                std::fs::write(self.out_dir.join("config.h"), config_h)
-                    .map_err(|e| CliError::io(format!("Failed to write config.h: {e}")))?;
-
-                println!("✓ Generated keymap.c and config.h");
-                println!("  Output: {}", self.out_dir.display());
+        // Render every requested file first, so a generation error writes nothing
+        let mut rendered = Vec::new();
+        for (name, format) in [
+            ("keymap.c", "keymap"),
+            ("config.h", "config"),
+            ("rules.mk", "rules"),
+            ("keymap.json", "json-keymap"),
+        ] {
+            let path = out_dir.join(name);
+            if format == "json-keymap" && !wants(format) {
+                continue;
             }
-            "keymap" => {
-                let keymap_c = generator
-                    .generate_keymap_c()
-                    .map_err(|e| CliError::io(format!("Failed to generate keymap.c: {e}")))?;
-
-                let keymap_c = if self.deterministic {
-                    normalize_for_deterministic(&keymap_c)
-                } else {
-                    keymap_c
-                };
-
-                std::fs::write(self.out_dir.join("keymap.c"), keymap_c)
-                    .map_err(|e| CliError::io(format!("Failed to write keymap.c: {e}")))?;
-
-                println!("✓ Generated keymap.c");
-                println!("  Output: {}", self.out_dir.display());
+            if !wants(format) {
+                rendered.push((name, path, None));
+                continue;
             }
-            "config" => {
-                let config_h = generator
-                    .generate_merged_config_h()
-                    .map_err(|e| CliError::io(format!("Failed to generate config.h: {e}")))?;
-
-                let config_h = if self.deterministic {
-                    normalize_for_deterministic(&config_h)
-                } else {
-                    config_h
-                };
-
-                std::fs::write(self.out_dir.join("config.h"), config_h)
-                    .map_err(|e| CliError::io(format!("Failed to write config.h: {e}")))?;
-
-                println!("✓ Generated config.h");
-                println!("  Output: {}", self.out_dir.display());
+
+            let content = match format {
+                "keymap" => generator.generate_keymap_c(),
+                "config" => generator.generate_merged_config_h(),
+                "json-keymap" => generator.generate_keymap_json(),
+                _ => generator.generate_rules_mk(),
             }
-            _ => unreachable!("Format already validated"),
+            .map_err(|e| CliError::io(format!("Failed to generate {name}: {e}")))?;
+
+            // Apply deterministic transformations if requested (only C sources have a timestamp)
+            let content = if self.deterministic && matches!(format, "keymap" | "config") {
+                normalize_for_deterministic(&content)
+            } else {
+                content
+            };
+            rendered.push((name, path, Some(content)));
         }
 
+        // Write them in order, stopping at the first failure. Each write is
+        // atomic, so a failed file keeps its previous copy, but files written
+        // before it are not rolled back.
+        let mut files = Vec::new();
+        let mut failure = None;
+        for (name, path, content) in rendered {
+            let (status, error) = match content {
+                None => (FileStatus::Skipped, None),
+                Some(_) if failure.is_some() => (FileStatus::NotWritten, None),
+                Some(content) => match write_if_changed(&path, &content) {
+                    Ok(status) => (status, None),
+                    Err(e) => {
+                        failure = Some(format!("Failed to write {name}: {e}"));
+                        (FileStatus::Failed, Some(e.to_string()))
+                    }
+                },
+            };
+            files.push(GeneratedFile {
+                name,
+                path,
+                status,
+                error,
+            });
+        }
+
+        if let Some(message) = failure {
+            let stale: Vec<&str> = files
+                .iter()
+                .filter(|f| matches!(f.status, FileStatus::Failed | FileStatus::NotWritten))
+                .map(|f| f.name)
+                .collect();
+            let message = format!("{message} (not updated: {})", join_names(&stale));
+            if self.json {
+                self.print_summary(ExitCode::IoError, validation, out_dir, files, started)?;
+            } else {
+                let written: Vec<&str> = files
+                    .iter()
+                    .filter(|f| f.status == FileStatus::Written)
+                    .map(|f| f.name)
+                    .collect();
+                if !written.is_empty() {
+                    eprintln!("Written before the failure: {}", join_names(&written));
+                }
+            }
+            return Err(CliError::io(message));
+        }
+
+        let exit_code = if report.warnings.is_empty() {
+            ExitCode::Success
+        } else {
+            ExitCode::SuccessWithWarnings
+        };
+
+        if self.json {
+            self.print_summary(exit_code, validation, out_dir, files, started)?;
+        } else {
+            for warning in &report.warnings {
+                eprintln!("Warning: {}", warning.message);
+            }
+            let generated: Vec<&str> = files
+                .iter()
+                .filter(|f| f.status != FileStatus::Skipped)
+                .map(|f| f.name)
+                .collect();
+            println!("✓ Generated {}", join_names(&generated));
+            let unchanged: Vec<&str> = files
+                .iter()
+                .filter(|f| f.status == FileStatus::Unchanged)
+                .map(|f| f.name)
+                .collect();
+            if !unchanged.is_empty() {
+                println!("  Unchanged: {}", unchanged.join(", "));
+            }
+            println!("  Output: {}", out_dir.display());
+        }
+
+        Ok(exit_code)
+    }
+
+    /// Prints the JSON summary for `--json`.
+    fn print_summary(
+        &self,
+        exit_code: ExitCode,
+        validation: GenerateValidation,
+        out_dir: &Path,
+        files: Vec<GeneratedFile>,
+        started: Instant,
+    ) -> CliResult<()> {
+        let status = match exit_code {
+            ExitCode::Success => "success",
+            ExitCode::SuccessWithWarnings => "warnings",
+            ExitCode::ValidationError | ExitCode::IoError => "failed",
+        };
+        let summary = GenerateSummary {
+            status,
+            exit_code: exit_code.as_i32(),
+            validation,
+            output_dir: out_dir.to_path_buf(),
+            files,
+            duration_ms: u64::try_from(started.elapsed().as_millis()).unwrap_or(u64::MAX),
+        };
+        println!(
+            "{}",
+            serde_json::to_string_pretty(&summary)
+                .map_err(|e| CliError::io(format!("Failed to serialize JSON: {e}")))?
+        );
         Ok(())
     }
 }
 
+/// Converts a validation report into summary messages.
+fn validation_summary(report: &ValidationReport) -> GenerateValidation {
+    let errors = report.errors.iter().map(ValidationMessage::from_error);
+    let warnings = report.warnings.iter().map(ValidationMessage::from_warning);
+
+    GenerateValidation {
+        valid: report.is_valid(),
+        messages: errors.chain(warnings).collect(),
+    }
+}
+
+/// Writes `content` to `path` unless the file already holds exactly that content.
+fn write_if_changed(path: &Path, content: &str) -> anyhow::Result<FileStatus> {
+    if std::fs::read_to_string(path).is_ok_and(|existing| existing == content) {
+        return Ok(FileStatus::Unchanged);
+    }
+    atomic_write::write(path, content)?;
+    Ok(FileStatus::Written)
+}
+
+/// Joins file names as "a", "a and b" or "a, b and c".
+fn join_names(names: &[&str]) -> String {
+    match names {
+        [] => String::new(),
+        [only] => (*only).to_string(),
+        [rest @ .., last] => format!("{} and {last}", rest.join(", ")),
+    }
+}
+
 /// Normalize generated code for deterministic output (remove timestamps)
 fn normalize_for_deterministic(content: &str) -> String {
     content
@@ -0,0 +1,114 @@
+//! Heatmap command for visualizing key usage from frequency data.
+
+use crate::atomic_write;
+use crate::cli::common::{is_stdio, load_config, read_layout, CliError, CliResult};
+use crate::export::heatmap::{render_heatmap_html, render_heatmap_svg, summary_lines};
+use crate::services::geometry;
+use crate::services::heatmap::{parse_frequency_csv, Heatmap};
+use clap::Args;
+use std::fs;
+use std::path::PathBuf;
+
+/// Render a key usage heatmap from typing frequency data
+#[derive(Debug, Clone, Args)]
+pub struct HeatmapArgs {
+    /// Path to layout markdown file (`-` reads from stdin)
+    #[arg(short, long, value_name = "FILE")]
+    pub layout: PathBuf,
+
+    /// Frequency CSV with `keycode,count` lines (keycodes or characters)
+    #[arg(long, value_name = "FILE")]
+    pub freq: PathBuf,
+
+    /// Output format: svg or html
+    #[arg(long, value_name = "TYPE", default_value = "svg")]
+    pub format: String,
+
+    /// Output file (`-` writes to stdout)
+    #[arg(short, long, value_name = "FILE", default_value = "-")]
+    pub output: PathBuf,
+
+    /// Render one heatmap per layer instead of summing all layers
+    #[arg(long)]
+    pub per_layer: bool,
+
+    /// Leave transparent keys out (also on when `ui.transparent_keys` is "hidden")
+    #[arg(long)]
+    pub skip_transparent: bool,
+
+    /// Path to QMK firmware repository (draws keys at physical positions)
+    #[arg(long, value_name = "PATH")]
+    pub qmk_path: Option<PathBuf>,
+}
+
+impl HeatmapArgs {
+    /// Execute the heatmap command
+    pub fn execute(&self) -> CliResult<()> {
+        if !matches!(self.format.as_str(), "svg" | "html") {
+            return Err(CliError::validation(format!(
+                "Invalid format '{}'. Must be 'svg' or 'html'",
+                self.format
+            )));
+        }
+
+        let layout = read_layout(&self.layout)?;
+
+        let content = fs::read_to_string(&self.freq)
+            .map_err(|e| CliError::io(format!("Failed to read frequency file: {e}")))?;
+        let entries = parse_frequency_csv(&content)
+            .map_err(|e| CliError::validation(format!("Invalid frequency file: {e}")))?;
+
+        let mut config = load_config(&self.layout)?;
+        let skip_transparent =
+            self.skip_transparent || config.ui.transparent_keys.skips_in_exports();
+
+        // Physical key positions when a QMK checkout is available
+        let geometry = match (&self.qmk_path, &layout.metadata.layout_variant) {
+            (Some(qmk_path), Some(variant)) => {
+                config.paths.qmk_firmware = Some(qmk_path.clone());
+                let geo_context = geometry::GeometryContext {
+                    config: &config,
+                    metadata: &layout.metadata,
+                };
+                let result = geometry::build_geometry_for_layout(geo_context, variant)
+                    .map_err(|e| CliError::io(format!("Failed to build geometry: {e}")))?;
+                Some(result.geometry)
+            }
+            _ => None,
+        };
+
+        let heatmap = Heatmap::compute(&layout, &entries);
+        let document = if self.format == "html" {
+            render_heatmap_html(
+                &layout,
+                &heatmap,
+                geometry.as_ref(),
+                self.per_layer,
+                skip_transparent,
+            )
+        } else {
+            render_heatmap_svg(
+                &layout,
+                &heatmap,
+                geometry.as_ref(),
+                self.per_layer,
+                skip_transparent,
+            )
+        };
+
+        if is_stdio(&self.output) {
+            print!("{document}");
+            return Ok(());
+        }
+
+        atomic_write::write(&self.output, document)
+            .map_err(|e| CliError::io(format!("Failed to write output file: {e}")))?;
+
+        println!("✓ Wrote heatmap to: {}", self.output.display());
+        for line in summary_lines(&layout, &heatmap) {
+            println!("  {line}");
+        }
+
+        Ok(())
+    }
+}
@@ -0,0 +1,107 @@
+//! History command: show when a layout was last generated and built.
+
+use crate::cli::common::{CliError, CliResult};
+use crate::services::history::{HistoryEntry, HistoryService};
+use chrono::Local;
+use clap::Args;
+use serde::Serialize;
+use std::path::{Path, PathBuf};
+
+/// Show the generation and build history of a layout
+#[derive(Debug, Clone, Args)]
+pub struct HistoryArgs {
+    /// Path to layout markdown file
+    #[arg(short, long, value_name = "FILE")]
+    pub layout: PathBuf,
+
+    /// Show only the N most recent runs
+    #[arg(short = 'n', long, value_name = "N")]
+    pub limit: Option<usize>,
+
+    /// Output as JSON
+    #[arg(long)]
+    pub json: bool,
+}
+
+/// JSON output of `history --json`.
+#[derive(Debug, Serialize)]
+struct HistoryResponse<'a> {
+    /// Layout file
+    layout: &'a Path,
+    /// History file the entries were read from
+    history_file: PathBuf,
+    /// Runs, newest first
+    entries: Vec<&'a HistoryEntry>,
+    /// Lines in the history file that could not be parsed
+    skipped_lines: usize,
+}
+
+impl HistoryArgs {
+    /// Execute the history command.
+    pub fn execute(&self) -> CliResult<()> {
+        if !self.layout.exists() {
+            return Err(CliError::not_found(format!(
+                "Layout file not found: {}",
+                self.layout.display()
+            )));
+        }
+
+        let history_file = HistoryService::history_path(&self.layout);
+        let history = HistoryService::read(&history_file)
+            .map_err(|e| CliError::io(format!("Failed to read history: {e}")))?;
+        let entries: Vec<&HistoryEntry> = history
+            .entries
+            .iter()
+            .rev()
+            .take(self.limit.unwrap_or(usize::MAX))
+            .collect();
+
+        if self.json {
+            let response = HistoryResponse {
+                layout: &self.layout,
+                history_file,
+                entries,
+                skipped_lines: history.skipped_lines,
+            };
+            println!(
+                "{}",
+                serde_json::to_string_pretty(&response)
+                    .map_err(|e| CliError::io(format!("Failed to serialize JSON: {e}")))?
+            );
+            return Ok(());
+        }
+
+        if history.skipped_lines > 0 {
+            eprintln!(
+                "Warning: Skipped {} unreadable line(s) in {}",
+                history.skipped_lines,
+                history_file.display()
+            );
+        }
+        if entries.is_empty() {
+            println!(
+                "No generate or build runs recorded for {}",
+                self.layout.display()
+            );
+            return Ok(());
+        }
+
+        println!("History for {}:", self.layout.display());
+        for entry in entries {
+            let qmk = entry
+                .qmk_version
+                .as_deref()
+                .map_or_else(String::new, |version| format!(", QMK {version}"));
+            println!(
+                "  {}  {}  (lazyqmk {}{qmk})",
+                entry
+                    .timestamp
+                    .with_timezone(&Local)
+                    .format("%Y-%m-%d %H:%M:%S"),
+                entry.summary(),
+                entry.lazyqmk_version
+            );
+        }
+        Ok(())
+    }
+}
@@ -1,6 +1,7 @@
 //! Inspect command for reading layout sections.
 
-use crate::cli::common::{CliError, CliResult};
+use crate::cli::common::{wildcard_match, CliError, CliResult};
+use crate::models::Layout;
 use crate::services::LayoutService;
 use clap::Args;
 use serde::Serialize;
@@ -13,10 +14,23 @@ pub struct InspectArgs {
     #[arg(short, long, value_name = "FILE")]
     pub layout: PathBuf,
 
-    /// Section to inspect: metadata, layers, categories, tap-dances, settings
+    /// Section to inspect: metadata, layers, keys, categories, tap-dances, settings
     #[arg(short, long, value_name = "NAME")]
     pub section: String,
 
+    /// Only keys on this layer index (keys section)
+    #[arg(long, value_name = "N")]
+    pub layer: Option<usize>,
+
+    /// Only keys assigned this category ID (keys section)
+    #[arg(long, value_name = "ID")]
+    pub category: Option<String>,
+
+    /// Only keycodes matching PATTERN, where `*` matches any run and `?` one
+    /// character, e.g. 'LT(*' (keys section)
+    #[arg(long, value_name = "PATTERN")]
+    pub keycode: Option<String>,
+
     /// Output as JSON
     #[arg(long)]
     pub json: bool,
@@ -25,6 +39,7 @@ pub struct InspectArgs {
 #[derive(Debug, Serialize)]
 struct MetadataSection {
     name: String,
+    description: String,
     author: String,
     keyboard: Option<String>,
     layout_variant: Option<String>,
@@ -45,6 +60,26 @@ struct LayerInfo {
     number: usize,
     name: String,
     key_count: usize,
+    enabled: bool,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    modified: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    description: Option<String>,
+}
+
+/// One key in the keys section; every field is always present so the JSON
+/// array has a fixed schema for jq
+#[derive(Debug, Serialize)]
+struct KeyInfo {
+    layer: usize,
+    layer_name: String,
+    row: u8,
+    col: u8,
+    keycode: String,
+    /// Resolved color as #RRGGBB
+    color: String,
+    category: Option<String>,
+    note: Option<String>,
 }
 
 #[derive(Debug, Serialize)]
@@ -93,23 +128,30 @@ impl InspectArgs {
         // Validate section name
         if !matches!(
             self.section.as_str(),
-            "metadata" | "layers" | "categories" | "tap-dances" | "settings"
+            "metadata" | "layers" | "keys" | "categories" | "tap-dances" | "settings"
         ) {
             return Err(CliError::validation(format!(
-                "Invalid section '{}'. Must be one of: metadata, layers, categories, tap-dances, settings",
+                "Invalid section '{}'. Must be one of: metadata, layers, keys, categories, tap-dances, settings",
                 self.section
             )));
         }
+        if self.section != "keys"
+            && (self.layer.is_some() || self.category.is_some() || self.keycode.is_some())
+        {
+            return Err(CliError::validation(
+                "--layer, --category and --keycode only apply to --section keys",
+            ));
+        }
 
         // Load layout
-        let layout = LayoutService::load(&self.layout)
-            .map_err(|e| CliError::io(format!("Failed to load layout: {e}")))?;
+        let layout = LayoutService::load(&self.layout)?;
 
         // Output based on section
         match self.section.as_str() {
             "metadata" => {
                 let section = MetadataSection {
                     name: layout.metadata.name.clone(),
+                    description: layout.metadata.description.clone(),
                     author: layout.metadata.author.clone(),
                     keyboard: layout.metadata.keyboard.clone(),
                     layout_variant: layout.metadata.layout_variant.clone(),
@@ -131,6 +173,9 @@ impl InspectArgs {
                     if !section.author.is_empty() {
                         println!("  Author:         {}", section.author);
                     }
+                    if !section.description.is_empty() {
+                        println!("  Description:    {}", indent(&section.description, 18));
+                    }
                     if let Some(keyboard) = section.keyboard {
                         println!("  Keyboard:       {}", keyboard);
                     }
@@ -156,6 +201,9 @@ impl InspectArgs {
                         number: idx,
                         name: layer.name.clone(),
                         key_count: layer.keys.len(),
+                        enabled: layer.enabled,
+                        modified: layer.modified.map(|m| m.to_rfc3339()),
+                        description: layer.description.clone(),
                     })
                     .collect();
 
@@ -174,11 +222,41 @@ impl InspectArgs {
                     println!("Layers ({} total):", section.count);
                     for layer in &section.layers {
                         println!(
-                            "  [{}] {} ({} keys)",
-                            layer.number, layer.name, layer.key_count
+                            "  [{}] {} ({} keys){}",
+                            layer.number,
+                            layer.name,
+                            layer.key_count,
+                            if layer.enabled { "" } else { " [disabled]" }
                         );
+                        if let Some(description) = &layer.description {
+                            println!("      {}", indent(description, 6));
+                        }
+                        if let Some(modified) = &layer.modified {
+                            println!("      Last modified: {modified}");
+                        }
+                    }
+                }
+            }
+            "keys" => {
+                if let Some(layer) = self.layer {
+                    if layer >= layout.layers.len() {
+                        return Err(CliError::validation(format!(
+                            "Layer {layer} does not exist (layout has {} layers)",
+                            layout.layers.len()
+                        )));
                     }
                 }
+
+                let keys = self.matching_keys(&layout);
+                if self.json {
+                    println!(
+                        "{}",
+                        serde_json::to_string_pretty(&keys)
+                            .map_err(|e| CliError::io(format!("Failed to serialize JSON: {e}")))?
+                    );
+                } else {
+                    print_keys_table(&keys);
+                }
             }
             "categories" => {
                 let categories: Vec<CategoryInfo> = layout
@@ -290,4 +368,90 @@ impl InspectArgs {
 
         Ok(())
     }
+
+    /// Keys passing the --layer, --category and --keycode filters, in layer
+    /// then row/column order.
+    fn matching_keys(&self, layout: &Layout) -> Vec<KeyInfo> {
+        let mut keys = Vec::new();
+        for (index, layer) in layout.layers.iter().enumerate() {
+            if self.layer.is_some_and(|wanted| wanted != index) {
+                continue;
+            }
+            let mut layer_keys: Vec<_> = layer
+                .keys
+                .iter()
+                .filter(|key| {
+                    self.category
+                        .as_ref()
+                        .is_none_or(|wanted| key.category_id.as_ref() == Some(wanted))
+                })
+                .filter(|key| {
+                    self.keycode
+                        .as_deref()
+                        .is_none_or(|pattern| wildcard_match(pattern, &key.keycode))
+                })
+                .collect();
+            layer_keys.sort_by_key(|key| (key.position.row, key.position.col));
+
+            keys.extend(layer_keys.into_iter().map(|key| KeyInfo {
+                layer: index,
+                layer_name: layer.name.clone(),
+                row: key.position.row,
+                col: key.position.col,
+                keycode: key.keycode.clone(),
+                color: layout.resolve_key_color(index, key).to_hex(),
+                category: key.category_id.clone(),
+                note: key.description.clone(),
+            }));
+        }
+        keys
+    }
+}
+
+/// Prints keys as an aligned table.
+fn print_keys_table(keys: &[KeyInfo]) {
+    println!("Keys ({} matching):", keys.len());
+    if keys.is_empty() {
+        return;
+    }
+
+    let keycode_width = keys
+        .iter()
+        .map(|k| k.keycode.len())
+        .max()
+        .unwrap_or(0)
+        .max(7);
+    let category_width = keys
+        .iter()
+        .map(|k| k.category.as_deref().map_or(1, str::len))
+        .max()
+        .unwrap_or(0)
+        .max(8);
+    println!(
+        "  {:<5}  {:<7}  {:<keycode_width$}  {:<7}  {:<category_width$}  NOTE",
+        "LAYER", "POS", "KEYCODE", "COLOR", "CATEGORY"
+    );
+    for key in keys {
+        let position = format!("{},{}", key.row, key.col);
+        let note = key
+            .note
+            .as_deref()
+            .and_then(|note| note.lines().next())
+            .unwrap_or_default();
+        println!(
+            "  {:<5}  {:<7}  {:<keycode_width$}  {:<7}  {:<category_width$}  {note}",
+            key.layer,
+            position,
+            key.keycode,
+            key.color,
+            key.category.as_deref().unwrap_or("-"),
+        );
+    }
+}
+
+/// Indents continuation lines of multi-line text so they line up under the first
+fn indent(text: &str, width: usize) -> String {
+    text.lines()
+        .collect::<Vec<_>>()
+        .join(&format!("\n{}", " ".repeat(width)))
 }
@@ -36,8 +36,7 @@ impl KeycodeArgs {
     /// Execute the keycode resolve command
     pub fn execute(&self) -> CliResult<()> {
         // Load layout for layer context
-        let layout = LayoutService::load(&self.layout)
-            .map_err(|e| CliError::io(format!("Failed to load layout: {e}")))?;
+        let layout = LayoutService::load(&self.layout)?;
 
         // Load keycode database
         let keycode_db = KeycodeDb::load()
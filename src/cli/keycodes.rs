@@ -30,8 +30,18 @@ struct KeycodeOutput {
     description: Option<String>,
 }
 
+#[derive(Debug, Serialize)]
+struct DatabaseInfo {
+    /// Curated database schema version
+    version: String,
+    /// Version of the QMK keycode data the database was built from
+    qmk_version: String,
+}
+
 #[derive(Debug, Serialize)]
 struct KeycodesJsonResponse {
+    /// Keycode database version metadata
+    database: DatabaseInfo,
     /// List of keycodes
     keycodes: Vec<KeycodeOutput>,
     /// Total count of keycodes in response
@@ -58,7 +68,7 @@ impl KeycodesArgs {
         };
 
         if self.json {
-            self.output_json(&keycodes)?;
+            self.output_json(&db, &keycodes)?;
         } else {
             self.output_table(&keycodes);
         }
@@ -67,7 +77,11 @@ impl KeycodesArgs {
     }
 
     /// Output keycodes as JSON
-    fn output_json(&self, keycodes: &[&crate::keycode_db::KeycodeDefinition]) -> CliResult<()> {
+    fn output_json(
+        &self,
+        db: &KeycodeDb,
+        keycodes: &[&crate::keycode_db::KeycodeDefinition],
+    ) -> CliResult<()> {
         let output_keycodes = keycodes
             .iter()
             .map(|kc| KeycodeOutput {
@@ -79,6 +93,10 @@ impl KeycodesArgs {
             .collect::<Vec<_>>();
 
         let response = KeycodesJsonResponse {
+            database: DatabaseInfo {
+                version: db.version().to_string(),
+                qmk_version: db.qmk_version().to_string(),
+            },
             count: output_keycodes.len(),
             keycodes: output_keycodes,
         };
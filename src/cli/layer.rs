@@ -0,0 +1,400 @@
+//! Layer commands for layouts.
+//!
+//! `layer set` pastes a grid of keycodes onto a layer, using the same parser
+//! as bracketed paste in the editor. `layer mirror` copies one half of a
+//! layer onto the other half of a split keyboard, like `Alt+Shift+M`.
+//! `layer duplicate` appends a copy of a layer, like `d` in the layer
+//! manager, and `layer move` moves one, rewriting numeric layer references.
+
+use crate::cli::common::{is_stdio, load_config, read_layout, write_layout, CliError, CliResult};
+use crate::keycode_db::KeycodeDb;
+use crate::models::Position;
+use crate::services::geometry;
+use crate::services::keycode_grid::{CellStatus, GridPlan, KeycodeGrid};
+use crate::services::layer_refs;
+use crate::services::mirror::{MirrorDirection, MirrorPlan};
+use clap::{Args, Subcommand};
+use std::io::{IsTerminal, Read};
+use std::path::PathBuf;
+
+/// Edit the keys of a layer
+#[derive(Debug, Clone, Args)]
+pub struct LayerArgs {
+    /// Layer subcommand
+    #[command(subcommand)]
+    pub command: LayerCommand,
+}
+
+/// Layer subcommands
+#[derive(Debug, Clone, Subcommand)]
+pub enum LayerCommand {
+    /// Assign a grid of keycodes to a layer
+    Set(SetLayerArgs),
+    /// Copy one half of a layer onto the other half of a split keyboard
+    Mirror(MirrorLayerArgs),
+    /// Append a copy of a layer
+    Duplicate(DuplicateLayerArgs),
+    /// Move a layer to another index, updating references to the layers
+    Move(MoveLayerArgs),
+}
+
+/// Assign a grid of keycodes to a layer
+#[derive(Debug, Clone, Args)]
+pub struct SetLayerArgs {
+    /// Path to layout markdown file (`-` for stdin/stdout)
+    #[arg(short, long, value_name = "FILE")]
+    pub layout: PathBuf,
+
+    /// Layer index
+    #[arg(long, value_name = "N")]
+    pub layer: usize,
+
+    /// Grid file: whitespace/comma-separated rows or a Markdown table (`-` for stdin)
+    #[arg(long, value_name = "FILE")]
+    pub grid: PathBuf,
+
+    /// Key the grid's first cell lands on, as row,col
+    #[arg(long, value_name = "ROW,COL", default_value = "0,0")]
+    pub position: Position,
+
+    /// Apply the valid cells even if others are invalid or have no key
+    #[arg(long)]
+    pub skip_invalid: bool,
+
+    /// Show where each cell would land without writing the layout
+    #[arg(long)]
+    pub dry_run: bool,
+}
+
+/// Copy one half of a layer onto the other half of a split keyboard
+#[derive(Debug, Clone, Args)]
+pub struct MirrorLayerArgs {
+    /// Path to layout markdown file (`-` for stdin/stdout)
+    #[arg(short, long, value_name = "FILE")]
+    pub layout: PathBuf,
+
+    /// Path to QMK firmware repository (defaults to the configured path)
+    #[arg(long, value_name = "PATH")]
+    pub qmk_path: Option<PathBuf>,
+
+    /// Layer index
+    #[arg(long, value_name = "N")]
+    pub layer: usize,
+
+    /// Half to copy from: left-to-right or right-to-left
+    #[arg(long, value_name = "DIRECTION")]
+    pub direction: MirrorDirection,
+
+    /// Copy modifiers as they are instead of swapping left and right variants
+    #[arg(long)]
+    pub keep_sides: bool,
+
+    /// Show which keys would be copied without writing the layout
+    #[arg(long)]
+    pub dry_run: bool,
+}
+
+/// Append a copy of a layer
+#[derive(Debug, Clone, Args)]
+pub struct DuplicateLayerArgs {
+    /// Path to layout markdown file (`-` for stdin/stdout)
+    #[arg(short, long, value_name = "FILE")]
+    pub layout: PathBuf,
+
+    /// Index of the layer to copy
+    #[arg(long, value_name = "N")]
+    pub from: usize,
+
+    /// Name of the copy (defaults to "<name> (copy)")
+    #[arg(long, value_name = "NAME")]
+    pub name: Option<String>,
+}
+
+/// Move a layer to another index, updating references to the layers
+#[derive(Debug, Clone, Args)]
+pub struct MoveLayerArgs {
+    /// Path to layout markdown file (`-` for stdin/stdout)
+    #[arg(short, long, value_name = "FILE")]
+    pub layout: PathBuf,
+
+    /// Index of the layer to move
+    #[arg(long, value_name = "N")]
+    pub from: usize,
+
+    /// Index to move it to; the layers in between shift over
+    #[arg(long, value_name = "N")]
+    pub to: usize,
+}
+
+impl LayerArgs {
+    /// Execute the layer command
+    pub fn execute(&self) -> CliResult<()> {
+        match &self.command {
+            LayerCommand::Set(args) => args.execute(),
+            LayerCommand::Mirror(args) => args.execute(),
+            LayerCommand::Duplicate(args) => args.execute(),
+            LayerCommand::Move(args) => args.execute(),
+        }
+    }
+}
+
+impl SetLayerArgs {
+    /// Execute the set command
+    pub fn execute(&self) -> CliResult<()> {
+        if is_stdio(&self.layout) && is_stdio(&self.grid) {
+            return Err(CliError::validation(
+                "The layout and the grid cannot both be read from stdin",
+            ));
+        }
+        let text = self.read_grid()?;
+        let grid = KeycodeGrid::parse(&text)
+            .map_err(|e| CliError::validation(format!("Invalid keycode grid: {e}")))?;
+
+        let mut layout = read_layout(&self.layout)?;
+        let layer_count = layout.layers.len();
+        let layer = layout.layers.get_mut(self.layer).ok_or_else(|| {
+            CliError::validation(format!(
+                "Layer {} does not exist (layout has {layer_count} layers)",
+                self.layer
+            ))
+        })?;
+
+        let keycode_db = KeycodeDb::load()
+            .map_err(|e| CliError::io(format!("Failed to load keycode database: {e}")))?;
+        // Without a keyboard geometry the layer's own keys are the visual grid
+        let positions: Vec<Position> = layer.keys.iter().map(|key| key.position).collect();
+        let plan = GridPlan::new(&grid, layer, &positions, self.position, |keycode| {
+            keycode_db.is_valid(keycode)
+        });
+
+        let problems: Vec<String> = plan
+            .cells
+            .iter()
+            .filter_map(|cell| {
+                let reason = match cell.status {
+                    CellStatus::Ready => return None,
+                    CellStatus::InvalidKeycode => "invalid keycode",
+                    CellStatus::NoKey => "no key at this position",
+                };
+                Some(format!(
+                    "row {}, col {}: {} ({reason})",
+                    cell.row + 1,
+                    cell.col + 1,
+                    cell.keycode
+                ))
+            })
+            .collect();
+        if !problems.is_empty() && !self.skip_invalid {
+            return Err(CliError::validation(format!(
+                "{} grid cell(s) cannot be applied (use --skip-invalid to apply the rest):\n  {}",
+                problems.len(),
+                problems.join("\n  ")
+            )));
+        }
+        // Status goes to stderr when the layout itself is written to stdout
+        let report = |line: String| {
+            if is_stdio(&self.layout) {
+                eprintln!("{line}");
+            } else {
+                println!("{line}");
+            }
+        };
+        for problem in &problems {
+            report(format!("Skipped {problem}"));
+        }
+
+        if self.dry_run {
+            for cell in plan.ready() {
+                if let Some(pos) = cell.target {
+                    report(format!(
+                        "  ({}, {})  {} -> {}",
+                        pos.row,
+                        pos.col,
+                        cell.replaces.as_deref().unwrap_or("-"),
+                        cell.keycode
+                    ));
+                }
+            }
+            report(format!(
+                "Would set {} key(s) on layer {}",
+                plan.count(CellStatus::Ready),
+                self.layer
+            ));
+            return Ok(());
+        }
+
+        let applied = plan.apply(layer);
+        write_layout(&layout, &self.layout)?;
+        report(format!("Set {applied} key(s) on layer {}", self.layer));
+        Ok(())
+    }
+
+    /// Reads the grid file, or stdin when `--grid -` is given.
+    fn read_grid(&self) -> CliResult<String> {
+        if !is_stdio(&self.grid) {
+            return std::fs::read_to_string(&self.grid).map_err(|e| {
+                CliError::io(format!(
+                    "Failed to read grid file {}: {e}",
+                    self.grid.display()
+                ))
+            });
+        }
+
+        let mut stdin = std::io::stdin();
+        if stdin.is_terminal() {
+            return Err(CliError::validation(
+                "Refusing to read the grid from an interactive terminal. Pipe a grid into stdin or pass a file path",
+            ));
+        }
+        let mut text = String::new();
+        stdin
+            .read_to_string(&mut text)
+            .map_err(|e| CliError::io(format!("Failed to read grid from stdin: {e}")))?;
+        Ok(text)
+    }
+}
+
+impl MirrorLayerArgs {
+    /// Execute the mirror command
+    pub fn execute(&self) -> CliResult<()> {
+        let mut layout = read_layout(&self.layout)?;
+        let mut config = load_config(&self.layout)?;
+        if let Some(qmk_path) = &self.qmk_path {
+            config.paths.qmk_firmware = Some(qmk_path.clone());
+        }
+        if config.paths.qmk_firmware.is_none() {
+            return Err(CliError::validation(
+                "QMK firmware path not configured. Use --qmk-path",
+            ));
+        }
+        let layout_variant = layout
+            .metadata
+            .layout_variant
+            .clone()
+            .ok_or_else(|| CliError::validation("Layout variant not set in layout metadata"))?;
+        let geo_context = geometry::GeometryContext {
+            config: &config,
+            metadata: &layout.metadata,
+        };
+        let geo_result = geometry::build_geometry_for_layout(geo_context, &layout_variant)
+            .map_err(|e| CliError::io(format!("Failed to build geometry: {e}")))?;
+
+        let layer_count = layout.layers.len();
+        let layer = layout.layers.get_mut(self.layer).ok_or_else(|| {
+            CliError::validation(format!(
+                "Layer {} does not exist (layout has {layer_count} layers)",
+                self.layer
+            ))
+        })?;
+        let plan = MirrorPlan::for_layer(
+            &geo_result.geometry,
+            &geo_result.mapping,
+            self.layer,
+            self.direction,
+            !self.keep_sides,
+        );
+        if plan.pairs.is_empty() {
+            return Err(CliError::validation(format!(
+                "Nothing to mirror: no key on the {} half has a symmetric counterpart",
+                self.direction.source_side()
+            )));
+        }
+
+        // Status goes to stderr when the layout itself is written to stdout
+        let report = |line: String| {
+            if is_stdio(&self.layout) {
+                eprintln!("{line}");
+            } else {
+                println!("{line}");
+            }
+        };
+        let skipped = if plan.skipped > 0 {
+            format!(", skipped {} without a counterpart", plan.skipped)
+        } else {
+            String::new()
+        };
+
+        if self.dry_run {
+            for &(from, to) in &plan.pairs {
+                report(format!(
+                    "  ({}, {}) -> ({}, {})",
+                    from.row, from.col, to.row, to.col
+                ));
+            }
+            report(format!(
+                "Would mirror {} key(s) on layer {} {}{skipped}",
+                plan.pairs.len(),
+                self.layer,
+                self.direction.name()
+            ));
+            return Ok(());
+        }
+
+        let applied = plan.apply(layer);
+        write_layout(&layout, &self.layout)?;
+        report(format!(
+            "Mirrored {applied} key(s) on layer {} {}{skipped}",
+            self.layer,
+            self.direction.name()
+        ));
+        Ok(())
+    }
+}
+
+impl DuplicateLayerArgs {
+    /// Execute the duplicate command
+    pub fn execute(&self) -> CliResult<()> {
+        let mut layout = read_layout(&self.layout)?;
+        let source = layout.layers.get(self.from).ok_or_else(|| {
+            CliError::validation(format!(
+                "Layer {} does not exist (layout has {} layers)",
+                self.from,
+                layout.layers.len()
+            ))
+        })?;
+        let name = self
+            .name
+            .clone()
+            .unwrap_or_else(|| format!("{} (copy)", source.name));
+
+        let index = layout
+            .duplicate_layer(self.from, name)
+            .map_err(|e| CliError::validation(format!("Cannot duplicate layer: {e}")))?;
+        write_layout(&layout, &self.layout)?;
+
+        let line = format!(
+            "Duplicated layer {} as layer {index} '{}'",
+            self.from, layout.layers[index].name
+        );
+        // Status goes to stderr when the layout itself is written to stdout
+        if is_stdio(&self.layout) {
+            eprintln!("{line}");
+        } else {
+            println!("{line}");
+        }
+        Ok(())
+    }
+}
+
+impl MoveLayerArgs {
+    /// Execute the move command
+    pub fn execute(&self) -> CliResult<()> {
+        let mut layout = read_layout(&self.layout)?;
+        let updated = layer_refs::move_layer(&mut layout, self.from, self.to)
+            .map_err(|e| CliError::validation(format!("Cannot move layer: {e}")))?;
+        layout.metadata.touch();
+        write_layout(&layout, &self.layout)?;
+
+        let line = format!(
+            "Moved layer '{}' from {} to {}, updated {updated} reference(s)",
+            layout.layers[self.to].name, self.from, self.to
+        );
+        // Status goes to stderr when the layout itself is written to stdout
+        if is_stdio(&self.layout) {
+            eprintln!("{line}");
+        } else {
+            println!("{line}");
+        }
+        Ok(())
+    }
+}
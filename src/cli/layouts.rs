@@ -0,0 +1,205 @@
+//! Layouts command: list the layout files in a directory.
+
+use crate::cli::common::{CliError, CliResult};
+use crate::paths;
+use crate::services::layouts::{scan_layout_dir, ScannedLayout};
+use chrono::{DateTime, Local, Utc};
+use clap::{Args, Subcommand};
+use serde::Serialize;
+use std::path::{Path, PathBuf};
+
+/// Manage the layout files in a directory
+#[derive(Debug, Clone, Args)]
+pub struct LayoutsArgs {
+    /// Layouts subcommand
+    #[command(subcommand)]
+    pub command: LayoutsCommand,
+}
+
+/// Layouts subcommands
+#[derive(Debug, Clone, Subcommand)]
+pub enum LayoutsCommand {
+    /// List layout files with their keyboard, layer count and parse status
+    List(ListArgs),
+}
+
+/// List layout files in a directory
+#[derive(Debug, Clone, Args)]
+pub struct ListArgs {
+    /// Directory to scan (default: the LazyQMK layouts directory)
+    #[arg(short, long, value_name = "DIR")]
+    pub dir: Option<PathBuf>,
+
+    /// Only list files that fail to parse
+    #[arg(long)]
+    pub problems: bool,
+
+    /// Output results as JSON
+    #[arg(long)]
+    pub json: bool,
+}
+
+/// One layout file in `layouts list --json` output.
+#[derive(Debug, Serialize)]
+struct LayoutListEntry {
+    /// Layout file
+    path: PathBuf,
+    /// Layout name, if the file parsed
+    name: Option<String>,
+    /// QMK keyboard, if the file parsed and names one
+    keyboard: Option<String>,
+    /// Layout variant, if the file parsed and names one
+    variant: Option<String>,
+    /// Number of layers, if the file parsed
+    layers: Option<usize>,
+    /// File modification time
+    modified: Option<DateTime<Utc>>,
+    /// "ok" or "error"
+    status: &'static str,
+    /// Parse error message
+    error: Option<String>,
+}
+
+impl LayoutListEntry {
+    fn from_scanned(scanned: ScannedLayout) -> Self {
+        let modified = scanned.modified.map(DateTime::<Utc>::from);
+        match scanned.layout {
+            Ok(layout) => Self {
+                path: scanned.path,
+                name: Some(layout.metadata.name),
+                keyboard: layout.metadata.keyboard,
+                variant: layout.metadata.layout_variant,
+                layers: Some(layout.layers.len()),
+                modified,
+                status: "ok",
+                error: None,
+            },
+            Err(error) => Self {
+                path: scanned.path,
+                name: None,
+                keyboard: None,
+                variant: None,
+                layers: None,
+                modified,
+                status: "error",
+                error: Some(error),
+            },
+        }
+    }
+}
+
+/// JSON output of `layouts list --json`.
+#[derive(Debug, Serialize)]
+struct LayoutListResponse<'a> {
+    /// Scanned directory
+    directory: &'a Path,
+    /// Layout files, most recently modified first
+    layouts: Vec<LayoutListEntry>,
+    /// Number of listed files
+    count: usize,
+    /// Number of listed files that failed to parse
+    problems: usize,
+}
+
+impl LayoutsArgs {
+    /// Execute the layouts command
+    pub fn execute(&self) -> CliResult<()> {
+        match &self.command {
+            LayoutsCommand::List(args) => args.execute(),
+        }
+    }
+
+    /// Whether the subcommand was asked for JSON output
+    #[must_use]
+    pub const fn json_output(&self) -> bool {
+        match &self.command {
+            LayoutsCommand::List(args) => args.json,
+        }
+    }
+}
+
+impl ListArgs {
+    /// Execute the list command
+    pub fn execute(&self) -> CliResult<()> {
+        let dir = match &self.dir {
+            Some(dir) => dir.clone(),
+            None => paths::layouts_dir()
+                .map_err(|e| CliError::io(format!("Failed to locate layouts directory: {e}")))?,
+        };
+        if !dir.is_dir() {
+            return Err(CliError::io(format!(
+                "Layouts directory not found: {}",
+                dir.display()
+            )));
+        }
+
+        let layouts: Vec<LayoutListEntry> = scan_layout_dir(&dir)
+            .map_err(|e| CliError::io(format!("{e:#}")))?
+            .into_iter()
+            .map(LayoutListEntry::from_scanned)
+            .filter(|entry| !self.problems || entry.error.is_some())
+            .collect();
+        let problems = layouts.iter().filter(|entry| entry.error.is_some()).count();
+
+        if self.json {
+            let response = LayoutListResponse {
+                directory: &dir,
+                count: layouts.len(),
+                layouts,
+                problems,
+            };
+            println!(
+                "{}",
+                serde_json::to_string_pretty(&response)
+                    .map_err(|e| CliError::io(format!("Failed to serialize JSON: {e}")))?
+            );
+            return Ok(());
+        }
+
+        if layouts.is_empty() {
+            if self.problems {
+                println!("No unparseable layouts in {}", dir.display());
+            } else {
+                println!("No layouts found in {}", dir.display());
+            }
+            return Ok(());
+        }
+
+        println!("Layouts in {} ({}):\n", dir.display(), layouts.len());
+        for entry in &layouts {
+            let file = entry
+                .path
+                .file_name()
+                .map_or_else(String::new, |n| n.to_string_lossy().into_owned());
+            let modified = entry.modified.map_or_else(
+                || "unknown".to_string(),
+                |time| {
+                    time.with_timezone(&Local)
+                        .format("%Y-%m-%d %H:%M")
+                        .to_string()
+                },
+            );
+            match &entry.error {
+                None => {
+                    let keyboard = entry.keyboard.as_deref().unwrap_or("no keyboard");
+                    let variant = entry
+                        .variant
+                        .as_deref()
+                        .map_or_else(String::new, |variant| format!(" / {variant}"));
+                    println!(
+                        "  ✓ {} ({file})\n    {keyboard}{variant}, {} layer(s), modified {modified}",
+                        entry.name.as_deref().unwrap_or_default(),
+                        entry.layers.unwrap_or_default()
+                    );
+                }
+                Some(error) => {
+                    println!("  ✗ {file}\n    modified {modified}: {error}");
+                }
+            }
+        }
+        if problems > 0 {
+            println!("\n{problems} file(s) failed to parse");
+        }
+        Ok(())
+    }
+}
@@ -0,0 +1,143 @@
+//! Library commands: share tap dances between layouts through a TOML file.
+
+use crate::cli::common::{CliError, CliResult};
+use crate::services::library::Library;
+use crate::services::LayoutService;
+use clap::{Args, Subcommand};
+use std::path::PathBuf;
+
+/// Export and import shared tap dance libraries
+#[derive(Debug, Clone, Args)]
+pub struct LibraryArgs {
+    /// Library subcommand
+    #[command(subcommand)]
+    pub command: LibraryCommand,
+}
+
+/// Library subcommands
+#[derive(Debug, Clone, Subcommand)]
+pub enum LibraryCommand {
+    /// Write a layout's tap dances to a library file
+    Export(ExportArgs),
+    /// Merge tap dances from a library file into a layout
+    Import(ImportArgs),
+}
+
+/// Write a layout's tap dances to a library file
+#[derive(Debug, Clone, Args)]
+pub struct ExportArgs {
+    /// Path to layout file
+    #[arg(short, long, value_name = "FILE")]
+    pub layout: PathBuf,
+
+    /// Library file to write
+    #[arg(short, long, value_name = "FILE")]
+    pub output: PathBuf,
+
+    /// Overwrite OUTPUT if it already exists
+    #[arg(long)]
+    pub force: bool,
+}
+
+/// Merge tap dances from a library file into a layout
+#[derive(Debug, Clone, Args)]
+pub struct ImportArgs {
+    /// Path to layout file
+    #[arg(short, long, value_name = "FILE")]
+    pub layout: PathBuf,
+
+    /// Library file to read
+    #[arg(long, value_name = "FILE")]
+    pub from: PathBuf,
+
+    /// Only import these definitions (comma-separated names)
+    #[arg(long, value_name = "NAMES", value_delimiter = ',')]
+    pub only: Option<Vec<String>>,
+
+    /// Import the non-conflicting definitions even if others conflict
+    #[arg(long)]
+    pub skip_conflicts: bool,
+}
+
+impl LibraryArgs {
+    /// Execute the library command
+    pub fn execute(&self) -> CliResult<()> {
+        match &self.command {
+            LibraryCommand::Export(args) => args.execute(),
+            LibraryCommand::Import(args) => args.execute(),
+        }
+    }
+}
+
+impl ExportArgs {
+    /// Execute the export command
+    pub fn execute(&self) -> CliResult<()> {
+        if self.output.exists() && !self.force {
+            return Err(CliError::validation(format!(
+                "{} already exists. Use --force to overwrite",
+                self.output.display()
+            )));
+        }
+
+        let layout = LayoutService::load(&self.layout)?;
+        let library = Library::from_layout(&layout);
+        library
+            .save(&self.output)
+            .map_err(|e| CliError::io(format!("{e:#}")))?;
+
+        println!(
+            "Exported {} tap dance(s) to {}",
+            library.tap_dances.len(),
+            self.output.display()
+        );
+        Ok(())
+    }
+}
+
+impl ImportArgs {
+    /// Execute the import command
+    pub fn execute(&self) -> CliResult<()> {
+        let library =
+            Library::load(&self.from).map_err(|e| CliError::validation(format!("{e:#}")))?;
+        let mut layout = LayoutService::load(&self.layout)?;
+        let only = self.only.as_deref();
+
+        let plan = library.plan_import(&layout, only);
+        if !plan.missing.is_empty() {
+            return Err(CliError::validation(format!(
+                "Not in library {}: {}",
+                self.from.display(),
+                plan.missing.join(", ")
+            )));
+        }
+        if plan.has_conflicts() && !self.skip_conflicts {
+            return Err(CliError::validation(format!(
+                "Layout already defines different tap dance(s) with the same name: {}. \
+                 Rename or delete them first, or use --skip-conflicts to import the rest",
+                plan.conflicts.join(", ")
+            )));
+        }
+
+        let report = library.import_into(&mut layout, only);
+        if !report.added.is_empty() {
+            LayoutService::save(&layout, &self.layout)
+                .map_err(|e| CliError::io(format!("Failed to save layout: {e}")))?;
+        }
+
+        println!(
+            "Imported {} tap dance(s) into {}",
+            report.added.len(),
+            self.layout.display()
+        );
+        for name in &report.added {
+            println!("  + {name}");
+        }
+        for name in &report.unchanged {
+            println!("  = {name} (already identical)");
+        }
+        for name in &report.conflicts {
+            println!("  ! {name} (conflict, kept layout's version)");
+        }
+        Ok(())
+    }
+}
@@ -0,0 +1,257 @@
+//! Lint command for layout style rules.
+
+use crate::cli::common::{
+    is_stdio, load_config, read_layout, write_layout, CliError, CliResult, ValidationLocation,
+    ValidationPosition,
+};
+use crate::models::Layout;
+use crate::services::lint::{LintReport, LintSeverity, Linter};
+use clap::Args;
+use serde::Serialize;
+use std::collections::BTreeMap;
+use std::path::{Path, PathBuf};
+
+/// Check a layout against style rules (beyond hard validation)
+#[derive(Debug, Clone, Args)]
+#[allow(clippy::struct_excessive_bools)] // Independent command-line flags
+pub struct LintArgs {
+    /// Path to layout markdown file (use - for stdin)
+    #[arg(
+        short,
+        long,
+        value_name = "FILE",
+        required_unless_present = "list_rules"
+    )]
+    pub layout: Option<PathBuf>,
+
+    /// Output results as JSON
+    #[arg(long)]
+    pub json: bool,
+
+    /// Treat warnings as errors (exit non-zero)
+    #[arg(long)]
+    pub strict: bool,
+
+    /// List available rules and exit
+    #[arg(long)]
+    pub list_rules: bool,
+
+    /// Apply automatic fixes (e.g., preferred shifted forms), save, and lint again
+    #[arg(long)]
+    pub fix: bool,
+}
+
+/// JSON response for the lint command (mirrors `validate --json`).
+#[derive(Debug, Serialize)]
+struct LintResponse {
+    /// True if there are no error findings (and no warnings with --strict)
+    valid: bool,
+    /// Findings in rule order
+    errors: Vec<LintMessage>,
+    /// Result per rule ID: "passed", "info", "warning", "failed", or "disabled"
+    checks: BTreeMap<&'static str, &'static str>,
+}
+
+/// A lint finding in JSON output.
+#[derive(Debug, Serialize)]
+struct LintMessage {
+    /// Severity: "error", "warning", or "info"
+    severity: LintSeverity,
+    /// Rule ID (e.g., "L003")
+    rule: &'static str,
+    /// Human-readable message
+    message: String,
+    /// Layer index, if the finding is about a layer or key
+    #[serde(skip_serializing_if = "Option::is_none")]
+    layer: Option<usize>,
+    /// Key location, if the finding is about a key
+    #[serde(skip_serializing_if = "Option::is_none")]
+    location: Option<ValidationLocation>,
+}
+
+/// Rule description for `--list-rules --json`.
+#[derive(Debug, Serialize)]
+struct RuleInfo {
+    id: &'static str,
+    name: &'static str,
+    severity: LintSeverity,
+    description: &'static str,
+}
+
+impl LintArgs {
+    /// Execute the lint command
+    pub fn execute(&self) -> CliResult<()> {
+        if self.list_rules {
+            return self.print_rules(&Linter::default());
+        }
+
+        let path = self
+            .layout
+            .as_ref()
+            .ok_or_else(|| CliError::validation("--layout is required"))?;
+        if self.fix && is_stdio(path) {
+            return Err(CliError::validation(
+                "--fix needs a layout file to save to, not stdin",
+            ));
+        }
+        let mut layout = read_layout(path)?;
+        let config = load_config(path)?;
+        let linter = Linter::default().with_disabled(&config.lint.disable);
+        if self.fix {
+            self.apply_fixes(&linter, &mut layout, path)?;
+        }
+        let report = linter.lint(&layout);
+        let failed = report.has_errors() || (self.strict && report.has_warnings());
+
+        if self.json {
+            let response = LintResponse {
+                valid: !failed,
+                errors: report
+                    .findings
+                    .iter()
+                    .map(|f| LintMessage {
+                        severity: f.severity,
+                        rule: f.rule,
+                        message: f.message.clone(),
+                        layer: f.layer,
+                        location: f
+                            .layer
+                            .zip(f.position)
+                            .map(|(layer, pos)| ValidationLocation {
+                                layer,
+                                position: ValidationPosition {
+                                    row: pos.row,
+                                    col: pos.col,
+                                },
+                            }),
+                    })
+                    .collect(),
+                checks: checks(&linter, &report),
+            };
+            println!(
+                "{}",
+                serde_json::to_string_pretty(&response)
+                    .map_err(|e| CliError::io(format!("Failed to serialize JSON: {e}")))?
+            );
+        } else {
+            print_report(&linter, &report);
+        }
+
+        if report.has_errors() {
+            return Err(CliError::validation("Lint errors found"));
+        }
+        if self.strict && report.has_warnings() {
+            return Err(CliError::validation("Lint warnings found in strict mode"));
+        }
+
+        Ok(())
+    }
+
+    /// Applies every rule's automatic fix, listing the changes, and saves the
+    /// layout if anything changed.
+    ///
+    /// With --json the list goes to stderr so stdout stays parseable.
+    fn apply_fixes(&self, linter: &Linter, layout: &mut Layout, path: &Path) -> CliResult<()> {
+        let print = |line: String| {
+            if self.json {
+                eprintln!("{line}");
+            } else {
+                println!("{line}");
+            }
+        };
+
+        let mut total = 0;
+        for (rule, changes) in linter.fix(layout) {
+            print(format!("{} ({rule}):", path.display()));
+            for change in &changes {
+                print(format!("  {change}"));
+            }
+            total += changes.len();
+        }
+        if total > 0 {
+            write_layout(layout, path)?;
+        }
+        print(format!("Applied {total} fix(es)\n"));
+        Ok(())
+    }
+
+    /// Prints the available rules.
+    fn print_rules(&self, linter: &Linter) -> CliResult<()> {
+        if self.json {
+            let rules: Vec<RuleInfo> = linter
+                .rules()
+                .iter()
+                .map(|r| RuleInfo {
+                    id: r.id(),
+                    name: r.name(),
+                    severity: r.severity(),
+                    description: r.description(),
+                })
+                .collect();
+            println!(
+                "{}",
+                serde_json::to_string_pretty(&rules)
+                    .map_err(|e| CliError::io(format!("Failed to serialize JSON: {e}")))?
+            );
+        } else {
+            for rule in linter.rules() {
+                println!(
+                    "{}  {:<28} {:<8} {}",
+                    rule.id(),
+                    rule.name(),
+                    rule.severity().to_string(),
+                    rule.description()
+                );
+            }
+        }
+        Ok(())
+    }
+}
+
+/// Summarizes each rule's outcome.
+fn checks(linter: &Linter, report: &LintReport) -> BTreeMap<&'static str, &'static str> {
+    linter
+        .rules()
+        .iter()
+        .map(|rule| {
+            let status = if report.disabled.contains(&rule.id()) {
+                "disabled"
+            } else {
+                match report.findings_for(rule.id()).map(|f| f.severity).max() {
+                    None => "passed",
+                    Some(LintSeverity::Info) => "info",
+                    Some(LintSeverity::Warning) => "warning",
+                    Some(LintSeverity::Error) => "failed",
+                }
+            };
+            (rule.id(), status)
+        })
+        .collect()
+}
+
+/// Prints a human-readable lint report.
+fn print_report(linter: &Linter, report: &LintReport) {
+    if report.findings.is_empty() {
+        println!("✓ No lint findings");
+    } else {
+        println!("Lint findings:");
+        for finding in &report.findings {
+            let prefix = match finding.severity {
+                LintSeverity::Error => "✗",
+                LintSeverity::Warning => "⚠",
+                LintSeverity::Info => "ℹ",
+            };
+            let location = match (finding.layer, finding.position) {
+                (Some(layer), Some(pos)) => format!(" [Layer {layer} ({}, {})]", pos.row, pos.col),
+                (Some(layer), None) => format!(" [Layer {layer}]"),
+                _ => String::new(),
+            };
+            println!("  {prefix} {}{location} {}", finding.rule, finding.message);
+        }
+    }
+
+    println!("\nRules:");
+    for (id, status) in checks(linter, report) {
+        println!("  {id}: {status}");
+    }
+}
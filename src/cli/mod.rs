@@ -3,33 +3,61 @@
 //! This module provides headless, scriptable access to LazyQMK's core functionality
 //! for automation, testing, and CI/CD integration.
 
+pub mod apply;
+pub mod build;
 pub mod category;
 pub mod common;
 pub mod config;
+pub mod convert;
+pub mod doctor;
 pub mod export;
+pub mod fix;
 pub mod generate;
+pub mod heatmap;
 pub mod help;
+pub mod history;
 pub mod inspect;
 pub mod keycode;
 pub mod keycodes;
+pub mod layer;
 pub mod layer_refs;
+pub mod layouts;
+pub mod library;
+pub mod lint;
+pub mod new;
 pub mod qmk;
+pub mod remap;
+pub mod rename_keycode;
 pub mod tap_dance;
 pub mod template;
 pub mod validate;
 
 // Re-export types used by main.rs and tests
+pub use apply::ApplyArgs;
+pub use build::BuildArgs;
 pub use category::CategoryArgs;
 pub use common::ExitCode;
 pub use config::ConfigArgs;
+pub use convert::ConvertArgs;
+pub use doctor::DoctorArgs;
 pub use export::ExportArgs;
+pub use fix::FixArgs;
 pub use generate::GenerateArgs;
+pub use heatmap::HeatmapArgs;
 pub use help::HelpArgs;
+pub use history::HistoryArgs;
 pub use inspect::InspectArgs;
 pub use keycode::KeycodeArgs;
 pub use keycodes::KeycodesArgs;
+pub use layer::LayerArgs;
 pub use layer_refs::LayerRefsArgs;
+pub use layouts::LayoutsArgs;
+pub use library::LibraryArgs;
+pub use lint::LintArgs;
+pub use new::NewArgs;
 pub use qmk::{GeometryArgs, ListKeyboardsArgs, ListLayoutsArgs};
+pub use remap::RemapArgs;
+pub use rename_keycode::RenameKeycodeArgs;
 pub use tap_dance::TapDanceArgs;
 pub use template::TemplateArgs;
 pub use validate::ValidateArgs;
@@ -0,0 +1,134 @@
+//! New command: create a blank layout for a keyboard.
+
+use crate::cli::common::{load_config, write_layout, CliError, CliResult};
+use crate::config::Config;
+use crate::parser::keyboard_json::{extract_layout_names, parse_keyboard_info_json};
+use crate::paths;
+use crate::services::new_layout::{self, NewLayoutOptions};
+use clap::Args;
+use std::path::{Path, PathBuf};
+
+/// Create a new layout for a keyboard, prefilled from its stored defaults
+#[derive(Debug, Clone, Args)]
+pub struct NewArgs {
+    /// QMK keyboard path (e.g., crkbd/rev1)
+    #[arg(short, long, value_name = "KEYBOARD")]
+    pub keyboard: String,
+
+    /// Layout variant (default: the one last used for this keyboard, or the
+    /// keyboard's only one)
+    #[arg(long, value_name = "NAME")]
+    pub variant: Option<String>,
+
+    /// Layout name (default: <keyboard>_layout)
+    #[arg(short, long, value_name = "NAME")]
+    pub name: Option<String>,
+
+    /// Keymap name (default: the keyboard's keymap pattern, or the layout name)
+    #[arg(long, value_name = "NAME")]
+    pub keymap: Option<String>,
+
+    /// Number of layers (default: the keyboard's layer count, or 1)
+    #[arg(long, value_name = "N")]
+    pub layers: Option<u8>,
+
+    /// Layout file to write (default: <name>.md in the layouts directory)
+    #[arg(short, long, value_name = "FILE")]
+    pub output: Option<PathBuf>,
+
+    /// Path to QMK firmware repository (default: the configured one)
+    #[arg(long, value_name = "PATH")]
+    pub qmk_path: Option<PathBuf>,
+}
+
+impl NewArgs {
+    /// Execute the new command.
+    pub fn execute(&self) -> CliResult<()> {
+        let mut config = load_config(Path::new("."))?;
+        if let Some(qmk_path) = &self.qmk_path {
+            config.paths.qmk_firmware = Some(qmk_path.clone());
+        }
+
+        let variant = self.variant(&config)?;
+        let name = self.name.clone().unwrap_or_else(|| {
+            let board = self
+                .keyboard
+                .split('/')
+                .next_back()
+                .unwrap_or(&self.keyboard);
+            format!("{board}_layout")
+        });
+
+        let created = new_layout::create(
+            &config,
+            NewLayoutOptions {
+                keyboard: &self.keyboard,
+                variant: &variant,
+                name: &name,
+                keymap: self.keymap.as_deref(),
+                layer_count: self.layers,
+            },
+        )
+        .map_err(|e| CliError::validation(format!("Failed to create layout: {e}")))?;
+
+        let path = match &self.output {
+            Some(path) => path.clone(),
+            None => {
+                let dir = paths::layouts_dir().map_err(|e| {
+                    CliError::io(format!("Failed to locate layouts directory: {e}"))
+                })?;
+                std::fs::create_dir_all(&dir).map_err(|e| {
+                    CliError::io(format!("Failed to create {}: {e}", dir.display()))
+                })?;
+                dir.join(format!("{}.md", new_layout::sanitize_name(&name)))
+            }
+        };
+        if path.exists() {
+            return Err(CliError::validation(format!(
+                "{} already exists",
+                path.display()
+            )));
+        }
+        write_layout(&created.layout, &path)?;
+
+        // Preselect this variant for the keyboard's next layout
+        config.remember_variant(&self.keyboard, &variant);
+        if let Err(e) = config.save() {
+            eprintln!("Warning: Failed to remember the layout variant: {e}");
+        }
+
+        let metadata = &created.layout.metadata;
+        println!("✓ Created {}", path.display());
+        println!("  Keyboard: {}", metadata.keyboard.as_deref().unwrap_or(""));
+        println!("  Variant: {variant}");
+        println!(
+            "  Keymap: {}",
+            metadata.keymap_name.as_deref().unwrap_or("")
+        );
+        println!("  Layers: {}", created.layout.layers.len());
+        Ok(())
+    }
+
+    /// The `--variant`, the keyboard's remembered one, or its only one.
+    fn variant(&self, config: &Config) -> CliResult<String> {
+        if let Some(variant) = &self.variant {
+            return Ok(variant.clone());
+        }
+        if let Some(variant) = config.keyboard_defaults(&self.keyboard).default_variant {
+            return Ok(variant);
+        }
+
+        let qmk_path = config.paths.qmk_firmware.as_deref().ok_or_else(|| {
+            CliError::validation("QMK firmware path not configured. Use --qmk-path")
+        })?;
+        let info = parse_keyboard_info_json(qmk_path, &self.keyboard)
+            .map_err(|e| CliError::validation(format!("Failed to read keyboard info: {e}")))?;
+        match extract_layout_names(&info).as_slice() {
+            [only] => Ok(only.clone()),
+            variants => Err(CliError::validation(format!(
+                "Layout variant not specified. Use --variant (available: {})",
+                variants.join(", ")
+            ))),
+        }
+    }
+}
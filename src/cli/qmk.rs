@@ -5,6 +5,7 @@
 //! and keyboard.json files directly.
 
 use crate::cli::common::{CliError, CliResult};
+use crate::models::VisualLayoutMapping;
 use crate::parser::keyboard_json::{
     build_keyboard_geometry_with_rgb, build_matrix_to_led_map, discover_keyboard_config,
     extract_layout_variants, parse_keyboard_info_json, parse_variant_keyboard_json,
@@ -290,6 +291,10 @@ pub struct GeometryArgs {
     #[arg(long, value_name = "NAME")]
     pub layout_name: String,
 
+    /// Show a table keyed by editor grid position (row, col)
+    #[arg(long)]
+    pub positions: bool,
+
     /// Output results as JSON
     #[arg(long)]
     pub json: bool,
@@ -330,6 +335,9 @@ struct KeyMapping {
     led_index: u8,
     /// Visual coordinates (x, y)
     visual_position: [f32; 2],
+    /// Editor grid position [row, col] (with `--positions`)
+    #[serde(skip_serializing_if = "Option::is_none")]
+    position: Option<[u8; 2]>,
 }
 
 impl GeometryArgs {
@@ -377,7 +385,10 @@ impl GeometryArgs {
         .map_err(|e| CliError::validation(format!("Invalid layout: {e}")))?;
 
         // Build response
-        let mappings: Vec<KeyMapping> = geometry
+        let visual_mapping = self
+            .positions
+            .then(|| VisualLayoutMapping::build(&geometry));
+        let mut mappings: Vec<KeyMapping> = geometry
             .keys
             .iter()
             .map(|key| KeyMapping {
@@ -385,8 +396,16 @@ impl GeometryArgs {
                 matrix: [key.matrix_position.0, key.matrix_position.1],
                 led_index: key.led_index,
                 visual_position: [key.visual_x, key.visual_y],
+                position: visual_mapping.as_ref().and_then(|mapping| {
+                    mapping
+                        .matrix_to_visual_pos(key.matrix_position.0, key.matrix_position.1)
+                        .map(|pos| [pos.row, pos.col])
+                }),
             })
             .collect();
+        if self.positions {
+            mappings.sort_by_key(|mapping| mapping.position);
+        }
 
         let response = GeometryResponse {
             keyboard: self.keyboard.clone(),
@@ -414,6 +433,10 @@ impl GeometryArgs {
                 response.matrix.rows, response.matrix.cols
             );
             println!("Keys: {}", response.key_count);
+            if self.positions {
+                print_position_table(&response.mappings);
+                return Ok(());
+            }
             println!("\nCoordinate Mappings:");
             println!("  Visual | Matrix    | LED | Position");
             println!("  -------|-----------|-----|----------");
@@ -433,3 +456,20 @@ impl GeometryArgs {
         Ok(())
     }
 }
+
+/// Prints the `--positions` table, one row per key in editor grid order.
+fn print_position_table(mappings: &[KeyMapping]) {
+    println!("\nKey Positions:");
+    println!("  Position | Matrix    | LED | Layout");
+    println!("  ---------|-----------|-----|-------");
+    for mapping in mappings {
+        let position = mapping.position.map_or_else(
+            || "-".to_string(),
+            |[row, col]| format!("({row:2}, {col:2})"),
+        );
+        println!(
+            "  {:8} | ({:2}, {:2}) | {:3} | {:6}",
+            position, mapping.matrix[0], mapping.matrix[1], mapping.led_index, mapping.visual_index
+        );
+    }
+}
@@ -0,0 +1,154 @@
+//! Remap command for migrating a layout to a different keyboard.
+
+use crate::cli::common::{is_stdio, load_config, read_layout, write_layout, CliError, CliResult};
+use crate::firmware::validator::FirmwareValidator;
+use crate::keycode_db::KeycodeDb;
+use crate::services::geometry;
+use crate::services::remap::{remap_layout, Board, PositionMap, RemapReport};
+use clap::Args;
+use std::fs;
+use std::path::PathBuf;
+
+/// Migrate a layout to another keyboard by matching physical key positions
+#[derive(Debug, Clone, Args)]
+pub struct RemapArgs {
+    /// Path to layout markdown file (`-` reads from stdin)
+    #[arg(short, long, value_name = "FILE")]
+    pub layout: PathBuf,
+
+    /// Path to QMK firmware repository
+    #[arg(long, value_name = "PATH")]
+    pub qmk_path: PathBuf,
+
+    /// Target keyboard (e.g., "lily58/rev1")
+    #[arg(long, value_name = "KEYBOARD")]
+    pub to_keyboard: String,
+
+    /// Target layout variant (e.g., "LAYOUT")
+    #[arg(long, value_name = "VARIANT")]
+    pub to_variant: String,
+
+    /// Where to write the remapped layout (`-` writes to stdout)
+    #[arg(short, long, value_name = "FILE")]
+    pub output: PathBuf,
+
+    /// TOML file with explicit `"row,col" = "row,col"` position pairs
+    #[arg(long, value_name = "FILE")]
+    pub map: Option<PathBuf>,
+
+    /// Print the report as JSON
+    #[arg(long)]
+    pub json: bool,
+}
+
+impl RemapArgs {
+    /// Execute the remap command
+    pub fn execute(&self) -> CliResult<()> {
+        let layout = read_layout(&self.layout)?;
+
+        let source_variant = layout.metadata.layout_variant.clone().ok_or_else(|| {
+            CliError::validation("Source layout has no layout variant in its metadata")
+        })?;
+
+        let explicit = match &self.map {
+            Some(path) => {
+                let content = fs::read_to_string(path)
+                    .map_err(|e| CliError::io(format!("Failed to read position map: {e}")))?;
+                PositionMap::parse(&content)
+                    .map_err(|e| CliError::validation(format!("Invalid position map: {e}")))?
+            }
+            None => PositionMap::default(),
+        };
+
+        let mut config = load_config(&self.layout)?;
+        config.paths.qmk_firmware = Some(self.qmk_path.clone());
+
+        let source = geometry::build_geometry_for_layout(
+            geometry::GeometryContext {
+                config: &config,
+                metadata: &layout.metadata,
+            },
+            &source_variant,
+        )
+        .map_err(|e| CliError::io(format!("Failed to build source geometry: {e}")))?;
+
+        let mut target_metadata = layout.metadata.clone();
+        target_metadata.keyboard = Some(self.to_keyboard.clone());
+        target_metadata.layout_variant = Some(self.to_variant.clone());
+        let target = geometry::build_geometry_for_layout(
+            geometry::GeometryContext {
+                config: &config,
+                metadata: &target_metadata,
+            },
+            &self.to_variant,
+        )
+        .map_err(|e| CliError::io(format!("Failed to build target geometry: {e}")))?;
+
+        let (mut remapped, report) = remap_layout(
+            &layout,
+            Board {
+                geometry: &source.geometry,
+                mapping: &source.mapping,
+            },
+            Board {
+                geometry: &target.geometry,
+                mapping: &target.mapping,
+            },
+            &explicit,
+        )
+        .map_err(|e| CliError::validation(e.to_string()))?;
+
+        remapped.metadata.keyboard = Some(target.variant_path.clone());
+        remapped.metadata.layout_variant = Some(self.to_variant.clone());
+
+        let keycode_db = KeycodeDb::load()
+            .map_err(|e| CliError::io(format!("Failed to load keycode database: {e}")))?;
+        let validation =
+            FirmwareValidator::new(&remapped, &target.geometry, &target.mapping, &keycode_db)
+                .validate()
+                .map_err(|e| CliError::io(format!("Validation failed: {e}")))?;
+        if !validation.is_valid() {
+            return Err(CliError::validation(format!(
+                "Remapped layout does not validate against {}:\n{}",
+                self.to_keyboard,
+                validation.format_message()
+            )));
+        }
+
+        write_layout(&remapped, &self.output)?;
+
+        let lines = if self.json {
+            vec![serde_json::to_string_pretty(&report)
+                .map_err(|e| CliError::io(format!("Failed to serialize report: {e}")))?]
+        } else {
+            report_lines(&report)
+        };
+        // Keep stdout clean for the layout itself when writing to stdout
+        for line in lines {
+            if is_stdio(&self.output) {
+                eprintln!("{line}");
+            } else {
+                println!("{line}");
+            }
+        }
+
+        Ok(())
+    }
+}
+
+/// Human-readable per-layer remap report.
+fn report_lines(report: &RemapReport) -> Vec<String> {
+    let mut lines = vec!["Remap report:".to_string()];
+    for stats in &report.layers {
+        lines.push(format!(
+            "  Layer {} ({}): {} auto-matched, {} explicit, {} orphaned, {} empty",
+            stats.layer,
+            stats.name,
+            stats.auto_matched,
+            stats.explicit,
+            stats.orphaned,
+            stats.empty
+        ));
+    }
+    lines
+}
@@ -0,0 +1,106 @@
+//! Rename-keycode command: rewrite a keycode across several layout files.
+
+use crate::cli::common::{CliError, CliResult};
+use crate::keycode_db::KeycodeDb;
+use crate::services::keycode_rename::{rename_keycode, validate_keycode_name, RenameCounts};
+use crate::services::LayoutService;
+use clap::Args;
+use std::path::PathBuf;
+
+/// Rename a keycode in every layout it appears in
+#[derive(Debug, Clone, Args)]
+pub struct RenameKeycodeArgs {
+    /// Keycode to replace (e.g., "MY_EMOJI")
+    #[arg(long, value_name = "KEYCODE")]
+    pub from: String,
+
+    /// New keycode name (e.g., "EMOJI_PICK")
+    #[arg(long, value_name = "KEYCODE")]
+    pub to: String,
+
+    /// Layout markdown files to rewrite
+    #[arg(value_name = "FILES", required = true)]
+    pub layouts: Vec<PathBuf>,
+
+    /// Report what would change without writing any file
+    #[arg(long)]
+    pub dry_run: bool,
+
+    /// Rename even if the new name is an existing keycode
+    #[arg(long)]
+    pub force: bool,
+}
+
+impl RenameKeycodeArgs {
+    /// Execute the rename-keycode command.
+    ///
+    /// All files are loaded before any is written, so a parse error in one
+    /// file leaves every file untouched.
+    pub fn execute(&self) -> CliResult<()> {
+        validate_keycode_name(&self.from).map_err(|e| CliError::validation(e.to_string()))?;
+        validate_keycode_name(&self.to).map_err(|e| CliError::validation(e.to_string()))?;
+        if self.from == self.to {
+            return Err(CliError::validation("--from and --to are the same keycode"));
+        }
+
+        if !self.force {
+            let keycode_db = KeycodeDb::load()
+                .map_err(|e| CliError::io(format!("Failed to load keycode database: {e}")))?;
+            if let Some(existing) = keycode_db.get(&self.to) {
+                return Err(CliError::validation(format!(
+                    "'{}' is already a keycode ({}); use --force to rename anyway",
+                    self.to, existing.name
+                )));
+            }
+        }
+
+        let mut layouts = Vec::with_capacity(self.layouts.len());
+        for path in &self.layouts {
+            let layout = LayoutService::load(path)?;
+            layouts.push((path, layout));
+        }
+
+        let mut total = 0;
+        for (path, layout) in &mut layouts {
+            let counts = rename_keycode(layout, &self.from, &self.to);
+            println!("{}: {}", path.display(), describe(&counts));
+            total += counts.total();
+
+            if counts.total() > 0 && !self.dry_run {
+                LayoutService::save(layout, path).map_err(|e| {
+                    CliError::io(format!("Failed to save layout {}: {e}", path.display()))
+                })?;
+            }
+        }
+
+        let verb = if self.dry_run {
+            "Would rename"
+        } else {
+            "Renamed"
+        };
+        println!(
+            "{verb} {} -> {} in {total} place(s) across {} file(s)",
+            self.from,
+            self.to,
+            self.layouts.len()
+        );
+        Ok(())
+    }
+}
+
+/// Formats per-file counts, e.g. "3 keys, 1 tap dance".
+fn describe(counts: &RenameCounts) -> String {
+    if counts.total() == 0 {
+        return "no matches".to_string();
+    }
+    [
+        (counts.keys, "key", "keys"),
+        (counts.tap_dances, "tap dance", "tap dances"),
+        (counts.orphaned_keys, "orphaned key", "orphaned keys"),
+    ]
+    .into_iter()
+    .filter(|(count, _, _)| *count > 0)
+    .map(|(count, one, many)| format!("{count} {}", if count == 1 { one } else { many }))
+    .collect::<Vec<_>>()
+    .join(", ")
+}
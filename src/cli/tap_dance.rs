@@ -62,6 +62,14 @@ pub struct AddArgs {
     /// Optional keycode for hold
     #[arg(long)]
     pub hold: Option<String>,
+
+    /// Optional tapping term for this dance in milliseconds (50-2000)
+    #[arg(long, value_name = "MS")]
+    pub tapping_term: Option<u16>,
+
+    /// Send the single tap immediately on every tap (cannot be combined with --double)
+    #[arg(long)]
+    pub on_each_tap: bool,
 }
 
 /// Delete a tap dance definition
@@ -104,6 +112,10 @@ struct TapDanceInfo {
     single_tap: String,
     double_tap: Option<String>,
     hold: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    tapping_term_ms: Option<u16>,
+    #[serde(skip_serializing_if = "std::ops::Not::not")]
+    on_each_tap: bool,
     #[serde(rename = "type")]
     td_type: String,
 }
@@ -125,13 +137,22 @@ impl TapDanceArgs {
             TapDanceCommand::Validate(args) => execute_validate(args),
         }
     }
+
+    /// Whether the subcommand was asked for JSON output
+    #[must_use]
+    pub const fn json_output(&self) -> bool {
+        match &self.command {
+            TapDanceCommand::List(args) => args.json,
+            TapDanceCommand::Validate(args) => args.json,
+            TapDanceCommand::Add(_) | TapDanceCommand::Delete(_) => false,
+        }
+    }
 }
 
 /// Execute the list subcommand
 fn execute_list(args: &ListArgs) -> CliResult<()> {
     // Load layout
-    let layout = LayoutService::load(&args.layout)
-        .map_err(|e| CliError::io(format!("Failed to load layout: {e}")))?;
+    let layout = LayoutService::load(&args.layout)?;
 
     let tap_dances: Vec<TapDanceInfo> = layout
         .tap_dances
@@ -150,6 +171,8 @@ fn execute_list(args: &ListArgs) -> CliResult<()> {
                 single_tap: td.single_tap.clone(),
                 double_tap: td.double_tap.clone(),
                 hold: td.hold.clone(),
+                tapping_term_ms: td.tapping_term_ms,
+                on_each_tap: td.on_each_tap,
                 td_type: td_type.to_string(),
             }
         })
@@ -175,6 +198,12 @@ fn execute_list(args: &ListArgs) -> CliResult<()> {
             if let Some(ref hold) = td.hold {
                 print!(", hold={hold}");
             }
+            if let Some(term) = td.tapping_term_ms {
+                print!(", term={term}ms");
+            }
+            if td.on_each_tap {
+                print!(", on_each_tap");
+            }
             println!();
         }
     }
@@ -185,8 +214,7 @@ fn execute_list(args: &ListArgs) -> CliResult<()> {
 /// Execute the add subcommand
 fn execute_add(args: &AddArgs) -> CliResult<()> {
     // Load layout
-    let mut layout = LayoutService::load(&args.layout)
-        .map_err(|e| CliError::io(format!("Failed to load layout: {e}")))?;
+    let mut layout = LayoutService::load(&args.layout)?;
 
     // Check if name already exists
     if layout.get_tap_dance(&args.name).is_some() {
@@ -207,6 +235,14 @@ fn execute_add(args: &AddArgs) -> CliResult<()> {
         tap_dance = tap_dance.with_hold(hold);
     }
 
+    if let Some(term) = args.tapping_term {
+        tap_dance = tap_dance.with_tapping_term(term);
+    }
+
+    if args.on_each_tap {
+        tap_dance = tap_dance.with_on_each_tap();
+    }
+
     // Validate tap dance
     tap_dance
         .validate()
@@ -233,8 +269,7 @@ fn execute_add(args: &AddArgs) -> CliResult<()> {
 /// Execute the delete subcommand
 fn execute_delete(args: &DeleteArgs) -> CliResult<()> {
     // Load layout
-    let mut layout = LayoutService::load(&args.layout)
-        .map_err(|e| CliError::io(format!("Failed to load layout: {e}")))?;
+    let mut layout = LayoutService::load(&args.layout)?;
 
     // Check if tap dance exists
     if layout.get_tap_dance(&args.name).is_none() {
@@ -285,8 +320,7 @@ fn execute_delete(args: &DeleteArgs) -> CliResult<()> {
 /// Execute the validate subcommand
 fn execute_validate(args: &ValidateArgs) -> CliResult<()> {
     // Load layout
-    let layout = LayoutService::load(&args.layout)
-        .map_err(|e| CliError::io(format!("Failed to load layout: {e}")))?;
+    let layout = LayoutService::load(&args.layout)?;
 
     // Find orphaned references (used in layers but no definition)
     let orphaned = find_orphaned_references(&layout);
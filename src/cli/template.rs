@@ -1,9 +1,9 @@
 //! Template management commands for layout files.
 
 use crate::cli::common::{CliError, CliResult};
-use crate::config::Config;
 use crate::parser::layout::parse_markdown_layout;
 use crate::parser::template_gen::save_markdown_layout;
+use crate::paths;
 use crate::services::LayoutService;
 use chrono::Utc;
 use clap::{Args, Subcommand};
@@ -99,6 +99,12 @@ impl TemplateArgs {
             TemplateCommand::Apply(args) => args.execute(),
         }
     }
+
+    /// Whether the subcommand was asked for JSON output
+    #[must_use]
+    pub const fn json_output(&self) -> bool {
+        matches!(&self.command, TemplateCommand::List(args) if args.json)
+    }
 }
 
 impl ListArgs {
@@ -188,15 +194,14 @@ impl SaveArgs {
     pub fn execute(&self) -> CliResult<()> {
         // Validate layout file exists and is readable
         if !self.layout.exists() {
-            return Err(CliError::validation(format!(
+            return Err(CliError::not_found(format!(
                 "Layout file not found: {}",
                 self.layout.display()
             )));
         }
 
         // Load the layout
-        let mut layout = LayoutService::load(&self.layout)
-            .map_err(|e| CliError::io(format!("Failed to load layout: {e}")))?;
+        let mut layout = LayoutService::load(&self.layout)?;
 
         // Parse tags if provided
         let tags = if let Some(tag_str) = &self.tags {
@@ -285,8 +290,7 @@ impl ApplyArgs {
             .ok_or_else(|| CliError::validation(format!("Template '{}' not found", self.name)))?;
 
         // Load the template
-        let mut layout = LayoutService::load(&template_path)
-            .map_err(|e| CliError::io(format!("Failed to load template: {e}")))?;
+        let mut layout = LayoutService::load(&template_path)?;
 
         // Update metadata for new layout
         layout.metadata.is_template = false;
@@ -306,9 +310,8 @@ impl ApplyArgs {
 
 /// Get the platform-specific template directory
 fn get_template_dir() -> CliResult<PathBuf> {
-    let config_dir = Config::config_dir()
-        .map_err(|e| CliError::io(format!("Failed to get config directory: {e}")))?;
-    Ok(config_dir.join("templates"))
+    paths::templates_dir()
+        .map_err(|e| CliError::io(format!("Failed to get templates directory: {e}")))
 }
 
 /// Sanitize a string to be a valid filename
@@ -1,23 +1,37 @@
 //! Validation command for layout files.
+//!
+//! Accepts several files at once (repeated `--layout` flags or positional
+//! paths and globs), sharing one keycode database and geometry cache.
+//! With `--fix`, every finding that has a quick fix is fixed and saved before
+//! the files are validated.
 
 use crate::cli::common::{
-    CliError, CliResult, ValidationChecks, ValidationLocation, ValidationMessage,
-    ValidationPosition, ValidationResponse,
+    wildcard_match, CliError, CliResult, ErrorKind, ValidationChecks, ValidationMessage,
+    ValidationResponse,
 };
+use crate::constants::APP_BINARY_NAME;
 use crate::firmware::validator::FirmwareValidator;
 use crate::keycode_db::KeycodeDb;
 use crate::models::keyboard_geometry::KeyboardGeometry;
 use crate::models::visual_layout_mapping::VisualLayoutMapping;
+use crate::services::quick_fix::QuickFix;
 use crate::services::LayoutService;
 use clap::Args;
-use std::path::PathBuf;
+use serde::Serialize;
+use std::collections::{BTreeMap, HashMap};
+use std::path::{Path, PathBuf};
 
-/// Validate a layout file for errors and warnings
+/// Validate layout files for errors and warnings
 #[derive(Debug, Clone, Args)]
+#[allow(clippy::struct_excessive_bools)] // Independent command-line flags
 pub struct ValidateArgs {
-    /// Path to layout markdown file
+    /// Path to layout markdown file (can be repeated)
     #[arg(short, long, value_name = "FILE")]
-    pub layout: PathBuf,
+    pub layout: Vec<PathBuf>,
+
+    /// Layout files or glob patterns (e.g., "layouts/*.md")
+    #[arg(value_name = "PATH")]
+    pub paths: Vec<String>,
 
     /// Output results as JSON
     #[arg(long)]
@@ -26,147 +40,438 @@ pub struct ValidateArgs {
     /// Treat warnings as errors (exit non-zero)
     #[arg(long)]
     pub strict: bool,
+
+    /// Stop at the first layout that fails
+    #[arg(long)]
+    pub fail_fast: bool,
+
+    /// Apply every auto-fixable finding, save, and re-validate
+    #[arg(long, conflicts_with = "fix_dry_run")]
+    pub fix: bool,
+
+    /// List the fixes --fix would apply without writing any file
+    #[arg(long)]
+    pub fix_dry_run: bool,
+}
+
+/// Geometry and mapping built for a layout shape, shared across files.
+type GeometryCache = HashMap<GeometryKey, (KeyboardGeometry, VisualLayoutMapping)>;
+
+/// Keyboard, variant, and key positions that determine a minimal geometry.
+type GeometryKey = (String, String, Vec<(u8, u8)>);
+
+/// Combined JSON output when validating multiple files.
+#[derive(Debug, Serialize)]
+struct MultiValidationResponse {
+    /// True if every checked file passed
+    valid: bool,
+    /// Number of files checked (fewer than requested with --fail-fast)
+    checked: usize,
+    /// Number of files that failed
+    failed: usize,
+    /// Results keyed by file path
+    files: BTreeMap<String, FileResult>,
+}
+
+/// Validation result for a single file in a multi-file run.
+#[derive(Debug, Serialize)]
+#[serde(untagged)]
+enum FileResult {
+    /// The file was loaded and validated
+    Validated(ValidationResponse),
+    /// The file could not be loaded or validated
+    Error {
+        /// Always false
+        valid: bool,
+        /// Why the file could not be validated
+        error: String,
+        /// Kind of failure (`not_found`, `parse`, ...)
+        kind: ErrorKind,
+    },
 }
 
 impl ValidateArgs {
     /// Execute the validate command
     pub fn execute(&self) -> CliResult<()> {
-        // Load layout
-        let layout = LayoutService::load(&self.layout)
-            .map_err(|e| CliError::io(format!("Failed to load layout: {e}")))?;
-
-        // Build minimal geometry for validation
-        let geometry = build_minimal_geometry_for_layout(&layout)?;
-        let mapping = VisualLayoutMapping::build(&geometry);
+        let files = self.layout_files()?;
 
-        // Load keycode database
+        // Load keycode database once for all files
         let keycode_db = KeycodeDb::load()
             .map_err(|e| CliError::io(format!("Failed to load keycode database: {e}")))?;
+        let mut geometries = GeometryCache::new();
 
-        // Validate
-        let validator = FirmwareValidator::new(&layout, &geometry, &mapping, &keycode_db);
-        let report = validator
-            .validate()
-            .map_err(|e| CliError::io(format!("Validation failed: {e}")))?;
-
-        // Build response
-        let mut checks = ValidationChecks::all_passed();
-        let mut messages = Vec::new();
-
-        // Convert errors
-        for error in &report.errors {
-            // Update check status based on error kind
-            match error.kind {
-                crate::firmware::validator::ValidationErrorKind::InvalidKeycode => {
-                    checks.keycodes = "failed".to_string();
-                }
-                crate::firmware::validator::ValidationErrorKind::MissingPosition
-                | crate::firmware::validator::ValidationErrorKind::DuplicatePosition
-                | crate::firmware::validator::ValidationErrorKind::MatrixOutOfBounds
-                | crate::firmware::validator::ValidationErrorKind::MismatchedKeyCount => {
-                    checks.positions = "failed".to_string();
-                }
-                crate::firmware::validator::ValidationErrorKind::EmptyLayer => {
-                    checks.layer_refs = "failed".to_string();
-                }
-            }
+        if self.fix || self.fix_dry_run {
+            self.apply_fixes(&files)?;
+        }
 
-            let location =
-                if let (Some(layer), Some(row), Some(col)) = (error.layer, error.row, error.col) {
-                    Some(ValidationLocation {
-                        layer,
-                        position: ValidationPosition { row, col },
-                    })
-                } else {
-                    None
-                };
-
-            messages.push(ValidationMessage {
-                severity: "error".to_string(),
-                message: error.message.clone(),
-                location,
-            });
-        }
-
-        // Convert warnings
-        for warning in &report.warnings {
-            let msg = warning.message.clone();
-
-            // Update check status for tap dance warnings
-            if msg.contains("Tap dance") || msg.contains("tap dance") {
-                checks.tap_dances = "warning".to_string();
-            }
+        if let [file] = files.as_slice() {
+            let response = validate_file(file, &keycode_db, &mut geometries)?;
+            self.print_single(file, &response)?;
+            return self.check(&response);
+        }
 
-            messages.push(ValidationMessage {
-                severity: "warning".to_string(),
-                message: msg,
-                location: None,
-            });
+        let mut results = Vec::new();
+        for file in &files {
+            let result = validate_file(file, &keycode_db, &mut geometries);
+            let failed = result.as_ref().map_or(true, |r| self.check(r).is_err());
+            results.push((file, result));
+            if failed && self.fail_fast {
+                break;
+            }
         }
 
-        let response = ValidationResponse {
-            valid: report.is_valid(),
-            errors: messages,
-            checks,
-        };
+        let outcomes: Vec<CliResult<()>> = results
+            .iter()
+            .map(|(_, result)| match result {
+                Ok(response) => self.check(response),
+                Err(e) => Err(CliError {
+                    message: e.message.clone(),
+                    exit_code: e.exit_code,
+                    kind: e.kind,
+                }),
+            })
+            .collect();
+        let failed = outcomes.iter().filter(|o| o.is_err()).count();
 
-        // Output results
         if self.json {
+            let files = results
+                .into_iter()
+                .map(|(path, result)| {
+                    let result = match result {
+                        Ok(response) => FileResult::Validated(response),
+                        Err(e) => FileResult::Error {
+                            valid: false,
+                            error: e.message,
+                            kind: e.kind,
+                        },
+                    };
+                    (path.display().to_string(), result)
+                })
+                .collect();
+            let response = MultiValidationResponse {
+                valid: failed == 0,
+                checked: outcomes.len(),
+                failed,
+                files,
+            };
             println!(
                 "{}",
                 serde_json::to_string_pretty(&response)
                     .map_err(|e| CliError::io(format!("Failed to serialize JSON: {e}")))?
             );
         } else {
-            // Human-readable output
-            if response.valid {
-                println!("✓ Validation passed");
+            for ((path, result), outcome) in results.iter().zip(&outcomes) {
+                match (result, outcome) {
+                    (Ok(response), Ok(())) => {
+                        println!("✓ {}", path.display());
+                        print_issues(path, response, "    ");
+                    }
+                    (Ok(response), Err(e)) => {
+                        println!("✗ {} ({})", path.display(), e.message);
+                        print_issues(path, response, "    ");
+                    }
+                    (Err(e), _) => println!("✗ {} ({})", path.display(), e.message),
+                }
+            }
+            println!(
+                "\n{} of {} layouts passed",
+                outcomes.len() - failed,
+                outcomes.len()
+            );
+            if outcomes.len() < files.len() {
+                println!(
+                    "Stopped after first failure, {} not checked",
+                    files.len() - outcomes.len()
+                );
+            }
+        }
+
+        // Exit with the worst result across files
+        let worst = outcomes
+            .into_iter()
+            .filter_map(Result::err)
+            .max_by_key(|e| e.exit_code as i32);
+        match worst {
+            Some(e) => Err(CliError {
+                message: format!("{failed} layout(s) failed validation"),
+                exit_code: e.exit_code,
+                kind: e.kind,
+            }),
+            None => Ok(()),
+        }
+    }
+
+    /// Applies every quick fix to each file, listing the changes.
+    ///
+    /// Files that fail to load are skipped here and reported by validation.
+    /// With --json the list goes to stderr so stdout stays parseable.
+    fn apply_fixes(&self, files: &[PathBuf]) -> CliResult<()> {
+        let print = |line: String| {
+            if self.json {
+                eprintln!("{line}");
             } else {
-                println!("✗ Validation failed");
+                println!("{line}");
             }
+        };
 
-            println!("\nChecks:");
-            println!("  Keycodes:   {}", response.checks.keycodes);
-            println!("  Positions:  {}", response.checks.positions);
-            println!("  Layer refs: {}", response.checks.layer_refs);
-            println!("  Tap dances: {}", response.checks.tap_dances);
-
-            if !response.errors.is_empty() {
-                println!("\nIssues:");
-                for msg in &response.errors {
-                    let prefix = if msg.severity == "error" {
-                        "  ✗"
-                    } else {
-                        "  ⚠"
-                    };
-                    if let Some(loc) = &msg.location {
-                        println!(
-                            "{} [Layer {} ({}, {})] {}",
-                            prefix, loc.layer, loc.position.row, loc.position.col, msg.message
-                        );
-                    } else {
-                        println!("{} {}", prefix, msg.message);
-                    }
+        let mut total = 0;
+        for path in files {
+            let Ok(mut layout) = LayoutService::load(path) else {
+                continue;
+            };
+            let mut changed = 0;
+            for fix in QuickFix::ALL {
+                let changes = fix.apply(&mut layout);
+                if changes.is_empty() {
+                    continue;
+                }
+                print(format!("{} ({}):", path.display(), fix.description()));
+                for change in &changes {
+                    print(format!("  {change}"));
                 }
+                changed += changes.len();
             }
+
+            if changed > 0 && self.fix {
+                LayoutService::save(&layout, path).map_err(|e| {
+                    CliError::io(format!("Failed to save layout {}: {e}", path.display()))
+                })?;
+            }
+            total += changed;
         }
 
-        // Exit code
+        let verb = if self.fix { "Applied" } else { "Would apply" };
+        print(format!("{verb} {total} fix(es)\n"));
+        Ok(())
+    }
+
+    /// Collects layout files from --layout flags and positional paths.
+    fn layout_files(&self) -> CliResult<Vec<PathBuf>> {
+        let mut files = self.layout.clone();
+        for pattern in &self.paths {
+            files.extend(expand_glob(pattern)?);
+        }
+        if files.is_empty() {
+            return Err(CliError::validation(
+                "No layout files given (use --layout FILE or pass paths)",
+            ));
+        }
+        Ok(files)
+    }
+
+    /// Checks a response against the exit criteria.
+    fn check(&self, response: &ValidationResponse) -> CliResult<()> {
         if !response.valid {
             return Err(CliError::validation("Validation failed"));
         }
 
-        if self.strict && !response.errors.is_empty() {
-            let has_warnings = response.errors.iter().any(|m| m.severity == "warning");
-            if has_warnings {
-                return Err(CliError::validation("Warnings found in strict mode"));
-            }
+        if self.strict && response.errors.iter().any(|m| m.severity == "warning") {
+            return Err(CliError::validation("Warnings found in strict mode"));
+        }
+
+        Ok(())
+    }
+
+    /// Prints the report for a single file.
+    fn print_single(&self, path: &Path, response: &ValidationResponse) -> CliResult<()> {
+        if self.json {
+            println!(
+                "{}",
+                serde_json::to_string_pretty(response)
+                    .map_err(|e| CliError::io(format!("Failed to serialize JSON: {e}")))?
+            );
+            return Ok(());
+        }
+
+        if response.valid {
+            println!("✓ Validation passed");
+        } else {
+            println!("✗ Validation failed");
+        }
+
+        println!("\nChecks:");
+        println!("  Keycodes:   {}", response.checks.keycodes);
+        println!("  Positions:  {}", response.checks.positions);
+        println!("  Layer refs: {}", response.checks.layer_refs);
+        println!("  Tap dances: {}", response.checks.tap_dances);
+
+        if !response.errors.is_empty() {
+            println!("\nIssues:");
+            print_issues(path, response, "  ");
         }
 
         Ok(())
     }
 }
 
+/// Prints each error and warning on its own line.
+///
+/// Issues at a key are followed by the command that opens the editor there,
+/// and each rule with a quick fix by the `fix` command that applies it.
+fn print_issues(path: &Path, response: &ValidationResponse, indent: &str) {
+    for msg in &response.errors {
+        let prefix = if msg.severity == "error" {
+            "✗"
+        } else {
+            "⚠"
+        };
+        if let Some(loc) = &msg.location {
+            println!(
+                "{indent}{prefix} [Layer {} ({}, {})] {}",
+                loc.layer, loc.position.row, loc.position.col, msg.message
+            );
+            println!(
+                "{indent}  → {APP_BINARY_NAME} edit {} --layer {} --position {},{}",
+                path.display(),
+                loc.layer,
+                loc.position.row,
+                loc.position.col
+            );
+        } else {
+            println!("{indent}{prefix} {}", msg.message);
+        }
+    }
+
+    let mut rules: Vec<&str> = response.errors.iter().filter_map(|m| m.rule).collect();
+    rules.sort_unstable();
+    rules.dedup();
+    for rule in rules {
+        println!(
+            "{indent}Quick fix: {APP_BINARY_NAME} fix --rule {rule} {}",
+            path.display()
+        );
+    }
+}
+
+/// Expands a path whose file name may contain `*` or `?` wildcards.
+///
+/// Paths without wildcards are returned as-is. Only the last path component
+/// is matched, which covers patterns like `layouts/*.md`.
+fn expand_glob(pattern: &str) -> CliResult<Vec<PathBuf>> {
+    let path = PathBuf::from(pattern);
+    let Some(name) = path.file_name().and_then(|n| n.to_str()) else {
+        return Ok(vec![path]);
+    };
+    if !name.contains(['*', '?']) {
+        return Ok(vec![path]);
+    }
+
+    let dir = match path.parent() {
+        Some(parent) if !parent.as_os_str().is_empty() => parent.to_path_buf(),
+        _ => PathBuf::from("."),
+    };
+    let entries = std::fs::read_dir(&dir)
+        .map_err(|e| CliError::io(format!("Failed to read {}: {e}", dir.display())))?;
+
+    let mut matches: Vec<PathBuf> = entries
+        .filter_map(Result::ok)
+        .filter(|entry| {
+            entry
+                .file_name()
+                .to_str()
+                .is_some_and(|file| wildcard_match(name, file))
+        })
+        .map(|entry| dir.join(entry.file_name()))
+        .filter(|p| p.is_file())
+        .collect();
+    matches.sort();
+
+    if matches.is_empty() {
+        return Err(CliError::io(format!("No files match '{pattern}'")));
+    }
+    Ok(matches)
+}
+
+/// Loads and validates one layout file.
+fn validate_file(
+    path: &Path,
+    keycode_db: &KeycodeDb,
+    geometries: &mut GeometryCache,
+) -> CliResult<ValidationResponse> {
+    // Load layout
+    let layout = LayoutService::load(path)?;
+
+    // Build minimal geometry for validation, reusing it for identical shapes
+    let key = geometry_key(&layout);
+    if !geometries.contains_key(&key) {
+        let geometry = build_minimal_geometry_for_layout(&layout)?;
+        let mapping = VisualLayoutMapping::build(&geometry);
+        geometries.insert(key.clone(), (geometry, mapping));
+    }
+    let (geometry, mapping) = &geometries[&key];
+
+    // Validate
+    let validator = FirmwareValidator::new(&layout, geometry, mapping, keycode_db);
+    let report = validator
+        .validate()
+        .map_err(|e| CliError::io(format!("Validation failed: {e}")))?;
+
+    // Build response
+    let mut checks = ValidationChecks::all_passed();
+    let mut messages = Vec::new();
+
+    // Convert errors
+    for error in &report.errors {
+        // Update check status based on error kind
+        match error.kind {
+            crate::firmware::validator::ValidationErrorKind::InvalidKeycode => {
+                checks.keycodes = "failed".to_string();
+            }
+            crate::firmware::validator::ValidationErrorKind::MissingPosition
+            | crate::firmware::validator::ValidationErrorKind::DuplicatePosition
+            | crate::firmware::validator::ValidationErrorKind::MatrixOutOfBounds
+            | crate::firmware::validator::ValidationErrorKind::MismatchedKeyCount => {
+                checks.positions = "failed".to_string();
+            }
+            crate::firmware::validator::ValidationErrorKind::EmptyLayer
+            | crate::firmware::validator::ValidationErrorKind::DisabledLayerReference => {
+                checks.layer_refs = "failed".to_string();
+            }
+            crate::firmware::validator::ValidationErrorKind::InvalidTapDance => {
+                checks.tap_dances = "failed".to_string();
+            }
+        }
+
+        messages.push(ValidationMessage::from_error(error));
+    }
+
+    // Convert warnings
+    for warning in &report.warnings {
+        // Update check status for tap dance warnings
+        if warning.message.contains("Tap dance") || warning.message.contains("tap dance") {
+            checks.tap_dances = "warning".to_string();
+        }
+
+        messages.push(ValidationMessage::from_warning(warning));
+    }
+
+    Ok(ValidationResponse {
+        valid: report.is_valid(),
+        errors: messages,
+        checks,
+    })
+}
+
+/// Cache key for the minimal geometry of a layout.
+fn geometry_key(layout: &crate::models::Layout) -> GeometryKey {
+    let positions = layout
+        .layers
+        .first()
+        .map(|layer| {
+            layer
+                .keys
+                .iter()
+                .map(|k| (k.position.row, k.position.col))
+                .collect()
+        })
+        .unwrap_or_default();
+    (
+        layout.metadata.keyboard.clone().unwrap_or_default(),
+        layout.metadata.layout_variant.clone().unwrap_or_default(),
+        positions,
+    )
+}
+
 /// Build minimal geometry for a layout based on its key count
 fn build_minimal_geometry_for_layout(
     layout: &crate::models::Layout,
@@ -3,10 +3,22 @@
 //! This module handles loading, validating, and saving application configuration
 //! in TOML format with platform-specific directory resolution.
 
+use crate::atomic_write;
 use anyhow::{Context, Result};
 use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
 use std::fs;
-use std::path::PathBuf;
+use std::path::{Path, PathBuf};
+
+/// File name of a project configuration, see [`ProjectConfig`]
+pub const PROJECT_CONFIG_FILE: &str = ".lazyqmk.toml";
+
+/// Environment variables overriding path settings, by setting key
+const ENV_OVERRIDES: [(&str, &str); 3] = [
+    ("LAZYQMK_QMK_FIRMWARE", "paths.qmk_firmware"),
+    ("LAZYQMK_OUTPUT_DIR", "build.output_dir"),
+    ("LAZYQMK_TEMPLATE_DIR", "build.template_dir"),
+];
 
 /// Theme display mode preference.
 #[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, Default)]
@@ -18,6 +30,96 @@ pub enum ThemeMode {
     Dark,
     /// Always use light theme
     Light,
+    /// Pure black and white, with text markers for every highlighted state
+    HighContrast,
+}
+
+/// How firmware is compiled.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, Default)]
+#[serde(rename_all = "lowercase")]
+pub enum BuildBackend {
+    /// `qmk compile` when the qmk CLI is on PATH and the QMK tree supports
+    /// it, direct `make` otherwise
+    #[default]
+    Auto,
+    /// Direct `make <keyboard>:<keymap>` in the QMK tree
+    Make,
+    /// `qmk compile -kb <keyboard> -km <keymap>`
+    Qmk,
+}
+
+impl BuildBackend {
+    /// Name as written in the config and on the command line.
+    #[must_use]
+    pub const fn name(self) -> &'static str {
+        match self {
+            Self::Auto => "auto",
+            Self::Make => "make",
+            Self::Qmk => "qmk",
+        }
+    }
+}
+
+impl std::str::FromStr for BuildBackend {
+    type Err = String;
+
+    fn from_str(value: &str) -> Result<Self, Self::Err> {
+        match value.to_lowercase().as_str() {
+            "auto" => Ok(Self::Auto),
+            "make" => Ok(Self::Make),
+            "qmk" => Ok(Self::Qmk),
+            _ => Err(format!(
+                "Invalid build backend '{value}'. Must be 'auto', 'make', or 'qmk'"
+            )),
+        }
+    }
+}
+
+/// How transparent (`KC_TRNS`) keys are drawn.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, Default)]
+#[serde(rename_all = "lowercase")]
+pub enum TransparentKeyStyle {
+    /// Like any other key, with a transparent legend
+    #[default]
+    Label,
+    /// Greyed background and a `▽` glyph
+    Dim,
+    /// Outline only; exports leave the key out entirely
+    Hidden,
+}
+
+impl TransparentKeyStyle {
+    /// All styles, in settings order.
+    #[must_use]
+    pub const fn all() -> [Self; 3] {
+        [Self::Label, Self::Dim, Self::Hidden]
+    }
+
+    /// Name as written in the config.
+    #[must_use]
+    pub const fn name(self) -> &'static str {
+        match self {
+            Self::Label => "label",
+            Self::Dim => "dim",
+            Self::Hidden => "hidden",
+        }
+    }
+
+    /// Human-readable style name.
+    #[must_use]
+    pub const fn display_name(self) -> &'static str {
+        match self {
+            Self::Label => "Label",
+            Self::Dim => "Dim",
+            Self::Hidden => "Hidden",
+        }
+    }
+
+    /// Whether exports should leave transparent keys out.
+    #[must_use]
+    pub const fn skips_in_exports(self) -> bool {
+        matches!(self, Self::Hidden)
+    }
 }
 
 /// Path configuration for file system locations.
@@ -25,16 +127,42 @@ pub enum ThemeMode {
 pub struct PathConfig {
     /// QMK firmware directory path (e.g., "/`path/to/qmk_firmware`")
     pub qmk_firmware: Option<PathBuf>,
+    /// Shared tap dance library file (default: `library.toml` in the config
+    /// directory)
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub library: Option<PathBuf>,
+    /// Cache directory for parsed `info.json` data; set by the editor at
+    /// startup and never saved, so CLI commands and tests don't cache
+    #[serde(skip)]
+    pub info_cache: Option<PathBuf>,
 }
 
 /// Firmware build configuration.
 ///
-/// Note: keyboard, layout_variant, keymap_name, and output_format have been moved
-/// to per-layout .md file metadata. Only global build settings remain here.
+/// Note: keyboard, layout_variant, keymap_name, and output_format live in
+/// per-layout .md file metadata. `keyboard` and `keymap` here are only
+/// fallbacks for layouts that don't set them.
 #[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
 pub struct BuildConfig {
     /// Build output directory (where all firmware files go)
     pub output_dir: PathBuf,
+    /// Directory with user templates overriding the embedded keymap.c,
+    /// config.h and rules.mk templates (missing files fall back to embedded)
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub template_dir: Option<PathBuf>,
+    /// Keyboard to build when the layout doesn't set one
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub keyboard: Option<String>,
+    /// Keymap name to build when the layout doesn't set one
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub keymap: Option<String>,
+    /// Controller converter passed as `CONVERT_TO` (e.g., "rp2040_ce")
+    /// when the layout doesn't set one
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub converter: Option<String>,
+    /// Build backend: "auto", "make" or "qmk"
+    #[serde(default)]
+    pub backend: BuildBackend,
 }
 
 impl Default for BuildConfig {
@@ -42,18 +170,22 @@ impl Default for BuildConfig {
         // Use config directory for build output by default
         let output_dir = Self::default_output_dir().unwrap_or_else(|_| PathBuf::from(".build"));
 
-        Self { output_dir }
+        Self {
+            output_dir,
+            template_dir: None,
+            keyboard: None,
+            keymap: None,
+            converter: None,
+            backend: BuildBackend::Auto,
+        }
     }
 }
 
 impl BuildConfig {
-    /// Gets the default output directory path.
-    ///
-    /// - Linux: `~/.config/LazyQMK/builds/`
-    /// - macOS: `~/Library/Application Support/LazyQMK/builds/`
-    /// - Windows: `%APPDATA%\LazyQMK\builds\`
+    /// Gets the default output directory path, `builds/` in the data
+    /// directory (see [`crate::paths`]).
     fn default_output_dir() -> Result<PathBuf> {
-        Ok(Config::config_dir()?.join("builds"))
+        Ok(crate::paths::AppPaths::current()?.builds_dir())
     }
 
     /// Determines the keyboard variant subdirectory based on layout and key count.
@@ -187,10 +319,11 @@ impl BuildConfig {
 
 /// UI preferences configuration.
 #[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[allow(clippy::struct_excessive_bools)] // Independent on/off preferences
 pub struct UiConfig {
     /// Display help on startup
     pub show_help_on_startup: bool,
-    /// Theme mode preference (Auto, Dark, Light)
+    /// Theme mode preference (Auto, Dark, Light, High contrast)
     #[serde(default)]
     pub theme_mode: ThemeMode,
     /// Unified keyboard scale factor (1.0 = default, <1.0 smaller, >1.0 larger)
@@ -199,6 +332,34 @@ pub struct UiConfig {
     /// Last selected language in the keycode picker (for convenience)
     #[serde(default)]
     pub last_language: Option<String>,
+    /// Merge queued arrow-key repeats into one move per frame, so the cursor
+    /// stops when the key is released
+    #[serde(default = "default_true")]
+    pub coalesce_navigation: bool,
+    /// Move by 2-3 keys while an arrow key is held
+    #[serde(default = "default_true")]
+    pub navigation_acceleration: bool,
+    /// Ring the terminal bell and send a desktop notification when a
+    /// firmware build finishes
+    #[serde(default = "default_true")]
+    pub build_notifications: bool,
+    /// Capture the mouse in the TUI; off leaves text selection to the terminal
+    #[serde(default = "default_true")]
+    pub mouse: bool,
+    /// Replace animations with static markers: pasted keys stay marked until
+    /// the next input and loading screens show elapsed time, not a spinner
+    #[serde(default)]
+    pub reduced_motion: bool,
+    /// Show a banner under the title bar when the layout has validation
+    /// errors or warnings
+    #[serde(default = "default_true")]
+    pub validation_banner: bool,
+    /// The first-run tutorial was finished or skipped
+    #[serde(default)]
+    pub tutorial_completed: bool,
+    /// How transparent keys are drawn in the keyboard and in exports
+    #[serde(default)]
+    pub transparent_keys: TransparentKeyStyle,
 }
 
 /// Default keyboard scale (1.0 = 100%)
@@ -206,6 +367,10 @@ fn default_keyboard_scale() -> f32 {
     1.0
 }
 
+const fn default_true() -> bool {
+    true
+}
+
 impl Default for UiConfig {
     fn default() -> Self {
         Self {
@@ -213,10 +378,124 @@ impl Default for UiConfig {
             theme_mode: ThemeMode::default(),
             keyboard_scale: default_keyboard_scale(),
             last_language: None,
+            coalesce_navigation: true,
+            navigation_acceleration: true,
+            build_notifications: true,
+            mouse: true,
+            reduced_motion: false,
+            validation_banner: true,
+            tutorial_completed: false,
+            transparent_keys: TransparentKeyStyle::default(),
         }
     }
 }
 
+/// Lint settings applied to every layout, on top of the rules a layout
+/// disables in its frontmatter.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize, Default)]
+pub struct LintConfig {
+    /// Rule IDs that are not checked
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub disable: Vec<String>,
+}
+
+impl LintConfig {
+    /// Returns true if no lint settings are configured.
+    #[must_use]
+    pub fn is_empty(&self) -> bool {
+        self.disable.is_empty()
+    }
+}
+
+/// Defaults for new layouts of one keyboard, stored under `[keyboards."<path>"]`.
+///
+/// An entry for `crkbd` also applies to `crkbd/rev1`; the most specific
+/// entry wins.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize, Default)]
+pub struct KeyboardDefaults {
+    /// Layout variant preselected for new layouts, updated whenever a layout
+    /// is created (e.g., "`LAYOUT_split_3x6_3`")
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub default_variant: Option<String>,
+    /// Keymap name for new layouts, with `{user}`, `{layout}` and
+    /// `{keyboard}` replaced (e.g., "{user}_{layout}")
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub keymap_pattern: Option<String>,
+    /// Number of layers new layouts start with
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub layer_count: Option<u8>,
+}
+
+impl KeyboardDefaults {
+    /// The most specific entry of `table` for `keyboard`, with its key.
+    #[must_use]
+    pub fn lookup<'a>(
+        table: &'a BTreeMap<String, Self>,
+        keyboard: &str,
+    ) -> Option<(&'a str, &'a Self)> {
+        table
+            .iter()
+            .filter(|(key, _)| {
+                keyboard == key.as_str()
+                    || keyboard
+                        .strip_prefix(key.as_str())
+                        .is_some_and(|rest| rest.starts_with('/'))
+            })
+            .max_by_key(|(key, _)| key.len())
+            .map(|(key, defaults)| (key.as_str(), defaults))
+    }
+
+    /// Keymap name from the pattern for a layout named `layout_name`, or
+    /// `None` without a pattern.
+    #[must_use]
+    #[allow(clippy::literal_string_with_formatting_args)] // Pattern placeholders, not format args
+    pub fn keymap_name(&self, keyboard: &str, layout_name: &str) -> Option<String> {
+        let pattern = self.keymap_pattern.as_deref()?;
+        let user = std::env::var("USER")
+            .or_else(|_| std::env::var("USERNAME"))
+            .unwrap_or_else(|_| "user".to_string());
+        let board = keyboard.split('/').next().unwrap_or(keyboard);
+        Some(
+            pattern
+                .replace("{user}", &user)
+                .replace("{layout}", layout_name)
+                .replace("{keyboard}", board),
+        )
+    }
+}
+
+/// Where a setting's value came from, when not from the user config.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "lowercase")]
+pub enum ValueOrigin {
+    /// The project's `.lazyqmk.toml`
+    Project,
+    /// An environment variable
+    Environment,
+}
+
+impl ValueOrigin {
+    /// Label shown next to the value in `config show`.
+    #[must_use]
+    pub const fn label(self) -> &'static str {
+        match self {
+            Self::Project => "project",
+            Self::Environment => "env",
+        }
+    }
+}
+
+/// Layers applied over the user config by [`Config::apply_layers`].
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct ConfigLayers {
+    /// Project file that was merged in, if one was found
+    pub project_file: Option<PathBuf>,
+    /// Origin of each overridden setting, by key (e.g. `build.output_dir`)
+    pub origins: BTreeMap<&'static str, ValueOrigin>,
+    /// The user config before layering, which is what [`Config::save`] writes
+    user: Option<Box<Config>>,
+}
+
 /// Application configuration.
 ///
 /// # File Location
@@ -240,6 +519,15 @@ pub struct Config {
     pub build: BuildConfig,
     /// UI preferences
     pub ui: UiConfig,
+    /// Lint settings
+    #[serde(default, skip_serializing_if = "LintConfig::is_empty")]
+    pub lint: LintConfig,
+    /// Defaults for new layouts, by QMK keyboard path
+    #[serde(default, skip_serializing_if = "BTreeMap::is_empty")]
+    pub keyboards: BTreeMap<String, KeyboardDefaults>,
+    /// Project and environment overrides merged in after loading
+    #[serde(skip)]
+    pub layers: ConfigLayers,
 }
 
 impl Config {
@@ -250,9 +538,34 @@ impl Config {
             paths: PathConfig::default(),
             build: BuildConfig::default(),
             ui: UiConfig::default(),
+            lint: LintConfig::default(),
+            keyboards: BTreeMap::new(),
+            layers: ConfigLayers::default(),
         }
     }
 
+    /// Defaults for new layouts of `keyboard` (empty if none are stored).
+    #[must_use]
+    pub fn keyboard_defaults(&self, keyboard: &str) -> KeyboardDefaults {
+        KeyboardDefaults::lookup(&self.keyboards, keyboard)
+            .map(|(_, defaults)| defaults.clone())
+            .unwrap_or_default()
+    }
+
+    /// The entry stored under exactly `keyboard`, created if missing.
+    pub fn keyboard_defaults_mut(&mut self, keyboard: &str) -> &mut KeyboardDefaults {
+        self.keyboards.entry(keyboard.to_string()).or_default()
+    }
+
+    /// Remembers `variant` as the one to preselect for `keyboard`'s next
+    /// layout, in the entry that applies to it (a new one if none does).
+    pub fn remember_variant(&mut self, keyboard: &str, variant: &str) {
+        let key = KeyboardDefaults::lookup(&self.keyboards, keyboard)
+            .map_or(keyboard, |(key, _)| key)
+            .to_string();
+        self.keyboard_defaults_mut(&key).default_variant = Some(variant.to_string());
+    }
+
     /// Checks if the config file exists on disk.
     ///
     /// Returns true if config.toml exists, false otherwise.
@@ -272,25 +585,16 @@ impl Config {
         self.paths.qmk_firmware.is_some()
     }
 
-    /// Gets the platform-specific config directory path.
+    /// Gets the config directory path.
     ///
-    /// - Linux: `~/.config/LazyQMK/`
+    /// - Linux: `$XDG_CONFIG_HOME/LazyQMK/` (`~/.config/LazyQMK/`)
     /// - macOS: `~/Library/Application Support/LazyQMK/`
     /// - Windows: `%APPDATA%\LazyQMK\`
     ///
-    /// For testing, this can be overridden with the `LAZYQMK_CONFIG_DIR` environment variable.
+    /// Portable mode and the `LAZYQMK_CONFIG_DIR` environment variable
+    /// override it, see [`crate::paths`].
     pub fn config_dir() -> Result<PathBuf> {
-        // Check for test override first
-        if let Ok(test_dir) = std::env::var("LAZYQMK_CONFIG_DIR") {
-            return Ok(PathBuf::from(test_dir));
-        }
-
-        // Normal behavior: use platform-specific config directory
-        let config_dir = dirs::config_dir()
-            .context("Failed to determine config directory")?
-            .join(crate::branding::APP_DATA_DIR);
-
-        Ok(config_dir)
+        crate::paths::config_dir()
     }
 
     /// Gets the full path to the config file.
@@ -298,6 +602,15 @@ impl Config {
         Ok(Self::config_dir()?.join("config.toml"))
     }
 
+    /// Gets the shared library file: the configured path, or `library.toml`
+    /// in the config directory.
+    pub fn library_path(&self) -> Result<PathBuf> {
+        match &self.paths.library {
+            Some(path) => Ok(path.clone()),
+            None => Ok(Self::config_dir()?.join("library.toml")),
+        }
+    }
+
     /// Loads configuration from the config file.
     ///
     /// If the file doesn't exist, returns default configuration.
@@ -339,6 +652,93 @@ impl Config {
         Ok(config)
     }
 
+    /// Loads the user config for working on `start` (a layout file or a
+    /// directory), with its project file and the environment merged in.
+    ///
+    /// Like the commands that call it, falls back to defaults when the user
+    /// config can't be loaded; an invalid project file is an error.
+    pub fn for_layout(start: &Path) -> Result<Self> {
+        let mut config = Self::load().unwrap_or_default();
+        config.apply_layers(start)?;
+        Ok(config)
+    }
+
+    /// Merges the nearest project file for `start` and then the environment
+    /// over this config.
+    ///
+    /// Precedence, lowest first: defaults, user config, project file,
+    /// environment (`LAZYQMK_QMK_FIRMWARE`, `LAZYQMK_OUTPUT_DIR`,
+    /// `LAZYQMK_TEMPLATE_DIR`). Command-line flags are applied by each
+    /// command afterwards.
+    pub fn apply_layers(&mut self, start: &Path) -> Result<()> {
+        let user = self.clone();
+        if let Some(path) = ProjectConfig::discover(start) {
+            ProjectConfig::load(&path)?.apply(self);
+            self.layers.project_file = Some(path);
+        }
+        for (var, key) in ENV_OVERRIDES {
+            if let Some(value) = std::env::var_os(var).filter(|v| !v.is_empty()) {
+                self.set_path(key, PathBuf::from(value));
+                self.layers.origins.insert(key, ValueOrigin::Environment);
+            }
+        }
+        if !self.layers.origins.is_empty() {
+            self.layers.user = Some(Box::new(user));
+        }
+        Ok(())
+    }
+
+    /// Origin of the setting `key` if a project file or the environment set it.
+    #[must_use]
+    pub fn origin(&self, key: &str) -> Option<ValueOrigin> {
+        self.layers.origins.get(key).copied()
+    }
+
+    /// This config with overridden settings put back to their user config
+    /// values.
+    fn persisted(&self) -> Self {
+        let mut persisted = self.clone();
+        if let Some(user) = &self.layers.user {
+            for key in self.layers.origins.keys() {
+                persisted.copy_setting(key, user);
+            }
+        }
+        persisted.layers = ConfigLayers::default();
+        persisted
+    }
+
+    /// Sets a path setting by key.
+    fn set_path(&mut self, key: &str, value: PathBuf) {
+        match key {
+            "paths.qmk_firmware" => self.paths.qmk_firmware = Some(value),
+            "paths.library" => self.paths.library = Some(value),
+            "build.output_dir" => self.build.output_dir = value,
+            "build.template_dir" => self.build.template_dir = Some(value),
+            _ => {}
+        }
+    }
+
+    /// Copies the setting `key` from `other`.
+    fn copy_setting(&mut self, key: &str, other: &Self) {
+        match key {
+            "paths.qmk_firmware" => self
+                .paths
+                .qmk_firmware
+                .clone_from(&other.paths.qmk_firmware),
+            "paths.library" => self.paths.library.clone_from(&other.paths.library),
+            "build.output_dir" => self.build.output_dir.clone_from(&other.build.output_dir),
+            "build.template_dir" => self
+                .build
+                .template_dir
+                .clone_from(&other.build.template_dir),
+            "build.keyboard" => self.build.keyboard.clone_from(&other.build.keyboard),
+            "build.keymap" => self.build.keymap.clone_from(&other.build.keymap),
+            "build.converter" => self.build.converter.clone_from(&other.build.converter),
+            "lint.disable" => self.lint.disable.clone_from(&other.lint.disable),
+            _ => {}
+        }
+    }
+
     /// Attempts to fix a stale QMK firmware path.
     ///
     /// If the path doesn't exist, looks for a directory with similar naming
@@ -382,10 +782,12 @@ impl Config {
 
     /// Saves configuration to the config file using atomic write.
     ///
-    /// Uses temp file + rename pattern for atomic writes.
+    /// Uses temp file + rename pattern for atomic writes. Settings that came
+    /// from a project file or the environment keep their user config value.
     #[allow(dead_code)]
     pub fn save(&self) -> Result<()> {
-        self.validate()?;
+        let saved = self.persisted();
+        saved.validate()?;
 
         // Ensure config directory exists
         let config_dir = Self::config_dir()?;
@@ -395,24 +797,14 @@ impl Config {
         ))?;
 
         // Serialize to TOML
-        let content = toml::to_string_pretty(self).context("Failed to serialize configuration")?;
+        let content =
+            toml::to_string_pretty(&saved).context("Failed to serialize configuration")?;
 
         let config_path = Self::config_file_path()?;
-        let temp_path = config_path.with_extension("toml.tmp");
-
-        // Write to temp file
-        fs::write(&temp_path, content).context(format!(
-            "Failed to write temp config file: {}",
-            temp_path.display()
-        ))?;
-
-        // Atomic rename
-        fs::rename(&temp_path, &config_path).context(format!(
-            "Failed to rename temp config file to: {}",
+        atomic_write::write(&config_path, content).context(format!(
+            "Failed to write config file: {}",
             config_path.display()
-        ))?;
-
-        Ok(())
+        ))
     }
 
     /// Validates configuration values.
@@ -467,6 +859,148 @@ impl Default for Config {
     }
 }
 
+/// Settings committed alongside a project's layouts in `.lazyqmk.toml`.
+///
+/// The nearest file in the layout's directory or its ancestors is merged
+/// over the user config; unset values leave the user config alone. Relative
+/// paths resolve against the file's directory.
+///
+/// ```toml
+/// [build]
+/// output_dir = "firmware"
+/// template_dir = "templates"
+///
+/// [lint]
+/// disable = ["L003"]
+/// ```
+#[derive(Debug, Clone, Default, PartialEq, Eq, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct ProjectConfig {
+    /// File system paths
+    pub paths: ProjectPaths,
+    /// Firmware build settings
+    pub build: ProjectBuild,
+    /// Lint settings; rules disabled here are added to the user's
+    pub lint: LintConfig,
+}
+
+/// Path settings of a [`ProjectConfig`].
+#[derive(Debug, Clone, Default, PartialEq, Eq, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct ProjectPaths {
+    /// QMK firmware directory
+    pub qmk_firmware: Option<PathBuf>,
+    /// Shared tap dance library file
+    pub library: Option<PathBuf>,
+}
+
+/// Build settings of a [`ProjectConfig`].
+#[derive(Debug, Clone, Default, PartialEq, Eq, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct ProjectBuild {
+    /// Build output directory
+    pub output_dir: Option<PathBuf>,
+    /// Keymap template override directory
+    pub template_dir: Option<PathBuf>,
+    /// Keyboard for layouts that don't set one
+    pub keyboard: Option<String>,
+    /// Keymap name for layouts that don't set one
+    pub keymap: Option<String>,
+    /// Controller converter for layouts that don't set one
+    pub converter: Option<String>,
+}
+
+impl ProjectConfig {
+    /// Finds the nearest project file in `start` or its ancestors; a file
+    /// `start` is looked up from its directory.
+    #[must_use]
+    pub fn discover(start: &Path) -> Option<PathBuf> {
+        let start = std::path::absolute(start).ok()?;
+        let dir = if start.is_file() {
+            start.parent()?
+        } else {
+            &start
+        };
+        dir.ancestors()
+            .map(|dir| dir.join(PROJECT_CONFIG_FILE))
+            .find(|path| path.is_file())
+    }
+
+    /// Reads a project file, resolving its relative paths against the
+    /// file's directory.
+    pub fn load(path: &Path) -> Result<Self> {
+        let content = fs::read_to_string(path)
+            .with_context(|| format!("Failed to read project config: {}", path.display()))?;
+        let mut project: Self = toml::from_str(&content)
+            .with_context(|| format!("Failed to parse project config: {}", path.display()))?;
+
+        let base = path.parent().unwrap_or_else(|| Path::new("."));
+        for value in [
+            &mut project.paths.qmk_firmware,
+            &mut project.paths.library,
+            &mut project.build.output_dir,
+            &mut project.build.template_dir,
+        ]
+        .into_iter()
+        .flatten()
+        {
+            if value.is_relative() {
+                *value = base.join(&*value);
+            }
+        }
+        Ok(project)
+    }
+
+    /// Merges the set values into `config`, recording them as project
+    /// values.
+    fn apply(self, config: &mut Config) {
+        let paths = [
+            ("paths.qmk_firmware", self.paths.qmk_firmware),
+            ("paths.library", self.paths.library),
+            ("build.output_dir", self.build.output_dir),
+            ("build.template_dir", self.build.template_dir),
+        ];
+        for (key, value) in paths {
+            if let Some(value) = value {
+                config.set_path(key, value);
+                config.layers.origins.insert(key, ValueOrigin::Project);
+            }
+        }
+
+        let names = [
+            (
+                "build.keyboard",
+                self.build.keyboard,
+                &mut config.build.keyboard,
+            ),
+            ("build.keymap", self.build.keymap, &mut config.build.keymap),
+            (
+                "build.converter",
+                self.build.converter,
+                &mut config.build.converter,
+            ),
+        ];
+        for (key, value, target) in names {
+            if value.is_some() {
+                *target = value;
+                config.layers.origins.insert(key, ValueOrigin::Project);
+            }
+        }
+
+        if !self.lint.disable.is_empty() {
+            for rule in self.lint.disable {
+                if !config.lint.disable.contains(&rule) {
+                    config.lint.disable.push(rule);
+                }
+            }
+            config
+                .layers
+                .origins
+                .insert("lint.disable", ValueOrigin::Project);
+        }
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -480,6 +1014,8 @@ mod tests {
         assert!(config.ui.show_help_on_startup);
         assert_eq!(config.ui.theme_mode, ThemeMode::Auto);
         assert!((config.ui.keyboard_scale - 1.0).abs() < f32::EPSILON);
+        assert!(config.ui.coalesce_navigation);
+        assert!(config.ui.navigation_acceleration);
         // New config should not be considered configured
         assert!(!config.is_configured());
         // Note: keyboard, layout, keymap, and output_format are now per-layout in metadata
@@ -525,6 +1061,77 @@ mod tests {
         assert!(config.validate().is_ok());
     }
 
+    #[test]
+    fn test_project_config_discovered_from_nested_layout() {
+        let temp_dir = TempDir::new().unwrap();
+        let nested = temp_dir.path().join("a").join("b");
+        fs::create_dir_all(&nested).unwrap();
+        let layout = nested.join("layout.md");
+        fs::write(&layout, "").unwrap();
+        assert_eq!(ProjectConfig::discover(&layout), None);
+
+        let project_file = temp_dir.path().join("a").join(PROJECT_CONFIG_FILE);
+        fs::write(&project_file, "").unwrap();
+        assert_eq!(ProjectConfig::discover(&layout), Some(project_file.clone()));
+        assert_eq!(ProjectConfig::discover(&nested), Some(project_file));
+    }
+
+    #[test]
+    fn test_project_config_resolves_relative_paths() {
+        let temp_dir = TempDir::new().unwrap();
+        let path = temp_dir.path().join(PROJECT_CONFIG_FILE);
+        fs::write(
+            &path,
+            "[paths]\nlibrary = \"lib/library.toml\"\n[build]\noutput_dir = \"/abs/out\"\ntemplate_dir = \"templates\"\n",
+        )
+        .unwrap();
+
+        let project = ProjectConfig::load(&path).unwrap();
+        assert_eq!(
+            project.paths.library,
+            Some(temp_dir.path().join("lib/library.toml"))
+        );
+        assert_eq!(project.build.output_dir, Some(PathBuf::from("/abs/out")));
+        assert_eq!(
+            project.build.template_dir,
+            Some(temp_dir.path().join("templates"))
+        );
+    }
+
+    #[test]
+    fn test_project_values_override_user_but_are_not_saved() {
+        let temp_dir = TempDir::new().unwrap();
+        fs::write(
+            temp_dir.path().join(PROJECT_CONFIG_FILE),
+            "[build]\noutput_dir = \"out\"\nkeymap = \"project\"\n[lint]\ndisable = [\"L002\"]\n",
+        )
+        .unwrap();
+
+        let mut config = Config::new();
+        config.build.output_dir = PathBuf::from("/user/out");
+        config.build.keyboard = Some("crkbd".to_string());
+        config.lint.disable = vec!["L001".to_string()];
+        config.apply_layers(temp_dir.path()).unwrap();
+
+        assert_eq!(config.build.output_dir, temp_dir.path().join("out"));
+        assert_eq!(config.build.keymap.as_deref(), Some("project"));
+        assert_eq!(config.build.keyboard.as_deref(), Some("crkbd"));
+        assert_eq!(config.lint.disable, ["L001", "L002"]);
+        assert_eq!(
+            config.origin("build.output_dir"),
+            Some(ValueOrigin::Project)
+        );
+        assert_eq!(config.origin("build.keyboard"), None);
+
+        // Settings changed in the editor are saved, project values are not
+        config.ui.keyboard_scale = 1.5;
+        let persisted = config.persisted();
+        assert_eq!(persisted.build.output_dir, PathBuf::from("/user/out"));
+        assert_eq!(persisted.build.keymap, None);
+        assert_eq!(persisted.lint.disable, ["L001"]);
+        assert!((persisted.ui.keyboard_scale - 1.5).abs() < f32::EPSILON);
+    }
+
     #[test]
     fn test_config_save_and_load() {
         let temp_dir = TempDir::new().unwrap();
@@ -548,6 +1155,29 @@ mod tests {
         // Note: keyboard and layout are now per-layout in metadata
     }
 
+    #[test]
+    fn test_build_backend_parses_lowercase_names() {
+        let build: BuildConfig =
+            toml::from_str("output_dir = \"out\"\nbackend = \"make\"\n").unwrap();
+        assert_eq!(build.backend, BuildBackend::Make);
+        let build: BuildConfig = toml::from_str("output_dir = \"out\"\n").unwrap();
+        assert_eq!(build.backend, BuildBackend::Auto);
+
+        assert_eq!("QMK".parse::<BuildBackend>(), Ok(BuildBackend::Qmk));
+        assert!("ninja".parse::<BuildBackend>().is_err());
+        assert_eq!(BuildBackend::Make.name(), "make");
+    }
+
+    #[test]
+    fn test_transparent_key_style_defaults_to_label() {
+        let ui: UiConfig = toml::from_str("show_help_on_startup = true\n").unwrap();
+        assert_eq!(ui.transparent_keys, TransparentKeyStyle::Label);
+        let ui: UiConfig =
+            toml::from_str("show_help_on_startup = true\ntransparent_keys = \"hidden\"\n").unwrap();
+        assert_eq!(ui.transparent_keys, TransparentKeyStyle::Hidden);
+        assert!(ui.transparent_keys.skips_in_exports());
+    }
+
     // Note: set_keyboard, set_layout, and set_output_format methods removed
     // These settings are now per-layout in metadata, not global config
 
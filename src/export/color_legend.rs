@@ -176,7 +176,7 @@ mod tests {
         // Add a key with color override
         let key =
             KeyDefinition::new(Position::new(0, 0), "KC_A").with_color(RgbColor::new(255, 0, 0));
-        layer.add_key(key);
+        layer.add_key(key).unwrap();
 
         layout.add_layer(layer).unwrap();
 
@@ -226,8 +226,8 @@ mod tests {
         let key2 =
             KeyDefinition::new(Position::new(0, 1), "KC_B").with_color(RgbColor::new(255, 0, 0));
 
-        layer.add_key(key1);
-        layer.add_key(key2);
+        layer.add_key(key1).unwrap();
+        layer.add_key(key2).unwrap();
 
         layout.add_layer(layer).unwrap();
 
@@ -0,0 +1,401 @@
+//! SVG and HTML rendering of key usage heatmaps.
+//!
+//! Keys are drawn at their physical positions when keyboard geometry is
+//! available, otherwise on the layout's row/column grid. Colors run from blue
+//! (least used) through yellow to red (most used); unused keys are grey.
+//! Rotary encoders are drawn as circles labelled with both turn directions.
+//! Transparent keys can be left out so cheat sheets only show assigned keys.
+
+use crate::models::keyboard_geometry::place_encoders;
+use crate::models::{KeyboardGeometry, Layer, Layout, Position};
+use crate::services::heatmap::Heatmap;
+use std::collections::BTreeMap;
+use std::fmt::Write as _;
+
+/// Size of one key unit in pixels
+const UNIT: f32 = 54.0;
+/// Gap between keys in pixels
+const GAP: f32 = 3.0;
+/// Height reserved for a block title in pixels
+const TITLE_HEIGHT: f32 = 28.0;
+/// Space between layer blocks in pixels
+const BLOCK_SPACING: f32 = 24.0;
+
+/// Press counts by (row, col)
+type PositionCounts = BTreeMap<(u8, u8), u64>;
+
+/// Number of most used keys listed in the summary
+const TOP_KEYS: usize = 10;
+
+/// Renders the heatmap as a standalone SVG document.
+///
+/// With `per_layer`, each layer gets its own block with its own scale;
+/// otherwise counts from all layers are summed onto one keyboard labelled
+/// with the base layer. With `skip_transparent`, transparent keys are not
+/// drawn.
+#[must_use]
+pub fn render_heatmap_svg(
+    layout: &Layout,
+    heatmap: &Heatmap,
+    geometry: Option<&KeyboardGeometry>,
+    per_layer: bool,
+    skip_transparent: bool,
+) -> String {
+    let blocks: Vec<(String, usize, PositionCounts)> = if per_layer {
+        layout
+            .layers
+            .iter()
+            .enumerate()
+            .map(|(idx, layer)| {
+                (
+                    format!("Layer {idx}: {}", layer.name),
+                    idx,
+                    heatmap.layer_counts(idx),
+                )
+            })
+            .collect()
+    } else {
+        vec![("All layers".to_string(), 0, heatmap.combined_counts())]
+    };
+
+    let mut body = String::new();
+    let mut y_offset = 0.0_f32;
+    let mut width = 0.0_f32;
+
+    for (title, layer_idx, counts) in &blocks {
+        let Some(layer) = layout.layers.get(*layer_idx) else {
+            continue;
+        };
+        let max = counts.values().copied().max().unwrap_or(0);
+
+        let _ = writeln!(
+            body,
+            r#"  <text x="0" y="{:.1}" class="title">{}</text>"#,
+            y_offset + 18.0,
+            escape(title)
+        );
+        let top = y_offset + TITLE_HEIGHT;
+        let mut bottom = top;
+
+        for key in layer
+            .keys
+            .iter()
+            .filter(|key| !(skip_transparent && key.is_transparent()))
+        {
+            let (x, y, w, h) = key_box(geometry, key.position.row, key.position.col);
+            let count = counts
+                .get(&(key.position.row, key.position.col))
+                .copied()
+                .unwrap_or(0);
+            let (px, py) = (x * UNIT, y.mul_add(UNIT, top));
+            let (pw, ph) = (w.mul_add(UNIT, -GAP), h.mul_add(UNIT, -GAP));
+
+            let _ = writeln!(
+                body,
+                r#"  <g><title>{} ({})</title><rect x="{px:.1}" y="{py:.1}" width="{pw:.1}" height="{ph:.1}" rx="4" fill="{}"/>"#,
+                escape(&key.keycode),
+                count,
+                heat_color(count, max)
+            );
+            let _ = writeln!(
+                body,
+                r#"    <text x="{:.1}" y="{:.1}" class="label">{}</text><text x="{:.1}" y="{:.1}" class="count">{}</text></g>"#,
+                px + pw / 2.0,
+                py + ph / 2.0 - 2.0,
+                escape(&short_label(&key.keycode)),
+                px + pw / 2.0,
+                py + ph - 6.0,
+                if count > 0 {
+                    count.to_string()
+                } else {
+                    String::new()
+                }
+            );
+
+            width = width.max(px + pw);
+            bottom = bottom.max(py + ph);
+        }
+
+        for (index, position) in encoder_positions(geometry, layer).iter().enumerate() {
+            let encoder = layer.encoder(index);
+            let radius = (UNIT - GAP) / 2.0;
+            let cx = f32::from(position.col).mul_add(UNIT, radius);
+            let cy = f32::from(position.row).mul_add(UNIT, top + radius);
+
+            let _ = writeln!(
+                body,
+                r##"  <g><title>Encoder {index}: {} / {}</title><circle cx="{cx:.1}" cy="{cy:.1}" r="{radius:.1}" fill="#eeeeee" stroke="#999999"/>"##,
+                escape(&encoder.ccw),
+                escape(&encoder.cw)
+            );
+            let _ = writeln!(
+                body,
+                r#"    <text x="{cx:.1}" y="{:.1}" class="label">↺ {}</text><text x="{cx:.1}" y="{:.1}" class="label">↻ {}</text></g>"#,
+                cy - 3.0,
+                escape(&short_label(&encoder.ccw)),
+                cy + 11.0,
+                escape(&short_label(&encoder.cw))
+            );
+
+            width = width.max(cx + radius);
+            bottom = bottom.max(cy + radius);
+        }
+
+        y_offset = bottom + BLOCK_SPACING;
+    }
+
+    let height = (y_offset - BLOCK_SPACING).max(TITLE_HEIGHT);
+    let mut svg = String::new();
+    let _ = writeln!(
+        svg,
+        r#"<svg xmlns="http://www.w3.org/2000/svg" width="{width:.0}" height="{height:.0}" viewBox="0 0 {width:.1} {height:.1}" font-family="sans-serif">"#
+    );
+    svg.push_str("  <style>\n");
+    svg.push_str("    .title { font-size: 16px; font-weight: bold; fill: #333; }\n");
+    svg.push_str("    .label { font-size: 11px; text-anchor: middle; fill: #111; }\n");
+    svg.push_str("    .count { font-size: 9px; text-anchor: middle; fill: #333; }\n");
+    svg.push_str("  </style>\n");
+    svg.push_str(&body);
+    svg.push_str("</svg>\n");
+    svg
+}
+
+/// Renders the heatmap and its summary as a standalone HTML page.
+#[must_use]
+pub fn render_heatmap_html(
+    layout: &Layout,
+    heatmap: &Heatmap,
+    geometry: Option<&KeyboardGeometry>,
+    per_layer: bool,
+    skip_transparent: bool,
+) -> String {
+    let name = escape(&layout.metadata.name);
+    let mut html = String::new();
+    html.push_str("<!DOCTYPE html>\n<html>\n<head>\n<meta charset=\"utf-8\">\n");
+    let _ = writeln!(html, "<title>Key usage: {name}</title>");
+    html.push_str(
+        "<style>body { font-family: sans-serif; margin: 2em; } \
+         td, th { padding: 2px 12px; text-align: left; }</style>\n",
+    );
+    html.push_str("</head>\n<body>\n");
+    let _ = writeln!(html, "<h1>Key usage: {name}</h1>");
+    push_paragraphs(&mut html, &layout.metadata.description);
+    html.push_str(&render_heatmap_svg(
+        layout,
+        heatmap,
+        geometry,
+        per_layer,
+        skip_transparent,
+    ));
+    if layout
+        .layers
+        .iter()
+        .any(|layer| layer.description.is_some())
+    {
+        html.push_str("<h2>Layers</h2>\n");
+        for layer in &layout.layers {
+            if let Some(description) = &layer.description {
+                let _ = writeln!(
+                    html,
+                    "<h3>Layer {}: {}</h3>",
+                    layer.number,
+                    escape(&layer.name)
+                );
+                push_paragraphs(&mut html, description);
+            }
+        }
+    }
+    html.push_str("<h2>Summary</h2>\n<ul>\n");
+    for line in summary_lines(layout, heatmap) {
+        let _ = writeln!(html, "<li>{}</li>", escape(&line));
+    }
+    html.push_str("</ul>\n</body>\n</html>\n");
+    html
+}
+
+/// Writes prose as HTML paragraphs, one per blank-line separated block.
+fn push_paragraphs(html: &mut String, text: &str) {
+    for paragraph in text.split("\n\n").map(str::trim).filter(|p| !p.is_empty()) {
+        let _ = writeln!(html, "<p>{}</p>", escape(paragraph).replace('\n', "<br>\n"));
+    }
+}
+
+/// Summary statistics as human-readable lines.
+#[must_use]
+pub fn summary_lines(layout: &Layout, heatmap: &Heatmap) -> Vec<String> {
+    let mut lines = vec![format!(
+        "Presses: {} total, {} matched ({}), {} unmatched entries",
+        heatmap.total,
+        heatmap.matched,
+        percent(heatmap.matched, heatmap.total),
+        heatmap.unmatched.len()
+    )];
+
+    let layer_totals = heatmap.layer_totals(layout.layers.len());
+    let all_layers: u64 = layer_totals.iter().sum();
+    for (idx, total) in layer_totals.iter().enumerate() {
+        lines.push(format!(
+            "Layer {idx} ({}): {total} ({})",
+            layout.layers[idx].name,
+            percent(*total, all_layers)
+        ));
+    }
+    for (row, total) in heatmap.row_totals() {
+        lines.push(format!(
+            "Row {row}: {total} ({})",
+            percent(total, all_layers)
+        ));
+    }
+
+    let top: Vec<String> = heatmap
+        .top_keys(TOP_KEYS)
+        .iter()
+        .map(|usage| format!("{} [L{}] {}", usage.keycode, usage.layer, usage.count))
+        .collect();
+    if !top.is_empty() {
+        lines.push(format!("Most used: {}", top.join(", ")));
+    }
+    if !heatmap.unmatched.is_empty() {
+        let unmatched: Vec<&str> = heatmap
+            .unmatched
+            .iter()
+            .take(TOP_KEYS)
+            .map(|entry| entry.key.as_str())
+            .collect();
+        lines.push(format!("Not in layout: {}", unmatched.join(" ")));
+    }
+    lines
+}
+
+/// Grid positions of the encoders drawn next to `layer`.
+///
+/// Without geometry, the layer's own encoder assignments are placed after its
+/// last key.
+fn encoder_positions(geometry: Option<&KeyboardGeometry>, layer: &Layer) -> Vec<Position> {
+    geometry.map_or_else(
+        || {
+            let keys: Vec<Position> = layer.keys.iter().map(|key| key.position).collect();
+            place_encoders(&keys, &vec![None; layer.encoders.len()])
+        },
+        KeyboardGeometry::encoder_positions,
+    )
+}
+
+/// Key rectangle `(x, y, width, height)` in key units.
+fn key_box(geometry: Option<&KeyboardGeometry>, row: u8, col: u8) -> (f32, f32, f32, f32) {
+    let (row, col) = (f32::from(row), f32::from(col));
+    geometry
+        .and_then(|geometry| {
+            geometry
+                .keys
+                .iter()
+                .find(|key| (key.visual_y - row).abs() < 0.5 && (key.visual_x - col).abs() < 0.5)
+        })
+        .map_or((col, row, 1.0, 1.0), |key| {
+            (key.visual_x, key.visual_y, key.width, key.height)
+        })
+}
+
+/// Fill color for `count` on a blue → yellow → red scale relative to `max`.
+fn heat_color(count: u64, max: u64) -> String {
+    const COLD: (i64, i64, i64) = (49, 130, 189);
+    const WARM: (i64, i64, i64) = (254, 224, 139);
+    const HOT: (i64, i64, i64) = (215, 48, 39);
+
+    if count == 0 || max == 0 {
+        return "#e6e6e6".to_string();
+    }
+
+    // Position on the scale in thousandths
+    let t = i64::try_from(count.saturating_mul(1000) / max).unwrap_or(1000);
+    let (from, to, t) = if t <= 500 {
+        (COLD, WARM, t * 2)
+    } else {
+        (WARM, HOT, (t - 500) * 2)
+    };
+    let mix = |a: i64, b: i64| u8::try_from(a + (b - a) * t / 1000).unwrap_or(u8::MAX);
+    format!(
+        "#{:02x}{:02x}{:02x}",
+        mix(from.0, to.0),
+        mix(from.1, to.1),
+        mix(from.2, to.2)
+    )
+}
+
+/// Short key label: keycode without the `KC_` prefix.
+fn short_label(keycode: &str) -> String {
+    keycode.strip_prefix("KC_").unwrap_or(keycode).to_string()
+}
+
+/// Formats `part` as a percentage of `whole` with one decimal.
+fn percent(part: u64, whole: u64) -> String {
+    if whole == 0 {
+        return "0.0%".to_string();
+    }
+    let tenths = part.saturating_mul(1000) / whole;
+    format!("{}.{}%", tenths / 10, tenths % 10)
+}
+
+/// Escapes text for XML/HTML.
+fn escape(text: &str) -> String {
+    text.replace('&', "&amp;")
+        .replace('<', "&lt;")
+        .replace('>', "&gt;")
+        .replace('"', "&quot;")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_heat_color_scale() {
+        assert_eq!(heat_color(0, 10), "#e6e6e6");
+        assert_eq!(heat_color(1, 1000), "#3182bd");
+        assert_eq!(heat_color(10, 10), "#d73027");
+    }
+
+    #[test]
+    fn test_svg_skips_transparent_keys() {
+        use crate::models::{KeyDefinition, RgbColor};
+
+        let mut layer = Layer::new(0, "Base", RgbColor::new(0, 0, 0)).unwrap();
+        for (col, keycode) in [(0, "KC_A"), (1, "KC_TRNS")] {
+            layer
+                .add_key(KeyDefinition::new(Position::new(0, col), keycode))
+                .unwrap();
+        }
+        let mut layout = Layout::new("Test").unwrap();
+        layout.layers.push(layer);
+        let heatmap = Heatmap::compute(&layout, &[]);
+
+        assert!(render_heatmap_svg(&layout, &heatmap, None, false, false).contains("KC_TRNS"));
+        let svg = render_heatmap_svg(&layout, &heatmap, None, false, true);
+        assert!(!svg.contains("KC_TRNS"));
+        assert!(svg.contains("<title>KC_A (0)</title>"));
+    }
+
+    #[test]
+    fn test_svg_draws_encoders_after_last_key() {
+        use crate::models::{EncoderMapping, KeyDefinition, RgbColor};
+
+        let mut layer = Layer::new(0, "Base", RgbColor::new(0, 0, 0)).unwrap();
+        layer
+            .add_key(KeyDefinition::new(Position::new(0, 0), "KC_A"))
+            .unwrap();
+        layer.set_encoder(0, EncoderMapping::new("KC_VOLD", "KC_VOLU"));
+        let mut layout = Layout::new("Test").unwrap();
+        layout.layers.push(layer);
+
+        let heatmap = Heatmap::compute(&layout, &[]);
+        let svg = render_heatmap_svg(&layout, &heatmap, None, false, false);
+        assert!(svg.contains("<title>Encoder 0: KC_VOLD / KC_VOLU</title>"));
+        assert!(svg.contains(r#"<circle cx="79.5""#));
+    }
+
+    #[test]
+    fn test_percent() {
+        assert_eq!(percent(1, 3), "33.3%");
+        assert_eq!(percent(0, 0), "0.0%");
+    }
+}
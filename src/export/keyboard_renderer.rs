@@ -14,6 +14,7 @@ use std::collections::HashMap;
 /// * `layout` - The complete layout containing layer data
 /// * `layer_idx` - Index of the layer to render
 /// * `geometry` - Keyboard geometry for physical positioning
+/// * `skip_transparent` - Leave transparent (`KC_TRNS`) keys out of the diagram
 ///
 /// # Returns
 ///
@@ -32,6 +33,7 @@ pub fn render_layer_diagram(
     layout: &Layout,
     layer_idx: usize,
     geometry: &KeyboardGeometry,
+    skip_transparent: bool,
 ) -> Result<String> {
     let layer = layout
         .layers
@@ -45,7 +47,7 @@ pub fn render_layer_diagram(
     writeln!(output, "Layer {}: {}", layer_idx, layer.name).unwrap();
 
     // Build key grid with positioning
-    let key_grid = build_key_grid(layout, layer_idx, geometry)?;
+    let key_grid = build_key_grid(layout, layer_idx, geometry, skip_transparent)?;
 
     // Render the grid to ASCII/Unicode
     let diagram = render_grid(&key_grid);
@@ -85,6 +87,7 @@ fn build_key_grid(
     layout: &Layout,
     layer_idx: usize,
     geometry: &KeyboardGeometry,
+    skip_transparent: bool,
 ) -> Result<KeyGrid> {
     let layer = layout
         .layers
@@ -113,8 +116,8 @@ fn build_key_grid(
         // We need to find which key_def corresponds to this geometry key
 
         // Calculate the visual position from geometry
-        let visual_row = key_geom.visual_y.round() as u8;
-        let visual_col = key_geom.visual_x.round() as u8;
+        let visual = key_geom.visual_position();
+        let (visual_row, visual_col) = (visual.row, visual.col);
 
         // Look up key by visual position
         let key_def = match visual_to_key.get(&(visual_row, visual_col)) {
@@ -126,6 +129,10 @@ fn build_key_grid(
             }
         };
 
+        if skip_transparent && key_def.is_transparent() {
+            continue;
+        }
+
         // Convert visual position to grid coordinates
         // Use a simple scaling: divide by standard key width (assuming ~1u spacing)
         let row = (key_geom.visual_y / 1.25).round() as usize;
@@ -672,7 +679,9 @@ mod tests {
         for row in 0..3 {
             for col in 0..3 {
                 let keycode = format!("KC_{}", (b'A' + (row * 3 + col)) as char);
-                layer.add_key(KeyDefinition::new(Position::new(row, col), &keycode));
+                layer
+                    .add_key(KeyDefinition::new(Position::new(row, col), &keycode))
+                    .unwrap();
             }
         }
 
@@ -738,19 +747,32 @@ mod tests {
         let layout = create_test_layout();
         let geometry = create_test_geometry();
 
-        let grid = build_key_grid(&layout, 0, &geometry).unwrap();
+        let grid = build_key_grid(&layout, 0, &geometry, false).unwrap();
 
         assert_eq!(grid.keys.len(), 9); // 3x3 grid
         assert!(grid.max_row >= 2);
         assert!(grid.max_col >= 2);
     }
 
+    #[test]
+    fn test_build_key_grid_skips_transparent_keys() {
+        let mut layout = create_test_layout();
+        layout.layers[0].keys[4].keycode = "KC_TRNS".to_string();
+        let geometry = create_test_geometry();
+
+        let grid = build_key_grid(&layout, 0, &geometry, false).unwrap();
+        assert_eq!(grid.keys.len(), 9);
+        let grid = build_key_grid(&layout, 0, &geometry, true).unwrap();
+        assert_eq!(grid.keys.len(), 8);
+        assert!(grid.keys.iter().all(|key| key.label != "TRNS"));
+    }
+
     #[test]
     fn test_render_layer_diagram() {
         let layout = create_test_layout();
         let geometry = create_test_geometry();
 
-        let diagram = render_layer_diagram(&layout, 0, &geometry).unwrap();
+        let diagram = render_layer_diagram(&layout, 0, &geometry, false).unwrap();
 
         // Basic checks
         assert!(diagram.contains("Layer 0: Base"));
@@ -0,0 +1,363 @@
+//! Per-layer data exports for external visualization tools.
+//!
+//! Two formats are built from the same resolved key data:
+//! - keymap-drawer YAML (`layout` + `layers`), so layouts can be rendered
+//!   with that ecosystem's vector diagrams. Layouts don't store combos yet,
+//!   so no `combos` section is written.
+//! - A generic JSON dump of every layer's keys with their position, keycode,
+//!   legend, resolved color and category.
+//!
+//! Keys are listed in layout order (the order of the keyboard's info.json
+//! layout), and legends come from the keycode database's shared legend
+//! decoder, as in the editor.
+
+use anyhow::{Context, Result};
+use serde::ser::SerializeMap;
+use serde::{Serialize, Serializer};
+
+use crate::keycode_db::KeycodeDb;
+use crate::models::{KeyDefinition, Layout, Position, VisualLayoutMapping};
+
+/// Legend of a key split into its tap and hold parts.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct LegendData {
+    /// What tapping the key sends (e.g., "Space")
+    pub tap: String,
+    /// What holding or otherwise triggering it does (e.g., "L1 hold")
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub hold: Option<String>,
+}
+
+/// One key of a layer with its resolved data.
+#[derive(Debug, Clone, Serialize)]
+pub struct KeyData {
+    /// Index in the keyboard's layout order
+    pub layout_index: usize,
+    /// Visual position in the editor grid
+    pub position: Position,
+    /// Keycode as stored in the layout
+    pub keycode: String,
+    /// Legend shown for the key
+    pub legend: LegendData,
+    /// Resolved color as `#RRGGBB`
+    pub color: String,
+    /// Category of the key, if any
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub category: Option<String>,
+    /// Transparent (`KC_TRNS`)
+    #[serde(skip)]
+    transparent: bool,
+    /// Hold action alone, for tap/hold splits (e.g., "L1")
+    #[serde(skip)]
+    held: Option<String>,
+}
+
+/// Keys of one layer in layout order.
+#[derive(Debug, Clone, Serialize)]
+pub struct LayerData {
+    /// Layer index in the layout
+    pub index: usize,
+    /// Layer name
+    pub name: String,
+    /// Whether the layer is included in firmware
+    pub enabled: bool,
+    /// Keys in layout order
+    pub keys: Vec<KeyData>,
+}
+
+/// Collects every layer's keys in layout order with resolved legends and colors.
+pub fn collect_layers(
+    layout: &Layout,
+    mapping: &VisualLayoutMapping,
+    keycode_db: &KeycodeDb,
+) -> Result<Vec<LayerData>> {
+    layout
+        .layers
+        .iter()
+        .enumerate()
+        .map(|(index, layer)| {
+            let mut keys = layer
+                .keys
+                .iter()
+                .map(|key| {
+                    let layout_index = mapping
+                        .visual_to_layout_index(key.position.row, key.position.col)
+                        .with_context(|| {
+                            format!(
+                                "Failed to map visual position ({}, {}) to layout index",
+                                key.position.row, key.position.col
+                            )
+                        })?;
+                    Ok(key_data(
+                        layout,
+                        index,
+                        key,
+                        usize::from(layout_index),
+                        keycode_db,
+                    ))
+                })
+                .collect::<Result<Vec<_>>>()?;
+            keys.sort_by_key(|key| key.layout_index);
+            Ok(LayerData {
+                index,
+                name: layer.name.clone(),
+                enabled: layer.enabled,
+                keys,
+            })
+        })
+        .collect()
+}
+
+/// Resolved data of `key` on layer `layer_idx`.
+fn key_data(
+    layout: &Layout,
+    layer_idx: usize,
+    key: &KeyDefinition,
+    layout_index: usize,
+    keycode_db: &KeycodeDb,
+) -> KeyData {
+    let resolve_layer = |layer_ref: &str| resolve_layer_number(layout, layer_ref);
+    let (legend, held) = match keycode_db.key_legend(&key.keycode, resolve_layer) {
+        Some(legend) => (
+            LegendData {
+                tap: legend.tap,
+                hold: Some(legend.hold),
+            },
+            legend.held,
+        ),
+        None => (
+            LegendData {
+                tap: plain_legend(layout, &key.keycode, keycode_db),
+                hold: None,
+            },
+            None,
+        ),
+    };
+    KeyData {
+        layout_index,
+        position: key.position,
+        keycode: key.keycode.clone(),
+        legend,
+        color: layout.resolve_key_color(layer_idx, key).to_hex(),
+        category: key.category_id.clone(),
+        transparent: key.is_transparent(),
+        held,
+    }
+}
+
+/// Legend of a keycode without a tap/hold split: nothing for `KC_NO`, "▽"
+/// for transparent keys, layer keys with their layer number, and the
+/// database's display name otherwise.
+fn plain_legend(layout: &Layout, keycode: &str, keycode_db: &KeycodeDb) -> String {
+    match keycode {
+        "KC_NO" | "XXXXXXX" => String::new(),
+        "KC_TRNS" | "KC_TRANSPARENT" | "_______" => "▽".to_string(),
+        _ => match keycode_db.parse_layer_keycode(keycode) {
+            Some((prefix, layer_ref, suffix)) if suffix.is_empty() => {
+                format!("{prefix}({})", resolve_layer_number(layout, &layer_ref))
+            }
+            _ => keycode_db.display_name(keycode),
+        },
+    }
+}
+
+/// Layer number of a layer reference (`@id` or a number).
+fn resolve_layer_number(layout: &Layout, layer_ref: &str) -> String {
+    layer_ref
+        .strip_prefix('@')
+        .and_then(|id| layout.get_layer_index_by_id(id))
+        .map_or_else(|| layer_ref.to_string(), |index| index.to_string())
+}
+
+/// Root of the layers JSON dump.
+#[derive(Serialize)]
+struct LayersJson<'a> {
+    name: &'a str,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    keyboard: Option<&'a str>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    layout_variant: Option<&'a str>,
+    layers: &'a [LayerData],
+}
+
+/// Exports every layer's resolved keys as pretty-printed JSON.
+pub fn export_layers_json(
+    layout: &Layout,
+    mapping: &VisualLayoutMapping,
+    keycode_db: &KeycodeDb,
+) -> Result<String> {
+    let layers = collect_layers(layout, mapping, keycode_db)?;
+    let metadata = &layout.metadata;
+    let mut json = serde_json::to_string_pretty(&LayersJson {
+        name: &metadata.name,
+        keyboard: metadata.keyboard.as_deref(),
+        layout_variant: metadata.layout_variant.as_deref(),
+        layers: &layers,
+    })
+    .context("Failed to serialize layers")?;
+    json.push('\n');
+    Ok(json)
+}
+
+/// A key in keymap-drawer's notation: a plain legend or a tap/hold split.
+#[derive(Serialize)]
+#[serde(untagged)]
+enum DrawerKey {
+    Plain(String),
+    Split {
+        t: String,
+        #[serde(skip_serializing_if = "Option::is_none")]
+        h: Option<String>,
+        #[serde(rename = "type", skip_serializing_if = "Option::is_none")]
+        kind: Option<&'static str>,
+    },
+}
+
+impl From<&KeyData> for DrawerKey {
+    fn from(key: &KeyData) -> Self {
+        if key.transparent {
+            return Self::Split {
+                t: key.legend.tap.clone(),
+                h: None,
+                kind: Some("trans"),
+            };
+        }
+        match &key.legend.hold {
+            Some(hold) => Self::Split {
+                t: key.legend.tap.clone(),
+                h: Some(key.held.clone().unwrap_or_else(|| hold.clone())),
+                kind: None,
+            },
+            None => Self::Plain(key.legend.tap.clone()),
+        }
+    }
+}
+
+/// keymap-drawer's physical layout reference.
+#[derive(Serialize)]
+struct DrawerLayout<'a> {
+    qmk_keyboard: &'a str,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    layout_name: Option<&'a str>,
+}
+
+/// Layers by name, in layout order.
+struct DrawerLayers(Vec<(String, Vec<DrawerKey>)>);
+
+impl Serialize for DrawerLayers {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        let mut map = serializer.serialize_map(Some(self.0.len()))?;
+        for (name, keys) in &self.0 {
+            map.serialize_entry(name, keys)?;
+        }
+        map.end()
+    }
+}
+
+/// Root of the keymap-drawer YAML.
+#[derive(Serialize)]
+struct KeymapDrawer<'a> {
+    layout: DrawerLayout<'a>,
+    layers: DrawerLayers,
+}
+
+/// Exports the layout in keymap-drawer's YAML keymap format.
+pub fn export_keymap_drawer(
+    layout: &Layout,
+    mapping: &VisualLayoutMapping,
+    keycode_db: &KeycodeDb,
+) -> Result<String> {
+    let metadata = &layout.metadata;
+    let keyboard = metadata
+        .keyboard
+        .as_deref()
+        .context("Keyboard not set in layout metadata")?;
+
+    let mut names: Vec<String> = Vec::new();
+    let layers = collect_layers(layout, mapping, keycode_db)?
+        .iter()
+        .map(|layer| {
+            // keymap-drawer keys layers by name, so they must be unique
+            let mut name = layer.name.clone();
+            if names.contains(&name) {
+                name = format!("{name} {}", layer.index);
+            }
+            names.push(name.clone());
+            (name, layer.keys.iter().map(DrawerKey::from).collect())
+        })
+        .collect();
+
+    serde_yml::to_string(&KeymapDrawer {
+        layout: DrawerLayout {
+            qmk_keyboard: keyboard,
+            layout_name: metadata.layout_variant.as_deref(),
+        },
+        layers: DrawerLayers(layers),
+    })
+    .context("Failed to serialize keymap-drawer YAML")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::models::{KeyGeometry, KeyboardGeometry, Layer, RgbColor};
+
+    /// A 1x3 board with a tap-hold, a transparent and a layer key
+    fn fixture() -> (Layout, VisualLayoutMapping) {
+        let mut geometry = KeyboardGeometry::new("test", "LAYOUT", 1, 3);
+        // Layout order runs right to left
+        for col in 0..3u8 {
+            geometry.add_key(KeyGeometry::new((0, col), 2 - col, f32::from(col), 0.0));
+        }
+        let mut layout = Layout::new("Drawer").unwrap();
+        layout.metadata.keyboard = Some("test".to_string());
+        for (index, keycodes) in [
+            ["LT(1, KC_SPC)", "KC_TRNS", "MO(1)"],
+            ["KC_A", "KC_NO", "KC_MINS"],
+        ]
+        .iter()
+        .enumerate()
+        {
+            let mut layer = Layer::new(index as u8, "Layer", RgbColor::new(0, 0, 0)).unwrap();
+            for (col, keycode) in keycodes.iter().enumerate() {
+                layer
+                    .add_key(KeyDefinition::at(Position::new(0, col as u8)).keycode(*keycode))
+                    .unwrap();
+            }
+            layout.add_layer(layer).unwrap();
+        }
+        (layout, VisualLayoutMapping::build(&geometry))
+    }
+
+    #[test]
+    fn test_layers_are_in_layout_order_with_shared_legends() {
+        let (layout, mapping) = fixture();
+        let db = KeycodeDb::load().unwrap();
+        let layers = collect_layers(&layout, &mapping, &db).unwrap();
+
+        let legends: Vec<&str> = layers[0]
+            .keys
+            .iter()
+            .map(|k| k.legend.tap.as_str())
+            .collect();
+        assert_eq!(legends, ["MO(1)", "▽", "Space"]);
+        assert_eq!(layers[0].keys[2].legend.hold.as_deref(), Some("L1 hold"));
+        assert_eq!(layers[1].keys[0].legend.tap, "-");
+    }
+
+    #[test]
+    fn test_keymap_drawer_splits_tap_hold_and_names_layers_uniquely() {
+        let (layout, mapping) = fixture();
+        let db = KeycodeDb::load().unwrap();
+        let yaml = export_keymap_drawer(&layout, &mapping, &db).unwrap();
+
+        let parsed: serde_yml::Value = serde_yml::from_str(&yaml).unwrap();
+        assert_eq!(parsed["layout"]["qmk_keyboard"], "test");
+        let base = &parsed["layers"]["Layer"];
+        assert_eq!(base[0], "MO(1)");
+        assert_eq!(base[1]["type"], "trans");
+        assert_eq!(base[2]["t"], "Space");
+        assert_eq!(base[2]["h"], "L1");
+        assert_eq!(parsed["layers"]["Layer 1"][1], "");
+    }
+}
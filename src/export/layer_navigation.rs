@@ -184,7 +184,9 @@ mod tests {
     fn test_generate_layer_navigation_no_refs() {
         let mut layout = Layout::new("Test").unwrap();
         let mut layer0 = Layer::new(0, "Base", RgbColor::new(255, 0, 0)).unwrap();
-        layer0.add_key(KeyDefinition::new(Position::new(0, 0), "KC_A"));
+        layer0
+            .add_key(KeyDefinition::new(Position::new(0, 0), "KC_A"))
+            .unwrap();
         layout.add_layer(layer0).unwrap();
 
         let output = generate_layer_navigation(&layout);
@@ -199,7 +201,9 @@ mod tests {
         let mut layout = Layout::new("Test").unwrap();
 
         let mut layer0 = Layer::new(0, "Base", RgbColor::new(255, 0, 0)).unwrap();
-        layer0.add_key(KeyDefinition::new(Position::new(0, 0), "MO(1)"));
+        layer0
+            .add_key(KeyDefinition::new(Position::new(0, 0), "MO(1)"))
+            .unwrap();
         layout.add_layer(layer0).unwrap();
 
         let layer1 = Layer::new(1, "Lower", RgbColor::new(0, 255, 0)).unwrap();
@@ -218,9 +222,15 @@ mod tests {
         let mut layout = Layout::new("Test").unwrap();
 
         let mut layer0 = Layer::new(0, "Base", RgbColor::new(255, 0, 0)).unwrap();
-        layer0.add_key(KeyDefinition::new(Position::new(0, 0), "MO(1)"));
-        layer0.add_key(KeyDefinition::new(Position::new(0, 1), "LT(2, KC_SPC)"));
-        layer0.add_key(KeyDefinition::new(Position::new(0, 2), "TG(3)"));
+        layer0
+            .add_key(KeyDefinition::new(Position::new(0, 0), "MO(1)"))
+            .unwrap();
+        layer0
+            .add_key(KeyDefinition::new(Position::new(0, 1), "LT(2, KC_SPC)"))
+            .unwrap();
+        layer0
+            .add_key(KeyDefinition::new(Position::new(0, 2), "TG(3)"))
+            .unwrap();
         layout.add_layer(layer0).unwrap();
 
         let layer1 = Layer::new(1, "Lower", RgbColor::new(0, 255, 0)).unwrap();
@@ -245,11 +255,15 @@ mod tests {
         let mut layout = Layout::new("Test").unwrap();
 
         let mut layer0 = Layer::new(0, "Base", RgbColor::new(255, 0, 0)).unwrap();
-        layer0.add_key(KeyDefinition::new(Position::new(0, 0), "MO(1)"));
+        layer0
+            .add_key(KeyDefinition::new(Position::new(0, 0), "MO(1)"))
+            .unwrap();
         layout.add_layer(layer0).unwrap();
 
         let mut layer1 = Layer::new(1, "Lower", RgbColor::new(0, 255, 0)).unwrap();
-        layer1.add_key(KeyDefinition::new(Position::new(1, 0), "TO(0)")); // Back to base
+        layer1
+            .add_key(KeyDefinition::new(Position::new(1, 0), "TO(0)"))
+            .unwrap(); // Back to base
         layout.add_layer(layer1).unwrap();
 
         let output = generate_layer_navigation(&layout);
@@ -265,9 +279,15 @@ mod tests {
         let mut layout = Layout::new("Test").unwrap();
 
         let mut layer0 = Layer::new(0, "Base", RgbColor::new(255, 0, 0)).unwrap();
-        layer0.add_key(KeyDefinition::new(Position::new(0, 0), "KC_TRNS"));
-        layer0.add_key(KeyDefinition::new(Position::new(0, 1), "KC_NO"));
-        layer0.add_key(KeyDefinition::new(Position::new(0, 2), "MO(1)"));
+        layer0
+            .add_key(KeyDefinition::new(Position::new(0, 0), "KC_TRNS"))
+            .unwrap();
+        layer0
+            .add_key(KeyDefinition::new(Position::new(0, 1), "KC_NO"))
+            .unwrap();
+        layer0
+            .add_key(KeyDefinition::new(Position::new(0, 2), "MO(1)"))
+            .unwrap();
         layout.add_layer(layer0).unwrap();
 
         let layer1 = Layer::new(1, "Lower", RgbColor::new(0, 255, 0)).unwrap();
@@ -286,7 +306,9 @@ mod tests {
         let mut layout = Layout::new("Test").unwrap();
 
         let mut layer0 = Layer::new(0, "Base", RgbColor::new(255, 0, 0)).unwrap();
-        layer0.add_key(KeyDefinition::new(Position::new(0, 0), "MO(99)")); // Out of bounds
+        layer0
+            .add_key(KeyDefinition::new(Position::new(0, 0), "MO(99)"))
+            .unwrap(); // Out of bounds
         layout.add_layer(layer0).unwrap();
 
         let output = generate_layer_navigation(&layout);
@@ -308,8 +330,12 @@ mod tests {
 
         let mut layer0 = Layer::new(0, "Base", RgbColor::new(255, 0, 0)).unwrap();
         // Add same reference twice at different positions
-        layer0.add_key(KeyDefinition::new(Position::new(0, 0), "MO(1)"));
-        layer0.add_key(KeyDefinition::new(Position::new(0, 1), "MO(1)"));
+        layer0
+            .add_key(KeyDefinition::new(Position::new(0, 0), "MO(1)"))
+            .unwrap();
+        layer0
+            .add_key(KeyDefinition::new(Position::new(0, 1), "MO(1)"))
+            .unwrap();
         layout.add_layer(layer0).unwrap();
 
         let layer1 = Layer::new(1, "Lower", RgbColor::new(0, 255, 0)).unwrap();
@@ -330,8 +356,12 @@ mod tests {
         let mut layout = Layout::new("Test").unwrap();
 
         let mut layer0 = Layer::new(0, "Base", RgbColor::new(255, 0, 0)).unwrap();
-        layer0.add_key(KeyDefinition::new(Position::new(0, 0), "MO(1)"));
-        layer0.add_key(KeyDefinition::new(Position::new(0, 1), "LT(2, KC_SPC)"));
+        layer0
+            .add_key(KeyDefinition::new(Position::new(0, 0), "MO(1)"))
+            .unwrap();
+        layer0
+            .add_key(KeyDefinition::new(Position::new(0, 1), "LT(2, KC_SPC)"))
+            .unwrap();
         layout.add_layer(layer0).unwrap();
 
         let layer1 = Layer::new(1, "Lower", RgbColor::new(0, 255, 0)).unwrap();
@@ -1,22 +1,25 @@
 //! Export functionality for keyboard layouts.
 //!
-//! This module provides tools to export keyboard layout configurations in various formats,
-//! currently focused on generating markdown documentation with visual representations
-//! and configuration summaries.
+//! This module provides tools to export keyboard layout configurations in various formats:
+//! markdown documentation with visual representations and configuration summaries, and
+//! per-layer data for external visualization tools (see [`layer_data`]).
 
 use crate::keycode_db::KeycodeDb;
-use crate::models::{KeyboardGeometry, Layout};
+use crate::models::{KeyboardGeometry, Layer, Layout};
 use anyhow::Result;
 use std::fmt::Write as _;
 
 pub mod color_legend;
+pub mod heatmap;
 pub mod keyboard_renderer;
+pub mod layer_data;
 pub mod layer_navigation;
 pub mod settings_summary;
 pub mod tap_dance_docs;
 
 pub use color_legend::generate_color_legend;
 pub use keyboard_renderer::render_layer_diagram;
+pub use layer_data::{export_keymap_drawer, export_layers_json};
 pub use layer_navigation::generate_layer_navigation;
 pub use settings_summary::generate_settings_summary;
 pub use tap_dance_docs::generate_tap_dance_docs;
@@ -32,10 +35,13 @@ pub use tap_dance_docs::generate_tap_dance_docs;
 /// - Layer navigation map
 /// - Tap dance documentation
 /// - Settings summary
+///
+/// With `skip_transparent`, transparent keys are left out of the diagrams.
 pub fn export_to_markdown(
     layout: &Layout,
     geometry: &KeyboardGeometry,
     keycode_db: &KeycodeDb,
+    skip_transparent: bool,
 ) -> Result<String> {
     let mut output = String::new();
 
@@ -47,10 +53,16 @@ pub fn export_to_markdown(
 
     // 3. Keyboard Overview (Base Layer)
     output.push_str("## Keyboard Layout\n\n");
-    let base_diagram = render_layer_diagram(layout, 0, geometry)?;
+    if let Some(base) = layout.layers.first() {
+        push_layer_description(&mut output, base);
+    }
+    let base_diagram = render_layer_diagram(layout, 0, geometry, skip_transparent)?;
     output.push_str("```\n");
     output.push_str(&base_diagram);
     output.push_str("```\n\n");
+    if let Some(base) = layout.layers.first() {
+        push_encoders(&mut output, base, geometry);
+    }
 
     // 4. Layer-by-Layer Diagrams
     for (idx, layer) in layout.layers.iter().enumerate() {
@@ -58,12 +70,14 @@ pub fn export_to_markdown(
             continue; // Base layer already shown
         }
 
-        let layer_diagram = render_layer_diagram(layout, idx, geometry)?;
+        let layer_diagram = render_layer_diagram(layout, idx, geometry, skip_transparent)?;
 
         let _ = writeln!(output, "## Layer {idx}: {}\n", layer.name);
+        push_layer_description(&mut output, layer);
         output.push_str("```\n");
         output.push_str(&layer_diagram);
         output.push_str("```\n\n");
+        push_encoders(&mut output, layer, geometry);
 
         // Add layer metadata
         let _ = writeln!(
@@ -127,6 +141,31 @@ fn generate_header(output: &mut String, layout: &Layout) {
     }
 }
 
+/// Write a layer's description as prose under its heading, if it has one
+fn push_layer_description(output: &mut String, layer: &Layer) {
+    if let Some(description) = &layer.description {
+        output.push_str(description.trim());
+        output.push_str("\n\n");
+    }
+}
+
+/// List what each of the keyboard's encoders does on a layer
+fn push_encoders(output: &mut String, layer: &Layer, geometry: &KeyboardGeometry) {
+    if geometry.encoder_count == 0 {
+        return;
+    }
+    output.push_str("**Encoders:**\n\n");
+    for index in 0..usize::from(geometry.encoder_count) {
+        let encoder = layer.encoder(index);
+        let _ = writeln!(
+            output,
+            "- E{index}: ↺ `{}` / ↻ `{}`",
+            encoder.ccw, encoder.cw
+        );
+    }
+    output.push('\n');
+}
+
 /// Generate the quick reference section
 fn generate_quick_reference(output: &mut String, layout: &Layout) {
     output.push_str("## Quick Reference\n\n");
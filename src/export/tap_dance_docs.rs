@@ -63,6 +63,13 @@ pub fn generate_tap_dance_docs(layout: &Layout, keycode_db: &KeycodeDb) -> Strin
             let _ = writeln!(output, "- **Hold:** {}", hold_display);
         }
 
+        if let Some(term) = tap_dance.tapping_term_ms {
+            let _ = writeln!(output, "- **Tapping Term:** {term}ms");
+        }
+        if tap_dance.on_each_tap {
+            let _ = writeln!(output, "- **On Each Tap:** single tap sent immediately");
+        }
+
         output.push('\n');
     }
 
@@ -187,7 +194,7 @@ mod tests {
         // Add layer with keys referencing the tap dance
         let mut layer = Layer::new(0, "Base", RgbColor::new(255, 255, 255)).unwrap();
         let key_ref = KeyDefinition::new(Position::new(2, 3), "TD(quote_dance)");
-        layer.add_key(key_ref);
+        layer.add_key(key_ref).unwrap();
         layout.add_layer(layer).unwrap();
 
         let db = KeycodeDb::load().unwrap();
@@ -209,8 +216,12 @@ mod tests {
 
         // Add layer with multiple references
         let mut layer = Layer::new(0, "Base", RgbColor::new(255, 255, 255)).unwrap();
-        layer.add_key(KeyDefinition::new(Position::new(0, 0), "TD(td1)"));
-        layer.add_key(KeyDefinition::new(Position::new(1, 5), "TD(td2)"));
+        layer
+            .add_key(KeyDefinition::new(Position::new(0, 0), "TD(td1)"))
+            .unwrap();
+        layer
+            .add_key(KeyDefinition::new(Position::new(1, 5), "TD(td2)"))
+            .unwrap();
         layout.add_layer(layer).unwrap();
 
         let db = KeycodeDb::load().unwrap();
@@ -230,14 +241,22 @@ mod tests {
 
         // Add layer 0
         let mut layer0 = Layer::new(0, "Base", RgbColor::new(255, 255, 255)).unwrap();
-        layer0.add_key(KeyDefinition::new(Position::new(0, 0), "KC_A"));
-        layer0.add_key(KeyDefinition::new(Position::new(0, 1), "TD(dance)"));
+        layer0
+            .add_key(KeyDefinition::new(Position::new(0, 0), "KC_A"))
+            .unwrap();
+        layer0
+            .add_key(KeyDefinition::new(Position::new(0, 1), "TD(dance)"))
+            .unwrap();
         layout.add_layer(layer0).unwrap();
 
         // Add layer 1
         let mut layer1 = Layer::new(1, "Alt", RgbColor::new(255, 0, 0)).unwrap();
-        layer1.add_key(KeyDefinition::new(Position::new(0, 0), "KC_B"));
-        layer1.add_key(KeyDefinition::new(Position::new(1, 3), "TD(dance)"));
+        layer1
+            .add_key(KeyDefinition::new(Position::new(0, 0), "KC_B"))
+            .unwrap();
+        layer1
+            .add_key(KeyDefinition::new(Position::new(1, 3), "TD(dance)"))
+            .unwrap();
         layout.add_layer(layer1).unwrap();
 
         let db = KeycodeDb::load().unwrap();
@@ -257,7 +276,9 @@ mod tests {
 
         // Add a regular key
         let mut layer = Layer::new(0, "Base", RgbColor::new(255, 255, 255)).unwrap();
-        layer.add_key(KeyDefinition::new(Position::new(0, 0), "KC_B"));
+        layer
+            .add_key(KeyDefinition::new(Position::new(0, 0), "KC_B"))
+            .unwrap();
         layout.add_layer(layer).unwrap();
 
         let db = KeycodeDb::load().unwrap();
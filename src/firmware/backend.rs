@@ -0,0 +1,161 @@
+//! Build backends: the command that compiles a keymap in the QMK tree.
+//!
+//! `qmk compile` sets up the environment and parallelism itself; direct
+//! `make <keyboard>:<keymap>` works without the qmk CLI. Both drive QMK's
+//! make-based build, so they print the same compiler output and size report
+//! and the rest of the build pipeline doesn't care which one ran.
+
+use std::fmt;
+use std::path::Path;
+use std::process::{Command, Stdio};
+
+use super::converter;
+use super::environment::find_executable;
+use crate::config::BuildBackend;
+
+/// A resolved build backend (what [`BuildBackend::Auto`] picked).
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Backend {
+    /// Direct `make` in the QMK tree
+    Make,
+    /// The qmk CLI's `qmk compile`
+    QmkCli,
+}
+
+impl Backend {
+    /// Backend to use for `choice` in the QMK tree at `qmk_path`.
+    ///
+    /// `auto` prefers the qmk CLI when it is on `PATH` and the tree supports
+    /// it, falling back to `make`.
+    #[must_use]
+    pub fn resolve(choice: BuildBackend, qmk_path: &Path) -> Self {
+        Self::resolve_with(choice, qmk_path, find_executable("qmk").is_some())
+    }
+
+    /// [`Self::resolve`] with the qmk CLI lookup given, for tests.
+    #[must_use]
+    pub fn resolve_with(choice: BuildBackend, qmk_path: &Path, qmk_on_path: bool) -> Self {
+        match choice {
+            BuildBackend::Make => Self::Make,
+            BuildBackend::Qmk => Self::QmkCli,
+            BuildBackend::Auto if qmk_on_path && supports_qmk_cli(qmk_path) => Self::QmkCli,
+            BuildBackend::Auto => Self::Make,
+        }
+    }
+
+    /// Program the backend runs.
+    #[must_use]
+    pub const fn program(self) -> &'static str {
+        match self {
+            Self::Make => "make",
+            Self::QmkCli => "qmk",
+        }
+    }
+
+    /// Arguments that build `keymap` for `keyboard`, passing `converter` as
+    /// `CONVERT_TO`.
+    #[must_use]
+    pub fn args(self, keyboard: &str, keymap: &str, converter: Option<&str>) -> Vec<String> {
+        match self {
+            Self::Make => {
+                let jobs = std::thread::available_parallelism().map_or(1, usize::from);
+                let mut args = vec![format!("{keyboard}:{keymap}"), format!("-j{jobs}")];
+                if let Some(converter) = converter {
+                    args.push(format!("CONVERT_TO={converter}"));
+                }
+                args
+            }
+            Self::QmkCli => converter::compile_args(keyboard, keymap, converter),
+        }
+    }
+
+    /// The build command as shown to the user.
+    #[must_use]
+    pub fn command_line(self, keyboard: &str, keymap: &str, converter: Option<&str>) -> String {
+        match self {
+            Self::Make => format!(
+                "{} {}",
+                self.program(),
+                self.args(keyboard, keymap, converter).join(" ")
+            ),
+            Self::QmkCli => converter::compile_command(keyboard, keymap, converter),
+        }
+    }
+
+    /// The build command, run in `qmk_path` with its output piped.
+    #[must_use]
+    pub fn command(
+        self,
+        qmk_path: &Path,
+        keyboard: &str,
+        keymap: &str,
+        converter: Option<&str>,
+    ) -> Command {
+        let mut cmd = Command::new(self.program());
+        cmd.args(self.args(keyboard, keymap, converter))
+            .current_dir(qmk_path)
+            .stdout(Stdio::piped())
+            .stderr(Stdio::piped());
+        cmd
+    }
+}
+
+impl fmt::Display for Backend {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Make => write!(f, "make"),
+            Self::QmkCli => write!(f, "qmk CLI"),
+        }
+    }
+}
+
+/// Whether the QMK tree at `qmk_path` ships the Python library `qmk compile`
+/// runs from (trees older than the qmk CLI only build with `make`).
+#[must_use]
+pub fn supports_qmk_cli(qmk_path: &Path) -> bool {
+    qmk_path.join("lib").join("python").join("qmk").is_dir()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tempfile::TempDir;
+
+    #[test]
+    fn test_auto_prefers_qmk_cli_when_available() {
+        let old_tree = TempDir::new().unwrap();
+        let new_tree = TempDir::new().unwrap();
+        std::fs::create_dir_all(new_tree.path().join("lib/python/qmk")).unwrap();
+
+        let auto = |path: &Path, on_path| Backend::resolve_with(BuildBackend::Auto, path, on_path);
+        assert_eq!(auto(new_tree.path(), true), Backend::QmkCli);
+        assert_eq!(auto(new_tree.path(), false), Backend::Make);
+        assert_eq!(auto(old_tree.path(), true), Backend::Make);
+
+        // Explicit choices are kept whatever is installed
+        assert_eq!(
+            Backend::resolve_with(BuildBackend::Qmk, old_tree.path(), false),
+            Backend::QmkCli
+        );
+        assert_eq!(
+            Backend::resolve_with(BuildBackend::Make, new_tree.path(), true),
+            Backend::Make
+        );
+    }
+
+    #[test]
+    fn test_backend_command_lines() {
+        assert_eq!(
+            Backend::QmkCli.command_line("crkbd/rev1", "default", Some("rp2040_ce")),
+            "qmk compile -kb crkbd/rev1 -km default -e CONVERT_TO=rp2040_ce"
+        );
+
+        let args = Backend::Make.args("crkbd/rev1", "default", Some("rp2040_ce"));
+        assert_eq!(args[0], "crkbd/rev1:default");
+        assert!(args[1].starts_with("-j"));
+        assert_eq!(args[2], "CONVERT_TO=rp2040_ce");
+        assert!(Backend::Make
+            .command_line("crkbd/rev1", "default", None)
+            .starts_with("make crkbd/rev1:default -j"));
+    }
+}
@@ -6,11 +6,16 @@
 // Allow small types passed by reference for API consistency
 #![allow(clippy::trivially_copy_pass_by_ref)]
 
+use super::backend::Backend;
+use super::completion::{BuildCompletion, CompletionHooks};
+use super::converter;
+use super::size::{parse_size_output, FirmwareSize, McuSpec};
+use crate::config::BuildBackend;
 use anyhow::{Context, Result};
 use std::path::PathBuf;
-use std::process::{Command, Stdio};
 use std::sync::mpsc::{channel, Receiver, Sender};
 use std::thread;
+use std::time::{Duration, Instant};
 
 /// Build status tracking.
 #[derive(Debug, Clone, PartialEq, Eq)]
@@ -68,6 +73,8 @@ pub enum BuildMessage {
         firmware_path: Option<PathBuf>,
         /// Error message if build failed
         error: Option<String>,
+        /// Flash/RAM usage parsed from the build output
+        size: Option<FirmwareSize>,
     },
 }
 
@@ -105,17 +112,32 @@ pub struct BuildState {
     pub log_lines: Vec<(LogLevel, String)>,
     /// Last status message
     pub last_message: String,
+    /// Flash/RAM usage of the last successful build
+    pub size: Option<FirmwareSize>,
+    /// Subscribers told when a build completes
+    pub hooks: CompletionHooks,
+    /// Backend the next build runs with (`auto` picks one per build)
+    pub backend: BuildBackend,
+    /// `keyboard:keymap` of the current build
+    target: String,
+    /// When the current build started
+    started_at: Option<Instant>,
 }
 
 impl BuildState {
     /// Creates a new idle build state.
     #[must_use]
-    pub const fn new() -> Self {
+    pub fn new() -> Self {
         Self {
             status: BuildStatus::Idle,
             receiver: None,
             log_lines: Vec::new(),
             last_message: String::new(),
+            size: None,
+            hooks: CompletionHooks::new(),
+            backend: BuildBackend::Auto,
+            target: String::new(),
+            started_at: None,
         }
     }
 
@@ -135,7 +157,7 @@ impl BuildState {
         if let Some(receiver) = &self.receiver {
             match receiver.try_recv() {
                 Ok(message) => {
-                    self.handle_message(message);
+                    self.apply(message);
                     true
                 }
                 Err(std::sync::mpsc::TryRecvError::Empty) => false,
@@ -150,8 +172,10 @@ impl BuildState {
         }
     }
 
-    /// Handles a build message.
-    fn handle_message(&mut self, message: BuildMessage) {
+    /// Applies a build message to the state.
+    ///
+    /// A `Complete` message notifies the completion hooks.
+    pub fn apply(&mut self, message: BuildMessage) {
         match message {
             BuildMessage::Progress { status, message } => {
                 self.status = status.clone();
@@ -166,6 +190,7 @@ impl BuildState {
                 success,
                 firmware_path,
                 error,
+                size,
             } => {
                 self.status = if success {
                     BuildStatus::Success
@@ -173,30 +198,48 @@ impl BuildState {
                     BuildStatus::Failed
                 };
 
+                if let Some(size) = &size {
+                    self.log_lines
+                        .push((LogLevel::Ok, format!("Firmware size: {}", size.summary())));
+                }
+                self.size = size;
+
                 if let Some(path) = firmware_path {
                     self.last_message = format!("Firmware written to {}", path.display());
                     self.log_lines
                         .push((LogLevel::Ok, self.last_message.clone()));
                 }
 
+                let duration = self
+                    .started_at
+                    .take()
+                    .map_or(Duration::ZERO, |started| started.elapsed());
+                let completion =
+                    BuildCompletion::new(success, self.target.clone(), duration, error.clone());
+
                 if let Some(err) = error {
                     self.last_message.clone_from(&err);
                     self.log_lines.push((LogLevel::Error, err));
                 }
 
                 self.receiver = None;
+                self.hooks.notify(&completion);
             }
         }
     }
 
     /// Starts a build in the background.
     ///
-    /// Returns a receiver for build messages.
+    /// `converter` is passed as `CONVERT_TO` for replacement controllers.
+    /// `mcu` supplies flash/RAM capacity for the size report when the build
+    /// output doesn't include it.
     pub fn start_build(
         &mut self,
         qmk_path: PathBuf,
         keyboard: String,
         keymap: String,
+        converter: Option<String>,
+        mcu: Option<&'static McuSpec>,
     ) -> Result<()> {
         if self.is_building() {
             anyhow::bail!("Build already in progress");
@@ -207,14 +250,27 @@ impl BuildState {
         self.status = BuildStatus::Compiling;
         self.log_lines.clear();
         self.last_message = "Starting build...".to_string();
+        self.size = None;
+        self.target = format!("{keyboard}:{keymap}");
+        self.started_at = Some(Instant::now());
+        let backend = Backend::resolve(self.backend, &qmk_path);
 
         // Spawn background thread
         thread::spawn(move || {
-            if let Err(e) = run_build(sender.clone(), qmk_path, keyboard, keymap) {
+            if let Err(e) = run_build(
+                sender.clone(),
+                backend,
+                qmk_path,
+                keyboard,
+                keymap,
+                converter,
+                mcu,
+            ) {
                 let _ = sender.send(BuildMessage::Complete {
                     success: false,
                     firmware_path: None,
                     error: Some(format!("Build failed: {e}")),
+                    size: None,
                 });
             }
         });
@@ -234,12 +290,16 @@ impl Default for BuildState {
 /// The keyboard parameter may include variant subdirectories (e.g., "`keebart/corne_choc_pro/standard`").
 /// QMK's build system will use the variant-specific keyboard.json for configuration.
 ///
-/// Uses `qmk compile` CLI command which is the standard way to build QMK firmware.
+/// Runs `qmk compile` or `make` depending on `backend`; both print the same
+/// compiler output and size report.
 fn run_build(
     sender: Sender<BuildMessage>,
+    backend: Backend,
     qmk_path: PathBuf,
     keyboard: String,
     keymap: String,
+    converter: Option<String>,
+    mcu: Option<&'static McuSpec>,
 ) -> Result<()> {
     // Send progress: Compiling
     sender
@@ -252,25 +312,17 @@ fn run_build(
     sender
         .send(BuildMessage::Log {
             level: LogLevel::Info,
-            message: format!("Running: qmk compile -kb {keyboard} -km {keymap}"),
+            message: format!(
+                "Running ({backend}): {}",
+                backend.command_line(&keyboard, &keymap, converter.as_deref())
+            ),
         })
         .ok();
 
-    // Build using qmk compile command (standard QMK CLI)
-    let mut cmd = Command::new("qmk");
-    cmd.arg("compile")
-        .arg("-kb")
-        .arg(&keyboard)
-        .arg("-km")
-        .arg(&keymap)
-        .current_dir(&qmk_path)
-        .stdout(Stdio::piped())
-        .stderr(Stdio::piped());
-
-    // Execute command
-    let output = cmd
+    let output = backend
+        .command(&qmk_path, &keyboard, &keymap, converter.as_deref())
         .output()
-        .context("Failed to execute qmk compile command")?;
+        .with_context(|| format!("Failed to execute {}", backend.program()))?;
 
     // Parse output
     let stdout = String::from_utf8_lossy(&output.stdout);
@@ -309,13 +361,16 @@ fn run_build(
     // Check success
     if output.status.success() {
         // Find firmware file
-        let firmware_path = find_firmware_file(&qmk_path, &keyboard, &keymap)?;
+        let firmware_path =
+            find_firmware_file(&qmk_path, &keyboard, &keymap, converter.as_deref())?;
+        let size = parse_size_output(&stdout, mcu);
 
         sender
             .send(BuildMessage::Complete {
                 success: true,
                 firmware_path: Some(firmware_path),
                 error: None,
+                size,
             })
             .ok();
     } else {
@@ -323,7 +378,10 @@ fn run_build(
             .send(BuildMessage::Complete {
                 success: false,
                 firmware_path: None,
-                error: Some("qmk compile command failed. Check build log for details.".to_string()),
+                error: Some(format!(
+                    "Build with {backend} failed. Check build log for details."
+                )),
+                size: None,
             })
             .ok();
     }
@@ -333,16 +391,19 @@ fn run_build(
 
 /// Finds the compiled firmware file.
 ///
-/// QMK typically outputs to .build/{keyboard}_{keymap}.{ext}
-fn find_firmware_file(qmk_path: &PathBuf, keyboard: &str, keymap: &str) -> Result<PathBuf> {
-    // Clean keyboard path (replace / with _)
-    let keyboard_clean = keyboard.replace('/', "_");
-
-    // Try common firmware extensions in order
-    let extensions = ["uf2", "hex", "bin"];
-
-    for ext in &extensions {
-        let firmware_name = format!("{keyboard_clean}_{keymap}.{ext}");
+/// QMK typically outputs to .build/{keyboard}_{keymap}.{ext}, with
+/// `_{converter}` appended when building for a converter.
+fn find_firmware_file(
+    qmk_path: &PathBuf,
+    keyboard: &str,
+    keymap: &str,
+    converter: Option<&str>,
+) -> Result<PathBuf> {
+    let basename = converter::firmware_basename(keyboard, keymap, converter);
+
+    // Try the converter's format first, then the common extensions
+    for ext in converter::firmware_extensions(converter) {
+        let firmware_name = format!("{basename}.{ext}");
         let firmware_path = qmk_path.join(".build").join(&firmware_name);
 
         if firmware_path.exists() {
@@ -394,7 +455,7 @@ mod tests {
             message: "Test".to_string(),
         };
 
-        state.handle_message(message);
+        state.apply(message);
         assert_eq!(state.status, BuildStatus::Compiling);
         assert_eq!(state.last_message, "Test");
         assert_eq!(state.log_lines.len(), 1);
@@ -407,11 +468,36 @@ mod tests {
             success: true,
             firmware_path: Some(PathBuf::from("/test/firmware.uf2")),
             error: None,
+            size: None,
         };
 
-        state.handle_message(message);
+        state.apply(message);
         assert_eq!(state.status, BuildStatus::Success);
         assert!(state.last_message.contains("firmware.uf2"));
+        assert!(state.size.is_none());
+    }
+
+    #[test]
+    fn test_build_message_complete_with_size() {
+        let mut state = BuildState::new();
+        let size = FirmwareSize {
+            flash_used: 26_904,
+            flash_max: Some(28_672),
+            ram_used: None,
+            ram_max: None,
+        };
+        state.apply(BuildMessage::Complete {
+            success: true,
+            firmware_path: None,
+            error: None,
+            size: Some(size),
+        });
+
+        assert_eq!(state.size, Some(size));
+        assert!(state
+            .log_lines
+            .iter()
+            .any(|(_, line)| line == "Firmware size: Flash 26904/28672 bytes (93%)"));
     }
 
     #[test]
@@ -421,13 +507,45 @@ mod tests {
             success: false,
             firmware_path: None,
             error: Some("Build failed".to_string()),
+            size: None,
         };
 
-        state.handle_message(message);
+        state.apply(message);
         assert_eq!(state.status, BuildStatus::Failed);
         assert_eq!(state.last_message, "Build failed");
     }
 
+    #[test]
+    fn test_build_complete_notifies_hooks() {
+        let mut state = BuildState::new();
+        state.target = "crkbd:default".to_string();
+        state.started_at = Some(Instant::now());
+        let completions = std::sync::Arc::new(std::sync::Mutex::new(Vec::new()));
+        let seen = std::sync::Arc::clone(&completions);
+        state
+            .hooks
+            .subscribe(move |completion| seen.lock().unwrap().push(completion.clone()));
+
+        state.apply(BuildMessage::Log {
+            level: LogLevel::Info,
+            message: "Compiling".to_string(),
+        });
+        assert!(completions.lock().unwrap().is_empty());
+
+        state.apply(BuildMessage::Complete {
+            success: false,
+            firmware_path: None,
+            error: Some("make error".to_string()),
+            size: None,
+        });
+        let completions = completions.lock().unwrap().clone();
+        assert_eq!(completions.len(), 1);
+        assert!(!completions[0].success);
+        assert_eq!(completions[0].target, "crkbd:default");
+        assert_eq!(completions[0].error.as_deref(), Some("make error"));
+        assert!(state.started_at.is_none());
+    }
+
     #[test]
     fn test_log_level_color() {
         assert_eq!(LogLevel::Info.color(), ratatui::style::Color::Gray);
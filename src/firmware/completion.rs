@@ -0,0 +1,177 @@
+//! Build completion hooks.
+//!
+//! Frontends learn that a firmware build finished by subscribing to
+//! [`CompletionHooks`] instead of polling build state. The build pipeline
+//! calls [`CompletionHooks::notify`] once per build with a
+//! [`BuildCompletion`] carrying the outcome and duration.
+
+use serde::{Deserialize, Serialize};
+use std::sync::{Arc, Mutex};
+use std::time::Duration;
+
+/// Outcome of a finished firmware build.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct BuildCompletion {
+    /// Whether the build succeeded
+    pub success: bool,
+    /// What was built, as `keyboard:keymap`
+    pub target: String,
+    /// Layout file that was built, if known
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub layout: Option<String>,
+    /// Time from start to finish in milliseconds
+    pub duration_ms: u64,
+    /// Error message if the build failed
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub error: Option<String>,
+}
+
+impl BuildCompletion {
+    /// Creates a completion record.
+    #[must_use]
+    pub fn new(
+        success: bool,
+        target: impl Into<String>,
+        duration: Duration,
+        error: Option<String>,
+    ) -> Self {
+        Self {
+            success,
+            target: target.into(),
+            layout: None,
+            duration_ms: u64::try_from(duration.as_millis()).unwrap_or(u64::MAX),
+            error,
+        }
+    }
+
+    /// Sets the layout file that was built (builder pattern).
+    #[must_use]
+    #[allow(dead_code)] // Only the web server's build jobs know the layout file
+    pub fn with_layout(mut self, layout: impl Into<String>) -> Self {
+        self.layout = Some(layout.into());
+        self
+    }
+
+    /// Build duration.
+    #[must_use]
+    pub const fn duration(&self) -> Duration {
+        Duration::from_millis(self.duration_ms)
+    }
+
+    /// Short title, e.g. "Build succeeded".
+    #[must_use]
+    pub const fn title(&self) -> &'static str {
+        if self.success {
+            "Build succeeded"
+        } else {
+            "Build failed"
+        }
+    }
+
+    /// One-line summary, e.g. "crkbd:default built in 1m 05s".
+    #[must_use]
+    pub fn summary(&self) -> String {
+        let duration = format_duration(self.duration());
+        if self.success {
+            format!("{} built in {duration}", self.target)
+        } else {
+            format!("{} failed after {duration}", self.target)
+        }
+    }
+}
+
+/// Formats a build duration as "42s" or "1m 05s".
+#[must_use]
+pub fn format_duration(duration: Duration) -> String {
+    let secs = duration.as_secs();
+    if secs < 60 {
+        format!("{secs}s")
+    } else {
+        format!("{}m {:02}s", secs / 60, secs % 60)
+    }
+}
+
+/// Callback run when a build finishes.
+type Hook = Arc<dyn Fn(&BuildCompletion) + Send + Sync>;
+
+/// Subscribers to build completion.
+///
+/// Clones share the same subscriber list.
+#[derive(Clone, Default)]
+pub struct CompletionHooks {
+    hooks: Arc<Mutex<Vec<Hook>>>,
+}
+
+impl CompletionHooks {
+    /// Creates an empty hook list.
+    #[must_use]
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Registers a callback for every later build completion.
+    pub fn subscribe(&self, hook: impl Fn(&BuildCompletion) + Send + Sync + 'static) {
+        self.lock().push(Arc::new(hook));
+    }
+
+    /// Calls every subscriber with `completion`.
+    pub fn notify(&self, completion: &BuildCompletion) {
+        // Call outside the lock so a hook can subscribe without deadlocking
+        let hooks = self.lock().clone();
+        for hook in hooks {
+            hook(completion);
+        }
+    }
+
+    fn lock(&self) -> std::sync::MutexGuard<'_, Vec<Hook>> {
+        self.hooks
+            .lock()
+            .unwrap_or_else(std::sync::PoisonError::into_inner)
+    }
+}
+
+impl std::fmt::Debug for CompletionHooks {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("CompletionHooks")
+            .field("subscribers", &self.lock().len())
+            .finish()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_summary_and_duration_format() {
+        let ok = BuildCompletion::new(true, "crkbd:default", Duration::from_secs(65), None);
+        assert_eq!(ok.title(), "Build succeeded");
+        assert_eq!(ok.summary(), "crkbd:default built in 1m 05s");
+
+        let failed = BuildCompletion::new(
+            false,
+            "crkbd:default",
+            Duration::from_millis(12_400),
+            Some("make error".to_string()),
+        );
+        assert_eq!(failed.title(), "Build failed");
+        assert_eq!(failed.summary(), "crkbd:default failed after 12s");
+    }
+
+    #[test]
+    fn test_every_subscriber_is_notified() {
+        let hooks = CompletionHooks::new();
+        let seen = Arc::new(Mutex::new(Vec::new()));
+        for id in 0..2 {
+            let seen = Arc::clone(&seen);
+            hooks
+                .clone()
+                .subscribe(move |completion: &BuildCompletion| {
+                    seen.lock().unwrap().push((id, completion.success));
+                });
+        }
+
+        hooks.notify(&BuildCompletion::new(true, "a:b", Duration::ZERO, None));
+        assert_eq!(*seen.lock().unwrap(), vec![(0, true), (1, true)]);
+    }
+}
@@ -0,0 +1,220 @@
+//! Controller converters (`CONVERT_TO`) and the `qmk compile` command line.
+//!
+//! Boards designed for a Pro Micro can be built for a pin-compatible
+//! replacement controller by passing `-e CONVERT_TO=<converter>` to
+//! `qmk compile`. QMK then appends `_<converter>` to the firmware file name,
+//! and RP2040 replacements produce a `.uf2` instead of a `.hex`.
+
+use anyhow::Result;
+
+/// A converter QMK ships, with the firmware format it produces.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Converter {
+    /// Value passed as `CONVERT_TO`
+    pub name: &'static str,
+    /// Extension of the firmware file the build produces
+    pub extension: &'static str,
+}
+
+/// Converters known to QMK (`platforms/chibios/converters/`).
+pub const KNOWN_CONVERTERS: &[Converter] = &[
+    Converter {
+        name: "blok",
+        extension: "uf2",
+    },
+    Converter {
+        name: "bit_c_pro",
+        extension: "uf2",
+    },
+    Converter {
+        name: "bonsai_c4",
+        extension: "bin",
+    },
+    Converter {
+        name: "elite_pi",
+        extension: "uf2",
+    },
+    Converter {
+        name: "gemini",
+        extension: "uf2",
+    },
+    Converter {
+        name: "helios",
+        extension: "uf2",
+    },
+    Converter {
+        name: "imera",
+        extension: "uf2",
+    },
+    Converter {
+        name: "kb2040",
+        extension: "uf2",
+    },
+    Converter {
+        name: "liatris",
+        extension: "uf2",
+    },
+    Converter {
+        name: "michi",
+        extension: "uf2",
+    },
+    Converter {
+        name: "promicro_rp2040",
+        extension: "uf2",
+    },
+    Converter {
+        name: "proton_c",
+        extension: "bin",
+    },
+    Converter {
+        name: "rp2040_ce",
+        extension: "uf2",
+    },
+    Converter {
+        name: "sparkfun_pm2040",
+        extension: "uf2",
+    },
+    Converter {
+        name: "stemcell",
+        extension: "uf2",
+    },
+    Converter {
+        name: "svlinky",
+        extension: "uf2",
+    },
+];
+
+/// Firmware extensions searched after a build, most common first.
+const FIRMWARE_EXTENSIONS: [&str; 3] = ["uf2", "hex", "bin"];
+
+/// Looks up a known converter by name.
+#[must_use]
+pub fn find_converter(name: &str) -> Option<&'static Converter> {
+    KNOWN_CONVERTERS.iter().find(|c| c.name == name)
+}
+
+/// Checks that a converter name is safe to pass as `CONVERT_TO=<name>`.
+///
+/// Only lowercase letters, digits, and underscores are allowed. Whether the
+/// converter exists is checked separately by [`converter_warning`].
+pub fn validate_converter_name(name: &str) -> Result<()> {
+    if name.is_empty() {
+        anyhow::bail!("Converter name cannot be empty");
+    }
+    if !name
+        .chars()
+        .all(|c| c.is_ascii_lowercase() || c.is_ascii_digit() || c == '_')
+    {
+        anyhow::bail!("Invalid converter '{name}': use lowercase letters, digits, and underscores");
+    }
+    Ok(())
+}
+
+/// Returns a warning for converters QMK is not known to ship.
+///
+/// Unknown names are allowed (QMK adds converters over time), but are most
+/// likely typos.
+#[must_use]
+pub fn converter_warning(name: &str) -> Option<String> {
+    if find_converter(name).is_some() {
+        return None;
+    }
+    let known: Vec<&str> = KNOWN_CONVERTERS.iter().map(|c| c.name).collect();
+    Some(format!(
+        "Unknown converter '{name}'; known converters: {}",
+        known.join(", ")
+    ))
+}
+
+/// Arguments for `qmk`, e.g. `compile -kb crkbd -km default -e CONVERT_TO=rp2040_ce`.
+#[must_use]
+pub fn compile_args(keyboard: &str, keymap: &str, converter: Option<&str>) -> Vec<String> {
+    let mut args = vec![
+        "compile".to_string(),
+        "-kb".to_string(),
+        keyboard.to_string(),
+        "-km".to_string(),
+        keymap.to_string(),
+    ];
+    if let Some(converter) = converter {
+        args.push("-e".to_string());
+        args.push(format!("CONVERT_TO={converter}"));
+    }
+    args
+}
+
+/// The compile command as shown to the user.
+#[must_use]
+pub fn compile_command(keyboard: &str, keymap: &str, converter: Option<&str>) -> String {
+    format!(
+        "qmk {}",
+        compile_args(keyboard, keymap, converter).join(" ")
+    )
+}
+
+/// Firmware file name without extension, as QMK writes it to `.build/`.
+#[must_use]
+pub fn firmware_basename(keyboard: &str, keymap: &str, converter: Option<&str>) -> String {
+    let keyboard_clean = keyboard.replace('/', "_");
+    converter.map_or_else(
+        || format!("{keyboard_clean}_{keymap}"),
+        |converter| format!("{keyboard_clean}_{keymap}_{converter}"),
+    )
+}
+
+/// Firmware extensions to look for, the converter's own format first.
+#[must_use]
+pub fn firmware_extensions(converter: Option<&str>) -> Vec<&'static str> {
+    let preferred = converter.and_then(find_converter).map(|c| c.extension);
+    let mut extensions: Vec<&'static str> = preferred.into_iter().collect();
+    extensions.extend(
+        FIRMWARE_EXTENSIONS
+            .iter()
+            .filter(|ext| Some(**ext) != preferred),
+    );
+    extensions
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_compile_args_with_converter() {
+        assert_eq!(
+            compile_command("crkbd/rev1", "default", None),
+            "qmk compile -kb crkbd/rev1 -km default"
+        );
+        assert_eq!(
+            compile_command("crkbd/rev1", "default", Some("rp2040_ce")),
+            "qmk compile -kb crkbd/rev1 -km default -e CONVERT_TO=rp2040_ce"
+        );
+    }
+
+    #[test]
+    fn test_firmware_name_and_extensions() {
+        assert_eq!(
+            firmware_basename("crkbd/rev1", "default", Some("rp2040_ce")),
+            "crkbd_rev1_default_rp2040_ce"
+        );
+        assert_eq!(
+            firmware_basename("crkbd/rev1", "default", None),
+            "crkbd_rev1_default"
+        );
+        assert_eq!(firmware_extensions(None), ["uf2", "hex", "bin"]);
+        assert_eq!(firmware_extensions(Some("proton_c")), ["bin", "uf2", "hex"]);
+        assert_eq!(firmware_extensions(Some("mystery")), ["uf2", "hex", "bin"]);
+    }
+
+    #[test]
+    fn test_converter_validation_and_warning() {
+        assert!(validate_converter_name("rp2040_ce").is_ok());
+        assert!(validate_converter_name("").is_err());
+        assert!(validate_converter_name("rp2040 ce").is_err());
+        assert!(validate_converter_name("X=1").is_err());
+
+        assert!(converter_warning("liatris").is_none());
+        let warning = converter_warning("liatriss").unwrap();
+        assert!(warning.contains("Unknown converter 'liatriss'"));
+    }
+}
@@ -0,0 +1,504 @@
+//! Build environment checks.
+//!
+//! A build without its toolchain fails deep inside `make` with output like
+//! `arm-none-eabi-gcc: not found`. These checks run before a build starts and
+//! in `lazyqmk doctor`, and name what is missing together with the command
+//! that installs it on this OS.
+
+use std::fmt;
+use std::fs;
+use std::path::{Path, PathBuf};
+
+use super::backend::Backend;
+use crate::parser::keyboard_json::parse_keyboard_info_json;
+
+/// Compiler family a keyboard's processor needs.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Toolchain {
+    /// AVR microcontrollers (`atmega32u4`, `at90usb1286`, ...)
+    Avr,
+    /// ARM Cortex-M microcontrollers (`STM32F411`, `RP2040`, ...)
+    Arm,
+    /// RISC-V microcontrollers (`GD32VF103`)
+    RiscV,
+}
+
+impl Toolchain {
+    /// Every toolchain, in the order `lazyqmk doctor` lists them
+    pub const ALL: [Self; 3] = [Self::Avr, Self::Arm, Self::RiscV];
+
+    /// Toolchain for a QMK processor name, if it is one we know.
+    #[must_use]
+    pub fn for_processor(processor: &str) -> Option<Self> {
+        let processor = processor.trim().to_ascii_lowercase();
+        if processor.starts_with("gd32v") {
+            Some(Self::RiscV)
+        } else if processor.starts_with("at32") {
+            // Artery parts are Cortex-M despite the Atmel-like prefix
+            Some(Self::Arm)
+        } else if processor.starts_with("at") {
+            Some(Self::Avr)
+        } else if ["stm32", "rp2040", "mk", "wb32", "gd32", "sn32", "cortex-m"]
+            .iter()
+            .any(|prefix| processor.starts_with(prefix))
+        {
+            Some(Self::Arm)
+        } else {
+            None
+        }
+    }
+
+    /// Compilers that provide the toolchain; finding any one is enough
+    #[must_use]
+    pub const fn compilers(self) -> &'static [&'static str] {
+        match self {
+            Self::Avr => &["avr-gcc"],
+            Self::Arm => &["arm-none-eabi-gcc"],
+            Self::RiscV => &["riscv64-unknown-elf-gcc", "riscv32-unknown-elf-gcc"],
+        }
+    }
+
+    /// Display name
+    #[must_use]
+    pub const fn name(self) -> &'static str {
+        match self {
+            Self::Avr => "AVR",
+            Self::Arm => "ARM",
+            Self::RiscV => "RISC-V",
+        }
+    }
+
+    /// Command that installs the toolchain on `platform`
+    #[must_use]
+    pub const fn install_command(self, platform: Platform) -> &'static str {
+        match (self, platform) {
+            (_, Platform::MacOs) => "brew install qmk/qmk/qmk",
+            (_, Platform::Windows) => "install QMK MSYS from https://msys.qmk.fm/",
+            (Self::Avr, Platform::Debian) => "sudo apt install gcc-avr avr-libc binutils-avr",
+            (Self::Arm, Platform::Debian) => {
+                "sudo apt install gcc-arm-none-eabi binutils-arm-none-eabi libnewlib-arm-none-eabi"
+            }
+            (Self::RiscV, Platform::Debian) => {
+                "sudo apt install gcc-riscv64-unknown-elf picolibc-riscv64-unknown-elf"
+            }
+            (Self::Avr, Platform::Fedora) => "sudo dnf install avr-gcc avr-libc avr-binutils",
+            (Self::Arm, Platform::Fedora) => {
+                "sudo dnf install arm-none-eabi-gcc-cs arm-none-eabi-newlib"
+            }
+            (Self::Avr, Platform::Arch) => "sudo pacman -S avr-gcc avr-libc avr-binutils",
+            (Self::Arm, Platform::Arch) => "sudo pacman -S arm-none-eabi-gcc arm-none-eabi-newlib",
+            // `qmk setup` installs whatever the distribution packages
+            _ => "qmk setup",
+        }
+    }
+}
+
+impl fmt::Display for Toolchain {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{} toolchain ({})", self.name(), self.compilers()[0])
+    }
+}
+
+/// Operating system family, for install commands.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Platform {
+    /// macOS (Homebrew)
+    MacOs,
+    /// Debian, Ubuntu and derivatives (apt)
+    Debian,
+    /// Fedora and derivatives (dnf)
+    Fedora,
+    /// Arch Linux and derivatives (pacman)
+    Arch,
+    /// Any other Linux or Unix
+    Other,
+    /// Windows (QMK MSYS)
+    Windows,
+}
+
+impl Platform {
+    /// Platform this binary runs on; Linux distributions are told apart by
+    /// `/etc/os-release`.
+    #[must_use]
+    pub fn detect() -> Self {
+        match std::env::consts::OS {
+            "macos" => Self::MacOs,
+            "windows" => Self::Windows,
+            _ => fs::read_to_string("/etc/os-release")
+                .map_or(Self::Other, |content| Self::from_os_release(&content)),
+        }
+    }
+
+    /// Linux distribution family from the contents of `/etc/os-release`.
+    #[must_use]
+    pub fn from_os_release(content: &str) -> Self {
+        let ids: Vec<String> = content
+            .lines()
+            .filter_map(|line| {
+                line.strip_prefix("ID=")
+                    .or_else(|| line.strip_prefix("ID_LIKE="))
+            })
+            .flat_map(|value| {
+                value
+                    .trim_matches('"')
+                    .split_whitespace()
+                    .map(str::to_ascii_lowercase)
+                    .collect::<Vec<_>>()
+            })
+            .collect();
+        let has = |names: &[&str]| ids.iter().any(|id| names.contains(&id.as_str()));
+        if has(&["debian", "ubuntu"]) {
+            Self::Debian
+        } else if has(&["fedora", "rhel"]) {
+            Self::Fedora
+        } else if has(&["arch"]) {
+            Self::Arch
+        } else {
+            Self::Other
+        }
+    }
+
+    /// Command that installs the QMK CLI
+    #[must_use]
+    pub const fn qmk_install_command(self) -> &'static str {
+        match self {
+            Self::MacOs => "brew install qmk/qmk/qmk",
+            Self::Windows => "install QMK MSYS from https://msys.qmk.fm/",
+            _ => "python3 -m pip install --user qmk",
+        }
+    }
+
+    /// Command that installs GNU make
+    #[must_use]
+    pub const fn make_install_command(self) -> &'static str {
+        match self {
+            Self::MacOs => "xcode-select --install",
+            Self::Debian => "sudo apt install make",
+            Self::Fedora => "sudo dnf install make",
+            Self::Arch => "sudo pacman -S make",
+            Self::Other => "install GNU make with your package manager",
+            Self::Windows => "install QMK MSYS from https://msys.qmk.fm/",
+        }
+    }
+}
+
+/// A missing piece of the build environment.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct EnvProblem {
+    /// What is missing
+    pub message: String,
+    /// Command or step that fixes it
+    pub fix: String,
+}
+
+impl fmt::Display for EnvProblem {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}. To fix: {}", self.message, self.fix)
+    }
+}
+
+impl std::error::Error for EnvProblem {}
+
+/// Outcome of one environment check.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct EnvCheck {
+    /// What was checked (e.g. "qmk CLI")
+    pub name: String,
+    /// What was found, or the problem
+    pub result: Result<String, EnvProblem>,
+}
+
+/// Checks the QMK tree, the program `backend` runs, `toolchains` and the
+/// tree's git submodules, looking programs up on `PATH`.
+#[must_use]
+pub fn check_environment(
+    qmk_path: &Path,
+    backend: Backend,
+    toolchains: &[Toolchain],
+) -> Vec<EnvCheck> {
+    check_environment_with(
+        qmk_path,
+        backend,
+        toolchains,
+        Platform::detect(),
+        find_executable,
+    )
+}
+
+/// [`check_environment`] with the platform and program lookup given, for
+/// tests.
+pub fn check_environment_with(
+    qmk_path: &Path,
+    backend: Backend,
+    toolchains: &[Toolchain],
+    platform: Platform,
+    find: impl Fn(&str) -> Option<PathBuf>,
+) -> Vec<EnvCheck> {
+    let mut checks = vec![EnvCheck {
+        name: "QMK firmware tree".to_string(),
+        result: check_qmk_tree(qmk_path),
+    }];
+
+    checks.push(EnvCheck {
+        name: backend.to_string(),
+        result: find(backend.program())
+            .map(|path| path.display().to_string())
+            .ok_or_else(|| match backend {
+                Backend::QmkCli => EnvProblem {
+                    message: "The qmk CLI is not installed or not on PATH".to_string(),
+                    fix: platform.qmk_install_command().to_string(),
+                },
+                Backend::Make => EnvProblem {
+                    message: "make is not installed or not on PATH".to_string(),
+                    fix: platform.make_install_command().to_string(),
+                },
+            }),
+    });
+
+    for &toolchain in toolchains {
+        let found = toolchain.compilers().iter().find_map(|name| find(name));
+        checks.push(EnvCheck {
+            name: toolchain.to_string(),
+            result: found
+                .map(|path| path.display().to_string())
+                .ok_or_else(|| EnvProblem {
+                    message: format!(
+                        "{} not found; {} keyboards need the {} toolchain",
+                        toolchain.compilers()[0],
+                        toolchain.name(),
+                        toolchain.name()
+                    ),
+                    fix: toolchain.install_command(platform).to_string(),
+                }),
+        });
+    }
+
+    if qmk_path.is_dir() {
+        checks.push(EnvCheck {
+            name: "QMK submodules".to_string(),
+            result: check_submodules(qmk_path),
+        });
+    }
+    checks
+}
+
+/// Fails with the first problem in the environment a build of `keyboard`
+/// needs.
+///
+/// # Errors
+///
+/// Returns the first missing piece of the environment.
+pub fn ensure_build_environment(
+    qmk_path: &Path,
+    backend: Backend,
+    keyboard: &str,
+    converter: Option<&str>,
+) -> Result<(), EnvProblem> {
+    let toolchains: Vec<_> = toolchain_for_build(qmk_path, keyboard, converter)
+        .into_iter()
+        .collect();
+    check_environment(qmk_path, backend, &toolchains)
+        .into_iter()
+        .find_map(|check| check.result.err())
+        .map_or(Ok(()), Err)
+}
+
+/// Toolchain a build of `keyboard` needs, from its `info.json` processor.
+///
+/// Converters all target ChibiOS controllers, so they need ARM whatever the
+/// board's own processor is. `None` if the processor is unknown.
+#[must_use]
+pub fn toolchain_for_build(
+    qmk_path: &Path,
+    keyboard: &str,
+    converter: Option<&str>,
+) -> Option<Toolchain> {
+    if converter.is_some() {
+        return Some(Toolchain::Arm);
+    }
+    parse_keyboard_info_json(qmk_path, keyboard)
+        .ok()
+        .and_then(|info| info.processor)
+        .and_then(|processor| Toolchain::for_processor(&processor))
+}
+
+/// Path of the program `name` on `PATH`, if it is there.
+#[must_use]
+pub fn find_executable(name: &str) -> Option<PathBuf> {
+    let path = std::env::var_os("PATH")?;
+    std::env::split_paths(&path).find_map(|dir| {
+        let candidate = dir.join(name);
+        if candidate.is_file() {
+            return Some(candidate);
+        }
+        let exe = candidate.with_extension("exe");
+        exe.is_file().then_some(exe)
+    })
+}
+
+fn check_qmk_tree(qmk_path: &Path) -> Result<String, EnvProblem> {
+    if qmk_path.join("keyboards").is_dir() {
+        Ok(qmk_path.display().to_string())
+    } else {
+        Err(EnvProblem {
+            message: format!("{} is not a QMK firmware tree", qmk_path.display()),
+            fix: "clone it with `qmk setup`, then `lazyqmk config set --qmk-path <DIR>`"
+                .to_string(),
+        })
+    }
+}
+
+/// Submodules listed in `.gitmodules` whose directories are missing or
+/// empty. A tree without `.gitmodules` (e.g. an extracted archive) passes.
+fn check_submodules(qmk_path: &Path) -> Result<String, EnvProblem> {
+    let Ok(gitmodules) = fs::read_to_string(qmk_path.join(".gitmodules")) else {
+        return Ok("no .gitmodules, skipped".to_string());
+    };
+    let paths: Vec<&str> = gitmodules
+        .lines()
+        .filter_map(|line| {
+            let (key, value) = line.split_once('=')?;
+            (key.trim() == "path").then(|| value.trim())
+        })
+        .collect();
+    let missing: Vec<&str> = paths
+        .iter()
+        .copied()
+        .filter(|path| {
+            fs::read_dir(qmk_path.join(path)).map_or(true, |mut entries| entries.next().is_none())
+        })
+        .collect();
+
+    if missing.is_empty() {
+        Ok(format!("{} initialized", paths.len()))
+    } else {
+        Err(EnvProblem {
+            message: format!("QMK submodules not initialized: {}", missing.join(", ")),
+            fix: format!(
+                "git -C \"{}\" submodule update --init --recursive",
+                qmk_path.display()
+            ),
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tempfile::TempDir;
+
+    fn qmk_tree(submodules: &[(&str, bool)]) -> TempDir {
+        let dir = TempDir::new().unwrap();
+        fs::create_dir(dir.path().join("keyboards")).unwrap();
+        let gitmodules = submodules
+            .iter()
+            .map(|(path, _)| format!("[submodule \"{path}\"]\n\tpath = {path}"))
+            .collect::<Vec<_>>()
+            .join("\n");
+        fs::write(dir.path().join(".gitmodules"), gitmodules).unwrap();
+        for (path, initialized) in submodules {
+            let path = dir.path().join(path);
+            fs::create_dir_all(&path).unwrap();
+            if *initialized {
+                fs::write(path.join("README"), "").unwrap();
+            }
+        }
+        dir
+    }
+
+    #[test]
+    fn test_toolchain_for_processor() {
+        assert_eq!(Toolchain::for_processor("atmega32u4"), Some(Toolchain::Avr));
+        assert_eq!(
+            Toolchain::for_processor("at90usb1286"),
+            Some(Toolchain::Avr)
+        );
+        assert_eq!(Toolchain::for_processor("STM32F411"), Some(Toolchain::Arm));
+        assert_eq!(Toolchain::for_processor("RP2040"), Some(Toolchain::Arm));
+        assert_eq!(Toolchain::for_processor("AT32F415"), Some(Toolchain::Arm));
+        assert_eq!(Toolchain::for_processor("MKL26Z64"), Some(Toolchain::Arm));
+        assert_eq!(
+            Toolchain::for_processor("GD32VF103"),
+            Some(Toolchain::RiscV)
+        );
+        assert_eq!(Toolchain::for_processor("z80"), None);
+    }
+
+    #[test]
+    fn test_platform_from_os_release() {
+        let ubuntu = "NAME=\"Ubuntu\"\nID=ubuntu\nID_LIKE=debian\n";
+        assert_eq!(Platform::from_os_release(ubuntu), Platform::Debian);
+        let manjaro = "ID=manjaro\nID_LIKE=\"arch\"\n";
+        assert_eq!(Platform::from_os_release(manjaro), Platform::Arch);
+        let rocky = "ID=\"rocky\"\nID_LIKE=\"rhel centos fedora\"\n";
+        assert_eq!(Platform::from_os_release(rocky), Platform::Fedora);
+        assert_eq!(Platform::from_os_release("ID=nixos\n"), Platform::Other);
+    }
+
+    #[test]
+    fn test_missing_toolchain_names_install_command() {
+        let tree = qmk_tree(&[("lib/chibios", true)]);
+        let find = |name: &str| (name == "qmk").then(|| PathBuf::from("/usr/bin/qmk"));
+
+        let checks = check_environment_with(
+            tree.path(),
+            Backend::QmkCli,
+            &[Toolchain::Arm],
+            Platform::Debian,
+            find,
+        );
+        let names: Vec<_> = checks.iter().map(|c| c.name.as_str()).collect();
+        assert_eq!(
+            names,
+            [
+                "QMK firmware tree",
+                "qmk CLI",
+                "ARM toolchain (arm-none-eabi-gcc)",
+                "QMK submodules"
+            ]
+        );
+        let problems: Vec<_> = checks
+            .iter()
+            .filter_map(|c| c.result.as_ref().err())
+            .collect();
+        assert_eq!(problems.len(), 1);
+        assert!(problems[0].message.contains("arm-none-eabi-gcc not found"));
+        assert!(problems[0].fix.contains("apt install gcc-arm-none-eabi"));
+    }
+
+    #[test]
+    fn test_uninitialized_submodules_and_missing_cli() {
+        let tree = qmk_tree(&[("lib/chibios", false), ("lib/lufa", true)]);
+        let checks =
+            check_environment_with(tree.path(), Backend::QmkCli, &[], Platform::MacOs, |_| None);
+
+        let cli = checks[1].result.as_ref().unwrap_err();
+        assert_eq!(cli.fix, "brew install qmk/qmk/qmk");
+
+        // The make backend needs make instead of the qmk CLI
+        let checks =
+            check_environment_with(tree.path(), Backend::Make, &[], Platform::Arch, |_| None);
+        assert_eq!(checks[1].name, "make");
+        assert_eq!(
+            checks[1].result.as_ref().unwrap_err().fix,
+            "sudo pacman -S make"
+        );
+        let submodules = checks[2].result.as_ref().unwrap_err();
+        assert_eq!(
+            submodules.message,
+            "QMK submodules not initialized: lib/chibios"
+        );
+        assert!(submodules.fix.contains("submodule update --init"));
+    }
+
+    #[test]
+    fn test_not_a_qmk_tree() {
+        let dir = TempDir::new().unwrap();
+        let missing = dir.path().join("qmk_firmware");
+        let checks =
+            check_environment_with(&missing, Backend::Make, &[], Platform::Other, |_| None);
+        assert!(checks[0].result.is_err());
+        // Submodules can't be checked without a tree
+        assert_eq!(checks.len(), 2);
+    }
+}
@@ -6,19 +6,307 @@
 // Allow format! appended to String - more readable than write! in code generation
 #![allow(clippy::format_push_string)]
 
+use crate::atomic_write;
 use crate::config::Config;
 use crate::constants::APP_BINARY_NAME;
+use crate::firmware::paths;
+use crate::firmware::templates::{
+    FeaturesContext, GeneratorInfo, LayerContext, MetadataContext, RgbContext, Sections,
+    SettingsContext, TapDanceContext, TemplateContext, TemplateKind, TemplateSet,
+};
 use crate::keycode_db::KeycodeDb;
 use crate::models::keyboard_geometry::KeyboardGeometry;
+use crate::models::layer::Layer;
 use crate::models::layout::Layout;
 use crate::models::visual_layout_mapping::VisualLayoutMapping;
+use crate::services::layer_refs::disabled_layer_refs;
 use anyhow::{Context, Result};
+use serde::Serialize;
 use std::collections::HashSet;
+use std::fmt;
 use std::fs;
+use std::io;
+use std::path::{Path, PathBuf};
 
 // Language-specific keycode headers are now loaded dynamically from KeycodeDb.languages()
 // No more hardcoded KEYCODE_PREFIX_HEADERS constant needed!
 
+/// Marker identifying files written by the generator.
+const GENERATED_MARKER: &str = "Generated by";
+
+/// What generating a file did to the QMK keymap directory.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum FileStatus {
+    /// The keymap directory had no such file or different content
+    Written,
+    /// The keymap directory already had identical content
+    Unchanged,
+    /// A hand-written file in the keymap directory was left alone; only the
+    /// archive copy was written
+    HandWritten,
+    /// Writing the file failed; the keymap directory keeps its previous copy
+    Failed,
+    /// Not attempted because an earlier file failed; the keymap directory
+    /// keeps its previous copy
+    NotWritten,
+}
+
+impl FileStatus {
+    /// Short label for summaries.
+    #[must_use]
+    pub const fn label(self) -> &'static str {
+        match self {
+            Self::Written => "written",
+            Self::Unchanged => "unchanged",
+            Self::HandWritten => "kept hand-written",
+            Self::Failed => "failed",
+            Self::NotWritten => "not written",
+        }
+    }
+}
+
+/// A file attempted by [`FirmwareGenerator::generate`].
+#[derive(Debug, Clone)]
+pub struct GeneratedFile {
+    /// File name (e.g., "keymap.c")
+    pub name: &'static str,
+    /// Copy in the timestamped archive directory
+    #[allow(dead_code)] // Read by library callers and tests
+    pub archive_path: PathBuf,
+    /// Copy in the QMK keymap directory
+    pub keymap_path: PathBuf,
+    /// Size of the generated content in bytes
+    pub size: usize,
+    /// Effect on the keymap directory
+    pub status: FileStatus,
+    /// Why the file could not be written ([`FileStatus::Failed`] only)
+    pub error: Option<FileWriteError>,
+}
+
+/// Every file one [`FirmwareGenerator::generate`] run attempted, in write
+/// order.
+///
+/// Each file is written with [`atomic_write::write`] (a temporary file
+/// renamed over the target): first to the archive directory, then to the
+/// keymap directory unless that copy is already identical. A failed write
+/// therefore leaves the previous copy of that file intact, but nothing is
+/// rolled back: files written before the failure keep their new content.
+/// Generation stops at the first failure and lists the remaining files as
+/// [`FileStatus::NotWritten`], so the keymap directory may mix new and
+/// previous files; [`Self::stale_files`] names the ones that are behind.
+#[derive(Debug, Clone)]
+pub struct GenerationOutcome {
+    /// Timestamped archive directory
+    pub archive_dir: PathBuf,
+    /// QMK keymap directory the build uses
+    pub keymap_dir: PathBuf,
+    /// Attempted files in write order
+    pub files: Vec<GeneratedFile>,
+}
+
+impl GenerationOutcome {
+    /// Finds a generated file by name.
+    #[allow(dead_code)] // Used by library callers and tests
+    #[must_use]
+    pub fn file(&self, name: &str) -> Option<&GeneratedFile> {
+        self.files.iter().find(|file| file.name == name)
+    }
+
+    /// The file whose write failed, if any.
+    #[must_use]
+    pub fn failed(&self) -> Option<&GeneratedFile> {
+        self.files
+            .iter()
+            .find(|file| file.status == FileStatus::Failed)
+    }
+
+    /// Whether every file was written (or kept, for a hand-written one).
+    #[must_use]
+    pub fn is_complete(&self) -> bool {
+        self.failed().is_none()
+    }
+
+    /// Names of the files the keymap directory still has a previous copy of
+    /// because writing them failed or was never attempted.
+    #[must_use]
+    pub fn stale_files(&self) -> Vec<&'static str> {
+        self.files
+            .iter()
+            .filter(|file| matches!(file.status, FileStatus::Failed | FileStatus::NotWritten))
+            .map(|file| file.name)
+            .collect()
+    }
+
+    /// Writes a file to the timestamped archive and the QMK keymap directory
+    /// and records it; after a failure, files are only recorded.
+    ///
+    /// With `keep_keymap_copy`, only the archive copy is written.
+    fn write(&mut self, name: &'static str, content: &str, keep_keymap_copy: bool) {
+        let archive_path = self.archive_dir.join(name);
+        let keymap_path = self.keymap_dir.join(name);
+
+        let (status, error) = if self.is_complete() {
+            let written = write_file(&archive_path, content).and_then(|()| {
+                if keep_keymap_copy {
+                    Ok(FileStatus::HandWritten)
+                } else if fs::read(&keymap_path)
+                    .is_ok_and(|existing| existing == content.as_bytes())
+                {
+                    Ok(FileStatus::Unchanged)
+                } else {
+                    write_file(&keymap_path, content).map(|()| FileStatus::Written)
+                }
+            });
+            match written {
+                Ok(status) => (status, None),
+                Err(error) => (FileStatus::Failed, Some(error)),
+            }
+        } else {
+            (FileStatus::NotWritten, None)
+        };
+
+        self.files.push(GeneratedFile {
+            name,
+            archive_path,
+            keymap_path,
+            size: content.len(),
+            status,
+            error,
+        });
+    }
+}
+
+/// A generated file that could not be written, with the I/O error as the
+/// operating system reported it.
+#[derive(Debug, Clone)]
+pub struct FileWriteError {
+    /// Path that failed to be written
+    pub path: PathBuf,
+    /// Underlying I/O error message
+    pub message: String,
+}
+
+impl FileWriteError {
+    /// Wraps a failed [`atomic_write::write`], keeping the innermost I/O error.
+    fn new(path: &Path, error: &anyhow::Error) -> Self {
+        let message = error
+            .chain()
+            .find_map(|cause| cause.downcast_ref::<io::Error>())
+            .map_or_else(|| error.to_string(), ToString::to_string);
+        Self {
+            path: path.to_path_buf(),
+            message,
+        }
+    }
+}
+
+impl fmt::Display for FileWriteError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "Failed to write {}: {}",
+            self.path.display(),
+            self.message
+        )
+    }
+}
+
+impl std::error::Error for FileWriteError {}
+
+/// Generation that stopped at a failed write, with the files written before
+/// it.
+///
+/// Returned (inside [`anyhow::Error`]) by [`FirmwareGenerator::generate`]
+/// once writing has started; earlier failures leave no files behind and are
+/// plain errors.
+#[derive(Debug, Clone)]
+pub struct GenerationError {
+    /// Every attempted file, including the failed one
+    pub outcome: GenerationOutcome,
+}
+
+impl fmt::Display for GenerationError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self.outcome.failed().and_then(|file| file.error.as_ref()) {
+            Some(error) => write!(f, "{error}")?,
+            None => write!(f, "Failed to write generated files")?,
+        }
+        let stale = self.outcome.stale_files();
+        match stale.as_slice() {
+            [] => Ok(()),
+            [only] => write!(f, " ({only} was not updated)"),
+            names => write!(f, " ({} were not updated)", names.join(", ")),
+        }
+    }
+}
+
+impl std::error::Error for GenerationError {}
+
+/// Atomically writes a generated file, reporting failures as [`FileWriteError`].
+fn write_file(path: &Path, content: &str) -> Result<(), FileWriteError> {
+    atomic_write::write(path, content).map_err(|e| FileWriteError::new(path, &e))
+}
+
+/// Derives C enum names for layers from their names.
+///
+/// Names are uppercased with every run of non-alphanumeric characters replaced
+/// by a single underscore and prefixed with `_` (e.g., "Nav & Sym" → `_NAV_SYM`).
+/// Layers without a usable name become `_LAYER<index>`; duplicates get numeric
+/// suffixes (`_NAV`, `_NAV_2`, ...).
+#[must_use]
+pub fn layer_enum_names(layers: &[Layer]) -> Vec<String> {
+    let mut used = HashSet::new();
+    layers
+        .iter()
+        .enumerate()
+        .map(|(idx, layer)| {
+            let mut base = String::from("_");
+            for c in layer.name.chars() {
+                if c.is_ascii_alphanumeric() {
+                    base.push(c.to_ascii_uppercase());
+                } else if !base.ends_with('_') {
+                    base.push('_');
+                }
+            }
+            let trimmed = base.trim_end_matches('_');
+            let base = if trimmed.is_empty() {
+                format!("_LAYER{idx}")
+            } else {
+                trimmed.to_string()
+            };
+
+            let mut name = base.clone();
+            let mut suffix = 2;
+            while !used.insert(name.clone()) {
+                name = format!("{base}_{suffix}");
+                suffix += 1;
+            }
+            name
+        })
+        .collect()
+}
+
+/// How resolved layer references name their layer.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum LayerRefStyle {
+    /// Layer enum name from keymap.c (e.g., `MO(_NAV)`)
+    EnumName,
+    /// Firmware layer number (e.g., `MO(1)`), for keymap.json
+    Number,
+}
+
+/// QMK `keymap.json` contents.
+#[derive(Debug, Serialize)]
+struct KeymapJson<'a> {
+    version: u8,
+    keyboard: &'a str,
+    keymap: &'a str,
+    layout: &'a str,
+    layers: Vec<Vec<String>>,
+    author: &'a str,
+    notes: &'a str,
+}
+
 /// Firmware generator for keymap.c and config.h.
 pub struct FirmwareGenerator<'a> {
     layout: &'a Layout,
@@ -26,12 +314,16 @@ pub struct FirmwareGenerator<'a> {
     mapping: &'a VisualLayoutMapping,
     config: &'a Config,
     keycode_db: &'a KeycodeDb,
+    /// C enum names for each layer, by index
+    layer_names: Vec<String>,
+    /// Indices of the layers compiled into firmware, in order
+    firmware_layers: Vec<usize>,
 }
 
 impl<'a> FirmwareGenerator<'a> {
     /// Creates a new firmware generator.
     #[must_use]
-    pub const fn new(
+    pub fn new(
         layout: &'a Layout,
         geometry: &'a KeyboardGeometry,
         mapping: &'a VisualLayoutMapping,
@@ -44,30 +336,92 @@ impl<'a> FirmwareGenerator<'a> {
             mapping,
             config,
             keycode_db,
+            layer_names: layer_enum_names(&layout.layers),
+            firmware_layers: layout
+                .layers
+                .iter()
+                .enumerate()
+                .filter(|(_, layer)| layer.enabled)
+                .map(|(index, _)| index)
+                .collect(),
         }
     }
 
-    /// Generates keymap.c and config.h files.
+    /// Layers compiled into firmware with their layout index and enum name.
+    ///
+    /// Disabled layers are skipped; since layer references resolve to enum
+    /// names, the remaining layers are renumbered consistently.
+    fn enabled_layers(&self) -> impl Iterator<Item = (usize, &'a Layer, &str)> + '_ {
+        self.firmware_layers.iter().map(|&index| {
+            (
+                index,
+                &self.layout.layers[index],
+                self.layer_names[index].as_str(),
+            )
+        })
+    }
+
+    /// Fails if the layers compiled into firmware reference a disabled one.
+    fn check_disabled_layer_refs(&self) -> Result<()> {
+        if self.firmware_layers.is_empty() {
+            anyhow::bail!("All layers are disabled; enable at least one layer");
+        }
+        if let Some(layer_ref) = disabled_layer_refs(&self.layout.layers).first() {
+            let from = &self.layout.layers[layer_ref.from_layer];
+            let to = &self.layout.layers[layer_ref.to_layer];
+            anyhow::bail!(
+                "Layer {} '{}' key ({}, {}) {} references disabled layer {} '{}'",
+                layer_ref.from_layer,
+                from.name,
+                layer_ref.position.row,
+                layer_ref.position.col,
+                layer_ref.keycode,
+                layer_ref.to_layer,
+                to.name
+            );
+        }
+        Ok(())
+    }
+
+    /// Generates keymap.c, config.h and rules.mk files.
     ///
     /// Files are written to both:
     /// 1. Timestamped output directory (for archival)
     /// 2. QMK keymap directory (for building)
     ///
-    /// Returns paths to the generated files in the timestamped directory:
-    /// (keymap_path, config_h_path)
-    pub fn generate(&self) -> Result<(String, String)> {
-        // Create timestamped output directory
-        let timestamp_dir = self.create_timestamped_output_dir()?;
+    /// All files are rendered before any is written, so a template or
+    /// layout error leaves both directories untouched. A failed write is
+    /// reported as a [`GenerationError`] listing every attempted file; see
+    /// [`GenerationOutcome`] for what a partial run leaves behind.
+    pub fn generate(&self) -> Result<GenerationOutcome> {
+        // Refuse to write anything if the keymap directory escapes the QMK tree
+        let keymap_dir = self.get_keymap_directory()?;
 
-        // Generate config.h (merge with keyboard config if exists)
         let config_h = self.generate_merged_config_h()?;
-        let config_h_path = self.write_file_to_both(&timestamp_dir, "config.h", &config_h)?;
-
-        // Generate keymap.c
         let keymap_c = self.generate_keymap_c()?;
-        let keymap_path = self.write_file_to_both(&timestamp_dir, "keymap.c", &keymap_c)?;
+        let rules_mk = self.generate_rules_mk()?;
+
+        // Create timestamped output directory
+        let archive_dir = self.create_timestamped_output_dir()?;
+
+        // Leave a hand-written rules.mk in the keymap directory alone
+        let hand_written_rules = fs::read_to_string(keymap_dir.join("rules.mk"))
+            .is_ok_and(|content| !content.contains(GENERATED_MARKER));
+
+        let mut outcome = GenerationOutcome {
+            archive_dir,
+            keymap_dir,
+            files: Vec::new(),
+        };
+        outcome.write("config.h", &config_h, false);
+        outcome.write("keymap.c", &keymap_c, false);
+        outcome.write("rules.mk", &rules_mk, hand_written_rules);
 
-        Ok((keymap_path, config_h_path))
+        if outcome.is_complete() {
+            Ok(outcome)
+        } else {
+            Err(GenerationError { outcome }.into())
+        }
     }
 
     /// Generates keymap.c C code.
@@ -75,76 +429,176 @@ impl<'a> FirmwareGenerator<'a> {
     /// Creates a QMK keymap file with PROGMEM arrays for each layer.
     /// Keys are ordered by LED index as required by QMK.
     pub fn generate_keymap_c(&self) -> Result<String> {
-        let mut code = String::new();
+        let context = self.template_context()?;
+        self.templates().render(TemplateKind::KeymapC, &context)
+    }
 
-        // File header
-        code.push_str(&format!("// Generated by {}\n", APP_BINARY_NAME));
-        code.push_str(&format!("// Layout: {}\n", self.layout.metadata.name));
-        code.push_str(&format!(
-            "// Keyboard: {}\n",
-            self.layout
-                .metadata
+    /// Generates a QMK `keymap.json` (the format QMK Configurator imports).
+    ///
+    /// Each layer lists its keycodes in the same layout order as the
+    /// `LAYOUT(...)` arrays of [`Self::generate_keymap_c`]. Since the JSON
+    /// keymap has no layer enum, layer references use firmware layer numbers
+    /// (e.g., `MO(1)` rather than `MO(_NAV)`).
+    pub fn generate_keymap_json(&self) -> Result<String> {
+        self.check_disabled_layer_refs()?;
+        let metadata = &self.layout.metadata;
+
+        let layers = self
+            .enabled_layers()
+            .map(|(_, layer, _)| self.layer_keys_by_layout(layer, LayerRefStyle::Number))
+            .collect::<Result<Vec<_>>>()?;
+        let keymap = KeymapJson {
+            version: 1,
+            keyboard: metadata
                 .keyboard
                 .as_deref()
-                .unwrap_or("unknown")
-        ));
-        code.push_str(&format!(
-            "// Layout Variant: {}\n",
-            self.layout
-                .metadata
-                .layout_variant
+                .context("Keyboard not set in layout metadata")?,
+            keymap: metadata
+                .keymap_name
                 .as_deref()
-                .unwrap_or("unknown")
-        ));
-        code.push('\n');
-
-        // Includes
-        code.push_str("#include QMK_KEYBOARD_H\n");
+                .context("Keymap name not set in layout metadata")?,
+            layout: self.layout_macro(),
+            layers,
+            author: &metadata.author,
+            notes: &metadata.description,
+        };
 
-        // Add language-specific keycode headers if needed
-        let required_headers = self.detect_required_headers();
-        for header in &required_headers {
-            code.push_str(&format!("#include \"{}\"\n", header));
-        }
+        let mut json =
+            serde_json::to_string_pretty(&keymap).context("Failed to serialize keymap.json")?;
+        json.push('\n');
+        Ok(json)
+    }
 
-        // Tap dance requires process_tap_dance.h definitions
-        if !self.layout.tap_dances.is_empty() {
-            code.push_str("#include \"process_keycode/process_tap_dance.h\"\n");
-        }
+    /// Generates rules.mk for the keymap.
+    ///
+    /// Enables the QMK features the keymap relies on (e.g., tap dance).
+    pub fn generate_rules_mk(&self) -> Result<String> {
+        let context = self.template_context()?;
+        self.templates().render(TemplateKind::RulesMk, &context)
+    }
 
-        code.push('\n');
+    /// Template sources, honoring the configured template directory.
+    fn templates(&self) -> TemplateSet {
+        TemplateSet::new(self.config.build.template_dir.clone())
+    }
 
-        // Add tap dance configuration if any tap dances are defined
-        if !self.layout.tap_dances.is_empty() {
-            code.push_str("// Tap Dance Configuration\n");
-            code.push_str(&self.generate_tap_dance_enum());
-            code.push('\n');
-            code.push_str(&self.generate_tap_dance_helpers());
-            if !self.generate_tap_dance_helpers().is_empty() {
-                code.push('\n');
+    /// Builds the data passed to firmware templates.
+    fn template_context(&self) -> Result<TemplateContext<'_>> {
+        let metadata = &self.layout.metadata;
+        self.check_disabled_layer_refs()?;
+
+        let mut layers = Vec::with_capacity(self.firmware_layers.len());
+        for (index, (_, layer, enum_name)) in self.enabled_layers().enumerate() {
+            let mut keys: Vec<_> = layer.keys.iter().collect();
+            keys.sort_by_key(|key| (key.position.row, key.position.col));
+            let mut rows: Vec<Vec<String>> = Vec::new();
+            let mut current_row = None;
+            for key in keys {
+                if current_row != Some(key.position.row) {
+                    rows.push(Vec::new());
+                    current_row = Some(key.position.row);
+                }
+                if let Some(row) = rows.last_mut() {
+                    row.push(
+                        self.process_keycode_for_tap_dance(&self.resolve_keycode(&key.keycode)),
+                    );
+                }
             }
-            code.push_str(&self.generate_tap_dance_actions());
-            code.push('\n');
+
+            layers.push(LayerContext {
+                index,
+                name: &layer.name,
+                enum_name,
+                keycodes: self.generate_layer_keys_by_layout(layer)?,
+                rows,
+            });
         }
 
-        // Keymap definition
+        let mut tap_dances: Vec<_> = self.layout.tap_dances.iter().collect();
+        tap_dances.sort_by_key(|td| &td.name);
+        let tap_dances = tap_dances
+            .into_iter()
+            .map(|td| TapDanceContext {
+                name: td.name.clone(),
+                enum_name: format!("TD_{}", td.name.to_uppercase()),
+                single_tap: self.resolve_keycode(&td.single_tap),
+                double_tap: td.double_tap.as_deref().map(|k| self.resolve_keycode(k)),
+                hold: td.hold.as_deref().map(|k| self.resolve_keycode(k)),
+                tapping_term_ms: td.tapping_term_ms,
+                on_each_tap: td.on_each_tap,
+            })
+            .collect();
+
+        let sections = Sections {
+            keymaps: self.generate_keymaps_array(&layers),
+            encoder_map: self.generate_conditional_encoder_map()?,
+            rgb_matrix: self.generate_rgb_matrix_color_table()?,
+            idle_effect: self.generate_idle_effect_code()?,
+            rgb_indicators: self.generate_rgb_indicators_code()?,
+            tap_dance_enum: self.generate_tap_dance_enum(),
+            tap_dance_helpers: self.generate_tap_dance_helpers(),
+            tap_dance_actions: self.generate_tap_dance_actions(),
+            config: self.generate_config_defines()?,
+        };
+
+        Ok(TemplateContext {
+            generator: GeneratorInfo {
+                name: APP_BINARY_NAME,
+                version: env!("CARGO_PKG_VERSION"),
+            },
+            generated_at: chrono::Local::now().format("%Y-%m-%d %H:%M:%S").to_string(),
+            metadata: MetadataContext {
+                name: &metadata.name,
+                description: &metadata.description,
+                author: &metadata.author,
+                keyboard: metadata.keyboard.as_deref(),
+                layout_variant: metadata.layout_variant.as_deref(),
+                keymap_name: metadata.keymap_name.as_deref(),
+            },
+            layers,
+            tap_dances,
+            headers: self.detect_required_headers(),
+            settings: SettingsContext {
+                tap_hold: &self.layout.tap_hold_settings,
+                idle_effect: &self.layout.idle_effect_settings,
+                rgb: RgbContext {
+                    enabled: self.layout.rgb_enabled,
+                    brightness_percent: self.layout.rgb_brightness.as_percent(),
+                    timeout_ms: self.layout.rgb_timeout_ms,
+                },
+            },
+            features: FeaturesContext {
+                rgb_matrix: self.geometry.has_rgb_matrix(),
+                custom_colors: self.layout_has_custom_colors(),
+                idle_effect: self.layout.idle_effect_settings.enabled
+                    && self.geometry.has_rgb_matrix(),
+                encoders: self.geometry.encoder_count,
+            },
+            sections,
+        })
+    }
+
+    /// QMK layout macro the keymap is written for.
+    fn layout_macro(&self) -> &str {
+        self.layout
+            .metadata
+            .layout_variant
+            .as_deref()
+            .unwrap_or("LAYOUT")
+    }
+
+    /// Generates the `keymaps[]` array from the layers' layout-ordered keycodes.
+    fn generate_keymaps_array(&self, layers: &[LayerContext<'_>]) -> String {
+        let mut code = String::new();
         code.push_str("const uint16_t PROGMEM keymaps[][MATRIX_ROWS][MATRIX_COLS] = {\n");
 
         // Generate each layer
-        for (layer_idx, layer) in self.layout.layers.iter().enumerate() {
-            code.push_str(&format!("    // Layer {}: {}\n", layer_idx, layer.name));
-            code.push_str(&format!(
-                "[{}] = {}(",
-                layer_idx,
-                self.layout
-                    .metadata
-                    .layout_variant
-                    .as_deref()
-                    .unwrap_or("LAYOUT")
-            ));
+        for layer in layers {
+            code.push_str(&format!("    // Layer {}: {}\n", layer.index, layer.name));
+            code.push_str(&format!("[{}] = {}(", layer.enum_name, self.layout_macro()));
 
-            // Generate keys in layout order (matches info.json layout array)
-            let keys_by_layout = self.generate_layer_keys_by_layout(layer)?;
+            // Keys in layout order (matches info.json layout array)
+            let keys_by_layout = &layer.keycodes;
 
             // Format keys (wrap at reasonable line length)
             let keys_str = keys_by_layout.join(", ");
@@ -168,7 +622,7 @@ impl<'a> FirmwareGenerator<'a> {
             }
 
             code.push(')');
-            if layer_idx < self.layout.layers.len() - 1 {
+            if layer.index < layers.len() - 1 {
                 code.push_str(",\n");
             } else {
                 code.push('\n');
@@ -176,20 +630,7 @@ impl<'a> FirmwareGenerator<'a> {
         }
 
         code.push_str("};\n");
-
-        // Add conditional encoder_map (only when ENCODER_MAP_ENABLE is defined)
-        code.push('\n');
-        code.push_str(&self.generate_conditional_encoder_map()?);
-
-        // Add optional RGB matrix base color table when RGB is present.
-        code.push('\n');
-        code.push_str(&self.generate_rgb_matrix_color_table()?);
-
-        // Add idle effect state machine code if enabled
-        code.push('\n');
-        code.push_str(&self.generate_idle_effect_code()?);
-
-        Ok(code)
+        code
     }
 
     /// Scans all keycodes in the layout and returns the set of additional
@@ -241,37 +682,18 @@ impl<'a> FirmwareGenerator<'a> {
         code.push_str("#ifdef ENCODER_MAP_ENABLE\n");
         code.push_str("const uint16_t PROGMEM encoder_map[][NUM_ENCODERS][NUM_DIRECTIONS] = {\n");
 
-        // Generate encoder bindings for each layer
-        // Default encoder actions cycle through: RGB effect, hue, brightness, saturation
-        let default_encoder_bindings = [
-            ("RM_NEXT", "RM_PREV"), // Encoder 0: RGB effect
-            ("RM_HUEU", "RM_HUED"), // Encoder 1: RGB hue
-            ("RM_VALU", "RM_VALD"), // Encoder 2: RGB brightness
-            ("RM_SATU", "RM_SATD"), // Encoder 3: RGB saturation
-            ("KC_VOLU", "KC_VOLD"), // Encoder 4+: Volume (fallback for extra encoders)
-        ];
+        // Layers without their own assignment fall back to RGB and volume controls
+        for (_, layer, layer_name) in self.enabled_layers() {
+            code.push_str(&format!("    [{layer_name}] = {{\n"));
 
-        for (layer_idx, _layer) in self.layout.layers.iter().enumerate() {
-            code.push_str(&format!("    [{layer_idx}] = {{\n"));
-
-            // Generate encoder bindings based on actual encoder count
             for enc_idx in 0..encoder_count {
-                let (ccw, cw) = if enc_idx < default_encoder_bindings.len() {
-                    default_encoder_bindings[enc_idx]
-                } else {
-                    // Fallback for any additional encoders beyond our defaults
-                    default_encoder_bindings[default_encoder_bindings.len() - 1]
-                };
+                let encoder = layer.encoder(enc_idx);
+                let ccw = self.resolve_keycode(&encoder.ccw);
+                let cw = self.resolve_keycode(&encoder.cw);
                 code.push_str(&format!("        ENCODER_CCW_CW({ccw}, {cw}),\n"));
             }
 
-            code.push_str("    }");
-
-            if layer_idx < self.layout.layers.len() - 1 {
-                code.push_str(",\n");
-            } else {
-                code.push_str(",\n");
-            }
+            code.push_str("    },\n");
         }
 
         code.push_str("};\n");
@@ -286,12 +708,18 @@ impl<'a> FirmwareGenerator<'a> {
     /// The layout order matches the info.json layout array, which is what QMK's
     /// LAYOUT macro expects.
     ///
-    /// Layer references (e.g., `MO(@uuid)`) are resolved to numeric indices
-    /// (e.g., `MO(1)`) for firmware compatibility.
+    /// Layer references (e.g., `MO(@uuid)`) are resolved to layer enum names
+    /// (e.g., `MO(_NAV)`).
     fn generate_layer_keys_by_layout(
         &self,
         layer: &crate::models::layer::Layer,
     ) -> Result<Vec<String>> {
+        self.layer_keys_by_layout(layer, LayerRefStyle::EnumName)
+    }
+
+    /// Keycodes of `layer` in layout order, shared by keymap.c and keymap.json
+    /// so both always agree on key positions.
+    fn layer_keys_by_layout(&self, layer: &Layer, style: LayerRefStyle) -> Result<Vec<String>> {
         let key_count = self.mapping.key_count();
         let mut keys_by_layout = vec![String::from("KC_NO"); key_count];
 
@@ -310,8 +738,8 @@ impl<'a> FirmwareGenerator<'a> {
                     )
                 })?;
 
-            // Resolve layer references in keycode (e.g., MO(@uuid) -> MO(1))
-            let resolved_keycode = self.resolve_keycode(&key.keycode);
+            // Resolve layer references in keycode (e.g., MO(@uuid) -> MO(_NAV))
+            let resolved_keycode = self.resolve_keycode_as(&key.keycode, style);
 
             // Process tap dance keycodes (e.g., TD(name) -> TD(TD_NAME))
             let processed_keycode = self.process_keycode_for_tap_dance(&resolved_keycode);
@@ -323,18 +751,39 @@ impl<'a> FirmwareGenerator<'a> {
         Ok(keys_by_layout)
     }
 
-    /// Resolves a keycode, converting layer references to numeric indices.
+    /// Resolves a keycode, converting layer references to layer enum names.
     ///
-    /// If the keycode contains a layer reference like `MO(@uuid)`, it will be
-    /// resolved to the numeric index like `MO(1)`. If resolution fails (e.g.,
-    /// the referenced layer no longer exists), the original keycode is returned.
+    /// If the keycode contains a layer reference like `MO(@uuid)` or `MO(1)`, it
+    /// is resolved to the layer's enum name like `MO(_NAV)`. If resolution fails
+    /// (e.g., the referenced layer no longer exists), the original keycode is returned.
     fn resolve_keycode(&self, keycode: &str) -> String {
-        // Try to resolve layer references using the keycode database
-        if let Some(resolved) = self.layout.resolve_layer_keycode(keycode, self.keycode_db) {
-            resolved
-        } else {
-            // Not a layer keycode or resolution failed - use as-is
-            keycode.to_string()
+        self.resolve_keycode_as(keycode, LayerRefStyle::EnumName)
+    }
+
+    /// Resolves a keycode like [`Self::resolve_keycode`], naming the
+    /// referenced layer in the given style.
+    fn resolve_keycode_as(&self, keycode: &str, style: LayerRefStyle) -> String {
+        let Some((prefix, layer_ref, suffix)) = self.keycode_db.parse_layer_keycode(keycode) else {
+            // Not a layer keycode - use as-is
+            return keycode.to_string();
+        };
+
+        let layer_index = match layer_ref.strip_prefix('@') {
+            Some(layer_id) => self.layout.get_layer_index_by_id(layer_id),
+            None => layer_ref.parse::<usize>().ok(),
+        };
+        let name = layer_index.and_then(|idx| match style {
+            LayerRefStyle::EnumName => self.layer_names.get(idx).cloned(),
+            LayerRefStyle::Number => self
+                .firmware_layers
+                .iter()
+                .position(|&enabled| enabled == idx)
+                .map(|number| number.to_string()),
+        });
+        match name {
+            Some(name) if suffix.is_empty() => format!("{prefix}({name})"),
+            Some(name) => format!("{prefix}({name}{suffix}"),
+            None => keycode.to_string(),
         }
     }
 
@@ -432,7 +881,7 @@ impl<'a> FirmwareGenerator<'a> {
         }
 
         let mut code = String::new();
-        let layer_count = self.layout.layers.len();
+        let layer_count = self.firmware_layers.len();
         let led_count = self.mapping.key_count();
 
         code.push_str("#ifdef RGB_MATRIX_ENABLE\n");
@@ -440,9 +889,9 @@ impl<'a> FirmwareGenerator<'a> {
             "const uint8_t PROGMEM layer_base_colors[{layer_count}][{led_count}][3] = {{\n"
         ));
 
-        for layer_idx in 0..layer_count {
+        for (position, (layer_idx, _, layer_name)) in self.enabled_layers().enumerate() {
             let colors = self.generate_layer_colors_by_led(layer_idx)?;
-            code.push_str("    {\n");
+            code.push_str(&format!("    [{layer_name}] = {{\n"));
 
             for (led_idx, color) in colors.iter().enumerate() {
                 code.push_str(&format!(
@@ -458,7 +907,7 @@ impl<'a> FirmwareGenerator<'a> {
             }
 
             code.push_str("    }");
-            if layer_idx < layer_count - 1 {
+            if position < layer_count - 1 {
                 code.push_str(",\n");
             } else {
                 code.push('\n');
@@ -474,13 +923,143 @@ impl<'a> FirmwareGenerator<'a> {
         Ok(code)
     }
 
+    /// Returns true if an always-on category can light keys.
+    fn has_always_on_keys(&self) -> bool {
+        self.geometry.has_rgb_matrix() && self.layout.categories.iter().any(|c| c.always_on)
+    }
+
+    /// Category RGB flag bits of each LED on a layer (see
+    /// [`Self::generate_rgb_indicators_code`]).
+    fn generate_layer_flags_by_led(&self, layer_idx: usize) -> Result<Vec<u8>> {
+        let mut flags_by_led = vec![0; self.mapping.key_count()];
+        let layer = self
+            .layout
+            .get_layer(layer_idx)
+            .with_context(|| format!("Invalid layer index {layer_idx}"))?;
+
+        for key in &layer.keys {
+            let Some(category) = self.layout.key_category(layer_idx, key) else {
+                continue;
+            };
+            let led_idx = self
+                .mapping
+                .visual_to_led_index(key.position.row, key.position.col)
+                .with_context(|| {
+                    format!(
+                        "Failed to map visual position ({}, {}) to LED index",
+                        key.position.row, key.position.col
+                    )
+                })?;
+            flags_by_led[led_idx as usize] =
+                u8::from(category.always_on) | (u8::from(category.breathing) << 1);
+        }
+
+        Ok(flags_by_led)
+    }
+
+    /// Generates `rgb_matrix_indicators_advanced_user` for category flags.
+    ///
+    /// Emits a per-layer LED flag table (`0x01` always-on, `0x02` breathing).
+    /// Breathing LEDs are scaled by a sine wave over their base color; while
+    /// the RGB timeout (or the idle effect's off state) is active, every LED
+    /// except the always-on ones is set to black.
+    fn generate_rgb_indicators_code(&self) -> Result<String> {
+        if !self.geometry.has_rgb_matrix()
+            || !self
+                .layout
+                .categories
+                .iter()
+                .any(|c| c.always_on || c.breathing)
+        {
+            return Ok(String::new());
+        }
+
+        let layer_count = self.firmware_layers.len();
+        let led_count = self.mapping.key_count();
+        let sleeping = if !self.has_always_on_keys() {
+            None
+        } else if self.layout.idle_effect_settings.enabled {
+            Some("idle_state == IDLE_STATE_OFF")
+        } else if self.layout.rgb_timeout_ms > 0 {
+            Some("last_input_activity_elapsed() >= LQMK_RGB_TIMEOUT_MS")
+        } else {
+            None
+        };
+
+        let mut code = String::new();
+        code.push_str("#ifdef RGB_MATRIX_ENABLE\n");
+        code.push_str("// Category RGB flags\n");
+        code.push_str("#define LQMK_LED_ALWAYS_ON 0x01\n");
+        code.push_str("#define LQMK_LED_BREATHING 0x02\n");
+        code.push('\n');
+        code.push_str(&format!(
+            "const uint8_t PROGMEM lqmk_led_flags[{layer_count}][{led_count}] = {{\n"
+        ));
+        for (position, (layer_idx, _, layer_name)) in self.enabled_layers().enumerate() {
+            let flags = self
+                .generate_layer_flags_by_led(layer_idx)?
+                .iter()
+                .map(|flags| format!("0x{flags:02X}"))
+                .collect::<Vec<_>>()
+                .join(", ");
+            code.push_str(&format!("    [{layer_name}] = {{{flags}}}"));
+            code.push_str(if position < layer_count - 1 {
+                ",\n"
+            } else {
+                "\n"
+            });
+        }
+        code.push_str("};\n");
+        code.push('\n');
+
+        code.push_str(
+            "bool rgb_matrix_indicators_advanced_user(uint8_t led_min, uint8_t led_max) {\n",
+        );
+        code.push_str(
+            "    uint8_t layer = get_highest_layer(layer_state | default_layer_state);\n",
+        );
+        code.push_str(&format!("    if (layer >= {layer_count}) {{\n"));
+        code.push_str("        return false;\n");
+        code.push_str("    }\n");
+        if let Some(sleeping) = sleeping {
+            code.push_str(&format!("    bool sleeping = {sleeping};\n"));
+        }
+        code.push_str("    uint8_t breath = sin8((uint8_t)(timer_read() / 8));\n");
+        code.push_str(&format!(
+            "    for (uint8_t i = led_min; i < led_max && i < {led_count}; i++) {{\n"
+        ));
+        code.push_str("        uint8_t flags = pgm_read_byte(&lqmk_led_flags[layer][i]);\n");
+        if sleeping.is_some() {
+            code.push_str("        if (sleeping && !(flags & LQMK_LED_ALWAYS_ON)) {\n");
+            code.push_str("            rgb_matrix_set_color(i, 0, 0, 0);\n");
+            code.push_str("        } else if (flags & LQMK_LED_BREATHING) {\n");
+        } else {
+            code.push_str("        if (flags & LQMK_LED_BREATHING) {\n");
+        }
+        code.push_str("            rgb_matrix_set_color(i,\n");
+        for channel in 0..3 {
+            let end = if channel < 2 { "," } else { ");" };
+            code.push_str(&format!(
+                "                scale8(pgm_read_byte(&layer_base_colors[layer][i][{channel}]), breath){end}\n"
+            ));
+        }
+        code.push_str("        }\n");
+        code.push_str("    }\n");
+        code.push_str("    return false;\n");
+        code.push_str("}\n");
+        code.push_str("#endif\n");
+
+        Ok(code)
+    }
+
     /// Gets the keymap output directory.
     ///
     /// Creates directory structure if it doesn't exist:
     /// {`qmk_firmware}/keyboards/{keyboard}/keymaps/{keymap`}/
     ///
     /// The keyboard path may include variant subdirectories (e.g., "`keebart/corne_choc_pro/standard`").
-    /// The keymap directory is created under the exact keyboard path used for building.
+    /// The keymap directory is created under the exact keyboard path used for building,
+    /// after checking that it cannot resolve outside the keyboard's `keymaps/` directory.
     fn get_keymap_directory(&self) -> Result<std::path::PathBuf> {
         let qmk_path = self
             .config
@@ -504,11 +1083,7 @@ impl<'a> FirmwareGenerator<'a> {
             .as_deref()
             .context("Keymap name not set in layout metadata")?;
 
-        let keymap_dir = qmk_path
-            .join("keyboards")
-            .join(keyboard)
-            .join("keymaps")
-            .join(keymap);
+        let keymap_dir = paths::resolve_keymap_dir(qmk_path, keyboard, keymap)?;
 
         // Create directory if it doesn't exist
         fs::create_dir_all(&keymap_dir)
@@ -547,29 +1122,6 @@ impl<'a> FirmwareGenerator<'a> {
         Ok(output_dir)
     }
 
-    /// Writes a file to both the timestamped archive and the QMK keymap directory.
-    ///
-    /// Returns the path in the timestamped directory.
-    fn write_file_to_both(
-        &self,
-        timestamp_dir: &std::path::Path,
-        filename: &str,
-        content: &str,
-    ) -> Result<String> {
-        // Write to timestamped archive directory
-        let archive_path = timestamp_dir.join(filename);
-        fs::write(&archive_path, content)
-            .with_context(|| format!("Failed to write {}", archive_path.display()))?;
-
-        // Write to QMK keymap directory
-        let keymap_dir = self.get_keymap_directory()?;
-        let qmk_path = keymap_dir.join(filename);
-        fs::write(&qmk_path, content)
-            .with_context(|| format!("Failed to write {}", qmk_path.display()))?;
-
-        Ok(archive_path.display().to_string())
-    }
-
     /// Generates idle effect state machine code if enabled.
     ///
     /// Emits C code to manage idle timeout and transition between ACTIVE, IDLE_EFFECT, and OFF states.
@@ -614,8 +1166,16 @@ impl<'a> FirmwareGenerator<'a> {
         code.push_str(
             "            if (elapsed >= LQMK_IDLE_TIMEOUT_MS + LQMK_IDLE_EFFECT_DURATION_MS) {\n",
         );
-        code.push_str("                // Transition to off\n");
-        code.push_str("                rgb_matrix_disable_noeeprom();\n");
+        if self.has_always_on_keys() {
+            // The indicators callback blanks everything but the always-on keys
+            code.push_str("                // Transition to off (always-on keys stay lit)\n");
+            code.push_str(
+                "                rgb_matrix_mode_noeeprom(RGB_MATRIX_TUI_LAYER_COLORS);\n",
+            );
+        } else {
+            code.push_str("                // Transition to off\n");
+            code.push_str("                rgb_matrix_disable_noeeprom();\n");
+        }
         code.push_str("                idle_state = IDLE_STATE_OFF;\n");
         code.push_str("            }\n");
         code.push_str("            break;\n");
@@ -698,9 +1258,11 @@ impl<'a> FirmwareGenerator<'a> {
         code
     }
 
-    /// Generates helper functions for 3-way tap dances (with hold behavior).
+    /// Generates callback functions for tap dances that need the advanced form.
     ///
-    /// For each tap dance that has a hold action, generates finished/reset callback functions.
+    /// Dances with a hold action or per-dance options get finished/reset
+    /// callbacks (plus an each-tap callback for on-each-tap dances). Dances with
+    /// a custom tapping term also get a `get_tapping_term` override.
     fn generate_tap_dance_helpers(&self) -> String {
         let mut code = String::new();
 
@@ -708,16 +1270,25 @@ impl<'a> FirmwareGenerator<'a> {
         let mut sorted_tds: Vec<_> = self.layout.tap_dances.iter().collect();
         sorted_tds.sort_by_key(|td| &td.name);
 
-        for td in sorted_tds {
-            // Only generate helper functions for 3-way tap dances (those with hold)
-            if td.hold.is_none() {
+        for td in &sorted_tds {
+            if !td.needs_advanced_action() {
                 continue;
             }
 
             let name_lower = td.name.to_lowercase();
-            let single_tap = &td.single_tap;
-            let double_tap = td.double_tap.as_ref().unwrap(); // 3-way always has double_tap
-            let hold = td.hold.as_ref().unwrap();
+            let single_tap = self.resolve_keycode(&td.single_tap);
+            let double_tap = td.double_tap.as_deref().map(|k| self.resolve_keycode(k));
+            let hold = td.hold.as_deref().map(|k| self.resolve_keycode(k));
+
+            // Generate each-tap function: the single tap fires immediately
+            if td.on_each_tap {
+                code.push_str(&format!(
+                    "void td_{}_each(tap_dance_state_t *state, void *user_data) {{\n",
+                    name_lower
+                ));
+                code.push_str(&format!("    tap_code16({});\n", single_tap));
+                code.push_str("}\n\n");
+            }
 
             // Generate finished function
             code.push_str(&format!(
@@ -725,13 +1296,28 @@ impl<'a> FirmwareGenerator<'a> {
                 name_lower
             ));
             code.push_str("    if (state->count == 1) {\n");
-            code.push_str("        if (state->interrupted || !state->pressed) {\n");
-            code.push_str(&format!("            register_code16({});\n", single_tap));
-            code.push_str("        } else {\n");
-            code.push_str(&format!("            register_code16({});\n", hold));
-            code.push_str("        }\n");
-            code.push_str("    } else if (state->count == 2) {\n");
-            code.push_str(&format!("        register_code16({});\n", double_tap));
+            match (&hold, td.on_each_tap) {
+                (Some(hold), false) => {
+                    code.push_str("        if (state->interrupted || !state->pressed) {\n");
+                    code.push_str(&format!("            register_code16({});\n", single_tap));
+                    code.push_str("        } else {\n");
+                    code.push_str(&format!("            register_code16({});\n", hold));
+                    code.push_str("        }\n");
+                }
+                (Some(hold), true) => {
+                    code.push_str("        if (!state->interrupted && state->pressed) {\n");
+                    code.push_str(&format!("            register_code16({});\n", hold));
+                    code.push_str("        }\n");
+                }
+                (None, false) => {
+                    code.push_str(&format!("        register_code16({});\n", single_tap));
+                }
+                (None, true) => {}
+            }
+            if let Some(double_tap) = &double_tap {
+                code.push_str("    } else if (state->count == 2) {\n");
+                code.push_str(&format!("        register_code16({});\n", double_tap));
+            }
             code.push_str("    }\n");
             code.push_str("}\n\n");
 
@@ -741,10 +1327,36 @@ impl<'a> FirmwareGenerator<'a> {
                 name_lower
             ));
             code.push_str("    if (state->count == 1) {\n");
-            code.push_str(&format!("        unregister_code16({});\n", single_tap));
-            code.push_str(&format!("        unregister_code16({});\n", hold));
-            code.push_str("    } else if (state->count == 2) {\n");
-            code.push_str(&format!("        unregister_code16({});\n", double_tap));
+            if !td.on_each_tap {
+                code.push_str(&format!("        unregister_code16({});\n", single_tap));
+            }
+            if let Some(hold) = &hold {
+                code.push_str(&format!("        unregister_code16({});\n", hold));
+            }
+            if let Some(double_tap) = &double_tap {
+                code.push_str("    } else if (state->count == 2) {\n");
+                code.push_str(&format!("        unregister_code16({});\n", double_tap));
+            }
+            code.push_str("    }\n");
+            code.push_str("}\n\n");
+        }
+
+        // Per-dance tapping terms (requires TAPPING_TERM_PER_KEY in config.h)
+        let custom_terms: Vec<_> = sorted_tds
+            .iter()
+            .filter_map(|td| td.tapping_term_ms.map(|term| (td, term)))
+            .collect();
+        if !custom_terms.is_empty() {
+            code.push_str("uint16_t get_tapping_term(uint16_t keycode, keyrecord_t *record) {\n");
+            code.push_str("    switch (keycode) {\n");
+            for (td, term) in custom_terms {
+                code.push_str(&format!(
+                    "        case TD(TD_{}):\n            return {};\n",
+                    td.name.to_uppercase(),
+                    term
+                ));
+            }
+            code.push_str("        default:\n            return TAPPING_TERM;\n");
             code.push_str("    }\n");
             code.push_str("}\n\n");
         }
@@ -755,7 +1367,8 @@ impl<'a> FirmwareGenerator<'a> {
     /// Generates tap dance actions array.
     ///
     /// Creates `tap_dance_action_t tap_dance_actions[] = { ... };`
-    /// Uses ACTION_TAP_DANCE_DOUBLE for 2-way, ACTION_TAP_DANCE_FN_ADVANCED for 3-way.
+    /// Uses ACTION_TAP_DANCE_DOUBLE for plain 2-way dances and
+    /// ACTION_TAP_DANCE_FN_ADVANCED for dances with a hold or per-dance options.
     fn generate_tap_dance_actions(&self) -> String {
         if self.layout.tap_dances.is_empty() {
             return String::new();
@@ -771,20 +1384,26 @@ impl<'a> FirmwareGenerator<'a> {
         for (idx, td) in sorted_tds.iter().enumerate() {
             let enum_name = format!("TD_{}", td.name.to_uppercase());
 
-            let action = if td.has_hold() {
-                // 3-way tap dance: ACTION_TAP_DANCE_FN_ADVANCED(NULL, finished, reset)
+            let action = if td.needs_advanced_action() {
+                // Hold or per-dance options: ACTION_TAP_DANCE_FN_ADVANCED(each, finished, reset)
                 let name_lower = td.name.to_lowercase();
+                let each = if td.on_each_tap {
+                    format!("td_{name_lower}_each")
+                } else {
+                    "NULL".to_string()
+                };
                 format!(
-                    "ACTION_TAP_DANCE_FN_ADVANCED(NULL, td_{}_finished, td_{}_reset)",
-                    name_lower, name_lower
+                    "ACTION_TAP_DANCE_FN_ADVANCED({}, td_{}_finished, td_{}_reset)",
+                    each, name_lower, name_lower
                 )
             } else if let Some(double) = &td.double_tap {
                 // 2-way tap dance: ACTION_TAP_DANCE_DOUBLE(single, double)
-                let single = &td.single_tap;
+                let single = self.resolve_keycode(&td.single_tap);
+                let double = self.resolve_keycode(double);
                 format!("ACTION_TAP_DANCE_DOUBLE({}, {})", single, double)
             } else {
                 // Single-tap-only fallback: treat as double with same keycode to avoid helper fns
-                let single = &td.single_tap;
+                let single = self.resolve_keycode(&td.single_tap);
                 format!("ACTION_TAP_DANCE_DOUBLE({}, {})", single, single)
             };
 
@@ -823,24 +1442,18 @@ impl<'a> FirmwareGenerator<'a> {
     /// This generates a minimal keymap-specific config.h.
     /// Note: `RGB_MATRIX_LED_COUNT` should be defined in the keyboard's variant-specific
     /// keyboard.json file, not in the keymap config.h.
-    #[allow(clippy::cast_possible_truncation)]
     pub fn generate_merged_config_h(&self) -> Result<String> {
+        let context = self.template_context()?;
+        self.templates().render(TemplateKind::ConfigH, &context)
+    }
+
+    /// Generates the config.h defines that follow the file header.
+    #[allow(clippy::cast_possible_truncation)]
+    fn generate_config_defines(&self) -> Result<String> {
         use crate::models::HoldDecisionMode;
 
         let mut content = String::new();
 
-        // Add our generated configuration
-        content.push_str(&format!("// Generated by {}\n", APP_BINARY_NAME));
-        content.push_str(&format!("// Layout: {}\n", self.layout.metadata.name));
-        content.push_str(&format!(
-            "// Generated: {}\n",
-            chrono::Local::now().format("%Y-%m-%d %H:%M:%S")
-        ));
-        content.push('\n');
-        content.push_str("#pragma once\n");
-        content.push('\n');
-        content.push_str("// Add keymap-specific configuration here\n");
-
         // === Tap-Hold Settings ===
         let ths = &self.layout.tap_hold_settings;
 
@@ -892,6 +1505,18 @@ impl<'a> FirmwareGenerator<'a> {
             }
         }
 
+        // === Tap Dance Settings ===
+        // Per-dance tapping terms are served by get_tapping_term() in keymap.c
+        if self
+            .layout
+            .tap_dances
+            .iter()
+            .any(|td| td.tapping_term_ms.is_some())
+        {
+            content.push_str("\n// Tap Dance Configuration\n");
+            content.push_str("#define TAPPING_TERM_PER_KEY\n");
+        }
+
         // === RGB Settings ===
         // RGB Matrix brightness (0-255, converted from 0-100%)
         let brightness_255 = (u16::from(self.layout.rgb_brightness.as_percent()) * 255 / 100) as u8;
@@ -920,6 +1545,14 @@ impl<'a> FirmwareGenerator<'a> {
                 "#define LQMK_IDLE_EFFECT_MODE {}\n",
                 idle_settings.idle_effect_mode.qmk_mode_name()
             ));
+        } else if self.layout.rgb_timeout_ms > 0 && self.has_always_on_keys() {
+            // Always-on keys need the matrix running, so the timeout blanks
+            // the other LEDs in rgb_matrix_indicators_advanced_user instead
+            content.push_str("\n// RGB Timeout (other keys go dark, always-on keys stay lit)\n");
+            content.push_str(&format!(
+                "#define LQMK_RGB_TIMEOUT_MS {}\n",
+                self.layout.rgb_timeout_ms
+            ));
         } else {
             // RGB Matrix timeout (auto-off after inactivity) - only when idle effect is disabled
             if self.layout.rgb_timeout_ms > 0 {
@@ -940,7 +1573,7 @@ impl<'a> FirmwareGenerator<'a> {
             content.push_str("#    undef RGB_MATRIX_DEFAULT_MODE\n");
             content.push_str("#    define RGB_MATRIX_DEFAULT_MODE RGB_MATRIX_TUI_LAYER_COLORS\n");
             content.push_str("#    define LAYER_BASE_COLORS_LAYER_COUNT ");
-            content.push_str(&format!("{}\n", self.layout.layers.len()));
+            content.push_str(&format!("{}\n", self.firmware_layers.len()));
             content.push_str("#endif\n");
         }
 
@@ -952,7 +1585,7 @@ impl<'a> FirmwareGenerator<'a> {
 mod tests {
     use super::*;
     use crate::models::keyboard_geometry::KeyGeometry;
-    use crate::models::layer::{KeyDefinition, Layer, Position};
+    use crate::models::layer::{EncoderMapping, KeyDefinition, Layer, Position};
     use crate::models::RgbColor;
     use std::path::PathBuf;
 
@@ -972,8 +1605,12 @@ mod tests {
         layout.metadata.output_format = Some("uf2".to_string());
 
         let mut layer = Layer::new(0, "Base", RgbColor::new(255, 255, 255)).unwrap();
-        layer.add_key(KeyDefinition::new(Position::new(0, 0), "KC_A"));
-        layer.add_key(KeyDefinition::new(Position::new(0, 1), "KC_B"));
+        layer
+            .add_key(KeyDefinition::new(Position::new(0, 0), "KC_A"))
+            .unwrap();
+        layer
+            .add_key(KeyDefinition::new(Position::new(0, 1), "KC_B"))
+            .unwrap();
         layout.add_layer(layer).unwrap();
 
         let mut geometry = KeyboardGeometry::new("test", "LAYOUT", 2, 2);
@@ -1005,7 +1642,8 @@ mod tests {
 
         // Verify keymap structure
         assert!(keymap_c.contains("const uint16_t PROGMEM keymaps[]"));
-        assert!(keymap_c.contains("[0] = LAYOUT("));
+        assert!(keymap_c.contains("enum layers {\n    _BASE = 0,\n};"));
+        assert!(keymap_c.contains("[_BASE] = LAYOUT("));
         assert!(keymap_c.contains("KC_A"));
         assert!(keymap_c.contains("KC_B"));
 
@@ -1021,6 +1659,169 @@ mod tests {
         assert!(keymap_c.contains("#endif"));
     }
 
+    /// Adds layers "Exp" (disabled) and "Nav", with a key on the base layer
+    /// reaching Nav.
+    fn add_experimental_layers(layout: &mut Layout) {
+        for (number, name) in [(1, "Exp"), (2, "Nav")] {
+            let mut layer = Layer::new(number, name, RgbColor::new(0, 0, 255)).unwrap();
+            for col in 0..2 {
+                layer
+                    .add_key(KeyDefinition::new(Position::new(0, col), "KC_TRNS"))
+                    .unwrap();
+            }
+            layout.add_layer(layer).unwrap();
+        }
+        layout.layers[1].enabled = false;
+        let nav_id = layout.layers[2].id.clone();
+        layout.layers[0].keys[1].keycode = format!("MO(@{nav_id})");
+    }
+
+    #[test]
+    fn test_disabled_layers_are_skipped() {
+        let (mut layout, geometry, mapping, config, keycode_db) = create_test_setup();
+        add_experimental_layers(&mut layout);
+        let generator = FirmwareGenerator::new(&layout, &geometry, &mapping, &config, &keycode_db);
+
+        let keymap_c = generator.generate_keymap_c().unwrap();
+        assert!(keymap_c.contains("enum layers {\n    _BASE = 0,\n    _NAV,\n};"));
+        assert!(!keymap_c.contains("_EXP"));
+        assert!(keymap_c.contains("KC_A, MO(_NAV)"));
+        assert!(keymap_c.contains("layer_base_colors[2]"));
+    }
+
+    /// Splits the arguments of a `LAYOUT(...)` call at top-level commas;
+    /// `args` starts after the opening parenthesis.
+    fn split_layout_args(args: &str) -> Vec<String> {
+        let mut keys = vec![String::new()];
+        let mut depth = 0;
+        for c in args.chars() {
+            match c {
+                '(' => depth += 1,
+                ')' if depth == 0 => break,
+                ')' => depth -= 1,
+                ',' if depth == 0 => {
+                    keys.push(String::new());
+                    continue;
+                }
+                _ => {}
+            }
+            if !c.is_whitespace() || depth > 0 {
+                keys.last_mut().unwrap().push(c);
+            }
+        }
+        keys.into_iter().map(|key| key.trim().to_string()).collect()
+    }
+
+    #[test]
+    fn test_keymap_json_matches_keymap_c_order() {
+        let (mut layout, geometry, mapping, config, keycode_db) = create_test_setup();
+        add_experimental_layers(&mut layout);
+        layout.layers[2].keys[0].keycode = "LT(2, KC_SPC)".to_string();
+        let generator = FirmwareGenerator::new(&layout, &geometry, &mapping, &config, &keycode_db);
+
+        let keymap_c = generator.generate_keymap_c().unwrap();
+        let json: serde_json::Value =
+            serde_json::from_str(&generator.generate_keymap_json().unwrap()).unwrap();
+        assert_eq!(json["keyboard"], "test");
+        assert_eq!(json["keymap"], "default");
+        assert_eq!(json["layout"], "LAYOUT");
+        let layers = json["layers"].as_array().unwrap();
+        assert_eq!(layers.len(), 2, "Disabled layers are skipped");
+
+        for (number, enum_name) in ["_BASE", "_NAV"].into_iter().enumerate() {
+            let start = keymap_c.find(&format!("[{enum_name}] = LAYOUT(")).unwrap()
+                + enum_name.len()
+                + "[] = LAYOUT(".len();
+            let c_keys = split_layout_args(&keymap_c[start..]);
+            let json_keys: Vec<&str> = layers[number]
+                .as_array()
+                .unwrap()
+                .iter()
+                .map(|key| key.as_str().unwrap())
+                .collect();
+            assert_eq!(c_keys.len(), json_keys.len());
+
+            for (position, (c_key, json_key)) in c_keys.iter().zip(&json_keys).enumerate() {
+                // keymap.json numbers layers where keymap.c uses the layer enum
+                let c_key = c_key.replace("_BASE", "0").replace("_NAV", "1");
+                assert_eq!(c_key, *json_key, "layer {number}, position {position}");
+            }
+        }
+        assert_eq!(layers[0][1], "MO(1)");
+        assert_eq!(layers[1][0], "LT(1, KC_SPC)");
+    }
+
+    #[test]
+    fn test_reference_to_disabled_layer_fails() {
+        let (mut layout, geometry, mapping, config, keycode_db) = create_test_setup();
+        add_experimental_layers(&mut layout);
+        layout.layers[2].keys[0].keycode = "TG(1)".to_string();
+        let generator = FirmwareGenerator::new(&layout, &geometry, &mapping, &config, &keycode_db);
+
+        let error = generator.generate_keymap_c().unwrap_err().to_string();
+        assert!(error.contains("Layer 2 'Nav' key (0, 0) TG(1) references disabled layer 1 'Exp'"));
+    }
+
+    #[test]
+    fn test_encoder_map_uses_layer_assignments() {
+        let (mut layout, geometry, mapping, config, keycode_db) = create_test_setup();
+        layout.layers[0].set_encoder(1, EncoderMapping::new("KC_VOLD", "KC_VOLU"));
+        let generator = FirmwareGenerator::new(&layout, &geometry, &mapping, &config, &keycode_db);
+
+        let encoder_map = generator.generate_conditional_encoder_map().unwrap();
+        assert!(encoder_map.contains(
+            "ENCODER_CCW_CW(RM_NEXT, RM_PREV),\n        ENCODER_CCW_CW(KC_VOLD, KC_VOLU)"
+        ));
+        assert!(encoder_map.contains("ENCODER_CCW_CW(RM_VALU, RM_VALD)"));
+    }
+
+    #[test]
+    fn test_layer_enum_names_sanitized_and_deduplicated() {
+        let color = RgbColor::new(0, 0, 0);
+        let layers: Vec<Layer> = ["Base", "nav", "Nav & Sym", "Nav", "Nav", "äöü", "2nd layer"]
+            .iter()
+            .enumerate()
+            .map(|(i, name)| Layer::new(i as u8, *name, color).unwrap())
+            .collect();
+
+        assert_eq!(
+            layer_enum_names(&layers),
+            [
+                "_BASE",
+                "_NAV",
+                "_NAV_SYM",
+                "_NAV_2",
+                "_NAV_3",
+                "_LAYER5",
+                "_2ND_LAYER"
+            ]
+        );
+    }
+
+    #[test]
+    fn test_layer_keycodes_use_enum_names() {
+        let (mut layout, geometry, mapping, config, keycode_db) = create_test_setup();
+        let mut nav = Layer::new(1, "Nav", RgbColor::new(0, 0, 0)).unwrap();
+        nav.add_key(KeyDefinition::new(Position::new(0, 0), "KC_TRNS"))
+            .unwrap();
+        nav.add_key(KeyDefinition::new(Position::new(0, 1), "TO(0)"))
+            .unwrap();
+        let nav_id = nav.id.clone();
+        layout.add_layer(nav).unwrap();
+        layout.layers[0].keys[0].keycode = format!("LT(@{nav_id}, KC_SPC)");
+        layout.layers[0].keys[1].keycode = "MO(7)".to_string();
+
+        let generator = FirmwareGenerator::new(&layout, &geometry, &mapping, &config, &keycode_db);
+        let keymap_c = generator.generate_keymap_c().unwrap();
+
+        assert!(keymap_c.contains("    _NAV,\n"));
+        assert!(keymap_c.contains("[_NAV] = LAYOUT(KC_TRNS, TO(_BASE))"));
+        assert!(keymap_c.contains("LT(_NAV, KC_SPC)"));
+        // References to missing layers are left as-is
+        assert!(keymap_c.contains("MO(7)"));
+        assert!(keymap_c.contains("    [_NAV] = {\n"));
+    }
+
     #[test]
     fn test_generate_merged_config_h_sets_default_mode_when_colored_and_rgb() {
         let (mut layout, geometry, mapping, config, keycode_db) = create_test_setup();
@@ -1095,8 +1896,12 @@ mod tests {
 
         // Add a second layer with a different default color
         let mut layer2 = Layer::new(1, "Navigation", RgbColor::new(0, 128, 255)).unwrap();
-        layer2.add_key(KeyDefinition::new(Position::new(0, 0), "KC_LEFT"));
-        layer2.add_key(KeyDefinition::new(Position::new(0, 1), "KC_RIGHT"));
+        layer2
+            .add_key(KeyDefinition::new(Position::new(0, 0), "KC_LEFT"))
+            .unwrap();
+        layer2
+            .add_key(KeyDefinition::new(Position::new(0, 1), "KC_RIGHT"))
+            .unwrap();
         layout.add_layer(layer2).unwrap();
 
         let generator = FirmwareGenerator::new(&layout, &geometry, &mapping, &config, &keycode_db);
@@ -1255,14 +2060,22 @@ mod tests {
         // Layer 0: White default
         // Layer 1: Red default
         let mut layer1 = Layer::new(1, "Red Layer", RgbColor::new(255, 0, 0)).unwrap();
-        layer1.add_key(KeyDefinition::new(Position::new(0, 0), "KC_1"));
-        layer1.add_key(KeyDefinition::new(Position::new(0, 1), "KC_2"));
+        layer1
+            .add_key(KeyDefinition::new(Position::new(0, 0), "KC_1"))
+            .unwrap();
+        layer1
+            .add_key(KeyDefinition::new(Position::new(0, 1), "KC_2"))
+            .unwrap();
         layout.add_layer(layer1).unwrap();
 
         // Layer 2: Green default
         let mut layer2 = Layer::new(2, "Green Layer", RgbColor::new(0, 255, 0)).unwrap();
-        layer2.add_key(KeyDefinition::new(Position::new(0, 0), "KC_3"));
-        layer2.add_key(KeyDefinition::new(Position::new(0, 1), "KC_4"));
+        layer2
+            .add_key(KeyDefinition::new(Position::new(0, 0), "KC_3"))
+            .unwrap();
+        layer2
+            .add_key(KeyDefinition::new(Position::new(0, 1), "KC_4"))
+            .unwrap();
         layout.add_layer(layer2).unwrap();
 
         let generator = FirmwareGenerator::new(&layout, &geometry, &mapping, &config, &keycode_db);
@@ -1626,8 +2439,8 @@ mod tests {
 
     //     // Add a second layer
     //     let mut layer2 = Layer::new(1, "Layer 2", RgbColor::new(0, 255, 0)).unwrap();
-    //     layer2.add_key(KeyDefinition::new(Position::new(0, 0), "KC_1"));
-    //     layer2.add_key(KeyDefinition::new(Position::new(0, 1), "KC_2"));
+    //     layer2.add_key(KeyDefinition::new(Position::new(0, 0), "KC_1")).unwrap();
+    //     layer2.add_key(KeyDefinition::new(Position::new(0, 1), "KC_2")).unwrap();
     //     layout.add_layer(layer2).unwrap();
 
     //     let generator = FirmwareGenerator::new(&layout, &geometry, &mapping, &config, &keycode_db);
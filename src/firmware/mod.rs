@@ -1,13 +1,29 @@
 //! Firmware generation and compilation.
 //!
-//! This module handles generating keymap.c and config.h files,
-//! as well as background compilation of QMK firmware using `qmk compile`.
+//! This module handles generating keymap.c, config.h and rules.mk files
+//! from overridable templates, as well as background compilation of QMK firmware using `qmk compile`
+//! or direct `make` (see [`backend`]) and reporting of the resulting firmware size. Builds first
+//! check that the toolchain and QMK tree they need are in place.
 
+pub mod backend;
 pub mod builder;
+pub mod completion;
+pub mod converter;
+pub mod environment;
 pub mod generator;
+pub mod paths;
+pub mod preflight;
+pub mod size;
+pub mod target;
+pub mod templates;
 pub mod validator;
 
 // Re-export firmware types
+pub use backend::Backend;
 pub use builder::{BuildState, BuildStatus};
-pub use generator::FirmwareGenerator;
-pub use validator::FirmwareValidator;
+pub use completion::{BuildCompletion, CompletionHooks};
+pub use generator::{FileStatus, FirmwareGenerator, GenerationError, GenerationOutcome};
+pub use preflight::KeyboardMismatch;
+pub use size::{McuSpec, SizeEstimate};
+pub use target::BuildTarget;
+pub use validator::{FirmwareValidator, ValidationCache};
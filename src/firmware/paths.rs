@@ -0,0 +1,180 @@
+//! Safe resolution of QMK keymap output paths.
+//!
+//! Keymap and keyboard names come from layout metadata, which may be edited by
+//! hand or sent over HTTP by the web editor. Before anything is written, the
+//! keymap directory is checked to resolve to a direct child of
+//! `<qmk>/keyboards/<keyboard>/keymaps/`, so names like `../../evil` or
+//! `..\..\evil` can never place files outside the QMK tree.
+
+use anyhow::{Context, Result};
+use std::path::{Component, Path, PathBuf};
+
+/// Validates a keymap name for use as a single directory name.
+///
+/// Rejects empty names, `.`/`..`, and anything containing a Unix or Windows
+/// path separator, a drive colon, or a NUL byte.
+pub fn validate_keymap_name(keymap: &str) -> Result<()> {
+    if keymap.trim().is_empty() {
+        anyhow::bail!("Keymap name cannot be empty");
+    }
+    if keymap == "." || keymap == ".." || keymap.contains("..") {
+        anyhow::bail!("Invalid keymap name '{keymap}': path traversal not allowed");
+    }
+    if keymap.contains(['/', '\\', ':', '\0']) {
+        anyhow::bail!("Invalid keymap name '{keymap}': must not contain path separators");
+    }
+    Ok(())
+}
+
+/// Validates a keyboard path such as `splitkb/halcyon/corne`.
+///
+/// Forward slashes separate keyboard folders; everything else that could
+/// leave `keyboards/` (`..`, absolute paths, backslashes, drive letters) is
+/// rejected.
+pub fn validate_keyboard_path(keyboard: &str) -> Result<()> {
+    if keyboard.trim().is_empty() {
+        anyhow::bail!("Keyboard path cannot be empty");
+    }
+    if keyboard.contains(['\\', ':', '\0']) {
+        anyhow::bail!("Invalid keyboard path '{keyboard}': must use '/' separators only");
+    }
+    let all_normal = Path::new(keyboard)
+        .components()
+        .all(|component| matches!(component, Component::Normal(_)));
+    if !all_normal || keyboard.split('/').any(|part| part == "..") {
+        anyhow::bail!("Invalid keyboard path '{keyboard}': path traversal not allowed");
+    }
+    Ok(())
+}
+
+/// Resolves the keymap directory `<qmk>/keyboards/<keyboard>/keymaps/<keymap>`.
+///
+/// Validates both names, then resolves symlinks on the existing part of the
+/// path and checks that the result is still directly inside the keyboard's
+/// `keymaps` directory. Nothing is created on disk.
+pub fn resolve_keymap_dir(qmk_path: &Path, keyboard: &str, keymap: &str) -> Result<PathBuf> {
+    validate_keyboard_path(keyboard)?;
+    validate_keymap_name(keymap)?;
+
+    let keymaps_root = qmk_path.join("keyboards").join(keyboard).join("keymaps");
+    let keymap_dir = keymaps_root.join(keymap);
+
+    let resolved_root = resolve_existing(&keymaps_root)?;
+    let resolved_dir = resolve_existing(&keymap_dir)?;
+    if resolved_dir.parent() != Some(resolved_root.as_path()) {
+        anyhow::bail!(
+            "Keymap directory {} resolves outside {}",
+            keymap_dir.display(),
+            keymaps_root.display()
+        );
+    }
+
+    Ok(keymap_dir)
+}
+
+/// Canonicalizes the deepest existing ancestor of `path` and appends the rest.
+fn resolve_existing(path: &Path) -> Result<PathBuf> {
+    let mut existing = path;
+    let mut missing = Vec::new();
+    while !existing.exists() {
+        let Some(parent) = existing.parent() else {
+            break;
+        };
+        if let Some(name) = existing.file_name() {
+            missing.push(name.to_os_string());
+        }
+        existing = parent;
+    }
+
+    let mut resolved = if existing.as_os_str().is_empty() {
+        std::env::current_dir().context("Failed to read current directory")?
+    } else {
+        existing
+            .canonicalize()
+            .with_context(|| format!("Failed to resolve {}", existing.display()))?
+    };
+    for name in missing.into_iter().rev() {
+        resolved.push(name);
+    }
+    Ok(resolved)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tempfile::TempDir;
+
+    #[test]
+    fn test_keymap_name_rejects_unix_and_windows_traversal() {
+        assert!(validate_keymap_name("default").is_ok());
+        assert!(validate_keymap_name("my_keymap-2").is_ok());
+
+        for bad in [
+            "",
+            ".",
+            "..",
+            "../../something",
+            "foo/bar",
+            "/etc",
+            "..\\..\\something",
+            "foo\\bar",
+            "C:\\Windows",
+            "C:evil",
+            "\\\\server\\share",
+        ] {
+            assert!(
+                validate_keymap_name(bad).is_err(),
+                "{bad:?} should be rejected"
+            );
+        }
+    }
+
+    #[test]
+    fn test_keyboard_path_rejects_traversal() {
+        assert!(validate_keyboard_path("crkbd").is_ok());
+        assert!(validate_keyboard_path("splitkb/halcyon/corne").is_ok());
+
+        for bad in [
+            "",
+            "../secret",
+            "crkbd/../../etc",
+            "/etc/keyboard",
+            "..\\secret",
+            "crkbd\\..\\..\\etc",
+            "C:\\keyboards",
+        ] {
+            assert!(
+                validate_keyboard_path(bad).is_err(),
+                "{bad:?} should be rejected"
+            );
+        }
+    }
+
+    #[test]
+    fn test_resolve_keymap_dir_stays_inside_keymaps() {
+        let temp = TempDir::new().unwrap();
+        let qmk = temp.path();
+        std::fs::create_dir_all(qmk.join("keyboards/crkbd/keymaps")).unwrap();
+
+        let dir = resolve_keymap_dir(qmk, "crkbd", "default").unwrap();
+        assert_eq!(dir, qmk.join("keyboards/crkbd/keymaps/default"));
+        assert!(!dir.exists(), "resolving must not create the directory");
+
+        assert!(resolve_keymap_dir(qmk, "crkbd", "../../evil").is_err());
+        assert!(resolve_keymap_dir(qmk, "crkbd", "..\\..\\evil").is_err());
+        assert!(resolve_keymap_dir(qmk, "../crkbd", "default").is_err());
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn test_resolve_keymap_dir_rejects_symlink_escape() {
+        let temp = TempDir::new().unwrap();
+        let qmk = temp.path().join("qmk");
+        let outside = temp.path().join("outside");
+        std::fs::create_dir_all(qmk.join("keyboards/crkbd/keymaps")).unwrap();
+        std::fs::create_dir_all(&outside).unwrap();
+        std::os::unix::fs::symlink(&outside, qmk.join("keyboards/crkbd/keymaps/linked")).unwrap();
+
+        assert!(resolve_keymap_dir(&qmk, "crkbd", "linked").is_err());
+    }
+}
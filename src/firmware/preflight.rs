@@ -0,0 +1,187 @@
+//! Checks run before firmware files are generated or built.
+//!
+//! Generation writes into the QMK tree, so a layout made for one keyboard
+//! must not silently land in another keyboard's keymap directory because the
+//! configured build target (`build.keyboard`, or `--keyboard` on the CLI) is
+//! stale. Generate, build and the web endpoints all call
+//! [`check_keyboard_target`] and ask for confirmation on a mismatch.
+
+use std::fmt;
+use std::path::{Path, PathBuf};
+
+use crate::config::{BuildConfig, Config};
+use crate::firmware::target::{BuildTarget, TargetSource};
+use crate::models::LayoutMetadata;
+
+/// The layout's keyboard differs from the keyboard the build is set up for.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct KeyboardMismatch {
+    /// Keyboard from the layout metadata
+    pub layout_keyboard: String,
+    /// Keyboard the build is configured for
+    pub build_keyboard: String,
+    /// Where the build keyboard came from (override or config)
+    pub build_source: TargetSource,
+    /// Where the generated files would be written
+    pub target_path: PathBuf,
+}
+
+impl fmt::Display for KeyboardMismatch {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "Layout is for keyboard '{}' but the build target is '{}' ({}); files would be written to {}",
+            self.layout_keyboard,
+            self.build_keyboard,
+            self.build_source,
+            self.target_path.display()
+        )
+    }
+}
+
+/// Compares the layout's keyboard with the configured build keyboard.
+///
+/// The build keyboard is `keyboard_override` if given, else
+/// `build.keyboard`. Nothing is reported when either side is unset, or when
+/// one names a variant of the other (`crkbd` and `crkbd/rev1`).
+#[must_use]
+pub fn check_keyboard_target(
+    metadata: &LayoutMetadata,
+    keyboard_override: Option<&str>,
+    build: &BuildConfig,
+    target_path: &Path,
+) -> Option<KeyboardMismatch> {
+    let layout_keyboard = non_empty(metadata.keyboard.as_deref())?;
+    let (build_keyboard, build_source) = non_empty(keyboard_override)
+        .map(|keyboard| (keyboard, TargetSource::Override))
+        .or_else(|| non_empty(build.keyboard.as_deref()).map(|k| (k, TargetSource::Config)))?;
+
+    if same_keyboard(layout_keyboard, build_keyboard) {
+        return None;
+    }
+    Some(KeyboardMismatch {
+        layout_keyboard: layout_keyboard.to_string(),
+        build_keyboard: build_keyboard.to_string(),
+        build_source,
+        target_path: target_path.to_path_buf(),
+    })
+}
+
+/// Keymap directory a build of `target` writes to:
+/// `<qmk>/keyboards/<keyboard>/keymaps/<keymap>`.
+#[must_use]
+pub fn keymap_target_path(config: &Config, target: &BuildTarget) -> PathBuf {
+    config
+        .paths
+        .qmk_firmware
+        .clone()
+        .unwrap_or_default()
+        .join("keyboards")
+        .join(&target.keyboard)
+        .join("keymaps")
+        .join(&target.keymap)
+}
+
+/// Whether two keyboard paths name the same board, treating one that
+/// continues the other's path (a variant) as the same.
+fn same_keyboard(a: &str, b: &str) -> bool {
+    let a = a.trim_matches('/');
+    let b = b.trim_matches('/');
+    let (short, long) = if a.len() <= b.len() { (a, b) } else { (b, a) };
+    long == short
+        || long
+            .strip_prefix(short)
+            .is_some_and(|rest| rest.starts_with('/'))
+}
+
+fn non_empty(value: Option<&str>) -> Option<&str> {
+    value.filter(|value| !value.trim().is_empty())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn metadata(keyboard: Option<&str>) -> LayoutMetadata {
+        LayoutMetadata {
+            keyboard: keyboard.map(str::to_string),
+            ..LayoutMetadata::default()
+        }
+    }
+
+    fn build(keyboard: Option<&str>) -> BuildConfig {
+        BuildConfig {
+            keyboard: keyboard.map(str::to_string),
+            ..BuildConfig::default()
+        }
+    }
+
+    #[test]
+    fn test_mismatch_against_config_and_override() {
+        let path = Path::new("/qmk/keyboards/crkbd/keymaps/default");
+
+        let mismatch =
+            check_keyboard_target(&metadata(Some("lily58")), None, &build(Some("crkbd")), path)
+                .unwrap();
+        assert_eq!(mismatch.layout_keyboard, "lily58");
+        assert_eq!(mismatch.build_keyboard, "crkbd");
+        assert_eq!(mismatch.build_source, TargetSource::Config);
+        assert!(mismatch.to_string().contains(&path.display().to_string()));
+
+        let mismatch = check_keyboard_target(
+            &metadata(Some("crkbd")),
+            Some("planck"),
+            &build(Some("crkbd")),
+            path,
+        )
+        .unwrap();
+        assert_eq!(mismatch.build_keyboard, "planck");
+        assert_eq!(mismatch.build_source, TargetSource::Override);
+    }
+
+    #[test]
+    fn test_no_mismatch_for_variants_or_unset_keyboards() {
+        let path = Path::new("out");
+        for (layout, target) in [
+            (Some("crkbd"), Some("crkbd")),
+            (Some("crkbd/rev1"), Some("crkbd")),
+            (Some("crkbd"), Some("crkbd/rev4_1/standard")),
+            (None, Some("crkbd")),
+            (Some("crkbd"), None),
+            (Some("crkbd"), Some(" ")),
+        ] {
+            assert_eq!(
+                check_keyboard_target(&metadata(layout), None, &build(target), path),
+                None,
+                "{layout:?} vs {target:?}"
+            );
+        }
+        assert!(check_keyboard_target(
+            &metadata(Some("crkbd_plus")),
+            None,
+            &build(Some("crkbd")),
+            path
+        )
+        .is_some());
+    }
+
+    #[test]
+    fn test_keymap_target_path() {
+        let mut config = Config::default();
+        config.paths.qmk_firmware = Some(PathBuf::from("/qmk"));
+        let layout = metadata(Some("lily58"));
+
+        let target = BuildTarget::resolve(None, None, None, &layout, &config.build).unwrap();
+        assert_eq!(
+            keymap_target_path(&config, &target),
+            PathBuf::from("/qmk/keyboards/lily58/keymaps/default")
+        );
+        let target =
+            BuildTarget::resolve(Some("crkbd"), Some("mine"), None, &layout, &config.build)
+                .unwrap();
+        assert_eq!(
+            keymap_target_path(&config, &target),
+            PathBuf::from("/qmk/keyboards/crkbd/keymaps/mine")
+        );
+    }
+}
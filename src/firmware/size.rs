@@ -0,0 +1,430 @@
+//! Firmware size reporting and estimation.
+//!
+//! After a build, QMK prints the firmware size (an `avr-size` or
+//! `arm-none-eabi-size` table, plus a "firmware size is fine" check on AVR).
+//! This module extracts flash/RAM usage from that output and provides a rough
+//! pre-build estimate based on the layout's enabled features.
+
+use regex::Regex;
+use serde::{Deserialize, Serialize};
+
+use crate::models::Layout;
+
+/// Flash and RAM capacity of a microcontroller.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct McuSpec {
+    /// QMK processor name (e.g., "atmega32u4")
+    pub name: &'static str,
+    /// Flash available to the firmware in bytes (after the bootloader)
+    pub flash_bytes: u64,
+    /// RAM in bytes
+    pub ram_bytes: u64,
+}
+
+/// Known QMK processors.
+///
+/// AVR flash sizes exclude the default bootloader, matching the limit QMK
+/// itself checks against.
+const MCU_SPECS: &[McuSpec] = &[
+    McuSpec {
+        name: "atmega32u4",
+        flash_bytes: 28_672,
+        ram_bytes: 2_560,
+    },
+    McuSpec {
+        name: "atmega32u2",
+        flash_bytes: 28_672,
+        ram_bytes: 1_024,
+    },
+    McuSpec {
+        name: "at90usb1286",
+        flash_bytes: 122_880,
+        ram_bytes: 8_192,
+    },
+    McuSpec {
+        name: "rp2040",
+        flash_bytes: 2_097_152,
+        ram_bytes: 270_336,
+    },
+    McuSpec {
+        name: "stm32f072",
+        flash_bytes: 131_072,
+        ram_bytes: 16_384,
+    },
+    McuSpec {
+        name: "stm32f303",
+        flash_bytes: 262_144,
+        ram_bytes: 40_960,
+    },
+    McuSpec {
+        name: "stm32f401",
+        flash_bytes: 262_144,
+        ram_bytes: 65_536,
+    },
+    McuSpec {
+        name: "stm32f411",
+        flash_bytes: 524_288,
+        ram_bytes: 131_072,
+    },
+];
+
+impl McuSpec {
+    /// Looks up a processor by its QMK name (case-insensitive).
+    #[must_use]
+    pub fn lookup(processor: &str) -> Option<&'static Self> {
+        MCU_SPECS
+            .iter()
+            .find(|spec| spec.name.eq_ignore_ascii_case(processor.trim()))
+    }
+}
+
+/// Flash and RAM usage of a compiled firmware.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub struct FirmwareSize {
+    /// Flash used in bytes
+    pub flash_used: u64,
+    /// Flash available in bytes, if known
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub flash_max: Option<u64>,
+    /// Statically allocated RAM in bytes, if reported
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub ram_used: Option<u64>,
+    /// RAM available in bytes, if known
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub ram_max: Option<u64>,
+}
+
+impl FirmwareSize {
+    /// Flash usage as a percentage of capacity.
+    #[must_use]
+    pub fn flash_percent(&self) -> Option<u64> {
+        percent(self.flash_used, self.flash_max)
+    }
+
+    /// RAM usage as a percentage of capacity.
+    #[must_use]
+    pub fn ram_percent(&self) -> Option<u64> {
+        self.ram_used.and_then(|used| percent(used, self.ram_max))
+    }
+
+    /// Full summary, e.g. "Flash 26904/28672 bytes (93%), RAM 1800/2560 bytes (70%)".
+    #[must_use]
+    pub fn summary(&self) -> String {
+        let flash = format!(
+            "Flash {}",
+            usage(self.flash_used, self.flash_max, self.flash_percent())
+        );
+        match self.ram_used {
+            Some(ram_used) => format!(
+                "{flash}, RAM {}",
+                usage(ram_used, self.ram_max, self.ram_percent())
+            ),
+            None => flash,
+        }
+    }
+
+    /// Compact summary for the status bar, e.g. "Flash 93% · RAM 70%".
+    #[must_use]
+    pub fn short_summary(&self) -> String {
+        let flash = self.flash_percent().map_or_else(
+            || format!("Flash {} B", self.flash_used),
+            |p| format!("Flash {p}%"),
+        );
+        match (self.ram_used, self.ram_percent()) {
+            (_, Some(p)) => format!("{flash} · RAM {p}%"),
+            (Some(used), None) => format!("{flash} · RAM {used} B"),
+            (None, None) => flash,
+        }
+    }
+}
+
+fn percent(used: u64, max: Option<u64>) -> Option<u64> {
+    max.filter(|&m| m > 0).map(|m| used * 100 / m)
+}
+
+fn usage(used: u64, max: Option<u64>, percent: Option<u64>) -> String {
+    match (max, percent) {
+        (Some(max), Some(p)) => format!("{used}/{max} bytes ({p}%)"),
+        _ => format!("{used} bytes"),
+    }
+}
+
+/// Extracts flash/RAM usage from QMK build output.
+///
+/// The last size table wins (QMK prints "Size before" and "Size after").
+/// Flash capacity comes from QMK's own size check when present, otherwise
+/// from `mcu`. RAM is only reported for `.elf` tables, since `.hex` tables
+/// lump everything into the data column.
+#[must_use]
+pub fn parse_size_output(output: &str, mcu: Option<&McuSpec>) -> Option<FirmwareSize> {
+    // QMK's AVR size check, e.g. "The firmware size is fine - 26904/28672 (93%, 1768 bytes free)"
+    let check_regex = Regex::new(r"(?i)the firmware (?:size )?is.*?(\d+)/(\d+)").unwrap();
+    // A row of a Berkeley-format size table: text data bss dec hex filename
+    let row_regex =
+        Regex::new(r"^\s*(\d+)\s+(\d+)\s+(\d+)\s+\d+\s+[0-9a-fA-F]+\s+(\S+)\s*$").unwrap();
+
+    let mut checked: Option<(u64, u64)> = None;
+    let mut table: Option<(u64, Option<u64>)> = None;
+
+    for line in output.lines() {
+        if let Some(caps) = row_regex.captures(line) {
+            let text: u64 = caps[1].parse().ok()?;
+            let data: u64 = caps[2].parse().ok()?;
+            let bss: u64 = caps[3].parse().ok()?;
+            let ram = std::path::Path::new(&caps[4])
+                .extension()
+                .is_some_and(|ext| ext.eq_ignore_ascii_case("elf"))
+                .then_some(data + bss);
+            table = Some((text + data, ram));
+        } else if let Some(caps) = check_regex.captures(line) {
+            if let (Ok(used), Ok(max)) = (caps[1].parse(), caps[2].parse()) {
+                checked = Some((used, max));
+            }
+        }
+    }
+
+    let (flash_used, flash_max) = match (checked, table) {
+        (Some((used, max)), _) => (used, Some(max)),
+        (None, Some((used, _))) => (used, mcu.map(|m| m.flash_bytes)),
+        (None, None) => return None,
+    };
+    let ram_used = table.and_then(|(_, ram)| ram);
+
+    Some(FirmwareSize {
+        flash_used,
+        flash_max,
+        ram_used,
+        ram_max: ram_used.and(mcu.map(|m| m.ram_bytes)),
+    })
+}
+
+/// Approximate flash cost of QMK core with a basic keymap.
+const BASE_COST: u64 = 15_000;
+/// Flash cost of enabling tap dance.
+const TAP_DANCE_COST: u64 = 1_100;
+/// Flash cost per tap dance action.
+const TAP_DANCE_ACTION_COST: u64 = 40;
+/// Extra cost per three-way (hold) tap dance action.
+const TAP_DANCE_HOLD_COST: u64 = 150;
+/// Flash cost of RGB matrix with its default effects.
+const RGB_MATRIX_COST: u64 = 9_000;
+/// Flash cost of the idle effect state machine.
+const IDLE_EFFECT_COST: u64 = 400;
+/// Flash cost of enabling combos.
+const COMBO_COST: u64 = 1_300;
+/// Flash cost of chordal hold.
+const CHORDAL_HOLD_COST: u64 = 500;
+/// Flash cost of flow tap.
+const FLOW_TAP_COST: u64 = 300;
+/// Bytes per key per layer in the keymap array.
+const KEYMAP_BYTES_PER_KEY: u64 = 2;
+/// Bytes per key per layer in the RGB layer color table.
+const RGB_BYTES_PER_KEY: u64 = 3;
+
+/// Rough pre-build flash estimate, broken down by feature.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct SizeEstimate {
+    /// Estimated total flash in bytes
+    pub total: u64,
+    /// Per-feature costs in bytes
+    pub items: Vec<(&'static str, u64)>,
+}
+
+impl SizeEstimate {
+    /// Estimates flash usage from the layout's enabled features.
+    ///
+    /// Costs are typical AVR figures and only meant to flag layouts that are
+    /// likely to overflow small MCUs before spending time on a build.
+    #[must_use]
+    pub fn for_layout(layout: &Layout) -> Self {
+        let key_slots: u64 = layout.layers.iter().map(|l| l.keys.len() as u64).sum();
+        let mut items = vec![
+            ("QMK core", BASE_COST),
+            ("keymap", key_slots * KEYMAP_BYTES_PER_KEY),
+        ];
+
+        if !layout.tap_dances.is_empty() {
+            let actions = layout.tap_dances.len() as u64;
+            let holds = layout
+                .tap_dances
+                .iter()
+                .filter(|td| td.is_three_way())
+                .count() as u64;
+            items.push((
+                "tap dance",
+                TAP_DANCE_COST + actions * TAP_DANCE_ACTION_COST + holds * TAP_DANCE_HOLD_COST,
+            ));
+        }
+
+        if layout
+            .layers
+            .iter()
+            .flat_map(|l| &l.keys)
+            .any(|k| k.combo_participant)
+        {
+            items.push(("combos", COMBO_COST));
+        }
+
+        if layout.rgb_enabled {
+            items.push((
+                "RGB matrix",
+                RGB_MATRIX_COST + key_slots * RGB_BYTES_PER_KEY,
+            ));
+            if layout.idle_effect_settings.enabled {
+                items.push(("idle effect", IDLE_EFFECT_COST));
+            }
+        }
+
+        if layout.tap_hold_settings.chordal_hold {
+            items.push(("chordal hold", CHORDAL_HOLD_COST));
+        }
+        if layout.tap_hold_settings.flow_tap_term.is_some() {
+            items.push(("flow tap", FLOW_TAP_COST));
+        }
+
+        let total = items.iter().map(|(_, cost)| cost).sum();
+        Self { total, items }
+    }
+
+    /// Returns a warning if the estimate exceeds the MCU's flash.
+    #[must_use]
+    pub fn overflow_warning(&self, mcu: &McuSpec) -> Option<String> {
+        if self.total <= mcu.flash_bytes {
+            return None;
+        }
+
+        // Name the largest optional features as the likely culprits
+        let mut features: Vec<_> = self.items.iter().skip(2).collect();
+        features.sort_by_key(|(_, cost)| std::cmp::Reverse(*cost));
+        let culprits: Vec<&str> = features.iter().map(|(name, _)| *name).collect();
+
+        let warning = format!(
+            "Estimated firmware size ~{} bytes exceeds {} flash ({} bytes)",
+            self.total, mcu.name, mcu.flash_bytes
+        );
+        if culprits.is_empty() {
+            Some(warning)
+        } else {
+            Some(format!(
+                "{warning}; largest features: {}",
+                culprits.join(", ")
+            ))
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::models::{KeyDefinition, Layer, Position, RgbColor, TapDanceAction};
+
+    const AVR_OUTPUT: &str = "\
+Size before:
+   text    data     bss     dec     hex filename
+      0   26904       0   26904    6918 .build/crkbd_rev1_default.hex
+
+Size after:
+   text    data     bss     dec     hex filename
+      0   26904       0   26904    6918 .build/crkbd_rev1_default.hex
+
+Copying crkbd_rev1_default.hex to qmk_firmware folder                 [OK]
+Checking file size of crkbd_rev1_default.hex                           [OK]
+ * The firmware size is fine - 26904/28672 (93%, 1768 bytes free)
+";
+
+    const ARM_OUTPUT: &str = "\
+   text    data     bss     dec     hex filename
+  60000    1200   20000   81200   13d30 .build/keebart_corne_default.elf
+";
+
+    #[test]
+    fn test_mcu_lookup_case_insensitive() {
+        assert_eq!(McuSpec::lookup("RP2040").unwrap().name, "rp2040");
+        assert_eq!(McuSpec::lookup("atmega32u4").unwrap().flash_bytes, 28_672);
+        assert!(McuSpec::lookup("unknown").is_none());
+    }
+
+    #[test]
+    fn test_parse_avr_output_uses_size_check() {
+        let size = parse_size_output(AVR_OUTPUT, None).unwrap();
+        assert_eq!(size.flash_used, 26_904);
+        assert_eq!(size.flash_max, Some(28_672));
+        assert_eq!(size.flash_percent(), Some(93));
+        assert_eq!(size.ram_used, None);
+        assert_eq!(size.short_summary(), "Flash 93%");
+    }
+
+    #[test]
+    fn test_parse_arm_elf_output() {
+        let mcu = McuSpec::lookup("stm32f411");
+        let size = parse_size_output(ARM_OUTPUT, mcu).unwrap();
+        assert_eq!(size.flash_used, 61_200);
+        assert_eq!(size.flash_max, Some(524_288));
+        assert_eq!(size.ram_used, Some(21_200));
+        assert_eq!(size.ram_percent(), Some(16));
+        assert_eq!(
+            size.summary(),
+            "Flash 61200/524288 bytes (11%), RAM 21200/131072 bytes (16%)"
+        );
+    }
+
+    #[test]
+    fn test_parse_without_size_lines() {
+        assert!(parse_size_output("Compiling keymap.c [OK]", None).is_none());
+    }
+
+    #[test]
+    fn test_parse_unknown_mcu_reports_bytes() {
+        let size = parse_size_output(ARM_OUTPUT, None).unwrap();
+        assert_eq!(size.flash_max, None);
+        assert_eq!(size.short_summary(), "Flash 61200 B · RAM 21200 B");
+    }
+
+    fn layout_with_keys(layers: usize, keys: u8) -> Layout {
+        let mut layout = Layout::new("Size").unwrap();
+        for i in 0..layers {
+            let mut layer = Layer::new(i as u8, format!("L{i}"), RgbColor::new(0, 0, 0)).unwrap();
+            for col in 0..keys {
+                layer
+                    .add_key(KeyDefinition::new(Position::new(0, col), "KC_A"))
+                    .unwrap();
+            }
+            layout.add_layer(layer).unwrap();
+        }
+        layout
+    }
+
+    #[test]
+    fn test_estimate_counts_enabled_features() {
+        let mut layout = layout_with_keys(2, 10);
+        layout.rgb_enabled = false;
+        let base = SizeEstimate::for_layout(&layout);
+        assert_eq!(base.total, BASE_COST + 20 * KEYMAP_BYTES_PER_KEY);
+
+        layout
+            .tap_dances
+            .push(TapDanceAction::new("esc", "KC_ESC").with_double_tap("KC_CAPS"));
+        let with_td = SizeEstimate::for_layout(&layout);
+        assert_eq!(
+            with_td.total - base.total,
+            TAP_DANCE_COST + TAP_DANCE_ACTION_COST
+        );
+    }
+
+    #[test]
+    fn test_overflow_warning() {
+        let mut layout = layout_with_keys(16, 42);
+        layout
+            .tap_dances
+            .push(TapDanceAction::new("esc", "KC_ESC").with_double_tap("KC_CAPS"));
+        let estimate = SizeEstimate::for_layout(&layout);
+
+        let avr = McuSpec::lookup("atmega32u4").unwrap();
+        let warning = estimate.overflow_warning(avr).unwrap();
+        assert!(warning.contains("atmega32u4"));
+        assert!(warning.contains("largest features: RGB matrix"));
+
+        let rp2040 = McuSpec::lookup("rp2040").unwrap();
+        assert!(estimate.overflow_warning(rp2040).is_none());
+    }
+}
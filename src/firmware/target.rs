@@ -0,0 +1,278 @@
+//! Resolution of the keyboard, keymap and converter a build targets.
+//!
+//! Precedence, highest first:
+//! 1. Explicit overrides (`lazyqmk build --keyboard/--keymap/--converter`)
+//! 2. The layout's own metadata (`keyboard`, `keymap_name`, `converter`)
+//! 3. The global fallbacks in `config.build` (`keyboard`, `keymap`, `converter`)
+//! 4. For the keymap only, `default`; without a converter the board's own
+//!    controller is targeted
+
+use anyhow::Result;
+use serde::Serialize;
+use std::fmt;
+
+use crate::config::BuildConfig;
+use crate::firmware::{converter, paths, Backend};
+use crate::models::LayoutMetadata;
+
+/// Keymap name used when neither the layout nor the config sets one.
+pub const DEFAULT_KEYMAP: &str = "default";
+
+/// Where a build target value came from.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum TargetSource {
+    /// Explicit command-line override
+    Override,
+    /// Layout metadata
+    Layout,
+    /// Global build config
+    Config,
+    /// Built-in default
+    Default,
+}
+
+impl fmt::Display for TargetSource {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let label = match self {
+            Self::Override => "override",
+            Self::Layout => "layout",
+            Self::Config => "config",
+            Self::Default => "default",
+        };
+        f.write_str(label)
+    }
+}
+
+/// Effective keyboard, keymap and converter for a build.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct BuildTarget {
+    /// QMK keyboard path (e.g., "crkbd/rev1")
+    pub keyboard: String,
+    /// Keymap directory name
+    pub keymap: String,
+    /// Where the keyboard came from
+    pub keyboard_source: TargetSource,
+    /// Where the keymap came from
+    pub keymap_source: TargetSource,
+    /// Controller converter passed as `CONVERT_TO`, if any
+    pub converter: Option<String>,
+    /// Where the converter came from (`None` without a converter)
+    pub converter_source: Option<TargetSource>,
+}
+
+impl BuildTarget {
+    /// Resolves the build target for a layout.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if no keyboard is set anywhere, if the resolved
+    /// keyboard or keymap is not a safe path component, or if the converter
+    /// name contains characters QMK would not accept.
+    pub fn resolve(
+        keyboard_override: Option<&str>,
+        keymap_override: Option<&str>,
+        converter_override: Option<&str>,
+        metadata: &LayoutMetadata,
+        build: &BuildConfig,
+    ) -> Result<Self> {
+        let (keyboard, keyboard_source) = pick(
+            keyboard_override,
+            metadata.keyboard.as_deref(),
+            build.keyboard.as_deref(),
+        )
+        .ok_or_else(|| {
+            anyhow::anyhow!(
+                "No keyboard set: add one to the layout metadata or set build.keyboard in the config"
+            )
+        })?;
+        let (keymap, keymap_source) = pick(
+            keymap_override,
+            metadata.keymap_name.as_deref(),
+            build.keymap.as_deref(),
+        )
+        .unwrap_or((DEFAULT_KEYMAP, TargetSource::Default));
+        let converter = pick(
+            converter_override,
+            metadata.converter.as_deref(),
+            build.converter.as_deref(),
+        );
+
+        paths::validate_keyboard_path(keyboard)?;
+        paths::validate_keymap_name(keymap)?;
+        if let Some((name, _)) = converter {
+            converter::validate_converter_name(name)?;
+        }
+
+        Ok(Self {
+            keyboard: keyboard.to_string(),
+            keymap: keymap.to_string(),
+            keyboard_source,
+            keymap_source,
+            converter: converter.map(|(name, _)| name.to_string()),
+            converter_source: converter.map(|(_, source)| source),
+        })
+    }
+
+    /// Warning for a converter QMK is not known to ship (likely a typo).
+    #[must_use]
+    pub fn converter_warning(&self) -> Option<String> {
+        self.converter
+            .as_deref()
+            .and_then(converter::converter_warning)
+    }
+
+    /// The command line `backend` builds this target with.
+    #[must_use]
+    pub fn compile_command(&self, backend: Backend) -> String {
+        backend.command_line(&self.keyboard, &self.keymap, self.converter.as_deref())
+    }
+
+    /// Writes the target into layout metadata so generation uses it.
+    pub fn apply_to(&self, metadata: &mut LayoutMetadata) {
+        metadata.keyboard = Some(self.keyboard.clone());
+        metadata.keymap_name = Some(self.keymap.clone());
+        metadata.converter.clone_from(&self.converter);
+    }
+}
+
+/// `keyboard:keymap`, as accepted by `qmk compile`/`qmk flash`.
+impl fmt::Display for BuildTarget {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}:{}", self.keyboard, self.keymap)
+    }
+}
+
+/// Picks the first non-empty value in precedence order.
+fn pick<'a>(
+    override_value: Option<&'a str>,
+    layout_value: Option<&'a str>,
+    config_value: Option<&'a str>,
+) -> Option<(&'a str, TargetSource)> {
+    [
+        (override_value, TargetSource::Override),
+        (layout_value, TargetSource::Layout),
+        (config_value, TargetSource::Config),
+    ]
+    .into_iter()
+    .find_map(|(value, source)| {
+        value
+            .filter(|value| !value.trim().is_empty())
+            .map(|value| (value, source))
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn metadata(keyboard: Option<&str>, keymap: Option<&str>) -> LayoutMetadata {
+        LayoutMetadata {
+            keyboard: keyboard.map(str::to_string),
+            keymap_name: keymap.map(str::to_string),
+            ..LayoutMetadata::default()
+        }
+    }
+
+    fn build_config(keyboard: Option<&str>, keymap: Option<&str>) -> BuildConfig {
+        BuildConfig {
+            keyboard: keyboard.map(str::to_string),
+            keymap: keymap.map(str::to_string),
+            ..BuildConfig::default()
+        }
+    }
+
+    #[test]
+    fn test_precedence_override_layout_config_default() {
+        let layout = metadata(Some("crkbd"), Some("mine"));
+        let config = build_config(Some("planck"), Some("global"));
+
+        let target =
+            BuildTarget::resolve(Some("lily58"), Some("cli"), None, &layout, &config).unwrap();
+        assert_eq!(target.to_string(), "lily58:cli");
+        assert_eq!(target.keyboard_source, TargetSource::Override);
+        assert_eq!(target.keymap_source, TargetSource::Override);
+
+        let target = BuildTarget::resolve(None, None, None, &layout, &config).unwrap();
+        assert_eq!(target.to_string(), "crkbd:mine");
+        assert_eq!(target.keyboard_source, TargetSource::Layout);
+
+        let target =
+            BuildTarget::resolve(None, None, None, &metadata(None, None), &config).unwrap();
+        assert_eq!(target.to_string(), "planck:global");
+        assert_eq!(target.keymap_source, TargetSource::Config);
+
+        let target = BuildTarget::resolve(
+            None,
+            None,
+            None,
+            &metadata(Some("crkbd"), None),
+            &BuildConfig::default(),
+        )
+        .unwrap();
+        assert_eq!(target.to_string(), "crkbd:default");
+        assert_eq!(target.keymap_source, TargetSource::Default);
+    }
+
+    #[test]
+    fn test_resolve_requires_a_keyboard() {
+        let err = BuildTarget::resolve(
+            None,
+            None,
+            None,
+            &metadata(None, None),
+            &BuildConfig::default(),
+        )
+        .unwrap_err();
+        assert!(err.to_string().contains("No keyboard set"));
+    }
+
+    #[test]
+    fn test_resolve_rejects_unsafe_names() {
+        let config = BuildConfig::default();
+        assert!(
+            BuildTarget::resolve(Some("../x"), None, None, &metadata(None, None), &config).is_err()
+        );
+        assert!(BuildTarget::resolve(
+            None,
+            Some("..\\x"),
+            None,
+            &metadata(Some("crkbd"), None),
+            &config
+        )
+        .is_err());
+    }
+
+    #[test]
+    fn test_converter_precedence_and_warning() {
+        let mut layout = metadata(Some("crkbd"), None);
+        let mut config = BuildConfig {
+            converter: Some("elite_pi".to_string()),
+            ..BuildConfig::default()
+        };
+
+        let target = BuildTarget::resolve(None, None, None, &layout, &config).unwrap();
+        assert_eq!(target.converter.as_deref(), Some("elite_pi"));
+        assert_eq!(target.converter_source, Some(TargetSource::Config));
+
+        layout.converter = Some("rp2040_ce".to_string());
+        let target = BuildTarget::resolve(None, None, None, &layout, &config).unwrap();
+        assert_eq!(target.converter_source, Some(TargetSource::Layout));
+        assert_eq!(
+            target.compile_command(Backend::QmkCli),
+            "qmk compile -kb crkbd -km default -e CONVERT_TO=rp2040_ce"
+        );
+        assert!(target.converter_warning().is_none());
+
+        let target = BuildTarget::resolve(None, None, Some("liatriss"), &layout, &config).unwrap();
+        assert_eq!(target.converter_source, Some(TargetSource::Override));
+        assert!(target.converter_warning().is_some());
+
+        config.converter = None;
+        layout.converter = None;
+        let target = BuildTarget::resolve(None, None, None, &layout, &config).unwrap();
+        assert_eq!(target.converter, None);
+        assert_eq!(target.converter_source, None);
+        assert!(BuildTarget::resolve(None, None, Some("a b"), &layout, &config).is_err());
+    }
+}
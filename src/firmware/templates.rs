@@ -0,0 +1,387 @@
+//! Templates for generated firmware files.
+//!
+//! keymap.c, config.h and rules.mk are rendered from Jinja-style templates
+//! (via `minijinja`). The embedded defaults produce the standard output; a
+//! user template directory (`build.template_dir` in config.toml) can override
+//! any of them, and files missing from it fall back to the embedded version.
+//!
+//! Templates receive a [`TemplateContext`]:
+//!
+//! - `generator`: `name`, `version`
+//! - `generated_at`: local timestamp (`YYYY-MM-DD HH:MM:SS`)
+//! - `metadata`: `name`, `description`, `author`, `keyboard`, `layout_variant`, `keymap_name`
+//! - `layers`: `index`, `name`, `enum_name`, `keycodes` (layout order, as passed
+//!   to the `LAYOUT` macro) and `rows` (keycodes grouped by visual row)
+//! - `tap_dances`: `name`, `enum_name`, `single_tap`, `double_tap`, `hold`
+//! - `headers`: extra `#include` headers needed by language keycodes
+//! - `settings`: `tap_hold`, `idle_effect`, `rgb` (`enabled`, `brightness_percent`, `timeout_ms`)
+//! - `features`: `rgb_matrix`, `custom_colors`, `idle_effect`, `encoders`
+//! - `sections`: pre-rendered C blocks (`keymaps`, `encoder_map`, `rgb_matrix`,
+//!   `idle_effect`, `rgb_indicators`, `tap_dance_enum`, `tap_dance_helpers`,
+//!   `tap_dance_actions`, `config`)
+//!
+//! Use `lazyqmk generate --dump-templates <DIR>` to write the embedded
+//! templates out as a starting point.
+
+use std::fs;
+use std::path::{Path, PathBuf};
+
+use anyhow::{anyhow, Context, Result};
+use minijinja::{Environment, UndefinedBehavior};
+use serde::Serialize;
+
+use crate::atomic_write;
+use crate::models::layout::{IdleEffectSettings, TapHoldSettings};
+
+/// A generated file that can be templated.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum TemplateKind {
+    /// keymap.c
+    KeymapC,
+    /// config.h
+    ConfigH,
+    /// rules.mk
+    RulesMk,
+}
+
+impl TemplateKind {
+    /// All templated files.
+    pub const ALL: [Self; 3] = [Self::KeymapC, Self::ConfigH, Self::RulesMk];
+
+    /// Template file name, as looked up in the template directory.
+    #[must_use]
+    pub const fn file_name(self) -> &'static str {
+        match self {
+            Self::KeymapC => "keymap.c.jinja",
+            Self::ConfigH => "config.h.jinja",
+            Self::RulesMk => "rules.mk.jinja",
+        }
+    }
+
+    /// Embedded default template source.
+    #[must_use]
+    pub const fn embedded(self) -> &'static str {
+        match self {
+            Self::KeymapC => include_str!("templates/keymap.c.jinja"),
+            Self::ConfigH => include_str!("templates/config.h.jinja"),
+            Self::RulesMk => include_str!("templates/rules.mk.jinja"),
+        }
+    }
+}
+
+/// Template sources, preferring files from a user template directory.
+#[derive(Debug, Clone, Default)]
+pub struct TemplateSet {
+    dir: Option<PathBuf>,
+}
+
+impl TemplateSet {
+    /// Creates a template set that looks for overrides in `dir`.
+    #[must_use]
+    pub const fn new(dir: Option<PathBuf>) -> Self {
+        Self { dir }
+    }
+
+    /// Returns the template source for `kind` and whether it is a user override.
+    pub fn source(&self, kind: TemplateKind) -> Result<(String, bool)> {
+        if let Some(dir) = &self.dir {
+            let path = dir.join(kind.file_name());
+            if path.is_file() {
+                let source = fs::read_to_string(&path)
+                    .with_context(|| format!("Failed to read template {}", path.display()))?;
+                return Ok((source, true));
+            }
+        }
+        Ok((kind.embedded().to_string(), false))
+    }
+
+    /// Renders the template for `kind` with `context`.
+    ///
+    /// Errors name the template file and, when known, the line.
+    pub fn render(&self, kind: TemplateKind, context: &TemplateContext) -> Result<String> {
+        let (source, _) = self.source(kind)?;
+        render_source(kind.file_name(), &source, context)
+    }
+}
+
+/// Renders a template source string.
+fn render_source(name: &str, source: &str, context: &TemplateContext) -> Result<String> {
+    let mut env = Environment::new();
+    env.set_trim_blocks(true);
+    env.set_lstrip_blocks(true);
+    env.set_undefined_behavior(UndefinedBehavior::Strict);
+
+    env.template_from_named_str(name, source)
+        .and_then(|template| template.render(context))
+        .map_err(|e| {
+            let name = e.name().unwrap_or(name).to_string();
+            let detail = e
+                .detail()
+                .map_or_else(|| e.kind().to_string(), str::to_string);
+            match e.line() {
+                Some(line) => anyhow!("Template error in {name} line {line}: {detail}"),
+                None => anyhow!("Template error in {name}: {detail}"),
+            }
+        })
+}
+
+/// Writes the embedded templates to `dir`, creating it if needed.
+///
+/// Returns the written paths.
+pub fn dump_embedded(dir: &Path) -> Result<Vec<PathBuf>> {
+    fs::create_dir_all(dir)
+        .with_context(|| format!("Failed to create directory {}", dir.display()))?;
+
+    TemplateKind::ALL
+        .iter()
+        .map(|kind| {
+            let path = dir.join(kind.file_name());
+            atomic_write::write(&path, kind.embedded())
+                .with_context(|| format!("Failed to write {}", path.display()))?;
+            Ok(path)
+        })
+        .collect()
+}
+
+/// Data available to firmware templates.
+#[derive(Debug, Clone, Serialize)]
+pub struct TemplateContext<'a> {
+    /// Generator name and version
+    pub generator: GeneratorInfo,
+    /// Generation timestamp
+    pub generated_at: String,
+    /// Layout metadata
+    pub metadata: MetadataContext<'a>,
+    /// Layers in index order
+    pub layers: Vec<LayerContext<'a>>,
+    /// Tap dances sorted by name
+    pub tap_dances: Vec<TapDanceContext>,
+    /// Extra headers for language-specific keycodes
+    pub headers: Vec<String>,
+    /// Layout settings
+    pub settings: SettingsContext<'a>,
+    /// Enabled firmware features
+    pub features: FeaturesContext,
+    /// Pre-rendered C code blocks
+    pub sections: Sections,
+}
+
+/// Generator identification.
+#[derive(Debug, Clone, Serialize)]
+pub struct GeneratorInfo {
+    /// Binary name
+    pub name: &'static str,
+    /// Crate version
+    pub version: &'static str,
+}
+
+/// Layout metadata exposed to templates.
+#[derive(Debug, Clone, Serialize)]
+pub struct MetadataContext<'a> {
+    /// Layout name
+    pub name: &'a str,
+    /// Layout description
+    pub description: &'a str,
+    /// Layout author
+    pub author: &'a str,
+    /// Keyboard path
+    pub keyboard: Option<&'a str>,
+    /// QMK layout macro
+    pub layout_variant: Option<&'a str>,
+    /// Keymap name
+    pub keymap_name: Option<&'a str>,
+}
+
+/// A layer exposed to templates.
+#[derive(Debug, Clone, Serialize)]
+pub struct LayerContext<'a> {
+    /// Layer index
+    pub index: usize,
+    /// Layer name
+    pub name: &'a str,
+    /// C enum name (e.g. `_NAV`)
+    pub enum_name: &'a str,
+    /// Resolved keycodes in layout order
+    pub keycodes: Vec<String>,
+    /// Resolved keycodes grouped by visual row
+    pub rows: Vec<Vec<String>>,
+}
+
+/// A tap dance exposed to templates.
+#[derive(Debug, Clone, Serialize)]
+pub struct TapDanceContext {
+    /// Tap dance name
+    pub name: String,
+    /// C enum name (e.g. `TD_ESC_CAPS`)
+    pub enum_name: String,
+    /// Resolved single-tap keycode
+    pub single_tap: String,
+    /// Resolved double-tap keycode
+    pub double_tap: Option<String>,
+    /// Resolved hold keycode
+    pub hold: Option<String>,
+    /// Per-dance tapping term in milliseconds
+    pub tapping_term_ms: Option<u16>,
+    /// Whether the single tap fires on every tap
+    pub on_each_tap: bool,
+}
+
+/// Layout settings exposed to templates.
+#[derive(Debug, Clone, Serialize)]
+pub struct SettingsContext<'a> {
+    /// Tap-hold settings
+    pub tap_hold: &'a TapHoldSettings,
+    /// Idle effect settings
+    pub idle_effect: &'a IdleEffectSettings,
+    /// RGB settings
+    pub rgb: RgbContext,
+}
+
+/// RGB settings exposed to templates.
+#[derive(Debug, Clone, Serialize)]
+pub struct RgbContext {
+    /// Whether RGB is enabled in the layout
+    pub enabled: bool,
+    /// Maximum brightness (0-100)
+    pub brightness_percent: u8,
+    /// Auto-off timeout in milliseconds (0 = disabled)
+    pub timeout_ms: u32,
+}
+
+/// Firmware features in use.
+#[derive(Debug, Clone, Serialize)]
+pub struct FeaturesContext {
+    /// Keyboard has an RGB matrix
+    pub rgb_matrix: bool,
+    /// Layout defines custom colors
+    pub custom_colors: bool,
+    /// Idle effect code is generated
+    pub idle_effect: bool,
+    /// Number of encoders
+    pub encoders: u8,
+}
+
+/// Pre-rendered C code blocks, empty when not applicable.
+#[derive(Debug, Clone, Default, Serialize)]
+pub struct Sections {
+    /// `keymaps[]` array
+    pub keymaps: String,
+    /// `encoder_map[]` inside `#ifdef ENCODER_MAP_ENABLE`
+    pub encoder_map: String,
+    /// RGB matrix base color table
+    pub rgb_matrix: String,
+    /// Idle effect state machine
+    pub idle_effect: String,
+    /// `rgb_matrix_indicators_advanced_user` for category RGB flags
+    pub rgb_indicators: String,
+    /// `enum tap_dance_ids`
+    pub tap_dance_enum: String,
+    /// Finished/reset callbacks for tap dances with hold
+    pub tap_dance_helpers: String,
+    /// `tap_dance_actions[]` array
+    pub tap_dance_actions: String,
+    /// config.h defines after the header
+    pub config: String,
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::models::layout::Layout;
+    use tempfile::TempDir;
+
+    fn context(layout: &Layout) -> TemplateContext<'_> {
+        TemplateContext {
+            generator: GeneratorInfo {
+                name: "lazyqmk",
+                version: "0.0.0",
+            },
+            generated_at: String::new(),
+            metadata: MetadataContext {
+                name: &layout.metadata.name,
+                description: &layout.metadata.description,
+                author: &layout.metadata.author,
+                keyboard: None,
+                layout_variant: None,
+                keymap_name: None,
+            },
+            layers: Vec::new(),
+            tap_dances: Vec::new(),
+            headers: Vec::new(),
+            settings: SettingsContext {
+                tap_hold: &layout.tap_hold_settings,
+                idle_effect: &layout.idle_effect_settings,
+                rgb: RgbContext {
+                    enabled: true,
+                    brightness_percent: 100,
+                    timeout_ms: 0,
+                },
+            },
+            features: FeaturesContext {
+                rgb_matrix: false,
+                custom_colors: false,
+                idle_effect: false,
+                encoders: 0,
+            },
+            sections: Sections::default(),
+        }
+    }
+
+    #[test]
+    fn test_user_template_overrides_embedded() {
+        let layout = Layout::new("Test").unwrap();
+        let dir = TempDir::new().unwrap();
+        fs::write(
+            dir.path().join("rules.mk.jinja"),
+            "# {{ metadata.name }}\nCOMBO_ENABLE = yes\n",
+        )
+        .unwrap();
+
+        let templates = TemplateSet::new(Some(dir.path().to_path_buf()));
+        let rules = templates
+            .render(TemplateKind::RulesMk, &context(&layout))
+            .unwrap();
+        assert_eq!(rules, "# Test\nCOMBO_ENABLE = yes");
+
+        // config.h is not in the directory: embedded template is used
+        let (_, is_override) = templates.source(TemplateKind::ConfigH).unwrap();
+        assert!(!is_override);
+        let config_h = templates
+            .render(TemplateKind::ConfigH, &context(&layout))
+            .unwrap();
+        assert!(config_h.contains("#pragma once"));
+    }
+
+    #[test]
+    fn test_render_error_names_template_and_line() {
+        let layout = Layout::new("Test").unwrap();
+        let dir = TempDir::new().unwrap();
+        fs::write(
+            dir.path().join("keymap.c.jinja"),
+            "// ok\n// ok\n{{ metadata.nonexistent }}\n",
+        )
+        .unwrap();
+
+        let templates = TemplateSet::new(Some(dir.path().to_path_buf()));
+        let err = templates
+            .render(TemplateKind::KeymapC, &context(&layout))
+            .unwrap_err()
+            .to_string();
+        assert!(err.contains("keymap.c.jinja"), "{err}");
+        assert!(err.contains("line 3"), "{err}");
+    }
+
+    #[test]
+    fn test_dump_embedded_writes_all_templates() {
+        let dir = TempDir::new().unwrap();
+        let target = dir.path().join("templates");
+
+        let written = dump_embedded(&target).unwrap();
+
+        assert_eq!(written.len(), TemplateKind::ALL.len());
+        for kind in TemplateKind::ALL {
+            let content = fs::read_to_string(target.join(kind.file_name())).unwrap();
+            assert_eq!(content, kind.embedded());
+        }
+    }
+}
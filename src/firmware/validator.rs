@@ -7,9 +7,12 @@
 #![allow(clippy::format_push_string)]
 
 use crate::keycode_db::KeycodeDb;
+use crate::models::category::CategoryFlag;
 use crate::models::keyboard_geometry::KeyboardGeometry;
 use crate::models::layout::Layout;
 use crate::models::visual_layout_mapping::VisualLayoutMapping;
+use crate::services::deprecated_keycodes::modernize_keycode;
+use crate::services::layer_refs::{disabled_layer_refs, out_of_range_layer_refs};
 use anyhow::Result;
 use std::collections::HashSet;
 
@@ -166,6 +169,10 @@ pub enum ValidationErrorKind {
     EmptyLayer,
     /// Number of keys doesn't match keyboard geometry
     MismatchedKeyCount,
+    /// Tap dance definition is invalid (e.g., tapping term out of range)
+    InvalidTapDance,
+    /// Enabled layer references a layer left out of firmware
+    DisabledLayerReference,
 }
 
 impl std::fmt::Display for ValidationErrorKind {
@@ -177,15 +184,41 @@ impl std::fmt::Display for ValidationErrorKind {
             Self::MatrixOutOfBounds => write!(f, "Matrix Out of Bounds"),
             Self::EmptyLayer => write!(f, "Empty Layer"),
             Self::MismatchedKeyCount => write!(f, "Mismatched Key Count"),
+            Self::InvalidTapDance => write!(f, "Invalid Tap Dance"),
+            Self::DisabledLayerReference => write!(f, "Disabled Layer Reference"),
         }
     }
 }
 
+/// Rule ID of the check for transparent keys on the base layer.
+///
+/// Warnings from this check can be fixed with `lazyqmk fix --rule base-layer-trns`.
+pub const BASE_LAYER_TRNS_RULE: &str = "base-layer-trns";
+
+/// Rule ID of the check for keycode names QMK has removed.
+pub const DEPRECATED_KEYCODE_RULE: &str = "deprecated-keycode";
+
+/// Rule ID of the check for category references with no matching category.
+pub const MISSING_CATEGORY_RULE: &str = "missing-category";
+
+/// Rule ID of the check for numeric layer references past the last layer.
+pub const LAYER_REF_OFFSET_RULE: &str = "layer-ref-offset";
+
 /// Validation warning (non-blocking).
 #[derive(Debug, Clone)]
 pub struct ValidationWarning {
     /// Warning message
     pub message: String,
+    /// Layer index the warning refers to
+    pub layer: Option<usize>,
+    /// Visual row of the key the warning refers to
+    pub row: Option<u8>,
+    /// Visual column of the key the warning refers to
+    pub col: Option<u8>,
+    /// Optional suggestion for resolving the warning
+    pub suggestion: Option<String>,
+    /// ID of the check that raised the warning, if it has a quick fix
+    pub rule: Option<&'static str>,
 }
 
 impl ValidationWarning {
@@ -193,13 +226,48 @@ impl ValidationWarning {
     pub fn new(message: impl Into<String>) -> Self {
         Self {
             message: message.into(),
+            layer: None,
+            row: None,
+            col: None,
+            suggestion: None,
+            rule: None,
         }
     }
+
+    /// Sets the key the warning refers to.
+    #[must_use]
+    pub const fn at(mut self, layer: usize, row: u8, col: u8) -> Self {
+        self.layer = Some(layer);
+        self.row = Some(row);
+        self.col = Some(col);
+        self
+    }
+
+    /// Sets a suggestion for resolving the warning.
+    #[must_use]
+    pub fn with_suggestion(mut self, suggestion: impl Into<String>) -> Self {
+        self.suggestion = Some(suggestion.into());
+        self
+    }
+
+    /// Sets the ID of the check that raised the warning.
+    #[must_use]
+    pub const fn with_rule(mut self, rule: &'static str) -> Self {
+        self.rule = Some(rule);
+        self
+    }
 }
 
 impl std::fmt::Display for ValidationWarning {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
-        write!(f, "{}", self.message)
+        if let (Some(layer), Some(row), Some(col)) = (self.layer, self.row, self.col) {
+            write!(f, "[Layer {layer} ({row}, {col})] ")?;
+        }
+        write!(f, "{}", self.message)?;
+        if let Some(suggestion) = &self.suggestion {
+            write!(f, "\n    → {suggestion}")?;
+        }
+        Ok(())
     }
 }
 
@@ -236,6 +304,11 @@ impl<'a> FirmwareValidator<'a> {
     /// - Matrix coordinates are within keyboard bounds
     /// - All required positions are present
     /// - No duplicate positions per layer
+    /// - No transparent keys on the base layer (warning)
+    /// - No removed keycode names (warning)
+    /// - No references to missing categories (warning)
+    /// - No category RGB flags while RGB is disabled (warning)
+    /// - No numeric layer references past the last layer (warning)
     #[allow(clippy::unnecessary_wraps)]
     pub fn validate(&self) -> Result<ValidationReport> {
         let mut report = ValidationReport::new();
@@ -275,9 +348,24 @@ impl<'a> FirmwareValidator<'a> {
         // Check matrix coverage
         self.validate_matrix_coverage(&mut report);
 
+        // Check for transparent keys with nothing below them
+        self.validate_base_layer_transparency(&mut report);
+
         // Check for orphaned tap dances
         self.validate_tap_dances(&mut report);
 
+        // Check that disabled layers aren't reachable from firmware
+        self.validate_disabled_layers(&mut report);
+
+        // Check for references to categories that no longer exist
+        self.validate_category_refs(&mut report);
+
+        // Check for category RGB flags that can't take effect
+        self.validate_category_rgb_flags(&mut report);
+
+        // Check for layer references left dangling by a deleted layer
+        self.validate_layer_ref_range(&mut report);
+
         Ok(report)
     }
 
@@ -358,6 +446,18 @@ impl<'a> FirmwareValidator<'a> {
         col: u8,
         keycode: &str,
     ) {
+        // Check the modern name so a renamed key isn't also reported invalid
+        let modern = modernize_keycode(keycode);
+        if let Some(modern) = &modern {
+            report.add_warning(
+                ValidationWarning::new(format!("'{keycode}' uses a keycode name QMK has removed"))
+                    .at(layer, row, col)
+                    .with_suggestion(format!("Use {modern}"))
+                    .with_rule(DEPRECATED_KEYCODE_RULE),
+            );
+        }
+        let keycode = modern.as_deref().unwrap_or(keycode);
+
         if !self.keycode_db.is_valid(keycode) {
             // Try to find similar keycodes for suggestion
             let suggestions = self.keycode_db.search(keycode);
@@ -445,8 +545,147 @@ impl<'a> FirmwareValidator<'a> {
         }
     }
 
+    /// Warns about `KC_TRNS` on the base layer.
+    ///
+    /// There is no lower layer to fall through to, so these keys compile but
+    /// do nothing, exactly like `KC_NO`.
+    fn validate_base_layer_transparency(&self, report: &mut ValidationReport) {
+        let Some(base) = self.layout.layers.first() else {
+            return;
+        };
+        for key in base.keys.iter().filter(|k| k.is_transparent()) {
+            let pos = key.position;
+            report.add_warning(
+                ValidationWarning::new(format!(
+                    "{} on the base layer does nothing (there is no lower layer)",
+                    key.keycode
+                ))
+                .at(0, pos.row, pos.col)
+                .with_suggestion("Use KC_NO or assign a real keycode")
+                .with_rule(BASE_LAYER_TRNS_RULE),
+            );
+        }
+    }
+
+    /// Warns about layers and keys whose category is missing.
+    ///
+    /// Colors fall back to the layer default, so this only loses
+    /// organization, not firmware output.
+    fn validate_category_refs(&self, report: &mut ValidationReport) {
+        let is_missing = |cat_id: &&String| self.layout.get_category(cat_id).is_none();
+        for (layer_idx, layer) in self.layout.layers.iter().enumerate() {
+            if let Some(cat_id) = layer.category_id.as_ref().filter(is_missing) {
+                report.add_warning(
+                    ValidationWarning::new(format!(
+                        "Layer {layer_idx} '{}' references missing category '{cat_id}'",
+                        layer.name
+                    ))
+                    .with_suggestion("Create the category or clear the reference")
+                    .with_rule(MISSING_CATEGORY_RULE),
+                );
+            }
+            for key in &layer.keys {
+                if let Some(cat_id) = key.category_id.as_ref().filter(is_missing) {
+                    report.add_warning(
+                        ValidationWarning::new(format!(
+                            "{} references missing category '{cat_id}'",
+                            key.keycode
+                        ))
+                        .at(layer_idx, key.position.row, key.position.col)
+                        .with_suggestion("Create the category or clear the reference")
+                        .with_rule(MISSING_CATEGORY_RULE),
+                    );
+                }
+            }
+        }
+    }
+
+    /// Warns about categories with RGB flags while RGB is turned off.
+    fn validate_category_rgb_flags(&self, report: &mut ValidationReport) {
+        if self.layout.rgb_enabled {
+            return;
+        }
+        for category in &self.layout.categories {
+            let flags: Vec<&str> = category.flags().map(CategoryFlag::name).collect();
+            if flags.is_empty() {
+                continue;
+            }
+            report.add_warning(
+                ValidationWarning::new(format!(
+                    "Category '{}' has RGB flags ({}) but RGB is disabled",
+                    category.id,
+                    flags.join(", ")
+                ))
+                .with_suggestion("Enable RGB in the layout settings or clear the flags"),
+            );
+        }
+    }
+
+    /// Warns about numeric layer references past the last layer.
+    ///
+    /// These are usually left behind by deleting a layer, which shifts the
+    /// layers after it down without rewriting keys such as `MO(3)`.
+    fn validate_layer_ref_range(&self, report: &mut ValidationReport) {
+        let layer_count = self.layout.layers.len();
+        for layer_ref in out_of_range_layer_refs(&self.layout.layers) {
+            report.add_warning(
+                ValidationWarning::new(format!(
+                    "{} references layer {}, but the layout only has {layer_count} layers",
+                    layer_ref.keycode, layer_ref.to_layer
+                ))
+                .at(
+                    layer_ref.from_layer,
+                    layer_ref.position.row,
+                    layer_ref.position.col,
+                )
+                .with_suggestion("Shift the reference down by the number of removed layers")
+                .with_rule(LAYER_REF_OFFSET_RULE),
+            );
+        }
+    }
+
+    /// Validates that no enabled layer references a disabled one.
+    fn validate_disabled_layers(&self, report: &mut ValidationReport) {
+        let layers = &self.layout.layers;
+        if !layers.iter().any(|layer| layer.enabled) {
+            report.add_error(
+                ValidationError::new(
+                    ValidationErrorKind::DisabledLayerReference,
+                    "All layers are disabled, so there is nothing to generate",
+                )
+                .with_suggestion("Enable at least one layer"),
+            );
+            return;
+        }
+
+        for layer_ref in disabled_layer_refs(layers) {
+            let target = &layers[layer_ref.to_layer];
+            report.add_error(
+                ValidationError::new(
+                    ValidationErrorKind::DisabledLayerReference,
+                    format!(
+                        "{} references layer {} '{}', which is disabled",
+                        layer_ref.keycode, layer_ref.to_layer, target.name
+                    ),
+                )
+                .with_layer(layer_ref.from_layer)
+                .with_position(layer_ref.position.row, layer_ref.position.col)
+                .with_suggestion("Enable the target layer or change the key"),
+            );
+        }
+    }
+
     /// Validates tap dance definitions.
     fn validate_tap_dances(&self, report: &mut ValidationReport) {
+        for td in &self.layout.tap_dances {
+            if let Err(e) = td.validate() {
+                report.add_error(ValidationError::new(
+                    ValidationErrorKind::InvalidTapDance,
+                    e.to_string(),
+                ));
+            }
+        }
+
         // Check for orphaned tap dances (defined but never used)
         let orphaned = self.layout.get_orphaned_tap_dances();
         for td_name in orphaned {
@@ -458,6 +697,87 @@ impl<'a> FirmwareValidator<'a> {
     }
 }
 
+/// Identifies the inputs a [`ValidationReport`] was computed from.
+#[derive(Debug, Clone, PartialEq, Eq)]
+struct ValidationKey {
+    /// [`Layout::content_hash`], which ignores timestamps
+    layout_hash: String,
+    /// Keyboard, variant, matrix size and key count of the geometry
+    geometry: String,
+}
+
+impl ValidationKey {
+    fn new(layout: &Layout, geometry: &KeyboardGeometry) -> Self {
+        Self {
+            layout_hash: layout.content_hash(),
+            geometry: format!(
+                "{}/{}/{}x{}/{}",
+                geometry.keyboard_name,
+                geometry.layout_name,
+                geometry.matrix_rows,
+                geometry.matrix_cols,
+                geometry.keys.len()
+            ),
+        }
+    }
+}
+
+/// Remembers the last validation so unchanged layouts aren't revalidated.
+///
+/// The report is reused while the layout's content hash and the geometry stay
+/// the same; [`ValidationCache::invalidate`] drops it outright.
+#[derive(Debug, Clone, Default)]
+pub struct ValidationCache {
+    last: Option<(ValidationKey, ValidationReport)>,
+    hits: usize,
+    misses: usize,
+}
+
+impl ValidationCache {
+    /// Validates `layout`, reusing the previous report if nothing changed.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if validation itself fails.
+    pub fn validate(
+        &mut self,
+        layout: &Layout,
+        geometry: &KeyboardGeometry,
+        mapping: &VisualLayoutMapping,
+        keycode_db: &KeycodeDb,
+    ) -> Result<ValidationReport> {
+        let key = ValidationKey::new(layout, geometry);
+        if let Some((cached_key, report)) = &self.last {
+            if *cached_key == key {
+                self.hits += 1;
+                return Ok(report.clone());
+            }
+        }
+
+        self.misses += 1;
+        let report = FirmwareValidator::new(layout, geometry, mapping, keycode_db).validate()?;
+        self.last = Some((key, report.clone()));
+        Ok(report)
+    }
+
+    /// Drops the cached report.
+    pub fn invalidate(&mut self) {
+        self.last = None;
+    }
+
+    /// Number of validations answered from the cache.
+    #[must_use]
+    pub const fn hits(&self) -> usize {
+        self.hits
+    }
+
+    /// Number of validations that had to run.
+    #[must_use]
+    pub const fn misses(&self) -> usize {
+        self.misses
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -468,8 +788,12 @@ mod tests {
     fn create_test_setup() -> (Layout, KeyboardGeometry, VisualLayoutMapping, KeycodeDb) {
         let mut layout = Layout::new("Test").unwrap();
         let mut layer = Layer::new(0, "Base", RgbColor::new(255, 255, 255)).unwrap();
-        layer.add_key(KeyDefinition::new(Position::new(0, 0), "KC_A"));
-        layer.add_key(KeyDefinition::new(Position::new(0, 1), "KC_B"));
+        layer
+            .add_key(KeyDefinition::new(Position::new(0, 0), "KC_A"))
+            .unwrap();
+        layer
+            .add_key(KeyDefinition::new(Position::new(0, 1), "KC_B"))
+            .unwrap();
         layout.add_layer(layer).unwrap();
 
         let mut geometry = KeyboardGeometry::new("test", "LAYOUT", 2, 2);
@@ -496,8 +820,12 @@ mod tests {
     fn test_invalid_keycode() {
         let (mut layout, geometry, mapping, keycode_db) = create_test_setup();
         let mut layer = Layer::new(1, "Invalid", RgbColor::new(255, 0, 0)).unwrap();
-        layer.add_key(KeyDefinition::new(Position::new(0, 0), "INVALID_KEY"));
-        layer.add_key(KeyDefinition::new(Position::new(0, 1), "KC_B"));
+        layer
+            .add_key(KeyDefinition::new(Position::new(0, 0), "INVALID_KEY"))
+            .unwrap();
+        layer
+            .add_key(KeyDefinition::new(Position::new(0, 1), "KC_B"))
+            .unwrap();
         layout.add_layer(layer).unwrap();
 
         let validator = FirmwareValidator::new(&layout, &geometry, &mapping, &keycode_db);
@@ -512,8 +840,13 @@ mod tests {
     fn test_duplicate_position() {
         let (mut layout, geometry, mapping, keycode_db) = create_test_setup();
         let mut layer = Layer::new(1, "Duplicate", RgbColor::new(255, 0, 0)).unwrap();
-        layer.add_key(KeyDefinition::new(Position::new(0, 0), "KC_A"));
-        layer.add_key(KeyDefinition::new(Position::new(0, 0), "KC_B")); // Duplicate
+        layer
+            .add_key(KeyDefinition::new(Position::new(0, 0), "KC_A"))
+            .unwrap();
+        // add_key rejects duplicates, so bypass it as a hand-edited file would
+        layer
+            .keys
+            .push(KeyDefinition::new(Position::new(0, 0), "KC_B"));
         layout.add_layer(layer).unwrap();
 
         let validator = FirmwareValidator::new(&layout, &geometry, &mapping, &keycode_db);
@@ -526,6 +859,34 @@ mod tests {
             .any(|e| e.kind == ValidationErrorKind::DuplicatePosition));
     }
 
+    #[test]
+    fn test_reference_to_disabled_layer() {
+        let (mut layout, geometry, mapping, keycode_db) = create_test_setup();
+        let mut layer = Layer::new(1, "Experimental", RgbColor::new(255, 0, 0)).unwrap();
+        layer
+            .add_key(KeyDefinition::new(Position::new(0, 0), "KC_TRNS"))
+            .unwrap();
+        layer
+            .add_key(KeyDefinition::new(Position::new(0, 1), "KC_TRNS"))
+            .unwrap();
+        layer.enabled = false;
+        layout.add_layer(layer).unwrap();
+        let validator = FirmwareValidator::new(&layout, &geometry, &mapping, &keycode_db);
+        assert!(validator.validate().unwrap().is_valid());
+
+        layout.layers[0].keys[1].keycode = "MO(1)".to_string();
+        let validator = FirmwareValidator::new(&layout, &geometry, &mapping, &keycode_db);
+        let report = validator.validate().unwrap();
+        assert_eq!(report.errors.len(), 1);
+        let error = &report.errors[0];
+        assert_eq!(error.kind, ValidationErrorKind::DisabledLayerReference);
+        assert_eq!(
+            (error.layer, error.row, error.col),
+            (Some(0), Some(0), Some(1))
+        );
+        assert!(error.message.contains("'Experimental', which is disabled"));
+    }
+
     #[test]
     fn test_empty_layer() {
         let (mut layout, geometry, mapping, keycode_db) = create_test_setup();
@@ -583,6 +944,101 @@ mod tests {
             .contains("Tap dance 'unused_td' is defined but never used"));
     }
 
+    #[test]
+    fn test_base_layer_trns_warning() {
+        let (mut layout, geometry, mapping, keycode_db) = create_test_setup();
+        layout.layers[0].keys[1].keycode = "KC_TRNS".to_string();
+        let mut layer = Layer::new(1, "Upper", RgbColor::new(255, 0, 0)).unwrap();
+        for col in 0..2 {
+            layer
+                .add_key(KeyDefinition::new(Position::new(0, col), "KC_TRNS"))
+                .unwrap();
+        }
+        layout.add_layer(layer).unwrap();
+
+        let validator = FirmwareValidator::new(&layout, &geometry, &mapping, &keycode_db);
+        let report = validator.validate().unwrap();
+
+        // Transparent keys above the base layer are fine
+        assert!(report.is_valid());
+        assert_eq!(report.warnings.len(), 1);
+        let warning = &report.warnings[0];
+        assert_eq!(warning.rule, Some(BASE_LAYER_TRNS_RULE));
+        assert_eq!(
+            (warning.layer, warning.row, warning.col),
+            (Some(0), Some(0), Some(1))
+        );
+        assert!(warning.to_string().starts_with("[Layer 0 (0, 1)] KC_TRNS"));
+    }
+
+    #[test]
+    fn test_fixable_warnings() {
+        let (mut layout, geometry, mapping, keycode_db) = create_test_setup();
+        layout.layers[0].keys[0].keycode = "KC_LCTRL".to_string();
+        layout.layers[0].keys[1].keycode = "MO(2)".to_string();
+        layout.layers[0].keys[1].category_id = Some("navigation".to_string());
+
+        let validator = FirmwareValidator::new(&layout, &geometry, &mapping, &keycode_db);
+        let report = validator.validate().unwrap();
+
+        // A renamed keycode is a warning, not an invalid keycode error
+        assert!(report.is_valid());
+        let rules: Vec<_> = report.warnings.iter().map(|w| w.rule).collect();
+        assert_eq!(
+            rules,
+            [
+                Some(DEPRECATED_KEYCODE_RULE),
+                Some(MISSING_CATEGORY_RULE),
+                Some(LAYER_REF_OFFSET_RULE),
+            ]
+        );
+        assert!(report.warnings[0].to_string().contains("→ Use KC_LCTL"));
+    }
+
+    #[test]
+    fn test_category_rgb_flags_warn_when_rgb_disabled() {
+        use crate::models::Category;
+
+        let (mut layout, geometry, mapping, keycode_db) = create_test_setup();
+        let mut nav = Category::new("nav", "Nav", RgbColor::new(0, 255, 0)).unwrap();
+        nav.always_on = true;
+        nav.breathing = true;
+        layout.categories.push(nav);
+        layout.layers[0].keys[0].category_id = Some("nav".to_string());
+
+        let validator = FirmwareValidator::new(&layout, &geometry, &mapping, &keycode_db);
+        assert!(validator.validate().unwrap().warnings.is_empty());
+
+        layout.rgb_enabled = false;
+        let validator = FirmwareValidator::new(&layout, &geometry, &mapping, &keycode_db);
+        let report = validator.validate().unwrap();
+        assert!(report.is_valid());
+        assert_eq!(report.warnings.len(), 1);
+        assert_eq!(
+            report.warnings[0].message,
+            "Category 'nav' has RGB flags (always-on, breathing) but RGB is disabled"
+        );
+    }
+
+    #[test]
+    fn test_tap_dance_term_out_of_range_is_error() {
+        use crate::models::layout::TapDanceAction;
+
+        let (mut layout, geometry, mapping, keycode_db) = create_test_setup();
+
+        let mut tap_dance = TapDanceAction::new("slow_td", "KC_A");
+        tap_dance.tapping_term_ms = Some(5000);
+        layout.tap_dances.push(tap_dance);
+        layout.layers[0].keys[0].keycode = "TD(slow_td)".to_string();
+
+        let validator = FirmwareValidator::new(&layout, &geometry, &mapping, &keycode_db);
+        let report = validator.validate().unwrap();
+
+        assert!(!report.is_valid());
+        assert_eq!(report.errors[0].kind, ValidationErrorKind::InvalidTapDance);
+        assert!(report.errors[0].message.contains("between 50 and 2000ms"));
+    }
+
     #[test]
     fn test_used_tap_dance_no_warning() {
         use crate::models::layout::TapDanceAction;
@@ -608,4 +1064,50 @@ mod tests {
             .iter()
             .any(|w| w.message.contains("Tap dance")));
     }
+
+    #[test]
+    fn test_validation_cache_reuses_report_until_layout_changes() {
+        let (mut layout, geometry, mapping, keycode_db) = create_test_setup();
+        let mut cache = ValidationCache::default();
+
+        assert!(cache
+            .validate(&layout, &geometry, &mapping, &keycode_db)
+            .unwrap()
+            .is_valid());
+        // Touching timestamps doesn't change the content hash
+        layout.metadata.touch();
+        cache
+            .validate(&layout, &geometry, &mapping, &keycode_db)
+            .unwrap();
+        assert_eq!((cache.hits(), cache.misses()), (1, 1));
+
+        layout.layers[0].keys[0].keycode = "INVALID_KEY".to_string();
+        let report = cache
+            .validate(&layout, &geometry, &mapping, &keycode_db)
+            .unwrap();
+        assert!(!report.is_valid());
+        assert_eq!((cache.hits(), cache.misses()), (1, 2));
+
+        cache.invalidate();
+        cache
+            .validate(&layout, &geometry, &mapping, &keycode_db)
+            .unwrap();
+        assert_eq!((cache.hits(), cache.misses()), (1, 3));
+    }
+
+    #[test]
+    fn test_validation_cache_keyed_by_geometry() {
+        let (layout, geometry, mapping, keycode_db) = create_test_setup();
+        let mut cache = ValidationCache::default();
+        cache
+            .validate(&layout, &geometry, &mapping, &keycode_db)
+            .unwrap();
+
+        let mut other = geometry.clone();
+        other.layout_name = "LAYOUT_other".to_string();
+        cache
+            .validate(&layout, &other, &mapping, &keycode_db)
+            .unwrap();
+        assert_eq!((cache.hits(), cache.misses()), (0, 2));
+    }
 }
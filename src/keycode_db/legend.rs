@@ -0,0 +1,299 @@
+//! Key legends for parameterized keycodes.
+//!
+//! Decodes tap-hold style keycodes (MT, LT, LM, OSM, `SH_T`, TD and the named
+//! mod-taps) into a tap line and a hold descriptor for keys two text lines
+//! tall ("Space" / "L1 hold"), plus an abbreviated compound form for keys with
+//! a single line ("Spc/L1").
+
+use super::{KeycodeDb, TapHoldType};
+
+/// Legend of a parameterized keycode.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct KeyLegend {
+    /// First line: the tap keycode's display name (e.g., "Space")
+    pub tap: String,
+    /// Second line: what holding or otherwise triggering the key does
+    /// (e.g., "L1 hold", "⌃ hold")
+    pub hold: String,
+    /// Both on one line, abbreviated (e.g., "Spc/L1")
+    pub compound: String,
+    /// The hold action alone (e.g., "L1", "⌃"), or `None` for keys without
+    /// a tap/hold split (LM, OSM, TD)
+    pub held: Option<String>,
+}
+
+impl KeycodeDb {
+    /// Decodes `keycode` into a two-line legend, or `None` for keycodes that
+    /// take no keycode or modifier parameter.
+    ///
+    /// `resolve_layer` turns a layer reference (number or `@id`) into the
+    /// layer number shown.
+    #[must_use]
+    pub fn key_legend(
+        &self,
+        keycode: &str,
+        resolve_layer: impl Fn(&str) -> String,
+    ) -> Option<KeyLegend> {
+        if let Some(info) = self.parse_tap_hold(keycode) {
+            let arg2 = info.arg2.as_deref().unwrap_or("");
+            return Some(match info.tap_hold_type {
+                TapHoldType::LayerTap => {
+                    let layer = format!("L{}", resolve_layer(&info.arg1));
+                    self.tap_legend(arg2, &format!("{layer} hold"), &layer)
+                }
+                TapHoldType::ModTap => {
+                    let mods = modifier_symbols(&info.arg1);
+                    self.tap_legend(arg2, &format!("{mods} hold"), &mods)
+                }
+                TapHoldType::ModTapNamed => {
+                    let mods = mod_tap_symbols(&info.prefix);
+                    self.tap_legend(&info.arg1, &format!("{mods} hold"), &mods)
+                }
+                TapHoldType::SwapHands => self.tap_legend(&info.arg1, "swap hold", "Sw"),
+                TapHoldType::LayerMod => {
+                    let label =
+                        format!("L{}+{}", resolve_layer(&info.arg1), modifier_symbols(arg2));
+                    KeyLegend {
+                        tap: label.clone(),
+                        hold: "hold".to_string(),
+                        compound: label,
+                        held: None,
+                    }
+                }
+            });
+        }
+
+        if let Some(mods) = keycode
+            .strip_prefix("OSM(")
+            .and_then(|rest| rest.strip_suffix(')'))
+        {
+            let mods = modifier_symbols(mods);
+            return Some(KeyLegend {
+                compound: format!("OS{mods}"),
+                tap: mods,
+                hold: "one-shot".to_string(),
+                held: None,
+            });
+        }
+
+        self.parse_tap_dance_keycode(keycode).map(|name| KeyLegend {
+            compound: format!("TD:{name}"),
+            tap: name,
+            hold: "tap dance".to_string(),
+            held: None,
+        })
+    }
+
+    /// Legend of a key that sends `tap_keycode` when tapped.
+    fn tap_legend(&self, tap_keycode: &str, hold: &str, hold_abbreviation: &str) -> KeyLegend {
+        let tap = self.display_name(tap_keycode);
+        KeyLegend {
+            compound: format!("{}/{hold_abbreviation}", abbreviate(&tap)),
+            tap,
+            hold: hold.to_string(),
+            held: Some(hold_abbreviation.to_string()),
+        }
+    }
+
+    /// Display name of a plain keycode: its database name ("Space"), the
+    /// unshifted symbol of a symbol key ("-" for "- _"), or the keycode
+    /// without its `KC_` prefix.
+    #[must_use]
+    pub fn display_name(&self, keycode: &str) -> String {
+        let Some(definition) = self.get(keycode) else {
+            return keycode.strip_prefix("KC_").unwrap_or(keycode).to_string();
+        };
+        let name = definition.name.as_str();
+        match name.split_once(' ') {
+            Some((symbol, _)) if symbol.chars().count() == 1 => symbol.to_string(),
+            _ => name.to_string(),
+        }
+    }
+}
+
+/// Abbreviates a display name to at most three characters.
+///
+/// Names of up to three characters are kept ("A", "F12", "Tab"), names of
+/// several words become their initials ("Page Up" -> "PU"), and longer single
+/// words keep their first letter and the consonants after it ("Space" ->
+/// "Spc", "Escape" -> "Esc").
+#[must_use]
+pub fn abbreviate(name: &str) -> String {
+    if name.chars().count() <= 3 {
+        return name.to_string();
+    }
+    let words: Vec<&str> = name.split_whitespace().collect();
+    if words.len() > 1 {
+        return words
+            .iter()
+            .filter_map(|word| word.chars().next())
+            .take(3)
+            .collect();
+    }
+    let mut chars = name.chars();
+    chars
+        .next()
+        .into_iter()
+        .chain(chars.filter(|c| !"aeiouAEIOU".contains(*c)))
+        .take(3)
+        .collect()
+}
+
+/// Symbols of the modifiers in a `MOD_*` expression ("`MOD_LCTL | MOD_LSFT`"
+/// -> "⌃⇧"), in Ctrl, Shift, Alt, GUI order.
+#[must_use]
+pub fn modifier_symbols(mods: &str) -> String {
+    let symbols: String = [
+        (["CTL", "CTRL"], '⌃'),
+        (["SFT", "SHIFT"], '⇧'),
+        (["ALT", "OPT"], '⌥'),
+        (["GUI", "CMD"], '⌘'),
+    ]
+    .iter()
+    .filter(|(names, _)| names.iter().any(|name| mods.contains(name)))
+    .map(|(_, symbol)| symbol)
+    .collect();
+    if mods.contains("MEH") {
+        "⌃⇧⌥".to_string()
+    } else if mods.contains("HYPR") {
+        "⌃⇧⌥⌘".to_string()
+    } else if symbols.is_empty() {
+        mods.trim().chars().take(3).collect()
+    } else {
+        symbols
+    }
+}
+
+/// Symbols of the modifiers a named mod-tap holds (`LCS_T` -> "⌃⇧").
+fn mod_tap_symbols(prefix: &str) -> String {
+    let name = prefix.strip_suffix("_T").unwrap_or(prefix);
+    let name = name
+        .strip_prefix(['L', 'R'])
+        .filter(|rest| !rest.is_empty())
+        .unwrap_or(name);
+    match name {
+        "CTL" => "⌃".to_string(),
+        "SFT" => "⇧".to_string(),
+        "ALT" | "OPT" => "⌥".to_string(),
+        "GUI" | "CMD" | "WIN" => "⌘".to_string(),
+        "SGUI" => "⇧⌘".to_string(),
+        // Shift + AltGr
+        "SAGR" => "⇧⌥".to_string(),
+        "MEH" => "⌃⇧⌥".to_string(),
+        "HYPR" | "ALL" => "⌃⇧⌥⌘".to_string(),
+        letters => letters
+            .chars()
+            .filter_map(|letter| match letter {
+                'C' => Some('⌃'),
+                'S' => Some('⇧'),
+                'A' => Some('⌥'),
+                'G' => Some('⌘'),
+                _ => None,
+            })
+            .collect(),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn legend(keycode: &str) -> Option<KeyLegend> {
+        let db = KeycodeDb::load().unwrap();
+        db.key_legend(keycode, |layer| {
+            if layer == "@nav" {
+                "2".to_string()
+            } else {
+                layer.to_string()
+            }
+        })
+    }
+
+    fn lines(keycode: &str) -> (String, String, String) {
+        let legend = legend(keycode).unwrap();
+        (legend.tap, legend.hold, legend.compound)
+    }
+
+    fn expected(tap: &str, hold: &str, compound: &str) -> (String, String, String) {
+        (tap.to_string(), hold.to_string(), compound.to_string())
+    }
+
+    #[test]
+    fn test_layer_tap_and_layer_mod() {
+        assert_eq!(
+            lines("LT(1, KC_SPC)"),
+            expected("Space", "L1 hold", "Spc/L1")
+        );
+        assert_eq!(
+            lines("LT(@nav,KC_ESC)"),
+            expected("Escape", "L2 hold", "Esc/L2")
+        );
+        assert_eq!(lines("LM(1, MOD_LSFT)"), expected("L1+⇧", "hold", "L1+⇧"));
+    }
+
+    #[test]
+    fn test_mod_taps() {
+        assert_eq!(lines("MT(MOD_LCTL, KC_A)"), expected("A", "⌃ hold", "A/⌃"));
+        assert_eq!(
+            lines("MT(MOD_LCTL | MOD_LSFT, KC_ENT)"),
+            expected("Enter", "⌃⇧ hold", "Ent/⌃⇧")
+        );
+        assert_eq!(lines("LCTL_T(KC_A)"), expected("A", "⌃ hold", "A/⌃"));
+        assert_eq!(lines("RSFT_T(KC_SLSH)"), expected("/", "⇧ hold", "//⇧"));
+        assert_eq!(lines("LCS_T(KC_TAB)"), expected("Tab", "⌃⇧ hold", "Tab/⌃⇧"));
+        assert_eq!(lines("MEH_T(KC_F1)"), expected("F1", "⌃⇧⌥ hold", "F1/⌃⇧⌥"));
+    }
+
+    #[test]
+    fn test_one_shot_swap_hands_and_tap_dance() {
+        assert_eq!(lines("OSM(MOD_LSFT)"), expected("⇧", "one-shot", "OS⇧"));
+        assert_eq!(
+            lines("SH_T(KC_BSPC)"),
+            expected("Backspace", "swap hold", "Bck/Sw")
+        );
+        assert_eq!(
+            lines("TD(esc_caps)"),
+            expected("esc_caps", "tap dance", "TD:esc_caps")
+        );
+    }
+
+    #[test]
+    fn test_held_action_only_for_tap_hold_splits() {
+        assert_eq!(legend("LT(1, KC_SPC)").unwrap().held.as_deref(), Some("L1"));
+        assert_eq!(legend("LGUI_T(KC_D)").unwrap().held.as_deref(), Some("⌘"));
+        assert_eq!(legend("OSM(MOD_LSFT)").unwrap().held, None);
+        assert_eq!(legend("TD(esc_caps)").unwrap().held, None);
+    }
+
+    #[test]
+    fn test_plain_keycodes_have_no_legend() {
+        assert!(legend("KC_A").is_none());
+        assert!(legend("MO(1)").is_none());
+        assert!(legend("LCTL(KC_C)").is_none());
+    }
+
+    #[test]
+    fn test_abbreviate() {
+        assert_eq!(abbreviate("A"), "A");
+        assert_eq!(abbreviate("F12"), "F12");
+        assert_eq!(abbreviate("Space"), "Spc");
+        assert_eq!(abbreviate("Escape"), "Esc");
+        assert_eq!(abbreviate("Home"), "Hm");
+        assert_eq!(abbreviate("Page Up"), "PU");
+        assert_eq!(abbreviate("Locking Caps Lock"), "LCL");
+    }
+
+    #[test]
+    fn test_modifier_symbols() {
+        assert_eq!(modifier_symbols("MOD_LCTL"), "⌃");
+        assert_eq!(modifier_symbols("MOD_RALT"), "⌥");
+        assert_eq!(
+            modifier_symbols("MOD_LGUI | MOD_LSFT | MOD_LCTL | MOD_LALT"),
+            "⌃⇧⌥⌘"
+        );
+        assert_eq!(modifier_symbols("MOD_MEH"), "⌃⇧⌥");
+        assert_eq!(mod_tap_symbols("RGUI_T"), "⌘");
+        assert_eq!(mod_tap_symbols("LSAG_T"), "⇧⌥⌘");
+        assert_eq!(mod_tap_symbols("HYPR_T"), "⌃⇧⌥⌘");
+    }
+}
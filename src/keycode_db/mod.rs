@@ -7,6 +7,7 @@
 
 #[cfg(feature = "web")]
 mod display;
+mod legend;
 
 // Re-exports for web feature - used by web::mod.rs but may appear unused
 // when compiling the main binary with web feature enabled.
@@ -16,6 +17,8 @@ pub use display::{
     ActionKind, KeyDetailAction, KeyDisplay, KeyDisplayMetadata, TapDanceDisplayInfo,
 };
 
+pub use legend::KeyLegend;
+
 use anyhow::{Context, Result};
 use regex::Regex;
 use serde::{Deserialize, Serialize};
@@ -90,6 +93,22 @@ struct CategoriesIndex {
     categories: Vec<KeycodeCategory>,
 }
 
+/// Merged upstream QMK keycode data generated by `build.rs`.
+#[derive(Debug, Clone, Deserialize)]
+struct UpstreamKeycodes {
+    version: String,
+    keycodes: Vec<UpstreamKeycode>,
+}
+
+/// A keycode from QMK's `keycodes_*.hjson` data files.
+#[derive(Debug, Clone, Deserialize)]
+struct UpstreamKeycode {
+    key: String,
+    group: String,
+    #[serde(default)]
+    aliases: Vec<String>,
+}
+
 /// Category file schema (categories/*.json).
 #[derive(Debug, Clone, Serialize, Deserialize)]
 struct CategoryFile {
@@ -166,6 +185,10 @@ pub struct KeycodeDb {
     patterns: Vec<(String, Regex)>,
     /// Language-specific keycodes (loaded separately from main categories)
     languages: Vec<LanguageKeycodes>,
+    /// Curated database schema version (from categories.json)
+    version: String,
+    /// Version of the vendored QMK keycode snapshot
+    qmk_version: String,
 }
 
 /// Type of tap-hold keycode
@@ -262,6 +285,13 @@ impl KeycodeDb {
             }
         }
 
+        // Merge the vendored QMK keycode snapshot so upstream spellings and
+        // keycodes without a curated entry are recognized.
+        let upstream: UpstreamKeycodes =
+            serde_json::from_str(include_str!(concat!(env!("OUT_DIR"), "/qmk_keycodes.json")))
+                .context("Failed to parse QMK keycode snapshot")?;
+        Self::merge_upstream(&mut all_keycodes, &mut lookup, &upstream.keycodes);
+
         // Load language-specific keycodes and merge them into the database so
         // validation recognizes DE_/FR_/… codes.
         let languages = Self::load_languages()?;
@@ -293,9 +323,95 @@ impl KeycodeDb {
             lookup,
             patterns,
             languages,
+            version: index.version,
+            qmk_version: upstream.version,
         })
     }
 
+    /// Merges upstream QMK keycodes into the curated database.
+    ///
+    /// Upstream names matching a curated entry become aliases of it; keycodes
+    /// with no curated entry are added under a category mapped from their group.
+    fn merge_upstream(
+        keycodes: &mut Vec<KeycodeDefinition>,
+        lookup: &mut HashMap<String, usize>,
+        upstream: &[UpstreamKeycode],
+    ) {
+        for kc in upstream {
+            let names: Vec<&String> = std::iter::once(&kc.key).chain(&kc.aliases).collect();
+
+            let idx = if let Some(idx) = names.iter().find_map(|name| lookup.get(*name).copied()) {
+                idx
+            } else {
+                // QMK prefers the short alias in keymaps (e.g. DM_REC1)
+                let code = kc.aliases.first().unwrap_or(&kc.key).clone();
+                keycodes.push(KeycodeDefinition {
+                    name: Self::upstream_display_name(&kc.key),
+                    category: Self::upstream_category(&kc.group).to_string(),
+                    description: None,
+                    pattern: None,
+                    aliases: Vec::new(),
+                    params: Vec::new(),
+                    code,
+                });
+                keycodes.len() - 1
+            };
+
+            for name in names {
+                if lookup.contains_key(name) {
+                    continue;
+                }
+                lookup.insert(name.clone(), idx);
+                let definition = &mut keycodes[idx];
+                if definition.code != *name {
+                    definition.aliases.push(name.clone());
+                }
+            }
+        }
+    }
+
+    /// Maps a QMK keycode group to a picker category.
+    fn upstream_category(group: &str) -> &'static str {
+        match group {
+            "basic" => "basic",
+            "quantum" => "system",
+            "media" => "media",
+            "mouse" => "mouse",
+            "modifiers" => "modifiers",
+            "lighting" => "rgb",
+            "audio" => "audio",
+            _ => "advanced",
+        }
+    }
+
+    /// Builds a display name from an upstream key, e.g. "`QK_MAKE`" -> "Make".
+    fn upstream_display_name(key: &str) -> String {
+        key.strip_prefix("QK_")
+            .unwrap_or(key)
+            .split('_')
+            .map(|word| {
+                let lower = word.to_lowercase();
+                let mut chars = lower.chars();
+                chars.next().map_or_else(String::new, |first| {
+                    first.to_uppercase().collect::<String>() + chars.as_str()
+                })
+            })
+            .collect::<Vec<_>>()
+            .join(" ")
+    }
+
+    /// Gets the curated database schema version.
+    #[must_use]
+    pub fn version(&self) -> &str {
+        &self.version
+    }
+
+    /// Gets the version of the vendored QMK keycode snapshot.
+    #[must_use]
+    pub fn qmk_version(&self) -> &str {
+        &self.qmk_version
+    }
+
     /// Loads language-specific keycode files.
     fn load_languages() -> Result<Vec<LanguageKeycodes>> {
         // Include all language files at compile time
@@ -444,7 +560,7 @@ impl KeycodeDb {
             .collect();
 
         // Sort by relevance (descending)
-        results.sort_by(|a, b| b.1.cmp(&a.1));
+        results.sort_by_key(|r| std::cmp::Reverse(r.1));
 
         results.into_iter().map(|(keycode, _)| keycode).collect()
     }
@@ -577,7 +693,7 @@ impl KeycodeDb {
             .collect();
 
         // Sort by relevance (descending)
-        results.sort_by(|a, b| b.1.cmp(&a.1));
+        results.sort_by_key(|r| std::cmp::Reverse(r.1));
 
         results.into_iter().map(|(keycode, _)| keycode).collect()
     }
@@ -847,6 +963,32 @@ mod tests {
         assert!(db.is_valid("KC_ENTER")); // Alias
     }
 
+    #[test]
+    fn test_upstream_snapshot_merged() {
+        let db = get_test_db();
+        // Upstream-only spellings become aliases of curated entries
+        assert!(db.is_valid("QK_BOOTLOADER"));
+        assert_eq!(db.get("QK_BOOTLOADER").unwrap().code, "QK_BOOT");
+        assert!(db.is_valid("QK_RBT"));
+        // Upstream keycodes without a curated entry are added
+        let make = db.get("QK_MAKE").unwrap();
+        assert_eq!(make.name, "Make");
+        assert_eq!(make.category, "system");
+        // Newer keycodes
+        for code in [
+            "QK_DYNAMIC_MACRO_RECORD_START_1",
+            "DM_PLY2",
+            "QK_REP",
+            "QK_AREP",
+            "QK_LOCK",
+            "QK_CAPS_WORD_TOGGLE",
+        ] {
+            assert!(db.is_valid(code), "{code} should be valid");
+        }
+        assert_eq!(db.qmk_version(), "0.0.6");
+        assert_eq!(db.version(), "2.0");
+    }
+
     #[test]
     fn test_is_valid_special_keys() {
         let db = get_test_db();
@@ -13,6 +13,7 @@
 
 // Module declarations
 pub mod app;
+pub mod atomic_write;
 pub mod branding;
 pub mod cli;
 pub mod config;
@@ -22,6 +23,7 @@ pub mod firmware;
 pub mod keycode_db;
 pub mod models;
 pub mod parser;
+pub mod paths;
 pub mod services;
 pub mod shortcuts;
 pub mod tui;
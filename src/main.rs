@@ -24,7 +24,7 @@ mod shortcuts;
 mod tui;
 
 // Import from library to avoid module conflicts and duplication
-use lazyqmk::{branding, config};
+use lazyqmk::{atomic_write, branding, config, paths};
 
 use anyhow::Result;
 use clap::{Parser, Subcommand};
@@ -33,6 +33,7 @@ use std::path::PathBuf;
 
 /// LazyQMK - Keyboard layout editor for QMK firmware
 #[derive(Parser, Debug)]
+#[allow(clippy::struct_excessive_bools)] // Independent command-line flags
 #[command(
     author,
     version,
@@ -58,6 +59,9 @@ QUICK START:
   
   # Open specific layout in TUI
   lazyqmk my-layout.md
+
+  # Open a layout on layer 2 with the key at row 1, column 4 selected
+  lazyqmk edit my-layout.md --layer 2 --position 1,4
   
   # Start web server (default: http://localhost:3001)
   lazyqmk web
@@ -91,7 +95,7 @@ struct Cli {
     #[command(subcommand)]
     command: Option<Command>,
 
-    /// Path to layout markdown file (TUI mode only)
+    /// Path to layout markdown file (same as `edit FILE`)
     #[arg(value_name = "FILE")]
     layout_path: Option<PathBuf>,
 
@@ -102,6 +106,65 @@ struct Cli {
     /// Specify QMK firmware path
     #[arg(long, value_name = "PATH")]
     qmk_path: Option<PathBuf>,
+
+    /// Layout variant to use if the layout's variant no longer exists (TUI mode only)
+    #[arg(long, value_name = "LAYOUT")]
+    assume_variant: Option<String>,
+
+    /// Print a timing breakdown of startup and validation cache stats on exit (TUI mode only)
+    #[arg(long)]
+    profile_startup: bool,
+
+    /// Don't capture the mouse, so the terminal's text selection works (TUI mode only)
+    #[arg(long)]
+    no_mouse: bool,
+
+    /// Keep config, data and caches in `.lazyqmk/` in the current directory
+    /// (or set LAZYQMK_PORTABLE=1)
+    #[arg(long, global = true)]
+    portable: bool,
+}
+
+/// Editor arguments (`lazyqmk edit`; `lazyqmk FILE` is an alias)
+#[derive(clap::Args, Debug, Default)]
+#[allow(clippy::struct_excessive_bools)] // Independent command-line flags
+struct EditArgs {
+    /// Path to layout markdown file (shows the layout picker if omitted)
+    #[arg(value_name = "FILE")]
+    layout_path: Option<PathBuf>,
+
+    /// Layer to show when the editor opens
+    #[arg(long, value_name = "N", requires = "layout_path")]
+    layer: Option<usize>,
+
+    /// Key to select when the editor opens, as row,col
+    #[arg(long, value_name = "ROW,COL", requires = "layout_path")]
+    position: Option<models::Position>,
+
+    /// Open the layout without allowing changes to be kept or saved
+    #[arg(long, requires = "layout_path")]
+    read_only: bool,
+
+    /// QMK firmware path to use instead of the configured one
+    #[arg(long, value_name = "PATH")]
+    qmk_path: Option<PathBuf>,
+
+    /// Layout variant to use if the layout's variant no longer exists
+    #[arg(long, value_name = "LAYOUT")]
+    assume_variant: Option<String>,
+
+    /// Print a timing breakdown of startup and validation cache stats on exit
+    #[arg(long)]
+    profile_startup: bool,
+
+    /// Don't capture the mouse, so the terminal's text selection works
+    #[arg(long)]
+    no_mouse: bool,
+
+    /// Start builds without checking for the toolchain, qmk CLI and QMK
+    /// submodules
+    #[arg(long, requires = "layout_path")]
+    skip_env_check: bool,
 }
 
 /// Web server arguments
@@ -116,6 +179,16 @@ struct WebArgs {
     #[arg(long, default_value = "127.0.0.1")]
     host: String,
 
+    /// Access token required for API requests (or set LAZYQMK_AUTH_TOKEN).
+    /// Mandatory when binding to a non-loopback address
+    #[arg(long)]
+    auth_token: Option<String>,
+
+    /// Allow binding to a non-loopback address without an access token
+    /// (e.g., inside a container, behind port mapping)
+    #[arg(long)]
+    allow_unauthenticated: bool,
+
     /// Workspace directory containing layout files.
     /// Defaults to platform-specific layouts directory.
     #[arg(short, long)]
@@ -124,16 +197,37 @@ struct WebArgs {
     /// Enable verbose logging
     #[arg(short, long)]
     verbose: bool,
+
+    /// Hide layout names in request logs by logging route templates
+    /// (e.g. /api/layouts/{filename}) instead of request paths
+    #[arg(long)]
+    redact_logs: bool,
 }
 
 #[derive(Subcommand, Debug)]
 enum Command {
+    /// Open a layout in the terminal editor
+    Edit(EditArgs),
+    /// Create a new layout for a keyboard
+    New(cli::NewArgs),
     /// Validate a layout file for errors and warnings
     Validate(cli::ValidateArgs),
+    /// Check a layout against style rules
+    Lint(cli::LintArgs),
+    /// Apply the quick fix for a validation rule
+    Fix(cli::FixArgs),
     /// Generate QMK firmware files (keymap.c, config.h)
     Generate(cli::GenerateArgs),
+    /// Generate firmware files into the QMK tree and compile them
+    Build(cli::BuildArgs),
+    /// Show when a layout was generated and built, and how it went
+    History(cli::HistoryArgs),
+    /// Check that firmware builds can run (toolchain, qmk CLI, submodules)
+    Doctor(cli::DoctorArgs),
     /// Export keyboard layout to markdown documentation
     Export(cli::ExportArgs),
+    /// Render a key usage heatmap from typing frequency data
+    Heatmap(cli::HeatmapArgs),
     /// Display help topics and keybindings
     #[command(name = "show-help")]
     ShowHelp(cli::HelpArgs),
@@ -146,9 +240,15 @@ enum Command {
     /// Manage tap dance definitions
     #[command(name = "tap-dance")]
     TapDance(cli::TapDanceArgs),
+    /// Edit the keys of a layer (e.g. paste a keycode grid)
+    Layer(cli::LayerArgs),
     /// Show layer references and transparency warnings
     #[command(name = "layer-refs")]
     LayerRefs(cli::LayerRefsArgs),
+    /// List saved layout files and whether they parse
+    Layouts(cli::LayoutsArgs),
+    /// Export and import shared tap dance libraries
+    Library(cli::LibraryArgs),
     /// List all compilable keyboards in QMK firmware directory
     #[command(name = "list-keyboards")]
     ListKeyboards(cli::ListKeyboardsArgs),
@@ -161,125 +261,180 @@ enum Command {
     Config(cli::ConfigArgs),
     /// Manage categories in a layout
     Category(cli::CategoryArgs),
+    /// Apply a batch of operations from a JSON file to a layout
+    Apply(cli::ApplyArgs),
     /// Manage layout templates
     Template(cli::TemplateArgs),
+    /// Migrate a layout to another keyboard by matching key positions
+    Remap(cli::RemapArgs),
+    /// Rename a keycode across one or more layout files
+    #[command(name = "rename-keycode")]
+    RenameKeycode(cli::RenameKeycodeArgs),
+    /// Convert a layout between file formats (Markdown, JSON)
+    Convert(cli::ConvertArgs),
     /// Start web server for browser-based editor
     #[cfg(feature = "web")]
     Web(WebArgs),
 }
 
+impl Command {
+    /// Whether the command was asked for JSON output, so errors are
+    /// reported as JSON too
+    const fn json_output(&self) -> bool {
+        match self {
+            Self::Validate(args) => args.json,
+            Self::Lint(args) => args.json,
+            Self::Generate(args) => args.json,
+            Self::History(args) => args.json,
+            Self::Doctor(args) => args.json,
+            Self::Inspect(args) => args.json,
+            Self::Keycode(args) => args.json,
+            Self::Keycodes(args) => args.json,
+            Self::LayerRefs(args) => args.json,
+            Self::ListKeyboards(args) => args.json,
+            Self::ListLayouts(args) => args.json,
+            Self::Geometry(args) => args.json,
+            Self::Apply(args) => args.json,
+            Self::Remap(args) => args.json,
+            Self::TapDance(args) => args.json_output(),
+            Self::Category(args) => args.json_output(),
+            Self::Layouts(args) => args.json_output(),
+            Self::Template(args) => args.json_output(),
+            _ => false,
+        }
+    }
+}
+
 fn main() -> Result<()> {
     let cli = Cli::parse();
+    paths::set_portable(cli.portable);
+    migrate_legacy_paths();
 
     // Handle CLI subcommands first (headless mode)
     if let Some(command) = cli.command {
         use cli::ExitCode;
 
+        let json = command.json_output();
         let exit_code = match command {
+            Command::Edit(args) => {
+                print_banner();
+                return run_editor(args);
+            }
             Command::Validate(args) => match args.execute() {
                 Ok(()) => ExitCode::Success,
-                Err(e) => {
-                    eprintln!("Error: {}", e.message);
-                    e.exit_code
-                }
+                Err(e) => e.report(json),
+            },
+            Command::Lint(args) => match args.execute() {
+                Ok(()) => ExitCode::Success,
+                Err(e) => e.report(json),
+            },
+            Command::Fix(args) => match args.execute() {
+                Ok(()) => ExitCode::Success,
+                Err(e) => e.report(json),
             },
             Command::Generate(args) => match args.execute() {
+                Ok(code) => code,
+                Err(e) => e.report(json),
+            },
+            Command::Build(args) => match args.execute() {
                 Ok(()) => ExitCode::Success,
-                Err(e) => {
-                    eprintln!("Error: {}", e.message);
-                    e.exit_code
-                }
+                Err(e) => e.report(json),
+            },
+            Command::History(args) => match args.execute() {
+                Ok(()) => ExitCode::Success,
+                Err(e) => e.report(json),
+            },
+            Command::Doctor(args) => match args.execute() {
+                Ok(()) => ExitCode::Success,
+                Err(e) => e.report(json),
+            },
+            Command::Remap(args) => match args.execute() {
+                Ok(()) => ExitCode::Success,
+                Err(e) => e.report(json),
+            },
+            Command::RenameKeycode(args) => match args.execute() {
+                Ok(()) => ExitCode::Success,
+                Err(e) => e.report(json),
+            },
+            Command::Convert(args) => match args.execute() {
+                Ok(()) => ExitCode::Success,
+                Err(e) => e.report(json),
+            },
+            Command::Heatmap(args) => match args.execute() {
+                Ok(()) => ExitCode::Success,
+                Err(e) => e.report(json),
             },
             Command::Export(args) => match args.execute() {
                 Ok(()) => ExitCode::Success,
-                Err(e) => {
-                    eprintln!("Error: {}", e.message);
-                    e.exit_code
-                }
+                Err(e) => e.report(json),
             },
             Command::ShowHelp(args) => match args.execute() {
                 Ok(()) => ExitCode::Success,
-                Err(e) => {
-                    eprintln!("Error: {}", e.message);
-                    e.exit_code
-                }
+                Err(e) => e.report(json),
             },
             Command::Inspect(args) => match args.execute() {
                 Ok(()) => ExitCode::Success,
-                Err(e) => {
-                    eprintln!("Error: {}", e.message);
-                    e.exit_code
-                }
+                Err(e) => e.report(json),
             },
             Command::Keycode(args) => match args.execute() {
                 Ok(()) => ExitCode::Success,
-                Err(e) => {
-                    eprintln!("Error: {}", e.message);
-                    e.exit_code
-                }
+                Err(e) => e.report(json),
             },
             Command::Keycodes(args) => match args.execute() {
                 Ok(()) => ExitCode::Success,
-                Err(e) => {
-                    eprintln!("Error: {}", e.message);
-                    e.exit_code
-                }
+                Err(e) => e.report(json),
             },
             Command::TapDance(args) => match args.execute() {
                 Ok(()) => ExitCode::Success,
-                Err(e) => {
-                    eprintln!("Error: {}", e.message);
-                    e.exit_code
-                }
+                Err(e) => e.report(json),
+            },
+            Command::Layer(args) => match args.execute() {
+                Ok(()) => ExitCode::Success,
+                Err(e) => e.report(json),
             },
             Command::LayerRefs(args) => match args.execute() {
                 Ok(()) => ExitCode::Success,
-                Err(e) => {
-                    eprintln!("Error: {}", e.message);
-                    e.exit_code
-                }
+                Err(e) => e.report(json),
+            },
+            Command::Layouts(args) => match args.execute() {
+                Ok(()) => ExitCode::Success,
+                Err(e) => e.report(json),
+            },
+            Command::Library(args) => match args.execute() {
+                Ok(()) => ExitCode::Success,
+                Err(e) => e.report(json),
             },
             Command::ListKeyboards(args) => match args.execute() {
                 Ok(()) => ExitCode::Success,
-                Err(e) => {
-                    eprintln!("Error: {}", e.message);
-                    e.exit_code
-                }
+                Err(e) => e.report(json),
             },
             Command::ListLayouts(args) => match args.execute() {
                 Ok(()) => ExitCode::Success,
-                Err(e) => {
-                    eprintln!("Error: {}", e.message);
-                    e.exit_code
-                }
+                Err(e) => e.report(json),
             },
             Command::Geometry(args) => match args.execute() {
                 Ok(()) => ExitCode::Success,
-                Err(e) => {
-                    eprintln!("Error: {}", e.message);
-                    e.exit_code
-                }
+                Err(e) => e.report(json),
+            },
+            Command::New(args) => match args.execute() {
+                Ok(()) => ExitCode::Success,
+                Err(e) => e.report(json),
             },
             Command::Config(args) => match args.execute() {
                 Ok(()) => ExitCode::Success,
-                Err(e) => {
-                    eprintln!("Error: {}", e.message);
-                    e.exit_code
-                }
+                Err(e) => e.report(json),
             },
             Command::Category(args) => match args.execute() {
                 Ok(()) => ExitCode::Success,
-                Err(e) => {
-                    eprintln!("Error: {}", e.message);
-                    e.exit_code
-                }
+                Err(e) => e.report(json),
+            },
+            Command::Apply(args) => match args.execute() {
+                Ok(()) => ExitCode::Success,
+                Err(e) => e.report(json),
             },
             Command::Template(args) => match args.execute() {
                 Ok(()) => ExitCode::Success,
-                Err(e) => {
-                    eprintln!("Error: {}", e.message);
-                    e.exit_code
-                }
+                Err(e) => e.report(json),
             },
             #[cfg(feature = "web")]
             Command::Web(args) => {
@@ -291,18 +446,60 @@ fn main() -> Result<()> {
         std::process::exit(exit_code as i32);
     }
 
-    // TUI mode - print branding
-    println!("{} v{}", APP_NAME, env!("CARGO_PKG_VERSION"));
-    println!("{}", APP_DESCRIPTION);
-    println!();
+    print_banner();
 
     if cli.init {
+        apply_mouse_preference(cli.no_mouse);
         // Run onboarding wizard
         app::run_onboarding_wizard_terminal()?;
         return Ok(());
     }
 
-    if let Some(path) = cli.layout_path {
+    // `lazyqmk [FILE]` is shorthand for `lazyqmk edit [FILE]`
+    run_editor(EditArgs {
+        layout_path: cli.layout_path,
+        qmk_path: cli.qmk_path,
+        assume_variant: cli.assume_variant,
+        profile_startup: cli.profile_startup,
+        no_mouse: cli.no_mouse,
+        ..EditArgs::default()
+    })
+}
+
+/// Applies `ui.mouse` and `--no-mouse` before a terminal UI starts.
+fn apply_mouse_preference(no_mouse: bool) {
+    let configured = config::Config::load().map_or(true, |config| config.ui.mouse);
+    tui::set_mouse_capture_preference(configured && !no_mouse);
+}
+
+/// Moves files that earlier versions kept in the config directory to the
+/// data and cache directories.
+fn migrate_legacy_paths() {
+    let Ok(app_paths) = paths::AppPaths::current() else {
+        return;
+    };
+    match paths::migrate_legacy(&app_paths) {
+        Ok(moved) => {
+            for (from, to) in moved {
+                eprintln!("Moved {} to {}", from.display(), to.display());
+            }
+        }
+        Err(e) => eprintln!("Warning: {e:#}"),
+    }
+}
+
+/// Prints the app name and version before a terminal UI starts.
+fn print_banner() {
+    println!("{} v{}", APP_NAME, env!("CARGO_PKG_VERSION"));
+    println!("{}", APP_DESCRIPTION);
+    println!();
+}
+
+/// Opens a layout in the editor, or the layout picker without a file.
+fn run_editor(args: EditArgs) -> Result<()> {
+    apply_mouse_preference(args.no_mouse);
+
+    if let Some(path) = args.layout_path {
         // Validate the file path before attempting to load
         if !path.exists() {
             eprintln!("Error: Layout file not found: {}", path.display());
@@ -332,54 +529,16 @@ fn main() -> Result<()> {
             }
         }
 
-        // Load the layout
-        let layout = services::LayoutService::load(&path)?;
-
-        // Load or create default config
-        let config_result = config::Config::load();
-        let config = config_result.unwrap_or_else(|_| config::Config::default());
-
-        // Try to build proper geometry from QMK if config is available
-        let (geometry, mapping) = if config.paths.qmk_firmware.is_some() {
-            // Get layout variant from metadata
-            let layout_variant = layout.metadata.layout_variant.as_ref()
-                .ok_or_else(|| anyhow::anyhow!("Layout variant not specified in layout metadata - layout may be from an older version"))?;
-
-            // Try to build geometry using the service
-            let geo_context = services::geometry::GeometryContext {
-                config: &config,
-                metadata: &layout.metadata,
-            };
-
-            match services::geometry::build_geometry_for_layout(geo_context, layout_variant) {
-                Ok(geo_result) => (geo_result.geometry, geo_result.mapping),
-                Err(_) => {
-                    // Fall back to minimal geometry on error
-                    let geo_result = services::geometry::build_minimal_geometry();
-                    (geo_result.geometry, geo_result.mapping)
-                }
-            }
-        } else {
-            // No QMK path configured, use minimal geometry
-            let geo_result = services::geometry::build_minimal_geometry();
-            (geo_result.geometry, geo_result.mapping)
+        let options = app::launch::EditOptions {
+            assume_variant: args.assume_variant,
+            profile_startup: args.profile_startup,
+            layer: args.layer,
+            position: args.position,
+            read_only: args.read_only,
+            qmk_path: args.qmk_path,
+            skip_env_check: args.skip_env_check,
         };
-
-        // Initialize TUI
-        let mut terminal = tui::setup_terminal()?;
-        let mut app_state = tui::AppState::new(layout, Some(path), geometry, mapping, config)?;
-
-        // Adjust layers to match geometry (ensures keys match visual positions)
-        app_state.adjust_layers_to_geometry()?;
-
-        // Run main TUI loop
-        let result = tui::run_tui(&mut app_state, &mut terminal);
-
-        // Restore terminal
-        tui::restore_terminal(terminal)?;
-
-        // Check for errors
-        result?;
+        app::launch::open_layout_file(path, &options)?;
     } else {
         // No file argument provided - check if config exists and is properly configured
         if !config::Config::exists() {
@@ -449,7 +608,7 @@ fn run_web_server(args: WebArgs) -> Result<()> {
     let workspace_root = match args.workspace {
         Some(path) => path,
         None => {
-            let layouts_dir = config::Config::config_dir()?.join("layouts");
+            let layouts_dir = paths::layouts_dir()?;
             if !layouts_dir.exists() {
                 std::fs::create_dir_all(&layouts_dir).context(format!(
                     "Failed to create layouts directory: {}",
@@ -472,10 +631,13 @@ fn run_web_server(args: WebArgs) -> Result<()> {
         .parse()
         .context("Failed to parse socket address")?;
 
+    let auth =
+        lazyqmk::web::auth::AuthOptions::from_args(args.auth_token, args.allow_unauthenticated);
+
     // Use tokio runtime to run the async web server
     let runtime = tokio::runtime::Runtime::new()?;
     runtime.block_on(async move {
         // Import web module from library
-        lazyqmk::web::run_server(cfg, workspace_root, addr).await
+        lazyqmk::web::run_server(cfg, workspace_root, addr, auth, args.redact_logs).await
     })
 }
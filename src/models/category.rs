@@ -23,6 +23,47 @@ pub struct Category {
     pub name: String,
     /// RGB color for visual identification
     pub color: RgbColor,
+    /// Keys stay lit through the RGB timeout
+    #[serde(default)]
+    pub always_on: bool,
+    /// Keys pulse in firmware instead of showing a steady color
+    #[serde(default)]
+    pub breathing: bool,
+}
+
+/// Firmware RGB behavior that can be set on a category.
+///
+/// Flags apply to the category's keys and to every key of a layer assigned
+/// to it, and are generated into `rgb_matrix_indicators_advanced_user`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum CategoryFlag {
+    /// Stay lit through the RGB timeout
+    AlwaysOn,
+    /// Pulse instead of a steady color
+    Breathing,
+}
+
+impl CategoryFlag {
+    /// All flags, in the order they are written to markdown.
+    #[must_use]
+    pub const fn all() -> &'static [Self] {
+        &[Self::AlwaysOn, Self::Breathing]
+    }
+
+    /// Name used in the markdown `## Categories` section.
+    #[must_use]
+    pub const fn name(self) -> &'static str {
+        match self {
+            Self::AlwaysOn => "always-on",
+            Self::Breathing => "breathing",
+        }
+    }
+
+    /// Parses a flag from its markdown name.
+    #[must_use]
+    pub fn from_name(name: &str) -> Option<Self> {
+        Self::all().iter().copied().find(|flag| flag.name() == name)
+    }
 }
 
 impl Category {
@@ -52,7 +93,38 @@ impl Category {
         Self::validate_id(&id)?;
         Self::validate_name(&name)?;
 
-        Ok(Self { id, name, color })
+        Ok(Self {
+            id,
+            name,
+            color,
+            always_on: false,
+            breathing: false,
+        })
+    }
+
+    /// Returns true if the flag is set.
+    #[must_use]
+    pub const fn has_flag(&self, flag: CategoryFlag) -> bool {
+        match flag {
+            CategoryFlag::AlwaysOn => self.always_on,
+            CategoryFlag::Breathing => self.breathing,
+        }
+    }
+
+    /// Turns a flag on or off.
+    pub const fn set_flag(&mut self, flag: CategoryFlag, on: bool) {
+        match flag {
+            CategoryFlag::AlwaysOn => self.always_on = on,
+            CategoryFlag::Breathing => self.breathing = on,
+        }
+    }
+
+    /// Flags that are set, in markdown order.
+    pub fn flags(&self) -> impl Iterator<Item = CategoryFlag> + '_ {
+        CategoryFlag::all()
+            .iter()
+            .copied()
+            .filter(|flag| self.has_flag(*flag))
     }
 
     /// Validates category ID format (kebab-case).
@@ -171,4 +243,27 @@ mod tests {
         assert!(category.set_name("").is_err());
         assert!(category.set_name("a".repeat(51)).is_err());
     }
+
+    #[test]
+    fn test_flags() {
+        let mut category = Category::new("nav", "Nav", RgbColor::new(0, 255, 0)).unwrap();
+        assert_eq!(category.flags().count(), 0);
+
+        category.set_flag(CategoryFlag::Breathing, true);
+        category.set_flag(CategoryFlag::AlwaysOn, true);
+        assert_eq!(
+            category.flags().collect::<Vec<_>>(),
+            vec![CategoryFlag::AlwaysOn, CategoryFlag::Breathing]
+        );
+        assert_eq!(
+            CategoryFlag::from_name("always-on"),
+            Some(CategoryFlag::AlwaysOn)
+        );
+        assert_eq!(CategoryFlag::from_name("blink"), None);
+
+        // Layouts saved before the flags existed still load
+        let json = r#"{"id":"nav","name":"Nav","color":{"r":0,"g":255,"b":0}}"#;
+        let old: Category = serde_json::from_str(json).unwrap();
+        assert!(!old.always_on && !old.breathing);
+    }
 }
@@ -1,12 +1,110 @@
 //! Color palette data structures for the enhanced color picker.
 //!
 //! This module provides a curated color palette based on Tailwind CSS colors,
-//! with 12 base colors and 9 shades each.
+//! with 12 base colors and 9 shades each, plus colorblind-safe alternatives
+//! (see [`PaletteKind`]).
 
 use serde::Deserialize;
+use std::fmt;
+use std::str::FromStr;
 
 use super::RgbColor;
 
+/// Shade levels generated for the built-in colorblind-safe palettes.
+const SHADE_LEVELS: [u16; 9] = [50, 100, 200, 300, 400, 500, 600, 700, 800];
+
+/// Okabe-Ito palette: colors distinguishable with every common color vision
+/// deficiency. Its black is left out, since a black LED is just an unlit key.
+const OKABE_ITO: [(&str, RgbColor); 7] = [
+    ("Orange", RgbColor::new(0xE6, 0x9F, 0x00)),
+    ("Sky Blue", RgbColor::new(0x56, 0xB4, 0xE9)),
+    ("Bluish Green", RgbColor::new(0x00, 0x9E, 0x73)),
+    ("Yellow", RgbColor::new(0xF0, 0xE4, 0x42)),
+    ("Blue", RgbColor::new(0x00, 0x72, 0xB2)),
+    ("Vermillion", RgbColor::new(0xD5, 0x5E, 0x00)),
+    ("Reddish Purple", RgbColor::new(0xCC, 0x79, 0xA7)),
+];
+
+/// Eight evenly spaced samples of the viridis colormap. Lightness increases
+/// monotonically, so the colors stay ordered even without color vision.
+const VIRIDIS: [(&str, RgbColor); 8] = [
+    ("Viridis 1", RgbColor::new(0x44, 0x01, 0x54)),
+    ("Viridis 2", RgbColor::new(0x46, 0x32, 0x7E)),
+    ("Viridis 3", RgbColor::new(0x36, 0x5C, 0x8D)),
+    ("Viridis 4", RgbColor::new(0x27, 0x7F, 0x8E)),
+    ("Viridis 5", RgbColor::new(0x1F, 0xA1, 0x87)),
+    ("Viridis 6", RgbColor::new(0x4A, 0xC1, 0x6D)),
+    ("Viridis 7", RgbColor::new(0xA0, 0xDA, 0x39)),
+    ("Viridis 8", RgbColor::new(0xFD, 0xE7, 0x25)),
+];
+
+/// Built-in palettes selectable in the color picker and `category colorize`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum PaletteKind {
+    /// Tailwind CSS colors (12 hues)
+    #[default]
+    Tailwind,
+    /// Okabe-Ito colorblind-safe palette
+    OkabeIto,
+    /// Viridis-derived colorblind-safe ramp
+    Viridis,
+}
+
+impl PaletteKind {
+    /// All palettes in cycling order.
+    pub const ALL: [Self; 3] = [Self::Tailwind, Self::OkabeIto, Self::Viridis];
+
+    /// Identifier used on the command line (e.g., "okabe-ito").
+    #[must_use]
+    pub const fn id(self) -> &'static str {
+        match self {
+            Self::Tailwind => "tailwind",
+            Self::OkabeIto => "okabe-ito",
+            Self::Viridis => "viridis",
+        }
+    }
+
+    /// Display name for the UI.
+    #[must_use]
+    pub const fn label(self) -> &'static str {
+        match self {
+            Self::Tailwind => "Tailwind",
+            Self::OkabeIto => "Okabe-Ito (colorblind-safe)",
+            Self::Viridis => "Viridis (colorblind-safe)",
+        }
+    }
+
+    /// The palette after this one, wrapping around.
+    #[must_use]
+    pub const fn next(self) -> Self {
+        match self {
+            Self::Tailwind => Self::OkabeIto,
+            Self::OkabeIto => Self::Viridis,
+            Self::Viridis => Self::Tailwind,
+        }
+    }
+}
+
+impl fmt::Display for PaletteKind {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(self.id())
+    }
+}
+
+impl FromStr for PaletteKind {
+    type Err = anyhow::Error;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        Self::ALL
+            .into_iter()
+            .find(|kind| kind.id().eq_ignore_ascii_case(s.trim()))
+            .ok_or_else(|| {
+                let ids: Vec<&str> = Self::ALL.iter().map(|kind| kind.id()).collect();
+                anyhow::anyhow!("Unknown palette '{s}'. Available: {}", ids.join(", "))
+            })
+    }
+}
+
 /// A complete color palette with multiple base colors.
 #[derive(Debug, Clone, Deserialize)]
 pub struct ColorPalette {
@@ -47,6 +145,31 @@ impl Shade {
 }
 
 impl PaletteColor {
+    /// Builds a color with nine shades around `base` (the 500 shade).
+    fn from_base(name: &str, base: RgbColor) -> Self {
+        let white = RgbColor::new(255, 255, 255);
+        let black = RgbColor::new(0, 0, 0);
+        let shades = SHADE_LEVELS
+            .iter()
+            .zip([90, 80, 60, 40, 20, 0, 20, 40, 60])
+            .map(|(&level, amount)| {
+                let towards = if level < 500 { white } else { black };
+                let rgb = mix(base, towards, amount);
+                Shade {
+                    level,
+                    hex: rgb.to_hex(),
+                    r: rgb.r,
+                    g: rgb.g,
+                    b: rgb.b,
+                }
+            })
+            .collect();
+        Self {
+            name: name.to_string(),
+            shades,
+        }
+    }
+
     /// Get the "primary" shade (500 level, or middle shade).
     #[must_use]
     pub fn primary_shade(&self) -> Option<&Shade> {
@@ -80,6 +203,36 @@ impl ColorPalette {
         Ok(palette)
     }
 
+    /// Returns the built-in palette of the given kind.
+    ///
+    /// The colorblind-safe palettes are generated from their base colors;
+    /// the 500 shade is the base color itself, lighter and darker shades are
+    /// mixed towards white and black.
+    #[must_use]
+    pub fn for_kind(kind: PaletteKind) -> Self {
+        let bases: &[(&str, RgbColor)] = match kind {
+            PaletteKind::Tailwind => return Self::default(),
+            PaletteKind::OkabeIto => &OKABE_ITO,
+            PaletteKind::Viridis => &VIRIDIS,
+        };
+        Self {
+            colors: bases
+                .iter()
+                .map(|&(name, base)| PaletteColor::from_base(name, base))
+                .collect(),
+        }
+    }
+
+    /// Primary (500) shade of every color, in palette order.
+    #[must_use]
+    pub fn primary_colors(&self) -> Vec<RgbColor> {
+        self.colors
+            .iter()
+            .filter_map(PaletteColor::primary_shade)
+            .map(Shade::to_rgb)
+            .collect()
+    }
+
     /// Get a color by name (case-insensitive).
     #[must_use]
     pub fn get_color(&self, name: &str) -> Option<&PaletteColor> {
@@ -148,6 +301,19 @@ impl Default for ColorPalette {
     }
 }
 
+/// Mixes `percent`% of `towards` into `base`.
+fn mix(base: RgbColor, towards: RgbColor, percent: u16) -> RgbColor {
+    let channel = |a: u8, b: u8| {
+        let mixed = (u16::from(a) * (100 - percent) + u16::from(b) * percent) / 100;
+        u8::try_from(mixed).unwrap_or(u8::MAX)
+    };
+    RgbColor::new(
+        channel(base.r, towards.r),
+        channel(base.g, towards.g),
+        channel(base.b, towards.b),
+    )
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -235,4 +401,50 @@ mod tests {
         assert_eq!(default.g, 114);
         assert_eq!(default.b, 128);
     }
+
+    #[test]
+    fn test_palette_kind_parse_and_cycle() {
+        assert_eq!(
+            "okabe-ito".parse::<PaletteKind>().unwrap(),
+            PaletteKind::OkabeIto
+        );
+        assert_eq!(
+            "Viridis".parse::<PaletteKind>().unwrap(),
+            PaletteKind::Viridis
+        );
+        assert!("rainbow".parse::<PaletteKind>().is_err());
+
+        let mut kind = PaletteKind::default();
+        for _ in 0..PaletteKind::ALL.len() {
+            kind = kind.next();
+        }
+        assert_eq!(kind, PaletteKind::Tailwind);
+    }
+
+    #[test]
+    fn test_colorblind_palettes_have_shades_around_base() {
+        for kind in [PaletteKind::OkabeIto, PaletteKind::Viridis] {
+            let palette = ColorPalette::for_kind(kind);
+            assert!(palette.color_count() >= 7);
+            for color in &palette.colors {
+                assert_eq!(color.shade_count(), 9);
+                let shades: Vec<RgbColor> = color.shades.iter().map(Shade::to_rgb).collect();
+                // Lightest to darkest
+                assert!(shades[0].relative_luminance() > shades[8].relative_luminance());
+            }
+        }
+
+        let okabe = ColorPalette::for_kind(PaletteKind::OkabeIto);
+        assert_eq!(okabe.primary_colors()[0], RgbColor::new(0xE6, 0x9F, 0x00));
+    }
+
+    #[test]
+    fn test_okabe_ito_is_colorblind_distinguishable() {
+        let colors = ColorPalette::for_kind(PaletteKind::OkabeIto).primary_colors();
+        for (i, a) in colors.iter().enumerate() {
+            for b in &colors[i + 1..] {
+                assert!(a.colorblind_distance(b) > 10.0, "{a} and {b} are too close");
+            }
+        }
+    }
 }
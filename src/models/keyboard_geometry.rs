@@ -5,6 +5,9 @@
 #![allow(clippy::cast_sign_loss)]
 
 use serde::{Deserialize, Serialize};
+use std::collections::{BTreeMap, HashSet};
+
+use crate::models::layer::Position;
 
 /// Terminal rendering scale factors.
 ///
@@ -21,6 +24,62 @@ pub mod terminal_scale {
     pub const MIN_HEIGHT: u16 = 3;
 }
 
+/// What a key is pressed with, inferred from the geometry or set by the user.
+///
+/// A key can have several roles: the outer home-row key is both a home-row
+/// and a pinky key.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[serde(default)]
+pub struct KeyRole {
+    /// Pressed by a thumb
+    pub thumb: bool,
+    /// On the home row
+    pub home_row: bool,
+    /// In a pinky column
+    pub pinky: bool,
+}
+
+impl KeyRole {
+    /// Returns true if the key has no role.
+    #[must_use]
+    pub const fn is_none(self) -> bool {
+        !self.thumb && !self.home_row && !self.pinky
+    }
+
+    /// Short label for the key overlay, e.g. "HP" for a home-row pinky key.
+    #[must_use]
+    pub fn short_label(self) -> String {
+        if self.is_none() {
+            return "-".to_string();
+        }
+        let mut label = String::new();
+        for (set, letter) in [(self.thumb, 'T'), (self.home_row, 'H'), (self.pinky, 'P')] {
+            if set {
+                label.push(letter);
+            }
+        }
+        label
+    }
+}
+
+impl std::fmt::Display for KeyRole {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let names: Vec<&str> = [
+            (self.thumb, "thumb"),
+            (self.home_row, "home row"),
+            (self.pinky, "pinky"),
+        ]
+        .into_iter()
+        .filter_map(|(set, name)| set.then_some(name))
+        .collect();
+        if names.is_empty() {
+            write!(f, "none")
+        } else {
+            write!(f, "{}", names.join(", "))
+        }
+    }
+}
+
 /// Individual key's physical properties from QMK layout definition.
 ///
 /// # Coordinate Conversion (to terminal)
@@ -48,8 +107,15 @@ pub struct KeyGeometry {
     pub width: f32,
     /// Key height in keyboard units (default 1.0)
     pub height: f32,
-    /// Rotation in degrees (default 0.0, future use)
+    /// Clockwise rotation in degrees (default 0.0)
     pub rotation: f32,
+    /// Point the key is rotated around, in keyboard units; the key's own
+    /// center when unset
+    #[serde(default)]
+    pub rotation_origin: Option<(f32, f32)>,
+    /// Role of the key (thumb, home row, pinky)
+    #[serde(default)]
+    pub role: KeyRole,
 }
 
 #[allow(dead_code)]
@@ -71,6 +137,12 @@ impl KeyGeometry {
             width: 1.0,
             height: 1.0,
             rotation: 0.0,
+            rotation_origin: None,
+            role: KeyRole {
+                thumb: false,
+                home_row: false,
+                pinky: false,
+            },
         }
     }
 
@@ -95,6 +167,61 @@ impl KeyGeometry {
         self
     }
 
+    /// Sets the point the key is rotated around.
+    #[must_use]
+    pub const fn with_rotation_origin(mut self, x: f32, y: f32) -> Self {
+        self.rotation_origin = Some((x, y));
+        self
+    }
+
+    /// Returns true if the key is drawn rotated.
+    #[must_use]
+    pub fn is_rotated(&self) -> bool {
+        self.rotation.abs() > f32::EPSILON
+    }
+
+    /// Center of the key after rotation, in keyboard units.
+    #[must_use]
+    pub fn center(&self) -> (f32, f32) {
+        let (cx, cy) = (
+            self.visual_x + self.width / 2.0,
+            self.visual_y + self.height / 2.0,
+        );
+        let Some((ox, oy)) = self.rotation_origin.filter(|_| self.is_rotated()) else {
+            return (cx, cy);
+        };
+        let (sin, cos) = self.rotation.to_radians().sin_cos();
+        let (dx, dy) = (cx - ox, cy - oy);
+        (
+            dx.mul_add(cos, -dy * sin) + ox,
+            dx.mul_add(sin, dy * cos) + oy,
+        )
+    }
+
+    /// Axis-aligned bounding box of the rotated key as (x, y, width, height)
+    /// in keyboard units.
+    #[must_use]
+    pub fn bounding_box(&self) -> (f32, f32, f32, f32) {
+        let (cx, cy) = self.center();
+        let (sin, cos) = self.rotation.to_radians().sin_cos();
+        let width = self.width.mul_add(cos.abs(), self.height * sin.abs());
+        let height = self.width.mul_add(sin.abs(), self.height * cos.abs());
+        (cx - width / 2.0, cy - height / 2.0, width, height)
+    }
+
+    /// Visual grid position: the unrotated key moved onto its rotated center,
+    /// rounded to the grid.
+    #[must_use]
+    pub fn visual_position(&self) -> Position {
+        let (x, y) = if self.is_rotated() {
+            let (cx, cy) = self.center();
+            (cx - self.width / 2.0, cy - self.height / 2.0)
+        } else {
+            (self.visual_x, self.visual_y)
+        };
+        Position::new(y.round() as u8, x.round() as u8)
+    }
+
     /// Converts visual X position to terminal characters.
     /// Uses the default X scale factor.
     #[must_use]
@@ -171,6 +298,10 @@ pub struct KeyboardGeometry {
     /// Number of rotary encoders (0 if no encoders)
     #[serde(default)]
     pub encoder_count: u8,
+    /// Matrix position of each encoder's push button by encoder index, where
+    /// info.json declares one
+    #[serde(default)]
+    pub encoder_buttons: BTreeMap<u8, (u8, u8)>,
 }
 
 #[allow(dead_code)]
@@ -189,6 +320,7 @@ impl KeyboardGeometry {
             matrix_cols,
             keys: Vec::new(),
             encoder_count: 0,
+            encoder_buttons: BTreeMap::new(),
         }
     }
 
@@ -215,6 +347,23 @@ impl KeyboardGeometry {
         self.keys.iter().find(|k| k.matrix_position == position)
     }
 
+    /// Visual grid positions of the encoders, by encoder index.
+    ///
+    /// See [`place_encoders`]; an encoder is anchored on its push button when
+    /// info.json declares one.
+    #[must_use]
+    pub fn encoder_positions(&self) -> Vec<Position> {
+        let keys: Vec<Position> = self.keys.iter().map(KeyGeometry::visual_position).collect();
+        let anchors: Vec<Option<Position>> = (0..self.encoder_count)
+            .map(|index| {
+                let button = self.encoder_buttons.get(&index)?;
+                self.get_key_by_matrix(*button)
+                    .map(KeyGeometry::visual_position)
+            })
+            .collect();
+        place_encoders(&keys, &anchors)
+    }
+
     /// Checks if the keyboard has RGB matrix LEDs.
     ///
     /// Returns true if there are any keys defined (RGB LED count matches key count).
@@ -224,6 +373,31 @@ impl KeyboardGeometry {
     }
 }
 
+/// Places encoders on free cells of the visual grid, one per anchor.
+///
+/// Each encoder goes into the first free cell right of its anchor in the
+/// anchor's row. Encoders without an anchor are appended after the last key,
+/// or after the previous encoder, so they line up at the end of the bottom row.
+#[must_use]
+pub fn place_encoders(keys: &[Position], anchors: &[Option<Position>]) -> Vec<Position> {
+    let mut taken: HashSet<Position> = keys.iter().copied().collect();
+    let mut previous = keys.iter().copied().max_by_key(|pos| (pos.row, pos.col));
+    let mut positions = Vec::with_capacity(anchors.len());
+
+    for anchor in anchors {
+        let anchor = anchor.or(previous);
+        let (row, mut col) = anchor.map_or((0, 0), |pos| (pos.row, pos.col.saturating_add(1)));
+        while taken.contains(&Position::new(row, col)) && col < u8::MAX {
+            col += 1;
+        }
+        let position = Position::new(row, col);
+        taken.insert(position);
+        positions.push(position);
+        previous = Some(position);
+    }
+    positions
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -254,6 +428,28 @@ mod tests {
         assert_eq!(key.rotation, 15.0);
     }
 
+    #[test]
+    fn test_rotated_key_center_and_bounding_box() {
+        // A 1u key turned 90 degrees clockwise around its bottom-left corner
+        // ends up below and right of that corner
+        let key = KeyGeometry::new((0, 0), 0, 4.0, 3.0)
+            .with_rotation(90.0)
+            .with_rotation_origin(4.0, 4.0);
+        let (cx, cy) = key.center();
+        assert!((cx - 4.5).abs() < 1e-4 && (cy - 4.5).abs() < 1e-4);
+        assert_eq!(key.visual_position(), Position::new(4, 4));
+
+        // Without an origin the key turns in place; at 45 degrees its
+        // bounding box grows to the diagonal
+        let key = KeyGeometry::new((0, 0), 0, 2.0, 1.0).with_rotation(45.0);
+        assert_eq!(key.center(), (2.5, 1.5));
+        assert_eq!(key.visual_position(), Position::new(1, 2));
+        let (x, y, w, h) = key.bounding_box();
+        assert!((w - std::f32::consts::SQRT_2).abs() < 1e-4);
+        assert!((h - std::f32::consts::SQRT_2).abs() < 1e-4);
+        assert!((x + w / 2.0 - 2.5).abs() < 1e-4 && (y + h / 2.0 - 1.5).abs() < 1e-4);
+    }
+
     #[test]
     fn test_key_geometry_terminal_conversion() {
         let key = KeyGeometry::new((0, 0), 0, 2.0, 1.0);
@@ -286,4 +482,28 @@ mod tests {
         assert!(geom.get_key_by_matrix((0, 0)).is_some());
         assert!(geom.get_key_by_matrix((0, 1)).is_some());
     }
+
+    #[test]
+    fn test_encoder_positions_follow_buttons_or_last_key() {
+        let mut geom = KeyboardGeometry::new("test", "LAYOUT", 2, 3);
+        for (row, col) in [(0, 0), (0, 1), (0, 2), (1, 0), (1, 1)] {
+            geom.add_key(KeyGeometry::new(
+                (row, col),
+                row * 3 + col,
+                f32::from(col),
+                f32::from(row),
+            ));
+        }
+        geom.encoder_count = 3;
+        geom.encoder_buttons.insert(1, (0, 0));
+
+        assert_eq!(
+            geom.encoder_positions(),
+            vec![
+                Position::new(1, 2),
+                Position::new(0, 3),
+                Position::new(0, 4),
+            ]
+        );
+    }
 }
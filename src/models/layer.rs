@@ -1,7 +1,8 @@
 //! Layer and key definition data structures.
 
 use crate::models::RgbColor;
-use anyhow::Result;
+use anyhow::{Context, Result};
+use chrono::{DateTime, Utc};
 use serde::{Deserialize, Serialize};
 use uuid::Uuid;
 
@@ -80,6 +81,26 @@ impl Position {
     }
 }
 
+impl std::str::FromStr for Position {
+    type Err = anyhow::Error;
+
+    /// Parses a `row,col` position such as `"2,5"`.
+    fn from_str(text: &str) -> Result<Self> {
+        let (row, col) = text
+            .split_once(',')
+            .with_context(|| format!("Invalid position '{text}', expected 'row,col'"))?;
+        let row = row
+            .trim()
+            .parse()
+            .with_context(|| format!("Invalid row in position '{text}'"))?;
+        let col = col
+            .trim()
+            .parse()
+            .with_context(|| format!("Invalid column in position '{text}'"))?;
+        Ok(Self::new(row, col))
+    }
+}
+
 /// Individual key assignment with position, keycode, and optional overrides.
 ///
 /// # Validation
@@ -123,6 +144,50 @@ impl KeyDefinition {
         }
     }
 
+    /// Starts building a transparent key at `position`.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use lazyqmk::models::{KeyDefinition, Position, RgbColor};
+    ///
+    /// let key = KeyDefinition::at(Position::new(0, 1))
+    ///     .keycode("KC_A")
+    ///     .color(RgbColor::new(255, 0, 0))
+    ///     .category("nav");
+    /// assert_eq!(key.keycode, "KC_A");
+    /// assert_eq!(key.category_id.as_deref(), Some("nav"));
+    /// ```
+    pub fn at(position: Position) -> Self {
+        Self::new(position, "KC_TRNS")
+    }
+
+    /// Sets the keycode.
+    pub fn keycode(mut self, keycode: impl Into<String>) -> Self {
+        self.keycode = keycode.into();
+        self
+    }
+
+    /// Sets the color override.
+    pub const fn color(self, color: RgbColor) -> Self {
+        self.with_color(color)
+    }
+
+    /// Sets the category.
+    pub fn category(self, category_id: impl Into<String>) -> Self {
+        self.with_category(category_id)
+    }
+
+    /// Sets the display label.
+    pub fn label(self, label: impl Into<String>) -> Self {
+        self.with_label(label)
+    }
+
+    /// Sets the description.
+    pub fn description(self, description: impl Into<String>) -> Self {
+        self.with_description(description)
+    }
+
     /// Sets the color override for this key.
     #[must_use]
     pub const fn with_color(mut self, color: RgbColor) -> Self {
@@ -161,6 +226,44 @@ impl KeyDefinition {
     }
 }
 
+/// Keycodes a rotary encoder sends on one layer.
+///
+/// Encoder turns are sent as taps, so only basic keycodes make sense here.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct EncoderMapping {
+    /// Keycode for a counter-clockwise turn
+    pub ccw: String,
+    /// Keycode for a clockwise turn
+    pub cw: String,
+}
+
+impl EncoderMapping {
+    /// Creates a mapping from the counter-clockwise and clockwise keycodes.
+    pub fn new(ccw: impl Into<String>, cw: impl Into<String>) -> Self {
+        Self {
+            ccw: ccw.into(),
+            cw: cw.into(),
+        }
+    }
+
+    /// Mapping used for an encoder the layer does not assign.
+    ///
+    /// Cycles through RGB effect, hue, brightness and saturation, with volume
+    /// for any further encoders.
+    #[must_use]
+    pub fn fallback(index: usize) -> Self {
+        const FALLBACKS: [(&str, &str); 5] = [
+            ("RM_NEXT", "RM_PREV"), // Encoder 0: RGB effect
+            ("RM_HUEU", "RM_HUED"), // Encoder 1: RGB hue
+            ("RM_VALU", "RM_VALD"), // Encoder 2: RGB brightness
+            ("RM_SATU", "RM_SATD"), // Encoder 3: RGB saturation
+            ("KC_VOLU", "KC_VOLD"), // Encoder 4+: Volume
+        ];
+        let (ccw, cw) = FALLBACKS[index.min(FALLBACKS.len() - 1)];
+        Self::new(ccw, cw)
+    }
+}
+
 /// A single layer of the keyboard with color and key assignments.
 ///
 /// # Validation
@@ -183,13 +286,29 @@ pub struct Layer {
     pub default_color: RgbColor,
     /// Optional category assignment for entire layer
     pub category_id: Option<String>,
+    /// Optional notes on the layer's purpose (e.g., "mirrors macOS shortcuts")
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub description: Option<String>,
     /// Key assignments for all positions (fixed size per layout)
     pub keys: Vec<KeyDefinition>,
+    /// Whether the layer is compiled into firmware (default: true)
+    /// Disabled layers stay in the layout file but are skipped by the
+    /// firmware generator.
+    #[serde(default = "default_layer_enabled")]
+    pub enabled: bool,
     /// Whether layer-level RGB colors are enabled (default: true)
     /// When false, layer default color and layer category color are disabled,
     /// but individual key colors and key category colors still work.
     #[serde(default = "default_layer_colors_enabled")]
     pub layer_colors_enabled: bool,
+    /// Rotary encoder assignments by encoder index; encoders past the end
+    /// use [`EncoderMapping::fallback`]
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub encoders: Vec<EncoderMapping>,
+    /// When a key or property of this layer last changed; `None` for layers
+    /// from files written before per-layer tracking
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub modified: Option<DateTime<Utc>>,
 }
 
 /// Generates a new unique layer ID
@@ -197,6 +316,11 @@ fn generate_layer_id() -> String {
     Uuid::new_v4().to_string()
 }
 
+/// Default value for `enabled` (true)
+const fn default_layer_enabled() -> bool {
+    true
+}
+
 /// Default value for `layer_colors_enabled` (true)
 const fn default_layer_colors_enabled() -> bool {
     true
@@ -235,11 +359,40 @@ impl Layer {
             name,
             default_color,
             category_id: None,
+            description: None,
             keys: Vec::new(),
+            enabled: true,
             layer_colors_enabled: true,
+            encoders: Vec::new(),
+            modified: Some(Utc::now()),
+        })
+    }
+
+    /// Copies this layer's keys, encoders and settings into a new layer
+    /// `number` named `name`, with its own ID.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the name or number is invalid.
+    pub fn duplicate(&self, number: u8, name: impl Into<String>) -> Result<Self> {
+        let copy = Self::new(number, name, self.default_color)?;
+        Ok(Self {
+            id: copy.id,
+            number: copy.number,
+            name: copy.name,
+            modified: copy.modified,
+            ..self.clone()
         })
     }
 
+    /// Compares keys and properties, ignoring the `modified` timestamp.
+    #[must_use]
+    pub fn content_eq(&self, other: &Self) -> bool {
+        let mut this = self.clone();
+        this.modified = other.modified;
+        this == *other
+    }
+
     /// Validates layer name.
     fn validate_name(name: &str) -> Result<()> {
         if name.is_empty() {
@@ -258,8 +411,21 @@ impl Layer {
     }
 
     /// Adds a key definition to this layer.
-    pub fn add_key(&mut self, key: KeyDefinition) {
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the layer already has a key at the same position.
+    pub fn add_key(&mut self, key: KeyDefinition) -> Result<()> {
+        if self.get_key(key.position).is_some() {
+            anyhow::bail!(
+                "Duplicate position ({}, {}) in layer {}",
+                key.position.row,
+                key.position.col,
+                self.number
+            );
+        }
         self.keys.push(key);
+        Ok(())
     }
 
     /// Gets a reference to the key at the given position.
@@ -273,6 +439,29 @@ impl Layer {
         self.keys.iter_mut().find(|k| k.position == position)
     }
 
+    /// Keycodes of encoder `index` on this layer.
+    #[must_use]
+    pub fn encoder(&self, index: usize) -> EncoderMapping {
+        self.encoders
+            .get(index)
+            .cloned()
+            .unwrap_or_else(|| EncoderMapping::fallback(index))
+    }
+
+    /// Assigns encoder `index`, giving lower unassigned encoders their
+    /// fallback mapping.
+    pub fn set_encoder(&mut self, index: usize, mapping: EncoderMapping) {
+        while self.encoders.len() < index {
+            self.encoders
+                .push(EncoderMapping::fallback(self.encoders.len()));
+        }
+        if let Some(slot) = self.encoders.get_mut(index) {
+            *slot = mapping;
+        } else {
+            self.encoders.push(mapping);
+        }
+    }
+
     /// Sets the category for this layer.
     pub fn set_category(&mut self, category_id: Option<String>) {
         self.category_id = category_id;
@@ -291,6 +480,11 @@ impl Layer {
         Ok(())
     }
 
+    /// Toggles whether the layer is compiled into firmware.
+    pub const fn toggle_enabled(&mut self) {
+        self.enabled = !self.enabled;
+    }
+
     /// Toggles layer-level RGB colors on/off.
     pub const fn toggle_layer_colors(&mut self) {
         self.layer_colors_enabled = !self.layer_colors_enabled;
@@ -306,6 +500,15 @@ impl Layer {
 mod tests {
     use super::*;
 
+    #[test]
+    fn test_position_from_str() {
+        assert_eq!("2,5".parse::<Position>().unwrap(), Position::new(2, 5));
+        assert_eq!(" 1 , 0 ".parse::<Position>().unwrap(), Position::new(1, 0));
+        assert!("2".parse::<Position>().is_err());
+        assert!("a,1".parse::<Position>().is_err());
+        assert!("1,300".parse::<Position>().is_err());
+    }
+
     #[test]
     fn test_key_definition_new() {
         let pos = Position::new(0, 0);
@@ -366,6 +569,18 @@ mod tests {
         assert!(layer.keys.is_empty());
     }
 
+    #[test]
+    fn test_layer_set_encoder_fills_gaps() {
+        let mut layer = Layer::new(0, "Base", RgbColor::new(255, 0, 0)).unwrap();
+        assert_eq!(layer.encoder(1), EncoderMapping::fallback(1));
+
+        layer.set_encoder(1, EncoderMapping::new("KC_VOLD", "KC_VOLU"));
+        assert_eq!(layer.encoders.len(), 2);
+        assert_eq!(layer.encoder(0), EncoderMapping::fallback(0));
+        assert_eq!(layer.encoder(1).cw, "KC_VOLU");
+        assert_eq!(EncoderMapping::fallback(9).ccw, "KC_VOLU");
+    }
+
     #[test]
     fn test_layer_validate_name() {
         let color = RgbColor::new(255, 0, 0);
@@ -382,18 +597,56 @@ mod tests {
         let pos = Position::new(0, 0);
         let key = KeyDefinition::new(pos, "KC_A");
 
-        layer.add_key(key.clone());
+        layer.add_key(key.clone()).unwrap();
         let retrieved = layer.get_key(pos).unwrap();
         assert_eq!(retrieved, &key);
     }
 
+    #[test]
+    fn test_layer_add_key_rejects_duplicate_position() {
+        let mut layer = Layer::new(2, "Nav", RgbColor::new(255, 0, 0)).unwrap();
+        layer
+            .add_key(KeyDefinition::at(Position::new(1, 1)).keycode("KC_A"))
+            .unwrap();
+
+        let err = layer
+            .add_key(KeyDefinition::at(Position::new(1, 1)).keycode("KC_B"))
+            .unwrap_err();
+        assert!(err
+            .to_string()
+            .contains("Duplicate position (1, 1) in layer 2"));
+        assert_eq!(layer.keys.len(), 1);
+        assert_eq!(layer.keys[0].keycode, "KC_A");
+    }
+
+    #[test]
+    fn test_key_definition_at_builder() {
+        let red = RgbColor::new(255, 0, 0);
+        let key = KeyDefinition::at(Position::new(2, 3))
+            .keycode("KC_A")
+            .color(red)
+            .category("nav")
+            .label("A")
+            .description("Home row");
+
+        assert_eq!(
+            key,
+            KeyDefinition::new(Position::new(2, 3), "KC_A")
+                .with_color(red)
+                .with_category("nav")
+                .with_label("A")
+                .with_description("Home row")
+        );
+        assert!(KeyDefinition::at(Position::new(0, 0)).is_transparent());
+    }
+
     #[test]
     fn test_layer_get_key_mut() {
         let mut layer = Layer::new(0, "Base", RgbColor::new(255, 0, 0)).unwrap();
         let pos = Position::new(0, 0);
         let key = KeyDefinition::new(pos, "KC_A");
 
-        layer.add_key(key);
+        layer.add_key(key).unwrap();
         {
             let key_mut = layer.get_key_mut(pos).unwrap();
             key_mut.keycode = "KC_B".to_string();
@@ -7,9 +7,7 @@
 #![allow(clippy::cast_possible_truncation)]
 
 use crate::keycode_db::KeycodeDb;
-#[cfg(test)]
-use crate::models::layer::Position;
-use crate::models::layer::{KeyDefinition, Layer};
+use crate::models::layer::{KeyDefinition, Layer, Position};
 use crate::models::{Category, RgbColor};
 use anyhow::Result;
 use chrono::{DateTime, Utc};
@@ -327,6 +325,88 @@ impl IdleEffectSettings {
     }
 }
 
+// ============================================================================
+// Orphaned Keys
+// ============================================================================
+
+/// A key with no position on the layout's keyboard.
+///
+/// Produced when a layout is remapped to a keyboard with fewer or differently
+/// placed keys, so assignments are kept for the user to place by hand.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct OrphanedKey {
+    /// Layer the key belonged to
+    pub layer: usize,
+    /// Position on the previous keyboard
+    pub position: Position,
+    /// Assigned keycode
+    pub keycode: String,
+}
+
+/// How many keys and layers are assigned to a category.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct CategoryUsage {
+    /// Keys assigned to the category, across all layers
+    pub keys: usize,
+    /// Layers assigned to the category
+    pub layers: usize,
+}
+
+/// A deleted category together with every assignment it had.
+///
+/// Returned by [`Layout::delete_category`]; [`Layout::restore_category`]
+/// puts the category and its assignments back.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct DeletedCategory {
+    /// The category as it was
+    pub category: Category,
+    /// Its index in the category list
+    pub index: usize,
+    /// Indices of layers that were assigned to it
+    pub layers: Vec<usize>,
+    /// Layer index and position of each key that was assigned to it
+    pub keys: Vec<(usize, Position)>,
+}
+
+impl DeletedCategory {
+    /// Number of keys and layers the category was assigned to.
+    #[must_use]
+    pub fn usage(&self) -> CategoryUsage {
+        CategoryUsage {
+            keys: self.keys.len(),
+            layers: self.layers.len(),
+        }
+    }
+}
+
+/// Which level of the four-level priority system a key's color comes from.
+///
+/// See [`Layout::resolve_key_color`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ColorSource {
+    /// The key's individual color override
+    KeyOverride,
+    /// The color of the key's category
+    KeyCategory,
+    /// The color of the layer's category
+    LayerCategory,
+    /// The layer's default color
+    LayerDefault,
+}
+
+impl ColorSource {
+    /// Human-readable name of the source.
+    #[must_use]
+    pub const fn label(self) -> &'static str {
+        match self {
+            Self::KeyOverride => "key override",
+            Self::KeyCategory => "key category",
+            Self::LayerCategory => "layer category",
+            Self::LayerDefault => "layer default",
+        }
+    }
+}
+
 // ============================================================================
 // Tap Dance Settings
 // ============================================================================
@@ -348,8 +428,20 @@ pub struct TapDanceAction {
     /// Optional keycode sent on hold (None = no hold action)
     #[serde(skip_serializing_if = "Option::is_none")]
     pub hold: Option<String>,
+    /// Optional tapping term for this dance in milliseconds (None = global term)
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub tapping_term_ms: Option<u16>,
+    /// Send the single-tap keycode immediately on every tap instead of
+    /// waiting for the dance to finish ("tap then hold")
+    #[serde(default, skip_serializing_if = "std::ops::Not::not")]
+    pub on_each_tap: bool,
 }
 
+/// Minimum per-dance tapping term in milliseconds.
+pub const TAP_DANCE_TERM_MIN_MS: u16 = 50;
+/// Maximum per-dance tapping term in milliseconds.
+pub const TAP_DANCE_TERM_MAX_MS: u16 = 2000;
+
 // Keep public API methods for future TUI editor (Phase 3) and firmware generation (Phase 4)
 #[allow(dead_code)]
 impl TapDanceAction {
@@ -362,6 +454,8 @@ impl TapDanceAction {
             single_tap: single_tap.into(),
             double_tap: None,
             hold: None,
+            tapping_term_ms: None,
+            on_each_tap: false,
         }
     }
 
@@ -377,6 +471,18 @@ impl TapDanceAction {
         self
     }
 
+    /// Sets a per-dance tapping term (builder pattern).
+    pub const fn with_tapping_term(mut self, term_ms: u16) -> Self {
+        self.tapping_term_ms = Some(term_ms);
+        self
+    }
+
+    /// Sends the single tap on every tap (builder pattern).
+    pub const fn with_on_each_tap(mut self) -> Self {
+        self.on_each_tap = true;
+        self
+    }
+
     /// Validates the tap dance action.
     ///
     /// Checks:
@@ -384,6 +490,8 @@ impl TapDanceAction {
     /// - Single tap keycode is non-empty
     /// - Double tap keycode (if present) is non-empty
     /// - Hold keycode (if present) is non-empty
+    /// - Tapping term (if present) is within 50-2000ms
+    /// - On-each-tap dances have no double tap
     pub fn validate(&self) -> Result<()> {
         if self.name.is_empty() {
             anyhow::bail!("Tap dance name cannot be empty");
@@ -417,6 +525,25 @@ impl TapDanceAction {
             }
         }
 
+        if let Some(term) = self.tapping_term_ms {
+            if !(TAP_DANCE_TERM_MIN_MS..=TAP_DANCE_TERM_MAX_MS).contains(&term) {
+                anyhow::bail!(
+                    "Tap dance '{}': tapping term must be between {} and {}ms",
+                    self.name,
+                    TAP_DANCE_TERM_MIN_MS,
+                    TAP_DANCE_TERM_MAX_MS
+                );
+            }
+        }
+
+        // The single tap is already sent by the time a second tap lands
+        if self.on_each_tap && self.double_tap.is_some() {
+            anyhow::bail!(
+                "Tap dance '{}': on-each-tap cannot be combined with a double tap",
+                self.name
+            );
+        }
+
         Ok(())
     }
 
@@ -440,6 +567,19 @@ impl TapDanceAction {
     pub const fn has_hold(&self) -> bool {
         self.hold.is_some()
     }
+
+    /// Returns true if per-dance options are set (custom term or on-each-tap).
+    #[must_use]
+    pub const fn has_options(&self) -> bool {
+        self.tapping_term_ms.is_some() || self.on_each_tap
+    }
+
+    /// Returns true if this dance needs custom finished/reset callbacks
+    /// rather than the simple `ACTION_TAP_DANCE_DOUBLE` form.
+    #[must_use]
+    pub const fn needs_advanced_action(&self) -> bool {
+        self.has_hold() || self.has_options()
+    }
 }
 
 // ============================================================================
@@ -774,9 +914,80 @@ pub struct LayoutMetadata {
     /// QMK keymap name (e.g., "my_custom_keymap")
     #[serde(default, skip_serializing_if = "Option::is_none")]
     pub keymap_name: Option<String>,
+    /// Controller converter passed as `CONVERT_TO` (e.g., "rp2040_ce"),
+    /// overriding `build.converter` in the config
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub converter: Option<String>,
     /// Firmware output format: "uf2", "hex", or "bin"
     #[serde(default, skip_serializing_if = "Option::is_none")]
     pub output_format: Option<String>,
+    /// Lint configuration (e.g., rules disabled for this layout)
+    #[serde(default, skip_serializing_if = "LintSettings::is_empty")]
+    pub lint: LintSettings,
+}
+
+/// Per-layout lint configuration stored in the frontmatter.
+///
+/// ```yaml
+/// lint:
+///   disable: ["L003"]
+///   legend_color: "#000000"
+///   min_color_distance: 20
+///   min_led_value: 32
+///   host_layout: german
+///   shifted_form: named
+/// ```
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct LintSettings {
+    /// Rule IDs that are not checked for this layout
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub disable: Vec<String>,
+    /// Legend text color checked against key colors (hex, default white)
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub legend_color: Option<String>,
+    /// Minimum perceptual distance (CIE76 ΔE) between category colors
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub min_color_distance: Option<u8>,
+    /// Lowest LED channel value (0-255) that still reads as lit on the board
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub min_led_value: Option<u8>,
+    /// OS keyboard layout the board types into ("us", "uk", "german")
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub host_layout: Option<String>,
+    /// Preferred spelling of shifted symbols, checked by `L012`
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub shifted_form: Option<ShiftedForm>,
+}
+
+/// How shifted symbols should be written in a layout.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum ShiftedForm {
+    /// Named keycodes such as `KC_LPRN`
+    Named,
+    /// Shift-wrapped base keys such as `S(KC_9)`
+    Modifier,
+}
+
+impl LintSettings {
+    /// Returns true if no lint settings are configured.
+    #[must_use]
+    pub fn is_empty(&self) -> bool {
+        self.disable.is_empty()
+            && self.legend_color.is_none()
+            && self.min_color_distance.is_none()
+            && self.min_led_value.is_none()
+            && self.host_layout.is_none()
+            && self.shifted_form.is_none()
+    }
+
+    /// Returns true if the rule with the given ID is disabled.
+    #[must_use]
+    pub fn is_disabled(&self, rule_id: &str) -> bool {
+        self.disable
+            .iter()
+            .any(|id| id.eq_ignore_ascii_case(rule_id))
+    }
 }
 
 #[allow(dead_code)]
@@ -799,7 +1010,9 @@ impl LayoutMetadata {
             layout_variant: None,
             keyboard: None,
             keymap_name: None,
+            converter: None,
             output_format: None,
+            lint: LintSettings::default(),
         })
     }
 
@@ -932,6 +1145,11 @@ pub struct Layout {
     /// Tap dance action definitions
     #[serde(default, skip_serializing_if = "Vec::is_empty")]
     pub tap_dances: Vec<TapDanceAction>,
+
+    // === Orphaned Keys ===
+    /// Keys without a position on this keyboard (e.g., left over from a remap)
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub orphaned_keys: Vec<OrphanedKey>,
 }
 
 /// Default for rgb_enabled is true
@@ -956,6 +1174,7 @@ impl Layout {
             idle_effect_settings: IdleEffectSettings::default(),
             tap_hold_settings: TapHoldSettings::default(),
             tap_dances: Vec::new(),
+            orphaned_keys: Vec::new(),
         })
     }
 
@@ -980,6 +1199,25 @@ impl Layout {
         Ok(())
     }
 
+    /// Appends a copy of layer `from` named `name` as the next layer,
+    /// returning its index.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if layer `from` doesn't exist, the name is invalid
+    /// or the layout already has the most layers QMK supports.
+    pub fn duplicate_layer(&mut self, from: usize, name: impl Into<String>) -> Result<usize> {
+        let source = self
+            .layers
+            .get(from)
+            .ok_or_else(|| anyhow::anyhow!("Layer {from} does not exist"))?;
+        let number = u8::try_from(self.layers.len())
+            .map_err(|_| anyhow::anyhow!("Too many layers to add another"))?;
+        let copy = source.duplicate(number, name)?;
+        self.add_layer(copy)?;
+        Ok(usize::from(number))
+    }
+
     /// Gets a reference to the layer at the given index.
     #[must_use]
     pub fn get_layer(&self, index: usize) -> Option<&Layer> {
@@ -1026,6 +1264,125 @@ impl Layout {
         }
     }
 
+    /// Counts the keys and layers assigned to the category `id`.
+    #[must_use]
+    pub fn category_usage(&self, id: &str) -> CategoryUsage {
+        let mut usage = CategoryUsage::default();
+        for layer in &self.layers {
+            if layer.category_id.as_deref() == Some(id) {
+                usage.layers += 1;
+            }
+            usage.keys += layer
+                .keys
+                .iter()
+                .filter(|key| key.category_id.as_deref() == Some(id))
+                .count();
+        }
+        usage
+    }
+
+    /// Removes a category and unassigns it from every key and layer,
+    /// recording what was removed so [`Self::restore_category`] can undo it.
+    pub fn delete_category(&mut self, id: &str) -> Option<DeletedCategory> {
+        let index = self.categories.iter().position(|c| c.id == id)?;
+        let category = self.categories.remove(index);
+        let mut layers = Vec::new();
+        let mut keys = Vec::new();
+
+        for (layer_idx, layer) in self.layers.iter_mut().enumerate() {
+            if layer.category_id.as_deref() == Some(id) {
+                layer.category_id = None;
+                layers.push(layer_idx);
+            }
+            for key in &mut layer.keys {
+                if key.category_id.as_deref() == Some(id) {
+                    key.category_id = None;
+                    keys.push((layer_idx, key.position));
+                }
+            }
+        }
+
+        self.metadata.touch();
+        Some(DeletedCategory {
+            category,
+            index,
+            layers,
+            keys,
+        })
+    }
+
+    /// Puts back a category removed by [`Self::delete_category`], at its old
+    /// place in the list, and reassigns its keys and layers.
+    pub fn restore_category(&mut self, deleted: DeletedCategory) {
+        let id = deleted.category.id.clone();
+        let index = deleted.index.min(self.categories.len());
+        self.categories.insert(index, deleted.category);
+
+        for layer_idx in deleted.layers {
+            if let Some(layer) = self.layers.get_mut(layer_idx) {
+                layer.category_id = Some(id.clone());
+            }
+        }
+        for (layer_idx, position) in deleted.keys {
+            if let Some(key) = self
+                .layers
+                .get_mut(layer_idx)
+                .and_then(|layer| layer.get_key_mut(position))
+            {
+                key.category_id = Some(id.clone());
+            }
+        }
+        self.metadata.touch();
+    }
+
+    /// Stamps `modified` on every layer whose content differs from its
+    /// counterpart (matched by ID) in `before`.
+    ///
+    /// A layer whose timestamp is already newer than its counterpart's keeps it,
+    /// and a layer without a counterpart is only stamped if it has no timestamp.
+    /// Returns true if any layer was stamped.
+    pub fn stamp_changed_layers(&mut self, before: &[Layer]) -> bool {
+        let now = Utc::now();
+        let mut stamped = false;
+        for layer in &mut self.layers {
+            let stale = before.iter().find(|b| b.id == layer.id).map_or_else(
+                || layer.modified.is_none(),
+                |previous| layer.modified <= previous.modified && !layer.content_eq(previous),
+            );
+            if stale {
+                layer.modified = Some(now);
+                stamped = true;
+            }
+        }
+        stamped
+    }
+
+    /// Stable hash of the layout's content, as 16 hex characters.
+    ///
+    /// Timestamps and layer IDs are ignored, so two files with the same keys,
+    /// colors and settings hash identically even if they were saved at different
+    /// times or parsed from a legacy file without stored layer IDs.
+    #[must_use]
+    pub fn content_hash(&self) -> String {
+        // FNV-1a, 64-bit
+        const OFFSET_BASIS: u64 = 0xcbf2_9ce4_8422_2325;
+        const PRIME: u64 = 0x0100_0000_01b3;
+
+        let mut normalized = self.clone();
+        normalized.metadata.created = DateTime::<Utc>::UNIX_EPOCH;
+        normalized.metadata.modified = DateTime::<Utc>::UNIX_EPOCH;
+        for layer in &mut normalized.layers {
+            layer.id.clear();
+            layer.modified = None;
+        }
+
+        let bytes = serde_json::to_vec(&normalized).unwrap_or_default();
+        let hash = bytes.iter().fold(OFFSET_BASIS, |hash, byte| {
+            (hash ^ u64::from(*byte)).wrapping_mul(PRIME)
+        });
+        format!("{hash:016x}")
+    }
+
     /// Toggles layer-level RGB colors for a specific layer.
     pub fn toggle_layer_colors(&mut self, layer_idx: usize) -> Option<bool> {
         if let Some(layer) = self.layers.get_mut(layer_idx) {
@@ -1076,7 +1433,7 @@ impl Layout {
     /// let key = KeyDefinition::new(Position::new(0, 0), "KC_A")
     ///     .with_color(RgbColor::new(255, 0, 0));
     ///
-    /// layer.add_key(key.clone());
+    /// layer.add_key(key.clone()).unwrap();
     /// layout.add_layer(layer).unwrap();
     ///
     /// let color = layout.resolve_key_color(0, &key);
@@ -1084,15 +1441,26 @@ impl Layout {
     /// ```
     #[must_use]
     pub fn resolve_key_color(&self, layer_idx: usize, key: &KeyDefinition) -> RgbColor {
+        self.resolve_key_color_source(layer_idx, key).0
+    }
+
+    /// Resolves the color for a key like [`Self::resolve_key_color`], also
+    /// returning which priority level it came from.
+    #[must_use]
+    pub fn resolve_key_color_source(
+        &self,
+        layer_idx: usize,
+        key: &KeyDefinition,
+    ) -> (RgbColor, ColorSource) {
         // 1. Individual key color override (highest priority)
         if let Some(color) = key.color_override {
-            return color;
+            return (color, ColorSource::KeyOverride);
         }
 
         // 2. Key category color
         if let Some(cat_id) = &key.category_id {
             if let Some(category) = self.get_category(cat_id) {
-                return category.color;
+                return (category.color, ColorSource::KeyCategory);
             }
         }
 
@@ -1100,16 +1468,29 @@ impl Layout {
         if let Some(layer) = self.get_layer(layer_idx) {
             if let Some(cat_id) = &layer.category_id {
                 if let Some(category) = self.get_category(cat_id) {
-                    return category.color;
+                    return (category.color, ColorSource::LayerCategory);
                 }
             }
 
             // 4. Layer default color (fallback)
-            return layer.default_color;
+            return (layer.default_color, ColorSource::LayerDefault);
         }
 
         // Fallback to white if layer doesn't exist (shouldn't happen)
-        RgbColor::default()
+        (RgbColor::default(), ColorSource::LayerDefault)
+    }
+
+    /// Category whose firmware RGB flags apply to a key: the key's own
+    /// category, otherwise its layer's.
+    #[must_use]
+    pub fn key_category(&self, layer_idx: usize, key: &KeyDefinition) -> Option<&Category> {
+        key.category_id
+            .as_deref()
+            .and_then(|id| self.get_category(id))
+            .or_else(|| {
+                let layer = self.get_layer(layer_idx)?;
+                self.get_category(layer.category_id.as_deref()?)
+            })
     }
 
     /// Resolves the color for a key, respecting the layer's `colors_enabled` flag.
@@ -1340,6 +1721,8 @@ impl Layout {
                     single_tap: "KC_NO".to_string(),
                     double_tap: None,
                     hold: None,
+                    tapping_term_ms: None,
+                    on_each_tap: false,
                 };
                 self.tap_dances.push(placeholder);
             }
@@ -1442,19 +1825,17 @@ impl Layout {
         }
     }
 
-    /// Validates the layout structure.
+    /// Checks the invariants every layout must hold, even before it is fitted
+    /// to a keyboard.
     ///
-    /// Checks:
-    /// - At least one layer exists
-    /// - All layers have the same number of keys
+    /// Run after parsing and importing. Checks:
+    /// - Layer numbers match their index
     /// - No duplicate positions within each layer
-    /// - All category references exist
-    /// - All tap dance references are valid
-    pub fn validate(&self) -> Result<()> {
-        if self.layers.is_empty() {
-            anyhow::bail!("Layout must have at least one layer");
-        }
-
+    ///
+    /// Category references are not checked here: a layout whose categories
+    /// went missing still loads, and `lazyqmk validate --fix` recreates them
+    /// (see [`Layout::missing_category_ids`]).
+    pub fn validate_invariants(&self) -> Result<()> {
         // Check layer numbers are sequential
         for (idx, layer) in self.layers.iter().enumerate() {
             if layer.number != idx as u8 {
@@ -1466,21 +1847,6 @@ impl Layout {
             }
         }
 
-        // Check all layers have same number of keys
-        if let Some(first_layer) = self.layers.first() {
-            let expected_key_count = first_layer.keys.len();
-            for layer in &self.layers {
-                if layer.keys.len() != expected_key_count {
-                    anyhow::bail!(
-                        "All layers must have the same number of keys. Layer {} has {}, expected {}",
-                        layer.number,
-                        layer.keys.len(),
-                        expected_key_count
-                    );
-                }
-            }
-        }
-
         // Check for duplicate positions within each layer
         for layer in &self.layers {
             let mut positions = std::collections::HashSet::new();
@@ -1496,29 +1862,53 @@ impl Layout {
             }
         }
 
-        // Validate category references
+        Ok(())
+    }
+
+    /// Returns the category IDs referenced by layers or keys but missing from
+    /// the category list, in order of first use.
+    #[must_use]
+    pub fn missing_category_ids(&self) -> Vec<String> {
+        let mut missing: Vec<String> = Vec::new();
         for layer in &self.layers {
-            if let Some(cat_id) = &layer.category_id {
-                if !self.categories.iter().any(|c| &c.id == cat_id) {
-                    anyhow::bail!(
-                        "Layer {} references non-existent category '{}'",
-                        layer.number,
-                        cat_id
-                    );
+            let refs = layer
+                .category_id
+                .iter()
+                .chain(layer.keys.iter().filter_map(|k| k.category_id.as_ref()));
+            for cat_id in refs {
+                if self.get_category(cat_id).is_none() && !missing.contains(cat_id) {
+                    missing.push(cat_id.clone());
                 }
             }
+        }
+        missing
+    }
 
-            for key in &layer.keys {
-                if let Some(cat_id) = &key.category_id {
-                    if !self.categories.iter().any(|c| &c.id == cat_id) {
-                        anyhow::bail!(
-                            "Key at ({}, {}) in layer {} references non-existent category '{}'",
-                            key.position.row,
-                            key.position.col,
-                            layer.number,
-                            cat_id
-                        );
-                    }
+    /// Validates the layout structure.
+    ///
+    /// Checks:
+    /// - At least one layer exists
+    /// - The invariants from [`Layout::validate_invariants`]
+    /// - All layers have the same number of keys
+    /// - All tap dance references are valid
+    pub fn validate(&self) -> Result<()> {
+        if self.layers.is_empty() {
+            anyhow::bail!("Layout must have at least one layer");
+        }
+
+        self.validate_invariants()?;
+
+        // Check all layers have same number of keys
+        if let Some(first_layer) = self.layers.first() {
+            let expected_key_count = first_layer.keys.len();
+            for layer in &self.layers {
+                if layer.keys.len() != expected_key_count {
+                    anyhow::bail!(
+                        "All layers must have the same number of keys. Layer {} has {}, expected {}",
+                        layer.number,
+                        layer.keys.len(),
+                        expected_key_count
+                    );
                 }
             }
         }
@@ -1534,6 +1924,83 @@ impl Layout {
 mod tests {
     use super::*;
 
+    #[test]
+    fn test_content_hash_ignores_timestamps_and_layer_ids() {
+        let mut layout = Layout::new("Test").unwrap();
+        layout
+            .add_layer(Layer::new(0, "Base", RgbColor::new(255, 255, 255)).unwrap())
+            .unwrap();
+        let hash = layout.content_hash();
+        assert_eq!(hash.len(), 16);
+
+        let mut copy = layout.clone();
+        copy.metadata.modified = Utc::now() + chrono::Duration::days(1);
+        copy.layers[0].id = "other-id".to_string();
+        copy.layers[0].modified = None;
+        assert_eq!(copy.content_hash(), hash);
+
+        copy.layers[0].name = "Renamed".to_string();
+        assert_ne!(copy.content_hash(), hash);
+    }
+
+    #[test]
+    fn test_duplicate_layer_appends_renumbered_copy() {
+        let mut layout = Layout::new("Test").unwrap();
+        for (number, name) in [(0, "Base"), (1, "Nav")] {
+            layout
+                .add_layer(Layer::new(number, name, RgbColor::new(0, 0, 255)).unwrap())
+                .unwrap();
+        }
+        let mut key = KeyDefinition::new(Position::new(0, 0), "KC_LEFT");
+        key.label = Some("Left".to_string());
+        layout.layers[1].add_key(key).unwrap();
+        layout.layers[1].category_id = Some("nav".to_string());
+
+        assert_eq!(layout.duplicate_layer(1, "Nav Copy").unwrap(), 2);
+        let (source, copy) = (&layout.layers[1], &layout.layers[2]);
+        assert_eq!(copy.number, 2);
+        assert_eq!(copy.name, "Nav Copy");
+        assert_ne!(copy.id, source.id);
+        assert_eq!(copy.keys, source.keys);
+        assert_eq!(copy.default_color, source.default_color);
+        assert_eq!(copy.category_id.as_deref(), Some("nav"));
+
+        assert!(layout.duplicate_layer(5, "Missing").is_err());
+        assert!(layout.duplicate_layer(0, "").is_err());
+        assert_eq!(layout.layers.len(), 3);
+    }
+
+    #[test]
+    fn test_stamp_changed_layers_only_touches_edited_layers() {
+        let mut layout = Layout::new("Test").unwrap();
+        for (number, name) in [(0, "Base"), (1, "Nav")] {
+            let mut layer = Layer::new(number, name, RgbColor::new(255, 255, 255)).unwrap();
+            layer.modified = None;
+            layout.add_layer(layer).unwrap();
+        }
+        let before = layout.layers.clone();
+        assert!(!layout.stamp_changed_layers(&before));
+
+        layout.layers[1].name = "Navigation".to_string();
+        assert!(layout.stamp_changed_layers(&before));
+        assert!(layout.layers[0].modified.is_none());
+        let stamped = layout.layers[1].modified.unwrap();
+
+        // A newer timestamp set by the caller is kept, a stale one is replaced
+        let before = layout.layers.clone();
+        let later = stamped + chrono::Duration::hours(1);
+        layout.layers[1].enabled = false;
+        layout.layers[1].modified = Some(later);
+        assert!(!layout.stamp_changed_layers(&before));
+        assert_eq!(layout.layers[1].modified, Some(later));
+
+        let before = layout.layers.clone();
+        layout.layers[1].enabled = true;
+        layout.layers[1].modified = Some(stamped);
+        assert!(layout.stamp_changed_layers(&before));
+        assert!(layout.layers[1].modified.unwrap() > stamped);
+    }
+
     #[test]
     fn test_layout_metadata_new() {
         let metadata = LayoutMetadata::new("Test Layout").unwrap();
@@ -1622,7 +2089,7 @@ mod tests {
         // Test 1: Individual override (highest priority)
         let key_with_override =
             KeyDefinition::new(Position::new(0, 0), "KC_A").with_color(RgbColor::new(255, 0, 0));
-        layer.add_key(key_with_override.clone());
+        layer.add_key(key_with_override.clone()).unwrap();
 
         layout.add_layer(layer).unwrap();
 
@@ -1638,7 +2105,8 @@ mod tests {
         layout
             .get_layer_mut(0)
             .unwrap()
-            .add_key(key_with_category.clone());
+            .add_key(key_with_category.clone())
+            .unwrap();
 
         let color = layout.resolve_key_color(0, &key_with_category);
         assert_eq!(color, RgbColor::new(0, 255, 0));
@@ -1648,7 +2116,8 @@ mod tests {
         layout
             .get_layer_mut(0)
             .unwrap()
-            .add_key(key_default.clone());
+            .add_key(key_default.clone())
+            .unwrap();
 
         let color = layout.resolve_key_color(0, &key_default);
         assert_eq!(color, RgbColor::new(255, 255, 255));
@@ -1663,8 +2132,12 @@ mod tests {
 
         // Add a layer with keys
         let mut layer = Layer::new(0, "Base", RgbColor::new(255, 0, 0)).unwrap();
-        layer.add_key(KeyDefinition::new(Position::new(0, 0), "KC_A"));
-        layer.add_key(KeyDefinition::new(Position::new(0, 1), "KC_B"));
+        layer
+            .add_key(KeyDefinition::new(Position::new(0, 0), "KC_A"))
+            .unwrap();
+        layer
+            .add_key(KeyDefinition::new(Position::new(0, 1), "KC_B"))
+            .unwrap();
         layout.add_layer(layer).unwrap();
 
         // Should pass now
@@ -1672,13 +2145,51 @@ mod tests {
 
         // Add another layer with different key count
         let mut layer2 = Layer::new(1, "Lower", RgbColor::new(0, 255, 0)).unwrap();
-        layer2.add_key(KeyDefinition::new(Position::new(0, 0), "KC_1"));
+        layer2
+            .add_key(KeyDefinition::new(Position::new(0, 0), "KC_1"))
+            .unwrap();
         layout.add_layer(layer2).unwrap();
 
         // Should fail - mismatched key counts
         assert!(layout.validate().is_err());
     }
 
+    #[test]
+    fn test_validate_invariants() {
+        let mut layout = Layout::new("Test").unwrap();
+        // An empty layout and uneven layers are fine before fitting to a keyboard
+        assert!(layout.validate_invariants().is_ok());
+
+        let mut base = Layer::new(0, "Base", RgbColor::new(255, 255, 255)).unwrap();
+        base.add_key(KeyDefinition::at(Position::new(0, 0)).keycode("KC_A"))
+            .unwrap();
+        base.add_key(KeyDefinition::at(Position::new(0, 1)).keycode("KC_B"))
+            .unwrap();
+        layout.add_layer(base).unwrap();
+        layout
+            .add_layer(Layer::new(1, "Nav", RgbColor::new(0, 0, 255)).unwrap())
+            .unwrap();
+        assert!(layout.validate_invariants().is_ok());
+
+        let mut bad = layout.clone();
+        bad.layers[1].number = 3;
+        let err = bad.validate_invariants().unwrap_err().to_string();
+        assert!(err.contains("index 1 has number 3"), "{err}");
+
+        let mut bad = layout.clone();
+        bad.layers[0].keys[1].position = Position::new(0, 0);
+        let err = bad.validate_invariants().unwrap_err().to_string();
+        assert!(err.contains("Duplicate position (0, 0)"), "{err}");
+
+        // Missing categories are reported by the validator, not rejected
+        let mut missing = layout;
+        missing.layers[0].keys[0].category_id = Some("missing".to_string());
+        missing.layers[1].category_id = Some("other".to_string());
+        missing.layers[0].keys[1].category_id = Some("missing".to_string());
+        assert!(missing.validate_invariants().is_ok());
+        assert_eq!(missing.missing_category_ids(), ["missing", "other"]);
+    }
+
     // === Tap-Hold Settings Tests ===
 
     #[test]
@@ -12,17 +12,18 @@ pub mod rgb;
 pub mod visual_layout_mapping;
 
 // Re-export all model types
-pub use category::Category;
-pub use color_palette::{ColorPalette, Shade};
-pub use keyboard_geometry::{KeyGeometry, KeyboardGeometry};
+pub use category::{Category, CategoryFlag};
+pub use color_palette::{ColorPalette, PaletteKind, Shade};
+pub use keyboard_geometry::{KeyGeometry, KeyRole, KeyboardGeometry};
 #[allow(unused_imports)]
 pub use layer::{
-    validate_layer_number, KeyDefinition, Layer, Position, DEFAULT_QMK_LAYER_LIMIT,
+    validate_layer_number, EncoderMapping, KeyDefinition, Layer, Position, DEFAULT_QMK_LAYER_LIMIT,
     MAX_QMK_LAYER_LIMIT,
 };
 pub use layout::{
-    HoldDecisionMode, IdleEffectSettings, Layout, LayoutMetadata, RgbBrightness, RgbMatrixEffect,
-    RgbSaturation, TapDanceAction, TapHoldPreset, TapHoldSettings, UncoloredKeyBehavior,
+    CategoryUsage, HoldDecisionMode, IdleEffectSettings, Layout, LayoutMetadata, OrphanedKey,
+    RgbBrightness, RgbMatrixEffect, RgbSaturation, ShiftedForm, TapDanceAction, TapHoldPreset,
+    TapHoldSettings, UncoloredKeyBehavior,
 };
-pub use rgb::RgbColor;
+pub use rgb::{ColorVision, RgbColor, MIN_LEGEND_CONTRAST, MIN_VISIBLE_LED_VALUE};
 pub use visual_layout_mapping::VisualLayoutMapping;
@@ -13,6 +13,63 @@ use anyhow::{Context, Result};
 use serde::{Deserialize, Serialize};
 use std::fmt;
 
+/// Minimum WCAG contrast ratio for legends against their key color.
+///
+/// 3.0 is the WCAG AA threshold for large text, which keycap legends are.
+pub const MIN_LEGEND_CONTRAST: f32 = 3.0;
+
+/// Lowest brightest-channel value an LED must reach to read as lit.
+///
+/// Below this, colors that look fine on screen show as black on the board.
+pub const MIN_VISIBLE_LED_VALUE: u8 = 24;
+
+/// Kinds of color vision deficiency that can be simulated.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ColorVision {
+    /// Red-blind (missing L cones)
+    Protanopia,
+    /// Green-blind (missing M cones), the most common form
+    Deuteranopia,
+    /// Blue-blind (missing S cones)
+    Tritanopia,
+}
+
+impl ColorVision {
+    /// All simulated deficiencies.
+    pub const ALL: [Self; 3] = [Self::Protanopia, Self::Deuteranopia, Self::Tritanopia];
+
+    /// Simulation matrix in linear RGB (Machado et al. 2009, severity 1.0).
+    const fn matrix(self) -> [[f32; 3]; 3] {
+        match self {
+            Self::Protanopia => [
+                [0.152_286, 1.052_583, -0.204_868],
+                [0.114_503, 0.786_281, 0.099_216],
+                [-0.003_882, -0.048_116, 1.051_998],
+            ],
+            Self::Deuteranopia => [
+                [0.367_322, 0.860_646, -0.227_968],
+                [0.280_085, 0.672_501, 0.047_413],
+                [-0.011_820, 0.042_940, 0.968_881],
+            ],
+            Self::Tritanopia => [
+                [1.255_528, -0.076_749, -0.178_779],
+                [-0.078_411, 0.930_809, 0.148_602],
+                [0.004_733, 0.691_367, 0.303_900],
+            ],
+        }
+    }
+}
+
+impl fmt::Display for ColorVision {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Protanopia => write!(f, "protanopia"),
+            Self::Deuteranopia => write!(f, "deuteranopia"),
+            Self::Tritanopia => write!(f, "tritanopia"),
+        }
+    }
+}
+
 /// RGB color value with hex string representation.
 ///
 /// Represents a color using red, green, and blue channels (0-255 each).
@@ -122,6 +179,51 @@ impl RgbColor {
         }
     }
 
+    /// HSV value of the color: its brightest channel (0-255).
+    #[must_use]
+    pub const fn value(&self) -> u8 {
+        let rg = if self.r > self.g { self.r } else { self.g };
+        if rg > self.b {
+            rg
+        } else {
+            self.b
+        }
+    }
+
+    /// Value of the color once the board scales it to `percent` brightness.
+    ///
+    /// Uses the same integer math as [`RgbColor::dim`].
+    #[must_use]
+    pub const fn scaled_value(&self, percent: u8) -> u8 {
+        self.dim(percent).value()
+    }
+
+    /// Lowest brightness percentage at which the scaled value reaches
+    /// `threshold`, or `None` if the color stays below it even at 100%.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use lazyqmk::models::RgbColor;
+    ///
+    /// let navy = RgbColor::new(0, 0, 80);
+    /// assert_eq!(navy.min_visible_brightness(24), Some(30));
+    /// assert_eq!(RgbColor::new(10, 10, 10).min_visible_brightness(24), None);
+    /// ```
+    #[must_use]
+    pub const fn min_visible_brightness(&self, threshold: u8) -> Option<u8> {
+        let value = self.value() as u16;
+        let threshold = threshold as u16;
+        if threshold == 0 {
+            Some(0)
+        } else if value < threshold {
+            None
+        } else {
+            // Ceiling of threshold / value as a percentage
+            Some((threshold * 100).div_ceil(value) as u8)
+        }
+    }
+
     /// Converts the RGB color to HSV (Hue, Saturation, Value) color space.
     ///
     /// # Returns
@@ -251,6 +353,117 @@ impl RgbColor {
         let new_s = (s * f32::from(percent) / 100.0).min(1.0);
         Self::from_hsv(h, new_s, v)
     }
+
+    /// Relative luminance as defined by WCAG 2.x (0.0 = black, 1.0 = white).
+    #[must_use]
+    pub fn relative_luminance(&self) -> f32 {
+        dot([0.2126, 0.7152, 0.0722], self.to_linear())
+    }
+
+    /// WCAG contrast ratio between two colors, from 1.0 (identical
+    /// luminance) to 21.0 (black on white). The order of the colors does not
+    /// matter.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use lazyqmk::models::RgbColor;
+    ///
+    /// let black = RgbColor::new(0, 0, 0);
+    /// let white = RgbColor::new(255, 255, 255);
+    /// assert!((black.contrast_ratio(&white) - 21.0).abs() < 0.01);
+    /// ```
+    #[must_use]
+    pub fn contrast_ratio(&self, other: &Self) -> f32 {
+        let a = self.relative_luminance();
+        let b = other.relative_luminance();
+        (a.max(b) + 0.05) / (a.min(b) + 0.05)
+    }
+
+    /// Perceptual distance (CIE76 ΔE in CIELAB). Around 2.3 is a just
+    /// noticeable difference; below 10 colors are easily confused at a glance.
+    #[must_use]
+    pub fn perceptual_distance(&self, other: &Self) -> f32 {
+        let (lab1, lab2) = (self.to_lab(), other.to_lab());
+        let delta = [lab1[0] - lab2[0], lab1[1] - lab2[1], lab1[2] - lab2[2]];
+        dot(delta, delta).sqrt()
+    }
+
+    /// Smallest perceptual distance between two colors under normal vision
+    /// and each simulated [`ColorVision`] deficiency.
+    ///
+    /// Red and green are far apart for most people but nearly identical with
+    /// deuteranopia, so this returns a small value for them.
+    #[must_use]
+    pub fn colorblind_distance(&self, other: &Self) -> f32 {
+        ColorVision::ALL
+            .iter()
+            .map(|&vision| {
+                self.simulate(vision)
+                    .perceptual_distance(&other.simulate(vision))
+            })
+            .fold(self.perceptual_distance(other), f32::min)
+    }
+
+    /// How this color appears with the given color vision deficiency.
+    #[must_use]
+    pub fn simulate(&self, vision: ColorVision) -> Self {
+        let linear = self.to_linear();
+        Self::from_linear(vision.matrix().map(|row| dot(row, linear)))
+    }
+
+    /// Linear-light channels (sRGB transfer function removed), 0.0-1.0.
+    fn to_linear(self) -> [f32; 3] {
+        [self.r, self.g, self.b].map(|c| {
+            let c = f32::from(c) / 255.0;
+            if c <= 0.040_45 {
+                c / 12.92
+            } else {
+                ((c + 0.055) / 1.055).powf(2.4)
+            }
+        })
+    }
+
+    /// Inverse of [`Self::to_linear`], clamping out-of-gamut values.
+    fn from_linear(rgb: [f32; 3]) -> Self {
+        let [r, g, b] = rgb.map(|c| {
+            let c = c.clamp(0.0, 1.0);
+            let c = if c <= 0.003_130_8 {
+                c * 12.92
+            } else {
+                1.055f32.mul_add(c.powf(1.0 / 2.4), -0.055)
+            };
+            (c * 255.0).round().clamp(0.0, 255.0) as u8
+        });
+        Self::new(r, g, b)
+    }
+
+    /// CIELAB coordinates `[L, a, b]` under the D65 white point.
+    fn to_lab(self) -> [f32; 3] {
+        let linear = self.to_linear();
+        // XYZ divided by the D65 white point, then the CIELAB companding
+        let companded = |row: [f32; 3], white: f32| {
+            let t = dot(row, linear) / white;
+            if t > 0.008_856 {
+                t.cbrt()
+            } else {
+                7.787f32.mul_add(t, 16.0 / 116.0)
+            }
+        };
+        let fx = companded([0.412_456_4, 0.357_576_1, 0.180_437_5], 0.950_47);
+        let fy = companded([0.212_672_9, 0.715_152_2, 0.072_175], 1.0);
+        let fz = companded([0.019_333_9, 0.119_192, 0.950_304_1], 1.088_83);
+        [
+            116.0f32.mul_add(fy, -16.0),
+            500.0 * (fx - fy),
+            200.0 * (fy - fz),
+        ]
+    }
+}
+
+/// Dot product of two 3-vectors.
+fn dot(a: [f32; 3], b: [f32; 3]) -> f32 {
+    a[0].mul_add(b[0], a[1].mul_add(b[1], a[2] * b[2]))
 }
 
 impl fmt::Display for RgbColor {
@@ -320,6 +533,78 @@ mod tests {
         assert_eq!(color, RgbColor::new(255, 255, 255));
     }
 
+    // Contrast and perceptual distance tests
+
+    #[test]
+    fn test_contrast_ratio_known_values() {
+        let black = RgbColor::new(0, 0, 0);
+        let white = RgbColor::new(255, 255, 255);
+        assert!((black.contrast_ratio(&white) - 21.0).abs() < 0.01);
+        assert!((white.contrast_ratio(&black) - 21.0).abs() < 0.01);
+        assert!((white.contrast_ratio(&white) - 1.0).abs() < 0.001);
+
+        // #777777 on white is the classic "just fails AA" gray (~4.48)
+        let gray = RgbColor::new(0x77, 0x77, 0x77);
+        assert!((gray.contrast_ratio(&white) - 4.48).abs() < 0.01);
+
+        // White legends on yellow are unreadable
+        let yellow = RgbColor::new(255, 255, 0);
+        assert!(white.contrast_ratio(&yellow) < MIN_LEGEND_CONTRAST);
+    }
+
+    #[test]
+    fn test_relative_luminance_bounds() {
+        assert!(RgbColor::new(0, 0, 0).relative_luminance().abs() < f32::EPSILON);
+        assert!((RgbColor::new(255, 255, 255).relative_luminance() - 1.0).abs() < 0.001);
+        // Green contributes far more than blue
+        assert!(
+            RgbColor::new(0, 255, 0).relative_luminance()
+                > RgbColor::new(0, 0, 255).relative_luminance()
+        );
+    }
+
+    #[test]
+    fn test_perceptual_distance() {
+        let red = RgbColor::new(255, 0, 0);
+        assert!(red.perceptual_distance(&red) < 0.001);
+        // Black to white spans the full lightness axis
+        let black = RgbColor::new(0, 0, 0);
+        let white = RgbColor::new(255, 255, 255);
+        assert!((black.perceptual_distance(&white) - 100.0).abs() < 0.1);
+        // Nearly identical colors are close, distinct hues are far apart
+        assert!(red.perceptual_distance(&RgbColor::new(250, 5, 5)) < 3.0);
+        assert!(red.perceptual_distance(&RgbColor::new(0, 0, 255)) > 100.0);
+    }
+
+    #[test]
+    fn test_colorblind_distance_catches_red_green() {
+        let red = RgbColor::new(0xD3, 0x2F, 0x2F);
+        let green = RgbColor::new(0x38, 0x8E, 0x3C);
+        let normal = red.perceptual_distance(&green);
+        let colorblind = red.colorblind_distance(&green);
+        assert!(normal > 50.0);
+        assert!(colorblind < normal / 2.0);
+
+        // Blue and orange stay distinguishable for every deficiency
+        let blue = RgbColor::new(0x00, 0x72, 0xB2);
+        let orange = RgbColor::new(0xE6, 0x9F, 0x00);
+        assert!(blue.colorblind_distance(&orange) > 30.0);
+    }
+
+    #[test]
+    fn test_simulate_preserves_grays() {
+        for vision in ColorVision::ALL {
+            for level in [0, 128, 255] {
+                let gray = RgbColor::new(level, level, level);
+                let simulated = gray.simulate(vision);
+                assert!(
+                    gray.perceptual_distance(&simulated) < 2.0,
+                    "{vision} changed {gray} to {simulated}"
+                );
+            }
+        }
+    }
+
     // HSV conversion tests
 
     #[test]
@@ -525,4 +810,31 @@ mod tests {
         assert_eq!(white.saturate(100), white);
         assert_eq!(white.saturate(200), white);
     }
+
+    #[test]
+    fn test_value_and_scaled_value() {
+        assert_eq!(RgbColor::new(40, 200, 90).value(), 200);
+        assert_eq!(RgbColor::new(0, 0, 0).value(), 0);
+        assert_eq!(RgbColor::new(40, 200, 90).scaled_value(50), 100);
+        assert_eq!(RgbColor::new(40, 200, 90).scaled_value(100), 200);
+        assert_eq!(RgbColor::new(40, 200, 90).scaled_value(0), 0);
+    }
+
+    #[test]
+    fn test_min_visible_brightness() {
+        let color = RgbColor::new(0, 70, 35);
+        let percent = color.min_visible_brightness(MIN_VISIBLE_LED_VALUE).unwrap();
+        assert_eq!(percent, 35);
+
+        // The returned brightness is the lowest one that reaches the threshold
+        assert!(color.scaled_value(percent) >= MIN_VISIBLE_LED_VALUE);
+        assert!(color.scaled_value(percent - 1) < MIN_VISIBLE_LED_VALUE);
+
+        assert_eq!(
+            RgbColor::new(255, 0, 0).min_visible_brightness(24),
+            Some(10)
+        );
+        assert_eq!(RgbColor::new(20, 20, 20).min_visible_brightness(24), None);
+        assert_eq!(RgbColor::new(0, 0, 0).min_visible_brightness(0), Some(0));
+    }
 }
@@ -6,7 +6,7 @@
 #![allow(clippy::cast_precision_loss)]
 #![allow(clippy::doc_link_code)]
 
-use crate::models::keyboard_geometry::KeyboardGeometry;
+use crate::models::keyboard_geometry::{KeyRole, KeyboardGeometry};
 use crate::models::layer::Position;
 use std::collections::HashMap;
 
@@ -51,6 +51,10 @@ pub struct VisualLayoutMapping {
     pub visual_to_matrix: HashMap<Position, (u8, u8)>,
     /// Maximum column index in visual layout (cached for navigation efficiency)
     pub max_col: u8,
+    /// Visual position → key role (thumb, home row, pinky)
+    pub roles: HashMap<Position, KeyRole>,
+    /// Encoder index → visual position (encoders sit on free grid cells)
+    pub encoder_positions: Vec<Position>,
 }
 
 #[allow(dead_code)]
@@ -66,6 +70,8 @@ impl VisualLayoutMapping {
             matrix_to_visual: HashMap::new(),
             visual_to_matrix: HashMap::new(),
             max_col: 0,
+            roles: HashMap::new(),
+            encoder_positions: Vec::new(),
         }
     }
 
@@ -103,19 +109,21 @@ impl VisualLayoutMapping {
 
             // Compute visual position from physical coordinates
             // Quantize to grid (round to nearest integer position)
-            let visual_row = key.visual_y.round() as u8;
-            let visual_col = key.visual_x.round() as u8;
-            let visual_pos = Position::new(visual_row, visual_col);
+            let visual_pos = key.visual_position();
 
             // Build Matrix <-> Visual mappings
             mapping.matrix_to_visual.insert(matrix_pos, visual_pos);
             mapping.visual_to_matrix.insert(visual_pos, matrix_pos);
+            mapping.roles.insert(visual_pos, key.role);
         }
 
+        mapping.encoder_positions = geometry.encoder_positions();
+
         // Compute max column for efficient navigation
         mapping.max_col = mapping
             .visual_to_matrix
             .keys()
+            .chain(&mapping.encoder_positions)
             .map(|pos| pos.col)
             .max()
             .unwrap_or(0);
@@ -182,12 +190,33 @@ impl VisualLayoutMapping {
         self.visual_to_matrix.keys().copied().collect()
     }
 
+    /// Role of the key at a visual position (no role if there is no key).
+    #[must_use]
+    pub fn role(&self, pos: Position) -> KeyRole {
+        self.roles.get(&pos).copied().unwrap_or_default()
+    }
+
     /// Checks if a visual position is valid (has a physical key).
     #[must_use]
     pub fn is_valid_position(&self, pos: Position) -> bool {
         self.visual_to_matrix.contains_key(&pos)
     }
 
+    /// Index of the encoder at a visual position, if there is one.
+    #[must_use]
+    pub fn encoder_at(&self, pos: Position) -> Option<u8> {
+        self.encoder_positions
+            .iter()
+            .position(|&p| p == pos)
+            .and_then(|index| u8::try_from(index).ok())
+    }
+
+    /// Checks if the cursor can stop at a visual position (a key or an encoder).
+    #[must_use]
+    pub fn is_navigable(&self, pos: Position) -> bool {
+        self.is_valid_position(pos) || self.encoder_positions.contains(&pos)
+    }
+
     /// Finds the nearest valid position when moving up from the current position.
     ///
     /// If current row - 1 has a key in same column, returns that.
@@ -201,7 +230,7 @@ impl VisualLayoutMapping {
         // Try same column first
         let target_row = current.row - 1;
         let same_col = Position::new(target_row, current.col);
-        if self.is_valid_position(same_col) {
+        if self.is_navigable(same_col) {
             return Some(same_col);
         }
 
@@ -216,7 +245,7 @@ impl VisualLayoutMapping {
 
         // Try same column first
         let same_col = Position::new(target_row, current.col);
-        if self.is_valid_position(same_col) {
+        if self.is_navigable(same_col) {
             return Some(same_col);
         }
 
@@ -234,7 +263,7 @@ impl VisualLayoutMapping {
         // Search leftward for next valid position in same row
         for col in (0..current.col).rev() {
             let pos = Position::new(current.row, col);
-            if self.is_valid_position(pos) {
+            if self.is_navigable(pos) {
                 return Some(pos);
             }
         }
@@ -248,7 +277,7 @@ impl VisualLayoutMapping {
         // Use the actual max column from the mapping for proper navigation support
         for col in (current.col + 1)..=self.max_col {
             let pos = Position::new(current.row, col);
-            if self.is_valid_position(pos) {
+            if self.is_navigable(pos) {
                 return Some(pos);
             }
         }
@@ -260,7 +289,7 @@ impl VisualLayoutMapping {
         let mut best: Option<Position> = None;
         let mut best_distance = u8::MAX;
 
-        for pos in self.visual_to_matrix.keys() {
+        for pos in self.visual_to_matrix.keys().chain(&self.encoder_positions) {
             if pos.row == row {
                 let distance = pos.col.abs_diff(target_col);
 
@@ -443,4 +472,26 @@ mod tests {
         let next = mapping.find_position_right(pos);
         assert_eq!(next, None);
     }
+
+    #[test]
+    fn test_navigation_reaches_encoders() {
+        let mut geometry = KeyboardGeometry::new("test", "LAYOUT", 2, 2);
+        geometry.add_key(KeyGeometry::new((0, 0), 0, 0.0, 0.0));
+        geometry.add_key(KeyGeometry::new((0, 1), 1, 1.0, 0.0));
+        geometry.add_key(KeyGeometry::new((1, 0), 2, 0.0, 1.0));
+        geometry.encoder_count = 1;
+
+        let mapping = VisualLayoutMapping::build(&geometry);
+        let encoder = Position::new(1, 1);
+        assert_eq!(mapping.encoder_at(encoder), Some(0));
+        assert!(!mapping.is_valid_position(encoder));
+        assert_eq!(
+            mapping.find_position_right(Position::new(1, 0)),
+            Some(encoder)
+        );
+        assert_eq!(
+            mapping.find_position_down(Position::new(0, 1)),
+            Some(encoder)
+        );
+    }
 }
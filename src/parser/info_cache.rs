@@ -0,0 +1,131 @@
+//! On-disk cache of merged keyboard `info.json` data.
+//!
+//! QMK's `info.json`/`keyboard.json` files are JSON5, which is slow to parse,
+//! and a keyboard's layouts can be spread over several of them. The merged
+//! result is cached as plain JSON, one file per keyboard, and reused while
+//! every source file keeps its size and modification time.
+//!
+//! The cache is opt-in: callers pass the cache directory explicitly (the
+//! editor sets `PathConfig::info_cache` at startup), so CLI commands and
+//! tests never write to the config directory.
+
+use anyhow::{Context, Result};
+use serde::{Deserialize, Serialize};
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::time::UNIX_EPOCH;
+
+use super::keyboard_json::QmkInfoJson;
+use crate::atomic_write;
+
+/// Identity of a source file when the entry was written.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+struct SourceStamp {
+    path: PathBuf,
+    len: u64,
+    modified_ns: u128,
+}
+
+impl SourceStamp {
+    fn of(path: &Path) -> Option<Self> {
+        let metadata = fs::metadata(path).ok()?;
+        let modified = metadata.modified().ok()?.duration_since(UNIX_EPOCH).ok()?;
+        Some(Self {
+            path: path.to_path_buf(),
+            len: metadata.len(),
+            modified_ns: modified.as_nanos(),
+        })
+    }
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+struct CacheEntry {
+    sources: Vec<SourceStamp>,
+    info: QmkInfoJson,
+}
+
+/// Returns the cached data for `keyboard` if it was built from exactly
+/// `sources`, all unchanged since.
+#[must_use]
+pub fn load(dir: &Path, keyboard: &str, sources: &[PathBuf]) -> Option<QmkInfoJson> {
+    let content = fs::read_to_string(entry_path(dir, keyboard)).ok()?;
+    let entry: CacheEntry = serde_json::from_str(&content).ok()?;
+    (entry.sources == stamps(sources)?).then_some(entry.info)
+}
+
+/// Stores the merged data for `keyboard`, built from `sources`.
+///
+/// # Errors
+///
+/// Returns an error if a source file can't be inspected or the entry can't
+/// be written.
+pub fn store(dir: &Path, keyboard: &str, sources: &[PathBuf], info: &QmkInfoJson) -> Result<()> {
+    let entry = CacheEntry {
+        sources: stamps(sources).context("Failed to read source file metadata")?,
+        info: info.clone(),
+    };
+    fs::create_dir_all(dir).context("Failed to create info.json cache directory")?;
+    let json = serde_json::to_string(&entry).context("Failed to serialize cache entry")?;
+    atomic_write::write(&entry_path(dir, keyboard), json).context("Failed to write cache entry")
+}
+
+fn stamps(sources: &[PathBuf]) -> Option<Vec<SourceStamp>> {
+    sources.iter().map(|path| SourceStamp::of(path)).collect()
+}
+
+/// `crkbd/rev1` is stored as `crkbd__rev1.json`.
+fn entry_path(dir: &Path, keyboard: &str) -> PathBuf {
+    dir.join(format!("{}.json", keyboard.replace('/', "__")))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tempfile::TempDir;
+
+    fn sample_info() -> QmkInfoJson {
+        serde_json::from_str(
+            r#"{"keyboard_name": "test", "layouts": {"LAYOUT": {"layout": [{"x": 0, "y": 0, "matrix": [0, 0]}]}}}"#,
+        )
+        .unwrap()
+    }
+
+    #[test]
+    fn test_store_and_load_round_trip() {
+        let temp = TempDir::new().unwrap();
+        let source = temp.path().join("info.json");
+        fs::write(&source, "{}").unwrap();
+        let sources = vec![source];
+        let cache = temp.path().join("cache");
+
+        assert!(load(&cache, "test/rev1", &sources).is_none());
+        store(&cache, "test/rev1", &sources, &sample_info()).unwrap();
+
+        let info = load(&cache, "test/rev1", &sources).unwrap();
+        assert_eq!(info.keyboard_name.as_deref(), Some("test"));
+        assert!(info.layouts.contains_key("LAYOUT"));
+        assert!(cache.join("test__rev1.json").exists());
+    }
+
+    #[test]
+    fn test_changed_or_different_sources_miss() {
+        let temp = TempDir::new().unwrap();
+        let source = temp.path().join("info.json");
+        fs::write(&source, "{}").unwrap();
+        let other = temp.path().join("keyboard.json");
+        fs::write(&other, "{}").unwrap();
+        let cache = temp.path().join("cache");
+        store(
+            &cache,
+            "test",
+            std::slice::from_ref(&source),
+            &sample_info(),
+        )
+        .unwrap();
+
+        assert!(load(&cache, "test", &[source.clone(), other]).is_none());
+
+        fs::write(&source, "{ \"layouts\": {} }").unwrap();
+        assert!(load(&cache, "test", &[source]).is_none());
+    }
+}
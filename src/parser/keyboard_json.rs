@@ -9,11 +9,12 @@
 
 use anyhow::{Context, Result};
 use serde::{Deserialize, Serialize};
-use std::collections::HashMap;
+use std::collections::{BTreeMap, HashMap};
 use std::fs;
 use std::path::Path;
 
-use crate::models::{KeyGeometry, KeyboardGeometry};
+use super::info_cache;
+use crate::models::{KeyGeometry, KeyRole, KeyboardGeometry};
 
 /// QMK info.json structure (simplified for our needs)
 #[derive(Debug, Clone, Deserialize, Serialize)]
@@ -33,6 +34,8 @@ pub struct QmkInfoJson {
     pub matrix_pins: Option<MatrixPins>,
     /// Encoder configuration
     pub encoder: Option<EncoderConfig>,
+    /// Microcontroller (e.g., "atmega32u4", "RP2040")
+    pub processor: Option<String>,
 }
 
 /// Encoder configuration from info.json
@@ -76,6 +79,15 @@ pub struct KeyPosition {
     /// Rotation in degrees (default 0.0)
     #[serde(default)]
     pub r: f32,
+    /// X position of the rotation origin
+    #[serde(default)]
+    pub rx: Option<f32>,
+    /// Y position of the rotation origin
+    #[serde(default)]
+    pub ry: Option<f32>,
+    /// Index of the encoder this key is the push button of
+    #[serde(default)]
+    pub encoder: Option<u8>,
 }
 
 /// Matrix pins configuration
@@ -99,6 +111,8 @@ pub struct VariantKeyboardJson {
     pub layouts: HashMap<String, LayoutDefinition>,
     /// Encoder configuration (can also be in keyboard.json)
     pub encoder: Option<EncoderConfig>,
+    /// Microcontroller (can also be in keyboard.json)
+    pub processor: Option<String>,
 }
 
 /// RGB matrix configuration from keyboard.json
@@ -226,6 +240,18 @@ pub struct KeyboardConfig {
     pub has_layouts: bool,
 }
 
+impl KeyboardConfig {
+    /// All discovered configuration files.
+    #[must_use]
+    pub fn source_files(&self) -> Vec<std::path::PathBuf> {
+        [&self.info_json, &self.keyboard_json, &self.parent_info_json]
+            .into_iter()
+            .flatten()
+            .cloned()
+            .collect()
+    }
+}
+
 /// Discovers all configuration files for a keyboard.
 ///
 /// This function systematically checks for info.json and keyboard.json files
@@ -377,10 +403,26 @@ fn format_found_files(config: &KeyboardConfig) -> String {
 ///
 /// # Returns
 ///
-/// Parsed QMK info.json structure (potentially merged from multiple files)
+/// Parsed QMK info.json structure (potentially merged from multiple files).
 pub fn parse_keyboard_info_json(qmk_path: &Path, keyboard: &str) -> Result<QmkInfoJson> {
+    parse_keyboard_info_json_cached(qmk_path, keyboard, None)
+}
+
+/// [`parse_keyboard_info_json`] through the [`info_cache`] in `cache_dir`,
+/// so unchanged files are not parsed again.
+pub fn parse_keyboard_info_json_cached(
+    qmk_path: &Path,
+    keyboard: &str,
+    cache_dir: Option<&Path>,
+) -> Result<QmkInfoJson> {
     // Step 1: Discover configuration files
     let config = discover_keyboard_config(qmk_path, keyboard)?;
+    let cache = cache_dir.map(|dir| (dir, config.source_files()));
+    if let Some((dir, sources)) = &cache {
+        if let Some(info) = info_cache::load(dir, keyboard, sources) {
+            return Ok(info);
+        }
+    }
 
     // Step 2: Load and merge configuration
     let info = load_merged_config(&config)?;
@@ -395,6 +437,11 @@ pub fn parse_keyboard_info_json(qmk_path: &Path, keyboard: &str) -> Result<QmkIn
         );
     }
 
+    if let Some((dir, sources)) = &cache {
+        // A failed write only costs the next startup a reparse
+        let _ = info_cache::store(dir, keyboard, sources, &info);
+    }
+
     Ok(info)
 }
 
@@ -416,6 +463,7 @@ fn load_merged_config(config: &KeyboardConfig) -> Result<QmkInfoJson> {
             layouts: variant.layouts,
             matrix_pins: None,
             encoder: variant.encoder,
+            processor: variant.processor,
         }
     } else {
         anyhow::bail!(
@@ -439,6 +487,13 @@ fn load_merged_config(config: &KeyboardConfig) -> Result<QmkInfoJson> {
         }
     }
 
+    // Variants commonly declare the processor only in keyboard.json
+    if info.processor.is_none() {
+        if let Some(ref kb_path) = config.keyboard_json {
+            info.processor = parse_variant_json(kb_path)?.processor;
+        }
+    }
+
     Ok(info)
 }
 
@@ -644,6 +699,7 @@ pub fn build_keyboard_geometry_with_rgb(
 
     // Build KeyGeometry for each key
     let mut keys = Vec::new();
+    let mut encoder_buttons = BTreeMap::new();
     for (layout_index, key_pos) in layout_def.layout.iter().enumerate() {
         let matrix_position = key_pos.matrix.unwrap(); // Already validated above
         let matrix_pos_tuple = (matrix_position[0], matrix_position[1]);
@@ -663,8 +719,15 @@ pub fn build_keyboard_geometry_with_rgb(
             width: key_pos.w,
             height: key_pos.h,
             rotation: key_pos.r,
+            // A missing coordinate of the origin defaults to 0 as in KLE
+            rotation_origin: (key_pos.rx.is_some() || key_pos.ry.is_some())
+                .then(|| (key_pos.rx.unwrap_or(0.0), key_pos.ry.unwrap_or(0.0))),
+            role: KeyRole::default(),
         };
 
+        if let Some(encoder) = key_pos.encoder {
+            encoder_buttons.insert(encoder, matrix_pos_tuple);
+        }
         keys.push(key_geometry);
     }
 
@@ -675,6 +738,7 @@ pub fn build_keyboard_geometry_with_rgb(
         matrix_cols,
         keys,
         encoder_count: 0, // Will be set by caller if encoder info is available
+        encoder_buttons,
     })
 }
 
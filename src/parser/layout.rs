@@ -9,7 +9,10 @@
 #![allow(clippy::cast_sign_loss)]
 
 use crate::constants::APP_BINARY_NAME;
-use crate::models::{Category, KeyDefinition, Layer, Layout, LayoutMetadata, Position, RgbColor};
+use crate::models::{
+    Category, CategoryFlag, EncoderMapping, KeyDefinition, Layer, Layout, LayoutMetadata, Position,
+    RgbColor,
+};
 use anyhow::{Context, Result};
 use regex::Regex;
 use std::path::Path;
@@ -54,6 +57,8 @@ enum ParseState {
 /// **Color**: #808080
 /// **Category**: optional-category-id
 ///
+/// Optional prose describing the layer.
+///
 /// | KC_TAB | KC_Q | ... |
 /// |--------|------|-----|
 /// | KC_A   | KC_S | ... |
@@ -61,6 +66,7 @@ enum ParseState {
 /// ## Categories
 ///
 /// - category-id: Category Name (#RRGGBB)
+/// - other-id: Other Name (#RRGGBB) [always-on, breathing]
 /// ```
 ///
 /// # Errors
@@ -119,6 +125,7 @@ pub fn parse_markdown_layout_str(content: &str) -> Result<Layout> {
         idle_effect_settings: crate::models::IdleEffectSettings::default(),
         tap_hold_settings: crate::models::TapHoldSettings::default(),
         tap_dances: Vec::new(),
+        orphaned_keys: Vec::new(),
     };
 
     // Parse content (layers and categories)
@@ -127,7 +134,7 @@ pub fn parse_markdown_layout_str(content: &str) -> Result<Layout> {
     // Auto-create missing tap dance definitions for any TD() references
     layout.auto_create_tap_dances();
 
-    // Validate the parsed layout
+    // Validate the parsed layout (including `validate_invariants`)
     layout.validate()?;
 
     Ok(layout)
@@ -249,6 +256,13 @@ fn parse_content(lines: &[&str], layout: &mut Layout) -> Result<()> {
             continue;
         }
 
+        // Check for orphaned keys section (## Orphaned Keys)
+        if line == "## Orphaned Keys" {
+            line_num = parse_orphaned_keys(lines, line_num, layout)
+                .with_context(|| format!("Error parsing orphaned keys at line {}", line_num + 1))?;
+            continue;
+        }
+
         // Check for tap dances section (## Tap Dances)
         if line == "## Tap Dances" {
             line_num = parse_tap_dances(lines, line_num, layout)
@@ -284,12 +298,20 @@ fn parse_layer(lines: &[&str], start_line: usize, layout: &mut Layout) -> Result
     let mut layer_color = None;
     let mut layer_category = None;
     let mut layer_colors_enabled = true; // Default to true
+    let mut enabled = true;
     let mut layer_id = None; // Optional layer ID for persistence
+    let mut modified = None;
+    let mut description: Vec<&str> = Vec::new(); // Prose lines, "" between paragraphs
+    let mut encoders: Vec<(Option<String>, Option<String>)> = Vec::new(); // (CCW, CW) by index
+    let encoder_regex = Regex::new(r"^\*\*Encoder\s+(\d+)\s+(CCW|CW)\*\*:\s*(.+)$").unwrap();
 
     while line_num < lines.len() {
         let line = lines[line_num].trim();
 
         if line.is_empty() {
+            if description.last().is_some_and(|l| !l.is_empty()) {
+                description.push("");
+            }
             line_num += 1;
             continue;
         }
@@ -322,6 +344,14 @@ fn parse_layer(lines: &[&str], start_line: usize, layout: &mut Layout) -> Result
             continue;
         }
 
+        // Parse optional firmware inclusion: **Enabled**: true/false
+        if let Some(value) = line.strip_prefix("**Enabled**:") {
+            let value = value.trim().to_lowercase();
+            enabled = value == "true" || value == "yes" || value == "1";
+            line_num += 1;
+            continue;
+        }
+
         // Parse optional layer colors enabled: **Layer Colors**: true/false
         if line.starts_with("**Layer Colors**:") {
             let value = line
@@ -334,11 +364,42 @@ fn parse_layer(lines: &[&str], start_line: usize, layout: &mut Layout) -> Result
             continue;
         }
 
-        // Table starts - break out of properties loop
-        if line.starts_with('|') {
+        // Parse optional change timestamp: **Modified**: RFC 3339
+        if let Some(value) = line.strip_prefix("**Modified**:") {
+            let timestamp =
+                chrono::DateTime::parse_from_rfc3339(value.trim()).with_context(|| {
+                    format!("Invalid **Modified** timestamp for layer {layer_number}")
+                })?;
+            modified = Some(timestamp.with_timezone(&chrono::Utc));
+            line_num += 1;
+            continue;
+        }
+
+        // Parse optional encoder assignment: **Encoder N CCW**: keycode
+        if let Some(captures) = encoder_regex.captures(line) {
+            let index: usize = captures[1]
+                .parse()
+                .context("Failed to parse encoder index")?;
+            let keycode = captures[3].trim().to_string();
+            if encoders.len() <= index {
+                encoders.resize(index + 1, (None, None));
+            }
+            if &captures[2] == "CCW" {
+                encoders[index].0 = Some(keycode);
+            } else {
+                encoders[index].1 = Some(keycode);
+            }
+            line_num += 1;
+            continue;
+        }
+
+        // Table (or the next section for a layer without keys) ends the header
+        if line.starts_with('|') || line.starts_with("##") || line == "---" {
             break;
         }
 
+        // Anything else is the layer description
+        description.push(line);
         line_num += 1;
     }
 
@@ -354,6 +415,21 @@ fn parse_layer(lines: &[&str], start_line: usize, layout: &mut Layout) -> Result
     }
     layer.category_id = layer_category;
     layer.layer_colors_enabled = layer_colors_enabled;
+    layer.enabled = enabled;
+    layer.modified = modified;
+    // Encoders or directions left out keep their fallback keycodes
+    for (index, (ccw, cw)) in encoders.into_iter().enumerate() {
+        let fallback = EncoderMapping::fallback(index);
+        layer.set_encoder(
+            index,
+            EncoderMapping::new(ccw.unwrap_or(fallback.ccw), cw.unwrap_or(fallback.cw)),
+        );
+    }
+    let description = description.join("\n");
+    let description = description.trim();
+    if !description.is_empty() {
+        layer.description = Some(description.to_string());
+    }
 
     // Parse table
     line_num = parse_layer_table(lines, line_num, &mut layer)?;
@@ -426,7 +502,7 @@ fn parse_table_row(line: &str, row: u8, layer: &mut Layer) -> Result<()> {
         let key = parse_keycode_syntax(cell, row, col as u8)
             .with_context(|| format!("Error parsing cell at row {row}, col {col}: {cell}"))?;
 
-        layer.add_key(key);
+        layer.add_key(key)?;
     }
 
     Ok(())
@@ -439,7 +515,7 @@ fn parse_table_row(line: &str, row: u8, layer: &mut Layer) -> Result<()> {
 /// - `KC_X{#RRGGBB}` - with color override
 /// - `KC_X@category-id` - with category
 /// - `KC_X{#RRGGBB}@category-id` - with both
-fn parse_keycode_syntax(cell: &str, row: u8, col: u8) -> Result<KeyDefinition> {
+pub fn parse_keycode_syntax(cell: &str, row: u8, col: u8) -> Result<KeyDefinition> {
     // Updated regex to support:
     // - Basic keycodes: KC_A, KC_LEFT, etc.
     // - Parameterized keycodes: LT(0, KC_A), MT(MOD_LCTL, KC_A)
@@ -485,8 +561,10 @@ fn parse_keycode_syntax(cell: &str, row: u8, col: u8) -> Result<KeyDefinition> {
 fn parse_categories(lines: &[&str], start_line: usize, layout: &mut Layout) -> Result<usize> {
     let mut line_num = start_line + 1; // Skip "## Categories" header
 
-    let category_regex =
-        Regex::new(r"^-\s+([a-z][a-z0-9-]*):\s+(.+?)\s+\(#([0-9A-Fa-f]{6})\)$").unwrap();
+    let category_regex = Regex::new(
+        r"^-\s+([a-z][a-z0-9-]*):\s+(.+?)\s+\(#([0-9A-Fa-f]{6})\)(?:\s+\[([a-z, -]*)\])?$",
+    )
+    .unwrap();
 
     while line_num < lines.len() {
         let line = lines[line_num].trim();
@@ -502,14 +580,28 @@ fn parse_categories(lines: &[&str], start_line: usize, layout: &mut Layout) -> R
             break;
         }
 
-        // Parse category line: - id: Name (#RRGGBB)
+        // Parse category line: - id: Name (#RRGGBB) [flag, ...]
         if let Some(captures) = category_regex.captures(line) {
             let id = captures[1].to_string();
             let name = captures[2].to_string();
             let color_hex = format!("#{}", &captures[3]);
             let color = RgbColor::from_hex(&color_hex)?;
 
-            let category = Category::new(&id, &name, color)?;
+            let mut category = Category::new(&id, &name, color)?;
+            if let Some(flags) = captures.get(4) {
+                for flag in flags.as_str().split(',').map(str::trim) {
+                    if flag.is_empty() {
+                        continue;
+                    }
+                    let flag = CategoryFlag::from_name(flag).with_context(|| {
+                        format!(
+                            "Unknown flag '{flag}' on category '{id}' at line {}",
+                            line_num + 1
+                        )
+                    })?;
+                    category.set_flag(flag, true);
+                }
+            }
             layout.add_category(category)?;
         }
 
@@ -902,6 +994,78 @@ fn parse_key_descriptions(lines: &[&str], start_line: usize, layout: &mut Layout
     Ok(line_num)
 }
 
+/// Parses the orphaned keys section.
+///
+/// Format: `- layer:row:col: KEYCODE`
+/// Example: `- 1:4:6: KC_MUTE`
+#[allow(clippy::unnecessary_wraps)]
+fn parse_orphaned_keys(lines: &[&str], start_line: usize, layout: &mut Layout) -> Result<usize> {
+    let mut line_num = start_line + 1; // Skip "## Orphaned Keys" header
+
+    // Regex to match: - layer:row:col: keycode
+    let orphan_regex = Regex::new(r"^-\s+(\d+):(\d+):(\d+):\s+(.+)$").unwrap();
+
+    while line_num < lines.len() {
+        let line = lines[line_num].trim();
+
+        // Skip empty lines
+        if line.is_empty() {
+            line_num += 1;
+            continue;
+        }
+
+        // Stop at next section
+        if line.starts_with("##") || line.starts_with("---") {
+            break;
+        }
+
+        if let Some(captures) = orphan_regex.captures(line) {
+            layout.orphaned_keys.push(crate::models::OrphanedKey {
+                layer: captures[1].parse().unwrap_or(0),
+                position: Position::new(
+                    captures[2].parse().unwrap_or(0),
+                    captures[3].parse().unwrap_or(0),
+                ),
+                keycode: captures[4].trim().to_string(),
+            });
+        }
+
+        line_num += 1;
+    }
+
+    Ok(line_num)
+}
+
+/// Tap dance fields collected while parsing, before validation.
+#[derive(Default)]
+struct PendingTapDance {
+    name: Option<String>,
+    single: Option<String>,
+    double: Option<String>,
+    hold: Option<String>,
+    tapping_term_ms: Option<u16>,
+    on_each_tap: bool,
+}
+
+impl PendingTapDance {
+    /// Validates and adds the tap dance to the layout if it has a name and single tap.
+    fn finish(self, layout: &mut Layout) -> Result<()> {
+        use crate::models::TapDanceAction;
+
+        if let (Some(name), Some(single)) = (self.name, self.single) {
+            let mut td = TapDanceAction::new(name, single);
+            td.double_tap = self.double;
+            td.hold = self.hold;
+            td.tapping_term_ms = self.tapping_term_ms;
+            td.on_each_tap = self.on_each_tap;
+            // Validate before adding
+            td.validate()?;
+            layout.tap_dances.push(td);
+        }
+        Ok(())
+    }
+}
+
 /// Parses the tap dances section.
 ///
 /// Format:
@@ -912,40 +1076,15 @@ fn parse_key_descriptions(lines: &[&str], start_line: usize, layout: &mut Layout
 ///   - Single Tap: KC_ESC
 ///   - Double Tap: KC_CAPS
 ///   - Hold: KC_LCTL
+///   - Tapping Term: 250ms
+///   - On Each Tap: true
 /// ```
 #[allow(clippy::unnecessary_wraps)]
 fn parse_tap_dances(lines: &[&str], start_line: usize, layout: &mut Layout) -> Result<usize> {
-    use crate::models::TapDanceAction;
-
     let mut line_num = start_line + 1; // Skip "## Tap Dances" header
 
     // State for parsing current tap dance
-    let mut current_name: Option<String> = None;
-    let mut current_single: Option<String> = None;
-    let mut current_double: Option<String> = None;
-    let mut current_hold: Option<String> = None;
-
-    // Helper to finish current tap dance
-    let finish_current_td = |name: Option<String>,
-                             single: Option<String>,
-                             double: Option<String>,
-                             hold: Option<String>,
-                             layout: &mut Layout|
-     -> Result<()> {
-        if let (Some(name), Some(single)) = (name, single) {
-            let mut td = TapDanceAction::new(name, single);
-            if let Some(double) = double {
-                td = td.with_double_tap(double);
-            }
-            if let Some(hold) = hold {
-                td = td.with_hold(hold);
-            }
-            // Validate before adding
-            td.validate()?;
-            layout.tap_dances.push(td);
-        }
-        Ok(())
-    };
+    let mut current = PendingTapDance::default();
 
     while line_num < lines.len() {
         let line = lines[line_num];
@@ -960,33 +1099,20 @@ fn parse_tap_dances(lines: &[&str], start_line: usize, layout: &mut Layout) -> R
         // Stop at next section
         if trimmed.starts_with("##") || trimmed.starts_with("---") {
             // Finish current tap dance if any
-            finish_current_td(
-                current_name.take(),
-                current_single.take(),
-                current_double.take(),
-                current_hold.take(),
-                layout,
-            )?;
+            std::mem::take(&mut current).finish(layout)?;
             break;
         }
 
         // Parse tap dance name: - **name**: (check trimmed version)
         if trimmed.starts_with("- **") && trimmed.ends_with("**:") {
             // Finish previous tap dance if any
-            finish_current_td(
-                current_name.take(),
-                current_single.take(),
-                current_double.take(),
-                current_hold.take(),
-                layout,
-            )?;
+            std::mem::take(&mut current).finish(layout)?;
 
             // Extract name from: - **name**:
-            let name = trimmed
+            current.name = trimmed
                 .strip_prefix("- **")
                 .and_then(|s| s.strip_suffix("**:"))
                 .map(str::to_string);
-            current_name = name;
             line_num += 1;
             continue;
         }
@@ -994,11 +1120,22 @@ fn parse_tap_dances(lines: &[&str], start_line: usize, layout: &mut Layout) -> R
         // Parse properties: "  - Single Tap: KC_ESC" (use original line to preserve indent)
         if line.starts_with("  - ") {
             if let Some(keycode_part) = line.strip_prefix("  - Single Tap: ") {
-                current_single = Some(keycode_part.trim().to_string());
+                current.single = Some(keycode_part.trim().to_string());
             } else if let Some(keycode_part) = line.strip_prefix("  - Double Tap: ") {
-                current_double = Some(keycode_part.trim().to_string());
+                current.double = Some(keycode_part.trim().to_string());
             } else if let Some(keycode_part) = line.strip_prefix("  - Hold: ") {
-                current_hold = Some(keycode_part.trim().to_string());
+                current.hold = Some(keycode_part.trim().to_string());
+            } else if let Some(term_part) = line.strip_prefix("  - Tapping Term: ") {
+                let term = term_part.trim().trim_end_matches("ms").trim();
+                current.tapping_term_ms = Some(term.parse().with_context(|| {
+                    format!(
+                        "Invalid tapping term '{}' at line {}",
+                        term_part.trim(),
+                        line_num + 1
+                    )
+                })?);
+            } else if let Some(flag_part) = line.strip_prefix("  - On Each Tap: ") {
+                current.on_each_tap = flag_part.trim().eq_ignore_ascii_case("true");
             }
         }
 
@@ -1006,13 +1143,7 @@ fn parse_tap_dances(lines: &[&str], start_line: usize, layout: &mut Layout) -> R
     }
 
     // Finish last tap dance if any
-    finish_current_td(
-        current_name,
-        current_single,
-        current_double,
-        current_hold,
-        layout,
-    )?;
+    current.finish(layout)?;
 
     Ok(line_num)
 }
@@ -1282,12 +1413,16 @@ mod tap_dance_parsing_tests {
                 single_tap: "KC_ESC".to_string(),
                 double_tap: Some("KC_CAPS".to_string()),
                 hold: None,
+                tapping_term_ms: None,
+                on_each_tap: false,
             },
             TapDanceAction {
                 name: "shift_ctrl".to_string(),
                 single_tap: "KC_LSFT".to_string(),
                 double_tap: Some("KC_CAPS".to_string()),
                 hold: Some("KC_LCTL".to_string()),
+                tapping_term_ms: None,
+                on_each_tap: false,
             },
         ];
 
@@ -1335,4 +1470,31 @@ mod tap_dance_parsing_tests {
         assert_eq!(td2.double_tap, Some("KC_CAPS".to_string()));
         assert_eq!(td2.hold, Some("KC_LCTL".to_string()));
     }
+
+    #[test]
+    fn test_tap_dance_options_round_trip() {
+        use crate::models::{KeyDefinition, Layer, Position, RgbColor, TapDanceAction};
+        use crate::parser::template_gen;
+
+        let mut layout = Layout::new("Test Layout").expect("Failed to create layout");
+        layout.tap_dances = vec![TapDanceAction::new("slow_esc", "KC_ESC")
+            .with_hold("KC_LCTL")
+            .with_tapping_term(300)
+            .with_on_each_tap()];
+        let mut layer =
+            Layer::new(0, "Base", RgbColor::new(128, 128, 128)).expect("Failed to create layer");
+        layer.keys = vec![KeyDefinition::new(Position::new(0, 0), "TD(slow_esc)")];
+        layout.layers.push(layer);
+
+        let markdown =
+            template_gen::generate_markdown(&layout).expect("Failed to generate markdown");
+        assert!(markdown.contains("  - Tapping Term: 300ms"));
+        assert!(markdown.contains("  - On Each Tap: true"));
+
+        let parsed_layout = parse_markdown_layout_str(&markdown).expect("Parse failed");
+        assert_eq!(parsed_layout.tap_dances, layout.tap_dances);
+
+        let out_of_range = markdown.replace("300ms", "5000ms");
+        assert!(parse_markdown_layout_str(&out_of_range).is_err());
+    }
 }
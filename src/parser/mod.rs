@@ -3,6 +3,7 @@
 //! This module handles reading and writing keyboard layouts from Markdown,
 //! parsing QMK info.json files, and generating firmware configuration files.
 
+pub mod info_cache;
 pub mod keyboard_json;
 pub mod layout;
 pub mod template_gen;
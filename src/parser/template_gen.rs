@@ -8,7 +8,8 @@
 // Allow intentional type casts
 #![allow(clippy::cast_possible_truncation)]
 
-use crate::models::Layout;
+use crate::atomic_write;
+use crate::models::{CategoryFlag, Layout};
 use anyhow::{Context, Result};
 use std::path::Path;
 
@@ -25,7 +26,7 @@ use std::path::Path;
 /// - Atomic rename failures
 pub fn save_markdown_layout(layout: &Layout, path: &Path) -> Result<()> {
     let markdown = generate_markdown(layout)?;
-    atomic_write(path, &markdown)
+    atomic_write::write(path, markdown)
 }
 
 /// Generates Markdown content from a Layout.
@@ -87,6 +88,21 @@ pub fn generate_markdown(layout: &Layout) -> Result<String> {
         output.push_str(&generate_tap_dances(layout));
     }
 
+    // Generate orphaned keys section if any exist
+    if !layout.orphaned_keys.is_empty() {
+        // Add separator if nothing else was written after layers
+        if !has_key_descriptions(layout)
+            && layout.categories.is_empty()
+            && generate_settings(layout).is_none()
+            && layout.tap_dances.is_empty()
+        {
+            output.push_str("---\n\n");
+        } else {
+            output.push('\n');
+        }
+        output.push_str(&generate_orphaned_keys(layout));
+    }
+
     Ok(output)
 }
 
@@ -116,13 +132,38 @@ fn generate_layer(layer: &crate::models::Layer) -> Result<String> {
         output.push_str(&format!("**Category**: {cat_id}\n"));
     }
 
+    // Firmware inclusion (only write if disabled, since enabled is the default)
+    if !layer.enabled {
+        output.push_str("**Enabled**: false\n");
+    }
+
     // Layer colors enabled (only write if false, since true is the default)
     if !layer.layer_colors_enabled {
         output.push_str("**Layer Colors**: false\n");
     }
 
+    // Last change to the layer's keys or properties
+    if let Some(modified) = layer.modified {
+        output.push_str(&format!(
+            "**Modified**: {}\n",
+            modified.to_rfc3339_opts(chrono::SecondsFormat::AutoSi, true)
+        ));
+    }
+
+    // Encoder assignments, one line per direction
+    for (index, encoder) in layer.encoders.iter().enumerate() {
+        output.push_str(&format!("**Encoder {index} CCW**: {}\n", encoder.ccw));
+        output.push_str(&format!("**Encoder {index} CW**: {}\n", encoder.cw));
+    }
+
     output.push('\n');
 
+    // Optional description as prose between the properties and the table
+    if let Some(description) = &layer.description {
+        output.push_str(description.trim());
+        output.push_str("\n\n");
+    }
+
     // Generate table
     output.push_str(&generate_table(layer)?);
 
@@ -226,11 +267,16 @@ fn generate_categories(layout: &Layout) -> String {
 
     for category in &layout.categories {
         output.push_str(&format!(
-            "- {}: {} ({})\n",
+            "- {}: {} ({})",
             category.id,
             category.name,
             category.color.to_hex()
         ));
+        let flags: Vec<&str> = category.flags().map(CategoryFlag::name).collect();
+        if !flags.is_empty() {
+            output.push_str(&format!(" [{}]", flags.join(", ")));
+        }
+        output.push('\n');
     }
 
     output
@@ -274,6 +320,25 @@ fn generate_key_descriptions(layout: &Layout) -> Option<String> {
     Some(output)
 }
 
+/// Generates the orphaned keys section.
+///
+/// Format: `- layer:row:col: KEYCODE`
+fn generate_orphaned_keys(layout: &Layout) -> String {
+    let mut output = String::from("## Orphaned Keys\n\n");
+    output.push_str(
+        "Keys with no position on this keyboard. Place them by hand, then delete this section.\n\n",
+    );
+
+    for orphan in &layout.orphaned_keys {
+        output.push_str(&format!(
+            "- {}:{}:{}: {}\n",
+            orphan.layer, orphan.position.row, orphan.position.col, orphan.keycode
+        ));
+    }
+
+    output
+}
+
 /// Generates the settings section.
 /// Only writes non-default settings to keep files clean.
 fn generate_settings(layout: &Layout) -> Option<String> {
@@ -480,32 +545,17 @@ fn generate_tap_dances(layout: &Layout) -> String {
         if let Some(ref hold) = td.hold {
             output.push_str(&format!("  - Hold: {hold}\n"));
         }
+        if let Some(term) = td.tapping_term_ms {
+            output.push_str(&format!("  - Tapping Term: {term}ms\n"));
+        }
+        if td.on_each_tap {
+            output.push_str("  - On Each Tap: true\n");
+        }
     }
 
     output
 }
 
-/// Performs an atomic file write using temp file + rename pattern.
-///
-/// This ensures the target file is never left in a corrupted state:
-/// 1. Write to temporary file
-/// 2. Verify write success
-/// 3. Atomic rename to target path
-fn atomic_write(path: &Path, content: &str) -> Result<()> {
-    // Create temporary file path
-    let temp_path = path.with_extension("md.tmp");
-
-    // Write to temp file
-    std::fs::write(&temp_path, content)
-        .with_context(|| format!("Failed to write to temporary file: {}", temp_path.display()))?;
-
-    // Atomic rename
-    std::fs::rename(&temp_path, path)
-        .with_context(|| format!("Failed to rename temporary file to: {}", path.display()))?;
-
-    Ok(())
-}
-
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -528,7 +578,9 @@ mod tests {
             layout_variant: None,
             keyboard: None,
             keymap_name: None,
+            converter: None,
             output_format: None,
+            lint: crate::models::layout::LintSettings::default(),
         };
 
         let mut layer = Layer {
@@ -539,8 +591,12 @@ mod tests {
                 .unwrap_or_default()
                 .default_layer_color(),
             category_id: None,
+            description: None,
             keys: vec![],
+            enabled: true,
             layer_colors_enabled: true,
+            encoders: Vec::new(),
+            modified: None,
         };
 
         // Add some keys
@@ -568,6 +624,8 @@ mod tests {
             id: "navigation".to_string(),
             name: "Navigation".to_string(),
             color: RgbColor::new(0, 0, 255),
+            always_on: false,
+            breathing: false,
         };
 
         Layout {
@@ -582,6 +640,7 @@ mod tests {
             idle_effect_settings: crate::models::IdleEffectSettings::default(),
             tap_hold_settings: crate::models::TapHoldSettings::default(),
             tap_dances: vec![],
+            orphaned_keys: Vec::new(),
         }
     }
 
@@ -662,7 +721,23 @@ mod tests {
         let categories_section = generate_categories(&layout);
 
         assert!(categories_section.contains("## Categories"));
-        assert!(categories_section.contains("- navigation: Navigation (#0000FF)"));
+        assert!(categories_section.contains("- navigation: Navigation (#0000FF)\n"));
+    }
+
+    #[test]
+    fn test_category_flags_round_trip() {
+        let mut layout = create_test_layout();
+        layout.categories[0].always_on = true;
+        layout.categories[0].breathing = true;
+
+        let markdown = generate_markdown(&layout).unwrap();
+        assert!(markdown.contains("- navigation: Navigation (#0000FF) [always-on, breathing]"));
+
+        let parsed = parse_markdown_layout_str(&markdown).unwrap();
+        assert_eq!(parsed.categories, layout.categories);
+
+        let bad = markdown.replace("[always-on, breathing]", "[blinking]");
+        assert!(parse_markdown_layout_str(&bad).is_err());
     }
 
     #[test]
@@ -689,6 +764,19 @@ mod tests {
         );
     }
 
+    #[test]
+    fn test_renamed_layer_round_trip() {
+        let mut layout = create_test_layout();
+        let id = layout.layers[0].id.clone();
+        layout.layers[0].set_name("Nav: Arrows & #Symbols").unwrap();
+
+        let markdown = generate_markdown(&layout).unwrap();
+        let parsed = parse_markdown_layout_str(&markdown).unwrap();
+
+        assert_eq!(parsed.layers[0].name, "Nav: Arrows & #Symbols");
+        assert_eq!(parsed.layers[0].id, id);
+    }
+
     #[test]
     fn test_settings_round_trip() {
         use crate::models::UncoloredKeyBehavior;
@@ -912,6 +1000,109 @@ mod tests {
         assert!(!markdown.contains("## Key Descriptions"));
     }
 
+    #[test]
+    fn test_descriptions_round_trip() {
+        let mut layout = create_test_layout();
+        layout.metadata.description = "My daily driver.\n\nTuned for Vim.".to_string();
+        layout.layers[0].description =
+            Some("This layer mirrors macOS shortcuts.\nSecond line.\n\nNew paragraph.".into());
+        for (number, description) in [(1, Some("Reserved for later.")), (2, None)] {
+            let mut layer = Layer::new(number, "Other", RgbColor::new(0, 0, 0)).unwrap();
+            layer.description = description.map(str::to_string);
+            for col in 0..2 {
+                layer
+                    .add_key(KeyDefinition::at(Position::new(0, col)).keycode("KC_TRNS"))
+                    .unwrap();
+            }
+            layout.layers.push(layer);
+        }
+
+        let markdown = generate_markdown(&layout).unwrap();
+        assert!(markdown.contains("**Color**: #"));
+        assert!(markdown.contains("\n\nThis layer mirrors macOS shortcuts.\nSecond line."));
+
+        let parsed = parse_markdown_layout_str(&markdown).unwrap();
+        assert_eq!(parsed.metadata.description, layout.metadata.description);
+        let descriptions: Vec<_> = parsed
+            .layers
+            .iter()
+            .map(|l| l.description.clone())
+            .collect();
+        let expected: Vec<_> = layout
+            .layers
+            .iter()
+            .map(|l| l.description.clone())
+            .collect();
+        assert_eq!(descriptions, expected);
+        assert_eq!(parsed.layers[0].keys.len(), 2);
+    }
+
+    #[test]
+    fn test_encoders_round_trip() {
+        use crate::models::EncoderMapping;
+
+        let mut layout = create_test_layout();
+        layout.layers[0].set_encoder(1, EncoderMapping::new("KC_VOLD", "KC_VOLU"));
+
+        let markdown = generate_markdown(&layout).unwrap();
+        assert!(markdown.contains("**Encoder 0 CCW**: RM_NEXT"));
+        assert!(markdown.contains("**Encoder 1 CW**: KC_VOLU"));
+
+        let parsed = parse_markdown_layout_str(&markdown).unwrap();
+        assert_eq!(parsed.layers[0].encoders, layout.layers[0].encoders);
+        assert_eq!(parsed.layers[0].description, layout.layers[0].description);
+    }
+
+    #[test]
+    fn test_disabled_layer_round_trip() {
+        let mut layout = create_test_layout();
+        let markdown = generate_markdown(&layout).unwrap();
+        assert!(!markdown.contains("**Enabled**"));
+
+        layout.layers[0].enabled = false;
+        let markdown = generate_markdown(&layout).unwrap();
+        assert!(markdown.contains("**Enabled**: false"));
+        let parsed = parse_markdown_layout_str(&markdown).unwrap();
+        assert!(!parsed.layers[0].enabled);
+    }
+
+    #[test]
+    fn test_layer_modified_round_trip() {
+        let mut layout = create_test_layout();
+        let markdown = generate_markdown(&layout).unwrap();
+        assert!(!markdown.contains("**Modified**"));
+        assert_eq!(
+            parse_markdown_layout_str(&markdown).unwrap().layers[0].modified,
+            None
+        );
+
+        let modified = chrono::Utc::now();
+        layout.layers[0].modified = Some(modified);
+        let markdown = generate_markdown(&layout).unwrap();
+        assert!(markdown.contains("**Modified**: "));
+        let parsed = parse_markdown_layout_str(&markdown).unwrap();
+        assert_eq!(parsed.layers[0].modified, Some(modified));
+    }
+
+    #[test]
+    fn test_orphaned_keys_round_trip() {
+        use crate::models::{OrphanedKey, Position};
+
+        let mut layout = create_test_layout();
+        layout.orphaned_keys.push(OrphanedKey {
+            layer: 0,
+            position: Position::new(4, 6),
+            keycode: "LT(1, KC_SPC)".to_string(),
+        });
+
+        let markdown = generate_markdown(&layout).unwrap();
+        assert!(markdown.contains("## Orphaned Keys"));
+        assert!(markdown.contains("- 0:4:6: LT(1, KC_SPC)"));
+
+        let parsed = parse_markdown_layout_str(&markdown).unwrap();
+        assert_eq!(parsed.orphaned_keys, layout.orphaned_keys);
+    }
+
     // === Idle Effect Settings Tests ===
 
     #[test]
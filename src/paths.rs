@@ -0,0 +1,454 @@
+//! Where the application keeps its files.
+//!
+//! Everything is split over three directories:
+//!
+//! - **config**: `config.toml`, `library.toml` and key role overrides
+//! - **data**: layouts, templates, builds, crash bundles and snapshots of
+//!   unsaved layouts
+//! - **cache**: parsed `info.json` data, safe to delete
+//!
+//! They are resolved in this order:
+//!
+//! 1. `LAZYQMK_CONFIG_DIR`: all three in that directory (used by tests)
+//! 2. Portable mode (`--portable` or `LAZYQMK_PORTABLE=1`): all three in
+//!    `.lazyqmk/` in the current working directory, for running from a USB
+//!    stick
+//! 3. The platform directories:
+//!    - Linux: `$XDG_CONFIG_HOME/LazyQMK`, `$XDG_DATA_HOME/LazyQMK`,
+//!      `$XDG_CACHE_HOME/LazyQMK` (`~/.config`, `~/.local/share`, `~/.cache`)
+//!    - macOS: `~/Library/Application Support/LazyQMK` for config and data,
+//!      `~/Library/Caches/LazyQMK`
+//!    - Windows: `%APPDATA%\LazyQMK` for config and data,
+//!      `%LOCALAPPDATA%\LazyQMK` for the cache
+//!
+//! Earlier versions kept everything in the config directory. [`migrate_legacy`]
+//! moves those files to their new place on first run.
+//!
+//! Per-layout state (snapshots, generation history) stays in a `.lazyqmk/`
+//! folder next to the layout file, see [`layout_state_dir`].
+
+use anyhow::{Context, Result};
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::sync::atomic::{AtomicBool, Ordering};
+
+use crate::branding::APP_DATA_DIR;
+
+/// Environment variable putting config, data and cache in one directory
+pub const CONFIG_DIR_ENV: &str = "LAZYQMK_CONFIG_DIR";
+
+/// Environment variable turning on portable mode (`1`, `true` or `yes`)
+pub const PORTABLE_ENV: &str = "LAZYQMK_PORTABLE";
+
+/// Folder holding portable-mode files, and per-layout state next to layouts
+pub const STATE_DIR_NAME: &str = ".lazyqmk";
+
+/// Set by `--portable`
+static PORTABLE: AtomicBool = AtomicBool::new(false);
+
+/// Turns portable mode on for this process (the `--portable` flag).
+pub fn set_portable(portable: bool) {
+    PORTABLE.store(portable, Ordering::Relaxed);
+}
+
+/// Which rule chose the directories.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum PathSource {
+    /// `LAZYQMK_CONFIG_DIR`
+    Override,
+    /// `--portable` or `LAZYQMK_PORTABLE`
+    Portable,
+    /// The platform's config, data and cache directories
+    Platform,
+}
+
+/// Base directories as the platform reports them, before the app folder.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct PlatformDirs {
+    /// e.g. `~/.config`
+    pub config: PathBuf,
+    /// e.g. `~/.local/share`
+    pub data: PathBuf,
+    /// e.g. `~/.cache`
+    pub cache: PathBuf,
+}
+
+impl PlatformDirs {
+    /// The directories for the current user, if they can be determined.
+    #[must_use]
+    pub fn detect() -> Option<Self> {
+        Some(Self {
+            config: dirs::config_dir()?,
+            data: dirs::data_dir()?,
+            cache: dirs::cache_dir()?,
+        })
+    }
+}
+
+/// The resolved application directories.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct AppPaths {
+    /// Which rule chose the directories
+    pub source: PathSource,
+    /// Settings the user edits
+    pub config: PathBuf,
+    /// Files the app creates for the user
+    pub data: PathBuf,
+    /// Files that can be rebuilt at any time
+    pub cache: PathBuf,
+}
+
+impl AppPaths {
+    /// Resolves the directories for this process.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if portable mode is on and the working directory is
+    /// unknown, or if the platform directories can't be determined.
+    pub fn current() -> Result<Self> {
+        let cwd = std::env::current_dir().ok();
+        Self::resolve(
+            |name| std::env::var(name).ok(),
+            PORTABLE.load(Ordering::Relaxed),
+            cwd.as_deref(),
+            PlatformDirs::detect(),
+        )
+    }
+
+    /// Resolves the directories from explicit inputs, see the module docs
+    /// for the order.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the chosen rule is missing its input (`cwd` in
+    /// portable mode, `platform` otherwise).
+    pub fn resolve(
+        env: impl Fn(&str) -> Option<String>,
+        portable_flag: bool,
+        cwd: Option<&Path>,
+        platform: Option<PlatformDirs>,
+    ) -> Result<Self> {
+        if let Some(dir) = env(CONFIG_DIR_ENV).filter(|dir| !dir.is_empty()) {
+            return Ok(Self::single(PathSource::Override, PathBuf::from(dir)));
+        }
+
+        if portable_flag || env(PORTABLE_ENV).is_some_and(|value| is_truthy(&value)) {
+            let cwd = cwd.context("Failed to determine the working directory for portable mode")?;
+            return Ok(Self::single(PathSource::Portable, cwd.join(STATE_DIR_NAME)));
+        }
+
+        let platform = platform.context("Failed to determine config directory")?;
+        Ok(Self {
+            source: PathSource::Platform,
+            config: platform.config.join(APP_DATA_DIR),
+            data: platform.data.join(APP_DATA_DIR),
+            cache: platform.cache.join(APP_DATA_DIR),
+        })
+    }
+
+    /// Config, data and cache all in `dir`.
+    fn single(source: PathSource, dir: PathBuf) -> Self {
+        Self {
+            source,
+            config: dir.clone(),
+            data: dir.clone(),
+            cache: dir.join("cache"),
+        }
+    }
+
+    /// Saved layouts (`lazyqmk new`, the layout picker, the web workspace).
+    #[must_use]
+    pub fn layouts_dir(&self) -> PathBuf {
+        self.data.join("layouts")
+    }
+
+    /// Layout templates.
+    #[must_use]
+    pub fn templates_dir(&self) -> PathBuf {
+        self.data.join("templates")
+    }
+
+    /// Default firmware output directory.
+    #[must_use]
+    pub fn builds_dir(&self) -> PathBuf {
+        self.data.join("builds")
+    }
+
+    /// Crash bundles written when the editor fails.
+    #[must_use]
+    pub fn crash_dir(&self) -> PathBuf {
+        self.data.join("crash")
+    }
+
+    /// Snapshots of layouts that have never been saved.
+    #[must_use]
+    pub fn snapshots_dir(&self) -> PathBuf {
+        self.data.join("snapshots")
+    }
+
+    /// Cached `info.json` data.
+    #[must_use]
+    pub fn info_cache_dir(&self) -> PathBuf {
+        self.cache.join("info_json")
+    }
+
+    /// Legacy location of each directory that has moved, paired with its
+    /// new location.
+    ///
+    /// Earlier versions kept everything in the platform config directory.
+    /// Pairs that are the same path on this platform are left out.
+    #[must_use]
+    pub fn legacy_moves(&self) -> Vec<(PathBuf, PathBuf)> {
+        if self.source != PathSource::Platform {
+            return Vec::new();
+        }
+        let old = &self.config;
+        [
+            (old.join("layouts"), self.layouts_dir()),
+            (old.join("templates"), self.templates_dir()),
+            (old.join("builds"), self.builds_dir()),
+            (old.join("crash"), self.crash_dir()),
+            (
+                old.join(STATE_DIR_NAME).join("snapshots"),
+                self.snapshots_dir(),
+            ),
+            (old.join("cache").join("info_json"), self.info_cache_dir()),
+        ]
+        .into_iter()
+        .filter(|(from, to)| from != to)
+        .collect()
+    }
+}
+
+/// Whether an environment value turns a flag on.
+fn is_truthy(value: &str) -> bool {
+    matches!(
+        value.trim().to_ascii_lowercase().as_str(),
+        "1" | "true" | "yes" | "on"
+    )
+}
+
+/// The config directory for this process.
+///
+/// # Errors
+///
+/// See [`AppPaths::current`].
+pub fn config_dir() -> Result<PathBuf> {
+    Ok(AppPaths::current()?.config)
+}
+
+/// Saved layouts directory for this process.
+///
+/// # Errors
+///
+/// See [`AppPaths::current`].
+pub fn layouts_dir() -> Result<PathBuf> {
+    Ok(AppPaths::current()?.layouts_dir())
+}
+
+/// Templates directory for this process.
+///
+/// # Errors
+///
+/// See [`AppPaths::current`].
+pub fn templates_dir() -> Result<PathBuf> {
+    Ok(AppPaths::current()?.templates_dir())
+}
+
+/// The `.lazyqmk/` folder next to the layout saved at `layout_path`, holding
+/// its snapshots and generation history.
+#[must_use]
+pub fn layout_state_dir(layout_path: &Path) -> PathBuf {
+    let dir = match layout_path.parent() {
+        Some(parent) if !parent.as_os_str().is_empty() => parent,
+        _ => Path::new("."),
+    };
+    dir.join(STATE_DIR_NAME)
+}
+
+/// Moves files from legacy locations to the directories in `paths`.
+///
+/// A legacy directory is moved only when its new location does not exist
+/// yet, so nothing is overwritten and running it again does nothing.
+/// Returns the moves that were made.
+///
+/// # Errors
+///
+/// Returns an error if a directory exists but can't be moved.
+pub fn migrate_legacy(paths: &AppPaths) -> Result<Vec<(PathBuf, PathBuf)>> {
+    let mut moved = Vec::new();
+    for (from, to) in paths.legacy_moves() {
+        if !from.is_dir() || to.exists() {
+            continue;
+        }
+        move_dir(&from, &to)
+            .with_context(|| format!("Failed to move {} to {}", from.display(), to.display()))?;
+        moved.push((from, to));
+    }
+    Ok(moved)
+}
+
+/// Renames `from` to `to`, copying when they are on different file systems.
+fn move_dir(from: &Path, to: &Path) -> Result<()> {
+    if let Some(parent) = to.parent() {
+        fs::create_dir_all(parent)?;
+    }
+    if fs::rename(from, to).is_ok() {
+        return Ok(());
+    }
+    copy_dir(from, to)?;
+    fs::remove_dir_all(from)?;
+    Ok(())
+}
+
+fn copy_dir(from: &Path, to: &Path) -> Result<()> {
+    fs::create_dir_all(to)?;
+    for entry in fs::read_dir(from)? {
+        let entry = entry?;
+        let target = to.join(entry.file_name());
+        if entry.file_type()?.is_dir() {
+            copy_dir(&entry.path(), &target)?;
+        } else {
+            fs::copy(entry.path(), target)?;
+        }
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::collections::HashMap;
+    use tempfile::TempDir;
+
+    fn platform(root: &Path) -> PlatformDirs {
+        PlatformDirs {
+            config: root.join("config"),
+            data: root.join("data"),
+            cache: root.join("cache"),
+        }
+    }
+
+    fn resolve(env: &[(&str, &str)], flag: bool) -> AppPaths {
+        let env: HashMap<String, String> = env
+            .iter()
+            .map(|(k, v)| ((*k).to_string(), (*v).to_string()))
+            .collect();
+        AppPaths::resolve(
+            |name| env.get(name).cloned(),
+            flag,
+            Some(Path::new("/stick")),
+            Some(platform(Path::new("/home"))),
+        )
+        .unwrap()
+    }
+
+    #[test]
+    fn test_resolution_order() {
+        // Platform directories by default
+        let paths = resolve(&[], false);
+        assert_eq!(paths.source, PathSource::Platform);
+        assert_eq!(paths.config, Path::new("/home/config/LazyQMK"));
+        assert_eq!(paths.data, Path::new("/home/data/LazyQMK"));
+        assert_eq!(
+            paths.info_cache_dir(),
+            Path::new("/home/cache/LazyQMK/info_json")
+        );
+
+        // Portable mode, from the flag or the environment
+        for paths in [
+            resolve(&[], true),
+            resolve(&[(PORTABLE_ENV, "1")], false),
+            resolve(&[(PORTABLE_ENV, "true")], false),
+        ] {
+            assert_eq!(paths.source, PathSource::Portable);
+            assert_eq!(paths.config, Path::new("/stick/.lazyqmk"));
+            assert_eq!(
+                paths.templates_dir(),
+                Path::new("/stick/.lazyqmk/templates")
+            );
+            assert_eq!(paths.cache, Path::new("/stick/.lazyqmk/cache"));
+        }
+        assert_eq!(
+            resolve(&[(PORTABLE_ENV, "0")], false).source,
+            PathSource::Platform
+        );
+
+        // The explicit directory wins over portable mode
+        let paths = resolve(&[(CONFIG_DIR_ENV, "/tmp/cfg"), (PORTABLE_ENV, "1")], true);
+        assert_eq!(paths.source, PathSource::Override);
+        assert_eq!(paths.config, Path::new("/tmp/cfg"));
+        assert_eq!(paths.layouts_dir(), Path::new("/tmp/cfg/layouts"));
+
+        // Each rule needs its input
+        assert!(AppPaths::resolve(|_| None, true, None, None).is_err());
+        assert!(AppPaths::resolve(|_| None, false, None, None).is_err());
+    }
+
+    #[test]
+    fn test_legacy_moves_only_apply_to_platform_dirs() {
+        assert!(resolve(&[], true).legacy_moves().is_empty());
+        assert!(resolve(&[(CONFIG_DIR_ENV, "/tmp/cfg")], false)
+            .legacy_moves()
+            .is_empty());
+
+        // Where config and data are the same folder only the cache moves
+        let shared = AppPaths::resolve(
+            |_| None,
+            false,
+            None,
+            Some(PlatformDirs {
+                config: PathBuf::from("/support"),
+                data: PathBuf::from("/support"),
+                cache: PathBuf::from("/caches"),
+            }),
+        )
+        .unwrap();
+        let moves = shared.legacy_moves();
+        assert_eq!(moves.len(), 2);
+        assert!(moves
+            .iter()
+            .any(|(_, to)| to == Path::new("/caches/LazyQMK/info_json")));
+    }
+
+    #[test]
+    fn test_migrate_legacy_moves_without_overwriting() {
+        let temp = TempDir::new().unwrap();
+        let paths = AppPaths::resolve(|_| None, false, None, Some(platform(temp.path()))).unwrap();
+        let legacy_templates = paths.config.join("templates");
+        fs::create_dir_all(legacy_templates.join("nested")).unwrap();
+        fs::write(legacy_templates.join("nested").join("a.md"), "a").unwrap();
+        let legacy_layouts = paths.config.join("layouts");
+        fs::create_dir_all(&legacy_layouts).unwrap();
+        fs::write(legacy_layouts.join("old.md"), "old").unwrap();
+        // Layouts already exist in the new place
+        fs::create_dir_all(paths.layouts_dir()).unwrap();
+
+        let moved = migrate_legacy(&paths).unwrap();
+        assert_eq!(
+            moved,
+            vec![(legacy_templates.clone(), paths.templates_dir())]
+        );
+        assert_eq!(
+            fs::read_to_string(paths.templates_dir().join("nested").join("a.md")).unwrap(),
+            "a"
+        );
+        assert!(!legacy_templates.exists());
+        assert!(legacy_layouts.join("old.md").exists());
+
+        // Nothing left to do the second time
+        assert!(migrate_legacy(&paths).unwrap().is_empty());
+    }
+
+    #[test]
+    fn test_layout_state_dir() {
+        assert_eq!(
+            layout_state_dir(Path::new("/x/corne.md")),
+            Path::new("/x/.lazyqmk")
+        );
+        assert_eq!(
+            layout_state_dir(Path::new("corne.md")),
+            Path::new("./.lazyqmk")
+        );
+    }
+}
@@ -0,0 +1,97 @@
+//! Keycode names QMK has removed, with their current replacements.
+//!
+//! Layouts written for older QMK versions still use names such as `RESET` or
+//! `KC_LCTRL`, which current QMK no longer compiles. The validator warns about
+//! them and the `deprecated-keycode` quick fix rewrites them.
+
+use crate::services::keycode_rename::replace_keycode_token;
+
+/// Removed keycode names and their replacements.
+pub const DEPRECATED_KEYCODES: &[(&str, &str)] = &[
+    // Quantum keycodes renamed in the 2022 keycode overhaul
+    ("RESET", "QK_BOOT"),
+    ("DEBUG", "DB_TOGG"),
+    ("EEP_RST", "EE_CLR"),
+    ("KC_GESC", "QK_GESC"),
+    ("KC_LEAD", "QK_LEAD"),
+    ("KC_LOCK", "QK_LOCK"),
+    ("CAPS_WORD", "CW_TOGG"),
+    ("CAPSWRD", "CW_TOGG"),
+    ("KC_ASTG", "AS_TOGG"),
+    ("KC_ASUP", "AS_UP"),
+    ("KC_ASDN", "AS_DOWN"),
+    ("KC_ASRP", "AS_RPT"),
+    ("KC_ASON", "AS_ON"),
+    ("KC_ASOFF", "AS_OFF"),
+    // Long basic keycode names removed in favour of the short ones
+    ("KC_LCTRL", "KC_LCTL"),
+    ("KC_LSHIFT", "KC_LSFT"),
+    ("KC_RCTRL", "KC_RCTL"),
+    ("KC_RSHIFT", "KC_RSFT"),
+    ("KC_BSPACE", "KC_BSPC"),
+    ("KC_DELT", "KC_DEL"),
+    ("KC_CAPSLOCK", "KC_CAPS"),
+    ("KC_NUMLOCK", "KC_NUM"),
+    ("KC_NLCK", "KC_NUM"),
+    ("KC_SCROLLLOCK", "KC_SCRL"),
+    ("KC_SLCK", "KC_SCRL"),
+    ("KC_PSCREEN", "KC_PSCR"),
+    ("KC_PGDOWN", "KC_PGDN"),
+    ("KC_SCOLON", "KC_SCLN"),
+    ("KC_BSLASH", "KC_BSLS"),
+    ("KC_LBRACKET", "KC_LBRC"),
+    ("KC_RBRACKET", "KC_RBRC"),
+    ("KC_NONUS_BSLASH", "KC_NUBS"),
+    // Japanese keys now use the international keycodes
+    ("KC_ZKHK", "KC_GRV"),
+    ("KC_RO", "KC_INT1"),
+    ("KC_KANA", "KC_INT2"),
+    ("KC_JYEN", "KC_INT3"),
+    ("KC_HENK", "KC_INT4"),
+    ("KC_MHEN", "KC_INT5"),
+];
+
+/// Rewrites every removed name inside `keycode`, including parameters such
+/// as `LT(1, KC_BSPACE)`.
+///
+/// Returns `None` if the keycode uses no removed names.
+#[must_use]
+pub fn modernize_keycode(keycode: &str) -> Option<String> {
+    let mut modern: Option<String> = None;
+    for (old, new) in DEPRECATED_KEYCODES {
+        let current = modern.as_deref().unwrap_or(keycode);
+        if let Some(updated) = replace_keycode_token(current, old, new) {
+            modern = Some(updated);
+        }
+    }
+    modern
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::keycode_db::KeycodeDb;
+
+    #[test]
+    fn test_modernize_keycode() {
+        assert_eq!(modernize_keycode("RESET").as_deref(), Some("QK_BOOT"));
+        assert_eq!(
+            modernize_keycode("LT(1, KC_BSPACE)").as_deref(),
+            Some("LT(1, KC_BSPC)")
+        );
+        assert_eq!(
+            modernize_keycode("LCTL_T(KC_CAPSLOCK)").as_deref(),
+            Some("LCTL_T(KC_CAPS)")
+        );
+        assert_eq!(modernize_keycode("KC_A"), None);
+        assert_eq!(modernize_keycode("KC_ROUND"), None);
+    }
+
+    #[test]
+    fn test_replacements_are_current_keycodes() {
+        let db = KeycodeDb::load().unwrap();
+        for (old, new) in DEPRECATED_KEYCODES {
+            assert!(db.is_valid(new), "{old} -> {new} is not a known keycode");
+        }
+    }
+}
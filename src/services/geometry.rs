@@ -5,14 +5,20 @@
 //! visual layout mappings with RGB matrix support.
 
 use anyhow::{Context, Result};
+use serde::Serialize;
+use std::collections::HashSet;
+use std::fmt;
 
 use crate::{
     config::Config,
-    models::{KeyboardGeometry, LayoutMetadata, VisualLayoutMapping},
+    models::{
+        KeyDefinition, KeyboardGeometry, Layout, LayoutMetadata, Position, VisualLayoutMapping,
+    },
     parser::keyboard_json::{
-        build_keyboard_geometry_with_rgb, build_matrix_to_led_map, parse_keyboard_info_json,
-        parse_variant_keyboard_json,
+        build_keyboard_geometry_with_rgb, build_matrix_to_led_map, extract_layout_variants,
+        parse_keyboard_info_json_cached, parse_variant_keyboard_json, LayoutVariant,
     },
+    services::key_roles::{assign_roles, KeyRoleOverrides},
 };
 
 /// Context required for building keyboard geometry.
@@ -41,6 +47,275 @@ pub struct GeometryResult {
     pub variant_path: String,
 }
 
+/// An existing layout variant offered as a replacement for a missing one.
+#[derive(Debug, Clone, PartialEq)]
+pub struct VariantCandidate {
+    /// Layout variant name (e.g., "`LAYOUT_split_3x6_3`")
+    pub name: String,
+    /// Number of keys in this variant
+    pub key_count: usize,
+    /// Name similarity to the missing variant (0.0 - 1.0)
+    pub similarity: f64,
+}
+
+/// Error returned when a layout's variant does not exist in the keyboard's info.json.
+///
+/// This usually means the variant was renamed upstream. Callers can recover by
+/// downcasting the `anyhow::Error` and letting the user pick one of `candidates`.
+#[derive(Debug, Clone)]
+pub struct MissingVariantError {
+    /// Base keyboard name
+    pub keyboard: String,
+    /// Variant named in the layout metadata
+    pub requested: String,
+    /// Variants that do exist, most similar first
+    pub candidates: Vec<VariantCandidate>,
+}
+
+impl MissingVariantError {
+    /// Creates the error, ranking `available` by similarity to `requested`.
+    #[must_use]
+    pub fn new(keyboard: &str, requested: &str, available: &[LayoutVariant]) -> Self {
+        Self {
+            keyboard: keyboard.to_string(),
+            requested: requested.to_string(),
+            candidates: rank_variants(requested, available),
+        }
+    }
+
+    /// Finds a candidate by exact name.
+    #[must_use]
+    pub fn candidate(&self, name: &str) -> Option<&VariantCandidate> {
+        self.candidates.iter().find(|c| c.name == name)
+    }
+}
+
+impl fmt::Display for MissingVariantError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "Layout '{}' not found in keyboard info.json for {}",
+            self.requested, self.keyboard
+        )?;
+        if !self.candidates.is_empty() {
+            let names: Vec<&str> = self.candidates.iter().map(|c| c.name.as_str()).collect();
+            write!(f, " (available: {})", names.join(", "))?;
+        }
+        Ok(())
+    }
+}
+
+impl std::error::Error for MissingVariantError {}
+
+/// Ranks layout variants by name similarity to `requested`, most similar first.
+#[must_use]
+pub fn rank_variants(requested: &str, available: &[LayoutVariant]) -> Vec<VariantCandidate> {
+    let requested = requested.to_lowercase();
+    let mut candidates: Vec<VariantCandidate> = available
+        .iter()
+        .map(|variant| VariantCandidate {
+            name: variant.name.clone(),
+            key_count: variant.key_count,
+            similarity: name_similarity(&requested, &variant.name.to_lowercase()),
+        })
+        .collect();
+    candidates.sort_by(|a, b| {
+        b.similarity
+            .total_cmp(&a.similarity)
+            .then_with(|| a.name.cmp(&b.name))
+    });
+    candidates
+}
+
+/// Normalized Levenshtein similarity between two strings (1.0 = identical).
+fn name_similarity(a: &str, b: &str) -> f64 {
+    let a: Vec<char> = a.chars().collect();
+    let b: Vec<char> = b.chars().collect();
+    let longest = a.len().max(b.len());
+    if longest == 0 {
+        return 1.0;
+    }
+
+    let mut previous: Vec<usize> = (0..=b.len()).collect();
+    let mut current = vec![0; b.len() + 1];
+    for (i, ca) in a.iter().enumerate() {
+        current[0] = i + 1;
+        for (j, cb) in b.iter().enumerate() {
+            let substitution = previous[j] + usize::from(ca != cb);
+            current[j + 1] = substitution.min(previous[j + 1] + 1).min(current[j] + 1);
+        }
+        std::mem::swap(&mut previous, &mut current);
+    }
+
+    let as_f64 = |n: usize| f64::from(u32::try_from(n).unwrap_or(u32::MAX));
+    1.0 - as_f64(previous[b.len()]) / as_f64(longest)
+}
+
+/// Changes made when fitting a layout's layers to a geometry.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct GeometryAdjustReport {
+    /// Number of `KC_NO` keys added for new positions (across all layers)
+    pub added: usize,
+    /// Number of keys removed because their position no longer exists
+    pub removed: usize,
+    /// Removed keys that held a real keycode: (layer index, position, keycode)
+    pub removed_assigned: Vec<(usize, Position, String)>,
+}
+
+impl GeometryAdjustReport {
+    /// Returns true if no keys were added or removed.
+    #[must_use]
+    pub const fn is_empty(&self) -> bool {
+        self.added == 0 && self.removed == 0
+    }
+
+    /// One-line summary of the changes.
+    #[must_use]
+    pub fn summary(&self) -> String {
+        if self.is_empty() {
+            return "All keys match the geometry".to_string();
+        }
+        format!(
+            "{} key(s) added, {} removed ({} with assigned keycodes)",
+            self.added,
+            self.removed,
+            self.removed_assigned.len()
+        )
+    }
+}
+
+/// Keys of one layer at positions the geometry doesn't have.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct LayerMismatch {
+    /// Layer index
+    pub layer: usize,
+    /// Layer name
+    pub name: String,
+    /// Number of keys in the layer
+    pub key_count: usize,
+    /// Positions of the keys outside the geometry
+    pub extra: Vec<Position>,
+}
+
+/// Layers with more keys than the geometry, e.g. after a hand edit or an
+/// upstream geometry change.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct GeometryMismatch {
+    /// Number of key positions in the geometry
+    pub geometry_keys: usize,
+    /// Layers with keys outside the geometry
+    pub layers: Vec<LayerMismatch>,
+}
+
+impl GeometryMismatch {
+    /// Total number of keys outside the geometry, across all layers.
+    #[must_use]
+    pub fn extra_keys(&self) -> usize {
+        self.layers.iter().map(|layer| layer.extra.len()).sum()
+    }
+
+    /// One-line summary of the mismatch.
+    #[must_use]
+    pub fn summary(&self) -> String {
+        format!(
+            "{} key(s) on {} layer(s) are outside the {}-key geometry",
+            self.extra_keys(),
+            self.layers.len(),
+            self.geometry_keys
+        )
+    }
+}
+
+/// Finds layers with keys at positions `mapping` doesn't have.
+///
+/// Returns `None` when every key fits, or when the mapping is empty (no
+/// geometry could be built, so there is nothing to compare against).
+#[must_use]
+pub fn detect_geometry_mismatch(
+    layout: &Layout,
+    mapping: &VisualLayoutMapping,
+) -> Option<GeometryMismatch> {
+    let valid_positions: HashSet<Position> =
+        mapping.get_all_visual_positions().into_iter().collect();
+    if valid_positions.is_empty() {
+        return None;
+    }
+
+    let layers: Vec<LayerMismatch> = layout
+        .layers
+        .iter()
+        .enumerate()
+        .filter_map(|(layer_idx, layer)| {
+            let extra: Vec<Position> = layer
+                .keys
+                .iter()
+                .map(|key| key.position)
+                .filter(|position| !valid_positions.contains(position))
+                .collect();
+            (!extra.is_empty()).then(|| LayerMismatch {
+                layer: layer_idx,
+                name: layer.name.clone(),
+                key_count: layer.keys.len(),
+                extra,
+            })
+        })
+        .collect();
+
+    (!layers.is_empty()).then_some(GeometryMismatch {
+        geometry_keys: valid_positions.len(),
+        layers,
+    })
+}
+
+/// Fits every layer of `layout` to the positions in `mapping`.
+///
+/// Keys at positions that no longer exist are removed and `KC_NO` keys are
+/// added for new positions. Existing keys at valid positions are preserved.
+pub fn adjust_layout_to_mapping(
+    layout: &mut Layout,
+    mapping: &VisualLayoutMapping,
+) -> GeometryAdjustReport {
+    let valid_positions: HashSet<Position> =
+        mapping.get_all_visual_positions().into_iter().collect();
+    let mut report = GeometryAdjustReport::default();
+
+    for (layer_idx, layer) in layout.layers.iter_mut().enumerate() {
+        let before = layer.keys.len();
+        for key in layer
+            .keys
+            .iter()
+            .filter(|key| !valid_positions.contains(&key.position))
+            .filter(|key| !key.is_no_op() && !key.is_transparent())
+        {
+            report
+                .removed_assigned
+                .push((layer_idx, key.position, key.keycode.clone()));
+        }
+        layer
+            .keys
+            .retain(|key| valid_positions.contains(&key.position));
+        report.removed += before - layer.keys.len();
+
+        let existing_positions: HashSet<Position> = layer.keys.iter().map(|k| k.position).collect();
+        let mut missing_positions: Vec<Position> = valid_positions
+            .iter()
+            .filter(|pos| !existing_positions.contains(pos))
+            .copied()
+            .collect();
+        missing_positions.sort_by_key(|pos| (pos.row, pos.col));
+
+        report.added += missing_positions.len();
+        // Only positions the layer lacks, so none can collide
+        layer.keys.extend(
+            missing_positions
+                .into_iter()
+                .map(|pos| KeyDefinition::new(pos, "KC_NO")),
+        );
+    }
+
+    report
+}
+
 /// Extracts the base keyboard name from a keyboard path that may include a variant.
 ///
 /// # Examples
@@ -84,7 +359,8 @@ pub fn extract_base_keyboard(keyboard_path: &str) -> String {
 /// 3. Determines the correct keyboard variant based on key count
 /// 4. Loads RGB matrix mapping from variant's keyboard.json (if available)
 /// 5. Builds geometry with RGB support
-/// 6. Creates visual layout mapping
+/// 6. Assigns key roles (thumb, home row, pinky) with the user's overrides
+/// 7. Creates visual layout mapping
 ///
 /// # Arguments
 ///
@@ -102,7 +378,7 @@ pub fn extract_base_keyboard(keyboard_path: &str) -> String {
 /// - Keyboard is not specified in metadata
 /// - Layout variant is not specified in metadata
 /// - Failed to parse keyboard info.json
-/// - Layout not found in keyboard info.json
+/// - Layout not found in keyboard info.json (as [`MissingVariantError`])
 /// - Failed to build geometry
 ///
 /// # Fallback Behavior
@@ -133,14 +409,22 @@ pub fn build_geometry_for_layout(
     let base_keyboard = extract_base_keyboard(keyboard);
 
     // Parse keyboard info.json using the base keyboard path
-    let keyboard_info = parse_keyboard_info_json(qmk_path, &base_keyboard)
-        .context("Failed to parse keyboard info.json")?;
+    let keyboard_info = parse_keyboard_info_json_cached(
+        qmk_path,
+        &base_keyboard,
+        context.config.paths.info_cache.as_deref(),
+    )
+    .context("Failed to parse keyboard info.json")?;
 
     // Get the key count for the selected layout to determine the correct variant
-    let layout_def = keyboard_info.layouts.get(layout_name).context(format!(
-        "Layout '{}' not found in keyboard info.json",
-        layout_name
-    ))?;
+    let Some(layout_def) = keyboard_info.layouts.get(layout_name) else {
+        return Err(MissingVariantError::new(
+            &base_keyboard,
+            layout_name,
+            &extract_layout_variants(&keyboard_info),
+        )
+        .into());
+    };
     let key_count = layout_def.layout.len();
 
     // Determine the correct keyboard variant based on key count
@@ -173,6 +457,11 @@ pub fn build_geometry_for_layout(
         .map(|rotary| u8::try_from(rotary.len()).unwrap_or(u8::MAX))
         .unwrap_or(0);
 
+    // Tag thumbs, home row and pinkies; unreadable corrections fall back to
+    // the inferred roles rather than failing the whole geometry
+    let overrides = KeyRoleOverrides::load(keyboard, layout_name).unwrap_or_default();
+    assign_roles(&mut geometry, &overrides);
+
     // Build visual mapping
     let mapping = VisualLayoutMapping::build(&geometry);
 
@@ -297,4 +586,122 @@ mod tests {
         assert_eq!(result.geometry.matrix_cols, 0);
         assert_eq!(result.variant_path, "");
     }
+
+    #[test]
+    fn test_rank_variants_most_similar_first() {
+        let available = vec![
+            LayoutVariant {
+                name: "LAYOUT_ortho_4x12".to_string(),
+                key_count: 48,
+            },
+            LayoutVariant {
+                name: "LAYOUT_split_3x6_3_ex2".to_string(),
+                key_count: 46,
+            },
+            LayoutVariant {
+                name: "LAYOUT_split_3x5_3".to_string(),
+                key_count: 36,
+            },
+        ];
+
+        let ranked = rank_variants("LAYOUT_split_3x6_3", &available);
+        let names: Vec<&str> = ranked.iter().map(|c| c.name.as_str()).collect();
+        assert_eq!(
+            names,
+            [
+                "LAYOUT_split_3x5_3",
+                "LAYOUT_split_3x6_3_ex2",
+                "LAYOUT_ortho_4x12"
+            ]
+        );
+        assert!(ranked[0].similarity > ranked[2].similarity);
+        assert!(ranked.iter().all(|c| (0.0..=1.0).contains(&c.similarity)));
+        assert!((name_similarity("layout", "layout") - 1.0).abs() < f64::EPSILON);
+    }
+
+    #[test]
+    fn test_missing_variant_is_downcastable() {
+        let temp = tempfile::TempDir::new().unwrap();
+        let keyboard_dir = temp.path().join("keyboards").join("test_kb");
+        std::fs::create_dir_all(&keyboard_dir).unwrap();
+        std::fs::write(
+            keyboard_dir.join("info.json"),
+            r#"{
+                "keyboard_name": "test_kb",
+                "layouts": {
+                    "LAYOUT_renamed": {
+                        "layout": [{"matrix": [0, 0], "x": 0, "y": 0}]
+                    }
+                }
+            }"#,
+        )
+        .unwrap();
+
+        let mut config = Config::default();
+        config.paths.qmk_firmware = Some(temp.path().to_path_buf());
+        let mut metadata = LayoutMetadata::new("Test").unwrap();
+        metadata.keyboard = Some("test_kb".to_string());
+        let context = GeometryContext {
+            config: &config,
+            metadata: &metadata,
+        };
+
+        let error = build_geometry_for_layout(context.clone(), "LAYOUT_old").unwrap_err();
+        let missing = error
+            .downcast_ref::<MissingVariantError>()
+            .expect("should be a missing variant error");
+        assert_eq!(missing.requested, "LAYOUT_old");
+        assert_eq!(missing.keyboard, "test_kb");
+        assert_eq!(missing.candidates.len(), 1);
+        assert!(missing.candidate("LAYOUT_renamed").is_some());
+
+        assert!(build_geometry_for_layout(context, "LAYOUT_renamed").is_ok());
+    }
+
+    #[test]
+    fn test_adjust_layout_to_mapping_reports_changes() {
+        use crate::models::{KeyGeometry, Layer, RgbColor};
+
+        let mut geometry = KeyboardGeometry::new("kb", "LAYOUT", 1, 2);
+        geometry.add_key(KeyGeometry::new((0, 0), 0, 0.0, 0.0));
+        geometry.add_key(KeyGeometry::new((0, 1), 1, 1.0, 0.0));
+        let mapping = VisualLayoutMapping::build(&geometry);
+
+        let mut layout = Layout::new("Test").unwrap();
+        let mut layer = Layer::new(0, "Base", RgbColor::new(0, 0, 0)).unwrap();
+        layer
+            .add_key(KeyDefinition::new(Position::new(0, 0), "KC_A"))
+            .unwrap();
+        layer
+            .add_key(KeyDefinition::new(Position::new(3, 3), "KC_B"))
+            .unwrap();
+        layer
+            .add_key(KeyDefinition::new(Position::new(3, 4), "KC_TRNS"))
+            .unwrap();
+        layout.add_layer(layer).unwrap();
+
+        let mismatch = detect_geometry_mismatch(&layout, &mapping).unwrap();
+        assert_eq!(mismatch.geometry_keys, 2);
+        assert_eq!(mismatch.extra_keys(), 2);
+        assert_eq!(mismatch.layers[0].key_count, 3);
+        // An empty mapping has nothing to compare against
+        assert!(detect_geometry_mismatch(&layout, &VisualLayoutMapping::default()).is_none());
+
+        let report = adjust_layout_to_mapping(&mut layout, &mapping);
+
+        assert_eq!(report.added, 1);
+        assert_eq!(report.removed, 2);
+        assert_eq!(
+            report.removed_assigned,
+            vec![(0, Position::new(3, 3), "KC_B".to_string())]
+        );
+        assert_eq!(layout.layers[0].keys.len(), 2);
+        assert!(layout.layers[0]
+            .get_key(Position::new(0, 1))
+            .is_some_and(KeyDefinition::is_no_op));
+
+        // Adjusting again is a no-op
+        assert!(adjust_layout_to_mapping(&mut layout, &mapping).is_empty());
+        assert!(detect_geometry_mismatch(&layout, &mapping).is_none());
+    }
 }
@@ -0,0 +1,359 @@
+//! Key usage heatmap from typing frequency data.
+//!
+//! Frequency data is a CSV of `key,count` lines where `key` is either a QMK
+//! keycode (`KC_A`, `KC_SPC`) or a typed character (`a`, `;`, `!`). Each entry
+//! is attributed to the key that produces it, searching the base layer first
+//! and then higher layers. When a key only exists on a higher layer, the key
+//! used to reach that layer from the base layer is credited as well.
+
+use crate::models::{Layout, Position};
+use crate::services::layer_refs::build_layer_ref_index;
+use anyhow::{bail, Result};
+use serde::Serialize;
+use std::collections::BTreeMap;
+
+/// One `key,count` line of frequency data.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct FrequencyEntry {
+    /// Keycode or character as written in the file
+    pub key: String,
+    /// Number of presses
+    pub count: u64,
+}
+
+/// Parses `key,count` CSV frequency data.
+///
+/// Blank lines and lines starting with `#` are skipped, as is a leading
+/// `keycode,count` header. The key may be quoted (`","` for a comma).
+///
+/// # Errors
+///
+/// Returns an error naming the line if a count is not a number.
+pub fn parse_frequency_csv(content: &str) -> Result<Vec<FrequencyEntry>> {
+    let mut entries = Vec::new();
+
+    for (idx, line) in content.lines().enumerate() {
+        let line = line.trim_end_matches('\r');
+        if line.trim().is_empty() || line.starts_with('#') {
+            continue;
+        }
+
+        let Some((key, count)) = line.rsplit_once(',') else {
+            bail!("Line {}: expected 'key,count'", idx + 1);
+        };
+        let key = key
+            .strip_prefix('"')
+            .and_then(|k| k.strip_suffix('"'))
+            .unwrap_or(key);
+        let Ok(count) = count.trim().parse::<u64>() else {
+            if entries.is_empty() && idx == 0 {
+                // Header row
+                continue;
+            }
+            bail!("Line {}: invalid count '{}'", idx + 1, count.trim());
+        };
+
+        entries.push(FrequencyEntry {
+            key: key.to_string(),
+            count,
+        });
+    }
+
+    Ok(entries)
+}
+
+/// Usage count for one key on one layer.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct KeyUsage {
+    /// Layer index
+    pub layer: usize,
+    /// Key position
+    pub position: Position,
+    /// Keycode at this position
+    pub keycode: String,
+    /// Presses attributed to this key
+    pub count: u64,
+}
+
+/// Key usage computed from frequency data.
+#[derive(Debug, Clone, Default, Serialize)]
+pub struct Heatmap {
+    /// Usage per (layer, position), only for keys with a count
+    pub keys: Vec<KeyUsage>,
+    /// Entries that no key in the layout produces
+    pub unmatched: Vec<FrequencyEntry>,
+    /// Sum of all entry counts
+    pub total: u64,
+    /// Sum of the counts that were attributed to a key
+    pub matched: u64,
+}
+
+impl Heatmap {
+    /// Computes key usage for `layout` from `entries`.
+    #[must_use]
+    pub fn compute(layout: &Layout, entries: &[FrequencyEntry]) -> Self {
+        let ref_index = build_layer_ref_index(&layout.layers);
+        let mut counts: BTreeMap<(usize, u8, u8), u64> = BTreeMap::new();
+        let mut heatmap = Self::default();
+
+        for entry in entries {
+            heatmap.total += entry.count;
+
+            let Some((layer, position)) = find_key(layout, &entry.key) else {
+                heatmap.unmatched.push(entry.clone());
+                continue;
+            };
+            heatmap.matched += entry.count;
+            *counts
+                .entry((layer, position.row, position.col))
+                .or_default() += entry.count;
+
+            // Credit the key that reaches the layer from the base layer
+            if layer > 0 {
+                let access = ref_index
+                    .get(&layer)
+                    .and_then(|refs| refs.iter().find(|r| r.from_layer == 0));
+                if let Some(access) = access {
+                    *counts
+                        .entry((0, access.position.row, access.position.col))
+                        .or_default() += entry.count;
+                }
+            }
+        }
+
+        heatmap.keys = counts
+            .into_iter()
+            .map(|((layer, row, col), count)| {
+                let position = Position::new(row, col);
+                let keycode = layout.layers[layer]
+                    .get_key(position)
+                    .map(|key| key.keycode.clone())
+                    .unwrap_or_default();
+                KeyUsage {
+                    layer,
+                    position,
+                    keycode,
+                    count,
+                }
+            })
+            .collect();
+        heatmap
+    }
+
+    /// Counts for `layer`, by position.
+    #[must_use]
+    pub fn layer_counts(&self, layer: usize) -> BTreeMap<(u8, u8), u64> {
+        self.keys
+            .iter()
+            .filter(|usage| usage.layer == layer)
+            .map(|usage| ((usage.position.row, usage.position.col), usage.count))
+            .collect()
+    }
+
+    /// Counts summed over all layers, by position.
+    #[must_use]
+    pub fn combined_counts(&self) -> BTreeMap<(u8, u8), u64> {
+        let mut counts = BTreeMap::new();
+        for usage in &self.keys {
+            *counts
+                .entry((usage.position.row, usage.position.col))
+                .or_default() += usage.count;
+        }
+        counts
+    }
+
+    /// Presses per layer; the base layer includes layer access key presses.
+    #[must_use]
+    pub fn layer_totals(&self, layer_count: usize) -> Vec<u64> {
+        let mut totals = vec![0; layer_count];
+        for usage in &self.keys {
+            if let Some(total) = totals.get_mut(usage.layer) {
+                *total += usage.count;
+            }
+        }
+        totals
+    }
+
+    /// Presses per visual row, summed over all layers.
+    #[must_use]
+    pub fn row_totals(&self) -> BTreeMap<u8, u64> {
+        let mut totals = BTreeMap::new();
+        for usage in &self.keys {
+            *totals.entry(usage.position.row).or_default() += usage.count;
+        }
+        totals
+    }
+
+    /// The `n` most used keys, most used first.
+    #[must_use]
+    pub fn top_keys(&self, n: usize) -> Vec<&KeyUsage> {
+        let mut keys: Vec<_> = self.keys.iter().collect();
+        keys.sort_by(|a, b| b.count.cmp(&a.count).then(a.layer.cmp(&b.layer)));
+        keys.truncate(n);
+        keys
+    }
+}
+
+/// Finds the layer and position of the key producing `key`.
+///
+/// Candidates are tried in order; for each, layers are searched from the base
+/// layer up.
+fn find_key(layout: &Layout, key: &str) -> Option<(usize, Position)> {
+    for candidate in candidate_keycodes(key) {
+        for (layer_idx, layer) in layout.layers.iter().enumerate() {
+            let found = layer
+                .keys
+                .iter()
+                .find(|def| def.keycode == candidate || tap_keycode(&def.keycode) == candidate);
+            if let Some(def) = found {
+                return Some((layer_idx, def.position));
+            }
+        }
+    }
+    None
+}
+
+/// Returns the tap keycode of a dual-role key (`LT(1, KC_A)` → `KC_A`,
+/// `LSFT_T(KC_A)` → `KC_A`), or the keycode itself.
+fn tap_keycode(keycode: &str) -> &str {
+    let Some(inner) = keycode
+        .split_once('(')
+        .and_then(|(_, rest)| rest.strip_suffix(')'))
+    else {
+        return keycode;
+    };
+    inner.rsplit(',').next().map_or(keycode, str::trim)
+}
+
+/// Keycodes that produce `key`, most specific first.
+fn candidate_keycodes(key: &str) -> Vec<String> {
+    let mut chars = key.chars();
+    let (Some(c), None) = (chars.next(), chars.next()) else {
+        // Keycode or named key
+        let upper = key.trim().to_uppercase();
+        let named = match upper.as_str() {
+            "SPACE" => "KC_SPC",
+            "ENTER" | "RETURN" => "KC_ENT",
+            "TAB" => "KC_TAB",
+            "BACKSPACE" => "KC_BSPC",
+            "ESC" | "ESCAPE" => "KC_ESC",
+            _ if upper.starts_with("KC_") || upper.contains('(') => return vec![upper],
+            _ => return vec![format!("KC_{upper}")],
+        };
+        return vec![named.to_string()];
+    };
+
+    if c.is_ascii_alphabetic() {
+        return vec![format!("KC_{}", c.to_ascii_uppercase())];
+    }
+    if c.is_ascii_digit() {
+        return vec![format!("KC_{c}")];
+    }
+
+    let (base, shifted) = match c {
+        ' ' => ("KC_SPC", None),
+        '\t' => ("KC_TAB", None),
+        '-' => ("KC_MINS", None),
+        '_' => ("KC_MINS", Some("KC_UNDS")),
+        '=' => ("KC_EQL", None),
+        '+' => ("KC_EQL", Some("KC_PLUS")),
+        '[' => ("KC_LBRC", None),
+        '{' => ("KC_LBRC", Some("KC_LCBR")),
+        ']' => ("KC_RBRC", None),
+        '}' => ("KC_RBRC", Some("KC_RCBR")),
+        '\\' => ("KC_BSLS", None),
+        '|' => ("KC_BSLS", Some("KC_PIPE")),
+        ';' => ("KC_SCLN", None),
+        ':' => ("KC_SCLN", Some("KC_COLN")),
+        '\'' => ("KC_QUOT", None),
+        '"' => ("KC_QUOT", Some("KC_DQUO")),
+        '`' => ("KC_GRV", None),
+        '~' => ("KC_GRV", Some("KC_TILD")),
+        ',' => ("KC_COMM", None),
+        '<' => ("KC_COMM", Some("KC_LT")),
+        '.' => ("KC_DOT", None),
+        '>' => ("KC_DOT", Some("KC_GT")),
+        '/' => ("KC_SLSH", None),
+        '?' => ("KC_SLSH", Some("KC_QUES")),
+        '!' => ("KC_1", Some("KC_EXLM")),
+        '@' => ("KC_2", Some("KC_AT")),
+        '#' => ("KC_3", Some("KC_HASH")),
+        '$' => ("KC_4", Some("KC_DLR")),
+        '%' => ("KC_5", Some("KC_PERC")),
+        '^' => ("KC_6", Some("KC_CIRC")),
+        '&' => ("KC_7", Some("KC_AMPR")),
+        '*' => ("KC_8", Some("KC_ASTR")),
+        '(' => ("KC_9", Some("KC_LPRN")),
+        ')' => ("KC_0", Some("KC_RPRN")),
+        _ => return Vec::new(),
+    };
+
+    match shifted {
+        Some(shifted) => vec![
+            shifted.to_string(),
+            format!("S({base})"),
+            format!("LSFT({base})"),
+            base.to_string(),
+        ],
+        None => vec![base.to_string()],
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::models::{KeyDefinition, Layer, RgbColor};
+
+    fn test_layout() -> Layout {
+        let mut layout = Layout::new("Heat").unwrap();
+        let mut base = Layer::new(0, "Base", RgbColor::new(0, 0, 0)).unwrap();
+        base.add_key(KeyDefinition::new(Position::new(0, 0), "KC_A"))
+            .unwrap();
+        base.add_key(KeyDefinition::new(Position::new(0, 1), "LSFT_T(KC_S)"))
+            .unwrap();
+        base.add_key(KeyDefinition::new(Position::new(1, 0), "LT(1, KC_SPC)"))
+            .unwrap();
+        base.add_key(KeyDefinition::new(Position::new(1, 1), "KC_1"))
+            .unwrap();
+        let mut sym = Layer::new(1, "Sym", RgbColor::new(0, 0, 0)).unwrap();
+        sym.add_key(KeyDefinition::new(Position::new(0, 0), "KC_EXLM"))
+            .unwrap();
+        sym.add_key(KeyDefinition::new(Position::new(0, 1), "KC_SCLN"))
+            .unwrap();
+        layout.layers = vec![base, sym];
+        layout
+    }
+
+    #[test]
+    fn test_parse_frequency_csv() {
+        let entries =
+            parse_frequency_csv("keycode,count\n# comment\na,10\n\",\",3\nKC_SPC, 7\n").unwrap();
+        assert_eq!(entries.len(), 3);
+        assert_eq!(entries[1].key, ",");
+        assert_eq!(entries[2].count, 7);
+
+        let err = parse_frequency_csv("a,10\nb,lots\n").unwrap_err();
+        assert!(err.to_string().contains("Line 2"));
+    }
+
+    #[test]
+    fn test_compute_resolves_through_layers() {
+        let layout = test_layout();
+        let entries = parse_frequency_csv("a,10\ns,5\n;,4\n!,2\n\" \",8\nz,1\n").unwrap();
+
+        let heatmap = Heatmap::compute(&layout, &entries);
+
+        let base = heatmap.layer_counts(0);
+        // Space is the tap of LT(1, ...), which also reaches ';' and '!'
+
+        assert_eq!(base[&(0, 0)], 10);
+        assert_eq!(base[&(0, 1)], 5, "Tap keycode of mod-tap matches");
+        assert_eq!(heatmap.layer_counts(1)[&(0, 1)], 4);
+        assert_eq!(heatmap.layer_counts(1)[&(0, 0)], 2, "Shifted alias wins");
+        assert_eq!(base[&(1, 0)], 8 + 4 + 2);
+        assert_eq!(heatmap.total, 30);
+        assert_eq!(heatmap.unmatched.len(), 1);
+        assert_eq!(heatmap.matched, 29);
+        assert_eq!(heatmap.layer_totals(2), vec![29, 6]);
+    }
+}
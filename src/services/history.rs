@@ -0,0 +1,312 @@
+//! Generation and build history.
+//!
+//! Every firmware generation or build of a layout file appends one JSON line
+//! to `.lazyqmk/history/<layout>.jsonl` next to the layout, recording when it
+//! ran, with which lazyqmk and QMK versions, and how it went. The file is
+//! pruned to the newest [`DEFAULT_HISTORY_RETENTION`] entries, and lines that
+//! fail to parse are skipped on read rather than failing the whole history.
+
+use anyhow::{Context, Result};
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use std::fs::{self, OpenOptions};
+use std::io::Write as _;
+use std::path::{Path, PathBuf};
+use std::process::Command;
+
+use crate::firmware::size::FirmwareSize;
+use crate::paths;
+use crate::services::layouts::sanitize_filename;
+
+/// Default number of history entries kept per layout before the oldest are pruned.
+pub const DEFAULT_HISTORY_RETENTION: usize = 100;
+
+/// Operation a history entry records.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum HistoryOperation {
+    /// Firmware files were generated
+    Generate,
+    /// Firmware files were generated and compiled
+    Build,
+}
+
+impl std::fmt::Display for HistoryOperation {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::Generate => write!(f, "generate"),
+            Self::Build => write!(f, "build"),
+        }
+    }
+}
+
+/// One generation or build run.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct HistoryEntry {
+    /// When the run finished
+    pub timestamp: DateTime<Utc>,
+    /// What was run
+    pub operation: HistoryOperation,
+    /// Whether it succeeded
+    pub success: bool,
+    /// lazyqmk version that ran it
+    pub lazyqmk_version: String,
+    /// `git describe` of the QMK firmware tree, if it is a git checkout
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub qmk_version: Option<String>,
+    /// Number of validation warnings
+    #[serde(default)]
+    pub warnings: usize,
+    /// Flash/RAM usage of a successful build
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub firmware_size: Option<FirmwareSize>,
+    /// First line of the error for a failed run
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub error: Option<String>,
+}
+
+impl HistoryEntry {
+    /// Creates a successful entry for `operation`, stamped now.
+    #[must_use]
+    pub fn new(operation: HistoryOperation) -> Self {
+        Self {
+            timestamp: Utc::now(),
+            operation,
+            success: true,
+            lazyqmk_version: env!("CARGO_PKG_VERSION").to_string(),
+            qmk_version: None,
+            warnings: 0,
+            firmware_size: None,
+            error: None,
+        }
+    }
+
+    /// Records the QMK tree's `git describe`, if it has one.
+    #[must_use]
+    pub fn with_qmk_tree(mut self, qmk_path: Option<&Path>) -> Self {
+        self.qmk_version = qmk_path.and_then(describe_qmk_tree);
+        self
+    }
+
+    /// Marks the run as failed with the first line of `error`.
+    pub fn fail(&mut self, error: &str) {
+        self.timestamp = Utc::now();
+        self.success = false;
+        self.error = Some(error.lines().next().unwrap_or_default().to_string());
+    }
+
+    /// One-line summary, e.g. "build ✓ · 2 warning(s) · Flash 93% · RAM 70%".
+    #[must_use]
+    pub fn summary(&self) -> String {
+        let mut parts = vec![format!(
+            "{} {}",
+            self.operation,
+            if self.success { "✓" } else { "✗" }
+        )];
+        if self.warnings > 0 {
+            parts.push(format!("{} warning(s)", self.warnings));
+        }
+        if let Some(size) = &self.firmware_size {
+            parts.push(size.short_summary());
+        }
+        if let Some(error) = &self.error {
+            parts.push(error.clone());
+        }
+        parts.join(" · ")
+    }
+}
+
+/// History read from disk.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct History {
+    /// Entries, oldest first
+    pub entries: Vec<HistoryEntry>,
+    /// Number of lines that could not be parsed
+    pub skipped_lines: usize,
+}
+
+/// Service for recording and reading generation/build history.
+pub struct HistoryService;
+
+impl HistoryService {
+    /// Returns the history file for the layout saved at `layout_path`.
+    #[must_use]
+    pub fn history_path(layout_path: &Path) -> PathBuf {
+        let name = layout_path
+            .file_stem()
+            .and_then(|s| s.to_str())
+            .map_or_else(|| "layout".to_string(), sanitize_filename);
+        paths::layout_state_dir(layout_path)
+            .join("history")
+            .join(format!("{name}.jsonl"))
+    }
+
+    /// Appends `entry` to the history of the layout at `layout_path`, then
+    /// prunes it to the newest `retention` entries.
+    pub fn record(layout_path: &Path, entry: &HistoryEntry, retention: usize) -> Result<()> {
+        Self::append(&Self::history_path(layout_path), entry, retention)
+    }
+
+    /// Appends `entry` to the history file at `path`, then prunes it to the
+    /// newest `retention` lines.
+    pub fn append(path: &Path, entry: &HistoryEntry, retention: usize) -> Result<()> {
+        if let Some(dir) = path.parent() {
+            fs::create_dir_all(dir).with_context(|| {
+                format!("Failed to create history directory: {}", dir.display())
+            })?;
+        }
+
+        let line = serde_json::to_string(entry).context("Failed to serialize history entry")?;
+        let mut file = OpenOptions::new()
+            .create(true)
+            .append(true)
+            .open(path)
+            .with_context(|| format!("Failed to open history file: {}", path.display()))?;
+        writeln!(file, "{line}")
+            .with_context(|| format!("Failed to write history file: {}", path.display()))?;
+        drop(file);
+
+        Self::prune(path, retention)?;
+        Ok(())
+    }
+
+    /// Drops the oldest lines so at most `retention` remain.
+    ///
+    /// Returns the number of lines removed.
+    pub fn prune(path: &Path, retention: usize) -> Result<usize> {
+        let content = fs::read_to_string(path)
+            .with_context(|| format!("Failed to read history file: {}", path.display()))?;
+        let lines: Vec<&str> = content.lines().filter(|l| !l.trim().is_empty()).collect();
+        if lines.len() <= retention {
+            return Ok(0);
+        }
+
+        let removed = lines.len() - retention;
+        let mut kept = lines[removed..].join("\n");
+        kept.push('\n');
+        crate::atomic_write::write(path, &kept)
+            .with_context(|| format!("Failed to prune history file: {}", path.display()))?;
+        Ok(removed)
+    }
+
+    /// Reads the history of the layout at `layout_path`.
+    ///
+    /// A missing history file is an empty history.
+    pub fn load(layout_path: &Path) -> Result<History> {
+        Self::read(&Self::history_path(layout_path))
+    }
+
+    /// Reads a history file, skipping lines that fail to parse.
+    ///
+    /// A missing file is an empty history.
+    pub fn read(path: &Path) -> Result<History> {
+        if !path.exists() {
+            return Ok(History::default());
+        }
+        let content = fs::read_to_string(path)
+            .with_context(|| format!("Failed to read history file: {}", path.display()))?;
+
+        let mut history = History::default();
+        for line in content.lines().filter(|l| !l.trim().is_empty()) {
+            match serde_json::from_str(line) {
+                Ok(entry) => history.entries.push(entry),
+                Err(_) => history.skipped_lines += 1,
+            }
+        }
+        Ok(history)
+    }
+}
+
+/// Runs `git describe` in the QMK tree, e.g. "0.26.10-3-gabc1234-dirty".
+///
+/// Only a checkout root is described, so a QMK tree nested in another
+/// repository doesn't report that repository's version.
+fn describe_qmk_tree(qmk_path: &Path) -> Option<String> {
+    if !qmk_path.join(".git").exists() {
+        return None;
+    }
+    let output = Command::new("git")
+        .arg("-C")
+        .arg(qmk_path)
+        .args(["describe", "--tags", "--always", "--dirty"])
+        .output()
+        .ok()?;
+    if !output.status.success() {
+        return None;
+    }
+    let version = String::from_utf8_lossy(&output.stdout).trim().to_string();
+    (!version.is_empty()).then_some(version)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tempfile::TempDir;
+
+    #[test]
+    fn test_history_path_next_to_layout() {
+        assert_eq!(
+            HistoryService::history_path(Path::new("/x/My Corne.md")),
+            Path::new("/x/.lazyqmk/history/my_corne.jsonl")
+        );
+    }
+
+    #[test]
+    fn test_record_prunes_and_skips_corrupt_lines() {
+        let temp = TempDir::new().unwrap();
+        let path = temp.path().join("history.jsonl");
+
+        HistoryService::append(&path, &HistoryEntry::new(HistoryOperation::Generate), 3).unwrap();
+        // A truncated write or a hand edit must not lose the rest of the history
+        fs::write(
+            &path,
+            format!("{}{{\"timestamp\":\n", fs::read_to_string(&path).unwrap()),
+        )
+        .unwrap();
+        for warnings in 1..=2 {
+            let mut entry = HistoryEntry::new(HistoryOperation::Build);
+            entry.fail("Build failed: oops\nmore");
+            entry.warnings = warnings;
+            HistoryService::append(&path, &entry, 3).unwrap();
+        }
+
+        let history = HistoryService::read(&path).unwrap();
+        assert_eq!(history.skipped_lines, 1);
+        assert_eq!(history.entries.len(), 2);
+        assert_eq!(history.entries[1].warnings, 2);
+        assert_eq!(
+            history.entries[1].error.as_deref(),
+            Some("Build failed: oops")
+        );
+
+        // The oldest line is the one pruned
+        HistoryService::append(&path, &HistoryEntry::new(HistoryOperation::Generate), 3).unwrap();
+        let history = HistoryService::read(&path).unwrap();
+        assert_eq!(history.skipped_lines, 0);
+        assert_eq!(history.entries.len(), 3);
+        assert_eq!(history.entries[0].warnings, 1);
+    }
+
+    #[test]
+    fn test_read_missing_file() {
+        let temp = TempDir::new().unwrap();
+        let history = HistoryService::read(&temp.path().join("missing.jsonl")).unwrap();
+        assert!(history.entries.is_empty());
+    }
+
+    #[test]
+    fn test_entry_summary() {
+        let mut entry = HistoryEntry::new(HistoryOperation::Build);
+        entry.warnings = 2;
+        entry.firmware_size = Some(FirmwareSize {
+            flash_used: 900,
+            flash_max: Some(1000),
+            ram_used: None,
+            ram_max: None,
+        });
+        assert_eq!(entry.summary(), "build ✓ · 2 warning(s) · Flash 90%");
+        let mut entry = HistoryEntry::new(HistoryOperation::Generate);
+        entry.fail("Validation failed");
+        assert_eq!(entry.summary(), "generate ✗ · Validation failed");
+    }
+}
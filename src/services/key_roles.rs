@@ -0,0 +1,326 @@
+//! Key roles: which keys are thumbs, home row, or pinky columns.
+//!
+//! Roles are inferred from the visual grid: a board whose columns have a gap
+//! is split into two halves, the lowest row of each half is its thumb row,
+//! the middle of the remaining rows is the home row, and the outer columns
+//! are pinky columns. Boards the heuristics get wrong can be corrected per
+//! keyboard and layout variant; the corrections are stored in
+//! `<config dir>/key_roles/<keyboard>__<variant>.toml`:
+//!
+//! ```toml
+//! [keys."3,5"]
+//! thumb = true
+//! ```
+
+use anyhow::{Context, Result};
+use serde::{Deserialize, Serialize};
+use std::collections::{BTreeMap, BTreeSet, HashMap};
+use std::fs;
+use std::path::{Path, PathBuf};
+
+use crate::config::Config;
+use crate::models::{KeyRole, KeyboardGeometry, Layout, Position};
+use crate::services::layouts::sanitize_filename;
+
+/// Columns a half needs before its two outer columns are both pinky columns.
+const WIDE_HALF_COLUMNS: usize = 6;
+
+/// Infers the role of every key from its visual position.
+#[must_use]
+pub fn infer_roles(positions: &[Position]) -> HashMap<Position, KeyRole> {
+    let mut roles: HashMap<Position, KeyRole> = positions
+        .iter()
+        .map(|&pos| (pos, KeyRole::default()))
+        .collect();
+
+    let columns: BTreeSet<u8> = positions.iter().map(|pos| pos.col).collect();
+    // The widest gap between used columns splits the halves of a split board
+    let split = columns
+        .iter()
+        .zip(columns.iter().skip(1))
+        .filter(|(a, b)| **b - **a > 1)
+        .max_by_key(|(a, b)| **b - **a)
+        .map(|(_, b)| *b);
+
+    let halves: Vec<(Vec<Position>, Side)> = match split {
+        Some(split) => {
+            let (left, right) = positions.iter().partition(|pos| pos.col < split);
+            vec![(left, Side::Left), (right, Side::Right)]
+        }
+        None => vec![(positions.to_vec(), Side::Both)],
+    };
+    for (keys, side) in halves {
+        infer_half(&keys, side, &mut roles);
+    }
+    roles
+}
+
+/// Which edges of a half face outwards.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Side {
+    Left,
+    Right,
+    Both,
+}
+
+/// Tags the thumb row, home row and pinky columns of one half.
+fn infer_half(keys: &[Position], side: Side, roles: &mut HashMap<Position, KeyRole>) {
+    let rows: BTreeSet<u8> = keys.iter().map(|pos| pos.row).collect();
+    // A single row has no thumb row to tell apart from the finger rows
+    let thumb_row = (rows.len() > 1)
+        .then(|| rows.iter().next_back().copied())
+        .flatten();
+    let finger_rows: Vec<u8> = rows
+        .iter()
+        .copied()
+        .filter(|&row| Some(row) != thumb_row)
+        .collect();
+    let home_row = finger_rows.get(finger_rows.len() / 2).copied();
+
+    let finger_columns: Vec<u8> = keys
+        .iter()
+        .filter(|pos| Some(pos.row) != thumb_row)
+        .map(|pos| pos.col)
+        .collect::<BTreeSet<_>>()
+        .into_iter()
+        .collect();
+    let outer = if finger_columns.len() >= WIDE_HALF_COLUMNS {
+        2
+    } else {
+        1
+    };
+    let mut pinky_columns: BTreeSet<u8> = BTreeSet::new();
+    if matches!(side, Side::Left | Side::Both) {
+        pinky_columns.extend(finger_columns.iter().take(outer).copied());
+    }
+    if matches!(side, Side::Right | Side::Both) {
+        pinky_columns.extend(finger_columns.iter().rev().take(outer).copied());
+    }
+
+    for pos in keys {
+        let role = roles.entry(*pos).or_default();
+        role.thumb = Some(pos.row) == thumb_row;
+        role.home_row = Some(pos.row) == home_row;
+        role.pinky = !role.thumb && pinky_columns.contains(&pos.col);
+    }
+}
+
+/// Role corrections for one keyboard and layout variant.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct KeyRoleOverrides {
+    /// Role per key, keyed by `"row,col"` visual position
+    #[serde(default)]
+    keys: BTreeMap<String, KeyRole>,
+}
+
+impl KeyRoleOverrides {
+    /// File holding the overrides for `keyboard` and `layout_variant`.
+    pub fn path(keyboard: &str, layout_variant: &str) -> Result<PathBuf> {
+        Ok(Config::config_dir()?.join("key_roles").join(format!(
+            "{}__{}.toml",
+            sanitize_filename(keyboard),
+            sanitize_filename(layout_variant)
+        )))
+    }
+
+    /// Loads the overrides for `keyboard` and `layout_variant`.
+    pub fn load(keyboard: &str, layout_variant: &str) -> Result<Self> {
+        Self::load_from(&Self::path(keyboard, layout_variant)?)
+    }
+
+    /// Loads overrides from `path`. A missing file has no overrides.
+    pub fn load_from(path: &Path) -> Result<Self> {
+        if !path.exists() {
+            return Ok(Self::default());
+        }
+        let content = fs::read_to_string(path)
+            .with_context(|| format!("Failed to read key roles: {}", path.display()))?;
+        toml::from_str(&content)
+            .with_context(|| format!("Failed to parse key roles: {}", path.display()))
+    }
+
+    /// Saves the overrides for `keyboard` and `layout_variant`.
+    pub fn save(&self, keyboard: &str, layout_variant: &str) -> Result<()> {
+        self.save_to(&Self::path(keyboard, layout_variant)?)
+    }
+
+    /// Saves the overrides to `path`, creating its directory.
+    pub fn save_to(&self, path: &Path) -> Result<()> {
+        if let Some(dir) = path.parent() {
+            fs::create_dir_all(dir).with_context(|| {
+                format!("Failed to create key roles directory: {}", dir.display())
+            })?;
+        }
+        let content = toml::to_string_pretty(self).context("Failed to serialize key roles")?;
+        crate::atomic_write::write(path, &content)
+            .with_context(|| format!("Failed to write key roles: {}", path.display()))
+    }
+
+    /// Overrides the role of the key at `pos`.
+    pub fn set(&mut self, pos: Position, role: KeyRole) {
+        self.keys.insert(position_key(pos), role);
+    }
+
+    /// Drops the override of the key at `pos`, returning whether it had one.
+    pub fn remove(&mut self, pos: Position) -> bool {
+        self.keys.remove(&position_key(pos)).is_some()
+    }
+
+    /// Replaces inferred roles with the overridden ones.
+    pub fn apply(&self, roles: &mut HashMap<Position, KeyRole>) {
+        for (key, role) in &self.keys {
+            if let Ok(pos) = key.parse::<Position>() {
+                if let Some(slot) = roles.get_mut(&pos) {
+                    *slot = *role;
+                }
+            }
+        }
+    }
+}
+
+/// TOML key of a position.
+fn position_key(pos: Position) -> String {
+    format!("{},{}", pos.row, pos.col)
+}
+
+/// Infers the roles of `positions` and applies `overrides`.
+#[must_use]
+pub fn resolve_roles(
+    positions: &[Position],
+    overrides: &KeyRoleOverrides,
+) -> HashMap<Position, KeyRole> {
+    let mut roles = infer_roles(positions);
+    overrides.apply(&mut roles);
+    roles
+}
+
+/// Sets `KeyGeometry.role` on every key of `geometry`.
+pub fn assign_roles(geometry: &mut KeyboardGeometry, overrides: &KeyRoleOverrides) {
+    let positions: Vec<Position> = geometry
+        .keys
+        .iter()
+        .map(crate::models::KeyGeometry::visual_position)
+        .collect();
+    let roles = resolve_roles(&positions, overrides);
+    for key in &mut geometry.keys {
+        key.role = roles
+            .get(&key.visual_position())
+            .copied()
+            .unwrap_or_default();
+    }
+}
+
+/// Roles for a layout without a keyboard geometry.
+///
+/// The base layer's keys stand in for the geometry. Overrides are loaded for
+/// the layout's keyboard and variant; unreadable overrides are ignored.
+#[must_use]
+pub fn layout_roles(layout: &Layout) -> HashMap<Position, KeyRole> {
+    let positions: Vec<Position> = layout
+        .layers
+        .first()
+        .map(|layer| layer.keys.iter().map(|key| key.position).collect())
+        .unwrap_or_default();
+    let overrides = match (&layout.metadata.keyboard, &layout.metadata.layout_variant) {
+        (Some(keyboard), Some(variant)) => {
+            KeyRoleOverrides::load(keyboard, variant).unwrap_or_default()
+        }
+        _ => KeyRoleOverrides::default(),
+    };
+    resolve_roles(&positions, &overrides)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Positions of a grid with `rows` rows and the given columns
+    fn grid(rows: u8, columns: &[u8]) -> Vec<Position> {
+        (0..rows)
+            .flat_map(|row| columns.iter().map(move |&col| Position::new(row, col)))
+            .collect()
+    }
+
+    fn tagged(roles: &HashMap<Position, KeyRole>, pick: fn(&KeyRole) -> bool) -> Vec<Position> {
+        let mut positions: Vec<Position> = roles
+            .iter()
+            .filter(|(_, role)| pick(role))
+            .map(|(pos, _)| *pos)
+            .collect();
+        positions.sort_by_key(|pos| (pos.row, pos.col));
+        positions
+    }
+
+    #[test]
+    fn test_infer_split_board() {
+        // 3x6 finger rows per half, three thumbs per half, halves 2 columns apart
+        let mut positions = grid(3, &[0, 1, 2, 3, 4, 5, 8, 9, 10, 11, 12, 13]);
+        positions.extend([3, 4, 5, 8, 9, 10].map(|col| Position::new(3, col)));
+        let roles = infer_roles(&positions);
+
+        assert_eq!(
+            tagged(&roles, |r| r.thumb),
+            [3, 4, 5, 8, 9, 10].map(|col| Position::new(3, col))
+        );
+        assert!(tagged(&roles, |r| r.home_row)
+            .iter()
+            .all(|pos| pos.row == 1));
+        let pinky_columns: BTreeSet<u8> =
+            tagged(&roles, |r| r.pinky).iter().map(|p| p.col).collect();
+        assert_eq!(pinky_columns, BTreeSet::from([0, 1, 12, 13]));
+        assert!(roles[&Position::new(1, 0)].home_row && roles[&Position::new(1, 0)].pinky);
+    }
+
+    #[test]
+    fn test_infer_unibody_board() {
+        // Number row, three letter rows and a bottom row
+        let roles = infer_roles(&grid(5, &[0, 1, 2, 3, 4]));
+
+        assert_eq!(
+            tagged(&roles, |r| r.thumb),
+            (0..5).map(|col| Position::new(4, col)).collect::<Vec<_>>()
+        );
+        assert!(tagged(&roles, |r| r.home_row)
+            .iter()
+            .all(|pos| pos.row == 2));
+        let pinky_columns: BTreeSet<u8> =
+            tagged(&roles, |r| r.pinky).iter().map(|p| p.col).collect();
+        assert_eq!(pinky_columns, BTreeSet::from([0, 4]));
+    }
+
+    #[test]
+    fn test_single_row_has_no_thumbs() {
+        let roles = infer_roles(&grid(1, &[0, 1, 2]));
+        assert!(tagged(&roles, |r| r.thumb).is_empty());
+        assert_eq!(tagged(&roles, |r| r.home_row).len(), 3);
+    }
+
+    #[test]
+    fn test_overrides_round_trip_and_apply() {
+        let temp = tempfile::TempDir::new().unwrap();
+        let path = temp.path().join("key_roles").join("board__layout.toml");
+
+        let mut overrides = KeyRoleOverrides::default();
+        let thumb = KeyRole {
+            thumb: true,
+            ..KeyRole::default()
+        };
+        overrides.set(Position::new(0, 2), thumb);
+        overrides.save_to(&path).unwrap();
+
+        let loaded = KeyRoleOverrides::load_from(&path).unwrap();
+        assert_eq!(loaded, overrides);
+
+        let roles = resolve_roles(&grid(2, &[0, 1, 2]), &loaded);
+        assert_eq!(roles[&Position::new(0, 2)], thumb);
+        assert!(roles[&Position::new(1, 2)].thumb);
+
+        let mut loaded = loaded;
+        assert!(loaded.remove(Position::new(0, 2)));
+        assert_eq!(loaded, KeyRoleOverrides::default());
+        assert_eq!(
+            KeyRoleOverrides::load_from(&temp.path().join("missing.toml")).unwrap(),
+            KeyRoleOverrides::default()
+        );
+    }
+}
@@ -0,0 +1,389 @@
+//! Keycode grids pasted onto a layer.
+//!
+//! A grid is a block of keycodes, either whitespace- or comma-separated rows
+//! (as copied from a `keymap.c` `LAYOUT(...)`) or a Markdown table (as copied
+//! from a layout file). It lands on a layer starting at a position:
+//!
+//! - Plain rows fill the keys of each row left to right, skipping the gaps
+//!   between split halves.
+//! - Markdown columns keep their place, so empty cells stay gaps, exactly as
+//!   in layout files.
+//!
+//! Every cell is checked before anything changes, so callers can preview the
+//! result and apply it in one step.
+
+use crate::models::{Layer, Position};
+use crate::parser::layout::parse_keycode_syntax;
+use anyhow::{bail, Result};
+use std::collections::HashSet;
+
+/// How a grid was written.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum GridFormat {
+    /// Rows of keycodes separated by whitespace or commas
+    Plain,
+    /// Markdown table rows
+    Markdown,
+}
+
+/// Parsed grid of keycodes.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct KeycodeGrid {
+    /// How the grid was written
+    pub format: GridFormat,
+    /// Cells by row; `None` is an empty Markdown cell (a gap)
+    pub rows: Vec<Vec<Option<String>>>,
+}
+
+impl KeycodeGrid {
+    /// Parses a plain or Markdown grid.
+    ///
+    /// Blank lines are skipped. A Markdown header row (one followed by a
+    /// `|---|` separator) is dropped, and `{#color}`/`@category` suffixes
+    /// copied from a layout file are stripped down to the keycode.
+    pub fn parse(text: &str) -> Result<Self> {
+        let lines: Vec<(usize, &str)> = text
+            .lines()
+            .enumerate()
+            .map(|(i, line)| (i + 1, line.trim()))
+            .filter(|(_, line)| !line.is_empty())
+            .collect();
+        let Some((_, first)) = lines.first() else {
+            bail!("No keycodes found");
+        };
+
+        let grid = if first.starts_with('|') {
+            let mut rows = Vec::new();
+            for (number, line) in &lines {
+                if !line.starts_with('|') {
+                    bail!("Line {number} is not a table row: {line}");
+                }
+                if is_separator_row(line) {
+                    // Everything above the separator is the header
+                    rows.clear();
+                    continue;
+                }
+                rows.push(split_table_row(line));
+            }
+            Self {
+                format: GridFormat::Markdown,
+                rows,
+            }
+        } else {
+            Self {
+                format: GridFormat::Plain,
+                rows: lines
+                    .iter()
+                    .map(|(_, line)| split_plain_row(line).into_iter().map(Some).collect())
+                    .collect(),
+            }
+        };
+
+        if grid.rows.iter().flatten().all(Option::is_none) {
+            bail!("No keycodes found");
+        }
+        Ok(grid)
+    }
+}
+
+/// Whether a cell lands on a key.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum CellStatus {
+    /// Valid keycode on an existing key
+    Ready,
+    /// Keycode not in the keycode database
+    InvalidKeycode,
+    /// No key at the target position (past the end of a row, a gap, or off the board)
+    NoKey,
+}
+
+/// One grid cell and where it lands.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct GridCell {
+    /// Row in the grid
+    pub row: usize,
+    /// Column in the grid
+    pub col: usize,
+    /// Keycode to assign
+    pub keycode: String,
+    /// Key the cell lands on
+    pub target: Option<Position>,
+    /// Keycode currently on the target key
+    pub replaces: Option<String>,
+    /// Whether the cell can be applied
+    pub status: CellStatus,
+}
+
+/// A grid placed onto a layer, ready to preview and apply.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct GridPlan {
+    /// Position of the grid's top-left cell
+    pub start: Position,
+    /// Non-empty cells in row order
+    pub cells: Vec<GridCell>,
+}
+
+impl GridPlan {
+    /// Places `grid` onto `layer` with its first cell at `start`.
+    ///
+    /// `positions` are the keys the grid may land on (the visual mapping in
+    /// the editor, the layer's keys on the command line); `is_valid` checks
+    /// a keycode against the keycode database.
+    pub fn new(
+        grid: &KeycodeGrid,
+        layer: &Layer,
+        positions: &[Position],
+        start: Position,
+        is_valid: impl Fn(&str) -> bool,
+    ) -> Self {
+        let available: HashSet<Position> = positions
+            .iter()
+            .copied()
+            .filter(|pos| layer.get_key(*pos).is_some())
+            .collect();
+
+        let mut cells = Vec::new();
+        for (row, grid_row) in grid.rows.iter().enumerate() {
+            let target_row = u8::try_from(usize::from(start.row) + row).ok();
+            // Plain rows walk the row's keys from the start column
+            let mut row_keys: Vec<Position> = available
+                .iter()
+                .copied()
+                .filter(|pos| Some(pos.row) == target_row && pos.col >= start.col)
+                .collect();
+            row_keys.sort_by_key(|pos| pos.col);
+
+            for (col, cell) in grid_row.iter().enumerate() {
+                let Some(keycode) = cell else {
+                    continue;
+                };
+                let target = match grid.format {
+                    GridFormat::Plain => row_keys.get(col).copied(),
+                    GridFormat::Markdown => target_row
+                        .zip(u8::try_from(usize::from(start.col) + col).ok())
+                        .map(|(r, c)| Position::new(r, c))
+                        .filter(|pos| available.contains(pos)),
+                };
+                let status = if !is_valid(keycode) {
+                    CellStatus::InvalidKeycode
+                } else if target.is_none() {
+                    CellStatus::NoKey
+                } else {
+                    CellStatus::Ready
+                };
+                cells.push(GridCell {
+                    row,
+                    col,
+                    keycode: keycode.clone(),
+                    target,
+                    replaces: target
+                        .and_then(|pos| layer.get_key(pos))
+                        .map(|key| key.keycode.clone()),
+                    status,
+                });
+            }
+        }
+        Self { start, cells }
+    }
+
+    /// Cells that will be applied.
+    pub fn ready(&self) -> impl Iterator<Item = &GridCell> {
+        self.cells
+            .iter()
+            .filter(|cell| cell.status == CellStatus::Ready)
+    }
+
+    /// Number of cells with the given status.
+    #[must_use]
+    pub fn count(&self, status: CellStatus) -> usize {
+        self.cells
+            .iter()
+            .filter(|cell| cell.status == status)
+            .count()
+    }
+
+    /// Assigns every ready cell's keycode to its key, keeping colors and
+    /// categories. Returns the number of keys changed.
+    pub fn apply(&self, layer: &mut Layer) -> usize {
+        let mut applied = 0;
+        for cell in self.ready() {
+            if let Some(key) = cell.target.and_then(|pos| layer.get_key_mut(pos)) {
+                key.keycode.clone_from(&cell.keycode);
+                applied += 1;
+            }
+        }
+        applied
+    }
+}
+
+/// Returns true for a Markdown separator row such as `|---|:--:|`.
+fn is_separator_row(line: &str) -> bool {
+    line.contains('-')
+        && line
+            .chars()
+            .all(|c| matches!(c, '|' | '-' | ':' | ' ' | '\t'))
+}
+
+/// Splits a Markdown row into cells, keeping empty cells as gaps.
+fn split_table_row(line: &str) -> Vec<Option<String>> {
+    let inner = line.strip_prefix('|').unwrap_or(line);
+    let inner = inner.strip_suffix('|').unwrap_or(inner);
+    inner
+        .split('|')
+        .map(str::trim)
+        .map(|cell| (!cell.is_empty()).then(|| strip_annotations(cell)))
+        .collect()
+}
+
+/// Splits a plain row on whitespace and commas outside parentheses, so
+/// `LT(1, KC_SPC)` stays one keycode.
+fn split_plain_row(line: &str) -> Vec<String> {
+    let mut cells = Vec::new();
+    let mut current = String::new();
+    let mut depth = 0usize;
+    for c in line.chars() {
+        match c {
+            '(' => {
+                depth += 1;
+                current.push(c);
+            }
+            ')' => {
+                depth = depth.saturating_sub(1);
+                current.push(c);
+            }
+            c if depth == 0 && (c.is_whitespace() || c == ',') => {
+                if !current.is_empty() {
+                    cells.push(strip_annotations(&current));
+                    current.clear();
+                }
+            }
+            c => current.push(c),
+        }
+    }
+    if !current.is_empty() {
+        cells.push(strip_annotations(&current));
+    }
+    cells
+}
+
+/// Drops layout-file `{#color}` and `@category` suffixes from a cell.
+fn strip_annotations(cell: &str) -> String {
+    parse_keycode_syntax(cell, 0, 0).map_or_else(|_| cell.to_string(), |key| key.keycode)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::models::{KeyDefinition, RgbColor};
+
+    /// Split layer: columns 0-2 and 4-6 on two rows, with a gap at column 3.
+    fn split_layer() -> Layer {
+        let mut layer = Layer::new(0, "Base", RgbColor::new(0, 0, 0)).unwrap();
+        for row in 0..2 {
+            for col in [0, 1, 2, 4, 5, 6] {
+                layer
+                    .add_key(KeyDefinition::new(Position::new(row, col), "KC_TRNS"))
+                    .unwrap();
+            }
+        }
+        layer
+    }
+
+    fn positions(layer: &Layer) -> Vec<Position> {
+        layer.keys.iter().map(|key| key.position).collect()
+    }
+
+    fn valid(keycode: &str) -> bool {
+        keycode.starts_with("KC_") || keycode.starts_with("LT(")
+    }
+
+    #[test]
+    fn test_parse_plain_rows() {
+        let grid = KeycodeGrid::parse("KC_Q, KC_W  LT(1, KC_SPC),\n\n  KC_A\tKC_S\n").unwrap();
+        assert_eq!(grid.format, GridFormat::Plain);
+        assert_eq!(
+            grid.rows,
+            vec![
+                vec![
+                    Some("KC_Q".to_string()),
+                    Some("KC_W".to_string()),
+                    Some("LT(1, KC_SPC)".to_string())
+                ],
+                vec![Some("KC_A".to_string()), Some("KC_S".to_string())],
+            ]
+        );
+    }
+
+    #[test]
+    fn test_parse_markdown_drops_header_and_annotations() {
+        let text = "| C0 | C1 | C2 |\n|----|:--:|----|\n| KC_A{#FF0000} |  | KC_B@nav |\n";
+        let grid = KeycodeGrid::parse(text).unwrap();
+        assert_eq!(grid.format, GridFormat::Markdown);
+        assert_eq!(
+            grid.rows,
+            vec![vec![
+                Some("KC_A".to_string()),
+                None,
+                Some("KC_B".to_string())
+            ]]
+        );
+
+        assert!(KeycodeGrid::parse("| KC_A |\nKC_B").is_err());
+        assert!(KeycodeGrid::parse(" \n").is_err());
+    }
+
+    #[test]
+    fn test_plain_rows_skip_split_gap() {
+        let layer = split_layer();
+        let grid = KeycodeGrid::parse("KC_A KC_B KC_C KC_D KC_E\nKC_F BAD").unwrap();
+        let plan = GridPlan::new(
+            &grid,
+            &layer,
+            &positions(&layer),
+            Position::new(0, 2),
+            valid,
+        );
+
+        let targets: Vec<Option<Position>> = plan.cells.iter().map(|c| c.target).collect();
+        assert_eq!(
+            targets,
+            vec![
+                Some(Position::new(0, 2)),
+                Some(Position::new(0, 4)),
+                Some(Position::new(0, 5)),
+                Some(Position::new(0, 6)),
+                None,
+                Some(Position::new(1, 2)),
+                Some(Position::new(1, 4)),
+            ]
+        );
+        assert_eq!(plan.count(CellStatus::Ready), 5);
+        assert_eq!(plan.count(CellStatus::NoKey), 1);
+        assert_eq!(plan.count(CellStatus::InvalidKeycode), 1);
+        assert_eq!(plan.cells[0].replaces.as_deref(), Some("KC_TRNS"));
+    }
+
+    #[test]
+    fn test_markdown_columns_keep_their_place() {
+        let mut layer = split_layer();
+        let grid = KeycodeGrid::parse("| KC_A | KC_B | KC_C | KC_D | KC_E |").unwrap();
+        let plan = GridPlan::new(
+            &grid,
+            &layer,
+            &positions(&layer),
+            Position::new(1, 0),
+            valid,
+        );
+
+        // Column 3 is the gap between the halves
+        assert_eq!(plan.cells[3].status, CellStatus::NoKey);
+        assert_eq!(plan.cells[4].target, Some(Position::new(1, 4)));
+
+        assert_eq!(plan.apply(&mut layer), 4);
+        assert_eq!(layer.get_key(Position::new(1, 4)).unwrap().keycode, "KC_E");
+        assert_eq!(
+            layer.get_key(Position::new(0, 0)).unwrap().keycode,
+            "KC_TRNS"
+        );
+    }
+}
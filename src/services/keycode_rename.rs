@@ -0,0 +1,231 @@
+//! Renaming a keycode everywhere it appears in a layout.
+//!
+//! Keycodes are rewritten token by token, so a rename of `MY_EMOJI` to
+//! `EMOJI_PICK` updates bare keys as well as parameters such as
+//! `MT(MOD_LSFT, MY_EMOJI)` or `TD(MY_EMOJI)`, but leaves `MY_EMOJI_2` alone.
+//! Tap dance actions and orphaned keys are rewritten too; a tap dance whose
+//! name matches is renamed so its `TD()` references stay valid.
+
+use crate::models::Layout;
+use anyhow::{bail, Result};
+use serde::Serialize;
+
+/// Number of places a rename changed in one layout.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize)]
+pub struct RenameCounts {
+    /// Keys on layers
+    pub keys: usize,
+    /// Tap dance actions (name or single/double/hold keycodes)
+    pub tap_dances: usize,
+    /// Orphaned keys kept from a previous keyboard
+    pub orphaned_keys: usize,
+}
+
+impl RenameCounts {
+    /// Total number of changed entries.
+    #[must_use]
+    pub const fn total(&self) -> usize {
+        self.keys + self.tap_dances + self.orphaned_keys
+    }
+}
+
+/// Checks that a name can be used as a keycode token (a C identifier).
+///
+/// # Errors
+///
+/// Returns an error if the name is empty or contains anything other than
+/// ASCII letters, digits, and underscores, or starts with a digit.
+pub fn validate_keycode_name(name: &str) -> Result<()> {
+    let Some(first) = name.chars().next() else {
+        bail!("Keycode name cannot be empty");
+    };
+    if first.is_ascii_digit() || !name.chars().all(is_identifier_char) {
+        bail!("Invalid keycode name '{name}': use letters, digits, and underscores");
+    }
+    Ok(())
+}
+
+/// Replaces every identifier token equal to `from` with `to`.
+///
+/// Returns `None` if the keycode does not contain `from`.
+#[must_use]
+pub fn replace_keycode_token(keycode: &str, from: &str, to: &str) -> Option<String> {
+    map_keycode_tokens(keycode, |token| (token == from).then_some(to))
+}
+
+/// Replaces each identifier token for which `replace` returns a new name.
+///
+/// Returns `None` if no token was replaced.
+#[must_use]
+pub fn map_keycode_tokens<'a>(
+    keycode: &str,
+    replace: impl Fn(&str) -> Option<&'a str>,
+) -> Option<String> {
+    let mut result = String::with_capacity(keycode.len());
+    let mut changed = false;
+    let mut rest = keycode;
+
+    while !rest.is_empty() {
+        let token_len = rest
+            .find(|c: char| !is_identifier_char(c))
+            .unwrap_or(rest.len());
+        if token_len == 0 {
+            // Copy one separator character (parenthesis, comma, space, ...)
+            let ch = rest.chars().next().unwrap_or_default();
+            result.push(ch);
+            rest = &rest[ch.len_utf8()..];
+            continue;
+        }
+        let (token, tail) = rest.split_at(token_len);
+        if let Some(replacement) = replace(token) {
+            result.push_str(replacement);
+            changed = true;
+        } else {
+            result.push_str(token);
+        }
+        rest = tail;
+    }
+
+    changed.then_some(result)
+}
+
+/// Renames keycode `from` to `to` throughout a layout.
+#[must_use]
+pub fn rename_keycode(layout: &mut Layout, from: &str, to: &str) -> RenameCounts {
+    let mut counts = RenameCounts::default();
+    let rewrite =
+        |keycode: &mut String| replace_keycode_token(keycode, from, to).map(|new| *keycode = new);
+
+    for layer in &mut layout.layers {
+        for key in &mut layer.keys {
+            if rewrite(&mut key.keycode).is_some() {
+                counts.keys += 1;
+            }
+        }
+    }
+
+    for tap_dance in &mut layout.tap_dances {
+        let mut changed = false;
+        if tap_dance.name == from {
+            tap_dance.name = to.to_string();
+            changed = true;
+        }
+        changed |= rewrite(&mut tap_dance.single_tap).is_some();
+        for keycode in [&mut tap_dance.double_tap, &mut tap_dance.hold]
+            .into_iter()
+            .flatten()
+        {
+            changed |= rewrite(keycode).is_some();
+        }
+        if changed {
+            counts.tap_dances += 1;
+        }
+    }
+
+    for orphan in &mut layout.orphaned_keys {
+        if rewrite(&mut orphan.keycode).is_some() {
+            counts.orphaned_keys += 1;
+        }
+    }
+
+    counts
+}
+
+const fn is_identifier_char(c: char) -> bool {
+    c.is_ascii_alphanumeric() || c == '_'
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::models::{KeyDefinition, Layer, OrphanedKey, Position, RgbColor, TapDanceAction};
+
+    #[test]
+    fn test_replace_keycode_token() {
+        assert_eq!(
+            replace_keycode_token("MY_EMOJI", "MY_EMOJI", "EMOJI_PICK").as_deref(),
+            Some("EMOJI_PICK")
+        );
+        assert_eq!(
+            replace_keycode_token("MT(MOD_LSFT, MY_EMOJI)", "MY_EMOJI", "EMOJI_PICK").as_deref(),
+            Some("MT(MOD_LSFT, EMOJI_PICK)")
+        );
+        assert_eq!(
+            replace_keycode_token("LT(1,MY_EMOJI)", "MY_EMOJI", "X").as_deref(),
+            Some("LT(1,X)")
+        );
+        // Partial matches are not renamed
+        assert_eq!(replace_keycode_token("MY_EMOJI_2", "MY_EMOJI", "X"), None);
+        assert_eq!(replace_keycode_token("KC_A", "MY_EMOJI", "X"), None);
+    }
+
+    #[test]
+    fn test_validate_keycode_name() {
+        assert!(validate_keycode_name("EMOJI_PICK").is_ok());
+        assert!(validate_keycode_name("").is_err());
+        assert!(validate_keycode_name("1KEY").is_err());
+        assert!(validate_keycode_name("MT(X)").is_err());
+    }
+
+    #[test]
+    fn test_rename_keycode_everywhere() {
+        let mut layout = Layout::new("Rename").unwrap();
+        let mut layer = Layer::new(0, "Base", RgbColor::new(255, 255, 255)).unwrap();
+        layer
+            .add_key(KeyDefinition::new(Position::new(0, 0), "MY_EMOJI"))
+            .unwrap();
+        layer
+            .add_key(KeyDefinition::new(
+                Position::new(0, 1),
+                "MT(MOD_LCTL, MY_EMOJI)",
+            ))
+            .unwrap();
+        layer
+            .add_key(KeyDefinition::new(Position::new(0, 2), "TD(MY_EMOJI)"))
+            .unwrap();
+        layer
+            .add_key(KeyDefinition::new(Position::new(0, 3), "KC_A"))
+            .unwrap();
+        layout.add_layer(layer).unwrap();
+        layout.tap_dances.push(TapDanceAction {
+            name: "MY_EMOJI".to_string(),
+            single_tap: "KC_E".to_string(),
+            double_tap: Some("MY_EMOJI".to_string()),
+            hold: None,
+            tapping_term_ms: None,
+            on_each_tap: false,
+        });
+        layout.orphaned_keys.push(OrphanedKey {
+            layer: 0,
+            position: Position::new(5, 5),
+            keycode: "MY_EMOJI".to_string(),
+        });
+
+        let counts = rename_keycode(&mut layout, "MY_EMOJI", "EMOJI_PICK");
+
+        assert_eq!(counts.keys, 3);
+        assert_eq!(counts.tap_dances, 1);
+        assert_eq!(counts.orphaned_keys, 1);
+        assert_eq!(counts.total(), 5);
+        let keycodes: Vec<&str> = layout.layers[0]
+            .keys
+            .iter()
+            .map(|k| k.keycode.as_str())
+            .collect();
+        assert_eq!(
+            keycodes,
+            [
+                "EMOJI_PICK",
+                "MT(MOD_LCTL, EMOJI_PICK)",
+                "TD(EMOJI_PICK)",
+                "KC_A"
+            ]
+        );
+        assert_eq!(layout.tap_dances[0].name, "EMOJI_PICK");
+        assert_eq!(
+            layout.tap_dances[0].double_tap.as_deref(),
+            Some("EMOJI_PICK")
+        );
+        assert!(layout.validate_tap_dances().is_ok());
+    }
+}
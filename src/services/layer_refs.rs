@@ -4,9 +4,13 @@
 //! across the keyboard layout, enabling features like:
 //! - Displaying inbound references when editing a layer
 //! - Warning when non-transparent keys might conflict with hold-to-layer keys
+//! - Moving layers while keeping their numeric references pointed at them
+//! - Deleting layers without leaving references to them behind
 
-use crate::models::{Layer, Position};
+use crate::models::{Layer, Layout, Position, TapDanceAction};
+use anyhow::{bail, Result};
 use std::collections::HashMap;
+use std::fmt;
 
 /// Type of layer reference (how a key activates another layer)
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
@@ -171,7 +175,7 @@ pub fn parse_layer_keycode(keycode: &str) -> Option<(LayerRefTarget, LayerRefKin
 /// use lazyqmk::services::layer_refs::build_layer_ref_index;
 ///
 /// let mut layer0 = Layer::new(0, "Base", RgbColor::new(255, 0, 0)).unwrap();
-/// layer0.add_key(KeyDefinition::new(Position::new(0, 0), "MO(1)"));
+/// layer0.add_key(KeyDefinition::new(Position::new(0, 0), "MO(1)")).unwrap();
 ///
 /// let layer1 = Layer::new(1, "Lower", RgbColor::new(0, 255, 0)).unwrap();
 ///
@@ -237,6 +241,411 @@ pub fn build_layer_ref_index(layers: &[Layer]) -> HashMap<usize, Vec<LayerRef>>
     index
 }
 
+/// Find keys on enabled layers that reference a disabled layer
+///
+/// Disabled layers are left out of generated firmware, so these keys would
+/// point at a layer that doesn't exist. Sorted by source layer and position.
+#[must_use]
+pub fn disabled_layer_refs(layers: &[Layer]) -> Vec<LayerRef> {
+    let mut refs: Vec<LayerRef> = build_layer_ref_index(layers)
+        .into_iter()
+        .filter(|(to_layer, _)| !layers[*to_layer].enabled)
+        .flat_map(|(_, refs)| refs)
+        .filter(|layer_ref| layers[layer_ref.from_layer].enabled)
+        .collect();
+    refs.sort_by_key(|r| (r.from_layer, r.position.row, r.position.col));
+    refs
+}
+
+/// Find keys whose numeric layer reference points past the last layer
+///
+/// Layers removed by editing the file by hand leave numeric keycodes such as
+/// `MO(3)` pointing past the last layer. `to_layer` holds
+/// the out-of-range index. Sorted by source layer and position.
+#[must_use]
+pub fn out_of_range_layer_refs(layers: &[Layer]) -> Vec<LayerRef> {
+    let mut refs = Vec::new();
+    for (from_layer, layer) in layers.iter().enumerate() {
+        for key in &layer.keys {
+            if let Some((LayerRefTarget::Index(to_layer), kind)) = parse_layer_keycode(&key.keycode)
+            {
+                if to_layer >= layers.len() {
+                    refs.push(LayerRef {
+                        from_layer,
+                        to_layer,
+                        position: key.position,
+                        kind,
+                        keycode: key.keycode.clone(),
+                    });
+                }
+            }
+        }
+    }
+    refs.sort_by_key(|r| (r.from_layer, r.position.row, r.position.col));
+    refs
+}
+
+/// Replace the numeric layer index of a layer keycode (`LT(3, KC_A)` ->
+/// `LT(2, KC_A)`)
+///
+/// Returns `None` if the keycode has no numeric layer parameter.
+#[must_use]
+pub fn with_layer_index(keycode: &str, index: usize) -> Option<String> {
+    let Some((LayerRefTarget::Index(_), _)) = parse_layer_keycode(keycode) else {
+        return None;
+    };
+    let open = keycode.find('(')?;
+    let end = open + 1 + keycode[open + 1..].find([',', ')'])?;
+    Some(format!("{}{index}{}", &keycode[..=open], &keycode[end..]))
+}
+
+/// Where a keycode sits in a layout.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum KeycodeSite {
+    /// A key on a layer
+    Key {
+        /// Index of the layer
+        layer: usize,
+        /// Position of the key
+        position: Position,
+    },
+    /// One turn direction of an encoder on a layer
+    Encoder {
+        /// Index of the layer
+        layer: usize,
+        /// Index of the encoder
+        index: usize,
+        /// Whether this is the clockwise keycode
+        clockwise: bool,
+    },
+    /// An action of a tap dance
+    TapDance {
+        /// Name of the tap dance
+        name: String,
+        /// Which action: "single tap", "double tap" or "hold"
+        action: &'static str,
+    },
+}
+
+impl KeycodeSite {
+    /// Layer the keycode is on; tap dances belong to no layer.
+    #[must_use]
+    pub const fn layer(&self) -> Option<usize> {
+        match self {
+            Self::Key { layer, .. } | Self::Encoder { layer, .. } => Some(*layer),
+            Self::TapDance { .. } => None,
+        }
+    }
+}
+
+impl fmt::Display for KeycodeSite {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Key { layer, position } => {
+                write!(f, "Layer {layer} ({},{})", position.row, position.col)
+            }
+            Self::Encoder {
+                layer,
+                index,
+                clockwise,
+            } => {
+                let turn = if *clockwise { "cw" } else { "ccw" };
+                write!(f, "Layer {layer} encoder {index} {turn}")
+            }
+            Self::TapDance { name, action } => write!(f, "Tap dance {name} {action}"),
+        }
+    }
+}
+
+/// Every keycode of the layout that can switch layers: keys, encoder turns
+/// and tap dance actions.
+fn layout_keycodes<'a>(
+    layers: &'a [Layer],
+    tap_dances: &'a [TapDanceAction],
+) -> Vec<(KeycodeSite, &'a String)> {
+    let mut keycodes = Vec::new();
+    for (number, layer) in layers.iter().enumerate() {
+        for key in &layer.keys {
+            let site = KeycodeSite::Key {
+                layer: number,
+                position: key.position,
+            };
+            keycodes.push((site, &key.keycode));
+        }
+        for (index, encoder) in layer.encoders.iter().enumerate() {
+            for (clockwise, keycode) in [(false, &encoder.ccw), (true, &encoder.cw)] {
+                let site = KeycodeSite::Encoder {
+                    layer: number,
+                    index,
+                    clockwise,
+                };
+                keycodes.push((site, keycode));
+            }
+        }
+    }
+    for dance in tap_dances {
+        let actions = [
+            ("single tap", Some(&dance.single_tap)),
+            ("double tap", dance.double_tap.as_ref()),
+            ("hold", dance.hold.as_ref()),
+        ];
+        for (action, keycode) in actions {
+            if let Some(keycode) = keycode {
+                let name = dance.name.clone();
+                keycodes.push((KeycodeSite::TapDance { name, action }, keycode));
+            }
+        }
+    }
+    keycodes
+}
+
+/// [`layout_keycodes`], for rewriting.
+fn layout_keycodes_mut(layout: &mut Layout) -> Vec<(KeycodeSite, &mut String)> {
+    let mut keycodes = Vec::new();
+    for (number, layer) in layout.layers.iter_mut().enumerate() {
+        for key in &mut layer.keys {
+            let site = KeycodeSite::Key {
+                layer: number,
+                position: key.position,
+            };
+            keycodes.push((site, &mut key.keycode));
+        }
+        for (index, encoder) in layer.encoders.iter_mut().enumerate() {
+            for (clockwise, keycode) in [(false, &mut encoder.ccw), (true, &mut encoder.cw)] {
+                let site = KeycodeSite::Encoder {
+                    layer: number,
+                    index,
+                    clockwise,
+                };
+                keycodes.push((site, keycode));
+            }
+        }
+    }
+    for dance in &mut layout.tap_dances {
+        let actions = [
+            ("single tap", Some(&mut dance.single_tap)),
+            ("double tap", dance.double_tap.as_mut()),
+            ("hold", dance.hold.as_mut()),
+        ];
+        for (action, keycode) in actions {
+            if let Some(keycode) = keycode {
+                let name = dance.name.clone();
+                keycodes.push((KeycodeSite::TapDance { name, action }, keycode));
+            }
+        }
+    }
+    keycodes
+}
+
+/// Whether `keycode` calls a layer function with an argument that is neither
+/// a layer number nor an `@id`, such as `MO(NAV)`: moving layers can't tell
+/// which layer it means.
+fn is_ambiguous_layer_keycode(keycode: &str) -> bool {
+    const LAYER_FUNCTIONS: [&str; 8] = ["MO(", "TG(", "TO(", "TT(", "OSL(", "DF(", "LT(", "LM("];
+    LAYER_FUNCTIONS
+        .iter()
+        .any(|function| keycode.starts_with(function))
+        && parse_layer_keycode(keycode).is_none()
+}
+
+/// Index a layer ends up at when layer `from` moves to index `to` and the
+/// layers in between shift to make room.
+#[must_use]
+pub const fn moved_layer_index(index: usize, from: usize, to: usize) -> usize {
+    if index == from {
+        to
+    } else if from < to && index > from && index <= to {
+        index - 1
+    } else if to < from && index >= to && index < from {
+        index + 1
+    } else {
+        index
+    }
+}
+
+/// Move layer `from` to index `to`, shifting the layers in between, and
+/// rewrite the numeric layer references (`MO(3)`, `LT(3, KC_X)`, ...) on
+/// keys, encoders and tap dances to follow their layers
+///
+/// `@id` references already follow their layer, and references past the
+/// last layer are left alone. Returns the number of keycodes rewritten.
+///
+/// # Errors
+///
+/// Returns an error if either index is out of range, the move would take
+/// the base layer away from index 0, or a layer keycode names its layer
+/// some other way (`MO(NAV)`), in which case nothing is changed.
+pub fn move_layer(layout: &mut Layout, from: usize, to: usize) -> Result<usize> {
+    let count = layout.layers.len();
+    if from >= count || to >= count {
+        bail!("Layer index out of range (layout has {count} layers)");
+    }
+    if from == 0 || to == 0 {
+        bail!("The base layer (layer 0) cannot be moved");
+    }
+    if from == to {
+        return Ok(0);
+    }
+
+    let keycodes = layout_keycodes_mut(layout);
+    if let Some((site, keycode)) = keycodes
+        .iter()
+        .find(|(_, keycode)| is_ambiguous_layer_keycode(keycode))
+    {
+        bail!("{site} holds {keycode}, which names no layer number or @id; fix it before moving layers");
+    }
+
+    let mut updated = 0;
+    for (_, keycode) in keycodes {
+        let Some((LayerRefTarget::Index(index), _)) = parse_layer_keycode(keycode) else {
+            continue;
+        };
+        let moved = moved_layer_index(index, from, to);
+        if index < count && moved != index {
+            if let Some(rewritten) = with_layer_index(keycode, moved) {
+                *keycode = rewritten;
+                updated += 1;
+            }
+        }
+    }
+
+    let layers = &mut layout.layers;
+    let layer = layers.remove(from);
+    layers.insert(to, layer);
+    for (number, layer) in layers.iter_mut().enumerate() {
+        layer.number = number as u8;
+    }
+    Ok(updated)
+}
+
+/// A keycode that switches to a given layer, see [`inbound_layer_refs`]
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct InboundRef {
+    /// Where the keycode is
+    pub site: KeycodeSite,
+    /// The keycode (e.g., "MO(1)", "LT(@id, KC_SPC)")
+    pub keycode: String,
+}
+
+impl InboundRef {
+    /// What [`delete_layer`] replaces the keycode with.
+    #[must_use]
+    pub fn replacement(&self) -> String {
+        cleared_keycode(&self.keycode)
+    }
+}
+
+/// What a reference to a deleted layer becomes: `LT(n, kc)` keeps its tap
+/// keycode, everything else turns into `KC_NO`.
+fn cleared_keycode(keycode: &str) -> String {
+    keycode
+        .strip_prefix("LT(")
+        .and_then(|inner| inner.strip_suffix(')'))
+        .and_then(|inner| inner.split_once(','))
+        .map(|(_, tap)| tap.trim())
+        .filter(|tap| !tap.is_empty())
+        .unwrap_or("KC_NO")
+        .to_string()
+}
+
+/// Whether `keycode` switches to layer `index`, whose id is `id`, either
+/// numerically or by `@id`.
+fn targets_layer(keycode: &str, index: usize, id: &str) -> bool {
+    match parse_layer_keycode(keycode) {
+        Some((LayerRefTarget::Index(target), _)) => target == index,
+        Some((LayerRefTarget::Uuid(target), _)) => target.strip_prefix('@') == Some(id),
+        None => false,
+    }
+}
+
+/// Find keycodes on keys and encoders of other layers, and in tap dances,
+/// that switch to layer `index`, numerically or by `@id`
+///
+/// These are the keycodes [`delete_layer`] clears. Listed layer by layer,
+/// then tap dances.
+#[must_use]
+pub fn inbound_layer_refs(
+    layers: &[Layer],
+    tap_dances: &[TapDanceAction],
+    index: usize,
+) -> Vec<InboundRef> {
+    let Some(layer) = layers.get(index) else {
+        return Vec::new();
+    };
+    layout_keycodes(layers, tap_dances)
+        .into_iter()
+        .filter(|(site, keycode)| {
+            site.layer() != Some(index) && targets_layer(keycode, index, &layer.id)
+        })
+        .map(|(site, keycode)| InboundRef {
+            site,
+            keycode: keycode.clone(),
+        })
+        .collect()
+}
+
+/// What [`delete_layer`] changed
+#[derive(Debug, Clone)]
+pub struct LayerDeletion {
+    /// The removed layer
+    pub layer: Layer,
+    /// Keycodes that referenced the removed layer and were cleared
+    pub cleared: usize,
+    /// Numeric references to later layers shifted down to follow them
+    pub renumbered: usize,
+}
+
+/// Delete layer `index`, clearing the keycodes on keys, encoders and tap
+/// dances that switch to it and shifting numeric references to the layers
+/// after it down by one
+///
+/// A cleared `LT(n, kc)` keeps its tap keycode `kc`; other cleared
+/// references become `KC_NO`. `@id` references to other layers already
+/// follow them, and references past the last layer are left alone.
+///
+/// # Errors
+///
+/// Returns an error if `index` is out of range or the layer is the only one.
+pub fn delete_layer(layout: &mut Layout, index: usize) -> Result<LayerDeletion> {
+    let count = layout.layers.len();
+    if index >= count {
+        bail!("Layer index out of range (layout has {count} layers)");
+    }
+    if count == 1 {
+        bail!("Cannot delete the last layer");
+    }
+
+    let deleted_id = layout.layers[index].id.clone();
+    let (mut cleared, mut renumbered) = (0, 0);
+    for (site, keycode) in layout_keycodes_mut(layout) {
+        if site.layer() == Some(index) {
+            continue;
+        }
+        if targets_layer(keycode, index, &deleted_id) {
+            *keycode = cleared_keycode(keycode);
+            cleared += 1;
+        } else if let Some((LayerRefTarget::Index(target), _)) = parse_layer_keycode(keycode) {
+            if target > index && target < count {
+                if let Some(shifted) = with_layer_index(keycode, target - 1) {
+                    *keycode = shifted;
+                    renumbered += 1;
+                }
+            }
+        }
+    }
+
+    let layers = &mut layout.layers;
+    let layer = layers.remove(index);
+    for (number, layer) in layers.iter_mut().enumerate() {
+        layer.number = number as u8;
+    }
+    Ok(LayerDeletion {
+        layer,
+        cleared,
+        renumbered,
+    })
+}
+
 /// Check if a keycode is transparent (allows fallthrough to lower layers)
 #[must_use]
 pub fn is_transparent(keycode: &str) -> bool {
@@ -287,7 +696,167 @@ pub fn check_transparency_conflict(
 #[cfg(test)]
 mod tests {
     use super::*;
-    use crate::models::{KeyDefinition, Layer, RgbColor};
+    use crate::models::{EncoderMapping, KeyDefinition, Layer, RgbColor, TapDanceAction};
+
+    /// Layout with one layer per entry of `keycodes`, keys laid out on row 0.
+    fn layout_with_keys(keycodes: &[Vec<&str>]) -> Layout {
+        let mut layout = Layout::new("Test").unwrap();
+        for (number, keys) in keycodes.iter().enumerate() {
+            let mut layer =
+                Layer::new(number as u8, format!("L{number}"), RgbColor::new(0, 0, 0)).unwrap();
+            for (col, keycode) in keys.iter().enumerate() {
+                layer
+                    .add_key(KeyDefinition::new(Position::new(0, col as u8), *keycode))
+                    .unwrap();
+            }
+            layout.layers.push(layer);
+        }
+        layout
+    }
+
+    #[test]
+    fn test_move_layer_rewrites_numeric_refs() {
+        let mut layout = layout_with_keys(&[
+            vec!["MO(3)", "LT(3, KC_X)", "TG(1)", "OSL(2)"],
+            vec!["TO(3)", "MO(9)", "KC_A"],
+            vec!["TO(0)"],
+            vec!["KC_TRNS"],
+        ]);
+
+        assert_eq!(move_layer(&mut layout, 3, 1).unwrap(), 5);
+        let layers = &layout.layers;
+        let names: Vec<_> = layers.iter().map(|l| l.name.as_str()).collect();
+        assert_eq!(names, ["L0", "L3", "L1", "L2"]);
+        assert!(layers
+            .iter()
+            .enumerate()
+            .all(|(i, l)| l.number as usize == i));
+        let base: Vec<_> = layers[0].keys.iter().map(|k| k.keycode.as_str()).collect();
+        assert_eq!(base, ["MO(1)", "LT(1, KC_X)", "TG(2)", "OSL(3)"]);
+        // Out-of-range references stay as they are
+        assert_eq!(layers[2].keys[0].keycode, "TO(1)");
+        assert_eq!(layers[2].keys[1].keycode, "MO(9)");
+
+        assert!(move_layer(&mut layout, 0, 2).is_err());
+        assert!(move_layer(&mut layout, 2, 0).is_err());
+        assert!(move_layer(&mut layout, 1, 4).is_err());
+        assert_eq!(move_layer(&mut layout, 2, 2).unwrap(), 0);
+    }
+
+    #[test]
+    fn test_move_layer_aborts_on_ambiguous_refs() {
+        let mut layout = layout_with_keys(&[vec!["MO(2)"], vec![], vec![]]);
+        layout
+            .tap_dances
+            .push(TapDanceAction::new("td_nav", "KC_A").with_hold("MO(NAV)"));
+
+        let err = move_layer(&mut layout, 2, 1).unwrap_err().to_string();
+        assert!(err.contains("Tap dance td_nav hold holds MO(NAV)"), "{err}");
+        assert_eq!(layout.layers[0].keys[0].keycode, "MO(2)");
+        assert_eq!(layout.layers[1].name, "L1");
+    }
+
+    #[test]
+    fn test_move_layer_rewrites_encoder_and_tap_dance_refs() {
+        let mut layout = layout_with_keys(&[vec![], vec![], vec![]]);
+        layout.layers[0]
+            .encoders
+            .push(EncoderMapping::new("MO(2)", "TG(1)"));
+        layout
+            .tap_dances
+            .push(TapDanceAction::new("td_nav", "OSL(1)").with_hold("LT(2, KC_ESC)"));
+
+        assert_eq!(move_layer(&mut layout, 2, 1).unwrap(), 4);
+        assert_eq!(
+            layout.layers[0].encoders[0],
+            EncoderMapping::new("MO(1)", "TG(2)")
+        );
+        let dance = &layout.tap_dances[0];
+        assert_eq!(dance.single_tap, "OSL(2)");
+        assert_eq!(dance.hold.as_deref(), Some("LT(1, KC_ESC)"));
+    }
+
+    #[test]
+    fn test_delete_layer_clears_and_renumbers_refs() {
+        let mut layout = layout_with_keys(&[vec![], vec![], vec![], vec![]]);
+        let deleted = format!("LT(@{}, KC_SPC)", layout.layers[1].id);
+        let keycodes = [
+            vec!["MO(1)", deleted.as_str(), "TG(2)", "LT(3, KC_X)", "MO(9)"],
+            vec!["TO(0)", "TG(2)"],
+            vec!["OSL(1)"],
+            vec!["KC_TRNS"],
+        ];
+        for (layer, keys) in layout.layers.iter_mut().zip(keycodes) {
+            for (col, keycode) in keys.into_iter().enumerate() {
+                layer
+                    .add_key(KeyDefinition::new(Position::new(0, col as u8), keycode))
+                    .unwrap();
+            }
+        }
+
+        let refs = inbound_layer_refs(&layout.layers, &layout.tap_dances, 1);
+        let sites: Vec<_> = refs.iter().map(|r| r.site.to_string()).collect();
+        assert_eq!(sites, ["Layer 0 (0,0)", "Layer 0 (0,1)", "Layer 2 (0,0)"]);
+        assert_eq!(refs[1].replacement(), "KC_SPC");
+
+        let deletion = delete_layer(&mut layout, 1).unwrap();
+        assert_eq!(deletion.layer.name, "L1");
+        assert_eq!((deletion.cleared, deletion.renumbered), (3, 2));
+        let layers = &layout.layers;
+        let names: Vec<_> = layers.iter().map(|l| l.name.as_str()).collect();
+        assert_eq!(names, ["L0", "L2", "L3"]);
+        assert!(layers
+            .iter()
+            .enumerate()
+            .all(|(i, l)| l.number as usize == i));
+        let base: Vec<_> = layers[0].keys.iter().map(|k| k.keycode.as_str()).collect();
+        // A cleared LT() keeps its tap keycode
+        assert_eq!(base, ["KC_NO", "KC_SPC", "TG(1)", "LT(2, KC_X)", "MO(9)"]);
+        assert_eq!(layers[1].keys[0].keycode, "KC_NO");
+
+        assert!(delete_layer(&mut layout, 3).is_err());
+        layout.layers.truncate(1);
+        assert!(delete_layer(&mut layout, 0).is_err());
+    }
+
+    #[test]
+    fn test_delete_layer_clears_encoder_and_tap_dance_refs() {
+        let mut layout = layout_with_keys(&[vec![], vec![], vec![]]);
+        layout.layers[0]
+            .encoders
+            .push(EncoderMapping::new("MO(1)", "TG(2)"));
+        // Encoders on the deleted layer go with it
+        layout.layers[1]
+            .encoders
+            .push(EncoderMapping::new("TO(0)", "TO(1)"));
+        layout.tap_dances.push(
+            TapDanceAction::new("td_nav", "OSL(2)")
+                .with_double_tap("TG(1)")
+                .with_hold("LT(1, KC_ESC)"),
+        );
+
+        let refs = inbound_layer_refs(&layout.layers, &layout.tap_dances, 1);
+        let sites: Vec<_> = refs.iter().map(|r| r.site.to_string()).collect();
+        assert_eq!(
+            sites,
+            [
+                "Layer 0 encoder 0 ccw",
+                "Tap dance td_nav double tap",
+                "Tap dance td_nav hold"
+            ]
+        );
+
+        let deletion = delete_layer(&mut layout, 1).unwrap();
+        assert_eq!((deletion.cleared, deletion.renumbered), (3, 2));
+        assert_eq!(
+            layout.layers[0].encoders[0],
+            EncoderMapping::new("KC_NO", "TG(1)")
+        );
+        let dance = &layout.tap_dances[0];
+        assert_eq!(dance.single_tap, "OSL(1)");
+        assert_eq!(dance.double_tap.as_deref(), Some("KC_NO"));
+        assert_eq!(dance.hold.as_deref(), Some("KC_ESC"));
+    }
 
     #[test]
     fn test_parse_layer_keycode_simple() {
@@ -387,8 +956,12 @@ mod tests {
     #[test]
     fn test_build_layer_ref_index_basic() {
         let mut layer0 = Layer::new(0, "Base", RgbColor::new(255, 0, 0)).unwrap();
-        layer0.add_key(KeyDefinition::new(Position::new(0, 0), "MO(1)"));
-        layer0.add_key(KeyDefinition::new(Position::new(0, 1), "LT(2, KC_SPC)"));
+        layer0
+            .add_key(KeyDefinition::new(Position::new(0, 0), "MO(1)"))
+            .unwrap();
+        layer0
+            .add_key(KeyDefinition::new(Position::new(0, 1), "LT(2, KC_SPC)"))
+            .unwrap();
 
         let layer1 = Layer::new(1, "Lower", RgbColor::new(0, 255, 0)).unwrap();
         let layer2 = Layer::new(2, "Raise", RgbColor::new(0, 0, 255)).unwrap();
@@ -413,11 +986,17 @@ mod tests {
     #[test]
     fn test_build_layer_ref_index_multiple_refs() {
         let mut layer0 = Layer::new(0, "Base", RgbColor::new(255, 0, 0)).unwrap();
-        layer0.add_key(KeyDefinition::new(Position::new(0, 0), "MO(1)"));
-        layer0.add_key(KeyDefinition::new(Position::new(0, 1), "TG(1)"));
+        layer0
+            .add_key(KeyDefinition::new(Position::new(0, 0), "MO(1)"))
+            .unwrap();
+        layer0
+            .add_key(KeyDefinition::new(Position::new(0, 1), "TG(1)"))
+            .unwrap();
 
         let mut layer1 = Layer::new(1, "Lower", RgbColor::new(0, 255, 0)).unwrap();
-        layer1.add_key(KeyDefinition::new(Position::new(1, 0), "MO(1)")); // Self-reference
+        layer1
+            .add_key(KeyDefinition::new(Position::new(1, 0), "MO(1)"))
+            .unwrap(); // Self-reference
 
         let layers = vec![layer0, layer1];
         let index = build_layer_ref_index(&layers);
@@ -429,10 +1008,18 @@ mod tests {
     #[test]
     fn test_build_layer_ref_index_ignores_invalid() {
         let mut layer0 = Layer::new(0, "Base", RgbColor::new(255, 0, 0)).unwrap();
-        layer0.add_key(KeyDefinition::new(Position::new(0, 0), "KC_A")); // Not a layer keycode
-        layer0.add_key(KeyDefinition::new(Position::new(0, 1), "KC_TRNS")); // Transparent
-        layer0.add_key(KeyDefinition::new(Position::new(0, 2), "MO(@uuid)")); // UUID ref
-        layer0.add_key(KeyDefinition::new(Position::new(0, 3), "MO(99)")); // Out of bounds
+        layer0
+            .add_key(KeyDefinition::new(Position::new(0, 0), "KC_A"))
+            .unwrap(); // Not a layer keycode
+        layer0
+            .add_key(KeyDefinition::new(Position::new(0, 1), "KC_TRNS"))
+            .unwrap(); // Transparent
+        layer0
+            .add_key(KeyDefinition::new(Position::new(0, 2), "MO(@uuid)"))
+            .unwrap(); // UUID ref
+        layer0
+            .add_key(KeyDefinition::new(Position::new(0, 3), "MO(99)"))
+            .unwrap(); // Out of bounds
 
         let layer1 = Layer::new(1, "Lower", RgbColor::new(0, 255, 0)).unwrap();
 
@@ -0,0 +1,240 @@
+//! Layout file formats.
+//!
+//! [`LayoutService`](super::LayoutService) loads and saves layouts through the
+//! formats registered in [`FORMATS`]. A file's format is picked by its
+//! extension, then by sniffing its content; Markdown is the default.
+//!
+//! - [`MarkdownFormat`] (`.md`): the human-readable format the editor writes.
+//! - [`JsonFormat`] (`.lazyqmk.json`): the model serialized directly, for
+//!   toolchains that prefer JSON.
+
+use anyhow::{Context, Result};
+use std::path::Path;
+
+use crate::atomic_write;
+use crate::models::Layout;
+use crate::parser;
+
+/// A file format layouts can be loaded from and saved to.
+pub trait LayoutFormat: Sync {
+    /// Short name shown to users (e.g., "markdown").
+    fn name(&self) -> &'static str;
+
+    /// File extension without the leading dot (e.g., "md").
+    fn extension(&self) -> &'static str;
+
+    /// Whether `path` has this format's extension.
+    fn matches_path(&self, path: &Path) -> bool {
+        path.file_name()
+            .and_then(|name| name.to_str())
+            .is_some_and(|name| {
+                name.to_ascii_lowercase()
+                    .ends_with(&format!(".{}", self.extension()))
+            })
+    }
+
+    /// Whether `content` looks like this format.
+    fn detect_content(&self, content: &str) -> bool;
+
+    /// Loads a layout from `path`.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the file can't be read or isn't a valid layout.
+    fn load(&self, path: &Path) -> Result<Layout>;
+
+    /// Saves `layout` to `path`, atomically.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the layout can't be serialized or written.
+    fn save(&self, layout: &Layout, path: &Path) -> Result<()>;
+}
+
+/// Markdown with YAML frontmatter (`.md`).
+pub struct MarkdownFormat;
+
+impl LayoutFormat for MarkdownFormat {
+    fn name(&self) -> &'static str {
+        "markdown"
+    }
+
+    fn extension(&self) -> &'static str {
+        "md"
+    }
+
+    fn detect_content(&self, content: &str) -> bool {
+        content.trim_start().starts_with("---")
+    }
+
+    fn load(&self, path: &Path) -> Result<Layout> {
+        parser::parse_markdown_layout(path)
+    }
+
+    fn save(&self, layout: &Layout, path: &Path) -> Result<()> {
+        parser::save_markdown_layout(layout, path)
+    }
+}
+
+/// The layout model serialized as JSON (`.lazyqmk.json`).
+pub struct JsonFormat;
+
+impl LayoutFormat for JsonFormat {
+    fn name(&self) -> &'static str {
+        "json"
+    }
+
+    fn extension(&self) -> &'static str {
+        "lazyqmk.json"
+    }
+
+    fn detect_content(&self, content: &str) -> bool {
+        serde_json::from_str::<serde_json::Value>(content).is_ok_and(|value| {
+            value
+                .get("metadata")
+                .is_some_and(serde_json::Value::is_object)
+                && value.get("layers").is_some_and(serde_json::Value::is_array)
+        })
+    }
+
+    fn load(&self, path: &Path) -> Result<Layout> {
+        let content = std::fs::read_to_string(path)
+            .with_context(|| format!("Failed to read layout file: {}", path.display()))?;
+        let mut layout: Layout = serde_json::from_str(&content)
+            .with_context(|| format!("Failed to parse layout file: {}", path.display()))?;
+
+        // Same post-processing as the Markdown parser
+        layout.auto_create_tap_dances();
+        layout.validate()?;
+        Ok(layout)
+    }
+
+    fn save(&self, layout: &Layout, path: &Path) -> Result<()> {
+        let mut json =
+            serde_json::to_string_pretty(layout).context("Failed to serialize layout to JSON")?;
+        json.push('\n');
+        atomic_write::write(path, json)
+    }
+}
+
+/// Registered formats, most specific extension first.
+pub static FORMATS: &[&dyn LayoutFormat] = &[&JsonFormat, &MarkdownFormat];
+
+/// The default format, used when nothing else matches.
+pub static DEFAULT_FORMAT: &dyn LayoutFormat = &MarkdownFormat;
+
+/// The format whose extension `path` has, if any.
+#[must_use]
+pub fn format_for_extension(path: &Path) -> Option<&'static dyn LayoutFormat> {
+    FORMATS
+        .iter()
+        .copied()
+        .find(|format| format.matches_path(path))
+}
+
+/// The format to save `path` in: by extension, else the default.
+#[must_use]
+pub fn format_for_save(path: &Path) -> &'static dyn LayoutFormat {
+    format_for_extension(path).unwrap_or(DEFAULT_FORMAT)
+}
+
+/// The format to load `path` with: by extension, then by content, else the
+/// default.
+#[must_use]
+pub fn format_for_load(path: &Path) -> &'static dyn LayoutFormat {
+    if let Some(format) = format_for_extension(path) {
+        return format;
+    }
+    std::fs::read_to_string(path)
+        .ok()
+        .and_then(|content| {
+            FORMATS
+                .iter()
+                .copied()
+                .find(|format| format.detect_content(&content))
+        })
+        .unwrap_or(DEFAULT_FORMAT)
+}
+
+/// Whether `path` has the extension of a registered format.
+#[must_use]
+pub fn is_layout_file(path: &Path) -> bool {
+    format_for_extension(path).is_some()
+}
+
+/// Looks up a format by name (e.g., "json").
+#[must_use]
+pub fn find_format(name: &str) -> Option<&'static dyn LayoutFormat> {
+    FORMATS
+        .iter()
+        .copied()
+        .find(|format| format.name().eq_ignore_ascii_case(name))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::models::{KeyDefinition, Layer, Position, RgbColor};
+    use tempfile::TempDir;
+
+    fn sample_layout() -> Layout {
+        let mut layout = Layout::new("Formats").unwrap();
+        let mut layer = Layer::new(0, "Base", RgbColor::new(255, 255, 255)).unwrap();
+        layer
+            .add_key(KeyDefinition::new(Position::new(0, 0), "KC_A"))
+            .unwrap();
+        layer
+            .add_key(KeyDefinition::new(Position::new(0, 1), "KC_TRNS"))
+            .unwrap();
+        layout.add_layer(layer).unwrap();
+        layout
+    }
+
+    #[test]
+    fn test_format_detection_by_extension() {
+        assert_eq!(format_for_save(Path::new("a.md")).name(), "markdown");
+        assert_eq!(format_for_save(Path::new("a.lazyqmk.json")).name(), "json");
+        assert_eq!(format_for_save(Path::new("A.LAZYQMK.JSON")).name(), "json");
+        // Plain .json isn't claimed, so it falls back to the default
+        assert_eq!(format_for_save(Path::new("a.json")).name(), "markdown");
+        assert!(is_layout_file(Path::new("a.lazyqmk.json")));
+        assert!(!is_layout_file(Path::new("a.txt")));
+    }
+
+    #[test]
+    fn test_format_detection_by_content() {
+        let temp = TempDir::new().unwrap();
+        let layout = sample_layout();
+
+        let json_path = temp.path().join("exported.json");
+        JsonFormat.save(&layout, &json_path).unwrap();
+        assert_eq!(format_for_load(&json_path).name(), "json");
+
+        let md_path = temp.path().join("layout.txt");
+        MarkdownFormat.save(&layout, &md_path).unwrap();
+        assert_eq!(format_for_load(&md_path).name(), "markdown");
+    }
+
+    #[test]
+    fn test_json_round_trip() {
+        let temp = TempDir::new().unwrap();
+        let path = temp.path().join("corne.lazyqmk.json");
+        let layout = sample_layout();
+
+        JsonFormat.save(&layout, &path).unwrap();
+        let loaded = JsonFormat.load(&path).unwrap();
+        assert_eq!(loaded, layout);
+    }
+
+    #[test]
+    fn test_markdown_to_json_preserves_model() {
+        let temp = TempDir::new().unwrap();
+        let md_path = temp.path().join("corne.md");
+        let json_path = temp.path().join("corne.lazyqmk.json");
+
+        MarkdownFormat.save(&sample_layout(), &md_path).unwrap();
+        let from_markdown = MarkdownFormat.load(&md_path).unwrap();
+        JsonFormat.save(&from_markdown, &json_path).unwrap();
+        assert_eq!(JsonFormat.load(&json_path).unwrap(), from_markdown);
+    }
+}
@@ -4,9 +4,57 @@
 //! interface for loading, saving, and managing layout files.
 
 use anyhow::{Context, Result};
+use std::collections::HashMap;
+use std::fmt;
 use std::path::{Path, PathBuf};
+use std::time::SystemTime;
 
-use crate::{models::Layout, parser};
+use super::layout_format::{self, LayoutFormat};
+use crate::models::Layout;
+
+/// Why a layout file could not be loaded.
+#[derive(Debug)]
+pub enum LayoutLoadError {
+    /// There is no file at the path
+    NotFound {
+        /// Path that was requested
+        path: PathBuf,
+    },
+    /// The file exists but could not be read
+    Io {
+        /// Path that was requested
+        path: PathBuf,
+        /// Underlying I/O error
+        source: std::io::Error,
+    },
+    /// The file was read but is not a valid layout
+    Parse {
+        /// Path that was requested
+        path: PathBuf,
+        /// Parser or validation error
+        source: anyhow::Error,
+    },
+}
+
+impl fmt::Display for LayoutLoadError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::NotFound { path } => write!(f, "Layout file not found: {}", path.display()),
+            Self::Io { path, source } => {
+                write!(f, "Failed to read layout from {}: {source}", path.display())
+            }
+            Self::Parse { path, source } => {
+                write!(
+                    f,
+                    "Failed to load layout from {}: {source:#}",
+                    path.display()
+                )
+            }
+        }
+    }
+}
+
+impl std::error::Error for LayoutLoadError {}
 
 /// Service for managing layout file I/O operations.
 ///
@@ -15,7 +63,7 @@ use crate::{models::Layout, parser};
 pub struct LayoutService;
 
 impl LayoutService {
-    /// Loads a layout from a Markdown file.
+    /// Loads a layout, in the format detected from its extension or content.
     ///
     /// # Arguments
     ///
@@ -24,7 +72,8 @@ impl LayoutService {
     /// # Returns
     ///
     /// * `Ok(Layout)` - Successfully parsed layout
-    /// * `Err(...)` - File not found, parse error, or I/O error
+    /// * `Err(...)` - File not found, parse error, or I/O error, as a
+    ///   [`LayoutLoadError`]
     ///
     /// # Examples
     ///
@@ -35,12 +84,44 @@ impl LayoutService {
     /// let layout = LayoutService::load(Path::new("my_layout.md"))?;
     /// # Ok::<(), anyhow::Error>(())
     /// ```
-    pub fn load(path: &Path) -> Result<Layout> {
-        parser::parse_markdown_layout(path)
-            .with_context(|| format!("Failed to load layout from {}", path.display()))
+    pub fn load(path: &Path) -> Result<Layout, LayoutLoadError> {
+        match std::fs::metadata(path) {
+            Err(e) if e.kind() == std::io::ErrorKind::NotFound => {
+                return Err(LayoutLoadError::NotFound {
+                    path: path.to_path_buf(),
+                });
+            }
+            Err(source) => {
+                return Err(LayoutLoadError::Io {
+                    path: path.to_path_buf(),
+                    source,
+                });
+            }
+            Ok(metadata) if !metadata.is_file() => {
+                return Err(LayoutLoadError::Io {
+                    path: path.to_path_buf(),
+                    source: std::io::Error::other("not a file"),
+                });
+            }
+            Ok(_) => {}
+        }
+        // Unreadable files (e.g. permissions) are I/O errors, not parse errors
+        if let Err(source) = std::fs::File::open(path) {
+            return Err(LayoutLoadError::Io {
+                path: path.to_path_buf(),
+                source,
+            });
+        }
+
+        layout_format::format_for_load(path)
+            .load(path)
+            .map_err(|source| LayoutLoadError::Parse {
+                path: path.to_path_buf(),
+                source,
+            })
     }
 
-    /// Saves a layout to a Markdown file.
+    /// Saves a layout in the format its extension names (Markdown by default).
     ///
     /// This performs an atomic write using a temp file + rename pattern to ensure
     /// the file is never left in a corrupted state.
@@ -68,14 +149,31 @@ impl LayoutService {
     /// # }
     /// ```
     pub fn save(layout: &Layout, path: &Path) -> Result<()> {
-        parser::save_markdown_layout(layout, path)
+        Self::save_as(layout, path, layout_format::format_for_save(path))
+    }
+
+    /// Saves a layout in an explicitly chosen format.
+    ///
+    /// Layers whose content differs from the file being replaced get a fresh
+    /// `modified` timestamp unless the caller already stamped them.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the layout can't be serialized or written.
+    pub fn save_as(layout: &Layout, path: &Path, format: &dyn LayoutFormat) -> Result<()> {
+        let previous = Self::load(path).map(|p| p.layers).unwrap_or_default();
+        let mut layout = layout.clone();
+        layout.stamp_changed_layers(&previous);
+        format
+            .save(&layout, path)
             .with_context(|| format!("Failed to save layout to {}", path.display()))
     }
 
     /// Renames a layout file if the layout name has changed.
     ///
     /// This is useful when a layout's name is changed through the metadata editor.
-    /// The function sanitizes the new name for use as a filename.
+    /// The function sanitizes the new name for use as a filename and keeps the
+    /// file's format extension.
     ///
     /// # Arguments
     ///
@@ -116,7 +214,8 @@ impl LayoutService {
         let sanitized_name = sanitize_filename(new_name);
 
         // Build new path with sanitized name
-        let new_path = parent.join(format!("{}.md", sanitized_name));
+        let extension = layout_format::format_for_save(old_path).extension();
+        let new_path = parent.join(format!("{sanitized_name}.{extension}"));
 
         // Check if rename is needed
         if new_path == old_path {
@@ -159,6 +258,80 @@ pub fn sanitize_filename(name: &str) -> String {
     name.replace(['/', '\\', ':', ' '], "_").to_lowercase()
 }
 
+/// Finds layout files whose content is identical to another file.
+///
+/// Takes `(path, content hash)` pairs, as produced by
+/// [`Layout::content_hash`], and maps each duplicate's path to the file it
+/// duplicates. Within a group of identical layouts the file with the shortest
+/// name (then the alphabetically first) is treated as the original, so
+/// `corne (1).md` is reported as identical to `corne.md`.
+#[must_use]
+pub fn find_duplicates(entries: &[(PathBuf, String)]) -> HashMap<PathBuf, PathBuf> {
+    let mut groups: HashMap<&str, Vec<&PathBuf>> = HashMap::new();
+    for (path, hash) in entries {
+        groups.entry(hash.as_str()).or_default().push(path);
+    }
+
+    let mut duplicates = HashMap::new();
+    for mut paths in groups.into_values().filter(|paths| paths.len() > 1) {
+        paths.sort_by_key(|path| {
+            let name = path
+                .file_name()
+                .map(|n| n.to_string_lossy().into_owned())
+                .unwrap_or_default();
+            (name.len(), name)
+        });
+        let original = paths[0];
+        for duplicate in &paths[1..] {
+            duplicates.insert((*duplicate).clone(), original.clone());
+        }
+    }
+    duplicates
+}
+
+/// A layout file found by [`scan_layout_dir`].
+#[derive(Debug)]
+pub struct ScannedLayout {
+    /// Path to the layout file
+    pub path: PathBuf,
+    /// File modification time, if the file system reports one
+    pub modified: Option<SystemTime>,
+    /// The parsed layout, or the parse error message
+    pub layout: std::result::Result<Layout, String>,
+}
+
+/// Scans `dir` (not recursively) for files in a registered layout format and
+/// parses each one.
+///
+/// Files that fail to parse are returned with their error rather than
+/// skipped. Results are sorted by file modification time, most recent first.
+/// Used by both the layout picker and `lazyqmk layouts list`.
+pub fn scan_layout_dir(dir: &Path) -> Result<Vec<ScannedLayout>> {
+    let entries = std::fs::read_dir(dir)
+        .with_context(|| format!("Failed to read layouts directory: {}", dir.display()))?;
+
+    let mut layouts = Vec::new();
+    for entry in entries {
+        let entry = entry.context("Failed to read directory entry")?;
+        let path = entry.path();
+        if !path.is_file() || !layout_format::is_layout_file(&path) {
+            continue;
+        }
+        layouts.push(ScannedLayout {
+            modified: entry.metadata().and_then(|m| m.modified()).ok(),
+            layout: LayoutService::load(&path).map_err(|e| format!("{e:#}")),
+            path,
+        });
+    }
+
+    layouts.sort_by(|a, b| {
+        b.modified
+            .cmp(&a.modified)
+            .then_with(|| a.path.cmp(&b.path))
+    });
+    Ok(layouts)
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -177,6 +350,74 @@ mod tests {
         );
     }
 
+    #[test]
+    fn test_find_duplicates_prefers_shortest_name() {
+        let entries = vec![
+            (PathBuf::from("layouts/corne (1).md"), "aaaa".to_string()),
+            (PathBuf::from("layouts/corne.md"), "aaaa".to_string()),
+            (PathBuf::from("layouts/corne-old.md"), "bbbb".to_string()),
+        ];
+
+        let duplicates = find_duplicates(&entries);
+        assert_eq!(duplicates.len(), 1);
+        assert_eq!(
+            duplicates.get(Path::new("layouts/corne (1).md")),
+            Some(&PathBuf::from("layouts/corne.md"))
+        );
+    }
+
+    #[test]
+    fn test_scan_layout_dir_reports_parse_errors() -> Result<()> {
+        let temp_dir = tempfile::TempDir::new()?;
+        let mut layout = Layout::new("Good")?;
+        layout.add_layer(crate::models::Layer::new(
+            0,
+            "Base",
+            crate::models::RgbColor::new(0, 0, 0),
+        )?)?;
+        LayoutService::save(&layout, &temp_dir.path().join("good.md"))?;
+        fs::write(temp_dir.path().join("broken.md"), "not a layout")?;
+        fs::write(temp_dir.path().join("notes.txt"), "ignored")?;
+
+        let scanned = scan_layout_dir(temp_dir.path())?;
+        assert_eq!(scanned.len(), 2);
+        let good = scanned
+            .iter()
+            .find(|s| s.path.ends_with("good.md"))
+            .unwrap();
+        assert_eq!(good.layout.as_ref().unwrap().metadata.name, "Good");
+        let broken = scanned
+            .iter()
+            .find(|s| s.path.ends_with("broken.md"))
+            .unwrap();
+        assert!(broken.layout.is_err());
+        Ok(())
+    }
+
+    #[test]
+    fn test_load_errors_are_typed() -> Result<()> {
+        let temp_dir = tempfile::TempDir::new()?;
+
+        let missing = temp_dir.path().join("missing.md");
+        assert!(matches!(
+            LayoutService::load(&missing),
+            Err(LayoutLoadError::NotFound { .. })
+        ));
+
+        let broken = temp_dir.path().join("broken.md");
+        fs::write(&broken, "not a layout")?;
+        assert!(matches!(
+            LayoutService::load(&broken),
+            Err(LayoutLoadError::Parse { .. })
+        ));
+
+        assert!(matches!(
+            LayoutService::load(temp_dir.path()),
+            Err(LayoutLoadError::Io { .. })
+        ));
+        Ok(())
+    }
+
     #[test]
     fn test_rename_file_if_needed_no_file() {
         let path = Path::new("/tmp/nonexistent_layout_test_12345.md");
@@ -228,6 +469,17 @@ mod tests {
         Ok(())
     }
 
+    #[test]
+    fn test_rename_file_keeps_format_extension() -> Result<()> {
+        let temp_dir = tempfile::TempDir::new()?;
+        let old_path = temp_dir.path().join("old.lazyqmk.json");
+        fs::write(&old_path, "{}")?;
+
+        let new_path = LayoutService::rename_file_if_needed(&old_path, "New")?.unwrap();
+        assert_eq!(new_path, temp_dir.path().join("new.lazyqmk.json"));
+        Ok(())
+    }
+
     #[test]
     fn test_rename_file_sanitizes_name() -> Result<()> {
         // Create a temporary file
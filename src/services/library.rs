@@ -0,0 +1,212 @@
+//! Shared definition library.
+//!
+//! A library is a TOML file of named definitions (currently tap dances) that
+//! can be exported from one layout and merged into others. Merging is by
+//! name: a definition the target layout lacks is added, an identical one is
+//! left alone, and a differing one is reported as a conflict and never
+//! overwritten.
+
+use anyhow::{Context, Result};
+use serde::{Deserialize, Serialize};
+use std::fs;
+use std::path::Path;
+
+use crate::atomic_write;
+use crate::models::{Layout, TapDanceAction};
+
+/// Current library file format version.
+pub const LIBRARY_VERSION: u32 = 1;
+
+const fn default_version() -> u32 {
+    LIBRARY_VERSION
+}
+
+/// Named definitions shared between layouts.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct Library {
+    /// File format version
+    #[serde(default = "default_version")]
+    pub version: u32,
+    /// Tap dance definitions, written as `[[tap_dance]]` tables
+    #[serde(default, rename = "tap_dance", skip_serializing_if = "Vec::is_empty")]
+    pub tap_dances: Vec<TapDanceAction>,
+}
+
+/// Outcome of merging a library into a layout.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct ImportReport {
+    /// Definitions added to the layout
+    pub added: Vec<String>,
+    /// Definitions the layout already had with the same content
+    pub unchanged: Vec<String>,
+    /// Definitions the layout has under the same name with different content
+    pub conflicts: Vec<String>,
+    /// Names requested with `only` that the library doesn't define
+    pub missing: Vec<String>,
+}
+
+impl ImportReport {
+    /// True if any definition collided with a differing one in the layout.
+    #[must_use]
+    pub fn has_conflicts(&self) -> bool {
+        !self.conflicts.is_empty()
+    }
+}
+
+impl Library {
+    /// Collects the shareable definitions of a layout.
+    #[must_use]
+    pub fn from_layout(layout: &Layout) -> Self {
+        Self {
+            version: LIBRARY_VERSION,
+            tap_dances: layout.tap_dances.clone(),
+        }
+    }
+
+    /// Reads and validates a library file.
+    pub fn load(path: &Path) -> Result<Self> {
+        let content = fs::read_to_string(path)
+            .with_context(|| format!("Failed to read library file: {}", path.display()))?;
+        let library: Self = toml::from_str(&content)
+            .with_context(|| format!("Failed to parse library file: {}", path.display()))?;
+        if library.version > LIBRARY_VERSION {
+            anyhow::bail!(
+                "Library file {} has version {}, this build supports up to {LIBRARY_VERSION}",
+                path.display(),
+                library.version
+            );
+        }
+        for tap_dance in &library.tap_dances {
+            tap_dance
+                .validate()
+                .with_context(|| format!("Invalid tap dance '{}' in library", tap_dance.name))?;
+        }
+        Ok(library)
+    }
+
+    /// Writes the library file, creating parent directories as needed.
+    pub fn save(&self, path: &Path) -> Result<()> {
+        if let Some(parent) = path.parent().filter(|p| !p.as_os_str().is_empty()) {
+            fs::create_dir_all(parent)
+                .with_context(|| format!("Failed to create directory: {}", parent.display()))?;
+        }
+        let content = toml::to_string_pretty(self).context("Failed to serialize library")?;
+        atomic_write::write(path, content)
+            .with_context(|| format!("Failed to write library file: {}", path.display()))
+    }
+
+    /// Compares the library against a layout without changing it.
+    ///
+    /// With `only`, just the named definitions are considered.
+    #[must_use]
+    pub fn plan_import(&self, layout: &Layout, only: Option<&[String]>) -> ImportReport {
+        let mut report = ImportReport::default();
+        for tap_dance in self.selected(only) {
+            match layout.get_tap_dance(&tap_dance.name) {
+                None => report.added.push(tap_dance.name.clone()),
+                Some(existing) if existing == tap_dance => {
+                    report.unchanged.push(tap_dance.name.clone());
+                }
+                Some(_) => report.conflicts.push(tap_dance.name.clone()),
+            }
+        }
+        if let Some(only) = only {
+            report.missing = only
+                .iter()
+                .filter(|name| !self.tap_dances.iter().any(|td| &td.name == *name))
+                .cloned()
+                .collect();
+        }
+        report
+    }
+
+    /// Adds the definitions the layout lacks.
+    ///
+    /// Conflicting definitions are skipped and listed in the report; the
+    /// layout's version is kept.
+    pub fn import_into(&self, layout: &mut Layout, only: Option<&[String]>) -> ImportReport {
+        let report = self.plan_import(layout, only);
+        for tap_dance in self.selected(only) {
+            if report.added.contains(&tap_dance.name) {
+                layout.tap_dances.push(tap_dance.clone());
+            }
+        }
+        report
+    }
+
+    /// Tap dances selected by `only`, or all of them.
+    fn selected<'a>(
+        &'a self,
+        only: Option<&'a [String]>,
+    ) -> impl Iterator<Item = &'a TapDanceAction> + 'a {
+        self.tap_dances
+            .iter()
+            .filter(move |td| only.is_none_or(|names| names.contains(&td.name)))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn layout_with(tap_dances: Vec<TapDanceAction>) -> Layout {
+        let mut layout = Layout::new("Library").unwrap();
+        layout.tap_dances = tap_dances;
+        layout
+    }
+
+    #[test]
+    fn test_save_and_load_round_trip() {
+        let temp = tempfile::TempDir::new().unwrap();
+        let path = temp.path().join("nested/lib.toml");
+        let library = Library::from_layout(&layout_with(vec![
+            TapDanceAction::new("quote_dance", "KC_QUOT").with_double_tap("KC_DQUO"),
+            TapDanceAction::new("nav_dance", "KC_ESC")
+                .with_hold("MO(1)")
+                .with_tapping_term(180),
+        ]));
+
+        library.save(&path).unwrap();
+        let content = fs::read_to_string(&path).unwrap();
+        assert!(content.contains("[[tap_dance]]"));
+        assert_eq!(Library::load(&path).unwrap(), library);
+    }
+
+    #[test]
+    fn test_import_merges_by_name_and_keeps_conflicts() {
+        let library = Library::from_layout(&layout_with(vec![
+            TapDanceAction::new("quote_dance", "KC_QUOT").with_double_tap("KC_DQUO"),
+            TapDanceAction::new("nav_dance", "KC_ESC").with_hold("MO(1)"),
+            TapDanceAction::new("caps_dance", "KC_LSFT").with_double_tap("KC_CAPS"),
+        ]));
+        let mut layout = layout_with(vec![
+            TapDanceAction::new("quote_dance", "KC_QUOT").with_double_tap("KC_DQUO"),
+            TapDanceAction::new("nav_dance", "KC_TAB").with_hold("MO(2)"),
+        ]);
+
+        let report = library.import_into(&mut layout, None);
+        assert_eq!(report.added, vec!["caps_dance"]);
+        assert_eq!(report.unchanged, vec!["quote_dance"]);
+        assert_eq!(report.conflicts, vec!["nav_dance"]);
+        assert_eq!(layout.tap_dances.len(), 3);
+        assert_eq!(
+            layout.get_tap_dance("nav_dance").unwrap().single_tap,
+            "KC_TAB"
+        );
+    }
+
+    #[test]
+    fn test_import_only_selected_names() {
+        let library = Library::from_layout(&layout_with(vec![
+            TapDanceAction::new("quote_dance", "KC_QUOT").with_double_tap("KC_DQUO"),
+            TapDanceAction::new("nav_dance", "KC_ESC").with_hold("MO(1)"),
+        ]));
+        let mut layout = layout_with(Vec::new());
+        let only = vec!["nav_dance".to_string(), "ghost_dance".to_string()];
+
+        let report = library.import_into(&mut layout, Some(&only));
+        assert_eq!(report.added, vec!["nav_dance"]);
+        assert_eq!(report.missing, vec!["ghost_dance"]);
+        assert!(layout.get_tap_dance("quote_dance").is_none());
+    }
+}
@@ -0,0 +1,1356 @@
+//! Layout linting: style rules beyond hard validation.
+//!
+//! Validation (see `firmware::validator`) rejects layouts that cannot be
+//! compiled. Linting reports layouts that compile but are probably not what
+//! the user wants, such as a layer that can be toggled on but never off.
+//!
+//! Each rule implements [`LintRule`] and has a stable ID (`L001`, ...) and a
+//! default severity. Rules can be disabled per layout in the frontmatter:
+//!
+//! ```yaml
+//! lint:
+//!   disable: ["L003"]
+//! ```
+
+use crate::models::{
+    ColorVision, KeyDefinition, KeyRole, Layout, Position, RgbColor, MIN_LEGEND_CONTRAST,
+    MIN_VISIBLE_LED_VALUE,
+};
+use crate::services::key_roles::layout_roles;
+use crate::services::layer_refs::{build_layer_ref_index, parse_layer_keycode, LayerRef};
+use crate::services::layer_refs::{LayerRefKind, LayerRefTarget};
+use crate::services::quick_fix::FixChange;
+use crate::services::shifted_pairs::{HostLayout, ShiftedSymbol};
+use serde::Serialize;
+use std::collections::HashMap;
+use std::fmt;
+
+/// Bare modifier keycodes (short and long QMK names).
+const MODIFIER_KEYCODES: &[&str] = &[
+    "KC_LCTL",
+    "KC_LSFT",
+    "KC_LALT",
+    "KC_LGUI",
+    "KC_RCTL",
+    "KC_RSFT",
+    "KC_RALT",
+    "KC_RGUI",
+    "KC_LEFT_CTRL",
+    "KC_LEFT_SHIFT",
+    "KC_LEFT_ALT",
+    "KC_LEFT_GUI",
+    "KC_RIGHT_CTRL",
+    "KC_RIGHT_SHIFT",
+    "KC_RIGHT_ALT",
+    "KC_RIGHT_GUI",
+    "KC_LCMD",
+    "KC_RCMD",
+    "KC_LOPT",
+    "KC_ROPT",
+    "KC_LWIN",
+    "KC_RWIN",
+    "KC_ALGR",
+];
+
+/// Legend color assumed when the layout does not set `lint.legend_color`.
+const DEFAULT_LEGEND_COLOR: RgbColor = RgbColor::new(255, 255, 255);
+
+/// Minimum category color distance when the layout does not set
+/// `lint.min_color_distance`.
+const DEFAULT_MIN_COLOR_DISTANCE: u8 = 15;
+
+/// How serious a lint finding is.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Serialize)]
+#[serde(rename_all = "lowercase")]
+pub enum LintSeverity {
+    /// Suggestion; never fails the lint run
+    Info,
+    /// Likely mistake; fails with `--strict`
+    Warning,
+    /// Almost certainly a mistake; always fails
+    Error,
+}
+
+impl fmt::Display for LintSeverity {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Info => write!(f, "info"),
+            Self::Warning => write!(f, "warning"),
+            Self::Error => write!(f, "error"),
+        }
+    }
+}
+
+/// A single lint finding.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct LintFinding {
+    /// ID of the rule that produced this finding
+    pub rule: &'static str,
+    /// Severity of the rule
+    pub severity: LintSeverity,
+    /// Human-readable description of the problem
+    pub message: String,
+    /// Layer the finding applies to
+    pub layer: Option<usize>,
+    /// Key position the finding applies to
+    pub position: Option<Position>,
+}
+
+/// Data shared by all rules while linting one layout.
+pub struct LintContext<'a> {
+    /// Layout being linted
+    pub layout: &'a Layout,
+    /// Inbound layer references, keyed by target layer index
+    pub layer_refs: HashMap<usize, Vec<LayerRef>>,
+    /// Role of each key position (thumb, home row, pinky)
+    pub roles: &'a HashMap<Position, KeyRole>,
+}
+
+impl<'a> LintContext<'a> {
+    /// Builds the context for a layout whose keys have the given roles.
+    #[must_use]
+    pub fn new(layout: &'a Layout, roles: &'a HashMap<Position, KeyRole>) -> Self {
+        Self {
+            layout,
+            layer_refs: build_layer_ref_index(&layout.layers),
+            roles,
+        }
+    }
+
+    /// Role of the key at `pos` (no role if unknown).
+    #[must_use]
+    pub fn role(&self, pos: Position) -> KeyRole {
+        self.roles.get(&pos).copied().unwrap_or_default()
+    }
+
+    /// Resolves a layer keycode target to a layer index.
+    #[must_use]
+    pub fn resolve_target(&self, target: &LayerRefTarget) -> Option<usize> {
+        match target {
+            LayerRefTarget::Index(idx) => Some(*idx),
+            LayerRefTarget::Uuid(uuid) => {
+                let id = uuid.strip_prefix('@').unwrap_or(uuid);
+                self.layout.layers.iter().position(|l| l.id == id)
+            }
+        }
+    }
+}
+
+/// A lint rule.
+///
+/// Implement this trait and add the rule to [`builtin_rules`] to ship a new
+/// rule. IDs must be unique and never reused, since layouts refer to them.
+pub trait LintRule {
+    /// Stable rule ID (e.g., "L001")
+    fn id(&self) -> &'static str;
+
+    /// Short kebab-case name (e.g., "thumb-keys")
+    fn name(&self) -> &'static str;
+
+    /// One-line description of what the rule checks
+    fn description(&self) -> &'static str;
+
+    /// Severity of findings from this rule
+    fn severity(&self) -> LintSeverity;
+
+    /// Checks the layout, appending any findings.
+    fn check(&self, ctx: &LintContext<'_>, findings: &mut Vec<LintFinding>);
+
+    /// Rewrites the layout so this rule's findings go away, returning the
+    /// changes. Rules without an automatic fix leave the layout alone.
+    fn fix(&self, _layout: &mut Layout) -> Vec<FixChange> {
+        Vec::new()
+    }
+
+    /// Creates a finding for this rule.
+    fn finding(
+        &self,
+        message: String,
+        layer: Option<usize>,
+        position: Option<Position>,
+    ) -> LintFinding {
+        LintFinding {
+            rule: self.id(),
+            severity: self.severity(),
+            message,
+            layer,
+            position,
+        }
+    }
+}
+
+/// Returns all built-in rules in ID order.
+#[must_use]
+pub fn builtin_rules() -> Vec<Box<dyn LintRule>> {
+    vec![
+        Box::new(ThumbKeys),
+        Box::new(BareModifierOffBase),
+        Box::new(LayerReturn),
+        Box::new(RedundantColorOverride),
+        Box::new(UnreachableLayer),
+        Box::new(SelfReferencingLayerKey),
+        Box::new(EmptyLayer),
+        Box::new(LegendContrast),
+        Box::new(SimilarCategoryColors),
+        Box::new(DimAtBrightness),
+        Box::new(DuplicateShiftedSymbol),
+        Box::new(ShiftedFormPreference),
+    ]
+}
+
+/// Result of linting a layout.
+#[derive(Debug, Clone, Default)]
+pub struct LintReport {
+    /// Findings in rule order
+    pub findings: Vec<LintFinding>,
+    /// Rules that were checked
+    pub checked: Vec<&'static str>,
+    /// Rules skipped because the layout or the config disables them
+    pub disabled: Vec<&'static str>,
+}
+
+impl LintReport {
+    /// Returns true if any finding has error severity.
+    #[must_use]
+    pub fn has_errors(&self) -> bool {
+        self.findings
+            .iter()
+            .any(|f| f.severity == LintSeverity::Error)
+    }
+
+    /// Returns true if any finding has warning severity.
+    #[must_use]
+    pub fn has_warnings(&self) -> bool {
+        self.findings
+            .iter()
+            .any(|f| f.severity == LintSeverity::Warning)
+    }
+
+    /// Findings produced by the given rule.
+    pub fn findings_for<'a>(&'a self, rule: &'a str) -> impl Iterator<Item = &'a LintFinding> {
+        self.findings.iter().filter(move |f| f.rule == rule)
+    }
+}
+
+/// Runs lint rules over a layout.
+pub struct Linter {
+    rules: Vec<Box<dyn LintRule>>,
+    /// Rule IDs disabled for every layout (`lint.disable` in the config)
+    disabled: Vec<String>,
+}
+
+impl Default for Linter {
+    fn default() -> Self {
+        Self::new(builtin_rules())
+    }
+}
+
+impl Linter {
+    /// Creates a linter with the given rules.
+    #[must_use]
+    pub fn new(rules: Vec<Box<dyn LintRule>>) -> Self {
+        Self {
+            rules,
+            disabled: Vec::new(),
+        }
+    }
+
+    /// Also skips the given rules, on top of those a layout disables.
+    #[must_use]
+    pub fn with_disabled(mut self, rule_ids: &[String]) -> Self {
+        self.disabled.extend_from_slice(rule_ids);
+        self
+    }
+
+    /// Rules this linter runs.
+    #[must_use]
+    pub fn rules(&self) -> &[Box<dyn LintRule>] {
+        &self.rules
+    }
+
+    /// Lints a layout, skipping rules disabled in its frontmatter or by
+    /// [`with_disabled`](Self::with_disabled).
+    #[must_use]
+    pub fn lint(&self, layout: &Layout) -> LintReport {
+        self.lint_with_roles(layout, &layout_roles(layout))
+    }
+
+    /// Lints a layout using key roles from its keyboard geometry.
+    #[must_use]
+    pub fn lint_with_roles(
+        &self,
+        layout: &Layout,
+        roles: &HashMap<Position, KeyRole>,
+    ) -> LintReport {
+        let ctx = LintContext::new(layout, roles);
+        let mut report = LintReport::default();
+
+        for rule in &self.rules {
+            if self.is_disabled(layout, rule.id()) {
+                report.disabled.push(rule.id());
+                continue;
+            }
+            report.checked.push(rule.id());
+            rule.check(&ctx, &mut report.findings);
+        }
+
+        report
+    }
+
+    /// Applies the automatic fix of every enabled rule, returning the changes
+    /// made by each rule that changed something.
+    pub fn fix(&self, layout: &mut Layout) -> Vec<(&'static str, Vec<FixChange>)> {
+        let mut applied = Vec::new();
+        for rule in &self.rules {
+            if self.is_disabled(layout, rule.id()) {
+                continue;
+            }
+            let changes = rule.fix(layout);
+            if !changes.is_empty() {
+                applied.push((rule.id(), changes));
+            }
+        }
+        applied
+    }
+
+    /// Returns true if the layout or this linter disables the rule.
+    fn is_disabled(&self, layout: &Layout, id: &str) -> bool {
+        layout.metadata.lint.is_disabled(id)
+            || self.disabled.iter().any(|d| d.eq_ignore_ascii_case(id))
+    }
+}
+
+/// Host layout set in `lint.host_layout` (US if unset or unknown).
+fn host_layout(layout: &Layout) -> HostLayout {
+    layout
+        .metadata
+        .lint
+        .host_layout
+        .as_deref()
+        .and_then(HostLayout::from_name)
+        .unwrap_or_default()
+}
+
+/// Returns true for bare modifier keycodes like `KC_LSFT`.
+fn is_modifier(keycode: &str) -> bool {
+    MODIFIER_KEYCODES.contains(&keycode)
+}
+
+/// Returns true for dual-role keys: layer-taps and mod-taps.
+fn is_dual_role(keycode: &str) -> bool {
+    keycode.starts_with("LT(")
+        || keycode.starts_with("MT(")
+        || keycode.starts_with("LM(")
+        || keycode
+            .split_once('(')
+            .is_some_and(|(prefix, _)| prefix.ends_with("_T"))
+}
+
+/// Returns true for keys that do nothing on their own layer.
+fn is_inert(key: &KeyDefinition) -> bool {
+    key.is_transparent() || key.is_no_op()
+}
+
+/// L001: thumb keys should be layer-taps, mod-taps, or modifiers.
+struct ThumbKeys;
+
+impl LintRule for ThumbKeys {
+    fn id(&self) -> &'static str {
+        "L001"
+    }
+
+    fn name(&self) -> &'static str {
+        "thumb-keys"
+    }
+
+    fn description(&self) -> &'static str {
+        "Base-layer thumb keys should be layer-taps, mod-taps, layer keys, or modifiers"
+    }
+
+    fn severity(&self) -> LintSeverity {
+        LintSeverity::Info
+    }
+
+    fn check(&self, ctx: &LintContext<'_>, findings: &mut Vec<LintFinding>) {
+        let Some(base) = ctx.layout.layers.first() else {
+            return;
+        };
+
+        for key in base.keys.iter().filter(|k| ctx.role(k.position).thumb) {
+            let kc = key.keycode.as_str();
+            if is_inert(key)
+                || is_dual_role(kc)
+                || is_modifier(kc)
+                || kc.starts_with("OSM(")
+                || parse_layer_keycode(kc).is_some()
+            {
+                continue;
+            }
+            findings.push(self.finding(
+                format!("Thumb key {kc} is single-purpose; consider a layer-tap or mod-tap"),
+                Some(0),
+                Some(key.position),
+            ));
+        }
+    }
+}
+
+/// L002: bare modifiers belong on the base layer.
+struct BareModifierOffBase;
+
+impl LintRule for BareModifierOffBase {
+    fn id(&self) -> &'static str {
+        "L002"
+    }
+
+    fn name(&self) -> &'static str {
+        "bare-modifier-off-base"
+    }
+
+    fn description(&self) -> &'static str {
+        "Bare modifiers on non-base layers are released when the layer turns off"
+    }
+
+    fn severity(&self) -> LintSeverity {
+        LintSeverity::Warning
+    }
+
+    fn check(&self, ctx: &LintContext<'_>, findings: &mut Vec<LintFinding>) {
+        for (layer_idx, layer) in ctx.layout.layers.iter().enumerate().skip(1) {
+            for key in layer.keys.iter().filter(|k| is_modifier(&k.keycode)) {
+                findings.push(self.finding(
+                    format!(
+                        "Bare modifier {} on layer '{}'; use OSM() or a mod-tap instead",
+                        key.keycode, layer.name
+                    ),
+                    Some(layer_idx),
+                    Some(key.position),
+                ));
+            }
+        }
+    }
+}
+
+/// L003: layers that stay on must have a way back to layer 0.
+struct LayerReturn;
+
+impl LayerReturn {
+    /// Returns true if a key on `layer_idx` can leave that layer.
+    fn has_exit(ctx: &LintContext<'_>, layer_idx: usize) -> bool {
+        ctx.layout.layers[layer_idx].keys.iter().any(|key| {
+            let Some((target, kind)) = parse_layer_keycode(&key.keycode) else {
+                return false;
+            };
+            let target = ctx.resolve_target(&target);
+            match kind {
+                // Switching to any other layer leaves this one
+                LayerRefKind::SwitchTo | LayerRefKind::DefaultSet => {
+                    target.is_some_and(|t| t != layer_idx)
+                }
+                // Toggling this layer again turns it off
+                LayerRefKind::Toggle | LayerRefKind::TapToggle => target == Some(layer_idx),
+                _ => false,
+            }
+        })
+    }
+}
+
+impl LintRule for LayerReturn {
+    fn id(&self) -> &'static str {
+        "L003"
+    }
+
+    fn name(&self) -> &'static str {
+        "layer-return"
+    }
+
+    fn description(&self) -> &'static str {
+        "Layers entered with TG/TO/TT/DF must have a key that leaves them"
+    }
+
+    fn severity(&self) -> LintSeverity {
+        LintSeverity::Error
+    }
+
+    fn check(&self, ctx: &LintContext<'_>, findings: &mut Vec<LintFinding>) {
+        for layer_idx in 1..ctx.layout.layers.len() {
+            let Some(refs) = ctx.layer_refs.get(&layer_idx) else {
+                continue;
+            };
+            let sticky = refs.iter().find(|r| {
+                r.from_layer != layer_idx
+                    && matches!(
+                        r.kind,
+                        LayerRefKind::Toggle
+                            | LayerRefKind::SwitchTo
+                            | LayerRefKind::TapToggle
+                            | LayerRefKind::DefaultSet
+                    )
+            });
+
+            if let Some(entry) = sticky {
+                if !Self::has_exit(ctx, layer_idx) {
+                    findings.push(self.finding(
+                        format!(
+                            "Layer '{}' stays on after {} but has no key to leave it (add TO(0) or {})",
+                            ctx.layout.layers[layer_idx].name,
+                            entry.keycode,
+                            entry.keycode
+                        ),
+                        Some(layer_idx),
+                        None,
+                    ));
+                }
+            }
+        }
+    }
+}
+
+/// L004: a key color override equal to its category color does nothing.
+struct RedundantColorOverride;
+
+impl LintRule for RedundantColorOverride {
+    fn id(&self) -> &'static str {
+        "L004"
+    }
+
+    fn name(&self) -> &'static str {
+        "redundant-color-override"
+    }
+
+    fn description(&self) -> &'static str {
+        "Key color overrides should not duplicate the key's category color"
+    }
+
+    fn severity(&self) -> LintSeverity {
+        LintSeverity::Info
+    }
+
+    fn check(&self, ctx: &LintContext<'_>, findings: &mut Vec<LintFinding>) {
+        for (layer_idx, layer) in ctx.layout.layers.iter().enumerate() {
+            for key in &layer.keys {
+                let Some(color) = key.color_override else {
+                    continue;
+                };
+                let category = key
+                    .category_id
+                    .as_deref()
+                    .or(layer.category_id.as_deref())
+                    .and_then(|id| ctx.layout.get_category(id));
+                if let Some(category) = category.filter(|c| c.color == color) {
+                    findings.push(self.finding(
+                        format!(
+                            "Color override {} matches category '{}'; remove the override",
+                            color.to_hex(),
+                            category.name
+                        ),
+                        Some(layer_idx),
+                        Some(key.position),
+                    ));
+                }
+            }
+        }
+    }
+}
+
+/// L005: every non-base layer should be reachable.
+struct UnreachableLayer;
+
+impl LintRule for UnreachableLayer {
+    fn id(&self) -> &'static str {
+        "L005"
+    }
+
+    fn name(&self) -> &'static str {
+        "unreachable-layer"
+    }
+
+    fn description(&self) -> &'static str {
+        "Non-base layers should be reachable from another layer"
+    }
+
+    fn severity(&self) -> LintSeverity {
+        LintSeverity::Warning
+    }
+
+    fn check(&self, ctx: &LintContext<'_>, findings: &mut Vec<LintFinding>) {
+        for (layer_idx, layer) in ctx.layout.layers.iter().enumerate().skip(1) {
+            let reachable = ctx
+                .layer_refs
+                .get(&layer_idx)
+                .is_some_and(|refs| refs.iter().any(|r| r.from_layer != layer_idx));
+            if !reachable {
+                findings.push(self.finding(
+                    format!("Layer '{}' has no key that activates it", layer.name),
+                    Some(layer_idx),
+                    None,
+                ));
+            }
+        }
+    }
+}
+
+/// L006: momentary layer keys targeting their own layer do nothing.
+struct SelfReferencingLayerKey;
+
+impl LintRule for SelfReferencingLayerKey {
+    fn id(&self) -> &'static str {
+        "L006"
+    }
+
+    fn name(&self) -> &'static str {
+        "self-referencing-layer-key"
+    }
+
+    fn description(&self) -> &'static str {
+        "MO/LT/LM/OSL keys should not target the layer they are on"
+    }
+
+    fn severity(&self) -> LintSeverity {
+        LintSeverity::Warning
+    }
+
+    fn check(&self, ctx: &LintContext<'_>, findings: &mut Vec<LintFinding>) {
+        let mut found = Vec::new();
+        for refs in ctx.layer_refs.values() {
+            for r in refs.iter().filter(|r| {
+                r.from_layer == r.to_layer
+                    && (r.kind.is_hold_like() || r.kind == LayerRefKind::OneShot)
+                    && r.kind != LayerRefKind::TapToggle
+            }) {
+                found.push(self.finding(
+                    format!(
+                        "{} on layer '{}' activates the layer it is already on",
+                        r.keycode, ctx.layout.layers[r.from_layer].name
+                    ),
+                    Some(r.from_layer),
+                    Some(r.position),
+                ));
+            }
+        }
+        // The reference index is a map, so order findings by location
+        found.sort_by_key(|f| (f.layer, f.position.map(|p| (p.row, p.col))));
+        findings.extend(found);
+    }
+}
+
+/// L007: layers with no assigned keys are usually leftovers.
+struct EmptyLayer;
+
+impl LintRule for EmptyLayer {
+    fn id(&self) -> &'static str {
+        "L007"
+    }
+
+    fn name(&self) -> &'static str {
+        "empty-layer"
+    }
+
+    fn description(&self) -> &'static str {
+        "Non-base layers should assign at least one key"
+    }
+
+    fn severity(&self) -> LintSeverity {
+        LintSeverity::Info
+    }
+
+    fn check(&self, ctx: &LintContext<'_>, findings: &mut Vec<LintFinding>) {
+        for (layer_idx, layer) in ctx.layout.layers.iter().enumerate().skip(1) {
+            if layer.keys.iter().all(is_inert) {
+                findings.push(self.finding(
+                    format!("Layer '{}' only contains transparent keys", layer.name),
+                    Some(layer_idx),
+                    None,
+                ));
+            }
+        }
+    }
+}
+
+/// L008: legends must stay readable on their key color.
+struct LegendContrast;
+
+impl LintRule for LegendContrast {
+    fn id(&self) -> &'static str {
+        "L008"
+    }
+
+    fn name(&self) -> &'static str {
+        "legend-contrast"
+    }
+
+    fn description(&self) -> &'static str {
+        "Legend text should have a contrast ratio of at least 3:1 against the key color"
+    }
+
+    fn severity(&self) -> LintSeverity {
+        LintSeverity::Warning
+    }
+
+    fn check(&self, ctx: &LintContext<'_>, findings: &mut Vec<LintFinding>) {
+        let legend = ctx
+            .layout
+            .metadata
+            .lint
+            .legend_color
+            .as_deref()
+            .and_then(|hex| RgbColor::from_hex(hex).ok())
+            .unwrap_or(DEFAULT_LEGEND_COLOR);
+
+        for (layer_idx, layer) in ctx.layout.layers.iter().enumerate() {
+            // One finding per color, located at its first key
+            let mut low_contrast: Vec<(RgbColor, Position, usize)> = Vec::new();
+            for key in layer.keys.iter().filter(|k| !is_inert(k)) {
+                let Some(color) = ctx.layout.resolve_key_color_if_enabled(layer_idx, key) else {
+                    continue;
+                };
+                if legend.contrast_ratio(&color) >= MIN_LEGEND_CONTRAST {
+                    continue;
+                }
+                match low_contrast.iter_mut().find(|(c, _, _)| *c == color) {
+                    Some((_, _, count)) => *count += 1,
+                    None => low_contrast.push((color, key.position, 1)),
+                }
+            }
+
+            for (color, position, count) in low_contrast {
+                let keys = if count == 1 {
+                    String::new()
+                } else {
+                    format!(" ({count} keys)")
+                };
+                findings.push(self.finding(
+                    format!(
+                        "Legend {} on {} has contrast {:.1}:1 (minimum {MIN_LEGEND_CONTRAST:.0}:1){keys}",
+                        legend.to_hex(),
+                        color.to_hex(),
+                        legend.contrast_ratio(&color)
+                    ),
+                    Some(layer_idx),
+                    Some(position),
+                ));
+            }
+        }
+    }
+}
+
+/// L009: category colors should be distinguishable, including for
+/// colorblind users.
+struct SimilarCategoryColors;
+
+impl LintRule for SimilarCategoryColors {
+    fn id(&self) -> &'static str {
+        "L009"
+    }
+
+    fn name(&self) -> &'static str {
+        "similar-category-colors"
+    }
+
+    fn description(&self) -> &'static str {
+        "Category colors should be distinguishable with common color vision deficiencies"
+    }
+
+    fn severity(&self) -> LintSeverity {
+        LintSeverity::Warning
+    }
+
+    fn check(&self, ctx: &LintContext<'_>, findings: &mut Vec<LintFinding>) {
+        let min_distance = f32::from(
+            ctx.layout
+                .metadata
+                .lint
+                .min_color_distance
+                .unwrap_or(DEFAULT_MIN_COLOR_DISTANCE),
+        );
+        let categories = &ctx.layout.categories;
+
+        for (i, a) in categories.iter().enumerate() {
+            for b in &categories[i + 1..] {
+                let normal = a.color.perceptual_distance(&b.color);
+                let (distance, vision) = ColorVision::ALL
+                    .iter()
+                    .map(|&vision| {
+                        let d = a
+                            .color
+                            .simulate(vision)
+                            .perceptual_distance(&b.color.simulate(vision));
+                        (d, Some(vision))
+                    })
+                    .fold(
+                        (normal, None),
+                        |best, next| {
+                            if next.0 < best.0 {
+                                next
+                            } else {
+                                best
+                            }
+                        },
+                    );
+                if distance >= min_distance {
+                    continue;
+                }
+                let seen_by = vision.map_or_else(String::new, |v| format!(" with {v}"));
+                findings.push(self.finding(
+                    format!(
+                        "Categories '{}' ({}) and '{}' ({}) are hard to tell apart{seen_by} (ΔE {distance:.1}, minimum {min_distance:.0})",
+                        a.name,
+                        a.color.to_hex(),
+                        b.name,
+                        b.color.to_hex()
+                    ),
+                    None,
+                    None,
+                ));
+            }
+        }
+    }
+}
+
+/// L010: key colors must stay visible at the layout's RGB brightness.
+struct DimAtBrightness;
+
+impl LintRule for DimAtBrightness {
+    fn id(&self) -> &'static str {
+        "L010"
+    }
+
+    fn name(&self) -> &'static str {
+        "dim-at-brightness"
+    }
+
+    fn description(&self) -> &'static str {
+        "Key colors should stay visible on the board at the layout's RGB brightness"
+    }
+
+    fn severity(&self) -> LintSeverity {
+        LintSeverity::Warning
+    }
+
+    fn check(&self, ctx: &LintContext<'_>, findings: &mut Vec<LintFinding>) {
+        if !ctx.layout.rgb_enabled {
+            return;
+        }
+        let brightness = ctx.layout.rgb_brightness.as_percent();
+        let threshold = ctx
+            .layout
+            .metadata
+            .lint
+            .min_led_value
+            .unwrap_or(MIN_VISIBLE_LED_VALUE);
+
+        for (layer_idx, layer) in ctx.layout.layers.iter().enumerate() {
+            // One finding per color, located at its first key
+            let mut too_dim: Vec<(RgbColor, Position, usize)> = Vec::new();
+            for key in layer.keys.iter().filter(|k| !is_inert(k)) {
+                let Some(color) = ctx.layout.resolve_key_color_if_enabled(layer_idx, key) else {
+                    continue;
+                };
+                // Pure black is a deliberate "off", not a visibility problem
+                if color.value() == 0 || color.scaled_value(brightness) >= threshold {
+                    continue;
+                }
+                match too_dim.iter_mut().find(|(c, _, _)| *c == color) {
+                    Some((_, _, count)) => *count += 1,
+                    None => too_dim.push((color, key.position, 1)),
+                }
+            }
+
+            for (color, position, count) in too_dim {
+                let keys = if count == 1 {
+                    String::new()
+                } else {
+                    format!(" ({count} keys)")
+                };
+                let visible_at = color.min_visible_brightness(threshold).map_or_else(
+                    || "too dark at any brightness".to_string(),
+                    |percent| format!("visible from {percent}%"),
+                );
+                findings.push(self.finding(
+                    format!(
+                        "Key color {} looks black on the board at {brightness}% brightness; {visible_at}{keys}",
+                        color.to_hex()
+                    ),
+                    Some(layer_idx),
+                    Some(position),
+                ));
+            }
+        }
+    }
+}
+
+/// L011: two keys on a layer should not type the same shifted symbol.
+struct DuplicateShiftedSymbol;
+
+impl LintRule for DuplicateShiftedSymbol {
+    fn id(&self) -> &'static str {
+        "L011"
+    }
+
+    fn name(&self) -> &'static str {
+        "duplicate-shifted-symbol"
+    }
+
+    fn description(&self) -> &'static str {
+        "Keys on a layer should not type the same shifted symbol (e.g., KC_LPRN and S(KC_9))"
+    }
+
+    fn severity(&self) -> LintSeverity {
+        LintSeverity::Warning
+    }
+
+    fn check(&self, ctx: &LintContext<'_>, findings: &mut Vec<LintFinding>) {
+        let setting = ctx.layout.metadata.lint.host_layout.as_deref();
+        if let Some(name) = setting.filter(|name| HostLayout::from_name(name).is_none()) {
+            let known: Vec<&str> = HostLayout::ALL.iter().map(|h| h.name()).collect();
+            findings.push(self.finding(
+                format!(
+                    "Unknown host layout '{name}' (expected one of: {}); assuming {}",
+                    known.join(", "),
+                    HostLayout::default()
+                ),
+                None,
+                None,
+            ));
+        }
+        let host = host_layout(ctx.layout);
+
+        for (layer_idx, layer) in ctx.layout.layers.iter().enumerate() {
+            // One finding per symbol, located at its first key
+            let mut symbols: Vec<(ShiftedSymbol, Vec<&KeyDefinition>)> = Vec::new();
+            for key in &layer.keys {
+                let Some(symbol) = ShiftedSymbol::parse(&key.keycode, host) else {
+                    continue;
+                };
+                match symbols.iter_mut().find(|(s, _)| *s == symbol) {
+                    Some((_, keys)) => keys.push(key),
+                    None => symbols.push((symbol, vec![key])),
+                }
+            }
+
+            for (symbol, keys) in symbols.iter().filter(|(_, keys)| keys.len() > 1) {
+                let spellings: Vec<String> = keys
+                    .iter()
+                    .map(|k| format!("{} at ({}, {})", k.keycode, k.position.row, k.position.col))
+                    .collect();
+                findings.push(self.finding(
+                    format!(
+                        "{} keys type '{}' on a {host} host: {}",
+                        keys.len(),
+                        symbol.produces,
+                        spellings.join(", ")
+                    ),
+                    Some(layer_idx),
+                    Some(keys[0].position),
+                ));
+            }
+        }
+    }
+}
+
+/// L012: shifted symbols should be spelled in the form set by
+/// `lint.shifted_form`.
+struct ShiftedFormPreference;
+
+impl ShiftedFormPreference {
+    /// Keys spelled against the preference, with the preferred spelling.
+    fn mismatches(layout: &Layout) -> Vec<(usize, Position, char, String)> {
+        let Some(form) = layout.metadata.lint.shifted_form else {
+            return Vec::new();
+        };
+        let host = host_layout(layout);
+        let mut mismatches = Vec::new();
+        for (layer_idx, layer) in layout.layers.iter().enumerate() {
+            for key in &layer.keys {
+                let Some(symbol) = ShiftedSymbol::parse(&key.keycode, host) else {
+                    continue;
+                };
+                let preferred = symbol.spelled(form, host);
+                if preferred != key.keycode {
+                    mismatches.push((layer_idx, key.position, symbol.produces, preferred));
+                }
+            }
+        }
+        mismatches
+    }
+}
+
+impl LintRule for ShiftedFormPreference {
+    fn id(&self) -> &'static str {
+        "L012"
+    }
+
+    fn name(&self) -> &'static str {
+        "shifted-form"
+    }
+
+    fn description(&self) -> &'static str {
+        "Shifted symbols should be spelled in the form set by lint.shifted_form"
+    }
+
+    fn severity(&self) -> LintSeverity {
+        LintSeverity::Info
+    }
+
+    fn check(&self, ctx: &LintContext<'_>, findings: &mut Vec<LintFinding>) {
+        for (layer_idx, position, produces, preferred) in Self::mismatches(ctx.layout) {
+            let keycode = ctx.layout.layers[layer_idx]
+                .get_key(position)
+                .map_or("", |k| k.keycode.as_str());
+            findings.push(self.finding(
+                format!("{keycode} types '{produces}'; the layout prefers {preferred}"),
+                Some(layer_idx),
+                Some(position),
+            ));
+        }
+    }
+
+    fn fix(&self, layout: &mut Layout) -> Vec<FixChange> {
+        let mut changes = Vec::new();
+        for (layer_idx, position, _, preferred) in Self::mismatches(layout) {
+            let Some(key) = layout.layers[layer_idx].get_key_mut(position) else {
+                continue;
+            };
+            changes.push(FixChange {
+                key: Some((layer_idx, position)),
+                description: format!("{} → {preferred}", key.keycode),
+            });
+            key.keycode = preferred;
+        }
+        changes
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::models::{Category, Layer, RgbBrightness, RgbColor};
+
+    /// Builds a 2x3 layout with the given keycodes per layer.
+    fn layout_with(layers: &[&[&str]]) -> Layout {
+        let mut layout = Layout::new("Lint Test").unwrap();
+        for (idx, keycodes) in layers.iter().enumerate() {
+            let mut layer = Layer::new(
+                u8::try_from(idx).unwrap(),
+                format!("Layer {idx}"),
+                RgbColor::new(255, 255, 255),
+            )
+            .unwrap();
+            for (i, keycode) in keycodes.iter().enumerate() {
+                let position =
+                    Position::new(u8::try_from(i / 3).unwrap(), u8::try_from(i % 3).unwrap());
+                layer
+                    .add_key(KeyDefinition::new(position, *keycode))
+                    .unwrap();
+            }
+            layout.add_layer(layer).unwrap();
+        }
+        layout
+    }
+
+    fn lint_rule(layout: &Layout, rule: &str) -> Vec<LintFinding> {
+        Linter::default()
+            .lint(layout)
+            .findings_for(rule)
+            .cloned()
+            .collect()
+    }
+
+    #[test]
+    fn test_rule_ids_are_unique() {
+        let rules = builtin_rules();
+        let mut ids: Vec<&str> = rules.iter().map(|r| r.id()).collect();
+        ids.dedup();
+        assert_eq!(ids.len(), rules.len());
+        assert!(rules.len() >= 6);
+    }
+
+    #[test]
+    fn test_thumb_keys() {
+        let layout =
+            layout_with(&[&["KC_Q", "KC_W", "KC_E", "KC_SPC", "LT(1, KC_ENT)", "KC_LSFT"]]);
+        let findings = lint_rule(&layout, "L001");
+        assert_eq!(findings.len(), 1);
+        assert_eq!(findings[0].position, Some(Position::new(1, 0)));
+        assert_eq!(findings[0].severity, LintSeverity::Info);
+    }
+
+    #[test]
+    fn test_bare_modifier_off_base() {
+        let layout = layout_with(&[
+            &["KC_LSFT", "MO(1)", "KC_E", "KC_A", "KC_B", "KC_C"],
+            &[
+                "KC_TRNS",
+                "KC_TRNS",
+                "KC_LCTL",
+                "OSM(MOD_LSFT)",
+                "KC_TRNS",
+                "KC_TRNS",
+            ],
+        ]);
+        let findings = lint_rule(&layout, "L002");
+        assert_eq!(findings.len(), 1);
+        assert_eq!(findings[0].layer, Some(1));
+        assert_eq!(findings[0].position, Some(Position::new(0, 2)));
+    }
+
+    #[test]
+    fn test_layer_return() {
+        // TG(1) from base with no way back
+        let trapped = layout_with(&[
+            &["TG(1)", "KC_W", "KC_E", "KC_A", "KC_B", "KC_C"],
+            &["KC_1", "KC_2", "KC_3", "KC_4", "KC_5", "KC_6"],
+        ]);
+        let findings = lint_rule(&trapped, "L003");
+        assert_eq!(findings.len(), 1);
+        assert_eq!(findings[0].severity, LintSeverity::Error);
+
+        // TG(1) on layer 1 turns it back off
+        let toggled = layout_with(&[
+            &["TG(1)", "KC_W", "KC_E", "KC_A", "KC_B", "KC_C"],
+            &["TG(1)", "KC_2", "KC_3", "KC_4", "KC_5", "KC_6"],
+        ]);
+        assert!(lint_rule(&toggled, "L003").is_empty());
+
+        // Momentary layers release on their own
+        let momentary = layout_with(&[
+            &["MO(1)", "KC_W", "KC_E", "KC_A", "KC_B", "KC_C"],
+            &["KC_TRNS", "KC_2", "KC_3", "KC_4", "KC_5", "KC_6"],
+        ]);
+        assert!(lint_rule(&momentary, "L003").is_empty());
+    }
+
+    #[test]
+    fn test_redundant_color_override() {
+        let mut layout = layout_with(&[&["KC_Q", "KC_W", "KC_E", "KC_A", "KC_B", "KC_C"]]);
+        let red = RgbColor::new(255, 0, 0);
+        layout
+            .categories
+            .push(Category::new("alpha", "Alpha", red).unwrap());
+        layout.layers[0].keys[0].category_id = Some("alpha".to_string());
+        layout.layers[0].keys[0].color_override = Some(red);
+        layout.layers[0].keys[1].category_id = Some("alpha".to_string());
+        layout.layers[0].keys[1].color_override = Some(RgbColor::new(0, 0, 255));
+
+        let findings = lint_rule(&layout, "L004");
+        assert_eq!(findings.len(), 1);
+        assert_eq!(findings[0].position, Some(Position::new(0, 0)));
+    }
+
+    #[test]
+    fn test_unreachable_layer() {
+        let layout = layout_with(&[
+            &["MO(1)", "KC_W", "KC_E", "KC_A", "KC_B", "KC_C"],
+            &["KC_1", "KC_2", "KC_3", "KC_4", "KC_5", "KC_6"],
+            &["KC_F1", "KC_F2", "KC_F3", "KC_F4", "KC_F5", "MO(2)"],
+        ]);
+        let findings = lint_rule(&layout, "L005");
+        assert_eq!(findings.len(), 1);
+        assert_eq!(findings[0].layer, Some(2));
+    }
+
+    #[test]
+    fn test_self_referencing_layer_key() {
+        let layout = layout_with(&[
+            &["MO(1)", "KC_W", "KC_E", "KC_A", "KC_B", "KC_C"],
+            &["MO(1)", "TG(1)", "KC_3", "KC_4", "KC_5", "KC_6"],
+        ]);
+        let findings = lint_rule(&layout, "L006");
+        assert_eq!(findings.len(), 1);
+        assert_eq!(findings[0].position, Some(Position::new(0, 0)));
+    }
+
+    #[test]
+    fn test_empty_layer() {
+        let layout = layout_with(&[
+            &["MO(1)", "KC_W", "KC_E", "KC_A", "KC_B", "KC_C"],
+            &[
+                "KC_TRNS", "KC_TRNS", "KC_NO", "KC_TRNS", "KC_TRNS", "KC_TRNS",
+            ],
+        ]);
+        let findings = lint_rule(&layout, "L007");
+        assert_eq!(findings.len(), 1);
+        assert_eq!(findings[0].layer, Some(1));
+    }
+
+    #[test]
+    fn test_disabled_rules_are_skipped() {
+        let mut layout = layout_with(&[
+            &["TG(1)", "KC_W", "KC_E", "KC_A", "KC_B", "KC_C"],
+            &["KC_1", "KC_2", "KC_3", "KC_4", "KC_5", "KC_6"],
+        ]);
+        layout.metadata.lint.disable = vec!["l003".to_string()];
+
+        let report = Linter::default().lint(&layout);
+        assert!(report.disabled.contains(&"L003"));
+        assert!(!report.checked.contains(&"L003"));
+        assert_eq!(report.findings_for("L003").count(), 0);
+        assert!(!report.has_errors());
+
+        // Rules disabled in the config are skipped as well
+        layout.metadata.lint.disable.clear();
+        let report = Linter::default()
+            .with_disabled(&["L003".to_string()])
+            .lint(&layout);
+        assert!(report.disabled.contains(&"L003"));
+    }
+
+    #[test]
+    fn test_custom_rule() {
+        struct NoKcA;
+        impl LintRule for NoKcA {
+            fn id(&self) -> &'static str {
+                "X001"
+            }
+            fn name(&self) -> &'static str {
+                "no-kc-a"
+            }
+            fn description(&self) -> &'static str {
+                "KC_A is not allowed"
+            }
+            fn severity(&self) -> LintSeverity {
+                LintSeverity::Error
+            }
+            fn check(&self, ctx: &LintContext<'_>, findings: &mut Vec<LintFinding>) {
+                for key in &ctx.layout.layers[0].keys {
+                    if key.keycode == "KC_A" {
+                        findings.push(self.finding(
+                            "KC_A".to_string(),
+                            Some(0),
+                            Some(key.position),
+                        ));
+                    }
+                }
+            }
+        }
+
+        let layout = layout_with(&[&["KC_Q", "KC_W", "KC_E", "KC_A", "KC_B", "KC_C"]]);
+        let report = Linter::new(vec![Box::new(NoKcA)]).lint(&layout);
+        assert_eq!(report.findings.len(), 1);
+        assert!(report.has_errors());
+    }
+
+    #[test]
+    fn test_legend_contrast() {
+        let mut layout = layout_with(&[&["KC_Q", "KC_W", "KC_E", "KC_A", "KC_TRNS", "KC_C"]]);
+        layout.layers[0].default_color = RgbColor::new(30, 30, 30);
+        let yellow = RgbColor::new(255, 255, 0);
+        for key in &mut layout.layers[0].keys[..5] {
+            key.color_override = Some(yellow);
+        }
+
+        // Transparent keys are skipped; identical colors are reported once
+        let findings = lint_rule(&layout, "L008");
+        assert_eq!(findings.len(), 1);
+        assert_eq!(findings[0].position, Some(Position::new(0, 0)));
+        assert!(findings[0].message.contains("#FFFF00"));
+        assert!(findings[0].message.contains("(4 keys)"));
+
+        // Dark legends are readable on yellow but not on the dark default
+        layout.metadata.lint.legend_color = Some("#000000".to_string());
+        let findings = lint_rule(&layout, "L008");
+        assert_eq!(findings.len(), 1);
+        assert_eq!(findings[0].position, Some(Position::new(1, 2)));
+    }
+
+    #[test]
+    fn test_similar_category_colors() {
+        let mut layout = layout_with(&[&["KC_Q", "KC_W", "KC_E", "KC_A", "KC_B", "KC_C"]]);
+        layout.categories = vec![
+            Category::new("errors", "Errors", RgbColor::new(0xD3, 0x2F, 0x2F)).unwrap(),
+            Category::new("ok", "OK", RgbColor::new(0x38, 0x8E, 0x3C)).unwrap(),
+            Category::new("nav", "Navigation", RgbColor::new(0x56, 0xB4, 0xE9)).unwrap(),
+        ];
+
+        let findings = lint_rule(&layout, "L009");
+        assert_eq!(findings.len(), 1);
+        assert!(findings[0].message.contains("'Errors'"));
+        assert!(findings[0].message.contains("'OK'"));
+
+        layout.metadata.lint.min_color_distance = Some(1);
+        assert!(lint_rule(&layout, "L009").is_empty());
+    }
+
+    #[test]
+    fn test_dim_at_brightness() {
+        let mut layout = layout_with(&[&["KC_Q", "KC_W", "KC_E", "KC_R"]]);
+        let navy = RgbColor::new(0, 0, 0x50);
+        for col in 0..3 {
+            layout.layers[0].keys[col].color_override = Some(navy);
+        }
+        layout.layers[0].keys[3].color_override = Some(RgbColor::new(0, 0, 0));
+
+        // Full brightness keeps navy visible, and black is never flagged
+        assert!(lint_rule(&layout, "L010").is_empty());
+
+        layout.rgb_brightness = RgbBrightness::new(20);
+        let findings = lint_rule(&layout, "L010");
+        assert_eq!(findings.len(), 1);
+        assert_eq!(findings[0].position, Some(Position::new(0, 0)));
+        assert!(findings[0].message.contains("#000050"));
+        assert!(findings[0].message.contains("20%"));
+        assert!(findings[0].message.contains("visible from 30%"));
+        assert!(findings[0].message.contains("(3 keys)"));
+
+        // A lower threshold accepts the dim navy
+        layout.metadata.lint.min_led_value = Some(16);
+        assert!(lint_rule(&layout, "L010").is_empty());
+
+        // Nothing is lit when the RGB master switch is off
+        layout.metadata.lint.min_led_value = None;
+        layout.rgb_enabled = false;
+        assert!(lint_rule(&layout, "L010").is_empty());
+    }
+
+    #[test]
+    fn test_duplicate_shifted_symbol() {
+        let mut layout = layout_with(&[&["KC_LPRN", "KC_A", "S(KC_9)", "KC_RPRN", "KC_9", "KC_B"]]);
+        let findings = lint_rule(&layout, "L011");
+        assert_eq!(findings.len(), 1);
+        assert_eq!(findings[0].position, Some(Position::new(0, 0)));
+        assert!(findings[0].message.contains("'('"));
+
+        // On a German host Shift+9 is ")", so KC_LPRN clashes with DE_RPRN
+        layout.layers[0].keys[2].keycode = "DE_RPRN".to_string();
+        layout.metadata.lint.host_layout = Some("german".to_string());
+        let findings = lint_rule(&layout, "L011");
+        assert_eq!(findings.len(), 1);
+        assert!(findings[0].message.contains("')'"));
+
+        layout.metadata.lint.host_layout = Some("dvorak".to_string());
+        let findings = lint_rule(&layout, "L011");
+        assert!(findings[0].message.contains("Unknown host layout 'dvorak'"));
+    }
+
+    #[test]
+    fn test_shifted_form_preference_and_fix() {
+        let mut layout = layout_with(&[&["KC_LPRN", "S(KC_0)", "LSFT(KC_1)", "KC_A"]]);
+        assert!(lint_rule(&layout, "L012").is_empty(), "No preference set");
+
+        layout.metadata.lint.shifted_form = Some(crate::models::ShiftedForm::Named);
+        let findings = lint_rule(&layout, "L012");
+        assert_eq!(findings.len(), 2);
+        assert_eq!(findings[0].severity, LintSeverity::Info);
+        assert_eq!(
+            findings[0].message,
+            "S(KC_0) types ')'; the layout prefers KC_RPRN"
+        );
+
+        let fixed = Linter::default().fix(&mut layout);
+        assert_eq!(fixed.len(), 1);
+        assert_eq!(fixed[0].0, "L012");
+        assert_eq!(fixed[0].1[0].description, "S(KC_0) → KC_RPRN");
+        let keycodes: Vec<&str> = layout.layers[0]
+            .keys
+            .iter()
+            .map(|k| k.keycode.as_str())
+            .collect();
+        assert_eq!(keycodes, ["KC_LPRN", "KC_RPRN", "KC_EXLM", "KC_A"]);
+        assert!(lint_rule(&layout, "L012").is_empty());
+
+        // Disabled rules are not fixed either
+        layout.metadata.lint.shifted_form = Some(crate::models::ShiftedForm::Modifier);
+        layout.metadata.lint.disable = vec!["L012".to_string()];
+        assert!(Linter::default().fix(&mut layout).is_empty());
+    }
+}
@@ -0,0 +1,439 @@
+//! Mirroring keys onto the other half of a split keyboard.
+//!
+//! Each key's center is reflected across the vertical center line of the
+//! geometry's bounding box, and the key whose center lies within half a unit
+//! of the reflection is its counterpart. Keys on the center line, or on
+//! boards whose halves don't line up, have no counterpart and are skipped.
+//!
+//! With side swapping on, left modifiers become right ones and vice versa,
+//! including inside `MT()`, `OSM()` and modifier wrappers, so mirrored home
+//! row mods stay symmetric.
+
+use crate::models::{KeyboardGeometry, Layer, Position, VisualLayoutMapping};
+use crate::services::keycode_rename::map_keycode_tokens;
+
+/// Furthest a counterpart's center may be from the reflected center, in units
+const MATCH_TOLERANCE: f32 = 0.5;
+
+/// Furthest a key's center may be from the center line to count as on it
+const CENTER_TOLERANCE: f32 = 0.25;
+
+/// Left and right variants of modifier keycodes, masks and wrappers.
+const SIDE_PAIRS: &[(&str, &str)] = &[
+    // Basic keycodes
+    ("KC_LCTL", "KC_RCTL"),
+    ("KC_LSFT", "KC_RSFT"),
+    ("KC_LALT", "KC_RALT"),
+    ("KC_LGUI", "KC_RGUI"),
+    ("KC_LOPT", "KC_ROPT"),
+    ("KC_LCMD", "KC_RCMD"),
+    ("KC_LWIN", "KC_RWIN"),
+    ("KC_LEFT_CTRL", "KC_RIGHT_CTRL"),
+    ("KC_LEFT_SHIFT", "KC_RIGHT_SHIFT"),
+    ("KC_LEFT_ALT", "KC_RIGHT_ALT"),
+    ("KC_LEFT_GUI", "KC_RIGHT_GUI"),
+    // Modifier masks for MT() and OSM()
+    ("MOD_LCTL", "MOD_RCTL"),
+    ("MOD_LSFT", "MOD_RSFT"),
+    ("MOD_LALT", "MOD_RALT"),
+    ("MOD_LGUI", "MOD_RGUI"),
+    // One-shot modifiers
+    ("OS_LCTL", "OS_RCTL"),
+    ("OS_LSFT", "OS_RSFT"),
+    ("OS_LALT", "OS_RALT"),
+    ("OS_LGUI", "OS_RGUI"),
+    // Modifier wrappers such as LSFT(KC_A)
+    ("LCTL", "RCTL"),
+    ("LSFT", "RSFT"),
+    ("LALT", "RALT"),
+    ("LGUI", "RGUI"),
+    ("LOPT", "ROPT"),
+    ("LCMD", "RCMD"),
+    ("LWIN", "RWIN"),
+    ("LCS", "RCS"),
+    ("LCA", "RCA"),
+    ("LSA", "RSA"),
+    ("LSG", "RSG"),
+    ("LAG", "RAG"),
+    // Mod-tap shorthands
+    ("LCTL_T", "RCTL_T"),
+    ("LSFT_T", "RSFT_T"),
+    ("LALT_T", "RALT_T"),
+    ("LGUI_T", "RGUI_T"),
+    ("LOPT_T", "ROPT_T"),
+    ("LCMD_T", "RCMD_T"),
+    ("LWIN_T", "RWIN_T"),
+    ("LCS_T", "RCS_T"),
+    ("LCA_T", "RCA_T"),
+    ("LSA_T", "RSA_T"),
+    ("LSG_T", "RSG_T"),
+    ("LAG_T", "RAG_T"),
+];
+
+/// Half of the board a whole-layer mirror copies from.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum MirrorDirection {
+    /// Copy the left half onto the right half
+    LeftToRight,
+    /// Copy the right half onto the left half
+    RightToLeft,
+}
+
+impl MirrorDirection {
+    /// Name as written on the command line.
+    #[must_use]
+    pub const fn name(self) -> &'static str {
+        match self {
+            Self::LeftToRight => "left-to-right",
+            Self::RightToLeft => "right-to-left",
+        }
+    }
+
+    /// Side of the board keys are copied from.
+    #[must_use]
+    pub const fn source_side(self) -> &'static str {
+        match self {
+            Self::LeftToRight => "left",
+            Self::RightToLeft => "right",
+        }
+    }
+
+    /// Direction copying from the half `position` is on, or `None` for a key
+    /// on the center line.
+    #[must_use]
+    pub fn from_side(
+        geometry: &KeyboardGeometry,
+        mapping: &VisualLayoutMapping,
+        position: Position,
+    ) -> Option<Self> {
+        let center = center_line(geometry)?;
+        let &(_, x, _) = key_centers(geometry, mapping)
+            .iter()
+            .find(|(pos, _, _)| *pos == position)?;
+        if x < center - CENTER_TOLERANCE {
+            Some(Self::LeftToRight)
+        } else if x > center + CENTER_TOLERANCE {
+            Some(Self::RightToLeft)
+        } else {
+            None
+        }
+    }
+}
+
+impl std::str::FromStr for MirrorDirection {
+    type Err = String;
+
+    fn from_str(value: &str) -> Result<Self, Self::Err> {
+        match value.to_lowercase().as_str() {
+            "left-to-right" => Ok(Self::LeftToRight),
+            "right-to-left" => Ok(Self::RightToLeft),
+            _ => Err(format!(
+                "Invalid direction '{value}'. Must be 'left-to-right' or 'right-to-left'"
+            )),
+        }
+    }
+}
+
+/// Keys to copy from one half of a layer onto the other.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct MirrorPlan {
+    /// Index of the layer the plan was made for
+    pub layer: usize,
+    /// Source and target position of each mirrored key
+    pub pairs: Vec<(Position, Position)>,
+    /// Number of source keys without a symmetric counterpart
+    pub skipped: usize,
+    /// Whether left and right modifier variants are swapped
+    pub swap_sides: bool,
+}
+
+impl MirrorPlan {
+    /// Pairs each of `sources` with its symmetric counterpart.
+    #[must_use]
+    pub fn new(
+        geometry: &KeyboardGeometry,
+        mapping: &VisualLayoutMapping,
+        layer: usize,
+        sources: &[Position],
+        swap_sides: bool,
+    ) -> Self {
+        let centers = key_centers(geometry, mapping);
+        let mut pairs = Vec::new();
+        let mut skipped = 0;
+        for &source in sources {
+            match counterpart(geometry, &centers, source) {
+                Some(target) => pairs.push((source, target)),
+                None => skipped += 1,
+            }
+        }
+        Self {
+            layer,
+            pairs,
+            skipped,
+            swap_sides,
+        }
+    }
+
+    /// Pairs every key on the source half of `direction` with its
+    /// counterpart; keys on the center line are left alone.
+    #[must_use]
+    pub fn for_layer(
+        geometry: &KeyboardGeometry,
+        mapping: &VisualLayoutMapping,
+        layer: usize,
+        direction: MirrorDirection,
+        swap_sides: bool,
+    ) -> Self {
+        let center = center_line(geometry).unwrap_or_default();
+        let mut sources: Vec<_> = key_centers(geometry, mapping)
+            .into_iter()
+            .filter(|&(_, x, _)| match direction {
+                MirrorDirection::LeftToRight => x < center - CENTER_TOLERANCE,
+                MirrorDirection::RightToLeft => x > center + CENTER_TOLERANCE,
+            })
+            .map(|(pos, _, _)| pos)
+            .collect();
+        sources.sort_by_key(|pos| (pos.row, pos.col));
+        Self::new(geometry, mapping, layer, &sources, swap_sides)
+    }
+
+    /// Whether `position` on `layer` would be overwritten.
+    #[must_use]
+    pub fn is_target(&self, layer: usize, position: Position) -> bool {
+        layer == self.layer && self.pairs.iter().any(|(_, to)| *to == position)
+    }
+
+    /// Copies each source key's keycode, color and category onto its target,
+    /// returning the number of keys written.
+    ///
+    /// Sources are read before anything is written, so a selection spanning
+    /// both halves swaps them.
+    pub fn apply(&self, layer: &mut Layer) -> usize {
+        let copies: Vec<_> = self
+            .pairs
+            .iter()
+            .filter_map(|&(from, to)| {
+                let key = layer.get_key(from)?;
+                let keycode = if self.swap_sides {
+                    swap_modifier_sides(&key.keycode).unwrap_or_else(|| key.keycode.clone())
+                } else {
+                    key.keycode.clone()
+                };
+                Some((to, keycode, key.color_override, key.category_id.clone()))
+            })
+            .collect();
+
+        let mut applied = 0;
+        for (to, keycode, color_override, category_id) in copies {
+            if let Some(key) = layer.get_key_mut(to) {
+                key.keycode = keycode;
+                key.color_override = color_override;
+                key.category_id = category_id;
+                applied += 1;
+            }
+        }
+        applied
+    }
+}
+
+/// Swaps left and right modifier variants throughout `keycode`
+/// (`MT(MOD_LSFT, KC_A)` -> `MT(MOD_RSFT, KC_A)`).
+///
+/// Returns `None` if the keycode has no sided modifier.
+#[must_use]
+pub fn swap_modifier_sides(keycode: &str) -> Option<String> {
+    map_keycode_tokens(keycode, |token| {
+        SIDE_PAIRS.iter().find_map(|&(left, right)| {
+            if token == left {
+                Some(right)
+            } else if token == right {
+                Some(left)
+            } else {
+                None
+            }
+        })
+    })
+}
+
+/// Center of each key in keyboard units, by visual position.
+fn key_centers(
+    geometry: &KeyboardGeometry,
+    mapping: &VisualLayoutMapping,
+) -> Vec<(Position, f32, f32)> {
+    mapping
+        .get_all_visual_positions()
+        .into_iter()
+        .filter_map(|pos| {
+            let matrix = mapping.visual_to_matrix_pos(pos.row, pos.col)?;
+            let key = geometry.get_key_by_matrix(matrix)?;
+            let (x, y) = key.center();
+            Some((pos, x, y))
+        })
+        .collect()
+}
+
+/// Horizontal center of the geometry's bounding box, in units.
+fn center_line(geometry: &KeyboardGeometry) -> Option<f32> {
+    let left = geometry
+        .keys
+        .iter()
+        .map(|key| key.bounding_box().0)
+        .reduce(f32::min)?;
+    let right = geometry
+        .keys
+        .iter()
+        .map(|key| {
+            let (x, _, width, _) = key.bounding_box();
+            x + width
+        })
+        .reduce(f32::max)?;
+    Some(f32::midpoint(left, right))
+}
+
+/// Key nearest to the reflection of `source` across the board's vertical
+/// center, if one lies within [`MATCH_TOLERANCE`].
+fn counterpart(
+    geometry: &KeyboardGeometry,
+    centers: &[(Position, f32, f32)],
+    source: Position,
+) -> Option<Position> {
+    let center = center_line(geometry)?;
+    let &(_, x, y) = centers.iter().find(|(pos, _, _)| *pos == source)?;
+    let mirrored_x = 2.0f32.mul_add(center, -x);
+
+    centers
+        .iter()
+        .filter(|(pos, cx, cy)| {
+            *pos != source
+                && (cx - mirrored_x).abs() < MATCH_TOLERANCE
+                && (cy - y).abs() < MATCH_TOLERANCE
+        })
+        .min_by(|a, b| {
+            let distance = |(_, cx, cy): &&(Position, f32, f32)| (cx - mirrored_x).hypot(cy - y);
+            distance(a).total_cmp(&distance(b))
+        })
+        .map(|(pos, _, _)| *pos)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::models::{KeyDefinition, KeyGeometry, RgbColor};
+
+    /// Split board: two 2×3 halves with a one-unit gap, plus a lone thumb
+    /// key on the left with no partner.
+    fn split() -> (KeyboardGeometry, VisualLayoutMapping) {
+        let mut geometry = KeyboardGeometry::new("split", "LAYOUT", 3, 6);
+        let mut led = 0;
+        for row in 0..2u8 {
+            for col in 0..6u8 {
+                let x = if col < 3 { col } else { col + 1 };
+                geometry.add_key(KeyGeometry::new(
+                    (row, col),
+                    led,
+                    f32::from(x),
+                    f32::from(row),
+                ));
+                led += 1;
+            }
+        }
+        geometry.add_key(KeyGeometry::new((2, 0), led, 3.0, 2.0));
+        let mapping = VisualLayoutMapping::build(&geometry);
+        (geometry, mapping)
+    }
+
+    #[test]
+    fn test_swap_modifier_sides() {
+        assert_eq!(swap_modifier_sides("KC_LSFT").as_deref(), Some("KC_RSFT"));
+        assert_eq!(
+            swap_modifier_sides("MT(MOD_LGUI, KC_A)").as_deref(),
+            Some("MT(MOD_RGUI, KC_A)")
+        );
+        assert_eq!(
+            swap_modifier_sides("OSM(MOD_RCTL | MOD_LSFT)").as_deref(),
+            Some("OSM(MOD_LCTL | MOD_RSFT)")
+        );
+        assert_eq!(
+            swap_modifier_sides("LALT_T(KC_S)").as_deref(),
+            Some("RALT_T(KC_S)")
+        );
+        assert_eq!(swap_modifier_sides("KC_LEFT"), None);
+        assert_eq!(swap_modifier_sides("LT(1, KC_L)"), None);
+    }
+
+    #[test]
+    fn test_plan_pairs_symmetric_keys() {
+        let (geometry, mapping) = split();
+        let pos = |row, col| mapping.matrix_to_visual_pos(row, col).unwrap();
+
+        let sources = [pos(0, 0), pos(1, 2), pos(2, 0)];
+        let plan = MirrorPlan::new(&geometry, &mapping, 1, &sources, true);
+
+        assert_eq!(plan.pairs, [(pos(0, 0), pos(0, 5)), (pos(1, 2), pos(1, 3))]);
+        // The thumb key sits on the center line
+        assert_eq!(plan.skipped, 1);
+        assert!(plan.is_target(1, pos(0, 5)));
+        assert!(!plan.is_target(0, pos(0, 5)));
+    }
+
+    #[test]
+    fn test_apply_copies_and_swaps_sides() {
+        let (geometry, mapping) = split();
+        let mut layer = Layer::new(0, "Base", RgbColor::new(255, 255, 255)).unwrap();
+        for pos in mapping.get_all_visual_positions() {
+            layer
+                .add_key(KeyDefinition::at(pos).keycode("KC_TRNS"))
+                .unwrap();
+        }
+        let home = mapping.matrix_to_visual_pos(1, 0).unwrap();
+        let mirrored = mapping.matrix_to_visual_pos(1, 5).unwrap();
+        let key = layer.get_key_mut(home).unwrap();
+        key.keycode = "LGUI_T(KC_A)".to_string();
+        key.category_id = Some("mods".to_string());
+
+        let plan = MirrorPlan::new(&geometry, &mapping, 0, &[home], true);
+        assert_eq!(plan.apply(&mut layer), 1);
+        let target = layer.get_key(mirrored).unwrap();
+        assert_eq!(target.keycode, "RGUI_T(KC_A)");
+        assert_eq!(target.category_id.as_deref(), Some("mods"));
+        assert_eq!(layer.get_key(home).unwrap().keycode, "LGUI_T(KC_A)");
+
+        let plan = MirrorPlan::new(&geometry, &mapping, 0, &[home], false);
+        layer.get_key_mut(mirrored).unwrap().keycode = "KC_NO".to_string();
+        plan.apply(&mut layer);
+        assert_eq!(layer.get_key(mirrored).unwrap().keycode, "LGUI_T(KC_A)");
+    }
+
+    #[test]
+    fn test_layer_plan_takes_one_half() {
+        let (geometry, mapping) = split();
+        let pos = |row, col| mapping.matrix_to_visual_pos(row, col).unwrap();
+
+        let plan =
+            MirrorPlan::for_layer(&geometry, &mapping, 2, MirrorDirection::RightToLeft, false);
+        assert_eq!(plan.layer, 2);
+        assert_eq!(plan.pairs.len(), 6);
+        assert!(plan.pairs.contains(&(pos(0, 5), pos(0, 0))));
+        let right: Vec<_> = (0..2)
+            .flat_map(|row| (3..6).map(move |col| pos(row, col)))
+            .collect();
+        assert!(plan.pairs.iter().all(|(from, _)| right.contains(from)));
+        // The thumb key on the center line is neither source nor target
+        assert_eq!(plan.skipped, 0);
+        assert!(!plan.is_target(2, pos(2, 0)));
+
+        assert_eq!(
+            MirrorDirection::from_side(&geometry, &mapping, pos(1, 1)),
+            Some(MirrorDirection::LeftToRight)
+        );
+        assert_eq!(
+            MirrorDirection::from_side(&geometry, &mapping, pos(2, 0)),
+            None
+        );
+        assert_eq!(
+            "Right-To-Left".parse::<MirrorDirection>(),
+            Ok(MirrorDirection::RightToLeft)
+        );
+        assert!("up".parse::<MirrorDirection>().is_err());
+    }
+}
@@ -3,10 +3,28 @@
 //! This module contains services that encapsulate complex business logic
 //! and coordinate between different parts of the application.
 
+pub mod deprecated_keycodes;
 pub mod geometry;
+pub mod heatmap;
+pub mod history;
+pub mod key_roles;
+pub mod keycode_grid;
+pub mod keycode_rename;
 pub mod layer_refs;
+pub mod layout_format;
 pub mod layouts;
+pub mod library;
+pub mod lint;
+pub mod mirror;
+pub mod modifier_wrap;
+pub mod new_layout;
+pub mod quick_fix;
+pub mod remap;
+pub mod shifted_pairs;
+pub mod simulator;
+pub mod snapshots;
+pub mod templates;
 
 // Re-export GeometryService if it exists, otherwise just re-export the module
 // pub use geometry::GeometryService;
-pub use layouts::LayoutService;
+pub use layouts::{LayoutLoadError, LayoutService};
@@ -0,0 +1,394 @@
+//! Wrapping keycodes in a modifier, for turning a block of keys into their
+//! shifted or ctrl'd variants at once.
+//!
+//! `KC_X` becomes `LCTL(KC_X)`, or the mod-tap `LCTL_T(KC_X)`, and a key
+//! already wrapped in the chosen modifier (or an alias such as `C`) is
+//! unwrapped instead. Modifier
+//! wrappers nest (`LCTL(LSFT(KC_X))`) up to one per modifier; mod-taps only
+//! take a basic keycode. Keys that can't be wrapped are skipped with a reason.
+
+use std::fmt;
+
+use crate::models::{Layer, Position};
+
+/// Modifier wrappers recognized as part of a nested chain
+const MODIFIER_WRAPPERS: &[&str] = &[
+    "LCTL", "LSFT", "LALT", "LGUI", "RCTL", "RSFT", "RALT", "RGUI", "LOPT", "ROPT", "LCMD", "RCMD",
+    "LWIN", "RWIN", "LCS", "LCA", "LCG", "LSA", "LSG", "LAG", "LCSG", "LCAG", "LSAG", "RCS", "RCA",
+    "RCG", "RSA", "RSG", "RAG", "RCSG", "RCAG", "RSAG", "MEH", "HYPR", "C", "S", "A", "G",
+];
+
+/// Alternative names of modifier wrappers, with the name they stand for
+const WRAPPER_ALIASES: &[(&str, &str)] = &[
+    ("C", "LCTL"),
+    ("S", "LSFT"),
+    ("A", "LALT"),
+    ("G", "LGUI"),
+    ("LOPT", "LALT"),
+    ("ROPT", "RALT"),
+    ("LCMD", "LGUI"),
+    ("RCMD", "RGUI"),
+    ("LWIN", "LGUI"),
+    ("RWIN", "RGUI"),
+];
+
+/// Deepest chain of modifier wrappers: one per modifier
+const MAX_NESTING: usize = 4;
+
+/// Keycodes that stand for "no key" and have nothing to wrap
+const EMPTY_KEYCODES: &[&str] = &["KC_NO", "XXXXXXX", "KC_TRNS", "KC_TRANSPARENT", "_______"];
+
+/// Modifier a selection can be wrapped in.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum WrapModifier {
+    /// Left Shift
+    Shift,
+    /// Left Control
+    Ctrl,
+    /// Left Alt
+    Alt,
+    /// Left GUI
+    Gui,
+}
+
+impl WrapModifier {
+    /// Modifier for its prompt key: `s`, `c`, `a` or `g`.
+    #[must_use]
+    pub const fn from_char(c: char) -> Option<Self> {
+        match c {
+            's' => Some(Self::Shift),
+            'c' => Some(Self::Ctrl),
+            'a' => Some(Self::Alt),
+            'g' => Some(Self::Gui),
+            _ => None,
+        }
+    }
+
+    /// Modifier wrapper function, e.g. `LCTL`.
+    #[must_use]
+    pub const fn wrapper(self) -> &'static str {
+        match self {
+            Self::Shift => "LSFT",
+            Self::Ctrl => "LCTL",
+            Self::Alt => "LALT",
+            Self::Gui => "LGUI",
+        }
+    }
+
+    /// Mod-tap function, e.g. `LCTL_T`.
+    #[must_use]
+    pub const fn mod_tap(self) -> &'static str {
+        match self {
+            Self::Shift => "LSFT_T",
+            Self::Ctrl => "LCTL_T",
+            Self::Alt => "LALT_T",
+            Self::Gui => "LGUI_T",
+        }
+    }
+
+    /// Function used for `mod_tap` or plain wrapping.
+    #[must_use]
+    pub const fn function(self, mod_tap: bool) -> &'static str {
+        if mod_tap {
+            self.mod_tap()
+        } else {
+            self.wrapper()
+        }
+    }
+}
+
+/// Why a key was left alone.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum WrapSkip {
+    /// `KC_NO` or `KC_TRNS`
+    Empty,
+    /// Not a basic keycode, e.g. `MO(1)` or a mod-tap around a wrapper
+    NotBasic,
+    /// Already wrapped in the modifier further in
+    AlreadyWrapped,
+    /// Already nested [`MAX_NESTING`] wrappers deep
+    NestingLimit,
+}
+
+impl fmt::Display for WrapSkip {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(match self {
+            Self::Empty => "empty",
+            Self::NotBasic => "not a basic keycode",
+            Self::AlreadyWrapped => "already has the modifier",
+            Self::NestingLimit => "too deeply nested",
+        })
+    }
+}
+
+/// Rewritten keycode of one key.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Rewrite {
+    /// New keycode
+    pub keycode: String,
+    /// Whether the modifier was removed rather than added
+    pub unwrapped: bool,
+}
+
+/// Wraps `keycode` in `modifier`, or unwraps it if the modifier (or its
+/// mod-tap, with `mod_tap`) is already the outermost function.
+///
+/// # Errors
+///
+/// Returns why the keycode can't be wrapped.
+pub fn wrap_keycode(
+    keycode: &str,
+    modifier: WrapModifier,
+    mod_tap: bool,
+) -> Result<Rewrite, WrapSkip> {
+    let keycode = keycode.trim();
+    if keycode.is_empty() || EMPTY_KEYCODES.contains(&keycode) {
+        return Err(WrapSkip::Empty);
+    }
+
+    let function = modifier.function(mod_tap);
+    if let Some((outer, inner)) = split_function(keycode) {
+        if canonical_wrapper(outer) == function {
+            return Ok(Rewrite {
+                keycode: inner.to_string(),
+                unwrapped: true,
+            });
+        }
+    }
+
+    if mod_tap {
+        // Mod-taps only take a basic keycode
+        if !is_basic(keycode) {
+            return Err(WrapSkip::NotBasic);
+        }
+    } else {
+        let (chain, base) = wrapper_chain(keycode);
+        if !is_basic(base) {
+            return Err(WrapSkip::NotBasic);
+        }
+        if chain.contains(&function) {
+            return Err(WrapSkip::AlreadyWrapped);
+        }
+        if chain.len() >= MAX_NESTING {
+            return Err(WrapSkip::NestingLimit);
+        }
+    }
+
+    Ok(Rewrite {
+        keycode: format!("{function}({keycode})"),
+        unwrapped: false,
+    })
+}
+
+/// Keycode changes for wrapping a selection on one layer.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct WrapPlan {
+    /// Index of the layer the plan was made for
+    pub layer: usize,
+    /// New keycode of each changed key, in selection order
+    pub changes: Vec<(Position, Rewrite)>,
+    /// Keys left alone, with their keycode and the reason
+    pub skipped: Vec<(Position, String, WrapSkip)>,
+}
+
+impl WrapPlan {
+    /// Rewrites each key of `layer` at `positions`; positions without a key
+    /// are ignored.
+    #[must_use]
+    pub fn new(
+        layer: &Layer,
+        layer_index: usize,
+        positions: &[Position],
+        modifier: WrapModifier,
+        mod_tap: bool,
+    ) -> Self {
+        let mut changes = Vec::new();
+        let mut skipped = Vec::new();
+        for &position in positions {
+            let Some(key) = layer.get_key(position) else {
+                continue;
+            };
+            match wrap_keycode(&key.keycode, modifier, mod_tap) {
+                Ok(rewrite) => changes.push((position, rewrite)),
+                Err(reason) => skipped.push((position, key.keycode.clone(), reason)),
+            }
+        }
+        Self {
+            layer: layer_index,
+            changes,
+            skipped,
+        }
+    }
+
+    /// Number of keys the plan wraps.
+    #[must_use]
+    pub fn wrapped(&self) -> usize {
+        self.changes.iter().filter(|(_, r)| !r.unwrapped).count()
+    }
+
+    /// Number of keys the plan unwraps.
+    #[must_use]
+    pub fn unwrapped(&self) -> usize {
+        self.changes.iter().filter(|(_, r)| r.unwrapped).count()
+    }
+
+    /// Writes the new keycodes, returning the number of keys changed.
+    pub fn apply(&self, layer: &mut Layer) -> usize {
+        let mut applied = 0;
+        for (position, rewrite) in &self.changes {
+            if let Some(key) = layer.get_key_mut(*position) {
+                key.keycode.clone_from(&rewrite.keycode);
+                applied += 1;
+            }
+        }
+        applied
+    }
+}
+
+/// Splits `NAME(inner)` into its function name and argument.
+fn split_function(keycode: &str) -> Option<(&str, &str)> {
+    let (name, rest) = keycode.split_once('(')?;
+    let inner = rest.strip_suffix(')')?;
+    Some((name, inner.trim()))
+}
+
+/// Canonical name of a modifier wrapper, e.g. `LCTL` for `C`.
+fn canonical_wrapper(name: &str) -> &str {
+    WRAPPER_ALIASES
+        .iter()
+        .find(|(alias, _)| *alias == name)
+        .map_or(name, |(_, canonical)| canonical)
+}
+
+/// Canonical names of the modifier wrappers around `keycode`, outermost
+/// first, and the keycode they wrap.
+fn wrapper_chain(mut keycode: &str) -> (Vec<&str>, &str) {
+    let mut chain = Vec::new();
+    while let Some((name, inner)) = split_function(keycode) {
+        if !MODIFIER_WRAPPERS.contains(&name) {
+            break;
+        }
+        chain.push(canonical_wrapper(name));
+        keycode = inner;
+    }
+    (chain, keycode)
+}
+
+/// Whether `keycode` is a plain `KC_` keycode a modifier can wrap.
+fn is_basic(keycode: &str) -> bool {
+    keycode.starts_with("KC_")
+        && !keycode.contains(['(', ')', ',', ' '])
+        && !EMPTY_KEYCODES.contains(&keycode)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::keycode_db::KeycodeDb;
+    use crate::models::{KeyDefinition, RgbColor};
+
+    fn wrap(keycode: &str, modifier: WrapModifier) -> Result<String, WrapSkip> {
+        wrap_keycode(keycode, modifier, false).map(|r| r.keycode)
+    }
+
+    #[test]
+    fn test_wrap_and_unwrap_modifier() {
+        assert_eq!(wrap("KC_X", WrapModifier::Ctrl).unwrap(), "LCTL(KC_X)");
+        assert_eq!(
+            wrap("LSFT(KC_X)", WrapModifier::Ctrl).unwrap(),
+            "LCTL(LSFT(KC_X))"
+        );
+        let unwrapped = wrap_keycode("LCTL(LSFT(KC_X))", WrapModifier::Ctrl, false).unwrap();
+        assert_eq!(unwrapped.keycode, "LSFT(KC_X)");
+        assert!(unwrapped.unwrapped);
+    }
+
+    #[test]
+    fn test_wrap_skips_unwrappable_keycodes() {
+        assert_eq!(wrap("KC_TRNS", WrapModifier::Ctrl), Err(WrapSkip::Empty));
+        assert_eq!(wrap("MO(1)", WrapModifier::Ctrl), Err(WrapSkip::NotBasic));
+        assert_eq!(
+            wrap("LT(1, KC_A)", WrapModifier::Ctrl),
+            Err(WrapSkip::NotBasic)
+        );
+        assert_eq!(
+            wrap("LSFT(LCTL(KC_A))", WrapModifier::Ctrl),
+            Err(WrapSkip::AlreadyWrapped)
+        );
+        assert_eq!(
+            wrap("RCTL(RSFT(RALT(RGUI(KC_A))))", WrapModifier::Ctrl),
+            Err(WrapSkip::NestingLimit)
+        );
+    }
+
+    #[test]
+    fn test_aliases_count_as_the_modifier() {
+        for (alias, modifier) in [
+            ("C", WrapModifier::Ctrl),
+            ("S", WrapModifier::Shift),
+            ("A", WrapModifier::Alt),
+            ("G", WrapModifier::Gui),
+            ("LOPT", WrapModifier::Alt),
+            ("LCMD", WrapModifier::Gui),
+            ("LWIN", WrapModifier::Gui),
+        ] {
+            assert_eq!(
+                wrap(&format!("RCTL({alias}(KC_A))"), modifier),
+                Err(WrapSkip::AlreadyWrapped),
+                "{alias} inside"
+            );
+            let outer = wrap_keycode(&format!("{alias}(KC_A)"), modifier, false).unwrap();
+            assert_eq!(outer.keycode, "KC_A", "{alias} outermost");
+            assert!(outer.unwrapped);
+        }
+        // Right-hand aliases are other modifiers
+        assert_eq!(
+            wrap("ROPT(KC_A)", WrapModifier::Alt).unwrap(),
+            "LALT(ROPT(KC_A))"
+        );
+    }
+
+    #[test]
+    fn test_mod_tap_takes_only_basic_keycodes() {
+        let mod_tap = |keycode| wrap_keycode(keycode, WrapModifier::Gui, true);
+        assert_eq!(mod_tap("KC_A").unwrap().keycode, "LGUI_T(KC_A)");
+        assert_eq!(mod_tap("LGUI_T(KC_A)").unwrap().keycode, "KC_A");
+        assert_eq!(mod_tap("LSFT(KC_A)"), Err(WrapSkip::NotBasic));
+        assert_eq!(mod_tap("LCTL_T(KC_A)"), Err(WrapSkip::NotBasic));
+    }
+
+    #[test]
+    fn test_wrapped_keycodes_are_valid() {
+        let db = KeycodeDb::load().unwrap();
+        for keycode in [
+            "LCTL(KC_X)",
+            "LCTL(LSFT(KC_X))",
+            "LGUI(LALT(LCTL(LSFT(KC_X))))",
+            "LALT_T(KC_X)",
+        ] {
+            assert!(db.is_valid(keycode), "{keycode} should be valid");
+        }
+    }
+
+    #[test]
+    fn test_plan_counts_and_applies() {
+        let mut layer = Layer::new(0, "Base", RgbColor::new(255, 255, 255)).unwrap();
+        let positions: Vec<_> = (0..3).map(|col| Position::new(0, col)).collect();
+        for (position, keycode) in positions.iter().zip(["KC_A", "LCTL(KC_B)", "MO(1)"]) {
+            layer
+                .add_key(KeyDefinition::at(*position).keycode(keycode))
+                .unwrap();
+        }
+
+        let plan = WrapPlan::new(&layer, 0, &positions, WrapModifier::Ctrl, false);
+        assert_eq!((plan.wrapped(), plan.unwrapped()), (1, 1));
+        assert_eq!(
+            plan.skipped,
+            [(Position::new(0, 2), "MO(1)".to_string(), WrapSkip::NotBasic)]
+        );
+
+        assert_eq!(plan.apply(&mut layer), 2);
+        let keycodes: Vec<_> = layer.keys.iter().map(|k| k.keycode.as_str()).collect();
+        assert_eq!(keycodes, ["LCTL(KC_A)", "KC_B", "MO(1)"]);
+    }
+}
@@ -0,0 +1,189 @@
+//! Blank layouts for a keyboard, created by the setup wizard and `lazyqmk new`.
+//!
+//! New layouts start from the keyboard's stored [`KeyboardDefaults`] (keymap
+//! name pattern, layer count), with `KC_TRNS` on every key of every layer.
+
+use anyhow::{Context, Result};
+
+use crate::config::{Config, KeyboardDefaults};
+use crate::firmware::paths;
+use crate::models::layer::KeyDefinition;
+use crate::models::{ColorPalette, KeyboardGeometry, Layer, Layout, VisualLayoutMapping};
+use crate::services::geometry::{self, GeometryContext};
+
+/// A freshly created layout with the geometry it was built for.
+#[derive(Debug, Clone)]
+pub struct NewLayout {
+    /// The layout, not yet saved
+    pub layout: Layout,
+    /// Physical key positions of the keyboard
+    pub geometry: KeyboardGeometry,
+    /// Visual-to-matrix mapping of the keyboard
+    pub mapping: VisualLayoutMapping,
+}
+
+/// What to create; unset fields fall back to the keyboard's defaults.
+#[derive(Debug, Clone, Copy)]
+pub struct NewLayoutOptions<'a> {
+    /// QMK keyboard path (e.g., "crkbd/rev1")
+    pub keyboard: &'a str,
+    /// Layout variant (e.g., "`LAYOUT_split_3x6_3`")
+    pub variant: &'a str,
+    /// Layout name, also used for the file name
+    pub name: &'a str,
+    /// Keymap name instead of the keyboard's pattern or the layout name
+    pub keymap: Option<&'a str>,
+    /// Layer count instead of the keyboard's default (1 without one)
+    pub layer_count: Option<u8>,
+}
+
+/// Turns a layout name into a file and keymap directory name.
+///
+/// Lowercased, with path separators, colons and spaces replaced, so the
+/// keymap can't clash with QMK's own (e.g., `default`) by case alone.
+#[must_use]
+pub fn sanitize_name(name: &str) -> String {
+    name.replace(['/', '\\', ':', ' '], "_").to_lowercase()
+}
+
+/// Creates a layout for `options.keyboard` with a blank layer per layer count.
+pub fn create(config: &Config, options: NewLayoutOptions<'_>) -> Result<NewLayout> {
+    let defaults = config.keyboard_defaults(options.keyboard);
+    let mut layout = Layout::new(options.name)?;
+    layout.metadata.keyboard = Some(options.keyboard.to_string());
+
+    let geo_result = geometry::build_geometry_for_layout(
+        GeometryContext {
+            config,
+            metadata: &layout.metadata,
+        },
+        options.variant,
+    )?;
+
+    let sanitized_name = sanitize_name(options.name);
+    let keymap = keymap_name(&defaults, options, &sanitized_name);
+    paths::validate_keymap_name(&keymap)?;
+
+    layout.metadata.keyboard = Some(geo_result.variant_path);
+    layout.metadata.layout_variant = Some(options.variant.to_string());
+    layout.metadata.keymap_name = Some(keymap);
+    layout.metadata.output_format = Some("uf2".to_string());
+
+    let layer_count = options.layer_count.or(defaults.layer_count).unwrap_or(1);
+    if layer_count == 0 {
+        anyhow::bail!("A layout needs at least one layer");
+    }
+    for number in 0..layer_count {
+        let name = if number == 0 {
+            "Base".to_string()
+        } else {
+            format!("Layer {number}")
+        };
+        layout.add_layer(blank_layer(number, &name, &geo_result.mapping)?)?;
+    }
+
+    Ok(NewLayout {
+        layout,
+        geometry: geo_result.geometry,
+        mapping: geo_result.mapping,
+    })
+}
+
+/// Keymap name for a new layout: the explicit one, the keyboard's pattern,
+/// or the sanitized layout name.
+fn keymap_name(
+    defaults: &KeyboardDefaults,
+    options: NewLayoutOptions<'_>,
+    sanitized_name: &str,
+) -> String {
+    options.keymap.map_or_else(
+        || {
+            defaults
+                .keymap_name(options.keyboard, sanitized_name)
+                .unwrap_or_else(|| sanitized_name.to_string())
+        },
+        str::to_string,
+    )
+}
+
+/// Creates a layer with `KC_TRNS` on every key of `mapping`.
+pub fn blank_layer(number: u8, name: &str, mapping: &VisualLayoutMapping) -> Result<Layer> {
+    // Use the color palette's default layer color (Gray-500)
+    let palette = ColorPalette::load().unwrap_or_default();
+    let mut layer = Layer::new(number, name.to_string(), palette.default_layer_color())?;
+
+    // Keys use visual positions (not matrix positions) for proper rendering
+    for pos in mapping.get_all_visual_positions() {
+        layer
+            .add_key(KeyDefinition::new(pos, "KC_TRNS".to_string()))
+            .with_context(|| format!("Failed to add key at {pos:?}"))?;
+    }
+
+    Ok(layer)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::config::KeyboardDefaults;
+
+    fn options(keymap: Option<&str>) -> NewLayoutOptions<'_> {
+        NewLayoutOptions {
+            keyboard: "crkbd/rev1",
+            variant: "LAYOUT_split_3x6_3",
+            name: "My Corne",
+            keymap,
+            layer_count: None,
+        }
+    }
+
+    #[test]
+    fn test_keymap_name_uses_pattern_then_layout_name() {
+        let mut defaults = KeyboardDefaults::default();
+        assert_eq!(
+            keymap_name(&defaults, options(None), "my_corne"),
+            "my_corne"
+        );
+
+        defaults.keymap_pattern = Some("{keyboard}_{layout}".to_string());
+        assert_eq!(
+            keymap_name(&defaults, options(None), "my_corne"),
+            "crkbd_my_corne"
+        );
+        assert_eq!(
+            keymap_name(&defaults, options(Some("mine")), "my_corne"),
+            "mine"
+        );
+    }
+
+    #[test]
+    fn test_keyboard_defaults_match_most_specific_entry() {
+        let mut config = Config::default();
+        config.keyboard_defaults_mut("crkbd").layer_count = Some(4);
+        config.remember_variant("crkbd/rev1", "LAYOUT_split_3x6_3");
+        // Stored under the existing, less specific entry
+        assert_eq!(config.keyboards.len(), 1);
+        assert_eq!(
+            config.keyboard_defaults("crkbd/rev4_0/standard"),
+            KeyboardDefaults {
+                default_variant: Some("LAYOUT_split_3x6_3".to_string()),
+                keymap_pattern: None,
+                layer_count: Some(4),
+            }
+        );
+        // A keyboard whose name merely starts the same doesn't match
+        assert_eq!(
+            config.keyboard_defaults("crkbd_mini"),
+            KeyboardDefaults::default()
+        );
+
+        config.keyboard_defaults_mut("crkbd/rev1").layer_count = Some(2);
+        assert_eq!(config.keyboard_defaults("crkbd/rev1").layer_count, Some(2));
+        assert_eq!(config.keyboard_defaults("crkbd/rev4").layer_count, Some(4));
+    }
+
+    #[test]
+    fn test_sanitize_name() {
+        assert_eq!(sanitize_name("My Corne: v2/left"), "my_corne__v2_left");
+    }
+}
@@ -0,0 +1,311 @@
+//! Automatic fixes for validation warnings.
+//!
+//! Each fix is named after the validator rule whose warnings it resolves, so
+//! `lazyqmk fix --rule base-layer-trns` clears exactly what `lazyqmk validate`
+//! reported under that rule. `lazyqmk validate --fix` and the TUI's
+//! validation results share the same registry.
+
+use crate::firmware::validator::{
+    BASE_LAYER_TRNS_RULE, DEPRECATED_KEYCODE_RULE, LAYER_REF_OFFSET_RULE, MISSING_CATEGORY_RULE,
+};
+use crate::models::{Category, Layout, Position, RgbColor};
+use crate::services::deprecated_keycodes::modernize_keycode;
+use crate::services::layer_refs::{out_of_range_layer_refs, with_layer_index};
+
+/// Color given to categories recreated by [`QuickFix::MissingCategories`].
+const DEFAULT_CATEGORY_COLOR: RgbColor = RgbColor::new(128, 128, 128);
+
+/// A fix that can be applied to a whole layout.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum QuickFix {
+    /// Rename removed keycodes to their current names
+    DeprecatedKeycodes,
+    /// Replace `KC_TRNS` on the base layer with `KC_NO`
+    BaseLayerTrns,
+    /// Create categories that layers or keys reference but that don't exist
+    MissingCategories,
+    /// Shift numeric layer references past the last layer back into range
+    LayerRefOffset,
+}
+
+/// One change made by a [`QuickFix`].
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct FixChange {
+    /// Layer index and position of the changed key, if the change is to a key
+    pub key: Option<(usize, Position)>,
+    /// What changed (e.g., "RESET → QK_BOOT")
+    pub description: String,
+}
+
+impl FixChange {
+    fn key(layer: usize, position: Position, description: String) -> Self {
+        Self {
+            key: Some((layer, position)),
+            description,
+        }
+    }
+}
+
+impl std::fmt::Display for FixChange {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        if let Some((layer, pos)) = self.key {
+            write!(f, "[Layer {layer} ({}, {})] ", pos.row, pos.col)?;
+        }
+        write!(f, "{}", self.description)
+    }
+}
+
+impl QuickFix {
+    /// Every available fix, in the order `validate --fix` applies them.
+    pub const ALL: &'static [Self] = &[
+        Self::DeprecatedKeycodes,
+        Self::BaseLayerTrns,
+        Self::MissingCategories,
+        Self::LayerRefOffset,
+    ];
+
+    /// Validator rule ID the fix resolves.
+    #[must_use]
+    pub const fn rule(self) -> &'static str {
+        match self {
+            Self::DeprecatedKeycodes => DEPRECATED_KEYCODE_RULE,
+            Self::BaseLayerTrns => BASE_LAYER_TRNS_RULE,
+            Self::MissingCategories => MISSING_CATEGORY_RULE,
+            Self::LayerRefOffset => LAYER_REF_OFFSET_RULE,
+        }
+    }
+
+    /// One-line description of what the fix changes.
+    #[must_use]
+    pub const fn description(self) -> &'static str {
+        match self {
+            Self::DeprecatedKeycodes => "Rename removed keycodes to their current names",
+            Self::BaseLayerTrns => "Convert all base-layer KC_TRNS to KC_NO",
+            Self::MissingCategories => "Create missing categories with a default color",
+            Self::LayerRefOffset => "Shift out-of-range layer references back into range",
+        }
+    }
+
+    /// Looks up the fix for a validator rule ID.
+    #[must_use]
+    pub fn from_rule(rule: &str) -> Option<Self> {
+        Self::ALL
+            .iter()
+            .copied()
+            .find(|fix| fix.rule().eq_ignore_ascii_case(rule))
+    }
+
+    /// Applies the fix, returning every change made.
+    pub fn apply(self, layout: &mut Layout) -> Vec<FixChange> {
+        match self {
+            Self::DeprecatedKeycodes => fix_deprecated_keycodes(layout),
+            Self::BaseLayerTrns => fix_base_layer_trns(layout),
+            Self::MissingCategories => fix_missing_categories(layout),
+            Self::LayerRefOffset => fix_layer_ref_offset(layout),
+        }
+    }
+}
+
+fn fix_deprecated_keycodes(layout: &mut Layout) -> Vec<FixChange> {
+    let mut changes = Vec::new();
+    for (layer_idx, layer) in layout.layers.iter_mut().enumerate() {
+        for key in &mut layer.keys {
+            if let Some(modern) = modernize_keycode(&key.keycode) {
+                let description = format!("{} → {modern}", key.keycode);
+                key.keycode = modern;
+                changes.push(FixChange::key(layer_idx, key.position, description));
+            }
+        }
+    }
+    changes
+}
+
+fn fix_base_layer_trns(layout: &mut Layout) -> Vec<FixChange> {
+    let Some(base) = layout.layers.first_mut() else {
+        return Vec::new();
+    };
+    let mut changes = Vec::new();
+    for key in base.keys.iter_mut().filter(|k| k.is_transparent()) {
+        let description = format!("{} → KC_NO", key.keycode);
+        key.keycode = "KC_NO".to_string();
+        changes.push(FixChange::key(0, key.position, description));
+    }
+    changes
+}
+
+/// Recreates each missing category, named after its ID ("home-row" →
+/// "Home Row"). IDs that aren't valid category IDs are left for the user.
+fn fix_missing_categories(layout: &mut Layout) -> Vec<FixChange> {
+    let mut changes = Vec::new();
+    for id in layout.missing_category_ids() {
+        let name = id
+            .split('-')
+            .filter(|word| !word.is_empty())
+            .map(|word| {
+                let mut chars = word.chars();
+                chars.next().map_or_else(String::new, |first| {
+                    first.to_uppercase().chain(chars).collect()
+                })
+            })
+            .collect::<Vec<_>>()
+            .join(" ");
+        let Ok(category) = Category::new(id.as_str(), name, DEFAULT_CATEGORY_COLOR) else {
+            continue;
+        };
+        if layout.add_category(category).is_ok() {
+            changes.push(FixChange {
+                key: None,
+                description: format!("Created category '{id}'"),
+            });
+        }
+    }
+    changes
+}
+
+/// Assumes the highest out-of-range reference was meant for the last layer
+/// and shifts every out-of-range reference down by the same offset.
+///
+/// References still in range can't be told apart from intended ones and are
+/// left alone, as are references the offset would push below layer 0.
+fn fix_layer_ref_offset(layout: &mut Layout) -> Vec<FixChange> {
+    let refs = out_of_range_layer_refs(&layout.layers);
+    let Some(max_ref) = refs.iter().map(|r| r.to_layer).max() else {
+        return Vec::new();
+    };
+    let offset = max_ref + 1 - layout.layers.len();
+
+    let mut changes = Vec::new();
+    for layer_ref in refs {
+        let Some(target) = layer_ref.to_layer.checked_sub(offset) else {
+            continue;
+        };
+        let Some(fixed) = with_layer_index(&layer_ref.keycode, target) else {
+            continue;
+        };
+        let layer = &mut layout.layers[layer_ref.from_layer];
+        if let Some(key) = layer.get_key_mut(layer_ref.position) {
+            let description = format!("{} → {fixed}", key.keycode);
+            key.keycode = fixed;
+            changes.push(FixChange::key(
+                layer_ref.from_layer,
+                layer_ref.position,
+                description,
+            ));
+        }
+    }
+    changes
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::models::{KeyDefinition, Layer};
+
+    /// Layout with one layer per keycode row
+    fn layout_with(layers: &[&[&str]]) -> Layout {
+        let mut layout = Layout::new("Fix").unwrap();
+        for (number, keycodes) in layers.iter().enumerate() {
+            let number = u8::try_from(number).unwrap();
+            let mut layer = Layer::new(number, "Layer", RgbColor::new(0, 0, 255)).unwrap();
+            for (col, keycode) in keycodes.iter().enumerate() {
+                let position = Position::new(0, u8::try_from(col).unwrap());
+                layer
+                    .add_key(KeyDefinition::at(position).keycode(*keycode))
+                    .unwrap();
+            }
+            layout.add_layer(layer).unwrap();
+        }
+        layout
+    }
+
+    fn keycodes(layout: &Layout, layer: usize) -> Vec<&str> {
+        layout.layers[layer]
+            .keys
+            .iter()
+            .map(|k| k.keycode.as_str())
+            .collect()
+    }
+
+    #[test]
+    fn test_base_layer_trns_fix() {
+        let row: &[&str] = &["KC_TRNS", "KC_A", "KC_TRANSPARENT"];
+        let mut layout = layout_with(&[row, row]);
+
+        assert_eq!(QuickFix::BaseLayerTrns.apply(&mut layout).len(), 2);
+        assert_eq!(keycodes(&layout, 0), ["KC_NO", "KC_A", "KC_NO"]);
+
+        // Higher layers keep falling through to the base layer
+        assert_eq!(layout.layers[1].keys[0].keycode, "KC_TRNS");
+        assert!(QuickFix::BaseLayerTrns.apply(&mut layout).is_empty());
+    }
+
+    #[test]
+    fn test_deprecated_keycodes_fix() {
+        let mut layout = layout_with(&[&["RESET", "KC_A", "LT(1, KC_BSPACE)"], &["KC_LCTRL"]]);
+
+        let changes = QuickFix::DeprecatedKeycodes.apply(&mut layout);
+        let changes: Vec<String> = changes.iter().map(ToString::to_string).collect();
+        assert_eq!(
+            changes,
+            [
+                "[Layer 0 (0, 0)] RESET → QK_BOOT",
+                "[Layer 0 (0, 2)] LT(1, KC_BSPACE) → LT(1, KC_BSPC)",
+                "[Layer 1 (0, 0)] KC_LCTRL → KC_LCTL",
+            ]
+        );
+        assert_eq!(keycodes(&layout, 0), ["QK_BOOT", "KC_A", "LT(1, KC_BSPC)"]);
+        assert!(QuickFix::DeprecatedKeycodes.apply(&mut layout).is_empty());
+    }
+
+    #[test]
+    fn test_missing_categories_fix() {
+        let mut layout = layout_with(&[&["KC_A", "KC_B", "KC_C"]]);
+        layout.layers[0].category_id = Some("home-row".to_string());
+        layout.layers[0].keys[0].category_id = Some("home-row".to_string());
+        layout.layers[0].keys[1].category_id = Some("symbols".to_string());
+        // Not a valid category ID, so it can't be recreated
+        layout.layers[0].keys[2].category_id = Some("Bad ID".to_string());
+
+        let changes = QuickFix::MissingCategories.apply(&mut layout);
+        assert_eq!(changes.len(), 2);
+        assert_eq!(changes[0].to_string(), "Created category 'home-row'");
+
+        let home_row = layout.get_category("home-row").unwrap();
+        assert_eq!(home_row.name, "Home Row");
+        assert_eq!(home_row.color, DEFAULT_CATEGORY_COLOR);
+        assert_eq!(layout.get_category("symbols").unwrap().name, "Symbols");
+        assert_eq!(layout.missing_category_ids(), ["Bad ID"]);
+    }
+
+    #[test]
+    fn test_layer_ref_offset_fix() {
+        // Three layers left after deleting one: MO(3) and LT(2, ...) were
+        // meant for the last two
+        let mut layout = layout_with(&[
+            &["MO(3)", "LT(3, KC_SPC)", "TG(1)", "MO(2)"],
+            &["KC_TRNS"],
+            &["KC_TRNS"],
+        ]);
+
+        let changes = QuickFix::LayerRefOffset.apply(&mut layout);
+        assert_eq!(changes.len(), 2);
+        assert_eq!(changes[0].to_string(), "[Layer 0 (0, 0)] MO(3) → MO(2)");
+        assert_eq!(
+            keycodes(&layout, 0),
+            ["MO(2)", "LT(2, KC_SPC)", "TG(1)", "MO(2)"]
+        );
+        assert!(QuickFix::LayerRefOffset.apply(&mut layout).is_empty());
+    }
+
+    #[test]
+    fn test_from_rule() {
+        assert_eq!(
+            QuickFix::from_rule("base-layer-trns"),
+            Some(QuickFix::BaseLayerTrns)
+        );
+        for fix in QuickFix::ALL {
+            assert_eq!(QuickFix::from_rule(fix.rule()), Some(*fix));
+        }
+        assert_eq!(QuickFix::from_rule("unknown"), None);
+    }
+}
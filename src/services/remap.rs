@@ -0,0 +1,378 @@
+//! Position remapping for migrating a layout between keyboards.
+//!
+//! Keys are matched by physical position: each board's key centers are
+//! scaled to its own bounding box, and source keys are paired with the
+//! nearest free target key. An explicit position map can pin pairs (or send
+//! a source key to the orphan section) before auto-matching runs:
+//!
+//! ```toml
+//! [positions]
+//! "3,0" = "4,0"
+//! "3,5" = "orphan"
+//! ```
+//!
+//! Source keys without a target are kept in the layout's orphaned keys;
+//! target positions without a source key become `KC_TRNS`.
+
+use crate::models::{
+    KeyDefinition, KeyboardGeometry, Layer, Layout, OrphanedKey, Position, VisualLayoutMapping,
+};
+use anyhow::{bail, Context, Result};
+use serde::{Deserialize, Serialize};
+use std::collections::{BTreeMap, HashMap, HashSet};
+
+/// Explicit source → target position pairs from a mapping file.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct PositionMap {
+    /// Source position and its target (`None` sends the key to orphans)
+    pub pairs: Vec<(Position, Option<Position>)>,
+}
+
+/// On-disk format of a mapping file.
+#[derive(Debug, Deserialize)]
+struct PositionMapFile {
+    #[serde(default)]
+    positions: BTreeMap<String, String>,
+}
+
+impl PositionMap {
+    /// Parses a TOML mapping file.
+    pub fn parse(content: &str) -> Result<Self> {
+        let file: PositionMapFile =
+            toml::from_str(content).context("Failed to parse position map")?;
+
+        let mut pairs = Vec::new();
+        for (source, target) in &file.positions {
+            let source = source.parse::<Position>()?;
+            let target = if target.trim().eq_ignore_ascii_case("orphan") {
+                None
+            } else {
+                Some(target.parse::<Position>()?)
+            };
+            pairs.push((source, target));
+        }
+        Ok(Self { pairs })
+    }
+}
+
+/// Geometry and mapping of one keyboard.
+#[derive(Debug, Clone, Copy)]
+pub struct Board<'a> {
+    /// Physical key geometry
+    pub geometry: &'a KeyboardGeometry,
+    /// Visual position mapping
+    pub mapping: &'a VisualLayoutMapping,
+}
+
+/// How many keys of one layer ended up where.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize)]
+pub struct LayerRemapStats {
+    /// Layer index
+    pub layer: usize,
+    /// Layer name
+    pub name: String,
+    /// Keys placed by nearest-position matching
+    pub auto_matched: usize,
+    /// Keys placed by the explicit position map
+    pub explicit: usize,
+    /// Source keys with no target, moved to the orphan section
+    pub orphaned: usize,
+    /// Target positions with no source key, filled with `KC_TRNS`
+    pub empty: usize,
+}
+
+/// Result of remapping a layout.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize)]
+pub struct RemapReport {
+    /// Per-layer counts
+    pub layers: Vec<LayerRemapStats>,
+}
+
+/// Remaps `layout` from the `source` board onto the `target` board.
+///
+/// The returned layout has keys at every target position; its metadata is
+/// unchanged so the caller can point it at the target keyboard.
+pub fn remap_layout(
+    layout: &Layout,
+    source: Board<'_>,
+    target: Board<'_>,
+    explicit: &PositionMap,
+) -> Result<(Layout, RemapReport)> {
+    let source_centers = normalized_centers(source);
+    let target_centers = normalized_centers(target);
+
+    // Source position → (target position or orphan, placed explicitly)
+    let mut assignment: HashMap<Position, Option<Position>> = HashMap::new();
+    let mut used_targets: HashSet<Position> = HashSet::new();
+    let mut explicit_sources: HashSet<Position> = HashSet::new();
+
+    for (from, to) in &explicit.pairs {
+        if !source.mapping.is_valid_position(*from) {
+            bail!(
+                "Position map source {},{} is not a key on the source keyboard",
+                from.row,
+                from.col
+            );
+        }
+        if let Some(to) = to {
+            if !target.mapping.is_valid_position(*to) {
+                bail!(
+                    "Position map target {},{} is not a key on the target keyboard",
+                    to.row,
+                    to.col
+                );
+            }
+            if !used_targets.insert(*to) {
+                bail!(
+                    "Position map assigns target {},{} more than once",
+                    to.row,
+                    to.col
+                );
+            }
+        }
+        assignment.insert(*from, *to);
+        explicit_sources.insert(*from);
+    }
+
+    // Greedy global nearest-pair matching for everything left
+    let mut candidates: Vec<(f32, Position, Position)> = Vec::new();
+    for (from, fx, fy) in &source_centers {
+        if assignment.contains_key(from) {
+            continue;
+        }
+        for (to, tx, ty) in &target_centers {
+            if used_targets.contains(to) {
+                continue;
+            }
+            let (dx, dy) = (fx - tx, fy - ty);
+            candidates.push((dx.mul_add(dx, dy * dy), *from, *to));
+        }
+    }
+    candidates.sort_by(|a, b| {
+        a.0.total_cmp(&b.0)
+            .then_with(|| (a.1.row, a.1.col).cmp(&(b.1.row, b.1.col)))
+            .then_with(|| (a.2.row, a.2.col).cmp(&(b.2.row, b.2.col)))
+    });
+    for (_, from, to) in candidates {
+        if assignment.contains_key(&from) || used_targets.contains(&to) {
+            continue;
+        }
+        assignment.insert(from, Some(to));
+        used_targets.insert(to);
+    }
+
+    let target_of: HashMap<Position, Position> = assignment
+        .iter()
+        .filter_map(|(from, to)| to.map(|to| (to, *from)))
+        .collect();
+
+    let mut target_positions: Vec<Position> =
+        target_centers.iter().map(|(pos, _, _)| *pos).collect();
+    target_positions.sort_by_key(|pos| (pos.row, pos.col));
+
+    let mut remapped = layout.clone();
+    let mut report = RemapReport::default();
+
+    for (idx, layer) in layout.layers.iter().enumerate() {
+        let mut stats = LayerRemapStats {
+            layer: idx,
+            name: layer.name.clone(),
+            ..LayerRemapStats::default()
+        };
+
+        let mut keys = Vec::with_capacity(target_positions.len());
+        for to in &target_positions {
+            let source_key = target_of
+                .get(to)
+                .and_then(|from| layer.get_key(*from).map(|key| (from, key)));
+            match source_key {
+                Some((from, key)) => {
+                    if explicit_sources.contains(from) {
+                        stats.explicit += 1;
+                    } else {
+                        stats.auto_matched += 1;
+                    }
+                    let mut key = key.clone();
+                    key.position = *to;
+                    keys.push(key);
+                }
+                None => {
+                    stats.empty += 1;
+                    keys.push(KeyDefinition::new(*to, "KC_TRNS"));
+                }
+            }
+        }
+
+        for key in &layer.keys {
+            let placed = assignment.get(&key.position).is_some_and(Option::is_some);
+            if placed || key.is_transparent() || key.is_no_op() {
+                continue;
+            }
+            stats.orphaned += 1;
+            remapped.orphaned_keys.push(OrphanedKey {
+                layer: idx,
+                position: key.position,
+                keycode: key.keycode.clone(),
+            });
+        }
+
+        remapped.layers[idx] = Layer {
+            keys,
+            ..layer.clone()
+        };
+        report.layers.push(stats);
+    }
+
+    remapped.validate_invariants()?;
+    Ok((remapped, report))
+}
+
+/// Key centers scaled to the board's bounding box (0.0..=1.0 on each axis).
+fn normalized_centers(board: Board<'_>) -> Vec<(Position, f32, f32)> {
+    let centers: Vec<(Position, f32, f32)> = board
+        .mapping
+        .get_all_visual_positions()
+        .into_iter()
+        .filter_map(|pos| {
+            let index = board.mapping.visual_to_layout_index(pos.row, pos.col)?;
+            let key = board
+                .geometry
+                .keys
+                .iter()
+                .find(|key| key.layout_index == index)?;
+            let (x, y) = key.center();
+            Some((pos, x, y))
+        })
+        .collect();
+
+    let (min_x, max_x) = bounds(centers.iter().map(|(_, x, _)| *x));
+    let (min_y, max_y) = bounds(centers.iter().map(|(_, _, y)| *y));
+    let scale = |value: f32, min: f32, max: f32| {
+        if max - min > f32::EPSILON {
+            (value - min) / (max - min)
+        } else {
+            0.5
+        }
+    };
+
+    centers
+        .into_iter()
+        .map(|(pos, x, y)| (pos, scale(x, min_x, max_x), scale(y, min_y, max_y)))
+        .collect()
+}
+
+/// Minimum and maximum of `values` (zeros when empty).
+fn bounds(values: impl Iterator<Item = f32>) -> (f32, f32) {
+    values
+        .fold(None, |acc: Option<(f32, f32)>, value| {
+            Some(acc.map_or((value, value), |(min, max)| {
+                (min.min(value), max.max(value))
+            }))
+        })
+        .unwrap_or((0.0, 0.0))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::models::{KeyGeometry, RgbColor};
+
+    /// Grid board with `rows` x `cols` 1u keys.
+    fn grid(rows: u8, cols: u8) -> (KeyboardGeometry, VisualLayoutMapping) {
+        let mut geometry = KeyboardGeometry::new("grid", "LAYOUT", rows, cols);
+        for row in 0..rows {
+            for col in 0..cols {
+                geometry.add_key(KeyGeometry::new(
+                    (row, col),
+                    row * cols + col,
+                    f32::from(col),
+                    f32::from(row),
+                ));
+            }
+        }
+        let mapping = VisualLayoutMapping::build(&geometry);
+        (geometry, mapping)
+    }
+
+    fn layout(rows: u8, cols: u8, keycodes: &[&str]) -> Layout {
+        let mut layout = Layout::new("Test").unwrap();
+        let mut layer = Layer::new(0, "Base", RgbColor::new(0, 0, 0)).unwrap();
+        for row in 0..rows {
+            for col in 0..cols {
+                let keycode = keycodes[usize::from(row * cols + col)];
+                layer
+                    .add_key(KeyDefinition::new(Position::new(row, col), keycode))
+                    .unwrap();
+            }
+        }
+        layout.add_layer(layer).unwrap();
+        layout
+    }
+
+    #[test]
+    fn test_remap_to_larger_board_fills_transparent() {
+        let (src_geo, src_map) = grid(2, 2);
+        let (dst_geo, dst_map) = grid(3, 3);
+        let source = layout(2, 2, &["KC_A", "KC_B", "KC_C", "KC_D"]);
+
+        let (remapped, report) = remap_layout(
+            &source,
+            Board {
+                geometry: &src_geo,
+                mapping: &src_map,
+            },
+            Board {
+                geometry: &dst_geo,
+                mapping: &dst_map,
+            },
+            &PositionMap::default(),
+        )
+        .unwrap();
+
+        let layer = &remapped.layers[0];
+        assert_eq!(layer.keys.len(), 9);
+        assert_eq!(layer.get_key(Position::new(0, 0)).unwrap().keycode, "KC_A");
+        assert_eq!(layer.get_key(Position::new(0, 2)).unwrap().keycode, "KC_B");
+        assert_eq!(layer.get_key(Position::new(2, 2)).unwrap().keycode, "KC_D");
+        assert_eq!(report.layers[0].auto_matched, 4);
+        assert_eq!(report.layers[0].empty, 5);
+        assert_eq!(report.layers[0].orphaned, 0);
+    }
+
+    #[test]
+    fn test_remap_explicit_map_and_orphans() {
+        let (src_geo, src_map) = grid(2, 2);
+        let (dst_geo, dst_map) = grid(1, 2);
+        let source = layout(2, 2, &["KC_A", "KC_B", "KC_C", "KC_TRNS"]);
+        let map =
+            PositionMap::parse("[positions]\n\"1,0\" = \"0,0\"\n\"0,0\" = \"orphan\"\n").unwrap();
+
+        let (remapped, report) = remap_layout(
+            &source,
+            Board {
+                geometry: &src_geo,
+                mapping: &src_map,
+            },
+            Board {
+                geometry: &dst_geo,
+                mapping: &dst_map,
+            },
+            &map,
+        )
+        .unwrap();
+
+        let layer = &remapped.layers[0];
+        assert_eq!(layer.get_key(Position::new(0, 0)).unwrap().keycode, "KC_C");
+        assert_eq!(layer.get_key(Position::new(0, 1)).unwrap().keycode, "KC_B");
+        assert_eq!(report.layers[0].explicit, 1);
+        assert_eq!(report.layers[0].auto_matched, 1);
+        // KC_TRNS is dropped rather than orphaned
+        assert_eq!(report.layers[0].orphaned, 1);
+        assert_eq!(remapped.orphaned_keys[0].keycode, "KC_A");
+    }
+
+    #[test]
+    fn test_position_map_rejects_bad_position() {
+        assert!(PositionMap::parse("[positions]\n\"1\" = \"0,0\"\n").is_err());
+    }
+}
@@ -0,0 +1,449 @@
+//! Shifted symbols: which character a shifted keycode types on the host.
+//!
+//! QMK's `KC_LPRN` is shorthand for `S(KC_9)`: both send Shift+9, and the
+//! character that produces depends on the keyboard layout the OS is set to
+//! ("(" on a US host, ")" on a German one). This module reduces keycodes to
+//! the base key they shift, names the character it types on a host layout,
+//! and spells it back in either form.
+
+use crate::models::ShiftedForm;
+use std::fmt;
+
+/// US shifted shorthands from QMK's `quantum_keycodes.h`, short and long
+/// names, with the base key each one shifts.
+const US_NAMED: &[(&str, &str)] = &[
+    ("KC_TILD", "KC_GRV"),
+    ("KC_EXLM", "KC_1"),
+    ("KC_AT", "KC_2"),
+    ("KC_HASH", "KC_3"),
+    ("KC_DLR", "KC_4"),
+    ("KC_PERC", "KC_5"),
+    ("KC_CIRC", "KC_6"),
+    ("KC_AMPR", "KC_7"),
+    ("KC_ASTR", "KC_8"),
+    ("KC_LPRN", "KC_9"),
+    ("KC_RPRN", "KC_0"),
+    ("KC_UNDS", "KC_MINS"),
+    ("KC_PLUS", "KC_EQL"),
+    ("KC_LCBR", "KC_LBRC"),
+    ("KC_RCBR", "KC_RBRC"),
+    ("KC_PIPE", "KC_BSLS"),
+    ("KC_COLN", "KC_SCLN"),
+    ("KC_DQUO", "KC_QUOT"),
+    ("KC_LABK", "KC_COMM"),
+    ("KC_RABK", "KC_DOT"),
+    ("KC_QUES", "KC_SLSH"),
+    ("KC_TILDE", "KC_GRV"),
+    ("KC_EXCLAIM", "KC_1"),
+    ("KC_DOLLAR", "KC_4"),
+    ("KC_PERCENT", "KC_5"),
+    ("KC_CIRCUMFLEX", "KC_6"),
+    ("KC_AMPERSAND", "KC_7"),
+    ("KC_ASTERISK", "KC_8"),
+    ("KC_LEFT_PAREN", "KC_9"),
+    ("KC_RIGHT_PAREN", "KC_0"),
+    ("KC_UNDERSCORE", "KC_MINS"),
+    ("KC_LEFT_CURLY_BRACE", "KC_LBRC"),
+    ("KC_RIGHT_CURLY_BRACE", "KC_RBRC"),
+    ("KC_COLON", "KC_SCLN"),
+    ("KC_DOUBLE_QUOTE", "KC_QUOT"),
+    ("KC_DQT", "KC_QUOT"),
+    ("KC_LEFT_ANGLE_BRACKET", "KC_COMM"),
+    ("KC_LT", "KC_COMM"),
+    ("KC_RIGHT_ANGLE_BRACKET", "KC_DOT"),
+    ("KC_GT", "KC_DOT"),
+    ("KC_QUESTION", "KC_SLSH"),
+];
+
+/// Long names of base keys, accepted inside `S()`.
+const BASE_ALIASES: &[(&str, &str)] = &[
+    ("KC_GRAVE", "KC_GRV"),
+    ("KC_MINUS", "KC_MINS"),
+    ("KC_EQUAL", "KC_EQL"),
+    ("KC_LEFT_BRACKET", "KC_LBRC"),
+    ("KC_RIGHT_BRACKET", "KC_RBRC"),
+    ("KC_BACKSLASH", "KC_BSLS"),
+    ("KC_SEMICOLON", "KC_SCLN"),
+    ("KC_QUOTE", "KC_QUOT"),
+    ("KC_COMMA", "KC_COMM"),
+    ("KC_SLASH", "KC_SLSH"),
+    ("KC_NONUS_HASH", "KC_NUHS"),
+    ("KC_NONUS_BACKSLASH", "KC_NUBS"),
+];
+
+/// Character each base key types with Shift on a US host.
+const US_SHIFTED: &[(&str, char)] = &[
+    ("KC_GRV", '~'),
+    ("KC_1", '!'),
+    ("KC_2", '@'),
+    ("KC_3", '#'),
+    ("KC_4", '$'),
+    ("KC_5", '%'),
+    ("KC_6", '^'),
+    ("KC_7", '&'),
+    ("KC_8", '*'),
+    ("KC_9", '('),
+    ("KC_0", ')'),
+    ("KC_MINS", '_'),
+    ("KC_EQL", '+'),
+    ("KC_LBRC", '{'),
+    ("KC_RBRC", '}'),
+    ("KC_BSLS", '|'),
+    ("KC_SCLN", ':'),
+    ("KC_QUOT", '"'),
+    ("KC_COMM", '<'),
+    ("KC_DOT", '>'),
+    ("KC_SLSH", '?'),
+];
+
+/// Character each base key types with Shift on a UK host.
+const UK_SHIFTED: &[(&str, char)] = &[
+    ("KC_GRV", '¬'),
+    ("KC_1", '!'),
+    ("KC_2", '"'),
+    ("KC_3", '£'),
+    ("KC_4", '$'),
+    ("KC_5", '%'),
+    ("KC_6", '^'),
+    ("KC_7", '&'),
+    ("KC_8", '*'),
+    ("KC_9", '('),
+    ("KC_0", ')'),
+    ("KC_MINS", '_'),
+    ("KC_EQL", '+'),
+    ("KC_LBRC", '{'),
+    ("KC_RBRC", '}'),
+    ("KC_SCLN", ':'),
+    ("KC_QUOT", '@'),
+    ("KC_NUHS", '~'),
+    ("KC_NUBS", '|'),
+    ("KC_COMM", '<'),
+    ("KC_DOT", '>'),
+    ("KC_SLSH", '?'),
+];
+
+/// UK shifted keycodes from QMK's `keymap_uk.h`.
+const UK_NAMED: &[(&str, &str)] = &[
+    ("UK_NOT", "KC_GRV"),
+    ("UK_EXLM", "KC_1"),
+    ("UK_DQUO", "KC_2"),
+    ("UK_PND", "KC_3"),
+    ("UK_DLR", "KC_4"),
+    ("UK_PERC", "KC_5"),
+    ("UK_CIRC", "KC_6"),
+    ("UK_AMPR", "KC_7"),
+    ("UK_ASTR", "KC_8"),
+    ("UK_LPRN", "KC_9"),
+    ("UK_RPRN", "KC_0"),
+    ("UK_UNDS", "KC_MINS"),
+    ("UK_PLUS", "KC_EQL"),
+    ("UK_LCBR", "KC_LBRC"),
+    ("UK_RCBR", "KC_RBRC"),
+    ("UK_COLN", "KC_SCLN"),
+    ("UK_AT", "KC_QUOT"),
+    ("UK_TILD", "KC_NUHS"),
+    ("UK_PIPE", "KC_NUBS"),
+    ("UK_LABK", "KC_COMM"),
+    ("UK_RABK", "KC_DOT"),
+    ("UK_QUES", "KC_SLSH"),
+];
+
+/// UK unshifted keycodes whose base key has a shifted symbol.
+const UK_BASE: &[(&str, &str)] = &[
+    ("UK_GRV", "KC_GRV"),
+    ("UK_MINS", "KC_MINS"),
+    ("UK_EQL", "KC_EQL"),
+    ("UK_LBRC", "KC_LBRC"),
+    ("UK_RBRC", "KC_RBRC"),
+    ("UK_SCLN", "KC_SCLN"),
+    ("UK_QUOT", "KC_QUOT"),
+    ("UK_HASH", "KC_NUHS"),
+    ("UK_BSLS", "KC_NUBS"),
+    ("UK_COMM", "KC_COMM"),
+    ("UK_DOT", "KC_DOT"),
+    ("UK_SLSH", "KC_SLSH"),
+];
+
+/// Character each base key types with Shift on a German host.
+///
+/// Keys that shift to a capital umlaut are left out: those are letters, not
+/// symbols anyone spells as a shorthand.
+const DE_SHIFTED: &[(&str, char)] = &[
+    ("KC_GRV", '°'),
+    ("KC_1", '!'),
+    ("KC_2", '"'),
+    ("KC_3", '§'),
+    ("KC_4", '$'),
+    ("KC_5", '%'),
+    ("KC_6", '&'),
+    ("KC_7", '/'),
+    ("KC_8", '('),
+    ("KC_9", ')'),
+    ("KC_0", '='),
+    ("KC_MINS", '?'),
+    ("KC_EQL", '`'),
+    ("KC_RBRC", '*'),
+    ("KC_NUHS", '\''),
+    ("KC_NUBS", '>'),
+    ("KC_COMM", ';'),
+    ("KC_DOT", ':'),
+    ("KC_SLSH", '_'),
+];
+
+/// German shifted keycodes from QMK's `keymap_german.h`.
+const DE_NAMED: &[(&str, &str)] = &[
+    ("DE_DEG", "KC_GRV"),
+    ("DE_EXLM", "KC_1"),
+    ("DE_DQUO", "KC_2"),
+    ("DE_SECT", "KC_3"),
+    ("DE_DLR", "KC_4"),
+    ("DE_PERC", "KC_5"),
+    ("DE_AMPR", "KC_6"),
+    ("DE_SLSH", "KC_7"),
+    ("DE_LPRN", "KC_8"),
+    ("DE_RPRN", "KC_9"),
+    ("DE_EQL", "KC_0"),
+    ("DE_QUES", "KC_MINS"),
+    ("DE_GRV", "KC_EQL"),
+    ("DE_ASTR", "KC_RBRC"),
+    ("DE_QUOT", "KC_NUHS"),
+    ("DE_RABK", "KC_NUBS"),
+    ("DE_SCLN", "KC_COMM"),
+    ("DE_COLN", "KC_DOT"),
+    ("DE_UNDS", "KC_SLSH"),
+];
+
+/// German unshifted keycodes whose base key has a shifted symbol.
+const DE_BASE: &[(&str, &str)] = &[
+    ("DE_CIRC", "KC_GRV"),
+    ("DE_SS", "KC_MINS"),
+    ("DE_ACUT", "KC_EQL"),
+    ("DE_PLUS", "KC_RBRC"),
+    ("DE_HASH", "KC_NUHS"),
+    ("DE_LABK", "KC_NUBS"),
+    ("DE_COMM", "KC_COMM"),
+    ("DE_DOT", "KC_DOT"),
+    ("DE_MINS", "KC_SLSH"),
+];
+
+/// Keyboard layout the OS on the host is set to.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum HostLayout {
+    /// US QWERTY (QMK's `KC_` names)
+    #[default]
+    Us,
+    /// UK QWERTY (`UK_` names)
+    Uk,
+    /// German QWERTZ (`DE_` names)
+    German,
+}
+
+impl HostLayout {
+    /// Every supported host layout.
+    pub const ALL: &'static [Self] = &[Self::Us, Self::Uk, Self::German];
+
+    /// Parses a layout name as written in `lint.host_layout`.
+    #[must_use]
+    pub fn from_name(name: &str) -> Option<Self> {
+        match name.trim().to_ascii_lowercase().as_str() {
+            "us" => Some(Self::Us),
+            "uk" | "gb" => Some(Self::Uk),
+            "german" | "de" => Some(Self::German),
+            _ => None,
+        }
+    }
+
+    /// Name used in settings and messages.
+    #[must_use]
+    pub const fn name(self) -> &'static str {
+        match self {
+            Self::Us => "us",
+            Self::Uk => "uk",
+            Self::German => "german",
+        }
+    }
+
+    /// Keycode prefix of the host's QMK keymap header (`KC_` for US).
+    const fn prefix(self) -> &'static str {
+        match self {
+            Self::Us => "KC_",
+            Self::Uk => "UK_",
+            Self::German => "DE_",
+        }
+    }
+
+    const fn shifted_chars(self) -> &'static [(&'static str, char)] {
+        match self {
+            Self::Us => US_SHIFTED,
+            Self::Uk => UK_SHIFTED,
+            Self::German => DE_SHIFTED,
+        }
+    }
+
+    const fn named(self) -> &'static [(&'static str, &'static str)] {
+        match self {
+            Self::Us => &[],
+            Self::Uk => UK_NAMED,
+            Self::German => DE_NAMED,
+        }
+    }
+
+    const fn base_names(self) -> &'static [(&'static str, &'static str)] {
+        match self {
+            Self::Us => &[],
+            Self::Uk => UK_BASE,
+            Self::German => DE_BASE,
+        }
+    }
+
+    /// Character the base key types with Shift held.
+    fn shifted_char(self, base: &str) -> Option<char> {
+        self.shifted_chars()
+            .iter()
+            .find(|(key, _)| *key == base)
+            .map(|&(_, ch)| ch)
+    }
+
+    /// Resolves an unshifted keycode to the base key it sends.
+    fn base_key(self, keycode: &str) -> Option<&'static str> {
+        let keycode = BASE_ALIASES
+            .iter()
+            .find(|(alias, _)| *alias == keycode)
+            .map_or(keycode, |&(_, base)| base);
+        if let Some(&(base, _)) = self.shifted_chars().iter().find(|(k, _)| *k == keycode) {
+            return Some(base);
+        }
+        if let Some(&(_, base)) = self.base_names().iter().find(|(k, _)| *k == keycode) {
+            return Some(base);
+        }
+        // Digits keep their position on every supported layout (DE_1, UK_1)
+        let digit = keycode.strip_prefix(self.prefix())?;
+        if digit.len() == 1 && digit.chars().all(|c| c.is_ascii_digit()) {
+            return self
+                .shifted_chars()
+                .iter()
+                .find(|(k, _)| k.strip_prefix("KC_") == Some(digit))
+                .map(|&(base, _)| base);
+        }
+        None
+    }
+}
+
+impl fmt::Display for HostLayout {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", self.name())
+    }
+}
+
+/// A shifted base key and the character it types on a host layout.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct ShiftedSymbol {
+    /// Base key sent with Shift (e.g., `KC_9`)
+    pub base: &'static str,
+    /// Character it types on the host
+    pub produces: char,
+}
+
+impl ShiftedSymbol {
+    /// Reads a keycode as a shifted symbol on `host`.
+    ///
+    /// Accepts named shorthands (`KC_LPRN`, `DE_LPRN`) and `S()`, `LSFT()` or
+    /// `RSFT()` around a base key. Returns `None` for anything else, including
+    /// shifted letters.
+    #[must_use]
+    pub fn parse(keycode: &str, host: HostLayout) -> Option<Self> {
+        let keycode = keycode.trim();
+        let base = if let Some(inner) = ["S(", "LSFT(", "RSFT("]
+            .iter()
+            .find_map(|prefix| keycode.strip_prefix(prefix)?.strip_suffix(')'))
+        {
+            host.base_key(inner.trim())?
+        } else {
+            US_NAMED
+                .iter()
+                .chain(host.named())
+                .find(|(name, _)| *name == keycode)
+                .map(|&(_, base)| base)?
+        };
+        Some(Self {
+            base,
+            produces: host.shifted_char(base)?,
+        })
+    }
+
+    /// Spells the symbol in `form`.
+    ///
+    /// The named form uses the host's own shorthand when it has one (so
+    /// `DE_LPRN` on a German host), falling back to the `KC_` shorthand and
+    /// then to `S()` for keys without a name.
+    #[must_use]
+    pub fn spelled(self, form: ShiftedForm, host: HostLayout) -> String {
+        let named = host
+            .named()
+            .iter()
+            .chain(US_NAMED)
+            .find(|(_, base)| *base == self.base)
+            .map(|&(name, _)| name);
+        match (form, named) {
+            (ShiftedForm::Named, Some(name)) => name.to_string(),
+            _ => format!("S({})", self.base),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_named_and_wrapped_forms_type_the_same_symbol() {
+        let named = ShiftedSymbol::parse("KC_LPRN", HostLayout::Us).unwrap();
+        assert_eq!(named.produces, '(');
+        for keycode in ["S(KC_9)", "LSFT(KC_9)", "RSFT( KC_9 )", "KC_LEFT_PAREN"] {
+            assert_eq!(ShiftedSymbol::parse(keycode, HostLayout::Us), Some(named));
+        }
+        assert_eq!(
+            ShiftedSymbol::parse("S(KC_QUOTE)", HostLayout::Us).map(|s| s.produces),
+            Some('"')
+        );
+        assert_eq!(ShiftedSymbol::parse("KC_9", HostLayout::Us), None);
+        assert_eq!(ShiftedSymbol::parse("S(KC_A)", HostLayout::Us), None);
+    }
+
+    #[test]
+    fn test_host_layout_decides_the_character() {
+        // Shift+9 is ")" on a German host, so KC_LPRN matches DE_RPRN there
+        let german = ShiftedSymbol::parse("KC_LPRN", HostLayout::German).unwrap();
+        assert_eq!(german.produces, ')');
+        assert_eq!(
+            ShiftedSymbol::parse("DE_RPRN", HostLayout::German),
+            Some(german)
+        );
+        assert_eq!(
+            ShiftedSymbol::parse("S(DE_8)", HostLayout::German).map(|s| s.produces),
+            Some('(')
+        );
+        assert_eq!(
+            ShiftedSymbol::parse("S(KC_QUOT)", HostLayout::Uk).map(|s| s.produces),
+            Some('@')
+        );
+        assert_eq!(HostLayout::from_name("DE"), Some(HostLayout::German));
+        assert_eq!(HostLayout::from_name("dvorak"), None);
+    }
+
+    #[test]
+    fn test_spelled_forms() {
+        let paren = ShiftedSymbol::parse("S(KC_9)", HostLayout::Us).unwrap();
+        assert_eq!(paren.spelled(ShiftedForm::Named, HostLayout::Us), "KC_LPRN");
+        assert_eq!(
+            paren.spelled(ShiftedForm::Modifier, HostLayout::Us),
+            "S(KC_9)"
+        );
+
+        let german = ShiftedSymbol::parse("KC_LPRN", HostLayout::German).unwrap();
+        assert_eq!(
+            german.spelled(ShiftedForm::Named, HostLayout::German),
+            "DE_RPRN"
+        );
+    }
+}
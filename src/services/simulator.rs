@@ -0,0 +1,441 @@
+//! Simulating keypresses on a layout to follow its layer switching.
+//!
+//! [`LayerSimulator`] keeps the layer state a keyboard running the generated
+//! firmware would have: the default layer, layers toggled on with `TG()` or
+//! `TO()`, layers active while an `MO()`/`LT()` key is held, and a pending
+//! `OSL()` one-shot layer. Pressing a position looks the key up on the
+//! highest active layer and falls through transparent keys to the layers
+//! below, as QMK does.
+
+use std::collections::BTreeSet;
+
+use crate::models::{KeyDefinition, Layout, Position};
+use crate::services::layer_refs::{parse_layer_keycode, LayerRefKind, LayerRefTarget};
+
+/// What pressing a key did.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum PressEffect {
+    /// The key sends a keycode
+    Sends(String),
+    /// A layer is active while the key is held (`MO`, or `LT`/`TT`/`LM`/`OSL` held)
+    HoldsLayer(usize),
+    /// A layer was toggled on or off (`TG`, or `TT` tapped)
+    ToggledLayer {
+        /// Layer index
+        layer: usize,
+        /// Whether the layer is now on
+        on: bool,
+    },
+    /// Every layer above the default was turned off and this one on (`TO`)
+    MovedTo(usize),
+    /// The layer is active for the next key only (`OSL`)
+    OneShot(usize),
+    /// The default layer changed (`DF`)
+    DefaultLayer(usize),
+    /// A hold-only layer key (`MO`, `LM`) was tapped, which does nothing
+    NeedsHold(usize),
+    /// No active layer has a key at the position, or the key is `KC_NO`
+    NoKey,
+}
+
+/// One simulated keypress.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Press {
+    /// Position that was pressed
+    pub position: Position,
+    /// Layer the key was found on, after falling through transparent keys
+    pub layer: usize,
+    /// Keycode of that key
+    pub keycode: String,
+    /// What the press did
+    pub effect: PressEffect,
+}
+
+/// A key held down that keeps a layer active.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct HeldKey {
+    /// Position of the held key
+    pub position: Position,
+    /// Layer it keeps active
+    pub layer: usize,
+}
+
+/// Layer state of a simulated keyboard.
+#[derive(Debug, Clone, Default)]
+pub struct LayerSimulator {
+    /// Default layer (`DF`), always active
+    default_layer: usize,
+    /// Layers turned on by `TG`, `TT` or `TO`
+    toggled: BTreeSet<usize>,
+    /// Keys held down that keep a layer active
+    held: Vec<HeldKey>,
+    /// Layer active for the next key only
+    one_shot: Option<usize>,
+}
+
+impl LayerSimulator {
+    /// Creates a simulator with only the base layer active.
+    #[must_use]
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Gets every active layer, lowest first.
+    #[must_use]
+    pub fn active_layers(&self) -> BTreeSet<usize> {
+        let mut active = self.toggled.clone();
+        active.insert(self.default_layer);
+        active.extend(self.held.iter().map(|held| held.layer));
+        active.extend(self.one_shot);
+        active
+    }
+
+    /// Gets the highest active layer, the one the keyboard shows.
+    #[must_use]
+    pub fn top_layer(&self) -> usize {
+        self.active_layers()
+            .last()
+            .copied()
+            .unwrap_or(self.default_layer)
+    }
+
+    /// Gets the default layer.
+    #[must_use]
+    pub const fn default_layer(&self) -> usize {
+        self.default_layer
+    }
+
+    /// Gets the keys currently held down.
+    #[must_use]
+    pub fn held(&self) -> &[HeldKey] {
+        &self.held
+    }
+
+    /// Checks whether the key at `position` is held down.
+    #[must_use]
+    pub fn is_held(&self, position: Position) -> bool {
+        self.held.iter().any(|held| held.position == position)
+    }
+
+    /// Gets the pending one-shot layer.
+    #[must_use]
+    pub const fn one_shot(&self) -> Option<usize> {
+        self.one_shot
+    }
+
+    /// Finds the key pressing `position` reaches: the one on the highest
+    /// active layer that isn't transparent.
+    #[must_use]
+    pub fn resolve<'a>(
+        &self,
+        layout: &'a Layout,
+        position: Position,
+    ) -> Option<(usize, &'a KeyDefinition)> {
+        self.active_layers().into_iter().rev().find_map(|index| {
+            layout
+                .layers
+                .get(index)?
+                .keys
+                .iter()
+                .find(|key| key.position == position && !key.is_transparent())
+                .map(|key| (index, key))
+        })
+    }
+
+    /// Taps (presses and releases) the key at `position`.
+    pub fn tap(&mut self, layout: &Layout, position: Position) -> Press {
+        let Some((layer, key)) = self.resolve(layout, position) else {
+            return self.finish(position, 0, String::new(), PressEffect::NoKey);
+        };
+        let keycode = key.keycode.clone();
+
+        let effect = match layer_action(layout, &keycode) {
+            Some((target, LayerRefKind::Momentary | LayerRefKind::LayerMod)) => {
+                PressEffect::NeedsHold(target)
+            }
+            Some((_, LayerRefKind::TapHold)) => PressEffect::Sends(tap_keycode(&keycode)),
+            Some((target, LayerRefKind::Toggle | LayerRefKind::TapToggle)) => {
+                let on = self.toggled.insert(target);
+                if !on {
+                    self.toggled.remove(&target);
+                }
+                PressEffect::ToggledLayer { layer: target, on }
+            }
+            Some((target, LayerRefKind::SwitchTo)) => {
+                // QMK's layer_move() replaces the whole layer state
+                self.toggled.clear();
+                self.held.clear();
+                self.one_shot = None;
+                if target != self.default_layer {
+                    self.toggled.insert(target);
+                }
+                PressEffect::MovedTo(target)
+            }
+            Some((target, LayerRefKind::OneShot)) => {
+                self.one_shot = Some(target);
+                PressEffect::OneShot(target)
+            }
+            Some((target, LayerRefKind::DefaultSet)) => {
+                self.default_layer = target;
+                PressEffect::DefaultLayer(target)
+            }
+            Some((_, LayerRefKind::Other)) | None if key.is_no_op() => PressEffect::NoKey,
+            Some((_, LayerRefKind::Other)) | None => PressEffect::Sends(tap_keycode(&keycode)),
+        };
+
+        self.finish(position, layer, keycode, effect)
+    }
+
+    /// Presses the key at `position` and keeps it held.
+    ///
+    /// Layer keys that act on hold keep their layer active until
+    /// [`release`](Self::release); any other key is tapped.
+    pub fn hold(&mut self, layout: &Layout, position: Position) -> Press {
+        let Some((layer, key)) = self.resolve(layout, position) else {
+            return self.finish(position, 0, String::new(), PressEffect::NoKey);
+        };
+
+        match layer_action(layout, &key.keycode) {
+            Some((
+                target,
+                LayerRefKind::Momentary
+                | LayerRefKind::TapHold
+                | LayerRefKind::TapToggle
+                | LayerRefKind::LayerMod
+                | LayerRefKind::OneShot,
+            )) => {
+                if !self.is_held(position) {
+                    self.held.push(HeldKey {
+                        position,
+                        layer: target,
+                    });
+                }
+                Press {
+                    position,
+                    layer,
+                    keycode: key.keycode.clone(),
+                    effect: PressEffect::HoldsLayer(target),
+                }
+            }
+            _ => self.tap(layout, position),
+        }
+    }
+
+    /// Releases the held key at `position`, returning the layer it kept
+    /// active.
+    pub fn release(&mut self, position: Position) -> Option<usize> {
+        let index = self
+            .held
+            .iter()
+            .position(|held| held.position == position)?;
+        Some(self.held.remove(index).layer)
+    }
+
+    /// Releases every key and turns every layer but the base layer off.
+    pub fn reset(&mut self) {
+        *self = Self::default();
+    }
+
+    /// Builds the press, using up a pending one-shot layer once a key has
+    /// been sent.
+    fn finish(
+        &mut self,
+        position: Position,
+        layer: usize,
+        keycode: String,
+        effect: PressEffect,
+    ) -> Press {
+        if matches!(effect, PressEffect::Sends(_) | PressEffect::NoKey) {
+            self.one_shot = None;
+        }
+        Press {
+            position,
+            layer,
+            keycode,
+            effect,
+        }
+    }
+}
+
+/// Gets the layer index and kind of a layer keycode, resolving `@id`
+/// references against the layout's layers.
+fn layer_action(layout: &Layout, keycode: &str) -> Option<(usize, LayerRefKind)> {
+    let (target, kind) = parse_layer_keycode(keycode)?;
+    let index = match target {
+        LayerRefTarget::Index(index) => index,
+        LayerRefTarget::Uuid(id) => {
+            let id = id.strip_prefix('@').unwrap_or(&id);
+            layout.layers.iter().position(|layer| layer.id == id)?
+        }
+    };
+    (index < layout.layers.len()).then_some((index, kind))
+}
+
+/// Gets the keycode a tap sends: the last argument of `LT()`, `MT()` and
+/// `*_T()` mod-taps, the keycode itself otherwise.
+#[must_use]
+pub fn tap_keycode(keycode: &str) -> String {
+    let Some((prefix, args)) = keycode.split_once('(') else {
+        return keycode.to_string();
+    };
+    if prefix == "LT" || prefix == "MT" || prefix.ends_with("_T") {
+        let args = args.strip_suffix(')').unwrap_or(args);
+        let tap = args.rsplit(',').next().unwrap_or(args).trim();
+        return tap.to_string();
+    }
+    keycode.to_string()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::models::{Layer, RgbColor};
+
+    /// Base: (0,0) A, (0,1) LT(1, SPC), (0,2) MO(2), (0,3) TG(1), (0,4) OSL(2)
+    /// Layer 1: (0,0) 1, everything else transparent, (0,5) TO(2)
+    /// Layer 2: (0,0) transparent, (0,1) F1
+    fn layout() -> Layout {
+        let mut layout = Layout::new("Sim").unwrap();
+        let rows: [&[&str]; 3] = [
+            &["KC_A", "LT(1, KC_SPC)", "MO(2)", "TG(1)", "OSL(2)", "KC_B"],
+            &["KC_1", "KC_TRNS", "KC_TRNS", "KC_TRNS", "KC_TRNS", "TO(2)"],
+            &[
+                "KC_TRNS", "KC_F1", "KC_TRNS", "KC_TRNS", "KC_TRNS", "KC_TRNS",
+            ],
+        ];
+        for (index, keycodes) in rows.iter().enumerate() {
+            let mut layer =
+                Layer::new(index as u8, format!("L{index}"), RgbColor::new(0, 0, 0)).unwrap();
+            for (col, keycode) in keycodes.iter().enumerate() {
+                layer
+                    .add_key(KeyDefinition::new(Position::new(0, col as u8), *keycode))
+                    .unwrap();
+            }
+            layout.add_layer(layer).unwrap();
+        }
+        layout
+    }
+
+    const fn pos(col: u8) -> Position {
+        Position::new(0, col)
+    }
+
+    #[test]
+    fn test_tap_sends_base_keycode() {
+        let layout = layout();
+        let mut sim = LayerSimulator::new();
+        let press = sim.tap(&layout, pos(0));
+        assert_eq!(press.effect, PressEffect::Sends("KC_A".to_string()));
+        assert_eq!(press.layer, 0);
+    }
+
+    #[test]
+    fn test_momentary_hold_and_release() {
+        let layout = layout();
+        let mut sim = LayerSimulator::new();
+        assert_eq!(sim.tap(&layout, pos(2)).effect, PressEffect::NeedsHold(2));
+        assert_eq!(sim.top_layer(), 0);
+
+        assert_eq!(sim.hold(&layout, pos(2)).effect, PressEffect::HoldsLayer(2));
+        assert_eq!(sim.top_layer(), 2);
+        let press = sim.tap(&layout, pos(1));
+        assert_eq!(press.effect, PressEffect::Sends("KC_F1".to_string()));
+
+        assert_eq!(sim.release(pos(2)), Some(2));
+        assert_eq!(sim.top_layer(), 0);
+        assert_eq!(sim.release(pos(2)), None);
+    }
+
+    #[test]
+    fn test_layer_tap_sends_on_tap_and_switches_on_hold() {
+        let layout = layout();
+        let mut sim = LayerSimulator::new();
+        let press = sim.tap(&layout, pos(1));
+        assert_eq!(press.effect, PressEffect::Sends("KC_SPC".to_string()));
+
+        sim.hold(&layout, pos(1));
+        assert!(sim.is_held(pos(1)));
+        let press = sim.tap(&layout, pos(0));
+        assert_eq!((press.layer, press.keycode.as_str()), (1, "KC_1"));
+    }
+
+    #[test]
+    fn test_transparent_keys_fall_through() {
+        let layout = layout();
+        let mut sim = LayerSimulator::new();
+        sim.hold(&layout, pos(2));
+        // Layer 2 is transparent at (0,0), so the base layer's key is used
+        let press = sim.tap(&layout, pos(0));
+        assert_eq!((press.layer, press.keycode.as_str()), (0, "KC_A"));
+    }
+
+    #[test]
+    fn test_toggle_turns_layer_on_and_off() {
+        let layout = layout();
+        let mut sim = LayerSimulator::new();
+        let effect = sim.tap(&layout, pos(3)).effect;
+        assert_eq!(effect, PressEffect::ToggledLayer { layer: 1, on: true });
+        assert_eq!(sim.top_layer(), 1);
+        // Layer 1 is transparent over TG(1), so the same key turns it off
+        let effect = sim.tap(&layout, pos(3)).effect;
+        assert_eq!(
+            effect,
+            PressEffect::ToggledLayer {
+                layer: 1,
+                on: false
+            }
+        );
+        assert_eq!(sim.top_layer(), 0);
+    }
+
+    #[test]
+    fn test_to_replaces_layer_state() {
+        let layout = layout();
+        let mut sim = LayerSimulator::new();
+        sim.tap(&layout, pos(3));
+        assert_eq!(sim.tap(&layout, pos(5)).effect, PressEffect::MovedTo(2));
+        assert_eq!(sim.active_layers(), BTreeSet::from([0, 2]));
+    }
+
+    #[test]
+    fn test_one_shot_layer_lasts_one_key() {
+        let layout = layout();
+        let mut sim = LayerSimulator::new();
+        assert_eq!(sim.tap(&layout, pos(4)).effect, PressEffect::OneShot(2));
+        assert_eq!(sim.one_shot(), Some(2));
+
+        let press = sim.tap(&layout, pos(1));
+        assert_eq!(press.effect, PressEffect::Sends("KC_F1".to_string()));
+        assert_eq!(sim.one_shot(), None);
+        assert_eq!(sim.top_layer(), 0);
+    }
+
+    #[test]
+    fn test_layer_ids_resolve_to_indices() {
+        let mut layout = layout();
+        let id = layout.layers[2].id.clone();
+        layout.layers[0].keys[5].keycode = format!("MO(@{id})");
+        let mut sim = LayerSimulator::new();
+        assert_eq!(sim.hold(&layout, pos(5)).effect, PressEffect::HoldsLayer(2));
+    }
+
+    #[test]
+    fn test_reset_returns_to_base_layer() {
+        let layout = layout();
+        let mut sim = LayerSimulator::new();
+        sim.hold(&layout, pos(2));
+        sim.tap(&layout, pos(3));
+        sim.reset();
+        assert_eq!(sim.active_layers(), BTreeSet::from([0]));
+        assert!(sim.held().is_empty());
+    }
+
+    #[test]
+    fn test_tap_keycode() {
+        assert_eq!(tap_keycode("LT(1, KC_SPC)"), "KC_SPC");
+        assert_eq!(tap_keycode("MT(MOD_LSFT, KC_A)"), "KC_A");
+        assert_eq!(tap_keycode("LCTL_T(KC_ESC)"), "KC_ESC");
+        assert_eq!(tap_keycode("LCTL(KC_C)"), "LCTL(KC_C)");
+        assert_eq!(tap_keycode("KC_Q"), "KC_Q");
+    }
+}
@@ -0,0 +1,427 @@
+//! Layout snapshot service.
+//!
+//! Snapshots are quick, git-independent checkpoints of the in-memory layout.
+//! They are stored next to the layout file in `.lazyqmk/snapshots/` using the
+//! regular Markdown layout format, named `<layout>-<timestamp>.md`.
+
+use anyhow::{Context, Result};
+use chrono::{DateTime, Local, NaiveDateTime, TimeZone};
+use std::collections::BTreeMap;
+use std::fs;
+use std::path::{Path, PathBuf};
+
+use crate::models::{Layout, Position};
+use crate::paths;
+use crate::services::layouts::sanitize_filename;
+use crate::services::LayoutService;
+
+/// Default number of snapshots kept per layout before the oldest are pruned.
+pub const DEFAULT_SNAPSHOT_RETENTION: usize = 20;
+
+/// Timestamp format used in snapshot filenames (sortable, millisecond precision).
+const TIMESTAMP_FORMAT: &str = "%Y%m%d-%H%M%S-%3f";
+
+/// Number of `-`-separated components in a formatted timestamp.
+const TIMESTAMP_PARTS: usize = 3;
+
+/// A snapshot file on disk.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct SnapshotInfo {
+    /// Full path to the snapshot file
+    pub path: PathBuf,
+    /// Layout name the snapshot belongs to (sanitized file stem)
+    pub layout_name: String,
+    /// Time the snapshot was taken
+    pub timestamp: DateTime<Local>,
+}
+
+/// A single key whose keycode differs between two layouts.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct KeyChange {
+    /// Layer index the key lives on
+    pub layer: usize,
+    /// Visual position of the key
+    pub position: Position,
+    /// Keycode in the older layout (`None` if the key did not exist)
+    pub old_keycode: Option<String>,
+    /// Keycode in the newer layout (`None` if the key was removed)
+    pub new_keycode: Option<String>,
+}
+
+/// Structural differences between two layouts.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct LayoutDiff {
+    /// Keys whose keycodes differ (on layers present in both layouts)
+    pub key_changes: Vec<KeyChange>,
+    /// Number of layers present only in the newer layout
+    pub layers_added: usize,
+    /// Number of layers present only in the older layout
+    pub layers_removed: usize,
+    /// Indices of layers whose name changed
+    pub renamed_layers: Vec<usize>,
+    /// Whether the category lists differ
+    pub categories_changed: bool,
+    /// Whether the tap dance lists differ
+    pub tap_dances_changed: bool,
+}
+
+impl LayoutDiff {
+    /// Computes the differences going from `old` to `new`.
+    #[must_use]
+    pub fn between(old: &Layout, new: &Layout) -> Self {
+        let mut diff = Self {
+            layers_added: new.layers.len().saturating_sub(old.layers.len()),
+            layers_removed: old.layers.len().saturating_sub(new.layers.len()),
+            categories_changed: old.categories != new.categories,
+            tap_dances_changed: old.tap_dances != new.tap_dances,
+            ..Self::default()
+        };
+
+        for (layer_idx, (old_layer, new_layer)) in old.layers.iter().zip(&new.layers).enumerate() {
+            if old_layer.name != new_layer.name {
+                diff.renamed_layers.push(layer_idx);
+            }
+
+            for old_key in &old_layer.keys {
+                let new_keycode = new_layer
+                    .get_key(old_key.position)
+                    .map(|k| k.keycode.clone());
+                if new_keycode.as_deref() != Some(old_key.keycode.as_str()) {
+                    diff.key_changes.push(KeyChange {
+                        layer: layer_idx,
+                        position: old_key.position,
+                        old_keycode: Some(old_key.keycode.clone()),
+                        new_keycode,
+                    });
+                }
+            }
+
+            for new_key in &new_layer.keys {
+                if old_layer.get_key(new_key.position).is_none() {
+                    diff.key_changes.push(KeyChange {
+                        layer: layer_idx,
+                        position: new_key.position,
+                        old_keycode: None,
+                        new_keycode: Some(new_key.keycode.clone()),
+                    });
+                }
+            }
+        }
+
+        diff
+    }
+
+    /// Returns true if no differences were found.
+    #[must_use]
+    pub fn is_empty(&self) -> bool {
+        self.key_changes.is_empty()
+            && self.layers_added == 0
+            && self.layers_removed == 0
+            && self.renamed_layers.is_empty()
+            && !self.categories_changed
+            && !self.tap_dances_changed
+    }
+
+    /// Number of changed keys on each layer that has any, by layer index.
+    #[must_use]
+    pub fn changes_by_layer(&self) -> BTreeMap<usize, usize> {
+        let mut counts = BTreeMap::new();
+        for change in &self.key_changes {
+            *counts.entry(change.layer).or_insert(0) += 1;
+        }
+        counts
+    }
+
+    /// Per-layer summary of key changes (e.g. "layer 2: 7 changed, layer 4: 1 changed").
+    ///
+    /// Differences other than keys are appended as in [`Self::summary`].
+    #[must_use]
+    pub fn layer_summary(&self) -> String {
+        if self.is_empty() {
+            return "identical".to_string();
+        }
+
+        let mut parts: Vec<String> = self
+            .changes_by_layer()
+            .into_iter()
+            .map(|(layer, count)| format!("layer {layer}: {count} changed"))
+            .collect();
+        let rest = Self {
+            key_changes: Vec::new(),
+            ..self.clone()
+        };
+        if !rest.is_empty() {
+            parts.push(rest.summary());
+        }
+        parts.join(", ")
+    }
+
+    /// One-line human-readable summary (e.g. "3 keys, +1 layer, categories").
+    #[must_use]
+    pub fn summary(&self) -> String {
+        if self.is_empty() {
+            return "identical".to_string();
+        }
+
+        let mut parts = Vec::new();
+        if !self.key_changes.is_empty() {
+            let count = self.key_changes.len();
+            parts.push(format!("{count} key{}", if count == 1 { "" } else { "s" }));
+        }
+        if self.layers_added > 0 {
+            let count = self.layers_added;
+            parts.push(format!(
+                "+{count} layer{}",
+                if count == 1 { "" } else { "s" }
+            ));
+        }
+        if self.layers_removed > 0 {
+            let count = self.layers_removed;
+            parts.push(format!(
+                "-{count} layer{}",
+                if count == 1 { "" } else { "s" }
+            ));
+        }
+        if !self.renamed_layers.is_empty() {
+            parts.push(format!("{} renamed", self.renamed_layers.len()));
+        }
+        if self.categories_changed {
+            parts.push("categories".to_string());
+        }
+        if self.tap_dances_changed {
+            parts.push("tap dances".to_string());
+        }
+        parts.join(", ")
+    }
+}
+
+/// Service for creating, listing, and pruning layout snapshots.
+pub struct SnapshotService;
+
+impl SnapshotService {
+    /// Returns the snapshot directory for a layout.
+    ///
+    /// Snapshots live in `.lazyqmk/snapshots/` next to the layout file. Layouts
+    /// that have never been saved use `snapshots/` in the data directory instead.
+    pub fn snapshots_dir(source_path: Option<&Path>) -> Result<PathBuf> {
+        match source_path {
+            Some(path) => Ok(paths::layout_state_dir(path).join("snapshots")),
+            None => Ok(paths::AppPaths::current()?.snapshots_dir()),
+        }
+    }
+
+    /// Returns the name used to group snapshots of a layout.
+    ///
+    /// Uses the layout file stem when available so renaming the layout inside
+    /// the editor does not orphan existing snapshots.
+    #[must_use]
+    pub fn layout_key(layout: &Layout, source_path: Option<&Path>) -> String {
+        source_path
+            .and_then(Path::file_stem)
+            .and_then(|s| s.to_str())
+            .map_or_else(
+                || sanitize_filename(&layout.metadata.name),
+                sanitize_filename,
+            )
+    }
+
+    /// Writes a snapshot of `layout` into `dir` and prunes old snapshots.
+    ///
+    /// Returns the created snapshot.
+    pub fn create(
+        layout: &Layout,
+        layout_name: &str,
+        dir: &Path,
+        retention: usize,
+    ) -> Result<SnapshotInfo> {
+        fs::create_dir_all(dir)
+            .with_context(|| format!("Failed to create snapshot directory: {}", dir.display()))?;
+
+        let timestamp = Local::now();
+        let filename = format!("{layout_name}-{}.md", timestamp.format(TIMESTAMP_FORMAT));
+        let path = dir.join(filename);
+
+        LayoutService::save(layout, &path)?;
+        Self::prune(dir, layout_name, retention)?;
+
+        Ok(SnapshotInfo {
+            path,
+            layout_name: layout_name.to_string(),
+            timestamp,
+        })
+    }
+
+    /// Lists snapshots for a layout, newest first.
+    ///
+    /// Returns an empty list if the directory does not exist.
+    pub fn list(dir: &Path, layout_name: &str) -> Result<Vec<SnapshotInfo>> {
+        if !dir.exists() {
+            return Ok(Vec::new());
+        }
+
+        let entries = fs::read_dir(dir)
+            .with_context(|| format!("Failed to read snapshot directory: {}", dir.display()))?;
+
+        let mut snapshots: Vec<SnapshotInfo> = entries
+            .flatten()
+            .filter_map(|entry| parse_snapshot_path(&entry.path()))
+            .filter(|info| info.layout_name == layout_name)
+            .collect();
+
+        snapshots.sort_by_key(|s| std::cmp::Reverse(s.timestamp));
+        Ok(snapshots)
+    }
+
+    /// Deletes the oldest snapshots so at most `retention` remain.
+    ///
+    /// Returns the number of snapshots removed.
+    pub fn prune(dir: &Path, layout_name: &str, retention: usize) -> Result<usize> {
+        let snapshots = Self::list(dir, layout_name)?;
+        let mut removed = 0;
+        for snapshot in snapshots.iter().skip(retention) {
+            fs::remove_file(&snapshot.path).with_context(|| {
+                format!("Failed to remove snapshot: {}", snapshot.path.display())
+            })?;
+            removed += 1;
+        }
+        Ok(removed)
+    }
+}
+
+/// Parses `<layout>-<YYYYMMDD>-<HHMMSS>-<mmm>.md` into a [`SnapshotInfo`].
+fn parse_snapshot_path(path: &Path) -> Option<SnapshotInfo> {
+    if path.extension().and_then(|e| e.to_str()) != Some("md") {
+        return None;
+    }
+    let stem = path.file_stem()?.to_str()?;
+
+    let parts: Vec<&str> = stem.rsplitn(TIMESTAMP_PARTS + 1, '-').collect();
+    if parts.len() != TIMESTAMP_PARTS + 1 {
+        return None;
+    }
+    let layout_name = parts[TIMESTAMP_PARTS];
+    let timestamp_str = format!("{}-{}-{}", parts[2], parts[1], parts[0]);
+
+    let naive = NaiveDateTime::parse_from_str(&timestamp_str, TIMESTAMP_FORMAT).ok()?;
+    let timestamp = Local.from_local_datetime(&naive).single()?;
+
+    Some(SnapshotInfo {
+        path: path.to_path_buf(),
+        layout_name: layout_name.to_string(),
+        timestamp,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::models::{KeyDefinition, Layer, RgbColor};
+    use tempfile::TempDir;
+
+    fn create_layout() -> Layout {
+        let mut layout = Layout::new("Snapshot Test").unwrap();
+        let mut layer = Layer::new(0, "Base", RgbColor::new(255, 255, 255)).unwrap();
+        layer
+            .add_key(KeyDefinition::new(Position::new(0, 0), "KC_A"))
+            .unwrap();
+        layer
+            .add_key(KeyDefinition::new(Position::new(0, 1), "KC_B"))
+            .unwrap();
+        layout.add_layer(layer).unwrap();
+        layout
+    }
+
+    #[test]
+    fn test_parse_snapshot_path() {
+        let info = parse_snapshot_path(Path::new("/tmp/my-layout-20250101-120000-123.md")).unwrap();
+        assert_eq!(info.layout_name, "my-layout");
+        assert_eq!(
+            info.timestamp.format(TIMESTAMP_FORMAT).to_string(),
+            "20250101-120000-123"
+        );
+
+        assert!(parse_snapshot_path(Path::new("/tmp/my-layout.md")).is_none());
+        assert!(parse_snapshot_path(Path::new("/tmp/a-20250101-120000-123.txt")).is_none());
+    }
+
+    #[test]
+    fn test_create_list_and_prune() {
+        let temp = TempDir::new().unwrap();
+        let dir = temp.path().join(".lazyqmk").join("snapshots");
+        let layout = create_layout();
+
+        for _ in 0..4 {
+            SnapshotService::create(&layout, "test", &dir, 3).unwrap();
+            std::thread::sleep(std::time::Duration::from_millis(5));
+        }
+        SnapshotService::create(&layout, "other", &dir, 3).unwrap();
+
+        let snapshots = SnapshotService::list(&dir, "test").unwrap();
+        assert_eq!(snapshots.len(), 3);
+        assert!(snapshots[0].timestamp >= snapshots[1].timestamp);
+
+        let restored = LayoutService::load(&snapshots[0].path).unwrap();
+        assert_eq!(restored.layers[0].keys.len(), 2);
+        assert_eq!(SnapshotService::list(&dir, "other").unwrap().len(), 1);
+    }
+
+    #[test]
+    fn test_list_missing_dir() {
+        let temp = TempDir::new().unwrap();
+        let snapshots = SnapshotService::list(&temp.path().join("missing"), "test").unwrap();
+        assert!(snapshots.is_empty());
+    }
+
+    #[test]
+    fn test_layout_diff_summary() {
+        let old = create_layout();
+        assert_eq!(LayoutDiff::between(&old, &old).summary(), "identical");
+
+        let mut new = old.clone();
+        new.layers[0].keys[0].keycode = "KC_Z".to_string();
+        new.layers[0].name = "Renamed".to_string();
+        let mut layer = Layer::new(1, "Extra", RgbColor::new(0, 0, 0)).unwrap();
+        layer
+            .add_key(KeyDefinition::new(Position::new(0, 0), "KC_C"))
+            .unwrap();
+        new.add_layer(layer).unwrap();
+
+        let diff = LayoutDiff::between(&old, &new);
+        assert_eq!(diff.key_changes.len(), 1);
+        assert_eq!(diff.key_changes[0].old_keycode.as_deref(), Some("KC_A"));
+        assert_eq!(diff.key_changes[0].new_keycode.as_deref(), Some("KC_Z"));
+        assert_eq!(diff.summary(), "1 key, +1 layer, 1 renamed");
+
+        let reverse = LayoutDiff::between(&new, &old);
+        assert_eq!(reverse.layers_removed, 1);
+    }
+
+    #[test]
+    fn test_layout_diff_layer_summary() {
+        let old = create_layout();
+        assert_eq!(LayoutDiff::between(&old, &old).layer_summary(), "identical");
+
+        let mut new = old.clone();
+        new.layers[0].keys[0].keycode = "KC_Z".to_string();
+        new.layers[0].keys[1].keycode = "KC_Y".to_string();
+        let diff = LayoutDiff::between(&old, &new);
+        assert_eq!(diff.changes_by_layer().get(&0), Some(&2));
+        assert_eq!(diff.layer_summary(), "layer 0: 2 changed");
+
+        new.layers[0].name = "Renamed".to_string();
+        assert_eq!(
+            LayoutDiff::between(&old, &new).layer_summary(),
+            "layer 0: 2 changed, 1 renamed"
+        );
+    }
+
+    #[test]
+    fn test_layout_key_prefers_file_stem() {
+        let layout = create_layout();
+        assert_eq!(
+            SnapshotService::layout_key(&layout, Some(Path::new("/x/My Corne.md"))),
+            "my_corne"
+        );
+        assert_eq!(SnapshotService::layout_key(&layout, None), "snapshot_test");
+    }
+}
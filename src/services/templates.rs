@@ -0,0 +1,349 @@
+//! Partial templates: saving only some sections of a layout as a template.
+//!
+//! A template can keep the whole layout or just its structure, without the
+//! personal parts (key assignments, colors, categories, tap dances, build
+//! settings). Excluded sections are reset to what a new layout has, so a
+//! partial template is still a valid layout file, and [`instantiate`] drops
+//! any references a hand-edited template left dangling.
+
+use crate::models::layout::LintSettings;
+use crate::models::{IdleEffectSettings, RgbBrightness, RgbSaturation, UncoloredKeyBehavior};
+use crate::models::{Layout, RgbColor};
+
+/// Keycode excluded key assignments become.
+const BLANK_KEYCODE: &str = "KC_TRNS";
+
+/// A part of a layout that a template can include or leave out.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum TemplateSection {
+    /// Keycodes, labels and notes of every key and encoder
+    Keys,
+    /// Layer colors, key color overrides and RGB settings
+    Colors,
+    /// Categories and the keys and layers assigned to them
+    Categories,
+    /// Tap dances and combo flags
+    TapDances,
+    /// Build and lint settings (keymap name, converter, output format)
+    Metadata,
+}
+
+impl TemplateSection {
+    /// Every section, in dialog order.
+    pub const ALL: &'static [Self] = &[
+        Self::Keys,
+        Self::Colors,
+        Self::Categories,
+        Self::TapDances,
+        Self::Metadata,
+    ];
+
+    /// Label shown next to the section's checkbox.
+    #[must_use]
+    pub const fn label(self) -> &'static str {
+        match self {
+            Self::Keys => "Key assignments (off: blank KC_TRNS structure)",
+            Self::Colors => "Colors and RGB settings",
+            Self::Categories => "Categories",
+            Self::TapDances => "Tap dances and combos",
+            Self::Metadata => "Build and lint settings",
+        }
+    }
+}
+
+/// Which sections a template includes (all of them by default).
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[allow(clippy::struct_excessive_bools)] // Independent on/off sections
+pub struct TemplateSections {
+    keys: bool,
+    colors: bool,
+    categories: bool,
+    tap_dances: bool,
+    metadata: bool,
+}
+
+impl Default for TemplateSections {
+    fn default() -> Self {
+        Self::FULL
+    }
+}
+
+impl TemplateSections {
+    /// Every section included: a copy of the whole layout.
+    pub const FULL: Self = Self {
+        keys: true,
+        colors: true,
+        categories: true,
+        tap_dances: true,
+        metadata: true,
+    };
+
+    /// Returns true if the template includes `section`.
+    #[must_use]
+    pub const fn includes(self, section: TemplateSection) -> bool {
+        match section {
+            TemplateSection::Keys => self.keys,
+            TemplateSection::Colors => self.colors,
+            TemplateSection::Categories => self.categories,
+            TemplateSection::TapDances => self.tap_dances,
+            TemplateSection::Metadata => self.metadata,
+        }
+    }
+
+    /// Includes or excludes `section`.
+    pub const fn set(&mut self, section: TemplateSection, included: bool) {
+        match section {
+            TemplateSection::Keys => self.keys = included,
+            TemplateSection::Colors => self.colors = included,
+            TemplateSection::Categories => self.categories = included,
+            TemplateSection::TapDances => self.tap_dances = included,
+            TemplateSection::Metadata => self.metadata = included,
+        }
+    }
+
+    /// Flips whether `section` is included.
+    pub const fn toggle(&mut self, section: TemplateSection) {
+        self.set(section, !self.includes(section));
+    }
+
+    /// Copies `layout` as a template with only the included sections.
+    ///
+    /// The layer structure (count, names, key positions, keyboard and
+    /// variant) is always kept, since that is what a template is for.
+    #[must_use]
+    pub fn apply(self, layout: &Layout) -> Layout {
+        let mut template = layout.clone();
+        template.metadata.is_template = true;
+        template.orphaned_keys.clear();
+
+        if !self.keys {
+            for layer in &mut template.layers {
+                layer.encoders.clear();
+                for key in &mut layer.keys {
+                    key.keycode = BLANK_KEYCODE.to_string();
+                    key.label = None;
+                    key.description = None;
+                }
+            }
+        }
+
+        if !self.colors {
+            for layer in &mut template.layers {
+                layer.default_color = RgbColor::default();
+                layer.layer_colors_enabled = true;
+                for key in &mut layer.keys {
+                    key.color_override = None;
+                }
+            }
+            template.rgb_enabled = true;
+            template.rgb_brightness = RgbBrightness::default();
+            template.rgb_saturation = RgbSaturation::default();
+            template.rgb_timeout_ms = 0;
+            template.uncolored_key_behavior = UncoloredKeyBehavior::default();
+            template.idle_effect_settings = IdleEffectSettings::default();
+        }
+
+        if !self.categories {
+            template.categories.clear();
+        }
+
+        if !self.tap_dances {
+            template.tap_dances.clear();
+            for key in template.layers.iter_mut().flat_map(|l| l.keys.iter_mut()) {
+                key.combo_participant = false;
+            }
+        }
+
+        if !self.metadata {
+            template.metadata.keymap_name = None;
+            template.metadata.converter = None;
+            template.metadata.output_format = None;
+            template.metadata.lint = LintSettings::default();
+        }
+
+        drop_dangling_references(&mut template);
+        template
+    }
+}
+
+/// Turns a loaded template into a new layout.
+///
+/// Sections the template left out already load as defaults; this also
+/// clears category and tap dance references whose definitions are missing,
+/// so a partial template never produces an invalid layout.
+#[must_use]
+pub fn instantiate(template: Layout) -> Layout {
+    let mut layout = template;
+    layout.metadata.is_template = false;
+    drop_dangling_references(&mut layout);
+    layout
+}
+
+/// Clears category assignments and `TD()` keys that refer to categories or
+/// tap dances the layout does not define.
+fn drop_dangling_references(layout: &mut Layout) {
+    let category_ids: Vec<String> = layout.categories.iter().map(|c| c.id.clone()).collect();
+    let tap_dances: Vec<String> = layout.tap_dances.iter().map(|td| td.name.clone()).collect();
+    let is_known_category = |id: &Option<String>| {
+        id.as_ref()
+            .is_none_or(|id| category_ids.iter().any(|known| known == id))
+    };
+
+    for layer in &mut layout.layers {
+        if !is_known_category(&layer.category_id) {
+            layer.category_id = None;
+        }
+        for key in &mut layer.keys {
+            if !is_known_category(&key.category_id) {
+                key.category_id = None;
+            }
+            let tap_dance = key
+                .keycode
+                .strip_prefix("TD(")
+                .and_then(|rest| rest.strip_suffix(')'));
+            if tap_dance.is_some_and(|name| !tap_dances.iter().any(|td| td == name)) {
+                key.keycode = BLANK_KEYCODE.to_string();
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::models::{Category, KeyDefinition, Layer, Position, TapDanceAction};
+    use crate::services::LayoutService;
+    use tempfile::TempDir;
+
+    /// Two layers using every section a template can leave out
+    fn full_layout() -> Layout {
+        let mut layout = Layout::new("Personal").unwrap();
+        layout
+            .categories
+            .push(Category::new("nav", "Navigation", RgbColor::new(0, 0, 255)).unwrap());
+        layout
+            .tap_dances
+            .push(TapDanceAction::new("esc_caps", "KC_ESC").with_double_tap("KC_CAPS"));
+        layout.rgb_brightness = RgbBrightness::from(40);
+        layout.metadata.keymap_name = Some("mine".to_string());
+        layout.metadata.converter = Some("rp2040_ce".to_string());
+
+        for number in 0..2 {
+            let mut layer =
+                Layer::new(number, format!("Layer {number}"), RgbColor::new(255, 0, 0)).unwrap();
+            let mut key = KeyDefinition::new(Position::new(0, 0), "TD(esc_caps)");
+            key.description = Some("Escape, caps lock on double tap".to_string());
+            key.color_override = Some(RgbColor::new(0, 255, 0));
+            key.category_id = Some("nav".to_string());
+            key.combo_participant = true;
+            layer.add_key(key).unwrap();
+            layer
+                .add_key(KeyDefinition::new(Position::new(0, 1), "KC_LEFT"))
+                .unwrap();
+            layout.add_layer(layer).unwrap();
+        }
+        layout
+    }
+
+    #[test]
+    fn test_every_exclusion_combination_round_trips() {
+        let layout = full_layout();
+        let temp = TempDir::new().unwrap();
+
+        for mask in 0..(1 << TemplateSection::ALL.len()) {
+            let mut sections = TemplateSections::default();
+            for (bit, &section) in TemplateSection::ALL.iter().enumerate() {
+                sections.set(section, mask & (1 << bit) != 0);
+            }
+            let includes = |section| sections.includes(section);
+            let template = sections.apply(&layout);
+            assert!(template.metadata.is_template);
+            // Combo flags are not written to the file
+            assert_eq!(
+                template.layers[1].keys[0].combo_participant,
+                includes(TemplateSection::TapDances)
+            );
+
+            let path = temp.path().join(format!("template-{mask}.md"));
+            LayoutService::save(&template, &path).unwrap();
+            let loaded = instantiate(LayoutService::load(&path).unwrap());
+
+            let key = &loaded.layers[1].keys[0];
+            assert_eq!(loaded.layers.len(), 2, "mask {mask}");
+            assert_eq!(loaded.layers[1].keys.len(), 2, "mask {mask}");
+            assert!(!loaded.metadata.is_template);
+
+            let expected_keycode =
+                if includes(TemplateSection::Keys) && includes(TemplateSection::TapDances) {
+                    "TD(esc_caps)"
+                } else {
+                    BLANK_KEYCODE
+                };
+            assert_eq!(key.keycode, expected_keycode, "mask {mask}");
+            assert_eq!(key.description.is_some(), includes(TemplateSection::Keys));
+            assert_eq!(
+                loaded.layers[1].keys[1].keycode,
+                if includes(TemplateSection::Keys) {
+                    "KC_LEFT"
+                } else {
+                    BLANK_KEYCODE
+                }
+            );
+
+            assert_eq!(
+                key.color_override.is_some(),
+                includes(TemplateSection::Colors)
+            );
+            assert_eq!(
+                loaded.layers[0].default_color,
+                if includes(TemplateSection::Colors) {
+                    RgbColor::new(255, 0, 0)
+                } else {
+                    RgbColor::default()
+                }
+            );
+            assert_eq!(
+                loaded.rgb_brightness == RgbBrightness::from(40),
+                includes(TemplateSection::Colors)
+            );
+
+            assert_eq!(
+                loaded.categories.len(),
+                usize::from(includes(TemplateSection::Categories))
+            );
+            assert_eq!(
+                key.category_id.is_some(),
+                includes(TemplateSection::Categories)
+            );
+
+            assert_eq!(
+                loaded.tap_dances.len(),
+                usize::from(includes(TemplateSection::TapDances))
+            );
+
+            assert_eq!(
+                loaded.metadata.converter.is_some(),
+                includes(TemplateSection::Metadata)
+            );
+            assert_eq!(
+                loaded.metadata.keymap_name.is_some(),
+                includes(TemplateSection::Metadata)
+            );
+        }
+    }
+
+    #[test]
+    fn test_instantiate_drops_dangling_references() {
+        let mut template = full_layout();
+        template.metadata.is_template = true;
+        // A hand-edited template that lost its category and tap dance tables
+        template.categories.clear();
+        template.tap_dances.clear();
+
+        let layout = instantiate(template);
+        let key = &layout.layers[0].keys[0];
+        assert_eq!(key.keycode, BLANK_KEYCODE);
+        assert!(key.category_id.is_none());
+        assert!(!layout.metadata.is_template);
+    }
+}
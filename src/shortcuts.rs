@@ -26,12 +26,28 @@ pub enum Action {
     JumpToFirst,
     /// Jump to the last item in the current context.
     JumpToLast,
+    /// Search every layer for a keycode, label or tap dance name.
+    SearchKeycode,
 
     // === LAYER SWITCHING ===
     /// Switch to the next layer.
     NextLayer,
     /// Switch to the previous layer.
     PreviousLayer,
+    /// Show the base layer's legends underneath the current layer.
+    ToggleBaseOverlay,
+    /// Cycle the coordinate overlay (position, matrix, LED, layout index, role).
+    CycleCoordinateOverlay,
+    /// Simulate typing on the layout, following its layer keys.
+    SimulateTyping,
+    /// Draw another layer until the next key, without leaving the current one.
+    PeekLayer,
+    /// Show two layers side by side, or back to one.
+    ToggleSplitView,
+    /// Move the focus to the split view's other pane.
+    FocusOtherPane,
+    /// Correct the role (thumb, home row, pinky) of the current key.
+    EditKeyRole,
 
     // === KEY EDITING ===
     /// Open the keycode picker dialog for editing a key.
@@ -46,8 +62,16 @@ pub enum Action {
     CutKey,
     /// Paste the clipboard contents to the current key.
     PasteKey,
-    /// Undo the last paste operation.
-    UndoPaste,
+    /// Paste a grid of keycodes onto the layer from the cursor.
+    PasteGrid,
+    /// Mark the current key for swapping, or swap it with the marked key.
+    SwapKey,
+
+    // === HISTORY ===
+    /// Undo the last edit.
+    Undo,
+    /// Redo the last undone edit.
+    Redo,
 
     // === SELECTION ===
     /// Toggle selection mode for multiple keys.
@@ -56,6 +80,12 @@ pub enum Action {
     ToggleCurrentKey,
     /// Start a rectangle selection of multiple keys.
     StartRectangleSelect,
+    /// Mirror the selected keys onto the other half of a split keyboard.
+    MirrorSelection,
+    /// Mirror the half of the layer the cursor is on onto the other half.
+    MirrorLayer,
+    /// Wrap the selected keys in a modifier, or unwrap them.
+    WrapSelectionInModifier,
 
     // === COLORS ===
     /// Open color picker to set color for the individual key.
@@ -74,6 +104,9 @@ pub enum Action {
     AssignCategoryToKey,
     /// Assign a category to the current layer.
     AssignCategoryToLayer,
+    /// Assign the Nth category (as numbered in the category manager) to the
+    /// current key or selection; 0 clears the category.
+    QuickAssignCategory(u8),
 
     // === MANAGERS & DIALOGS ===
     /// Open the layer manager dialog.
@@ -100,6 +133,14 @@ pub enum Action {
     GenerateFirmware,
     /// View the build log from the last firmware build.
     ViewBuildLog,
+    /// Check the layout against style rules and show the lint report.
+    LintLayout,
+    /// Validate the layout for firmware generation and show the results.
+    ValidateLayout,
+    /// Reopen the last validation report without revalidating.
+    ReviewValidation,
+    /// Hide the validation banner for the rest of the session.
+    HideValidationBanner,
 
     // === TEMPLATES ===
     /// Open the template browser to load a template configuration.
@@ -107,15 +148,29 @@ pub enum Action {
     /// Save the current configuration as a template.
     SaveAsTemplate,
 
+    // === SNAPSHOTS ===
+    /// Save a snapshot of the in-memory layout.
+    CreateSnapshot,
+    /// Open the snapshot browser to restore a snapshot.
+    BrowseSnapshots,
+
     // === CONFIGURATION ===
     /// Open the setup wizard for initial configuration.
     SetupWizard,
     /// Switch to a different layout variant.
     SwitchLayoutVariant,
+    /// Turn terminal mouse capture on or off.
+    ToggleMouseCapture,
 
     // === HELP ===
     /// Toggle the help panel display.
     ToggleHelp,
+    /// Open the command palette to search and run any action.
+    OpenCommandPalette,
+    /// View the log of actions taken this session.
+    ViewActionLog,
+    /// Show the generate/build history of the layout file.
+    ViewHistory,
 
     // === GENERAL ===
     /// Cancel the current operation or close dialogs.
@@ -135,10 +190,18 @@ impl Action {
             Self::NavigateRight => "navigate_right",
             Self::JumpToFirst => "jump_first",
             Self::JumpToLast => "jump_last",
+            Self::SearchKeycode => "search_keycode",
 
             // Layers
             Self::NextLayer => "next_layer",
             Self::PreviousLayer => "previous_layer",
+            Self::ToggleBaseOverlay => "toggle_base_overlay",
+            Self::CycleCoordinateOverlay => "cycle_coordinate_overlay",
+            Self::SimulateTyping => "simulate_typing",
+            Self::PeekLayer => "peek_layer",
+            Self::ToggleSplitView => "toggle_split_view",
+            Self::FocusOtherPane => "focus_other_pane",
+            Self::EditKeyRole => "edit_key_role",
 
             // Key editing
             Self::OpenKeycodePicker => "open_keycode_picker",
@@ -148,12 +211,20 @@ impl Action {
             Self::CopyKey => "copy_key",
             Self::CutKey => "cut_key",
             Self::PasteKey => "paste_key",
-            Self::UndoPaste => "undo_paste",
+            Self::PasteGrid => "paste_grid",
+            Self::SwapKey => "swap_key",
+
+            // History
+            Self::Undo => "undo",
+            Self::Redo => "redo",
 
             // Selection
             Self::ToggleSelectionMode => "toggle_selection_mode",
             Self::ToggleCurrentKey => "toggle_current_key",
             Self::StartRectangleSelect => "rectangle_select",
+            Self::MirrorSelection => "mirror_selection",
+            Self::MirrorLayer => "mirror_layer",
+            Self::WrapSelectionInModifier => "wrap_selection_in_modifier",
 
             // Colors
             Self::SetIndividualKeyColor => "set_individual_key_color",
@@ -165,6 +236,8 @@ impl Action {
             Self::OpenCategoryManager => "open_category_manager",
             Self::AssignCategoryToKey => "assign_category_to_key",
             Self::AssignCategoryToLayer => "assign_category_to_layer",
+            Self::QuickAssignCategory(0) => "clear_key_category",
+            Self::QuickAssignCategory(_) => "quick_assign_category",
 
             // Managers
             Self::OpenLayerManager => "open_layer_manager",
@@ -181,17 +254,29 @@ impl Action {
             Self::BuildFirmware => "build_firmware",
             Self::GenerateFirmware => "generate_firmware",
             Self::ViewBuildLog => "view_build_log",
+            Self::LintLayout => "lint_layout",
+            Self::ValidateLayout => "validate_layout",
+            Self::ReviewValidation => "review_validation",
+            Self::HideValidationBanner => "hide_validation_banner",
 
             // Templates
             Self::BrowseTemplates => "browse_templates",
             Self::SaveAsTemplate => "save_template",
 
+            // Snapshots
+            Self::CreateSnapshot => "create_snapshot",
+            Self::BrowseSnapshots => "browse_snapshots",
+
             // Configuration
             Self::SetupWizard => "setup_wizard",
             Self::SwitchLayoutVariant => "switch_layout_variant",
+            Self::ToggleMouseCapture => "toggle_mouse_capture",
 
             // Help
             Self::ToggleHelp => "toggle_help",
+            Self::OpenCommandPalette => "command_palette",
+            Self::ViewActionLog => "view_action_log",
+            Self::ViewHistory => "view_history",
 
             // General
             Self::Cancel => "cancel",
@@ -231,6 +316,38 @@ impl KeyBinding {
             modifiers: event.modifiers,
         }
     }
+
+    /// Format the binding for display, using the notation from help.toml
+    /// (e.g. "Ctrl+P", "Shift+Z", "↑", ":").
+    #[must_use]
+    pub fn label(&self) -> String {
+        let key = match self.code {
+            KeyCode::Char(' ') => "Space".to_string(),
+            KeyCode::Char(c) if !self.modifiers.is_empty() => c.to_ascii_uppercase().to_string(),
+            KeyCode::Char(c) => c.to_string(),
+            KeyCode::Up => "↑".to_string(),
+            KeyCode::Down => "↓".to_string(),
+            KeyCode::Left => "←".to_string(),
+            KeyCode::Right => "→".to_string(),
+            KeyCode::BackTab => return "Shift+Tab".to_string(),
+            KeyCode::Delete => "Del".to_string(),
+            KeyCode::Esc => "Esc".to_string(),
+            other => other.to_string(),
+        };
+
+        let mut label = String::new();
+        if self.modifiers.contains(KeyModifiers::CONTROL) {
+            label.push_str("Ctrl+");
+        }
+        if self.modifiers.contains(KeyModifiers::ALT) {
+            label.push_str("Alt+");
+        }
+        if self.modifiers.contains(KeyModifiers::SHIFT) {
+            label.push_str("Shift+");
+        }
+        label.push_str(&key);
+        label
+    }
 }
 
 impl ShortcutRegistry {
@@ -263,6 +380,7 @@ impl ShortcutRegistry {
         self.register(ctx, K::Char('l'), M::NONE, Action::NavigateRight);
         self.register(ctx, K::Home, M::NONE, Action::JumpToFirst);
         self.register(ctx, K::End, M::NONE, Action::JumpToLast);
+        self.register(ctx, K::Char('/'), M::NONE, Action::SearchKeycode);
 
         // === LAYERS ===
         self.register(ctx, K::Tab, M::NONE, Action::NextLayer);
@@ -271,6 +389,13 @@ impl ShortcutRegistry {
         self.register(ctx, K::Tab, M::SHIFT, Action::PreviousLayer);
         // Some terminals emit BackTab with the SHIFT modifier set
         self.register(ctx, K::BackTab, M::SHIFT, Action::PreviousLayer);
+        self.register(ctx, K::Char('o'), M::NONE, Action::ToggleBaseOverlay);
+        self.register(ctx, K::Char('O'), M::SHIFT, Action::CycleCoordinateOverlay);
+        self.register(ctx, K::Char('A'), M::SHIFT, Action::SimulateTyping);
+        self.register(ctx, K::Char('p'), M::ALT, Action::PeekLayer);
+        self.register(ctx, K::Char('\\'), M::NONE, Action::ToggleSplitView);
+        self.register(ctx, K::Char('o'), M::CONTROL, Action::FocusOtherPane);
+        self.register(ctx, K::Char('r'), M::ALT, Action::EditKeyRole);
 
         // === KEY EDITING ===
         self.register(ctx, K::Enter, M::NONE, Action::OpenKeycodePicker);
@@ -284,12 +409,21 @@ impl ShortcutRegistry {
         self.register(ctx, K::Char('x'), M::CONTROL, Action::CutKey);
         self.register(ctx, K::Char('p'), M::NONE, Action::PasteKey);
         self.register(ctx, K::Char('v'), M::CONTROL, Action::PasteKey);
-        self.register(ctx, K::Char('z'), M::CONTROL, Action::UndoPaste);
+        self.register(ctx, K::Char('P'), M::SHIFT, Action::PasteGrid);
+        self.register(ctx, K::Char('s'), M::NONE, Action::SwapKey);
+
+        // === HISTORY ===
+        self.register(ctx, K::Char('u'), M::NONE, Action::Undo);
+        self.register(ctx, K::Char('z'), M::CONTROL, Action::Undo);
+        self.register(ctx, K::Char('r'), M::CONTROL, Action::Redo);
 
         // === SELECTION ===
         self.register(ctx, K::Char('V'), M::SHIFT, Action::ToggleSelectionMode);
         self.register(ctx, K::Char(' '), M::NONE, Action::ToggleCurrentKey);
         self.register(ctx, K::Char('R'), M::SHIFT, Action::StartRectangleSelect);
+        self.register(ctx, K::Char('M'), M::SHIFT, Action::MirrorSelection);
+        self.register(ctx, K::Char('M'), M::ALT | M::SHIFT, Action::MirrorLayer);
+        self.register(ctx, K::Char('W'), M::SHIFT, Action::WrapSelectionInModifier);
 
         // === COLORS (v0.4.0: c = individual, Shift+C = layer) ===
         self.register(ctx, K::Char('c'), M::NONE, Action::SetIndividualKeyColor);
@@ -301,6 +435,11 @@ impl ShortcutRegistry {
         self.register(ctx, K::Char('K'), M::SHIFT, Action::OpenCategoryManager);
         self.register(ctx, K::Char('k'), M::CONTROL, Action::AssignCategoryToKey);
         self.register(ctx, K::Char('l'), M::CONTROL, Action::AssignCategoryToLayer);
+        // Alt+1..9 assign the numbered category, Alt+0 clears it
+        for n in 0..=9 {
+            let digit = char::from(b'0' + n);
+            self.register(ctx, K::Char(digit), M::ALT, Action::QuickAssignCategory(n));
+        }
 
         // === MANAGERS & DIALOGS (v0.4.0: Shift+L = layers, Shift+E = metadata) ===
         self.register(ctx, K::Char('L'), M::SHIFT, Action::OpenLayerManager);
@@ -317,17 +456,30 @@ impl ShortcutRegistry {
         self.register(ctx, K::Char('b'), M::CONTROL, Action::BuildFirmware);
         self.register(ctx, K::Char('g'), M::CONTROL, Action::GenerateFirmware);
         self.register(ctx, K::Char('B'), M::SHIFT, Action::ViewBuildLog);
+        self.register(ctx, K::Char('I'), M::SHIFT, Action::LintLayout);
+        self.register(ctx, K::Char('F'), M::SHIFT, Action::ValidateLayout);
+        self.register(ctx, K::Char('G'), M::SHIFT, Action::ReviewValidation);
+        self.register(ctx, K::Char('X'), M::SHIFT, Action::HideValidationBanner);
 
         // === TEMPLATES ===
         self.register(ctx, K::Char('t'), M::NONE, Action::BrowseTemplates);
         self.register(ctx, K::Char('T'), M::SHIFT, Action::SaveAsTemplate);
 
+        // === SNAPSHOTS ===
+        self.register(ctx, K::Char('z'), M::NONE, Action::CreateSnapshot);
+        self.register(ctx, K::Char('Z'), M::SHIFT, Action::BrowseSnapshots);
+
         // === CONFIGURATION ===
         self.register(ctx, K::Char('w'), M::CONTROL, Action::SetupWizard);
         self.register(ctx, K::Char('y'), M::CONTROL, Action::SwitchLayoutVariant);
+        self.register(ctx, K::Char('m'), M::ALT, Action::ToggleMouseCapture);
 
         // === HELP ===
         self.register(ctx, K::Char('?'), M::NONE, Action::ToggleHelp);
+        self.register(ctx, K::Char('p'), M::CONTROL, Action::OpenCommandPalette);
+        self.register(ctx, K::Char(':'), M::NONE, Action::OpenCommandPalette);
+        self.register(ctx, K::Char('h'), M::CONTROL, Action::ViewActionLog);
+        self.register(ctx, K::Char('H'), M::SHIFT, Action::ViewHistory);
 
         // === GENERAL ===
         self.register(ctx, K::Esc, M::NONE, Action::Cancel);
@@ -346,6 +498,22 @@ impl ShortcutRegistry {
         self.bindings.get(&(context.to_string(), binding)).copied()
     }
 
+    /// Get all key bindings for an action in the given context.
+    ///
+    /// Bindings are sorted so that unmodified keys come first, giving a
+    /// stable order for display in the help overlay and command palette.
+    #[must_use]
+    pub fn bindings_for(&self, context: &str, action: Action) -> Vec<KeyBinding> {
+        let mut bindings: Vec<KeyBinding> = self
+            .bindings
+            .iter()
+            .filter(|((ctx, _), a)| ctx == context && **a == action)
+            .map(|((_, binding), _)| *binding)
+            .collect();
+        bindings.sort_by_key(|b| (b.modifiers.bits(), b.label()));
+        bindings
+    }
+
     /// Check if a key event matches a specific action in the given context.
     #[must_use]
     #[allow(dead_code)]
@@ -490,4 +658,66 @@ mod tests {
             "Tab+SHIFT should also be mapped to PreviousLayer"
         );
     }
+
+    #[test]
+    fn test_command_palette_shortcuts() {
+        let registry = ShortcutRegistry::new();
+
+        let ctrl_p = KeyEvent::new(KeyCode::Char('p'), KeyModifiers::CONTROL);
+        assert_eq!(
+            registry.lookup("main", ctrl_p),
+            Some(Action::OpenCommandPalette)
+        );
+
+        let colon = KeyEvent::new(KeyCode::Char(':'), KeyModifiers::NONE);
+        assert_eq!(
+            registry.lookup("main", colon),
+            Some(Action::OpenCommandPalette)
+        );
+    }
+
+    #[test]
+    fn test_bindings_for_action() {
+        let registry = ShortcutRegistry::new();
+
+        let labels: Vec<String> = registry
+            .bindings_for("main", Action::OpenCommandPalette)
+            .iter()
+            .map(KeyBinding::label)
+            .collect();
+        assert_eq!(labels, vec![":", "Ctrl+P"]);
+
+        let labels: Vec<String> = registry
+            .bindings_for("main", Action::BrowseSnapshots)
+            .iter()
+            .map(KeyBinding::label)
+            .collect();
+        assert_eq!(labels, vec!["Shift+Z"]);
+
+        assert!(registry.bindings_for("other", Action::Save).is_empty());
+    }
+
+    #[test]
+    fn test_quick_assign_category_shortcuts() {
+        let registry = ShortcutRegistry::new();
+
+        let alt_3 = KeyEvent::new(KeyCode::Char('3'), KeyModifiers::ALT);
+        assert_eq!(
+            registry.lookup("main", alt_3),
+            Some(Action::QuickAssignCategory(3))
+        );
+
+        let alt_0 = KeyEvent::new(KeyCode::Char('0'), KeyModifiers::ALT);
+        assert_eq!(
+            registry.lookup("main", alt_0),
+            Some(Action::QuickAssignCategory(0))
+        );
+
+        let labels: Vec<String> = registry
+            .bindings_for("main", Action::QuickAssignCategory(9))
+            .iter()
+            .map(KeyBinding::label)
+            .collect();
+        assert_eq!(labels, vec!["Alt+9"]);
+    }
 }
@@ -0,0 +1,275 @@
+//! Session action log and its scrollable viewer.
+//!
+//! Every state-mutating action records an entry through
+//! [`AppState::log_action`](crate::tui::AppState::log_action), which also
+//! updates the status bar. The log keeps the most recent entries only and is
+//! written into the crash bundle if the TUI exits abnormally.
+
+use std::collections::VecDeque;
+use std::fmt::Write as _;
+
+use chrono::{DateTime, Local};
+use ratatui::{
+    layout::Rect,
+    style::{Modifier, Style},
+    text::{Line, Span},
+    widgets::{Block, Borders, Clear, List, ListItem, Paragraph},
+    Frame,
+};
+
+use super::Theme;
+
+/// Maximum number of entries kept in the session log
+const MAX_ACTION_LOG_ENTRIES: usize = 500;
+
+/// A single logged action.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ActionLogEntry {
+    /// When the action happened
+    pub timestamp: DateTime<Local>,
+    /// Short action name (e.g., "Paste")
+    pub action: String,
+    /// What the action did (e.g., "Pasted 12 keys on layer 2")
+    pub summary: String,
+}
+
+/// Bounded log of state-mutating actions for the current session.
+#[derive(Debug, Clone, Default)]
+pub struct ActionLog {
+    /// Entries, oldest first
+    entries: VecDeque<ActionLogEntry>,
+}
+
+impl ActionLog {
+    /// Creates an empty action log.
+    #[must_use]
+    pub const fn new() -> Self {
+        Self {
+            entries: VecDeque::new(),
+        }
+    }
+
+    /// Records an action, dropping the oldest entry when full.
+    pub fn record(&mut self, action: impl Into<String>, summary: impl Into<String>) {
+        if self.entries.len() == MAX_ACTION_LOG_ENTRIES {
+            self.entries.pop_front();
+        }
+        self.entries.push_back(ActionLogEntry {
+            timestamp: Local::now(),
+            action: action.into(),
+            summary: summary.into(),
+        });
+    }
+
+    /// Entries, oldest first.
+    pub fn entries(&self) -> impl DoubleEndedIterator<Item = &ActionLogEntry> {
+        self.entries.iter()
+    }
+
+    /// Number of entries in the log.
+    #[must_use]
+    pub fn len(&self) -> usize {
+        self.entries.len()
+    }
+
+    /// Whether the log is empty.
+    #[must_use]
+    pub fn is_empty(&self) -> bool {
+        self.entries.is_empty()
+    }
+
+    /// Plain-text dump of the log, one entry per line.
+    #[must_use]
+    pub fn to_text(&self) -> String {
+        let mut text = String::new();
+        for entry in &self.entries {
+            let _ = writeln!(
+                text,
+                "{} [{}] {}",
+                entry.timestamp.format("%Y-%m-%d %H:%M:%S"),
+                entry.action,
+                entry.summary
+            );
+        }
+        text
+    }
+}
+
+/// Events emitted by the ActionLogView component
+#[derive(Debug, Clone)]
+pub enum ActionLogEvent {
+    /// User closed the action log
+    Closed,
+}
+
+/// ActionLogView component that implements the ContextualComponent trait.
+///
+/// Entries are listed newest first.
+#[derive(Debug, Clone, Default)]
+pub struct ActionLogView {
+    /// Scroll offset (number of entries from the newest)
+    scroll_offset: usize,
+}
+
+impl ActionLogView {
+    /// Create a new ActionLogView
+    #[must_use]
+    pub const fn new() -> Self {
+        Self { scroll_offset: 0 }
+    }
+}
+
+impl crate::tui::component::ContextualComponent for ActionLogView {
+    type Context = ActionLog;
+    type Event = ActionLogEvent;
+
+    fn handle_input(
+        &mut self,
+        key: crossterm::event::KeyEvent,
+        context: &Self::Context,
+    ) -> Option<Self::Event> {
+        use crossterm::event::KeyCode;
+
+        let last = context.len().saturating_sub(1);
+        match key.code {
+            KeyCode::Esc | KeyCode::Char('q') => Some(ActionLogEvent::Closed),
+            KeyCode::Up | KeyCode::Char('k') => {
+                self.scroll_offset = self.scroll_offset.saturating_sub(1);
+                None
+            }
+            KeyCode::Down | KeyCode::Char('j') => {
+                self.scroll_offset = (self.scroll_offset + 1).min(last);
+                None
+            }
+            KeyCode::PageUp => {
+                self.scroll_offset = self.scroll_offset.saturating_sub(10);
+                None
+            }
+            KeyCode::PageDown => {
+                self.scroll_offset = (self.scroll_offset + 10).min(last);
+                None
+            }
+            KeyCode::Home | KeyCode::Char('g') => {
+                self.scroll_offset = 0;
+                None
+            }
+            KeyCode::End | KeyCode::Char('G') => {
+                self.scroll_offset = last;
+                None
+            }
+            _ => None,
+        }
+    }
+
+    fn render(&self, f: &mut Frame, area: Rect, theme: &Theme, context: &Self::Context) {
+        render_action_log(f, self, area, theme, context);
+    }
+}
+
+/// Renders the action log popup
+fn render_action_log(
+    f: &mut Frame,
+    view: &ActionLogView,
+    area: Rect,
+    theme: &Theme,
+    log: &ActionLog,
+) {
+    let area = centered_rect(80, 70, area);
+
+    f.render_widget(Clear, area);
+    let background = Block::default().style(Style::default().bg(theme.background));
+    f.render_widget(background, area);
+
+    let visible_lines = area.height.saturating_sub(2) as usize;
+    let items: Vec<ListItem> = if log.is_empty() {
+        vec![ListItem::new(Line::from(Span::styled(
+            "No actions yet this session",
+            Style::default().fg(theme.text_muted),
+        )))]
+    } else {
+        log.entries()
+            .rev()
+            .skip(view.scroll_offset)
+            .take(visible_lines)
+            .map(|entry| {
+                ListItem::new(Line::from(vec![
+                    Span::styled(
+                        format!("{} ", entry.timestamp.format("%H:%M:%S")),
+                        Style::default().fg(theme.text_muted),
+                    ),
+                    Span::styled(
+                        format!("{:<16} ", entry.action),
+                        Style::default().fg(theme.accent),
+                    ),
+                    Span::styled(entry.summary.clone(), Style::default().fg(theme.text)),
+                ]))
+            })
+            .collect()
+    };
+
+    let title = format!(
+        " Action Log ({}/{}) ",
+        (view.scroll_offset + 1).min(log.len()),
+        log.len()
+    );
+    let list = List::new(items).block(
+        Block::default()
+            .title(title)
+            .borders(Borders::ALL)
+            .border_style(Style::default().fg(theme.primary)),
+    );
+    f.render_widget(list, area);
+
+    let help_text = "↑↓: Scroll | Home/End: Jump | Esc: Close";
+    let help_area = Rect {
+        x: area.x + 2,
+        y: area.y + area.height.saturating_sub(1),
+        width: area.width.saturating_sub(4),
+        height: 1,
+    };
+    let help = Paragraph::new(help_text).style(
+        Style::default()
+            .fg(theme.text_muted)
+            .add_modifier(Modifier::DIM),
+    );
+    f.render_widget(help, help_area);
+}
+
+/// Helper to create a centered rectangle.
+fn centered_rect(percent_x: u16, percent_y: u16, r: Rect) -> Rect {
+    use ratatui::layout::{Constraint, Direction, Layout};
+
+    let popup_layout = Layout::default()
+        .direction(Direction::Vertical)
+        .constraints([
+            Constraint::Percentage((100 - percent_y) / 2),
+            Constraint::Percentage(percent_y),
+            Constraint::Percentage((100 - percent_y) / 2),
+        ])
+        .split(r);
+
+    Layout::default()
+        .direction(Direction::Horizontal)
+        .constraints([
+            Constraint::Percentage((100 - percent_x) / 2),
+            Constraint::Percentage(percent_x),
+            Constraint::Percentage((100 - percent_x) / 2),
+        ])
+        .split(popup_layout[1])[1]
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_action_log_is_bounded() {
+        let mut log = ActionLog::new();
+        for i in 0..MAX_ACTION_LOG_ENTRIES + 5 {
+            log.record("Paste", format!("entry {i}"));
+        }
+        assert_eq!(log.len(), MAX_ACTION_LOG_ENTRIES);
+        assert_eq!(log.entries().next().unwrap().summary, "entry 5");
+        assert!(log.to_text().contains("[Paste] entry 504"));
+    }
+}
@@ -3,6 +3,8 @@
 //! Provides a UI for creating, renaming, recoloring, and deleting categories.
 //! Accessible via Shift+K shortcut (mnemonic: K = Kategorties/Categories).
 
+use std::collections::HashMap;
+
 use crossterm::event::{KeyCode, KeyEvent};
 use ratatui::{
     layout::{Alignment, Constraint, Rect},
@@ -12,8 +14,9 @@ use ratatui::{
     Frame,
 };
 
-use crate::models::{Category, RgbColor};
+use crate::models::{Category, CategoryFlag, CategoryUsage, RgbColor};
 use crate::tui::component::Component;
+use crate::tui::text_input::TextInput;
 use crate::tui::Theme;
 
 /// Events emitted by the CategoryManager component
@@ -30,6 +33,13 @@ pub enum CategoryManagerEvent {
         /// New color (if changed)
         color: Option<RgbColor>,
     },
+    /// User toggled a firmware RGB flag
+    FlagToggled {
+        /// Category ID
+        id: String,
+        /// Flag that was toggled
+        flag: CategoryFlag,
+    },
     /// User cancelled without making changes
     Cancelled,
     /// Component closed naturally
@@ -44,7 +54,7 @@ pub enum ManagerMode {
     /// Creating a new category (entering name)
     CreatingName {
         /// User input for category name
-        input: String,
+        input: TextInput,
     },
     /// Creating a new category (selecting color)
     CreatingColor {
@@ -56,12 +66,14 @@ pub enum ManagerMode {
         /// ID of category being renamed
         category_id: String,
         /// User input for new name
-        input: String,
+        input: TextInput,
     },
     /// Confirming deletion
     ConfirmingDelete {
         /// ID of category to delete
         category_id: String,
+        /// Keys and layers that will lose the category
+        usage: CategoryUsage,
     },
 }
 
@@ -112,7 +124,7 @@ impl CategoryManagerState {
     /// Start creating a new category
     pub fn start_creating(&mut self) {
         self.mode = ManagerMode::CreatingName {
-            input: String::new(),
+            input: TextInput::default(),
         };
     }
 
@@ -120,14 +132,15 @@ impl CategoryManagerState {
     pub fn start_renaming(&mut self, category: &Category) {
         self.mode = ManagerMode::Renaming {
             category_id: category.id.clone(),
-            input: category.name.clone(),
+            input: TextInput::new(category.name.clone()),
         };
     }
 
     /// Start confirming deletion of the selected category
-    pub fn start_deleting(&mut self, category: &Category) {
+    pub fn start_deleting(&mut self, category: &Category, usage: CategoryUsage) {
         self.mode = ManagerMode::ConfirmingDelete {
             category_id: category.id.clone(),
+            usage,
         };
     }
 
@@ -151,6 +164,8 @@ pub struct CategoryManager {
     /// Categories to display and modify (reference - not owned)
     /// The component requires external categories data to function
     cached_categories: Vec<Category>,
+    /// Keys and layers assigned to each category, by ID
+    cached_usage: HashMap<String, CategoryUsage>,
 }
 
 impl CategoryManager {
@@ -160,9 +175,16 @@ impl CategoryManager {
         Self {
             state: CategoryManagerState::new(),
             cached_categories: categories,
+            cached_usage: HashMap::new(),
         }
     }
 
+    /// Update how many keys and layers use each category (shown when
+    /// confirming a deletion)
+    pub fn set_usage(&mut self, usage: HashMap<String, CategoryUsage>) {
+        self.cached_usage = usage;
+    }
+
     /// Update the categories list (needed for rendering)
     pub fn set_categories(&mut self, categories: Vec<Category>) {
         self.cached_categories = categories;
@@ -192,9 +214,7 @@ impl Component for CategoryManager {
     fn handle_input(&mut self, key: KeyEvent) -> Option<Self::Event> {
         match &self.state.mode {
             ManagerMode::Browsing => self.handle_browsing_input(key),
-            ManagerMode::CreatingName { input } => {
-                self.handle_creating_name_input(key, input.clone())
-            }
+            ManagerMode::CreatingName { .. } => self.handle_creating_name_input(key),
             ManagerMode::CreatingColor { name } => {
                 // Color selection is handled by parent - just cancel here
                 if key.code == KeyCode::Esc {
@@ -204,10 +224,8 @@ impl Component for CategoryManager {
                     None
                 }
             }
-            ManagerMode::Renaming { category_id, input } => {
-                self.handle_renaming_input(key, category_id.clone(), input.clone())
-            }
-            ManagerMode::ConfirmingDelete { category_id } => {
+            ManagerMode::Renaming { .. } => self.handle_renaming_input(key),
+            ManagerMode::ConfirmingDelete { category_id, .. } => {
                 self.handle_delete_confirmation_input(key, category_id.clone())
             }
         }
@@ -246,10 +264,17 @@ impl CategoryManager {
             }
             KeyCode::Char('d') => {
                 if let Some(category) = self.cached_categories.get(self.state.selected) {
-                    self.state.start_deleting(category);
+                    let usage = self
+                        .cached_usage
+                        .get(&category.id)
+                        .copied()
+                        .unwrap_or_default();
+                    self.state.start_deleting(category, usage);
                 }
                 None
             }
+            KeyCode::Char('o') => self.toggle_flag(CategoryFlag::AlwaysOn),
+            KeyCode::Char('b') => self.toggle_flag(CategoryFlag::Breathing),
             KeyCode::Up | KeyCode::Char('k') => {
                 self.state.select_previous(self.cached_categories.len());
                 None
@@ -262,92 +287,68 @@ impl CategoryManager {
         }
     }
 
+    /// Emits a flag toggle for the selected category
+    fn toggle_flag(&self, flag: CategoryFlag) -> Option<CategoryManagerEvent> {
+        self.cached_categories
+            .get(self.state.selected)
+            .map(|category| CategoryManagerEvent::FlagToggled {
+                id: category.id.clone(),
+                flag,
+            })
+    }
+
     /// Handle input in creating name mode
-    fn handle_creating_name_input(
-        &mut self,
-        key: KeyEvent,
-        mut input: String,
-    ) -> Option<CategoryManagerEvent> {
+    fn handle_creating_name_input(&mut self, key: KeyEvent) -> Option<CategoryManagerEvent> {
+        let ManagerMode::CreatingName { input } = &mut self.state.mode else {
+            return None;
+        };
         match key.code {
             KeyCode::Esc => {
                 self.state.cancel();
                 Some(CategoryManagerEvent::Cancelled)
             }
             KeyCode::Enter => {
-                if !input.trim().is_empty() {
+                if !input.value().trim().is_empty() {
                     // Transition to color selection mode
                     self.state.mode = ManagerMode::CreatingColor {
-                        name: input.clone(),
+                        name: input.value().to_string(),
                     };
-                    None
-                } else {
-                    None
-                }
-            }
-            KeyCode::Char(c) => {
-                input.push(c);
-                if let ManagerMode::CreatingName { input: ref mut i } = self.state.mode {
-                    *i = input;
                 }
                 None
             }
-            KeyCode::Backspace => {
-                input.pop();
-                if let ManagerMode::CreatingName { input: ref mut i } = self.state.mode {
-                    *i = input;
-                }
+            _ => {
+                input.handle_key(key);
                 None
             }
-            _ => None,
         }
     }
 
     /// Handle input in renaming mode
-    fn handle_renaming_input(
-        &mut self,
-        key: KeyEvent,
-        category_id: String,
-        mut input: String,
-    ) -> Option<CategoryManagerEvent> {
+    fn handle_renaming_input(&mut self, key: KeyEvent) -> Option<CategoryManagerEvent> {
+        let ManagerMode::Renaming { category_id, input } = &mut self.state.mode else {
+            return None;
+        };
         match key.code {
             KeyCode::Esc => {
                 self.state.cancel();
                 Some(CategoryManagerEvent::Cancelled)
             }
             KeyCode::Enter => {
-                if !input.trim().is_empty() {
-                    let event = Some(CategoryManagerEvent::CategoryUpdated {
-                        id: category_id,
-                        name: Some(input.clone()),
-                        color: None,
-                    });
-                    self.state.cancel();
-                    event
-                } else {
-                    None
-                }
-            }
-            KeyCode::Char(c) => {
-                input.push(c);
-                if let ManagerMode::Renaming {
-                    input: ref mut i, ..
-                } = self.state.mode
-                {
-                    *i = input;
+                if input.value().trim().is_empty() {
+                    return None;
                 }
-                None
+                let event = Some(CategoryManagerEvent::CategoryUpdated {
+                    id: category_id.clone(),
+                    name: Some(input.value().to_string()),
+                    color: None,
+                });
+                self.state.cancel();
+                event
             }
-            KeyCode::Backspace => {
-                input.pop();
-                if let ManagerMode::Renaming {
-                    input: ref mut i, ..
-                } = self.state.mode
-                {
-                    *i = input;
-                }
+            _ => {
+                input.handle_key(key);
                 None
             }
-            _ => None,
         }
     }
 
@@ -436,9 +437,9 @@ pub fn render_category_manager(
                 theme,
             );
         }
-        ManagerMode::ConfirmingDelete { category_id } => {
+        ManagerMode::ConfirmingDelete { category_id, usage } => {
             if let Some(category) = categories.iter().find(|c| &c.id == category_id) {
-                render_delete_confirmation(f, inner_area, category, theme);
+                render_delete_confirmation(f, inner_area, category, *usage, theme);
             }
         }
         ManagerMode::CreatingColor { name } => {
@@ -478,8 +479,21 @@ fn render_category_list(
                 Style::default().fg(theme.text)
             };
 
+            // Alt+1..9 quick-assign the first nine categories
+            let number = if i < 9 {
+                format!("{} ", i + 1)
+            } else {
+                "  ".to_string()
+            };
             let color_box = "█████ ".to_string();
+            let flags = cat.flags().fold(String::new(), |mut flags, flag| {
+                flags.push_str(" [");
+                flags.push_str(flag.name());
+                flags.push(']');
+                flags
+            });
             let content = Line::from(vec![
+                Span::styled(number, Style::default().fg(theme.text_muted)),
                 Span::styled(
                     color_box,
                     Style::default().fg(Color::Rgb(cat.color.r, cat.color.g, cat.color.b)),
@@ -489,6 +503,7 @@ fn render_category_list(
                     format!(" ({})", cat.id),
                     Style::default().fg(theme.text_muted),
                 ),
+                Span::styled(flags, Style::default().fg(theme.warning)),
             ]);
 
             ListItem::new(content)
@@ -497,7 +512,7 @@ fn render_category_list(
 
     let list = List::new(items)
         .block(Block::default().borders(Borders::ALL).title("Categories"))
-        .highlight_style(Style::default().bg(theme.surface));
+        .highlight_style(theme.selection(Style::default().bg(theme.surface)));
 
     f.render_widget(list, chunks[0]);
 
@@ -512,13 +527,19 @@ fn render_category_list(
             Span::styled("c", Style::default().fg(theme.primary)),
             Span::raw(": Change Color  "),
             Span::styled("d", Style::default().fg(theme.primary)),
-            Span::raw(": Delete"),
+            Span::raw(": Delete  "),
+            Span::styled("o", Style::default().fg(theme.primary)),
+            Span::raw(": Always On  "),
+            Span::styled("b", Style::default().fg(theme.primary)),
+            Span::raw(": Breathing"),
         ]),
         Line::from(vec![
             Span::styled("↑/↓", Style::default().fg(theme.primary)),
             Span::raw(": Navigate  "),
             Span::styled("Shift+L", Style::default().fg(theme.primary)),
             Span::raw(": Assign to Layer  "),
+            Span::styled("Alt+1-9", Style::default().fg(theme.primary)),
+            Span::raw(": Quick Assign (main view)  "),
             Span::styled("Esc", Style::default().fg(theme.primary)),
             Span::raw(": Close"),
         ]),
@@ -536,7 +557,7 @@ fn render_name_input(
     f: &mut Frame,
     area: Rect,
     title: &str,
-    input: &str,
+    input: &TextInput,
     prompt: &str,
     theme: &Theme,
 ) {
@@ -562,11 +583,13 @@ fn render_name_input(
         .title(title)
         .style(Style::default().fg(theme.primary));
 
-    let input_text = Paragraph::new(input)
-        .block(input_block)
-        .style(Style::default().fg(theme.text));
-
-    f.render_widget(input_text, chunks[1]);
+    input.render(
+        f,
+        chunks[1],
+        input_block,
+        Style::default().fg(theme.text),
+        true,
+    );
 
     // Help text
     let help = vec![Line::from(vec![
@@ -584,12 +607,19 @@ fn render_name_input(
 }
 
 /// Render delete confirmation dialog
-fn render_delete_confirmation(f: &mut Frame, area: Rect, category: &Category, theme: &Theme) {
+fn render_delete_confirmation(
+    f: &mut Frame,
+    area: Rect,
+    category: &Category,
+    usage: CategoryUsage,
+    theme: &Theme,
+) {
     let chunks = ratatui::layout::Layout::default()
         .direction(ratatui::layout::Direction::Vertical)
         .constraints([
             Constraint::Length(3), // Warning
-            Constraint::Length(3), // Category info
+            Constraint::Length(2), // Category info
+            Constraint::Length(2), // Affected keys and layers
             Constraint::Min(1),    // Spacer
             Constraint::Length(3), // Help
         ])
@@ -620,6 +650,20 @@ fn render_delete_confirmation(f: &mut Frame, area: Rect, category: &Category, th
         .style(Style::default().fg(theme.text));
     f.render_widget(info_widget, chunks[1]);
 
+    // Affected keys and layers
+    let affected = if usage == CategoryUsage::default() {
+        "No keys or layers use this category.".to_string()
+    } else {
+        format!(
+            "{} key(s) and {} layer(s) will lose this category (Ctrl+Z restores them).",
+            usage.keys, usage.layers
+        )
+    };
+    let affected_widget = Paragraph::new(affected)
+        .alignment(Alignment::Center)
+        .style(Style::default().fg(theme.warning));
+    f.render_widget(affected_widget, chunks[2]);
+
     // Help
     let help = vec![Line::from(vec![
         Span::styled("y", Style::default().fg(theme.primary)),
@@ -632,7 +676,7 @@ fn render_delete_confirmation(f: &mut Frame, area: Rect, category: &Category, th
         .alignment(Alignment::Center)
         .style(Style::default().fg(theme.text_muted));
 
-    f.render_widget(help_widget, chunks[3]);
+    f.render_widget(help_widget, chunks[4]);
 }
 
 /// Render color picker prompt
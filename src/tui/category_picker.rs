@@ -193,9 +193,11 @@ pub fn render_category_picker_component(
                 .border_style(Style::default().fg(theme.primary)),
         )
         .highlight_style(
-            Style::default()
-                .bg(theme.surface)
-                .add_modifier(Modifier::BOLD),
+            theme.selection(
+                Style::default()
+                    .bg(theme.surface)
+                    .add_modifier(Modifier::BOLD),
+            ),
         )
         .highlight_symbol("► ");
 
@@ -2,7 +2,7 @@
 //!
 //! Provides clipboard state management for copying, cutting, and pasting
 //! keys within and across layers. Supports both single-key and multi-key
-//! selection operations, plus undo functionality.
+//! selection operations. Undo lives in [`super::undo`].
 
 use crate::models::{Position, RgbColor};
 
@@ -26,17 +26,6 @@ pub struct MultiKeyContent {
     pub anchor: Position,
 }
 
-/// State saved for undo operation
-#[derive(Debug, Clone)]
-pub struct UndoState {
-    /// Layer index where the change was made
-    pub layer_index: usize,
-    /// Keys that were modified (position + original content)
-    pub original_keys: Vec<(Position, ClipboardContent)>,
-    /// Description of the operation (for status message)
-    pub description: String,
-}
-
 /// Clipboard state for key operations.
 #[derive(Debug, Clone, Default)]
 pub struct KeyClipboard {
@@ -49,8 +38,6 @@ pub struct KeyClipboard {
     cut_source: Option<(usize, Position)>,
     /// Multiple cut sources for multi-key cut
     multi_cut_sources: Vec<(usize, Position)>,
-    /// Undo stack (most recent operation)
-    undo_state: Option<UndoState>,
 }
 
 impl KeyClipboard {
@@ -62,7 +49,6 @@ impl KeyClipboard {
             multi_content: None,
             cut_source: None,
             multi_cut_sources: Vec::new(),
-            undo_state: None,
         }
     }
 
@@ -254,37 +240,6 @@ impl KeyClipboard {
             None
         }
     }
-
-    /// Save undo state before making changes.
-    pub fn save_undo(
-        &mut self,
-        layer_index: usize,
-        keys: Vec<(Position, ClipboardContent)>,
-        description: String,
-    ) {
-        self.undo_state = Some(UndoState {
-            layer_index,
-            original_keys: keys,
-            description,
-        });
-    }
-
-    /// Get the undo state (if any).
-    #[must_use]
-    pub const fn get_undo(&self) -> Option<&UndoState> {
-        self.undo_state.as_ref()
-    }
-
-    /// Take the undo state (consuming it).
-    pub const fn take_undo(&mut self) -> Option<UndoState> {
-        self.undo_state.take()
-    }
-
-    /// Check if undo is available.
-    #[must_use]
-    pub const fn can_undo(&self) -> bool {
-        self.undo_state.is_some()
-    }
 }
 
 #[cfg(test)]
@@ -470,30 +425,4 @@ mod tests {
         assert!(clipboard.is_cut_source(0, Position::new(0, 1)));
         assert!(!clipboard.is_cut_source(0, Position::new(1, 0)));
     }
-
-    #[test]
-    fn test_undo_state() {
-        let mut clipboard = KeyClipboard::new();
-
-        assert!(!clipboard.can_undo());
-
-        let keys = vec![(
-            Position::new(0, 0),
-            ClipboardContent {
-                keycode: "KC_A".to_string(),
-                color_override: None,
-                category_id: None,
-            },
-        )];
-        clipboard.save_undo(0, keys, "Pasted KC_B".to_string());
-
-        assert!(clipboard.can_undo());
-
-        let undo = clipboard.take_undo().unwrap();
-        assert_eq!(undo.layer_index, 0);
-        assert_eq!(undo.original_keys.len(), 1);
-        assert_eq!(undo.description, "Pasted KC_B");
-
-        assert!(!clipboard.can_undo());
-    }
 }
@@ -3,6 +3,9 @@
 //! Supports two modes:
 //! - Palette mode: Select from curated colors with shades
 //! - Custom RGB mode: Fine-tune with RGB sliders
+//!
+//! From either mode, `e` starts the eyedropper: the popup shrinks to a hint
+//! line while the board selection picks a key whose color to sample.
 
 // Input handlers use Result<bool> for consistency even when they never fail
 #![allow(clippy::unnecessary_wraps)]
@@ -18,11 +21,11 @@ use ratatui::{
     layout::{Constraint, Direction, Layout, Rect},
     style::{Color, Modifier, Style},
     text::{Line, Span},
-    widgets::{Block, Borders, Clear, Gauge, Paragraph},
+    widgets::{Block, Borders, Clear, Gauge, Paragraph, Wrap},
     Frame,
 };
 
-use crate::models::{ColorPalette, RgbColor};
+use crate::models::{ColorPalette, PaletteKind, Position, RgbColor};
 use crate::tui::component::{ColorPickerContext, Component};
 use crate::tui::Theme;
 
@@ -35,6 +38,8 @@ pub enum ColorPickerEvent {
     ColorCleared,
     /// User cancelled without making changes
     Cancelled,
+    /// User wants to sample a color from a key on the board
+    SampleRequested,
 }
 
 /// RGB channel being edited
@@ -87,6 +92,8 @@ pub struct ColorPickerState {
     pub selected_shade: usize,
     /// Focus within palette mode
     pub palette_focus: PaletteFocus,
+    /// Which built-in palette is shown
+    pub palette_kind: PaletteKind,
     /// The color palette data
     pub palette: ColorPalette,
 }
@@ -105,6 +112,7 @@ impl ColorPickerState {
             selected_color: 0,
             selected_shade: 4, // Default to middle shade (500)
             palette_focus: PaletteFocus::Colors,
+            palette_kind: PaletteKind::default(),
             palette,
         }
     }
@@ -122,6 +130,7 @@ impl ColorPickerState {
             selected_color: 0,
             selected_shade: 4,
             palette_focus: PaletteFocus::Colors,
+            palette_kind: PaletteKind::default(),
             palette,
         };
         // Try to find matching color in palette
@@ -248,6 +257,16 @@ impl ColorPickerState {
         }
     }
 
+    /// Switch to the next built-in palette, selecting its first color
+    pub fn cycle_palette(&mut self) {
+        self.palette_kind = self.palette_kind.next();
+        self.palette = ColorPalette::for_kind(self.palette_kind);
+        self.selected_color = 0;
+        self.selected_shade = 4;
+        self.palette_focus = PaletteFocus::Colors;
+        self.sync_from_palette();
+    }
+
     /// Move focus between colors and shades
     pub const fn toggle_palette_focus(&mut self) {
         self.palette_focus = match self.palette_focus {
@@ -270,6 +289,8 @@ pub struct ColorPicker {
     state: ColorPickerState,
     /// Context - what is being colored
     context: ColorPickerContext,
+    /// Selected key to return to while the eyedropper is active
+    sample_origin: Option<Position>,
 }
 
 impl ColorPicker {
@@ -279,15 +300,46 @@ impl ColorPicker {
         Self {
             state: ColorPickerState::with_color(color),
             context,
+            sample_origin: None,
         }
     }
 
+    /// Replace the current color, e.g. with one sampled from a key
+    pub fn set_color(&mut self, color: RgbColor) {
+        self.state.r = color.r;
+        self.state.g = color.g;
+        self.state.b = color.b;
+        self.state.find_closest_palette_color(color);
+    }
+
+    /// Start the eyedropper, remembering the selected key to return to
+    pub const fn start_sampling(&mut self, origin: Position) {
+        self.sample_origin = Some(origin);
+    }
+
+    /// Stop the eyedropper, returning the key that was selected before it
+    pub const fn finish_sampling(&mut self) -> Option<Position> {
+        self.sample_origin.take()
+    }
+
+    /// Whether the eyedropper is active
+    #[must_use]
+    pub const fn is_sampling(&self) -> bool {
+        self.sample_origin.is_some()
+    }
+
     /// Get the context of what is being colored
     #[must_use]
     pub const fn get_context(&self) -> ColorPickerContext {
         self.context
     }
 
+    /// The color currently selected in the picker
+    #[must_use]
+    pub fn color(&self) -> RgbColor {
+        self.state.get_color()
+    }
+
     /// Get the current picker mode (Palette or CustomRgb)
     #[must_use]
     pub const fn get_mode(&self) -> ColorPickerMode {
@@ -325,14 +377,13 @@ impl Component for ColorPicker {
         }
     }
 
-    fn render(&self, f: &mut Frame, _area: Rect, theme: &Theme) {
+    fn render(&self, f: &mut Frame, area: Rect, theme: &Theme) {
         match self.state.mode {
-            ColorPickerMode::Palette => render_palette_mode_component(f, self, theme),
-            ColorPickerMode::CustomRgb => render_rgb_mode_component(f, self, theme),
+            ColorPickerMode::Palette => render_palette_mode_component(f, area, self, theme),
+            ColorPickerMode::CustomRgb => render_rgb_mode_component(f, area, self, theme),
         }
 
         // Border around everything
-        let area = centered_rect(70, 70, f.area());
         let block = Block::default()
             .borders(Borders::ALL)
             .border_style(Style::default().fg(theme.primary));
@@ -354,6 +405,7 @@ impl ColorPicker {
                     Some(ColorPickerEvent::ColorCleared)
                 }
             }
+            KeyCode::Char('e') => Some(ColorPickerEvent::SampleRequested),
             KeyCode::Char('c' | 'C') => {
                 // Switch to custom RGB mode
                 self.state.mode = ColorPickerMode::CustomRgb;
@@ -364,6 +416,10 @@ impl ColorPicker {
                 self.state.toggle_palette_focus();
                 None
             }
+            KeyCode::Char('p' | 'P') => {
+                self.state.cycle_palette();
+                None
+            }
             KeyCode::Up | KeyCode::Char('k') => {
                 match self.state.palette_focus {
                     PaletteFocus::Colors => self.state.navigate_palette(0, -1),
@@ -420,6 +476,7 @@ impl ColorPicker {
                     Some(ColorPickerEvent::ColorCleared)
                 }
             }
+            KeyCode::Char('e') => Some(ColorPickerEvent::SampleRequested),
             KeyCode::Char('p' | 'P') => {
                 // Switch to palette mode
                 self.state.mode = ColorPickerMode::Palette;
@@ -455,9 +512,7 @@ impl ColorPicker {
 }
 
 /// Render palette selection mode (for Component)
-fn render_palette_mode_component(f: &mut Frame, picker: &ColorPicker, theme: &Theme) {
-    let area = centered_rect(70, 70, f.area());
-
+fn render_palette_mode_component(f: &mut Frame, area: Rect, picker: &ColorPicker, theme: &Theme) {
     // Clear the background area first
     f.render_widget(Clear, area);
 
@@ -502,7 +557,11 @@ fn render_palette_mode_component(f: &mut Frame, picker: &ColorPicker, theme: &Th
     } else {
         Style::default().fg(theme.text_muted)
     };
-    let step1 = Paragraph::new("Step 1: Choose Base Color").style(step1_style);
+    let step1 = Paragraph::new(format!(
+        "Step 1: Choose Base Color ({})",
+        picker_state.palette_kind.label()
+    ))
+    .style(step1_style);
     f.render_widget(step1, chunks[1]);
 
     // Color grid (4 columns x 3 rows)
@@ -531,8 +590,12 @@ fn render_palette_mode_component(f: &mut Frame, picker: &ColorPicker, theme: &Th
         Span::raw(" Navigate  "),
         Span::styled("Tab", Style::default().fg(theme.accent)),
         Span::raw(" Switch Step  "),
+        Span::styled("p", Style::default().fg(theme.accent)),
+        Span::raw(" Palette  "),
         Span::styled("c", Style::default().fg(theme.accent)),
         Span::raw(" Custom RGB  "),
+        Span::styled("e", Style::default().fg(theme.accent)),
+        Span::raw(" Sample Key  "),
         Span::styled("x", Style::default().fg(theme.accent)),
         Span::raw(" Clear  "),
         Span::styled("Enter", Style::default().fg(theme.accent)),
@@ -540,7 +603,7 @@ fn render_palette_mode_component(f: &mut Frame, picker: &ColorPicker, theme: &Th
         Span::styled("Esc", Style::default().fg(theme.accent)),
         Span::raw(" Cancel"),
     ])];
-    let instructions_widget = Paragraph::new(instructions);
+    let instructions_widget = Paragraph::new(instructions).wrap(Wrap { trim: true });
     f.render_widget(instructions_widget, chunks[9]);
 }
 
@@ -730,9 +793,7 @@ fn render_preview_component(
 }
 
 /// Render custom RGB mode (for Component)
-fn render_rgb_mode_component(f: &mut Frame, picker: &ColorPicker, theme: &Theme) {
-    let area = centered_rect(70, 70, f.area());
-
+fn render_rgb_mode_component(f: &mut Frame, area: Rect, picker: &ColorPicker, theme: &Theme) {
     // Clear the background area first
     f.render_widget(Clear, area);
 
@@ -824,6 +885,8 @@ fn render_rgb_mode_component(f: &mut Frame, picker: &ColorPicker, theme: &Theme)
         Span::raw(" Channel  "),
         Span::styled("p", Style::default().fg(theme.accent)),
         Span::raw(" Palette  "),
+        Span::styled("e", Style::default().fg(theme.accent)),
+        Span::raw(" Sample Key  "),
         Span::styled("x", Style::default().fg(theme.accent)),
         Span::raw(" Clear  "),
         Span::styled("Enter", Style::default().fg(theme.accent)),
@@ -831,7 +894,7 @@ fn render_rgb_mode_component(f: &mut Frame, picker: &ColorPicker, theme: &Theme)
         Span::styled("Esc", Style::default().fg(theme.accent)),
         Span::raw(" Cancel"),
     ])];
-    let instructions_widget = Paragraph::new(instructions);
+    let instructions_widget = Paragraph::new(instructions).wrap(Wrap { trim: true });
     f.render_widget(instructions_widget, chunks[6]);
 }
 
@@ -863,23 +926,112 @@ fn render_channel_slider(
     f.render_widget(gauge, area);
 }
 
-/// Helper to create a centered rectangle
-fn centered_rect(percent_x: u16, percent_y: u16, r: Rect) -> Rect {
-    let popup_layout = Layout::default()
-        .direction(Direction::Vertical)
-        .constraints([
-            Constraint::Percentage((100 - percent_y) / 2),
-            Constraint::Percentage(percent_y),
-            Constraint::Percentage((100 - percent_y) / 2),
-        ])
-        .split(r);
+/// Render the minimized picker shown while the eyedropper is active: one
+/// hint line along the bottom of `screen`, leaving the board visible.
+pub fn render_sampling_hint(f: &mut Frame, screen: Rect, picker: &ColorPicker, theme: &Theme) {
+    if screen.height == 0 {
+        return;
+    }
+    let area = Rect::new(screen.x, screen.y + screen.height - 1, screen.width, 1);
+    let current = picker.color();
+    let hint = Line::from(vec![
+        Span::styled(
+            " Eyedropper ",
+            Style::default()
+                .fg(theme.primary)
+                .add_modifier(Modifier::BOLD),
+        ),
+        Span::styled(
+            "  ",
+            Style::default().bg(Color::Rgb(current.r, current.g, current.b)),
+        ),
+        Span::raw("  "),
+        Span::styled("←→↑↓", Style::default().fg(theme.accent)),
+        Span::raw(" Move  "),
+        Span::styled("Enter", Style::default().fg(theme.accent)),
+        Span::raw(" Sample  "),
+        Span::styled("Esc", Style::default().fg(theme.accent)),
+        Span::raw(" Back to picker"),
+    ]);
+    f.render_widget(Clear, area);
+    f.render_widget(
+        Paragraph::new(hint).style(Style::default().bg(theme.background)),
+        area,
+    );
+}
 
-    Layout::default()
-        .direction(Direction::Horizontal)
-        .constraints([
-            Constraint::Percentage((100 - percent_x) / 2),
-            Constraint::Percentage(percent_x),
-            Constraint::Percentage((100 - percent_x) / 2),
-        ])
-        .split(popup_layout[1])[1]
+/// Width of the picker popup
+const POPUP_WIDTH: u16 = 60;
+
+/// Height of the picker popup (palette mode needs all of it)
+const POPUP_HEIGHT: u16 = 28;
+
+/// Where to draw the picker within `screen`.
+///
+/// The popup goes in the corner farthest from `avoid` (the key being
+/// colored), preferring corners that don't cover it, so the live preview
+/// stays visible. Without a key to avoid, it is centered.
+#[must_use]
+pub fn popup_area(screen: Rect, avoid: Option<Rect>) -> Rect {
+    let width = POPUP_WIDTH.min(screen.width);
+    let height = POPUP_HEIGHT.min(screen.height);
+    let left = screen.x;
+    let right = screen.x + screen.width - width;
+    let top = screen.y;
+    let bottom = screen.y + screen.height - height;
+
+    let Some(avoid) = avoid else {
+        return Rect::new(
+            u16::midpoint(left, right),
+            u16::midpoint(top, bottom),
+            width,
+            height,
+        );
+    };
+
+    let center = |r: Rect| {
+        (
+            i32::from(r.x) * 2 + i32::from(r.width),
+            i32::from(r.y) * 2 + i32::from(r.height),
+        )
+    };
+    let (key_x, key_y) = center(avoid);
+    [(left, top), (right, top), (left, bottom), (right, bottom)]
+        .into_iter()
+        .map(|(x, y)| Rect::new(x, y, width, height))
+        .max_by_key(|corner| {
+            let (x, y) = center(*corner);
+            let distance = (x - key_x).pow(2) + (y - key_y).pow(2);
+            (!corner.intersects(avoid), distance)
+        })
+        .unwrap_or_default()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_popup_area_avoids_selected_key() {
+        let screen = Rect::new(0, 0, 160, 50);
+
+        // Key in the top-left: popup goes bottom-right
+        let area = popup_area(screen, Some(Rect::new(2, 4, 7, 4)));
+        assert_eq!(area, Rect::new(100, 22, 60, 28));
+
+        // Key in the bottom-right: popup goes top-left
+        let area = popup_area(screen, Some(Rect::new(150, 40, 7, 4)));
+        assert_eq!(area, Rect::new(0, 0, 60, 28));
+
+        // No key: centered
+        assert_eq!(popup_area(screen, None), Rect::new(50, 11, 60, 28));
+    }
+
+    #[test]
+    fn test_popup_area_fits_small_screens() {
+        let screen = Rect::new(0, 0, 50, 20);
+        let area = popup_area(screen, Some(Rect::new(0, 0, 7, 4)));
+        assert_eq!((area.width, area.height), (50, 20));
+        assert!(screen.contains(area.as_position()));
+    }
 }
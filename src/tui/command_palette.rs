@@ -0,0 +1,374 @@
+//! Command palette for searching and running any action.
+//!
+//! Lists every action from the shared action registry with its name and
+//! current key binding. Typing filters the list with fuzzy matching, and
+//! Enter runs the highlighted action exactly as its key binding would.
+
+use crossterm::event::{KeyCode, KeyEvent, KeyModifiers};
+use ratatui::{
+    layout::{Constraint, Direction, Layout as RatatuiLayout, Rect},
+    style::{Modifier, Style},
+    text::{Line, Span},
+    widgets::{Block, Borders, Clear, List, ListItem, ListState, Paragraph},
+    Frame,
+};
+
+use crate::shortcuts::{Action, ShortcutRegistry};
+use crate::tui::handlers::action_handlers::registry::registered_actions;
+use crate::tui::help_registry::HelpRegistry;
+use crate::tui::text_input::TextInput;
+
+/// A single command shown in the palette
+#[derive(Debug, Clone)]
+pub struct PaletteEntry {
+    /// Action executed when the entry is chosen
+    pub action: Action,
+    /// Human-readable command name
+    pub name: String,
+    /// One-line description
+    pub description: String,
+    /// Current key bindings, formatted for display (e.g. "Ctrl+P / :")
+    pub keys: String,
+}
+
+/// Events emitted by the CommandPalette component
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum CommandPaletteEvent {
+    /// User chose an action to execute
+    Execute(Action),
+    /// User closed the palette
+    Cancelled,
+}
+
+/// CommandPalette component that implements the Component trait
+#[derive(Debug, Clone)]
+pub struct CommandPalette {
+    /// All commands, in registry order
+    entries: Vec<PaletteEntry>,
+    /// Current filter text
+    query: TextInput,
+    /// Indices into `entries` matching the query, best match first
+    filtered: Vec<usize>,
+    /// Selected index within `filtered`
+    selected: usize,
+}
+
+impl CommandPalette {
+    /// Create a new CommandPalette listing every registered action.
+    #[must_use]
+    pub fn new() -> Self {
+        let entries = registered_actions(&ShortcutRegistry::new(), &HelpRegistry::default())
+            .into_iter()
+            .filter(|a| a.action != Action::OpenCommandPalette)
+            .map(|a| PaletteEntry {
+                action: a.action,
+                name: a.name,
+                description: a.description,
+                keys: a.keys.join(" / "),
+            })
+            .collect();
+        Self::with_entries(entries)
+    }
+
+    /// Create a palette from an explicit list of entries.
+    #[must_use]
+    pub fn with_entries(entries: Vec<PaletteEntry>) -> Self {
+        let mut palette = Self {
+            entries,
+            query: TextInput::default(),
+            filtered: Vec::new(),
+            selected: 0,
+        };
+        palette.refilter();
+        palette
+    }
+
+    /// Currently highlighted entry (if any).
+    #[must_use]
+    pub fn selected_entry(&self) -> Option<&PaletteEntry> {
+        self.filtered
+            .get(self.selected)
+            .and_then(|&i| self.entries.get(i))
+    }
+
+    /// Recompute the filtered list after the query changes.
+    fn refilter(&mut self) {
+        let mut scored: Vec<(usize, i32)> = self
+            .entries
+            .iter()
+            .enumerate()
+            .filter_map(|(i, entry)| {
+                let query = self.query.value();
+                if query.is_empty() {
+                    return Some((i, 0));
+                }
+                let name_score = fuzzy_score(query, &entry.name);
+                // Description matches rank below any name match
+                let desc_score = fuzzy_score(query, &entry.description).map(|s| s - 1000);
+                name_score.max(desc_score).map(|s| (i, s))
+            })
+            .collect();
+
+        // Stable sort keeps registry order for equal scores
+        scored.sort_by_key(|&(_, score)| std::cmp::Reverse(score));
+        self.filtered = scored.into_iter().map(|(i, _)| i).collect();
+        self.selected = 0;
+    }
+
+    /// Moves selection up.
+    const fn select_previous(&mut self) {
+        self.selected = self.selected.saturating_sub(1);
+    }
+
+    /// Moves selection down.
+    const fn select_next(&mut self) {
+        if self.selected + 1 < self.filtered.len() {
+            self.selected += 1;
+        }
+    }
+}
+
+impl Default for CommandPalette {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+/// Scores `text` against `query` as a case-insensitive subsequence match.
+///
+/// Returns `None` if the query characters do not all appear in order.
+/// Consecutive matches and matches at word starts score higher.
+fn fuzzy_score(query: &str, text: &str) -> Option<i32> {
+    let text: Vec<char> = text.to_lowercase().chars().collect();
+    let mut score = 0;
+    let mut pos = 0;
+    let mut prev_match: Option<usize> = None;
+
+    for qc in query.to_lowercase().chars().filter(|c| !c.is_whitespace()) {
+        let found = text[pos..].iter().position(|&c| c == qc)? + pos;
+        score += 1;
+        if prev_match.is_some_and(|p| p + 1 == found) {
+            score += 5;
+        }
+        if found == 0 || !text[found - 1].is_alphanumeric() {
+            score += 10;
+        }
+        prev_match = Some(found);
+        pos = found + 1;
+    }
+
+    Some(score)
+}
+
+impl crate::tui::component::Component for CommandPalette {
+    type Event = CommandPaletteEvent;
+
+    fn handle_input(&mut self, key: KeyEvent) -> Option<Self::Event> {
+        let ctrl = key.modifiers.contains(KeyModifiers::CONTROL);
+        match key.code {
+            KeyCode::Esc => Some(CommandPaletteEvent::Cancelled),
+            KeyCode::Enter => self
+                .selected_entry()
+                .map(|e| CommandPaletteEvent::Execute(e.action)),
+            KeyCode::Up => {
+                self.select_previous();
+                None
+            }
+            KeyCode::Char('p') if ctrl => {
+                self.select_previous();
+                None
+            }
+            KeyCode::Down => {
+                self.select_next();
+                None
+            }
+            KeyCode::Char('n') if ctrl => {
+                self.select_next();
+                None
+            }
+            _ => {
+                if self.query.handle_key(key) {
+                    self.refilter();
+                }
+                None
+            }
+        }
+    }
+
+    fn render(&self, f: &mut Frame, area: Rect, theme: &crate::tui::theme::Theme) {
+        render_command_palette(f, self, area, theme);
+    }
+}
+
+/// Renders the command palette popup
+fn render_command_palette(
+    f: &mut Frame,
+    palette: &CommandPalette,
+    area: Rect,
+    theme: &crate::tui::theme::Theme,
+) {
+    let popup_area = centered_rect(60, 60, area);
+
+    f.render_widget(Clear, popup_area);
+    let background = Block::default().style(Style::default().bg(theme.background));
+    f.render_widget(background, popup_area);
+
+    let chunks = RatatuiLayout::default()
+        .direction(Direction::Vertical)
+        .constraints([
+            Constraint::Length(3), // Query input
+            Constraint::Min(3),    // Command list
+            Constraint::Length(3), // Description
+        ])
+        .split(popup_area);
+
+    let query_style = Style::default().fg(theme.accent);
+    let mut query_spans = vec![Span::raw("> ")];
+    query_spans.extend(palette.query.spans(query_style, true));
+    let input = Paragraph::new(Line::from(query_spans))
+        .style(query_style)
+        .block(
+            Block::default()
+                .borders(Borders::ALL)
+                .title(" Command Palette ")
+                .style(Style::default().fg(theme.primary)),
+        );
+    f.render_widget(input, chunks[0]);
+
+    let items: Vec<ListItem> = palette
+        .filtered
+        .iter()
+        .filter_map(|&i| palette.entries.get(i))
+        .map(|entry| {
+            ListItem::new(Line::from(vec![
+                Span::styled(entry.name.clone(), Style::default().fg(theme.text)),
+                Span::styled(
+                    if entry.keys.is_empty() {
+                        String::new()
+                    } else {
+                        format!("  {}", entry.keys)
+                    },
+                    Style::default().fg(theme.text_muted),
+                ),
+            ]))
+        })
+        .collect();
+
+    let list_title = format!(" Commands ({}) ", palette.filtered.len());
+    let list = List::new(items)
+        .block(Block::default().borders(Borders::ALL).title(list_title))
+        .highlight_style(
+            theme.selection(
+                Style::default()
+                    .fg(theme.background)
+                    .bg(theme.primary)
+                    .add_modifier(Modifier::BOLD),
+            ),
+        );
+    let mut list_state = ListState::default();
+    if !palette.filtered.is_empty() {
+        list_state.select(Some(palette.selected));
+    }
+    f.render_stateful_widget(list, chunks[1], &mut list_state);
+
+    let description = palette
+        .selected_entry()
+        .map_or(String::new(), |e| e.description.clone());
+    let details = Paragraph::new(description)
+        .style(Style::default().fg(theme.text_muted))
+        .block(Block::default().borders(Borders::ALL));
+    f.render_widget(details, chunks[2]);
+}
+
+/// Helper to create a centered rectangle
+fn centered_rect(percent_x: u16, percent_y: u16, r: Rect) -> Rect {
+    let popup_layout = RatatuiLayout::default()
+        .direction(Direction::Vertical)
+        .constraints([
+            Constraint::Percentage((100 - percent_y) / 2),
+            Constraint::Percentage(percent_y),
+            Constraint::Percentage((100 - percent_y) / 2),
+        ])
+        .split(r);
+
+    RatatuiLayout::default()
+        .direction(Direction::Horizontal)
+        .constraints([
+            Constraint::Percentage((100 - percent_x) / 2),
+            Constraint::Percentage(percent_x),
+            Constraint::Percentage((100 - percent_x) / 2),
+        ])
+        .split(popup_layout[1])[1]
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::tui::component::Component;
+
+    fn key(code: KeyCode) -> KeyEvent {
+        KeyEvent::new(code, KeyModifiers::NONE)
+    }
+
+    fn type_query(palette: &mut CommandPalette, query: &str) {
+        for c in query.chars() {
+            palette.handle_input(key(KeyCode::Char(c)));
+        }
+    }
+
+    #[test]
+    fn test_fuzzy_score() {
+        assert!(fuzzy_score("sv", "Save layout").is_some());
+        assert!(fuzzy_score("xyz", "Save layout").is_none());
+        assert!(fuzzy_score("save", "Save layout") > fuzzy_score("save", "Browse snapshots, ave"));
+        assert_eq!(fuzzy_score("", "anything"), Some(0));
+    }
+
+    #[test]
+    fn test_palette_lists_registry_actions() {
+        let palette = CommandPalette::new();
+        assert!(palette.entries.iter().any(|e| e.action == Action::Save));
+        assert!(!palette
+            .entries
+            .iter()
+            .any(|e| e.action == Action::OpenCommandPalette));
+
+        let save = palette
+            .entries
+            .iter()
+            .find(|e| e.action == Action::Save)
+            .unwrap();
+        assert_eq!(save.keys, "Ctrl+S");
+    }
+
+    #[test]
+    fn test_palette_filter_and_execute() {
+        let mut palette = CommandPalette::new();
+        type_query(&mut palette, "save layout");
+        assert_eq!(
+            palette.handle_input(key(KeyCode::Enter)),
+            Some(CommandPaletteEvent::Execute(Action::Save))
+        );
+
+        type_query(&mut palette, "zzzzqqq");
+        assert!(palette.selected_entry().is_none());
+        assert_eq!(palette.handle_input(key(KeyCode::Enter)), None);
+
+        assert_eq!(
+            palette.handle_input(key(KeyCode::Esc)),
+            Some(CommandPaletteEvent::Cancelled)
+        );
+    }
+
+    #[test]
+    fn test_palette_navigation_clamps() {
+        let mut palette = CommandPalette::new();
+        palette.handle_input(key(KeyCode::Up));
+        assert_eq!(palette.selected, 0);
+
+        for _ in 0..palette.entries.len() + 5 {
+            palette.handle_input(key(KeyCode::Down));
+        }
+        assert_eq!(palette.selected, palette.filtered.len() - 1);
+    }
+}
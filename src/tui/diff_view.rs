@@ -0,0 +1,134 @@
+//! Diff mode for the keyboard widget.
+//!
+//! Compares the layout being edited against a snapshot or the saved file
+//! using the same [`LayoutDiff`] as the snapshot browser, so both agree on
+//! what counts as a change.
+
+use std::collections::HashMap;
+
+use crate::models::{Layout, Position};
+use crate::services::snapshots::{KeyChange, LayoutDiff};
+
+/// Differences between the edited layout and the one it is compared against.
+#[derive(Debug, Clone)]
+pub struct DiffView {
+    /// What the layout is compared against (e.g. "saved file")
+    pub label: String,
+    /// Differences going from the compared layout to the edited one
+    pub diff: LayoutDiff,
+    /// Index into `diff.key_changes` by layer and position
+    by_key: HashMap<(usize, Position), usize>,
+}
+
+impl DiffView {
+    /// Compares `current` against `base`, labelled `label` in the UI.
+    #[must_use]
+    pub fn new(label: impl Into<String>, base: &Layout, current: &Layout) -> Self {
+        let diff = LayoutDiff::between(base, current);
+        let by_key = diff
+            .key_changes
+            .iter()
+            .enumerate()
+            .map(|(i, change)| ((change.layer, change.position), i))
+            .collect();
+        Self {
+            label: label.into(),
+            diff,
+            by_key,
+        }
+    }
+
+    /// The change of the key at `position` on `layer`, if it changed.
+    #[must_use]
+    pub fn change(&self, layer: usize, position: Position) -> Option<&KeyChange> {
+        self.by_key
+            .get(&(layer, position))
+            .and_then(|&i| self.diff.key_changes.get(i))
+    }
+
+    /// Indices of layers with changed keys, in order.
+    #[must_use]
+    pub fn changed_layers(&self) -> Vec<usize> {
+        self.diff.changes_by_layer().into_keys().collect()
+    }
+
+    /// The next (or previous) layer with changed keys after `current`,
+    /// wrapping around.
+    #[must_use]
+    pub fn cycle_layer(&self, current: usize, forward: bool) -> Option<usize> {
+        let layers = self.changed_layers();
+        if forward {
+            layers
+                .iter()
+                .find(|&&layer| layer > current)
+                .or_else(|| layers.first())
+                .copied()
+        } else {
+            layers
+                .iter()
+                .rev()
+                .find(|&&layer| layer < current)
+                .or_else(|| layers.last())
+                .copied()
+        }
+    }
+
+    /// One-line summary, e.g. "layer 2: 7 changed, layer 4: 1 changed".
+    #[must_use]
+    pub fn summary(&self) -> String {
+        self.diff.layer_summary()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::models::{KeyDefinition, Layer, RgbColor};
+
+    fn create_layout(layers: usize) -> Layout {
+        let mut layout = Layout::new("Diff Test").unwrap();
+        for i in 0..layers {
+            let mut layer = Layer::new(i as u8, "Layer", RgbColor::new(0, 0, 0)).unwrap();
+            for col in 0..3 {
+                layer
+                    .add_key(KeyDefinition::new(Position::new(0, col), "KC_TRNS"))
+                    .unwrap();
+            }
+            layout.add_layer(layer).unwrap();
+        }
+        layout
+    }
+
+    #[test]
+    fn test_diff_view_indexes_changes() {
+        let base = create_layout(3);
+        let mut current = base.clone();
+        current.layers[1].keys[2].keycode = "KC_A".to_string();
+
+        let view = DiffView::new("saved file", &base, &current);
+        let change = view.change(1, Position::new(0, 2)).unwrap();
+        assert_eq!(change.old_keycode.as_deref(), Some("KC_TRNS"));
+        assert_eq!(change.new_keycode.as_deref(), Some("KC_A"));
+        assert!(view.change(0, Position::new(0, 2)).is_none());
+        assert_eq!(view.summary(), "layer 1: 1 changed");
+    }
+
+    #[test]
+    fn test_cycle_layer_wraps_around_changed_layers() {
+        let base = create_layout(4);
+        let mut current = base.clone();
+        current.layers[1].keys[0].keycode = "KC_A".to_string();
+        current.layers[3].keys[0].keycode = "KC_B".to_string();
+
+        let view = DiffView::new("snapshot", &base, &current);
+        assert_eq!(view.changed_layers(), vec![1, 3]);
+        assert_eq!(view.cycle_layer(0, true), Some(1));
+        assert_eq!(view.cycle_layer(1, true), Some(3));
+        assert_eq!(view.cycle_layer(3, true), Some(1));
+        assert_eq!(view.cycle_layer(1, false), Some(3));
+        assert_eq!(view.cycle_layer(2, false), Some(1));
+
+        let unchanged = DiffView::new("snapshot", &base, &base);
+        assert_eq!(unchanged.cycle_layer(0, true), None);
+    }
+}
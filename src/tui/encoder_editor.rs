@@ -0,0 +1,222 @@
+//! Encoder editor popup.
+//!
+//! Edits the keycodes a rotary encoder sends on the current layer. Each
+//! direction is picked with the keycode picker; the layout only changes when
+//! the editor is saved.
+
+use crossterm::event::{KeyCode, KeyEvent, KeyModifiers};
+use ratatui::{
+    layout::Rect,
+    style::{Modifier, Style},
+    text::{Line, Span},
+    widgets::{Block, BorderType, Borders, Clear, Paragraph},
+    Frame,
+};
+
+use super::Theme;
+use crate::models::EncoderMapping;
+
+/// Turn direction being edited
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum EncoderField {
+    /// Counter-clockwise turn
+    Ccw,
+    /// Clockwise turn
+    Cw,
+}
+
+impl EncoderField {
+    /// The other direction.
+    const fn toggled(self) -> Self {
+        match self {
+            Self::Ccw => Self::Cw,
+            Self::Cw => Self::Ccw,
+        }
+    }
+
+    /// Human-readable direction name.
+    #[must_use]
+    pub const fn label(self) -> &'static str {
+        match self {
+            Self::Ccw => "counter-clockwise",
+            Self::Cw => "clockwise",
+        }
+    }
+}
+
+/// Events emitted by the EncoderEditor component
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum EncoderEditorEvent {
+    /// User wants to pick the keycode for a direction
+    Pick(EncoderField),
+    /// User saved the mapping
+    Saved(EncoderMapping),
+    /// User closed the editor without saving
+    Cancelled,
+}
+
+/// EncoderEditor component that implements the Component trait
+#[derive(Debug, Clone)]
+pub struct EncoderEditor {
+    /// Index of the edited encoder
+    index: u8,
+    /// Mapping being edited
+    mapping: EncoderMapping,
+    /// Selected direction
+    field: EncoderField,
+}
+
+impl EncoderEditor {
+    /// Create an editor for encoder `index`, starting from its current
+    /// mapping.
+    #[must_use]
+    pub const fn new(index: u8, mapping: EncoderMapping) -> Self {
+        Self {
+            index,
+            mapping,
+            field: EncoderField::Ccw,
+        }
+    }
+
+    /// Index of the edited encoder.
+    #[must_use]
+    pub const fn index(&self) -> u8 {
+        self.index
+    }
+
+    /// Selected direction, the one a picked keycode goes to.
+    #[must_use]
+    pub const fn field(&self) -> EncoderField {
+        self.field
+    }
+
+    /// Sets the keycode of a direction.
+    pub fn set_keycode(&mut self, field: EncoderField, keycode: String) {
+        match field {
+            EncoderField::Ccw => self.mapping.ccw = keycode,
+            EncoderField::Cw => self.mapping.cw = keycode,
+        }
+    }
+}
+
+impl crate::tui::component::Component for EncoderEditor {
+    type Event = EncoderEditorEvent;
+
+    fn handle_input(&mut self, key: KeyEvent) -> Option<Self::Event> {
+        match key.code {
+            KeyCode::Up
+            | KeyCode::Down
+            | KeyCode::Char('k' | 'j')
+            | KeyCode::Tab
+            | KeyCode::BackTab => self.field = self.field.toggled(),
+            KeyCode::Enter => return Some(EncoderEditorEvent::Pick(self.field)),
+            KeyCode::Backspace | KeyCode::Delete => {
+                self.set_keycode(self.field, "KC_TRNS".to_string());
+            }
+            KeyCode::Char('s') if key.modifiers.contains(KeyModifiers::CONTROL) => {
+                return Some(EncoderEditorEvent::Saved(self.mapping.clone()));
+            }
+            KeyCode::Esc | KeyCode::Char('q') => return Some(EncoderEditorEvent::Cancelled),
+            _ => {}
+        }
+        None
+    }
+
+    fn render(&self, f: &mut Frame, area: Rect, theme: &Theme) {
+        let width = 44.min(area.width);
+        let height = 8.min(area.height);
+        let popup = Rect {
+            x: area.x + (area.width - width) / 2,
+            y: area.y + (area.height - height) / 2,
+            width,
+            height,
+        };
+
+        f.render_widget(Clear, popup);
+        let block = Block::default()
+            .borders(Borders::ALL)
+            .border_type(BorderType::Rounded)
+            .title(format!(" Encoder {} ", self.index))
+            .border_style(Style::default().fg(theme.primary))
+            .style(Style::default().bg(theme.background));
+
+        let row = |field: EncoderField, arrow: &'static str, keycode: &str| {
+            let selected = self.field == field;
+            let style = if selected {
+                Style::default()
+                    .fg(theme.accent)
+                    .add_modifier(Modifier::BOLD)
+            } else {
+                Style::default().fg(theme.text)
+            };
+            Line::from(vec![
+                Span::styled(if selected { " ▶ " } else { "   " }, style),
+                Span::styled(format!("{arrow} {:<4}", short_direction(field)), style),
+                Span::styled(keycode.to_string(), style),
+            ])
+        };
+        let lines = vec![
+            row(EncoderField::Ccw, "↺", &self.mapping.ccw),
+            row(EncoderField::Cw, "↻", &self.mapping.cw),
+            Line::from(""),
+            Line::from(Span::styled(
+                " Enter: Pick  Bksp: Clear  Ctrl+S: Save",
+                Style::default()
+                    .fg(theme.text_muted)
+                    .add_modifier(Modifier::DIM),
+            )),
+            Line::from(Span::styled(
+                " Esc: Cancel",
+                Style::default()
+                    .fg(theme.text_muted)
+                    .add_modifier(Modifier::DIM),
+            )),
+        ];
+        f.render_widget(Paragraph::new(lines).block(block), popup);
+    }
+}
+
+/// Column label of a direction.
+const fn short_direction(field: EncoderField) -> &'static str {
+    match field {
+        EncoderField::Ccw => "CCW",
+        EncoderField::Cw => "CW",
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::tui::component::Component;
+
+    fn press(editor: &mut EncoderEditor, code: KeyCode) -> Option<EncoderEditorEvent> {
+        editor.handle_input(KeyEvent::new(code, KeyModifiers::NONE))
+    }
+
+    #[test]
+    fn test_pick_clear_and_save() {
+        let mut editor = EncoderEditor::new(0, EncoderMapping::new("KC_VOLD", "KC_VOLU"));
+        assert_eq!(
+            press(&mut editor, KeyCode::Enter),
+            Some(EncoderEditorEvent::Pick(EncoderField::Ccw))
+        );
+
+        press(&mut editor, KeyCode::Down);
+        assert_eq!(editor.field(), EncoderField::Cw);
+        editor.set_keycode(editor.field(), "KC_MNXT".to_string());
+        press(&mut editor, KeyCode::Up);
+        press(&mut editor, KeyCode::Backspace);
+
+        let saved = editor.handle_input(KeyEvent::new(KeyCode::Char('s'), KeyModifiers::CONTROL));
+        assert_eq!(
+            saved,
+            Some(EncoderEditorEvent::Saved(EncoderMapping::new(
+                "KC_TRNS", "KC_MNXT"
+            )))
+        );
+        assert_eq!(
+            press(&mut editor, KeyCode::Esc),
+            Some(EncoderEditorEvent::Cancelled)
+        );
+    }
+}
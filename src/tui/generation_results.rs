@@ -0,0 +1,499 @@
+//! Firmware generation results popup.
+//!
+//! Shown after Ctrl+G: every generated file with its path, size and whether
+//! the QMK keymap directory changed. When a write fails, the files written
+//! before it are still listed, with the failed file and the ones left
+//! behind, so it is clear which files in the keymap directory are stale.
+
+use std::path::{Path, PathBuf};
+
+use ratatui::{
+    layout::{Constraint, Rect},
+    style::{Modifier, Style},
+    text::{Line, Span},
+    widgets::{Block, Borders, Cell, Clear, Paragraph, Row, Table, TableState},
+    Frame,
+};
+
+use super::Theme;
+use crate::firmware::{FileStatus, GenerationError, GenerationOutcome};
+
+/// Events emitted by the GenerationResultsView component
+#[derive(Debug, Clone)]
+pub enum GenerationResultsEvent {
+    /// User closed the popup
+    Closed,
+    /// Open a directory in the system file manager
+    OpenDirectory(PathBuf),
+    /// Copy a path to the clipboard
+    CopyPath(PathBuf),
+}
+
+/// How generation ended
+#[derive(Debug, Clone)]
+pub enum GenerationResult {
+    /// Files were written, possibly stopping at a failed one
+    Written(GenerationOutcome),
+    /// Generation failed before any file was written
+    Failed {
+        /// Error messages, outermost first
+        messages: Vec<String>,
+    },
+}
+
+impl GenerationResult {
+    /// Builds the result for a generation error, keeping the files written
+    /// before a failed write.
+    #[must_use]
+    pub fn from_error(error: &anyhow::Error) -> Self {
+        if let Some(partial) = error.downcast_ref::<GenerationError>() {
+            return Self::Written(partial.outcome.clone());
+        }
+        Self::Failed {
+            messages: error.chain().map(ToString::to_string).collect(),
+        }
+    }
+}
+
+/// GenerationResultsView component that implements the Component trait
+#[derive(Debug, Clone)]
+pub struct GenerationResultsView {
+    /// Generated files or the error
+    result: GenerationResult,
+    /// Validation warnings reported before generating
+    warnings: usize,
+    /// Whether the layout had unsaved changes, so the layout file on disk
+    /// doesn't match the generated files
+    unsaved_changes: bool,
+    /// Selected file row
+    selected: usize,
+}
+
+impl GenerationResultsView {
+    /// Create a view of a generation result, selecting the failed file if
+    /// a write failed.
+    #[must_use]
+    pub fn new(result: GenerationResult, warnings: usize) -> Self {
+        let selected = match &result {
+            GenerationResult::Written(outcome) => outcome
+                .files
+                .iter()
+                .position(|file| file.status == FileStatus::Failed)
+                .unwrap_or(0),
+            GenerationResult::Failed { .. } => 0,
+        };
+        Self {
+            result,
+            warnings,
+            unsaved_changes: false,
+            selected,
+        }
+    }
+
+    /// Notes that the layout had unsaved changes when generating.
+    #[must_use]
+    pub const fn with_unsaved_changes(mut self) -> Self {
+        self.unsaved_changes = true;
+        self
+    }
+
+    /// Whether every file was generated.
+    #[must_use]
+    pub fn succeeded(&self) -> bool {
+        matches!(&self.result, GenerationResult::Written(outcome) if outcome.is_complete())
+    }
+
+    /// One-line summary for the status bar.
+    #[must_use]
+    pub fn summary(&self) -> String {
+        match &self.result {
+            GenerationResult::Written(outcome) => {
+                if let Some(error) = outcome.failed().and_then(|file| file.error.as_ref()) {
+                    return format!(
+                        "Generation failed writing {}: {} - not updated: {}",
+                        error.path.display(),
+                        error.message,
+                        outcome.stale_files().join(", ")
+                    );
+                }
+                let written = outcome
+                    .files
+                    .iter()
+                    .filter(|file| file.status == FileStatus::Written)
+                    .count();
+                let warnings = match self.warnings {
+                    0 => String::new(),
+                    1 => ", 1 warning".to_string(),
+                    n => format!(", {n} warnings"),
+                };
+                let unsaved = if self.unsaved_changes {
+                    " - layout file not saved"
+                } else {
+                    ""
+                };
+                format!(
+                    "✓ Generated {} files ({written} written){warnings}{unsaved}",
+                    outcome.files.len()
+                )
+            }
+            GenerationResult::Failed { messages } => {
+                let message = messages.first().map_or("unknown error", String::as_str);
+                format!("Generation failed: {message}")
+            }
+        }
+    }
+
+    /// Path of the selected file; for the failed file, the path that could
+    /// not be written.
+    #[must_use]
+    pub fn selected_path(&self) -> Option<&Path> {
+        match &self.result {
+            GenerationResult::Written(outcome) => outcome.files.get(self.selected).map(|file| {
+                file.error
+                    .as_ref()
+                    .map_or(file.keymap_path.as_path(), |error| error.path.as_path())
+            }),
+            GenerationResult::Failed { .. } => None,
+        }
+    }
+
+    /// Number of rows that can be selected.
+    fn row_count(&self) -> usize {
+        match &self.result {
+            GenerationResult::Written(outcome) => outcome.files.len(),
+            GenerationResult::Failed { .. } => 0,
+        }
+    }
+}
+
+impl crate::tui::component::Component for GenerationResultsView {
+    type Event = GenerationResultsEvent;
+
+    fn handle_input(&mut self, key: crossterm::event::KeyEvent) -> Option<Self::Event> {
+        use crossterm::event::KeyCode;
+
+        match key.code {
+            KeyCode::Esc | KeyCode::Enter | KeyCode::Char('q') => {
+                Some(GenerationResultsEvent::Closed)
+            }
+            KeyCode::Up | KeyCode::Char('k') => {
+                self.selected = self.selected.saturating_sub(1);
+                None
+            }
+            KeyCode::Down | KeyCode::Char('j') => {
+                self.selected = (self.selected + 1).min(self.row_count().saturating_sub(1));
+                None
+            }
+            KeyCode::Char('o') => self
+                .selected_path()
+                .and_then(Path::parent)
+                .map(|dir| GenerationResultsEvent::OpenDirectory(dir.to_path_buf())),
+            KeyCode::Char('a') => match &self.result {
+                GenerationResult::Written(outcome) => Some(GenerationResultsEvent::OpenDirectory(
+                    outcome.archive_dir.clone(),
+                )),
+                GenerationResult::Failed { .. } => None,
+            },
+            KeyCode::Char('y') => self
+                .selected_path()
+                .map(|path| GenerationResultsEvent::CopyPath(path.to_path_buf())),
+            _ => None,
+        }
+    }
+
+    fn render(&self, f: &mut Frame, area: Rect, theme: &Theme) {
+        render_generation_results(f, self, area, theme);
+    }
+}
+
+/// Formats a byte count for display.
+fn format_size(bytes: usize) -> String {
+    if bytes < 1024 {
+        format!("{bytes} B")
+    } else {
+        #[allow(clippy::cast_precision_loss)]
+        let kib = bytes as f64 / 1024.0;
+        format!("{kib:.1} KiB")
+    }
+}
+
+/// Renders the generation results popup
+fn render_generation_results(
+    f: &mut Frame,
+    view: &GenerationResultsView,
+    area: Rect,
+    theme: &Theme,
+) {
+    let area = centered_rect(80, 60, area);
+
+    f.render_widget(Clear, area);
+    let background = Block::default().style(Style::default().bg(theme.background));
+    f.render_widget(background, area);
+
+    let (title, border_color) = if view.succeeded() {
+        (" Firmware Generated ", theme.success)
+    } else {
+        (" Firmware Generation Failed ", theme.error)
+    };
+    let block = Block::default()
+        .title(title)
+        .borders(Borders::ALL)
+        .border_style(Style::default().fg(border_color));
+    let inner = block.inner(area);
+    f.render_widget(block, area);
+
+    let help_text = match &view.result {
+        GenerationResult::Written(_) => {
+            "↑↓: Select | o: Open folder | a: Open archive | y: Copy path | Esc: Close"
+        }
+        GenerationResult::Failed { .. } => "Esc: Close",
+    };
+    let [body, help] =
+        ratatui::layout::Layout::vertical([Constraint::Min(1), Constraint::Length(1)]).areas(inner);
+
+    match &view.result {
+        GenerationResult::Written(outcome) => render_files(f, view, outcome, body, theme),
+        GenerationResult::Failed { messages } => {
+            let lines: Vec<Line> = messages
+                .iter()
+                .map(|message| {
+                    Line::from(Span::styled(
+                        message.clone(),
+                        Style::default().fg(theme.error),
+                    ))
+                })
+                .collect();
+            f.render_widget(
+                Paragraph::new(lines).wrap(ratatui::widgets::Wrap { trim: false }),
+                body,
+            );
+        }
+    }
+
+    f.render_widget(
+        Paragraph::new(help_text).style(
+            Style::default()
+                .fg(theme.text_muted)
+                .add_modifier(Modifier::DIM),
+        ),
+        help,
+    );
+}
+
+/// Renders the generated files table with the archive directory, warning
+/// count and any write error above it
+fn render_files(
+    f: &mut Frame,
+    view: &GenerationResultsView,
+    outcome: &GenerationOutcome,
+    area: Rect,
+    theme: &Theme,
+) {
+    let write_error = outcome.failed().and_then(|file| file.error.as_ref());
+    let header_height = 3 + u16::from(view.unsaved_changes) + u16::from(write_error.is_some());
+    let [header, table_area] =
+        ratatui::layout::Layout::vertical([Constraint::Length(header_height), Constraint::Min(1)])
+            .areas(area);
+
+    let warnings_style = if view.warnings == 0 {
+        Style::default().fg(theme.text_muted)
+    } else {
+        Style::default().fg(theme.warning)
+    };
+    let mut header_lines = vec![
+        Line::from(vec![
+            Span::styled("Keymap:  ", Style::default().fg(theme.text_muted)),
+            Span::raw(outcome.keymap_dir.display().to_string()),
+        ]),
+        Line::from(vec![
+            Span::styled("Archive: ", Style::default().fg(theme.text_muted)),
+            Span::raw(outcome.archive_dir.display().to_string()),
+        ]),
+        Line::from(Span::styled(
+            format!("Validation warnings: {}", view.warnings),
+            warnings_style,
+        )),
+    ];
+    if view.unsaved_changes {
+        header_lines.push(Line::from(Span::styled(
+            "Generated from unsaved changes - the layout file on disk is stale (Ctrl+S saves)",
+            Style::default().fg(theme.warning),
+        )));
+    }
+    if let Some(error) = write_error {
+        header_lines.push(Line::from(Span::styled(
+            format!("{error} - files marked not written keep their previous copy"),
+            Style::default().fg(theme.error),
+        )));
+    }
+    f.render_widget(Paragraph::new(header_lines), header);
+
+    let rows = outcome.files.iter().map(|file| {
+        let status_color = match file.status {
+            FileStatus::Written => theme.success,
+            FileStatus::Unchanged => theme.text_muted,
+            FileStatus::HandWritten | FileStatus::NotWritten => theme.warning,
+            FileStatus::Failed => theme.error,
+        };
+        Row::new(vec![
+            Cell::from(file.name),
+            Cell::from(format_size(file.size)),
+            Cell::from(file.status.label()).style(Style::default().fg(status_color)),
+            Cell::from(file.keymap_path.display().to_string()),
+        ])
+    });
+    let table = Table::new(
+        rows,
+        [
+            Constraint::Length(10),
+            Constraint::Length(10),
+            Constraint::Length(18),
+            Constraint::Min(10),
+        ],
+    )
+    .header(
+        Row::new(vec!["File", "Size", "Status", "Path"]).style(
+            Style::default()
+                .fg(theme.primary)
+                .add_modifier(Modifier::BOLD),
+        ),
+    )
+    .row_highlight_style(theme.selection(Style::default().bg(theme.surface)));
+
+    let mut state = TableState::default().with_selected(Some(view.selected));
+    f.render_stateful_widget(table, table_area, &mut state);
+}
+
+/// Helper to create a centered rectangle.
+fn centered_rect(percent_x: u16, percent_y: u16, r: Rect) -> Rect {
+    use ratatui::layout::{Direction, Layout};
+
+    let popup_layout = Layout::default()
+        .direction(Direction::Vertical)
+        .constraints([
+            Constraint::Percentage((100 - percent_y) / 2),
+            Constraint::Percentage(percent_y),
+            Constraint::Percentage((100 - percent_y) / 2),
+        ])
+        .split(r);
+
+    Layout::default()
+        .direction(Direction::Horizontal)
+        .constraints([
+            Constraint::Percentage((100 - percent_x) / 2),
+            Constraint::Percentage(percent_x),
+            Constraint::Percentage((100 - percent_x) / 2),
+        ])
+        .split(popup_layout[1])[1]
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::firmware::generator::{FileWriteError, GeneratedFile};
+    use crate::tui::component::Component;
+    use crossterm::event::{KeyCode, KeyEvent, KeyModifiers};
+
+    fn press(view: &mut GenerationResultsView, code: KeyCode) -> Option<GenerationResultsEvent> {
+        view.handle_input(KeyEvent::new(code, KeyModifiers::NONE))
+    }
+
+    fn file(name: &'static str, status: FileStatus) -> GeneratedFile {
+        GeneratedFile {
+            name,
+            archive_path: PathBuf::from("/out/kb_km_1").join(name),
+            keymap_path: PathBuf::from("/qmk/keymaps/km").join(name),
+            size: 2048,
+            status,
+            error: None,
+        }
+    }
+
+    fn outcome(files: Vec<GeneratedFile>) -> GenerationOutcome {
+        GenerationOutcome {
+            archive_dir: PathBuf::from("/out/kb_km_1"),
+            keymap_dir: PathBuf::from("/qmk/keymaps/km"),
+            files,
+        }
+    }
+
+    #[test]
+    fn test_generated_files_summary_and_shortcuts() {
+        let outcome = outcome(vec![
+            file("config.h", FileStatus::Written),
+            file("keymap.c", FileStatus::Unchanged),
+        ]);
+        let mut view = GenerationResultsView::new(GenerationResult::Written(outcome), 2);
+        assert!(view.succeeded());
+        assert_eq!(
+            view.summary(),
+            "✓ Generated 2 files (1 written), 2 warnings"
+        );
+        assert_eq!(
+            view.clone().with_unsaved_changes().summary(),
+            "✓ Generated 2 files (1 written), 2 warnings - layout file not saved"
+        );
+        assert_eq!(format_size(2048), "2.0 KiB");
+
+        press(&mut view, KeyCode::Down);
+        let Some(GenerationResultsEvent::CopyPath(path)) = press(&mut view, KeyCode::Char('y'))
+        else {
+            panic!("expected a copy");
+        };
+        assert_eq!(path, PathBuf::from("/qmk/keymaps/km/keymap.c"));
+        let Some(GenerationResultsEvent::OpenDirectory(dir)) = press(&mut view, KeyCode::Char('a'))
+        else {
+            panic!("expected an open");
+        };
+        assert_eq!(dir, PathBuf::from("/out/kb_km_1"));
+    }
+
+    #[test]
+    fn test_write_failure_keeps_written_files_and_io_error() {
+        let mut failed = file("keymap.c", FileStatus::Failed);
+        failed.error = Some(FileWriteError {
+            path: PathBuf::from("/qmk/keymaps/km/keymap.c"),
+            message: "Permission denied".to_string(),
+        });
+        let error = anyhow::Error::new(GenerationError {
+            outcome: outcome(vec![
+                file("config.h", FileStatus::Written),
+                failed,
+                file("rules.mk", FileStatus::NotWritten),
+            ]),
+        });
+        assert_eq!(
+            error.to_string(),
+            "Failed to write /qmk/keymaps/km/keymap.c: Permission denied \
+             (keymap.c, rules.mk were not updated)"
+        );
+
+        let mut view = GenerationResultsView::new(GenerationResult::from_error(&error), 0);
+        assert!(!view.succeeded());
+        assert_eq!(view.row_count(), 3);
+        assert_eq!(
+            view.summary(),
+            "Generation failed writing /qmk/keymaps/km/keymap.c: Permission denied \
+             - not updated: keymap.c, rules.mk"
+        );
+        // The failed file starts selected
+        let Some(GenerationResultsEvent::OpenDirectory(dir)) = press(&mut view, KeyCode::Char('o'))
+        else {
+            panic!("expected an open");
+        };
+        assert_eq!(dir, PathBuf::from("/qmk/keymaps/km"));
+    }
+
+    #[test]
+    fn test_failure_before_writing_has_no_files() {
+        let error = anyhow::anyhow!("Template error").context("Failed to render keymap.c");
+        let mut view = GenerationResultsView::new(GenerationResult::from_error(&error), 0);
+        assert!(!view.succeeded());
+        assert_eq!(
+            view.summary(),
+            "Generation failed: Failed to render keymap.c"
+        );
+        assert!(press(&mut view, KeyCode::Char('a')).is_none());
+        assert!(press(&mut view, KeyCode::Char('y')).is_none());
+    }
+}
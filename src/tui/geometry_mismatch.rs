@@ -0,0 +1,115 @@
+//! Dialog for a layout with more keys than the keyboard geometry.
+//!
+//! Shown when the editor opens a layout whose layers have keys at positions
+//! the geometry doesn't have (a hand-edited file, or a geometry changed
+//! upstream). The layout can be opened read-only, fitted to the geometry, or
+//! not opened at all.
+
+use ratatui::{
+    layout::{Alignment, Constraint, Direction, Layout as RatatuiLayout, Rect},
+    style::{Modifier, Style},
+    text::{Line, Span},
+    widgets::{Block, Borders, Clear, List, ListItem, Paragraph, Wrap},
+    Frame,
+};
+
+use crate::services::geometry::GeometryMismatch;
+use crate::tui::help_registry::{self, HelpRegistry};
+use crate::tui::theme::Theme;
+
+/// Renders the geometry mismatch dialog
+pub fn render(f: &mut Frame, mismatch: &GeometryMismatch, theme: &Theme) {
+    let popup_area = centered_rect(70, 60, f.area());
+
+    f.render_widget(Clear, popup_area);
+    let background = Block::default().style(Style::default().bg(theme.background));
+    f.render_widget(background, popup_area);
+
+    let chunks = RatatuiLayout::default()
+        .direction(Direction::Vertical)
+        .constraints([
+            Constraint::Length(6), // Explanation
+            Constraint::Min(4),    // Layers with extra keys
+            Constraint::Length(1), // Hints
+        ])
+        .split(popup_area);
+
+    let explanation = Paragraph::new(vec![
+        Line::from(Span::styled(
+            mismatch.summary(),
+            Style::default()
+                .fg(theme.warning)
+                .add_modifier(Modifier::BOLD),
+        )),
+        Line::from(
+            "Read-only opens the layout without changing the file. Fitting removes the \
+             extra keys (the removed keycodes are listed in the action log) and adds \
+             KC_NO for missing positions; the file only changes when you save.",
+        ),
+    ])
+    .wrap(Wrap { trim: true })
+    .style(Style::default().fg(theme.text))
+    .block(
+        Block::default()
+            .borders(Borders::ALL)
+            .title(" Layout Doesn't Match Geometry ")
+            .style(Style::default().fg(theme.warning)),
+    );
+    f.render_widget(explanation, chunks[0]);
+
+    let items: Vec<ListItem> = mismatch
+        .layers
+        .iter()
+        .map(|layer| {
+            ListItem::new(Line::from(vec![
+                Span::styled(
+                    format!("Layer {}: {:<24}", layer.layer, layer.name),
+                    Style::default().fg(theme.text),
+                ),
+                Span::styled(
+                    format!("{:>4} keys, {} extra", layer.key_count, layer.extra.len()),
+                    Style::default().fg(theme.text_muted),
+                ),
+            ]))
+        })
+        .collect();
+    let list = List::new(items).block(
+        Block::default()
+            .borders(Borders::ALL)
+            .title(format!(" Geometry: {} keys ", mismatch.geometry_keys))
+            .style(Style::default().fg(theme.primary)),
+    );
+    f.render_widget(list, chunks[1]);
+
+    let hints = HelpRegistry::default()
+        .format_status_bar_hints(help_registry::contexts::GEOMETRY_MISMATCH, 3)
+        .into_iter()
+        .map(|(key, hint)| format!("{key}: {hint}"))
+        .collect::<Vec<_>>()
+        .join(" | ");
+    let hints = Paragraph::new(hints)
+        .style(Style::default().fg(theme.text_muted))
+        .alignment(Alignment::Center);
+    f.render_widget(hints, chunks[2]);
+}
+
+/// Helper to create a centered rectangle
+fn centered_rect(percent_x: u16, percent_y: u16, r: Rect) -> Rect {
+    let popup_layout = RatatuiLayout::default()
+        .direction(Direction::Vertical)
+        .constraints([
+            Constraint::Percentage((100 - percent_y) / 2),
+            Constraint::Percentage(percent_y),
+            Constraint::Percentage((100 - percent_y) / 2),
+        ])
+        .split(r);
+
+    RatatuiLayout::default()
+        .direction(Direction::Horizontal)
+        .constraints([
+            Constraint::Percentage((100 - percent_x) / 2),
+            Constraint::Percentage(percent_x),
+            Constraint::Percentage((100 - percent_x) / 2),
+        ])
+        .split(popup_layout[1])[1]
+}
@@ -0,0 +1,313 @@
+//! Keycode grid paste popup.
+//!
+//! Takes a grid of keycodes (from a bracketed paste, typed in, or read from
+//! a file) and previews where each cell lands on the current layer before
+//! applying it. Invalid keycodes and cells without a key are highlighted and
+//! skipped when the grid is applied.
+
+use crossterm::event::{KeyCode, KeyEvent};
+use ratatui::{
+    layout::Rect,
+    style::{Modifier, Style},
+    text::{Line, Span},
+    widgets::{Block, Borders, Clear, Paragraph},
+    Frame,
+};
+
+use super::text_editor::{TextEditor, TextEditorEvent};
+use super::Theme;
+use crate::services::keycode_grid::{CellStatus, GridCell, GridPlan};
+
+/// Widest keycode shown in a preview cell before it is truncated
+const MAX_CELL_WIDTH: usize = 14;
+
+/// Events emitted by the GridPasteView component
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum GridPasteEvent {
+    /// User submitted a grid, or the path of a file holding one
+    Submitted(String),
+    /// User confirmed the previewed grid
+    Apply,
+    /// User closed the popup without applying
+    Cancelled,
+}
+
+/// GridPasteView component that implements the Component trait
+#[derive(Debug, Clone)]
+pub struct GridPasteView {
+    /// Entry editor, shown until a grid has been parsed
+    editor: Option<TextEditor>,
+    /// Parsed grid placed onto the layer
+    plan: Option<GridPlan>,
+    /// Index of the inspected cell in `plan.cells`
+    selected: usize,
+}
+
+impl GridPasteView {
+    /// Create a view asking for a grid or a file path.
+    #[must_use]
+    pub fn entry() -> Self {
+        Self {
+            editor: Some(TextEditor::new(
+                "Paste Keycode Grid (or type a file path)",
+                "",
+            )),
+            plan: None,
+            selected: 0,
+        }
+    }
+
+    /// Create a view previewing `plan`.
+    #[must_use]
+    pub const fn preview(plan: GridPlan) -> Self {
+        Self {
+            editor: None,
+            plan: Some(plan),
+            selected: 0,
+        }
+    }
+
+    /// Show the preview of `plan` in place of the entry editor.
+    pub fn set_plan(&mut self, plan: GridPlan) {
+        self.editor = None;
+        self.plan = Some(plan);
+        self.selected = 0;
+    }
+
+    /// The previewed plan, once a grid has been parsed.
+    #[must_use]
+    pub const fn plan(&self) -> Option<&GridPlan> {
+        self.plan.as_ref()
+    }
+
+    /// Whether the entry editor is showing.
+    #[must_use]
+    pub const fn is_entry(&self) -> bool {
+        self.editor.is_some()
+    }
+
+    /// Insert pasted text into the entry editor.
+    pub fn insert_text(&mut self, text: &str) {
+        if let Some(editor) = &mut self.editor {
+            editor.insert_text(text);
+        }
+    }
+
+    /// The inspected cell.
+    fn selected_cell(&self) -> Option<&GridCell> {
+        self.plan.as_ref()?.cells.get(self.selected)
+    }
+
+    /// Move the inspection cursor to the nearest cell in the row `delta` rows away.
+    fn move_row(&mut self, delta: isize) {
+        let Some(plan) = &self.plan else {
+            return;
+        };
+        let Some(current) = plan.cells.get(self.selected) else {
+            return;
+        };
+        let Some(row) = current.row.checked_add_signed(delta) else {
+            return;
+        };
+        let col = current.col;
+        if let Some((index, _)) = plan
+            .cells
+            .iter()
+            .enumerate()
+            .filter(|(_, cell)| cell.row == row)
+            .min_by_key(|(_, cell)| cell.col.abs_diff(col))
+        {
+            self.selected = index;
+        }
+    }
+}
+
+impl crate::tui::component::Component for GridPasteView {
+    type Event = GridPasteEvent;
+
+    fn handle_input(&mut self, key: KeyEvent) -> Option<Self::Event> {
+        if let Some(editor) = &mut self.editor {
+            return match editor.handle_input(key)? {
+                TextEditorEvent::Saved(text) => Some(GridPasteEvent::Submitted(text)),
+                TextEditorEvent::Cancelled => Some(GridPasteEvent::Cancelled),
+            };
+        }
+
+        let last = self
+            .plan
+            .as_ref()
+            .map_or(0, |plan| plan.cells.len().saturating_sub(1));
+        match key.code {
+            KeyCode::Esc | KeyCode::Char('q') => return Some(GridPasteEvent::Cancelled),
+            KeyCode::Enter => return Some(GridPasteEvent::Apply),
+            KeyCode::Left | KeyCode::Char('h') => self.selected = self.selected.saturating_sub(1),
+            KeyCode::Right | KeyCode::Char('l') => self.selected = (self.selected + 1).min(last),
+            KeyCode::Up | KeyCode::Char('k') => self.move_row(-1),
+            KeyCode::Down | KeyCode::Char('j') => self.move_row(1),
+            _ => {}
+        }
+        None
+    }
+
+    fn render(&self, f: &mut Frame, area: Rect, theme: &Theme) {
+        if let Some(editor) = &self.editor {
+            editor.render(f, area, theme);
+        } else if let Some(plan) = &self.plan {
+            render_preview(f, self, plan, area, theme);
+        }
+    }
+}
+
+/// Renders the grid preview popup
+fn render_preview(f: &mut Frame, view: &GridPasteView, plan: &GridPlan, area: Rect, theme: &Theme) {
+    let area = centered_rect(90, 70, area);
+
+    f.render_widget(Clear, area);
+    let background = Block::default().style(Style::default().bg(theme.background));
+    f.render_widget(background, area);
+
+    let ready = plan.count(CellStatus::Ready);
+    let invalid = plan.count(CellStatus::InvalidKeycode);
+    let no_key = plan.count(CellStatus::NoKey);
+
+    let mut lines = vec![
+        Line::from(vec![
+            Span::styled(
+                format!(
+                    "Starting at row {}, col {} · {ready} key(s) ready",
+                    plan.start.row, plan.start.col
+                ),
+                Style::default().fg(theme.text_secondary),
+            ),
+            Span::styled(
+                format!(" · {invalid} invalid"),
+                Style::default().fg(if invalid > 0 {
+                    theme.error
+                } else {
+                    theme.text_secondary
+                }),
+            ),
+            Span::styled(
+                format!(" · {no_key} without a key"),
+                Style::default().fg(if no_key > 0 {
+                    theme.warning
+                } else {
+                    theme.text_secondary
+                }),
+            ),
+        ]),
+        Line::from(""),
+    ];
+
+    // Lay the cells out in their grid rows and columns
+    let width = plan
+        .cells
+        .iter()
+        .map(|cell| cell.keycode.chars().count())
+        .max()
+        .unwrap_or(0)
+        .min(MAX_CELL_WIDTH);
+    let rows = plan.cells.last().map_or(0, |cell| cell.row + 1);
+    for row in 0..rows {
+        let mut spans = Vec::new();
+        let mut next_col = 0;
+        for (index, cell) in plan.cells.iter().enumerate().filter(|(_, c)| c.row == row) {
+            // Empty Markdown cells stay blank
+            for _ in next_col..cell.col {
+                spans.push(Span::raw(" ".repeat(width + 1)));
+            }
+            next_col = cell.col + 1;
+
+            let mut style = match cell.status {
+                CellStatus::Ready => Style::default().fg(theme.text),
+                CellStatus::InvalidKeycode => Style::default()
+                    .fg(theme.error)
+                    .add_modifier(Modifier::BOLD | Modifier::UNDERLINED),
+                CellStatus::NoKey => Style::default()
+                    .fg(theme.text_muted)
+                    .add_modifier(Modifier::CROSSED_OUT),
+            };
+            if index == view.selected {
+                style = style.add_modifier(Modifier::REVERSED);
+            }
+            let text: String = cell.keycode.chars().take(width).collect();
+            spans.push(Span::styled(format!("{text:<width$}"), style));
+            spans.push(Span::raw(" "));
+        }
+        lines.push(Line::from(spans));
+    }
+
+    lines.push(Line::from(""));
+    if let Some(cell) = view.selected_cell() {
+        let (detail, color) = match (cell.status, cell.target) {
+            (CellStatus::InvalidKeycode, _) => (
+                format!("'{}' is not a valid keycode", cell.keycode),
+                theme.error,
+            ),
+            (CellStatus::NoKey, _) => ("No key here; skipped".to_string(), theme.warning),
+            (CellStatus::Ready, Some(pos)) => (
+                format!(
+                    "→ key ({}, {}): {} → {}",
+                    pos.row,
+                    pos.col,
+                    cell.replaces.as_deref().unwrap_or("-"),
+                    cell.keycode
+                ),
+                theme.text,
+            ),
+            (CellStatus::Ready, None) => (String::new(), theme.text),
+        };
+        lines.push(Line::from(vec![
+            Span::styled(
+                format!("Grid row {}, col {} ", cell.row + 1, cell.col + 1),
+                Style::default().fg(theme.text_muted),
+            ),
+            Span::styled(detail, Style::default().fg(color)),
+        ]));
+    }
+
+    let paragraph = Paragraph::new(lines).block(
+        Block::default()
+            .title(" Paste Keycode Grid ")
+            .borders(Borders::ALL)
+            .border_style(Style::default().fg(theme.primary)),
+    );
+    f.render_widget(paragraph, area);
+
+    let help_text = format!("Enter: Apply {ready} key(s) | ←↑↓→: Inspect | Esc: Cancel");
+    let help_area = Rect {
+        x: area.x + 2,
+        y: area.y + area.height.saturating_sub(1),
+        width: area.width.saturating_sub(4),
+        height: 1,
+    };
+    let help = Paragraph::new(help_text).style(
+        Style::default()
+            .fg(theme.text_muted)
+            .add_modifier(Modifier::DIM),
+    );
+    f.render_widget(help, help_area);
+}
+
+/// Helper to create a centered rectangle.
+fn centered_rect(percent_x: u16, percent_y: u16, r: Rect) -> Rect {
+    use ratatui::layout::{Constraint, Direction, Layout};
+
+    let popup_layout = Layout::default()
+        .direction(Direction::Vertical)
+        .constraints([
+            Constraint::Percentage((100 - percent_y) / 2),
+            Constraint::Percentage(percent_y),
+            Constraint::Percentage((100 - percent_y) / 2),
+        ])
+        .split(r);
+
+    Layout::default()
+        .direction(Direction::Horizontal)
+        .constraints([
+            Constraint::Percentage((100 - percent_x) / 2),
+            Constraint::Percentage(percent_x),
+            Constraint::Percentage((100 - percent_x) / 2),
+        ])
+        .split(popup_layout[1])[1]
+}
@@ -38,3 +38,131 @@ pub fn handle_assign_category_to_layer(state: &mut AppState) -> Result<bool> {
     state.set_status("Select category for layer - Enter to apply");
     Ok(false)
 }
+
+/// Handle quick category assignment (Alt+1..9, Alt+0)
+///
+/// Assigns the `N`th category as numbered in the category manager to the
+/// selected keys, or the current key without a selection. `N == 0` clears
+/// the category instead.
+pub fn handle_quick_assign_category<const N: u8>(state: &mut AppState) -> Result<bool> {
+    let category = match usize::from(N).checked_sub(1) {
+        None => None,
+        Some(idx) => {
+            if let Some(category) = state.layout.categories.get(idx) {
+                Some((category.id.clone(), category.name.clone()))
+            } else {
+                state.set_error(format!(
+                    "No category {N} - see the category manager (Shift+K)"
+                ));
+                return Ok(false);
+            }
+        }
+    };
+
+    let positions = if state.selection_mode.is_some() && !state.selected_keys.is_empty() {
+        state.selected_keys.clone()
+    } else {
+        vec![state.selected_position]
+    };
+
+    let mut count = 0;
+    if let Some(layer) = state.layout.layers.get_mut(state.current_layer) {
+        for key in layer
+            .keys
+            .iter_mut()
+            .filter(|k| positions.contains(&k.position))
+        {
+            key.category_id = category.as_ref().map(|(id, _)| id.clone());
+            count += 1;
+        }
+    }
+
+    if count == 0 {
+        state.set_error("No key selected");
+        return Ok(false);
+    }
+
+    state.mark_dirty();
+    let target = if count == 1 {
+        "key".to_string()
+    } else {
+        format!("{count} keys")
+    };
+    match category {
+        Some((_, name)) => {
+            state.log_action("Assign category", format!("Assigned '{name}' to {target}"));
+        }
+        None => state.log_action("Assign category", format!("Removed category from {target}")),
+    }
+    Ok(false)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::models::{Category, KeyDefinition, Layer, Layout, Position, RgbColor};
+
+    fn state_with_categories() -> AppState {
+        let mut layout = Layout::new("Quick assign").unwrap();
+        for (id, name) in [("nav", "Navigation"), ("sym", "Symbols")] {
+            layout
+                .categories
+                .push(Category::new(id, name, RgbColor::new(0, 0, 255)).unwrap());
+        }
+        let mut layer = Layer::new(0, "Base", RgbColor::new(255, 255, 255)).unwrap();
+        for col in 0..3 {
+            layer
+                .add_key(KeyDefinition::at(Position::new(0, col)).keycode("KC_A"))
+                .unwrap();
+        }
+        layout.add_layer(layer).unwrap();
+        AppState::new(
+            layout,
+            None,
+            crate::models::KeyboardGeometry::new("test", "test", 1, 3),
+            crate::models::VisualLayoutMapping::default(),
+            crate::config::Config::default(),
+        )
+        .unwrap()
+    }
+
+    fn categories(state: &AppState) -> Vec<Option<&str>> {
+        state.layout.layers[0]
+            .keys
+            .iter()
+            .map(|k| k.category_id.as_deref())
+            .collect()
+    }
+
+    #[test]
+    fn test_quick_assign_to_current_key() {
+        let mut state = state_with_categories();
+        state.selected_position = Position::new(0, 1);
+
+        handle_quick_assign_category::<2>(&mut state).unwrap();
+        assert_eq!(categories(&state), [None, Some("sym"), None]);
+        assert!(state.dirty);
+
+        handle_quick_assign_category::<0>(&mut state).unwrap();
+        assert_eq!(categories(&state), [None, None, None]);
+    }
+
+    #[test]
+    fn test_quick_assign_to_selection() {
+        let mut state = state_with_categories();
+        state.selection_mode = Some(crate::tui::SelectionMode::Normal);
+        state.selected_keys = vec![Position::new(0, 0), Position::new(0, 2)];
+
+        handle_quick_assign_category::<1>(&mut state).unwrap();
+        assert_eq!(categories(&state), [Some("nav"), None, Some("nav")]);
+    }
+
+    #[test]
+    fn test_quick_assign_unknown_category() {
+        let mut state = state_with_categories();
+
+        handle_quick_assign_category::<3>(&mut state).unwrap();
+        assert_eq!(categories(&state), [None, None, None]);
+        assert!(state.error_message.is_some());
+    }
+}
@@ -70,7 +70,7 @@ pub fn handle_toggle_layer_colors(state: &mut AppState) -> Result<bool> {
         } else {
             format!("Layer {} colors disabled", state.current_layer)
         };
-        state.set_status(status);
+        state.log_action("Toggle layer colors", status);
     }
     Ok(false)
 }
@@ -85,6 +85,6 @@ pub fn handle_toggle_all_layer_colors(state: &mut AppState) -> Result<bool> {
     } else {
         "All layer colors disabled".to_string()
     };
-    state.set_status(status);
+    state.log_action("Toggle layer colors", status);
     Ok(false)
 }
@@ -1,10 +1,11 @@
 // File operations action handlers
 
+use crate::atomic_write;
 use crate::export::export_to_markdown;
+use crate::services::snapshots::{SnapshotService, DEFAULT_SNAPSHOT_RETENTION};
 use crate::services::LayoutService;
 use crate::tui::{AppState, ExportFilenameDialogState, PopupType, TemplateSaveDialogState};
 use anyhow::Result;
-use std::fs;
 use std::path::PathBuf;
 
 /// Handle quit action
@@ -19,10 +20,14 @@ pub fn handle_quit(state: &mut AppState) -> Result<bool> {
 
 /// Handle save action
 pub fn handle_save(state: &mut AppState) -> Result<bool> {
+    if state.is_read_only() {
+        state.set_error("Layout is open read-only; reopen without --read-only to save");
+        return Ok(false);
+    }
     if let Some(path) = &state.source_path.clone() {
         LayoutService::save(&state.layout, path)?;
         state.mark_clean();
-        state.set_status("Saved");
+        state.log_action("Save", "Saved");
     } else {
         state.set_error("No file path set");
     }
@@ -41,7 +46,12 @@ pub fn handle_export_layout(state: &mut AppState) -> Result<bool> {
 /// Perform the actual export to markdown
 pub fn perform_export(state: &mut AppState, filename: &str) -> Result<()> {
     // Generate markdown content
-    let markdown_content = export_to_markdown(&state.layout, &state.geometry, &state.keycode_db)?;
+    let markdown_content = export_to_markdown(
+        &state.layout,
+        &state.geometry,
+        &state.keycode_db,
+        state.config.ui.transparent_keys.skips_in_exports(),
+    )?;
 
     // Determine output path
     let output_path = if filename.contains('/') || filename.contains('\\') {
@@ -61,12 +71,38 @@ pub fn perform_export(state: &mut AppState, filename: &str) -> Result<()> {
     };
 
     // Write to file
-    fs::write(&output_path, markdown_content)?;
+    atomic_write::write(&output_path, markdown_content)?;
 
     state.set_status(format!("✓ Exported to: {}", output_path.display()));
     Ok(())
 }
 
+/// Handle create snapshot action
+///
+/// Writes the in-memory layout (including unsaved changes) to the snapshot
+/// directory without touching the layout file itself.
+pub fn handle_create_snapshot(state: &mut AppState) -> Result<bool> {
+    let source_path = state.source_path.as_deref();
+    let layout_name = SnapshotService::layout_key(&state.layout, source_path);
+    let result = SnapshotService::snapshots_dir(source_path).and_then(|dir| {
+        SnapshotService::create(
+            &state.layout,
+            &layout_name,
+            &dir,
+            DEFAULT_SNAPSHOT_RETENTION,
+        )
+    });
+
+    match result {
+        Ok(snapshot) => state.set_status(format!(
+            "Snapshot saved: {}",
+            snapshot.timestamp.format("%Y-%m-%d %H:%M:%S")
+        )),
+        Err(e) => state.set_error(format!("Failed to save snapshot: {e}")),
+    }
+    Ok(false)
+}
+
 /// Handle save as template action
 pub fn handle_save_as_template(state: &mut AppState) -> Result<bool> {
     state.template_save_dialog_state =
@@ -4,143 +4,193 @@ use crate::models::Position;
 use crate::tui::{clipboard, AppState};
 use anyhow::Result;
 
+/// Keys to act on: the multi-selection, or `None` outside selection mode.
+fn selection(state: &AppState) -> Option<Vec<Position>> {
+    (state.selection_mode.is_some() && !state.selected_keys.is_empty())
+        .then(|| state.selected_keys.clone())
+}
+
+/// Ends selection mode after the selected keys were acted on.
+fn end_selection(state: &mut AppState) {
+    state.selection_mode = None;
+    state.selected_keys.clear();
+}
+
+/// Clipboard contents of the keys at `positions` on `layer`.
+fn collect_keys(
+    state: &AppState,
+    layer: usize,
+    positions: &[Position],
+) -> Vec<(Position, clipboard::ClipboardContent)> {
+    let Some(layer) = state.layout.layers.get(layer) else {
+        return Vec::new();
+    };
+    positions
+        .iter()
+        .filter_map(|pos| {
+            let key = layer.keys.iter().find(|k| k.position == *pos)?;
+            Some((
+                *pos,
+                clipboard::ClipboardContent {
+                    keycode: key.keycode.clone(),
+                    color_override: key.color_override,
+                    category_id: key.category_id.clone(),
+                },
+            ))
+        })
+        .collect()
+}
+
+/// Writes `content` to the key at `position` on `layer`.
+///
+/// Returns false if there is no such key.
+fn apply_content(
+    state: &mut AppState,
+    layer: usize,
+    position: Position,
+    content: &clipboard::ClipboardContent,
+) -> bool {
+    let Some(key) = state
+        .layout
+        .layers
+        .get_mut(layer)
+        .and_then(|layer| layer.keys.iter_mut().find(|k| k.position == position))
+    else {
+        return false;
+    };
+    key.keycode = content.keycode.clone();
+    key.color_override = content.color_override;
+    key.category_id = content.category_id.clone();
+    true
+}
+
+/// Empties the keys a cut was taken from, on whichever layer they are.
+fn clear_cut_sources(state: &mut AppState, sources: &[(usize, Position)]) {
+    for &(layer_idx, pos) in sources {
+        if let Some(layer) = state.layout.layers.get_mut(layer_idx) {
+            if let Some(source_key) = layer.keys.iter_mut().find(|k| k.position == pos) {
+                source_key.keycode = "KC_TRNS".to_string();
+                source_key.color_override = None;
+                source_key.category_id = None;
+            }
+        }
+    }
+    state.clipboard.clear_cut_source();
+}
+
 /// Handle clear key action
+///
+/// Acts on the current layer, which in the split view is the focused pane's.
 pub fn handle_clear_key(state: &mut AppState) -> Result<bool> {
-    if state.selection_mode.is_some() && !state.selected_keys.is_empty() {
+    let layer = state.current_layer;
+    if let Some(positions) = selection(state) {
         // Clear all selected keys
-        let layer = state.current_layer;
-        for pos in &state.selected_keys.clone() {
-            if let Some(layer) = state.layout.layers.get_mut(layer) {
-                if let Some(key) = layer.keys.iter_mut().find(|k| k.position == *pos) {
-                    key.keycode = "KC_TRNS".to_string();
-                }
+        if let Some(layer) = state.layout.layers.get_mut(layer) {
+            for key in layer
+                .keys
+                .iter_mut()
+                .filter(|k| positions.contains(&k.position))
+            {
+                key.keycode = "KC_TRNS".to_string();
             }
         }
-        let count = state.selected_keys.len();
-        state.selected_keys.clear();
-        state.selection_mode = None;
+        end_selection(state);
         state.mark_dirty();
-        state.set_status(format!("Cleared {count} keys"));
+        state.log_action(
+            "Clear keys",
+            format!("Cleared {} keys on layer {layer}", positions.len()),
+        );
     } else if let Some(key) = state.get_selected_key_mut() {
         key.keycode = "KC_TRNS".to_string();
-        state.mark_dirty();
-        state.set_status("Key cleared (KC_TRNS)");
+        state.mark_selected_key_dirty();
+        state.log_action("Clear key", "Key cleared (KC_TRNS)");
     }
     Ok(false)
 }
 
 /// Handle copy key action
 pub fn handle_copy_key(state: &mut AppState) -> Result<bool> {
-    if state.selection_mode.is_some() && !state.selected_keys.is_empty() {
-        // Copy all selected keys
-        let layer = state.current_layer;
-        let anchor = state.selected_keys[0];
-        let mut keys: Vec<(Position, clipboard::ClipboardContent)> = Vec::new();
-
-        for pos in &state.selected_keys {
-            if let Some(layer) = state.layout.layers.get(layer) {
-                if let Some(key) = layer.keys.iter().find(|k| k.position == *pos) {
-                    keys.push((
-                        *pos,
-                        clipboard::ClipboardContent {
-                            keycode: key.keycode.clone(),
-                            color_override: key.color_override,
-                            category_id: key.category_id.clone(),
-                        },
-                    ));
-                }
-            }
-        }
-
-        let msg = state.clipboard.copy_multi(keys, anchor);
-        state.selection_mode = None;
-        state.selected_keys.clear();
-        state.set_status(msg);
-    } else if let Some(key) = state.get_selected_key() {
-        // Clone key data to avoid borrow conflict with clipboard
-        let keycode = key.keycode.clone();
-        let color_override = key.color_override;
-        let category_id = key.category_id.clone();
-        let msg = state
-            .clipboard
-            .copy(&keycode, color_override, category_id.as_deref());
-        state.set_status(msg);
-    } else {
-        state.set_error("No key to copy");
-    }
+    copy_or_cut(state, state.current_layer, false);
     Ok(false)
 }
 
 /// Handle cut key action
 pub fn handle_cut_key(state: &mut AppState) -> Result<bool> {
-    if state.selection_mode.is_some() && !state.selected_keys.is_empty() {
-        // Cut all selected keys
-        let layer = state.current_layer;
-        let anchor = state.selected_keys[0];
-        let positions = state.selected_keys.clone();
-        let mut keys: Vec<(Position, clipboard::ClipboardContent)> = Vec::new();
-
-        for pos in &state.selected_keys {
-            if let Some(layer_ref) = state.layout.layers.get(layer) {
-                if let Some(key) = layer_ref.keys.iter().find(|k| k.position == *pos) {
-                    keys.push((
-                        *pos,
-                        clipboard::ClipboardContent {
-                            keycode: key.keycode.clone(),
-                            color_override: key.color_override,
-                            category_id: key.category_id.clone(),
-                        },
-                    ));
-                }
-            }
-        }
+    copy_or_cut(state, state.current_layer, true);
+    Ok(false)
+}
 
-        let msg = state.clipboard.cut_multi(keys, anchor, layer, positions);
-        state.selection_mode = None;
-        state.selected_keys.clear();
-        state.set_status(msg);
-    } else if let Some(key) = state.get_selected_key() {
-        // Clone key data to avoid borrow conflict with clipboard
-        let keycode = key.keycode.clone();
-        let color_override = key.color_override;
-        let category_id = key.category_id.clone();
-        let msg = state.clipboard.cut(
-            &keycode,
-            color_override,
-            category_id.as_deref(),
-            state.current_layer,
-            state.selected_position,
-        );
+/// Copies (or cuts) the selected keys, or the selected key, of `layer`.
+///
+/// A cut remembers `layer`, so pasting on another layer or in the split
+/// view's other pane still empties the source keys.
+fn copy_or_cut(state: &mut AppState, layer: usize, cut: bool) {
+    if let Some(positions) = selection(state) {
+        let anchor = positions[0];
+        let keys = collect_keys(state, layer, &positions);
+        let msg = if cut {
+            state.clipboard.cut_multi(keys, anchor, layer, positions)
+        } else {
+            state.clipboard.copy_multi(keys, anchor)
+        };
+        end_selection(state);
         state.set_status(msg);
-    } else {
-        state.set_error("No key to cut");
+        return;
     }
-    Ok(false)
+
+    let position = state.selected_position;
+    let Some((_, content)) = collect_keys(state, layer, &[position]).pop() else {
+        state.set_error(if cut {
+            "No key to cut"
+        } else {
+            "No key to copy"
+        });
+        return;
+    };
+    let category_id = content.category_id.as_deref();
+    let msg = if cut {
+        state.clipboard.cut(
+            &content.keycode,
+            content.color_override,
+            category_id,
+            layer,
+            position,
+        )
+    } else {
+        state
+            .clipboard
+            .copy(&content.keycode, content.color_override, category_id)
+    };
+    state.set_status(msg);
 }
 
 /// Handle paste key action
 pub fn handle_paste_key(state: &mut AppState) -> Result<bool> {
+    paste(state, state.current_layer, state.selected_position);
+    Ok(false)
+}
+
+/// Pastes the clipboard at `target` on `layer`.
+///
+/// A multi-key clipboard is placed with its anchor at `target`, dropping keys
+/// that fall outside the keyboard.
+fn paste(state: &mut AppState, layer: usize, target: Position) {
     // Check if clipboard has content
     if !state.clipboard.has_content() {
         state.set_error("Nothing to paste");
-        return Ok(false);
+        return;
     }
 
     // Check for multi-key paste first
     if state.clipboard.is_multi() {
         if let Some(multi) = state.clipboard.get_multi_content().cloned() {
-            // Calculate target positions relative to current position
-            // The anchor is the reference point, we need to shift all keys
+            // Calculate offset from the anchor to the target
             let anchor = multi.anchor;
-            let current = state.selected_position;
-
-            // Calculate offset from anchor to current position
-            let row_offset = current.row as isize - anchor.row as isize;
-            let col_offset = current.col as isize - anchor.col as isize;
+            let row_offset = target.row as isize - anchor.row as isize;
+            let col_offset = target.col as isize - anchor.col as isize;
 
-            // Collect valid target positions and save undo state
+            // Collect valid target positions
             let mut paste_targets: Vec<(Position, clipboard::ClipboardContent)> = Vec::new();
-            let mut undo_keys: Vec<(Position, clipboard::ClipboardContent)> = Vec::new();
 
             for (pos, content) in &multi.keys {
                 // Calculate target position
@@ -156,20 +206,6 @@ pub fn handle_paste_key(state: &mut AppState) -> Result<bool> {
 
                     // Check if target position is valid
                     if state.mapping.is_valid_position(target_pos) {
-                        // Save original for undo
-                        if let Some(layer) = state.layout.layers.get(state.current_layer) {
-                            if let Some(key) = layer.keys.iter().find(|k| k.position == target_pos)
-                            {
-                                undo_keys.push((
-                                    target_pos,
-                                    clipboard::ClipboardContent {
-                                        keycode: key.keycode.clone(),
-                                        color_override: key.color_override,
-                                        category_id: key.category_id.clone(),
-                                    },
-                                ));
-                            }
-                        }
                         paste_targets.push((target_pos, content.clone()));
                     }
                 }
@@ -177,16 +213,9 @@ pub fn handle_paste_key(state: &mut AppState) -> Result<bool> {
 
             if paste_targets.is_empty() {
                 state.set_error("No valid positions for paste");
-                return Ok(false);
+                return;
             }
 
-            // Save undo state
-            state.clipboard.save_undo(
-                state.current_layer,
-                undo_keys,
-                format!("Pasted {} keys", paste_targets.len()),
-            );
-
             // Get cut sources before paste
             let cut_sources: Vec<(usize, Position)> =
                 state.clipboard.get_multi_cut_sources().to_vec();
@@ -194,107 +223,133 @@ pub fn handle_paste_key(state: &mut AppState) -> Result<bool> {
             // Apply pastes
             let paste_count = paste_targets.len();
             for (target_pos, content) in &paste_targets {
-                if let Some(layer) = state.layout.layers.get_mut(state.current_layer) {
-                    if let Some(key) = layer.keys.iter_mut().find(|k| k.position == *target_pos) {
-                        key.keycode = content.keycode.clone();
-                        key.color_override = content.color_override;
-                        key.category_id = content.category_id.clone();
-                    }
-                }
+                apply_content(state, layer, *target_pos, content);
             }
 
             // Clear cut sources if this was a cut operation
-            for (layer_idx, pos) in cut_sources {
-                if let Some(layer) = state.layout.layers.get_mut(layer_idx) {
-                    if let Some(source_key) = layer.keys.iter_mut().find(|k| k.position == pos) {
-                        source_key.keycode = "KC_TRNS".to_string();
-                        source_key.color_override = None;
-                        source_key.category_id = None;
-                    }
-                }
-            }
-            state.clipboard.clear_cut_source();
+            clear_cut_sources(state, &cut_sources);
 
-            // Flash the first pasted key (current position)
-            state.flash_highlight = Some((state.current_layer, current, 5));
+            // Flash the first pasted key (the target)
+            state.flash_highlight = Some((layer, target, 5));
 
             state.mark_dirty();
-            state.set_status(format!("Pasted {paste_count} keys"));
+            state.log_action(
+                "Paste",
+                format!("Pasted {paste_count} keys on layer {layer}"),
+            );
         }
     } else if let Some(content) = state.clipboard.get_content().cloned() {
-        // Single key paste (original logic)
         // Get cut source before modifying clipboard
         let cut_source = state.clipboard.get_cut_source();
 
-        // Save undo state before making changes
-        if let Some(key) = state.get_selected_key() {
-            let original = clipboard::ClipboardContent {
-                keycode: key.keycode.clone(),
-                color_override: key.color_override,
-                category_id: key.category_id.clone(),
-            };
-            state.clipboard.save_undo(
-                state.current_layer,
-                vec![(state.selected_position, original)],
-                format!("Pasted: {}", content.keycode),
-            );
-        }
-
-        // Apply clipboard content to selected key
-        if let Some(key) = state.get_selected_key_mut() {
-            key.keycode = content.keycode.clone();
-            key.color_override = content.color_override;
-            key.category_id = content.category_id.clone();
-            state.mark_dirty();
-            state.set_status(format!("Pasted: {}", content.keycode));
-
-            // Trigger flash highlight (5 frames ~= 250ms at 50ms/frame)
-            state.flash_highlight = Some((state.current_layer, state.selected_position, 5));
+        // Apply clipboard content to the target key
+        if !apply_content(state, layer, target, &content) {
+            return;
         }
 
         // If this was a cut operation, clear the source key
-        if let Some((layer_idx, pos)) = cut_source {
-            if let Some(layer) = state.layout.layers.get_mut(layer_idx) {
-                if let Some(source_key) = layer.keys.iter_mut().find(|k| k.position == pos) {
-                    source_key.keycode = "KC_TRNS".to_string();
-                    source_key.color_override = None;
-                    source_key.category_id = None;
-                }
-            }
-            state.clipboard.clear_cut_source();
+        if let Some(source) = cut_source {
+            clear_cut_sources(state, &[source]);
         }
+
+        // Paste and cut land in one undo step
+        state.mark_dirty();
+        state.log_action("Paste", format!("Pasted: {}", content.keycode));
+
+        // Trigger flash highlight (5 frames ~= 250ms at 50ms/frame)
+        state.flash_highlight = Some((layer, target, 5));
     } else {
         state.set_error("Nothing in clipboard");
     }
+}
+
+/// Handle undo action
+pub fn handle_undo(state: &mut AppState) -> Result<bool> {
+    match state.undo() {
+        Some(label) => state.log_action("Undo", format!("Undone: {label}")),
+        None => state.set_error("Nothing to undo"),
+    }
     Ok(false)
 }
 
-/// Handle undo paste action
-pub fn handle_undo_paste(state: &mut AppState) -> Result<bool> {
-    // Use get_undo() to peek at undo info before taking it
-    if let Some(undo_info) = state.clipboard.get_undo() {
-        let key_count = undo_info.original_keys.len();
-        let layer_idx = undo_info.layer_index;
-        let description = undo_info.description.clone();
-
-        // Now take and apply the undo
-        if let Some(undo) = state.clipboard.take_undo() {
-            // Restore original keys
-            for (pos, content) in undo.original_keys {
-                if let Some(layer) = state.layout.layers.get_mut(layer_idx) {
-                    if let Some(key) = layer.keys.iter_mut().find(|k| k.position == pos) {
-                        key.keycode = content.keycode;
-                        key.color_override = content.color_override;
-                        key.category_id = content.category_id;
-                    }
-                }
-            }
-            state.mark_dirty();
-            state.set_status(format!("Undone {key_count} key(s): {description}"));
+/// Handle redo action
+pub fn handle_redo(state: &mut AppState) -> Result<bool> {
+    match state.redo() {
+        Some(label) => state.log_action("Redo", format!("Redone: {label}")),
+        None => state.set_error("Nothing to redo"),
+    }
+    Ok(false)
+}
+
+/// Handle paste grid action
+pub fn handle_paste_grid(state: &mut AppState) -> Result<bool> {
+    state.open_grid_paste(None);
+    state.set_status("Paste a keycode grid or type a file path - Ctrl+S: preview, Esc: cancel");
+    Ok(false)
+}
+
+/// Handle swap key action
+///
+/// The first press marks the current key; the second, on another key of any
+/// layer, swaps keycode, color override and category between the two.
+pub fn handle_swap_key(state: &mut AppState) -> Result<bool> {
+    let current = (state.current_layer, state.selected_position);
+    let Some(marked) = state.pending_swap else {
+        let Some(key) = state.get_selected_key() else {
+            return Ok(false);
+        };
+        let status = format!(
+            "Marked {} on layer {} for swapping - press s on another key (Tab for other layers), Esc: cancel",
+            key.keycode, current.0
+        );
+        state.pending_swap = Some(current);
+        state.flash_highlight = Some((current.0, current.1, 5));
+        state.set_status(status);
+        return Ok(false);
+    };
+
+    state.pending_swap = None;
+    if marked == current {
+        state.set_status("Swap cancelled");
+        return Ok(false);
+    }
+
+    let find = |state: &AppState, (layer, pos): (usize, Position)| {
+        state
+            .layout
+            .layers
+            .get(layer)
+            .and_then(|layer| layer.keys.iter().find(|k| k.position == pos))
+            .map(|k| (k.keycode.clone(), k.color_override, k.category_id.clone()))
+    };
+    let (Some(first), Some(second)) = (find(state, marked), find(state, current)) else {
+        state.set_error("The marked key no longer exists");
+        return Ok(false);
+    };
+
+    let summary = format!(
+        "Swapped {} (layer {}) with {} (layer {})",
+        first.0, marked.0, second.0, current.0
+    );
+    for ((layer, pos), (keycode, color_override, category_id)) in
+        [(marked, second), (current, first)]
+    {
+        if let Some(key) = state
+            .layout
+            .layers
+            .get_mut(layer)
+            .and_then(|layer| layer.keys.iter_mut().find(|k| k.position == pos))
+        {
+            key.keycode = keycode;
+            key.color_override = color_override;
+            key.category_id = category_id;
         }
-    } else {
-        state.set_error("Nothing to undo");
     }
+
+    state.flash_highlight = Some((current.0, current.1, 5));
+    state.mark_dirty();
+    state.refresh_layer_refs();
+    state.log_action("Swap keys", summary);
     Ok(false)
 }
 
@@ -311,3 +366,138 @@ pub fn handle_toggle_current_key(state: &mut AppState) -> Result<bool> {
     }
     Ok(false)
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::models::{KeyDefinition, Layer, Layout, RgbColor};
+
+    /// Two layers of three keys: `KC_A`..`KC_C` and `KC_1`..`KC_3`
+    fn create_state() -> AppState {
+        let mut layout = Layout::new("Swap").unwrap();
+        for (i, keycodes) in [["KC_A", "KC_B", "KC_C"], ["KC_1", "KC_2", "KC_3"]]
+            .into_iter()
+            .enumerate()
+        {
+            let mut layer = Layer::new(i as u8, "Layer", RgbColor::new(0, 0, 0)).unwrap();
+            for (col, keycode) in keycodes.into_iter().enumerate() {
+                layer
+                    .add_key(KeyDefinition::at(Position::new(0, col as u8)).keycode(keycode))
+                    .unwrap();
+            }
+            layout.add_layer(layer).unwrap();
+        }
+        AppState::new(
+            layout,
+            None,
+            crate::models::KeyboardGeometry::new("test", "test", 1, 3),
+            crate::models::VisualLayoutMapping::default(),
+            crate::config::Config::default(),
+        )
+        .unwrap()
+    }
+
+    fn keycodes(state: &AppState, layer: usize) -> Vec<&str> {
+        state.layout.layers[layer]
+            .keys
+            .iter()
+            .map(|k| k.keycode.as_str())
+            .collect()
+    }
+
+    #[test]
+    fn test_swap_exchanges_keys_and_their_colors() {
+        let mut state = create_state();
+        state.layout.layers[0].keys[0].color_override = Some(RgbColor::new(255, 0, 0));
+        state.layout.layers[0].keys[0].category_id = Some("nav".to_string());
+
+        handle_swap_key(&mut state).unwrap();
+        assert_eq!(state.pending_swap, Some((0, Position::new(0, 0))));
+        assert!(!state.dirty);
+
+        state.selected_position = Position::new(0, 2);
+        handle_swap_key(&mut state).unwrap();
+        assert_eq!(keycodes(&state, 0), ["KC_C", "KC_B", "KC_A"]);
+        let swapped = &state.layout.layers[0].keys[2];
+        assert_eq!(swapped.color_override, Some(RgbColor::new(255, 0, 0)));
+        assert_eq!(swapped.category_id.as_deref(), Some("nav"));
+        assert_eq!(state.layout.layers[0].keys[0].color_override, None);
+        assert!(state.pending_swap.is_none());
+        assert!(state.dirty);
+    }
+
+    #[test]
+    fn test_swap_across_layers_uses_marked_layer() {
+        let mut state = create_state();
+        state.selected_position = Position::new(0, 1);
+        handle_swap_key(&mut state).unwrap();
+
+        state.current_layer = 1;
+        handle_swap_key(&mut state).unwrap();
+        assert_eq!(keycodes(&state, 0), ["KC_A", "KC_2", "KC_C"]);
+        assert_eq!(keycodes(&state, 1), ["KC_1", "KC_B", "KC_3"]);
+    }
+
+    #[test]
+    fn test_escape_cancels_pending_swap() {
+        let mut state = create_state();
+        handle_swap_key(&mut state).unwrap();
+        super::super::selection::handle_cancel(&mut state).unwrap();
+        assert!(state.pending_swap.is_none());
+
+        state.selected_position = Position::new(0, 1);
+        handle_swap_key(&mut state).unwrap();
+        assert_eq!(keycodes(&state, 0), ["KC_A", "KC_B", "KC_C"]);
+        assert!(!state.dirty);
+    }
+
+    #[test]
+    fn test_cut_in_one_pane_pastes_into_the_other() {
+        let mut state = create_state();
+        assert!(state.toggle_split_view());
+        handle_cut_key(&mut state).unwrap();
+
+        assert_eq!(
+            state.focus_other_pane(),
+            Some(crate::tui::split_view::PaneSide::Right)
+        );
+        assert_eq!(state.current_layer, 1);
+        state.selected_position = Position::new(0, 2);
+        handle_paste_key(&mut state).unwrap();
+        assert_eq!(keycodes(&state, 0), ["KC_TRNS", "KC_B", "KC_C"]);
+        assert_eq!(keycodes(&state, 1), ["KC_1", "KC_2", "KC_A"]);
+        assert_eq!(state.flash_highlight, Some((1, Position::new(0, 2), 5)));
+    }
+
+    #[test]
+    fn test_each_pane_keeps_its_own_selection() {
+        let mut state = create_state();
+        state.toggle_split_view();
+        super::super::selection::handle_toggle_selection_mode(&mut state).unwrap();
+
+        state.focus_other_pane();
+        assert!(state.selection_mode.is_none());
+        assert!(state.selected_keys.is_empty());
+        state.selected_position = Position::new(0, 1);
+        handle_copy_key(&mut state).unwrap();
+        assert_eq!(
+            state.clipboard.get_content().map(|c| c.keycode.as_str()),
+            Some("KC_2")
+        );
+
+        state.focus_other_pane();
+        assert_eq!(state.current_layer, 0);
+        assert_eq!(state.selected_keys, [Position::new(0, 0)]);
+        handle_clear_key(&mut state).unwrap();
+        assert_eq!(keycodes(&state, 0), ["KC_TRNS", "KC_B", "KC_C"]);
+        assert_eq!(keycodes(&state, 1), ["KC_1", "KC_2", "KC_3"]);
+
+        // Closing keeps the focused pane
+        state.focus_other_pane();
+        assert!(!state.toggle_split_view());
+        assert_eq!(
+            (state.current_layer, state.selected_position),
+            (1, Position::new(0, 1))
+        );
+    }
+}
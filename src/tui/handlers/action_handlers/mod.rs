@@ -24,5 +24,8 @@ pub mod navigation;
 /// Popup and overlay management handlers
 pub mod popups;
 
+/// Action registry mapping every action to its handler
+pub mod registry;
+
 /// Key selection mode handlers
 pub mod selection;
@@ -1,6 +1,10 @@
 // Navigation action handlers
 
 use crate::models::{Position, VisualLayoutMapping};
+use crate::shortcuts::Action;
+use crate::tui::handlers::tutorial;
+use crate::tui::key_repeat::NetMove;
+use crate::tui::keyboard::KeyboardWidget;
 use crate::tui::AppState;
 use crate::tui::SelectionMode;
 use anyhow::Result;
@@ -29,6 +33,43 @@ fn calculate_rectangle_selection(
     selected
 }
 
+/// Apply coalesced navigation, one key at a time so each step follows the
+/// keyboard's geometry like a single keypress would.
+pub fn apply_net_move(state: &mut AppState, net: NetMove) -> Result<bool> {
+    let vertical = if net.dy < 0 {
+        Action::NavigateUp
+    } else {
+        Action::NavigateDown
+    };
+    let horizontal = if net.dx < 0 {
+        Action::NavigateLeft
+    } else {
+        Action::NavigateRight
+    };
+    for _ in 0..net.dy.unsigned_abs() {
+        if net.dy < 0 {
+            handle_navigate_up(state)?;
+        } else {
+            handle_navigate_down(state)?;
+        }
+    }
+    for _ in 0..net.dx.unsigned_abs() {
+        if net.dx < 0 {
+            handle_navigate_left(state)?;
+        } else {
+            handle_navigate_right(state)?;
+        }
+    }
+
+    // Coalesced moves bypass action dispatch, so report them to the tutorial here
+    for (steps, action) in [(net.dy, vertical), (net.dx, horizontal)] {
+        if steps != 0 {
+            tutorial::observe_action(state, action);
+        }
+    }
+    Ok(false)
+}
+
 /// Handle navigate up action
 pub fn handle_navigate_up(state: &mut AppState) -> Result<bool> {
     if let Some(new_pos) = state.mapping.find_position_up(state.selected_position) {
@@ -42,6 +83,18 @@ pub fn handle_navigate_up(state: &mut AppState) -> Result<bool> {
     Ok(false)
 }
 
+/// Select the key under the terminal cell (`column`, `row`)
+pub fn handle_click(state: &mut AppState, column: u16, row: u16) {
+    let area = state.keyboard_area.get();
+    if let Some(new_pos) = KeyboardWidget::key_at(area, state, column, row) {
+        state.selected_position = new_pos;
+        if let Some(SelectionMode::Rectangle { start }) = state.selection_mode {
+            state.selected_keys = calculate_rectangle_selection(start, new_pos, &state.mapping);
+        }
+        state.clear_error();
+    }
+}
+
 /// Handle navigate down action
 pub fn handle_navigate_down(state: &mut AppState) -> Result<bool> {
     if let Some(new_pos) = state.mapping.find_position_down(state.selected_position) {
@@ -102,6 +155,7 @@ pub fn handle_next_layer(state: &mut AppState) -> Result<bool> {
     } else {
         state.current_layer = 0;
     }
+    state.refresh_base_overlay();
     state.set_status(format!("Layer {}", state.current_layer));
     state.clear_error();
     Ok(false)
@@ -119,7 +173,102 @@ pub fn handle_previous_layer(state: &mut AppState) -> Result<bool> {
     } else {
         state.current_layer = state.layout.layers.len() - 1;
     }
+    state.refresh_base_overlay();
     state.set_status(format!("Layer {}", state.current_layer));
     state.clear_error();
     Ok(false)
 }
+
+/// Handle toggle base overlay action
+pub fn handle_toggle_base_overlay(state: &mut AppState) -> Result<bool> {
+    if state.base_overlay.take().is_some() {
+        state.set_status("Base layer overlay off");
+    } else {
+        state.base_overlay = Some(KeyboardWidget::base_layer_legends(state));
+        if state.current_layer == 0 {
+            state.set_status("Base layer overlay on - switch layers to see base legends");
+        } else {
+            state.set_status("Base layer overlay on - base legends shown under each key");
+        }
+    }
+    Ok(false)
+}
+
+/// Handle toggle mouse capture action
+pub fn handle_toggle_mouse_capture(state: &mut AppState) -> Result<bool> {
+    let enabled = !crate::tui::mouse_capture_enabled();
+    match crate::tui::set_mouse_capture(enabled) {
+        Ok(()) if enabled => state.set_status("Mouse capture on"),
+        Ok(()) => state.set_status("Mouse capture off - terminal text selection works again"),
+        Err(e) => state.set_error(format!("{e:#}")),
+    }
+    Ok(false)
+}
+
+/// Handle cycle coordinate overlay action
+pub fn handle_cycle_coordinate_overlay(state: &mut AppState) -> Result<bool> {
+    state.coordinate_overlay = state.coordinate_overlay.next();
+    state.set_status(format!(
+        "Coordinate overlay: {}",
+        state.coordinate_overlay.label()
+    ));
+    Ok(false)
+}
+
+/// Handle simulate typing action
+pub fn handle_simulate_typing(state: &mut AppState) -> Result<bool> {
+    crate::tui::handlers::simulation::start_simulation(state);
+    Ok(false)
+}
+
+/// Handle peek layer action
+pub fn handle_peek_layer(state: &mut AppState) -> Result<bool> {
+    crate::tui::handlers::peek::start_peek(state);
+    Ok(false)
+}
+
+/// Handle toggle split view action
+pub fn handle_toggle_split_view(state: &mut AppState) -> Result<bool> {
+    if state.toggle_split_view() {
+        let other = state
+            .split_view
+            .as_ref()
+            .map_or(0, |split| split.other.layer);
+        state.set_status(format!(
+            "Split view: layer {} | layer {other} - Ctrl+O: other pane, \\: close",
+            state.current_layer
+        ));
+    } else {
+        state.set_status(format!(
+            "Split view closed - on layer {}",
+            state.current_layer
+        ));
+    }
+    Ok(false)
+}
+
+/// Handle focus other pane action
+pub fn handle_focus_other_pane(state: &mut AppState) -> Result<bool> {
+    match state.focus_other_pane() {
+        Some(side) => state.set_status(format!(
+            "Editing the {} pane (layer {})",
+            side.label(),
+            state.current_layer
+        )),
+        None => state.set_error("Not in split view - press \\ to compare two layers"),
+    }
+    Ok(false)
+}
+
+/// Handle edit key role action
+pub fn handle_edit_key_role(state: &mut AppState) -> Result<bool> {
+    state.open_key_role_editor();
+    Ok(false)
+}
+
+/// Handle search keycode action
+pub fn handle_search_keycode(state: &mut AppState) -> Result<bool> {
+    state.open_keycode_search();
+    state.set_status("Search keys on every layer - Enter: jump, Esc: close");
+    Ok(false)
+}
@@ -39,6 +39,81 @@ pub fn handle_browse_templates(state: &mut AppState) -> Result<bool> {
     Ok(false)
 }
 
+/// Handle browse snapshots action
+pub fn handle_browse_snapshots(state: &mut AppState) -> Result<bool> {
+    state.open_snapshot_browser();
+    state.set_status("Snapshot Browser - Enter: restore, Esc: close");
+    Ok(false)
+}
+
+/// Handle validate layout action
+pub fn handle_validate_layout(state: &mut AppState) -> Result<bool> {
+    state.open_validation_results()?;
+    match state.active_component {
+        Some(crate::tui::ActiveComponent::ValidationResults(ref view)) if !view.is_empty() => {
+            let count = view.len();
+            state.set_status(format!(
+                "Validation: {count} issue(s) - Enter: jump to key, f: errors only, x: quick fix"
+            ));
+        }
+        _ => state.set_status("Validation passed - Esc: close"),
+    }
+    Ok(false)
+}
+
+/// Handle review validation action
+pub fn handle_review_validation(state: &mut AppState) -> Result<bool> {
+    if state.review_validation_results() {
+        state.set_status("Last validation results - Enter: jump to key, Esc: close");
+    } else {
+        state.set_error("No validation results yet - press Shift+F or Ctrl+G to validate");
+    }
+    Ok(false)
+}
+
+/// Handle hide validation banner action
+pub fn handle_hide_validation_banner(state: &mut AppState) -> Result<bool> {
+    state.validation_banner.hide();
+    state.set_status(
+        "Validation banner hidden for this session - turn it off for good in Settings (Shift+S)",
+    );
+    Ok(false)
+}
+
+/// Handle lint layout action
+pub fn handle_lint_layout(state: &mut AppState) -> Result<bool> {
+    state.open_lint_report();
+    let count = match state.active_component {
+        Some(crate::tui::ActiveComponent::LintReport(ref view)) => view.report().findings.len(),
+        _ => 0,
+    };
+    state.set_status(format!(
+        "Lint: {count} finding(s) - Enter: jump to key, Esc: close"
+    ));
+    Ok(false)
+}
+
+/// Handle view action log action
+pub fn handle_view_action_log(state: &mut AppState) -> Result<bool> {
+    state.open_action_log();
+    state.set_status(format!(
+        "Action log: {} entries - Esc: close",
+        state.action_log.len()
+    ));
+    Ok(false)
+}
+
+/// Handle view history action
+pub fn handle_view_history(state: &mut AppState) -> Result<bool> {
+    state.open_history();
+    let count = match state.active_component {
+        Some(crate::tui::ActiveComponent::History(ref view)) => view.run_count(),
+        _ => 0,
+    };
+    state.set_status(format!("History: {count} run(s) - Esc: close"));
+    Ok(false)
+}
+
 /// Handle toggle help action
 pub fn handle_toggle_help(state: &mut AppState) -> Result<bool> {
     if state.active_popup == Some(PopupType::HelpOverlay) {
@@ -66,6 +141,11 @@ pub fn handle_view_build_log(state: &mut AppState) -> Result<bool> {
 
 /// Handle open keycode picker action
 pub fn handle_open_keycode_picker(state: &mut AppState) -> Result<bool> {
+    if let Some(index) = state.selected_encoder() {
+        state.open_encoder_editor(index);
+        state.set_status("Encoder editor - Enter: Pick, Ctrl+S: Save");
+        return Ok(false);
+    }
     match get_selected_key_info(state) {
         Some((key, true)) => {
             // Key is assigned - open key editor
@@ -130,3 +210,9 @@ pub fn handle_open_tap_dance_editor(state: &mut AppState) -> Result<bool> {
     state.set_status("Tap Dance Editor - n: new, d: delete, Enter: select, Esc: cancel");
     Ok(false)
 }
+
+/// Handle open command palette action
+pub fn handle_open_command_palette(state: &mut AppState) -> Result<bool> {
+    state.open_command_palette();
+    Ok(false)
+}
@@ -0,0 +1,298 @@
+// Action registry shared by key dispatch, help, and the command palette
+
+use crate::shortcuts::{Action, ShortcutRegistry};
+use crate::tui::help_registry::HelpRegistry;
+use crate::tui::AppState;
+use anyhow::Result;
+
+use super::{category, color, file_ops, firmware, key_ops, layout, navigation, popups, selection};
+
+/// Signature shared by all action handlers
+pub type ActionHandler = fn(&mut AppState) -> Result<bool>;
+
+/// An action paired with the handler that executes it
+#[derive(Debug, Clone, Copy)]
+pub struct ActionEntry {
+    /// The action
+    pub action: Action,
+    /// Handler invoked when the action is triggered
+    pub handler: ActionHandler,
+}
+
+/// Shorthand for building registry entries
+const fn entry(action: Action, handler: ActionHandler) -> ActionEntry {
+    ActionEntry { action, handler }
+}
+
+/// Every action the main view can execute, in display order.
+pub const ACTIONS: &[ActionEntry] = &[
+    // Navigation
+    entry(Action::NavigateUp, navigation::handle_navigate_up),
+    entry(Action::NavigateDown, navigation::handle_navigate_down),
+    entry(Action::NavigateLeft, navigation::handle_navigate_left),
+    entry(Action::NavigateRight, navigation::handle_navigate_right),
+    entry(Action::JumpToFirst, navigation::handle_jump_to_first),
+    entry(Action::JumpToLast, navigation::handle_jump_to_last),
+    entry(Action::SearchKeycode, navigation::handle_search_keycode),
+    entry(Action::NextLayer, navigation::handle_next_layer),
+    entry(Action::PreviousLayer, navigation::handle_previous_layer),
+    entry(
+        Action::ToggleBaseOverlay,
+        navigation::handle_toggle_base_overlay,
+    ),
+    entry(
+        Action::CycleCoordinateOverlay,
+        navigation::handle_cycle_coordinate_overlay,
+    ),
+    entry(Action::SimulateTyping, navigation::handle_simulate_typing),
+    entry(Action::PeekLayer, navigation::handle_peek_layer),
+    entry(
+        Action::ToggleSplitView,
+        navigation::handle_toggle_split_view,
+    ),
+    entry(Action::FocusOtherPane, navigation::handle_focus_other_pane),
+    entry(Action::EditKeyRole, navigation::handle_edit_key_role),
+    // Key operations
+    entry(
+        Action::OpenKeycodePicker,
+        popups::handle_open_keycode_picker,
+    ),
+    entry(Action::ClearKey, key_ops::handle_clear_key),
+    entry(Action::CopyKey, key_ops::handle_copy_key),
+    entry(Action::CutKey, key_ops::handle_cut_key),
+    entry(Action::PasteKey, key_ops::handle_paste_key),
+    entry(Action::PasteGrid, key_ops::handle_paste_grid),
+    entry(Action::SwapKey, key_ops::handle_swap_key),
+    entry(Action::Undo, key_ops::handle_undo),
+    entry(Action::Redo, key_ops::handle_redo),
+    entry(Action::ToggleCurrentKey, key_ops::handle_toggle_current_key),
+    // Selection
+    entry(
+        Action::ToggleSelectionMode,
+        selection::handle_toggle_selection_mode,
+    ),
+    entry(
+        Action::StartRectangleSelect,
+        selection::handle_start_rectangle_select,
+    ),
+    entry(Action::MirrorSelection, selection::handle_mirror_selection),
+    entry(Action::MirrorLayer, selection::handle_mirror_layer),
+    entry(
+        Action::WrapSelectionInModifier,
+        selection::handle_wrap_selection,
+    ),
+    // Colors
+    entry(
+        Action::SetIndividualKeyColor,
+        color::handle_set_individual_key_color,
+    ),
+    entry(Action::SetLayerColor, color::handle_set_layer_color),
+    entry(Action::ToggleLayerColors, color::handle_toggle_layer_colors),
+    entry(
+        Action::ToggleAllLayerColors,
+        color::handle_toggle_all_layer_colors,
+    ),
+    // Categories
+    entry(
+        Action::OpenCategoryManager,
+        popups::handle_open_category_manager,
+    ),
+    entry(
+        Action::AssignCategoryToKey,
+        category::handle_assign_category_to_key,
+    ),
+    entry(
+        Action::AssignCategoryToLayer,
+        category::handle_assign_category_to_layer,
+    ),
+    entry(
+        Action::QuickAssignCategory(1),
+        category::handle_quick_assign_category::<1>,
+    ),
+    entry(
+        Action::QuickAssignCategory(2),
+        category::handle_quick_assign_category::<2>,
+    ),
+    entry(
+        Action::QuickAssignCategory(3),
+        category::handle_quick_assign_category::<3>,
+    ),
+    entry(
+        Action::QuickAssignCategory(4),
+        category::handle_quick_assign_category::<4>,
+    ),
+    entry(
+        Action::QuickAssignCategory(5),
+        category::handle_quick_assign_category::<5>,
+    ),
+    entry(
+        Action::QuickAssignCategory(6),
+        category::handle_quick_assign_category::<6>,
+    ),
+    entry(
+        Action::QuickAssignCategory(7),
+        category::handle_quick_assign_category::<7>,
+    ),
+    entry(
+        Action::QuickAssignCategory(8),
+        category::handle_quick_assign_category::<8>,
+    ),
+    entry(
+        Action::QuickAssignCategory(9),
+        category::handle_quick_assign_category::<9>,
+    ),
+    entry(
+        Action::QuickAssignCategory(0),
+        category::handle_quick_assign_category::<0>,
+    ),
+    // Managers & dialogs
+    entry(Action::OpenLayerManager, popups::handle_open_layer_manager),
+    entry(Action::OpenSettings, popups::handle_open_settings),
+    entry(Action::EditMetadata, popups::handle_edit_metadata),
+    entry(
+        Action::OpenTapDanceEditor,
+        popups::handle_open_tap_dance_editor,
+    ),
+    // File operations
+    entry(Action::Save, file_ops::handle_save),
+    entry(Action::ExportLayout, file_ops::handle_export_layout),
+    entry(Action::Quit, file_ops::handle_quit),
+    // Build & firmware
+    entry(Action::BuildFirmware, firmware::handle_build_firmware),
+    entry(Action::GenerateFirmware, firmware::handle_generate_firmware),
+    entry(Action::ViewBuildLog, popups::handle_view_build_log),
+    entry(Action::LintLayout, popups::handle_lint_layout),
+    entry(Action::ValidateLayout, popups::handle_validate_layout),
+    entry(Action::ReviewValidation, popups::handle_review_validation),
+    entry(
+        Action::HideValidationBanner,
+        popups::handle_hide_validation_banner,
+    ),
+    // Templates
+    entry(Action::BrowseTemplates, popups::handle_browse_templates),
+    entry(Action::SaveAsTemplate, file_ops::handle_save_as_template),
+    // Snapshots
+    entry(Action::CreateSnapshot, file_ops::handle_create_snapshot),
+    entry(Action::BrowseSnapshots, popups::handle_browse_snapshots),
+    // Configuration
+    entry(Action::SetupWizard, popups::handle_setup_wizard),
+    entry(
+        Action::SwitchLayoutVariant,
+        layout::handle_switch_layout_variant,
+    ),
+    entry(
+        Action::ToggleMouseCapture,
+        navigation::handle_toggle_mouse_capture,
+    ),
+    // Help
+    entry(Action::ToggleHelp, popups::handle_toggle_help),
+    entry(
+        Action::OpenCommandPalette,
+        popups::handle_open_command_palette,
+    ),
+    entry(Action::ViewActionLog, popups::handle_view_action_log),
+    entry(Action::ViewHistory, popups::handle_view_history),
+    // General
+    entry(Action::Cancel, selection::handle_cancel),
+];
+
+/// Look up the handler for an action
+#[must_use]
+pub fn handler_for(action: Action) -> Option<ActionHandler> {
+    ACTIONS
+        .iter()
+        .find(|entry| entry.action == action)
+        .map(|entry| entry.handler)
+}
+
+/// An action resolved against its help text and current key bindings
+#[derive(Debug, Clone)]
+pub struct RegisteredAction {
+    /// The action
+    pub action: Action,
+    /// Human-readable name from help.toml (falls back to the action ID)
+    pub name: String,
+    /// Description from help.toml
+    pub description: String,
+    /// Current key bindings in the main context, formatted for display
+    pub keys: Vec<String>,
+}
+
+/// Resolve every registered action against help text and key bindings
+#[must_use]
+pub fn registered_actions(
+    shortcuts: &ShortcutRegistry,
+    help: &HelpRegistry,
+) -> Vec<RegisteredAction> {
+    ACTIONS
+        .iter()
+        .map(|entry| {
+            let (name, description) = help.get_action(entry.action.id()).map_or_else(
+                || (entry.action.id().to_string(), String::new()),
+                |h| (h.name.clone(), h.description.clone()),
+            );
+
+            let mut keys: Vec<String> = Vec::new();
+            for binding in shortcuts.bindings_for("main", entry.action) {
+                let label = binding.label();
+                if !keys.contains(&label) {
+                    keys.push(label);
+                }
+            }
+
+            RegisteredAction {
+                action: entry.action,
+                name,
+                description,
+                keys,
+            }
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::collections::HashSet;
+
+    #[test]
+    fn test_actions_are_unique() {
+        let mut seen = HashSet::new();
+        for entry in ACTIONS {
+            assert!(
+                seen.insert(entry.action),
+                "{:?} registered twice",
+                entry.action
+            );
+        }
+    }
+
+    #[test]
+    fn test_every_action_has_help_text() {
+        let help = HelpRegistry::default();
+        for entry in ACTIONS {
+            assert!(
+                help.get_action(entry.action.id()).is_some(),
+                "Missing [actions.{}] in help.toml",
+                entry.action.id()
+            );
+        }
+    }
+
+    #[test]
+    fn test_registered_actions_include_bindings() {
+        let actions = registered_actions(&ShortcutRegistry::new(), &HelpRegistry::default());
+        assert_eq!(actions.len(), ACTIONS.len());
+
+        let save = actions.iter().find(|a| a.action == Action::Save).unwrap();
+        assert_eq!(save.name, "Save layout");
+        assert_eq!(save.keys, vec!["Ctrl+S"]);
+
+        // Tab+Shift and BackTab both display as Shift+Tab
+        let prev = actions
+            .iter()
+            .find(|a| a.action == Action::PreviousLayer)
+            .unwrap();
+        assert_eq!(prev.keys, vec!["Shift+Tab"]);
+    }
+}
@@ -1,5 +1,8 @@
 // Selection action handlers
 
+use crate::services::mirror::{MirrorDirection, MirrorPlan};
+use crate::tui::handlers::mirror::start_mirror_preview;
+use crate::tui::handlers::modifier_wrap::start_wrap_prompt;
 use crate::tui::{AppState, SelectionMode};
 use anyhow::Result;
 
@@ -41,3 +44,90 @@ pub fn handle_start_rectangle_select(state: &mut AppState) -> Result<bool> {
     }
     Ok(false)
 }
+
+/// Handle mirror selection action
+///
+/// Pairs the selected keys (or the current key) with their counterparts on
+/// the other half and highlights the targets until Enter applies the mirror.
+pub fn handle_mirror_selection(state: &mut AppState) -> Result<bool> {
+    let sources = if state.selection_mode.is_some() && !state.selected_keys.is_empty() {
+        state.selected_keys.clone()
+    } else {
+        vec![state.selected_position]
+    };
+    let plan = MirrorPlan::new(
+        &state.geometry,
+        &state.mapping,
+        state.current_layer,
+        &sources,
+        true,
+    );
+    if plan.pairs.is_empty() {
+        state.set_error("Nothing to mirror: no selected key has a symmetric counterpart");
+        return Ok(false);
+    }
+    start_mirror_preview(state, plan);
+    Ok(false)
+}
+
+/// Handle mirror layer action
+///
+/// Pairs every key on the half the cursor is on with its counterpart on the
+/// other half and previews the targets like a mirrored selection.
+pub fn handle_mirror_layer(state: &mut AppState) -> Result<bool> {
+    let Some(direction) =
+        MirrorDirection::from_side(&state.geometry, &state.mapping, state.selected_position)
+    else {
+        state.set_error("Move to a key on the half to copy from; this key is on the center line");
+        return Ok(false);
+    };
+    let plan = MirrorPlan::for_layer(
+        &state.geometry,
+        &state.mapping,
+        state.current_layer,
+        direction,
+        true,
+    );
+    if plan.pairs.is_empty() {
+        state.set_error("Nothing to mirror: no key on this half has a symmetric counterpart");
+        return Ok(false);
+    }
+    start_mirror_preview(state, plan);
+    Ok(false)
+}
+
+/// Handle wrap selection in modifier action
+///
+/// Prompts for the modifier to wrap the selected keys (or the current key)
+/// in; keys already wrapped in it are unwrapped.
+pub fn handle_wrap_selection(state: &mut AppState) -> Result<bool> {
+    let positions = if state.selection_mode.is_some() && !state.selected_keys.is_empty() {
+        state.selected_keys.clone()
+    } else {
+        vec![state.selected_position]
+    };
+    start_wrap_prompt(state, positions);
+    Ok(false)
+}
+
+/// Handle cancel action (Escape)
+///
+/// Cancels, in order of precedence: selection mode, a pending cut, or
+/// clipboard contents.
+pub fn handle_cancel(state: &mut AppState) -> Result<bool> {
+    if state.pending_swap.take().is_some() {
+        state.set_status("Swap cancelled");
+    } else if state.selection_mode.is_some() {
+        state.selection_mode = None;
+        state.selected_keys.clear();
+        state.set_status("Selection cancelled");
+    } else if state.clipboard.is_cut() {
+        state.clipboard.cancel_cut();
+        state.set_status("Cut cancelled");
+    } else if state.clipboard.has_content() {
+        // Clear clipboard if there's content but no active cut
+        state.clipboard.clear();
+        state.set_status("Clipboard cleared");
+    }
+    Ok(false)
+}
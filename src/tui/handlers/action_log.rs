@@ -0,0 +1,29 @@
+//! Action log input handlers.
+
+use anyhow::Result;
+use crossterm::event;
+
+use crate::tui::{action_log::ActionLogEvent, component::ContextualComponent, AppState};
+
+/// Handle input for the action log
+pub fn handle_action_log_input(state: &mut AppState, key: event::KeyEvent) -> Result<bool> {
+    use crate::tui::ActiveComponent;
+
+    if let Some(ActiveComponent::ActionLog(ref mut view)) = state.active_component {
+        if let Some(event) = view.handle_input(key, &state.action_log) {
+            return handle_action_log_event(state, event);
+        }
+    }
+    Ok(false)
+}
+
+/// Handle action log events
+fn handle_action_log_event(state: &mut AppState, event: ActionLogEvent) -> Result<bool> {
+    match event {
+        ActionLogEvent::Closed => {
+            state.close_component();
+            state.set_status("Action log closed");
+        }
+    }
+    Ok(false)
+}
@@ -2,65 +2,206 @@
 
 use anyhow::Result;
 
-use crate::firmware::BuildState;
+use crate::firmware::builder::LogLevel;
+use crate::firmware::{environment, preflight};
+use crate::firmware::{Backend, BuildState, BuildTarget, CompletionHooks, McuSpec, SizeEstimate};
+use crate::models::Layout;
+use crate::parser::keyboard_json::parse_keyboard_info_json;
+use crate::services::history::{HistoryEntry, HistoryOperation};
 use crate::shortcuts::Action;
-use crate::tui::AppState;
+use crate::tui::generation_results::{GenerationResult, GenerationResultsView};
+use crate::tui::{messages, notify, AppState, FirmwareAction, PopupType};
 
 use super::action_handlers;
 
-use action_handlers::{
-    category, color, file_ops, firmware, key_ops, layout, navigation, popups, selection,
-};
+/// Opens the keyboard mismatch prompt when the layout's keyboard differs
+/// from `build.keyboard` in the config.
+///
+/// Returns whether `action` now waits for the user's confirmation.
+fn confirm_keyboard_target(state: &mut AppState, action: FirmwareAction) -> bool {
+    // An unresolvable target is reported by the action itself
+    let Ok(target) = BuildTarget::resolve(
+        None,
+        None,
+        None,
+        &state.layout.metadata,
+        &state.config.build,
+    ) else {
+        return false;
+    };
+    let Some(mismatch) = preflight::check_keyboard_target(
+        &state.layout.metadata,
+        None,
+        &state.config.build,
+        &preflight::keymap_target_path(&state.config, &target),
+    ) else {
+        return false;
+    };
+    state.keyboard_mismatch = Some((mismatch, action));
+    state.active_popup = Some(PopupType::KeyboardMismatch);
+    state.set_status("Layout keyboard differs from the build target - y: continue, n: cancel");
+    true
+}
+
+/// Opens the stale generation prompt when the layout changed since the last
+/// generation, so the build doesn't silently compile something else than
+/// what was reviewed.
+///
+/// Returns whether the build now waits for the user's answer.
+fn confirm_regeneration(state: &mut AppState) -> bool {
+    let Some(hash) = &state.generation_hash else {
+        return false;
+    };
+    if *hash == state.layout.content_hash() {
+        return false;
+    }
+    state.active_popup = Some(PopupType::StaleGeneration);
+    state.set_status(
+        "Layout changed since last generation - y: regenerate and build, n: build last generated files, Esc: cancel",
+    );
+    true
+}
 
-/// Handle firmware generation with validation
+/// Handle firmware generation with validation, asking first if the layout is
+/// for another keyboard than the build target
 pub(super) fn handle_firmware_generation(state: &mut AppState) -> Result<()> {
-    use crate::firmware::{FirmwareGenerator, FirmwareValidator};
+    if confirm_keyboard_target(state, FirmwareAction::Generate) {
+        return Ok(());
+    }
+    run_firmware_generation(state)
+}
 
-    // Step 1: Validate layout
+/// Validates and generates firmware files without the keyboard check
+pub(super) fn run_firmware_generation(state: &mut AppState) -> Result<()> {
+    let layout = state.layout.clone();
+    let mut entry = state.history_entry(HistoryOperation::Generate);
+    if let Some(results) = generate_firmware(state, &layout, &mut entry)? {
+        let results = if state.dirty {
+            results.with_unsaved_changes()
+        } else {
+            results
+        };
+        state.show_generation_results(results);
+    }
+    state.record_history(&entry);
+    Ok(())
+}
+
+/// Validates and generates firmware files for `layout`, noting warnings and
+/// failures in the history `entry`.
+///
+/// Returns the generated files or the generation error, or `None` if
+/// validation failed (the validation results are shown instead).
+fn generate_firmware(
+    state: &mut AppState,
+    layout: &Layout,
+    entry: &mut HistoryEntry,
+) -> Result<Option<GenerationResultsView>> {
+    use crate::firmware::FirmwareGenerator;
+
+    // Step 1: Validate layout (cached while the layout is unchanged)
     state.set_status("Validating layout...");
 
-    let validator = FirmwareValidator::new(
-        &state.layout,
+    let report = state.validation_cache.validate(
+        layout,
         &state.geometry,
         &state.mapping,
         &state.keycode_db,
-    );
-    let report = validator.validate()?;
+    )?;
+    entry.warnings = report.warnings.len();
 
     if !report.is_valid() {
-        // Show validation errors
-        let error_msg = report.format_message();
-        state.set_error(format!("Validation failed:\n{error_msg}"));
-        return Ok(());
+        // Show validation errors in the results popup
+        let errors = report.errors.len();
+        entry.fail(&format!("Validation failed: {errors} error(s)"));
+        state.show_validation_results(report);
+        state.set_error(format!(
+            "Validation failed: {errors} error(s) - Enter: jump to key, Shift+G: reopen results"
+        ));
+        return Ok(None);
     }
 
     // Step 2: Generate firmware files
     state.set_status("Generating firmware files...");
 
     let generator = FirmwareGenerator::new(
-        &state.layout,
+        layout,
         &state.geometry,
         &state.mapping,
         &state.config,
         &state.keycode_db,
     );
 
-    match generator.generate() {
-        Ok((keymap_path, config_path)) => {
-            state.set_status(format!("✓ Generated: {keymap_path}, {config_path}"));
+    let result = match generator.generate() {
+        Ok(outcome) => {
+            state.generation_hash = Some(state.layout.content_hash());
+            GenerationResult::Written(outcome)
         }
         Err(e) => {
-            state.set_error(format!("Generation failed: {e}"));
+            entry.fail(&format!("Generation failed: {e}"));
+            GenerationResult::from_error(&e)
         }
-    }
-
-    Ok(())
+    };
+    Ok(Some(GenerationResultsView::new(result, entry.warnings)))
 }
 
 /// Handle firmware build in background
+///
+/// Builds the keyboard and keymap from the open layout's metadata, falling
+/// back to `build.keyboard`/`build.keymap` in the config when it has none.
+/// Asks first if the layout is for another keyboard than `build.keyboard`,
+/// then if it changed since the last generation.
 pub(super) fn handle_firmware_build(state: &mut AppState) -> Result<()> {
-    // Generate firmware files first (keymap.c, config.h)
-    handle_firmware_generation(state)?;
+    if confirm_keyboard_target(state, FirmwareAction::Build) {
+        return Ok(());
+    }
+    continue_firmware_build(state)
+}
+
+/// Starts the build once the keyboard target is confirmed, asking first if
+/// the layout changed since the last generation
+pub(super) fn continue_firmware_build(state: &mut AppState) -> Result<()> {
+    if confirm_regeneration(state) {
+        return Ok(());
+    }
+    run_firmware_build(state, true)
+}
+
+/// Starts the build without asking, regenerating the firmware files first
+/// unless `regenerate` is off and the last generated files are built as-is
+pub(super) fn run_firmware_build(state: &mut AppState, regenerate: bool) -> Result<()> {
+    let target = match BuildTarget::resolve(
+        None,
+        None,
+        None,
+        &state.layout.metadata,
+        &state.config.build,
+    ) {
+        Ok(target) => target,
+        Err(e) => {
+            state.set_error(format!("Cannot build: {e}"));
+            return Ok(());
+        }
+    };
+
+    // Generate firmware files first (keymap.c, config.h) into the target's keymap directory
+    let mut entry = state.history_entry(HistoryOperation::Build);
+    if regenerate {
+        let mut layout = state.layout.clone();
+        target.apply_to(&mut layout.metadata);
+        match generate_firmware(state, &layout, &mut entry)? {
+            Some(results) if results.succeeded() => state.set_status(results.summary()),
+            Some(results) => {
+                state.show_generation_results(results);
+                state.record_history(&entry);
+                return Ok(());
+            }
+            None => {
+                state.record_history(&entry);
+                return Ok(());
+            }
+        }
+    }
 
     // Check that QMK firmware path is configured
     let qmk_path = if let Some(path) = &state.config.paths.qmk_firmware {
@@ -86,7 +227,7 @@ pub(super) fn handle_firmware_build(state: &mut AppState) -> Result<()> {
     // Determine correct keyboard variant path for building
     // This ensures we target the specific variant (e.g. "keebart/corne_choc_pro/standard")
     // so that QMK loads the correct configuration (including RGB settings)
-    let keyboard = state.layout.metadata.keyboard.as_deref().unwrap_or("");
+    let keyboard = target.keyboard.as_str();
     let base_keyboard = AppState::extract_base_keyboard(keyboard);
     let key_count = state.geometry.keys.len();
 
@@ -100,97 +241,99 @@ pub(super) fn handle_firmware_build(state: &mut AppState) -> Result<()> {
             keyboard.to_string()
         });
 
+    // Fail fast with the fix instead of raw make output when the toolchain
+    // or QMK tree is incomplete
+    if !state.skip_env_check {
+        if let Err(problem) = environment::ensure_build_environment(
+            &qmk_path,
+            Backend::resolve(state.config.build.backend, &qmk_path),
+            &build_keyboard,
+            target.converter.as_deref(),
+        ) {
+            state.set_error(format!("Cannot build: {problem}"));
+            return Ok(());
+        }
+    }
+
     // Start the build
-    let keymap = state
-        .layout
-        .metadata
-        .keymap_name
-        .clone()
-        .unwrap_or_else(|| "default".to_string());
-    build_state.start_build(qmk_path, build_keyboard, keymap)?;
+    let keymap = target.keymap.clone();
+    // Processor capacity for the size report and the pre-build estimate.
+    // A converter swaps the controller, so the board's processor no longer applies.
+    let mcu = parse_keyboard_info_json(&qmk_path, &build_keyboard)
+        .ok()
+        .filter(|_| target.converter.is_none())
+        .and_then(|info| info.processor)
+        .and_then(|processor| McuSpec::lookup(&processor));
+
+    // Subscribed per build so a changed notification setting applies to the next one
+    build_state.hooks = CompletionHooks::new();
+    if state.config.ui.build_notifications {
+        build_state.hooks.subscribe(notify::notify_build_finished);
+    }
+    build_state.backend = state.config.build.backend;
+    build_state.start_build(
+        qmk_path,
+        build_keyboard,
+        keymap,
+        target.converter.clone(),
+        mcu,
+    )?;
+    // Build output reaches the state through the event loop's message queue
+    if let Some(receiver) = build_state.receiver.take() {
+        messages::forward(receiver, state.messages.sender());
+    }
+    // Recorded with the outcome once the build finishes
+    state.pending_build_history = Some(entry);
+    let build_state = state.build_state.as_mut().unwrap();
+    if let Some(warning) = target.converter_warning() {
+        build_state
+            .log_lines
+            .push((LogLevel::Error, format!("Warning: {warning}")));
+    }
 
-    state.set_status("Build started - check status with Shift+B");
+    let overflow =
+        mcu.and_then(|mcu| SizeEstimate::for_layout(&state.layout).overflow_warning(mcu));
+    if let Some(warning) = overflow {
+        build_state
+            .log_lines
+            .push((LogLevel::Error, format!("Warning: {warning}")));
+        state.set_status_with_style(
+            format!("Build started for {target} - {warning}"),
+            state.theme.warning,
+        );
+    } else {
+        state.set_status(format!(
+            "Build started for {target} ({}) - check status with Shift+B",
+            describe_sources(&target)
+        ));
+    }
 
     Ok(())
 }
 
+/// Short note on where the build target came from, e.g. "from layout".
+fn describe_sources(target: &BuildTarget) -> String {
+    let sources = if target.keyboard_source == target.keymap_source {
+        format!("from {}", target.keyboard_source)
+    } else {
+        format!(
+            "keyboard from {}, keymap from {}",
+            target.keyboard_source, target.keymap_source
+        )
+    };
+    match &target.converter {
+        Some(converter) => format!("{sources}, converter {converter}"),
+        None => sources,
+    }
+}
+
 /// Dispatch action to appropriate handler
+///
+/// Handlers are looked up in the shared action registry so key bindings and
+/// the command palette execute actions identically.
 pub fn dispatch_action(state: &mut AppState, action: Action) -> Result<bool> {
-    match action {
-        // Navigation (8 actions)
-        Action::NavigateUp => navigation::handle_navigate_up(state),
-        Action::NavigateDown => navigation::handle_navigate_down(state),
-        Action::NavigateLeft => navigation::handle_navigate_left(state),
-        Action::NavigateRight => navigation::handle_navigate_right(state),
-        Action::JumpToFirst => navigation::handle_jump_to_first(state),
-        Action::JumpToLast => navigation::handle_jump_to_last(state),
-        Action::NextLayer => navigation::handle_next_layer(state),
-        Action::PreviousLayer => navigation::handle_previous_layer(state),
-
-        // File operations (4 actions)
-        Action::Quit => file_ops::handle_quit(state),
-        Action::Save => file_ops::handle_save(state),
-        Action::ExportLayout => file_ops::handle_export_layout(state),
-        Action::SaveAsTemplate => file_ops::handle_save_as_template(state),
-
-        // Popup management (10 actions)
-        Action::OpenKeycodePicker => popups::handle_open_keycode_picker(state),
-        Action::OpenLayerManager => popups::handle_open_layer_manager(state),
-        Action::OpenCategoryManager => popups::handle_open_category_manager(state),
-        Action::OpenSettings => popups::handle_open_settings(state),
-        Action::EditMetadata => popups::handle_edit_metadata(state),
-        Action::OpenTapDanceEditor => popups::handle_open_tap_dance_editor(state),
-        Action::SetupWizard => popups::handle_setup_wizard(state),
-        Action::BrowseTemplates => popups::handle_browse_templates(state),
-        Action::ViewBuildLog => popups::handle_view_build_log(state),
-        Action::ToggleHelp => popups::handle_toggle_help(state),
-
-        // Key operations (6 actions)
-        Action::ClearKey => key_ops::handle_clear_key(state),
-        Action::CopyKey => key_ops::handle_copy_key(state),
-        Action::CutKey => key_ops::handle_cut_key(state),
-        Action::PasteKey => key_ops::handle_paste_key(state),
-        Action::UndoPaste => key_ops::handle_undo_paste(state),
-        Action::ToggleCurrentKey => key_ops::handle_toggle_current_key(state),
-
-        // Selection (2 actions)
-        Action::ToggleSelectionMode => selection::handle_toggle_selection_mode(state),
-        Action::StartRectangleSelect => selection::handle_start_rectangle_select(state),
-
-        // Color management (4 actions)
-        Action::SetIndividualKeyColor => color::handle_set_individual_key_color(state),
-        Action::SetLayerColor => color::handle_set_layer_color(state),
-        Action::ToggleLayerColors => color::handle_toggle_layer_colors(state),
-        Action::ToggleAllLayerColors => color::handle_toggle_all_layer_colors(state),
-
-        // Category assignment (2 actions)
-        Action::AssignCategoryToKey => category::handle_assign_category_to_key(state),
-        Action::AssignCategoryToLayer => category::handle_assign_category_to_layer(state),
-
-        // Firmware (2 actions)
-        Action::BuildFirmware => firmware::handle_build_firmware(state),
-        Action::GenerateFirmware => firmware::handle_generate_firmware(state),
-
-        // Layout (1 action)
-        Action::SwitchLayoutVariant => layout::handle_switch_layout_variant(state),
-
-        // Cancel (1 action)
-        Action::Cancel => {
-            // Cancel selection/cut/clipboard (Escape)
-            #[allow(clippy::redundant_pattern_matching)]
-            if let Some(_) = state.selection_mode {
-                state.selection_mode = None;
-                state.selected_keys.clear();
-                state.set_status("Selection cancelled");
-            } else if state.clipboard.is_cut() {
-                state.clipboard.cancel_cut();
-                state.set_status("Cut cancelled");
-            } else if state.clipboard.has_content() {
-                // Clear clipboard if there's content but no active cut
-                state.clipboard.clear();
-                state.set_status("Clipboard cleared");
-            }
-            Ok(false)
-        }
-    }
+    let quit = action_handlers::registry::handler_for(action)
+        .map_or(Ok(false), |handler| handler(state))?;
+    super::tutorial::observe_action(state, action);
+    Ok(quit)
 }
@@ -19,6 +19,14 @@ pub fn handle_category_manager_input(state: &mut AppState, key: event::KeyEvent)
 
     // Update component with latest categories before handling input
     manager.set_categories(state.layout.categories.clone());
+    manager.set_usage(
+        state
+            .layout
+            .categories
+            .iter()
+            .map(|c| (c.id.clone(), state.layout.category_usage(&c.id)))
+            .collect(),
+    );
 
     // Handle special case: Shift+L to assign category to layer (not handled by component)
     #[allow(clippy::collapsible_if)]
@@ -30,10 +38,13 @@ pub fn handle_category_manager_input(state: &mut AppState, key: event::KeyEvent)
                 if let Some(layer) = state.layout.layers.get_mut(state.current_layer) {
                     layer.category_id = Some(category_id);
                     state.mark_dirty();
-                    state.set_status(format!(
-                        "Layer {} assigned to category '{}'",
-                        state.current_layer, category_name
-                    ));
+                    state.log_action(
+                        "Assign category",
+                        format!(
+                            "Layer {} assigned to category '{}'",
+                            state.current_layer, category_name
+                        ),
+                    );
                 }
             } else {
                 state.set_error("No category selected");
@@ -45,6 +56,19 @@ pub fn handle_category_manager_input(state: &mut AppState, key: event::KeyEvent)
         }
     }
 
+    // Handle special case: Ctrl+Z undoes the last edit (such as a deleted
+    // category) without leaving the manager
+    if key.code == event::KeyCode::Char('z')
+        && key.modifiers.contains(KeyModifiers::CONTROL)
+        && matches!(manager.state().mode, ManagerMode::Browsing)
+    {
+        crate::tui::handlers::action_handlers::key_ops::handle_undo(state)?;
+        manager.set_categories(state.layout.categories.clone());
+        state.category_manager_state = manager.state().clone();
+        state.active_component = Some(ActiveComponent::CategoryManager(manager));
+        return Ok(false);
+    }
+
     // Handle special case: 'c' to change color in browsing mode (opens color picker)
     if key.code == event::KeyCode::Char('c')
         && matches!(manager.state().mode, ManagerMode::Browsing)
@@ -71,23 +95,20 @@ pub fn handle_category_manager_input(state: &mut AppState, key: event::KeyEvent)
     if let Some(event) = event {
         match event {
             CategoryManagerEvent::CategoryDeleted(category_id) => {
-                // Delete category and clean up references (T111, T112)
-                state.layout.categories.retain(|c| c.id != category_id);
-
-                // Clean up references in layers and keys
-                for layer in &mut state.layout.layers {
-                    if layer.category_id.as_ref() == Some(&category_id) {
-                        layer.category_id = None;
-                    }
-                    for key in &mut layer.keys {
-                        if key.category_id.as_ref() == Some(&category_id) {
-                            key.category_id = None;
-                        }
-                    }
+                // Delete category and clean up references (T111, T112);
+                // Ctrl+Z restores them from the undo history
+                if let Some(deleted) = state.layout.delete_category(&category_id) {
+                    let name = deleted.category.name.clone();
+                    let usage = deleted.usage();
+                    state.mark_dirty();
+                    state.log_action(
+                        "Delete category",
+                        format!(
+                            "Deleted category '{name}' from {} key(s) and {} layer(s) - Ctrl+Z to undo",
+                            usage.keys, usage.layers
+                        ),
+                    );
                 }
-
-                state.mark_dirty();
-                state.set_status("Category deleted");
                 // Update component with new categories
                 manager.set_categories(state.layout.categories.clone());
             }
@@ -118,7 +139,7 @@ pub fn handle_category_manager_input(state: &mut AppState, key: event::KeyEvent)
                     state.mark_dirty();
                 }
                 if let Some(msg) = status_message {
-                    state.set_status(msg);
+                    state.log_action("Edit category", msg);
                 }
                 if let Some(err) = error_message {
                     state.set_error(err);
@@ -127,6 +148,21 @@ pub fn handle_category_manager_input(state: &mut AppState, key: event::KeyEvent)
                 // Update component with modified categories
                 manager.set_categories(state.layout.categories.clone());
             }
+            CategoryManagerEvent::FlagToggled { id, flag } => {
+                if let Some(category) = state.layout.categories.iter_mut().find(|c| c.id == id) {
+                    let on = !category.has_flag(flag);
+                    category.set_flag(flag, on);
+                    let message = format!(
+                        "Category '{}': {} {}",
+                        category.name,
+                        flag.name(),
+                        if on { "on" } else { "off" }
+                    );
+                    state.mark_dirty();
+                    state.log_action("Edit category", message);
+                }
+                manager.set_categories(state.layout.categories.clone());
+            }
             CategoryManagerEvent::Cancelled => {
                 state.set_status("Cancelled");
             }
@@ -0,0 +1,179 @@
+//! Color picker eyedropper input handlers.
+
+use crossterm::event::{self, KeyCode};
+
+use crate::tui::{ActiveComponent, AppState};
+
+/// Minimizes the open color picker and lets the board selection pick a key
+/// to sample.
+pub fn start_color_sampling(state: &mut AppState) {
+    if let Some(ActiveComponent::ColorPicker(picker)) = &mut state.active_component {
+        picker.start_sampling(state.selected_position);
+        state.set_status("Eyedropper: move to a key and press Enter to sample its color");
+    }
+}
+
+/// Whether the open color picker's eyedropper is active
+#[must_use]
+pub fn is_sampling(state: &AppState) -> bool {
+    matches!(
+        &state.active_component,
+        Some(ActiveComponent::ColorPicker(picker)) if picker.is_sampling()
+    )
+}
+
+/// Handle input while the eyedropper is active
+pub fn handle_color_sample_input(state: &mut AppState, key: event::KeyEvent) {
+    let position = state.selected_position;
+    let next = match key.code {
+        KeyCode::Up | KeyCode::Char('k') => state.mapping.find_position_up(position),
+        KeyCode::Down | KeyCode::Char('j') => state.mapping.find_position_down(position),
+        KeyCode::Left | KeyCode::Char('h') => state.mapping.find_position_left(position),
+        KeyCode::Right | KeyCode::Char('l') => state.mapping.find_position_right(position),
+        KeyCode::Enter => {
+            sample_selected_key(state);
+            return;
+        }
+        KeyCode::Esc => {
+            finish_sampling(state);
+            state.set_status("Eyedropper cancelled");
+            return;
+        }
+        _ => None,
+    };
+    if let Some(next) = next {
+        state.selected_position = next;
+    }
+}
+
+/// Copies the highlighted key's resolved color into the picker and returns
+/// to it, noting where the color came from.
+fn sample_selected_key(state: &mut AppState) {
+    let Some(key) = state.get_selected_key() else {
+        state.set_status("No key here to sample");
+        return;
+    };
+    let (color, source) = state
+        .layout
+        .resolve_key_color_source(state.current_layer, key);
+
+    finish_sampling(state);
+    if let Some(ActiveComponent::ColorPicker(picker)) = &mut state.active_component {
+        picker.set_color(color);
+    }
+    state.set_status(format!("Sampled {} ({})", color.to_hex(), source.label()));
+}
+
+/// Leaves the eyedropper, reselecting the key the picker was opened for.
+fn finish_sampling(state: &mut AppState) {
+    if let Some(ActiveComponent::ColorPicker(picker)) = &mut state.active_component {
+        if let Some(origin) = picker.finish_sampling() {
+            state.selected_position = origin;
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::models::{
+        Category, KeyDefinition, KeyGeometry, KeyboardGeometry, Layer, Layout, Position, RgbColor,
+        VisualLayoutMapping,
+    };
+    use crate::tui::component::ColorPickerContext;
+    use crate::tui::handlers::handle_popup_input;
+    use crossterm::event::{KeyEvent, KeyModifiers};
+
+    const RED: RgbColor = RgbColor::new(255, 0, 0);
+    const TEAL: RgbColor = RgbColor::new(0, 128, 128);
+
+    /// One row of three keys: plain, with an override, with a category
+    fn state() -> AppState {
+        let mut geometry = KeyboardGeometry::new("test", "test", 1, 3);
+        let mut layer = Layer::new(0, "Base", RgbColor::new(255, 255, 255)).unwrap();
+        for col in 0..3 {
+            geometry.add_key(KeyGeometry::new((0, col), col, f32::from(col), 0.0));
+            layer
+                .add_key(KeyDefinition::at(Position::new(0, col)).keycode("KC_A"))
+                .unwrap();
+        }
+        layer.keys[1].color_override = Some(RED);
+        layer.keys[2].category_id = Some("nav".to_string());
+        let mut layout = Layout::new("Test").unwrap();
+        layout
+            .categories
+            .push(Category::new("nav", "Nav", TEAL).unwrap());
+        layout.add_layer(layer).unwrap();
+        let mapping = VisualLayoutMapping::build(&geometry);
+        AppState::new(
+            layout,
+            None,
+            geometry,
+            mapping,
+            crate::config::Config::default(),
+        )
+        .unwrap()
+    }
+
+    fn press(state: &mut AppState, code: KeyCode) {
+        handle_popup_input(state, KeyEvent::new(code, KeyModifiers::NONE)).unwrap();
+    }
+
+    fn picker_color(state: &AppState) -> RgbColor {
+        match &state.active_component {
+            Some(ActiveComponent::ColorPicker(picker)) => picker.color(),
+            _ => panic!("color picker closed"),
+        }
+    }
+
+    #[test]
+    fn test_sample_copies_color_and_returns_to_picker() {
+        let mut state = state();
+        state.selected_position = Position::new(0, 0);
+        state.open_color_picker(ColorPickerContext::IndividualKey, RgbColor::default());
+
+        press(&mut state, KeyCode::Char('e'));
+        assert!(is_sampling(&state));
+        press(&mut state, KeyCode::Right);
+        press(&mut state, KeyCode::Right);
+        assert_eq!(state.selected_position, Position::new(0, 2));
+        press(&mut state, KeyCode::Enter);
+
+        assert!(!is_sampling(&state));
+        assert_eq!(picker_color(&state), TEAL);
+        assert_eq!(state.selected_position, Position::new(0, 0));
+        assert!(state.status_message.contains("key category"));
+
+        // Applying colors the original key, not the sampled one
+        press(&mut state, KeyCode::Enter);
+        assert_eq!(state.layout.layers[0].keys[0].color_override, Some(TEAL));
+    }
+
+    #[test]
+    fn test_escape_returns_without_sampling() {
+        let mut state = state();
+        state.selected_position = Position::new(0, 0);
+        state.open_color_picker(ColorPickerContext::LayerDefault, TEAL);
+
+        press(&mut state, KeyCode::Char('e'));
+        press(&mut state, KeyCode::Right);
+        press(&mut state, KeyCode::Esc);
+
+        assert!(!is_sampling(&state));
+        assert_eq!(picker_color(&state), TEAL);
+        assert_eq!(state.selected_position, Position::new(0, 0));
+    }
+
+    #[test]
+    fn test_sample_notes_override_source() {
+        let mut state = state();
+        state.selected_position = Position::new(0, 1);
+        state.open_color_picker(ColorPickerContext::Category, TEAL);
+
+        press(&mut state, KeyCode::Char('e'));
+        press(&mut state, KeyCode::Enter);
+
+        assert_eq!(picker_color(&state), RED);
+        assert!(state.status_message.contains("key override"));
+    }
+}
@@ -0,0 +1,29 @@
+//! Command palette input handlers.
+
+use anyhow::Result;
+use crossterm::event;
+
+use crate::tui::{command_palette::CommandPaletteEvent, component::Component, AppState};
+
+/// Handle input for command palette
+pub fn handle_command_palette_input(state: &mut AppState, key: event::KeyEvent) -> Result<bool> {
+    use crate::tui::ActiveComponent;
+
+    if let Some(ActiveComponent::CommandPalette(ref mut palette)) = state.active_component {
+        if let Some(event) = palette.handle_input(key) {
+            return match event {
+                CommandPaletteEvent::Execute(action) => {
+                    // Close first so the action runs against the main view,
+                    // exactly as if its key binding had been pressed there
+                    state.close_component();
+                    super::dispatch_action(state, action)
+                }
+                CommandPaletteEvent::Cancelled => {
+                    state.close_component();
+                    Ok(false)
+                }
+            };
+        }
+    }
+    Ok(false)
+}
@@ -0,0 +1,125 @@
+//! Diff mode input handlers.
+
+use anyhow::Result;
+use crossterm::event::{self, KeyCode};
+
+use crate::models::Layout;
+use crate::tui::{AppState, DiffView};
+
+/// Shows how the layout differs from `base` on the keyboard.
+///
+/// Jumps to the first layer with changed keys unless the current one has some.
+pub fn start_diff_view(state: &mut AppState, label: impl Into<String>, base: &Layout) {
+    let view = DiffView::new(label, base, &state.layout);
+    let layers = view.changed_layers();
+    if !layers.contains(&state.current_layer) {
+        if let Some(&first) = layers.first() {
+            state.current_layer = first;
+            state.refresh_base_overlay();
+        }
+    }
+    state.diff_view = Some(view);
+    show_diff_status(state);
+}
+
+/// Handle input while diff mode is shown
+pub fn handle_diff_view_input(state: &mut AppState, key: event::KeyEvent) -> Result<bool> {
+    match key.code {
+        KeyCode::Tab | KeyCode::BackTab => {
+            let forward = key.code == KeyCode::Tab;
+            let next = state
+                .diff_view
+                .as_ref()
+                .and_then(|view| view.cycle_layer(state.current_layer, forward));
+            if let Some(layer) = next {
+                state.current_layer = layer;
+                state.refresh_base_overlay();
+            }
+            show_diff_status(state);
+        }
+        KeyCode::Esc => {
+            state.diff_view = None;
+            state.set_status("Diff mode closed");
+        }
+        _ => {}
+    }
+    Ok(false)
+}
+
+/// Shows the per-layer summary and the diff mode keys in the status bar.
+fn show_diff_status(state: &mut AppState) {
+    let Some(view) = &state.diff_view else {
+        return;
+    };
+    let status = format!(
+        "Diff vs {}: {} - Tab/Shift+Tab: changed layers, Esc: exit",
+        view.label,
+        view.summary()
+    );
+    state.set_status(status);
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::models::{
+        KeyDefinition, KeyGeometry, KeyboardGeometry, Layer, Position, RgbColor,
+        VisualLayoutMapping,
+    };
+    use crossterm::event::KeyModifiers;
+
+    /// Three layers of a single transparent key
+    fn create_state() -> AppState {
+        let mut geometry = KeyboardGeometry::new("test", "test", 1, 1);
+        geometry.add_key(KeyGeometry::new((0, 0), 0, 0.0, 0.0));
+        let mut layout = Layout::new("Diff").unwrap();
+        for i in 0..3 {
+            let mut layer = Layer::new(i, "Layer", RgbColor::new(0, 0, 0)).unwrap();
+            layer
+                .add_key(KeyDefinition::new(Position::new(0, 0), "KC_TRNS"))
+                .unwrap();
+            layout.add_layer(layer).unwrap();
+        }
+        let mapping = VisualLayoutMapping::build(&geometry);
+        AppState::new(
+            layout,
+            None,
+            geometry,
+            mapping,
+            crate::config::Config::default(),
+        )
+        .unwrap()
+    }
+
+    fn press(state: &mut AppState, code: KeyCode) {
+        handle_diff_view_input(state, event::KeyEvent::new(code, KeyModifiers::NONE)).unwrap();
+    }
+
+    #[test]
+    fn test_diff_mode_jumps_to_changed_layers_and_exits() {
+        let mut state = create_state();
+        let base = state.layout.clone();
+        state.layout.layers[1].keys[0].keycode = "KC_A".to_string();
+        state.layout.layers[2].keys[0].keycode = "KC_B".to_string();
+
+        start_diff_view(&mut state, "saved file", &base);
+        assert_eq!(state.current_layer, 1);
+        assert!(state
+            .status_message
+            .contains("layer 1: 1 changed, layer 2: 1 changed"));
+
+        press(&mut state, KeyCode::Tab);
+        assert_eq!(state.current_layer, 2);
+        press(&mut state, KeyCode::Tab);
+        assert_eq!(state.current_layer, 1);
+        press(&mut state, KeyCode::BackTab);
+        assert_eq!(state.current_layer, 2);
+
+        // Editing keys are ignored while the diff is shown
+        press(&mut state, KeyCode::Char('d'));
+        assert!(state.diff_view.is_some());
+
+        press(&mut state, KeyCode::Esc);
+        assert!(state.diff_view.is_none());
+    }
+}
@@ -0,0 +1,51 @@
+//! Encoder editor input handlers.
+
+use anyhow::Result;
+use crossterm::event;
+
+use crate::tui::{component::Component, encoder_editor::EncoderEditorEvent};
+use crate::tui::{ActiveComponent, AppState};
+
+/// Handle input for the encoder editor popup
+pub fn handle_encoder_editor_input(state: &mut AppState, key: event::KeyEvent) -> Result<bool> {
+    if let Some(ActiveComponent::EncoderEditor(ref mut editor)) = state.active_component {
+        if let Some(event) = editor.handle_input(key) {
+            return handle_encoder_editor_event(state, event);
+        }
+    }
+    Ok(false)
+}
+
+/// Handle encoder editor events
+fn handle_encoder_editor_event(state: &mut AppState, event: EncoderEditorEvent) -> Result<bool> {
+    let Some(ActiveComponent::EncoderEditor(editor)) = state.active_component.take() else {
+        return Ok(false);
+    };
+    let index = editor.index();
+
+    match event {
+        EncoderEditorEvent::Pick(field) => {
+            // Keep the editor while the picker is open; the picked keycode
+            // goes back into it
+            state.encoder_editor_cache = Some(editor);
+            state.open_keycode_picker();
+            state.set_status(format!("Select keycode for a {} turn", field.label()));
+        }
+        EncoderEditorEvent::Saved(mapping) => {
+            if let Some(layer) = state.layout.layers.get_mut(state.current_layer) {
+                layer.set_encoder(usize::from(index), mapping.clone());
+                state.mark_dirty();
+                state.log_action(
+                    "Set encoder",
+                    format!("Encoder {index}: ↺ {}  ↻ {}", mapping.ccw, mapping.cw),
+                );
+            }
+            state.close_component();
+        }
+        EncoderEditorEvent::Cancelled => {
+            state.close_component();
+            state.set_status("Encoder unchanged");
+        }
+    }
+    Ok(false)
+}
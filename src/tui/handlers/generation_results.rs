@@ -0,0 +1,43 @@
+//! Firmware generation results input handlers.
+
+use anyhow::Result;
+use crossterm::event;
+
+use crate::tui::{component::Component, generation_results::GenerationResultsEvent, AppState};
+
+/// Handle input for the generation results popup
+pub fn handle_generation_results_input(state: &mut AppState, key: event::KeyEvent) -> Result<bool> {
+    use crate::tui::ActiveComponent;
+
+    if let Some(ActiveComponent::GenerationResults(ref mut view)) = state.active_component {
+        if let Some(event) = view.handle_input(key) {
+            return handle_generation_results_event(state, event);
+        }
+    }
+    Ok(false)
+}
+
+/// Handle generation results events
+fn handle_generation_results_event(
+    state: &mut AppState,
+    event: GenerationResultsEvent,
+) -> Result<bool> {
+    match event {
+        GenerationResultsEvent::Closed => {
+            state.close_component();
+            state.set_status("Generation results closed");
+        }
+        GenerationResultsEvent::OpenDirectory(dir) => match opener::open(&dir) {
+            Ok(()) => state.set_status(format!("Opened {}", dir.display())),
+            Err(e) => state.set_error(format!("Failed to open {}: {e}", dir.display())),
+        },
+        GenerationResultsEvent::CopyPath(path) => {
+            let text = path.display().to_string();
+            match arboard::Clipboard::new().and_then(|mut clipboard| clipboard.set_text(text)) {
+                Ok(()) => state.set_status(format!("Copied {}", path.display())),
+                Err(e) => state.set_error(format!("Failed to copy to clipboard: {e}")),
+            }
+        }
+    }
+    Ok(false)
+}
@@ -0,0 +1,197 @@
+//! Keycode grid paste input handlers.
+
+use anyhow::Result;
+use crossterm::event;
+use std::path::Path;
+
+use crate::services::keycode_grid::{CellStatus, GridPlan};
+use crate::tui::{component::Component, grid_paste::GridPasteEvent};
+use crate::tui::{ActiveComponent, AppState};
+
+/// Handle input for the grid paste popup
+pub fn handle_grid_paste_input(state: &mut AppState, key: event::KeyEvent) -> Result<bool> {
+    if let Some(ActiveComponent::GridPaste(ref mut view)) = state.active_component {
+        if let Some(event) = view.handle_input(key) {
+            return handle_grid_paste_event(state, event);
+        }
+    }
+    Ok(false)
+}
+
+/// Handle grid paste events
+fn handle_grid_paste_event(state: &mut AppState, event: GridPasteEvent) -> Result<bool> {
+    match event {
+        GridPasteEvent::Submitted(text) => {
+            // A single line naming a file reads the grid from that file
+            let path = Path::new(text.trim());
+            let text = if !text.contains('\n') && path.is_file() {
+                match std::fs::read_to_string(path) {
+                    Ok(content) => content,
+                    Err(e) => {
+                        state.set_error(format!("Failed to read {}: {e}", path.display()));
+                        return Ok(false);
+                    }
+                }
+            } else {
+                text
+            };
+            match state.plan_grid(&text) {
+                Ok(plan) => {
+                    if let Some(ActiveComponent::GridPaste(ref mut view)) = state.active_component {
+                        view.set_plan(plan);
+                    }
+                }
+                Err(e) => state.set_error(format!("Invalid keycode grid: {e}")),
+            }
+        }
+        GridPasteEvent::Apply => {
+            let plan = match state.active_component {
+                Some(ActiveComponent::GridPaste(ref view)) => view.plan().cloned(),
+                _ => None,
+            };
+            if let Some(plan) = plan {
+                apply_grid(state, &plan);
+            }
+        }
+        GridPasteEvent::Cancelled => {
+            state.close_component();
+            state.set_status("Grid paste cancelled");
+        }
+    }
+    Ok(false)
+}
+
+/// Applies the ready cells of `plan` to the current layer as one undo step.
+fn apply_grid(state: &mut AppState, plan: &GridPlan) {
+    let layer_idx = state.current_layer;
+    let Some(layer) = state.layout.layers.get_mut(layer_idx) else {
+        return;
+    };
+
+    let lands_on_key = plan.ready().any(|cell| {
+        cell.target
+            .is_some_and(|position| layer.get_key(position).is_some())
+    });
+    if !lands_on_key {
+        state.set_error("Nothing to paste: no cell has a valid keycode on a key");
+        return;
+    }
+
+    let applied = plan.apply(layer);
+    state.close_component();
+    state.flash_highlight = Some((layer_idx, plan.start, 5));
+    state.mark_dirty();
+    state.refresh_layer_refs();
+
+    let skipped = plan.count(CellStatus::InvalidKeycode) + plan.count(CellStatus::NoKey);
+    let summary = if skipped > 0 {
+        format!("Pasted {applied} key(s) onto layer {layer_idx}, skipped {skipped}")
+    } else {
+        format!("Pasted {applied} key(s) onto layer {layer_idx}")
+    };
+    state.log_action("Paste grid", summary);
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::models::{
+        KeyDefinition, KeyGeometry, KeyboardGeometry, Layer, Layout, Position, RgbColor,
+        VisualLayoutMapping,
+    };
+    use crate::tui::handlers::action_handlers::key_ops::handle_undo;
+    use crate::tui::PopupType;
+    use crossterm::event::{KeyCode, KeyEvent, KeyModifiers};
+
+    /// 2×3 layer of KC_TRNS with a matching visual mapping
+    fn grid_state() -> AppState {
+        let mut geometry = KeyboardGeometry::new("test", "test", 2, 3);
+        let mut layer = Layer::new(0, "Base", RgbColor::new(255, 255, 255)).unwrap();
+        for row in 0..2 {
+            for col in 0..3 {
+                geometry.add_key(KeyGeometry::new(
+                    (row, col),
+                    row * 3 + col,
+                    f32::from(col),
+                    f32::from(row),
+                ));
+                layer
+                    .add_key(KeyDefinition::at(Position::new(row, col)).keycode("KC_TRNS"))
+                    .unwrap();
+            }
+        }
+        let mut layout = Layout::new("Grid").unwrap();
+        layout.add_layer(layer).unwrap();
+        let mapping = VisualLayoutMapping::build(&geometry);
+        AppState::new(
+            layout,
+            None,
+            geometry,
+            mapping,
+            crate::config::Config::default(),
+        )
+        .unwrap()
+    }
+
+    fn keycodes(state: &AppState) -> Vec<&str> {
+        (0..2)
+            .flat_map(|row| (0..3).map(move |col| Position::new(row, col)))
+            .map(|pos| {
+                state.layout.layers[0]
+                    .get_key(pos)
+                    .unwrap()
+                    .keycode
+                    .as_str()
+            })
+            .collect()
+    }
+
+    fn press_key(state: &mut AppState, code: KeyCode, modifiers: KeyModifiers) {
+        handle_grid_paste_input(state, KeyEvent::new(code, modifiers)).unwrap();
+    }
+
+    #[test]
+    fn test_pasted_grid_applies_as_one_undo_step() {
+        let mut state = grid_state();
+        state.selected_position = Position::new(0, 1);
+
+        state.open_grid_paste(Some("KC_A NOT_A_KEY KC_C\nKC_D KC_E"));
+        assert_eq!(state.active_popup, Some(PopupType::GridPaste));
+
+        press_key(&mut state, KeyCode::Enter, KeyModifiers::NONE);
+        assert!(state.active_popup.is_none());
+        assert!(state.dirty);
+        // The invalid cell is skipped and KC_C runs off the end of row 0
+        assert_eq!(
+            keycodes(&state),
+            ["KC_TRNS", "KC_A", "KC_TRNS", "KC_TRNS", "KC_D", "KC_E"]
+        );
+
+        handle_undo(&mut state).unwrap();
+        assert!(keycodes(&state).iter().all(|k| *k == "KC_TRNS"));
+    }
+
+    #[test]
+    fn test_grid_read_from_file() {
+        let temp = tempfile::TempDir::new().unwrap();
+        let path = temp.path().join("grid.md");
+        std::fs::write(&path, "| A | B |\n|---|---|\n| KC_1 | |\n| | KC_2 |\n").unwrap();
+
+        let mut state = grid_state();
+        state.open_grid_paste(None);
+        for c in path.display().to_string().chars() {
+            press_key(&mut state, KeyCode::Char(c), KeyModifiers::NONE);
+        }
+        press_key(&mut state, KeyCode::Char('s'), KeyModifiers::CONTROL);
+        match &state.active_component {
+            Some(ActiveComponent::GridPaste(view)) => assert!(!view.is_entry()),
+            _ => panic!("grid paste closed"),
+        }
+
+        press_key(&mut state, KeyCode::Enter, KeyModifiers::NONE);
+        assert_eq!(
+            keycodes(&state),
+            ["KC_1", "KC_TRNS", "KC_TRNS", "KC_TRNS", "KC_2", "KC_TRNS"]
+        );
+    }
+}
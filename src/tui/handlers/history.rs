@@ -0,0 +1,29 @@
+//! Generate/build history input handlers.
+
+use anyhow::Result;
+use crossterm::event;
+
+use crate::tui::{component::Component, history::HistoryEvent, AppState};
+
+/// Handle input for the history popup
+pub fn handle_history_input(state: &mut AppState, key: event::KeyEvent) -> Result<bool> {
+    use crate::tui::ActiveComponent;
+
+    if let Some(ActiveComponent::History(ref mut view)) = state.active_component {
+        if let Some(event) = view.handle_input(key) {
+            return handle_history_event(state, event);
+        }
+    }
+    Ok(false)
+}
+
+/// Handle history events
+fn handle_history_event(state: &mut AppState, event: HistoryEvent) -> Result<bool> {
+    match event {
+        HistoryEvent::Closed => {
+            state.close_component();
+            state.set_status("History closed");
+        }
+    }
+    Ok(false)
+}
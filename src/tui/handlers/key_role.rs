@@ -0,0 +1,84 @@
+//! Key role editor input handlers.
+
+use anyhow::{Context, Result};
+use crossterm::event;
+
+use crate::models::{KeyRole, Position};
+use crate::services::key_roles::KeyRoleOverrides;
+use crate::tui::{component::Component, key_role_editor::KeyRoleEditorEvent};
+use crate::tui::{ActiveComponent, AppState};
+
+/// Handle input for the key role editor popup
+pub fn handle_key_role_editor_input(state: &mut AppState, key: event::KeyEvent) -> Result<bool> {
+    if let Some(ActiveComponent::KeyRoleEditor(ref mut editor)) = state.active_component {
+        let position = editor.position();
+        if let Some(event) = editor.handle_input(key) {
+            return handle_key_role_editor_event(state, position, event);
+        }
+    }
+    Ok(false)
+}
+
+/// Handle key role editor events
+fn handle_key_role_editor_event(
+    state: &mut AppState,
+    position: Position,
+    event: KeyRoleEditorEvent,
+) -> Result<bool> {
+    let role = match event {
+        KeyRoleEditorEvent::Saved(role) => Some(role),
+        KeyRoleEditorEvent::Reset => None,
+        KeyRoleEditorEvent::Cancelled => {
+            state.close_component();
+            state.set_status("Key role unchanged");
+            return Ok(false);
+        }
+    };
+
+    match save_override(state, position, role) {
+        Ok(role) => {
+            state.set_key_role(position, role);
+            state.close_component();
+            state.set_status(format!(
+                "Key ({}, {}) role: {role}{}",
+                position.row,
+                position.col,
+                if event == KeyRoleEditorEvent::Reset {
+                    " (inferred)"
+                } else {
+                    ""
+                }
+            ));
+        }
+        Err(e) => state.set_error(format!("Failed to save key role: {e:#}")),
+    }
+    Ok(false)
+}
+
+/// Stores `role` as the override for the key at `position`, or drops the
+/// override when `role` is `None`. Returns the key's resulting role.
+fn save_override(state: &AppState, position: Position, role: Option<KeyRole>) -> Result<KeyRole> {
+    let metadata = &state.layout.metadata;
+    let keyboard = metadata
+        .keyboard
+        .as_deref()
+        .context("Layout has no keyboard")?;
+    let variant = metadata
+        .layout_variant
+        .as_deref()
+        .context("Layout has no layout variant")?;
+
+    let mut overrides = KeyRoleOverrides::load(keyboard, variant)?;
+    let role = match role {
+        Some(role) => {
+            overrides.set(position, role);
+            role
+        }
+        None => {
+            overrides.remove(position);
+            state.inferred_key_role(position)
+        }
+    };
+    overrides.save(keyboard, variant)?;
+    Ok(role)
+}
@@ -0,0 +1,42 @@
+//! Keycode search input handlers.
+
+use anyhow::Result;
+use crossterm::event;
+
+use crate::tui::{component::Component, keycode_search::KeycodeSearchEvent, AppState};
+
+/// Handle input for the keycode search
+pub fn handle_keycode_search_input(state: &mut AppState, key: event::KeyEvent) -> Result<bool> {
+    use crate::tui::ActiveComponent;
+
+    if let Some(ActiveComponent::KeycodeSearch(ref mut view)) = state.active_component {
+        if let Some(event) = view.handle_input(key) {
+            return handle_keycode_search_event(state, event);
+        }
+    }
+    Ok(false)
+}
+
+/// Handle keycode search events
+fn handle_keycode_search_event(state: &mut AppState, event: KeycodeSearchEvent) -> Result<bool> {
+    match event {
+        KeycodeSearchEvent::JumpTo { layer, position } => {
+            if layer < state.layout.layers.len() {
+                state.current_layer = layer;
+                state.selected_position = position;
+                state.close_component();
+                state.set_status(format!(
+                    "Jumped to layer {layer} ({},{})",
+                    position.row, position.col
+                ));
+            } else {
+                state.set_error(format!("Layer {layer} no longer exists"));
+            }
+        }
+        KeycodeSearchEvent::Closed => {
+            state.close_component();
+            state.set_status("Search closed");
+        }
+    }
+    Ok(false)
+}
@@ -3,6 +3,8 @@
 use anyhow::Result;
 use crossterm::event;
 
+use crate::models::KeyDefinition;
+use crate::services::layer_refs;
 use crate::tui::component::Component;
 use crate::tui::{ActiveComponent, AppState, LayerManagerEvent};
 
@@ -22,83 +24,138 @@ pub fn handle_layer_manager_input(state: &mut AppState, key: event::KeyEvent) ->
     // Process the event if one was emitted
     if let Some(event) = event {
         match event {
-            LayerManagerEvent::LayerAdded { layer } => {
+            LayerManagerEvent::LayerAdded { mut layer } => {
+                // Give the new layer a transparent key at every position of the keyboard
+                layer.keys = state
+                    .mapping
+                    .get_all_visual_positions()
+                    .into_iter()
+                    .map(|position| KeyDefinition::new(position, "KC_TRNS"))
+                    .collect();
+                layer
+                    .keys
+                    .sort_by_key(|key| (key.position.row, key.position.col));
+
                 // Add the new layer
                 state.layout.layers.push(layer);
                 state.mark_dirty();
                 state.refresh_layer_refs(); // Update layer reference index
-                state.set_status(format!(
-                    "Layer '{}' created",
-                    state.layout.layers.last().unwrap().name
-                ));
+                state.log_action(
+                    "Add layer",
+                    format!(
+                        "Layer '{}' created",
+                        state.layout.layers.last().unwrap().name
+                    ),
+                );
 
                 // Update component with new layers
                 manager.set_layers(state.layout.layers.clone());
             }
             LayerManagerEvent::LayerDeleted { index } => {
-                // Delete layer (only if not the last one)
-                if state.layout.layers.len() > 1 {
-                    state.layout.layers.remove(index);
+                // Delete layer, clearing references to it and renumbering later ones
+                match layer_refs::delete_layer(&mut state.layout, index) {
+                    Ok(deletion) => {
+                        // Adjust current layer if needed
+                        if state.current_layer >= state.layout.layers.len() {
+                            state.current_layer = state.layout.layers.len() - 1;
+                        } else if state.current_layer > index {
+                            // If we deleted a layer before the current one, adjust index
+                            state.current_layer -= 1;
+                        }
+                        // Deleting the current layer leaves the same index, which
+                        // now points to the next layer
+                        let layer_count = state.layout.layers.len();
+                        if let Some(split) = state.split_view.as_mut() {
+                            split.layer_deleted(index, layer_count);
+                        }
 
-                    // Renumber remaining layers
-                    for (i, layer) in state.layout.layers.iter_mut().enumerate() {
-                        layer.number = i as u8;
-                    }
+                        state.mark_dirty();
+                        state.refresh_layer_refs(); // Update layer reference index
+                        let mut summary = vec![format!("Layer '{}' deleted", deletion.layer.name)];
+                        if deletion.cleared > 0 {
+                            summary.push(format!("{} reference(s) cleared", deletion.cleared));
+                        }
+                        if deletion.renumbered > 0 {
+                            summary
+                                .push(format!("{} reference(s) renumbered", deletion.renumbered));
+                        }
+                        state.log_action("Delete layer", summary.join(", "));
 
-                    // Adjust current layer if needed
-                    if state.current_layer >= state.layout.layers.len() {
-                        state.current_layer = state.layout.layers.len() - 1;
-                    } else if state.current_layer == index {
-                        // If we deleted the current layer, stay at the same index
-                        // (which now points to the next layer)
-                        state.current_layer = index.min(state.layout.layers.len() - 1);
-                    } else if state.current_layer > index {
-                        // If we deleted a layer before the current one, adjust index
-                        state.current_layer -= 1;
+                        // Update component with new layers
+                        manager.set_layers(state.layout.layers.clone());
+                        manager.set_tap_dances(state.layout.tap_dances.clone());
                     }
-
-                    state.mark_dirty();
-                    state.refresh_layer_refs(); // Update layer reference index
-                    state.set_status("Layer deleted");
-
-                    // Update component with new layers
-                    manager.set_layers(state.layout.layers.clone());
-                } else {
-                    state.set_error("Cannot delete the last layer");
+                    Err(e) => state.set_error(e.to_string()),
                 }
             }
             LayerManagerEvent::LayerRenamed { index, name } => {
-                // Rename layer
+                // Rename layer (the markdown parser trims names, so store them trimmed)
+                let name = name.trim();
+                if let Some(layer) = state.layout.layers.get_mut(index) {
+                    if layer.name == name {
+                        state.set_status("Layer name unchanged");
+                    } else {
+                        let old = layer.name.clone();
+                        match layer.set_name(name) {
+                            Ok(()) => {
+                                state.mark_dirty();
+                                state.log_action(
+                                    "Rename layer",
+                                    format!("Layer '{old}' renamed to '{name}'"),
+                                );
+
+                                // Update component with new layers
+                                manager.set_layers(state.layout.layers.clone());
+                            }
+                            Err(e) => state.set_error(format!("Cannot rename layer: {e}")),
+                        }
+                    }
+                }
+            }
+            LayerManagerEvent::LayerDescriptionChanged { index, description } => {
                 if let Some(layer) = state.layout.layers.get_mut(index) {
-                    layer.name = name.clone();
+                    let summary = if description.is_some() {
+                        format!("Updated description of '{}'", layer.name)
+                    } else {
+                        format!("Removed description of '{}'", layer.name)
+                    };
+                    layer.description = description;
                     state.mark_dirty();
-                    state.set_status(format!("Layer renamed to '{name}'"));
+                    state.log_action("Describe layer", summary);
 
                     // Update component with new layers
                     manager.set_layers(state.layout.layers.clone());
                 }
             }
             LayerManagerEvent::LayerReordered { from, to } => {
-                // Reorder layers (swap)
-                state.layout.layers.swap(from, to);
-
-                // Renumber layers
-                for (i, layer) in state.layout.layers.iter_mut().enumerate() {
-                    layer.number = i as u8;
-                }
+                // Move the layer and rewrite MO()/LT()/... references to follow
+                match layer_refs::move_layer(&mut state.layout, from, to) {
+                    Ok(updated) => {
+                        state.current_layer =
+                            layer_refs::moved_layer_index(state.current_layer, from, to);
+                        if let Some(split) = state.split_view.as_mut() {
+                            split.layer_moved(from, to);
+                        }
+                        state.mark_dirty();
+                        state.refresh_layer_refs();
+                        state.log_action(
+                            "Move layer",
+                            format!(
+                                "Moved layer '{}' to position {to}, updated {updated} reference(s)",
+                                state.layout.layers[to].name
+                            ),
+                        );
 
-                // Adjust current_layer if needed
-                if state.current_layer == from {
-                    state.current_layer = to;
-                } else if state.current_layer == to {
-                    state.current_layer = from;
+                        // Update component with new layers
+                        manager.set_layers(state.layout.layers.clone());
+                        manager.set_tap_dances(state.layout.tap_dances.clone());
+                        manager.select(to);
+                    }
+                    Err(e) => {
+                        manager.select(from);
+                        state.set_error(format!("Cannot move layer: {e}"));
+                    }
                 }
-
-                state.mark_dirty();
-                state.set_status(format!("Layer moved to position {to}"));
-
-                // Update component with new layers
-                manager.set_layers(state.layout.layers.clone());
             }
             LayerManagerEvent::LayerDuplicated {
                 source_index,
@@ -107,11 +164,14 @@ pub fn handle_layer_manager_input(state: &mut AppState, key: event::KeyEvent) ->
                 // Duplicate layer
                 state.layout.layers.push(layer);
                 state.mark_dirty();
-                state.set_status(format!(
-                    "Duplicated layer {} as '{}'",
-                    source_index,
-                    state.layout.layers.last().unwrap().name
-                ));
+                state.log_action(
+                    "Duplicate layer",
+                    format!(
+                        "Duplicated layer {} as '{}'",
+                        source_index,
+                        state.layout.layers.last().unwrap().name
+                    ),
+                );
 
                 // Update component with new layers
                 manager.set_layers(state.layout.layers.clone());
@@ -127,7 +187,7 @@ pub fn handle_layer_manager_input(state: &mut AppState, key: event::KeyEvent) ->
                         }
                     }
                     state.mark_dirty();
-                    state.set_status(format!("Copied layer {from} to layer {to}"));
+                    state.log_action("Copy layer", format!("Copied layer {from} to layer {to}"));
 
                     // Update component with new layers
                     manager.set_layers(state.layout.layers.clone());
@@ -163,7 +223,10 @@ pub fn handle_layer_manager_input(state: &mut AppState, key: event::KeyEvent) ->
                 }
 
                 state.mark_dirty();
-                state.set_status(format!("Swapped layers {layer1} and {layer2}"));
+                state.log_action(
+                    "Swap layers",
+                    format!("Swapped layers {layer1} and {layer2}"),
+                );
 
                 // Update component with new layers
                 manager.set_layers(state.layout.layers.clone());
@@ -173,11 +236,31 @@ pub fn handle_layer_manager_input(state: &mut AppState, key: event::KeyEvent) ->
                 if let Some(layer) = state.layout.layers.get_mut(index) {
                     layer.layer_colors_enabled = enabled;
                     state.mark_dirty();
-                    state.set_status(if enabled {
-                        format!("Layer {index} colors enabled")
-                    } else {
-                        format!("Layer {index} colors disabled")
-                    });
+                    state.log_action(
+                        "Toggle layer colors",
+                        if enabled {
+                            format!("Layer {index} colors enabled")
+                        } else {
+                            format!("Layer {index} colors disabled")
+                        },
+                    );
+
+                    // Update component with new layers
+                    manager.set_layers(state.layout.layers.clone());
+                }
+            }
+            LayerManagerEvent::LayerEnabledToggled { index, enabled } => {
+                if let Some(layer) = state.layout.layers.get_mut(index) {
+                    layer.enabled = enabled;
+                    state.mark_dirty();
+                    state.log_action(
+                        "Toggle layer in firmware",
+                        if enabled {
+                            format!("Layer {index} included in firmware")
+                        } else {
+                            format!("Layer {index} left out of firmware")
+                        },
+                    );
 
                     // Update component with new layers
                     manager.set_layers(state.layout.layers.clone());
@@ -209,3 +292,160 @@ pub fn handle_layer_manager_input(state: &mut AppState, key: event::KeyEvent) ->
 
     Ok(false)
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::models::{
+        KeyGeometry, KeyboardGeometry, Layer, Layout, Position, RgbColor, VisualLayoutMapping,
+    };
+    use crate::tui::handlers::action_handlers::key_ops::handle_undo;
+    use crossterm::event::{KeyCode, KeyEvent, KeyModifiers};
+
+    /// Four layers of one key; the base layer's key switches to layer 1
+    fn state_with_layers() -> AppState {
+        let mut geometry = KeyboardGeometry::new("test", "test", 1, 1);
+        geometry.add_key(KeyGeometry::new((0, 0), 0, 0.0, 0.0));
+        let mut layout = Layout::new("Layers").unwrap();
+        for number in 0..4u8 {
+            let mut layer =
+                Layer::new(number, format!("L{number}"), RgbColor::new(0, 0, 0)).unwrap();
+            let keycode = if number == 0 { "MO(1)" } else { "KC_TRNS" };
+            layer
+                .add_key(KeyDefinition::at(Position::new(0, 0)).keycode(keycode))
+                .unwrap();
+            layout.add_layer(layer).unwrap();
+        }
+        let mapping = VisualLayoutMapping::build(&geometry);
+        AppState::new(
+            layout,
+            None,
+            geometry,
+            mapping,
+            crate::config::Config::default(),
+        )
+        .unwrap()
+    }
+
+    fn press(state: &mut AppState, code: KeyCode, modifiers: KeyModifiers) {
+        handle_layer_manager_input(state, KeyEvent::new(code, modifiers)).unwrap();
+    }
+
+    fn names(state: &AppState) -> Vec<&str> {
+        state
+            .layout
+            .layers
+            .iter()
+            .map(|l| l.name.as_str())
+            .collect()
+    }
+
+    #[test]
+    fn test_reorder_previews_then_applies_as_one_undo_step() {
+        let mut state = state_with_layers();
+        state.current_layer = 1;
+        state.open_layer_manager();
+
+        press(&mut state, KeyCode::Down, KeyModifiers::SHIFT);
+        press(&mut state, KeyCode::Down, KeyModifiers::SHIFT);
+        // Nothing moves until Enter, but the keyboard shows the moving layer
+        assert_eq!(names(&state), ["L0", "L1", "L2", "L3"]);
+        assert_eq!(state.display_layer(), 1);
+        assert!(!state.dirty);
+
+        press(&mut state, KeyCode::Enter, KeyModifiers::NONE);
+        assert_eq!(names(&state), ["L0", "L2", "L3", "L1"]);
+        assert_eq!(state.layout.layers[0].keys[0].keycode, "MO(3)");
+        assert_eq!(state.current_layer, 3);
+
+        handle_undo(&mut state).unwrap();
+        assert_eq!(names(&state), ["L0", "L1", "L2", "L3"]);
+        assert_eq!(state.layout.layers[0].keys[0].keycode, "MO(1)");
+    }
+
+    /// Opens the split view with layer `other` in the other pane
+    fn split_with_other_pane_on(state: &mut AppState, other: usize) {
+        assert!(state.toggle_split_view());
+        state.split_view.as_mut().unwrap().other.layer = other;
+    }
+
+    fn other_pane_layer(state: &AppState) -> usize {
+        state.split_view.as_ref().unwrap().other.layer
+    }
+
+    /// Deletes layer `index` through the layer manager
+    fn delete_layer(state: &mut AppState, index: usize) {
+        state.current_layer = index;
+        state.open_layer_manager();
+        press(state, KeyCode::Char('d'), KeyModifiers::NONE);
+        press(state, KeyCode::Char('y'), KeyModifiers::NONE);
+    }
+
+    #[test]
+    fn test_split_pane_follows_its_layer_when_layers_move() {
+        let mut state = state_with_layers();
+        split_with_other_pane_on(&mut state, 3);
+        state.current_layer = 3;
+        state.open_layer_manager();
+
+        press(&mut state, KeyCode::Char('m'), KeyModifiers::NONE);
+        press(&mut state, KeyCode::Up, KeyModifiers::NONE);
+        press(&mut state, KeyCode::Up, KeyModifiers::NONE);
+        press(&mut state, KeyCode::Enter, KeyModifiers::NONE);
+        assert_eq!(names(&state), ["L0", "L3", "L1", "L2"]);
+        assert_eq!(state.layout.layers[other_pane_layer(&state)].name, "L3");
+    }
+
+    #[test]
+    fn test_split_pane_shifts_down_when_an_earlier_layer_is_deleted() {
+        let mut state = state_with_layers();
+        split_with_other_pane_on(&mut state, 3);
+
+        delete_layer(&mut state, 1);
+        assert_eq!(names(&state), ["L0", "L2", "L3"]);
+        assert_eq!(other_pane_layer(&state), 2);
+    }
+
+    #[test]
+    fn test_split_pane_moves_to_a_neighbour_when_its_layer_is_deleted() {
+        let mut state = state_with_layers();
+        split_with_other_pane_on(&mut state, 2);
+        state.split_view.as_mut().unwrap().other.selected_keys = vec![Position::new(0, 0)];
+
+        delete_layer(&mut state, 2);
+        assert_eq!(other_pane_layer(&state), 2);
+        assert_eq!(state.layout.layers[2].name, "L3");
+        assert!(state
+            .split_view
+            .as_ref()
+            .unwrap()
+            .other
+            .selected_keys
+            .is_empty());
+
+        // The last layer's neighbour is the one before it
+        delete_layer(&mut state, 2);
+        assert_eq!(other_pane_layer(&state), 1);
+    }
+
+    #[test]
+    fn test_reorder_cancels_on_esc_and_keeps_the_base_layer_first() {
+        let mut state = state_with_layers();
+        state.current_layer = 2;
+        state.open_layer_manager();
+
+        press(&mut state, KeyCode::Char('m'), KeyModifiers::NONE);
+        press(&mut state, KeyCode::Up, KeyModifiers::NONE);
+        press(&mut state, KeyCode::Up, KeyModifiers::NONE);
+        press(&mut state, KeyCode::Esc, KeyModifiers::NONE);
+        assert_eq!(names(&state), ["L0", "L1", "L2", "L3"]);
+        assert!(!state.dirty);
+
+        // Up stops below the base layer
+        press(&mut state, KeyCode::Char('m'), KeyModifiers::NONE);
+        press(&mut state, KeyCode::Up, KeyModifiers::NONE);
+        press(&mut state, KeyCode::Up, KeyModifiers::NONE);
+        press(&mut state, KeyCode::Enter, KeyModifiers::NONE);
+        assert_eq!(names(&state), ["L0", "L2", "L1", "L3"]);
+    }
+}
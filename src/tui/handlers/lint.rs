@@ -0,0 +1,41 @@
+//! Lint report input handlers.
+
+use anyhow::Result;
+use crossterm::event;
+
+use crate::tui::{component::Component, lint_report::LintReportEvent, AppState};
+
+/// Handle input for the lint report
+pub fn handle_lint_report_input(state: &mut AppState, key: event::KeyEvent) -> Result<bool> {
+    use crate::tui::ActiveComponent;
+
+    if let Some(ActiveComponent::LintReport(ref mut view)) = state.active_component {
+        if let Some(event) = view.handle_input(key) {
+            return handle_lint_report_event(state, event);
+        }
+    }
+    Ok(false)
+}
+
+/// Handle lint report events
+fn handle_lint_report_event(state: &mut AppState, event: LintReportEvent) -> Result<bool> {
+    match event {
+        LintReportEvent::JumpTo { layer, position } => {
+            if layer < state.layout.layers.len() {
+                state.current_layer = layer;
+                if let Some(position) = position {
+                    state.selected_position = position;
+                }
+                state.close_component();
+                state.set_status(format!("Jumped to layer {layer}"));
+            } else {
+                state.set_error(format!("Layer {layer} no longer exists"));
+            }
+        }
+        LintReportEvent::Closed => {
+            state.close_component();
+            state.set_status("Lint report closed");
+        }
+    }
+    Ok(false)
+}
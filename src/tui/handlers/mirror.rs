@@ -0,0 +1,182 @@
+//! Mirror preview input handlers.
+//!
+//! `Shift+M` mirrors the selected keys (or the current key); `Alt+Shift+M`
+//! mirrors every key on the cursor's half of the layer. While the targets are
+//! highlighted `s` toggles modifier side swapping and `Enter` applies the
+//! whole mirror as one undo step.
+
+use anyhow::Result;
+use crossterm::event::{self, KeyCode};
+
+use crate::services::mirror::MirrorPlan;
+use crate::tui::AppState;
+
+/// Shows `plan`'s targets and waits for confirmation.
+pub fn start_mirror_preview(state: &mut AppState, plan: MirrorPlan) {
+    state.pending_mirror = Some(plan);
+    show_preview_status(state);
+}
+
+/// Handle input while a mirror is waiting for confirmation
+pub fn handle_mirror_preview_input(state: &mut AppState, key: event::KeyEvent) -> Result<bool> {
+    match key.code {
+        KeyCode::Enter => {
+            if let Some(plan) = state.pending_mirror.take() {
+                apply_mirror(state, &plan);
+            }
+        }
+        KeyCode::Char('s') => {
+            if let Some(plan) = &mut state.pending_mirror {
+                plan.swap_sides = !plan.swap_sides;
+            }
+            show_preview_status(state);
+        }
+        KeyCode::Esc => {
+            state.pending_mirror = None;
+            state.set_status("Mirror cancelled");
+        }
+        _ => {}
+    }
+    Ok(false)
+}
+
+/// Describes the pending mirror and its keys in the status bar.
+fn show_preview_status(state: &mut AppState) {
+    let Some(plan) = &state.pending_mirror else {
+        return;
+    };
+    let skipped = if plan.skipped > 0 {
+        format!(", {} without a counterpart", plan.skipped)
+    } else {
+        String::new()
+    };
+    let swap = if plan.swap_sides { "on" } else { "off" };
+    let status = format!(
+        "Mirror {} key(s) onto the highlighted keys{skipped} - Enter: apply, s: swap L/R mods [{swap}], Esc: cancel",
+        plan.pairs.len()
+    );
+    state.set_status(status);
+}
+
+/// Applies `plan` to its layer as one undo step.
+fn apply_mirror(state: &mut AppState, plan: &MirrorPlan) {
+    let Some(layer) = state.layout.layers.get_mut(plan.layer) else {
+        return;
+    };
+
+    let applied = plan.apply(layer);
+    state.selection_mode = None;
+    state.selected_keys.clear();
+    state.mark_dirty();
+    state.refresh_layer_refs();
+
+    let summary = if plan.skipped > 0 {
+        format!(
+            "Mirrored {applied} key(s) on layer {}, skipped {} without a counterpart",
+            plan.layer, plan.skipped
+        )
+    } else {
+        format!("Mirrored {applied} key(s) on layer {}", plan.layer)
+    };
+    state.log_action("Mirror keys", summary);
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::models::{
+        KeyDefinition, KeyGeometry, KeyboardGeometry, Layer, Layout, Position, RgbColor,
+        VisualLayoutMapping,
+    };
+    use crate::shortcuts::Action;
+    use crate::tui::handlers::action_handlers::key_ops::handle_undo;
+    use crate::tui::handlers::dispatch_action;
+    use crossterm::event::{KeyEvent, KeyModifiers};
+
+    /// One row of four keys: two per half
+    fn split_state() -> AppState {
+        let mut geometry = KeyboardGeometry::new("test", "test", 1, 4);
+        let mut layer = Layer::new(0, "Base", RgbColor::new(255, 255, 255)).unwrap();
+        for col in 0..4 {
+            geometry.add_key(KeyGeometry::new((0, col), col, f32::from(col), 0.0));
+            layer
+                .add_key(KeyDefinition::at(Position::new(0, col)).keycode("KC_TRNS"))
+                .unwrap();
+        }
+        layer.keys[0].keycode = "KC_LSFT".to_string();
+        layer.keys[1].keycode = "KC_A".to_string();
+        let mut layout = Layout::new("Split").unwrap();
+        layout.add_layer(layer).unwrap();
+        let mapping = VisualLayoutMapping::build(&geometry);
+        AppState::new(
+            layout,
+            None,
+            geometry,
+            mapping,
+            crate::config::Config::default(),
+        )
+        .unwrap()
+    }
+
+    fn keycodes(state: &AppState) -> Vec<&str> {
+        state.layout.layers[0]
+            .keys
+            .iter()
+            .map(|k| k.keycode.as_str())
+            .collect()
+    }
+
+    fn press(state: &mut AppState, code: KeyCode) {
+        handle_mirror_preview_input(state, KeyEvent::new(code, KeyModifiers::NONE)).unwrap();
+    }
+
+    #[test]
+    fn test_mirror_preview_applies_as_one_undo_step() {
+        let mut state = split_state();
+        dispatch_action(&mut state, Action::ToggleSelectionMode).unwrap();
+        state.selected_keys.push(Position::new(0, 1));
+
+        dispatch_action(&mut state, Action::MirrorSelection).unwrap();
+        let plan = state.pending_mirror.as_ref().unwrap();
+        assert!(plan.is_target(0, Position::new(0, 3)));
+        assert!(plan.is_target(0, Position::new(0, 2)));
+        // Nothing changes until the preview is confirmed
+        assert!(!state.dirty);
+
+        press(&mut state, KeyCode::Enter);
+        assert!(state.pending_mirror.is_none());
+        assert!(state.dirty);
+        assert_eq!(keycodes(&state), ["KC_LSFT", "KC_A", "KC_A", "KC_RSFT"]);
+
+        handle_undo(&mut state).unwrap();
+        assert_eq!(keycodes(&state), ["KC_LSFT", "KC_A", "KC_TRNS", "KC_TRNS"]);
+    }
+
+    #[test]
+    fn test_mirror_preview_swap_toggle_and_cancel() {
+        let mut state = split_state();
+        dispatch_action(&mut state, Action::MirrorSelection).unwrap();
+        press(&mut state, KeyCode::Char('s'));
+        assert!(!state.pending_mirror.as_ref().unwrap().swap_sides);
+        press(&mut state, KeyCode::Enter);
+        assert_eq!(keycodes(&state)[3], "KC_LSFT");
+
+        dispatch_action(&mut state, Action::MirrorSelection).unwrap();
+        press(&mut state, KeyCode::Esc);
+        assert!(state.pending_mirror.is_none());
+    }
+
+    #[test]
+    fn test_mirror_layer_copies_the_cursor_half() {
+        let mut state = split_state();
+        state.selected_position = Position::new(0, 3);
+        state.layout.layers[0].keys[2].keycode = "KC_B".to_string();
+
+        dispatch_action(&mut state, Action::MirrorLayer).unwrap();
+        let plan = state.pending_mirror.as_ref().unwrap();
+        assert_eq!(plan.pairs.len(), 2);
+        assert!(plan.is_target(0, Position::new(0, 0)));
+        press(&mut state, KeyCode::Enter);
+        assert_eq!(keycodes(&state), ["KC_TRNS", "KC_B", "KC_B", "KC_TRNS"]);
+    }
+}
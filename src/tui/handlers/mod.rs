@@ -1,21 +1,56 @@
 //! Input handler modules for different TUI contexts.
 
 pub mod action_handlers;
+pub mod action_log;
 pub mod actions;
 pub mod category;
+pub mod color_sample;
+pub mod command_palette;
+pub mod diff_view;
+pub mod encoder;
+pub mod generation_results;
+pub mod grid_paste;
+pub mod history;
+pub mod key_role;
+pub mod keycode_search;
 pub mod layer;
+pub mod lint;
 pub mod main;
+pub mod mirror;
+pub mod modifier_wrap;
+pub mod peek;
 pub mod popups;
 pub mod settings;
+pub mod simulation;
+pub mod snapshots;
 pub mod tap_dance;
 pub mod templates;
+pub mod tutorial;
+pub mod validation;
 
 // Re-export handler functions
+pub use action_log::handle_action_log_input;
 pub use actions::dispatch_action;
 pub use category::handle_category_manager_input;
+pub use command_palette::handle_command_palette_input;
+pub use diff_view::handle_diff_view_input;
+pub use encoder::handle_encoder_editor_input;
+pub use generation_results::handle_generation_results_input;
+pub use grid_paste::handle_grid_paste_input;
+pub use history::handle_history_input;
+pub use key_role::handle_key_role_editor_input;
+pub use keycode_search::handle_keycode_search_input;
 pub use layer::handle_layer_manager_input;
+pub use lint::handle_lint_report_input;
 pub use main::handle_main_input;
+pub use mirror::handle_mirror_preview_input;
+pub use modifier_wrap::handle_wrap_prompt_input;
+pub use peek::handle_peek_input;
 pub use popups::handle_popup_input;
 pub use settings::handle_settings_manager_input;
+pub use simulation::handle_simulation_input;
+pub use snapshots::handle_snapshot_browser_input;
 pub use tap_dance::handle_tap_dance_editor_input;
 pub use templates::{handle_template_browser_input, handle_template_save_dialog_input};
+pub use tutorial::handle_tutorial_input;
+pub use validation::handle_validation_results_input;
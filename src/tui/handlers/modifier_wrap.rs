@@ -0,0 +1,207 @@
+//! Wrap-in-modifier prompt input handlers.
+
+use anyhow::Result;
+use crossterm::event::{self, KeyCode};
+
+use crate::models::Position;
+use crate::services::modifier_wrap::{WrapModifier, WrapPlan};
+use crate::tui::AppState;
+
+/// Keys waiting for the modifier to wrap them in (Shift+W).
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct PendingWrap {
+    /// Layer the keys are on
+    pub layer: usize,
+    /// Keys to wrap, in selection order
+    pub positions: Vec<Position>,
+    /// Whether to wrap in the modifier's mod-tap instead
+    pub mod_tap: bool,
+}
+
+/// Prompts for the modifier to wrap `positions` in.
+pub fn start_wrap_prompt(state: &mut AppState, positions: Vec<Position>) {
+    state.pending_wrap = Some(PendingWrap {
+        layer: state.current_layer,
+        positions,
+        mod_tap: false,
+    });
+    show_prompt_status(state);
+}
+
+/// Handle input while the wrap prompt waits for a modifier
+pub fn handle_wrap_prompt_input(state: &mut AppState, key: event::KeyEvent) -> Result<bool> {
+    match key.code {
+        KeyCode::Char('t') => {
+            if let Some(pending) = &mut state.pending_wrap {
+                pending.mod_tap = !pending.mod_tap;
+            }
+            show_prompt_status(state);
+        }
+        KeyCode::Char(c) => {
+            if let Some(modifier) = WrapModifier::from_char(c) {
+                if let Some(pending) = state.pending_wrap.take() {
+                    apply_wrap(state, &pending, modifier);
+                }
+            }
+        }
+        KeyCode::Esc => {
+            state.pending_wrap = None;
+            state.set_status("Wrap cancelled");
+        }
+        _ => {}
+    }
+    Ok(false)
+}
+
+/// Describes the pending wrap and its choices in the status bar.
+fn show_prompt_status(state: &mut AppState) {
+    let Some(pending) = &state.pending_wrap else {
+        return;
+    };
+    let (suffix, mod_tap) = if pending.mod_tap {
+        ("_T", "on")
+    } else {
+        ("", "off")
+    };
+    let status = format!(
+        "Wrap {} key(s) in s: LSFT{suffix}, c: LCTL{suffix}, a: LALT{suffix}, g: LGUI{suffix} - t: mod-tap [{mod_tap}], Esc: cancel",
+        pending.positions.len()
+    );
+    state.set_status(status);
+}
+
+/// Wraps the pending keys in `modifier` as one undo step and reports the
+/// keys that were skipped.
+fn apply_wrap(state: &mut AppState, pending: &PendingWrap, modifier: WrapModifier) {
+    let Some(layer) = state.layout.layers.get_mut(pending.layer) else {
+        return;
+    };
+    let plan = WrapPlan::new(
+        layer,
+        pending.layer,
+        &pending.positions,
+        modifier,
+        pending.mod_tap,
+    );
+    let function = modifier.function(pending.mod_tap);
+
+    if !plan.changes.is_empty() {
+        plan.apply(layer);
+        state.selection_mode = None;
+        state.selected_keys.clear();
+        state.mark_dirty();
+        state.refresh_layer_refs();
+    }
+
+    let skipped = if plan.skipped.is_empty() {
+        String::new()
+    } else {
+        let keys: Vec<_> = plan
+            .skipped
+            .iter()
+            .map(|(_, keycode, reason)| format!("{keycode} ({reason})"))
+            .collect();
+        format!(", skipped {}: {}", keys.len(), keys.join(", "))
+    };
+    let summary = format!(
+        "{function} on layer {}: wrapped {}, unwrapped {}{skipped}",
+        pending.layer,
+        plan.wrapped(),
+        plan.unwrapped()
+    );
+    state.log_action("Wrap in modifier", summary);
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::models::{
+        KeyDefinition, KeyGeometry, KeyboardGeometry, Layer, Layout, RgbColor, VisualLayoutMapping,
+    };
+    use crate::shortcuts::Action;
+    use crate::tui::handlers::action_handlers::key_ops::handle_undo;
+    use crate::tui::handlers::dispatch_action;
+    use crossterm::event::{KeyEvent, KeyModifiers};
+
+    /// One row of keys with the given keycodes
+    fn state_with(keycodes: &[&str]) -> AppState {
+        let cols = u8::try_from(keycodes.len()).unwrap();
+        let mut geometry = KeyboardGeometry::new("test", "test", 1, cols);
+        let mut layer = Layer::new(0, "Base", RgbColor::new(255, 255, 255)).unwrap();
+        for (col, keycode) in (0..cols).zip(keycodes) {
+            geometry.add_key(KeyGeometry::new((0, col), col, f32::from(col), 0.0));
+            layer
+                .add_key(KeyDefinition::at(Position::new(0, col)).keycode(*keycode))
+                .unwrap();
+        }
+        let mut layout = Layout::new("Wrap").unwrap();
+        layout.add_layer(layer).unwrap();
+        let mapping = VisualLayoutMapping::build(&geometry);
+        AppState::new(
+            layout,
+            None,
+            geometry,
+            mapping,
+            crate::config::Config::default(),
+        )
+        .unwrap()
+    }
+
+    fn keycodes(state: &AppState) -> Vec<&str> {
+        state.layout.layers[0]
+            .keys
+            .iter()
+            .map(|k| k.keycode.as_str())
+            .collect()
+    }
+
+    fn press(state: &mut AppState, c: char) {
+        handle_wrap_prompt_input(state, KeyEvent::new(KeyCode::Char(c), KeyModifiers::NONE))
+            .unwrap();
+    }
+
+    fn select_all(state: &mut AppState) {
+        dispatch_action(state, Action::ToggleSelectionMode).unwrap();
+        let cols = state.layout.layers[0].keys.len();
+        state.selected_keys = (0..cols)
+            .map(|col| Position::new(0, u8::try_from(col).unwrap()))
+            .collect();
+    }
+
+    #[test]
+    fn test_wrap_selection_applies_as_one_undo_step() {
+        let mut state = state_with(&["KC_A", "LCTL(KC_B)", "MO(1)"]);
+        select_all(&mut state);
+
+        dispatch_action(&mut state, Action::WrapSelectionInModifier).unwrap();
+        assert!(state.pending_wrap.is_some());
+        assert!(!state.dirty);
+
+        press(&mut state, 'c');
+        assert!(state.pending_wrap.is_none());
+        assert!(state.dirty);
+        assert_eq!(keycodes(&state), ["LCTL(KC_A)", "KC_B", "MO(1)"]);
+        let status = &state.status_message;
+        assert!(status.contains("wrapped 1, unwrapped 1"), "{status}");
+        assert!(status.contains("MO(1) (not a basic keycode)"), "{status}");
+
+        handle_undo(&mut state).unwrap();
+        assert_eq!(keycodes(&state), ["KC_A", "LCTL(KC_B)", "MO(1)"]);
+    }
+
+    #[test]
+    fn test_wrap_prompt_mod_tap_and_cancel() {
+        let mut state = state_with(&["KC_A", "KC_S"]);
+        select_all(&mut state);
+        dispatch_action(&mut state, Action::WrapSelectionInModifier).unwrap();
+        press(&mut state, 't');
+        press(&mut state, 'g');
+        assert_eq!(keycodes(&state), ["LGUI_T(KC_A)", "LGUI_T(KC_S)"]);
+
+        dispatch_action(&mut state, Action::WrapSelectionInModifier).unwrap();
+        handle_wrap_prompt_input(&mut state, KeyEvent::new(KeyCode::Esc, KeyModifiers::NONE))
+            .unwrap();
+        assert!(state.pending_wrap.is_none());
+        assert_eq!(keycodes(&state), ["LGUI_T(KC_A)", "LGUI_T(KC_S)"]);
+    }
+}
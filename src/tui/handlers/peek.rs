@@ -0,0 +1,139 @@
+//! Layer peek input handlers.
+//!
+//! Terminals don't report key releases, so a peek lasts until the next key
+//! press rather than while a key is held; opening a popup ends it.
+
+use anyhow::Result;
+use crossterm::event::{self, KeyCode};
+
+use crate::tui::{AppState, Peek};
+
+/// Waits for the number of the layer to peek at.
+pub fn start_peek(state: &mut AppState) {
+    state.peek = Some(Peek::Choosing);
+    state.set_status(format!(
+        "Peek at layer: 0-{} (Esc: cancel)",
+        state.layout.layers.len().saturating_sub(1)
+    ));
+}
+
+/// Handle input while peeking at a layer
+///
+/// Digits pick the layer and Tab/Shift+Tab cycle through the layers; any
+/// other key ends the peek without doing anything else.
+pub fn handle_peek_input(state: &mut AppState, key: event::KeyEvent) -> Result<bool> {
+    let layer_count = state.layout.layers.len();
+    let peeked = match state.peek {
+        Some(Peek::Showing(layer)) => Some(layer),
+        _ => None,
+    };
+
+    let next = match key.code {
+        KeyCode::Char(c) if c.is_ascii_digit() => {
+            let layer = c.to_digit(10).unwrap_or_default() as usize;
+            if layer >= layer_count {
+                state.peek = None;
+                state.set_error(format!("Layer {layer} does not exist"));
+                return Ok(false);
+            }
+            layer
+        }
+        KeyCode::Tab if layer_count > 0 => {
+            (peeked.unwrap_or(state.current_layer) + 1) % layer_count
+        }
+        KeyCode::BackTab if layer_count > 0 => {
+            let from = peeked.unwrap_or(state.current_layer);
+            (from + layer_count - 1) % layer_count
+        }
+        _ => {
+            state.peek = None;
+            if peeked.is_some() {
+                state.set_status(format!("Back on layer {}", state.current_layer));
+            } else {
+                state.set_status("Peek cancelled");
+            }
+            return Ok(false);
+        }
+    };
+
+    state.peek = Some(Peek::Showing(next));
+    let name = &state.layout.layers[next].name;
+    state.set_status(format!(
+        "Peeking layer {next} ({name}) - 0-9/Tab: another layer, any other key: back to layer {}",
+        state.current_layer
+    ));
+    Ok(false)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::models::{
+        KeyDefinition, KeyboardGeometry, Layer, Layout, Position, RgbColor, VisualLayoutMapping,
+    };
+    use crate::shortcuts::Action;
+    use crate::tui::handlers::dispatch_action;
+    use crossterm::event::{KeyEvent, KeyModifiers};
+
+    fn create_state() -> AppState {
+        let mut layout = Layout::new("Peek").unwrap();
+        for (number, name) in ["Base", "Nav", "Sym"].into_iter().enumerate() {
+            let mut layer = Layer::new(number as u8, name, RgbColor::new(0, 0, 0)).unwrap();
+            layer
+                .add_key(KeyDefinition::at(Position::new(0, 0)).keycode("KC_A"))
+                .unwrap();
+            layout.add_layer(layer).unwrap();
+        }
+        AppState::new(
+            layout,
+            None,
+            KeyboardGeometry::new("test", "test", 1, 1),
+            VisualLayoutMapping::default(),
+            crate::config::Config::default(),
+        )
+        .unwrap()
+    }
+
+    fn press(state: &mut AppState, code: KeyCode) {
+        handle_peek_input(state, KeyEvent::new(code, KeyModifiers::NONE)).unwrap();
+    }
+
+    #[test]
+    fn test_peek_draws_layer_and_keeps_editing_layer() {
+        let mut state = create_state();
+        state.current_layer = 1;
+        dispatch_action(&mut state, Action::PeekLayer).unwrap();
+        assert_eq!(state.peek, Some(Peek::Choosing));
+        assert_eq!(state.display_layer(), 1);
+
+        press(&mut state, KeyCode::Char('2'));
+        assert_eq!(state.display_layer(), 2);
+        assert_eq!(state.current_layer, 1);
+        assert!(state.status_message.contains("Peeking layer 2 (Sym)"));
+
+        press(&mut state, KeyCode::Tab);
+        assert_eq!(state.display_layer(), 0);
+        press(&mut state, KeyCode::BackTab);
+        assert_eq!(state.display_layer(), 2);
+
+        // A popup hides the peek
+        state.active_popup = Some(crate::tui::PopupType::HelpOverlay);
+        assert_eq!(state.display_layer(), 1);
+        state.active_popup = None;
+
+        // Any other key returns without acting on it
+        press(&mut state, KeyCode::Char('x'));
+        assert!(state.peek.is_none());
+        assert_eq!(state.display_layer(), 1);
+        assert!(!state.dirty);
+    }
+
+    #[test]
+    fn test_peek_rejects_missing_layer() {
+        let mut state = create_state();
+        start_peek(&mut state);
+        press(&mut state, KeyCode::Char('7'));
+        assert!(state.peek.is_none());
+        assert!(state.error_message.is_some());
+    }
+}
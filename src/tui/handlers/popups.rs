@@ -5,14 +5,15 @@ use crossterm::event::{self, KeyCode, KeyModifiers};
 
 use crate::keycode_db::{KeycodeDb, ParamType};
 use crate::services::LayoutService;
+use crate::tui::handlers::{actions, color_sample};
 use crate::tui::{
     build_log::BuildLogEvent,
     color_picker::ColorPickerEvent,
     component::{Component, ContextualComponent},
     key_editor, keycode_picker,
     keycode_picker::KeycodePickerEvent,
-    metadata_editor, onboarding_wizard, ActiveComponent, AppState, LayoutVariantPickerEvent,
-    PopupType,
+    metadata_editor, onboarding_wizard, ActiveComponent, AppState, FirmwareAction,
+    LayoutVariantPickerEvent, PopupType,
 };
 
 /// Extracts the tap dance name from a TD(name) keycode.
@@ -160,9 +161,9 @@ fn handle_parameter_collected(state: &mut AppState, param_value: String) {
         if let Some(final_keycode) = state.pending_keycode.build_keycode() {
             if let Some(key) = state.get_selected_key_mut() {
                 key.keycode = final_keycode.clone();
-                state.mark_dirty();
+                state.mark_selected_key_dirty();
                 state.refresh_layer_refs(); // Update layer reference index
-                state.set_status(format!("Assigned: {final_keycode}"));
+                state.log_action("Assign keycode", format!("Assigned: {final_keycode}"));
             }
         } else {
             state.set_error("Failed to build final keycode");
@@ -192,6 +193,22 @@ fn handle_keycode_picker_event(state: &mut AppState, event: KeycodePickerEvent)
 
     match event {
         KeycodePickerEvent::KeycodeSelected(keycode) => {
+            // Picking a direction for the encoder editor
+            if let Some(mut editor) = state.encoder_editor_cache.take() {
+                // Turns are sent as taps, so only basic keycodes work
+                if !is_basic_keycode(&keycode) {
+                    state.set_error("Only basic keycodes allowed for encoder turns");
+                    state.encoder_editor_cache = Some(editor);
+                    return Ok(false);
+                }
+                let field = editor.field();
+                editor.set_keycode(field, keycode.clone());
+                state.set_status(format!("Encoder {} turn set to: {keycode}", field.label()));
+                state.active_component = Some(ActiveComponent::EncoderEditor(editor));
+                state.active_popup = Some(PopupType::EncoderEditor);
+                return Ok(false);
+            }
+
             // If user selected TD() directly from the picker, launch the tap dance form
             if keycode == "TD()" || keycode.starts_with("TD(") {
                 // Existing names for duplicate validation
@@ -291,9 +308,9 @@ fn handle_keycode_picker_event(state: &mut AppState, event: KeycodePickerEvent)
                             form.set_hold(keycode.clone());
                             state.set_status(format!("Hold set to: {keycode}"));
                         }
-                        FormRow::Name => {
+                        FormRow::Name | FormRow::Term | FormRow::EachTap => {
                             // Should never happen
-                            state.set_error("Invalid state: picker opened for name field");
+                            state.set_error("Invalid state: picker opened for a non-keycode field");
                         }
                     }
                 }
@@ -322,9 +339,9 @@ fn handle_keycode_picker_event(state: &mut AppState, event: KeycodePickerEvent)
 
                 if let Some(key) = state.get_selected_key_mut() {
                     key.keycode = new_keycode.clone();
-                    state.mark_dirty();
+                    state.mark_selected_key_dirty();
                     state.refresh_layer_refs(); // Update layer reference index
-                    state.set_status(format!("Updated: {new_keycode}"));
+                    state.log_action("Edit keycode", format!("Updated: {new_keycode}"));
                 }
 
                 state.active_popup = Some(PopupType::KeyEditor);
@@ -350,23 +367,31 @@ fn handle_keycode_picker_event(state: &mut AppState, event: KeycodePickerEvent)
 
             if let Some(key) = state.get_selected_key_mut() {
                 key.keycode = keycode.clone();
-                state.mark_dirty();
+                state.mark_selected_key_dirty();
                 state.refresh_layer_refs(); // Update layer reference index
 
                 // Show appropriate status message
                 if let Some(warning) = warning_msg {
-                    state.set_status_with_style(
+                    state.log_action(
+                        "Assign keycode",
                         format!("Assigned: {} - {}", keycode, warning),
-                        state.theme.error,
                     );
+                    state.status_color_override = Some(state.theme.error);
                 } else {
-                    state.set_status(format!("Assigned: {keycode}"));
+                    state.log_action("Assign keycode", format!("Assigned: {keycode}"));
                 }
             }
 
             state.close_component();
         }
         KeycodePickerEvent::Cancelled => {
+            if let Some(editor) = state.encoder_editor_cache.take() {
+                state.active_component = Some(ActiveComponent::EncoderEditor(editor));
+                state.active_popup = Some(PopupType::EncoderEditor);
+                state.set_status("Picker cancelled");
+                return Ok(false);
+            }
+
             // Check if we're in a tap dance form picker flow
             if let Some(form) = state.tap_dance_form_cache.take() {
                 // Clear pick target
@@ -398,12 +423,15 @@ fn handle_category_picker_event(
                 Some(crate::tui::CategoryPickerContext::IndividualKey) => {
                     if let Some(key) = state.get_selected_key_mut() {
                         key.category_id.clone_from(&category_id);
-                        state.mark_dirty();
+                        state.mark_selected_key_dirty();
 
                         if let Some(id) = category_id {
-                            state.set_status(format!("Assigned key category '{id}'"));
+                            state.log_action(
+                                "Assign category",
+                                format!("Assigned key category '{id}'"),
+                            );
                         } else {
-                            state.set_status("Removed key category");
+                            state.log_action("Assign category", "Removed key category");
                         }
                     }
                 }
@@ -413,9 +441,12 @@ fn handle_category_picker_event(
                         state.mark_dirty();
 
                         if let Some(id) = category_id {
-                            state.set_status(format!("Assigned layer category '{id}'"));
+                            state.log_action(
+                                "Assign category",
+                                format!("Assigned layer category '{id}'"),
+                            );
                         } else {
-                            state.set_status("Removed layer category");
+                            state.log_action("Assign category", "Removed layer category");
                         }
                     }
                 }
@@ -433,10 +464,15 @@ fn handle_category_picker_event(
                         if count > 0 {
                             state.mark_dirty();
                             if let Some(id) = &category_id {
-                                state
-                                    .set_status(format!("Applied category '{id}' to {count} keys"));
+                                state.log_action(
+                                    "Assign category",
+                                    format!("Applied category '{id}' to {count} keys"),
+                                );
                             } else {
-                                state.set_status(format!("Removed category from {count} keys"));
+                                state.log_action(
+                                    "Assign category",
+                                    format!("Removed category from {count} keys"),
+                                );
                             }
                         }
                     }
@@ -460,6 +496,12 @@ fn handle_category_picker_event(
 
 /// Handle color picker input using Component trait pattern
 fn handle_color_picker_event(state: &mut AppState, key: event::KeyEvent) -> Result<bool> {
+    // The eyedropper takes input until a key is sampled or it is cancelled
+    if color_sample::is_sampling(state) {
+        color_sample::handle_color_sample_input(state, key);
+        return Ok(false);
+    }
+
     // Get mutable reference to the ColorPicker component
     if let Some(ActiveComponent::ColorPicker(ref mut picker)) = state.active_component {
         // Handle input and get event
@@ -475,18 +517,21 @@ fn handle_color_picker_event(state: &mut AppState, key: event::KeyEvent) -> Resu
                         crate::tui::component::ColorPickerContext::IndividualKey => {
                             if let Some(key) = state.get_selected_key_mut() {
                                 key.color_override = Some(color);
-                                state.mark_dirty();
-                                state.set_status(format!("Set key color to {}", color.to_hex()));
+                                state.mark_selected_key_dirty();
+                                state.log_action(
+                                    "Set key color",
+                                    format!("Set key color to {}", color.to_hex()),
+                                );
                             }
                         }
                         crate::tui::component::ColorPickerContext::LayerDefault => {
                             if let Some(layer) = state.layout.layers.get_mut(state.current_layer) {
                                 layer.default_color = color;
                                 state.mark_dirty();
-                                state.set_status(format!(
-                                    "Set layer default color to {}",
-                                    color.to_hex()
-                                ));
+                                state.log_action(
+                                    "Set layer color",
+                                    format!("Set layer default color to {}", color.to_hex()),
+                                );
                             }
                         }
                         crate::tui::component::ColorPickerContext::Category => {
@@ -503,7 +548,10 @@ fn handle_color_picker_event(state: &mut AppState, key: event::KeyEvent) -> Resu
                                     {
                                         state.layout.categories.push(category);
                                         state.mark_dirty();
-                                        state.set_status(format!("Created category '{name}'"));
+                                        state.log_action(
+                                            "Create category",
+                                            format!("Created category '{name}'"),
+                                        );
                                     } else {
                                         state.set_error("Failed to create category");
                                     }
@@ -518,7 +566,10 @@ fn handle_color_picker_event(state: &mut AppState, key: event::KeyEvent) -> Resu
                                         let name = category.name.clone();
                                         category.set_color(color);
                                         state.mark_dirty();
-                                        state.set_status(format!("Updated color for '{name}'"));
+                                        state.log_action(
+                                            "Edit category",
+                                            format!("Updated color for '{name}'"),
+                                        );
                                     }
                                 }
                                 _ => {
@@ -548,10 +599,10 @@ fn handle_color_picker_event(state: &mut AppState, key: event::KeyEvent) -> Resu
 
                                 if count > 0 {
                                     state.mark_dirty();
-                                    state.set_status(format!(
-                                        "Set color to {} for {count} keys",
-                                        color.to_hex()
-                                    ));
+                                    state.log_action(
+                                        "Set key color",
+                                        format!("Set color to {} for {count} keys", color.to_hex()),
+                                    );
                                 }
                             }
                         }
@@ -568,8 +619,11 @@ fn handle_color_picker_event(state: &mut AppState, key: event::KeyEvent) -> Resu
                         crate::tui::component::ColorPickerContext::IndividualKey => {
                             if let Some(key) = state.get_selected_key_mut() {
                                 key.color_override = None;
-                                state.mark_dirty();
-                                state.set_status("Cleared key color (using layer default)");
+                                state.mark_selected_key_dirty();
+                                state.log_action(
+                                    "Clear key color",
+                                    "Cleared key color (using layer default)",
+                                );
                             }
                         }
                         crate::tui::component::ColorPickerContext::LayerDefault => {
@@ -577,7 +631,7 @@ fn handle_color_picker_event(state: &mut AppState, key: event::KeyEvent) -> Resu
                             if let Some(layer) = state.layout.layers.get_mut(state.current_layer) {
                                 layer.default_color = default_color;
                                 state.mark_dirty();
-                                state.set_status("Reset layer color to white");
+                                state.log_action("Reset layer color", "Reset layer color to white");
                             }
                         }
                         crate::tui::component::ColorPickerContext::Category => {
@@ -599,9 +653,12 @@ fn handle_color_picker_event(state: &mut AppState, key: event::KeyEvent) -> Resu
 
                                 if count > 0 {
                                     state.mark_dirty();
-                                    state.set_status(format!(
-                                        "Cleared color for {count} keys (using layer default)"
-                                    ));
+                                    state.log_action(
+                                        "Clear key color",
+                                        format!(
+                                            "Cleared color for {count} keys (using layer default)"
+                                        ),
+                                    );
                                 }
                             }
                         }
@@ -614,6 +671,7 @@ fn handle_color_picker_event(state: &mut AppState, key: event::KeyEvent) -> Resu
                     state.close_component();
                     state.set_status("Cancelled");
                 }
+                ColorPickerEvent::SampleRequested => color_sample::start_color_sampling(state),
             }
         }
     }
@@ -690,6 +748,10 @@ fn handle_help_overlay_event(
             state.close_component();
             state.set_status("Press ? for help");
         }
+        HelpOverlayEvent::RestartTutorial => {
+            state.close_component();
+            super::tutorial::start_tutorial(state);
+        }
     }
     Ok(false)
 }
@@ -734,10 +796,11 @@ fn handle_metadata_editor_event(
                     match LayoutService::rename_file_if_needed(old_path, &name) {
                         Ok(Some(new_path)) => {
                             state.source_path = Some(new_path);
-                            state.set_status(format!("Layout renamed to '{name}'"));
+                            state
+                                .log_action("Edit metadata", format!("Layout renamed to '{name}'"));
                         }
                         Ok(None) => {
-                            state.set_status("Metadata updated");
+                            state.log_action("Edit metadata", "Metadata updated");
                         }
                         Err(e) => {
                             state.set_error(format!("Failed to rename file: {e}"));
@@ -807,10 +870,16 @@ fn handle_layout_variant_picker_event(
                     if state.return_to_settings_after_picker {
                         state.return_to_settings_after_picker = false;
                         state.open_settings_manager();
-                        state.set_status(format!("Switched to layout: {selected}"));
+                        state.log_action(
+                            "Switch variant",
+                            format!("Switched to layout: {selected}"),
+                        );
                     } else {
                         state.active_popup = None;
-                        state.set_status(format!("Switched to layout: {selected}"));
+                        state.log_action(
+                            "Switch variant",
+                            format!("Switched to layout: {selected}"),
+                        );
                     }
                     state.mark_dirty(); // Config change requires save
                 }
@@ -887,9 +956,9 @@ fn handle_layer_picker_event(
 
                     if let Some(key) = state.get_selected_key_mut() {
                         key.keycode = new_keycode.clone();
-                        state.mark_dirty();
+                        state.mark_selected_key_dirty();
                         state.refresh_layer_refs(); // Update layer reference index
-                        state.set_status(format!("Updated: {new_keycode}"));
+                        state.log_action("Edit keycode", format!("Updated: {new_keycode}"));
                     }
 
                     state.active_popup = Some(PopupType::KeyEditor);
@@ -914,9 +983,9 @@ fn handle_layer_picker_event(
                     // Default to MO() for momentary layer switch
                     let keycode = format!("MO({})", layer_ref);
                     key.keycode = keycode.clone();
-                    state.mark_dirty();
+                    state.mark_selected_key_dirty();
                     state.refresh_layer_refs(); // Update layer reference index
-                    state.set_status(format!("Assigned: {keycode}"));
+                    state.log_action("Assign keycode", format!("Assigned: {keycode}"));
                 }
             }
 
@@ -948,6 +1017,19 @@ fn handle_layer_picker_event(
 pub fn handle_tap_keycode_picker_input(state: &mut AppState, key: event::KeyEvent) -> Result<bool> {
     // Use the component-based picker
     if let Some(ActiveComponent::KeycodePicker(ref mut picker)) = state.active_component {
+        // Direct entry owns Enter/Esc until it closes
+        if picker.state().direct_entry.is_some() {
+            if let Some(KeycodePickerEvent::KeycodeSelected(keycode)) =
+                picker.handle_input(key, &state.keycode_db)
+            {
+                if is_basic_keycode(&keycode) {
+                    handle_parameter_collected(state, keycode);
+                } else {
+                    state.set_error("Only basic keycodes allowed for tap action");
+                }
+            }
+            return Ok(false);
+        }
         match key.code {
             KeyCode::Esc => {
                 // Cancel the whole parameterized keycode flow
@@ -1019,8 +1101,8 @@ fn handle_modifier_picker_event(
 
                 if let Some(key) = state.get_selected_key_mut() {
                     key.keycode = new_keycode.clone();
-                    state.mark_dirty();
-                    state.set_status(format!("Updated: {new_keycode}"));
+                    state.mark_selected_key_dirty();
+                    state.log_action("Edit keycode", format!("Updated: {new_keycode}"));
                 }
 
                 state.active_popup = Some(PopupType::KeyEditor);
@@ -1269,18 +1351,18 @@ pub fn handle_tap_dance_form_input(state: &mut AppState, key: event::KeyEvent) -
 
                 match context {
                     crate::tui::TapDanceFormContext::FromEditor => {
-                        state.set_status(status_message);
+                        state.log_action("Save tap dance", status_message);
                         state.active_popup = None;
                         state.active_component = None;
                         state.open_tap_dance_editor();
                     }
                     crate::tui::TapDanceFormContext::FromKeycodePicker => {
-                        state.set_status(status_message);
+                        state.log_action("Save tap dance", status_message);
                         if let Some(key) = state.get_selected_key_mut() {
                             let td_keycode = format!("TD({name})");
                             key.keycode = td_keycode.clone();
-                            state.mark_dirty();
-                            state.set_status(format!("Applied: {td_keycode}"));
+                            state.mark_selected_key_dirty();
+                            state.log_action("Assign tap dance", format!("Applied: {td_keycode}"));
                         } else {
                             state.set_error("No key selected");
                         }
@@ -1333,19 +1415,13 @@ pub fn handle_export_filename_dialog_input(
     use crate::tui::handlers::action_handlers::file_ops;
 
     match key.code {
-        KeyCode::Char(c) => {
-            // Add character to filename
-            state.export_filename_dialog_state.filename.push(c);
-            Ok(false)
-        }
-        KeyCode::Backspace => {
-            // Remove last character
-            state.export_filename_dialog_state.filename.pop();
-            Ok(false)
-        }
         KeyCode::Enter => {
             // Perform export
-            let filename = state.export_filename_dialog_state.filename.clone();
+            let filename = state
+                .export_filename_dialog_state
+                .filename
+                .value()
+                .to_string();
             state.active_popup = None;
 
             if filename.trim().is_empty() {
@@ -1379,7 +1455,10 @@ pub fn handle_export_filename_dialog_input(
             state.set_status("Export cancelled");
             Ok(false)
         }
-        _ => Ok(false),
+        _ => {
+            state.export_filename_dialog_state.filename.handle_key(key);
+            Ok(false)
+        }
     }
 }
 
@@ -1411,6 +1490,82 @@ pub fn handle_unsaved_prompt_input(state: &mut AppState, key: event::KeyEvent) -
     }
 }
 
+/// Handle input for the keyboard mismatch prompt shown before generating
+pub fn handle_keyboard_mismatch_input(state: &mut AppState, key: event::KeyEvent) -> Result<bool> {
+    match key.code {
+        KeyCode::Char('y' | 'Y') | KeyCode::Enter => {
+            state.active_popup = None;
+            match state.keyboard_mismatch.take().map(|(_, action)| action) {
+                Some(FirmwareAction::Generate) => actions::run_firmware_generation(state)?,
+                Some(FirmwareAction::Build) => actions::continue_firmware_build(state)?,
+                None => {}
+            }
+            Ok(false)
+        }
+        KeyCode::Char('n' | 'N' | 'q') | KeyCode::Esc => {
+            state.active_popup = None;
+            state.keyboard_mismatch = None;
+            state.set_status("Cancelled");
+            Ok(false)
+        }
+        _ => Ok(false),
+    }
+}
+
+/// Handle input for the prompt shown when building a layout that changed
+/// since the last generation
+pub fn handle_stale_generation_input(state: &mut AppState, key: event::KeyEvent) -> Result<bool> {
+    match key.code {
+        KeyCode::Char('y' | 'Y') | KeyCode::Enter => {
+            state.active_popup = None;
+            actions::run_firmware_build(state, true)?;
+        }
+        KeyCode::Char('n' | 'N') => {
+            state.active_popup = None;
+            actions::run_firmware_build(state, false)?;
+        }
+        KeyCode::Char('q') | KeyCode::Esc => {
+            state.active_popup = None;
+            state.set_status("Cancelled");
+        }
+        _ => {}
+    }
+    Ok(false)
+}
+
+/// Handle input for the geometry mismatch dialog shown on load
+pub fn handle_geometry_mismatch_input(state: &mut AppState, key: event::KeyEvent) -> Result<bool> {
+    match key.code {
+        KeyCode::Char('r' | 'R') => {
+            state.resolve_geometry_mismatch(true)?;
+            Ok(false)
+        }
+        KeyCode::Char('a' | 'A') | KeyCode::Enter => {
+            let report = state.resolve_geometry_mismatch(false)?;
+            let mut summary = report.summary();
+            if !report.removed_assigned.is_empty() {
+                let removed = report
+                    .removed_assigned
+                    .iter()
+                    .map(|(layer, pos, keycode)| {
+                        format!("layer {layer} ({}, {}) {keycode}", pos.row, pos.col)
+                    })
+                    .collect::<Vec<_>>()
+                    .join(", ");
+                summary = format!("{summary}; removed: {removed}");
+            }
+            state.log_action("Fit layout to geometry", summary);
+            Ok(false)
+        }
+        KeyCode::Esc | KeyCode::Char('q' | 'Q') => {
+            // Abort: the layout is not opened
+            state.should_quit = true;
+            Ok(true)
+        }
+        _ => Ok(false),
+    }
+}
+
 /// Handle input when popup is active (dispatcher)
 pub fn handle_popup_input(state: &mut AppState, key: event::KeyEvent) -> Result<bool> {
     let popup_type = state.active_popup.clone();
@@ -1443,8 +1598,22 @@ pub fn handle_popup_input(state: &mut AppState, key: event::KeyEvent) -> Result<
         Some(PopupType::LayerPicker) => handle_layer_picker_input(state, key),
         Some(PopupType::TemplateBrowser) => super::handle_template_browser_input(state, key),
         Some(PopupType::TemplateSaveDialog) => super::handle_template_save_dialog_input(state, key),
+        Some(PopupType::SnapshotBrowser) => super::handle_snapshot_browser_input(state, key),
+        Some(PopupType::KeycodeSearch) => super::handle_keycode_search_input(state, key),
+        Some(PopupType::LintReport) => super::handle_lint_report_input(state, key),
+        Some(PopupType::ValidationResults) => super::handle_validation_results_input(state, key),
+        Some(PopupType::ActionLog) => super::handle_action_log_input(state, key),
+        Some(PopupType::History) => super::handle_history_input(state, key),
+        Some(PopupType::GenerationResults) => super::handle_generation_results_input(state, key),
+        Some(PopupType::GridPaste) => super::handle_grid_paste_input(state, key),
+        Some(PopupType::KeyRoleEditor) => super::handle_key_role_editor_input(state, key),
+        Some(PopupType::EncoderEditor) => super::handle_encoder_editor_input(state, key),
+        Some(PopupType::CommandPalette) => super::handle_command_palette_input(state, key),
         Some(PopupType::ExportFilenameDialog) => handle_export_filename_dialog_input(state, key),
         Some(PopupType::UnsavedChangesPrompt) => handle_unsaved_prompt_input(state, key),
+        Some(PopupType::GeometryMismatch) => handle_geometry_mismatch_input(state, key),
+        Some(PopupType::KeyboardMismatch) => handle_keyboard_mismatch_input(state, key),
+        Some(PopupType::StaleGeneration) => handle_stale_generation_input(state, key),
         Some(PopupType::BuildLog) => handle_build_log_input(state, key),
         Some(PopupType::HelpOverlay) => handle_help_overlay_input(state, key),
         Some(PopupType::MetadataEditor) => handle_metadata_editor_input(state, key),
@@ -1487,6 +1656,7 @@ mod tests {
             idle_effect_settings: crate::models::IdleEffectSettings::default(),
             tap_hold_settings: crate::models::TapHoldSettings::default(),
             tap_dances: vec![],
+            orphaned_keys: Vec::new(),
         };
         let mut state = AppState::new(
             layout,
@@ -1520,7 +1690,9 @@ mod tests {
         // Setup layout
         use crate::models::{KeyDefinition, Layer, Position};
         let mut layer = Layer::new(0, "Base", crate::models::RgbColor::default()).unwrap();
-        layer.add_key(KeyDefinition::new(Position::new(0, 0), "KC_TRNS"));
+        layer
+            .add_key(KeyDefinition::new(Position::new(0, 0), "KC_TRNS"))
+            .unwrap();
         state.layout.layers.push(layer);
         state.current_layer = 0;
         state.selected_position = Position::new(0, 0);
@@ -1582,7 +1754,9 @@ mod tests {
         // Setup layout
         use crate::models::{KeyDefinition, Layer, Position};
         let mut layer = Layer::new(0, "Base", crate::models::RgbColor::default()).unwrap();
-        layer.add_key(KeyDefinition::new(Position::new(0, 0), "KC_TRNS"));
+        layer
+            .add_key(KeyDefinition::new(Position::new(0, 0), "KC_TRNS"))
+            .unwrap();
         state.layout.layers.push(layer);
         state.current_layer = 0;
         state.selected_position = Position::new(0, 0);
@@ -1604,7 +1778,9 @@ mod tests {
         // Setup layout with a key
         use crate::models::{KeyDefinition, Layer, Position};
         let mut layer = Layer::new(0, "Base", crate::models::RgbColor::default()).unwrap();
-        layer.add_key(KeyDefinition::new(Position::new(0, 0), "KC_TRNS"));
+        layer
+            .add_key(KeyDefinition::new(Position::new(0, 0), "KC_TRNS"))
+            .unwrap();
         state.layout.layers.push(layer);
         state.current_layer = 0;
         state.selected_position = Position::new(0, 0);
@@ -1658,7 +1834,9 @@ mod tests {
 
         // Setup: Add a layer with a key that has this tap dance
         let mut layer = Layer::new(0, "Base", crate::models::RgbColor::default()).unwrap();
-        layer.add_key(KeyDefinition::new(Position::new(0, 0), "TD(slash)"));
+        layer
+            .add_key(KeyDefinition::new(Position::new(0, 0), "TD(slash)"))
+            .unwrap();
         state.layout.layers.push(layer);
         state.current_layer = 0;
         state.selected_position = Position::new(0, 0);
@@ -1724,4 +1902,192 @@ mod tests {
         // Layer references should be rejected
         assert!(!is_basic_or_layer_keycode("MO(@layer_id)"));
     }
+
+    #[test]
+    fn test_encoder_editor_picks_and_saves_directions() {
+        use crate::models::{KeyGeometry, KeyboardGeometry, Layer, Position, VisualLayoutMapping};
+        use crossterm::event::{KeyCode, KeyEvent, KeyModifiers};
+
+        let mut state = create_test_state();
+        let mut geometry = KeyboardGeometry::new("test", "test", 1, 1);
+        geometry.add_key(KeyGeometry::new((0, 0), 0, 0.0, 0.0));
+        geometry.encoder_count = 1;
+        state.mapping = VisualLayoutMapping::build(&geometry);
+        state.geometry = geometry;
+        state
+            .layout
+            .layers
+            .push(Layer::new(0, "Base", crate::models::RgbColor::default()).unwrap());
+        state.selected_position = Position::new(0, 1);
+
+        crate::tui::handlers::action_handlers::popups::handle_open_keycode_picker(&mut state)
+            .unwrap();
+        assert_eq!(state.active_popup, Some(PopupType::EncoderEditor));
+
+        let press = |state: &mut AppState, code: KeyCode, modifiers: KeyModifiers| {
+            handle_popup_input(state, KeyEvent::new(code, modifiers)).unwrap();
+        };
+        press(&mut state, KeyCode::Down, KeyModifiers::NONE);
+        press(&mut state, KeyCode::Enter, KeyModifiers::NONE);
+        assert_eq!(state.active_popup, Some(PopupType::KeycodePicker));
+
+        // Turns are taps, so parameterized keycodes are refused
+        handle_keycode_picker_event(
+            &mut state,
+            KeycodePickerEvent::KeycodeSelected("LT(1, KC_A)".to_string()),
+        )
+        .unwrap();
+        assert!(state.error_message.is_some());
+        handle_keycode_picker_event(
+            &mut state,
+            KeycodePickerEvent::KeycodeSelected("KC_VOLU".to_string()),
+        )
+        .unwrap();
+        assert_eq!(state.active_popup, Some(PopupType::EncoderEditor));
+
+        press(&mut state, KeyCode::Char('s'), KeyModifiers::CONTROL);
+        assert_eq!(state.active_popup, None);
+        assert_eq!(state.layout.layers[0].encoder(0).cw, "KC_VOLU");
+        assert_eq!(state.layout.layers[0].encoder(0).ccw, "RM_NEXT");
+        assert!(state.dirty);
+    }
+
+    fn mismatched_state() -> AppState {
+        use crate::models::{
+            KeyDefinition, KeyGeometry, KeyboardGeometry, Layer, Position, VisualLayoutMapping,
+        };
+
+        let mut state = create_test_state();
+        let mut geometry = KeyboardGeometry::new("test", "test", 1, 2);
+        geometry.add_key(KeyGeometry::new((0, 0), 0, 0.0, 0.0));
+        geometry.add_key(KeyGeometry::new((0, 1), 1, 1.0, 0.0));
+        state.mapping = VisualLayoutMapping::build(&geometry);
+        state.geometry = geometry;
+        let mut layer = Layer::new(0, "Base", crate::models::RgbColor::default()).unwrap();
+        for (col, keycode) in ["KC_A", "KC_B", "KC_C"].into_iter().enumerate() {
+            layer
+                .add_key(KeyDefinition::new(Position::new(0, col as u8), keycode))
+                .unwrap();
+        }
+        state.layout.layers.push(layer);
+        state.selected_position = Position::new(0, 2);
+        state.check_geometry_on_load().unwrap();
+        state
+    }
+
+    #[test]
+    fn test_geometry_mismatch_prompts_before_dropping_keys() {
+        let state = mismatched_state();
+
+        assert_eq!(state.active_popup, Some(PopupType::GeometryMismatch));
+        assert_eq!(state.geometry_mismatch.as_ref().unwrap().extra_keys(), 1);
+        // Nothing is removed until the user decides
+        assert_eq!(state.layout.layers[0].keys.len(), 3);
+    }
+
+    #[test]
+    fn test_geometry_mismatch_auto_adjust_logs_orphans() {
+        use crossterm::event::KeyEvent;
+
+        let mut state = mismatched_state();
+        let quit = handle_popup_input(
+            &mut state,
+            KeyEvent::new(KeyCode::Char('a'), KeyModifiers::NONE),
+        )
+        .unwrap();
+
+        assert!(!quit);
+        assert!(state.active_popup.is_none());
+        assert!(state.geometry_mismatch.is_none());
+        assert_eq!(state.layout.layers[0].keys.len(), 2);
+        assert_eq!(state.selected_position, crate::models::Position::new(0, 0));
+        assert!(state.dirty);
+        let entry = state.action_log.entries().last().unwrap();
+        assert_eq!(entry.action, "Fit layout to geometry");
+        assert!(entry.summary.contains("layer 0 (0, 2) KC_C"));
+    }
+
+    #[test]
+    fn test_geometry_mismatch_read_only_and_abort() {
+        use crossterm::event::KeyEvent;
+
+        let mut state = mismatched_state();
+        handle_popup_input(
+            &mut state,
+            KeyEvent::new(KeyCode::Char('r'), KeyModifiers::NONE),
+        )
+        .unwrap();
+        assert!(state.is_read_only());
+        assert!(!state.dirty);
+        assert_eq!(state.layout.layers[0].keys.len(), 2);
+
+        let mut state = mismatched_state();
+        let quit = handle_popup_input(&mut state, KeyEvent::new(KeyCode::Esc, KeyModifiers::NONE))
+            .unwrap();
+        assert!(quit);
+        assert!(state.should_quit);
+    }
+
+    #[test]
+    fn test_keyboard_mismatch_asks_before_generating() {
+        use crossterm::event::KeyEvent;
+
+        let mut state = create_test_state();
+        state.layout.metadata.keyboard = Some("lily58".to_string());
+        state.layout.metadata.keymap_name = Some("mine".to_string());
+        state.config.build.keyboard = Some("crkbd".to_string());
+
+        actions::handle_firmware_generation(&mut state).unwrap();
+        assert_eq!(state.active_popup, Some(PopupType::KeyboardMismatch));
+        let (mismatch, action) = state.keyboard_mismatch.clone().unwrap();
+        assert_eq!(action, FirmwareAction::Generate);
+        assert_eq!(mismatch.layout_keyboard, "lily58");
+        assert_eq!(mismatch.build_keyboard, "crkbd");
+        assert!(mismatch
+            .target_path
+            .ends_with("keyboards/lily58/keymaps/mine"));
+
+        handle_popup_input(&mut state, KeyEvent::new(KeyCode::Esc, KeyModifiers::NONE)).unwrap();
+        assert!(state.active_popup.is_none());
+        assert!(state.keyboard_mismatch.is_none());
+
+        // Confirming runs the generation it was asked for
+        actions::handle_firmware_build(&mut state).unwrap();
+        assert_eq!(
+            state.keyboard_mismatch.as_ref().map(|(_, action)| *action),
+            Some(FirmwareAction::Build)
+        );
+        handle_popup_input(
+            &mut state,
+            KeyEvent::new(KeyCode::Char('y'), KeyModifiers::NONE),
+        )
+        .unwrap();
+        assert!(state.keyboard_mismatch.is_none());
+        assert_ne!(state.active_popup, Some(PopupType::KeyboardMismatch));
+        assert_ne!(state.status_message, "Cancelled");
+
+        // Matching keyboards (or a variant of the target) don't ask
+        state.config.build.keyboard = Some("lily58/rev1".to_string());
+        actions::handle_firmware_generation(&mut state).unwrap();
+        assert!(state.keyboard_mismatch.is_none());
+    }
+
+    #[test]
+    fn test_stale_generation_asks_before_building() {
+        use crossterm::event::KeyEvent;
+
+        let mut state = create_test_state();
+        state.generation_hash = Some("older layout".to_string());
+
+        actions::handle_firmware_build(&mut state).unwrap();
+        assert_eq!(state.active_popup, Some(PopupType::StaleGeneration));
+        handle_popup_input(&mut state, KeyEvent::new(KeyCode::Esc, KeyModifiers::NONE)).unwrap();
+        assert!(state.active_popup.is_none());
+        assert_eq!(state.status_message, "Cancelled");
+
+        // Building what was just generated doesn't ask
+        state.generation_hash = Some(state.layout.content_hash());
+        actions::handle_firmware_build(&mut state).unwrap();
+        assert_ne!(state.active_popup, Some(PopupType::StaleGeneration));
+    }
 }
@@ -241,10 +241,41 @@ fn handle_browsing_enter(state: &mut AppState) -> Result<bool> {
                         .state_mut()
                         .start_toggling_boolean(*setting, state.config.ui.show_help_on_startup);
                 }
+                SettingItem::NavigationCoalescing => {
+                    manager
+                        .state_mut()
+                        .start_toggling_boolean(*setting, state.config.ui.coalesce_navigation);
+                }
+                SettingItem::NavigationAcceleration => {
+                    manager
+                        .state_mut()
+                        .start_toggling_boolean(*setting, state.config.ui.navigation_acceleration);
+                }
+                SettingItem::BuildNotifications => {
+                    manager
+                        .state_mut()
+                        .start_toggling_boolean(*setting, state.config.ui.build_notifications);
+                }
+                SettingItem::ReducedMotion => {
+                    manager
+                        .state_mut()
+                        .start_toggling_boolean(*setting, state.config.ui.reduced_motion);
+                }
+                SettingItem::ValidationBanner => {
+                    manager
+                        .state_mut()
+                        .start_toggling_boolean(*setting, state.config.ui.validation_banner);
+                }
+                SettingItem::TransparentKeys => {
+                    manager
+                        .state_mut()
+                        .start_selecting_transparent_keys(state.config.ui.transparent_keys);
+                }
                 SettingItem::ThemeMode => {
                     let selected = match state.config.ui.theme_mode {
                         crate::config::ThemeMode::Dark => 1,
                         crate::config::ThemeMode::Light => 2,
+                        crate::config::ThemeMode::HighContrast => 3,
                         crate::config::ThemeMode::Auto => 0,
                     };
                     manager.state_mut().start_selecting_theme_mode(selected);
@@ -335,10 +366,10 @@ fn apply_settings(state: &mut AppState) -> Result<()> {
                     if let Some(&preset) = TapHoldPreset::all().get(selected_idx) {
                         state.layout.tap_hold_settings.apply_preset(preset);
                         state.mark_dirty();
-                        state.set_status(format!(
-                            "Tap-hold preset set to: {}",
-                            preset.display_name()
-                        ));
+                        state.log_action(
+                            "Change setting",
+                            format!("Tap-hold preset set to: {}", preset.display_name()),
+                        );
                     }
                 }
             }
@@ -348,7 +379,10 @@ fn apply_settings(state: &mut AppState) -> Result<()> {
                         state.layout.tap_hold_settings.hold_mode = mode;
                         state.layout.tap_hold_settings.mark_custom();
                         state.mark_dirty();
-                        state.set_status(format!("Hold mode set to: {}", mode.display_name()));
+                        state.log_action(
+                            "Change setting",
+                            format!("Hold mode set to: {}", mode.display_name()),
+                        );
                     }
                 }
             }
@@ -379,7 +413,7 @@ fn apply_settings(state: &mut AppState) -> Result<()> {
                     };
                     state.layout.metadata.output_format = Some(format.to_string());
                     state.layout.metadata.touch();
-                    state.set_status(format!("Output format set to: {format}"));
+                    state.log_action("Change setting", format!("Output format set to: {format}"));
                 }
             }
             crate::tui::settings_manager::ManagerMode::SelectingThemeMode { .. } => {
@@ -387,16 +421,33 @@ fn apply_settings(state: &mut AppState) -> Result<()> {
                     let theme_mode = match selected_idx {
                         1 => crate::config::ThemeMode::Dark,
                         2 => crate::config::ThemeMode::Light,
+                        3 => crate::config::ThemeMode::HighContrast,
                         _ => crate::config::ThemeMode::Auto,
                     };
                     state.config.ui.theme_mode = theme_mode;
                     if let Err(e) = state.config.save() {
                         state.set_status(format!("Failed to save config: {e}"));
                     } else {
-                        state.set_status(format!(
-                            "Theme mode set to: {}",
-                            theme_mode_display(theme_mode)
-                        ));
+                        state.log_action(
+                            "Change setting",
+                            format!("Theme mode set to: {}", theme_mode_display(theme_mode)),
+                        );
+                    }
+                }
+            }
+            crate::tui::settings_manager::ManagerMode::SelectingTransparentKeys { .. } => {
+                if let Some(style) = manager_state
+                    .get_selected_option()
+                    .and_then(|idx| crate::config::TransparentKeyStyle::all().get(idx).copied())
+                {
+                    state.config.ui.transparent_keys = style;
+                    if let Err(e) = state.config.save() {
+                        state.set_status(format!("Failed to save config: {e}"));
+                    } else {
+                        state.log_action(
+                            "Change setting",
+                            format!("Transparent keys set to: {}", style.display_name()),
+                        );
                     }
                 }
             }
@@ -410,10 +461,10 @@ fn apply_settings(state: &mut AppState) -> Result<()> {
                     if let Some(&mode) = RgbMatrixEffect::all().get(selected_idx) {
                         state.layout.idle_effect_settings.idle_effect_mode = mode;
                         state.mark_dirty();
-                        state.set_status(format!(
-                            "Idle effect mode set to: {}",
-                            mode.display_name()
-                        ));
+                        state.log_action(
+                            "Change setting",
+                            format!("Idle effect mode set to: {}", mode.display_name()),
+                        );
                     }
                 }
             }
@@ -429,7 +480,7 @@ fn apply_numeric_setting(state: &mut AppState, setting: SettingItem, value: u16)
         SettingItem::TappingTerm => {
             state.layout.tap_hold_settings.tapping_term = value;
             state.layout.tap_hold_settings.mark_custom();
-            state.set_status(format!("Tapping term set to: {value}ms"));
+            state.log_action("Change setting", format!("Tapping term set to: {value}ms"));
         }
         SettingItem::QuickTapTerm => {
             state.layout.tap_hold_settings.quick_tap_term =
@@ -440,12 +491,18 @@ fn apply_numeric_setting(state: &mut AppState, setting: SettingItem, value: u16)
             } else {
                 format!("{value}ms")
             };
-            state.set_status(format!("Quick tap term set to: {display}"));
+            state.log_action(
+                "Change setting",
+                format!("Quick tap term set to: {display}"),
+            );
         }
         SettingItem::TappingToggle => {
             state.layout.tap_hold_settings.tapping_toggle = value as u8;
             state.layout.tap_hold_settings.mark_custom();
-            state.set_status(format!("Tapping toggle set to: {value} taps"));
+            state.log_action(
+                "Change setting",
+                format!("Tapping toggle set to: {value} taps"),
+            );
         }
         SettingItem::FlowTapTerm => {
             state.layout.tap_hold_settings.flow_tap_term =
@@ -456,15 +513,15 @@ fn apply_numeric_setting(state: &mut AppState, setting: SettingItem, value: u16)
             } else {
                 format!("{value}ms")
             };
-            state.set_status(format!("Flow tap term set to: {display}"));
+            state.log_action("Change setting", format!("Flow tap term set to: {display}"));
         }
         SettingItem::RgbBrightness => {
             state.layout.rgb_brightness = RgbBrightness::from(value as u8);
-            state.set_status(format!("RGB brightness set to: {value}%"));
+            state.log_action("Change setting", format!("RGB brightness set to: {value}%"));
         }
         SettingItem::RgbSaturation => {
             state.layout.rgb_saturation = RgbSaturation::from(value as u8);
-            state.set_status(format!("RGB saturation set to: {value}%"));
+            state.log_action("Change setting", format!("RGB saturation set to: {value}%"));
         }
         SettingItem::UncoloredKeyBehavior => {
             state.layout.uncolored_key_behavior = UncoloredKeyBehavior::from(value as u8);
@@ -472,11 +529,16 @@ fn apply_numeric_setting(state: &mut AppState, setting: SettingItem, value: u16)
                 0 => "Off (Black)",
                 100 => "Show Color",
                 n => {
-                    return state
-                        .set_status(format!("Uncolored key brightness set to: {n}% (Dimmed)"))
+                    return state.log_action(
+                        "Change setting",
+                        format!("Uncolored key brightness set to: {n}% (Dimmed)"),
+                    )
                 }
             };
-            state.set_status(format!("Uncolored key brightness set to: {description}"));
+            state.log_action(
+                "Change setting",
+                format!("Uncolored key brightness set to: {description}"),
+            );
         }
         SettingItem::RgbTimeout => {
             // value is in seconds, convert to milliseconds for storage
@@ -488,7 +550,7 @@ fn apply_numeric_setting(state: &mut AppState, setting: SettingItem, value: u16)
             } else {
                 format!("{value} sec")
             };
-            state.set_status(format!("RGB timeout set to: {display}"));
+            state.log_action("Change setting", format!("RGB timeout set to: {display}"));
         }
         SettingItem::KeyboardScale => {
             // value is percentage (100 = 100%), convert to multiplier
@@ -497,7 +559,10 @@ fn apply_numeric_setting(state: &mut AppState, setting: SettingItem, value: u16)
             if let Err(e) = state.config.save() {
                 state.set_status(format!("Failed to save config: {e}"));
             } else {
-                state.set_status(format!("Keyboard scale set to: {:.0}%", scale * 100.0));
+                state.log_action(
+                    "Change setting",
+                    format!("Keyboard scale set to: {:.0}%", scale * 100.0),
+                );
             }
         }
         SettingItem::IdleTimeout => {
@@ -510,7 +575,7 @@ fn apply_numeric_setting(state: &mut AppState, setting: SettingItem, value: u16)
             } else {
                 format!("{value} sec")
             };
-            state.set_status(format!("Idle timeout set to: {display}"));
+            state.log_action("Change setting", format!("Idle timeout set to: {display}"));
         }
         SettingItem::IdleEffectDuration => {
             // value is in seconds, convert to milliseconds for storage
@@ -522,7 +587,10 @@ fn apply_numeric_setting(state: &mut AppState, setting: SettingItem, value: u16)
             } else {
                 format!("{value} sec")
             };
-            state.set_status(format!("Idle effect duration set to: {display}"));
+            state.log_action(
+                "Change setting",
+                format!("Idle effect duration set to: {display}"),
+            );
         }
         _ => {}
     }
@@ -535,18 +603,21 @@ fn apply_boolean_setting(state: &mut AppState, setting: SettingItem, value: bool
             state.layout.tap_hold_settings.retro_tapping = value;
             state.layout.tap_hold_settings.mark_custom();
             let display = if value { "On" } else { "Off" };
-            state.set_status(format!("Retro tapping set to: {display}"));
+            state.log_action("Change setting", format!("Retro tapping set to: {display}"));
         }
         SettingItem::ChordalHold => {
             state.layout.tap_hold_settings.chordal_hold = value;
             state.layout.tap_hold_settings.mark_custom();
             let display = if value { "On" } else { "Off" };
-            state.set_status(format!("Chordal hold set to: {display}"));
+            state.log_action("Change setting", format!("Chordal hold set to: {display}"));
         }
         SettingItem::RgbEnabled => {
             state.layout.rgb_enabled = value;
             let display = if value { "On" } else { "Off" };
-            state.set_status(format!("RGB master switch set to: {display}"));
+            state.log_action(
+                "Change setting",
+                format!("RGB master switch set to: {display}"),
+            );
         }
         SettingItem::ShowHelpOnStartup => {
             state.config.ui.show_help_on_startup = value;
@@ -554,13 +625,79 @@ fn apply_boolean_setting(state: &mut AppState, setting: SettingItem, value: bool
                 state.set_status(format!("Failed to save config: {e}"));
             } else {
                 let display = if value { "On" } else { "Off" };
-                state.set_status(format!("Show help on startup set to: {display}"));
+                state.log_action(
+                    "Change setting",
+                    format!("Show help on startup set to: {display}"),
+                );
+            }
+        }
+        SettingItem::NavigationCoalescing => {
+            state.config.ui.coalesce_navigation = value;
+            if let Err(e) = state.config.save() {
+                state.set_status(format!("Failed to save config: {e}"));
+            } else {
+                let display = if value { "On" } else { "Off" };
+                state.log_action(
+                    "Change setting",
+                    format!("Navigation coalescing set to: {display}"),
+                );
+            }
+        }
+        SettingItem::NavigationAcceleration => {
+            state.config.ui.navigation_acceleration = value;
+            if let Err(e) = state.config.save() {
+                state.set_status(format!("Failed to save config: {e}"));
+            } else {
+                let display = if value { "On" } else { "Off" };
+                state.log_action(
+                    "Change setting",
+                    format!("Navigation acceleration set to: {display}"),
+                );
+            }
+        }
+        SettingItem::BuildNotifications => {
+            state.config.ui.build_notifications = value;
+            if let Err(e) = state.config.save() {
+                state.set_status(format!("Failed to save config: {e}"));
+            } else {
+                let display = if value { "On" } else { "Off" };
+                state.log_action(
+                    "Change setting",
+                    format!("Build notifications set to: {display}"),
+                );
+            }
+        }
+        SettingItem::ReducedMotion => {
+            state.config.ui.reduced_motion = value;
+            if let Err(e) = state.config.save() {
+                state.set_status(format!("Failed to save config: {e}"));
+            } else {
+                let display = if value { "On" } else { "Off" };
+                state.log_action(
+                    "Change setting",
+                    format!("Reduced motion set to: {display}"),
+                );
+            }
+        }
+        SettingItem::ValidationBanner => {
+            state.config.ui.validation_banner = value;
+            if let Err(e) = state.config.save() {
+                state.set_status(format!("Failed to save config: {e}"));
+            } else {
+                let display = if value { "On" } else { "Off" };
+                state.log_action(
+                    "Change setting",
+                    format!("Validation banner set to: {display}"),
+                );
             }
         }
         SettingItem::IdleEffectEnabled => {
             state.layout.idle_effect_settings.enabled = value;
             let display = if value { "On" } else { "Off" };
-            state.set_status(format!("Idle effect enabled set to: {display}"));
+            state.log_action(
+                "Change setting",
+                format!("Idle effect enabled set to: {display}"),
+            );
         }
         _ => {}
     }
@@ -577,7 +714,7 @@ fn apply_string_setting(state: &mut AppState, setting: SettingItem, value: Strin
             };
             state.layout.metadata.keymap_name = Some(keymap.clone());
             state.layout.metadata.touch();
-            state.set_status(format!("Keymap name set to: {keymap}"));
+            state.log_action("Change setting", format!("Keymap name set to: {keymap}"));
         }
         _ => {}
     }
@@ -596,14 +733,17 @@ fn apply_path_setting(state: &mut AppState, setting: SettingItem, value: String)
             if let Err(e) = state.config.save() {
                 state.set_status(format!("Failed to save config: {e}"));
             } else {
-                state.set_status(format!(
-                    "QMK firmware path set to: {}",
-                    if value.is_empty() {
-                        "(not set)"
-                    } else {
-                        &value
-                    }
-                ));
+                state.log_action(
+                    "Change setting",
+                    format!(
+                        "QMK firmware path set to: {}",
+                        if value.is_empty() {
+                            "(not set)"
+                        } else {
+                            &value
+                        }
+                    ),
+                );
             }
         }
         SettingItem::OutputDir => {
@@ -611,7 +751,10 @@ fn apply_path_setting(state: &mut AppState, setting: SettingItem, value: String)
             if let Err(e) = state.config.save() {
                 state.set_status(format!("Failed to save config: {e}"));
             } else {
-                state.set_status(format!("Output directory set to: {value}"));
+                state.log_action(
+                    "Change setting",
+                    format!("Output directory set to: {value}"),
+                );
             }
         }
         _ => {}
@@ -625,5 +768,6 @@ fn theme_mode_display(mode: crate::config::ThemeMode) -> &'static str {
         crate::config::ThemeMode::Auto => "Auto",
         crate::config::ThemeMode::Dark => "Dark",
         crate::config::ThemeMode::Light => "Light",
+        crate::config::ThemeMode::HighContrast => "High contrast",
     }
 }
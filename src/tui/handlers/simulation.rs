@@ -0,0 +1,243 @@
+//! Typing simulation input handlers.
+//!
+//! Typed characters press the base layer key that sends them; `Alt` with a
+//! character holds or releases the key instead, `Ctrl+T`/`Ctrl+G` tap or hold
+//! the selected key, `Ctrl+R` resets the layer state and `Esc` returns to the
+//! layer and key the simulation started from.
+
+use anyhow::Result;
+use crossterm::event::{self, KeyCode, KeyModifiers};
+
+use crate::models::Position;
+use crate::services::simulator::PressEffect;
+use crate::shortcuts::Action;
+use crate::tui::simulation::Simulation;
+use crate::tui::AppState;
+
+/// Starts simulating keypresses on the base layer.
+pub fn start_simulation(state: &mut AppState) {
+    state.simulation = Some(Simulation::new(
+        state.current_layer,
+        state.selected_position,
+    ));
+    state.current_layer = 0;
+    state.refresh_base_overlay();
+    state.set_status(
+        "Simulating - type to press keys, Alt+key: hold/release, Ctrl+T/Ctrl+G: tap/hold selected key, Esc: exit",
+    );
+}
+
+/// Handle input while the typing simulation runs
+pub fn handle_simulation_input(state: &mut AppState, key: event::KeyEvent) -> Result<bool> {
+    let ctrl = key.modifiers.contains(KeyModifiers::CONTROL);
+    let alt = key.modifiers.contains(KeyModifiers::ALT);
+
+    match key.code {
+        KeyCode::Esc => stop_simulation(state),
+        KeyCode::Up => return super::dispatch_action(state, Action::NavigateUp),
+        KeyCode::Down => return super::dispatch_action(state, Action::NavigateDown),
+        KeyCode::Left => return super::dispatch_action(state, Action::NavigateLeft),
+        KeyCode::Right => return super::dispatch_action(state, Action::NavigateRight),
+        KeyCode::Char('t') if ctrl => press(state, state.selected_position, false),
+        KeyCode::Char('g') if ctrl => press(state, state.selected_position, true),
+        KeyCode::Char('r') if ctrl => {
+            if let Some(sim) = &mut state.simulation {
+                sim.simulator.reset();
+            }
+            state.current_layer = 0;
+            state.refresh_base_overlay();
+            state.set_status("Simulation reset to the base layer");
+        }
+        code if !ctrl => {
+            let position = state
+                .simulation
+                .as_ref()
+                .and_then(|sim| sim.position_for(&state.layout, code));
+            match position {
+                Some(position) => press(state, position, alt),
+                None => state.set_error("No key on the base layer sends that key"),
+            }
+        }
+        _ => {}
+    }
+    Ok(false)
+}
+
+/// Ends the simulation and returns to the layer and key it started from.
+fn stop_simulation(state: &mut AppState) {
+    if let Some(sim) = state.simulation.take() {
+        state.current_layer = sim.restore_layer.min(state.layout.layers.len() - 1);
+        state.selected_position = sim.restore_position;
+        state.refresh_base_overlay();
+    }
+    state.set_status("Simulation ended");
+}
+
+/// Presses the key at `position`; holding a held key releases it.
+fn press(state: &mut AppState, position: Position, hold: bool) {
+    let Some(sim) = &mut state.simulation else {
+        return;
+    };
+
+    let status = if hold && sim.simulator.is_held(position) {
+        let layer = sim.simulator.release(position);
+        layer.map_or_else(String::new, |layer| {
+            format!(
+                "Released ({},{}): layer {layer} off",
+                position.row, position.col
+            )
+        })
+    } else {
+        let press = if hold {
+            sim.simulator.hold(&state.layout, position)
+        } else {
+            sim.simulator.tap(&state.layout, position)
+        };
+        describe(&press.keycode, press.layer, position, &press.effect)
+    };
+
+    let held: Vec<String> = sim
+        .simulator
+        .held()
+        .iter()
+        .map(|held| {
+            format!(
+                "L{} at ({},{})",
+                held.layer, held.position.row, held.position.col
+            )
+        })
+        .collect();
+    let held = if held.is_empty() {
+        String::new()
+    } else {
+        format!(" - held: {}", held.join(", "))
+    };
+    let one_shot = sim
+        .simulator
+        .one_shot()
+        .map_or_else(String::new, |layer| format!(" - one-shot: L{layer}"));
+
+    state.current_layer = sim.simulator.top_layer();
+    state.selected_position = position;
+    state.flash_highlight = Some((state.current_layer, position, 5));
+    state.refresh_base_overlay();
+
+    let layer_name = state
+        .layout
+        .layers
+        .get(state.current_layer)
+        .map_or("", |layer| layer.name.as_str());
+    state.set_status(format!(
+        "Layer {} ({layer_name}): {status}{held}{one_shot}",
+        state.current_layer
+    ));
+}
+
+/// Describes what a press did for the status bar.
+fn describe(keycode: &str, layer: usize, position: Position, effect: &PressEffect) -> String {
+    let at = format!("({},{})", position.row, position.col);
+    match effect {
+        PressEffect::Sends(sent) => format!("{at} sends {sent} from layer {layer}"),
+        PressEffect::HoldsLayer(target) => {
+            format!("holding {keycode} at {at}: layer {target} on (Alt+key again releases)")
+        }
+        PressEffect::ToggledLayer { layer: target, on } => {
+            let on = if *on { "on" } else { "off" };
+            format!("{keycode} at {at}: layer {target} {on}")
+        }
+        PressEffect::MovedTo(target) => format!("{keycode} at {at}: moved to layer {target}"),
+        PressEffect::OneShot(target) => {
+            format!("{keycode} at {at}: layer {target} for the next key")
+        }
+        PressEffect::DefaultLayer(target) => {
+            format!("{keycode} at {at}: default layer is now {target}")
+        }
+        PressEffect::NeedsHold(target) => {
+            format!("{keycode} at {at} only turns layer {target} on while held - Alt+key holds it")
+        }
+        PressEffect::NoKey => format!("{at} has no key on the active layers"),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::models::{
+        KeyDefinition, KeyGeometry, KeyboardGeometry, Layer, Layout, RgbColor, VisualLayoutMapping,
+    };
+
+    fn create_state() -> AppState {
+        let mut geometry = KeyboardGeometry::new("test", "test", 1, 3);
+        for col in 0..3u8 {
+            geometry.add_key(KeyGeometry::new((0, col), col, f32::from(col), 0.0));
+        }
+        let mut layout = Layout::new("Test").unwrap();
+        let rows: [[&str; 3]; 2] = [
+            ["KC_A", "LT(1, KC_SPC)", "TG(1)"],
+            ["KC_1", "KC_TRNS", "KC_TRNS"],
+        ];
+        for (index, keycodes) in rows.iter().enumerate() {
+            let mut layer =
+                Layer::new(index as u8, format!("L{index}"), RgbColor::new(0, 0, 0)).unwrap();
+            for (col, keycode) in keycodes.iter().enumerate() {
+                layer
+                    .add_key(KeyDefinition::new(Position::new(0, col as u8), *keycode))
+                    .unwrap();
+            }
+            layout.add_layer(layer).unwrap();
+        }
+        let mapping = VisualLayoutMapping::build(&geometry);
+        AppState::new(
+            layout,
+            None,
+            geometry,
+            mapping,
+            crate::config::Config::default(),
+        )
+        .unwrap()
+    }
+
+    fn type_key(state: &mut AppState, code: KeyCode, modifiers: KeyModifiers) {
+        handle_simulation_input(state, event::KeyEvent::new(code, modifiers)).unwrap();
+    }
+
+    #[test]
+    fn test_holding_layer_tap_follows_layer_and_restores_view() {
+        let mut state = create_state();
+        state.current_layer = 1;
+        state.selected_position = Position::new(0, 2);
+        start_simulation(&mut state);
+        assert_eq!(state.current_layer, 0);
+
+        type_key(&mut state, KeyCode::Char('a'), KeyModifiers::NONE);
+        assert!(state.status_message.contains("sends KC_A from layer 0"));
+
+        type_key(&mut state, KeyCode::Char(' '), KeyModifiers::ALT);
+        assert_eq!(state.current_layer, 1);
+        type_key(&mut state, KeyCode::Char('a'), KeyModifiers::NONE);
+        assert!(state.status_message.contains("sends KC_1 from layer 1"));
+        assert!(state.status_message.contains("held: L1 at (0,1)"));
+
+        type_key(&mut state, KeyCode::Char(' '), KeyModifiers::ALT);
+        assert_eq!(state.current_layer, 0);
+
+        type_key(&mut state, KeyCode::Esc, KeyModifiers::NONE);
+        assert!(state.simulation.is_none());
+        assert_eq!(state.current_layer, 1);
+        assert_eq!(state.selected_position, Position::new(0, 2));
+    }
+
+    #[test]
+    fn test_selected_key_can_be_tapped() {
+        let mut state = create_state();
+        start_simulation(&mut state);
+        state.selected_position = Position::new(0, 2);
+        type_key(&mut state, KeyCode::Char('t'), KeyModifiers::CONTROL);
+        assert_eq!(state.current_layer, 1);
+
+        type_key(&mut state, KeyCode::Char('r'), KeyModifiers::CONTROL);
+        assert_eq!(state.current_layer, 0);
+        type_key(&mut state, KeyCode::Char('q'), KeyModifiers::NONE);
+        assert!(state.error_message.is_some());
+    }
+}
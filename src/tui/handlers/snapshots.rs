@@ -0,0 +1,90 @@
+//! Snapshot browser input handlers.
+
+use anyhow::{Context, Result};
+use crossterm::event;
+
+use crate::services::LayoutService;
+use crate::tui::handlers::diff_view::start_diff_view;
+use crate::tui::{component::Component, snapshot_browser::SnapshotBrowserEvent, AppState};
+
+/// Handle input for snapshot browser
+pub fn handle_snapshot_browser_input(state: &mut AppState, key: event::KeyEvent) -> Result<bool> {
+    use crate::tui::ActiveComponent;
+
+    if let Some(ActiveComponent::SnapshotBrowser(ref mut browser)) = state.active_component {
+        if let Some(event) = browser.handle_input(key) {
+            return handle_snapshot_browser_event(state, event);
+        }
+    }
+    Ok(false)
+}
+
+/// Handle snapshot browser events
+fn handle_snapshot_browser_event(
+    state: &mut AppState,
+    event: SnapshotBrowserEvent,
+) -> Result<bool> {
+    match event {
+        SnapshotBrowserEvent::Restore(path) => {
+            match LayoutService::load(&path)
+                .with_context(|| format!("Loading snapshot from {}", path.display()))
+            {
+                Ok(layout) => {
+                    // Keep source_path so saving writes back to the original file,
+                    // which stays untouched until the user saves explicitly.
+                    state.layout = layout;
+                    state.adjust_layers_to_geometry()?;
+                    state.refresh_layer_refs();
+                    if state.current_layer >= state.layout.layers.len() {
+                        state.current_layer = 0;
+                    }
+                    state.mark_dirty();
+                    state.close_component();
+                    state.log_action(
+                        "Restore snapshot",
+                        "Snapshot restored - save with Ctrl+S to keep it",
+                    );
+                }
+                Err(e) => {
+                    state.set_error(format!("Failed to restore snapshot: {e}"));
+                }
+            }
+        }
+        SnapshotBrowserEvent::Diff(info) => {
+            match LayoutService::load(&info.path)
+                .with_context(|| format!("Loading snapshot from {}", info.path.display()))
+            {
+                Ok(snapshot) => {
+                    let label = format!("snapshot {}", info.timestamp.format("%Y-%m-%d %H:%M:%S"));
+                    state.close_component();
+                    start_diff_view(state, label, &snapshot);
+                }
+                Err(e) => {
+                    state.set_error(format!("Failed to load snapshot: {e}"));
+                }
+            }
+        }
+        SnapshotBrowserEvent::DiffSaved => {
+            let Some(path) = state.source_path.clone() else {
+                state.set_error("Layout has not been saved yet");
+                return Ok(false);
+            };
+            match LayoutService::load(&path)
+                .with_context(|| format!("Loading saved layout from {}", path.display()))
+            {
+                Ok(saved) => {
+                    state.close_component();
+                    start_diff_view(state, "saved file", &saved);
+                }
+                Err(e) => {
+                    state.set_error(format!("Failed to load saved layout: {e}"));
+                }
+            }
+        }
+        SnapshotBrowserEvent::Cancelled => {
+            state.close_component();
+            state.set_status("Snapshot browser closed");
+        }
+    }
+    Ok(false)
+}
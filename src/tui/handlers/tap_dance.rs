@@ -3,6 +3,7 @@
 use anyhow::Result;
 use crossterm::event;
 
+use crate::services::library::Library;
 use crate::tui::component::Component;
 use crate::tui::tap_dance_editor::TapDanceEditorEvent;
 use crate::tui::{ActiveComponent, AppState, PopupType};
@@ -36,7 +37,7 @@ pub fn handle_tap_dance_editor_input(state: &mut AppState, key: event::KeyEvent)
                     let td_keycode = format!("TD({name})");
                     key.keycode = td_keycode.clone();
                     state.mark_dirty();
-                    state.set_status(format!("Applied: {td_keycode}"));
+                    state.log_action("Assign tap dance", format!("Applied: {td_keycode}"));
                 } else {
                     state.set_error("No key selected");
                 }
@@ -104,11 +105,44 @@ pub fn handle_tap_dance_editor_input(state: &mut AppState, key: event::KeyEvent)
                 // Remove from layout.tap_dances
                 state.layout.remove_tap_dance(&name);
                 state.mark_dirty();
-                state.set_status(format!("Deleted tap dance '{name}'"));
+                state.log_action("Delete tap dance", format!("Deleted tap dance '{name}'"));
 
                 // Refresh editor with updated list
                 editor = crate::tui::tap_dance_editor::TapDanceEditor::new(&state.layout);
             }
+            TapDanceEditorEvent::OpenLibrary => {
+                let loaded = state
+                    .config
+                    .library_path()
+                    .and_then(|path| Library::load(&path).map(|library| (path, library)));
+                match loaded {
+                    Ok((path, library)) => {
+                        editor.show_library(library, &state.layout);
+                        state.set_status(format!("Importing from {}", path.display()));
+                    }
+                    Err(e) => state.set_error(format!("{e:#}")),
+                }
+            }
+            TapDanceEditorEvent::Import(name) => {
+                if let Some(library) = editor.library() {
+                    let report =
+                        library.import_into(&mut state.layout, Some(std::slice::from_ref(&name)));
+                    if report.has_conflicts() {
+                        state.set_error(format!(
+                            "Layout already has a different '{name}' - rename or delete it first"
+                        ));
+                    } else if report.added.is_empty() {
+                        state.set_status(format!("'{name}' is already in the layout"));
+                    } else {
+                        state.mark_dirty();
+                        state.log_action(
+                            "Import tap dance",
+                            format!("Imported '{name}' from library"),
+                        );
+                    }
+                }
+                editor.refresh(&state.layout);
+            }
             TapDanceEditorEvent::Cancelled => {
                 // Close the component
                 state.active_popup = None;
@@ -3,8 +3,10 @@
 use anyhow::{Context, Result};
 use crossterm::event::{self, KeyCode, KeyModifiers};
 
-use crate::services::LayoutService;
-use crate::tui::{component::Component, template_browser::TemplateBrowserState, AppState};
+use crate::services::{templates, LayoutService};
+use crate::tui::{
+    component::Component, template_browser::TemplateBrowserState, AppState, TemplateSaveField,
+};
 
 /// Handle input for template browser
 pub fn handle_template_browser_input(state: &mut AppState, key: event::KeyEvent) -> Result<bool> {
@@ -32,12 +34,14 @@ fn handle_template_browser_event(
             match LayoutService::load(&path)
                 .with_context(|| format!("Loading template from {}", path.display()))
             {
-                Ok(layout) => {
-                    state.layout = layout;
+                Ok(template) => {
+                    state.layout = templates::instantiate(template);
                     state.source_path = None; // New layout from template
                     state.mark_dirty(); // Mark as dirty since it's unsaved
+                                        // A new layout starts a new undo history
+                    state.undo_history.reset(&state.layout);
                     state.close_component();
-                    state.set_status("Template loaded");
+                    state.log_action("Load template", "Template loaded");
                 }
                 Err(e) => {
                     state.set_error(format!("Failed to load template: {e}"));
@@ -62,21 +66,21 @@ pub fn handle_template_save_dialog_input(
     state: &mut AppState,
     key: event::KeyEvent,
 ) -> Result<bool> {
+    let dialog_state = &mut state.template_save_dialog_state;
+    let on_sections = dialog_state.active_field == TemplateSaveField::Sections;
     match key.code {
-        KeyCode::Char(c) => {
-            // Add character to active field
-            state
-                .template_save_dialog_state
-                .get_active_field_mut()
-                .push(c);
+        KeyCode::Char(' ') | KeyCode::Char('x') if on_sections => {
+            // Toggle the highlighted section
+            let section = dialog_state.selected_section();
+            dialog_state.sections.toggle(section);
             Ok(false)
         }
-        KeyCode::Backspace => {
-            // Remove character from active field
-            state
-                .template_save_dialog_state
-                .get_active_field_mut()
-                .pop();
+        KeyCode::Up | KeyCode::Char('k') if on_sections => {
+            dialog_state.move_section(false);
+            Ok(false)
+        }
+        KeyCode::Down | KeyCode::Char('j') if on_sections => {
+            dialog_state.move_section(true);
             Ok(false)
         }
         KeyCode::Tab => {
@@ -93,7 +97,7 @@ pub fn handle_template_save_dialog_input(
             let dialog_state = &state.template_save_dialog_state;
 
             // Validate name is not empty
-            if dialog_state.name.trim().is_empty() {
+            if dialog_state.name.value().trim().is_empty() {
                 state.set_error("Template name cannot be empty");
                 return Ok(false);
             }
@@ -105,18 +109,18 @@ pub fn handle_template_save_dialog_input(
                 templates_dir.display()
             ))?;
 
-            // Update layout metadata for template
-            let mut template_layout = state.layout.clone();
-            template_layout.metadata.name = dialog_state.name.clone();
-            template_layout.metadata.description = dialog_state.description.clone();
-            template_layout.metadata.author = dialog_state.author.clone();
+            // Keep only the selected sections, then set the template metadata
+            let mut template_layout = dialog_state.sections.apply(&state.layout);
+            template_layout.metadata.name = dialog_state.name.value().to_string();
+            template_layout.metadata.description = dialog_state.description.value().to_string();
+            template_layout.metadata.author = dialog_state.author.value().to_string();
             template_layout.metadata.tags = dialog_state.parse_tags();
-            template_layout.metadata.is_template = true;
             template_layout.metadata.touch();
 
             // Generate filename from name (sanitize)
             let filename = dialog_state
                 .name
+                .value()
                 .to_lowercase()
                 .replace(' ', "-")
                 .chars()
@@ -143,6 +147,12 @@ pub fn handle_template_save_dialog_input(
             state.set_status("Template save cancelled");
             Ok(false)
         }
-        _ => Ok(false),
+        _ => {
+            // Edit the active text field
+            if let Some(field) = dialog_state.get_active_field_mut() {
+                field.handle_key(key);
+            }
+            Ok(false)
+        }
     }
 }
@@ -0,0 +1,125 @@
+//! First-run tutorial handlers.
+
+use crossterm::event::{self, KeyCode, KeyModifiers};
+
+use crate::shortcuts::Action;
+use crate::tui::{AppState, Tutorial};
+
+/// Starts the tour from its first step.
+pub fn start_tutorial(state: &mut AppState) {
+    match Tutorial::load() {
+        Ok(tutorial) => {
+            state.tutorial = Some(tutorial);
+            state.set_status("Tour started - Ctrl+N: next step, Esc: skip");
+        }
+        Err(e) => state.set_status(format!("Failed to load tutorial: {e}")),
+    }
+}
+
+/// Handles the tour's own keys; returns true if `key` was one of them.
+///
+/// Every other key reaches the main view, so the suggested actions can be
+/// tried while the tour is shown.
+pub fn handle_tutorial_input(state: &mut AppState, key: event::KeyEvent) -> bool {
+    let Some(tutorial) = &mut state.tutorial else {
+        return false;
+    };
+    match (key.code, key.modifiers) {
+        (KeyCode::Char('n'), KeyModifiers::CONTROL) => {
+            tutorial.next();
+            if tutorial.is_finished() {
+                finish_tutorial(state, "Tour finished");
+            }
+            true
+        }
+        (KeyCode::Esc, _) => {
+            finish_tutorial(state, "Tour skipped");
+            true
+        }
+        _ => false,
+    }
+}
+
+/// Advances the tour if `action` is what the current step asks for.
+pub fn observe_action(state: &mut AppState, action: Action) {
+    let Some(tutorial) = &mut state.tutorial else {
+        return;
+    };
+    if tutorial.observe(action.id()) && tutorial.is_finished() {
+        finish_tutorial(state, "Tour finished");
+    }
+}
+
+/// Ends the tour and remembers not to show it on the next launch.
+fn finish_tutorial(state: &mut AppState, outcome: &str) {
+    state.tutorial = None;
+    state.config.ui.tutorial_completed = true;
+    if let Err(e) = state.config.save() {
+        state.set_status(format!("Failed to save config: {e}"));
+    } else {
+        state.set_status(format!("{outcome} - press ? then t to take it again"));
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::models::{
+        KeyDefinition, KeyGeometry, KeyboardGeometry, Layer, Layout, Position, RgbColor,
+        VisualLayoutMapping,
+    };
+    use crate::tui::handlers::dispatch_action;
+
+    /// Two layers of a 1x2 board
+    fn create_state() -> AppState {
+        let mut geometry = KeyboardGeometry::new("test", "test", 1, 2);
+        geometry.add_key(KeyGeometry::new((0, 0), 0, 0.0, 0.0));
+        geometry.add_key(KeyGeometry::new((0, 1), 1, 1.0, 0.0));
+        let mut layout = Layout::new("Tour").unwrap();
+        for i in 0..2 {
+            let mut layer = Layer::new(i, "Layer", RgbColor::new(0, 0, 0)).unwrap();
+            for col in 0..2 {
+                layer
+                    .add_key(KeyDefinition::new(Position::new(0, col), "KC_TRNS"))
+                    .unwrap();
+            }
+            layout.add_layer(layer).unwrap();
+        }
+        let mapping = VisualLayoutMapping::build(&geometry);
+        AppState::new(
+            layout,
+            None,
+            geometry,
+            mapping,
+            crate::config::Config::default(),
+        )
+        .unwrap()
+    }
+
+    fn step_title(state: &AppState) -> &str {
+        &state.tutorial.as_ref().unwrap().step().unwrap().title
+    }
+
+    #[test]
+    fn test_tour_follows_actions_and_keys() {
+        let mut state = create_state();
+        start_tutorial(&mut state);
+        assert_eq!(step_title(&state), "Moving around");
+
+        // Unrelated actions and keys are left to the main view
+        dispatch_action(&mut state, Action::NextLayer).unwrap();
+        assert_eq!(state.current_layer, 1);
+        assert_eq!(step_title(&state), "Moving around");
+        let enter = event::KeyEvent::new(KeyCode::Enter, KeyModifiers::NONE);
+        assert!(!handle_tutorial_input(&mut state, enter));
+
+        dispatch_action(&mut state, Action::NavigateRight).unwrap();
+        assert_eq!(step_title(&state), "Assigning keycodes");
+
+        let next = event::KeyEvent::new(KeyCode::Char('n'), KeyModifiers::CONTROL);
+        assert!(handle_tutorial_input(&mut state, next));
+        assert_eq!(step_title(&state), "Layers");
+        dispatch_action(&mut state, Action::PreviousLayer).unwrap();
+        assert_eq!(step_title(&state), "Status bar");
+    }
+}
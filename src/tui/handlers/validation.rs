@@ -0,0 +1,171 @@
+//! Validation results input handlers.
+
+use anyhow::Result;
+use crossterm::event;
+
+use crate::tui::{component::Component, validation_results::ValidationResultsEvent, AppState};
+
+/// Handle input for the validation results popup
+pub fn handle_validation_results_input(state: &mut AppState, key: event::KeyEvent) -> Result<bool> {
+    use crate::tui::ActiveComponent;
+
+    if let Some(ActiveComponent::ValidationResults(ref mut view)) = state.active_component {
+        if let Some(event) = view.handle_input(key) {
+            return handle_validation_results_event(state, event);
+        }
+    }
+    Ok(false)
+}
+
+/// Handle validation results events
+fn handle_validation_results_event(
+    state: &mut AppState,
+    event: ValidationResultsEvent,
+) -> Result<bool> {
+    match event {
+        ValidationResultsEvent::JumpTo { layer, position } => {
+            if layer < state.layout.layers.len() {
+                state.current_layer = layer;
+                if let Some(position) = position {
+                    state.selected_position = position;
+                }
+                state.refresh_base_overlay();
+                state.close_component();
+                state.set_status(format!("Jumped to layer {layer}"));
+            } else {
+                state.set_error(format!("Layer {layer} no longer exists"));
+            }
+        }
+        ValidationResultsEvent::ApplyFix(fix) => {
+            let changed = fix.apply(&mut state.layout).len();
+            if changed > 0 {
+                state.mark_dirty();
+                state.refresh_layer_refs();
+            }
+            // Show the remaining issues
+            state.open_validation_results()?;
+            state.log_action(
+                "Quick fix",
+                format!("{}: {changed} change(s)", fix.description()),
+            );
+        }
+        ValidationResultsEvent::Closed => {
+            state.close_component();
+            state.set_status("Validation results closed");
+        }
+    }
+    Ok(false)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::models::{KeyDefinition, Layer, Layout, Position, RgbColor};
+    use crate::tui::ActiveComponent;
+    use crossterm::event::{KeyCode, KeyEvent, KeyModifiers};
+
+    /// One-layer state with the given keycodes in row 0
+    fn state_with_keys(keycodes: &[&str]) -> AppState {
+        let mut layout = Layout::new("Fix").unwrap();
+        let mut layer = Layer::new(0, "Base", RgbColor::new(255, 255, 255)).unwrap();
+        for (col, keycode) in keycodes.iter().enumerate() {
+            let position = Position::new(0, u8::try_from(col).unwrap());
+            layer
+                .add_key(KeyDefinition::at(position).keycode(*keycode))
+                .unwrap();
+        }
+        layout.add_layer(layer).unwrap();
+        AppState::new(
+            layout,
+            None,
+            crate::models::KeyboardGeometry::new("test", "test", 1, 2),
+            crate::models::VisualLayoutMapping::default(),
+            crate::config::Config::default(),
+        )
+        .unwrap()
+    }
+
+    #[test]
+    fn test_failed_generation_opens_results_for_review() {
+        let mut state = state_with_keys(&["NOT_A_KEYCODE", "KC_A"]);
+        assert!(!state.review_validation_results());
+
+        crate::tui::handlers::actions::handle_firmware_generation(&mut state).unwrap();
+        assert_eq!(
+            state.active_popup,
+            Some(crate::tui::PopupType::ValidationResults)
+        );
+        assert!(state
+            .last_validation
+            .as_ref()
+            .is_some_and(|r| !r.is_valid()));
+
+        press_key(&mut state, KeyCode::Esc);
+        assert!(state.active_popup.is_none());
+        assert!(state.review_validation_results());
+        assert_eq!(
+            state.active_popup,
+            Some(crate::tui::PopupType::ValidationResults)
+        );
+    }
+
+    fn press_key(state: &mut AppState, code: KeyCode) {
+        handle_validation_results_input(state, KeyEvent::new(code, KeyModifiers::NONE)).unwrap();
+    }
+
+    #[test]
+    fn test_quick_fix_from_validation_results() {
+        let mut state = state_with_keys(&["KC_TRNS", "KC_A"]);
+
+        state.open_validation_results().unwrap();
+        let issues = match &state.active_component {
+            Some(ActiveComponent::ValidationResults(view)) => view.len(),
+            _ => panic!("validation report not open"),
+        };
+
+        // Walk the list until the base-layer warning's fix applies
+        for _ in 0..issues {
+            press_key(&mut state, KeyCode::Char('x'));
+            if !state.dirty {
+                press_key(&mut state, KeyCode::Down);
+            }
+        }
+        assert_eq!(state.layout.layers[0].keys[0].keycode, "KC_NO");
+        assert!(state.dirty);
+        assert!(matches!(
+            state.active_component,
+            Some(ActiveComponent::ValidationResults(_))
+        ));
+    }
+
+    #[test]
+    fn test_validation_banner_tracks_edits() {
+        let mut state = state_with_keys(&["KC_TRNS", "KC_A"]);
+        assert_eq!(state.validation_banner_message(), None, "Not validated yet");
+
+        state.refresh_validation_banner();
+        let message = state
+            .validation_banner_message()
+            .expect("Base-layer KC_TRNS warns");
+        assert!(message.contains("press Shift+F to review"));
+
+        // Fixing the warning drops the count; bringing it back restores it
+        state.layout.layers[0].keys[0].keycode = "KC_B".to_string();
+        state.mark_dirty();
+        state.refresh_validation_banner();
+        assert_ne!(state.validation_banner_message().as_ref(), Some(&message));
+
+        state.layout.layers[0].keys[0].keycode = "KC_TRNS".to_string();
+        state.mark_dirty();
+        state.refresh_validation_banner();
+        assert_eq!(state.validation_banner_message(), Some(message));
+
+        // Turned off globally, or hidden for the session
+        state.config.ui.validation_banner = false;
+        assert_eq!(state.validation_banner_message(), None);
+        state.config.ui.validation_banner = true;
+        crate::tui::handlers::action_handlers::popups::handle_hide_validation_banner(&mut state)
+            .unwrap();
+        assert_eq!(state.validation_banner_message(), None);
+    }
+}
@@ -40,6 +40,8 @@ const MIN_MODAL_HEIGHT: u16 = 10;
 pub enum HelpOverlayEvent {
     /// User closed the help overlay
     Closed,
+    /// User asked to take the first-run tutorial again
+    RestartTutorial,
 }
 
 /// State for the help overlay.
@@ -559,6 +561,7 @@ impl HelpOverlayState {
         if let Some(ctx) = registry.get_context(contexts::MAIN) {
             for binding in &ctx.bindings {
                 if binding.action.contains("Save")
+                    || binding.action.contains("snapshot")
                     || binding.action.contains("metadata")
                     || binding.action == "Quit"
                 {
@@ -627,6 +630,7 @@ impl HelpOverlayState {
             for binding in &ctx.bindings {
                 if binding.action.contains("help")
                     || binding.action.contains("Help")
+                    || binding.action.contains("palette")
                     || binding.action.contains("Cancel")
                 {
                     let keys = Self::format_keys(&binding.keys, &binding.alt_keys);
@@ -668,7 +672,7 @@ impl HelpOverlayState {
             Style::default().fg(theme.text_muted),
         )]));
         lines.push(Line::from(vec![Span::styled(
-            "              Press '?' to close • ↑↓ to scroll               ",
+            "        Press '?' to close • ↑↓ to scroll • t for the tour        ",
             Style::default().fg(theme.text_muted),
         )]));
         lines.push(Line::from(vec![Span::styled(
@@ -719,6 +723,7 @@ impl crate::tui::component::Component for HelpOverlay {
             KeyCode::Char('?') | KeyCode::Esc | KeyCode::Char('q') => {
                 Some(HelpOverlayEvent::Closed)
             }
+            KeyCode::Char('t') => Some(HelpOverlayEvent::RestartTutorial),
             KeyCode::Up | KeyCode::Char('k') => {
                 self.state.scroll_up();
                 None
@@ -42,6 +42,15 @@ pub struct Context {
     pub bindings: Vec<Binding>,
 }
 
+/// Display name and description for an action (command palette entry)
+#[derive(Debug, Clone, Deserialize)]
+pub struct ActionHelp {
+    /// Human-readable command name
+    pub name: String,
+    /// One-line description of what the command does
+    pub description: String,
+}
+
 /// Metadata about the help file
 #[derive(Debug, Clone, Deserialize)]
 pub struct HelpMeta {
@@ -56,6 +65,8 @@ pub struct HelpMeta {
 struct HelpFile {
     meta: HelpMeta,
     contexts: HashMap<String, Context>,
+    #[serde(default)]
+    actions: HashMap<String, ActionHelp>,
 }
 
 /// Help registry providing access to keybinding definitions
@@ -63,6 +74,7 @@ struct HelpFile {
 pub struct HelpRegistry {
     meta: HelpMeta,
     contexts: HashMap<String, Context>,
+    actions: HashMap<String, ActionHelp>,
 }
 
 impl HelpRegistry {
@@ -72,6 +84,7 @@ impl HelpRegistry {
         Ok(Self {
             meta: help_file.meta,
             contexts: help_file.contexts,
+            actions: help_file.actions,
         })
     }
 
@@ -87,6 +100,12 @@ impl HelpRegistry {
         self.contexts.get(name)
     }
 
+    /// Get the display name and description for an action ID
+    #[must_use]
+    pub fn get_action(&self, id: &str) -> Option<&ActionHelp> {
+        self.actions.get(id)
+    }
+
     /// Get all bindings for a context, sorted by priority
     #[must_use]
     pub fn get_bindings(&self, context_name: &str) -> Vec<&Binding> {
@@ -217,8 +236,42 @@ pub mod contexts {
     pub const HELP: &str = "help";
     /// Selection mode
     pub const SELECTION: &str = "selection";
+    /// Typing simulation
+    pub const SIMULATION: &str = "simulation";
+    /// Layer peek
+    pub const PEEK: &str = "peek";
     /// Template browser
     pub const TEMPLATE_BROWSER: &str = "template_browser";
+    /// Command palette
+    pub const COMMAND_PALETTE: &str = "command_palette";
+    /// Snapshot browser
+    pub const SNAPSHOT_BROWSER: &str = "snapshot_browser";
+    /// Keycode search
+    pub const KEYCODE_SEARCH: &str = "keycode_search";
+    /// Lint report
+    pub const LINT_REPORT: &str = "lint_report";
+    /// Firmware validation results
+    pub const VALIDATION_RESULTS: &str = "validation_results";
+    /// Firmware generation results
+    pub const GENERATION_RESULTS: &str = "generation_results";
+    /// Action log
+    pub const ACTION_LOG: &str = "action_log";
+    /// Generate/build history
+    pub const HISTORY: &str = "history";
+    /// Keycode grid paste
+    pub const GRID_PASTE: &str = "grid_paste";
+    /// Key role editor
+    pub const KEY_ROLE_EDITOR: &str = "key_role_editor";
+    /// Encoder editor
+    pub const ENCODER_EDITOR: &str = "encoder_editor";
+    /// Missing layout variant dialog
+    pub const VARIANT_CONFLICT: &str = "variant_conflict";
+    /// Layout with keys outside the geometry dialog
+    pub const GEOMETRY_MISMATCH: &str = "geometry_mismatch";
+    /// Layout keyboard differs from the build target dialog
+    pub const KEYBOARD_MISMATCH: &str = "keyboard_mismatch";
+    /// Layout changed since the last generation dialog
+    pub const STALE_GENERATION: &str = "stale_generation";
     /// Template save dialog
     pub const TEMPLATE_SAVE: &str = "template_save";
     /// Setup wizard
@@ -0,0 +1,208 @@
+//! Generation and build history viewer.
+//!
+//! Lists the runs recorded by [`HistoryService`](crate::services::history::HistoryService)
+//! for the open layout file, newest first.
+
+use chrono::Local;
+use ratatui::{
+    layout::Rect,
+    style::{Modifier, Style},
+    text::{Line, Span},
+    widgets::{Block, Borders, Clear, List, ListItem, Paragraph},
+    Frame,
+};
+
+use super::Theme;
+use crate::services::history::History;
+
+/// Events emitted by the HistoryView component
+#[derive(Debug, Clone)]
+pub enum HistoryEvent {
+    /// User closed the history
+    Closed,
+}
+
+/// HistoryView component that implements the Component trait
+#[derive(Debug, Clone)]
+pub struct HistoryView {
+    /// History loaded when the view opened
+    history: History,
+    /// Why there is no history to show (e.g., the layout was never saved)
+    unavailable: Option<String>,
+    /// Scroll offset (number of entries from the newest)
+    scroll_offset: usize,
+}
+
+impl HistoryView {
+    /// Create a new HistoryView showing `history`.
+    #[must_use]
+    pub const fn new(history: History) -> Self {
+        Self {
+            history,
+            unavailable: None,
+            scroll_offset: 0,
+        }
+    }
+
+    /// Create a HistoryView that explains why no history is shown.
+    #[must_use]
+    pub fn unavailable(reason: impl Into<String>) -> Self {
+        Self {
+            history: History::default(),
+            unavailable: Some(reason.into()),
+            scroll_offset: 0,
+        }
+    }
+
+    /// Number of recorded runs.
+    #[must_use]
+    pub fn run_count(&self) -> usize {
+        self.history.entries.len()
+    }
+}
+
+impl crate::tui::component::Component for HistoryView {
+    type Event = HistoryEvent;
+
+    fn handle_input(&mut self, key: crossterm::event::KeyEvent) -> Option<Self::Event> {
+        use crossterm::event::KeyCode;
+
+        let last = self.run_count().saturating_sub(1);
+        match key.code {
+            KeyCode::Esc | KeyCode::Char('q') => Some(HistoryEvent::Closed),
+            KeyCode::Up | KeyCode::Char('k') => {
+                self.scroll_offset = self.scroll_offset.saturating_sub(1);
+                None
+            }
+            KeyCode::Down | KeyCode::Char('j') => {
+                self.scroll_offset = (self.scroll_offset + 1).min(last);
+                None
+            }
+            KeyCode::PageUp => {
+                self.scroll_offset = self.scroll_offset.saturating_sub(10);
+                None
+            }
+            KeyCode::PageDown => {
+                self.scroll_offset = (self.scroll_offset + 10).min(last);
+                None
+            }
+            KeyCode::Home | KeyCode::Char('g') => {
+                self.scroll_offset = 0;
+                None
+            }
+            KeyCode::End | KeyCode::Char('G') => {
+                self.scroll_offset = last;
+                None
+            }
+            _ => None,
+        }
+    }
+
+    fn render(&self, f: &mut Frame, area: Rect, theme: &Theme) {
+        render_history(f, self, area, theme);
+    }
+}
+
+/// Renders the history popup
+fn render_history(f: &mut Frame, view: &HistoryView, area: Rect, theme: &Theme) {
+    let area = centered_rect(80, 70, area);
+
+    f.render_widget(Clear, area);
+    let background = Block::default().style(Style::default().bg(theme.background));
+    f.render_widget(background, area);
+
+    let visible_lines = area.height.saturating_sub(2) as usize;
+    let items: Vec<ListItem> = if view.history.entries.is_empty() {
+        let message = view
+            .unavailable
+            .as_deref()
+            .unwrap_or("No generate or build runs recorded yet");
+        vec![ListItem::new(Line::from(Span::styled(
+            message.to_string(),
+            Style::default().fg(theme.text_muted),
+        )))]
+    } else {
+        view.history
+            .entries
+            .iter()
+            .rev()
+            .skip(view.scroll_offset)
+            .take(visible_lines)
+            .map(|entry| {
+                let color = if entry.success {
+                    theme.success
+                } else {
+                    theme.error
+                };
+                let versions = entry.qmk_version.as_deref().map_or_else(
+                    || format!("  v{}", entry.lazyqmk_version),
+                    |qmk| format!("  v{} · QMK {qmk}", entry.lazyqmk_version),
+                );
+                ListItem::new(Line::from(vec![
+                    Span::styled(
+                        format!(
+                            "{} ",
+                            entry
+                                .timestamp
+                                .with_timezone(&Local)
+                                .format("%Y-%m-%d %H:%M")
+                        ),
+                        Style::default().fg(theme.text_muted),
+                    ),
+                    Span::styled(entry.summary(), Style::default().fg(color)),
+                    Span::styled(versions, Style::default().fg(theme.text_muted)),
+                ]))
+            })
+            .collect()
+    };
+
+    let skipped = match view.history.skipped_lines {
+        0 => String::new(),
+        n => format!(", {n} unreadable"),
+    };
+    let title = format!(" Generate/Build History ({}{skipped}) ", view.run_count());
+    let list = List::new(items).block(
+        Block::default()
+            .title(title)
+            .borders(Borders::ALL)
+            .border_style(Style::default().fg(theme.primary)),
+    );
+    f.render_widget(list, area);
+
+    let help_text = "↑↓: Scroll | Home/End: Jump | Esc: Close";
+    let help_area = Rect {
+        x: area.x + 2,
+        y: area.y + area.height.saturating_sub(1),
+        width: area.width.saturating_sub(4),
+        height: 1,
+    };
+    let help = Paragraph::new(help_text).style(
+        Style::default()
+            .fg(theme.text_muted)
+            .add_modifier(Modifier::DIM),
+    );
+    f.render_widget(help, help_area);
+}
+
+/// Helper to create a centered rectangle.
+fn centered_rect(percent_x: u16, percent_y: u16, r: Rect) -> Rect {
+    use ratatui::layout::{Constraint, Direction, Layout};
+
+    let popup_layout = Layout::default()
+        .direction(Direction::Vertical)
+        .constraints([
+            Constraint::Percentage((100 - percent_y) / 2),
+            Constraint::Percentage(percent_y),
+            Constraint::Percentage((100 - percent_y) / 2),
+        ])
+        .split(r);
+
+    Layout::default()
+        .direction(Direction::Horizontal)
+        .constraints([
+            Constraint::Percentage((100 - percent_x) / 2),
+            Constraint::Percentage(percent_x),
+            Constraint::Percentage((100 - percent_x) / 2),
+        ])
+        .split(popup_layout[1])[1]
+}
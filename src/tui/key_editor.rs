@@ -17,6 +17,7 @@ use ratatui::{
     Frame,
 };
 
+use super::text_input::TextInput;
 use super::AppState;
 
 /// Extracts the tap dance name from a TD(name) keycode.
@@ -168,10 +169,8 @@ pub struct KeyEditorState {
     pub layer_idx: usize,
     /// Current mode (View or `EditDescription`)
     pub mode: KeyEditorMode,
-    /// Buffer for editing description
-    pub description_buffer: String,
-    /// Cursor position in description buffer
-    pub cursor_position: usize,
+    /// Description being edited
+    pub description_input: TextInput,
     /// Original description (for cancel/restore)
     pub original_description: Option<String>,
     /// When editing a combo keycode part, which part and the parsed type
@@ -187,13 +186,12 @@ impl Default for KeyEditorState {
 impl KeyEditorState {
     /// Create a new key editor state
     #[must_use]
-    pub const fn new() -> Self {
+    pub fn new() -> Self {
         Self {
             position: Position { row: 0, col: 0 },
             layer_idx: 0,
             mode: KeyEditorMode::View,
-            description_buffer: String::new(),
-            cursor_position: 0,
+            description_input: TextInput::default(),
             original_description: None,
             combo_edit: None,
         }
@@ -204,94 +202,48 @@ impl KeyEditorState {
         self.position = key.position;
         self.layer_idx = layer_idx;
         self.mode = KeyEditorMode::View;
-        self.description_buffer = key.description.clone().unwrap_or_default();
-        self.cursor_position = self.description_buffer.len();
+        self.description_input
+            .set_value(key.description.clone().unwrap_or_default());
         self.original_description.clone_from(&key.description);
         self.combo_edit = None;
     }
 
     /// Start editing the description
-    pub const fn start_edit_description(&mut self) {
+    pub fn start_edit_description(&mut self) {
         self.mode = KeyEditorMode::EditDescription;
-        self.cursor_position = self.description_buffer.len();
+        self.description_input.move_end(false);
     }
 
     /// Cancel description editing and restore original
     pub fn cancel_edit_description(&mut self) {
         self.mode = KeyEditorMode::View;
-        self.description_buffer = self.original_description.clone().unwrap_or_default();
-        self.cursor_position = self.description_buffer.len();
+        self.description_input
+            .set_value(self.original_description.clone().unwrap_or_default());
     }
 
     /// Confirm description editing
     pub fn confirm_edit_description(&mut self) {
         self.mode = KeyEditorMode::View;
-        self.original_description = if self.description_buffer.is_empty() {
-            None
-        } else {
-            Some(self.description_buffer.clone())
-        };
+        self.original_description = self.get_description();
     }
 
     /// Get the edited description (None if empty)
     #[must_use]
     pub fn get_description(&self) -> Option<String> {
-        if self.description_buffer.is_empty() {
+        if self.description_input.is_empty() {
             None
         } else {
-            Some(self.description_buffer.clone())
+            Some(self.description_input.value().to_string())
         }
     }
 
-    /// Handle character input in description edit mode
-    pub fn handle_char(&mut self, c: char) {
+    /// Handle a text editing key in description edit mode
+    pub fn handle_description_key(&mut self, key: crossterm::event::KeyEvent) {
         if self.mode == KeyEditorMode::EditDescription {
-            self.description_buffer.insert(self.cursor_position, c);
-            self.cursor_position += 1;
-        }
-    }
-
-    /// Handle backspace in description edit mode
-    pub fn handle_backspace(&mut self) {
-        if self.mode == KeyEditorMode::EditDescription && self.cursor_position > 0 {
-            self.cursor_position -= 1;
-            self.description_buffer.remove(self.cursor_position);
+            self.description_input.handle_key(key);
         }
     }
 
-    /// Handle delete in description edit mode
-    pub fn handle_delete(&mut self) {
-        if self.mode == KeyEditorMode::EditDescription
-            && self.cursor_position < self.description_buffer.len()
-        {
-            self.description_buffer.remove(self.cursor_position);
-        }
-    }
-
-    /// Move cursor left
-    pub const fn cursor_left(&mut self) {
-        if self.cursor_position > 0 {
-            self.cursor_position -= 1;
-        }
-    }
-
-    /// Move cursor right
-    pub const fn cursor_right(&mut self) {
-        if self.cursor_position < self.description_buffer.len() {
-            self.cursor_position += 1;
-        }
-    }
-
-    /// Move cursor to start
-    pub const fn cursor_home(&mut self) {
-        self.cursor_position = 0;
-    }
-
-    /// Move cursor to end
-    pub const fn cursor_end(&mut self) {
-        self.cursor_position = self.description_buffer.len();
-    }
-
     /// Check if we're in edit mode
     #[must_use]
     pub const fn is_editing(&self) -> bool {
@@ -656,34 +608,36 @@ pub fn render_key_editor(f: &mut Frame, state: &AppState) {
         Style::default().fg(theme.text)
     };
 
-    let desc_content = if editor_state.is_editing() {
-        // Show cursor in edit mode
-        let before_cursor = &editor_state.description_buffer[..editor_state.cursor_position];
-        let after_cursor = &editor_state.description_buffer[editor_state.cursor_position..];
-        format!("{before_cursor}█{after_cursor}")
-    } else if editor_state.description_buffer.is_empty() {
-        "(No description - press D to add)".to_string()
-    } else {
-        editor_state.description_buffer.clone()
-    };
-
     let desc_title = if editor_state.is_editing() {
         " Description (editing) "
     } else {
         " Description "
     };
+    let desc_block = Block::default()
+        .title(desc_title)
+        .borders(Borders::ALL)
+        .border_style(if editor_state.is_editing() {
+            Style::default().fg(theme.accent)
+        } else {
+            Style::default().fg(theme.inactive)
+        });
 
-    let description_display = Paragraph::new(desc_content).style(desc_style).block(
-        Block::default()
-            .title(desc_title)
-            .borders(Borders::ALL)
-            .border_style(if editor_state.is_editing() {
-                Style::default().fg(theme.accent)
-            } else {
-                Style::default().fg(theme.inactive)
-            }),
-    );
-    f.render_widget(description_display, chunks[2]);
+    if editor_state.is_editing() {
+        // Show cursor in edit mode
+        editor_state
+            .description_input
+            .render(f, chunks[2], desc_block, desc_style, true);
+    } else {
+        let desc_content = if editor_state.description_input.is_empty() {
+            "(No description - press D to add)"
+        } else {
+            editor_state.description_input.value()
+        };
+        let description_display = Paragraph::new(desc_content)
+            .style(desc_style)
+            .block(desc_block);
+        f.render_widget(description_display, chunks[2]);
+    }
 
     // Check if this is a combo keycode (for showing H/T options)
     let combo_type = parse_combo_keycode(&state.keycode_db, &key.keycode);
@@ -840,31 +794,12 @@ pub fn handle_input(state: &mut AppState, key: crossterm::event::KeyEvent) -> an
                 if let Some(key) = state.get_selected_key_mut() {
                     key.description = description;
                     state.mark_dirty();
-                    state.set_status("Description saved");
+                    state.log_action("Edit description", "Description saved");
                 }
             }
-            KeyCode::Backspace => {
-                state.key_editor_state.handle_backspace();
-            }
-            KeyCode::Delete => {
-                state.key_editor_state.handle_delete();
-            }
-            KeyCode::Left | KeyCode::Char('h') => {
-                state.key_editor_state.cursor_left();
+            _ => {
+                state.key_editor_state.handle_description_key(key);
             }
-            KeyCode::Right | KeyCode::Char('l') => {
-                state.key_editor_state.cursor_right();
-            }
-            KeyCode::Home => {
-                state.key_editor_state.cursor_home();
-            }
-            KeyCode::End => {
-                state.key_editor_state.cursor_end();
-            }
-            KeyCode::Char(c) => {
-                state.key_editor_state.handle_char(c);
-            }
-            _ => {}
         }
     } else {
         // View mode
@@ -0,0 +1,251 @@
+//! Coalescing and acceleration of held navigation keys.
+//!
+//! Terminals deliver key repeat as a stream of key presses. Handling them one
+//! per frame lets the queue grow while a key is held, and the cursor keeps
+//! moving after release. Instead, every event already queued is drained each
+//! frame and consecutive navigation events are merged into one net move.
+//!
+//! Acceleration counts consecutive moves in one direction: once a key has
+//! repeated for a while, each repeat moves 2 and then 3 keys.
+
+use std::collections::VecDeque;
+use std::time::{Duration, Instant};
+
+use crate::shortcuts::Action;
+
+/// Direction of a navigation key.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum NavDirection {
+    /// Up
+    Up,
+    /// Down
+    Down,
+    /// Left
+    Left,
+    /// Right
+    Right,
+}
+
+impl NavDirection {
+    /// The direction a navigation action moves in.
+    #[must_use]
+    pub const fn from_action(action: Action) -> Option<Self> {
+        match action {
+            Action::NavigateUp => Some(Self::Up),
+            Action::NavigateDown => Some(Self::Down),
+            Action::NavigateLeft => Some(Self::Left),
+            Action::NavigateRight => Some(Self::Right),
+            _ => None,
+        }
+    }
+}
+
+/// Net cursor movement, in keys (positive is down/right).
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct NetMove {
+    /// Horizontal steps
+    pub dx: i32,
+    /// Vertical steps
+    pub dy: i32,
+}
+
+impl NetMove {
+    /// Adds `steps` keys of movement in `direction`.
+    pub const fn push(&mut self, direction: NavDirection, steps: i32) {
+        match direction {
+            NavDirection::Up => self.dy -= steps,
+            NavDirection::Down => self.dy += steps,
+            NavDirection::Left => self.dx -= steps,
+            NavDirection::Right => self.dx += steps,
+        }
+    }
+}
+
+/// One unit of input to handle.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum InputBatch<E> {
+    /// Consecutive navigation events merged into their net movement
+    Move(NetMove),
+    /// Any other event, handled as is
+    Event(E),
+}
+
+/// Takes the next batch from the front of `events`.
+///
+/// Consecutive events `direction` classifies as navigation are merged, each
+/// contributing `steps(direction)` keys; other events are returned one by
+/// one. Classification happens per batch, so state changed by an earlier
+/// event (e.g., an opened popup) is seen by the events after it.
+pub fn next_batch<E>(
+    events: &mut VecDeque<E>,
+    mut direction: impl FnMut(&E) -> Option<NavDirection>,
+    mut steps: impl FnMut(NavDirection) -> i32,
+) -> Option<InputBatch<E>> {
+    let first = events.front()?;
+    if direction(first).is_none() {
+        return events.pop_front().map(InputBatch::Event);
+    }
+
+    let mut net = NetMove::default();
+    while let Some(dir) = events.front().and_then(&mut direction) {
+        net.push(dir, steps(dir));
+        events.pop_front();
+    }
+    Some(InputBatch::Move(net))
+}
+
+/// Maximum gap between repeats that still counts as holding the key.
+pub const REPEAT_WINDOW: Duration = Duration::from_millis(150);
+
+/// Consecutive moves before each repeat moves 2 keys.
+pub const ACCELERATE_AFTER: usize = 6;
+
+/// Consecutive moves before each repeat moves 3 keys.
+pub const FAST_AFTER: usize = 12;
+
+/// Tracks consecutive moves in one direction to accelerate held keys.
+#[derive(Debug, Clone, Default)]
+pub struct NavAccelerator {
+    last: Option<(NavDirection, Instant)>,
+    streak: usize,
+}
+
+impl NavAccelerator {
+    /// Records a move at `now` and returns how many keys it should move.
+    pub fn step(&mut self, direction: NavDirection, now: Instant) -> i32 {
+        let repeating = self.last.is_some_and(|(last, at)| {
+            last == direction && now.saturating_duration_since(at) <= REPEAT_WINDOW
+        });
+        self.streak = if repeating { self.streak + 1 } else { 1 };
+        self.last = Some((direction, now));
+
+        if self.streak > FAST_AFTER {
+            3
+        } else if self.streak > ACCELERATE_AFTER {
+            2
+        } else {
+            1
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Merges a whole event stream into batches with a fixed classification.
+    fn coalesce<E>(
+        events: impl IntoIterator<Item = E>,
+        mut direction: impl FnMut(&E) -> Option<NavDirection>,
+        mut steps: impl FnMut(NavDirection) -> i32,
+    ) -> Vec<InputBatch<E>> {
+        let mut events: VecDeque<E> = events.into_iter().collect();
+        std::iter::from_fn(|| next_batch(&mut events, &mut direction, &mut steps)).collect()
+    }
+
+    /// Synthetic events: arrows are navigation, anything else is not.
+    fn classify(event: &char) -> Option<NavDirection> {
+        match event {
+            '↑' => Some(NavDirection::Up),
+            '↓' => Some(NavDirection::Down),
+            '←' => Some(NavDirection::Left),
+            '→' => Some(NavDirection::Right),
+            _ => None,
+        }
+    }
+
+    fn batches(stream: &str) -> Vec<InputBatch<char>> {
+        coalesce(stream.chars(), classify, |_| 1)
+    }
+
+    fn moved(dx: i32, dy: i32) -> InputBatch<char> {
+        InputBatch::Move(NetMove { dx, dy })
+    }
+
+    #[test]
+    fn test_repeats_merge_into_net_move() {
+        assert_eq!(batches("→→→→→→"), [moved(6, 0)]);
+        assert_eq!(batches("↑↑↓"), [moved(0, -1)]);
+        assert_eq!(batches("→↓→↓"), [moved(2, 2)]);
+        assert_eq!(batches("←→"), [moved(0, 0)]);
+        assert!(batches("").is_empty());
+    }
+
+    #[test]
+    fn test_other_events_split_runs() {
+        assert_eq!(
+            batches("→→x→e"),
+            [
+                moved(2, 0),
+                InputBatch::Event('x'),
+                moved(1, 0),
+                InputBatch::Event('e')
+            ]
+        );
+        assert_eq!(
+            batches("ab"),
+            [InputBatch::Event('a'), InputBatch::Event('b')]
+        );
+    }
+
+    #[test]
+    fn test_classification_sees_earlier_events() {
+        // After 'p' opens a "popup", arrows are no longer navigation
+        let mut events: VecDeque<char> = "→p→→".chars().collect();
+        let mut popup = false;
+        let mut handled = Vec::new();
+        while let Some(batch) = next_batch(
+            &mut events,
+            |e| if popup { None } else { classify(e) },
+            |_| 1,
+        ) {
+            if batch == InputBatch::Event('p') {
+                popup = true;
+            }
+            handled.push(batch);
+        }
+        assert_eq!(
+            handled,
+            [
+                moved(1, 0),
+                InputBatch::Event('p'),
+                InputBatch::Event('→'),
+                InputBatch::Event('→')
+            ]
+        );
+    }
+
+    #[test]
+    fn test_acceleration_after_consecutive_moves() {
+        let mut accel = NavAccelerator::default();
+        let start = Instant::now();
+        let steps: Vec<i32> = (0..16u64)
+            .map(|i| accel.step(NavDirection::Right, start + Duration::from_millis(i * 30)))
+            .collect();
+        assert_eq!(steps, [1, 1, 1, 1, 1, 1, 2, 2, 2, 2, 2, 2, 3, 3, 3, 3]);
+    }
+
+    #[test]
+    fn test_acceleration_resets_on_pause_or_turn() {
+        let mut accel = NavAccelerator::default();
+        let start = Instant::now();
+        for i in 0..10u64 {
+            accel.step(NavDirection::Down, start + Duration::from_millis(i * 30));
+        }
+        let later = start + Duration::from_millis(300);
+        assert_eq!(accel.step(NavDirection::Down, later), 2);
+        assert_eq!(accel.step(NavDirection::Up, later), 1);
+        assert_eq!(accel.step(NavDirection::Up, later + REPEAT_WINDOW * 2), 1);
+    }
+
+    #[test]
+    fn test_coalesced_stream_with_acceleration() {
+        let mut accel = NavAccelerator::default();
+        let now = Instant::now();
+        // A flood of 14 repeats drained in one frame: 6×1 + 6×2 + 2×3
+        let batches = coalesce("→".repeat(14).chars(), classify, |dir| {
+            accel.step(dir, now)
+        });
+        assert_eq!(batches, [moved(24, 0)]);
+    }
+}
@@ -0,0 +1,156 @@
+//! Key role editor popup.
+//!
+//! Corrects the inferred role (thumb, home row, pinky) of the selected key.
+//! Saved roles are stored per keyboard and layout variant, see
+//! [`crate::services::key_roles`].
+
+use crossterm::event::{KeyCode, KeyEvent};
+use ratatui::{
+    layout::Rect,
+    style::{Modifier, Style},
+    text::{Line, Span},
+    widgets::{Block, Borders, Clear, Paragraph},
+    Frame,
+};
+
+use super::Theme;
+use crate::models::{KeyRole, Position};
+
+/// Events emitted by the KeyRoleEditor component
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum KeyRoleEditorEvent {
+    /// User saved `role` as the key's override
+    Saved(KeyRole),
+    /// User dropped the override, going back to the inferred role
+    Reset,
+    /// User closed the editor without saving
+    Cancelled,
+}
+
+/// KeyRoleEditor component that implements the Component trait
+#[derive(Debug, Clone)]
+pub struct KeyRoleEditor {
+    /// Visual position of the edited key
+    position: Position,
+    /// Role being edited
+    role: KeyRole,
+    /// Role the geometry heuristics give the key
+    inferred: KeyRole,
+}
+
+impl KeyRoleEditor {
+    /// Create an editor for the key at `position`, starting from its current
+    /// role.
+    #[must_use]
+    pub const fn new(position: Position, role: KeyRole, inferred: KeyRole) -> Self {
+        Self {
+            position,
+            role,
+            inferred,
+        }
+    }
+
+    /// Visual position of the edited key.
+    #[must_use]
+    pub const fn position(&self) -> Position {
+        self.position
+    }
+}
+
+impl crate::tui::component::Component for KeyRoleEditor {
+    type Event = KeyRoleEditorEvent;
+
+    fn handle_input(&mut self, key: KeyEvent) -> Option<Self::Event> {
+        match key.code {
+            KeyCode::Char('t') => self.role.thumb = !self.role.thumb,
+            KeyCode::Char('h') => self.role.home_row = !self.role.home_row,
+            KeyCode::Char('p') => self.role.pinky = !self.role.pinky,
+            KeyCode::Char('r') => return Some(KeyRoleEditorEvent::Reset),
+            KeyCode::Enter => return Some(KeyRoleEditorEvent::Saved(self.role)),
+            KeyCode::Esc | KeyCode::Char('q') => return Some(KeyRoleEditorEvent::Cancelled),
+            _ => {}
+        }
+        None
+    }
+
+    fn render(&self, f: &mut Frame, area: Rect, theme: &Theme) {
+        let width = 44.min(area.width);
+        let height = 10.min(area.height);
+        let popup = Rect {
+            x: area.x + (area.width - width) / 2,
+            y: area.y + (area.height - height) / 2,
+            width,
+            height,
+        };
+
+        f.render_widget(Clear, popup);
+        let block = Block::default()
+            .borders(Borders::ALL)
+            .title(format!(
+                " Key Role ({}, {}) ",
+                self.position.row, self.position.col
+            ))
+            .border_style(Style::default().fg(theme.primary))
+            .style(Style::default().bg(theme.background));
+
+        let checkbox = |key: &'static str, name: &'static str, set: bool| {
+            Line::from(vec![
+                Span::styled(format!(" {key} "), Style::default().fg(theme.primary)),
+                Span::styled(
+                    format!("[{}] {name}", if set { "x" } else { " " }),
+                    Style::default().fg(theme.text),
+                ),
+            ])
+        };
+        let lines = vec![
+            checkbox("t", "Thumb", self.role.thumb),
+            checkbox("h", "Home row", self.role.home_row),
+            checkbox("p", "Pinky", self.role.pinky),
+            Line::from(""),
+            Line::from(Span::styled(
+                format!(" Inferred: {}", self.inferred),
+                Style::default().fg(theme.text_muted),
+            )),
+            Line::from(""),
+            Line::from(Span::styled(
+                " Enter: Save  r: Reset  Esc: Cancel",
+                Style::default()
+                    .fg(theme.text_muted)
+                    .add_modifier(Modifier::DIM),
+            )),
+        ];
+        f.render_widget(Paragraph::new(lines).block(block), popup);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::tui::component::Component;
+    use crossterm::event::KeyModifiers;
+
+    fn press(editor: &mut KeyRoleEditor, c: char) -> Option<KeyRoleEditorEvent> {
+        editor.handle_input(KeyEvent::new(KeyCode::Char(c), KeyModifiers::NONE))
+    }
+
+    #[test]
+    fn test_toggles_and_save() {
+        let inferred = KeyRole {
+            home_row: true,
+            ..KeyRole::default()
+        };
+        let mut editor = KeyRoleEditor::new(Position::new(1, 0), inferred, inferred);
+        assert_eq!(press(&mut editor, 'p'), None);
+        assert_eq!(press(&mut editor, 'h'), None);
+
+        let saved = editor.handle_input(KeyEvent::new(KeyCode::Enter, KeyModifiers::NONE));
+        assert_eq!(
+            saved,
+            Some(KeyRoleEditorEvent::Saved(KeyRole {
+                pinky: true,
+                ..KeyRole::default()
+            }))
+        );
+        assert_eq!(press(&mut editor, 'r'), Some(KeyRoleEditorEvent::Reset));
+    }
+}
@@ -5,6 +5,9 @@
 //! - Tap-hold keycodes (LT, MT, LM, `SH_T`) with dual-line display
 //! - Color type indicators in border (i=individual, k=category, L=layer, d=default)
 //! - RGB color borders based on the color priority system
+//! - Rotary encoders as rounded boxes with their CCW/CW legends stacked
+//! - Transparent keys drawn per [`TransparentKeyStyle`]: labelled, dimmed, or
+//!   as bare outlines
 
 // Allow intentional type casts for terminal rendering
 #![allow(clippy::cast_possible_truncation)]
@@ -14,41 +17,383 @@ use ratatui::{
     layout::Rect,
     style::{Color, Modifier, Style},
     text::{Line, Span},
-    widgets::{Block, Borders, Paragraph},
+    widgets::{Block, BorderType, Borders, Paragraph},
     Frame,
 };
 
+use std::collections::{HashMap, HashSet};
+
 use super::AppState;
-use crate::keycode_db::TapHoldType;
+use crate::config::TransparentKeyStyle;
+use crate::keycode_db::KeyLegend;
+use crate::models::{
+    EncoderMapping, KeyDefinition, KeyGeometry, KeyboardGeometry, Layout, Position, RgbColor,
+    VisualLayoutMapping,
+};
+use crate::services::snapshots::KeyChange;
 
 /// Keyboard widget renders the visual keyboard layout
 pub struct KeyboardWidget;
 
-/// Parsed representation of a tap-hold keycode
+/// Keys an in-progress color would recolor.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum PreviewTarget {
+    /// Individual keys on the current layer (they get a color override)
+    Keys(Vec<Position>),
+    /// Keys colored by this category
+    Category(String),
+    /// Keys colored by the current layer's default color
+    LayerDefault,
+}
+
+/// An open color picker's in-progress color.
+///
+/// Drawn instead of the model's color on the keys it targets; the layout is
+/// only changed once the picker is confirmed, so cancelling needs no revert.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ColorPreview {
+    /// Color being adjusted
+    pub color: RgbColor,
+    /// Keys it would apply to
+    pub target: PreviewTarget,
+}
+
+impl ColorPreview {
+    /// Whether `key` on layer `layer_idx` would take the previewed color.
+    #[must_use]
+    pub fn applies_to(&self, layout: &Layout, layer_idx: usize, key: &KeyDefinition) -> bool {
+        let key_category = key
+            .category_id
+            .as_deref()
+            .filter(|id| layout.get_category(id).is_some());
+        let layer_category = layout
+            .get_layer(layer_idx)
+            .and_then(|layer| layer.category_id.as_deref())
+            .filter(|id| layout.get_category(id).is_some());
+
+        match &self.target {
+            PreviewTarget::Keys(positions) => positions.contains(&key.position),
+            PreviewTarget::Category(id) => {
+                key.color_override.is_none() && key_category.or(layer_category) == Some(id.as_str())
+            }
+            PreviewTarget::LayerDefault => {
+                key.color_override.is_none() && key_category.is_none() && layer_category.is_none()
+            }
+        }
+    }
+}
+
+/// Debug overlay drawn in each key's top border.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum CoordinateOverlay {
+    /// No overlay
+    #[default]
+    Off,
+    /// Visual grid position (row,col)
+    Position,
+    /// Electrical matrix position (row,col)
+    Matrix,
+    /// RGB LED index
+    Led,
+    /// Index in the info.json layout array
+    LayoutIndex,
+    /// Key role: T(humb), H(ome row), P(inky)
+    Role,
+}
+
+impl CoordinateOverlay {
+    /// The next mode in the cycle, wrapping back to `Off`.
+    #[must_use]
+    pub const fn next(self) -> Self {
+        match self {
+            Self::Off => Self::Position,
+            Self::Position => Self::Matrix,
+            Self::Matrix => Self::Led,
+            Self::Led => Self::LayoutIndex,
+            Self::LayoutIndex => Self::Role,
+            Self::Role => Self::Off,
+        }
+    }
+
+    /// Human-readable mode name.
+    #[must_use]
+    pub const fn label(self) -> &'static str {
+        match self {
+            Self::Off => "off",
+            Self::Position => "position",
+            Self::Matrix => "matrix",
+            Self::Led => "LED index",
+            Self::LayoutIndex => "layout index",
+            Self::Role => "key role",
+        }
+    }
+
+    /// Text shown on a key, or `None` when off or the key has no geometry.
+    #[must_use]
+    pub fn text(self, position: Position, geometry: Option<&KeyGeometry>) -> Option<String> {
+        match self {
+            Self::Off => None,
+            Self::Position => Some(format!("{},{}", position.row, position.col)),
+            Self::Matrix => {
+                geometry.map(|g| format!("{},{}", g.matrix_position.0, g.matrix_position.1))
+            }
+            Self::Led => geometry.map(|g| g.led_index.to_string()),
+            Self::LayoutIndex => geometry.map(|g| g.layout_index.to_string()),
+            Self::Role => geometry.map(|g| g.role.short_label()),
+        }
+    }
+}
+
+/// Characters of a key's border.
+struct BorderSymbols {
+    top_left: char,
+    top_right: char,
+    bottom_left: char,
+    bottom_right: char,
+    horizontal: char,
+    vertical: char,
+}
+
+/// Border of upright keys
+const PLAIN_BORDER: BorderSymbols = BorderSymbols {
+    top_left: '┌',
+    top_right: '┐',
+    bottom_left: '└',
+    bottom_right: '┘',
+    horizontal: '─',
+    vertical: '│',
+};
+
+/// Border of rotated keys, drawn as their approximate bounding box
+const ROTATED_BORDER: BorderSymbols = BorderSymbols {
+    top_left: '╭',
+    top_right: '╮',
+    bottom_left: '╰',
+    bottom_right: '╯',
+    horizontal: '╌',
+    vertical: '┆',
+};
+
+/// Border of keys that changed in diff mode
+const DIFF_BORDER: BorderSymbols = BorderSymbols {
+    top_left: '╔',
+    top_right: '╗',
+    bottom_left: '╚',
+    bottom_right: '╝',
+    horizontal: '═',
+    vertical: '║',
+};
+
+/// Draw data of one key that stays the same from frame to frame.
 #[derive(Debug, Clone)]
-pub struct TapHoldKeycode {
-    /// The hold action (e.g., "L1" for layer 1, "CTL" for Ctrl)
-    pub hold: String,
-    /// The tap action (e.g., "A" for `KC_A`)
-    pub tap: String,
+pub struct KeyDraw {
+    /// Index of the key in its layer's key list
+    pub index: usize,
+    /// Visual position of the key
+    pub position: Position,
+    /// Terminal area of the key
+    pub area: Rect,
+    /// Legend lines
+    pub content: Vec<Line<'static>>,
+    /// Border color
+    pub color: Color,
+    /// Color source indicator drawn in the top border
+    pub indicator: &'static str,
+    /// Whether the key is rotated and so drawn as its bounding box
+    pub rotated: bool,
+    /// Background of the key when it isn't highlighted (dimmed transparent
+    /// keys)
+    pub fill: Option<Color>,
+    /// Whether the key is transparent and drawn dimmed or hidden, so
+    /// inherited legends are dimmed too
+    pub muted: bool,
+}
+
+/// What a [`KeyRenderCache`] was filled for.
+#[derive(Debug, Clone, PartialEq)]
+struct DrawContext {
+    generation: u64,
+    layer: usize,
+    key_count: usize,
+    inner_area: Rect,
+    theme: super::Theme,
+    scale_bits: u32,
+    dimmed: bool,
+    transparent_keys: TransparentKeyStyle,
+}
+
+/// Per-key draw data reused across frames.
+///
+/// Filled for one layer, keyboard area, theme, scale, base overlay state and
+/// transparent key style, and refilled when any of these change or after [`invalidate`]. A
+/// single-key edit only recomputes that key, see [`invalidate_key`]. Each
+/// pane of the split view has its own slot; slot 0 is also the keyboard
+/// outside the split view.
+///
+/// [`invalidate`]: KeyRenderCache::invalidate
+/// [`invalidate_key`]: KeyRenderCache::invalidate_key
+#[derive(Debug, Default)]
+pub struct KeyRenderCache {
+    generation: u64,
+    slots: [CacheSlot; 2],
+    rebuilds: usize,
+}
+
+/// Draw data of one pane.
+#[derive(Debug, Default)]
+struct CacheSlot {
+    context: Option<DrawContext>,
+    /// Draw data per key of the layer, `None` for clipped keys
+    keys: Vec<Option<KeyDraw>>,
+    /// Keys edited since the last frame, as (layer, position)
+    stale: Vec<(usize, Position)>,
+}
+
+impl KeyRenderCache {
+    /// Drops all draw data; the next frame recomputes every key.
+    pub fn invalidate(&mut self) {
+        self.generation = self.generation.wrapping_add(1);
+        for slot in &mut self.slots {
+            slot.stale.clear();
+        }
+    }
+
+    /// Recomputes the key at `position` on `layer` on the next frame.
+    pub fn invalidate_key(&mut self, layer: usize, position: Position) {
+        for slot in &mut self.slots {
+            slot.stale.push((layer, position));
+        }
+    }
+
+    /// Brings the draw data of cache slot `slot` up to date for drawing
+    /// `layer` in `inner_area`.
+    pub fn prepare(&mut self, slot: usize, inner_area: Rect, state: &AppState, layer: usize) {
+        let context = DrawContext {
+            generation: self.generation,
+            layer,
+            key_count: state.layout.layers.get(layer).map_or(0, |l| l.keys.len()),
+            inner_area,
+            theme: state.theme.clone(),
+            scale_bits: state.config.ui.keyboard_scale.to_bits(),
+            dimmed: KeyboardWidget::is_dimmed(state, layer),
+            transparent_keys: state.config.ui.transparent_keys,
+        };
+        let cached = &mut self.slots[slot];
+
+        if cached.context.as_ref() != Some(&context) {
+            cached.keys = (0..context.key_count)
+                .map(|index| KeyboardWidget::key_draw(inner_area, state, layer, index))
+                .collect();
+            cached.context = Some(context);
+            cached.stale.clear();
+            self.rebuilds += 1;
+            return;
+        }
+
+        // Clipped keys stay clipped, so only drawn keys need recomputing
+        for (stale_layer, position) in std::mem::take(&mut cached.stale) {
+            if stale_layer != layer {
+                continue;
+            }
+            for draw_slot in &mut cached.keys {
+                if let Some(draw) = draw_slot.as_ref().filter(|d| d.position == position) {
+                    *draw_slot = KeyboardWidget::key_draw(inner_area, state, layer, draw.index);
+                }
+            }
+        }
+    }
+
+    /// Draw data of the visible keys in cache slot `slot`.
+    pub fn keys(&self, slot: usize) -> impl Iterator<Item = &KeyDraw> {
+        self.slots[slot].keys.iter().flatten()
+    }
+
+    /// Number of times every key of a pane was recomputed.
+    #[must_use]
+    pub const fn rebuilds(&self) -> usize {
+        self.rebuilds
+    }
+}
+
+/// One keyboard drawn by the widget: the whole keyboard, or a pane of the
+/// split view.
+struct Pane<'a> {
+    /// Layer drawn
+    layer: usize,
+    /// Key drawn as selected
+    selected_position: Position,
+    /// Keys drawn as multi-selected
+    selected_keys: &'a [Position],
+    /// Whether edits go to this pane; `None` outside the split view
+    focused: Option<bool>,
+    /// Render cache slot of the pane
+    slot: usize,
 }
 
 impl KeyboardWidget {
-    /// Render the keyboard widget
-    #[allow(clippy::too_many_lines)]
+    /// Render the keyboard widget, as two panes in the split view
     pub fn render(f: &mut Frame, area: Rect, state: &AppState) {
-        use crate::models::keyboard_geometry::terminal_scale;
+        let focused = Pane {
+            layer: state.display_layer(),
+            selected_position: state.selected_position,
+            selected_keys: &state.selected_keys,
+            focused: None,
+            slot: 0,
+        };
+        let Some(split) = &state.split_view else {
+            Self::render_pane(f, area, state, &focused);
+            return;
+        };
 
-        let theme = &state.theme;
+        let areas = Self::pane_areas(area, state);
+        let focused = Pane {
+            focused: Some(true),
+            slot: split.focus.index(),
+            ..focused
+        };
+        let other = Pane {
+            layer: split
+                .other
+                .layer
+                .min(state.layout.layers.len().saturating_sub(1)),
+            selected_position: split.other.selected_position,
+            selected_keys: &split.other.selected_keys,
+            focused: Some(false),
+            slot: split.focus.other().index(),
+        };
+        Self::render_pane(f, areas[focused.slot], state, &focused);
+        Self::render_pane(f, areas[other.slot], state, &other);
+    }
 
-        // Get unified scale factor from config (1.0 = 100%)
-        // Apply to base scale factors from keyboard_geometry
-        let scale_multiplier = state.config.ui.keyboard_scale;
-        let scale_x = terminal_scale::DEFAULT_X_SCALE * scale_multiplier;
-        let scale_y = terminal_scale::DEFAULT_Y_SCALE * scale_multiplier;
+    /// Areas of the split view's left and right panes within `area`.
+    #[must_use]
+    pub fn pane_areas(area: Rect, state: &AppState) -> [Rect; 2] {
+        let (keyboard_width, _) = Self::required_size(
+            &state.geometry,
+            &state.mapping,
+            state.config.ui.keyboard_scale,
+        );
+        super::split_view::SplitView::pane_areas(area, keyboard_width)
+    }
+
+    /// Area of the keyboard edits go to: `area` itself, or the focused
+    /// pane's part of it in the split view.
+    #[must_use]
+    pub fn focused_area(area: Rect, state: &AppState) -> Rect {
+        state.split_view.as_ref().map_or(area, |split| {
+            Self::pane_areas(area, state)[split.focus.index()]
+        })
+    }
+
+    /// Render one keyboard: the whole widget, or a pane of the split view
+    #[allow(clippy::too_many_lines)]
+    fn render_pane(f: &mut Frame, area: Rect, state: &AppState, pane: &Pane) {
+        let theme = &state.theme;
+        let current_layer = pane.layer;
+        let has_focus = pane.focused != Some(false);
 
-        // Get current layer
-        let layer = if let Some(layer) = state.layout.layers.get(state.current_layer) {
+        // Get current layer (or the peeked one)
+        let layer = if let Some(layer) = state.layout.layers.get(current_layer) {
             layer
         } else {
             // If layer doesn't exist, show error
@@ -58,29 +403,75 @@ impl KeyboardWidget {
             return;
         };
 
+        // Base-layer legends to superimpose, unless we're on the base layer
+        let overlay = state.base_overlay.as_ref().filter(|_| current_layer != 0);
+
         // Build title with layer references info
-        let title = if let Some(refs) = state.layer_refs.get(&state.current_layer) {
+        let title = if let Some(refs) = state.layer_refs.get(&current_layer) {
             let ref_count = refs.len();
             if ref_count > 0 {
                 format!(
-                    " Layer {}: {} ({} inbound ref{}) ",
-                    state.current_layer,
+                    " Layer {current_layer}: {} ({} inbound ref{}) ",
                     layer.name,
                     ref_count,
                     if ref_count == 1 { "" } else { "s" }
                 )
             } else {
-                format!(" Layer {}: {} ", state.current_layer, layer.name)
+                format!(" Layer {current_layer}: {} ", layer.name)
             }
         } else {
-            format!(" Layer {}: {} ", state.current_layer, layer.name)
+            format!(" Layer {current_layer}: {} ", layer.name)
+        };
+
+        let title = if overlay.is_some() {
+            format!("{title}[base overlay] ")
+        } else {
+            title
+        };
+        let title = if layer.enabled {
+            title
+        } else {
+            format!("{title}[disabled] ")
+        };
+        let title = if state.coordinate_overlay == CoordinateOverlay::Off {
+            title
+        } else {
+            format!("{title}[{}] ", state.coordinate_overlay.label())
+        };
+        let title = match &state.diff_view {
+            Some(view) => format!("{title}[diff vs {}] ", view.label),
+            None => title,
+        };
+        let title = if state.simulation.is_some() {
+            format!("{title}[simulating] ")
+        } else {
+            title
+        };
+        let title = if current_layer == state.current_layer || !has_focus {
+            title
+        } else {
+            format!("{title}[peeking layer {current_layer} ({})] ", layer.name)
+        };
+        let title = if pane.focused == Some(true) {
+            format!("{title}[focused] ")
+        } else {
+            title
         };
 
         // Render outer container
         let outer_block = Block::default()
             .title(title)
             .borders(Borders::ALL)
-            .border_style(Style::default().fg(theme.primary))
+            .border_type(if pane.focused == Some(true) {
+                BorderType::Thick
+            } else {
+                BorderType::Plain
+            })
+            .border_style(Style::default().fg(if layer.enabled {
+                theme.primary
+            } else {
+                theme.text_muted
+            }))
             .style(Style::default().bg(theme.background));
         f.render_widget(outer_block, area);
 
@@ -92,176 +483,672 @@ impl KeyboardWidget {
             height: area.height.saturating_sub(2),
         };
 
-        // Calculate key dimensions from scaled values
-        // Base: 1u width = scale_x chars, 1u height = scale_y lines
-        // Add 2 for borders, enforce minimums for content visibility
-        let default_key_width: usize = ((scale_x + 2.0) as usize).max(7);
-        let default_key_height: usize = ((scale_y + 2.0) as usize).max(4);
+        // Pickers and the typing simulation act on the focused pane
+        let preview = state.color_preview().filter(|_| has_focus);
 
-        // Render each key as an individual block
-        for key in &layer.keys {
-            let row = key.position.row as usize;
-            let col = key.position.col as usize;
-
-            // Try to get actual key geometry for position-aware rendering
-            let key_geometry = state
-                .mapping
-                .visual_to_matrix_pos(key.position.row, key.position.col)
-                .and_then(|matrix_pos| state.geometry.get_key_by_matrix(matrix_pos));
-
-            // Use actual geometry dimensions if available, otherwise use defaults
-            // For grid-based layouts (visual row/col), we still position based on grid
-            // but can use actual key dimensions for sizing
-            let (key_width, key_height) = if let Some(geom) = key_geometry {
-                // Use actual key dimensions from geometry with custom scale, with minimum for borders
-                let w = (geom.terminal_width_with_scale(scale_x) as usize).max(default_key_width);
-                let h = (geom.terminal_height_with_scale(scale_y) as usize).max(default_key_height);
-                (w, h)
-            } else {
-                (default_key_width, default_key_height)
-            };
+        // Keys targeted by hold-like inbound refs on this layer get an overlay border
+        let hold_targets: HashSet<Position> = state
+            .layer_refs
+            .get(&current_layer)
+            .map(|refs| {
+                refs.iter()
+                    .filter(|r| r.kind.is_hold_like())
+                    .map(|r| r.position)
+                    .collect()
+            })
+            .unwrap_or_default();
 
-            // Calculate key position based on visual grid
-            // Note: For proper physical layout rendering, we'd use terminal_x/y,
-            // but the current navigation system expects a grid-based layout
-            let key_x = inner_area.x + (col * default_key_width) as u16;
-            let key_y = inner_area.y + (row * default_key_height) as u16;
+        // Per-key rects, legends and colors come from the cache; only the
+        // highlights below are worked out every frame
+        let mut cache = state.key_render_cache.borrow_mut();
+        cache.prepare(pane.slot, inner_area, state, current_layer);
 
-            // Skip if key is outside visible area
-            if key_x >= inner_area.x + inner_area.width || key_y >= inner_area.y + inner_area.height
-            {
+        for draw in cache.keys(pane.slot) {
+            let Some(key) = layer.keys.get(draw.index) else {
                 continue;
-            }
-
-            let key_area = Rect {
-                x: key_x,
-                y: key_y,
-                width: key_width
-                    .min((inner_area.x + inner_area.width).saturating_sub(key_x) as usize)
-                    as u16,
-                height: key_height
-                    .min((inner_area.y + inner_area.height).saturating_sub(key_y) as usize)
-                    as u16,
             };
 
-            // Skip if key area is too small
-            if key_area.width < 7 || key_area.height < 4 {
-                continue;
-            }
+            // Previewed keys drop their highlight so the color shows
+            let is_previewed = preview
+                .as_ref()
+                .is_some_and(|p| p.applies_to(&state.layout, current_layer, key));
 
-            let is_selected = row == state.selected_position.row as usize
-                && col == state.selected_position.col as usize;
+            let is_selected = key.position == pane.selected_position && !is_previewed;
 
             // Check if this key is the cut source (for visual feedback)
-            let is_cut_source = state
-                .clipboard
-                .is_cut_source(state.current_layer, key.position);
+            let is_cut_source = state.clipboard.is_cut_source(current_layer, key.position);
 
             // Check if this key is part of multi-selection
-            let is_in_selection = state.selected_keys.contains(&key.position);
+            let is_in_selection = pane.selected_keys.contains(&key.position) && !is_previewed;
 
-            // Check if this key should flash (paste feedback)
+            // Check if this key should flash (paste feedback), is marked for
+            // swapping, is a mirror target or is held down in the typing
+            // simulation
             let is_flashing = state
                 .flash_highlight
-                .is_some_and(|(layer, pos, _)| layer == state.current_layer && pos == key.position);
+                .is_some_and(|(layer, pos, _)| layer == current_layer && pos == key.position)
+                || state
+                    .pending_swap
+                    .is_some_and(|(layer, pos)| layer == current_layer && pos == key.position)
+                || state
+                    .pending_mirror
+                    .as_ref()
+                    .is_some_and(|plan| plan.is_target(current_layer, key.position))
+                || state
+                    .simulation
+                    .as_ref()
+                    .filter(|_| has_focus)
+                    .is_some_and(|sim| sim.simulator.is_held(key.position));
+
+            let (key_color, color_indicator) = match preview.as_ref().filter(|_| is_previewed) {
+                Some(preview) => Self::key_color(state, current_layer, key, Some(preview.color)),
+                None => (draw.color, draw.indicator),
+            };
+
+            Self::render_key_with_indicator(
+                f,
+                draw.area,
+                &draw.content,
+                color_indicator,
+                key_color,
+                is_selected,
+                is_cut_source,
+                is_in_selection,
+                is_flashing,
+                hold_targets.contains(&key.position),
+                draw.rotated,
+                theme,
+            );
 
-            // Resolve key color for display (respects colors_enabled and inactive_key_behavior)
-            let (key_color, color_indicator) = if let Some(current_layer) =
-                state.layout.layers.get(state.current_layer)
+            // Dimmed transparent keys get a greyed background unless highlighted
+            if let Some(fill) = draw
+                .fill
+                .filter(|_| !is_selected && !is_in_selection && !is_flashing)
             {
-                if !current_layer.layer_colors_enabled {
-                    // Layer colors disabled - use theme text_muted for visible border
-                    (theme.text_muted, "-")
-                } else {
-                    // Use resolve_display_color which considers inactive_key_behavior
-                    let (rgb, is_key_specific) =
-                        state.layout.resolve_display_color(state.current_layer, key);
-
-                    // Apply RGB settings (brightness and master switch)
-                    let final_rgb = state.layout.apply_rgb_settings(rgb);
-
-                    // Check if the color is too dark to be visible (e.g., black from "Off" behavior or master switch)
-                    // If brightness is below threshold, use theme.text_muted for visibility
-                    let brightness =
-                        (u16::from(final_rgb.r) + u16::from(final_rgb.g) + u16::from(final_rgb.b))
-                            / 3;
-                    let color = if brightness < 30 {
-                        // Color too dark for TUI visibility, use muted theme color
-                        theme.text_muted
-                    } else {
-                        Color::Rgb(final_rgb.r, final_rgb.g, final_rgb.b)
-                    };
+                let inner = Rect {
+                    x: draw.area.x + 1,
+                    y: draw.area.y + 1,
+                    width: draw.area.width.saturating_sub(2),
+                    height: draw.area.height.saturating_sub(2),
+                };
+                f.buffer_mut().set_style(inner, Style::default().bg(fill));
+            }
 
-                    let indicator = if is_key_specific {
-                        if key.color_override.is_some() {
-                            "i" // Individual override
-                        } else {
-                            "c" // Key category
-                        }
-                    } else if layer.category_id.is_some() {
-                        "L" // Layer category
-                    } else {
-                        "d" // Layer default
-                    };
-                    (color, indicator)
+            // Diff mode redraws changed keys and dims the rest
+            if let Some(view) = &state.diff_view {
+                match view.change(current_layer, key.position) {
+                    Some(change) => Self::render_diff_key(f, draw.area, change, state),
+                    None => f
+                        .buffer_mut()
+                        .set_style(draw.area, Style::default().add_modifier(Modifier::DIM)),
                 }
-            } else {
-                // Fallback - use theme text_muted for visible border
-                (theme.text_muted, "-")
+            }
+
+            if let Some(legend) = overlay.and_then(|legends| legends.get(&key.position)) {
+                Self::render_base_legend(f, draw.area, legend, draw.muted, theme);
+            }
+            if state.coordinate_overlay != CoordinateOverlay::Off {
+                let key_geometry = Self::key_geometry(state, key.position);
+                if let Some(coords) = state.coordinate_overlay.text(key.position, key_geometry) {
+                    Self::render_coordinates(f, draw.area, &coords, theme);
+                }
+            }
+        }
+
+        // Encoders sit on free grid cells, see VisualLayoutMapping::encoder_positions
+        for (index, &position) in state.mapping.encoder_positions.iter().enumerate() {
+            let Some(encoder_area) = Self::key_area(inner_area, state, position, None) else {
+                continue;
             };
+            Self::render_encoder(
+                f,
+                encoder_area,
+                index,
+                &layer.encoder(index),
+                position == pane.selected_position,
+                Self::is_dimmed(state, current_layer),
+                theme,
+            );
+        }
+    }
 
-            // Parse keycode to determine if it's a tap-hold type
-            let tap_hold = Self::parse_tap_hold_keycode(&key.keycode, state);
+    /// Draws encoder `index` as a rounded box with the counter-clockwise
+    /// legend above the clockwise one.
+    fn render_encoder(
+        f: &mut Frame,
+        area: Rect,
+        index: usize,
+        encoder: &EncoderMapping,
+        is_selected: bool,
+        dimmed: bool,
+        theme: &super::Theme,
+    ) {
+        let (border_style, content_style) = if is_selected {
+            (
+                Style::default()
+                    .fg(theme.accent)
+                    .add_modifier(Modifier::BOLD),
+                Style::default().fg(theme.background).bg(theme.accent),
+            )
+        } else {
+            (
+                Style::default().fg(theme.text_muted),
+                Style::default().fg(theme.text),
+            )
+        };
+        let content_style = if dimmed {
+            content_style.add_modifier(Modifier::DIM)
+        } else {
+            content_style
+        };
 
-            // Build content lines based on keycode type
-            let content: Vec<Line> = if let Some(th) = &tap_hold {
-                // Tap-hold keycode: show hold on top, tap on bottom
-                vec![
-                    Line::from(vec![Span::styled(
-                        format!("▼{:<5}", Self::truncate(&th.hold, 5)),
-                        Style::default().fg(theme.text_muted),
-                    )]),
-                    Line::from(vec![Span::styled(
-                        format!(" {:<5}", Self::truncate(&th.tap, 5)),
-                        Style::default().fg(theme.text),
-                    )]),
-                ]
-            } else {
+        let legend = |arrow: char, keycode: &str| {
+            let display = Self::format_simple_keycode(keycode);
+            Line::from(Span::styled(
+                format!("{arrow}{:<5}", Self::truncate(&display, 5)),
+                content_style,
+            ))
+        };
+        let block = Block::default()
+            .borders(Borders::ALL)
+            .border_type(BorderType::Rounded)
+            .border_style(border_style)
+            .title(Span::styled(
+                if is_selected && theme.high_contrast {
+                    format!(">E{index}")
+                } else {
+                    format!("E{index}")
+                },
+                border_style,
+            ));
+        let inner = block.inner(area);
+        f.render_widget(block, area);
+        f.render_widget(
+            Paragraph::new(vec![legend('↺', &encoder.ccw), legend('↻', &encoder.cw)])
+                .style(content_style),
+            inner,
+        );
+    }
+
+    /// Whether legends on `layer_idx` are drawn dimmed: under the base
+    /// overlay, or because the layer is disabled.
+    fn is_dimmed(state: &AppState, layer_idx: usize) -> bool {
+        (state.base_overlay.is_some() && layer_idx != 0)
+            || state
+                .layout
+                .layers
+                .get(layer_idx)
+                .is_some_and(|layer| !layer.enabled)
+    }
+
+    /// Computes the cached draw data of the `index`th key on `layer_idx`, or
+    /// `None` if the key is clipped.
+    #[must_use]
+    pub fn key_draw(
+        inner_area: Rect,
+        state: &AppState,
+        layer_idx: usize,
+        index: usize,
+    ) -> Option<KeyDraw> {
+        let theme = &state.theme;
+        let key = state.layout.layers.get(layer_idx)?.keys.get(index)?;
+        let key_geometry = Self::key_geometry(state, key.position);
+        let area = Self::key_area(inner_area, state, key.position, key_geometry)?;
+        let (color, indicator) = Self::key_color(state, layer_idx, key, None);
+        let transparent_style = key
+            .is_transparent()
+            .then_some(state.config.ui.transparent_keys)
+            .filter(|&style| style != TransparentKeyStyle::Label);
+
+        // Parse keycode to determine if it's a tap-hold type
+        let legend = Self::key_legend(&key.keycode, state);
+
+        // Dim the current layer's legends while the base overlay is shown
+        // or the layer is left out of firmware
+        let dim = if Self::is_dimmed(state, layer_idx) {
+            Modifier::DIM
+        } else {
+            Modifier::empty()
+        };
+
+        // Build content lines based on keycode type
+        let width = usize::from(area.width.saturating_sub(2));
+        let content = match &legend {
+            // Dimmed transparent key: a muted glyph instead of the label
+            _ if transparent_style == Some(TransparentKeyStyle::Dim) => vec![
+                Line::from(""),
+                Line::from(vec![Span::styled(
+                    " ▽",
+                    Style::default()
+                        .fg(theme.text_muted)
+                        .add_modifier(Modifier::DIM),
+                )]),
+            ],
+            // Hidden transparent key: outline only
+            _ if transparent_style == Some(TransparentKeyStyle::Hidden) => Vec::new(),
+            // Tap-hold keycode: tap on top, hold descriptor below
+            Some(legend) if area.height >= 4 => vec![
+                Line::from(vec![Span::styled(
+                    Self::fit(&legend.tap, width),
+                    Style::default().fg(theme.text).add_modifier(dim),
+                )]),
+                Line::from(vec![Span::styled(
+                    Self::fit(&legend.hold, width),
+                    Style::default().fg(theme.text_muted).add_modifier(dim),
+                )]),
+            ],
+            // Only one line inside the key: both abbreviated
+            Some(legend) => vec![Line::from(vec![Span::styled(
+                Self::fit(&legend.compound, width),
+                Style::default().fg(theme.text).add_modifier(dim),
+            )])],
+            None => {
                 // Simple keycode: center vertically with two lines
                 let display = Self::format_simple_keycode(&key.keycode);
                 vec![
                     Line::from(""), // Empty first line for vertical centering
                     Line::from(vec![Span::styled(
                         format!(" {:<5}", Self::truncate(&display, 5)),
-                        Style::default().fg(theme.text),
+                        Style::default().fg(theme.text).add_modifier(dim),
                     )]),
                 ]
-            };
+            }
+        };
 
-            // Render the key with custom border that includes color indicator
-            // Determine if this key is targeted by hold-like inbound refs on this layer
-            let has_hold_like_inbound = state
-                .layer_refs
-                .get(&state.current_layer)
-                .map(|refs| {
-                    refs.iter()
-                        .any(|r| r.position == key.position && r.kind.is_hold_like())
-                })
-                .unwrap_or(false);
+        let (color, indicator) = match transparent_style {
+            Some(_) => (theme.text_muted, ""),
+            None => (color, indicator),
+        };
 
-            Self::render_key_with_indicator(
-                f,
-                key_area,
-                &content,
-                color_indicator,
-                key_color,
-                is_selected,
-                is_cut_source,
-                is_in_selection,
-                is_flashing,
-                has_hold_like_inbound,
-                theme,
-            );
+        Some(KeyDraw {
+            index,
+            position: key.position,
+            area,
+            content,
+            color,
+            indicator,
+            rotated: key_geometry.is_some_and(KeyGeometry::is_rotated),
+            fill: (transparent_style == Some(TransparentKeyStyle::Dim)).then_some(theme.surface),
+            muted: transparent_style.is_some(),
+        })
+    }
+
+    /// Border color and color source indicator of `key` on layer `layer_idx`.
+    ///
+    /// `preview` replaces the key's resolved color while a color picker is
+    /// open.
+    fn key_color(
+        state: &AppState,
+        layer_idx: usize,
+        key: &KeyDefinition,
+        preview: Option<RgbColor>,
+    ) -> (Color, &'static str) {
+        let theme = &state.theme;
+        let Some(layer) = state.layout.layers.get(layer_idx) else {
+            // Fallback - use theme text_muted for visible border
+            return (theme.text_muted, "-");
+        };
+        if !layer.layer_colors_enabled {
+            // Layer colors disabled - use theme text_muted for visible border
+            return (theme.text_muted, "-");
+        }
+
+        // Use resolve_display_color which considers inactive_key_behavior
+        let (rgb, is_key_specific) = state.layout.resolve_display_color(layer_idx, key);
+        let rgb = preview.unwrap_or(rgb);
+
+        // Apply RGB settings (brightness and master switch)
+        let final_rgb = state.layout.apply_rgb_settings(rgb);
+
+        // Check if the color is too dark to be visible (e.g., black from "Off" behavior or master switch)
+        // If brightness is below threshold, use theme.text_muted for visibility
+        let brightness =
+            (u16::from(final_rgb.r) + u16::from(final_rgb.g) + u16::from(final_rgb.b)) / 3;
+        let color = if brightness < 30 {
+            // Color too dark for TUI visibility, use muted theme color
+            theme.text_muted
+        } else {
+            Color::Rgb(final_rgb.r, final_rgb.g, final_rgb.b)
+        };
+
+        let indicator = if is_key_specific {
+            if key.color_override.is_some() {
+                "i" // Individual override
+            } else {
+                "c" // Key category
+            }
+        } else if layer.category_id.is_some() {
+            "L" // Layer category
+        } else {
+            "d" // Layer default
+        };
+        (color, indicator)
+    }
+
+    /// Terminal area of the key at `position` when the keyboard is drawn in
+    /// `area`, if it is visible.
+    #[must_use]
+    pub fn key_rect(area: Rect, state: &AppState, position: Position) -> Option<Rect> {
+        let inner_area = Rect {
+            x: area.x + 1,
+            y: area.y + 1,
+            width: area.width.saturating_sub(2),
+            height: area.height.saturating_sub(2),
+        };
+        Self::key_area(
+            inner_area,
+            state,
+            position,
+            Self::key_geometry(state, position),
+        )
+    }
+
+    /// Key under the terminal cell (`column`, `row`) when the keyboard is
+    /// drawn in `area`.
+    ///
+    /// Rotated keys are drawn as their bounding box and can overlap their
+    /// neighbours, so among the keys covering the cell the one whose rotated
+    /// center is closest wins.
+    #[must_use]
+    pub fn key_at(area: Rect, state: &AppState, column: u16, row: u16) -> Option<Position> {
+        let distance = |rect: Rect| {
+            let dx = i32::from(rect.x) * 2 + i32::from(rect.width) - i32::from(column) * 2 - 1;
+            let dy = i32::from(rect.y) * 2 + i32::from(rect.height) - i32::from(row) * 2 - 1;
+            // Terminal cells are about twice as tall as they are wide
+            dx.pow(2) + (dy * 2).pow(2)
+        };
+        state
+            .mapping
+            .get_all_visual_positions()
+            .into_iter()
+            .filter_map(|position| {
+                let rect = Self::key_rect(area, state, position)?;
+                let inside = (rect.x..rect.x + rect.width).contains(&column)
+                    && (rect.y..rect.y + rect.height).contains(&row);
+                inside.then(|| (distance(rect), position))
+            })
+            .min_by_key(|&(distance, position)| (distance, position.row, position.col))
+            .map(|(_, position)| position)
+    }
+
+    /// Physical geometry of the key at a visual position, if known.
+    fn key_geometry(state: &AppState, position: Position) -> Option<&KeyGeometry> {
+        state
+            .mapping
+            .visual_to_matrix_pos(position.row, position.col)
+            .and_then(|matrix_pos| state.geometry.get_key_by_matrix(matrix_pos))
+    }
+
+    /// Area of a key within the keyboard's inner area, or `None` if it is
+    /// clipped or too small to draw.
+    fn key_area(
+        inner_area: Rect,
+        state: &AppState,
+        position: Position,
+        key_geometry: Option<&KeyGeometry>,
+    ) -> Option<Rect> {
+        let (x, y, key_width, key_height) =
+            Self::key_extent(position, key_geometry, state.config.ui.keyboard_scale);
+        let key_x = inner_area.x + x as u16;
+        let key_y = inner_area.y + y as u16;
+
+        // Skip if key is outside visible area
+        if key_x >= inner_area.x + inner_area.width || key_y >= inner_area.y + inner_area.height {
+            return None;
+        }
+
+        let key_area = Rect {
+            x: key_x,
+            y: key_y,
+            width: key_width.min((inner_area.x + inner_area.width).saturating_sub(key_x) as usize)
+                as u16,
+            height: key_height
+                .min((inner_area.y + inner_area.height).saturating_sub(key_y) as usize)
+                as u16,
+        };
+
+        // Skip if key area is too small
+        if key_area.width < 7 || key_area.height < 4 {
+            return None;
+        }
+        Some(key_area)
+    }
+
+    /// Unclipped offset and size of a key relative to the keyboard's inner
+    /// area, as (x, y, width, height) in cells.
+    fn key_extent(
+        position: Position,
+        key_geometry: Option<&KeyGeometry>,
+        scale_multiplier: f32,
+    ) -> (usize, usize, usize, usize) {
+        use crate::models::keyboard_geometry::terminal_scale;
+
+        // Apply the configured scale (1.0 = 100%) to the base scale factors
+        let scale_x = terminal_scale::DEFAULT_X_SCALE * scale_multiplier;
+        let scale_y = terminal_scale::DEFAULT_Y_SCALE * scale_multiplier;
+
+        // Calculate key dimensions from scaled values
+        // Base: 1u width = scale_x chars, 1u height = scale_y lines
+        // Add 2 for borders, enforce minimums for content visibility
+        let default_key_width: usize = ((scale_x + 2.0) as usize).max(7);
+        let default_key_height: usize = ((scale_y + 2.0) as usize).max(4);
+
+        // Use actual geometry dimensions if available, otherwise use defaults
+        // For grid-based layouts (visual row/col), we still position based on grid
+        // but can use actual key dimensions for sizing
+        let (key_width, key_height) = if let Some(geom) = key_geometry {
+            // Use actual key dimensions from geometry with custom scale, with minimum for borders
+            let w = (geom.terminal_width_with_scale(scale_x) as usize).max(default_key_width);
+            let h = (geom.terminal_height_with_scale(scale_y) as usize).max(default_key_height);
+            (w, h)
+        } else {
+            (default_key_width, default_key_height)
+        };
+
+        // Rotated keys take up their bounding box, grown evenly around the
+        // cell of the unrotated key so it stays centered on the rotated center
+        let (grow_x, grow_y) = match key_geometry.filter(|geom| geom.is_rotated()) {
+            Some(geom) => {
+                let (_, _, width, height) = geom.bounding_box();
+                (
+                    ((width - geom.width) * scale_x / 2.0) as usize,
+                    ((height - geom.height) * scale_y / 2.0) as usize,
+                )
+            }
+            None => (0, 0),
+        };
+
+        // Position on the visual grid
+        // Note: For proper physical layout rendering, we'd use terminal_x/y,
+        // but the current navigation system expects a grid-based layout
+        (
+            (usize::from(position.col) * default_key_width).saturating_sub(grow_x),
+            (usize::from(position.row) * default_key_height).saturating_sub(grow_y),
+            key_width + grow_x * 2,
+            key_height + grow_y * 2,
+        )
+    }
+
+    /// Size of the keyboard widget, borders included, needed to draw every
+    /// key and encoder unclipped: (width, height).
+    #[must_use]
+    pub fn required_size(
+        geometry: &KeyboardGeometry,
+        mapping: &VisualLayoutMapping,
+        keyboard_scale: f32,
+    ) -> (u16, u16) {
+        let keys = mapping
+            .get_all_visual_positions()
+            .into_iter()
+            .map(|position| {
+                let key_geometry = mapping
+                    .visual_to_matrix_pos(position.row, position.col)
+                    .and_then(|matrix_pos| geometry.get_key_by_matrix(matrix_pos));
+                (position, key_geometry)
+            });
+        let encoders = mapping
+            .encoder_positions
+            .iter()
+            .map(|&position| (position, None));
+
+        let (width, height) = keys
+            .chain(encoders)
+            .map(|(position, key_geometry)| {
+                let (x, y, width, height) =
+                    Self::key_extent(position, key_geometry, keyboard_scale);
+                (x + width, y + height)
+            })
+            .fold((0, 0), |(w, h), (x, y)| (w.max(x), h.max(y)));
+        (
+            u16::try_from(width + 2).unwrap_or(u16::MAX),
+            u16::try_from(height + 2).unwrap_or(u16::MAX),
+        )
+    }
+
+    /// Computes the base layer's legend for every key position.
+    ///
+    /// Tap-hold keys show their abbreviated tap and hold actions; transparent
+    /// and empty keys are left out.
+    #[must_use]
+    pub fn base_layer_legends(state: &AppState) -> HashMap<Position, String> {
+        let Some(base) = state.layout.layers.first() else {
+            return HashMap::new();
+        };
+        base.keys
+            .iter()
+            .filter(|key| !key.is_transparent() && !key.is_no_op())
+            .map(|key| {
+                let legend = Self::key_legend(&key.keycode, state).map_or_else(
+                    || Self::format_simple_keycode(&key.keycode),
+                    |legend| legend.compound,
+                );
+                (key.position, legend)
+            })
+            .collect()
+    }
+
+    /// Draws coordinate text into the left of the key's top border, leaving the
+    /// color indicator in the right corner visible.
+    fn render_coordinates(f: &mut Frame, area: Rect, text: &str, theme: &super::Theme) {
+        let max_len = area.width.saturating_sub(3) as usize;
+        let style = Style::default().fg(theme.text_muted);
+        let buf = f.buffer_mut();
+        for (i, ch) in Self::truncate(text, max_len).chars().enumerate() {
+            buf[(area.x + 1 + i as u16, area.y)]
+                .set_char(ch)
+                .set_style(style);
+        }
+    }
+
+    /// Draws a base-layer legend into the key's bottom border, leaving the
+    /// border corners (and so the selection highlight) intact.
+    ///
+    /// `muted` keeps the legend dim on transparent keys drawn dimmed or
+    /// hidden.
+    fn render_base_legend(
+        f: &mut Frame,
+        area: Rect,
+        legend: &str,
+        muted: bool,
+        theme: &super::Theme,
+    ) {
+        let bottom_y = area.y + area.height.saturating_sub(1);
+        let max_len = area.width.saturating_sub(2) as usize;
+        let style = Style::default()
+            .fg(theme.text_secondary)
+            .add_modifier(Modifier::ITALIC);
+        let style = if muted {
+            style.add_modifier(Modifier::DIM)
+        } else {
+            style
+        };
+        let buf = f.buffer_mut();
+        for (i, ch) in Self::truncate(legend, max_len).chars().enumerate() {
+            buf[(area.x + 1 + i as u16, bottom_y)]
+                .set_char(ch)
+                .set_style(style);
+        }
+    }
+
+    /// Draws a key changed in diff mode: a double border and the old legend
+    /// above an arrow to the new one.
+    fn render_diff_key(f: &mut Frame, area: Rect, change: &KeyChange, state: &AppState) {
+        if area.width < 3 || area.height < 3 {
+            return;
+        }
+        let theme = &state.theme;
+        let legend = |keycode: Option<&str>| {
+            keycode.map_or_else(
+                || "-".to_string(),
+                |keycode| {
+                    Self::key_legend(keycode, state).map_or_else(
+                        || Self::format_simple_keycode(keycode),
+                        |legend| legend.compound,
+                    )
+                },
+            )
+        };
+        let old = legend(change.old_keycode.as_deref());
+        let new = format!("→{}", legend(change.new_keycode.as_deref()));
+
+        let right_x = area.x + area.width - 1;
+        let bottom_y = area.y + area.height - 1;
+        let border_style = Style::default()
+            .fg(theme.warning)
+            .add_modifier(Modifier::BOLD);
+        let buf = f.buffer_mut();
+
+        for x in area.x..=right_x {
+            buf[(x, area.y)]
+                .set_char(DIFF_BORDER.horizontal)
+                .set_style(border_style);
+            buf[(x, bottom_y)]
+                .set_char(DIFF_BORDER.horizontal)
+                .set_style(border_style);
+        }
+        for y in area.y..=bottom_y {
+            buf[(area.x, y)]
+                .set_char(DIFF_BORDER.vertical)
+                .set_style(border_style);
+            buf[(right_x, y)]
+                .set_char(DIFF_BORDER.vertical)
+                .set_style(border_style);
+        }
+        buf[(area.x, area.y)].set_char(DIFF_BORDER.top_left);
+        buf[(right_x, area.y)].set_char(DIFF_BORDER.top_right);
+        buf[(area.x, bottom_y)].set_char(DIFF_BORDER.bottom_left);
+        buf[(right_x, bottom_y)].set_char(DIFF_BORDER.bottom_right);
+
+        // Old legend on the first row and the new one below, or both on one
+        // row when the key is only one row tall inside
+        let width = (area.width - 2) as usize;
+        let lines = if area.height >= 4 {
+            vec![
+                (old, Style::default().fg(theme.text_muted)),
+                (
+                    new,
+                    Style::default().fg(theme.text).add_modifier(Modifier::BOLD),
+                ),
+            ]
+        } else {
+            vec![(
+                format!("{old}{new}"),
+                Style::default().fg(theme.text).add_modifier(Modifier::BOLD),
+            )]
+        };
+        for row in 1..area.height - 1 {
+            for col in 1..area.width - 1 {
+                buf[(area.x + col, area.y + row)]
+                    .set_char(' ')
+                    .set_style(Style::default().bg(theme.background));
+            }
+        }
+        for (row, (text, style)) in lines.into_iter().enumerate() {
+            for (i, ch) in Self::truncate(&text, width).chars().enumerate() {
+                buf[(area.x + 1 + i as u16, area.y + 1 + row as u16)]
+                    .set_char(ch)
+                    .set_style(style);
+            }
         }
     }
 
@@ -278,8 +1165,29 @@ impl KeyboardWidget {
         is_in_selection: bool,
         is_flashing: bool,
         has_hold_like_inbound: bool,
+        rotated: bool,
         theme: &super::Theme,
     ) {
+        // The high-contrast theme draws every key white; the color indicator
+        // character still tells where the color comes from
+        let border_color = if theme.high_contrast {
+            theme.text
+        } else {
+            border_color
+        };
+        let marker = theme
+            .high_contrast
+            .then(|| {
+                Self::state_marker(
+                    is_selected,
+                    is_cut_source,
+                    is_in_selection,
+                    is_flashing,
+                    has_hold_like_inbound,
+                )
+            })
+            .flatten();
+
         // Determine colors based on selection, cut state, multi-selection, flash, and inbound holds
         let (border_style, content_bg, content_fg, overlay_border_color) = if is_flashing {
             // Flash highlight: bright accent background
@@ -362,14 +1270,26 @@ impl KeyboardWidget {
         let left_x = area.x;
         let right_x = area.x + area.width.saturating_sub(1);
 
+        // Rotated keys are only approximated by their bounding box, which a
+        // rounded, dashed border makes visible: ╭╌╌╌╌╌╌i╮
+        let symbols = if rotated {
+            ROTATED_BORDER
+        } else {
+            PLAIN_BORDER
+        };
+
         // Draw corners
-        buf[(left_x, top_y)].set_char('┌').set_style(border_style);
-        buf[(right_x, top_y)].set_char('┐').set_style(border_style);
+        buf[(left_x, top_y)]
+            .set_char(symbols.top_left)
+            .set_style(border_style);
+        buf[(right_x, top_y)]
+            .set_char(symbols.top_right)
+            .set_style(border_style);
         buf[(left_x, area.y + area.height.saturating_sub(1))]
-            .set_char('└')
+            .set_char(symbols.bottom_left)
             .set_style(border_style);
         buf[(right_x, area.y + area.height.saturating_sub(1))]
-            .set_char('┘')
+            .set_char(symbols.bottom_right)
             .set_style(border_style);
 
         // Top border with indicator in right corner (just before ┐)
@@ -380,7 +1300,12 @@ impl KeyboardWidget {
 
             for i in 0..top_width {
                 let x = left_x + 1 + i as u16;
-                if i == indicator_pos {
+                if let Some(marker) = marker.filter(|_| i == 0 && indicator_pos > 0) {
+                    // State marker in the leftmost position: ┌>─────i┐
+                    buf[(x, top_y)]
+                        .set_char(marker)
+                        .set_style(border_style.add_modifier(Modifier::REVERSED));
+                } else if i == indicator_pos {
                     // Draw the indicator character with the border color
                     let indicator_style = if is_selected {
                         Style::default()
@@ -392,10 +1317,12 @@ impl KeyboardWidget {
                             .add_modifier(Modifier::BOLD)
                     };
                     buf[(x, top_y)]
-                        .set_char(indicator.chars().next().unwrap_or('─'))
+                        .set_char(indicator.chars().next().unwrap_or(symbols.horizontal))
                         .set_style(indicator_style);
                 } else {
-                    buf[(x, top_y)].set_char('─').set_style(border_style);
+                    buf[(x, top_y)]
+                        .set_char(symbols.horizontal)
+                        .set_style(border_style);
                 }
             }
         }
@@ -403,17 +1330,17 @@ impl KeyboardWidget {
         // Bottom border
         for i in 1..area.width.saturating_sub(1) {
             buf[(left_x + i, area.y + area.height.saturating_sub(1))]
-                .set_char('─')
+                .set_char(symbols.horizontal)
                 .set_style(border_style);
         }
 
         // Left and right borders
         for row in 1..area.height.saturating_sub(1) {
             buf[(left_x, area.y + row)]
-                .set_char('│')
+                .set_char(symbols.vertical)
                 .set_style(border_style);
             buf[(right_x, area.y + row)]
-                .set_char('│')
+                .set_char(symbols.vertical)
                 .set_style(border_style);
         }
 
@@ -434,21 +1361,38 @@ impl KeyboardWidget {
             height: area.height.saturating_sub(2),
         };
 
+        // Legends of rotated keys sit in the middle of the bounding box,
+        // which is where the rotated key's center is
+        let top_offset = if rotated {
+            content_area.height.saturating_sub(content.len() as u16) / 2
+        } else {
+            0
+        };
+
         for (i, line) in content.iter().enumerate() {
-            if i >= content_area.height as usize {
+            if i + top_offset as usize >= content_area.height as usize {
                 break;
             }
-            let y = content_area.y + i as u16;
+            let y = content_area.y + top_offset + i as u16;
 
             // Render each span in the line
             let mut x = content_area.x;
+            if rotated {
+                let text = line.to_string();
+                x += content_area
+                    .width
+                    .saturating_sub(text.trim_end().chars().count() as u16)
+                    / 2;
+            }
             for span in &line.spans {
                 for ch in span.content.chars() {
                     if x >= content_area.x + content_area.width {
                         break;
                     }
                     let mut style = span.style;
-                    if is_selected {
+                    // High contrast has white backgrounds under flashed keys
+                    // too, so their legends need the inverse color as well
+                    if is_selected || (theme.high_contrast && content_bg.is_some()) {
                         style = style.fg(content_fg);
                         if let Some(bg) = content_bg {
                             style = style.bg(bg);
@@ -461,67 +1405,38 @@ impl KeyboardWidget {
         }
     }
 
-    /// Parse a keycode to extract tap-hold components if applicable.
-    ///
-    /// Uses the keycode database to dynamically detect mod-tap prefixes
-    /// rather than maintaining a hardcoded list.
-    fn parse_tap_hold_keycode(keycode: &str, state: &AppState) -> Option<TapHoldKeycode> {
-        // Use the keycode database to parse tap-hold keycodes
-        let info = state.keycode_db.parse_tap_hold(keycode)?;
-
-        match info.tap_hold_type {
-            TapHoldType::LayerTap => {
-                // LT(layer, keycode) - Layer Tap
-                let layer_display = Self::resolve_layer_display(&info.arg1, state);
-                let tap_display = Self::format_simple_keycode(info.arg2.as_deref().unwrap_or(""));
-                Some(TapHoldKeycode {
-                    hold: format!("L{layer_display}"),
-                    tap: tap_display,
-                })
-            }
-            TapHoldType::ModTap => {
-                // MT(mod, keycode) - Custom Mod Tap
-                let mod_display = Self::format_modifier(&info.arg1);
-                let tap_display = Self::format_simple_keycode(info.arg2.as_deref().unwrap_or(""));
-                Some(TapHoldKeycode {
-                    hold: mod_display,
-                    tap: tap_display,
-                })
-            }
-            TapHoldType::ModTapNamed => {
-                // Named mod-tap like LCTL_T(keycode)
-                // Use keycode_db to get the display name for the prefix
-                let mod_display = state
-                    .keycode_db
-                    .get_mod_tap_display(&info.prefix)
-                    .unwrap_or("MOD")
-                    .to_string();
-                let tap_display = Self::format_simple_keycode(&info.arg1);
-                Some(TapHoldKeycode {
-                    hold: mod_display,
-                    tap: tap_display,
-                })
-            }
-            TapHoldType::LayerMod => {
-                // LM(layer, mod) - Layer Mod
-                let layer_display = Self::resolve_layer_display(&info.arg1, state);
-                let mod_display = Self::format_modifier(info.arg2.as_deref().unwrap_or(""));
-                Some(TapHoldKeycode {
-                    hold: format!("L{layer_display}+"),
-                    tap: mod_display,
-                })
-            }
-            TapHoldType::SwapHands => {
-                // SH_T(keycode) - Swap Hands Tap
-                let tap_display = Self::format_simple_keycode(&info.arg1);
-                Some(TapHoldKeycode {
-                    hold: "SWAP".to_string(),
-                    tap: tap_display,
-                })
-            }
+    /// Text marker for a key state the high-contrast theme can't show with
+    /// color: `*` flashed or a mirror target, `>` selected, `+` in the
+    /// selection, `~` cut, `^` target of a hold-like layer key.
+    const fn state_marker(
+        is_selected: bool,
+        is_cut_source: bool,
+        is_in_selection: bool,
+        is_flashing: bool,
+        has_hold_like_inbound: bool,
+    ) -> Option<char> {
+        if is_flashing {
+            Some('*')
+        } else if is_selected {
+            Some('>')
+        } else if is_in_selection {
+            Some('+')
+        } else if is_cut_source {
+            Some('~')
+        } else if has_hold_like_inbound {
+            Some('^')
+        } else {
+            None
         }
     }
 
+    /// Two-line legend of a tap-hold, one-shot or tap dance keycode.
+    fn key_legend(keycode: &str, state: &AppState) -> Option<KeyLegend> {
+        state.keycode_db.key_legend(keycode, |layer_ref| {
+            Self::resolve_layer_display(layer_ref, state)
+        })
+    }
+
     /// Resolve layer reference to display string
     fn resolve_layer_display(layer_ref: &str, state: &AppState) -> String {
         // If it's a @uuid reference, find the layer number
@@ -536,32 +1451,6 @@ impl KeyboardWidget {
         layer_ref.to_string()
     }
 
-    /// Format modifier for compact display
-    fn format_modifier(mod_str: &str) -> String {
-        // Handle combined modifiers like "MOD_LCTL | MOD_LSFT"
-        let mut result = String::new();
-
-        if mod_str.contains("LCTL") || mod_str.contains("RCTL") {
-            result.push('C');
-        }
-        if mod_str.contains("LSFT") || mod_str.contains("RSFT") {
-            result.push('S');
-        }
-        if mod_str.contains("LALT") || mod_str.contains("RALT") {
-            result.push('A');
-        }
-        if mod_str.contains("LGUI") || mod_str.contains("RGUI") {
-            result.push('G');
-        }
-
-        if result.is_empty() {
-            // Fallback: take first 3 chars
-            mod_str.chars().take(3).collect()
-        } else {
-            result
-        }
-    }
-
     /// Format a simple keycode for compact display (removes KC_ prefix)
     fn format_simple_keycode(keycode: &str) -> String {
         // Remove KC_ prefix if present
@@ -569,6 +1458,16 @@ impl KeyboardWidget {
         display.to_string()
     }
 
+    /// Fits a legend line into `width` columns, with a leading space when
+    /// there is room for one.
+    fn fit(s: &str, width: usize) -> String {
+        if s.chars().count() < width {
+            format!(" {s}")
+        } else {
+            Self::truncate(s, width)
+        }
+    }
+
     /// Truncate a string to a maximum length
     fn truncate(s: &str, max_len: usize) -> String {
         if s.len() <= max_len {
@@ -582,6 +1481,51 @@ impl KeyboardWidget {
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::models::{Category, Layer};
+
+    #[test]
+    fn test_color_preview_targets() {
+        let mut layout = Layout::new("Preview").unwrap();
+        layout
+            .categories
+            .push(Category::new("nav", "Navigation", RgbColor::new(0, 0, 255)).unwrap());
+        let mut layer = Layer::new(0, "Base", RgbColor::new(255, 255, 255)).unwrap();
+        for key in [
+            KeyDefinition::at(Position::new(0, 0)).keycode("KC_A"),
+            KeyDefinition::at(Position::new(0, 1)).category("nav"),
+            KeyDefinition::at(Position::new(0, 2))
+                .category("nav")
+                .color(RgbColor::new(255, 0, 0)),
+        ] {
+            layer.add_key(key).unwrap();
+        }
+        layout.add_layer(layer).unwrap();
+        let keys = &layout.layers[0].keys;
+
+        let preview = |target| ColorPreview {
+            color: RgbColor::new(0, 255, 0),
+            target,
+        };
+        let targeted = |preview: ColorPreview| -> Vec<bool> {
+            keys.iter()
+                .map(|key| preview.applies_to(&layout, 0, key))
+                .collect()
+        };
+
+        assert_eq!(
+            targeted(preview(PreviewTarget::Keys(vec![Position::new(0, 2)]))),
+            [false, false, true]
+        );
+        // An individual override wins over the category
+        assert_eq!(
+            targeted(preview(PreviewTarget::Category("nav".to_string()))),
+            [false, true, false]
+        );
+        assert_eq!(
+            targeted(preview(PreviewTarget::LayerDefault)),
+            [true, false, false]
+        );
+    }
 
     #[test]
     fn test_format_simple_keycode() {
@@ -592,20 +1536,382 @@ mod tests {
     }
 
     #[test]
-    fn test_format_modifier() {
-        assert_eq!(KeyboardWidget::format_modifier("MOD_LCTL"), "C");
-        assert_eq!(KeyboardWidget::format_modifier("MOD_LSFT"), "S");
-        assert_eq!(KeyboardWidget::format_modifier("MOD_LALT"), "A");
-        assert_eq!(KeyboardWidget::format_modifier("MOD_LGUI"), "G");
-        assert_eq!(KeyboardWidget::format_modifier("MOD_LCTL | MOD_LSFT"), "CS");
-        assert_eq!(
-            KeyboardWidget::format_modifier("MOD_LCTL | MOD_LSFT | MOD_LALT"),
-            "CSA"
-        );
-        assert_eq!(
-            KeyboardWidget::format_modifier("MOD_LCTL | MOD_LSFT | MOD_LALT | MOD_LGUI"),
-            "CSAG"
-        );
+    fn test_coordinate_overlay_cycles_and_formats() {
+        let mut geometry = KeyGeometry::new((2, 5), 12, 0.0, 0.0);
+        geometry.layout_index = 7;
+        geometry.role.home_row = true;
+        geometry.role.pinky = true;
+        let position = Position::new(1, 3);
+
+        let mut mode = CoordinateOverlay::Off;
+        let mut shown = Vec::new();
+        loop {
+            mode = mode.next();
+            if mode == CoordinateOverlay::Off {
+                break;
+            }
+            shown.push(mode.text(position, Some(&geometry)).unwrap());
+        }
+        assert_eq!(shown, ["1,3", "2,5", "12", "7", "HP"]);
+        assert_eq!(CoordinateOverlay::Off.text(position, Some(&geometry)), None);
+        assert_eq!(CoordinateOverlay::Led.text(position, None), None);
+    }
+
+    /// Keyboard text drawn for `state` on a terminal big enough for 3 keys
+    fn render_text(state: &AppState) -> String {
+        let mut terminal =
+            ratatui::Terminal::new(ratatui::backend::TestBackend::new(40, 8)).unwrap();
+        terminal
+            .draw(|f| KeyboardWidget::render(f, f.area(), state))
+            .unwrap();
+        let buffer = terminal.backend().buffer();
+        buffer
+            .content()
+            .iter()
+            .map(ratatui::buffer::Cell::symbol)
+            .collect()
+    }
+
+    #[test]
+    fn test_render_cache_recomputes_only_edited_keys() {
+        let mut geometry = crate::models::KeyboardGeometry::new("test", "test", 1, 3);
+        let mut layer = Layer::new(0, "Base", RgbColor::new(255, 255, 255)).unwrap();
+        for col in 0..3 {
+            geometry.add_key(KeyGeometry::new((0, col), col, f32::from(col), 0.0));
+            layer
+                .add_key(KeyDefinition::at(Position::new(0, col)).keycode("KC_A"))
+                .unwrap();
+        }
+        let mut layout = Layout::new("Cache").unwrap();
+        layout.add_layer(layer).unwrap();
+        let mapping = crate::models::VisualLayoutMapping::build(&geometry);
+        let mut state = AppState::new(
+            layout,
+            None,
+            geometry,
+            mapping,
+            crate::config::Config::default(),
+        )
+        .unwrap();
+
+        render_text(&state);
+        render_text(&state);
+        assert_eq!(state.key_render_cache.borrow().rebuilds(), 1);
+
+        state.selected_position = Position::new(0, 1);
+        state.get_selected_key_mut().unwrap().keycode = "KC_B".to_string();
+        state.mark_selected_key_dirty();
+        let text = render_text(&state);
+        assert!(text.contains(" B "));
+        assert_eq!(state.key_render_cache.borrow().rebuilds(), 1);
+
+        state.layout.layers[0].keys[2].keycode = "KC_C".to_string();
+        state.mark_dirty();
+        assert!(render_text(&state).contains(" C "));
+        assert_eq!(state.key_render_cache.borrow().rebuilds(), 2);
+    }
+
+    #[test]
+    fn test_split_view_draws_both_layers() {
+        let mut geometry = crate::models::KeyboardGeometry::new("test", "test", 1, 2);
+        let mut layout = Layout::new("Split").unwrap();
+        for (index, (name, keycode)) in [("Base", "KC_A"), ("Num", "KC_1")].into_iter().enumerate()
+        {
+            let mut layer = Layer::new(index as u8, name, RgbColor::new(255, 255, 255)).unwrap();
+            for col in 0..2 {
+                layer
+                    .add_key(KeyDefinition::at(Position::new(0, col)).keycode(keycode))
+                    .unwrap();
+            }
+            layout.add_layer(layer).unwrap();
+        }
+        for col in 0..2 {
+            geometry.add_key(KeyGeometry::new((0, col), col, f32::from(col), 0.0));
+        }
+        let mapping = crate::models::VisualLayoutMapping::build(&geometry);
+        let mut state = AppState::new(
+            layout,
+            None,
+            geometry,
+            mapping,
+            crate::config::Config::default(),
+        )
+        .unwrap();
+        state.toggle_split_view();
+
+        let draw = |state: &AppState, width: u16, height: u16| {
+            let mut terminal =
+                ratatui::Terminal::new(ratatui::backend::TestBackend::new(width, height)).unwrap();
+            terminal
+                .draw(|f| KeyboardWidget::render(f, f.area(), state))
+                .unwrap();
+            let buffer = terminal.backend().buffer().clone();
+            (0..height)
+                .map(|y| {
+                    (0..width)
+                        .map(|x| buffer[(x, y)].symbol().to_string())
+                        .collect::<String>()
+                })
+                .collect::<Vec<_>>()
+        };
+
+        // Side by side: both titles on the first row, the focus on the left
+        let rows = draw(&state, 80, 8);
+        let left_title = rows[0].find("Layer 0: Base [focused]").unwrap();
+        let right_title = rows[0].find("Layer 1: Num").unwrap();
+        assert!(left_title < right_title);
+        assert!(rows
+            .iter()
+            .any(|row| row.contains(" A ") && row.contains(" 1 ")));
+
+        // Stacked when two keyboards don't fit next to each other
+        state.focus_other_pane();
+        let rows = draw(&state, 30, 16);
+        assert!(rows[0].contains("Layer 0: Base"));
+        assert!(!rows[0].contains("[focused]"));
+        assert!(rows[8].contains("Layer 1: Num [focused]"));
+    }
+
+    #[test]
+    fn test_tap_hold_keys_show_tap_and_hold_lines() {
+        let mut geometry = crate::models::KeyboardGeometry::new("test", "test", 1, 2);
+        let mut layer = Layer::new(0, "Base", RgbColor::new(255, 255, 255)).unwrap();
+        for (col, keycode) in [(0, "LT(1, KC_SPC)"), (1, "LCTL_T(KC_A)")] {
+            geometry.add_key(KeyGeometry::new((0, col), col, f32::from(col), 0.0));
+            layer
+                .add_key(KeyDefinition::at(Position::new(0, col)).keycode(keycode))
+                .unwrap();
+        }
+        let mut layout = Layout::new("Legends").unwrap();
+        layout.add_layer(layer).unwrap();
+        let mapping = crate::models::VisualLayoutMapping::build(&geometry);
+        let state = AppState::new(
+            layout,
+            None,
+            geometry,
+            mapping,
+            crate::config::Config::default(),
+        )
+        .unwrap();
+
+        let text = render_text(&state);
+        for legend in ["Space", "L1 hold", "A", "⌃ hold"] {
+            assert!(text.contains(legend), "{legend} missing from\n{text}");
+        }
+        assert!(!text.contains("LT(1"));
+
+        let overlay = KeyboardWidget::base_layer_legends(&state);
+        assert_eq!(overlay[&Position::new(0, 0)], "Spc/L1");
+    }
+
+    #[test]
+    fn test_transparent_key_styles() {
+        let mut geometry = crate::models::KeyboardGeometry::new("test", "test", 1, 2);
+        let mut layer = Layer::new(0, "Base", RgbColor::new(255, 255, 255)).unwrap();
+        for (col, keycode) in [(0, "KC_A"), (1, "KC_TRNS")] {
+            geometry.add_key(KeyGeometry::new((0, col), col, f32::from(col), 0.0));
+            layer
+                .add_key(KeyDefinition::at(Position::new(0, col)).keycode(keycode))
+                .unwrap();
+        }
+        let mut layout = Layout::new("Transparent").unwrap();
+        layout.add_layer(layer).unwrap();
+        let mapping = crate::models::VisualLayoutMapping::build(&geometry);
+        let mut state = AppState::new(
+            layout,
+            None,
+            geometry,
+            mapping,
+            crate::config::Config::default(),
+        )
+        .unwrap();
+
+        assert!(render_text(&state).contains("TRNS"));
+
+        state.config.ui.transparent_keys = TransparentKeyStyle::Dim;
+        let text = render_text(&state);
+        assert!(text.contains('▽') && !text.contains("TRNS"), "{text}");
+        let draw = KeyboardWidget::key_draw(Rect::new(1, 1, 38, 6), &state, 0, 1).unwrap();
+        assert_eq!(draw.fill, Some(state.theme.surface));
+        assert!(draw.muted);
+
+        state.config.ui.transparent_keys = TransparentKeyStyle::Hidden;
+        let text = render_text(&state);
+        assert!(!text.contains('▽') && !text.contains("TRNS"), "{text}");
+        assert!(text.contains(" A "));
+    }
+
+    #[test]
+    fn test_high_contrast_marks_states_with_text() {
+        let mut geometry = crate::models::KeyboardGeometry::new("test", "test", 1, 6);
+        let mut base = Layer::new(0, "Base", RgbColor::new(255, 0, 0)).unwrap();
+        let mut upper = Layer::new(1, "Upper", RgbColor::new(0, 0, 255)).unwrap();
+        for col in 0..6 {
+            geometry.add_key(KeyGeometry::new((0, col), col, f32::from(col), 0.0));
+            base.add_key(KeyDefinition::at(Position::new(0, col)).keycode("KC_A"))
+                .unwrap();
+            // A hold-like reference to the base layer from the last key
+            let keycode = if col == 5 { "LT(0, KC_B)" } else { "KC_B" };
+            upper
+                .add_key(KeyDefinition::at(Position::new(0, col)).keycode(keycode))
+                .unwrap();
+        }
+        let mut layout = Layout::new("Contrast").unwrap();
+        layout.add_layer(base).unwrap();
+        layout.add_layer(upper).unwrap();
+        let mapping = crate::models::VisualLayoutMapping::build(&geometry);
+        let mut state = AppState::new(
+            layout,
+            None,
+            geometry,
+            mapping,
+            crate::config::Config::default(),
+        )
+        .unwrap();
+        state.selected_position = Position::new(0, 0);
+        state.selected_keys = vec![Position::new(0, 1)];
+        state.flash_highlight = Some((0, Position::new(0, 2), 5));
+        state
+            .clipboard
+            .cut("KC_A", None, None, 0, Position::new(0, 3));
+
+        let area = Rect::new(0, 0, 60, 8);
+        let render = |state: &AppState| {
+            let mut terminal =
+                ratatui::Terminal::new(ratatui::backend::TestBackend::new(60, 8)).unwrap();
+            terminal
+                .draw(|f| KeyboardWidget::render(f, f.area(), state))
+                .unwrap();
+            terminal.backend().buffer().clone()
+        };
+        let rects: Vec<Rect> = (0..6)
+            .map(|col| KeyboardWidget::key_rect(area, &state, Position::new(0, col)).unwrap())
+            .collect();
+        let marker_cell = |buffer: &ratatui::buffer::Buffer, col: usize| {
+            buffer[(rects[col].x + 1, rects[col].y)].clone()
+        };
+
+        // Color alone tells the states apart in the dark theme
+        state.theme = crate::tui::Theme::dark();
+        let buffer = render(&state);
+        for col in 0..6 {
+            assert_eq!(marker_cell(&buffer, col).symbol(), "─");
+        }
+
+        state.theme = crate::tui::Theme::high_contrast();
+        let buffer = render(&state);
+        let markers: Vec<_> = (0..6)
+            .map(|col| marker_cell(&buffer, col).symbol().to_string())
+            .collect();
+        assert_eq!(markers, [">", "+", "*", "~", "─", "^"]);
+        assert!(marker_cell(&buffer, 0)
+            .modifier
+            .contains(Modifier::REVERSED));
+
+        // Every cell is black or white, and the selected key's legend is
+        // drawn in inverse video
+        for cell in buffer.content() {
+            for color in [cell.fg, cell.bg] {
+                assert!(
+                    matches!(color, Color::White | Color::Black | Color::Reset),
+                    "{color:?}"
+                );
+            }
+        }
+        let rect = rects[0];
+        let legend = (rect.y..rect.y + rect.height)
+            .flat_map(|y| (rect.x..rect.x + rect.width).map(move |x| (x, y)))
+            .map(|cell| &buffer[cell])
+            .find(|cell| cell.symbol() == "A")
+            .unwrap();
+        assert_eq!((legend.fg, legend.bg), (Color::Black, Color::White));
+    }
+
+    #[test]
+    fn test_diff_mode_marks_changed_keys() {
+        let mut geometry = crate::models::KeyboardGeometry::new("test", "test", 1, 2);
+        let mut layer = Layer::new(0, "Base", RgbColor::new(255, 255, 255)).unwrap();
+        for col in 0..2 {
+            geometry.add_key(KeyGeometry::new((0, col), col, f32::from(col), 0.0));
+            layer
+                .add_key(KeyDefinition::at(Position::new(0, col)).keycode("KC_A"))
+                .unwrap();
+        }
+        let mut layout = Layout::new("Diff").unwrap();
+        layout.add_layer(layer).unwrap();
+        let mapping = crate::models::VisualLayoutMapping::build(&geometry);
+        let mut state = AppState::new(
+            layout,
+            None,
+            geometry,
+            mapping,
+            crate::config::Config::default(),
+        )
+        .unwrap();
+        state.selected_position = Position::new(1, 0);
+        let base = state.layout.clone();
+        state.layout.layers[0].keys[1].keycode = "KC_B".to_string();
+        state.diff_view = Some(crate::tui::DiffView::new(
+            "saved file",
+            &base,
+            &state.layout,
+        ));
+
+        let area = Rect::new(0, 0, 40, 8);
+        let mut terminal =
+            ratatui::Terminal::new(ratatui::backend::TestBackend::new(40, 8)).unwrap();
+        terminal
+            .draw(|f| KeyboardWidget::render(f, f.area(), &state))
+            .unwrap();
+        let buffer = terminal.backend().buffer();
+        let unchanged = KeyboardWidget::key_rect(area, &state, Position::new(0, 0)).unwrap();
+        let changed = KeyboardWidget::key_rect(area, &state, Position::new(0, 1)).unwrap();
+
+        assert_eq!(buffer[(changed.x, changed.y)].symbol(), "╔");
+        let row = |y: u16| {
+            (changed.x + 1..changed.x + changed.width - 1)
+                .map(|x| buffer[(x, y)].symbol())
+                .collect::<String>()
+        };
+        assert!(row(changed.y + 1).starts_with('A'));
+        assert!(row(changed.y + 2).starts_with("→B"));
+
+        assert_eq!(buffer[(unchanged.x, unchanged.y)].symbol(), "┌");
+        assert!(buffer[(unchanged.x, unchanged.y)]
+            .modifier
+            .contains(Modifier::DIM));
+        let title: String = (0..40).map(|x| buffer[(x, 0)].symbol()).collect();
+        assert!(title.contains("[diff vs saved file]"));
+    }
+
+    #[test]
+    fn test_render_encoder_after_last_key() {
+        let mut geometry = crate::models::KeyboardGeometry::new("test", "test", 1, 2);
+        let mut layer = Layer::new(0, "Base", RgbColor::new(255, 255, 255)).unwrap();
+        for col in 0..2 {
+            geometry.add_key(KeyGeometry::new((0, col), col, f32::from(col), 0.0));
+            layer
+                .add_key(KeyDefinition::at(Position::new(0, col)).keycode("KC_A"))
+                .unwrap();
+        }
+        geometry.encoder_count = 1;
+        layer.set_encoder(0, EncoderMapping::new("KC_VOLD", "KC_VOLU"));
+        let mut layout = Layout::new("Encoders").unwrap();
+        layout.add_layer(layer).unwrap();
+        let mapping = crate::models::VisualLayoutMapping::build(&geometry);
+        let state = AppState::new(
+            layout,
+            None,
+            geometry,
+            mapping,
+            crate::config::Config::default(),
+        )
+        .unwrap();
+
+        let text = render_text(&state);
+        assert!(text.contains("E0"));
+        assert!(text.contains("↺VOLD"));
+        assert!(text.contains("↻VOLU"));
     }
 
     #[test]
@@ -12,7 +12,8 @@ use ratatui::{
 };
 
 use super::component::ContextualComponent;
-use crate::keycode_db::KeycodeDb;
+use super::text_input::TextInput;
+use crate::keycode_db::{KeycodeDb, KeycodeDefinition, TapHoldType};
 
 /// Events emitted by the KeycodePicker component
 #[derive(Debug, Clone)]
@@ -35,11 +36,79 @@ pub enum PickerFocus {
     LanguageSelector,
 }
 
+/// How a directly typed keycode relates to the keycode database
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum EntryValidity {
+    /// Nothing typed yet
+    Empty,
+    /// Listed in the database or matching one of its patterns
+    Known,
+    /// Parses as a layer, tap-hold or tap dance keycode
+    Parameterized,
+    /// Not recognized; assigned only after confirmation
+    Custom,
+}
+
+impl EntryValidity {
+    /// Classifies `input` against the database and the parameterized-keycode parsers.
+    #[must_use]
+    pub fn classify(input: &str, db: &KeycodeDb) -> Self {
+        if input.is_empty() {
+            return Self::Empty;
+        }
+        if db.get(input).is_some() {
+            return Self::Known;
+        }
+        // Parse before the pattern check: patterns accept any tap keycode
+        if let Some(info) = db.parse_tap_hold(input) {
+            // The tap part must itself be a valid keycode
+            let tap = match info.tap_hold_type {
+                TapHoldType::LayerTap | TapHoldType::ModTap => info.arg2,
+                TapHoldType::ModTapNamed | TapHoldType::SwapHands => Some(info.arg1),
+                TapHoldType::LayerMod => None,
+            };
+            return if tap.is_none_or(|tap| db.is_valid(&tap)) {
+                Self::Parameterized
+            } else {
+                Self::Custom
+            };
+        }
+        if db.parse_layer_keycode(input).is_some() || db.parse_tap_dance_keycode(input).is_some() {
+            return Self::Parameterized;
+        }
+        if db.is_valid(input) {
+            Self::Known
+        } else {
+            Self::Custom
+        }
+    }
+
+    /// Inline label shown next to the input line
+    #[must_use]
+    pub const fn label(self) -> &'static str {
+        match self {
+            Self::Empty => "",
+            Self::Known => "✓ known keycode",
+            Self::Parameterized => "✓ parameterized keycode",
+            Self::Custom => "? not in keycode database",
+        }
+    }
+}
+
+/// Direct keycode entry (typing the keycode instead of picking it)
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct DirectEntry {
+    /// Keycode typed so far
+    pub input: TextInput,
+    /// Enter was pressed once on an unrecognized keycode
+    pub confirm_custom: bool,
+}
+
 /// Keycode picker state
 #[derive(Debug, Clone)]
 pub struct KeycodePickerState {
     /// Search query string
-    pub search: String,
+    pub search: TextInput,
     /// Selected keycode index in the list
     pub selected: usize,
     /// Current category index (0 = All, last = Languages)
@@ -52,6 +121,8 @@ pub struct KeycodePickerState {
     pub selected_language: Option<String>,
     /// Selected language index in language list
     pub language_list_index: usize,
+    /// Direct entry input line, when active
+    pub direct_entry: Option<DirectEntry>,
 }
 
 impl Default for KeycodePickerState {
@@ -63,15 +134,16 @@ impl Default for KeycodePickerState {
 impl KeycodePickerState {
     /// Creates a new keycode picker state
     #[must_use]
-    pub const fn new() -> Self {
+    pub fn new() -> Self {
         Self {
-            search: String::new(),
+            search: TextInput::default(),
             selected: 0,
             category_index: 0,
             focus: PickerFocus::Keycodes,
             sidebar_scroll: 0,
             selected_language: None,
             language_list_index: 0,
+            direct_entry: None,
         }
     }
 
@@ -93,13 +165,14 @@ impl KeycodePickerState {
                 let languages_category_index = keycode_db.categories().len() + 1;
 
                 Self {
-                    search: String::new(),
+                    search: TextInput::default(),
                     selected: 0,
                     category_index: languages_category_index,
                     focus: PickerFocus::Keycodes,
                     sidebar_scroll: 0,
                     selected_language: Some(lang_id.clone()),
                     language_list_index,
+                    direct_entry: None,
                 }
             }
             None => Self::new(),
@@ -115,6 +188,7 @@ impl KeycodePickerState {
         self.sidebar_scroll = 0;
         self.selected_language = None;
         self.language_list_index = 0;
+        self.direct_entry = None;
     }
 }
 
@@ -169,6 +243,10 @@ impl ContextualComponent for KeycodePicker {
     ) -> Option<Self::Event> {
         let total_categories = context.categories().len() + 2; // +1 for "All", +1 for "Languages"
 
+        if self.state.direct_entry.is_some() {
+            return self.handle_direct_entry_input(key, context);
+        }
+
         match self.state.focus {
             PickerFocus::Sidebar => self.handle_sidebar_input(key, total_categories, context),
             PickerFocus::Keycodes => self.handle_keycodes_input(key, context),
@@ -309,6 +387,56 @@ impl KeycodePicker {
         }
     }
 
+    /// Handle input while the direct entry line is open
+    fn handle_direct_entry_input(
+        &mut self,
+        key: event::KeyEvent,
+        context: &KeycodeDb,
+    ) -> Option<KeycodePickerEvent> {
+        let entry = self.state.direct_entry.as_mut()?;
+        match key.code {
+            KeyCode::Esc => {
+                // Back to the list, keeping the picker open
+                self.state.direct_entry = None;
+                None
+            }
+            KeyCode::Tab => {
+                if let Some(completed) = complete_entry(entry.input.value(), context) {
+                    entry.input.set_value(completed);
+                    entry.confirm_custom = false;
+                }
+                None
+            }
+            KeyCode::Enter => {
+                let validity = EntryValidity::classify(entry.input.value(), context);
+                match validity {
+                    EntryValidity::Empty => None,
+                    EntryValidity::Custom if !entry.confirm_custom => {
+                        entry.confirm_custom = true;
+                        None
+                    }
+                    _ => {
+                        let keycode = entry.input.value().to_string();
+                        self.state.reset();
+                        Some(KeycodePickerEvent::KeycodeSelected(keycode))
+                    }
+                }
+            }
+            KeyCode::Backspace if entry.input.is_empty() => {
+                self.state.direct_entry = None;
+                None
+            }
+            // Spaces only make sense inside parameters, e.g. `LT(1, KC_A)`
+            KeyCode::Char(c) if c.is_whitespace() && !entry.input.value().contains('(') => None,
+            _ => {
+                if entry.input.handle_key(key) {
+                    entry.confirm_custom = false;
+                }
+                None
+            }
+        }
+    }
+
     /// Handle input when keycodes list has focus
     fn handle_keycodes_input(
         &mut self,
@@ -388,16 +516,24 @@ impl KeycodePicker {
                     (self.state.selected + 10).min(keycodes.len().saturating_sub(1));
                 None
             }
-            KeyCode::Char(c) => {
-                // Add to search (includes j, k, h, l when search is active)
-                self.state.search.push(c);
-                self.state.selected = 0; // Reset selection on new search
+            // Direct entry: ':' or an uppercase letter before any search text
+            KeyCode::Char(':') => {
+                self.state.direct_entry = Some(DirectEntry::default());
+                None
+            }
+            KeyCode::Char(c) if c.is_ascii_uppercase() && self.state.search.is_empty() => {
+                self.state.direct_entry = Some(DirectEntry {
+                    input: TextInput::from(c.to_string()),
+                    confirm_custom: false,
+                });
                 None
             }
-            KeyCode::Backspace => {
-                // Remove from search
-                self.state.search.pop();
-                self.state.selected = 0; // Reset selection
+            // Typing edits the search (includes j, k, h, l when search is
+            // active); the cursor stays at the end since the arrows navigate
+            KeyCode::Char(_) | KeyCode::Backspace | KeyCode::Delete => {
+                if self.state.search.handle_key(key) {
+                    self.state.selected = 0; // Reset selection on new search
+                }
                 None
             }
             _ => None,
@@ -418,7 +554,7 @@ pub fn get_filtered_keycodes_from_context<'a>(
     if category_index == languages_index {
         // Return language-specific keycodes if a language is selected
         if let Some(ref lang_id) = picker_state.selected_language {
-            return context.search_in_language(&picker_state.search, lang_id);
+            return context.search_in_language(picker_state.search.value(), lang_id);
         }
         // No language selected yet, return empty
         return Vec::new();
@@ -431,10 +567,53 @@ pub fn get_filtered_keycodes_from_context<'a>(
     };
 
     if let Some(cat_id) = active_category {
-        context.search_in_category(&picker_state.search, cat_id)
+        context.search_in_category(picker_state.search.value(), cat_id)
+    } else {
+        context.search(picker_state.search.value())
+    }
+}
+
+/// Database keycodes that `input` could be completed to.
+///
+/// Matches codes starting with `input`, or with `KC_` + `input` when nothing
+/// else matches, so `ENT` completes to `KC_ENT`.
+#[must_use]
+pub fn entry_completions<'a>(input: &str, context: &'a KeycodeDb) -> Vec<&'a KeycodeDefinition> {
+    if input.is_empty() {
+        return Vec::new();
+    }
+    let all = context.search("");
+    let matching = |prefix: &str| -> Vec<&'a KeycodeDefinition> {
+        all.iter()
+            .copied()
+            .filter(|kc| kc.code.starts_with(prefix))
+            .collect()
+    };
+    let direct = matching(input);
+    if direct.is_empty() && !input.starts_with("KC_") {
+        matching(&format!("KC_{input}"))
     } else {
-        context.search(&picker_state.search)
+        direct
+    }
+}
+
+/// Tab completion: extends `input` to the longest prefix shared by its completions.
+///
+/// Returns `None` when there is nothing to add.
+#[must_use]
+pub fn complete_entry(input: &str, context: &KeycodeDb) -> Option<String> {
+    let completions = entry_completions(input, context);
+    let (first, rest) = completions.split_first()?;
+    let mut common = first.code.as_str();
+    for kc in rest {
+        let shared = common
+            .char_indices()
+            .zip(kc.code.chars())
+            .find(|((_, a), b)| a != b)
+            .map_or(common.len().min(kc.code.len()), |((i, _), _)| i);
+        common = &common[..shared];
     }
+    (common.len() > input.len() && common != input).then(|| common.to_string())
 }
 
 /// Render the keycode picker popup using the Component
@@ -498,6 +677,11 @@ fn render_keycode_picker_internal(
     let languages_index = categories.len() + 1;
     let is_languages_mode = category_index == languages_index;
 
+    if let Some(entry) = &picker_state.direct_entry {
+        render_direct_entry(f, &content_chunks, entry, context, theme);
+        return;
+    }
+
     // Search box (hide when in language selector mode without language selected)
     let show_search = !is_languages_mode || picker_state.selected_language.is_some();
     if show_search {
@@ -507,19 +691,15 @@ fn render_keycode_picker_internal(
             theme.surface
         };
 
-        let search_text = vec![Line::from(vec![
-            Span::styled(" Search: ", Style::default().fg(theme.text_muted)),
-            Span::styled(
-                &picker_state.search,
-                Style::default().fg(theme.text).add_modifier(Modifier::BOLD),
-            ),
-            Span::styled(
-                "_",
-                Style::default()
-                    .fg(theme.primary)
-                    .add_modifier(Modifier::SLOW_BLINK),
-            ),
-        ])];
+        let mut search_spans = vec![Span::styled(
+            " Search: ",
+            Style::default().fg(theme.text_muted),
+        )];
+        search_spans.extend(picker_state.search.spans(
+            Style::default().fg(theme.text).add_modifier(Modifier::BOLD),
+            focus == PickerFocus::Keycodes,
+        ));
+        let search_text = vec![Line::from(search_spans)];
         let search = Paragraph::new(search_text).block(
             Block::default()
                 .borders(Borders::ALL)
@@ -595,7 +775,7 @@ fn render_keycode_picker_internal(
     let keycodes = if is_languages_mode {
         // Language-specific keycodes
         if let Some(ref lang_id) = picker_state.selected_language {
-            context.search_in_language(&picker_state.search, lang_id)
+            context.search_in_language(picker_state.search.value(), lang_id)
         } else {
             Vec::new()
         }
@@ -607,9 +787,9 @@ fn render_keycode_picker_internal(
         };
 
         if let Some(cat_id) = active_category {
-            context.search_in_category(&picker_state.search, cat_id)
+            context.search_in_category(picker_state.search.value(), cat_id)
         } else {
-            context.search(&picker_state.search)
+            context.search(picker_state.search.value())
         }
     };
 
@@ -669,10 +849,12 @@ fn render_keycode_picker_internal(
                 .style(Style::default().bg(theme.background)),
         )
         .highlight_style(
-            Style::default()
-                .bg(theme.surface)
-                .fg(theme.text)
-                .add_modifier(Modifier::BOLD),
+            theme.selection(
+                Style::default()
+                    .bg(theme.surface)
+                    .fg(theme.text)
+                    .add_modifier(Modifier::BOLD),
+            ),
         )
         .highlight_symbol("► ");
 
@@ -762,6 +944,13 @@ fn render_keycode_picker_internal(
                     .add_modifier(Modifier::BOLD),
             ),
             Span::raw(" Search"),
+            Span::styled(
+                "  :",
+                Style::default()
+                    .fg(theme.accent)
+                    .add_modifier(Modifier::BOLD),
+            ),
+            Span::raw(" Type keycode"),
         ]
     };
     let help = Paragraph::new(Line::from(help_spans))
@@ -770,6 +959,99 @@ fn render_keycode_picker_internal(
     f.render_widget(help, content_chunks[2]);
 }
 
+/// Render the direct entry line, its completions and help
+fn render_direct_entry(
+    f: &mut Frame,
+    content_chunks: &[Rect],
+    entry: &DirectEntry,
+    context: &KeycodeDb,
+    theme: &super::Theme,
+) {
+    let validity = EntryValidity::classify(entry.input.value(), context);
+    let validity_color = match validity {
+        EntryValidity::Known | EntryValidity::Parameterized => theme.success,
+        EntryValidity::Custom => theme.warning,
+        EntryValidity::Empty => theme.text_muted,
+    };
+    let label = if entry.confirm_custom {
+        "? not in keycode database - Enter again to assign anyway"
+    } else {
+        validity.label()
+    };
+
+    let mut input_spans = vec![Span::styled(
+        " Keycode: ",
+        Style::default().fg(theme.text_muted),
+    )];
+    input_spans.extend(entry.input.spans(
+        Style::default().fg(theme.text).add_modifier(Modifier::BOLD),
+        true,
+    ));
+    input_spans.push(Span::raw("  "));
+    input_spans.push(Span::styled(label, Style::default().fg(validity_color)));
+    let input_line = Line::from(input_spans);
+    let input = Paragraph::new(input_line).block(
+        Block::default()
+            .title(" Direct Entry ")
+            .borders(Borders::ALL)
+            .border_style(Style::default().fg(theme.primary))
+            .style(Style::default().bg(theme.background)),
+    );
+    f.render_widget(input, content_chunks[0]);
+
+    let completions = entry_completions(entry.input.value(), context);
+    let list_items: Vec<ListItem> = completions
+        .iter()
+        .map(|keycode| {
+            ListItem::new(Line::from(vec![
+                Span::styled(
+                    format!("{:16}", keycode.code),
+                    Style::default()
+                        .fg(theme.primary)
+                        .add_modifier(Modifier::BOLD),
+                ),
+                Span::styled(&keycode.name, Style::default().fg(theme.text)),
+            ]))
+        })
+        .collect();
+    let list = List::new(list_items).block(
+        Block::default()
+            .title(format!(" Completions ({}) ", completions.len()))
+            .borders(Borders::ALL)
+            .border_style(Style::default().fg(theme.surface))
+            .style(Style::default().bg(theme.background)),
+    );
+    f.render_widget(list, content_chunks[1]);
+
+    let help_spans = vec![
+        Span::styled(
+            "Tab",
+            Style::default()
+                .fg(theme.primary)
+                .add_modifier(Modifier::BOLD),
+        ),
+        Span::raw(" Complete  "),
+        Span::styled(
+            "Enter",
+            Style::default()
+                .fg(theme.success)
+                .add_modifier(Modifier::BOLD),
+        ),
+        Span::raw(" Assign  "),
+        Span::styled(
+            "Esc",
+            Style::default()
+                .fg(theme.error)
+                .add_modifier(Modifier::BOLD),
+        ),
+        Span::raw(" Back to list"),
+    ];
+    let help = Paragraph::new(Line::from(help_spans))
+        .style(Style::default().fg(theme.text_muted))
+        .block(Block::default().style(Style::default().bg(theme.background)));
+    f.render_widget(help, content_chunks[2]);
+}
+
 /// Render the language selector list
 fn render_language_selector(
     f: &mut Frame,
@@ -816,10 +1098,12 @@ fn render_language_selector(
                 .style(Style::default().bg(theme.background)),
         )
         .highlight_style(
-            Style::default()
-                .bg(theme.surface)
-                .fg(theme.text)
-                .add_modifier(Modifier::BOLD),
+            theme.selection(
+                Style::default()
+                    .bg(theme.surface)
+                    .fg(theme.text)
+                    .add_modifier(Modifier::BOLD),
+            ),
         )
         .highlight_symbol("► ");
 
@@ -941,3 +1225,84 @@ fn centered_rect(percent_x: u16, percent_y: u16, r: Rect) -> Rect {
         ])
         .split(popup_layout[1])[1]
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crossterm::event::KeyEvent;
+
+    fn press(
+        picker: &mut KeycodePicker,
+        db: &KeycodeDb,
+        code: KeyCode,
+    ) -> Option<KeycodePickerEvent> {
+        picker.handle_input(KeyEvent::new(code, KeyModifiers::NONE), db)
+    }
+
+    fn type_text(picker: &mut KeycodePicker, db: &KeycodeDb, text: &str) {
+        for c in text.chars() {
+            press(picker, db, KeyCode::Char(c));
+        }
+    }
+
+    #[test]
+    fn test_classify_direct_entry() {
+        let db = KeycodeDb::load().unwrap();
+        assert_eq!(EntryValidity::classify("", &db), EntryValidity::Empty);
+        assert_eq!(EntryValidity::classify("KC_A", &db), EntryValidity::Known);
+        assert_eq!(
+            EntryValidity::classify("LCTL(KC_C)", &db),
+            EntryValidity::Known
+        );
+        assert_eq!(
+            EntryValidity::classify("LT(@nav, KC_SPC)", &db),
+            EntryValidity::Parameterized
+        );
+        assert_eq!(
+            EntryValidity::classify("LT(@nav, KC_NOPE)", &db),
+            EntryValidity::Custom
+        );
+        assert_eq!(
+            EntryValidity::classify("MY_MACRO", &db),
+            EntryValidity::Custom
+        );
+    }
+
+    #[test]
+    fn test_tab_completes_with_kc_prefix() {
+        let db = KeycodeDb::load().unwrap();
+        assert_eq!(complete_entry("BSP", &db).as_deref(), Some("KC_BSPC"));
+        assert_eq!(complete_entry("KC_BSPC", &db), None);
+        assert_eq!(complete_entry("ZZZ_NOTHING", &db), None);
+    }
+
+    #[test]
+    fn test_direct_entry_assigns_known_and_confirmed_custom_codes() {
+        let db = KeycodeDb::load().unwrap();
+        let mut picker = KeycodePicker::new();
+
+        // Uppercase typing with an empty search opens direct entry
+        type_text(&mut picker, &db, "KC_B");
+        assert!(picker.state().search.is_empty());
+        match press(&mut picker, &db, KeyCode::Enter) {
+            Some(KeycodePickerEvent::KeycodeSelected(code)) => assert_eq!(code, "KC_B"),
+            other => panic!("expected selection, got {other:?}"),
+        }
+        assert!(picker.state().direct_entry.is_none());
+
+        // Unknown codes need a second Enter
+        press(&mut picker, &db, KeyCode::Char(':'));
+        type_text(&mut picker, &db, "MY_MACRO");
+        assert!(press(&mut picker, &db, KeyCode::Enter).is_none());
+        assert!(picker.state().direct_entry.as_ref().unwrap().confirm_custom);
+        assert!(matches!(
+            press(&mut picker, &db, KeyCode::Enter),
+            Some(KeycodePickerEvent::KeycodeSelected(code)) if code == "MY_MACRO"
+        ));
+
+        // Esc leaves direct entry without closing the picker
+        press(&mut picker, &db, KeyCode::Char(':'));
+        assert!(press(&mut picker, &db, KeyCode::Esc).is_none());
+        assert!(picker.state().direct_entry.is_none());
+    }
+}
@@ -0,0 +1,364 @@
+//! Keycode search popup listing the keys on every layer that match a query.
+//!
+//! The query is matched case-insensitively against the raw keycode, the key's
+//! label, its display name from the [`KeycodeDb`] and, for `TD()` keys, the
+//! tap dance name. Selecting a match with Enter jumps to that layer and key.
+
+use ratatui::{
+    layout::{Constraint, Direction, Layout as RatatuiLayout, Rect},
+    style::{Modifier, Style},
+    text::{Line, Span},
+    widgets::{Block, Borders, Clear, List, ListItem, ListState},
+    Frame,
+};
+
+use crate::keycode_db::KeycodeDb;
+use crate::models::{Layout, Position};
+use crate::tui::text_input::TextInput;
+
+/// Events emitted by the KeycodeSearchView component
+#[derive(Debug, Clone)]
+pub enum KeycodeSearchEvent {
+    /// User selected a match to jump to
+    JumpTo {
+        /// Layer index
+        layer: usize,
+        /// Key position
+        position: Position,
+    },
+    /// User closed the search
+    Closed,
+}
+
+/// A key that can be found by the search.
+#[derive(Debug, Clone)]
+pub struct KeycodeMatch {
+    /// Layer index
+    pub layer: usize,
+    /// Key position
+    pub position: Position,
+    /// Raw QMK keycode
+    pub keycode: String,
+    /// Display name from the keycode database, if it differs from the code
+    pub name: Option<String>,
+    /// Lowercased strings the query is matched against
+    haystack: Vec<String>,
+}
+
+impl KeycodeMatch {
+    /// Formats the match as "Layer 2 (1,5) KC_PSCR".
+    #[must_use]
+    pub fn location(&self) -> String {
+        format!(
+            "Layer {} ({},{}) {}",
+            self.layer, self.position.row, self.position.col, self.keycode
+        )
+    }
+
+    fn matches(&self, query: &str) -> bool {
+        self.haystack.iter().any(|text| text.contains(query))
+    }
+}
+
+/// KeycodeSearchView component that implements the Component trait
+#[derive(Debug, Clone)]
+pub struct KeycodeSearchView {
+    /// Search query
+    query: TextInput,
+    /// Every key of the layout, in layer then position order
+    keys: Vec<KeycodeMatch>,
+    /// Indices into `keys` matching the query
+    matches: Vec<usize>,
+    /// Currently selected match index
+    selected: usize,
+}
+
+impl KeycodeSearchView {
+    /// Create a new KeycodeSearchView over every layer of `layout`.
+    #[must_use]
+    pub fn new(layout: &Layout, keycode_db: &KeycodeDb) -> Self {
+        let mut keys = Vec::new();
+        for (layer_idx, layer) in layout.layers.iter().enumerate() {
+            let mut layer_keys: Vec<_> = layer.keys.iter().collect();
+            layer_keys.sort_by_key(|key| (key.position.row, key.position.col));
+            for key in layer_keys {
+                let name = keycode_db
+                    .get(&key.keycode)
+                    .map(|def| def.name.clone())
+                    .filter(|name| *name != key.keycode);
+                let haystack = [
+                    Some(key.keycode.clone()),
+                    key.label.clone(),
+                    name.clone(),
+                    keycode_db.parse_tap_dance_keycode(&key.keycode),
+                ]
+                .into_iter()
+                .flatten()
+                .map(|text| text.to_lowercase())
+                .collect();
+                keys.push(KeycodeMatch {
+                    layer: layer_idx,
+                    position: key.position,
+                    keycode: key.keycode.clone(),
+                    name,
+                    haystack,
+                });
+            }
+        }
+
+        Self {
+            query: TextInput::default(),
+            keys,
+            matches: Vec::new(),
+            selected: 0,
+        }
+    }
+
+    /// Gets the keys matching the current query.
+    pub fn matches(&self) -> impl Iterator<Item = &KeycodeMatch> {
+        self.matches.iter().map(|&idx| &self.keys[idx])
+    }
+
+    /// Gets the currently selected match (if any).
+    #[must_use]
+    pub fn selected_match(&self) -> Option<&KeycodeMatch> {
+        self.matches.get(self.selected).map(|&idx| &self.keys[idx])
+    }
+
+    /// Recomputes the matches for the current query.
+    fn refilter(&mut self) {
+        let query = self.query.value().trim().to_lowercase();
+        self.matches = if query.is_empty() {
+            Vec::new()
+        } else {
+            (0..self.keys.len())
+                .filter(|&idx| self.keys[idx].matches(&query))
+                .collect()
+        };
+        self.selected = 0;
+    }
+
+    /// Moves selection up.
+    const fn select_previous(&mut self) {
+        self.selected = self.selected.saturating_sub(1);
+    }
+
+    /// Moves selection down.
+    const fn select_next(&mut self) {
+        if self.selected + 1 < self.matches.len() {
+            self.selected += 1;
+        }
+    }
+}
+
+impl crate::tui::component::Component for KeycodeSearchView {
+    type Event = KeycodeSearchEvent;
+
+    fn handle_input(&mut self, key: crossterm::event::KeyEvent) -> Option<Self::Event> {
+        use crossterm::event::KeyCode;
+
+        match key.code {
+            KeyCode::Esc => Some(KeycodeSearchEvent::Closed),
+            KeyCode::Enter => self
+                .selected_match()
+                .map(|found| KeycodeSearchEvent::JumpTo {
+                    layer: found.layer,
+                    position: found.position,
+                }),
+            KeyCode::Up => {
+                self.select_previous();
+                None
+            }
+            KeyCode::Down => {
+                self.select_next();
+                None
+            }
+            _ => {
+                if self.query.handle_key(key) {
+                    self.refilter();
+                }
+                None
+            }
+        }
+    }
+
+    fn render(&self, f: &mut Frame, area: Rect, theme: &crate::tui::theme::Theme) {
+        render_keycode_search(f, self, area, theme);
+    }
+}
+
+/// Renders the keycode search popup
+fn render_keycode_search(
+    f: &mut Frame,
+    view: &KeycodeSearchView,
+    area: Rect,
+    theme: &crate::tui::theme::Theme,
+) {
+    let popup_area = centered_rect(60, 60, area);
+
+    f.render_widget(Clear, popup_area);
+    let background = Block::default().style(Style::default().bg(theme.background));
+    f.render_widget(background, popup_area);
+
+    let chunks = RatatuiLayout::default()
+        .direction(Direction::Vertical)
+        .constraints([
+            Constraint::Length(3), // Query
+            Constraint::Min(3),    // Matches
+        ])
+        .split(popup_area);
+
+    view.query.render(
+        f,
+        chunks[0],
+        Block::default()
+            .borders(Borders::ALL)
+            .title(" Search keycode, label or tap dance ")
+            .style(Style::default().fg(theme.primary)),
+        Style::default().fg(theme.text),
+        true,
+    );
+
+    let items: Vec<ListItem> = if view.matches.is_empty() {
+        let message = if view.query.value().trim().is_empty() {
+            "Type to search every layer"
+        } else {
+            "No matching keys"
+        };
+        vec![ListItem::new(Line::from(Span::styled(
+            message,
+            Style::default().fg(theme.text_muted),
+        )))]
+    } else {
+        view.matches()
+            .map(|found| {
+                let mut spans = vec![Span::styled(
+                    found.location(),
+                    Style::default().fg(theme.text),
+                )];
+                if let Some(name) = &found.name {
+                    spans.push(Span::styled(
+                        format!("  {name}"),
+                        Style::default().fg(theme.text_muted),
+                    ));
+                }
+                ListItem::new(Line::from(spans))
+            })
+            .collect()
+    };
+
+    let list = List::new(items)
+        .block(
+            Block::default()
+                .borders(Borders::ALL)
+                .title(format!(" Matches ({}) ", view.matches.len()))
+                .style(Style::default().fg(theme.primary)),
+        )
+        .highlight_style(
+            theme.selection(
+                Style::default()
+                    .fg(theme.background)
+                    .bg(theme.primary)
+                    .add_modifier(Modifier::BOLD),
+            ),
+        );
+    let mut list_state = ListState::default();
+    if !view.matches.is_empty() {
+        list_state.select(Some(view.selected));
+    }
+    f.render_stateful_widget(list, chunks[1], &mut list_state);
+}
+
+/// Helper to create a centered rectangle
+fn centered_rect(percent_x: u16, percent_y: u16, r: Rect) -> Rect {
+    let popup_layout = RatatuiLayout::default()
+        .direction(Direction::Vertical)
+        .constraints([
+            Constraint::Percentage((100 - percent_y) / 2),
+            Constraint::Percentage(percent_y),
+            Constraint::Percentage((100 - percent_y) / 2),
+        ])
+        .split(r);
+
+    RatatuiLayout::default()
+        .direction(Direction::Horizontal)
+        .constraints([
+            Constraint::Percentage((100 - percent_x) / 2),
+            Constraint::Percentage(percent_x),
+            Constraint::Percentage((100 - percent_x) / 2),
+        ])
+        .split(popup_layout[1])[1]
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::models::{KeyDefinition, Layer, RgbColor};
+    use crate::tui::component::Component;
+    use crossterm::event::{KeyCode, KeyEvent, KeyModifiers};
+
+    fn layout() -> Layout {
+        let mut layout = Layout::new("Search").unwrap();
+        let mut base = Layer::new(0, "Base", RgbColor::new(0, 0, 0)).unwrap();
+        base.add_key(KeyDefinition::new(Position::new(0, 0), "KC_A"))
+            .unwrap();
+        base.add_key(KeyDefinition::new(Position::new(0, 1), "TD(esc_caps)"))
+            .unwrap();
+        let mut fn_layer = Layer::new(1, "Fn", RgbColor::new(0, 0, 0)).unwrap();
+        fn_layer
+            .add_key(KeyDefinition::new(Position::new(1, 5), "KC_PSCR"))
+            .unwrap();
+        let mut labelled = KeyDefinition::new(Position::new(2, 0), "KC_F13");
+        labelled.label = Some("Screenshot".to_string());
+        fn_layer.add_key(labelled).unwrap();
+        layout.add_layer(base).unwrap();
+        layout.add_layer(fn_layer).unwrap();
+        layout
+    }
+
+    fn type_query(view: &mut KeycodeSearchView, query: &str) {
+        for c in query.chars() {
+            view.handle_input(KeyEvent::new(KeyCode::Char(c), KeyModifiers::NONE));
+        }
+    }
+
+    fn search(query: &str) -> Vec<String> {
+        let db = KeycodeDb::load().unwrap();
+        let mut view = KeycodeSearchView::new(&layout(), &db);
+        type_query(&mut view, query);
+        view.matches().map(KeycodeMatch::location).collect()
+    }
+
+    #[test]
+    fn test_matches_keycode_substring_case_insensitively() {
+        assert_eq!(search("pscr"), vec!["Layer 1 (1,5) KC_PSCR"]);
+        assert!(search("").is_empty());
+    }
+
+    #[test]
+    fn test_matches_labels_and_tap_dance_names() {
+        assert_eq!(search("screenshot"), vec!["Layer 1 (2,0) KC_F13"]);
+        assert_eq!(search("esc_caps"), vec!["Layer 0 (0,1) TD(esc_caps)"]);
+    }
+
+    #[test]
+    fn test_matches_database_display_names() {
+        let db = KeycodeDb::load().unwrap();
+        let name = db.get("KC_PSCR").unwrap().name.to_lowercase();
+        assert!(search(&name).contains(&"Layer 1 (1,5) KC_PSCR".to_string()));
+    }
+
+    #[test]
+    fn test_enter_jumps_to_selected_match() {
+        let db = KeycodeDb::load().unwrap();
+        let mut view = KeycodeSearchView::new(&layout(), &db);
+        type_query(&mut view, "kc_");
+        view.handle_input(KeyEvent::new(KeyCode::Down, KeyModifiers::NONE));
+        match view.handle_input(KeyEvent::new(KeyCode::Enter, KeyModifiers::NONE)) {
+            Some(KeycodeSearchEvent::JumpTo { layer, position }) => {
+                assert_eq!((layer, position), (1, Position::new(1, 5)));
+            }
+            other => panic!("expected jump, got {other:?}"),
+        }
+    }
+}
@@ -1,8 +1,15 @@
 //! Layer manager for CRUD operations on layers.
 //!
-//! Provides a UI for creating, renaming, reordering, toggling colors, and deleting layers.
+//! Provides a UI for creating, renaming, describing, reordering, toggling colors, and deleting layers.
 //! Accessible via Shift+Y shortcut.
+//!
+//! Renamed layers keep their ID, so `@id` references still point at them; names are trimmed and
+//! an empty one keeps the prompt open. Moving a layer previews the new order while the keyboard
+//! shows the moving layer, and nothing changes until Enter applies the whole move, one undo step.
+//! Moves and deletions rewrite the layer references on keys, encoders and tap dances (see
+//! [`layer_refs`]); the delete prompt lists the references it will clear.
 
+use chrono::Local;
 use crossterm::event::{KeyCode, KeyEvent, KeyModifiers};
 use ratatui::{
     layout::{Alignment, Constraint, Rect},
@@ -12,8 +19,11 @@ use ratatui::{
     Frame,
 };
 
-use crate::models::{Layer, Position, RgbColor};
+use crate::models::{Layer, Position, RgbColor, TapDanceAction};
+use crate::services::layer_refs;
 use crate::tui::component::Component;
+use crate::tui::text_editor::{TextEditor, TextEditorEvent};
+use crate::tui::text_input::TextInput;
 use crate::tui::Theme;
 
 /// Events emitted by the LayerManager component
@@ -21,7 +31,8 @@ use crate::tui::Theme;
 pub enum LayerManagerEvent {
     /// User created a new layer
     LayerAdded {
-        /// The new layer
+        /// The new layer, without keys (the handler adds a `KC_TRNS` key at
+        /// every position of the keyboard)
         layer: Layer,
     },
     /// User deleted a layer
@@ -36,7 +47,14 @@ pub enum LayerManagerEvent {
         /// New name
         name: String,
     },
-    /// User reordered layers (swap)
+    /// User edited a layer's description
+    LayerDescriptionChanged {
+        /// Index of the described layer
+        index: usize,
+        /// New description (None if cleared)
+        description: Option<String>,
+    },
+    /// User moved a layer to a new position
     LayerReordered {
         /// Original index
         from: usize,
@@ -77,6 +95,13 @@ pub enum LayerManagerEvent {
         /// New enabled state
         enabled: bool,
     },
+    /// User toggled whether a layer is compiled into firmware
+    LayerEnabledToggled {
+        /// Index of the layer
+        index: usize,
+        /// New enabled state
+        enabled: bool,
+    },
     /// User wants to switch to a layer
     LayerSwitched {
         /// Index of the layer to switch to
@@ -96,14 +121,21 @@ pub enum ManagerMode {
     /// Creating a new layer (entering name)
     CreatingName {
         /// User input for layer name
-        input: String,
+        input: TextInput,
     },
     /// Renaming a layer
     Renaming {
         /// Index of layer being renamed
         layer_index: usize,
         /// User input for new name
-        input: String,
+        input: TextInput,
+    },
+    /// Editing a layer's description
+    EditingDescription {
+        /// Index of layer being described
+        layer_index: usize,
+        /// Multi-line editor for the description
+        editor: TextEditor,
     },
     /// Confirming deletion
     ConfirmingDelete {
@@ -115,7 +147,7 @@ pub enum ManagerMode {
         /// Index of layer being duplicated
         source_index: usize,
         /// User input for new layer name
-        input: String,
+        input: TextInput,
     },
     /// Copying all keys to another layer (selecting target)
     CopyingTo {
@@ -131,6 +163,11 @@ pub enum ManagerMode {
         /// Currently selected swap target
         target_selected: usize,
     },
+    /// Moving a layer, previewing the new order (`selected` is where it lands)
+    Reordering {
+        /// Index of the layer being moved
+        from: usize,
+    },
 }
 
 /// State for the layer manager dialog
@@ -179,7 +216,7 @@ impl LayerManagerState {
     /// Start creating a new layer
     pub fn start_creating(&mut self) {
         self.mode = ManagerMode::CreatingName {
-            input: String::new(),
+            input: TextInput::default(),
         };
     }
 
@@ -187,7 +224,18 @@ impl LayerManagerState {
     pub fn start_renaming(&mut self, layer: &Layer) {
         self.mode = ManagerMode::Renaming {
             layer_index: self.selected,
-            input: layer.name.clone(),
+            input: TextInput::new(layer.name.clone()),
+        };
+    }
+
+    /// Start editing the description of the selected layer
+    pub fn start_describing(&mut self, layer: &Layer) {
+        self.mode = ManagerMode::EditingDescription {
+            layer_index: self.selected,
+            editor: TextEditor::new(
+                format!("Describe Layer: {}", layer.name),
+                layer.description.as_deref().unwrap_or_default(),
+            ),
         };
     }
 
@@ -202,7 +250,7 @@ impl LayerManagerState {
     pub fn start_duplicating(&mut self, layer: &Layer) {
         self.mode = ManagerMode::Duplicating {
             source_index: self.selected,
-            input: format!("{} (copy)", layer.name),
+            input: TextInput::new(format!("{} (copy)", layer.name)),
         };
     }
 
@@ -238,6 +286,13 @@ impl LayerManagerState {
         };
     }
 
+    /// Start moving the selected layer
+    pub fn start_reordering(&mut self) {
+        self.mode = ManagerMode::Reordering {
+            from: self.selected,
+        };
+    }
+
     /// Navigate in copy-to or swap mode
     pub const fn select_target_previous(&mut self, layer_count: usize) {
         match &mut self.mode {
@@ -299,13 +354,13 @@ impl LayerManagerState {
         match &self.mode {
             ManagerMode::CreatingName { input }
             | ManagerMode::Renaming { input, .. }
-            | ManagerMode::Duplicating { input, .. } => Some(input),
+            | ManagerMode::Duplicating { input, .. } => Some(input.value()),
             _ => None,
         }
     }
 
-    /// Get mutable reference to current input text
-    pub const fn get_input_mut(&mut self) -> Option<&mut String> {
+    /// Get mutable reference to current input field
+    pub const fn get_input_mut(&mut self) -> Option<&mut TextInput> {
         match &mut self.mode {
             ManagerMode::CreatingName { input }
             | ManagerMode::Renaming { input, .. }
@@ -328,17 +383,20 @@ pub struct LayerManager {
     state: LayerManagerState,
     /// Layers to display and modify (cached copy)
     cached_layers: Vec<Layer>,
+    /// Tap dances, which may also switch layers (cached copy)
+    cached_tap_dances: Vec<TapDanceAction>,
 }
 
 impl LayerManager {
-    /// Create a new LayerManager with initial layers
+    /// Create a new LayerManager with initial layers and tap dances
     #[must_use]
-    pub fn new(layers: Vec<Layer>, current_layer: usize) -> Self {
+    pub fn new(layers: Vec<Layer>, tap_dances: Vec<TapDanceAction>, current_layer: usize) -> Self {
         let mut state = LayerManagerState::new();
         state.reset(current_layer);
         Self {
             state,
             cached_layers: layers,
+            cached_tap_dances: tap_dances,
         }
     }
 
@@ -350,6 +408,25 @@ impl LayerManager {
             self.state.selected = self.cached_layers.len() - 1;
         }
     }
+
+    /// Update the tap dances (needed after moving or deleting layers)
+    pub fn set_tap_dances(&mut self, tap_dances: Vec<TapDanceAction>) {
+        self.cached_tap_dances = tap_dances;
+    }
+
+    /// Index of the layer being moved while previewing a new order
+    #[must_use]
+    pub const fn moving_layer(&self) -> Option<usize> {
+        match self.state.mode {
+            ManagerMode::Reordering { from } => Some(from),
+            _ => None,
+        }
+    }
+
+    /// Selects the layer at `index`.
+    pub const fn select(&mut self, index: usize) {
+        self.state.selected = index;
+    }
 }
 
 impl Component for LayerManager {
@@ -361,6 +438,7 @@ impl Component for LayerManager {
             ManagerMode::CreatingName { .. }
             | ManagerMode::Renaming { .. }
             | ManagerMode::Duplicating { .. } => self.handle_text_input(key),
+            ManagerMode::EditingDescription { .. } => self.handle_description_input(key),
             ManagerMode::ConfirmingDelete { layer_index } => {
                 self.handle_delete_confirmation(key, *layer_index)
             }
@@ -372,11 +450,19 @@ impl Component for LayerManager {
                 source_index,
                 target_selected,
             } => self.handle_swapping_input(key, *source_index, *target_selected),
+            ManagerMode::Reordering { from } => self.handle_reordering_input(key, *from),
         }
     }
 
     fn render(&self, f: &mut Frame, area: Rect, theme: &Theme) {
-        render_layer_manager(f, area, &self.state, &self.cached_layers, theme);
+        render_layer_manager(
+            f,
+            area,
+            &self.state,
+            &self.cached_layers,
+            &self.cached_tap_dances,
+            theme,
+        );
     }
 }
 
@@ -388,31 +474,20 @@ impl LayerManager {
                 self.state.reset(self.state.selected);
                 Some(LayerManagerEvent::Closed)
             }
-            KeyCode::Up if key.modifiers.contains(KeyModifiers::SHIFT) => {
-                // Move layer up (reorder)
-                let selected = self.state.selected;
-                if selected > 0 && self.cached_layers.len() > 1 {
-                    self.state.selected = selected - 1;
-                    Some(LayerManagerEvent::LayerReordered {
-                        from: selected,
-                        to: selected - 1,
-                    })
+            KeyCode::Up | KeyCode::Down if key.modifiers.contains(KeyModifiers::SHIFT) => {
+                // Start moving the layer and take the first step
+                if self.state.selected > 0 {
+                    self.state.start_reordering();
+                    self.handle_reordering_input(key, self.state.selected)
                 } else {
-                    None
+                    None // The base layer stays at index 0
                 }
             }
-            KeyCode::Down if key.modifiers.contains(KeyModifiers::SHIFT) => {
-                // Move layer down (reorder)
-                let selected = self.state.selected;
-                if selected < self.cached_layers.len() - 1 {
-                    self.state.selected = selected + 1;
-                    Some(LayerManagerEvent::LayerReordered {
-                        from: selected,
-                        to: selected + 1,
-                    })
-                } else {
-                    None
+            KeyCode::Char('m') => {
+                if self.state.selected > 0 {
+                    self.state.start_reordering();
                 }
+                None
             }
             KeyCode::Up | KeyCode::Char('k') => {
                 self.state.select_previous(self.cached_layers.len());
@@ -428,7 +503,7 @@ impl LayerManager {
                     index: self.state.selected,
                 })
             }
-            KeyCode::Char('n') => {
+            KeyCode::Char('n' | '+') => {
                 // Start creating new layer
                 self.state.start_creating();
                 None
@@ -440,6 +515,13 @@ impl LayerManager {
                 }
                 None
             }
+            KeyCode::Char('e') => {
+                // Start editing description
+                if let Some(layer) = self.cached_layers.get(self.state.selected) {
+                    self.state.start_describing(layer);
+                }
+                None
+            }
             KeyCode::Char('v') => {
                 // Toggle layer colors
                 let selected_idx = self.state.selected;
@@ -453,7 +535,17 @@ impl LayerManager {
                     None
                 }
             }
-            KeyCode::Char('d') => {
+            KeyCode::Char('f') => {
+                // Toggle firmware inclusion
+                let selected_idx = self.state.selected;
+                self.cached_layers.get(selected_idx).map(|layer| {
+                    LayerManagerEvent::LayerEnabledToggled {
+                        index: selected_idx,
+                        enabled: !layer.enabled,
+                    }
+                })
+            }
+            KeyCode::Char('d' | '-') => {
                 // Start delete confirmation
                 if self.cached_layers.len() <= 1 {
                     None // Can't delete last layer
@@ -491,6 +583,32 @@ impl LayerManager {
         }
     }
 
+    /// Handle input in the description editor
+    fn handle_description_input(&mut self, key: KeyEvent) -> Option<LayerManagerEvent> {
+        let ManagerMode::EditingDescription {
+            layer_index,
+            editor,
+        } = &mut self.state.mode
+        else {
+            return None;
+        };
+        let layer_index = *layer_index;
+
+        match editor.handle_input(key)? {
+            TextEditorEvent::Saved(text) => {
+                self.state.cancel();
+                Some(LayerManagerEvent::LayerDescriptionChanged {
+                    index: layer_index,
+                    description: (!text.is_empty()).then_some(text),
+                })
+            }
+            TextEditorEvent::Cancelled => {
+                self.state.cancel();
+                Some(LayerManagerEvent::Cancelled)
+            }
+        }
+    }
+
     /// Handle text input (for creating, renaming, duplicating)
     fn handle_text_input(&mut self, key: KeyEvent) -> Option<LayerManagerEvent> {
         match key.code {
@@ -512,18 +630,9 @@ impl LayerManager {
                             let new_index = self.cached_layers.len();
                             let default_color = RgbColor::new(128, 128, 128); // Gray-500
 
-                            if let Ok(mut new_layer) =
+                            if let Ok(new_layer) =
                                 Layer::new(new_index as u8, &input, default_color)
                             {
-                                // Copy key positions from first layer (with transparent keycodes)
-                                if let Some(first_layer) = self.cached_layers.first() {
-                                    for key in &first_layer.keys {
-                                        use crate::models::layer::KeyDefinition;
-                                        new_layer
-                                            .add_key(KeyDefinition::new(key.position, "KC_TRNS"));
-                                    }
-                                }
-
                                 self.state.cancel();
                                 Some(LayerManagerEvent::LayerAdded { layer: new_layer })
                             } else {
@@ -542,34 +651,17 @@ impl LayerManager {
                             let source_index = *source_index;
                             let new_index = self.cached_layers.len();
 
-                            if let Some(source) = self.cached_layers.get(source_index) {
-                                if let Ok(mut new_layer) =
-                                    Layer::new(new_index as u8, &input, source.default_color)
-                                {
-                                    // Copy all keys from source
-                                    for key in &source.keys {
-                                        use crate::models::layer::KeyDefinition;
-                                        let mut new_key =
-                                            KeyDefinition::new(key.position, &key.keycode);
-                                        new_key.color_override = key.color_override;
-                                        new_key.category_id = key.category_id.clone();
-                                        new_layer.add_key(new_key);
-                                    }
-                                    // Copy layer settings
-                                    new_layer.layer_colors_enabled = source.layer_colors_enabled;
-                                    new_layer.category_id = source.category_id.clone();
-
-                                    self.state.cancel();
-                                    Some(LayerManagerEvent::LayerDuplicated {
-                                        source_index,
-                                        layer: new_layer,
-                                    })
-                                } else {
-                                    None
+                            let new_layer = self
+                                .cached_layers
+                                .get(source_index)
+                                .and_then(|source| source.duplicate(new_index as u8, &input).ok());
+                            new_layer.map(|layer| {
+                                self.state.cancel();
+                                LayerManagerEvent::LayerDuplicated {
+                                    source_index,
+                                    layer,
                                 }
-                            } else {
-                                None
-                            }
+                            })
                         }
                         _ => None,
                     }
@@ -577,19 +669,12 @@ impl LayerManager {
                     None
                 }
             }
-            KeyCode::Char(c) => {
-                if let Some(input) = self.state.get_input_mut() {
-                    input.push(c);
-                }
-                None
-            }
-            KeyCode::Backspace => {
+            _ => {
                 if let Some(input) = self.state.get_input_mut() {
-                    input.pop();
+                    input.handle_key(key);
                 }
                 None
             }
-            _ => None,
         }
     }
 
@@ -735,19 +820,63 @@ impl LayerManager {
     }
 }
 
+impl LayerManager {
+    /// Handle input while moving a layer: nothing changes until Enter, which
+    /// applies the whole move at once
+    fn handle_reordering_input(&mut self, key: KeyEvent, from: usize) -> Option<LayerManagerEvent> {
+        match key.code {
+            KeyCode::Up | KeyCode::Char('k') => {
+                // Layer 0 is the base layer, nothing moves above it
+                if self.state.selected > 1 {
+                    self.state.selected -= 1;
+                }
+                None
+            }
+            KeyCode::Down | KeyCode::Char('j') => {
+                if self.state.selected + 1 < self.cached_layers.len() {
+                    self.state.selected += 1;
+                }
+                None
+            }
+            KeyCode::Enter => {
+                self.state.cancel();
+                let to = self.state.selected;
+                (to != from).then_some(LayerManagerEvent::LayerReordered { from, to })
+            }
+            KeyCode::Esc => {
+                self.state.reset(from);
+                None
+            }
+            _ => None,
+        }
+    }
+}
+
 /// Render the layer manager dialog
 pub fn render_layer_manager(
     f: &mut Frame,
     area: Rect,
     state: &LayerManagerState,
     layers: &[Layer],
+    tap_dances: &[TapDanceAction],
     theme: &Theme,
 ) {
-    // Center the dialog (80% width, 80% height)
+    // Center the dialog (80% width, 80% height). While moving a layer, keep
+    // it short and low so the keyboard above shows the layer being moved.
+    let reordering = matches!(state.mode, ManagerMode::Reordering { .. });
     let dialog_width = (area.width * 80) / 100;
-    let dialog_height = (area.height * 80) / 100;
+    let dialog_height = if reordering {
+        let list_height = u16::try_from(layers.len()).unwrap_or(u16::MAX);
+        list_height.saturating_add(5).min(area.height / 2)
+    } else {
+        (area.height * 80) / 100
+    };
     let dialog_x = (area.width - dialog_width) / 2;
-    let dialog_y = (area.height - dialog_height) / 2;
+    let dialog_y = if reordering {
+        area.height.saturating_sub(dialog_height + 1)
+    } else {
+        (area.height - dialog_height) / 2
+    };
 
     let dialog_area = Rect {
         x: dialog_x,
@@ -799,10 +928,12 @@ pub fn render_layer_manager(
                 theme,
             );
         }
+        ManagerMode::EditingDescription { editor, .. } => {
+            render_layer_list(f, inner_area, state, layers, theme);
+            editor.render(f, inner_area, theme);
+        }
         ManagerMode::ConfirmingDelete { layer_index } => {
-            if let Some(layer) = layers.get(*layer_index) {
-                render_delete_confirmation(f, inner_area, *layer_index, layer, layers.len(), theme);
-            }
+            render_delete_confirmation(f, inner_area, *layer_index, layers, tap_dances, theme);
         }
         ManagerMode::Duplicating {
             source_index,
@@ -847,9 +978,70 @@ pub fn render_layer_manager(
                 theme,
             );
         }
+        ManagerMode::Reordering { from } => {
+            render_reorder_preview(f, inner_area, state.selected, *from, layers, theme);
+        }
     }
 }
 
+/// Render the layers in the order they would have after moving layer `from`
+/// to `to`
+fn render_reorder_preview(
+    f: &mut Frame,
+    area: Rect,
+    to: usize,
+    from: usize,
+    layers: &[Layer],
+    theme: &Theme,
+) {
+    let chunks = ratatui::layout::Layout::default()
+        .direction(ratatui::layout::Direction::Vertical)
+        .constraints([Constraint::Min(3), Constraint::Length(1)])
+        .split(area);
+
+    let mut order: Vec<&Layer> = layers.iter().collect();
+    if from < order.len() && to < order.len() {
+        let moving = order.remove(from);
+        order.insert(to, moving);
+    }
+    let items: Vec<ListItem> = order
+        .iter()
+        .enumerate()
+        .map(|(i, layer)| {
+            let style = if i == to {
+                Style::default()
+                    .fg(theme.accent)
+                    .add_modifier(Modifier::BOLD)
+            } else {
+                Style::default().fg(theme.text)
+            };
+            ListItem::new(Line::from(vec![
+                Span::styled(
+                    format!("Layer {i}: "),
+                    Style::default().fg(theme.text_muted),
+                ),
+                Span::styled(&layer.name, style),
+            ]))
+        })
+        .collect();
+    let title = format!("Moving layer {from} to {to}");
+    let list = List::new(items).block(Block::default().borders(Borders::ALL).title(title));
+    f.render_widget(list, chunks[0]);
+
+    let help = Line::from(vec![
+        Span::styled("↑/↓", Style::default().fg(theme.primary)),
+        Span::raw(": Move  "),
+        Span::styled("Enter", Style::default().fg(theme.primary)),
+        Span::raw(": Apply  "),
+        Span::styled("Esc", Style::default().fg(theme.primary)),
+        Span::raw(": Cancel"),
+    ]);
+    f.render_widget(
+        Paragraph::new(help).style(Style::default().fg(theme.text_muted)),
+        chunks[1],
+    );
+}
+
 /// Render the list of layers
 fn render_layer_list(
     f: &mut Frame,
@@ -879,6 +1071,12 @@ fn render_layer_list(
             } else {
                 Style::default().fg(theme.text)
             };
+            // Layers left out of firmware are struck through
+            let style = if layer.enabled {
+                style
+            } else {
+                style.add_modifier(Modifier::DIM | Modifier::CROSSED_OUT)
+            };
 
             // Show color indicator for layer default color
             let color = &layer.default_color;
@@ -894,7 +1092,7 @@ fn render_layer_list(
                 Span::styled(" ○", Style::default().fg(theme.text_muted))
             };
 
-            let content = Line::from(vec![
+            let mut spans = vec![
                 color_box,
                 Span::styled(
                     format!("Layer {i}: "),
@@ -902,7 +1100,30 @@ fn render_layer_list(
                 ),
                 Span::styled(&layer.name, style),
                 colors_indicator,
-            ]);
+            ];
+            if !layer.enabled {
+                spans.push(Span::styled(
+                    " [disabled]",
+                    Style::default().fg(theme.text_muted),
+                ));
+            }
+            if let Some(modified) = layer.modified {
+                spans.push(Span::styled(
+                    format!(
+                        "  edited {}",
+                        modified.with_timezone(&Local).format("%Y-%m-%d %H:%M")
+                    ),
+                    Style::default().fg(theme.text_muted),
+                ));
+            }
+            // First line of the description as a hint
+            if let Some(summary) = layer.description.as_deref().and_then(|d| d.lines().next()) {
+                spans.push(Span::styled(
+                    format!("  {summary}"),
+                    Style::default().fg(theme.text_muted),
+                ));
+            }
+            let content = Line::from(spans);
 
             ListItem::new(content)
         })
@@ -910,7 +1131,7 @@ fn render_layer_list(
 
     let list = List::new(items)
         .block(Block::default().borders(Borders::ALL).title("Layers"))
-        .highlight_style(Style::default().bg(theme.surface));
+        .highlight_style(theme.selection(Style::default().bg(theme.surface)));
 
     f.render_widget(list, chunks[0]);
 
@@ -924,6 +1145,8 @@ fn render_layer_list(
             Span::raw(": Duplicate  "),
             Span::styled("r", Style::default().fg(theme.primary)),
             Span::raw(": Rename  "),
+            Span::styled("e", Style::default().fg(theme.primary)),
+            Span::raw(": Describe  "),
             Span::styled("d", Style::default().fg(theme.primary)),
             Span::raw(": Delete"),
         ]),
@@ -933,13 +1156,15 @@ fn render_layer_list(
             Span::styled("s", Style::default().fg(theme.primary)),
             Span::raw(": Swap with  "),
             Span::styled("v", Style::default().fg(theme.primary)),
-            Span::raw(": Toggle Colors"),
+            Span::raw(": Toggle Colors  "),
+            Span::styled("f", Style::default().fg(theme.primary)),
+            Span::raw(": In Firmware"),
         ]),
         Line::from(vec![
             Span::styled("↑/↓", Style::default().fg(theme.primary)),
             Span::raw(": Navigate  "),
-            Span::styled("Shift+↑/↓", Style::default().fg(theme.primary)),
-            Span::raw(": Reorder  "),
+            Span::styled("m/Shift+↑/↓", Style::default().fg(theme.primary)),
+            Span::raw(": Move  "),
             Span::styled("Enter", Style::default().fg(theme.primary)),
             Span::raw(": Go to"),
         ]),
@@ -961,7 +1186,7 @@ fn render_name_input(
     f: &mut Frame,
     area: Rect,
     title: &str,
-    input: &str,
+    input: &TextInput,
     prompt: &str,
     theme: &Theme,
 ) {
@@ -987,11 +1212,13 @@ fn render_name_input(
         .title(title)
         .style(Style::default().fg(theme.primary));
 
-    let input_text = Paragraph::new(input)
-        .block(input_block)
-        .style(Style::default().fg(theme.text));
-
-    f.render_widget(input_text, chunks[1]);
+    input.render(
+        f,
+        chunks[1],
+        input_block,
+        Style::default().fg(theme.text),
+        true,
+    );
 
     // Help text
     let help = vec![Line::from(vec![
@@ -1013,17 +1240,22 @@ fn render_delete_confirmation(
     f: &mut Frame,
     area: Rect,
     layer_index: usize,
-    layer: &Layer,
-    layer_count: usize,
+    layers: &[Layer],
+    tap_dances: &[TapDanceAction],
     theme: &Theme,
 ) {
+    let Some(layer) = layers.get(layer_index) else {
+        return;
+    };
+    let layer_count = layers.len();
+    let refs = layer_refs::inbound_layer_refs(layers, tap_dances, layer_index);
+
     let chunks = ratatui::layout::Layout::default()
         .direction(ratatui::layout::Direction::Vertical)
         .constraints([
             Constraint::Length(3), // Warning
             Constraint::Length(3), // Layer info
-            Constraint::Length(2), // Additional warning if needed
-            Constraint::Min(1),    // Spacer
+            Constraint::Min(2),    // Last layer warning or references to the layer
             Constraint::Length(3), // Help
         ])
         .split(area);
@@ -1051,7 +1283,7 @@ fn render_delete_confirmation(
         .style(Style::default().fg(theme.text));
     f.render_widget(info_widget, chunks[1]);
 
-    // Show warning if this is the last layer
+    // Show warning if this is the last layer, or the keys that switch to it
     if layer_count <= 1 {
         let last_layer_warning = Paragraph::new("Cannot delete the last layer!")
             .alignment(Alignment::Center)
@@ -1061,6 +1293,38 @@ fn render_delete_confirmation(
                     .add_modifier(Modifier::BOLD),
             );
         f.render_widget(last_layer_warning, chunks[2]);
+    } else if !refs.is_empty() {
+        let mut lines = vec![Line::from(Span::styled(
+            format!(
+                "{} keycode(s) elsewhere switch to this layer and will be replaced:",
+                refs.len()
+            ),
+            Style::default()
+                .fg(theme.warning)
+                .add_modifier(Modifier::BOLD),
+        ))];
+        let shown = usize::from(chunks[2].height.saturating_sub(2)).max(1);
+        for layer_ref in refs.iter().take(shown) {
+            lines.push(Line::from(Span::styled(
+                format!(
+                    "{} {} -> {}",
+                    layer_ref.site,
+                    layer_ref.keycode,
+                    layer_ref.replacement()
+                ),
+                Style::default().fg(theme.text),
+            )));
+        }
+        if refs.len() > shown {
+            lines.push(Line::from(Span::styled(
+                format!("... and {} more", refs.len() - shown),
+                Style::default().fg(theme.text_muted),
+            )));
+        }
+        f.render_widget(
+            Paragraph::new(lines).alignment(Alignment::Center),
+            chunks[2],
+        );
     }
 
     // Help
@@ -1072,7 +1336,11 @@ fn render_delete_confirmation(
     } else {
         vec![Line::from(vec![
             Span::styled("y", Style::default().fg(theme.primary)),
-            Span::raw(": Yes, delete  "),
+            Span::raw(if refs.is_empty() {
+                ": Yes, delete  "
+            } else {
+                ": Yes, delete and replace them  "
+            }),
             Span::styled("n/Esc", Style::default().fg(theme.primary)),
             Span::raw(": No, cancel"),
         ])]
@@ -1082,7 +1350,7 @@ fn render_delete_confirmation(
         .alignment(Alignment::Center)
         .style(Style::default().fg(theme.text_muted));
 
-    f.render_widget(help_widget, chunks[4]);
+    f.render_widget(help_widget, chunks[3]);
 }
 
 /// Render layer picker for copy-to or swap operations
@@ -1143,7 +1411,7 @@ fn render_layer_picker(
                 .borders(Borders::ALL)
                 .title(format!("Select Target - {title}")),
         )
-        .highlight_style(Style::default().bg(theme.surface));
+        .highlight_style(theme.selection(Style::default().bg(theme.surface)));
 
     f.render_widget(list, chunks[1]);
 
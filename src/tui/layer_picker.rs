@@ -221,10 +221,12 @@ pub fn render_layer_picker_component(
                 .style(Style::default().bg(theme.background)),
         )
         .highlight_style(
-            Style::default()
-                .bg(theme.surface)
-                .fg(theme.accent)
-                .add_modifier(Modifier::BOLD),
+            theme.selection(
+                Style::default()
+                    .bg(theme.surface)
+                    .fg(theme.accent)
+                    .add_modifier(Modifier::BOLD),
+            ),
         )
         .highlight_symbol(">> ");
 
@@ -7,15 +7,15 @@ use anyhow::{Context, Result};
 use ratatui::{
     layout::{Alignment, Constraint, Direction, Layout as RatatuiLayout, Rect},
     style::{Modifier, Style},
+    text::{Line, Span},
     widgets::{Block, Borders, List, ListItem, Paragraph},
     Frame,
 };
 use std::fs;
 use std::path::PathBuf;
 
-use crate::config::Config;
 use crate::models::LayoutMetadata;
-use crate::parser::layout as layout_parser;
+use crate::services::layouts::{find_duplicates, scan_layout_dir};
 
 /// Layout file information with path and metadata.
 #[derive(Debug, Clone)]
@@ -24,6 +24,10 @@ pub struct LayoutInfo {
     pub path: PathBuf,
     /// Layout metadata
     pub metadata: LayoutMetadata,
+    /// Hash of the parsed layout content (see `Layout::content_hash`)
+    pub content_hash: String,
+    /// Layout file this one is byte-for-byte equivalent to, if any
+    pub duplicate_of: Option<PathBuf>,
 }
 
 /// State for the layout picker dialog.
@@ -35,6 +39,10 @@ pub struct LayoutPickerState {
     pub selected: usize,
     /// Whether user wants to create new layout
     pub create_new: bool,
+    /// Index of a duplicate layout awaiting delete confirmation
+    pub pending_delete: Option<usize>,
+    /// Result of the last delete action
+    pub message: Option<String>,
 }
 
 impl LayoutPickerState {
@@ -45,6 +53,8 @@ impl LayoutPickerState {
             layouts: Vec::new(),
             selected: 0,
             create_new: false,
+            pending_delete: None,
+            message: None,
         }
     }
 
@@ -65,42 +75,44 @@ impl LayoutPickerState {
             return Ok(()); // Empty directory, no layouts
         }
 
-        // Scan directory for .md files
-        let entries = fs::read_dir(&layouts_dir).context(format!(
-            "Failed to read layouts directory: {}",
-            layouts_dir.display()
-        ))?;
-
-        for entry in entries {
-            let entry = entry.context("Failed to read directory entry")?;
-            let path = entry.path();
-
-            // Only process .md files
-            if path.extension().and_then(|s| s.to_str()) != Some("md") {
-                continue;
-            }
-
-            // Try to parse the layout file
-            match layout_parser::parse_markdown_layout(&path) {
+        for scanned in scan_layout_dir(&layouts_dir)? {
+            match scanned.layout {
                 Ok(layout) => {
                     // Don't include template files
                     if !layout.metadata.is_template {
                         self.layouts.push(LayoutInfo {
-                            path: path.clone(),
+                            path: scanned.path,
+                            content_hash: layout.content_hash(),
                             metadata: layout.metadata,
+                            duplicate_of: None,
                         });
                     }
                 }
                 Err(e) => {
-                    eprintln!("Warning: Failed to parse layout {}: {}", path.display(), e);
-                    // Continue scanning other files
+                    eprintln!(
+                        "Warning: Failed to parse layout {}: {}",
+                        scanned.path.display(),
+                        e
+                    );
+                    // Continue with the other files
                 }
             }
         }
 
+        // Flag layouts identical to another one
+        let hashes: Vec<(PathBuf, String)> = self
+            .layouts
+            .iter()
+            .map(|l| (l.path.clone(), l.content_hash.clone()))
+            .collect();
+        let mut duplicates = find_duplicates(&hashes);
+        for layout in &mut self.layouts {
+            layout.duplicate_of = duplicates.remove(&layout.path);
+        }
+
         // Sort layouts by modified date (most recent first)
         self.layouts
-            .sort_by(|a, b| b.metadata.modified.cmp(&a.metadata.modified));
+            .sort_by_key(|l| std::cmp::Reverse(l.metadata.modified));
 
         // Reset selection
         self.selected = 0;
@@ -108,15 +120,36 @@ impl LayoutPickerState {
         Ok(())
     }
 
-    /// Gets the platform-specific layouts directory path.
+    /// Deletes the duplicate layout at `index` and rescans the directory.
     ///
-    /// - Unix/Linux/macOS: `~/.config/LazyQMK/layouts/`
-    /// - Windows: `%APPDATA%\LazyQMK\layouts\`
+    /// Only layouts flagged as duplicates can be deleted, so the original of
+    /// each group is always kept.
+    pub fn delete_duplicate(&mut self, index: usize) -> Result<()> {
+        let layout = self.layouts.get(index).context("No layout selected")?;
+        if layout.duplicate_of.is_none() {
+            anyhow::bail!("{} is not a duplicate", file_name(&layout.path));
+        }
+
+        fs::remove_file(&layout.path)
+            .with_context(|| format!("Failed to delete {}", layout.path.display()))?;
+        self.scan_layouts()
+    }
+
+    /// Gets the layouts directory path, `layouts/` in the data directory
+    /// (see [`crate::paths`]).
     pub fn layouts_dir() -> Result<PathBuf> {
-        Ok(Config::config_dir()?.join("layouts"))
+        crate::paths::layouts_dir()
     }
 }
 
+/// File name of a layout path for display.
+fn file_name(path: &std::path::Path) -> String {
+    path.file_name().map_or_else(
+        || path.display().to_string(),
+        |n| n.to_string_lossy().into_owned(),
+    )
+}
+
 impl Default for LayoutPickerState {
     fn default() -> Self {
         Self::new()
@@ -153,7 +186,42 @@ impl crate::tui::component::Component for LayoutPicker {
     fn handle_input(&mut self, key: crossterm::event::KeyEvent) -> Option<Self::Event> {
         use crossterm::event::KeyCode;
 
+        // Confirmation for deleting a duplicate takes over all input
+        if let Some(index) = self.state.pending_delete.take() {
+            if matches!(key.code, KeyCode::Char('y' | 'Y')) {
+                let name = self
+                    .state
+                    .layouts
+                    .get(index)
+                    .map(|l| file_name(&l.path))
+                    .unwrap_or_default();
+                self.state.message = Some(match self.state.delete_duplicate(index) {
+                    Ok(()) => format!("Deleted {name}"),
+                    Err(e) => format!("Delete failed: {e}"),
+                });
+            } else {
+                self.state.message = Some("Delete cancelled".to_string());
+            }
+            return None;
+        }
+        self.state.message = None;
+
         match key.code {
+            KeyCode::Char('d') => {
+                if !self.state.create_new {
+                    match self.state.layouts.get(self.state.selected) {
+                        Some(layout) if layout.duplicate_of.is_some() => {
+                            self.state.pending_delete = Some(self.state.selected);
+                        }
+                        Some(_) => {
+                            self.state.message =
+                                Some("Only duplicate layouts can be deleted here".to_string());
+                        }
+                        None => {}
+                    }
+                }
+                None
+            }
             KeyCode::Up | KeyCode::Char('k') => {
                 if self.state.create_new {
                     // Move from "Create New" to last layout
@@ -268,13 +336,37 @@ fn render_layout_picker_component(
             .format("%Y-%m-%d %H:%M")
             .to_string();
 
-        let text = format!("{} ({})", layout_info.metadata.name, modified);
+        let mut spans = vec![Span::styled(
+            format!(
+                "{} [{}] ({})",
+                layout_info.metadata.name,
+                file_name(&layout_info.path),
+                modified
+            ),
+            style,
+        )];
+        if let Some(original) = &layout_info.duplicate_of {
+            spans.push(Span::styled(
+                format!("  identical to {}", file_name(original)),
+                Style::default().fg(theme.warning),
+            ));
+        }
 
-        items.push(ListItem::new(text).style(style));
+        items.push(ListItem::new(Line::from(spans)));
     }
 
+    let duplicate_count = state
+        .layouts
+        .iter()
+        .filter(|l| l.duplicate_of.is_some())
+        .count();
     let list_title = if state.layouts.is_empty() {
         "No saved layouts found".to_string()
+    } else if duplicate_count > 0 {
+        format!(
+            "Saved Layouts ({} total, {duplicate_count} duplicate)",
+            state.layouts.len()
+        )
     } else {
         format!("Saved Layouts ({} total)", state.layouts.len())
     };
@@ -282,17 +374,33 @@ fn render_layout_picker_component(
     let list = List::new(items)
         .block(Block::default().borders(Borders::ALL).title(list_title))
         .highlight_style(
-            Style::default()
-                .fg(theme.accent)
-                .add_modifier(Modifier::BOLD),
+            theme.selection(
+                Style::default()
+                    .fg(theme.accent)
+                    .add_modifier(Modifier::BOLD),
+            ),
         );
 
     f.render_widget(list, vertical_chunks[1]);
 
-    // Render instructions
-    let instructions = "↑↓: Navigate  |  Enter: Select  |  Esc: Cancel";
+    // Render instructions, or the pending confirmation / last result
+    let (instructions, color) = if let Some(index) = state.pending_delete {
+        let name = state
+            .layouts
+            .get(index)
+            .map(|l| file_name(&l.path))
+            .unwrap_or_default();
+        (format!("Delete {name}? (y/n)"), theme.warning)
+    } else if let Some(message) = &state.message {
+        (message.clone(), theme.text)
+    } else {
+        (
+            "↑↓: Navigate  |  Enter: Select  |  d: Delete duplicate  |  Esc: Cancel".to_string(),
+            theme.text_muted,
+        )
+    };
     let paragraph = Paragraph::new(instructions)
-        .style(Style::default().fg(theme.text_muted))
+        .style(Style::default().fg(color))
         .alignment(Alignment::Center)
         .block(Block::default().borders(Borders::ALL));
     f.render_widget(paragraph, vertical_chunks[2]);
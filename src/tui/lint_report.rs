@@ -0,0 +1,226 @@
+//! Lint report popup listing style findings for the current layout.
+//!
+//! Findings come from [`Linter`]; selecting one with Enter jumps to the
+//! layer and key it refers to.
+
+use ratatui::{
+    layout::{Constraint, Direction, Layout as RatatuiLayout, Rect},
+    style::{Modifier, Style},
+    text::{Line, Span},
+    widgets::{Block, Borders, Clear, List, ListItem, ListState, Paragraph, Wrap},
+    Frame,
+};
+
+use crate::models::{KeyRole, Layout, Position};
+use crate::services::lint::{LintFinding, LintReport, LintSeverity, Linter};
+use std::collections::HashMap;
+
+/// Events emitted by the LintReportView component
+#[derive(Debug, Clone)]
+pub enum LintReportEvent {
+    /// User selected a finding to jump to
+    JumpTo {
+        /// Layer index
+        layer: usize,
+        /// Key position, if the finding refers to a key
+        position: Option<Position>,
+    },
+    /// User closed the report
+    Closed,
+}
+
+/// LintReportView component that implements the Component trait
+#[derive(Debug, Clone)]
+pub struct LintReportView {
+    /// Findings and disabled rules
+    report: LintReport,
+    /// Currently selected finding index
+    selected: usize,
+}
+
+impl LintReportView {
+    /// Create a new LintReportView by linting `layout` with the key roles
+    /// of its keyboard, skipping the `disabled` rules as well as the ones the
+    /// layout disables.
+    #[must_use]
+    pub fn new(layout: &Layout, roles: &HashMap<Position, KeyRole>, disabled: &[String]) -> Self {
+        Self {
+            report: Linter::default()
+                .with_disabled(disabled)
+                .lint_with_roles(layout, roles),
+            selected: 0,
+        }
+    }
+
+    /// Gets the lint report.
+    #[must_use]
+    pub const fn report(&self) -> &LintReport {
+        &self.report
+    }
+
+    /// Gets the currently selected finding (if any).
+    #[must_use]
+    pub fn selected_finding(&self) -> Option<&LintFinding> {
+        self.report.findings.get(self.selected)
+    }
+
+    /// Moves selection up.
+    const fn select_previous(&mut self) {
+        self.selected = self.selected.saturating_sub(1);
+    }
+
+    /// Moves selection down.
+    const fn select_next(&mut self) {
+        if self.selected + 1 < self.report.findings.len() {
+            self.selected += 1;
+        }
+    }
+}
+
+impl crate::tui::component::Component for LintReportView {
+    type Event = LintReportEvent;
+
+    fn handle_input(&mut self, key: crossterm::event::KeyEvent) -> Option<Self::Event> {
+        use crossterm::event::KeyCode;
+
+        match key.code {
+            KeyCode::Esc | KeyCode::Char('q') => Some(LintReportEvent::Closed),
+            KeyCode::Enter => self.selected_finding().and_then(|finding| {
+                finding.layer.map(|layer| LintReportEvent::JumpTo {
+                    layer,
+                    position: finding.position,
+                })
+            }),
+            KeyCode::Up | KeyCode::Char('k') => {
+                self.select_previous();
+                None
+            }
+            KeyCode::Down | KeyCode::Char('j') => {
+                self.select_next();
+                None
+            }
+            _ => None,
+        }
+    }
+
+    fn render(&self, f: &mut Frame, area: Rect, theme: &crate::tui::theme::Theme) {
+        render_lint_report(f, self, area, theme);
+    }
+}
+
+/// Renders the lint report popup
+fn render_lint_report(
+    f: &mut Frame,
+    view: &LintReportView,
+    area: Rect,
+    theme: &crate::tui::theme::Theme,
+) {
+    let popup_area = centered_rect(70, 70, area);
+
+    f.render_widget(Clear, popup_area);
+    let background = Block::default().style(Style::default().bg(theme.background));
+    f.render_widget(background, popup_area);
+
+    let chunks = RatatuiLayout::default()
+        .direction(Direction::Vertical)
+        .constraints([
+            Constraint::Min(5),    // Findings list
+            Constraint::Length(3), // Summary
+        ])
+        .split(popup_area);
+
+    let findings = &view.report.findings;
+    let items: Vec<ListItem> = if findings.is_empty() {
+        vec![ListItem::new(Line::from(Span::styled(
+            "No lint findings",
+            Style::default().fg(theme.success),
+        )))]
+    } else {
+        findings
+            .iter()
+            .map(|finding| {
+                let (icon, color) = match finding.severity {
+                    LintSeverity::Error => ("✗", theme.error),
+                    LintSeverity::Warning => ("⚠", theme.warning),
+                    LintSeverity::Info => ("ℹ", theme.text_muted),
+                };
+                let location = match (finding.layer, finding.position) {
+                    (Some(layer), Some(pos)) => format!("L{layer} ({}, {})", pos.row, pos.col),
+                    (Some(layer), None) => format!("L{layer}"),
+                    _ => String::new(),
+                };
+                ListItem::new(Line::from(vec![
+                    Span::styled(
+                        format!("{icon} {} ", finding.rule),
+                        Style::default().fg(color),
+                    ),
+                    Span::styled(
+                        format!("{location:<12} "),
+                        Style::default().fg(theme.text_muted),
+                    ),
+                    Span::styled(finding.message.clone(), Style::default().fg(theme.text)),
+                ]))
+            })
+            .collect()
+    };
+
+    let list = List::new(items)
+        .block(
+            Block::default()
+                .borders(Borders::ALL)
+                .title(format!(" Lint Report ({}) ", findings.len()))
+                .style(Style::default().fg(theme.primary)),
+        )
+        .highlight_style(
+            theme.selection(
+                Style::default()
+                    .fg(theme.background)
+                    .bg(theme.primary)
+                    .add_modifier(Modifier::BOLD),
+            ),
+        );
+    let mut list_state = ListState::default();
+    if !findings.is_empty() {
+        list_state.select(Some(view.selected));
+    }
+    f.render_stateful_widget(list, chunks[0], &mut list_state);
+
+    let count = |severity| findings.iter().filter(|f| f.severity == severity).count();
+    let disabled = if view.report.disabled.is_empty() {
+        String::new()
+    } else {
+        format!(" · disabled: {}", view.report.disabled.join(", "))
+    };
+    let summary = format!(
+        "{} error(s), {} warning(s), {} info{disabled}",
+        count(LintSeverity::Error),
+        count(LintSeverity::Warning),
+        count(LintSeverity::Info)
+    );
+    let summary = Paragraph::new(summary)
+        .style(Style::default().fg(theme.text_muted))
+        .wrap(Wrap { trim: true })
+        .block(Block::default().borders(Borders::ALL).title("Summary"));
+    f.render_widget(summary, chunks[1]);
+}
+
+/// Helper to create a centered rectangle
+fn centered_rect(percent_x: u16, percent_y: u16, r: Rect) -> Rect {
+    let popup_layout = RatatuiLayout::default()
+        .direction(Direction::Vertical)
+        .constraints([
+            Constraint::Percentage((100 - percent_y) / 2),
+            Constraint::Percentage(percent_y),
+            Constraint::Percentage((100 - percent_y) / 2),
+        ])
+        .split(r);
+
+    RatatuiLayout::default()
+        .direction(Direction::Horizontal)
+        .constraints([
+            Constraint::Percentage((100 - percent_x) / 2),
+            Constraint::Percentage(percent_x),
+            Constraint::Percentage((100 - percent_x) / 2),
+        ])
+        .split(popup_layout[1])[1]
+}
@@ -0,0 +1,105 @@
+//! Loading screen shown while the editor waits for background work.
+//!
+//! Geometry is built on a worker thread at startup; meanwhile this screen
+//! shows the layout name and a spinner so the first frame appears at once.
+//! With reduced motion the spinner is replaced by the elapsed seconds.
+
+use anyhow::{Context, Result};
+use ratatui::{
+    backend::Backend,
+    layout::{Alignment, Rect},
+    style::{Modifier, Style},
+    text::{Line, Span},
+    widgets::{Block, Borders, Paragraph},
+    Frame, Terminal,
+};
+use std::sync::mpsc::{Receiver, RecvTimeoutError};
+use std::time::{Duration, Instant};
+
+use super::Theme;
+
+/// Braille spinner frames.
+const SPINNER: [&str; 10] = ["⠋", "⠙", "⠹", "⠸", "⠼", "⠴", "⠦", "⠧", "⠇", "⠏"];
+
+/// Time between spinner frames.
+const FRAME_INTERVAL: Duration = Duration::from_millis(80);
+
+/// Shows the loading screen until `receiver` yields a value.
+///
+/// Calls `on_frame` after each frame is drawn, with the time drawing took.
+/// With `reduced_motion` the screen shows elapsed seconds instead of a
+/// spinner.
+///
+/// # Errors
+///
+/// Returns an error if drawing fails or the worker thread exits without
+/// sending a value.
+pub fn wait_with_spinner<B: Backend, T>(
+    terminal: &mut Terminal<B>,
+    theme: &Theme,
+    layout_name: &str,
+    message: &str,
+    receiver: &Receiver<T>,
+    reduced_motion: bool,
+    mut on_frame: impl FnMut(Duration),
+) -> Result<T> {
+    let started = Instant::now();
+    for frame in 0.. {
+        let start = Instant::now();
+        let progress = if reduced_motion {
+            format!("[{}s]", started.elapsed().as_secs())
+        } else {
+            SPINNER[frame % SPINNER.len()].to_string()
+        };
+        terminal.draw(|f| render_loading(f, theme, layout_name, message, &progress))?;
+        on_frame(start.elapsed());
+        match receiver.recv_timeout(FRAME_INTERVAL) {
+            Ok(value) => return Ok(value),
+            Err(RecvTimeoutError::Timeout) => {}
+            Err(RecvTimeoutError::Disconnected) => break,
+        }
+    }
+    Err(RecvTimeoutError::Disconnected).context("Background task exited unexpectedly")
+}
+
+/// Renders the layout name and `progress` (a spinner frame or elapsed time)
+/// in the middle of the screen.
+pub fn render_loading(
+    f: &mut Frame,
+    theme: &Theme,
+    layout_name: &str,
+    message: &str,
+    progress: &str,
+) {
+    let full_bg = Block::default().style(Style::default().bg(theme.background));
+    f.render_widget(full_bg, f.area());
+
+    let area = f.area();
+    let width = area.width.min(60);
+    let height = area.height.min(5);
+    let popup = Rect::new(
+        area.x + (area.width - width) / 2,
+        area.y + (area.height - height) / 2,
+        width,
+        height,
+    );
+
+    let text = vec![
+        Line::from(Span::styled(
+            layout_name,
+            Style::default()
+                .fg(theme.primary)
+                .add_modifier(Modifier::BOLD),
+        )),
+        Line::from(vec![
+            Span::styled(progress, Style::default().fg(theme.accent)),
+            Span::styled(format!(" {message}"), Style::default().fg(theme.text_muted)),
+        ]),
+    ];
+    let paragraph = Paragraph::new(text).alignment(Alignment::Center).block(
+        Block::default()
+            .borders(Borders::ALL)
+            .style(Style::default().bg(theme.background)),
+    );
+    f.render_widget(paragraph, popup);
+}
@@ -0,0 +1,259 @@
+//! Messages from background tasks to the event loop.
+//!
+//! Background work never touches [`AppState`] directly. It sends an
+//! [`AppMessage`] into the state's [`MessageQueue`]; the event loop drains the
+//! queue once per iteration and [`apply_message`] updates the state in one
+//! place. Tests can inject message sequences the same way.
+
+use std::path::PathBuf;
+use std::sync::mpsc::{channel, Receiver, Sender};
+use std::thread;
+
+use super::AppState;
+use crate::firmware::builder::{BuildMessage, LogLevel};
+use crate::firmware::size::FirmwareSize;
+use crate::firmware::BuildStatus;
+
+/// A state update sent by a background task.
+#[derive(Debug, Clone)]
+pub enum AppMessage {
+    /// The firmware build moved to a new stage
+    BuildProgress {
+        /// New build status
+        status: BuildStatus,
+        /// Progress message
+        message: String,
+    },
+    /// A line of firmware build output
+    BuildLine {
+        /// Log level of the line
+        level: LogLevel,
+        /// Line content
+        message: String,
+    },
+    /// The firmware build finished
+    BuildFinished {
+        /// Whether the build succeeded
+        success: bool,
+        /// Path to the compiled firmware
+        firmware_path: Option<PathBuf>,
+        /// Error message if the build failed
+        error: Option<String>,
+        /// Flash/RAM usage parsed from the build output
+        size: Option<FirmwareSize>,
+    },
+}
+
+impl From<BuildMessage> for AppMessage {
+    fn from(message: BuildMessage) -> Self {
+        match message {
+            BuildMessage::Progress { status, message } => Self::BuildProgress { status, message },
+            BuildMessage::Log { level, message } => Self::BuildLine { level, message },
+            BuildMessage::Complete {
+                success,
+                firmware_path,
+                error,
+                size,
+            } => Self::BuildFinished {
+                success,
+                firmware_path,
+                error,
+                size,
+            },
+        }
+    }
+}
+
+/// Channel background tasks send [`AppMessage`]s into.
+#[derive(Debug)]
+pub struct MessageQueue {
+    /// Sending half, cloned for each background task
+    sender: Sender<AppMessage>,
+    /// Receiving half, drained by the event loop
+    receiver: Receiver<AppMessage>,
+}
+
+impl MessageQueue {
+    /// Creates an empty queue.
+    #[must_use]
+    pub fn new() -> Self {
+        let (sender, receiver) = channel();
+        Self { sender, receiver }
+    }
+
+    /// A sender for a background task.
+    #[must_use]
+    pub fn sender(&self) -> Sender<AppMessage> {
+        self.sender.clone()
+    }
+
+    /// Takes every message queued so far, oldest first.
+    #[must_use]
+    pub fn take_pending(&self) -> Vec<AppMessage> {
+        self.receiver.try_iter().collect()
+    }
+}
+
+impl Default for MessageQueue {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+/// Forwards everything received on `receiver` into `sender` as
+/// [`AppMessage`]s, on a thread that ends when either side hangs up.
+///
+/// Lets tasks that report over their own channel, such as
+/// [`BuildState::start_build`](crate::firmware::BuildState::start_build),
+/// feed the event loop.
+pub fn forward<T>(receiver: Receiver<T>, sender: Sender<AppMessage>)
+where
+    T: Send + 'static,
+    AppMessage: From<T>,
+{
+    thread::spawn(move || {
+        for message in receiver {
+            if sender.send(AppMessage::from(message)).is_err() {
+                break;
+            }
+        }
+    });
+}
+
+/// Applies every queued message to the state.
+///
+/// Returns true if any message was applied.
+pub fn drain_messages(state: &mut AppState) -> bool {
+    let messages = state.messages.take_pending();
+    let received = !messages.is_empty();
+    for message in messages {
+        apply_message(state, message);
+    }
+    received
+}
+
+/// Applies one background message to the state.
+pub fn apply_message(state: &mut AppState, message: AppMessage) {
+    match message {
+        AppMessage::BuildProgress { status, message } => {
+            apply_build_message(state, BuildMessage::Progress { status, message });
+        }
+        AppMessage::BuildLine { level, message } => {
+            apply_build_message(state, BuildMessage::Log { level, message });
+        }
+        AppMessage::BuildFinished {
+            success,
+            firmware_path,
+            error,
+            size,
+        } => {
+            apply_build_message(
+                state,
+                BuildMessage::Complete {
+                    success,
+                    firmware_path,
+                    error,
+                    size,
+                },
+            );
+            state.record_finished_build();
+        }
+    }
+}
+
+/// Passes a build message to the build state, starting one if needed.
+fn apply_build_message(state: &mut AppState, message: BuildMessage) {
+    state
+        .build_state
+        .get_or_insert_with(Default::default)
+        .apply(message);
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::config::Config;
+    use crate::models::{KeyboardGeometry, Layer, Layout, RgbColor, VisualLayoutMapping};
+    use crate::services::history::{HistoryOperation, HistoryService};
+
+    fn test_state() -> AppState {
+        let mut layout = Layout::new("Messages").unwrap();
+        layout
+            .add_layer(Layer::new(0, "Base", RgbColor::new(0, 0, 0)).unwrap())
+            .unwrap();
+        AppState::new(
+            layout,
+            None,
+            KeyboardGeometry::new("test", "test", 1, 1),
+            VisualLayoutMapping::default(),
+            Config::default(),
+        )
+        .unwrap()
+    }
+
+    #[test]
+    fn test_build_messages_update_state_and_record_history() {
+        let temp = tempfile::TempDir::new().unwrap();
+        let layout_path = temp.path().join("corne.md");
+        let mut state = test_state();
+        state.source_path = Some(layout_path.clone());
+        state.pending_build_history = Some(state.history_entry(HistoryOperation::Build));
+
+        let sender = state.messages.sender();
+        for message in [
+            AppMessage::BuildProgress {
+                status: BuildStatus::Compiling,
+                message: "Compiling".to_string(),
+            },
+            AppMessage::BuildLine {
+                level: LogLevel::Info,
+                message: "Linking".to_string(),
+            },
+        ] {
+            sender.send(message).unwrap();
+        }
+        assert!(drain_messages(&mut state));
+        let build_state = state.build_state.as_ref().unwrap();
+        assert!(build_state.is_building());
+        assert_eq!(build_state.log_lines.last().unwrap().1, "Linking");
+        assert!(state.pending_build_history.is_some());
+
+        sender
+            .send(AppMessage::BuildFinished {
+                success: false,
+                firmware_path: None,
+                error: Some("make error".to_string()),
+                size: None,
+            })
+            .unwrap();
+        assert!(drain_messages(&mut state));
+        assert!(!drain_messages(&mut state));
+        assert_eq!(
+            state.build_state.as_ref().unwrap().status,
+            BuildStatus::Failed
+        );
+        assert!(state.pending_build_history.is_none());
+        let history = HistoryService::load(&layout_path).unwrap();
+        assert_eq!(history.entries[0].error.as_deref(), Some("make error"));
+    }
+
+    #[test]
+    fn test_forward_converts_build_messages() {
+        let queue = MessageQueue::new();
+        let (sender, receiver) = channel();
+        forward(receiver, queue.sender());
+        sender
+            .send(BuildMessage::Log {
+                level: LogLevel::Ok,
+                message: "done".to_string(),
+            })
+            .unwrap();
+        drop(sender);
+
+        let message = queue
+            .receiver
+            .recv_timeout(std::time::Duration::from_secs(5))
+            .unwrap();
+        assert!(matches!(message, AppMessage::BuildLine { message, .. } if message == "done"));
+    }
+}
@@ -14,6 +14,8 @@ use ratatui::{
 };
 
 use super::component::Component;
+use super::text_editor::{TextEditor, TextEditorEvent};
+use super::text_input::TextInput;
 use super::Theme;
 use crate::models::LayoutMetadata;
 
@@ -92,7 +94,9 @@ impl MetadataField {
     pub const fn help_text(&self) -> &'static str {
         match self {
             Self::Name => "Layout name (max 100 characters) - changing this will rename the file",
-            Self::Description => "Long description of the layout",
+            Self::Description => {
+                "Long description of the layout, shown in exports - Ctrl+E for the multi-line editor"
+            }
             Self::Author => "Creator name",
             Self::Tags => "Comma-separated keywords (lowercase, hyphens only)",
         }
@@ -105,13 +109,13 @@ pub struct MetadataEditorState {
     /// Currently active field
     pub active_field: MetadataField,
     /// Name field value
-    pub name: String,
+    pub name: TextInput,
     /// Description field value
-    pub description: String,
+    pub description: TextInput,
     /// Author field value
-    pub author: String,
+    pub author: TextInput,
     /// Tags field value (comma-separated)
-    pub tags_input: String,
+    pub tags_input: TextInput,
     /// Original name when editor was opened (for detecting renames)
     pub original_name: String,
 }
@@ -122,16 +126,16 @@ impl MetadataEditorState {
     pub fn new(metadata: &LayoutMetadata) -> Self {
         Self {
             active_field: MetadataField::Name,
-            name: metadata.name.clone(),
-            description: metadata.description.clone(),
-            author: metadata.author.clone(),
-            tags_input: metadata.tags.join(", "),
+            name: TextInput::new(metadata.name.clone()),
+            description: TextInput::new(metadata.description.clone()),
+            author: TextInput::new(metadata.author.clone()),
+            tags_input: TextInput::new(metadata.tags.join(", ")),
             original_name: metadata.name.clone(),
         }
     }
 
     /// Get a mutable reference to the active field's value.
-    pub const fn get_active_field_mut(&mut self) -> &mut String {
+    pub const fn get_active_field_mut(&mut self) -> &mut TextInput {
         match self.active_field {
             MetadataField::Name => &mut self.name,
             MetadataField::Description => &mut self.description,
@@ -154,6 +158,7 @@ impl MetadataEditorState {
     #[must_use]
     pub fn parse_tags(&self) -> Vec<String> {
         self.tags_input
+            .value()
             .split(',')
             .map(|s| s.trim().to_lowercase())
             .filter(|s| !s.is_empty())
@@ -163,13 +168,14 @@ impl MetadataEditorState {
     /// Validate the metadata.
     pub fn validate(&self) -> Result<(), String> {
         // Name validation
-        if self.name.trim().is_empty() {
+        let name = self.name.value();
+        if name.trim().is_empty() {
             return Err("Name cannot be empty".to_string());
         }
-        if self.name.len() > 100 {
+        if name.len() > 100 {
             return Err(format!(
                 "Name exceeds maximum length of 100 characters (got {})",
-                self.name.len()
+                name.len()
             ));
         }
 
@@ -191,7 +197,7 @@ impl MetadataEditorState {
     /// Check if the name was changed from the original.
     #[must_use]
     pub fn name_changed(&self) -> bool {
-        self.name != self.original_name
+        self.name.value() != self.original_name
     }
 }
 
@@ -199,10 +205,10 @@ impl Default for MetadataEditorState {
     fn default() -> Self {
         Self {
             active_field: MetadataField::Name,
-            name: String::new(),
-            description: String::new(),
-            author: String::new(),
-            tags_input: String::new(),
+            name: TextInput::default(),
+            description: TextInput::default(),
+            author: TextInput::default(),
+            tags_input: TextInput::default(),
             original_name: String::new(),
         }
     }
@@ -213,6 +219,8 @@ impl Default for MetadataEditorState {
 pub struct MetadataEditor {
     /// Internal state of the metadata editor
     state: MetadataEditorState,
+    /// Multi-line popup for the description, when open
+    description_editor: Option<TextEditor>,
 }
 
 impl MetadataEditor {
@@ -221,6 +229,7 @@ impl MetadataEditor {
     pub fn new(metadata: &LayoutMetadata) -> Self {
         Self {
             state: MetadataEditorState::new(metadata),
+            description_editor: None,
         }
     }
 }
@@ -229,14 +238,37 @@ impl Component for MetadataEditor {
     type Event = MetadataEditorEvent;
 
     fn handle_input(&mut self, key: KeyEvent) -> Option<Self::Event> {
+        if let Some(editor) = &mut self.description_editor {
+            match editor.handle_input(key) {
+                Some(TextEditorEvent::Saved(text)) => {
+                    self.state.description.set_value(text);
+                    self.description_editor = None;
+                }
+                Some(TextEditorEvent::Cancelled) => self.description_editor = None,
+                None => {}
+            }
+            return None;
+        }
+
+        if self.state.active_field == MetadataField::Description
+            && key.code == KeyCode::Char('e')
+            && key.modifiers == KeyModifiers::CONTROL
+        {
+            self.description_editor = Some(TextEditor::new(
+                "Layout Description",
+                self.state.description.value(),
+            ));
+            return None;
+        }
+
         match handle_metadata_editor_input(&mut self.state, key) {
             MetadataEditorAction::Confirm => {
                 // Validate before emitting event
                 if self.state.validate().is_ok() {
                     Some(MetadataEditorEvent::MetadataUpdated {
-                        name: self.state.name.clone(),
-                        description: self.state.description.clone(),
-                        author: self.state.author.clone(),
+                        name: self.state.name.value().to_string(),
+                        description: self.state.description.value().to_string(),
+                        author: self.state.author.value().to_string(),
                         tags: self.state.parse_tags(),
                         name_changed: self.state.name_changed(),
                     })
@@ -252,6 +284,9 @@ impl Component for MetadataEditor {
 
     fn render(&self, f: &mut Frame, _area: Rect, theme: &Theme) {
         render_metadata_editor(f, &self.state, theme);
+        if let Some(editor) = &self.description_editor {
+            editor.render(f, f.area(), theme);
+        }
     }
 }
 
@@ -366,7 +401,7 @@ fn render_field(
     f: &mut Frame,
     area: Rect,
     field: MetadataField,
-    value: &str,
+    value: &TextInput,
     is_active: bool,
     theme: &Theme,
 ) {
@@ -385,21 +420,20 @@ fn render_field(
         Style::default().fg(theme.text_muted)
     };
 
-    // Display value with cursor if active
-    let display_value = if is_active {
-        format!("{value}_")
-    } else {
-        value.to_string()
-    };
-
     let block = Block::default()
         .title(label)
         .borders(Borders::ALL)
         .border_style(border_style);
 
-    let paragraph = Paragraph::new(display_value).style(style).block(block);
-
-    f.render_widget(paragraph, area);
+    // Multi-line descriptions are shown on one line and edited with Ctrl+E
+    if value.value().contains('\n') {
+        let paragraph = Paragraph::new(value.value().replace('\n', " ↵ "))
+            .style(style)
+            .block(block);
+        f.render_widget(paragraph, area);
+    } else {
+        value.render(f, area, block, style, is_active);
+    }
 }
 
 /// Handle input for the metadata editor.
@@ -426,22 +460,11 @@ pub fn handle_metadata_editor_input(
             MetadataEditorAction::Continue
         }
 
-        // Backspace
-        (KeyCode::Backspace, _) => {
-            let field = state.get_active_field_mut();
-            field.pop();
+        // Text editing and cursor keys
+        _ => {
+            state.get_active_field_mut().handle_key(key);
             MetadataEditorAction::Continue
         }
-
-        // Character input
-        (KeyCode::Char(c), KeyModifiers::NONE | KeyModifiers::SHIFT) => {
-            let field = state.get_active_field_mut();
-            field.push(c);
-            MetadataEditorAction::Continue
-        }
-
-        // Ignore other keys
-        _ => MetadataEditorAction::Continue,
     }
 }
 
@@ -14,84 +14,144 @@
 #![allow(clippy::cast_sign_loss)]
 #![allow(clippy::cast_lossless)]
 
+pub mod action_log;
 pub mod build_log;
 pub mod category_manager;
 pub mod category_picker;
 pub mod clipboard;
 pub mod color_picker;
+pub mod command_palette;
 pub mod component;
 pub mod config_dialogs;
+pub mod diff_view;
+pub mod encoder_editor;
+pub mod generation_results;
+pub mod geometry_mismatch;
+pub mod grid_paste;
 pub mod handlers;
 pub mod help_overlay;
 pub mod help_registry;
+pub mod history;
 pub mod key_editor;
+pub mod key_repeat;
+pub mod key_role_editor;
 pub mod keyboard;
 pub mod keycode_picker;
+pub mod keycode_search;
 pub mod layer_manager;
 pub mod layer_picker;
 pub mod layout_picker;
+pub mod lint_report;
+pub mod loading;
+pub mod messages;
 pub mod metadata_editor;
 pub mod modifier_picker;
+pub mod notify;
 #[allow(dead_code)]
 pub mod onboarding_wizard;
 pub mod settings_manager;
+pub mod simulation;
+pub mod snapshot_browser;
+pub mod split_view;
 pub mod status_bar;
 pub mod tap_dance_editor;
 pub mod tap_dance_form;
 pub mod template_browser;
+pub mod text_editor;
+pub mod text_input;
 pub mod theme;
+pub mod tutorial;
+pub mod undo;
+pub mod validation_banner;
+pub mod validation_results;
+pub mod variant_conflict;
 
 use anyhow::{Context, Result};
 use crossterm::{
-    event::{self, DisableMouseCapture, EnableMouseCapture, Event},
+    event::{
+        self, DisableBracketedPaste, DisableMouseCapture, EnableBracketedPaste, EnableMouseCapture,
+        Event,
+    },
     execute,
     terminal::{disable_raw_mode, enable_raw_mode, EnterAlternateScreen, LeaveAlternateScreen},
 };
 use ratatui::{
     backend::CrosstermBackend,
-    layout::{Constraint, Direction, Layout as RatatuiLayout, Rect},
+    layout::{Alignment, Constraint, Direction, Flex, Layout as RatatuiLayout, Rect},
     style::{Modifier, Style},
     text::{Line, Span},
     widgets::{Block, Borders, Clear, Paragraph, Wrap},
     Frame, Terminal,
 };
+use std::cell::{Cell, RefCell};
+use std::collections::VecDeque;
 use std::io;
 use std::path::PathBuf;
-use std::time::Duration;
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::time::{Duration, Instant};
 
+use crate::app::startup_profile::StartupProfile;
+use crate::atomic_write;
 use crate::config::Config;
-use crate::firmware::BuildState;
+use crate::firmware::validator::ValidationReport;
+use crate::firmware::{BuildState, BuildStatus, KeyboardMismatch, ValidationCache};
 use crate::keycode_db::KeycodeDb;
-use crate::models::{KeyboardGeometry, Layout, Position, VisualLayoutMapping};
+use crate::models::{KeyRole, KeyboardGeometry, Layer, Layout, Position, VisualLayoutMapping};
 use crate::services::geometry::{
-    build_geometry_for_layout, extract_base_keyboard, GeometryContext,
+    adjust_layout_to_mapping, build_geometry_for_layout, detect_geometry_mismatch,
+    extract_base_keyboard, GeometryAdjustReport, GeometryContext, GeometryMismatch,
+};
+use crate::services::history::{
+    HistoryEntry, HistoryOperation, HistoryService, DEFAULT_HISTORY_RETENTION,
 };
+use crate::services::key_roles::{infer_roles, layout_roles};
+use crate::services::keycode_grid::{GridPlan, KeycodeGrid};
 use crate::services::layer_refs::{build_layer_ref_index, LayerRef};
+use crate::services::mirror::MirrorPlan;
+use crate::services::templates::{TemplateSection, TemplateSections};
+use crate::shortcuts::ShortcutRegistry;
+use handlers::modifier_wrap::PendingWrap;
+use key_repeat::{InputBatch, NavAccelerator, NavDirection};
 use std::collections::HashMap;
+use text_input::TextInput;
 
 // Re-export TUI components
+pub use action_log::{ActionLog, ActionLogView};
 pub use build_log::BuildLog;
 pub use category_manager::{CategoryManager, CategoryManagerState};
 pub use category_picker::{CategoryPicker, CategoryPickerEvent};
 pub use color_picker::ColorPicker;
+pub use command_palette::CommandPalette;
 pub use component::{Component, ContextualComponent};
 pub use config_dialogs::{
     LayoutPicker as LayoutVariantPicker, LayoutPickerEvent as LayoutVariantPickerEvent,
 };
+pub use diff_view::DiffView;
+pub use encoder_editor::EncoderEditor;
+pub use generation_results::GenerationResultsView;
+pub use grid_paste::GridPasteView;
 pub use help_overlay::HelpOverlay;
+pub use history::HistoryView;
 pub use key_editor::KeyEditorState;
+pub use key_role_editor::KeyRoleEditor;
 pub use keyboard::KeyboardWidget;
 pub use keycode_picker::KeycodePicker;
+pub use keycode_search::KeycodeSearchView;
 pub use layer_manager::{LayerManager, LayerManagerEvent};
 #[allow(unused_imports)]
 pub use layer_picker::{LayerPicker, LayerPickerState};
 pub use layout_picker::LayoutPicker;
 // MetadataEditor component migrated in Wave 4c - uses Component trait pattern
 // SettingsManager component migrated in Wave 8 - uses custom ContextualComponent pattern
+pub use lint_report::LintReportView;
 pub use modifier_picker::ModifierPicker;
+pub use snapshot_browser::SnapshotBrowser;
 pub use status_bar::StatusBar;
 pub use template_browser::TemplateBrowser;
 pub use theme::Theme;
+pub use tutorial::Tutorial;
+pub use validation_banner::ValidationBanner;
+pub use validation_results::ValidationResultsView;
 
 // Import handler functions from the handlers module
 
@@ -161,7 +221,7 @@ pub enum TapDanceFormContext {
 #[derive(Debug, Clone)]
 pub struct ExportFilenameDialogState {
     /// Export filename input
-    pub filename: String,
+    pub filename: TextInput,
 }
 
 impl ExportFilenameDialogState {
@@ -171,7 +231,7 @@ impl ExportFilenameDialogState {
         let date = chrono::Local::now().format("%Y-%m-%d");
         let default_filename = format!("{layout_name}_export_{date}.md");
         Self {
-            filename: default_filename,
+            filename: TextInput::from(default_filename),
         }
     }
 }
@@ -188,55 +248,81 @@ pub struct TemplateSaveDialogState {
     /// Current input field
     pub active_field: TemplateSaveField,
     /// Template name
-    pub name: String,
+    pub name: TextInput,
     /// Template description
-    pub description: String,
+    pub description: TextInput,
     /// Template author
-    pub author: String,
+    pub author: TextInput,
     /// Template tags (comma-separated input)
-    pub tags_input: String,
+    pub tags_input: TextInput,
+    /// Layout sections the template includes
+    pub sections: TemplateSections,
+    /// Index into [`TemplateSection::ALL`] of the highlighted checkbox
+    pub selected_section: usize,
 }
 
 impl TemplateSaveDialogState {
     /// Creates a new template save dialog state with pre-filled values from current layout.
     #[must_use]
-    pub const fn new(layout_name: String) -> Self {
+    pub fn new(layout_name: String) -> Self {
         Self {
             active_field: TemplateSaveField::Name,
-            name: layout_name,
-            description: String::new(),
-            author: String::new(),
-            tags_input: String::new(),
+            name: TextInput::from(layout_name),
+            description: TextInput::default(),
+            author: TextInput::default(),
+            tags_input: TextInput::default(),
+            sections: TemplateSections::FULL,
+            selected_section: 0,
         }
     }
 
-    /// Get the active field's input string (mutable).
-    pub const fn get_active_field_mut(&mut self) -> &mut String {
+    /// Get the active field's input string (mutable); `None` for the
+    /// section checkboxes.
+    pub const fn get_active_field_mut(&mut self) -> Option<&mut TextInput> {
         match self.active_field {
-            TemplateSaveField::Name => &mut self.name,
-            TemplateSaveField::Description => &mut self.description,
-            TemplateSaveField::Author => &mut self.author,
-            TemplateSaveField::Tags => &mut self.tags_input,
+            TemplateSaveField::Name => Some(&mut self.name),
+            TemplateSaveField::Description => Some(&mut self.description),
+            TemplateSaveField::Author => Some(&mut self.author),
+            TemplateSaveField::Tags => Some(&mut self.tags_input),
+            TemplateSaveField::Sections => None,
         }
     }
 
+    /// The highlighted section checkbox.
+    #[must_use]
+    pub fn selected_section(&self) -> TemplateSection {
+        TemplateSection::ALL[self.selected_section % TemplateSection::ALL.len()]
+    }
+
+    /// Moves the checkbox highlight up or down, wrapping around.
+    pub const fn move_section(&mut self, down: bool) {
+        let count = TemplateSection::ALL.len();
+        self.selected_section = if down {
+            (self.selected_section + 1) % count
+        } else {
+            (self.selected_section + count - 1) % count
+        };
+    }
+
     /// Move to the next field.
     pub const fn next_field(&mut self) {
         self.active_field = match self.active_field {
             TemplateSaveField::Name => TemplateSaveField::Description,
             TemplateSaveField::Description => TemplateSaveField::Author,
             TemplateSaveField::Author => TemplateSaveField::Tags,
-            TemplateSaveField::Tags => TemplateSaveField::Name,
+            TemplateSaveField::Tags => TemplateSaveField::Sections,
+            TemplateSaveField::Sections => TemplateSaveField::Name,
         };
     }
 
     /// Move to the previous field.
     pub const fn previous_field(&mut self) {
         self.active_field = match self.active_field {
-            TemplateSaveField::Name => TemplateSaveField::Tags,
+            TemplateSaveField::Name => TemplateSaveField::Sections,
             TemplateSaveField::Description => TemplateSaveField::Name,
             TemplateSaveField::Author => TemplateSaveField::Description,
             TemplateSaveField::Tags => TemplateSaveField::Author,
+            TemplateSaveField::Sections => TemplateSaveField::Tags,
         };
     }
 
@@ -244,6 +330,7 @@ impl TemplateSaveDialogState {
     #[must_use]
     pub fn parse_tags(&self) -> Vec<String> {
         self.tags_input
+            .value()
             .split(',')
             .map(|s| s.trim().to_lowercase())
             .filter(|s| !s.is_empty())
@@ -268,6 +355,8 @@ pub enum TemplateSaveField {
     Author,
     /// Template tags field
     Tags,
+    /// Section checkboxes
+    Sections,
 }
 
 /// Popup types that can be displayed over the main UI
@@ -290,6 +379,28 @@ pub enum PopupType {
     TemplateBrowser,
     /// Template save dialog popup
     TemplateSaveDialog,
+    /// Snapshot browser popup
+    SnapshotBrowser,
+    /// Keycode search popup
+    KeycodeSearch,
+    /// Lint report popup
+    LintReport,
+    /// Firmware validation results popup
+    ValidationResults,
+    /// Firmware generation results popup
+    GenerationResults,
+    /// Session action log popup
+    ActionLog,
+    /// Generate/build history popup
+    History,
+    /// Keycode grid paste popup
+    GridPaste,
+    /// Key role editor popup
+    KeyRoleEditor,
+    /// Encoder editor popup
+    EncoderEditor,
+    /// Command palette popup
+    CommandPalette,
     /// Export filename dialog popup
     ExportFilenameDialog,
     /// Help overlay popup
@@ -300,6 +411,12 @@ pub enum PopupType {
     MetadataEditor,
     /// Unsaved changes confirmation popup
     UnsavedChangesPrompt,
+    /// Layout with keys outside the geometry, shown on load
+    GeometryMismatch,
+    /// Layout keyboard differs from the build target, shown before generating
+    KeyboardMismatch,
+    /// Layout changed since the last generation, shown before building
+    StaleGeneration,
     /// Layout picker popup
     LayoutPicker,
     /// Setup wizard popup
@@ -318,6 +435,24 @@ pub enum PopupType {
     TapDanceForm,
 }
 
+/// Firmware action that can wait on a confirmation
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum FirmwareAction {
+    /// Generate firmware files (Ctrl+G)
+    Generate,
+    /// Generate and compile (Ctrl+B)
+    Build,
+}
+
+/// Layer peek: another layer drawn in place of the current one (Alt+P)
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Peek {
+    /// Waiting for the number of the layer to peek at
+    Choosing,
+    /// Drawing this layer until the next other key
+    Showing(usize),
+}
+
 /// Selection mode for multi-key operations
 #[derive(Debug, Clone, PartialEq, Eq)]
 pub enum SelectionMode {
@@ -355,6 +490,28 @@ pub enum ActiveComponent {
     MetadataEditor(metadata_editor::MetadataEditor),
     /// Template browser component
     TemplateBrowser(TemplateBrowser),
+    /// Snapshot browser component
+    SnapshotBrowser(SnapshotBrowser),
+    /// Keycode search component
+    KeycodeSearch(KeycodeSearchView),
+    /// Lint report component
+    LintReport(LintReportView),
+    /// Firmware validation report component
+    ValidationResults(ValidationResultsView),
+    /// Firmware generation results component
+    GenerationResults(GenerationResultsView),
+    /// Session action log component
+    ActionLog(ActionLogView),
+    /// Generate/build history component
+    History(HistoryView),
+    /// Keycode grid paste component
+    GridPaste(GridPasteView),
+    /// Key role editor component
+    KeyRoleEditor(KeyRoleEditor),
+    /// Encoder editor component
+    EncoderEditor(EncoderEditor),
+    /// Command palette component
+    CommandPalette(CommandPalette),
     /// Layout picker component (for loading saved layouts)
     LayoutPicker(LayoutPicker),
     /// Tap dance editor component
@@ -375,6 +532,7 @@ pub enum ActiveComponent {
 ///
 /// All UI components read from this state immutably.
 /// Only event handlers modify state explicitly.
+#[allow(clippy::struct_excessive_bools)] // Independent editor flags
 pub struct AppState {
     // Core data
     /// Current keyboard layout
@@ -383,18 +541,22 @@ pub struct AppState {
     pub source_path: Option<PathBuf>,
     /// Whether layout has unsaved changes
     pub dirty: bool,
+    /// Layers as of the last edit, to find which ones the next edit touched
+    pub layer_baseline: Vec<Layer>,
 
     // UI state
     /// Current UI theme
     pub theme: Theme,
-    /// Currently displayed layer index
+    /// Layer being edited; in the split view, the focused pane's layer
     pub current_layer: usize,
-    /// Currently selected key position
+    /// Currently selected key position (of the focused pane in the split view)
     pub selected_position: Position,
     /// Currently active popup (if any)
     pub active_popup: Option<PopupType>,
     /// Status bar message
     pub status_message: String,
+    /// Log of state-mutating actions this session
+    pub action_log: ActionLog,
     /// Optional color override for status message (warnings/errors rendered via status)
     pub status_color_override: Option<ratatui::style::Color>,
     /// Current error message (if any)
@@ -423,18 +585,43 @@ pub struct AppState {
     pub tap_dance_form_cache: Option<tap_dance_form::TapDanceForm>,
     /// Tap dance form picker target (which field is being picked)
     pub tap_dance_form_pick_target: Option<tap_dance_form::FormRow>,
+    /// Encoder editor cache (preserved while picking a keycode for it)
+    pub encoder_editor_cache: Option<EncoderEditor>,
     /// Tap dance form context (where flow was launched from)
     pub tap_dance_form_context: Option<TapDanceFormContext>,
     /// Key editor component state
     pub key_editor_state: KeyEditorState,
     /// Key clipboard for copy/cut/paste operations
     pub clipboard: clipboard::KeyClipboard,
+    /// Undo/redo history of layout edits
+    pub undo_history: undo::UndoHistory,
     /// Flash highlight position (for paste feedback) - (layer, position, `remaining_frames`)
     pub flash_highlight: Option<(usize, Position, u8)>,
     /// Visual selection mode for multi-key operations
     pub selection_mode: Option<SelectionMode>,
     /// Selected keys in selection mode (positions on current layer)
     pub selected_keys: Vec<Position>,
+    /// Key marked for swapping as (layer, position); highlighted until the
+    /// swap or Esc (s)
+    pub pending_swap: Option<(usize, Position)>,
+    /// Mirror waiting for confirmation; its targets are highlighted (Shift+M)
+    pub pending_mirror: Option<MirrorPlan>,
+    /// Keys waiting for the modifier to wrap them in (Shift+W)
+    pub pending_wrap: Option<PendingWrap>,
+    /// Diff against a snapshot or the saved file shown on the keyboard
+    pub diff_view: Option<DiffView>,
+    /// Typing simulation following the layout's layer switching (Shift+A)
+    pub simulation: Option<simulation::Simulation>,
+    /// Layer peek; the keyboard draws the peeked layer while editing stays
+    /// on `current_layer` (Alt+P)
+    pub peek: Option<Peek>,
+    /// Two layers side by side (`\`); holds the pane without focus
+    pub split_view: Option<split_view::SplitView>,
+    /// Base-layer legends drawn under the current layer's keys (`o`), computed
+    /// when the overlay is toggled on or the layer changes, not per frame
+    pub base_overlay: Option<HashMap<Position, String>>,
+    /// Debug overlay showing key coordinates (Shift+O cycles)
+    pub coordinate_overlay: keyboard::CoordinateOverlay,
 
     // System resources
     /// Keycode database
@@ -449,6 +636,8 @@ pub struct AppState {
     // Firmware build state
     /// Current firmware build state (if building)
     pub build_state: Option<BuildState>,
+    /// Messages from background tasks, drained by the event loop
+    pub messages: messages::MessageQueue,
 
     // Layer reference tracking
     /// Index of layer references (which keys on which layers reference this layer)
@@ -460,6 +649,35 @@ pub struct AppState {
     pub should_quit: bool,
     /// Whether to return to settings manager after picker closes
     pub return_to_settings_after_picker: bool,
+    /// Startup timings (`--profile-startup`), completed by the first frame
+    pub startup_profile: Option<StartupProfile>,
+    /// Consecutive navigation moves, for held-key acceleration
+    pub nav_accelerator: NavAccelerator,
+    /// Last firmware validation, reused while the layout is unchanged
+    pub validation_cache: ValidationCache,
+    /// Per-key draw data of the keyboard widget, reused across frames
+    pub key_render_cache: RefCell<keyboard::KeyRenderCache>,
+    /// Where the keyboard was last drawn, for mapping mouse clicks to keys
+    pub keyboard_area: Cell<Rect>,
+    /// Most recent validation report shown, for reopening with Shift+G
+    pub last_validation: Option<ValidationReport>,
+    /// Issue count shown in the banner under the title bar
+    pub validation_banner: ValidationBanner,
+    /// First-run tutorial in progress
+    pub tutorial: Option<Tutorial>,
+    /// History entry of the running build, recorded when it finishes
+    pub pending_build_history: Option<HistoryEntry>,
+    /// Layout as opened with `--read-only`; any edit is reverted to it
+    pub read_only_layout: Option<Layout>,
+    /// Keys outside the geometry found on load, awaiting the user's choice
+    pub geometry_mismatch: Option<GeometryMismatch>,
+    /// Generate or build waiting for confirmation because the layout's
+    /// keyboard differs from the build target
+    pub keyboard_mismatch: Option<(KeyboardMismatch, FirmwareAction)>,
+    /// Content hash of the layout at the last successful generation
+    pub generation_hash: Option<String>,
+    /// Start builds without checking the toolchain first (`--skip-env-check`)
+    pub skip_env_check: bool,
 }
 
 impl AppState {
@@ -488,6 +706,22 @@ impl AppState {
         config: Config,
     ) -> Result<Self> {
         let keycode_db = KeycodeDb::load().context("Failed to load keycode database")?;
+        Self::with_keycode_db(layout, source_path, geometry, mapping, config, keycode_db)
+    }
+
+    /// Creates a new `AppState` with an already loaded keycode database.
+    ///
+    /// # Errors
+    ///
+    /// Returns error if config or layout validation fails
+    pub fn with_keycode_db(
+        layout: Layout,
+        source_path: Option<PathBuf>,
+        geometry: KeyboardGeometry,
+        mapping: VisualLayoutMapping,
+        config: Config,
+        keycode_db: KeycodeDb,
+    ) -> Result<Self> {
         let theme = Theme::detect();
 
         // Initialize selected position to first valid key position
@@ -497,8 +731,10 @@ impl AppState {
 
         // Build initial layer reference index
         let layer_refs = build_layer_ref_index(&layout.layers);
+        let undo_history = undo::UndoHistory::new(&layout);
 
         Ok(Self {
+            layer_baseline: layout.layers.clone(),
             layout,
             source_path,
             dirty: false,
@@ -507,6 +743,7 @@ impl AppState {
             selected_position,
             active_popup: None,
             status_message: "Press ? for help".to_string(),
+            action_log: ActionLog::new(),
             status_color_override: None,
             error_message: None,
             active_component: None,
@@ -518,24 +755,122 @@ impl AppState {
             pending_keycode: PendingKeycodeState::new(),
             tap_dance_form_cache: None,
             tap_dance_form_pick_target: None,
+            encoder_editor_cache: None,
             tap_dance_form_context: None,
             key_editor_state: KeyEditorState::new(),
 
             clipboard: clipboard::KeyClipboard::new(),
+            undo_history,
             flash_highlight: None,
             selection_mode: None,
             selected_keys: Vec::new(),
+            pending_swap: None,
+            pending_mirror: None,
+            pending_wrap: None,
+            diff_view: None,
+            simulation: None,
+            peek: None,
+            split_view: None,
+            base_overlay: None,
+            coordinate_overlay: keyboard::CoordinateOverlay::Off,
             keycode_db,
             geometry,
             mapping,
             config,
             build_state: None,
+            messages: messages::MessageQueue::new(),
             layer_refs,
             should_quit: false,
             return_to_settings_after_picker: false,
+            startup_profile: None,
+            nav_accelerator: NavAccelerator::default(),
+            validation_cache: ValidationCache::default(),
+            key_render_cache: RefCell::default(),
+            keyboard_area: Cell::default(),
+            last_validation: None,
+            validation_banner: ValidationBanner::default(),
+            tutorial: None,
+            pending_build_history: None,
+            read_only_layout: None,
+            geometry_mismatch: None,
+            keyboard_mismatch: None,
+            generation_hash: None,
+            skip_env_check: false,
         })
     }
 
+    /// Opens the editor on `layer` with `position` selected.
+    ///
+    /// # Errors
+    ///
+    /// Returns error if the layer does not exist or no key sits at the
+    /// position.
+    pub fn focus(&mut self, layer: Option<usize>, position: Option<Position>) -> Result<()> {
+        if let Some(layer) = layer {
+            if layer >= self.layout.layers.len() {
+                anyhow::bail!(
+                    "Layer {layer} does not exist (layout has {} layers)",
+                    self.layout.layers.len()
+                );
+            }
+            self.current_layer = layer;
+        }
+        if let Some(position) = position {
+            let layer = &self.layout.layers[self.current_layer];
+            if !layer.keys.iter().any(|k| k.position == position) {
+                anyhow::bail!(
+                    "No key at position {},{} in this layout",
+                    position.row,
+                    position.col
+                );
+            }
+            self.selected_position = position;
+        }
+        self.refresh_base_overlay();
+        Ok(())
+    }
+
+    /// Puts the editor in read-only mode: edits are reverted and saving is
+    /// refused.
+    pub fn set_read_only(&mut self) {
+        self.read_only_layout = Some(self.layout.clone());
+        self.set_status("Read-only: changes will not be kept");
+    }
+
+    /// Returns true if the editor was opened with `--read-only`.
+    #[must_use]
+    pub const fn is_read_only(&self) -> bool {
+        self.read_only_layout.is_some()
+    }
+
+    /// Reverts any change made in read-only mode.
+    ///
+    /// Returns true if there was something to revert.
+    pub fn revert_read_only_edits(&mut self) -> bool {
+        let Some(original) = &self.read_only_layout else {
+            return false;
+        };
+        if !self.dirty {
+            return false;
+        }
+        self.layout = original.clone();
+        self.layer_baseline = self.layout.layers.clone();
+        self.undo_history.reset(&self.layout);
+        self.dirty = false;
+        self.invalidate_validation();
+        self.key_render_cache.get_mut().invalidate();
+        self.current_layer = self
+            .current_layer
+            .min(self.layout.layers.len().saturating_sub(1));
+        self.refresh_layer_refs();
+        self.refresh_base_overlay();
+        self.set_status_with_style(
+            "Read-only: change discarded (reopen without --read-only to edit)",
+            self.theme.warning,
+        );
+        true
+    }
+
     /// Get the currently selected key (mutable)
     pub fn get_selected_key_mut(&mut self) -> Option<&mut crate::models::KeyDefinition> {
         let layer = self.layout.layers.get_mut(self.current_layer)?;
@@ -545,6 +880,90 @@ impl AppState {
             .find(|k| k.position == self.selected_position)
     }
 
+    /// The open color picker's in-progress color and the keys it targets.
+    #[must_use]
+    pub fn color_preview(&self) -> Option<keyboard::ColorPreview> {
+        use component::ColorPickerContext;
+        use keyboard::PreviewTarget;
+
+        let Some(ActiveComponent::ColorPicker(picker)) = &self.active_component else {
+            return None;
+        };
+        // The eyedropper shows the board's actual colors
+        if picker.is_sampling() {
+            return None;
+        }
+        let target = match picker.get_context() {
+            ColorPickerContext::IndividualKey => PreviewTarget::Keys(vec![self.selected_position]),
+            ColorPickerContext::MultiKeySelection => {
+                PreviewTarget::Keys(self.selected_keys.clone())
+            }
+            ColorPickerContext::LayerDefault => PreviewTarget::LayerDefault,
+            ColorPickerContext::Category => {
+                // A category being created isn't on any key yet
+                if self.category_manager_state.mode != category_manager::ManagerMode::Browsing {
+                    return None;
+                }
+                let category = self
+                    .layout
+                    .categories
+                    .get(self.category_manager_state.selected)?;
+                PreviewTarget::Category(category.id.clone())
+            }
+        };
+        Some(keyboard::ColorPreview {
+            color: picker.color(),
+            target,
+        })
+    }
+
+    /// Recomputes the base layer overlay legends if the overlay is on.
+    pub fn refresh_base_overlay(&mut self) {
+        if self.base_overlay.is_some() {
+            self.base_overlay = Some(keyboard::KeyboardWidget::base_layer_legends(self));
+        }
+    }
+
+    /// Reverts the most recent edit, returning to the layer it was made on.
+    ///
+    /// Returns the edit's label, or `None` if there is nothing to undo.
+    pub fn undo(&mut self) -> Option<String> {
+        let entry = self.undo_history.undo()?;
+        let (layout, layer, label) = (
+            entry.before.clone(),
+            entry.layer,
+            entry.describe().to_string(),
+        );
+        self.restore_history_layout(layout, layer);
+        Some(label)
+    }
+
+    /// Re-applies the most recently undone edit.
+    ///
+    /// Returns the edit's label, or `None` if there is nothing to redo.
+    pub fn redo(&mut self) -> Option<String> {
+        let entry = self.undo_history.redo()?;
+        let (layout, layer, label) = (
+            entry.after.clone(),
+            entry.layer,
+            entry.describe().to_string(),
+        );
+        self.restore_history_layout(layout, layer);
+        Some(label)
+    }
+
+    /// Replaces the layout with one from the undo history.
+    fn restore_history_layout(&mut self, layout: Layout, layer: usize) {
+        self.layout = layout;
+        self.layer_baseline.clone_from(&self.layout.layers);
+        self.dirty = true;
+        self.invalidate_validation();
+        self.key_render_cache.get_mut().invalidate();
+        self.current_layer = layer.min(self.layout.layers.len().saturating_sub(1));
+        self.refresh_layer_refs();
+        self.refresh_base_overlay();
+    }
+
     /// Get the currently selected key (immutable)
     #[must_use]
     pub fn get_selected_key(&self) -> Option<&crate::models::KeyDefinition> {
@@ -556,8 +975,30 @@ impl AppState {
     }
 
     /// Mark layout as dirty (unsaved changes)
-    pub const fn mark_dirty(&mut self) {
+    pub fn mark_dirty(&mut self) {
+        self.dirty = true;
+        self.stamp_changed_layers();
+        self.undo_history.record(&self.layout, self.current_layer);
+        self.invalidate_validation();
+        self.key_render_cache.get_mut().invalidate();
+    }
+
+    /// Mark layout as dirty after an edit of the selected key only, so the
+    /// keyboard widget redraws just that key
+    pub fn mark_selected_key_dirty(&mut self) {
         self.dirty = true;
+        self.stamp_changed_layers();
+        self.undo_history.record(&self.layout, self.current_layer);
+        self.invalidate_validation();
+        self.key_render_cache
+            .get_mut()
+            .invalidate_key(self.current_layer, self.selected_position);
+    }
+
+    /// Stamps `modified` on layers that changed since the previous edit
+    fn stamp_changed_layers(&mut self) {
+        self.layout.stamp_changed_layers(&self.layer_baseline);
+        self.layer_baseline.clone_from(&self.layout.layers);
     }
 
     /// Clear dirty flag (after save)
@@ -597,6 +1038,7 @@ impl AppState {
         // Update AppState with new geometry and mapping
         self.geometry = geo_result.geometry;
         self.mapping = geo_result.mapping;
+        self.key_render_cache.get_mut().invalidate();
 
         // Store the layout variant in the layout metadata for persistence
         self.layout.metadata.layout_variant = Some(layout_name.to_string());
@@ -625,40 +1067,112 @@ impl AppState {
     /// - Existing keys at valid positions are preserved
     ///
     /// Call this after loading a layout to ensure keys match the geometry.
-    pub fn adjust_layers_to_geometry(&mut self) -> Result<()> {
-        use crate::models::layer::KeyDefinition;
+    /// Returns a report of the keys that were added and removed.
+    pub fn adjust_layers_to_geometry(&mut self) -> Result<GeometryAdjustReport> {
+        Ok(adjust_layout_to_mapping(&mut self.layout, &self.mapping))
+    }
+
+    /// Fits the layers to the geometry on load, unless some layer has keys
+    /// the geometry doesn't; then the geometry mismatch dialog asks whether
+    /// to open read-only, fit the layers, or not open the layout.
+    pub fn check_geometry_on_load(&mut self) -> Result<()> {
+        match detect_geometry_mismatch(&self.layout, &self.mapping) {
+            Some(mismatch) => {
+                self.geometry_mismatch = Some(mismatch);
+                self.active_popup = Some(PopupType::GeometryMismatch);
+            }
+            None => {
+                self.adjust_layers_to_geometry()?;
+            }
+        }
+        Ok(())
+    }
 
-        // Get all valid positions from the mapping
-        let valid_positions: std::collections::HashSet<Position> = self
+    /// Resolves the geometry mismatch dialog by fitting the layers to the
+    /// geometry, either kept as an edit or as the read-only baseline.
+    ///
+    /// Returns the keys that were added and removed.
+    pub fn resolve_geometry_mismatch(&mut self, read_only: bool) -> Result<GeometryAdjustReport> {
+        let report = self.adjust_layers_to_geometry()?;
+        self.geometry_mismatch = None;
+        self.active_popup = None;
+        if !self
             .mapping
             .get_all_visual_positions()
-            .into_iter()
-            .collect();
-
-        // Adjust each layer
-        for layer in &mut self.layout.layers {
-            // Keep only keys that are still in valid positions
-            layer
-                .keys
-                .retain(|key| valid_positions.contains(&key.position));
-
-            // Find which positions are missing
-            let existing_positions: std::collections::HashSet<Position> =
-                layer.keys.iter().map(|k| k.position).collect();
-
-            let missing_positions: Vec<Position> = valid_positions
-                .iter()
-                .filter(|pos| !existing_positions.contains(pos))
-                .copied()
-                .collect();
-
-            // Add KC_NO keys for missing positions
-            for pos in missing_positions {
-                layer.add_key(KeyDefinition::new(pos, "KC_NO"));
+            .contains(&self.selected_position)
+        {
+            if let Some(first) = self.mapping.get_first_position() {
+                self.selected_position = first;
             }
         }
+        self.refresh_layer_refs();
+        self.refresh_base_overlay();
+        if read_only {
+            // The fitted layout is what's shown; the file stays as it is
+            self.layer_baseline.clone_from(&self.layout.layers);
+            self.undo_history.reset(&self.layout);
+            self.invalidate_validation();
+            self.key_render_cache.get_mut().invalidate();
+            self.set_read_only();
+        } else {
+            self.mark_dirty();
+        }
+        Ok(report)
+    }
 
-        Ok(())
+    /// Layer the keyboard draws: the peeked layer while peeking, otherwise
+    /// the current layer.
+    #[must_use]
+    pub fn display_layer(&self) -> usize {
+        if let Some(ActiveComponent::LayerManager(manager)) = &self.active_component {
+            if let Some(layer) = manager.moving_layer() {
+                return layer;
+            }
+        }
+        match self.peek {
+            Some(Peek::Showing(layer))
+                if self.active_popup.is_none() && layer < self.layout.layers.len() =>
+            {
+                layer
+            }
+            _ => self.current_layer,
+        }
+    }
+
+    /// Opens the split view with the next layer in the right pane, or
+    /// closes it keeping the focused pane.
+    ///
+    /// Returns whether the split view is now open.
+    pub fn toggle_split_view(&mut self) -> bool {
+        if self.split_view.take().is_some() {
+            return false;
+        }
+        let layer_count = self.layout.layers.len().max(1);
+        self.split_view = Some(split_view::SplitView::new(split_view::PaneState {
+            layer: (self.current_layer + 1) % layer_count,
+            selected_position: self.selected_position,
+            selection_mode: None,
+            selected_keys: Vec::new(),
+        }));
+        true
+    }
+
+    /// Moves the focus to the split view's other pane, swapping in its
+    /// layer, selected key and multi-selection.
+    ///
+    /// Returns the side now focused, or `None` outside the split view.
+    pub fn focus_other_pane(&mut self) -> Option<split_view::PaneSide> {
+        let split = self.split_view.as_mut()?;
+        let other = &mut split.other;
+        // Layer moves and deletions remap the other pane's layer, but undo can
+        // still take its layer away
+        other.layer = other.layer.min(self.layout.layers.len().saturating_sub(1));
+        std::mem::swap(&mut self.current_layer, &mut other.layer);
+        std::mem::swap(&mut self.selected_position, &mut other.selected_position);
+        std::mem::swap(&mut self.selection_mode, &mut other.selection_mode);
+        std::mem::swap(&mut self.selected_keys, &mut other.selected_keys);
+        split.focus = split.focus.other();
+        Some(split.focus)
     }
 
     /// Set status message
@@ -679,6 +1193,15 @@ impl AppState {
         self.status_color_override = Some(color);
     }
 
+    /// Record a state-mutating action in the session log and show its summary
+    /// in the status bar.
+    pub fn log_action(&mut self, action: &str, summary: impl Into<String>) {
+        let summary = summary.into();
+        self.undo_history.label(&summary);
+        self.action_log.record(action, summary.clone());
+        self.set_status(summary);
+    }
+
     /// Set error message
     pub fn set_error(&mut self, error: impl Into<String>) {
         self.error_message = Some(error.into());
@@ -745,7 +1268,11 @@ impl AppState {
 
     /// Open the layer manager component
     pub fn open_layer_manager(&mut self) {
-        let manager = LayerManager::new(self.layout.layers.clone(), self.current_layer);
+        let manager = LayerManager::new(
+            self.layout.layers.clone(),
+            self.layout.tap_dances.clone(),
+            self.current_layer,
+        );
         self.active_component = Some(ActiveComponent::LayerManager(manager));
         self.active_popup = Some(PopupType::LayerManager);
     }
@@ -764,6 +1291,270 @@ impl AppState {
         self.active_popup = Some(PopupType::TemplateBrowser);
     }
 
+    /// Open the snapshot browser component
+    pub fn open_snapshot_browser(&mut self) {
+        let browser = SnapshotBrowser::new(&self.layout, self.source_path.as_deref());
+        self.active_component = Some(ActiveComponent::SnapshotBrowser(browser));
+        self.active_popup = Some(PopupType::SnapshotBrowser);
+    }
+
+    /// Open the keycode search over every layer of the current layout
+    pub fn open_keycode_search(&mut self) {
+        let view = KeycodeSearchView::new(&self.layout, &self.keycode_db);
+        self.active_component = Some(ActiveComponent::KeycodeSearch(view));
+        self.active_popup = Some(PopupType::KeycodeSearch);
+    }
+
+    /// Open the lint report for the current layout
+    pub fn open_lint_report(&mut self) {
+        // Without a keyboard geometry the layout's own keys stand in for it
+        let view = if self.mapping.roles.is_empty() {
+            LintReportView::new(
+                &self.layout,
+                &layout_roles(&self.layout),
+                &self.config.lint.disable,
+            )
+        } else {
+            LintReportView::new(&self.layout, &self.mapping.roles, &self.config.lint.disable)
+        };
+        self.active_component = Some(ActiveComponent::LintReport(view));
+        self.active_popup = Some(PopupType::LintReport);
+    }
+
+    /// Validate the current layout for firmware generation and show the
+    /// results.
+    ///
+    /// # Errors
+    ///
+    /// Returns error if validation could not run.
+    pub fn open_validation_results(&mut self) -> Result<()> {
+        let report = self.validation_cache.validate(
+            &self.layout,
+            &self.geometry,
+            &self.mapping,
+            &self.keycode_db,
+        )?;
+        self.show_validation_results(report);
+        Ok(())
+    }
+
+    /// Drops the cached validation, so the banner and the next validation
+    /// see the edited layout.
+    fn invalidate_validation(&mut self) {
+        self.validation_cache.invalidate();
+        self.validation_banner.invalidate();
+    }
+
+    /// Revalidates the layout for the validation banner if it changed since
+    /// the last check. Cheap when nothing changed: the report comes from the
+    /// validation cache.
+    pub fn refresh_validation_banner(&mut self) {
+        if !self.config.ui.validation_banner || !self.validation_banner.is_stale() {
+            return;
+        }
+        // A layout the validator can't process gets no banner; generation
+        // reports the failure itself
+        let issues = self
+            .validation_cache
+            .validate(
+                &self.layout,
+                &self.geometry,
+                &self.mapping,
+                &self.keycode_db,
+            )
+            .map_or(0, |report| report.errors.len() + report.warnings.len());
+        self.validation_banner.update(issues);
+    }
+
+    /// Text of the validation banner, if it is shown.
+    #[must_use]
+    pub fn validation_banner_message(&self) -> Option<String> {
+        self.config
+            .ui
+            .validation_banner
+            .then(|| self.validation_banner.message())
+            .flatten()
+    }
+
+    /// Show a validation report and remember it for [`Self::review_validation_results`].
+    pub fn show_validation_results(&mut self, report: ValidationReport) {
+        let view = ValidationResultsView::new(&report);
+        self.active_component = Some(ActiveComponent::ValidationResults(view));
+        self.active_popup = Some(PopupType::ValidationResults);
+        self.last_validation = Some(report);
+    }
+
+    /// Show the files written by firmware generation, or why it failed
+    pub fn show_generation_results(&mut self, view: GenerationResultsView) {
+        self.set_status(view.summary());
+        self.active_component = Some(ActiveComponent::GenerationResults(view));
+        self.active_popup = Some(PopupType::GenerationResults);
+    }
+
+    /// Reopen the most recent validation report as it was, without
+    /// revalidating. Returns false if nothing has been validated yet.
+    pub fn review_validation_results(&mut self) -> bool {
+        let Some(report) = self.last_validation.take() else {
+            return false;
+        };
+        self.show_validation_results(report);
+        true
+    }
+
+    /// Open the session action log
+    pub fn open_action_log(&mut self) {
+        self.active_component = Some(ActiveComponent::ActionLog(ActionLogView::new()));
+        self.active_popup = Some(PopupType::ActionLog);
+    }
+
+    /// Open the generate/build history of the layout file
+    pub fn open_history(&mut self) {
+        let view = match &self.source_path {
+            Some(path) => match HistoryService::load(path) {
+                Ok(history) => HistoryView::new(history),
+                Err(e) => HistoryView::unavailable(format!("Failed to read history: {e}")),
+            },
+            None => HistoryView::unavailable("Save the layout to start recording its history"),
+        };
+        self.active_component = Some(ActiveComponent::History(view));
+        self.active_popup = Some(PopupType::History);
+    }
+
+    /// Open the keycode grid paste popup.
+    ///
+    /// With `text` (e.g. from a bracketed paste) the grid is previewed right
+    /// away; otherwise the popup asks for a grid or a file path.
+    pub fn open_grid_paste(&mut self, text: Option<&str>) {
+        let view = match text {
+            Some(text) => match self.plan_grid(text) {
+                Ok(plan) => GridPasteView::preview(plan),
+                Err(e) => {
+                    self.set_error(format!("Invalid keycode grid: {e}"));
+                    return;
+                }
+            },
+            None => GridPasteView::entry(),
+        };
+        self.active_component = Some(ActiveComponent::GridPaste(view));
+        self.active_popup = Some(PopupType::GridPaste);
+    }
+
+    /// Parses `text` as a keycode grid and places it on the current layer,
+    /// starting at the selected key.
+    pub fn plan_grid(&self, text: &str) -> Result<GridPlan> {
+        let grid = KeycodeGrid::parse(text)?;
+        let layer = self
+            .layout
+            .layers
+            .get(self.current_layer)
+            .context("No current layer")?;
+        Ok(GridPlan::new(
+            &grid,
+            layer,
+            &self.mapping.get_all_visual_positions(),
+            self.selected_position,
+            |keycode| self.keycode_db.is_valid(keycode),
+        ))
+    }
+
+    /// Open the key role editor for the selected key.
+    pub fn open_key_role_editor(&mut self) {
+        let position = self.selected_position;
+        if !self.mapping.roles.contains_key(&position) {
+            self.set_error("Key roles need a keyboard geometry");
+            return;
+        }
+        let editor = KeyRoleEditor::new(
+            position,
+            self.mapping.role(position),
+            self.inferred_key_role(position),
+        );
+        self.active_component = Some(ActiveComponent::KeyRoleEditor(editor));
+        self.active_popup = Some(PopupType::KeyRoleEditor);
+    }
+
+    /// Index of the encoder under the cursor, if the cursor is on one.
+    #[must_use]
+    pub fn selected_encoder(&self) -> Option<u8> {
+        self.mapping.encoder_at(self.selected_position)
+    }
+
+    /// Open the encoder editor for encoder `index` on the current layer.
+    pub fn open_encoder_editor(&mut self, index: u8) {
+        let Some(layer) = self.layout.layers.get(self.current_layer) else {
+            return;
+        };
+        let editor = EncoderEditor::new(index, layer.encoder(usize::from(index)));
+        self.active_component = Some(ActiveComponent::EncoderEditor(editor));
+        self.active_popup = Some(PopupType::EncoderEditor);
+    }
+
+    /// Role the geometry heuristics give the key at `position`, ignoring
+    /// overrides.
+    #[must_use]
+    pub fn inferred_key_role(&self, position: Position) -> KeyRole {
+        infer_roles(&self.mapping.get_all_visual_positions())
+            .get(&position)
+            .copied()
+            .unwrap_or_default()
+    }
+
+    /// Sets the role of the key at `position` in the geometry and mapping.
+    pub fn set_key_role(&mut self, position: Position, role: KeyRole) {
+        for key in &mut self.geometry.keys {
+            if key.visual_position() == position {
+                key.role = role;
+            }
+        }
+        self.mapping.roles.insert(position, role);
+    }
+
+    /// Starts a history entry for generating or building the layout.
+    #[must_use]
+    pub fn history_entry(&self, operation: HistoryOperation) -> HistoryEntry {
+        HistoryEntry::new(operation).with_qmk_tree(self.config.paths.qmk_firmware.as_deref())
+    }
+
+    /// Appends `entry` to the layout file's history.
+    ///
+    /// Layouts that were never saved have no history. A failure to write is
+    /// noted in the action log without replacing the status message.
+    pub fn record_history(&mut self, entry: &HistoryEntry) {
+        let Some(path) = &self.source_path else {
+            return;
+        };
+        if let Err(e) = HistoryService::record(path, entry, DEFAULT_HISTORY_RETENTION) {
+            self.action_log
+                .record("History", format!("Failed to record history: {e}"));
+        }
+    }
+
+    /// Records the pending build history entry once the build has finished.
+    pub fn record_finished_build(&mut self) {
+        let Some(build_state) = &self.build_state else {
+            return;
+        };
+        if self.pending_build_history.is_none() || build_state.is_building() {
+            return;
+        }
+        let Some(mut entry) = self.pending_build_history.take() else {
+            return;
+        };
+        if build_state.status == BuildStatus::Success {
+            entry.firmware_size = build_state.size;
+        } else {
+            entry.fail(&build_state.last_message);
+        }
+        self.record_history(&entry);
+    }
+
+    /// Open the command palette component
+    pub fn open_command_palette(&mut self) {
+        let palette = CommandPalette::new();
+        self.active_component = Some(ActiveComponent::CommandPalette(palette));
+        self.active_popup = Some(PopupType::CommandPalette);
+    }
+
     /// Open the layout variant picker component (for switching QMK keyboard layout variants)
     pub fn open_layout_variant_picker(&mut self, qmk_path: &PathBuf, keyboard: &str) -> Result<()> {
         let picker = LayoutVariantPicker::new(qmk_path, keyboard);
@@ -809,12 +1600,45 @@ impl AppState {
     // === Tap Dance Management Methods ===
 }
 
+/// Whether the terminal captures mouse events (`ui.mouse`, `--no-mouse`,
+/// toggled at runtime)
+static MOUSE_CAPTURE: AtomicBool = AtomicBool::new(true);
+
+/// Sets whether `setup_terminal` enables mouse capture.
+pub fn set_mouse_capture_preference(enabled: bool) {
+    MOUSE_CAPTURE.store(enabled, Ordering::Relaxed);
+}
+
+/// Whether mouse capture is currently on.
+#[must_use]
+pub fn mouse_capture_enabled() -> bool {
+    MOUSE_CAPTURE.load(Ordering::Relaxed)
+}
+
+/// Turns mouse capture on or off in the running terminal.
+///
+/// With capture off the terminal's own text selection works again.
+pub fn set_mouse_capture(enabled: bool) -> Result<()> {
+    let mut stdout = io::stdout();
+    if enabled {
+        execute!(stdout, EnableMouseCapture)
+    } else {
+        execute!(stdout, DisableMouseCapture)
+    }
+    .context("Failed to change mouse capture")?;
+    set_mouse_capture_preference(enabled);
+    Ok(())
+}
+
 /// Initialize terminal for TUI
 pub fn setup_terminal() -> Result<Terminal<CrosstermBackend<io::Stdout>>> {
     enable_raw_mode().context("Failed to enable raw mode")?;
     let mut stdout = io::stdout();
-    execute!(stdout, EnterAlternateScreen, EnableMouseCapture)
+    execute!(stdout, EnterAlternateScreen, EnableBracketedPaste)
         .context("Failed to enter alternate screen")?;
+    if mouse_capture_enabled() {
+        execute!(stdout, EnableMouseCapture).context("Failed to enable mouse capture")?;
+    }
     let backend = CrosstermBackend::new(stdout);
     let terminal = Terminal::new(backend).context("Failed to create terminal")?;
     Ok(terminal)
@@ -823,16 +1647,82 @@ pub fn setup_terminal() -> Result<Terminal<CrosstermBackend<io::Stdout>>> {
 /// Restore terminal to normal state
 pub fn restore_terminal(mut terminal: Terminal<CrosstermBackend<io::Stdout>>) -> Result<()> {
     disable_raw_mode().context("Failed to disable raw mode")?;
+    if mouse_capture_enabled() {
+        execute!(terminal.backend_mut(), DisableMouseCapture)
+            .context("Failed to disable mouse capture")?;
+    }
     execute!(
         terminal.backend_mut(),
         LeaveAlternateScreen,
-        DisableMouseCapture
+        DisableBracketedPaste
     )
     .context("Failed to leave alternate screen")?;
     terminal.show_cursor().context("Failed to show cursor")?;
     Ok(())
 }
 
+/// Runs the editor on an initialized terminal and restores the terminal
+/// afterwards.
+///
+/// If the editor fails or panics, a crash bundle with the session action
+/// log is written before the error is returned (or the panic resumed).
+pub fn run_editor(
+    state: &mut AppState,
+    mut terminal: Terminal<CrosstermBackend<io::Stdout>>,
+) -> Result<()> {
+    if !state.config.ui.tutorial_completed {
+        handlers::tutorial::start_tutorial(state);
+    }
+
+    let result = std::panic::catch_unwind(std::panic::AssertUnwindSafe(|| {
+        run_tui(state, &mut terminal)
+    }));
+
+    restore_terminal(terminal)?;
+
+    let failure = match &result {
+        Ok(Ok(())) => None,
+        Ok(Err(e)) => Some(e.to_string()),
+        Err(_) => Some("panic".to_string()),
+    };
+    if let Some(reason) = failure {
+        if let Ok(path) = write_crash_bundle(state, &reason) {
+            eprintln!("Crash report written to {}", path.display());
+        }
+    }
+
+    match result {
+        Ok(result) => result,
+        Err(panic) => std::panic::resume_unwind(panic),
+    }
+}
+
+/// Writes a crash bundle with the session action log to the data directory.
+///
+/// Returns the path of the written file.
+pub fn write_crash_bundle(state: &AppState, reason: &str) -> Result<PathBuf> {
+    let dir = crate::paths::AppPaths::current()?.crash_dir();
+    std::fs::create_dir_all(&dir).context("Failed to create crash directory")?;
+    let now = chrono::Local::now();
+    let path = dir.join(format!("crash-{}.log", now.format("%Y%m%d-%H%M%S")));
+
+    let source = state
+        .source_path
+        .as_ref()
+        .map_or_else(|| "(unsaved)".to_string(), |p| p.display().to_string());
+    let contents = format!(
+        "LazyQMK {} crash report\nTime: {}\nReason: {reason}\nLayout: {} ({source})\nUnsaved changes: {}\n\nAction log ({} entries):\n{}",
+        env!("CARGO_PKG_VERSION"),
+        now.format("%Y-%m-%d %H:%M:%S"),
+        state.layout.metadata.name,
+        state.dirty,
+        state.action_log.len(),
+        state.action_log.to_text()
+    );
+    atomic_write::write(&path, contents).context("Failed to write crash bundle")?;
+    Ok(path)
+}
+
 /// Main event loop
 pub fn run_tui(
     state: &mut AppState,
@@ -842,8 +1732,12 @@ pub fn run_tui(
         // Apply theme based on user preference (Auto detects OS, Dark/Light are explicit)
         state.theme = Theme::from_mode(state.config.ui.theme_mode);
 
-        // Decrement flash highlight counter
-        if let Some((layer, pos, frames)) = state.flash_highlight {
+        // Decrement flash highlight counter; with reduced motion the marker
+        // stays put until the next input instead
+        if let Some((layer, pos, frames)) = state
+            .flash_highlight
+            .filter(|_| !state.config.ui.reduced_motion)
+        {
             if frames > 1 {
                 state.flash_highlight = Some((layer, pos, frames - 1));
             } else {
@@ -852,26 +1746,34 @@ pub fn run_tui(
         }
 
         // Render current state
-        terminal.draw(|f| render(f, state))?;
+        let render_start = Instant::now();
+        terminal.draw(|f| render_frame(f, state))?;
+        if let Some(profile) = &mut state.startup_profile {
+            profile.mark_first_frame(render_start.elapsed());
+        }
+
+        // Validate after drawing so the first frame isn't held up; the
+        // banner shows from the next frame
+        state.refresh_validation_banner();
 
-        // Poll for events with 100ms timeout
+        // Poll for events with 100ms timeout, then drain everything queued so
+        // held keys never lag behind the cursor
         if event::poll(Duration::from_millis(100))? {
-            if let Event::Key(key) = event::read()? {
-                if handle_key_event(state, key)? {
-                    break; // User quit
-                }
-            } else if let Event::Resize(_, _) = event::read()? {
-                // Terminal resized, will re-render on next loop
+            let mut events = VecDeque::from([event::read()?]);
+            while event::poll(Duration::ZERO)? {
+                events.push_back(event::read()?);
             }
-        }
-
-        // Poll build state for updates
-        if let Some(build_state) = &mut state.build_state {
-            if build_state.poll() {
-                // Build message received, will update on next render
+            if state.config.ui.reduced_motion {
+                state.flash_highlight = None;
+            }
+            if handle_events(state, events)? {
+                break; // User quit
             }
         }
 
+        // Apply updates from background tasks; they show on the next render
+        messages::drain_messages(state);
+
         // Check if should quit
         if state.should_quit {
             break;
@@ -882,33 +1784,67 @@ pub fn run_tui(
 }
 
 /// Render the UI from current state
-fn render(f: &mut Frame, state: &AppState) {
+pub fn render_frame(f: &mut Frame, state: &AppState) {
     // Fill entire screen with theme background color first
     // This ensures consistent background regardless of terminal settings
     let full_bg = Block::default().style(Style::default().bg(state.theme.background));
     f.render_widget(full_bg, f.area());
 
+    // Below the minimum size the layout math clips or overflows, so nothing
+    // else (popups and overlays included) is drawn until the terminal grows
+    let (min_width, min_height) = minimum_terminal_size(state);
+    let area = f.area();
+    if area.width < min_width || area.height < min_height {
+        render_too_small(f, (min_width, min_height), &state.theme);
+        return;
+    }
+
+    let banner = state.validation_banner_message();
     let chunks = RatatuiLayout::default()
         .direction(Direction::Vertical)
         .constraints([
-            Constraint::Length(3), // Title bar
-            Constraint::Min(10),   // Main content
-            Constraint::Length(6), // Status bar (increased for description + clipboard + build + help)
+            Constraint::Length(TITLE_BAR_HEIGHT),
+            Constraint::Length(u16::from(banner.is_some())), // Validation banner
+            Constraint::Min(MIN_MAIN_HEIGHT),
+            Constraint::Length(STATUS_BAR_HEIGHT), // Description + clipboard + build + help
         ])
         .split(f.area());
 
     // Title bar with dirty indicator
     render_title_bar(f, chunks[0], state);
 
+    if let Some(message) = &banner {
+        validation_banner::render(f, chunks[1], message, &state.theme);
+    }
+
     // Main content area
-    render_main_content(f, chunks[1], state);
+    render_main_content(f, chunks[2], state);
 
     // Status bar
-    StatusBar::render(f, chunks[2], state, &state.theme);
+    StatusBar::render(f, chunks[3], state, &state.theme);
+
+    // Tutorial callout, out of the way while a popup is open
+    if let Some(tour) = state
+        .tutorial
+        .as_ref()
+        .filter(|_| state.active_popup.is_none())
+    {
+        let areas = tutorial::TutorialAreas {
+            title_bar: chunks[0],
+            keyboard: chunks[2],
+            status_bar: chunks[3],
+        };
+        tutorial::render(f, tour, areas, &state.theme);
+    }
 
     // Render popup if active
     if let Some(popup_type) = &state.active_popup {
-        render_popup(f, popup_type, state);
+        render_popup(
+            f,
+            popup_type,
+            state,
+            KeyboardWidget::focused_area(chunks[2], state),
+        );
     }
 
     // Render error overlay on top of everything if error is present
@@ -917,9 +1853,69 @@ fn render(f: &mut Frame, state: &AppState) {
     }
 }
 
+/// Height of the title bar
+const TITLE_BAR_HEIGHT: u16 = 3;
+/// Smallest height of the main content area
+const MIN_MAIN_HEIGHT: u16 = 10;
+/// Height of the status bar
+const STATUS_BAR_HEIGHT: u16 = 6;
+/// Narrowest width at which the title and status bars stay readable
+const MIN_CHROME_WIDTH: u16 = 60;
+
+/// Smallest usable terminal size for the loaded keyboard: (width, height).
+///
+/// The keyboard must fit unclipped between the title and status bars.
+#[must_use]
+pub fn minimum_terminal_size(state: &AppState) -> (u16, u16) {
+    minimum_size_for(
+        &state.geometry,
+        &state.mapping,
+        state.config.ui.keyboard_scale,
+    )
+}
+
+/// [`minimum_terminal_size`] for a geometry, mapping and keyboard scale.
+fn minimum_size_for(
+    geometry: &KeyboardGeometry,
+    mapping: &VisualLayoutMapping,
+    keyboard_scale: f32,
+) -> (u16, u16) {
+    let (keyboard_width, keyboard_height) =
+        KeyboardWidget::required_size(geometry, mapping, keyboard_scale);
+    (
+        keyboard_width.max(MIN_CHROME_WIDTH),
+        keyboard_height
+            .max(MIN_MAIN_HEIGHT)
+            .saturating_add(TITLE_BAR_HEIGHT + STATUS_BAR_HEIGHT),
+    )
+}
+
+/// Render the placeholder shown while the terminal is below `needed`
+fn render_too_small(f: &mut Frame, needed: (u16, u16), theme: &Theme) {
+    let area = f.area();
+    let message = format!(
+        "Terminal too small (need {}x{}, have {}x{})",
+        needed.0, needed.1, area.width, area.height
+    );
+    let [row] = RatatuiLayout::vertical([Constraint::Length(1)])
+        .flex(Flex::Center)
+        .areas(area);
+    let paragraph = Paragraph::new(message)
+        .alignment(Alignment::Center)
+        .wrap(Wrap { trim: true })
+        .style(Style::default().fg(theme.warning).bg(theme.background));
+    f.render_widget(paragraph, row);
+}
+
 /// Render title bar with layout name and dirty indicator
 fn render_title_bar(f: &mut Frame, area: Rect, state: &AppState) {
-    let dirty_indicator = if state.dirty { " *" } else { "" };
+    let dirty_indicator = if state.is_read_only() {
+        " [read-only]"
+    } else if state.dirty {
+        " *"
+    } else {
+        ""
+    };
     let title = format!(
         " {} - Layer {} {}",
         state.layout.metadata.name, state.current_layer, dirty_indicator
@@ -942,11 +1938,18 @@ fn render_title_bar(f: &mut Frame, area: Rect, state: &AppState) {
 
 /// Render main content (keyboard widget)
 fn render_main_content(f: &mut Frame, area: Rect, state: &AppState) {
+    // Mouse clicks and popups next to the selected key go to the focused pane
+    state
+        .keyboard_area
+        .set(KeyboardWidget::focused_area(area, state));
     KeyboardWidget::render(f, area, state);
 }
 
 /// Render active popup
-fn render_popup(f: &mut Frame, popup_type: &PopupType, state: &AppState) {
+///
+/// `keyboard_area` is where the keyboard is drawn, for popups that keep the
+/// selected key in view.
+fn render_popup(f: &mut Frame, popup_type: &PopupType, state: &AppState, keyboard_area: Rect) {
     match popup_type {
         PopupType::KeycodePicker => {
             // Use ContextualComponent trait pattern
@@ -957,7 +1960,16 @@ fn render_popup(f: &mut Frame, popup_type: &PopupType, state: &AppState) {
         PopupType::ColorPicker => {
             // Use Component trait pattern
             if let Some(ActiveComponent::ColorPicker(ref picker)) = state.active_component {
-                picker.render(f, f.area(), &state.theme);
+                if picker.is_sampling() {
+                    // Minimized so the whole board stays visible
+                    color_picker::render_sampling_hint(f, keyboard_area, picker, &state.theme);
+                } else {
+                    // Keep the key being colored visible behind the picker
+                    let selected_key =
+                        KeyboardWidget::key_rect(keyboard_area, state, state.selected_position);
+                    let area = color_picker::popup_area(f.area(), selected_key);
+                    picker.render(f, area, &state.theme);
+                }
             }
         }
         PopupType::CategoryPicker => {
@@ -989,6 +2001,61 @@ fn render_popup(f: &mut Frame, popup_type: &PopupType, state: &AppState) {
                 browser.render(f, f.area(), &state.theme);
             }
         }
+        PopupType::CommandPalette => {
+            if let Some(ActiveComponent::CommandPalette(ref palette)) = state.active_component {
+                palette.render(f, f.area(), &state.theme);
+            }
+        }
+        PopupType::SnapshotBrowser => {
+            if let Some(ActiveComponent::SnapshotBrowser(ref browser)) = state.active_component {
+                browser.render(f, f.area(), &state.theme);
+            }
+        }
+        PopupType::KeycodeSearch => {
+            if let Some(ActiveComponent::KeycodeSearch(ref view)) = state.active_component {
+                view.render(f, f.area(), &state.theme);
+            }
+        }
+        PopupType::LintReport => {
+            if let Some(ActiveComponent::LintReport(ref view)) = state.active_component {
+                view.render(f, f.area(), &state.theme);
+            }
+        }
+        PopupType::ValidationResults => {
+            if let Some(ActiveComponent::ValidationResults(ref view)) = state.active_component {
+                view.render(f, f.area(), &state.theme);
+            }
+        }
+        PopupType::GenerationResults => {
+            if let Some(ActiveComponent::GenerationResults(ref view)) = state.active_component {
+                view.render(f, f.area(), &state.theme);
+            }
+        }
+        PopupType::ActionLog => {
+            if let Some(ActiveComponent::ActionLog(ref view)) = state.active_component {
+                view.render(f, f.area(), &state.theme, &state.action_log);
+            }
+        }
+        PopupType::History => {
+            if let Some(ActiveComponent::History(ref view)) = state.active_component {
+                view.render(f, f.area(), &state.theme);
+            }
+        }
+        PopupType::GridPaste => {
+            if let Some(ActiveComponent::GridPaste(ref view)) = state.active_component {
+                view.render(f, f.area(), &state.theme);
+            }
+        }
+        PopupType::KeyRoleEditor => {
+            if let Some(ActiveComponent::KeyRoleEditor(ref editor)) = state.active_component {
+                editor.render(f, f.area(), &state.theme);
+            }
+        }
+        PopupType::EncoderEditor => {
+            if let Some(ActiveComponent::EncoderEditor(ref editor)) = state.active_component {
+                editor.render(f, f.area(), &state.theme);
+            }
+        }
         PopupType::TemplateSaveDialog => {
             render_template_save_dialog(f, state);
         }
@@ -998,6 +2065,19 @@ fn render_popup(f: &mut Frame, popup_type: &PopupType, state: &AppState) {
         PopupType::UnsavedChangesPrompt => {
             render_unsaved_prompt(f, &state.theme);
         }
+        PopupType::GeometryMismatch => {
+            if let Some(ref mismatch) = state.geometry_mismatch {
+                geometry_mismatch::render(f, mismatch, &state.theme);
+            }
+        }
+        PopupType::KeyboardMismatch => {
+            if let Some((ref mismatch, action)) = state.keyboard_mismatch {
+                render_keyboard_mismatch_prompt(f, mismatch, action, &state.theme);
+            }
+        }
+        PopupType::StaleGeneration => {
+            render_stale_generation_prompt(f, &state.theme);
+        }
         PopupType::BuildLog => {
             // Use ContextualComponent trait pattern
             if let Some(ActiveComponent::BuildLog(ref log)) = state.active_component {
@@ -1096,6 +2176,80 @@ fn render_unsaved_prompt(f: &mut Frame, theme: &Theme) {
     f.render_widget(prompt, area);
 }
 
+/// Render the confirmation shown before generating for another keyboard
+fn render_keyboard_mismatch_prompt(
+    f: &mut Frame,
+    mismatch: &KeyboardMismatch,
+    action: FirmwareAction,
+    theme: &Theme,
+) {
+    let area = centered_rect(70, 40, f.area());
+
+    f.render_widget(Clear, area);
+    let background = Block::default().style(Style::default().bg(theme.background));
+    f.render_widget(background, area);
+
+    let verb = match action {
+        FirmwareAction::Generate => "Generate",
+        FirmwareAction::Build => "Build",
+    };
+    let text = vec![
+        Line::from(""),
+        Line::from("The layout is for a different keyboard than the build target."),
+        Line::from(""),
+        Line::from(format!("  Layout keyboard: {}", mismatch.layout_keyboard)),
+        Line::from(format!(
+            "  Build target:    {} ({})",
+            mismatch.build_keyboard, mismatch.build_source
+        )),
+        Line::from(format!(
+            "  Target path:     {}",
+            mismatch.target_path.display()
+        )),
+        Line::from(""),
+        Line::from(format!("  [Y/Enter] {verb} anyway")),
+        Line::from("  [N/Esc] Cancel"),
+    ];
+
+    let prompt = Paragraph::new(text).wrap(Wrap { trim: false }).block(
+        Block::default()
+            .title(" Keyboard Mismatch ")
+            .borders(Borders::ALL)
+            .style(Style::default().fg(theme.warning)),
+    );
+
+    f.render_widget(prompt, area);
+}
+
+/// Render the confirmation shown before building a layout that changed since
+/// the last generation
+fn render_stale_generation_prompt(f: &mut Frame, theme: &Theme) {
+    let area = centered_rect(60, 30, f.area());
+
+    f.render_widget(Clear, area);
+    let background = Block::default().style(Style::default().bg(theme.background));
+    f.render_widget(background, area);
+
+    let text = vec![
+        Line::from(""),
+        Line::from("The layout changed since the firmware files were last generated."),
+        Line::from("Regenerate first?"),
+        Line::from(""),
+        Line::from("  [Y/Enter] Regenerate and build"),
+        Line::from("  [N] Build the last generated files"),
+        Line::from("  [Esc] Cancel"),
+    ];
+
+    let prompt = Paragraph::new(text).wrap(Wrap { trim: false }).block(
+        Block::default()
+            .title(" Layout Changed ")
+            .borders(Borders::ALL)
+            .style(Style::default().fg(theme.warning)),
+    );
+
+    f.render_widget(prompt, area);
+}
+
 /// Render error overlay on top of all other UI elements
 fn render_error_overlay(f: &mut Frame, error: &str, theme: &Theme) {
     let area = centered_rect(70, 40, f.area());
@@ -1164,7 +2318,7 @@ fn render_error_overlay(f: &mut Frame, error: &str, theme: &Theme) {
 
 /// Render template save dialog
 fn render_template_save_dialog(f: &mut Frame, state: &AppState) {
-    let area = centered_rect(70, 60, f.area());
+    let area = centered_rect(70, 80, f.area());
 
     let dialog_state = &state.template_save_dialog_state;
     let theme = &state.theme;
@@ -1185,6 +2339,7 @@ fn render_template_save_dialog(f: &mut Frame, state: &AppState) {
             Constraint::Length(3), // Description field
             Constraint::Length(3), // Author field
             Constraint::Length(3), // Tags field
+            Constraint::Length(u16::try_from(TemplateSection::ALL.len()).unwrap_or(5) + 2), // Sections
             Constraint::Min(2),    // Help text
             Constraint::Length(2), // Action buttons
         ])
@@ -1206,12 +2361,12 @@ fn render_template_save_dialog(f: &mut Frame, state: &AppState) {
     } else {
         Style::default().fg(theme.text)
     };
-    let name_text = if matches!(dialog_state.active_field, TemplateSaveField::Name) {
-        format!("Name: {}█", dialog_state.name)
-    } else {
-        format!("Name: {}", dialog_state.name)
-    };
-    let name = Paragraph::new(name_text)
+    let mut name_spans = vec![Span::raw("Name: ")];
+    name_spans.extend(dialog_state.name.spans(
+        name_style,
+        matches!(dialog_state.active_field, TemplateSaveField::Name),
+    ));
+    let name = Paragraph::new(Line::from(name_spans))
         .style(name_style)
         .block(Block::default().borders(Borders::ALL));
     f.render_widget(name, chunks[1]);
@@ -1222,12 +2377,12 @@ fn render_template_save_dialog(f: &mut Frame, state: &AppState) {
     } else {
         Style::default().fg(theme.text)
     };
-    let desc_text = if matches!(dialog_state.active_field, TemplateSaveField::Description) {
-        format!("Description: {}█", dialog_state.description)
-    } else {
-        format!("Description: {}", dialog_state.description)
-    };
-    let description = Paragraph::new(desc_text)
+    let mut desc_spans = vec![Span::raw("Description: ")];
+    desc_spans.extend(dialog_state.description.spans(
+        desc_style,
+        matches!(dialog_state.active_field, TemplateSaveField::Description),
+    ));
+    let description = Paragraph::new(Line::from(desc_spans))
         .style(desc_style)
         .block(Block::default().borders(Borders::ALL));
     f.render_widget(description, chunks[2]);
@@ -1238,12 +2393,12 @@ fn render_template_save_dialog(f: &mut Frame, state: &AppState) {
     } else {
         Style::default().fg(theme.text)
     };
-    let author_text = if matches!(dialog_state.active_field, TemplateSaveField::Author) {
-        format!("Author: {}█", dialog_state.author)
-    } else {
-        format!("Author: {}", dialog_state.author)
-    };
-    let author = Paragraph::new(author_text)
+    let mut author_spans = vec![Span::raw("Author: ")];
+    author_spans.extend(dialog_state.author.spans(
+        author_style,
+        matches!(dialog_state.active_field, TemplateSaveField::Author),
+    ));
+    let author = Paragraph::new(Line::from(author_spans))
         .style(author_style)
         .block(Block::default().borders(Borders::ALL));
     f.render_widget(author, chunks[3]);
@@ -1254,30 +2409,65 @@ fn render_template_save_dialog(f: &mut Frame, state: &AppState) {
     } else {
         Style::default().fg(theme.text)
     };
-    let tags_text = if matches!(dialog_state.active_field, TemplateSaveField::Tags) {
-        format!("Tags (comma-separated): {}█", dialog_state.tags_input)
-    } else {
-        format!("Tags (comma-separated): {}", dialog_state.tags_input)
-    };
-    let tags = Paragraph::new(tags_text)
+    let mut tags_spans = vec![Span::raw("Tags (comma-separated): ")];
+    tags_spans.extend(dialog_state.tags_input.spans(
+        tags_style,
+        matches!(dialog_state.active_field, TemplateSaveField::Tags),
+    ));
+    let tags = Paragraph::new(Line::from(tags_spans))
         .style(tags_style)
         .block(Block::default().borders(Borders::ALL));
     f.render_widget(tags, chunks[4]);
 
+    // Section checkboxes
+    let on_sections = matches!(dialog_state.active_field, TemplateSaveField::Sections);
+    let section_lines: Vec<Line> = TemplateSection::ALL
+        .iter()
+        .enumerate()
+        .map(|(i, &section)| {
+            let checkbox = if dialog_state.sections.includes(section) {
+                "[x]"
+            } else {
+                "[ ]"
+            };
+            let style = if on_sections && i == dialog_state.selected_section {
+                Style::default()
+                    .fg(theme.accent)
+                    .add_modifier(Modifier::BOLD)
+            } else {
+                Style::default().fg(theme.text)
+            };
+            Line::styled(format!("{checkbox} {}", section.label()), style)
+        })
+        .collect();
+    let border_style = if on_sections {
+        Style::default().fg(theme.accent)
+    } else {
+        Style::default().fg(theme.text)
+    };
+    let sections = Paragraph::new(section_lines).block(
+        Block::default()
+            .borders(Borders::ALL)
+            .border_style(border_style)
+            .title(" Include "),
+    );
+    f.render_widget(sections, chunks[5]);
+
     // Help text
     let help_text = vec![
         Line::from(""),
         Line::from("Tab/Shift+Tab: navigate fields"),
-        Line::from("Type: enter text | Backspace: delete"),
+        Line::from("Type: enter text | ←/→ Home/End: move | Shift: select"),
+        Line::from("Include: ↑/↓ select | Space: toggle section"),
     ];
     let help = Paragraph::new(help_text).style(Style::default().fg(theme.text_muted));
-    f.render_widget(help, chunks[5]);
+    f.render_widget(help, chunks[6]);
 
     // Action buttons
     let actions = Paragraph::new("Enter: save template | Esc: cancel")
         .style(Style::default().fg(theme.success))
         .block(Block::default().borders(Borders::ALL));
-    f.render_widget(actions, chunks[6]);
+    f.render_widget(actions, chunks[7]);
 }
 
 /// Render export filename dialog
@@ -1316,16 +2506,18 @@ fn render_export_filename_dialog(f: &mut Frame, state: &AppState) {
     f.render_widget(title, chunks[0]);
 
     // Filename field with cursor
-    let filename_text = format!("Filename: {}█", dialog_state.filename);
-    let filename = Paragraph::new(filename_text)
-        .style(Style::default().fg(theme.accent))
-        .block(Block::default().borders(Borders::ALL));
-    f.render_widget(filename, chunks[1]);
+    dialog_state.filename.render(
+        f,
+        chunks[1],
+        Block::default().borders(Borders::ALL).title(" Filename "),
+        Style::default().fg(theme.accent),
+        true,
+    );
 
     // Help text
     let help_text = vec![
         Line::from(""),
-        Line::from("Type: enter filename | Backspace: delete"),
+        Line::from("Type: enter filename | ←/→ Home/End: move cursor"),
     ];
     let help = Paragraph::new(help_text).style(Style::default().fg(theme.text_muted));
     f.render_widget(help, chunks[2]);
@@ -1359,6 +2551,116 @@ fn centered_rect(percent_x: u16, percent_y: u16, r: Rect) -> Rect {
 }
 
 /// Handle keyboard input events
+fn handle_events(state: &mut AppState, mut events: VecDeque<Event>) -> Result<bool> {
+    let registry = ShortcutRegistry::new();
+    let coalesce = state.config.ui.coalesce_navigation;
+    let accelerate = state.config.ui.navigation_acceleration;
+
+    while !events.is_empty() {
+        // Without coalescing, every event is its own batch
+        let mut single;
+        let queue = if coalesce {
+            &mut events
+        } else {
+            single = events.drain(..1).collect();
+            &mut single
+        };
+        let mut accelerator = std::mem::take(&mut state.nav_accelerator);
+        let batch = key_repeat::next_batch(
+            queue,
+            |event| navigation_direction(state, &registry, event),
+            |direction| {
+                if accelerate {
+                    accelerator.step(direction, Instant::now())
+                } else {
+                    1
+                }
+            },
+        );
+        state.nav_accelerator = accelerator;
+
+        let quit = match batch {
+            Some(InputBatch::Move(net)) => {
+                handlers::action_handlers::navigation::apply_net_move(state, net)?
+            }
+            Some(InputBatch::Event(Event::Key(key))) => handle_key_event(state, key)?,
+            Some(InputBatch::Event(Event::Paste(text))) => {
+                handle_paste_event(state, &text);
+                false
+            }
+            Some(InputBatch::Event(Event::Mouse(mouse))) => {
+                handle_mouse_event(state, mouse);
+                false
+            }
+            // Resizes re-render on the next loop
+            Some(InputBatch::Event(_)) | None => false,
+        };
+        state.revert_read_only_edits();
+        // Opening a popup ends a peek
+        if state.active_popup.is_some() {
+            state.peek = None;
+        }
+        if quit || state.should_quit {
+            return Ok(true);
+        }
+    }
+    Ok(false)
+}
+
+/// Direction of a main-view navigation key; keys in popups are never merged.
+fn navigation_direction(
+    state: &AppState,
+    registry: &ShortcutRegistry,
+    event: &Event,
+) -> Option<NavDirection> {
+    let Event::Key(key) = event else {
+        return None;
+    };
+    if state.error_message.is_some() || state.active_popup.is_some() {
+        return None;
+    }
+    registry
+        .lookup("main", *key)
+        .and_then(NavDirection::from_action)
+}
+
+/// Selects the key clicked with the left button in the main view.
+fn handle_mouse_event(state: &mut AppState, mouse: event::MouseEvent) {
+    if state.error_message.is_some() || state.active_popup.is_some() {
+        return;
+    }
+    if mouse.kind == event::MouseEventKind::Down(event::MouseButton::Left) {
+        handlers::action_handlers::navigation::handle_click(state, mouse.column, mouse.row);
+    }
+}
+
+/// Routes a bracketed paste: onto the layer as a keycode grid from the main
+/// view, or into the grid paste editor when it is open.
+fn handle_paste_event(state: &mut AppState, text: &str) {
+    if state.error_message.is_some() {
+        return;
+    }
+    match (&state.active_popup, &mut state.active_component) {
+        (None, _) => state.open_grid_paste(Some(text)),
+        (Some(PopupType::GridPaste), Some(ActiveComponent::GridPaste(view))) if view.is_entry() => {
+            view.insert_text(text);
+        }
+        (Some(PopupType::TemplateSaveDialog), _) => {
+            if let Some(field) = state.template_save_dialog_state.get_active_field_mut() {
+                field.insert_str(text);
+            }
+        }
+        (Some(PopupType::ExportFilenameDialog), _) => {
+            state.export_filename_dialog_state.filename.insert_str(text);
+        }
+        (Some(PopupType::KeyEditor), _) if state.key_editor_state.is_editing() => {
+            state.key_editor_state.description_input.insert_str(text);
+        }
+        // Other popups have no use for pasted text
+        _ => {}
+    }
+}
+
 fn handle_key_event(state: &mut AppState, key: event::KeyEvent) -> Result<bool> {
     use crossterm::event::KeyCode;
 
@@ -1372,11 +2674,52 @@ fn handle_key_event(state: &mut AppState, key: event::KeyEvent) -> Result<bool>
         return Ok(false);
     }
 
+    // Edits made while handling this key are one undo step
+    state.undo_history.seal();
+
+    // Mouse capture toggles everywhere, so text can be selected out of popups
+    if state.active_popup.is_some()
+        && key.code == KeyCode::Char('m')
+        && key.modifiers == event::KeyModifiers::ALT
+    {
+        return handlers::dispatch_action(state, crate::shortcuts::Action::ToggleMouseCapture);
+    }
+
     // Route to popup handler if popup is active
     if state.active_popup.is_some() {
         return handlers::handle_popup_input(state, key);
     }
 
+    // A pending mirror takes input until it is applied or cancelled
+    if state.pending_mirror.is_some() {
+        return handlers::handle_mirror_preview_input(state, key);
+    }
+
+    // A wrap prompt takes input until a modifier is chosen or it is cancelled
+    if state.pending_wrap.is_some() {
+        return handlers::handle_wrap_prompt_input(state, key);
+    }
+
+    // Diff mode takes input until it is exited
+    if state.diff_view.is_some() {
+        return handlers::handle_diff_view_input(state, key);
+    }
+
+    // A typing simulation takes input until it is exited
+    if state.simulation.is_some() {
+        return handlers::handle_simulation_input(state, key);
+    }
+
+    // A peek ends on the next key that doesn't pick another layer
+    if state.peek.is_some() {
+        return handlers::handle_peek_input(state, key);
+    }
+
+    // The tutorial's own keys (next step, skip) come before the main view's
+    if state.tutorial.is_some() && handlers::handle_tutorial_input(state, key) {
+        return Ok(false);
+    }
+
     // Main UI key handling
     handlers::handle_main_input(state, key)
 }
@@ -1540,4 +2883,142 @@ mod tests {
         let result = state.build_keycode();
         assert!(result.is_none(), "Empty params should return None");
     }
+
+    fn two_layer_state() -> AppState {
+        use crate::models::{KeyDefinition, Layer, RgbColor};
+
+        let mut layout = Layout::new("Focus").unwrap();
+        for number in 0..2 {
+            let mut layer =
+                Layer::new(number, format!("Layer {number}"), RgbColor::new(0, 0, 255)).unwrap();
+            for col in 0..3 {
+                layer
+                    .add_key(KeyDefinition::at(Position::new(0, col)).keycode("KC_A"))
+                    .unwrap();
+            }
+            layout.add_layer(layer).unwrap();
+        }
+        AppState::new(
+            layout,
+            None,
+            KeyboardGeometry::new("test", "test", 1, 3),
+            VisualLayoutMapping::default(),
+            Config::default(),
+        )
+        .unwrap()
+    }
+
+    #[test]
+    fn test_focus_layer_and_position() {
+        let mut state = two_layer_state();
+        state.focus(Some(1), Some(Position::new(0, 2))).unwrap();
+        assert_eq!(state.current_layer, 1);
+        assert_eq!(state.selected_position, Position::new(0, 2));
+
+        assert!(state.focus(Some(2), None).is_err());
+        assert!(state.focus(None, Some(Position::new(3, 0))).is_err());
+        assert_eq!(state.current_layer, 1);
+    }
+
+    #[test]
+    fn test_read_only_reverts_edits() {
+        let mut state = two_layer_state();
+        state.set_read_only();
+        assert!(state.is_read_only());
+
+        state.layout.layers[0].keys[0].keycode = "KC_B".to_string();
+        state.mark_dirty();
+        assert!(state.revert_read_only_edits());
+        assert_eq!(state.layout.layers[0].keys[0].keycode, "KC_A");
+        assert!(!state.dirty);
+
+        // Reverting an added layer moves off it
+        let extra = state.layout.layers[1].clone();
+        state.layout.layers.push(extra);
+        state.current_layer = 2;
+        state.mark_dirty();
+        state.revert_read_only_edits();
+        assert_eq!(state.layout.layers.len(), 2);
+        assert_eq!(state.current_layer, 1);
+
+        // Nothing to revert outside read-only mode
+        let mut state = two_layer_state();
+        state.mark_dirty();
+        assert!(!state.revert_read_only_edits());
+    }
+
+    #[test]
+    fn test_finished_build_is_recorded_once() {
+        let temp = tempfile::TempDir::new().unwrap();
+        let layout_path = temp.path().join("corne.md");
+        let mut state = two_layer_state();
+        state.source_path = Some(layout_path.clone());
+
+        let mut build_state = BuildState::new();
+        build_state.status = BuildStatus::Compiling;
+        state.build_state = Some(build_state);
+        state.pending_build_history = Some(state.history_entry(HistoryOperation::Build));
+
+        // Nothing is recorded while the build runs
+        state.record_finished_build();
+        assert!(state.pending_build_history.is_some());
+
+        let build_state = state.build_state.as_mut().unwrap();
+        build_state.status = BuildStatus::Failed;
+        build_state.last_message = "Build failed: make error".to_string();
+        state.record_finished_build();
+        state.record_finished_build();
+
+        let history = HistoryService::load(&layout_path).unwrap();
+        assert_eq!(history.entries.len(), 1);
+        assert!(!history.entries[0].success);
+        assert_eq!(
+            history.entries[0].error.as_deref(),
+            Some("Build failed: make error")
+        );
+    }
+
+    /// Geometry of `rows` x `cols` 1u keys on a regular grid
+    fn grid(rows: u8, cols: u8) -> KeyboardGeometry {
+        let mut geometry = KeyboardGeometry::new("test", "test", rows, cols);
+        for row in 0..rows {
+            for col in 0..cols {
+                geometry.add_key(crate::models::KeyGeometry::new(
+                    (row, col),
+                    row * cols + col,
+                    f32::from(col),
+                    f32::from(row),
+                ));
+            }
+        }
+        geometry
+    }
+
+    fn minimum_size(geometry: &KeyboardGeometry, scale: f32) -> (u16, u16) {
+        minimum_size_for(geometry, &VisualLayoutMapping::build(geometry), scale)
+    }
+
+    #[test]
+    fn test_minimum_size_fits_keyboard_and_chrome() {
+        // 10 keys of 9 cells plus borders; 3 rows of 4 lines plus borders,
+        // title and status bars
+        assert_eq!(minimum_size(&grid(3, 10), 1.0), (92, 23));
+    }
+
+    #[test]
+    fn test_minimum_size_small_keyboard_keeps_chrome_minimum() {
+        assert_eq!(minimum_size(&grid(1, 2), 1.0), (60, 19));
+    }
+
+    #[test]
+    fn test_minimum_size_grows_with_keyboard_scale() {
+        assert_eq!(minimum_size(&grid(3, 10), 2.0), (162, 32));
+    }
+
+    #[test]
+    fn test_minimum_size_counts_wide_keys() {
+        let mut geometry = grid(3, 10);
+        geometry.keys.last_mut().unwrap().width = 2.0;
+        assert_eq!(minimum_size(&geometry, 1.0), (97, 23));
+    }
 }
@@ -0,0 +1,140 @@
+//! Terminal notifications for finished builds.
+//!
+//! Rings the terminal bell and, in terminals known to show them, sends a
+//! desktop notification escape: OSC 9 (iTerm2, Ghostty) or OSC 777 (VTE
+//! terminals, WezTerm, foot, urxvt). Inside tmux or screen only the bell is
+//! sent, since the escapes would not reach the outer terminal.
+
+use std::io::Write;
+
+use crate::firmware::BuildCompletion;
+
+/// Desktop notification escape understood by a terminal.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum NotificationEscape {
+    /// `OSC 9 ; message ST`
+    Osc9,
+    /// `OSC 777 ; notify ; title ; body ST`
+    Osc777,
+}
+
+impl NotificationEscape {
+    /// Picks the escape the current terminal supports, if any.
+    #[must_use]
+    pub fn detect() -> Option<Self> {
+        Self::detect_from(|name| std::env::var(name).ok())
+    }
+
+    /// Picks the escape from environment variables looked up with `var`.
+    pub fn detect_from(var: impl Fn(&str) -> Option<String>) -> Option<Self> {
+        if var("TMUX").is_some() || var("STY").is_some() {
+            return None;
+        }
+        match var("TERM_PROGRAM").as_deref() {
+            Some("iTerm.app" | "ghostty") => return Some(Self::Osc9),
+            Some("WezTerm") => return Some(Self::Osc777),
+            _ => {}
+        }
+        if var("VTE_VERSION").is_some() {
+            return Some(Self::Osc777);
+        }
+        let term = var("TERM").unwrap_or_default();
+        (term.starts_with("foot") || term.starts_with("rxvt")).then_some(Self::Osc777)
+    }
+
+    /// Escape sequence showing `title` and `body`.
+    #[must_use]
+    pub fn sequence(self, title: &str, body: &str) -> String {
+        match self {
+            Self::Osc9 => format!("\x1b]9;{}: {}\x07", clean(title), clean(body)),
+            Self::Osc777 => format!(
+                "\x1b]777;notify;{};{}\x07",
+                clean(title).replace(';', ","),
+                clean(body)
+            ),
+        }
+    }
+}
+
+/// Bell plus notification escape (if supported) for a finished build.
+#[must_use]
+pub fn build_notification(
+    completion: &BuildCompletion,
+    escape: Option<NotificationEscape>,
+) -> String {
+    let mut output = String::from("\x07");
+    if let Some(escape) = escape {
+        output.push_str(&escape.sequence(completion.title(), &completion.summary()));
+    }
+    output
+}
+
+/// Completion hook: writes the bell and notification to the terminal.
+pub fn notify_build_finished(completion: &BuildCompletion) {
+    let output = build_notification(completion, NotificationEscape::detect());
+    let mut stdout = std::io::stdout();
+    // A notification that can't be written is not worth interrupting the UI for
+    let _ = stdout.write_all(output.as_bytes());
+    let _ = stdout.flush();
+}
+
+/// Removes control characters that would end the escape early.
+fn clean(text: &str) -> String {
+    text.chars().filter(|c| !c.is_control()).collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::time::Duration;
+
+    fn env<'a>(vars: &'a [(&'a str, &'a str)]) -> impl Fn(&str) -> Option<String> + 'a {
+        move |name| {
+            vars.iter()
+                .find(|(key, _)| *key == name)
+                .map(|(_, value)| (*value).to_string())
+        }
+    }
+
+    #[test]
+    fn test_detect_escape_from_environment() {
+        let detect = |vars: &[(&str, &str)]| NotificationEscape::detect_from(env(vars));
+        assert_eq!(
+            detect(&[("TERM_PROGRAM", "iTerm.app")]),
+            Some(NotificationEscape::Osc9)
+        );
+        assert_eq!(
+            detect(&[("VTE_VERSION", "7600"), ("TERM", "xterm-256color")]),
+            Some(NotificationEscape::Osc777)
+        );
+        assert_eq!(
+            detect(&[("TERM", "foot")]),
+            Some(NotificationEscape::Osc777)
+        );
+        assert_eq!(
+            detect(&[("TERM_PROGRAM", "iTerm.app"), ("TMUX", "/tmp/tmux")]),
+            None
+        );
+        assert_eq!(detect(&[("TERM", "xterm-256color")]), None);
+    }
+
+    #[test]
+    fn test_build_notification_carries_outcome_and_duration() {
+        let completion = BuildCompletion::new(
+            false,
+            "crkbd:default",
+            Duration::from_secs(75),
+            Some("make\nerror".to_string()),
+        );
+
+        assert_eq!(build_notification(&completion, None), "\x07");
+        assert_eq!(
+            build_notification(&completion, Some(NotificationEscape::Osc777)),
+            "\x07\x1b]777;notify;Build failed;crkbd:default failed after 1m 15s\x07"
+        );
+        assert_eq!(
+            build_notification(&completion, Some(NotificationEscape::Osc9)),
+            "\x07\x1b]9;Build failed: crkbd:default failed after 1m 15s\x07"
+        );
+    }
+}
@@ -16,13 +16,14 @@ use ratatui::{
     widgets::{Block, Borders, Clear, List, ListItem, Paragraph},
     Frame,
 };
-use std::collections::HashMap;
+use std::collections::{BTreeMap, HashMap};
 use std::path::PathBuf;
 
-use crate::config::Config;
+use crate::config::{Config, KeyboardDefaults};
 use crate::parser::keyboard_json::{
     extract_layout_names, parse_keyboard_info_json, scan_keyboards,
 };
+use crate::tui::text_input::TextInput;
 
 /// Onboarding wizard steps
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
@@ -124,11 +125,11 @@ pub struct OnboardingWizardState {
     /// User inputs collected so far
     pub inputs: HashMap<String, String>,
     /// Current text input buffer
-    pub input_buffer: String,
+    pub input_buffer: TextInput,
     /// List of available keyboards (populated after QMK path is set)
     pub available_keyboards: Vec<String>,
     /// Filter text for keyboard search
-    pub keyboard_filter: String,
+    pub keyboard_filter: TextInput,
     /// Selected keyboard index in list
     pub keyboard_selected_index: usize,
     /// Focus state for keyboard selection (filter input vs list)
@@ -143,6 +144,8 @@ pub struct OnboardingWizardState {
     pub is_complete: bool,
     /// Whether this is a keyboard-only change (skip other config steps)
     pub keyboard_change_only: bool,
+    /// Per-keyboard defaults from the config, for preselecting the variant
+    pub keyboard_defaults: BTreeMap<String, KeyboardDefaults>,
 }
 
 impl OnboardingWizardState {
@@ -152,9 +155,9 @@ impl OnboardingWizardState {
         Self {
             current_step: WizardStep::Welcome,
             inputs: HashMap::new(),
-            input_buffer: String::new(),
+            input_buffer: TextInput::default(),
             available_keyboards: Vec::new(),
-            keyboard_filter: String::new(),
+            keyboard_filter: TextInput::default(),
             keyboard_selected_index: 0,
             keyboard_selection_focus: KeyboardSelectionFocus::FilterInput,
             available_layouts: Vec::new(),
@@ -162,6 +165,7 @@ impl OnboardingWizardState {
             error_message: None,
             is_complete: false,
             keyboard_change_only: false,
+            keyboard_defaults: BTreeMap::new(),
         }
     }
 
@@ -188,9 +192,9 @@ impl OnboardingWizardState {
         Ok(Self {
             current_step: WizardStep::KeyboardSelection,
             inputs,
-            input_buffer: String::new(),
+            input_buffer: TextInput::default(),
             available_keyboards: keyboards,
-            keyboard_filter: String::new(),
+            keyboard_filter: TextInput::default(),
             keyboard_selected_index: 0,
             keyboard_selection_focus: KeyboardSelectionFocus::FilterInput,
             available_layouts: Vec::new(),
@@ -198,6 +202,7 @@ impl OnboardingWizardState {
             error_message: None,
             is_complete: false,
             keyboard_change_only: true,
+            keyboard_defaults: BTreeMap::new(),
         })
     }
 
@@ -236,9 +241,9 @@ impl OnboardingWizardState {
         Ok(Self {
             current_step: WizardStep::KeyboardSelection,
             inputs,
-            input_buffer: String::new(),
+            input_buffer: TextInput::default(),
             available_keyboards: keyboards,
-            keyboard_filter: String::new(),
+            keyboard_filter: TextInput::default(),
             keyboard_selected_index: 0,
             keyboard_selection_focus: KeyboardSelectionFocus::FilterInput,
             available_layouts: Vec::new(),
@@ -246,6 +251,7 @@ impl OnboardingWizardState {
             error_message: None,
             is_complete: false,
             keyboard_change_only: false, // Go through all steps
+            keyboard_defaults: config.keyboards.clone(),
         })
     }
 
@@ -254,7 +260,7 @@ impl OnboardingWizardState {
         if self.keyboard_filter.is_empty() {
             self.available_keyboards.clone()
         } else {
-            let filter_lower = self.keyboard_filter.to_lowercase();
+            let filter_lower = self.keyboard_filter.value().to_lowercase();
             self.available_keyboards
                 .iter()
                 .filter(|kb| kb.to_lowercase().contains(&filter_lower))
@@ -279,7 +285,7 @@ impl OnboardingWizardState {
                     return Ok(());
                 }
 
-                let qmk_path = PathBuf::from(&self.input_buffer);
+                let qmk_path = PathBuf::from(self.input_buffer.value());
                 if !qmk_path.exists() {
                     self.error_message =
                         Some(format!("Path does not exist: {}", qmk_path.display()));
@@ -299,8 +305,10 @@ impl OnboardingWizardState {
                     return Ok(());
                 }
 
-                self.inputs
-                    .insert("qmk_path".to_string(), self.input_buffer.clone());
+                self.inputs.insert(
+                    "qmk_path".to_string(),
+                    self.input_buffer.value().to_string(),
+                );
                 self.input_buffer.clear();
 
                 // Scan keyboards
@@ -334,7 +342,17 @@ impl OnboardingWizardState {
                 match parse_keyboard_info_json(&qmk_path, &keyboard) {
                     Ok(info) => {
                         self.available_layouts = extract_layout_names(&info);
-                        self.layout_selected_index = 0;
+                        // Preselect the variant last used for this keyboard
+                        let remembered =
+                            KeyboardDefaults::lookup(&self.keyboard_defaults, &keyboard)
+                                .and_then(|(_, defaults)| defaults.default_variant.as_ref());
+                        self.layout_selected_index = remembered
+                            .and_then(|variant| {
+                                self.available_layouts
+                                    .iter()
+                                    .position(|name| name == variant)
+                            })
+                            .unwrap_or(0);
                         self.current_step = WizardStep::LayoutSelection;
                     }
                     Err(e) => {
@@ -362,7 +380,8 @@ impl OnboardingWizardState {
                 if !self.inputs.contains_key("layout_name") {
                     let keyboard = self.inputs.get("keyboard").unwrap();
                     let default_name = keyboard.split('/').next_back().unwrap_or(keyboard);
-                    self.input_buffer = format!("{default_name}_layout");
+                    self.input_buffer
+                        .set_value(format!("{default_name}_layout"));
                 }
 
                 self.current_step = WizardStep::LayoutName;
@@ -374,14 +393,17 @@ impl OnboardingWizardState {
                     return Ok(());
                 }
 
-                self.inputs
-                    .insert("layout_name".to_string(), self.input_buffer.clone());
+                self.inputs.insert(
+                    "layout_name".to_string(),
+                    self.input_buffer.value().to_string(),
+                );
                 self.input_buffer.clear();
 
                 // Pre-populate output path with default if not already set
                 if !self.inputs.contains_key("output_path") {
-                    if let Ok(default_dir) = Config::config_dir() {
-                        self.input_buffer = default_dir.join("builds").display().to_string();
+                    if let Ok(paths) = crate::paths::AppPaths::current() {
+                        self.input_buffer
+                            .set_value(paths.builds_dir().display().to_string());
                     }
                 }
 
@@ -394,7 +416,7 @@ impl OnboardingWizardState {
                     return Ok(());
                 }
 
-                let output_path = PathBuf::from(&self.input_buffer);
+                let output_path = PathBuf::from(self.input_buffer.value());
 
                 // Validate parent directory exists or can be created
                 if let Some(parent) = output_path.parent() {
@@ -407,8 +429,10 @@ impl OnboardingWizardState {
                     }
                 }
 
-                self.inputs
-                    .insert("output_path".to_string(), self.input_buffer.clone());
+                self.inputs.insert(
+                    "output_path".to_string(),
+                    self.input_buffer.value().to_string(),
+                );
                 self.input_buffer.clear();
                 self.current_step = WizardStep::Confirmation;
             }
@@ -431,7 +455,7 @@ impl OnboardingWizardState {
             // Restore input buffer if going back to QmkPath
             if self.current_step == WizardStep::QmkPath {
                 if let Some(qmk_path) = self.inputs.get("qmk_path") {
-                    self.input_buffer = qmk_path.clone();
+                    self.input_buffer.set_value(qmk_path.clone());
                 }
             }
 
@@ -444,14 +468,14 @@ impl OnboardingWizardState {
             // Restore layout name when going back
             if self.current_step == WizardStep::LayoutName {
                 if let Some(layout_name) = self.inputs.get("layout_name") {
-                    self.input_buffer = layout_name.clone();
+                    self.input_buffer.set_value(layout_name.clone());
                 }
             }
 
             // Restore output path when going back
             if self.current_step == WizardStep::OutputPath {
                 if let Some(output_path) = self.inputs.get("output_path") {
-                    self.input_buffer = output_path.clone();
+                    self.input_buffer.set_value(output_path.clone());
                 }
             }
         }
@@ -490,12 +514,16 @@ impl OnboardingWizardState {
             wizard
                 .inputs
                 .insert("qmk_path".to_string(), qmk_path.display().to_string());
-            wizard.input_buffer = qmk_path.display().to_string();
+            wizard
+                .input_buffer
+                .set_value(qmk_path.display().to_string());
         }
 
         // Note: keyboard and layout are now per-layout in metadata, not in config
         // The wizard is for initial setup only
 
+        wizard.keyboard_defaults.clone_from(&config.keyboards);
+
         // Pre-populate output path
         wizard.inputs.insert(
             "output_path".to_string(),
@@ -620,6 +648,14 @@ fn render_welcome(f: &mut Frame, area: Rect, theme: &crate::tui::theme::Theme) {
     f.render_widget(paragraph, area);
 }
 
+/// The `> ` prompt line for a step's text input.
+fn input_line<'a>(input: &'a TextInput, theme: &crate::tui::theme::Theme) -> Line<'a> {
+    let style = Style::default().fg(theme.accent);
+    let mut spans = vec![Span::styled("> ", style)];
+    spans.extend(input.spans(style, true));
+    Line::from(spans)
+}
+
 fn render_qmk_path_input(
     f: &mut Frame,
     state: &OnboardingWizardState,
@@ -630,10 +666,7 @@ fn render_qmk_path_input(
         Line::from(""),
         Line::from("Enter the path to your QMK firmware directory:"),
         Line::from(""),
-        Line::from(Span::styled(
-            format!("> {}_", state.input_buffer),
-            Style::default().fg(theme.accent),
-        )),
+        input_line(&state.input_buffer, theme),
         Line::from(""),
         Line::from("Example: /home/user/qmk_firmware"),
         Line::from("         C:\\Users\\user\\qmk_firmware"),
@@ -680,12 +713,15 @@ fn render_keyboard_selection(
         Style::default().fg(theme.primary)
     };
 
-    let filter_input = Paragraph::new(format!("Filter: {}_", state.keyboard_filter))
-        .style(Style::default().fg(if filter_focused {
-            theme.accent
-        } else {
-            theme.text
-        }))
+    let filter_style = Style::default().fg(if filter_focused {
+        theme.accent
+    } else {
+        theme.text
+    });
+    let mut filter_spans = vec![Span::raw("Filter: ")];
+    filter_spans.extend(state.keyboard_filter.spans(filter_style, filter_focused));
+    let filter_input = Paragraph::new(Line::from(filter_spans))
+        .style(filter_style)
         .block(
             Block::default()
                 .borders(Borders::ALL)
@@ -741,9 +777,11 @@ fn render_keyboard_selection(
                 .style(list_border_style.bg(theme.background)),
         )
         .highlight_style(
-            Style::default()
-                .fg(theme.accent)
-                .add_modifier(Modifier::BOLD),
+            theme.selection(
+                Style::default()
+                    .fg(theme.accent)
+                    .add_modifier(Modifier::BOLD),
+            ),
         );
 
     f.render_widget(list, chunks[1]);
@@ -795,9 +833,11 @@ fn render_layout_selection(
                 .style(Style::default().fg(theme.primary).bg(theme.background)),
         )
         .highlight_style(
-            Style::default()
-                .fg(theme.accent)
-                .add_modifier(Modifier::BOLD),
+            theme.selection(
+                Style::default()
+                    .fg(theme.accent)
+                    .add_modifier(Modifier::BOLD),
+            ),
         );
 
     f.render_widget(list, area);
@@ -813,10 +853,7 @@ fn render_layout_name_input(
         Line::from(""),
         Line::from("Enter a name for your layout file:"),
         Line::from(""),
-        Line::from(Span::styled(
-            format!("> {}_", state.input_buffer),
-            Style::default().fg(theme.accent),
-        )),
+        input_line(&state.input_buffer, theme),
         Line::from(""),
         Line::from("This will be used as the filename (e.g., my_layout.md)"),
         Line::from("The file will be saved in your layouts directory."),
@@ -844,10 +881,7 @@ fn render_output_path_input(
         Line::from(""),
         Line::from("Enter the path where firmware files should be copied:"),
         Line::from(""),
-        Line::from(Span::styled(
-            format!("> {}_", state.input_buffer),
-            Style::default().fg(theme.accent),
-        )),
+        input_line(&state.input_buffer, theme),
         Line::from(""),
         Line::from("After building, firmware files (.uf2, .hex) will be copied here."),
         Line::from("This makes it easy to flash your keyboard."),
@@ -970,16 +1004,12 @@ pub fn handle_input(state: &mut OnboardingWizardState, key: KeyEvent) -> Result<
             KeyCode::Enter => {
                 state.next_step()?;
             }
-            KeyCode::Backspace => {
-                state.input_buffer.pop();
-            }
-            KeyCode::Char(c) => {
-                state.input_buffer.push(c);
-            }
             KeyCode::Esc => {
                 state.previous_step();
             }
-            _ => {}
+            _ => {
+                state.input_buffer.handle_key(key);
+            }
         },
         WizardStep::KeyboardSelection => match state.keyboard_selection_focus {
             KeyboardSelectionFocus::FilterInput => match key.code {
@@ -996,18 +1026,6 @@ pub fn handle_input(state: &mut OnboardingWizardState, key: KeyEvent) -> Result<
                         state.keyboard_selection_focus = KeyboardSelectionFocus::List;
                     }
                 }
-                KeyCode::Char(c) => {
-                    // Add character to filter
-                    state.keyboard_filter.push(c);
-                    // Reset selection to first item when filter changes
-                    state.keyboard_selected_index = 0;
-                }
-                KeyCode::Backspace => {
-                    // Remove character from filter
-                    state.keyboard_filter.pop();
-                    // Reset selection to first item when filter changes
-                    state.keyboard_selected_index = 0;
-                }
                 KeyCode::Esc => {
                     // If filter is active, clear it
                     if !state.keyboard_filter.is_empty() {
@@ -1020,7 +1038,12 @@ pub fn handle_input(state: &mut OnboardingWizardState, key: KeyEvent) -> Result<
                         state.previous_step();
                     }
                 }
-                _ => {}
+                _ => {
+                    // Reset selection to first item when filter changes
+                    if state.keyboard_filter.handle_key(key) {
+                        state.keyboard_selected_index = 0;
+                    }
+                }
             },
             KeyboardSelectionFocus::List => match key.code {
                 KeyCode::Tab => {
@@ -1028,9 +1051,7 @@ pub fn handle_input(state: &mut OnboardingWizardState, key: KeyEvent) -> Result<
                     state.keyboard_selection_focus = KeyboardSelectionFocus::FilterInput;
                 }
                 KeyCode::Up | KeyCode::Char('k') => {
-                    if state.keyboard_selected_index > 0 {
-                        state.keyboard_selected_index -= 1;
-                    }
+                    state.keyboard_selected_index = state.keyboard_selected_index.saturating_sub(1);
                 }
                 KeyCode::Down | KeyCode::Char('j') => {
                     let filtered_count = state.get_filtered_keyboards().len();
@@ -1050,14 +1071,11 @@ pub fn handle_input(state: &mut OnboardingWizardState, key: KeyEvent) -> Result<
         },
         WizardStep::LayoutSelection => match key.code {
             KeyCode::Up | KeyCode::Char('k') => {
-                if state.layout_selected_index > 0 {
-                    state.layout_selected_index -= 1;
-                }
+                state.layout_selected_index = state.layout_selected_index.saturating_sub(1);
             }
             KeyCode::Down | KeyCode::Char('j') => {
-                if state.layout_selected_index < state.available_layouts.len().saturating_sub(1) {
-                    state.layout_selected_index += 1;
-                }
+                let last_index = state.available_layouts.len().saturating_sub(1);
+                state.layout_selected_index = (state.layout_selected_index + 1).min(last_index);
             }
             KeyCode::Enter => {
                 state.next_step()?;
@@ -1079,31 +1097,23 @@ pub fn handle_input(state: &mut OnboardingWizardState, key: KeyEvent) -> Result<
             KeyCode::Enter => {
                 state.next_step()?;
             }
-            KeyCode::Backspace => {
-                state.input_buffer.pop();
-            }
-            KeyCode::Char(c) => {
-                state.input_buffer.push(c);
-            }
             KeyCode::Esc => {
                 state.previous_step();
             }
-            _ => {}
+            _ => {
+                state.input_buffer.handle_key(key);
+            }
         },
         WizardStep::OutputPath => match key.code {
             KeyCode::Enter => {
                 state.next_step()?;
             }
-            KeyCode::Backspace => {
-                state.input_buffer.pop();
-            }
-            KeyCode::Char(c) => {
-                state.input_buffer.push(c);
-            }
             KeyCode::Esc => {
                 state.previous_step();
             }
-            _ => {}
+            _ => {
+                state.input_buffer.handle_key(key);
+            }
         },
         WizardStep::Confirmation => match key.code {
             KeyCode::Enter => {
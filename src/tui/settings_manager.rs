@@ -13,11 +13,13 @@ use ratatui::{
     Frame,
 };
 
+use crate::config::TransparentKeyStyle;
 use crate::models::{
     HoldDecisionMode, IdleEffectSettings, RgbBrightness, RgbMatrixEffect, TapHoldPreset,
     TapHoldSettings, UncoloredKeyBehavior,
 };
 
+use super::text_input::TextInput;
 use super::Theme;
 
 /// Setting group for organization
@@ -84,10 +86,22 @@ pub enum SettingItem {
     // === UI Settings (Global) ===
     /// Display help on startup
     ShowHelpOnStartup,
-    /// Theme mode (Auto, Dark, Light)
+    /// Theme mode (Auto, Dark, Light, High contrast)
     ThemeMode,
     /// Unified keyboard scale factor
     KeyboardScale,
+    /// Merge queued navigation keys into one move per frame
+    NavigationCoalescing,
+    /// Move faster while a navigation key is held
+    NavigationAcceleration,
+    /// Bell and desktop notification when a build finishes
+    BuildNotifications,
+    /// Static markers instead of animations
+    ReducedMotion,
+    /// Banner for layouts with validation issues
+    ValidationBanner,
+    /// How transparent keys are drawn
+    TransparentKeys,
 
     // === RGB Settings (Per-Layout) ===
     /// Master switch for all RGB LEDs
@@ -145,6 +159,12 @@ impl SettingItem {
             Self::ShowHelpOnStartup,
             Self::ThemeMode,
             Self::KeyboardScale,
+            Self::NavigationCoalescing,
+            Self::NavigationAcceleration,
+            Self::BuildNotifications,
+            Self::ReducedMotion,
+            Self::ValidationBanner,
+            Self::TransparentKeys,
             // RGB (Per-Layout)
             Self::RgbEnabled,
             Self::RgbBrightness,
@@ -177,7 +197,15 @@ impl SettingItem {
             | Self::KeymapName
             | Self::OutputFormat
             | Self::OutputDir => SettingGroup::Build,
-            Self::ShowHelpOnStartup | Self::ThemeMode | Self::KeyboardScale => SettingGroup::Ui,
+            Self::ShowHelpOnStartup
+            | Self::ThemeMode
+            | Self::KeyboardScale
+            | Self::NavigationCoalescing
+            | Self::NavigationAcceleration
+            | Self::BuildNotifications
+            | Self::ReducedMotion
+            | Self::ValidationBanner
+            | Self::TransparentKeys => SettingGroup::Ui,
             Self::RgbEnabled
             | Self::RgbBrightness
             | Self::RgbSaturation
@@ -209,6 +237,12 @@ impl SettingItem {
             Self::OutputFormat => "Output Format",
             Self::OutputDir => "Output Directory",
             Self::ShowHelpOnStartup => "Show Help on Startup",
+            Self::NavigationCoalescing => "Coalesce Held Navigation",
+            Self::NavigationAcceleration => "Navigation Acceleration",
+            Self::BuildNotifications => "Build Notifications",
+            Self::ReducedMotion => "Reduced Motion",
+            Self::ValidationBanner => "Validation Banner",
+            Self::TransparentKeys => "Transparent Keys",
             Self::ThemeMode => "Theme Mode",
             Self::KeyboardScale => "Keyboard Scale",
             Self::RgbEnabled => "RGB Master Switch",
@@ -242,7 +276,25 @@ impl SettingItem {
             Self::OutputFormat => "Firmware output format: uf2, hex, or bin",
             Self::OutputDir => "Directory where built firmware will be saved",
             Self::ShowHelpOnStartup => "Display help overlay when application starts",
-            Self::ThemeMode => "Color theme: Auto (follow OS), Dark, or Light",
+            Self::NavigationCoalescing => {
+                "Merge queued arrow/hjkl repeats so the cursor stops when the key is released"
+            }
+            Self::NavigationAcceleration => {
+                "Jump 2-3 keys per repeat while a navigation key is held"
+            }
+            Self::BuildNotifications => {
+                "Ring the terminal bell and send a desktop notification when a build finishes"
+            }
+            Self::ReducedMotion => {
+                "Keep pasted keys marked until the next key press and show elapsed time instead of spinners"
+            }
+            Self::ValidationBanner => {
+                "Show a banner under the title when the layout has validation errors or warnings"
+            }
+            Self::TransparentKeys => {
+                "Draw KC_TRNS keys as labels, dimmed, or as outlines (hidden keys are left out of exports)"
+            }
+            Self::ThemeMode => "Color theme: Auto (follow OS), Dark, Light, or High contrast",
             Self::KeyboardScale => "Keyboard display size: 1.0 = default, 0.5 = half, 2.0 = double",
             Self::RgbEnabled => "Turn all RGB LEDs on or off",
             Self::RgbBrightness => "Global brightness multiplier for all LEDs (0-100%)",
@@ -296,8 +348,8 @@ pub enum ManagerMode {
     EditingNumeric {
         /// Which setting is being edited
         setting: SettingItem,
-        /// Current value as string for editing
-        value: String,
+        /// Current value as text for editing
+        value: TextInput,
         /// Minimum allowed value
         min: u16,
         /// Maximum allowed value
@@ -315,7 +367,7 @@ pub enum ManagerMode {
         /// Which setting is being edited
         setting: SettingItem,
         /// Current value
-        value: String,
+        value: TextInput,
     },
     /// Selecting output format (uf2, hex, bin)
     SelectingOutputFormat {
@@ -327,12 +379,17 @@ pub enum ManagerMode {
         /// Currently highlighted option index
         selected_option: usize,
     },
+    /// Selecting transparent key style (Label, Dim, Hidden)
+    SelectingTransparentKeys {
+        /// Currently highlighted option index
+        selected_option: usize,
+    },
     /// Editing a path (QMK path, output dir)
     EditingPath {
         /// Which setting is being edited
         setting: SettingItem,
         /// Current value
-        value: String,
+        value: TextInput,
     },
     /// Selecting idle effect mode
     SelectingIdleEffectMode {
@@ -409,7 +466,7 @@ impl SettingsManagerState {
     ) {
         self.mode = ManagerMode::EditingNumeric {
             setting,
-            value: current.to_string(),
+            value: TextInput::from(current.to_string()),
             min,
             max,
         };
@@ -430,6 +487,7 @@ impl SettingsManagerState {
             | ManagerMode::SelectingHoldMode { selected_option }
             | ManagerMode::SelectingOutputFormat { selected_option }
             | ManagerMode::SelectingThemeMode { selected_option }
+            | ManagerMode::SelectingTransparentKeys { selected_option }
             | ManagerMode::SelectingIdleEffectMode { selected_option } => {
                 if *selected_option > 0 {
                     *selected_option -= 1;
@@ -451,6 +509,7 @@ impl SettingsManagerState {
             | ManagerMode::SelectingHoldMode { selected_option }
             | ManagerMode::SelectingOutputFormat { selected_option }
             | ManagerMode::SelectingThemeMode { selected_option }
+            | ManagerMode::SelectingTransparentKeys { selected_option }
             | ManagerMode::SelectingIdleEffectMode { selected_option } => {
                 *selected_option = (*selected_option + 1) % option_count;
             }
@@ -469,6 +528,7 @@ impl SettingsManagerState {
             | ManagerMode::SelectingHoldMode { selected_option }
             | ManagerMode::SelectingOutputFormat { selected_option }
             | ManagerMode::SelectingThemeMode { selected_option }
+            | ManagerMode::SelectingTransparentKeys { selected_option }
             | ManagerMode::SelectingIdleEffectMode { selected_option } => Some(*selected_option),
             _ => None,
         }
@@ -477,35 +537,35 @@ impl SettingsManagerState {
     /// Handle character input for numeric editing
     pub fn handle_char_input(&mut self, c: char) {
         if let ManagerMode::EditingNumeric { value, max, .. } = &mut self.mode {
-            if c.is_ascii_digit() {
-                value.push(c);
-                // Cap at max length to prevent overflow
-                if value.len() > 4 {
-                    value.pop();
-                }
+            // Cap at max length to prevent overflow
+            if c.is_ascii_digit() && value.value().len() < 4 {
+                value.insert_char(c);
                 // Cap at max value
-                if let Ok(num) = value.parse::<u16>() {
+                if let Ok(num) = value.value().parse::<u16>() {
                     if num > *max {
-                        *value = max.to_string();
+                        value.set_value(max.to_string());
                     }
                 }
             }
         }
     }
 
-    /// Handle backspace for numeric editing
-    pub fn handle_backspace(&mut self) {
+    /// Handle cursor movement and deletion for numeric editing
+    pub fn handle_numeric_key(&mut self, key: KeyEvent) {
         if let ManagerMode::EditingNumeric { value, .. } = &mut self.mode {
-            value.pop();
+            // Digits go through `handle_char_input`
+            if !matches!(key.code, KeyCode::Char(_)) {
+                value.handle_key(key);
+            }
         }
     }
 
     /// Increment numeric value
     pub fn increment_numeric(&mut self, step: u16) {
         if let ManagerMode::EditingNumeric { value, max, .. } = &mut self.mode {
-            if let Ok(mut num) = value.parse::<u16>() {
+            if let Ok(mut num) = value.value().parse::<u16>() {
                 num = num.saturating_add(step).min(*max);
-                *value = num.to_string();
+                value.set_value(num.to_string());
             }
         }
     }
@@ -513,9 +573,9 @@ impl SettingsManagerState {
     /// Decrement numeric value
     pub fn decrement_numeric(&mut self, step: u16) {
         if let ManagerMode::EditingNumeric { value, min, .. } = &mut self.mode {
-            if let Ok(mut num) = value.parse::<u16>() {
+            if let Ok(mut num) = value.value().parse::<u16>() {
                 num = num.saturating_sub(step).max(*min);
-                *value = num.to_string();
+                value.set_value(num.to_string());
             }
         }
     }
@@ -524,7 +584,7 @@ impl SettingsManagerState {
     #[must_use]
     pub fn get_numeric_value(&self) -> Option<u16> {
         if let ManagerMode::EditingNumeric { value, min, .. } = &self.mode {
-            value.parse().ok().or(Some(*min))
+            value.value().parse().ok().or(Some(*min))
         } else {
             None
         }
@@ -544,7 +604,7 @@ impl SettingsManagerState {
     pub fn start_editing_string(&mut self, setting: SettingItem, current: String) {
         self.mode = ManagerMode::EditingString {
             setting,
-            value: current,
+            value: TextInput::from(current),
         };
     }
 
@@ -552,7 +612,7 @@ impl SettingsManagerState {
     pub fn start_editing_path(&mut self, setting: SettingItem, current: String) {
         self.mode = ManagerMode::EditingPath {
             setting,
-            value: current,
+            value: TextInput::from(current),
         };
     }
 
@@ -570,6 +630,15 @@ impl SettingsManagerState {
         };
     }
 
+    /// Start selecting transparent key style
+    pub fn start_selecting_transparent_keys(&mut self, current: TransparentKeyStyle) {
+        let selected_option = TransparentKeyStyle::all()
+            .iter()
+            .position(|&style| style == current)
+            .unwrap_or(0);
+        self.mode = ManagerMode::SelectingTransparentKeys { selected_option };
+    }
+
     /// Start selecting idle effect mode
     pub fn start_selecting_idle_effect_mode(&mut self, current: RgbMatrixEffect) {
         let selected_option = RgbMatrixEffect::all()
@@ -579,21 +648,11 @@ impl SettingsManagerState {
         self.mode = ManagerMode::SelectingIdleEffectMode { selected_option };
     }
 
-    /// Handle character input for string/path editing
-    pub fn handle_string_char_input(&mut self, c: char) {
+    /// Handle text editing keys for string/path editing
+    pub fn handle_string_key(&mut self, key: KeyEvent) {
         match &mut self.mode {
             ManagerMode::EditingString { value, .. } | ManagerMode::EditingPath { value, .. } => {
-                value.push(c);
-            }
-            _ => {}
-        }
-    }
-
-    /// Handle backspace for string/path editing
-    pub fn handle_string_backspace(&mut self) {
-        match &mut self.mode {
-            ManagerMode::EditingString { value, .. } | ManagerMode::EditingPath { value, .. } => {
-                value.pop();
+                value.handle_key(key);
             }
             _ => {}
         }
@@ -604,7 +663,7 @@ impl SettingsManagerState {
     pub fn get_string_value(&self) -> Option<&str> {
         match &self.mode {
             ManagerMode::EditingString { value, .. } | ManagerMode::EditingPath { value, .. } => {
-                Some(value)
+                Some(value.value())
             }
             _ => None,
         }
@@ -716,6 +775,9 @@ impl SettingsManager {
             ManagerMode::EditingString { .. } => self.handle_string_editing(key),
             ManagerMode::SelectingOutputFormat { .. } => self.handle_output_format_selection(key),
             ManagerMode::SelectingThemeMode { .. } => self.handle_theme_mode_selection(key),
+            ManagerMode::SelectingTransparentKeys { .. } => {
+                self.handle_transparent_keys_selection(key)
+            }
             ManagerMode::EditingPath { .. } => self.handle_path_editing(key),
             ManagerMode::SelectingIdleEffectMode { .. } => {
                 self.handle_idle_effect_mode_selection(key)
@@ -843,16 +905,15 @@ impl SettingsManager {
                 self.state.decrement_numeric(10);
                 None
             }
-            KeyCode::Char(c) if c.is_ascii_digit() => {
+            KeyCode::Char(c) => {
                 self.state.handle_char_input(c);
                 None
             }
-            KeyCode::Backspace => {
-                self.state.handle_backspace();
+            KeyCode::Enter => Some(SettingsManagerEvent::SettingsUpdated),
+            _ => {
+                self.state.handle_numeric_key(key);
                 None
             }
-            KeyCode::Enter => Some(SettingsManagerEvent::SettingsUpdated),
-            _ => None,
         }
     }
 
@@ -877,16 +938,11 @@ impl SettingsManager {
                 self.state.cancel();
                 None
             }
-            KeyCode::Char(c) => {
-                self.state.handle_string_char_input(c);
-                None
-            }
-            KeyCode::Backspace => {
-                self.state.handle_string_backspace();
+            KeyCode::Enter => Some(SettingsManagerEvent::SettingsUpdated),
+            _ => {
+                self.state.handle_string_key(key);
                 None
             }
-            KeyCode::Enter => Some(SettingsManagerEvent::SettingsUpdated),
-            _ => None,
         }
     }
 
@@ -916,11 +972,11 @@ impl SettingsManager {
                 None
             }
             KeyCode::Up | KeyCode::Char('k') => {
-                self.state.option_previous(3);
+                self.state.option_previous(4);
                 None
             }
             KeyCode::Down | KeyCode::Char('j') => {
-                self.state.option_next(3);
+                self.state.option_next(4);
                 None
             }
             KeyCode::Enter => Some(SettingsManagerEvent::SettingsUpdated),
@@ -928,18 +984,19 @@ impl SettingsManager {
         }
     }
 
-    fn handle_path_editing(&mut self, key: KeyEvent) -> Option<SettingsManagerEvent> {
+    fn handle_transparent_keys_selection(&mut self, key: KeyEvent) -> Option<SettingsManagerEvent> {
+        let count = TransparentKeyStyle::all().len();
         match key.code {
             KeyCode::Esc => {
                 self.state.cancel();
                 None
             }
-            KeyCode::Char(c) => {
-                self.state.handle_string_char_input(c);
+            KeyCode::Up | KeyCode::Char('k') => {
+                self.state.option_previous(count);
                 None
             }
-            KeyCode::Backspace => {
-                self.state.handle_string_backspace();
+            KeyCode::Down | KeyCode::Char('j') => {
+                self.state.option_next(count);
                 None
             }
             KeyCode::Enter => Some(SettingsManagerEvent::SettingsUpdated),
@@ -947,6 +1004,20 @@ impl SettingsManager {
         }
     }
 
+    fn handle_path_editing(&mut self, key: KeyEvent) -> Option<SettingsManagerEvent> {
+        match key.code {
+            KeyCode::Esc => {
+                self.state.cancel();
+                None
+            }
+            KeyCode::Enter => Some(SettingsManagerEvent::SettingsUpdated),
+            _ => {
+                self.state.handle_string_key(key);
+                None
+            }
+        }
+    }
+
     fn handle_idle_effect_mode_selection(&mut self, key: KeyEvent) -> Option<SettingsManagerEvent> {
         match key.code {
             KeyCode::Esc => {
@@ -1074,6 +1145,9 @@ pub fn render_settings_manager(
         ManagerMode::SelectingThemeMode { selected_option } => {
             render_theme_mode_selector(f, inner_area, *selected_option, theme);
         }
+        ManagerMode::SelectingTransparentKeys { selected_option } => {
+            render_transparent_keys_selector(f, inner_area, *selected_option, theme);
+        }
         ManagerMode::EditingPath { setting, value } => {
             render_path_editor(f, inner_area, *setting, value, theme);
         }
@@ -1178,7 +1252,7 @@ fn render_settings_list(
 
     let list = List::new(items)
         .block(Block::default().borders(Borders::ALL).title("Settings"))
-        .highlight_style(Style::default().bg(theme.surface));
+        .highlight_style(theme.selection(Style::default().bg(theme.surface)));
 
     f.render_widget(list, chunks[0]);
 
@@ -1254,10 +1328,42 @@ fn get_setting_value_display(
             "Off"
         }
         .to_string(),
+        SettingItem::NavigationCoalescing => if config.ui.coalesce_navigation {
+            "On"
+        } else {
+            "Off"
+        }
+        .to_string(),
+        SettingItem::NavigationAcceleration => if config.ui.navigation_acceleration {
+            "On"
+        } else {
+            "Off"
+        }
+        .to_string(),
+        SettingItem::BuildNotifications => if config.ui.build_notifications {
+            "On"
+        } else {
+            "Off"
+        }
+        .to_string(),
+        SettingItem::ReducedMotion => if config.ui.reduced_motion {
+            "On"
+        } else {
+            "Off"
+        }
+        .to_string(),
+        SettingItem::ValidationBanner => if config.ui.validation_banner {
+            "On"
+        } else {
+            "Off"
+        }
+        .to_string(),
+        SettingItem::TransparentKeys => config.ui.transparent_keys.display_name().to_string(),
         SettingItem::ThemeMode => match config.ui.theme_mode {
             crate::config::ThemeMode::Auto => "Auto".to_string(),
             crate::config::ThemeMode::Dark => "Dark".to_string(),
             crate::config::ThemeMode::Light => "Light".to_string(),
+            crate::config::ThemeMode::HighContrast => "High contrast".to_string(),
         },
         SettingItem::KeyboardScale => format!("{:.0}%", config.ui.keyboard_scale * 100.0),
         // Per-Layout: RGB
@@ -1425,7 +1531,7 @@ fn render_enum_selector<S1: AsRef<str>, S2: AsRef<str>>(
 
     let list = List::new(items)
         .block(Block::default().borders(Borders::ALL).title("Options"))
-        .highlight_style(Style::default().bg(theme.surface));
+        .highlight_style(theme.selection(Style::default().bg(theme.surface)));
 
     f.render_widget(list, chunks[1]);
 
@@ -1454,7 +1560,7 @@ fn render_numeric_editor(
     f: &mut Frame,
     area: Rect,
     setting: SettingItem,
-    value: &str,
+    value: &TextInput,
     min: u16,
     max: u16,
     theme: &Theme,
@@ -1480,15 +1586,17 @@ fn render_numeric_editor(
     f.render_widget(title_text, chunks[0]);
 
     // Input field with cursor
-    let display_value = format!("{value}▌");
     let input_block = Block::default()
         .borders(Borders::ALL)
         .border_style(Style::default().fg(theme.primary))
         .title("Value");
-    let input_text = Paragraph::new(display_value)
-        .style(Style::default().fg(theme.text))
-        .block(input_block);
-    f.render_widget(input_text, chunks[1]);
+    value.render(
+        f,
+        chunks[1],
+        input_block,
+        Style::default().fg(theme.text),
+        true,
+    );
 
     // Description and range
     let desc = vec![
@@ -1534,7 +1642,7 @@ fn render_string_editor(
     f: &mut Frame,
     area: Rect,
     setting: SettingItem,
-    value: &str,
+    value: &TextInput,
     theme: &Theme,
 ) {
     let chunks = ratatui::layout::Layout::default()
@@ -1558,15 +1666,17 @@ fn render_string_editor(
     f.render_widget(title_text, chunks[0]);
 
     // Input field with cursor
-    let display_value = format!("{value}▌");
     let input_block = Block::default()
         .borders(Borders::ALL)
         .border_style(Style::default().fg(theme.primary))
         .title("Value");
-    let input_text = Paragraph::new(display_value)
-        .style(Style::default().fg(theme.text))
-        .block(input_block);
-    f.render_widget(input_text, chunks[1]);
+    value.render(
+        f,
+        chunks[1],
+        input_block,
+        Style::default().fg(theme.text),
+        true,
+    );
 
     // Description
     let desc_text = Paragraph::new(setting.description())
@@ -1649,7 +1759,7 @@ fn render_output_format_selector(f: &mut Frame, area: Rect, selected_option: usi
 
     let list = List::new(items)
         .block(Block::default().borders(Borders::ALL).title("Format"))
-        .highlight_style(Style::default().bg(theme.surface));
+        .highlight_style(theme.selection(Style::default().bg(theme.surface)));
 
     f.render_widget(list, chunks[1]);
 
@@ -1699,6 +1809,7 @@ fn render_theme_mode_selector(f: &mut Frame, area: Rect, selected_option: usize,
         ("Auto", "Follow OS dark/light mode setting"),
         ("Dark", "Always use dark theme"),
         ("Light", "Always use light theme"),
+        ("High contrast", "Black and white with text markers"),
     ];
     let items: Vec<ListItem> = options
         .iter()
@@ -1726,7 +1837,89 @@ fn render_theme_mode_selector(f: &mut Frame, area: Rect, selected_option: usize,
 
     let list = List::new(items)
         .block(Block::default().borders(Borders::ALL).title("Theme"))
-        .highlight_style(Style::default().bg(theme.surface));
+        .highlight_style(theme.selection(Style::default().bg(theme.surface)));
+
+    f.render_widget(list, chunks[1]);
+
+    // Help text
+    let help = vec![
+        Line::from(""),
+        Line::from(vec![
+            Span::styled("↑/↓", Style::default().fg(theme.primary)),
+            Span::raw(": Select  "),
+            Span::styled("Enter", Style::default().fg(theme.primary)),
+            Span::raw(": Apply  "),
+            Span::styled("Esc", Style::default().fg(theme.primary)),
+            Span::raw(": Cancel"),
+        ]),
+    ];
+
+    let help_widget = Paragraph::new(help)
+        .alignment(Alignment::Center)
+        .style(Style::default().fg(theme.text_muted));
+
+    f.render_widget(help_widget, chunks[2]);
+}
+
+/// Render transparent key style selector
+fn render_transparent_keys_selector(
+    f: &mut Frame,
+    area: Rect,
+    selected_option: usize,
+    theme: &Theme,
+) {
+    let chunks = ratatui::layout::Layout::default()
+        .direction(ratatui::layout::Direction::Vertical)
+        .constraints([
+            Constraint::Length(2), // Title
+            Constraint::Min(5),    // Options
+            Constraint::Length(4), // Help
+        ])
+        .split(area);
+
+    // Title
+    let title_text = Paragraph::new("Transparent Keys")
+        .alignment(Alignment::Center)
+        .style(
+            Style::default()
+                .fg(theme.accent)
+                .add_modifier(Modifier::BOLD),
+        );
+    f.render_widget(title_text, chunks[0]);
+
+    // Options
+    let items: Vec<ListItem> = TransparentKeyStyle::all()
+        .iter()
+        .enumerate()
+        .map(|(idx, style_option)| {
+            let selected = idx == selected_option;
+            let style = if selected {
+                Style::default()
+                    .fg(theme.accent)
+                    .add_modifier(Modifier::BOLD)
+            } else {
+                Style::default().fg(theme.text)
+            };
+
+            let marker = if selected { "▶ " } else { "  " };
+            let description = match style_option {
+                TransparentKeyStyle::Label => "Drawn like other keys, labelled TRNS",
+                TransparentKeyStyle::Dim => "Greyed background and a ▽ glyph",
+                TransparentKeyStyle::Hidden => "Outline only, left out of exports",
+            };
+
+            ListItem::new(Line::from(vec![
+                Span::styled(marker, Style::default().fg(theme.primary)),
+                Span::styled(style_option.display_name(), style),
+                Span::styled(" - ", Style::default().fg(theme.text_muted)),
+                Span::styled(description, Style::default().fg(theme.text_muted)),
+            ]))
+        })
+        .collect();
+
+    let list = List::new(items)
+        .block(Block::default().borders(Borders::ALL).title("Style"))
+        .highlight_style(theme.selection(Style::default().bg(theme.surface)));
 
     f.render_widget(list, chunks[1]);
 
@@ -1751,7 +1944,13 @@ fn render_theme_mode_selector(f: &mut Frame, area: Rect, selected_option: usize,
 }
 
 /// Render path editor (for QMK path, output directory, etc.)
-fn render_path_editor(f: &mut Frame, area: Rect, setting: SettingItem, value: &str, theme: &Theme) {
+fn render_path_editor(
+    f: &mut Frame,
+    area: Rect,
+    setting: SettingItem,
+    value: &TextInput,
+    theme: &Theme,
+) {
     let chunks = ratatui::layout::Layout::default()
         .direction(ratatui::layout::Direction::Vertical)
         .constraints([
@@ -1773,18 +1972,20 @@ fn render_path_editor(f: &mut Frame, area: Rect, setting: SettingItem, value: &s
     f.render_widget(title_text, chunks[0]);
 
     // Input field with cursor
-    let display_value = format!("{value}▌");
     let input_block = Block::default()
         .borders(Borders::ALL)
         .border_style(Style::default().fg(theme.primary))
         .title("Path");
-    let input_text = Paragraph::new(display_value)
-        .style(Style::default().fg(theme.text))
-        .block(input_block);
-    f.render_widget(input_text, chunks[1]);
+    value.render(
+        f,
+        chunks[1],
+        input_block,
+        Style::default().fg(theme.text),
+        true,
+    );
 
     // Description and path validation hint
-    let path_exists = std::path::Path::new(value).exists();
+    let path_exists = std::path::Path::new(value.value()).exists();
     let status_line = if value.is_empty() {
         Line::from(Span::styled(
             "Enter a path",
@@ -1902,7 +2103,7 @@ fn render_boolean_toggle(
 
     let list = List::new(items)
         .block(Block::default().borders(Borders::ALL).title("Options"))
-        .highlight_style(Style::default().bg(theme.surface));
+        .highlight_style(theme.selection(Style::default().bg(theme.surface)));
 
     f.render_widget(list, chunks[1]);
 
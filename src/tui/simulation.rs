@@ -0,0 +1,122 @@
+//! Typing simulation for the keyboard widget.
+//!
+//! Keys typed on the real keyboard are matched to the base layer key that
+//! sends them, then pressed on a [`LayerSimulator`] so the keyboard widget
+//! can follow the layer a real board would be on.
+
+use crossterm::event::KeyCode;
+
+use crate::models::{Layout, Position};
+use crate::services::simulator::{tap_keycode, LayerSimulator};
+
+/// US keycodes of the symbol keys, with the plain and shifted character
+/// each one types.
+const SYMBOL_KEYCODES: &[(char, char, &str)] = &[
+    ('1', '!', "KC_1"),
+    ('2', '@', "KC_2"),
+    ('3', '#', "KC_3"),
+    ('4', '$', "KC_4"),
+    ('5', '%', "KC_5"),
+    ('6', '^', "KC_6"),
+    ('7', '&', "KC_7"),
+    ('8', '*', "KC_8"),
+    ('9', '(', "KC_9"),
+    ('0', ')', "KC_0"),
+    ('-', '_', "KC_MINS"),
+    ('=', '+', "KC_EQL"),
+    ('[', '{', "KC_LBRC"),
+    (']', '}', "KC_RBRC"),
+    ('\\', '|', "KC_BSLS"),
+    (';', ':', "KC_SCLN"),
+    ('\'', '"', "KC_QUOT"),
+    ('`', '~', "KC_GRV"),
+    (',', '<', "KC_COMM"),
+    ('.', '>', "KC_DOT"),
+    ('/', '?', "KC_SLSH"),
+];
+
+/// A running typing simulation.
+#[derive(Debug, Clone)]
+pub struct Simulation {
+    /// Layer state of the simulated keyboard
+    pub simulator: LayerSimulator,
+    /// Layer shown before the simulation started
+    pub restore_layer: usize,
+    /// Key selected before the simulation started
+    pub restore_position: Position,
+}
+
+impl Simulation {
+    /// Starts a simulation on the base layer, remembering the view to
+    /// return to.
+    #[must_use]
+    pub fn new(restore_layer: usize, restore_position: Position) -> Self {
+        Self {
+            simulator: LayerSimulator::new(),
+            restore_layer,
+            restore_position,
+        }
+    }
+
+    /// Finds the key on the default layer that sends what `code` typed.
+    #[must_use]
+    pub fn position_for(&self, layout: &Layout, code: KeyCode) -> Option<Position> {
+        let names = host_keycodes(code);
+        let layer = layout.layers.get(self.simulator.default_layer())?;
+        layer
+            .keys
+            .iter()
+            .find(|key| names.contains(&tap_keycode(&key.keycode)))
+            .map(|key| key.position)
+    }
+}
+
+/// Keycodes (short and long names) that send the key a terminal reported.
+fn host_keycodes(code: KeyCode) -> Vec<String> {
+    let names: &[&str] = match code {
+        KeyCode::Char(' ') => &["KC_SPC", "KC_SPACE"],
+        KeyCode::Enter => &["KC_ENT", "KC_ENTER"],
+        KeyCode::Tab | KeyCode::BackTab => &["KC_TAB"],
+        KeyCode::Backspace => &["KC_BSPC", "KC_BACKSPACE"],
+        KeyCode::Delete => &["KC_DEL", "KC_DELETE"],
+        KeyCode::Char(c) if c.is_ascii_alphabetic() => {
+            return vec![format!("KC_{}", c.to_ascii_uppercase())];
+        }
+        KeyCode::Char(c) => SYMBOL_KEYCODES
+            .iter()
+            .find(|(plain, shifted, _)| *plain == c || *shifted == c)
+            .map(|(_, _, name)| std::slice::from_ref(name))
+            .unwrap_or_default(),
+        _ => &[],
+    };
+    names.iter().map(ToString::to_string).collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::models::{KeyDefinition, Layer, RgbColor};
+
+    #[test]
+    fn test_typed_keys_map_to_base_layer_positions() {
+        let mut layout = Layout::new("Sim").unwrap();
+        let mut base = Layer::new(0, "Base", RgbColor::new(0, 0, 0)).unwrap();
+        for (col, keycode) in ["KC_Q", "LT(1, KC_SPACE)", "LSFT_T(KC_SCLN)", "KC_ENT"]
+            .into_iter()
+            .enumerate()
+        {
+            base.add_key(KeyDefinition::new(Position::new(0, col as u8), keycode))
+                .unwrap();
+        }
+        layout.add_layer(base).unwrap();
+
+        let sim = Simulation::new(0, Position::new(0, 0));
+        let at = |code| sim.position_for(&layout, code).map(|pos| pos.col);
+        assert_eq!(at(KeyCode::Char('Q')), Some(0));
+        assert_eq!(at(KeyCode::Char(' ')), Some(1));
+        assert_eq!(at(KeyCode::Char(':')), Some(2));
+        assert_eq!(at(KeyCode::Enter), Some(3));
+        assert_eq!(at(KeyCode::Char('z')), None);
+        assert_eq!(at(KeyCode::F(1)), None);
+    }
+}
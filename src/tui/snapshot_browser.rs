@@ -0,0 +1,215 @@
+//! Snapshot browser for restoring in-app layout snapshots.
+//!
+//! Lists snapshots taken of the current layout (newest first) together with
+//! a one-line summary of how each differs from the layout being edited. The
+//! differences can also be shown key by key on the keyboard (diff mode).
+
+use ratatui::{
+    layout::{Constraint, Direction, Layout as RatatuiLayout, Rect},
+    style::{Modifier, Style},
+    text::{Line, Span},
+    widgets::{Block, Borders, Clear, List, ListItem, Paragraph},
+    Frame,
+};
+use std::path::{Path, PathBuf};
+
+use crate::models::Layout;
+use crate::services::snapshots::{LayoutDiff, SnapshotInfo, SnapshotService};
+use crate::services::LayoutService;
+
+/// A snapshot together with its diff summary against the current layout.
+#[derive(Debug, Clone)]
+pub struct SnapshotEntry {
+    /// Snapshot file information
+    pub info: SnapshotInfo,
+    /// One-line diff summary (or load error) versus the current layout
+    pub summary: String,
+}
+
+/// Events emitted by the SnapshotBrowser component
+#[derive(Debug, Clone)]
+pub enum SnapshotBrowserEvent {
+    /// User selected a snapshot to restore
+    Restore(PathBuf),
+    /// User wants to see the changes since a snapshot
+    Diff(SnapshotInfo),
+    /// User wants to see the changes since the layout was last saved
+    DiffSaved,
+    /// User cancelled the browser
+    Cancelled,
+}
+
+/// SnapshotBrowser component that implements the Component trait
+#[derive(Debug, Clone)]
+pub struct SnapshotBrowser {
+    /// Snapshots for the current layout, newest first
+    entries: Vec<SnapshotEntry>,
+    /// Currently selected entry index
+    selected: usize,
+    /// Directory the snapshots were read from
+    dir: PathBuf,
+}
+
+impl SnapshotBrowser {
+    /// Create a new SnapshotBrowser listing snapshots of `layout`.
+    #[must_use]
+    pub fn new(layout: &Layout, source_path: Option<&Path>) -> Self {
+        let dir = SnapshotService::snapshots_dir(source_path).unwrap_or_default();
+        let layout_name = SnapshotService::layout_key(layout, source_path);
+        let entries = SnapshotService::list(&dir, &layout_name)
+            .unwrap_or_default()
+            .into_iter()
+            .map(|info| {
+                let summary = match LayoutService::load(&info.path) {
+                    Ok(snapshot) => LayoutDiff::between(&snapshot, layout).summary(),
+                    Err(e) => format!("unreadable: {e}"),
+                };
+                SnapshotEntry { info, summary }
+            })
+            .collect();
+
+        Self {
+            entries,
+            selected: 0,
+            dir,
+        }
+    }
+
+    /// Gets the currently selected snapshot (if any).
+    #[must_use]
+    pub fn selected_entry(&self) -> Option<&SnapshotEntry> {
+        self.entries.get(self.selected)
+    }
+
+    /// Moves selection up.
+    const fn select_previous(&mut self) {
+        self.selected = self.selected.saturating_sub(1);
+    }
+
+    /// Moves selection down.
+    const fn select_next(&mut self) {
+        if self.selected + 1 < self.entries.len() {
+            self.selected += 1;
+        }
+    }
+}
+
+impl crate::tui::component::Component for SnapshotBrowser {
+    type Event = SnapshotBrowserEvent;
+
+    fn handle_input(&mut self, key: crossterm::event::KeyEvent) -> Option<Self::Event> {
+        use crossterm::event::KeyCode;
+
+        match key.code {
+            KeyCode::Esc | KeyCode::Char('q') => Some(SnapshotBrowserEvent::Cancelled),
+            KeyCode::Enter => self
+                .selected_entry()
+                .map(|e| SnapshotBrowserEvent::Restore(e.info.path.clone())),
+            KeyCode::Char('d') => self
+                .selected_entry()
+                .map(|e| SnapshotBrowserEvent::Diff(e.info.clone())),
+            KeyCode::Char('s') => Some(SnapshotBrowserEvent::DiffSaved),
+            KeyCode::Up | KeyCode::Char('k') => {
+                self.select_previous();
+                None
+            }
+            KeyCode::Down | KeyCode::Char('j') => {
+                self.select_next();
+                None
+            }
+            _ => None,
+        }
+    }
+
+    fn render(&self, f: &mut Frame, area: Rect, theme: &crate::tui::theme::Theme) {
+        render_snapshot_browser(f, self, area, theme);
+    }
+}
+
+/// Renders the snapshot browser popup
+fn render_snapshot_browser(
+    f: &mut Frame,
+    browser: &SnapshotBrowser,
+    area: Rect,
+    theme: &crate::tui::theme::Theme,
+) {
+    let popup_area = centered_rect(60, 70, area);
+
+    f.render_widget(Clear, popup_area);
+    let background = Block::default().style(Style::default().bg(theme.background));
+    f.render_widget(background, popup_area);
+
+    let chunks = RatatuiLayout::default()
+        .direction(Direction::Vertical)
+        .constraints([
+            Constraint::Min(5),    // Snapshot list
+            Constraint::Length(3), // Directory
+        ])
+        .split(popup_area);
+
+    let items: Vec<ListItem> = if browser.entries.is_empty() {
+        vec![ListItem::new(Line::from(Span::styled(
+            "No snapshots yet. Press z in the editor to take one",
+            Style::default().fg(theme.warning),
+        )))]
+    } else {
+        browser
+            .entries
+            .iter()
+            .enumerate()
+            .map(|(i, entry)| {
+                let style = if i == browser.selected {
+                    Style::default()
+                        .fg(theme.background)
+                        .bg(theme.primary)
+                        .add_modifier(Modifier::BOLD)
+                } else {
+                    Style::default().fg(theme.text)
+                };
+                let timestamp = entry.info.timestamp.format("%Y-%m-%d %H:%M:%S").to_string();
+                ListItem::new(Line::from(vec![
+                    Span::styled(timestamp, style),
+                    Span::styled(
+                        format!("  {}", entry.summary),
+                        Style::default().fg(theme.text_muted),
+                    ),
+                ]))
+            })
+            .collect()
+    };
+
+    let list_title = format!(" Snapshots ({}) ", browser.entries.len());
+    let list = List::new(items).block(
+        Block::default()
+            .borders(Borders::ALL)
+            .title(list_title)
+            .style(Style::default().fg(theme.primary)),
+    );
+    f.render_widget(list, chunks[0]);
+
+    let dir = Paragraph::new(browser.dir.display().to_string())
+        .style(Style::default().fg(theme.text_muted))
+        .block(Block::default().borders(Borders::ALL).title("Location"));
+    f.render_widget(dir, chunks[1]);
+}
+
+/// Helper to create a centered rectangle
+fn centered_rect(percent_x: u16, percent_y: u16, r: Rect) -> Rect {
+    let popup_layout = RatatuiLayout::default()
+        .direction(Direction::Vertical)
+        .constraints([
+            Constraint::Percentage((100 - percent_y) / 2),
+            Constraint::Percentage(percent_y),
+            Constraint::Percentage((100 - percent_y) / 2),
+        ])
+        .split(r);
+
+    RatatuiLayout::default()
+        .direction(Direction::Horizontal)
+        .constraints([
+            Constraint::Percentage((100 - percent_x) / 2),
+            Constraint::Percentage(percent_x),
+            Constraint::Percentage((100 - percent_x) / 2),
+        ])
+        .split(popup_layout[1])[1]
+}
@@ -0,0 +1,160 @@
+//! Split view for the keyboard widget.
+//!
+//! Draws two layers side by side, or stacked when the terminal is too narrow
+//! for two keyboards, so they can be compared and keys copied between them.
+//! `AppState`'s current layer, selected key and multi-selection always
+//! belong to the focused pane; the other pane's are kept here and swapped in
+//! when the focus moves.
+
+use ratatui::layout::Rect;
+
+use super::SelectionMode;
+use crate::models::Position;
+use crate::services::layer_refs;
+
+/// Where a pane is drawn: left or right, or top and bottom when stacked.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum PaneSide {
+    /// Left pane, on top when stacked
+    Left,
+    /// Right pane, at the bottom when stacked
+    Right,
+}
+
+impl PaneSide {
+    /// The opposite side.
+    #[must_use]
+    pub const fn other(self) -> Self {
+        match self {
+            Self::Left => Self::Right,
+            Self::Right => Self::Left,
+        }
+    }
+
+    /// Index of the pane in [`SplitView::pane_areas`] and the render cache.
+    #[must_use]
+    pub const fn index(self) -> usize {
+        match self {
+            Self::Left => 0,
+            Self::Right => 1,
+        }
+    }
+
+    /// Name shown in the status bar.
+    #[must_use]
+    pub const fn label(self) -> &'static str {
+        match self {
+            Self::Left => "left",
+            Self::Right => "right",
+        }
+    }
+}
+
+/// Layer, selected key and multi-selection of a pane.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct PaneState {
+    /// Layer drawn in the pane
+    pub layer: usize,
+    /// Selected key of the pane
+    pub selected_position: Position,
+    /// Selection mode of the pane, if selecting
+    pub selection_mode: Option<SelectionMode>,
+    /// Keys selected in the pane
+    pub selected_keys: Vec<Position>,
+}
+
+/// Two layers drawn next to each other (`\`, Ctrl+O switches panes).
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct SplitView {
+    /// Side of the focused pane
+    pub focus: PaneSide,
+    /// The pane without focus
+    pub other: PaneState,
+}
+
+impl SplitView {
+    /// Split view focused on the left pane, with `other` on the right.
+    #[must_use]
+    pub const fn new(other: PaneState) -> Self {
+        Self {
+            focus: PaneSide::Left,
+            other,
+        }
+    }
+
+    /// Keeps the other pane on its layer after layer `from` moved to `to`.
+    pub const fn layer_moved(&mut self, from: usize, to: usize) {
+        self.other.layer = layer_refs::moved_layer_index(self.other.layer, from, to);
+    }
+
+    /// Keeps the other pane on its layer after layer `index` was deleted,
+    /// leaving `layer_count` layers. A pane showing the deleted layer moves to
+    /// the layer that took its place, or the one before it if it was last,
+    /// and drops its multi-selection.
+    pub fn layer_deleted(&mut self, index: usize, layer_count: usize) {
+        let other = &mut self.other;
+        if other.layer > index {
+            other.layer -= 1;
+        } else if other.layer == index {
+            other.layer = index.min(layer_count.saturating_sub(1));
+            other.selection_mode = None;
+            other.selected_keys.clear();
+        }
+    }
+
+    /// Areas of the left and right panes within `area`.
+    ///
+    /// The panes sit side by side when both fit a keyboard `keyboard_width`
+    /// cells wide, and are stacked otherwise.
+    #[must_use]
+    pub fn pane_areas(area: Rect, keyboard_width: u16) -> [Rect; 2] {
+        if area.width >= keyboard_width.saturating_mul(2) {
+            let left_width = area.width / 2;
+            [
+                Rect {
+                    width: left_width,
+                    ..area
+                },
+                Rect {
+                    x: area.x + left_width,
+                    width: area.width - left_width,
+                    ..area
+                },
+            ]
+        } else {
+            let top_height = area.height / 2;
+            [
+                Rect {
+                    height: top_height,
+                    ..area
+                },
+                Rect {
+                    y: area.y + top_height,
+                    height: area.height - top_height,
+                    ..area
+                },
+            ]
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_panes_sit_side_by_side_when_both_fit() {
+        let area = Rect::new(0, 3, 120, 30);
+        let [left, right] = SplitView::pane_areas(area, 60);
+        assert_eq!(left, Rect::new(0, 3, 60, 30));
+        assert_eq!(right, Rect::new(60, 3, 60, 30));
+    }
+
+    #[test]
+    fn test_panes_stack_on_narrow_terminals() {
+        let area = Rect::new(0, 3, 100, 31);
+        let [top, bottom] = SplitView::pane_areas(area, 60);
+        assert_eq!(top, Rect::new(0, 3, 100, 15));
+        assert_eq!(bottom, Rect::new(0, 18, 100, 16));
+    }
+}
@@ -29,10 +29,19 @@ impl StatusBar {
                 crate::firmware::BuildStatus::Failed => theme.error,
             };
 
-            Some(Line::from(vec![
+            let mut spans = vec![
                 Span::styled("Build: ", Style::default().fg(theme.primary)),
                 Span::styled(status.to_string(), Style::default().fg(color)),
-            ]))
+            ];
+            if let (crate::firmware::BuildStatus::Success, Some(size)) = (status, &build_state.size)
+            {
+                spans.push(Span::styled(
+                    format!(" ({})", size.short_summary()),
+                    Style::default().fg(theme.text_muted),
+                ));
+            }
+
+            Some(Line::from(spans))
         } else {
             None
         };
@@ -52,8 +61,16 @@ impl StatusBar {
                     Style::default().fg(theme.text_muted),
                 ),
                 Span::styled(preview, Style::default().fg(theme.accent)),
-                if state.clipboard.can_undo() {
-                    Span::styled(" | Ctrl+Z: Undo", Style::default().fg(theme.text_muted))
+                if state.undo_history.can_undo() {
+                    Span::styled(
+                        format!(" | u: Undo ({})", state.undo_history.undo_count()),
+                        Style::default().fg(theme.text_muted),
+                    )
+                } else {
+                    Span::raw("")
+                },
+                if state.undo_history.can_redo() {
+                    Span::styled(" | Ctrl+R: Redo", Style::default().fg(theme.text_muted))
                 } else {
                     Span::raw("")
                 },
@@ -80,6 +97,9 @@ impl StatusBar {
             None
         };
 
+        // An encoder has no description; show both of its directions instead
+        let description_line = description_line.or_else(|| Self::encoder_line(state, theme));
+
         // Determine if we should show hints (no active status/error message)
         let show_hints = state.status_message.is_empty()
             && state.error_message.is_none()
@@ -147,18 +167,49 @@ impl StatusBar {
         // Add help line at the bottom
         status_text.push(help_line);
 
+        // Mouse capture is on by default, so only its absence is shown
+        let mouse_title = if super::mouse_capture_enabled() {
+            Line::default()
+        } else {
+            Line::styled(" Mouse off (Alt+M) ", Style::default().fg(theme.warning)).right_aligned()
+        };
+
         let status = Paragraph::new(status_text)
             .style(Style::default().bg(theme.background))
             .block(
                 Block::default()
                     .borders(Borders::ALL)
                     .title(" Status ")
+                    .title(mouse_title)
                     .style(Style::default().bg(theme.background)),
             );
 
         f.render_widget(status, area);
     }
 
+    /// Keycodes of the selected encoder (only when no popup is active)
+    fn encoder_line(state: &AppState, theme: &Theme) -> Option<Line<'static>> {
+        if state.active_popup.is_some() {
+            return None;
+        }
+        let index = state.selected_encoder()?;
+        let encoder = state
+            .layout
+            .layers
+            .get(state.current_layer)?
+            .encoder(usize::from(index));
+        Some(Line::from(vec![
+            Span::styled(
+                format!("Encoder {index}: "),
+                Style::default().fg(theme.accent),
+            ),
+            Span::styled(
+                format!("↺ {}  ↻ {}", encoder.ccw, encoder.cw),
+                Style::default().fg(theme.text),
+            ),
+        ]))
+    }
+
     /// Get a line of contextual hints from the help registry (top hints line)
     fn get_hints_line(state: &AppState, theme: &Theme) -> Line<'static> {
         let context_name = Self::get_current_context(state);
@@ -221,11 +272,29 @@ impl StatusBar {
             Some(PopupType::ModifierPicker) => help_registry::contexts::MODIFIER_PICKER,
             Some(PopupType::TemplateBrowser) => help_registry::contexts::TEMPLATE_BROWSER,
             Some(PopupType::TemplateSaveDialog) => help_registry::contexts::TEMPLATE_SAVE,
+            Some(PopupType::SnapshotBrowser) => help_registry::contexts::SNAPSHOT_BROWSER,
+            Some(PopupType::KeycodeSearch) => help_registry::contexts::KEYCODE_SEARCH,
+            Some(PopupType::LintReport) => help_registry::contexts::LINT_REPORT,
+            Some(PopupType::ValidationResults) => help_registry::contexts::VALIDATION_RESULTS,
+            Some(PopupType::GenerationResults) => help_registry::contexts::GENERATION_RESULTS,
+            Some(PopupType::ActionLog) => help_registry::contexts::ACTION_LOG,
+            Some(PopupType::History) => help_registry::contexts::HISTORY,
+            Some(PopupType::GridPaste) => help_registry::contexts::GRID_PASTE,
+            Some(PopupType::KeyRoleEditor) => help_registry::contexts::KEY_ROLE_EDITOR,
+            Some(PopupType::EncoderEditor) => help_registry::contexts::ENCODER_EDITOR,
+            Some(PopupType::CommandPalette) => help_registry::contexts::COMMAND_PALETTE,
             Some(PopupType::SetupWizard) => help_registry::contexts::SETUP_WIZARD,
             Some(PopupType::UnsavedChangesPrompt) => help_registry::contexts::UNSAVED_PROMPT,
+            Some(PopupType::GeometryMismatch) => help_registry::contexts::GEOMETRY_MISMATCH,
+            Some(PopupType::KeyboardMismatch) => help_registry::contexts::KEYBOARD_MISMATCH,
+            Some(PopupType::StaleGeneration) => help_registry::contexts::STALE_GENERATION,
             _ => {
                 // Check for selection mode
-                if state.selection_mode.is_some() {
+                if state.simulation.is_some() {
+                    help_registry::contexts::SIMULATION
+                } else if state.peek.is_some() {
+                    help_registry::contexts::PEEK
+                } else if state.selection_mode.is_some() {
                     help_registry::contexts::SELECTION
                 } else {
                     help_registry::contexts::MAIN
@@ -2,8 +2,11 @@
 //!
 //! Provides UI for browsing, selecting, and managing tap dance actions.
 //! This is a view-only component - creation/editing happens through multi-stage picker flow.
+//! The library view lists the tap dances of the shared library file so they can be
+//! imported into the layout.
 
 use crate::models::Layout;
+use crate::services::library::Library;
 use crate::tui::theme::Theme;
 use crate::tui::Component;
 use crossterm::event::{KeyCode, KeyEvent};
@@ -26,10 +29,36 @@ pub enum TapDanceEditorEvent {
     Edit(usize),
     /// User wants to delete a tap dance
     Delete(String),
+    /// User wants to browse the shared library
+    OpenLibrary,
+    /// User wants to import a tap dance from the open library
+    Import(String),
     /// User cancelled the operation
     Cancelled,
 }
 
+/// How a library tap dance relates to the layout's definitions
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum LibraryStatus {
+    /// The layout has no tap dance with this name
+    New,
+    /// The layout has the same definition
+    Identical,
+    /// The layout has a different definition with this name
+    Conflict,
+}
+
+/// Library shown in place of the layout's tap dances
+#[derive(Debug, Clone)]
+struct LibraryView {
+    /// Library being browsed
+    library: Library,
+    /// Status of each library tap dance, in library order
+    statuses: Vec<LibraryStatus>,
+    /// List selection state
+    list_state: ListState,
+}
+
 /// Tap Dance Editor component state
 #[derive(Debug)]
 pub struct TapDanceEditor {
@@ -37,6 +66,8 @@ pub struct TapDanceEditor {
     list_state: ListState,
     /// Tap dances from the layout (read-only view)
     tap_dances: Vec<crate::models::TapDanceAction>,
+    /// Open library, if browsing one
+    library: Option<LibraryView>,
 }
 
 impl TapDanceEditor {
@@ -53,6 +84,37 @@ impl TapDanceEditor {
         Self {
             list_state,
             tap_dances,
+            library: None,
+        }
+    }
+
+    /// Switches to browsing `library`.
+    pub fn show_library(&mut self, library: Library, layout: &Layout) {
+        let mut list_state = ListState::default();
+        if !library.tap_dances.is_empty() {
+            list_state.select(Some(0));
+        }
+        self.library = Some(LibraryView {
+            statuses: library_statuses(&library, layout),
+            library,
+            list_state,
+        });
+    }
+
+    /// The library being browsed, if any.
+    #[must_use]
+    pub fn library(&self) -> Option<&Library> {
+        self.library.as_ref().map(|view| &view.library)
+    }
+
+    /// Reloads the layout's tap dances, keeping the open library and selections.
+    pub fn refresh(&mut self, layout: &Layout) {
+        self.tap_dances = layout.tap_dances.clone();
+        let last = self.tap_dances.len().checked_sub(1);
+        self.list_state
+            .select(last.map(|last| self.list_state.selected().unwrap_or(0).min(last)));
+        if let Some(view) = &mut self.library {
+            view.statuses = library_statuses(&view.library, layout);
         }
     }
 
@@ -74,6 +136,29 @@ impl Component for TapDanceEditor {
     type Event = TapDanceEditorEvent;
 
     fn handle_input(&mut self, key: KeyEvent) -> Option<Self::Event> {
+        if let Some(view) = &mut self.library {
+            let last = view.library.tap_dances.len().saturating_sub(1);
+            let selected = view.list_state.selected();
+            match key.code {
+                KeyCode::Up | KeyCode::Char('k') => {
+                    view.list_state
+                        .select(selected.map(|index| index.saturating_sub(1)));
+                }
+                KeyCode::Down | KeyCode::Char('j') => {
+                    view.list_state
+                        .select(selected.map(|index| (index + 1).min(last)));
+                }
+                KeyCode::Enter => {
+                    if let Some(tap_dance) = selected.and_then(|i| view.library.tap_dances.get(i)) {
+                        return Some(TapDanceEditorEvent::Import(tap_dance.name.clone()));
+                    }
+                }
+                KeyCode::Esc => self.library = None,
+                _ => {}
+            }
+            return None;
+        }
+
         match key.code {
             KeyCode::Up | KeyCode::Char('k') => {
                 if let Some(selected) = self.list_state.selected() {
@@ -107,6 +192,9 @@ impl Component for TapDanceEditor {
                     return Some(TapDanceEditorEvent::Delete(name));
                 }
             }
+            KeyCode::Char('i') => {
+                return Some(TapDanceEditorEvent::OpenLibrary);
+            }
             KeyCode::Esc => {
                 return Some(TapDanceEditorEvent::Cancelled);
             }
@@ -130,6 +218,11 @@ impl TapDanceEditor {
             .constraints([Constraint::Min(3), Constraint::Length(3)])
             .split(area);
 
+        if let Some(view) = &mut self.library {
+            render_library(view, frame, &chunks, theme);
+            return;
+        }
+
         // List of tap dances
         let items: Vec<ListItem> = if self.tap_dances.is_empty() {
             vec![ListItem::new(
@@ -139,16 +232,7 @@ impl TapDanceEditor {
         } else {
             self.tap_dances
                 .iter()
-                .map(|td| {
-                    let mut parts = vec![format!("{}: {}", td.name, td.single_tap)];
-                    if let Some(ref double) = td.double_tap {
-                        parts.push(format!(" → {double}"));
-                    }
-                    if let Some(ref hold) = td.hold {
-                        parts.push(format!(" (hold: {hold})"));
-                    }
-                    ListItem::new(parts.join(""))
-                })
+                .map(|td| ListItem::new(describe(td)))
                 .collect()
         };
 
@@ -160,10 +244,12 @@ impl TapDanceEditor {
                     .style(Style::default().bg(theme.background)),
             )
             .highlight_style(
-                Style::default()
-                    .bg(theme.highlight_bg)
-                    .fg(theme.text)
-                    .add_modifier(Modifier::BOLD),
+                theme.selection(
+                    Style::default()
+                        .bg(theme.highlight_bg)
+                        .fg(theme.text)
+                        .add_modifier(Modifier::BOLD),
+                ),
             )
             .style(Style::default().bg(theme.background).fg(theme.text));
 
@@ -206,6 +292,13 @@ impl TapDanceEditor {
                     .add_modifier(Modifier::BOLD),
             ),
             Span::raw(" Delete  "),
+            Span::styled(
+                "i",
+                Style::default()
+                    .fg(theme.accent)
+                    .add_modifier(Modifier::BOLD),
+            ),
+            Span::raw(" Library…  "),
             Span::styled(
                 "Esc",
                 Style::default()
@@ -226,12 +319,152 @@ impl TapDanceEditor {
     }
 }
 
+/// One-line summary of a tap dance.
+fn describe(td: &crate::models::TapDanceAction) -> String {
+    let mut parts = vec![format!("{}: {}", td.name, td.single_tap)];
+    if let Some(ref double) = td.double_tap {
+        parts.push(format!(" → {double}"));
+    }
+    if let Some(ref hold) = td.hold {
+        parts.push(format!(" (hold: {hold})"));
+    }
+    parts.join("")
+}
+
+/// Status of each library tap dance against the layout.
+fn library_statuses(library: &Library, layout: &Layout) -> Vec<LibraryStatus> {
+    library
+        .tap_dances
+        .iter()
+        .map(|td| match layout.get_tap_dance(&td.name) {
+            None => LibraryStatus::New,
+            Some(existing) if existing == td => LibraryStatus::Identical,
+            Some(_) => LibraryStatus::Conflict,
+        })
+        .collect()
+}
+
+/// Renders the library view: the list and its help line.
+fn render_library(view: &mut LibraryView, frame: &mut Frame, chunks: &[Rect], theme: &Theme) {
+    let items: Vec<ListItem> = if view.library.tap_dances.is_empty() {
+        vec![ListItem::new(
+            Line::from("The library has no tap dances.")
+                .style(Style::default().fg(theme.text_muted)),
+        )]
+    } else {
+        view.library
+            .tap_dances
+            .iter()
+            .zip(&view.statuses)
+            .map(|(td, status)| {
+                let (marker, note, color) = match status {
+                    LibraryStatus::New => ("+ ", "", theme.text),
+                    LibraryStatus::Identical => ("= ", "  (in layout)", theme.text_muted),
+                    LibraryStatus::Conflict => ("! ", "  (differs from layout)", theme.warning),
+                };
+                ListItem::new(Line::from(vec![
+                    Span::styled(marker, Style::default().fg(color)),
+                    Span::raw(describe(td)),
+                    Span::styled(note, Style::default().fg(color)),
+                ]))
+            })
+            .collect()
+    };
+
+    let list = List::new(items)
+        .block(
+            Block::default()
+                .borders(Borders::ALL)
+                .title(" Import from Library ")
+                .style(Style::default().bg(theme.background)),
+        )
+        .highlight_style(
+            theme.selection(
+                Style::default()
+                    .bg(theme.highlight_bg)
+                    .fg(theme.text)
+                    .add_modifier(Modifier::BOLD),
+            ),
+        )
+        .style(Style::default().bg(theme.background).fg(theme.text));
+    frame.render_stateful_widget(list, chunks[0], &mut view.list_state);
+
+    let key_style = Style::default()
+        .fg(theme.accent)
+        .add_modifier(Modifier::BOLD);
+    let help = Paragraph::new(Line::from(vec![
+        Span::styled("↑/↓", key_style),
+        Span::raw(" Navigate  "),
+        Span::styled("Enter", key_style),
+        Span::raw(" Import  "),
+        Span::styled("Esc", key_style),
+        Span::raw(" Back"),
+    ]))
+    .block(
+        Block::default()
+            .borders(Borders::ALL)
+            .title(" Help ")
+            .style(Style::default().bg(theme.background)),
+    )
+    .style(Style::default().bg(theme.background).fg(theme.text));
+    frame.render_widget(help, chunks[1]);
+}
+
 // Implement Clone for TapDanceEditor (needed for render pattern)
 impl Clone for TapDanceEditor {
     fn clone(&self) -> Self {
         Self {
             list_state: ListState::default().with_selected(self.list_state.selected()),
             tap_dances: self.tap_dances.clone(),
+            library: self.library.clone(),
         }
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::models::TapDanceAction;
+    use crossterm::event::KeyModifiers;
+
+    fn press(editor: &mut TapDanceEditor, code: KeyCode) -> Option<TapDanceEditorEvent> {
+        editor.handle_input(KeyEvent::new(code, KeyModifiers::NONE))
+    }
+
+    #[test]
+    fn test_library_view_imports_selected_and_closes() {
+        let mut layout = Layout::new("Editor").unwrap();
+        layout.tap_dances = vec![TapDanceAction::new("nav_dance", "KC_TAB")];
+        let library = Library {
+            tap_dances: vec![
+                TapDanceAction::new("nav_dance", "KC_ESC"),
+                TapDanceAction::new("quote_dance", "KC_QUOT").with_double_tap("KC_DQUO"),
+            ],
+            ..Library::default()
+        };
+
+        let mut editor = TapDanceEditor::new(&layout);
+        assert_eq!(
+            press(&mut editor, KeyCode::Char('i')),
+            Some(TapDanceEditorEvent::OpenLibrary)
+        );
+        editor.show_library(library, &layout);
+        assert_eq!(
+            editor.library.as_ref().unwrap().statuses,
+            vec![LibraryStatus::Conflict, LibraryStatus::New]
+        );
+
+        press(&mut editor, KeyCode::Down);
+        assert_eq!(
+            press(&mut editor, KeyCode::Enter),
+            Some(TapDanceEditorEvent::Import("quote_dance".to_string()))
+        );
+
+        assert_eq!(press(&mut editor, KeyCode::Esc), None);
+        assert!(editor.library().is_none());
+        assert_eq!(
+            press(&mut editor, KeyCode::Esc),
+            Some(TapDanceEditorEvent::Cancelled)
+        );
+    }
+}
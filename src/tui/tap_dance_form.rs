@@ -1,9 +1,12 @@
 //! Tap Dance Form Dialog (Option B UX)
 //!
 //! Single form dialog for creating/editing tap dance actions with inline pickers.
-//! Shows all fields (Name, Single Tap, Double Tap, Hold) in one form with Pick buttons.
+//! Shows all fields (Name, Single Tap, Double Tap, Hold) in one form with Pick buttons,
+//! followed by the per-dance options (Tapping Term, On Each Tap).
 
+use crate::models::layout::{TAP_DANCE_TERM_MAX_MS, TAP_DANCE_TERM_MIN_MS};
 use crate::models::TapDanceAction;
+use crate::tui::text_input::TextInput;
 use crate::tui::theme::Theme;
 use crate::tui::Component;
 use crossterm::event::{KeyCode, KeyEvent};
@@ -41,6 +44,10 @@ pub enum FormRow {
     Double,
     /// Hold field (picker)
     Hold,
+    /// Tapping term field (numeric input)
+    Term,
+    /// On-each-tap toggle
+    EachTap,
 }
 
 impl FormRow {
@@ -50,17 +57,21 @@ impl FormRow {
             Self::Name => Self::Single,
             Self::Single => Self::Double,
             Self::Double => Self::Hold,
-            Self::Hold => Self::Name,
+            Self::Hold => Self::Term,
+            Self::Term => Self::EachTap,
+            Self::EachTap => Self::Name,
         }
     }
 
     /// Get previous row (wraps around)
     const fn previous(&self) -> Self {
         match self {
-            Self::Name => Self::Hold,
+            Self::Name => Self::EachTap,
             Self::Single => Self::Name,
             Self::Double => Self::Single,
             Self::Hold => Self::Double,
+            Self::Term => Self::Hold,
+            Self::EachTap => Self::Term,
         }
     }
 }
@@ -75,13 +86,17 @@ pub struct TapDanceForm {
     /// Currently selected row
     selected_row: FormRow,
     /// Name input buffer (for editing)
-    name_input: String,
+    name_input: TextInput,
     /// Error message (if validation fails)
     error: Option<String>,
     /// Existing tap dance names (for duplicate checking)
     existing_names: Vec<String>,
     /// Whether name field is in edit mode
     name_editing: bool,
+    /// Tapping term input buffer (empty = global term)
+    term_input: TextInput,
+    /// Whether tapping term field is in edit mode
+    term_editing: bool,
 }
 
 impl TapDanceForm {
@@ -91,16 +106,24 @@ impl TapDanceForm {
             draft: TapDanceAction::new(String::new(), String::new()),
             editing_index: None,
             selected_row: FormRow::Name,
-            name_input: String::new(),
+            name_input: TextInput::default(),
             error: None,
             existing_names,
             name_editing: false,
+            term_input: TextInput::default(),
+            term_editing: false,
         }
     }
 
     /// Creates a new form for editing an existing tap dance
     pub fn new_edit(tap_dance: TapDanceAction, index: usize, existing_names: Vec<String>) -> Self {
-        let name_input = tap_dance.name.clone();
+        let name_input = TextInput::new(tap_dance.name.clone());
+        let term_input = TextInput::new(
+            tap_dance
+                .tapping_term_ms
+                .map(|term| term.to_string())
+                .unwrap_or_default(),
+        );
         Self {
             draft: tap_dance,
             editing_index: Some(index),
@@ -109,9 +132,16 @@ impl TapDanceForm {
             error: None,
             existing_names,
             name_editing: false,
+            term_input,
+            term_editing: false,
         }
     }
 
+    /// Whether the double tap is still needed (on-each-tap dances have none)
+    const fn needs_double(&self) -> bool {
+        self.draft.double_tap.is_none() && !self.draft.on_each_tap
+    }
+
     /// Get the number of required fields completed (out of 3: name, single, double)
     fn count_required_complete(&self) -> usize {
         let mut count = 0;
@@ -121,7 +151,7 @@ impl TapDanceForm {
         if !self.draft.single_tap.is_empty() {
             count += 1;
         }
-        if self.draft.double_tap.is_some() {
+        if !self.needs_double() {
             count += 1;
         }
         count
@@ -129,9 +159,7 @@ impl TapDanceForm {
 
     /// Check if the form can be saved (all required fields filled)
     fn can_save(&self) -> bool {
-        !self.draft.name.is_empty()
-            && !self.draft.single_tap.is_empty()
-            && self.draft.double_tap.is_some()
+        !self.draft.name.is_empty() && !self.draft.single_tap.is_empty() && !self.needs_double()
     }
 
     /// Validate name field
@@ -163,7 +191,7 @@ impl TapDanceForm {
 
     /// Apply name from input buffer to draft
     fn apply_name(&mut self) {
-        let trimmed = self.name_input.trim().to_string();
+        let trimmed = self.name_input.value().trim().to_string();
         match self.validate_name(&trimmed) {
             Ok(()) => {
                 self.draft.name = trimmed;
@@ -175,6 +203,36 @@ impl TapDanceForm {
         }
     }
 
+    /// Apply tapping term from input buffer to draft
+    fn apply_term(&mut self) {
+        let trimmed = self.term_input.value().trim();
+        if trimmed.is_empty() {
+            self.draft.tapping_term_ms = None;
+            self.error = None;
+            return;
+        }
+        match trimmed.parse::<u16>() {
+            Ok(term) if (TAP_DANCE_TERM_MIN_MS..=TAP_DANCE_TERM_MAX_MS).contains(&term) => {
+                self.draft.tapping_term_ms = Some(term);
+                self.error = None;
+            }
+            _ => {
+                self.error = Some(format!(
+                    "Tapping term must be between {TAP_DANCE_TERM_MIN_MS} and {TAP_DANCE_TERM_MAX_MS}ms"
+                ));
+            }
+        }
+    }
+
+    /// Toggle on-each-tap; turning it on drops the double tap, which it replaces
+    fn toggle_on_each_tap(&mut self) {
+        self.draft.on_each_tap = !self.draft.on_each_tap;
+        if self.draft.on_each_tap {
+            self.draft.double_tap = None;
+        }
+        self.error = None;
+    }
+
     /// Update single tap keycode
     pub fn set_single_tap(&mut self, keycode: String) {
         self.draft.single_tap = keycode;
@@ -184,6 +242,7 @@ impl TapDanceForm {
     /// Update double tap keycode
     pub fn set_double_tap(&mut self, keycode: String) {
         self.draft.double_tap = Some(keycode);
+        self.draft.on_each_tap = false;
         self.error = None;
     }
 
@@ -207,16 +266,6 @@ impl Component for TapDanceForm {
         // If name field is in edit mode, handle text input
         if self.name_editing && self.selected_row == FormRow::Name {
             match key.code {
-                KeyCode::Char(c) if key.modifiers.is_empty() => {
-                    self.name_input.push(c);
-                    self.error = None;
-                    return None;
-                }
-                KeyCode::Backspace => {
-                    self.name_input.pop();
-                    self.error = None;
-                    return None;
-                }
                 KeyCode::Enter => {
                     // Apply name and exit edit mode
                     self.apply_name();
@@ -225,13 +274,46 @@ impl Component for TapDanceForm {
                 }
                 KeyCode::Esc => {
                     // Cancel edit, restore original name
-                    self.name_input = self.draft.name.clone();
+                    self.name_input.set_value(self.draft.name.clone());
                     self.name_editing = false;
                     self.error = None;
                     return None;
                 }
-                _ => return None,
+                _ => {
+                    if self.name_input.handle_key(key) {
+                        self.error = None;
+                    }
+                    return None;
+                }
+            }
+        }
+
+        // If tapping term field is in edit mode, accept digits only
+        if self.term_editing && self.selected_row == FormRow::Term {
+            match key.code {
+                KeyCode::Char(c) if !c.is_ascii_digit() || self.term_input.value().len() >= 5 => {}
+                KeyCode::Enter => {
+                    self.apply_term();
+                    self.term_editing = false;
+                }
+                KeyCode::Esc => {
+                    // Cancel edit, restore original term
+                    self.term_input.set_value(
+                        self.draft
+                            .tapping_term_ms
+                            .map(|term| term.to_string())
+                            .unwrap_or_default(),
+                    );
+                    self.term_editing = false;
+                    self.error = None;
+                }
+                _ => {
+                    if self.term_input.handle_key(key) {
+                        self.error = None;
+                    }
+                }
             }
+            return None;
         }
 
         // Normal navigation and action handling
@@ -268,8 +350,17 @@ impl Component for TapDanceForm {
                     FormRow::Hold => {
                         return Some(TapDanceFormEvent::PickHold);
                     }
+                    FormRow::Term => {
+                        // Enter edit mode for tapping term field
+                        self.term_editing = true;
+                        self.error = None;
+                    }
+                    FormRow::EachTap => self.toggle_on_each_tap(),
                 }
             }
+            KeyCode::Char(' ') if self.selected_row == FormRow::EachTap => {
+                self.toggle_on_each_tap();
+            }
             KeyCode::Backspace => {
                 if self.selected_row == FormRow::Name && !self.name_editing {
                     // Delete name
@@ -279,6 +370,11 @@ impl Component for TapDanceForm {
                 } else if self.selected_row == FormRow::Hold {
                     // Clear hold (optional field)
                     self.clear_hold();
+                } else if self.selected_row == FormRow::Term {
+                    // Clear term (falls back to the global tapping term)
+                    self.term_input.clear();
+                    self.draft.tapping_term_ms = None;
+                    self.error = None;
                 }
             }
             KeyCode::Char('s')
@@ -311,7 +407,7 @@ impl Component for TapDanceForm {
 
     fn render(&self, frame: &mut Frame, area: Rect, theme: &Theme) {
         // Center the dialog (larger than name entry, more fields)
-        let dialog_area = centered_rect(70, 60, area);
+        let dialog_area = centered_rect(70, 80, area);
 
         // Draw translucent-ish backdrop behind the modal
         frame.render_widget(Clear, dialog_area);
@@ -327,6 +423,8 @@ impl Component for TapDanceForm {
                 Constraint::Length(3), // Single tap field
                 Constraint::Length(3), // Double tap field
                 Constraint::Length(3), // Hold field
+                Constraint::Length(3), // Tapping term field
+                Constraint::Length(3), // On each tap toggle
                 Constraint::Length(3), // Status line (required X/3)
                 Constraint::Min(2),    // Error message (if any)
                 Constraint::Length(3), // Action buttons
@@ -359,11 +457,13 @@ impl Component for TapDanceForm {
             Style::default().fg(theme.text)
         };
         let name_text = if self.name_editing && self.selected_row == FormRow::Name {
-            format!("Name: {}█", self.name_input)
+            let mut spans = vec![Span::raw("Name: ")];
+            spans.extend(self.name_input.spans(name_style, true));
+            Line::from(spans)
         } else if !self.draft.name.is_empty() {
-            format!("Name: {}", self.draft.name)
+            Line::from(format!("Name: {}", self.draft.name))
         } else {
-            "Name: (required)".to_string()
+            Line::from("Name: (required)")
         };
         let name_label = if self.selected_row == FormRow::Name {
             " Name [REQUIRED] ▶ "
@@ -450,6 +550,59 @@ impl Component for TapDanceForm {
         );
         frame.render_widget(hold, chunks[4]);
 
+        // Tapping term field
+        let term_style = if self.selected_row == FormRow::Term {
+            Style::default().fg(theme.accent)
+        } else {
+            Style::default().fg(theme.text)
+        };
+        let term_text = if self.term_editing && self.selected_row == FormRow::Term {
+            let mut spans = vec![Span::raw("Term: ")];
+            spans.extend(self.term_input.spans(term_style, true));
+            spans.push(Span::raw(" ms"));
+            Line::from(spans)
+        } else if let Some(term) = self.draft.tapping_term_ms {
+            Line::from(format!("Term: {term}ms [Edit/Clear]"))
+        } else {
+            Line::from("Term: global tapping term [Edit]")
+        };
+        let term_label = if self.selected_row == FormRow::Term {
+            " Tapping Term [OPTIONAL] ▶ "
+        } else {
+            " Tapping Term [OPTIONAL] "
+        };
+        let term = Paragraph::new(term_text).style(term_style).block(
+            Block::default()
+                .borders(Borders::ALL)
+                .title(term_label)
+                .style(Style::default().bg(theme.background)),
+        );
+        frame.render_widget(term, chunks[5]);
+
+        // On each tap toggle
+        let each_style = if self.selected_row == FormRow::EachTap {
+            Style::default().fg(theme.accent)
+        } else {
+            Style::default().fg(theme.text)
+        };
+        let each_text = if self.draft.on_each_tap {
+            "[x] Send single tap immediately (replaces double tap)"
+        } else {
+            "[ ] Send single tap immediately (replaces double tap)"
+        };
+        let each_label = if self.selected_row == FormRow::EachTap {
+            " On Each Tap [OPTIONAL] ▶ "
+        } else {
+            " On Each Tap [OPTIONAL] "
+        };
+        let each = Paragraph::new(each_text).style(each_style).block(
+            Block::default()
+                .borders(Borders::ALL)
+                .title(each_label)
+                .style(Style::default().bg(theme.background)),
+        );
+        frame.render_widget(each, chunks[6]);
+
         // Status line
         let required_count = self.count_required_complete();
         let status_text = format!("Required fields: {required_count}/3 complete");
@@ -463,7 +616,7 @@ impl Component for TapDanceForm {
                 .borders(Borders::ALL)
                 .style(Style::default().bg(theme.background)),
         );
-        frame.render_widget(status, chunks[5]);
+        frame.render_widget(status, chunks[7]);
 
         // Error message (if any)
         if let Some(ref error) = self.error {
@@ -476,7 +629,7 @@ impl Component for TapDanceForm {
                         .style(Style::default().bg(theme.background)),
                 )
                 .wrap(Wrap { trim: true });
-            frame.render_widget(error_widget, chunks[6]);
+            frame.render_widget(error_widget, chunks[8]);
         }
 
         // Action buttons
@@ -521,7 +674,7 @@ impl Component for TapDanceForm {
                 .borders(Borders::ALL)
                 .style(Style::default().bg(theme.background)),
         );
-        frame.render_widget(help, chunks[7]);
+        frame.render_widget(help, chunks[9]);
     }
 }
 
@@ -3,6 +3,7 @@
 //! Simple text input dialog for entering a tap dance name.
 //! Validates that the name is a valid C identifier.
 
+use crate::tui::text_input::TextInput;
 use crate::tui::theme::Theme;
 use crate::tui::Component;
 use crossterm::event::{KeyCode, KeyEvent};
@@ -27,7 +28,7 @@ pub enum TapDanceNameEntryEvent {
 #[derive(Debug, Clone)]
 pub struct TapDanceNameEntry {
     /// Current input buffer
-    input: String,
+    input: TextInput,
     /// Error message (if validation fails)
     error: Option<String>,
     /// Existing tap dance names (for duplicate checking)
@@ -38,7 +39,7 @@ impl TapDanceNameEntry {
     /// Creates a new tap dance name entry dialog
     pub fn new(existing_names: Vec<String>) -> Self {
         Self {
-            input: String::new(),
+            input: TextInput::default(),
             error: None,
             existing_names,
         }
@@ -46,19 +47,20 @@ impl TapDanceNameEntry {
 
     /// Validate the input name
     fn validate(&self) -> Result<(), String> {
+        let input = self.input.value();
         // Check if empty
-        if self.input.trim().is_empty() {
+        if input.trim().is_empty() {
             return Err("Name cannot be empty".to_string());
         }
 
         // Check if valid C identifier (alphanumeric + underscore)
-        if !self.input.chars().all(|c| c.is_ascii_alphanumeric() || c == '_') {
+        if !input.chars().all(|c| c.is_ascii_alphanumeric() || c == '_') {
             return Err("Name must contain only alphanumeric characters and underscores".to_string());
         }
 
         // Check for duplicates
-        if self.existing_names.iter().any(|n| n == &self.input) {
-            return Err(format!("Tap dance '{}' already exists", self.input));
+        if self.existing_names.iter().any(|n| n == input) {
+            return Err(format!("Tap dance '{input}' already exists"));
         }
 
         Ok(())
@@ -70,19 +72,13 @@ impl Component for TapDanceNameEntry {
 
     fn handle_input(&mut self, key: KeyEvent) -> Option<Self::Event> {
         match key.code {
-            KeyCode::Char(c) if key.modifiers.is_empty() => {
-                self.input.push(c);
-                self.error = None; // Clear error on new input
-            }
-            KeyCode::Backspace => {
-                self.input.pop();
-                self.error = None; // Clear error on edit
-            }
             KeyCode::Enter => {
                 // Validate and confirm
                 match self.validate() {
                     Ok(()) => {
-                        return Some(TapDanceNameEntryEvent::Confirmed(self.input.clone()));
+                        return Some(TapDanceNameEntryEvent::Confirmed(
+                            self.input.value().to_string(),
+                        ));
                     }
                     Err(e) => {
                         self.error = Some(e);
@@ -92,7 +88,11 @@ impl Component for TapDanceNameEntry {
             KeyCode::Esc => {
                 return Some(TapDanceNameEntryEvent::Cancelled);
             }
-            _ => {}
+            _ => {
+                if self.input.handle_key(key) {
+                    self.error = None; // Clear error on edit
+                }
+            }
         }
         None
     }
@@ -131,16 +131,16 @@ impl Component for TapDanceNameEntry {
         frame.render_widget(title, chunks[0]);
 
         // Input field with cursor
-        let input_text = format!("{}█", self.input);
-        let input = Paragraph::new(input_text)
-            .style(Style::default().fg(theme.text))
-            .block(
-                Block::default()
-                    .borders(Borders::ALL)
-                    .title(" Name ")
-                    .style(Style::default().bg(theme.background)),
-            );
-        frame.render_widget(input, chunks[1]);
+        self.input.render(
+            frame,
+            chunks[1],
+            Block::default()
+                .borders(Borders::ALL)
+                .title(" Name ")
+                .style(Style::default().bg(theme.background)),
+            Style::default().fg(theme.text),
+            true,
+        );
 
         // Validation hint
         let hint = Paragraph::new("Alphanumeric and underscores only")
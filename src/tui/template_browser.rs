@@ -19,9 +19,9 @@ use ratatui::{
 use std::fs;
 use std::path::PathBuf;
 
-use crate::config::Config;
 use crate::models::LayoutMetadata;
 use crate::parser::layout as layout_parser;
+use crate::tui::text_input::TextInput;
 
 /// Template metadata with file path for loading.
 #[derive(Debug, Clone)]
@@ -50,7 +50,7 @@ pub struct TemplateBrowserState {
     /// List of available templates
     pub templates: Vec<TemplateInfo>,
     /// Search filter text
-    pub search: String,
+    pub search: TextInput,
     /// Currently selected template index (in filtered list)
     pub selected: usize,
     /// Whether search is active
@@ -60,10 +60,10 @@ pub struct TemplateBrowserState {
 impl TemplateBrowserState {
     /// Creates a new template browser state.
     #[must_use]
-    pub const fn new() -> Self {
+    pub fn new() -> Self {
         Self {
             templates: Vec::new(),
-            search: String::new(),
+            search: TextInput::default(),
             selected: 0,
             search_active: false,
         }
@@ -133,12 +133,10 @@ impl TemplateBrowserState {
         Ok(())
     }
 
-    /// Gets the platform-specific templates directory path.
-    ///
-    /// - Unix/Linux/macOS: `~/.config/LazyQMK/templates/`
-    /// - Windows: `%APPDATA%\LazyQMK\templates\`
+    /// Gets the templates directory path, `templates/` in the data directory
+    /// (see [`crate::paths`]).
     pub fn templates_dir() -> Result<PathBuf> {
-        Ok(Config::config_dir()?.join("templates"))
+        crate::paths::templates_dir()
     }
 
     /// Filters templates by search text.
@@ -152,7 +150,7 @@ impl TemplateBrowserState {
             return self.templates.iter().collect();
         }
 
-        let search_lower = self.search.to_lowercase();
+        let search_lower = self.search.value().to_lowercase();
 
         self.templates
             .iter()
@@ -189,18 +187,12 @@ impl TemplateBrowserState {
         }
     }
 
-    /// Adds a character to the search text.
-    pub fn search_push(&mut self, ch: char) {
-        self.search.push(ch);
-        // Reset selection when search changes
-        self.selected = 0;
-    }
-
-    /// Removes last character from search text.
-    pub fn search_pop(&mut self) {
-        self.search.pop();
-        // Reset selection when search changes
-        self.selected = 0;
+    /// Edits the search text with a key press.
+    pub fn search_key(&mut self, key: crossterm::event::KeyEvent) {
+        if self.search.handle_key(key) {
+            // Reset selection when search changes
+            self.selected = 0;
+        }
     }
 
     /// Clears the search text.
@@ -268,15 +260,10 @@ impl crate::tui::component::Component for TemplateBrowser {
                         .get_selected_template()
                         .map(|t| TemplateBrowserEvent::TemplateSelected(t.path.clone()))
                 }
-                KeyCode::Backspace => {
-                    self.state.search_pop();
-                    None
-                }
-                KeyCode::Char(c) => {
-                    self.state.search_push(c);
+                _ => {
+                    self.state.search_key(key);
                     None
                 }
-                _ => None,
             }
         } else {
             // Navigation mode
@@ -364,19 +351,19 @@ fn render_template_browser_component(
     f.render_widget(title, chunks[0]);
 
     // Render search bar
-    let search_text = if state.search_active {
-        format!("Search: {}█", state.search)
-    } else {
-        format!("Search: {} (Press / to search)", state.search)
-    };
-
     let search_style = if state.search_active {
         Style::default().fg(theme.accent)
     } else {
         Style::default().fg(theme.text_muted)
     };
 
-    let search = Paragraph::new(search_text)
+    let mut search_spans = vec![Span::raw("Search: ")];
+    search_spans.extend(state.search.spans(search_style, state.search_active));
+    if !state.search_active {
+        search_spans.push(Span::raw(" (Press / to search)"));
+    }
+
+    let search = Paragraph::new(Line::from(search_spans))
         .block(Block::default().borders(Borders::ALL).style(search_style));
     f.render_widget(search, chunks[1]);
 
@@ -466,6 +453,7 @@ fn render_template_browser_component(
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crossterm::event::{KeyCode, KeyEvent, KeyModifiers};
 
     #[test]
     fn test_template_browser_state_new() {
@@ -480,14 +468,13 @@ mod tests {
     fn test_template_browser_search() {
         let mut state = TemplateBrowserState::new();
 
-        state.search_push('t');
-        state.search_push('e');
-        state.search_push('s');
-        state.search_push('t');
-        assert_eq!(state.search, "test");
+        for c in "test".chars() {
+            state.search_key(KeyEvent::new(KeyCode::Char(c), KeyModifiers::NONE));
+        }
+        assert_eq!(state.search.value(), "test");
 
-        state.search_pop();
-        assert_eq!(state.search, "tes");
+        state.search_key(KeyEvent::new(KeyCode::Backspace, KeyModifiers::NONE));
+        assert_eq!(state.search.value(), "tes");
 
         state.search_clear();
         assert!(state.search.is_empty());
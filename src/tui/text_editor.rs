@@ -0,0 +1,322 @@
+//! Multi-line text popup for free-form prose such as layer and layout
+//! descriptions.
+//!
+//! Enter inserts a newline, Ctrl+S saves, and Esc cancels.
+
+use crossterm::event::{KeyCode, KeyEvent, KeyModifiers};
+use ratatui::{
+    layout::{Constraint, Direction, Layout, Rect},
+    style::{Modifier, Style},
+    text::{Line, Span},
+    widgets::{Block, Borders, Clear, Paragraph},
+    Frame,
+};
+
+use crate::tui::component::Component;
+use crate::tui::Theme;
+
+/// Events emitted by the text editor popup
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum TextEditorEvent {
+    /// User saved the text (trimmed; empty if everything was deleted)
+    Saved(String),
+    /// User cancelled without saving
+    Cancelled,
+}
+
+/// Multi-line text editor popup
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct TextEditor {
+    /// Popup title
+    title: String,
+    /// Text being edited, one entry per line
+    lines: Vec<String>,
+    /// Cursor line
+    row: usize,
+    /// Cursor column in characters
+    col: usize,
+}
+
+impl TextEditor {
+    /// Create an editor for `text` with the cursor at the end.
+    #[must_use]
+    pub fn new(title: impl Into<String>, text: &str) -> Self {
+        let lines: Vec<String> = if text.is_empty() {
+            vec![String::new()]
+        } else {
+            text.lines().map(str::to_string).collect()
+        };
+        let row = lines.len() - 1;
+        let col = lines[row].chars().count();
+        Self {
+            title: title.into(),
+            lines,
+            row,
+            col,
+        }
+    }
+
+    /// The edited text with surrounding whitespace trimmed
+    #[must_use]
+    pub fn text(&self) -> String {
+        self.lines.join("\n").trim().to_string()
+    }
+
+    /// Insert `text` at the cursor, e.g. from a bracketed paste
+    pub fn insert_text(&mut self, text: &str) {
+        for c in text.chars() {
+            match c {
+                '\n' => self.insert_newline(),
+                '\r' => {}
+                c => self.insert_char(c),
+            }
+        }
+    }
+
+    /// Byte offset of the cursor within the current line
+    fn byte_offset(&self) -> usize {
+        let line = &self.lines[self.row];
+        line.char_indices()
+            .nth(self.col)
+            .map_or(line.len(), |(i, _)| i)
+    }
+
+    /// Number of characters on the current line
+    fn line_len(&self) -> usize {
+        self.lines[self.row].chars().count()
+    }
+
+    fn insert_char(&mut self, c: char) {
+        let offset = self.byte_offset();
+        self.lines[self.row].insert(offset, c);
+        self.col += 1;
+    }
+
+    fn insert_newline(&mut self) {
+        let offset = self.byte_offset();
+        let rest = self.lines[self.row].split_off(offset);
+        self.row += 1;
+        self.lines.insert(self.row, rest);
+        self.col = 0;
+    }
+
+    fn backspace(&mut self) {
+        if self.col > 0 {
+            self.col -= 1;
+            let offset = self.byte_offset();
+            self.lines[self.row].remove(offset);
+        } else if self.row > 0 {
+            // Join with the previous line
+            let line = self.lines.remove(self.row);
+            self.row -= 1;
+            self.col = self.line_len();
+            self.lines[self.row].push_str(&line);
+        }
+    }
+
+    fn delete(&mut self) {
+        if self.col < self.line_len() {
+            let offset = self.byte_offset();
+            self.lines[self.row].remove(offset);
+        } else if self.row + 1 < self.lines.len() {
+            // Join with the next line
+            let next = self.lines.remove(self.row + 1);
+            self.lines[self.row].push_str(&next);
+        }
+    }
+
+    fn move_left(&mut self) {
+        if self.col > 0 {
+            self.col -= 1;
+        } else if self.row > 0 {
+            self.row -= 1;
+            self.col = self.line_len();
+        }
+    }
+
+    fn move_right(&mut self) {
+        if self.col < self.line_len() {
+            self.col += 1;
+        } else if self.row + 1 < self.lines.len() {
+            self.row += 1;
+            self.col = 0;
+        }
+    }
+
+    fn move_up(&mut self) {
+        if self.row > 0 {
+            self.row -= 1;
+            self.col = self.col.min(self.line_len());
+        }
+    }
+
+    fn move_down(&mut self) {
+        if self.row + 1 < self.lines.len() {
+            self.row += 1;
+            self.col = self.col.min(self.line_len());
+        }
+    }
+}
+
+impl Component for TextEditor {
+    type Event = TextEditorEvent;
+
+    fn handle_input(&mut self, key: KeyEvent) -> Option<Self::Event> {
+        match (key.code, key.modifiers) {
+            (KeyCode::Esc, _) => return Some(TextEditorEvent::Cancelled),
+            (KeyCode::Char('s'), KeyModifiers::CONTROL) => {
+                return Some(TextEditorEvent::Saved(self.text()));
+            }
+            (KeyCode::Enter, _) => self.insert_newline(),
+            (KeyCode::Char(c), KeyModifiers::NONE | KeyModifiers::SHIFT) => self.insert_char(c),
+            (KeyCode::Backspace, _) => self.backspace(),
+            (KeyCode::Delete, _) => self.delete(),
+            (KeyCode::Left, _) => self.move_left(),
+            (KeyCode::Right, _) => self.move_right(),
+            (KeyCode::Up, _) => self.move_up(),
+            (KeyCode::Down, _) => self.move_down(),
+            (KeyCode::Home, _) => self.col = 0,
+            (KeyCode::End, _) => self.col = self.line_len(),
+            _ => {}
+        }
+        None
+    }
+
+    fn render(&self, f: &mut Frame, area: Rect, theme: &Theme) {
+        // Small popup centered in `area`
+        let width = 64.min(area.width);
+        let height = 14.min(area.height);
+        let popup = Rect {
+            x: area.x + (area.width - width) / 2,
+            y: area.y + (area.height - height) / 2,
+            width,
+            height,
+        };
+
+        f.render_widget(Clear, popup);
+        let block = Block::default()
+            .borders(Borders::ALL)
+            .title(format!(" {} ", self.title))
+            .border_style(Style::default().fg(theme.primary))
+            .style(Style::default().bg(theme.background));
+        let inner = block.inner(popup);
+        f.render_widget(block, popup);
+
+        let chunks = Layout::default()
+            .direction(Direction::Vertical)
+            .constraints([Constraint::Min(1), Constraint::Length(1)])
+            .split(inner);
+
+        // Scroll so the cursor line stays visible
+        let visible = usize::from(chunks[0].height).max(1);
+        let scroll = (self.row + 1).saturating_sub(visible);
+        let cursor_style = Style::default().add_modifier(Modifier::REVERSED);
+
+        let lines: Vec<Line> = self
+            .lines
+            .iter()
+            .enumerate()
+            .skip(scroll)
+            .take(visible)
+            .map(|(i, line)| {
+                if i != self.row {
+                    return Line::from(line.as_str());
+                }
+                let mut chars = line.chars();
+                let before: String = chars.by_ref().take(self.col).collect();
+                let at = chars.next().map_or(" ".to_string(), String::from);
+                let after: String = chars.collect();
+                Line::from(vec![
+                    Span::raw(before),
+                    Span::styled(at, cursor_style),
+                    Span::raw(after),
+                ])
+            })
+            .collect();
+        f.render_widget(
+            Paragraph::new(lines).style(Style::default().fg(theme.text)),
+            chunks[0],
+        );
+
+        let help = Line::from(vec![
+            Span::styled("Ctrl+S", Style::default().fg(theme.primary)),
+            Span::raw(": Save  "),
+            Span::styled("Enter", Style::default().fg(theme.primary)),
+            Span::raw(": New line  "),
+            Span::styled("Esc", Style::default().fg(theme.primary)),
+            Span::raw(": Cancel"),
+        ]);
+        f.render_widget(
+            Paragraph::new(help).style(Style::default().fg(theme.text_muted)),
+            chunks[1],
+        );
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn press(editor: &mut TextEditor, code: KeyCode) -> Option<TextEditorEvent> {
+        editor.handle_input(KeyEvent::new(code, KeyModifiers::NONE))
+    }
+
+    fn type_str(editor: &mut TextEditor, text: &str) {
+        for c in text.chars() {
+            press(editor, KeyCode::Char(c));
+        }
+    }
+
+    #[test]
+    fn test_typing_and_newlines() {
+        let mut editor = TextEditor::new("Notes", "");
+        type_str(&mut editor, "Mirrors");
+        press(&mut editor, KeyCode::Enter);
+        type_str(&mut editor, "macOS");
+
+        let saved = editor.handle_input(KeyEvent::new(KeyCode::Char('s'), KeyModifiers::CONTROL));
+        assert_eq!(
+            saved,
+            Some(TextEditorEvent::Saved("Mirrors\nmacOS".to_string()))
+        );
+    }
+
+    #[test]
+    fn test_backspace_and_delete_join_lines() {
+        let mut editor = TextEditor::new("Notes", "ab\ncd");
+        press(&mut editor, KeyCode::Home);
+        press(&mut editor, KeyCode::Backspace);
+        assert_eq!(editor.text(), "abcd");
+
+        press(&mut editor, KeyCode::Enter);
+        press(&mut editor, KeyCode::Up);
+        press(&mut editor, KeyCode::End);
+        press(&mut editor, KeyCode::Delete);
+        assert_eq!(editor.text(), "abcd");
+    }
+
+    #[test]
+    fn test_cursor_movement_clamps_to_line() {
+        let mut editor = TextEditor::new("Notes", "short\nlonger line");
+        press(&mut editor, KeyCode::Up);
+        type_str(&mut editor, "!");
+        assert_eq!(editor.text(), "short!\nlonger line");
+
+        // Left at the start of a line wraps to the end of the previous one
+        press(&mut editor, KeyCode::Down);
+        press(&mut editor, KeyCode::Home);
+        press(&mut editor, KeyCode::Left);
+        type_str(&mut editor, "é");
+        assert_eq!(editor.text(), "short!é\nlonger line");
+    }
+
+    #[test]
+    fn test_cancel() {
+        let mut editor = TextEditor::new("Notes", "text");
+        type_str(&mut editor, " more");
+        assert_eq!(
+            press(&mut editor, KeyCode::Esc),
+            Some(TextEditorEvent::Cancelled)
+        );
+    }
+}
@@ -0,0 +1,444 @@
+//! Single-line text input shared by the dialogs.
+//!
+//! Editing works on grapheme clusters, so Backspace removes an emoji or an
+//! accented letter as a whole. Left/Right/Home/End move the cursor, holding
+//! Shift extends a selection, and typing or pasting replaces the selection.
+//! Dialogs handle their own keys (Enter, Esc, Tab) first and pass the rest
+//! to [`TextInput::handle_key`].
+
+use std::fmt;
+use std::ops::Range;
+
+use crossterm::event::{KeyCode, KeyEvent, KeyModifiers};
+use ratatui::{
+    layout::Rect,
+    style::{Modifier, Style},
+    text::{Line, Span},
+    widgets::{Block, Paragraph},
+    Frame,
+};
+use unicode_segmentation::UnicodeSegmentation;
+use unicode_width::UnicodeWidthStr;
+
+/// Editable single-line text with a cursor and an optional selection
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct TextInput {
+    value: String,
+    /// Byte offset of the cursor, always on a grapheme boundary
+    cursor: usize,
+    /// Byte offset where the selection started; it runs to the cursor
+    anchor: Option<usize>,
+}
+
+impl TextInput {
+    /// Creates an input holding `value` with the cursor at the end.
+    #[must_use]
+    pub fn new(value: impl Into<String>) -> Self {
+        let value = value.into();
+        Self {
+            cursor: value.len(),
+            value,
+            anchor: None,
+        }
+    }
+
+    /// The current text
+    #[must_use]
+    pub fn value(&self) -> &str {
+        &self.value
+    }
+
+    /// Replaces the text, moving the cursor to the end.
+    pub fn set_value(&mut self, value: impl Into<String>) {
+        *self = Self::new(value);
+    }
+
+    /// Removes all text.
+    pub fn clear(&mut self) {
+        *self = Self::default();
+    }
+
+    /// Whether there is no text
+    #[must_use]
+    pub fn is_empty(&self) -> bool {
+        self.value.is_empty()
+    }
+
+    /// Display width of the text before the cursor, in terminal cells
+    #[must_use]
+    pub fn cursor_column(&self) -> usize {
+        self.value[..self.cursor].width()
+    }
+
+    /// Byte range of the selection, if anything is selected
+    #[must_use]
+    pub fn selection(&self) -> Option<Range<usize>> {
+        let anchor = self.anchor?;
+        let range = anchor.min(self.cursor)..anchor.max(self.cursor);
+        (!range.is_empty()).then_some(range)
+    }
+
+    /// Types `c` at the cursor, replacing the selection.
+    pub fn insert_char(&mut self, c: char) {
+        self.delete_selection();
+        self.value.insert(self.cursor, c);
+        self.cursor += c.len_utf8();
+    }
+
+    /// Inserts pasted text at the cursor, replacing the selection.
+    ///
+    /// Line breaks become spaces and other control characters are dropped,
+    /// since the input holds a single line.
+    pub fn insert_str(&mut self, text: &str) {
+        let text: String = text
+            .lines()
+            .collect::<Vec<_>>()
+            .join(" ")
+            .chars()
+            .filter(|c| !c.is_control())
+            .collect();
+        self.delete_selection();
+        self.value.insert_str(self.cursor, &text);
+        self.cursor += text.len();
+    }
+
+    /// Deletes the selection, or the grapheme before the cursor.
+    ///
+    /// Returns whether anything was deleted.
+    pub fn backspace(&mut self) -> bool {
+        if self.delete_selection() {
+            return true;
+        }
+        let start = self.prev_boundary(self.cursor);
+        self.value.replace_range(start..self.cursor, "");
+        let deleted = start < self.cursor;
+        self.cursor = start;
+        deleted
+    }
+
+    /// Deletes the selection, or the grapheme after the cursor.
+    ///
+    /// Returns whether anything was deleted.
+    pub fn delete(&mut self) -> bool {
+        if self.delete_selection() {
+            return true;
+        }
+        let end = self.next_boundary(self.cursor);
+        self.value.replace_range(self.cursor..end, "");
+        end > self.cursor
+    }
+
+    /// Moves the cursor one grapheme left, extending the selection if `select`.
+    pub fn move_left(&mut self, select: bool) {
+        let target = match self.selection() {
+            Some(range) if !select => range.start,
+            _ => self.prev_boundary(self.cursor),
+        };
+        self.move_to(target, select);
+    }
+
+    /// Moves the cursor one grapheme right, extending the selection if `select`.
+    pub fn move_right(&mut self, select: bool) {
+        let target = match self.selection() {
+            Some(range) if !select => range.end,
+            _ => self.next_boundary(self.cursor),
+        };
+        self.move_to(target, select);
+    }
+
+    /// Moves the cursor to the start, extending the selection if `select`.
+    pub fn move_home(&mut self, select: bool) {
+        self.move_to(0, select);
+    }
+
+    /// Moves the cursor to the end, extending the selection if `select`.
+    pub fn move_end(&mut self, select: bool) {
+        self.move_to(self.value.len(), select);
+    }
+
+    /// Selects all text, with the cursor at the end.
+    pub fn select_all(&mut self) {
+        self.anchor = Some(0);
+        self.cursor = self.value.len();
+    }
+
+    /// Applies an editing or cursor key.
+    ///
+    /// Handles printable characters, Backspace, Delete, Left, Right, Home and
+    /// End (with Shift to select) and Ctrl+A to select all. Returns whether
+    /// the text changed; other keys are ignored.
+    pub fn handle_key(&mut self, key: KeyEvent) -> bool {
+        let select = key.modifiers.contains(KeyModifiers::SHIFT);
+        match key.code {
+            KeyCode::Char('a') if key.modifiers == KeyModifiers::CONTROL => {
+                self.select_all();
+                false
+            }
+            KeyCode::Char(c)
+                if !key
+                    .modifiers
+                    .intersects(KeyModifiers::CONTROL | KeyModifiers::ALT) =>
+            {
+                self.insert_char(c);
+                true
+            }
+            KeyCode::Backspace => self.backspace(),
+            KeyCode::Delete => self.delete(),
+            KeyCode::Left => {
+                self.move_left(select);
+                false
+            }
+            KeyCode::Right => {
+                self.move_right(select);
+                false
+            }
+            KeyCode::Home => {
+                self.move_home(select);
+                false
+            }
+            KeyCode::End => {
+                self.move_end(select);
+                false
+            }
+            _ => false,
+        }
+    }
+
+    /// The text as spans, with the cursor cell and the selection reversed.
+    ///
+    /// Without `focused` the text is shown plain.
+    #[must_use]
+    pub fn spans(&self, style: Style, focused: bool) -> Vec<Span<'_>> {
+        self.spans_from(0, style, focused)
+    }
+
+    /// Renders the input inside `block`, scrolled so the cursor stays visible.
+    pub fn render(&self, f: &mut Frame, area: Rect, block: Block, style: Style, focused: bool) {
+        let inner = block.inner(area);
+        f.render_widget(block, area);
+
+        // Drop graphemes from the left until the cursor cell fits
+        let width = usize::from(inner.width);
+        let mut column = self.cursor_column();
+        let mut start = 0;
+        while start < self.cursor && column + 1 > width {
+            let next = self.next_boundary(start);
+            column -= self.value[start..next].width();
+            start = next;
+        }
+        let line = Line::from(self.spans_from(start, style, focused));
+        f.render_widget(Paragraph::new(line).style(style), inner);
+    }
+
+    fn spans_from(&self, start: usize, style: Style, focused: bool) -> Vec<Span<'_>> {
+        let text = &self.value;
+        if !focused {
+            return vec![Span::styled(&text[start..], style)];
+        }
+        let reversed = style.add_modifier(Modifier::REVERSED);
+        if let Some(range) = self.selection() {
+            let from = range.start.max(start);
+            let to = range.end.max(start);
+            return vec![
+                Span::styled(&text[start..from], style),
+                Span::styled(&text[from..to], reversed),
+                Span::styled(&text[to..], style),
+            ];
+        }
+        let end = self.next_boundary(self.cursor);
+        let at = if end > self.cursor {
+            &text[self.cursor..end]
+        } else {
+            " "
+        };
+        vec![
+            Span::styled(&text[start..self.cursor], style),
+            Span::styled(at, reversed),
+            Span::styled(&text[end..], style),
+        ]
+    }
+
+    fn move_to(&mut self, target: usize, select: bool) {
+        if select {
+            self.anchor.get_or_insert(self.cursor);
+        } else {
+            self.anchor = None;
+        }
+        self.cursor = target;
+    }
+
+    /// Removes the selected text, returning whether there was any.
+    fn delete_selection(&mut self) -> bool {
+        let Some(range) = self.selection() else {
+            self.anchor = None;
+            return false;
+        };
+        self.cursor = range.start;
+        self.value.replace_range(range, "");
+        self.anchor = None;
+        true
+    }
+
+    fn prev_boundary(&self, pos: usize) -> usize {
+        self.value[..pos]
+            .grapheme_indices(true)
+            .next_back()
+            .map_or(0, |(i, _)| i)
+    }
+
+    fn next_boundary(&self, pos: usize) -> usize {
+        self.value[pos..]
+            .graphemes(true)
+            .next()
+            .map_or(pos, |g| pos + g.len())
+    }
+}
+
+impl fmt::Display for TextInput {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(&self.value)
+    }
+}
+
+impl From<&str> for TextInput {
+    fn from(value: &str) -> Self {
+        Self::new(value)
+    }
+}
+
+impl From<String> for TextInput {
+    fn from(value: String) -> Self {
+        Self::new(value)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use ratatui::{backend::TestBackend, widgets::Borders, Terminal};
+
+    fn press(input: &mut TextInput, code: KeyCode, modifiers: KeyModifiers) -> bool {
+        input.handle_key(KeyEvent::new(code, modifiers))
+    }
+
+    fn type_str(input: &mut TextInput, text: &str) {
+        for c in text.chars() {
+            press(input, KeyCode::Char(c), KeyModifiers::NONE);
+        }
+    }
+
+    #[test]
+    fn test_backspace_removes_whole_graphemes() {
+        // Family emoji (joined by ZWJ), a flag, and e + combining acute
+        let mut input = TextInput::new("a👨‍👩‍👧🇩🇪e\u{301}");
+        assert!(input.backspace());
+        assert_eq!(input.value(), "a👨‍👩‍👧🇩🇪");
+        input.backspace();
+        assert_eq!(input.value(), "a👨‍👩‍👧");
+        input.backspace();
+        assert_eq!(input.value(), "a");
+        input.backspace();
+        assert!(!input.backspace());
+        assert!(input.is_empty());
+    }
+
+    #[test]
+    fn test_cursor_movement_and_insertion() {
+        let mut input = TextInput::new("né");
+        press(&mut input, KeyCode::Home, KeyModifiers::NONE);
+        type_str(&mut input, "ü");
+        press(&mut input, KeyCode::Right, KeyModifiers::NONE);
+        press(&mut input, KeyCode::Right, KeyModifiers::NONE);
+        type_str(&mut input, "!");
+        assert_eq!(input.value(), "üné!");
+
+        press(&mut input, KeyCode::Left, KeyModifiers::NONE);
+        press(&mut input, KeyCode::Left, KeyModifiers::NONE);
+        assert!(press(&mut input, KeyCode::Delete, KeyModifiers::NONE));
+        assert_eq!(input.value(), "ün!");
+        press(&mut input, KeyCode::End, KeyModifiers::NONE);
+        assert!(!press(&mut input, KeyCode::Delete, KeyModifiers::NONE));
+        assert_eq!(input.cursor_column(), 3);
+    }
+
+    #[test]
+    fn test_shift_selection_is_replaced() {
+        let mut input = TextInput::new("hello 🌍 world");
+        press(&mut input, KeyCode::Home, KeyModifiers::NONE);
+        for _ in 0..7 {
+            press(&mut input, KeyCode::Right, KeyModifiers::SHIFT);
+        }
+        // "hello " plus the four-byte globe
+        assert_eq!(input.selection(), Some(0..10));
+        type_str(&mut input, "Hi");
+        assert_eq!(input.value(), "Hi world");
+
+        // Left without Shift collapses the selection to its start
+        press(&mut input, KeyCode::End, KeyModifiers::SHIFT);
+        press(&mut input, KeyCode::Left, KeyModifiers::NONE);
+        assert_eq!(input.selection(), None);
+        type_str(&mut input, ",");
+        assert_eq!(input.value(), "Hi, world");
+
+        press(&mut input, KeyCode::Char('a'), KeyModifiers::CONTROL);
+        assert!(press(&mut input, KeyCode::Backspace, KeyModifiers::NONE));
+        assert!(input.is_empty());
+    }
+
+    #[test]
+    fn test_paste_flattens_lines() {
+        let mut input = TextInput::new("ab");
+        input.move_left(false);
+        input.insert_str("1\r\n2\t3\n");
+        assert_eq!(input.value(), "a1 23b");
+        input.select_all();
+        input.insert_str("🎹");
+        assert_eq!(input.value(), "🎹");
+    }
+
+    /// Renders `input` into a bordered field `width` cells wide.
+    fn render(input: &TextInput, width: u16) -> Terminal<TestBackend> {
+        let mut terminal = Terminal::new(TestBackend::new(width, 3)).unwrap();
+        terminal
+            .draw(|f| {
+                let block = Block::default().borders(Borders::ALL);
+                input.render(f, f.area(), block, Style::default(), true);
+            })
+            .unwrap();
+        terminal
+    }
+
+    fn reversed_cells(terminal: &Terminal<TestBackend>) -> Vec<(u16, String)> {
+        let buffer = terminal.backend().buffer();
+        (0..buffer.area.width)
+            .filter(|&x| buffer[(x, 1)].modifier.contains(Modifier::REVERSED))
+            .map(|x| (x, buffer[(x, 1)].symbol().to_string()))
+            .collect()
+    }
+
+    #[test]
+    fn test_render_cursor_inside_border() {
+        let mut input = TextInput::new("añb");
+        input.move_left(false);
+        let terminal = render(&input, 10);
+        // Border at x=0, so "a" is at 1, "ñ" at 2 and the cursor on "b" at 3
+        assert_eq!(reversed_cells(&terminal), [(3, "b".to_string())]);
+        assert_eq!(terminal.backend().buffer()[(0, 1)].symbol(), "│");
+
+        // At the end the cursor is a blank cell after wide characters
+        let input = TextInput::new("日本");
+        assert_eq!(reversed_cells(&render(&input, 10)), [(5, " ".to_string())]);
+    }
+
+    #[test]
+    fn test_render_scrolls_to_keep_cursor_visible() {
+        let input = TextInput::new("abcdefghij");
+        // Inner width 6: the last five letters and the cursor cell
+        let terminal = render(&input, 8);
+        let buffer = terminal.backend().buffer();
+        let row: String = (1..7).map(|x| buffer[(x, 1)].symbol()).collect();
+        assert_eq!(row, "fghij ");
+        assert_eq!(reversed_cells(&terminal), [(6, " ".to_string())]);
+        assert_eq!(buffer[(7, 1)].symbol(), "│");
+    }
+}
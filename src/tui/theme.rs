@@ -3,7 +3,7 @@
 //! This module provides a centralized theme management system that automatically
 //! detects the OS theme (dark/light mode) and applies appropriate colors.
 
-use ratatui::style::Color;
+use ratatui::style::{Color, Modifier, Style};
 
 /// Semantic color theme for the TUI.
 ///
@@ -44,6 +44,10 @@ pub struct Theme {
     pub active: Color,
     /// Inactive/disabled element color
     pub inactive: Color,
+
+    /// Whether highlighted states carry text markers and inverse video, so
+    /// nothing is conveyed by color alone
+    pub high_contrast: bool,
 }
 
 impl Theme {
@@ -93,6 +97,8 @@ impl Theme {
 
             active: Color::Yellow,
             inactive: Color::Gray,
+
+            high_contrast: false,
         }
     }
 
@@ -123,6 +129,53 @@ impl Theme {
 
             active: Color::Rgb(180, 100, 0),
             inactive: Color::Rgb(180, 180, 180),
+
+            high_contrast: false,
+        }
+    }
+
+    /// Creates a high-contrast theme for low-vision users.
+    ///
+    /// # Color Choices
+    /// - White on black only; every semantic color is white
+    /// - Selections use bold inverse video instead of a background color
+    /// - Keys and lists mark selected, cut and flagged states with text
+    #[must_use]
+    pub const fn high_contrast() -> Self {
+        Self {
+            primary: Color::White,
+            accent: Color::White,
+            success: Color::White,
+            error: Color::White,
+            warning: Color::White,
+
+            text: Color::White,
+            text_secondary: Color::White,
+            text_muted: Color::White,
+
+            background: Color::Black,
+            highlight_bg: Color::Black,
+            surface: Color::Black,
+
+            active: Color::White,
+            inactive: Color::White,
+
+            high_contrast: true,
+        }
+    }
+
+    /// Style of the selected row in a list: `style`, or bold inverse video
+    /// under the high-contrast theme.
+    #[must_use]
+    pub const fn selection(&self, style: Style) -> Style {
+        if self.high_contrast {
+            Style::new()
+                .fg(self.text)
+                .bg(self.background)
+                .add_modifier(Modifier::REVERSED)
+                .add_modifier(Modifier::BOLD)
+        } else {
+            style
         }
     }
 
@@ -131,12 +184,14 @@ impl Theme {
     /// - `Auto`: Detects OS dark/light mode and returns matching theme
     /// - `Dark`: Always returns dark theme
     /// - `Light`: Always returns light theme
+    /// - `HighContrast`: Always returns the high-contrast theme
     #[must_use]
     pub fn from_mode(mode: crate::config::ThemeMode) -> Self {
         match mode {
             crate::config::ThemeMode::Auto => Self::detect(),
             crate::config::ThemeMode::Dark => Self::dark(),
             crate::config::ThemeMode::Light => Self::light(),
+            crate::config::ThemeMode::HighContrast => Self::high_contrast(),
         }
     }
 }
@@ -216,6 +271,21 @@ mod tests {
         assert_ne!(theme.text, theme.text_muted);
     }
 
+    #[test]
+    fn test_theme_high_contrast() {
+        let theme = Theme::high_contrast();
+        assert!(theme.high_contrast);
+        assert!(!Theme::dark().high_contrast);
+        assert_eq!(theme.background, Color::Black);
+        assert_eq!(theme.text, Color::White);
+        assert_eq!(theme.accent, Color::White);
+
+        let selected = theme.selection(Style::default().bg(theme.surface));
+        assert!(selected.add_modifier.contains(Modifier::REVERSED));
+        let plain = Style::default().bg(Color::Blue);
+        assert_eq!(Theme::dark().selection(plain), plain);
+    }
+
     #[test]
     fn test_theme_detect() {
         // Just verify detect() returns a valid theme without panicking
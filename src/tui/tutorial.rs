@@ -0,0 +1,282 @@
+//! First-run tutorial: callouts that walk new users through the editor.
+//!
+//! Each step is a small popup next to the UI area it describes, naming one
+//! concept and a key to try. The step advances when the user performs one of
+//! its actions or presses Ctrl+N; Esc skips the rest of the tour. Steps are
+//! defined in `src/data/tutorial.toml`, so they can be reworded or reordered
+//! without touching code.
+
+use ratatui::{
+    layout::Rect,
+    style::{Modifier, Style},
+    text::{Line, Span},
+    widgets::{Block, Borders, Clear, Paragraph, Wrap},
+    Frame,
+};
+use serde::Deserialize;
+
+use crate::tui::theme::Theme;
+
+/// Embedded tutorial step definitions
+const TUTORIAL_TOML: &str = include_str!("../data/tutorial.toml");
+
+/// Widest a callout gets
+const CALLOUT_WIDTH: u16 = 50;
+
+/// UI area a tutorial step points at.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum TutorialAnchor {
+    /// The keyboard widget
+    Keyboard,
+    /// The layer shown in the title bar
+    LayerIndicator,
+    /// The status bar
+    StatusBar,
+}
+
+/// One step of the tour.
+#[derive(Debug, Clone, Deserialize)]
+pub struct TutorialStep {
+    /// Area the callout is drawn next to
+    pub anchor: TutorialAnchor,
+    /// Callout title
+    pub title: String,
+    /// Explanation of the concept
+    pub text: String,
+    /// Key(s) the user is asked to try
+    #[serde(rename = "try")]
+    pub try_keys: String,
+    /// Action IDs that complete the step
+    #[serde(default)]
+    pub advance_on: Vec<String>,
+}
+
+/// Root structure of the tutorial TOML file
+#[derive(Debug, Clone, Deserialize)]
+struct TutorialFile {
+    steps: Vec<TutorialStep>,
+}
+
+/// Progress through the tour.
+#[derive(Debug, Clone)]
+pub struct Tutorial {
+    steps: Vec<TutorialStep>,
+    current: usize,
+}
+
+impl Tutorial {
+    /// Starts the tour at its first step, loading the embedded steps.
+    pub fn load() -> Result<Self, toml::de::Error> {
+        let file: TutorialFile = toml::from_str(TUTORIAL_TOML)?;
+        Ok(Self::new(file.steps))
+    }
+
+    /// Starts a tour of `steps`.
+    #[must_use]
+    pub const fn new(steps: Vec<TutorialStep>) -> Self {
+        Self { steps, current: 0 }
+    }
+
+    /// The step shown, or `None` once the tour is over.
+    #[must_use]
+    pub fn step(&self) -> Option<&TutorialStep> {
+        self.steps.get(self.current)
+    }
+
+    /// Returns true once every step is done.
+    #[must_use]
+    pub fn is_finished(&self) -> bool {
+        self.current >= self.steps.len()
+    }
+
+    /// Moves on to the next step.
+    pub fn next(&mut self) {
+        self.current = (self.current + 1).min(self.steps.len());
+    }
+
+    /// Advances if `action_id` completes the current step; returns true if it did.
+    pub fn observe(&mut self, action_id: &str) -> bool {
+        let completes = self
+            .step()
+            .is_some_and(|step| step.advance_on.iter().any(|id| id == action_id));
+        if completes {
+            self.next();
+        }
+        completes
+    }
+
+    /// Current step number (1-based) and the step count.
+    #[must_use]
+    pub fn progress(&self) -> (usize, usize) {
+        (self.current + 1, self.steps.len())
+    }
+}
+
+/// Screen areas callouts are anchored to.
+#[derive(Debug, Clone, Copy)]
+pub struct TutorialAreas {
+    /// Title bar
+    pub title_bar: Rect,
+    /// Keyboard widget
+    pub keyboard: Rect,
+    /// Status bar
+    pub status_bar: Rect,
+}
+
+/// Draws the current step's callout next to its anchor.
+pub fn render(f: &mut Frame, tutorial: &Tutorial, areas: TutorialAreas, theme: &Theme) {
+    let Some(step) = tutorial.step() else {
+        return;
+    };
+    let (number, total) = tutorial.progress();
+
+    let screen = f.area();
+    let width = CALLOUT_WIDTH.min(screen.width.saturating_sub(2));
+    let inner_width = usize::from(width.saturating_sub(2)).max(1);
+    // Wrapped text, a blank line, "Try" and the key hints, plus the border
+    let text_lines = step.text.chars().count().div_ceil(inner_width) + 1;
+    let height = u16::try_from(text_lines + 5)
+        .unwrap_or(u16::MAX)
+        .min(screen.height);
+    let area = callout_area(step.anchor, areas, screen, width, height);
+
+    let pointer = match step.anchor {
+        TutorialAnchor::Keyboard => "◆",
+        TutorialAnchor::LayerIndicator => "▲",
+        TutorialAnchor::StatusBar => "▼",
+    };
+    let lines = vec![
+        Line::from(step.text.as_str()),
+        Line::from(""),
+        Line::from(vec![
+            Span::styled("Try: ", Style::default().fg(theme.text_secondary)),
+            Span::styled(
+                step.try_keys.as_str(),
+                Style::default()
+                    .fg(theme.accent)
+                    .add_modifier(Modifier::BOLD),
+            ),
+        ]),
+        Line::from(Span::styled(
+            "Ctrl+N: next · Esc: skip tour",
+            Style::default().fg(theme.text_muted),
+        )),
+    ];
+    let callout = Paragraph::new(lines)
+        .wrap(Wrap { trim: true })
+        .style(Style::default().fg(theme.text).bg(theme.background))
+        .block(
+            Block::default()
+                .borders(Borders::ALL)
+                .border_style(Style::default().fg(theme.primary))
+                .title(format!(" {pointer} Tour {number}/{total}: {} ", step.title)),
+        );
+
+    f.render_widget(Clear, area);
+    f.render_widget(callout, area);
+}
+
+/// Places a `width` x `height` callout next to `anchor`, inside `screen`.
+fn callout_area(
+    anchor: TutorialAnchor,
+    areas: TutorialAreas,
+    screen: Rect,
+    width: u16,
+    height: u16,
+) -> Rect {
+    let (x, y) = match anchor {
+        // Bottom right of the keyboard, where boards usually leave room
+        TutorialAnchor::Keyboard => (
+            areas.keyboard.right().saturating_sub(width + 1),
+            areas.keyboard.bottom().saturating_sub(height),
+        ),
+        // Just below the layer number in the title bar
+        TutorialAnchor::LayerIndicator => (areas.title_bar.x + 2, areas.title_bar.bottom()),
+        // Right above the status bar
+        TutorialAnchor::StatusBar => (
+            areas.status_bar.right().saturating_sub(width + 1),
+            areas.status_bar.y.saturating_sub(height),
+        ),
+    };
+    let x = x.min(screen.right().saturating_sub(width));
+    let y = y.min(screen.bottom().saturating_sub(height));
+    Rect::new(x, y, width, height)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::shortcuts::Action;
+
+    #[test]
+    fn test_embedded_steps_advance_on_real_actions() {
+        let tutorial = Tutorial::load().unwrap();
+        assert!(tutorial.steps.len() >= 3);
+        let actions: Vec<&str> = [
+            Action::NavigateUp,
+            Action::NavigateDown,
+            Action::NavigateLeft,
+            Action::NavigateRight,
+            Action::OpenKeycodePicker,
+            Action::NextLayer,
+            Action::PreviousLayer,
+            Action::Save,
+            Action::ToggleHelp,
+        ]
+        .map(Action::id)
+        .to_vec();
+        for step in &tutorial.steps {
+            assert!(!step.advance_on.is_empty(), "{}", step.title);
+            for id in &step.advance_on {
+                assert!(actions.contains(&id.as_str()), "Unknown action {id}");
+            }
+        }
+    }
+
+    #[test]
+    fn test_tour_advances_on_suggested_action_or_next() {
+        let mut tutorial = Tutorial::load().unwrap();
+        let total = tutorial.steps.len();
+        assert_eq!(tutorial.progress(), (1, total));
+
+        // Other actions don't count
+        assert!(!tutorial.observe("open_keycode_picker"));
+        assert!(tutorial.observe("navigate_left"));
+        assert_eq!(tutorial.step().unwrap().title, "Assigning keycodes");
+
+        tutorial.next();
+        assert_eq!(tutorial.progress(), (3, total));
+        while !tutorial.is_finished() {
+            tutorial.next();
+        }
+        assert!(tutorial.step().is_none());
+        assert!(!tutorial.observe("toggle_help"));
+    }
+
+    #[test]
+    fn test_callouts_stay_on_screen() {
+        let screen = Rect::new(0, 0, 80, 30);
+        let areas = TutorialAreas {
+            title_bar: Rect::new(0, 0, 80, 3),
+            keyboard: Rect::new(0, 3, 80, 21),
+            status_bar: Rect::new(0, 24, 80, 6),
+        };
+        for anchor in [
+            TutorialAnchor::Keyboard,
+            TutorialAnchor::LayerIndicator,
+            TutorialAnchor::StatusBar,
+        ] {
+            let area = callout_area(anchor, areas, screen, 50, 8);
+            assert!(area.right() <= screen.right() && area.bottom() <= screen.bottom());
+        }
+        assert_eq!(
+            callout_area(TutorialAnchor::LayerIndicator, areas, screen, 50, 8).y,
+            3
+        );
+        assert_eq!(
+            callout_area(TutorialAnchor::StatusBar, areas, screen, 50, 8).bottom(),
+            24
+        );
+    }
+}
@@ -0,0 +1,230 @@
+//! Undo/redo history for layout edits.
+//!
+//! Every edit ends in [`AppState::mark_dirty`](super::AppState::mark_dirty),
+//! which records the layout as it was before and after. Edits made while
+//! handling one key press are grouped into a single entry, so a paste or a
+//! mirror is undone in one step. The history spans all layers and is
+//! cleared when another layout is loaded.
+
+use crate::models::Layout;
+
+/// Number of edits kept; the oldest is dropped beyond this.
+pub const MAX_UNDO_ENTRIES: usize = 100;
+
+/// One undoable edit.
+#[derive(Debug, Clone)]
+pub struct EditAction {
+    /// What the edit did, taken from the action log (e.g. "Pasted: `KC_A`")
+    pub label: Option<String>,
+    /// Layer shown when the edit was made, returned to on undo and redo
+    pub layer: usize,
+    /// Layout before the edit
+    pub before: Layout,
+    /// Layout after the edit
+    pub after: Layout,
+}
+
+impl EditAction {
+    /// Label for status messages.
+    #[must_use]
+    pub fn describe(&self) -> &str {
+        self.label.as_deref().unwrap_or("edit")
+    }
+}
+
+/// Edits that can be undone, and undone edits that can be redone.
+#[derive(Debug, Clone)]
+pub struct UndoHistory {
+    entries: Vec<EditAction>,
+    /// Number of entries currently applied; the ones after it can be redone
+    cursor: usize,
+    /// Layout as of the last recorded edit
+    baseline: Layout,
+    /// Whether the newest entry still takes changes (until the edit is
+    /// logged or the next key press)
+    open: bool,
+}
+
+impl UndoHistory {
+    /// Creates an empty history starting from `layout`.
+    #[must_use]
+    pub fn new(layout: &Layout) -> Self {
+        Self {
+            entries: Vec::new(),
+            cursor: 0,
+            baseline: layout.clone(),
+            open: false,
+        }
+    }
+
+    /// Forgets all edits, starting over from `layout`.
+    pub fn reset(&mut self, layout: &Layout) {
+        *self = Self::new(layout);
+    }
+
+    /// Records the change from the last recorded layout to `layout`.
+    ///
+    /// Joins the newest entry while it is open, otherwise starts a new one
+    /// and drops anything that could have been redone.
+    pub fn record(&mut self, layout: &Layout, layer: usize) {
+        if *layout == self.baseline {
+            return;
+        }
+        let before = std::mem::replace(&mut self.baseline, layout.clone());
+        if self.open && self.cursor == self.entries.len() {
+            if let Some(last) = self.entries.last_mut() {
+                last.after = layout.clone();
+                return;
+            }
+        }
+
+        self.entries.truncate(self.cursor);
+        self.entries.push(EditAction {
+            label: None,
+            layer,
+            before,
+            after: layout.clone(),
+        });
+        if self.entries.len() > MAX_UNDO_ENTRIES {
+            self.entries.remove(0);
+        }
+        self.cursor = self.entries.len();
+        self.open = true;
+    }
+
+    /// Names the open entry and closes it.
+    pub fn label(&mut self, label: &str) {
+        if self.open {
+            if let Some(last) = self.entries.last_mut() {
+                last.label.get_or_insert_with(|| label.to_string());
+            }
+        }
+        self.open = false;
+    }
+
+    /// Closes the open entry, so the next edit starts a new one.
+    pub const fn seal(&mut self) {
+        self.open = false;
+    }
+
+    /// Steps back over the newest applied edit.
+    ///
+    /// Returns the edit; its `before` layout is the one to restore.
+    pub fn undo(&mut self) -> Option<&EditAction> {
+        self.open = false;
+        self.cursor = self.cursor.checked_sub(1)?;
+        let entry = &self.entries[self.cursor];
+        self.baseline.clone_from(&entry.before);
+        Some(entry)
+    }
+
+    /// Re-applies the most recently undone edit.
+    ///
+    /// Returns the edit; its `after` layout is the one to restore.
+    pub fn redo(&mut self) -> Option<&EditAction> {
+        self.open = false;
+        let entry = self.entries.get(self.cursor)?;
+        self.cursor += 1;
+        self.baseline.clone_from(&entry.after);
+        Some(entry)
+    }
+
+    /// Number of edits that can be undone.
+    #[must_use]
+    pub const fn undo_count(&self) -> usize {
+        self.cursor
+    }
+
+    /// Whether there is an edit to undo.
+    #[must_use]
+    pub const fn can_undo(&self) -> bool {
+        self.cursor > 0
+    }
+
+    /// Whether there is an undone edit to redo.
+    #[must_use]
+    pub fn can_redo(&self) -> bool {
+        self.cursor < self.entries.len()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// A layout that only differs from others by its name
+    fn layout(name: &str) -> Layout {
+        let mut layout = Layout::new(name).unwrap();
+        layout.metadata.created = chrono::DateTime::UNIX_EPOCH;
+        layout.metadata.modified = chrono::DateTime::UNIX_EPOCH;
+        layout
+    }
+
+    /// Records one labelled edit renaming the layout.
+    fn edit(history: &mut UndoHistory, name: &str) {
+        history.record(&layout(name), 0);
+        history.label(&format!("Renamed to {name}"));
+    }
+
+    #[test]
+    fn test_undo_and_redo_walk_the_history() {
+        let mut history = UndoHistory::new(&layout("a"));
+        assert!(!history.can_undo());
+        edit(&mut history, "b");
+        edit(&mut history, "c");
+        assert_eq!(history.undo_count(), 2);
+
+        let entry = history.undo().unwrap();
+        assert_eq!(entry.before.metadata.name, "b");
+        assert_eq!(entry.describe(), "Renamed to c");
+        assert_eq!(history.undo().unwrap().before.metadata.name, "a");
+        assert!(history.undo().is_none());
+        assert!(history.can_redo());
+
+        assert_eq!(history.redo().unwrap().after.metadata.name, "b");
+        assert!(history.can_redo());
+
+        // A new edit drops what could have been redone
+        edit(&mut history, "d");
+        assert!(!history.can_redo());
+        assert_eq!(history.undo().unwrap().before.metadata.name, "b");
+    }
+
+    #[test]
+    fn test_open_entry_groups_edits_until_labelled() {
+        let mut history = UndoHistory::new(&layout("a"));
+        history.record(&layout("b"), 0);
+        history.record(&layout("c"), 0);
+        history.label("Two renames");
+        history.record(&layout("c"), 0);
+        assert_eq!(history.undo_count(), 1);
+
+        let entry = history.undo().unwrap();
+        assert_eq!(entry.before.metadata.name, "a");
+        assert_eq!(entry.after.metadata.name, "c");
+
+        history.redo();
+        history.record(&layout("d"), 0);
+        history.seal();
+        history.record(&layout("e"), 0);
+        assert_eq!(history.undo_count(), 3);
+        assert_eq!(history.undo().unwrap().describe(), "edit");
+    }
+
+    #[test]
+    fn test_history_is_capped() {
+        let mut history = UndoHistory::new(&layout("start"));
+        for i in 0..=MAX_UNDO_ENTRIES {
+            edit(&mut history, &format!("name {i}"));
+        }
+        assert_eq!(history.undo_count(), MAX_UNDO_ENTRIES);
+        while history.can_undo() {
+            history.undo();
+        }
+        // The very first edit fell off the end
+        assert_eq!(history.redo().unwrap().before.metadata.name, "name 0");
+
+        history.reset(&layout("other"));
+        assert!(!history.can_undo() && !history.can_redo());
+    }
+}
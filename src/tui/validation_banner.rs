@@ -0,0 +1,118 @@
+//! Banner under the title bar for layouts with validation issues.
+//!
+//! The layout is validated in the background (through the validation cache)
+//! after loading and after every edit, so problems show up long before
+//! firmware generation fails on them.
+
+use ratatui::{
+    layout::Rect,
+    style::{Modifier, Style},
+    widgets::Paragraph,
+    Frame,
+};
+
+use crate::tui::theme::Theme;
+
+/// Issue count behind the validation banner.
+#[derive(Debug, Clone)]
+pub struct ValidationBanner {
+    /// Errors plus warnings found by the last validation
+    issues: usize,
+    /// The layout changed since the last validation
+    stale: bool,
+    /// Hidden for the rest of the session
+    hidden: bool,
+}
+
+impl Default for ValidationBanner {
+    fn default() -> Self {
+        Self {
+            issues: 0,
+            stale: true,
+            hidden: false,
+        }
+    }
+}
+
+impl ValidationBanner {
+    /// Marks the count out of date; the last count stays shown until the
+    /// layout is revalidated, so the banner doesn't flicker while editing.
+    pub const fn invalidate(&mut self) {
+        self.stale = true;
+    }
+
+    /// Returns true if the layout needs revalidating for the banner.
+    #[must_use]
+    pub const fn is_stale(&self) -> bool {
+        self.stale && !self.hidden
+    }
+
+    /// Records the issue count of a fresh validation.
+    pub const fn update(&mut self, issues: usize) {
+        self.issues = issues;
+        self.stale = false;
+    }
+
+    /// Hides the banner until the editor is restarted.
+    pub const fn hide(&mut self) {
+        self.hidden = true;
+    }
+
+    /// Banner text, or `None` when there is nothing to show.
+    #[must_use]
+    pub fn message(&self) -> Option<String> {
+        if self.hidden || self.issues == 0 {
+            return None;
+        }
+        let noun = if self.issues == 1 { "issue" } else { "issues" };
+        Some(format!(
+            "⚠ {} validation {noun} — press Shift+F to review, Shift+X to hide",
+            self.issues
+        ))
+    }
+}
+
+/// Draws the banner line.
+pub fn render(f: &mut Frame, area: Rect, message: &str, theme: &Theme) {
+    let banner = Paragraph::new(format!(" {message}")).style(
+        Style::default()
+            .fg(theme.warning)
+            .bg(theme.background)
+            .add_modifier(Modifier::BOLD),
+    );
+    f.render_widget(banner, area);
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_banner_message_follows_issue_count() {
+        let mut banner = ValidationBanner::default();
+        assert!(banner.is_stale());
+        assert_eq!(banner.message(), None);
+
+        banner.update(3);
+        assert!(!banner.is_stale());
+        assert_eq!(
+            banner.message().as_deref(),
+            Some("⚠ 3 validation issues — press Shift+F to review, Shift+X to hide")
+        );
+
+        // The old count stays up until the edit is revalidated
+        banner.invalidate();
+        assert!(banner.is_stale());
+        assert!(banner.message().unwrap().contains("3 validation issues"));
+        banner.update(1);
+        assert!(banner.message().unwrap().contains("1 validation issue "));
+        banner.update(0);
+        assert_eq!(banner.message(), None);
+
+        banner.update(2);
+        banner.hide();
+        assert_eq!(banner.message(), None);
+        banner.invalidate();
+        assert!(!banner.is_stale(), "Hidden banners don't revalidate");
+    }
+}
@@ -0,0 +1,423 @@
+//! Validation results popup listing firmware validation errors and warnings.
+//!
+//! Issues come from [`FirmwareValidator`](crate::firmware::FirmwareValidator)
+//! and are grouped by layer. Enter jumps to the key an issue refers to, `f`
+//! toggles showing errors only, and `x` applies the quick fix for the
+//! selected issue's rule when one exists.
+
+use ratatui::{
+    layout::{Constraint, Direction, Layout as RatatuiLayout, Rect},
+    style::{Modifier, Style},
+    text::{Line, Span},
+    widgets::{Block, Borders, Clear, List, ListItem, ListState, Paragraph, Wrap},
+    Frame,
+};
+
+use crate::firmware::validator::ValidationReport;
+use crate::models::Position;
+use crate::services::quick_fix::QuickFix;
+
+/// Issues moved by PageUp/PageDown
+const PAGE_SIZE: usize = 10;
+
+/// Events emitted by the ValidationResultsView component
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum ValidationResultsEvent {
+    /// User selected an issue to jump to
+    JumpTo {
+        /// Layer index
+        layer: usize,
+        /// Key position, if the issue refers to a key
+        position: Option<Position>,
+    },
+    /// User asked to apply the quick fix for the selected issue
+    ApplyFix(QuickFix),
+    /// User closed the report
+    Closed,
+}
+
+/// One error or warning in the report
+#[derive(Debug, Clone)]
+struct Issue {
+    /// True for errors, false for warnings
+    is_error: bool,
+    /// Message including the suggestion, if any
+    message: String,
+    /// Layer the issue refers to
+    layer: Option<usize>,
+    /// Key the issue refers to
+    position: Option<Position>,
+    /// Quick fix for the issue's rule
+    fix: Option<QuickFix>,
+}
+
+/// ValidationResultsView component that implements the Component trait
+#[derive(Debug, Clone)]
+pub struct ValidationResultsView {
+    /// Issues without a layer first, then by layer; errors before warnings
+    issues: Vec<Issue>,
+    /// Hide warnings
+    errors_only: bool,
+    /// Selected index among the visible issues
+    selected: usize,
+}
+
+impl ValidationResultsView {
+    /// Create a new ValidationResultsView from a validation report.
+    #[must_use]
+    pub fn new(report: &ValidationReport) -> Self {
+        let position = |row: Option<u8>, col: Option<u8>| Some(Position::new(row?, col?));
+        let describe = |message: &str, suggestion: Option<&String>| match suggestion {
+            Some(suggestion) => format!("{message} → {suggestion}"),
+            None => message.to_string(),
+        };
+
+        let errors = report.errors.iter().map(|e| Issue {
+            is_error: true,
+            message: describe(&e.message, e.suggestion.as_ref()),
+            layer: e.layer,
+            position: position(e.row, e.col),
+            fix: None,
+        });
+        let warnings = report.warnings.iter().map(|w| Issue {
+            is_error: false,
+            message: describe(&w.message, w.suggestion.as_ref()),
+            layer: w.layer,
+            position: position(w.row, w.col),
+            fix: w.rule.and_then(QuickFix::from_rule),
+        });
+
+        let mut issues: Vec<Issue> = errors.chain(warnings).collect();
+        issues.sort_by_key(|issue| (issue.layer, !issue.is_error));
+
+        Self {
+            issues,
+            errors_only: false,
+            selected: 0,
+        }
+    }
+
+    /// Number of issues in the report.
+    #[must_use]
+    pub fn len(&self) -> usize {
+        self.issues.len()
+    }
+
+    /// Returns true if validation found nothing.
+    #[must_use]
+    pub fn is_empty(&self) -> bool {
+        self.issues.is_empty()
+    }
+
+    /// Issues shown under the current filter.
+    fn visible(&self) -> impl Iterator<Item = &Issue> {
+        self.issues
+            .iter()
+            .filter(|issue| issue.is_error || !self.errors_only)
+    }
+
+    /// The selected issue, if any are shown.
+    fn selected_issue(&self) -> Option<&Issue> {
+        self.visible().nth(self.selected)
+    }
+
+    /// Moves selection up by `count` issues.
+    const fn select_previous(&mut self, count: usize) {
+        self.selected = self.selected.saturating_sub(count);
+    }
+
+    /// Moves selection down by `count` issues.
+    fn select_next(&mut self, count: usize) {
+        let last = self.visible().count().saturating_sub(1);
+        self.selected = (self.selected + count).min(last);
+    }
+
+    /// Toggles hiding warnings, keeping the selection in range.
+    fn toggle_errors_only(&mut self) {
+        self.errors_only = !self.errors_only;
+        self.selected = 0;
+    }
+}
+
+impl crate::tui::component::Component for ValidationResultsView {
+    type Event = ValidationResultsEvent;
+
+    fn handle_input(&mut self, key: crossterm::event::KeyEvent) -> Option<Self::Event> {
+        use crossterm::event::KeyCode;
+
+        match key.code {
+            KeyCode::Esc | KeyCode::Char('q') => Some(ValidationResultsEvent::Closed),
+            KeyCode::Enter => self.selected_issue().and_then(|issue| {
+                issue.layer.map(|layer| ValidationResultsEvent::JumpTo {
+                    layer,
+                    position: issue.position,
+                })
+            }),
+            KeyCode::Char('x') => self
+                .selected_issue()
+                .and_then(|issue| issue.fix)
+                .map(ValidationResultsEvent::ApplyFix),
+            KeyCode::Char('f') => {
+                self.toggle_errors_only();
+                None
+            }
+            KeyCode::Up | KeyCode::Char('k') => {
+                self.select_previous(1);
+                None
+            }
+            KeyCode::Down | KeyCode::Char('j') => {
+                self.select_next(1);
+                None
+            }
+            KeyCode::PageUp => {
+                self.select_previous(PAGE_SIZE);
+                None
+            }
+            KeyCode::PageDown => {
+                self.select_next(PAGE_SIZE);
+                None
+            }
+            KeyCode::Home => {
+                self.selected = 0;
+                None
+            }
+            KeyCode::End => {
+                self.selected = self.visible().count().saturating_sub(1);
+                None
+            }
+            _ => None,
+        }
+    }
+
+    fn render(&self, f: &mut Frame, area: Rect, theme: &crate::tui::theme::Theme) {
+        render_validation_results(f, self, area, theme);
+    }
+}
+
+/// Renders the validation results popup
+fn render_validation_results(
+    f: &mut Frame,
+    view: &ValidationResultsView,
+    area: Rect,
+    theme: &crate::tui::theme::Theme,
+) {
+    let popup_area = centered_rect(70, 70, area);
+
+    f.render_widget(Clear, popup_area);
+    let background = Block::default().style(Style::default().bg(theme.background));
+    f.render_widget(background, popup_area);
+
+    let chunks = RatatuiLayout::default()
+        .direction(Direction::Vertical)
+        .constraints([
+            Constraint::Min(5),    // Issue list
+            Constraint::Length(3), // Summary
+        ])
+        .split(popup_area);
+
+    // One header row per layer, then its issues
+    let mut items: Vec<ListItem> = Vec::new();
+    let mut selected_row = None;
+    let mut group = None;
+    for (index, issue) in view.visible().enumerate() {
+        if items.is_empty() || group != Some(issue.layer) {
+            group = Some(issue.layer);
+            let header = issue
+                .layer
+                .map_or_else(|| "General".to_string(), |layer| format!("Layer {layer}"));
+            items.push(ListItem::new(Line::from(Span::styled(
+                header,
+                Style::default()
+                    .fg(theme.accent)
+                    .add_modifier(Modifier::BOLD),
+            ))));
+        }
+        if index == view.selected {
+            selected_row = Some(items.len());
+        }
+
+        let (icon, color) = if issue.is_error {
+            ("✗", theme.error)
+        } else {
+            ("⚠", theme.warning)
+        };
+        let location = issue
+            .position
+            .map_or_else(String::new, |pos| format!("({}, {})", pos.row, pos.col));
+        items.push(ListItem::new(Line::from(vec![
+            Span::styled(format!("  {icon} "), Style::default().fg(color)),
+            Span::styled(
+                format!("{location:<9} "),
+                Style::default().fg(theme.text_muted),
+            ),
+            Span::styled(issue.message.clone(), Style::default().fg(theme.text)),
+        ])));
+    }
+    if items.is_empty() {
+        let message = if view.errors_only && !view.issues.is_empty() {
+            "No errors (f: show warnings)"
+        } else {
+            "No validation issues"
+        };
+        items.push(ListItem::new(Line::from(Span::styled(
+            message,
+            Style::default().fg(theme.success),
+        ))));
+    }
+
+    let filter = if view.errors_only {
+        " [errors only]"
+    } else {
+        ""
+    };
+    let list = List::new(items)
+        .block(
+            Block::default()
+                .borders(Borders::ALL)
+                .title(format!(
+                    " Validation Results ({}){filter} ",
+                    view.issues.len()
+                ))
+                .style(Style::default().fg(theme.primary)),
+        )
+        .highlight_style(
+            theme.selection(
+                Style::default()
+                    .fg(theme.background)
+                    .bg(theme.primary)
+                    .add_modifier(Modifier::BOLD),
+            ),
+        );
+    let mut list_state = ListState::default();
+    list_state.select(selected_row);
+    f.render_stateful_widget(list, chunks[0], &mut list_state);
+
+    let errors = view.issues.iter().filter(|i| i.is_error).count();
+    let fix = view
+        .selected_issue()
+        .and_then(|issue| issue.fix)
+        .map_or_else(String::new, |fix| format!(" · x: {}", fix.description()));
+    let summary = format!(
+        "{errors} error(s), {} warning(s){fix}",
+        view.issues.len() - errors
+    );
+    let summary = Paragraph::new(summary)
+        .style(Style::default().fg(theme.text_muted))
+        .wrap(Wrap { trim: true })
+        .block(Block::default().borders(Borders::ALL).title("Summary"));
+    f.render_widget(summary, chunks[1]);
+}
+
+/// Helper to create a centered rectangle
+fn centered_rect(percent_x: u16, percent_y: u16, r: Rect) -> Rect {
+    let popup_layout = RatatuiLayout::default()
+        .direction(Direction::Vertical)
+        .constraints([
+            Constraint::Percentage((100 - percent_y) / 2),
+            Constraint::Percentage(percent_y),
+            Constraint::Percentage((100 - percent_y) / 2),
+        ])
+        .split(r);
+
+    RatatuiLayout::default()
+        .direction(Direction::Horizontal)
+        .constraints([
+            Constraint::Percentage((100 - percent_x) / 2),
+            Constraint::Percentage(percent_x),
+            Constraint::Percentage((100 - percent_x) / 2),
+        ])
+        .split(popup_layout[1])[1]
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::firmware::validator::{
+        ValidationError, ValidationErrorKind, ValidationWarning, BASE_LAYER_TRNS_RULE,
+    };
+    use crate::tui::component::Component;
+    use crossterm::event::{KeyCode, KeyEvent, KeyModifiers};
+
+    fn press(view: &mut ValidationResultsView, code: KeyCode) -> Option<ValidationResultsEvent> {
+        view.handle_input(KeyEvent::new(code, KeyModifiers::NONE))
+    }
+
+    #[test]
+    fn test_jump_and_fix() {
+        let mut report = ValidationReport::new();
+        report.add_error(ValidationError::new(
+            ValidationErrorKind::InvalidTapDance,
+            "Bad tap dance",
+        ));
+        report.add_warning(
+            ValidationWarning::new("KC_TRNS on the base layer does nothing")
+                .at(0, 1, 2)
+                .with_rule(BASE_LAYER_TRNS_RULE),
+        );
+        let mut view = ValidationResultsView::new(&report);
+        assert_eq!(view.len(), 2);
+
+        // The error has neither a location nor a fix
+        assert_eq!(press(&mut view, KeyCode::Enter), None);
+        assert_eq!(press(&mut view, KeyCode::Char('x')), None);
+
+        press(&mut view, KeyCode::Down);
+        assert_eq!(
+            press(&mut view, KeyCode::Enter),
+            Some(ValidationResultsEvent::JumpTo {
+                layer: 0,
+                position: Some(Position::new(1, 2)),
+            })
+        );
+        assert_eq!(
+            press(&mut view, KeyCode::Char('x')),
+            Some(ValidationResultsEvent::ApplyFix(QuickFix::BaseLayerTrns))
+        );
+    }
+
+    #[test]
+    fn test_grouped_by_layer_and_errors_only() {
+        let mut report = ValidationReport::new();
+        report.add_warning(ValidationWarning::new("Layer 1 warning").at(1, 0, 0));
+        report.add_error(
+            ValidationError::new(ValidationErrorKind::InvalidKeycode, "Layer 1 error")
+                .with_layer(1)
+                .with_position(0, 1),
+        );
+        report.add_warning(ValidationWarning::new("General warning"));
+        report.add_error(
+            ValidationError::new(ValidationErrorKind::InvalidKeycode, "Layer 0 error")
+                .with_layer(0)
+                .with_position(2, 3),
+        );
+        let mut view = ValidationResultsView::new(&report);
+
+        let messages: Vec<&str> = view.visible().map(|i| i.message.as_str()).collect();
+        assert_eq!(
+            messages,
+            [
+                "General warning",
+                "Layer 0 error",
+                "Layer 1 error",
+                "Layer 1 warning"
+            ]
+        );
+
+        press(&mut view, KeyCode::Char('f'));
+        let messages: Vec<&str> = view.visible().map(|i| i.message.as_str()).collect();
+        assert_eq!(messages, ["Layer 0 error", "Layer 1 error"]);
+        assert_eq!(
+            press(&mut view, KeyCode::Enter),
+            Some(ValidationResultsEvent::JumpTo {
+                layer: 0,
+                position: Some(Position::new(2, 3)),
+            })
+        );
+
+        // Selection stays within the filtered list
+        press(&mut view, KeyCode::PageDown);
+        assert_eq!(view.selected, 1);
+        press(&mut view, KeyCode::Char('f'));
+        assert_eq!(view.visible().count(), 4);
+    }
+}
@@ -0,0 +1,224 @@
+//! Dialog for resolving a layout variant that no longer exists.
+//!
+//! Shown before the editor opens when a layout's `layout_variant` is missing
+//! from the keyboard's info.json (typically renamed upstream). Lists the
+//! variants that do exist, most similar first, so the user can pick one.
+
+use ratatui::{
+    layout::{Alignment, Constraint, Direction, Layout as RatatuiLayout, Rect},
+    style::{Modifier, Style},
+    text::{Line, Span},
+    widgets::{Block, Borders, Clear, List, ListItem, ListState, Paragraph, Wrap},
+    Frame,
+};
+
+use crate::services::geometry::MissingVariantError;
+use crate::tui::help_registry::{self, HelpRegistry};
+
+/// Events emitted by the VariantConflictDialog component
+#[derive(Debug, Clone)]
+pub enum VariantConflictEvent {
+    /// User picked a replacement variant
+    Selected(String),
+    /// User cancelled (layout is not opened)
+    Cancelled,
+}
+
+/// VariantConflictDialog component that implements the Component trait
+#[derive(Debug, Clone)]
+pub struct VariantConflictDialog {
+    /// The missing variant and ranked replacements
+    conflict: MissingVariantError,
+    /// Number of keys in the layout's base layer
+    layout_keys: usize,
+    /// Currently selected candidate index
+    selected: usize,
+}
+
+impl VariantConflictDialog {
+    /// Create a new dialog for `conflict`.
+    #[must_use]
+    pub const fn new(conflict: MissingVariantError, layout_keys: usize) -> Self {
+        Self {
+            conflict,
+            layout_keys,
+            selected: 0,
+        }
+    }
+
+    /// Moves selection up.
+    const fn select_previous(&mut self) {
+        self.selected = self.selected.saturating_sub(1);
+    }
+
+    /// Moves selection down.
+    const fn select_next(&mut self) {
+        if self.selected + 1 < self.conflict.candidates.len() {
+            self.selected += 1;
+        }
+    }
+}
+
+impl crate::tui::component::Component for VariantConflictDialog {
+    type Event = VariantConflictEvent;
+
+    fn handle_input(&mut self, key: crossterm::event::KeyEvent) -> Option<Self::Event> {
+        use crossterm::event::KeyCode;
+
+        match key.code {
+            KeyCode::Esc | KeyCode::Char('q') => Some(VariantConflictEvent::Cancelled),
+            KeyCode::Enter => self
+                .conflict
+                .candidates
+                .get(self.selected)
+                .map(|c| VariantConflictEvent::Selected(c.name.clone())),
+            KeyCode::Up | KeyCode::Char('k') => {
+                self.select_previous();
+                None
+            }
+            KeyCode::Down | KeyCode::Char('j') => {
+                self.select_next();
+                None
+            }
+            _ => None,
+        }
+    }
+
+    fn render(&self, f: &mut Frame, area: Rect, theme: &crate::tui::theme::Theme) {
+        render_variant_conflict(f, self, area, theme);
+    }
+}
+
+/// Renders the variant conflict dialog
+fn render_variant_conflict(
+    f: &mut Frame,
+    dialog: &VariantConflictDialog,
+    area: Rect,
+    theme: &crate::tui::theme::Theme,
+) {
+    let popup_area = centered_rect(70, 70, area);
+
+    f.render_widget(Clear, popup_area);
+    let background = Block::default().style(Style::default().bg(theme.background));
+    f.render_widget(background, popup_area);
+
+    let chunks = RatatuiLayout::default()
+        .direction(Direction::Vertical)
+        .constraints([
+            Constraint::Length(5), // Explanation
+            Constraint::Min(5),    // Candidate list
+            Constraint::Length(1), // Hints
+        ])
+        .split(popup_area);
+
+    let conflict = &dialog.conflict;
+    let explanation = Paragraph::new(vec![
+        Line::from(vec![
+            Span::raw("Layout variant "),
+            Span::styled(
+                conflict.requested.clone(),
+                Style::default()
+                    .fg(theme.warning)
+                    .add_modifier(Modifier::BOLD),
+            ),
+            Span::raw(format!(" no longer exists for {}.", conflict.keyboard)),
+        ]),
+        Line::from(format!(
+            "Pick a replacement ({} keys in this layout). Keys are fitted to the new geometry; \
+             the variant is only written to the file when you save.",
+            dialog.layout_keys
+        )),
+    ])
+    .wrap(Wrap { trim: true })
+    .style(Style::default().fg(theme.text))
+    .block(
+        Block::default()
+            .borders(Borders::ALL)
+            .title(" Layout Variant Not Found ")
+            .style(Style::default().fg(theme.warning)),
+    );
+    f.render_widget(explanation, chunks[0]);
+
+    let items: Vec<ListItem> = if conflict.candidates.is_empty() {
+        vec![ListItem::new(Line::from(Span::styled(
+            "No layout variants found for this keyboard",
+            Style::default().fg(theme.error),
+        )))]
+    } else {
+        conflict
+            .candidates
+            .iter()
+            .map(|candidate| {
+                let count_style = if candidate.key_count == dialog.layout_keys {
+                    Style::default().fg(theme.success)
+                } else {
+                    Style::default().fg(theme.text_muted)
+                };
+                ListItem::new(Line::from(vec![
+                    Span::styled(
+                        format!("{:<40}", candidate.name),
+                        Style::default().fg(theme.text),
+                    ),
+                    Span::styled(format!("{:>4} keys  ", candidate.key_count), count_style),
+                    Span::styled(
+                        format!("{:>3.0}% match", candidate.similarity * 100.0),
+                        Style::default().fg(theme.text_muted),
+                    ),
+                ]))
+            })
+            .collect()
+    };
+
+    let list = List::new(items)
+        .block(
+            Block::default()
+                .borders(Borders::ALL)
+                .title(" Available Variants ")
+                .style(Style::default().fg(theme.primary)),
+        )
+        .highlight_style(
+            theme.selection(
+                Style::default()
+                    .fg(theme.background)
+                    .bg(theme.primary)
+                    .add_modifier(Modifier::BOLD),
+            ),
+        );
+    let mut list_state = ListState::default();
+    if !conflict.candidates.is_empty() {
+        list_state.select(Some(dialog.selected));
+    }
+    f.render_stateful_widget(list, chunks[1], &mut list_state);
+
+    let hints = HelpRegistry::default()
+        .format_status_bar_hints(help_registry::contexts::VARIANT_CONFLICT, 3)
+        .into_iter()
+        .map(|(key, hint)| format!("{key}: {hint}"))
+        .collect::<Vec<_>>()
+        .join(" | ");
+    let hints = Paragraph::new(hints)
+        .style(Style::default().fg(theme.text_muted))
+        .alignment(Alignment::Center);
+    f.render_widget(hints, chunks[2]);
+}
+
+/// Helper to create a centered rectangle
+fn centered_rect(percent_x: u16, percent_y: u16, r: Rect) -> Rect {
+    let popup_layout = RatatuiLayout::default()
+        .direction(Direction::Vertical)
+        .constraints([
+            Constraint::Percentage((100 - percent_y) / 2),
+            Constraint::Percentage(percent_y),
+            Constraint::Percentage((100 - percent_y) / 2),
+        ])
+        .split(r);
+
+    RatatuiLayout::default()
+        .direction(Direction::Horizontal)
+        .constraints([
+            Constraint::Percentage((100 - percent_x) / 2),
+            Constraint::Percentage(percent_x),
+            Constraint::Percentage((100 - percent_x) / 2),
+        ])
+        .split(popup_layout[1])[1]
+}
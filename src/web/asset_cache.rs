@@ -0,0 +1,211 @@
+//! HTTP caching for keyboard geometry and keycode data.
+//!
+//! Geometry and keycode lists rarely change, so responses carry strong ETags
+//! and `Cache-Control` headers and conditional requests are answered with
+//! `304 Not Modified`.
+//!
+//! - Geometry ETags are derived from the keyboard's QMK config files (path,
+//!   size, and modification time), so they can be checked without parsing
+//!   `info.json`. Parsed geometry is cached per keyboard and variant.
+//! - Keycode ETags are derived from the binary and database versions, since the
+//!   keycode database is embedded at compile time.
+
+use std::collections::HashMap;
+use std::fmt::Write as _;
+use std::path::Path;
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::sync::{Arc, RwLock};
+use std::time::UNIX_EPOCH;
+
+use axum::{
+    http::{header, HeaderMap, HeaderValue, StatusCode},
+    response::{IntoResponse, Response},
+};
+use sha2::{Digest, Sha256};
+
+/// `Cache-Control` for geometry: always revalidate, since the QMK tree can change.
+pub const GEOMETRY_CACHE_CONTROL: &str = "no-cache";
+
+/// `Cache-Control` for keycode data, which only changes with the binary.
+pub const KEYCODES_CACHE_CONTROL: &str = "public, max-age=86400";
+
+/// Builds a strong ETag from the given parts.
+#[must_use]
+pub fn strong_etag(parts: &[&str]) -> String {
+    let mut hasher = Sha256::new();
+    hasher.update(env!("CARGO_PKG_VERSION"));
+    for part in parts {
+        hasher.update([0]);
+        hasher.update(part);
+    }
+    let digest = hasher.finalize();
+    let mut etag = String::from("\"");
+    for byte in &digest[..16] {
+        let _ = write!(etag, "{byte:02x}");
+    }
+    etag.push('"');
+    etag
+}
+
+/// Fingerprints a keyboard's QMK config files without parsing them.
+///
+/// Returns `None` if none of the files exist.
+#[must_use]
+pub fn keyboard_tree_key(qmk_path: &Path, keyboard: &str) -> Option<String> {
+    let keyboards_dir = qmk_path.join("keyboards");
+    let keyboard_dir = keyboards_dir.join(keyboard);
+
+    let mut candidates = vec![
+        keyboard_dir.join("info.json"),
+        keyboard_dir.join("keyboard.json"),
+    ];
+    if let Some((parent, _variant)) = keyboard.rsplit_once('/') {
+        candidates.push(keyboards_dir.join(parent).join("info.json"));
+    }
+
+    let mut key = String::new();
+    for path in candidates {
+        let Ok(metadata) = std::fs::metadata(&path) else {
+            continue;
+        };
+        let modified = metadata
+            .modified()
+            .ok()
+            .and_then(|t| t.duration_since(UNIX_EPOCH).ok())
+            .map_or(0, |d| d.as_nanos());
+        let _ = write!(key, "{}:{}:{modified};", path.display(), metadata.len());
+    }
+
+    (!key.is_empty()).then_some(key)
+}
+
+/// Returns true if the request's `If-None-Match` header matches `etag`.
+#[must_use]
+pub fn matches_if_none_match(headers: &HeaderMap, etag: &str) -> bool {
+    headers
+        .get_all(header::IF_NONE_MATCH)
+        .iter()
+        .filter_map(|value| value.to_str().ok())
+        .flat_map(|value| value.split(','))
+        .map(str::trim)
+        .any(|tag| tag == "*" || tag == etag)
+}
+
+/// Builds a `304 Not Modified` response.
+#[must_use]
+pub fn not_modified(etag: &str, cache_control: &'static str) -> Response {
+    let mut response = StatusCode::NOT_MODIFIED.into_response();
+    set_cache_headers(response.headers_mut(), etag, cache_control);
+    response
+}
+
+/// Wraps a response with `ETag` and `Cache-Control` headers.
+#[must_use]
+pub fn with_cache_headers(
+    response: impl IntoResponse,
+    etag: &str,
+    cache_control: &'static str,
+) -> Response {
+    let mut response = response.into_response();
+    set_cache_headers(response.headers_mut(), etag, cache_control);
+    response
+}
+
+fn set_cache_headers(headers: &mut HeaderMap, etag: &str, cache_control: &'static str) {
+    if let Ok(value) = HeaderValue::from_str(etag) {
+        headers.insert(header::ETAG, value);
+    }
+    headers.insert(
+        header::CACHE_CONTROL,
+        HeaderValue::from_static(cache_control),
+    );
+}
+
+/// Cached entry: the ETag a body was built for, and the JSON body.
+type CachedBody = (String, Arc<String>);
+
+/// Cache of serialized geometry responses keyed by keyboard and variant.
+#[derive(Debug, Default)]
+pub struct GeometryCache {
+    /// (keyboard, variant) -> (etag, JSON body)
+    entries: RwLock<HashMap<(String, String), CachedBody>>,
+    /// Number of times keyboard info was parsed to build a response
+    parses: AtomicUsize,
+}
+
+impl GeometryCache {
+    /// Returns the cached body if it was built for `etag`.
+    #[must_use]
+    pub fn get(&self, keyboard: &str, variant: &str, etag: &str) -> Option<Arc<String>> {
+        let entries = self.entries.read().ok()?;
+        entries
+            .get(&(keyboard.to_string(), variant.to_string()))
+            .filter(|(cached_etag, _)| cached_etag == etag)
+            .map(|(_, body)| Arc::clone(body))
+    }
+
+    /// Stores a body for `etag`, replacing any stale entry.
+    pub fn insert(&self, keyboard: &str, variant: &str, etag: &str, body: Arc<String>) {
+        if let Ok(mut entries) = self.entries.write() {
+            entries.insert(
+                (keyboard.to_string(), variant.to_string()),
+                (etag.to_string(), body),
+            );
+        }
+    }
+
+    /// Records that keyboard info was parsed.
+    pub fn record_parse(&self) {
+        self.parses.fetch_add(1, Ordering::Relaxed);
+    }
+
+    /// Number of times keyboard info was parsed to serve geometry.
+    #[must_use]
+    pub fn parse_count(&self) -> usize {
+        self.parses.load(Ordering::Relaxed)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_strong_etag_is_quoted_and_stable() {
+        let a = strong_etag(&["crkbd", "LAYOUT"]);
+        assert!(a.starts_with('"') && a.ends_with('"'));
+        assert_eq!(a, strong_etag(&["crkbd", "LAYOUT"]));
+        // Part boundaries matter
+        assert_ne!(strong_etag(&["ab", "c"]), strong_etag(&["a", "bc"]));
+    }
+
+    #[test]
+    fn test_matches_if_none_match() {
+        let etag = "\"abc\"";
+        let mut headers = HeaderMap::new();
+        assert!(!matches_if_none_match(&headers, etag));
+
+        headers.insert(
+            header::IF_NONE_MATCH,
+            HeaderValue::from_static("\"xyz\", \"abc\""),
+        );
+        assert!(matches_if_none_match(&headers, etag));
+
+        headers.insert(header::IF_NONE_MATCH, HeaderValue::from_static("*"));
+        assert!(matches_if_none_match(&headers, etag));
+    }
+
+    #[test]
+    fn test_keyboard_tree_key_changes_with_file() {
+        let temp = tempfile::TempDir::new().unwrap();
+        let dir = temp.path().join("keyboards").join("kb");
+        std::fs::create_dir_all(&dir).unwrap();
+        assert!(keyboard_tree_key(temp.path(), "kb").is_none());
+
+        std::fs::write(dir.join("info.json"), "{}").unwrap();
+        let before = keyboard_tree_key(temp.path(), "kb").unwrap();
+        std::fs::write(dir.join("info.json"), "{\"layouts\": {}}").unwrap();
+        let after = keyboard_tree_key(temp.path(), "kb").unwrap();
+        assert_ne!(before, after);
+    }
+}
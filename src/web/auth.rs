@@ -0,0 +1,204 @@
+//! Access token for servers reachable from the network.
+//!
+//! The server binds to loopback by default and needs no authentication. To
+//! reach it from another device (e.g., a tablet behind a reverse proxy), it
+//! must bind to a non-loopback address, which is only allowed together with
+//! an access token. API requests then have to present the token, either as
+//! `Authorization: Bearer <token>` or as a `?token=` query parameter (for
+//! `EventSource` streams and download links, which can't set headers).
+//!
+//! `GET /health` and the embedded frontend stay public so a reverse proxy can
+//! probe the server and the browser can load the page that asks for the
+//! token.
+//!
+//! Containers have to bind to all interfaces for port mapping to work; they
+//! can opt out of the token with `--allow-unauthenticated`.
+
+use std::net::SocketAddr;
+use std::sync::Arc;
+
+use axum::{
+    extract::{Query, Request, State},
+    http::{header, Method, StatusCode},
+    middleware::{self, Next},
+    response::{IntoResponse, Response},
+    Json, Router,
+};
+use serde::Deserialize;
+
+use super::ApiError;
+
+/// Environment variable read when no `--auth-token` flag is given.
+///
+/// Preferred over the flag, which other users can see in the process list.
+pub const AUTH_TOKEN_ENV: &str = "LAZYQMK_AUTH_TOKEN";
+
+/// Access control options for the server.
+#[derive(Debug, Clone, Default)]
+pub struct AuthOptions {
+    /// Token required for API requests
+    pub token: Option<String>,
+    /// Allow binding to a non-loopback address without a token
+    pub allow_unauthenticated: bool,
+}
+
+impl AuthOptions {
+    /// Options from command-line flags, taking the token from
+    /// [`AUTH_TOKEN_ENV`] when no flag is given.
+    #[must_use]
+    pub fn from_args(token: Option<String>, allow_unauthenticated: bool) -> Self {
+        Self {
+            token: token.or_else(|| std::env::var(AUTH_TOKEN_ENV).ok()),
+            allow_unauthenticated,
+        }
+    }
+
+    /// The token, unless it is blank.
+    #[must_use]
+    pub fn token(&self) -> Option<&str> {
+        self.token
+            .as_deref()
+            .filter(|token| !token.trim().is_empty())
+    }
+
+    /// Checks that `addr` may be bound with these options.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if `addr` is not a loopback address, no token is set,
+    /// and unauthenticated access wasn't explicitly allowed.
+    pub fn check_bind(&self, addr: &SocketAddr) -> anyhow::Result<()> {
+        if !addr.ip().is_loopback() && self.token().is_none() && !self.allow_unauthenticated {
+            anyhow::bail!(
+                "Binding to {} exposes the editor to the network; set an access token with \
+                 --auth-token or {AUTH_TOKEN_ENV}",
+                addr.ip()
+            );
+        }
+        Ok(())
+    }
+}
+
+/// Requires `token` on every `/api/` request handled by `router`.
+pub fn protect(router: Router, token: &str) -> Router {
+    router.layer(middleware::from_fn_with_state(
+        Arc::<str>::from(token),
+        require_token,
+    ))
+}
+
+#[derive(Deserialize)]
+struct TokenQuery {
+    token: Option<String>,
+}
+
+async fn require_token(State(token): State<Arc<str>>, request: Request, next: Next) -> Response {
+    let path = request.uri().path();
+    // CORS preflights never carry credentials
+    if !path.starts_with("/api/") || request.method() == Method::OPTIONS {
+        return next.run(request).await;
+    }
+
+    let bearer = request
+        .headers()
+        .get(header::AUTHORIZATION)
+        .and_then(|value| value.to_str().ok())
+        .and_then(|value| value.strip_prefix("Bearer "))
+        .map(str::to_string);
+    let presented = bearer.or_else(|| {
+        Query::<TokenQuery>::try_from_uri(request.uri())
+            .ok()
+            .and_then(|Query(query)| query.token)
+    });
+
+    if presented.is_some_and(|presented| tokens_match(&presented, &token)) {
+        next.run(request).await
+    } else {
+        (
+            StatusCode::UNAUTHORIZED,
+            Json(ApiError::new("Missing or invalid access token")),
+        )
+            .into_response()
+    }
+}
+
+/// Compares tokens without stopping at the first differing byte.
+fn tokens_match(presented: &str, expected: &str) -> bool {
+    presented.len() == expected.len()
+        && presented
+            .bytes()
+            .zip(expected.bytes())
+            .fold(0, |diff, (a, b)| diff | (a ^ b))
+            == 0
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use axum::{body::Body, routing::get};
+    use tower::ServiceExt;
+
+    fn app() -> Router {
+        let router = Router::new()
+            .route("/health", get(|| async { "ok" }))
+            .route("/api/layouts", get(|| async { "layouts" }));
+        protect(router, "s3cret")
+    }
+
+    async fn status(uri: &str, auth: Option<&str>) -> StatusCode {
+        let mut request = Request::builder().uri(uri);
+        if let Some(auth) = auth {
+            request = request.header(header::AUTHORIZATION, auth);
+        }
+        app()
+            .oneshot(request.body(Body::empty()).unwrap())
+            .await
+            .unwrap()
+            .status()
+    }
+
+    fn options(token: Option<&str>, allow_unauthenticated: bool) -> AuthOptions {
+        AuthOptions {
+            token: token.map(str::to_string),
+            allow_unauthenticated,
+        }
+    }
+
+    #[test]
+    fn test_check_bind_requires_token_off_loopback() {
+        let loopback: SocketAddr = "127.0.0.1:3001".parse().unwrap();
+        let lan: SocketAddr = "0.0.0.0:3001".parse().unwrap();
+
+        assert!(options(None, false).check_bind(&loopback).is_ok());
+        assert!(options(None, false)
+            .check_bind(&"[::1]:3001".parse().unwrap())
+            .is_ok());
+        assert!(options(Some("s3cret"), false).check_bind(&lan).is_ok());
+        assert!(options(None, true).check_bind(&lan).is_ok());
+
+        let err = options(None, false)
+            .check_bind(&lan)
+            .unwrap_err()
+            .to_string();
+        assert!(err.contains("--auth-token"));
+        assert!(options(Some("  "), false).check_bind(&lan).is_err());
+    }
+
+    #[tokio::test]
+    async fn test_api_requires_token() {
+        assert_eq!(status("/health", None).await, StatusCode::OK);
+        assert_eq!(status("/api/layouts", None).await, StatusCode::UNAUTHORIZED);
+        assert_eq!(
+            status("/api/layouts", Some("Bearer wrong")).await,
+            StatusCode::UNAUTHORIZED
+        );
+        assert_eq!(
+            status("/api/layouts", Some("Bearer s3cret")).await,
+            StatusCode::OK
+        );
+        assert_eq!(
+            status("/api/layouts?token=s3cret", None).await,
+            StatusCode::OK
+        );
+    }
+}
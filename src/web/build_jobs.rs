@@ -47,18 +47,21 @@ use std::io::{BufRead, BufReader, Read, Write};
 use std::path::{Path, PathBuf};
 use std::sync::{mpsc, Arc, Mutex, RwLock};
 use std::thread;
-use std::time::Duration;
+use std::time::{Duration, Instant};
 
 use serde::{Deserialize, Serialize};
 use sha2::{Digest, Sha256};
 use uuid::Uuid;
 
+use crate::config::BuildBackend;
+use crate::firmware::completion::{BuildCompletion, CompletionHooks};
+use crate::firmware::size::{parse_size_output, FirmwareSize, McuSpec};
+use crate::firmware::{converter, Backend};
+use crate::parser::keyboard_json::parse_keyboard_info_json;
+
 /// Maximum number of concurrent builds.
 const MAX_CONCURRENT_BUILDS: usize = 1;
 
-/// Supported firmware artifact extensions.
-const ARTIFACT_EXTENSIONS: &[&str] = &["uf2", "bin", "hex"];
-
 /// A firmware artifact produced by a build job.
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct BuildArtifact {
@@ -118,6 +121,9 @@ pub struct BuildJob {
     pub keyboard: String,
     /// Keymap name.
     pub keymap: String,
+    /// Controller converter passed as `CONVERT_TO` (if any).
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub converter: Option<String>,
     /// Time when job was created.
     pub created_at: String,
     /// Time when job started running (if started).
@@ -138,17 +144,26 @@ pub struct BuildJob {
     /// List of firmware artifacts produced by this build.
     #[serde(default, skip_serializing_if = "Vec::is_empty")]
     pub artifacts: Vec<BuildArtifact>,
+    /// Flash/RAM usage reported by the build (if successful).
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub firmware_size: Option<FirmwareSize>,
 }
 
 impl BuildJob {
     /// Creates a new pending build job.
-    fn new(layout_filename: String, keyboard: String, keymap: String) -> Self {
+    fn new(
+        layout_filename: String,
+        keyboard: String,
+        keymap: String,
+        converter: Option<String>,
+    ) -> Self {
         Self {
             id: Uuid::new_v4().to_string(),
             status: JobStatus::Pending,
             layout_filename,
             keyboard,
             keymap,
+            converter,
             created_at: chrono::Utc::now().to_rfc3339(),
             started_at: None,
             completed_at: None,
@@ -156,6 +171,7 @@ impl BuildJob {
             firmware_path: None,
             progress: 0,
             artifacts: Vec::new(),
+            firmware_size: None,
         }
     }
 }
@@ -176,6 +192,9 @@ pub struct LogEntry {
 pub struct StartBuildRequest {
     /// Layout filename to build.
     pub layout_filename: String,
+    /// Build even if the layout's keyboard differs from `build.keyboard`.
+    #[serde(default)]
+    pub allow_keyboard_mismatch: bool,
 }
 
 /// Response for starting a build job.
@@ -215,10 +234,10 @@ pub struct CancelJobResponse {
 /// Build command to be executed by worker thread.
 struct BuildCommand {
     job_id: String,
-    #[allow(dead_code)] // Stored for potential future logging/display
     layout_filename: String,
     keyboard: String,
     keymap: String,
+    converter: Option<String>,
     qmk_path: PathBuf,
     log_path: PathBuf,
     /// Job-specific output directory for artifacts.
@@ -232,6 +251,8 @@ pub struct BuildResult {
     pub firmware_path: PathBuf,
     /// All discovered artifacts with their metadata.
     pub artifacts: Vec<BuildArtifact>,
+    /// Flash/RAM usage parsed from the build output, if reported.
+    pub size: Option<FirmwareSize>,
 }
 
 /// Trait for firmware builders, allowing mock injection for tests.
@@ -242,6 +263,7 @@ pub trait FirmwareBuilder: Send + Sync {
     /// * `qmk_path` - Path to QMK firmware directory
     /// * `keyboard` - Keyboard identifier
     /// * `keymap` - Keymap name
+    /// * `converter` - Controller converter passed as `CONVERT_TO` (if any)
     /// * `output_dir` - Directory to copy artifacts into
     /// * `job_id` - Job identifier (for generating download URLs)
     /// * `log_writer` - Writer for build log output
@@ -253,6 +275,7 @@ pub trait FirmwareBuilder: Send + Sync {
         qmk_path: &PathBuf,
         keyboard: &str,
         keymap: &str,
+        converter: Option<&str>,
         output_dir: &Path,
         job_id: &str,
         log_writer: &mut dyn Write,
@@ -260,8 +283,11 @@ pub trait FirmwareBuilder: Send + Sync {
     ) -> Result<BuildResult, String>;
 }
 
-/// Real firmware builder using QMK CLI.
-pub struct RealFirmwareBuilder;
+/// Real firmware builder running `qmk compile` or `make`.
+pub struct RealFirmwareBuilder {
+    /// Backend builds run with (`auto` picks one per build)
+    pub backend: BuildBackend,
+}
 
 impl FirmwareBuilder for RealFirmwareBuilder {
     fn build(
@@ -269,20 +295,24 @@ impl FirmwareBuilder for RealFirmwareBuilder {
         qmk_path: &PathBuf,
         keyboard: &str,
         keymap: &str,
+        converter: Option<&str>,
         output_dir: &Path,
         job_id: &str,
         log_writer: &mut dyn Write,
         is_cancelled: &dyn Fn() -> bool,
     ) -> Result<BuildResult, String> {
         use std::io::{BufRead, BufReader};
-        use std::process::{Command, Stdio};
 
+        let backend = Backend::resolve(self.backend, qmk_path);
         let _ = writeln!(log_writer, "[INFO] Starting QMK compile...");
         let _ = writeln!(
             log_writer,
-            "[INFO] Running: qmk compile -kb {} -km {}",
-            keyboard, keymap
+            "[INFO] Running ({backend}): {}",
+            backend.command_line(keyboard, keymap, converter)
         );
+        if let Some(warning) = converter.and_then(converter::converter_warning) {
+            let _ = writeln!(log_writer, "[WARN] {warning}");
+        }
 
         // Check for cancellation before starting
         if is_cancelled() {
@@ -290,20 +320,11 @@ impl FirmwareBuilder for RealFirmwareBuilder {
             return Err("Build cancelled".to_string());
         }
 
-        let mut cmd = Command::new("qmk");
-        cmd.arg("compile")
-            .arg("-kb")
-            .arg(keyboard)
-            .arg("-km")
-            .arg(keymap)
-            .current_dir(qmk_path)
-            .stdout(Stdio::piped())
-            .stderr(Stdio::piped());
-
         // Spawn the process instead of waiting for output
-        let mut child = cmd
+        let mut child = backend
+            .command(qmk_path, keyboard, keymap, converter)
             .spawn()
-            .map_err(|e| format!("Failed to execute qmk: {e}"))?;
+            .map_err(|e| format!("Failed to execute {}: {e}", backend.program()))?;
 
         // Get handles for stdout/stderr
         let stdout = child
@@ -315,7 +336,8 @@ impl FirmwareBuilder for RealFirmwareBuilder {
             .take()
             .ok_or_else(|| "Failed to capture stderr".to_string())?;
 
-        // Stream stdout in background
+        // Stream stdout in background, keeping it for the size report
+        let mut output = String::new();
         let stdout_reader = BufReader::new(stdout);
         for line in stdout_reader.lines() {
             // Check for cancellation periodically
@@ -333,6 +355,8 @@ impl FirmwareBuilder for RealFirmwareBuilder {
                     "INFO"
                 };
                 let _ = writeln!(log_writer, "[{level}] {line}");
+                output.push_str(&line);
+                output.push('\n');
             }
         }
 
@@ -365,13 +389,15 @@ impl FirmwareBuilder for RealFirmwareBuilder {
         }
 
         if !status.success() {
-            return Err("QMK compile failed. Check build log for details.".to_string());
+            return Err(format!(
+                "Build with {backend} failed. Check build log for details."
+            ));
         }
 
         // Discover and copy artifacts
         let _ = writeln!(log_writer, "[INFO] Discovering firmware artifacts...");
         let artifacts = discover_and_copy_artifacts(
-            qmk_path, keyboard, keymap, output_dir, job_id, log_writer,
+            qmk_path, keyboard, keymap, converter, output_dir, job_id, log_writer,
         )?;
 
         if artifacts.is_empty() {
@@ -383,9 +409,21 @@ impl FirmwareBuilder for RealFirmwareBuilder {
         // Use first artifact as primary firmware path (for backward compatibility)
         let primary_path = output_dir.join(&artifacts[0].filename);
 
+        // A converter swaps the controller, so the board's processor no longer applies
+        let mcu = parse_keyboard_info_json(qmk_path, keyboard)
+            .ok()
+            .filter(|_| converter.is_none())
+            .and_then(|info| info.processor)
+            .and_then(|processor| McuSpec::lookup(&processor));
+        let size = parse_size_output(&output, mcu);
+        if let Some(size) = &size {
+            let _ = writeln!(log_writer, "[INFO] Firmware size: {}", size.summary());
+        }
+
         Ok(BuildResult {
             firmware_path: primary_path,
             artifacts,
+            size,
         })
     }
 }
@@ -393,13 +431,15 @@ impl FirmwareBuilder for RealFirmwareBuilder {
 /// Discovers firmware artifacts in QMK's `.build` directory and copies them to the output directory.
 ///
 /// Looks for files matching the pattern `<keyboard_clean>_<keymap>.<ext>` where keyboard slashes
-/// are replaced with underscores. Supports multiple file extensions (uf2, bin, hex) and handles
-/// variant suffixes via glob matching.
+/// are replaced with underscores (`<keyboard_clean>_<keymap>_<converter>.<ext>` when building
+/// for a converter). Supports multiple file extensions (uf2, bin, hex), the converter's own
+/// format first, and handles variant suffixes via glob matching.
 ///
 /// # Arguments
 /// * `qmk_path` - Path to QMK firmware directory
 /// * `keyboard` - Keyboard identifier (may contain slashes)
 /// * `keymap` - Keymap name
+/// * `converter` - Controller converter passed as `CONVERT_TO` (if any)
 /// * `output_dir` - Directory to copy artifacts into
 /// * `job_id` - Job identifier for generating download URLs
 /// * `log_writer` - Writer for log output
@@ -410,6 +450,7 @@ fn discover_and_copy_artifacts(
     qmk_path: &Path,
     keyboard: &str,
     keymap: &str,
+    converter: Option<&str>,
     output_dir: &Path,
     job_id: &str,
     log_writer: &mut dyn Write,
@@ -423,13 +464,13 @@ fn discover_and_copy_artifacts(
     fs::create_dir_all(output_dir)
         .map_err(|e| format!("Failed to create output directory: {e}"))?;
 
-    let keyboard_clean = keyboard.replace('/', "_");
-    let base_prefix = format!("{keyboard_clean}_{keymap}");
+    let base_prefix = converter::firmware_basename(keyboard, keymap, converter);
+    let extensions = converter::firmware_extensions(converter);
 
     let mut artifacts = Vec::new();
 
     // First pass: look for exact matches
-    for ext in ARTIFACT_EXTENSIONS {
+    for ext in &extensions {
         let exact_filename = format!("{base_prefix}.{ext}");
         let source_path = build_dir.join(&exact_filename);
 
@@ -457,7 +498,7 @@ fn discover_and_copy_artifacts(
                 continue;
             }
 
-            for ext in ARTIFACT_EXTENSIONS {
+            for ext in &extensions {
                 if filename.ends_with(&format!(".{ext}")) {
                     // Skip if we already have an artifact with this extension
                     if artifacts.iter().any(|a| a.artifact_type == *ext) {
@@ -565,6 +606,7 @@ impl FirmwareBuilder for MockFirmwareBuilder {
         _qmk_path: &PathBuf,
         keyboard: &str,
         keymap: &str,
+        converter: Option<&str>,
         output_dir: &Path,
         job_id: &str,
         log_writer: &mut dyn Write,
@@ -593,8 +635,8 @@ impl FirmwareBuilder for MockFirmwareBuilder {
         }
 
         if self.should_succeed {
-            let keyboard_clean = keyboard.replace('/', "_");
-            let filename = format!("{keyboard_clean}_{keymap}.uf2");
+            let basename = converter::firmware_basename(keyboard, keymap, converter);
+            let filename = format!("{basename}.uf2");
 
             // Create the output directory and mock firmware file
             let _ = fs::create_dir_all(output_dir);
@@ -623,6 +665,7 @@ impl FirmwareBuilder for MockFirmwareBuilder {
             Ok(BuildResult {
                 firmware_path,
                 artifacts,
+                size: None,
             })
         } else {
             let err = self
@@ -657,16 +700,23 @@ pub struct BuildJobManager {
     max_artifacts_age_hours: u64,
     /// Maximum total number of artifacts to keep (default: 50).
     max_total_artifacts: usize,
+    /// Subscribers told when a build completes or fails.
+    hooks: CompletionHooks,
 }
 
 impl BuildJobManager {
     /// Creates a new build job manager.
-    pub fn new(logs_dir: PathBuf, output_dir: PathBuf, qmk_path: Option<PathBuf>) -> Arc<Self> {
+    pub fn new(
+        logs_dir: PathBuf,
+        output_dir: PathBuf,
+        qmk_path: Option<PathBuf>,
+        backend: BuildBackend,
+    ) -> Arc<Self> {
         Self::with_builder(
             logs_dir,
             output_dir,
             qmk_path,
-            Arc::new(RealFirmwareBuilder),
+            Arc::new(RealFirmwareBuilder { backend }),
         )
     }
 
@@ -692,6 +742,7 @@ impl BuildJobManager {
             builder,
             max_artifacts_age_hours: 168, // 7 days
             max_total_artifacts: 50,
+            hooks: CompletionHooks::new(),
         });
 
         // Start worker thread
@@ -700,6 +751,13 @@ impl BuildJobManager {
         manager
     }
 
+    /// Registers a callback run when a build completes or fails.
+    ///
+    /// Cancelled builds are not reported.
+    pub fn on_complete(&self, hook: impl Fn(&BuildCompletion) + Send + Sync + 'static) {
+        self.hooks.subscribe(hook);
+    }
+
     /// Starts the background worker thread.
     fn start_worker(self: &Arc<Self>) {
         let (tx, rx) = mpsc::channel::<BuildCommand>();
@@ -731,6 +789,7 @@ impl BuildJobManager {
                 job.progress = 10;
             }
         }
+        let started = Instant::now();
 
         // Open log file
         let log_file = OpenOptions::new()
@@ -757,6 +816,7 @@ impl BuildJobManager {
                         &cmd.qmk_path,
                         &cmd.keyboard,
                         &cmd.keymap,
+                        cmd.converter.as_deref(),
                         &cmd.output_dir,
                         &cmd.job_id,
                         &mut file,
@@ -779,6 +839,16 @@ impl BuildJobManager {
             return;
         }
 
+        let completion = |success: bool, error: Option<String>| {
+            BuildCompletion::new(
+                success,
+                format!("{}:{}", cmd.keyboard, cmd.keymap),
+                started.elapsed(),
+                error,
+            )
+            .with_layout(cmd.layout_filename.clone())
+        };
+
         // Update job with result
         match result {
             Ok(build_result) => {
@@ -789,15 +859,20 @@ impl BuildJobManager {
                     Some(build_result.firmware_path.display().to_string()),
                     build_result.artifacts,
                 );
+                if let Some(job) = self.jobs.write().unwrap().get_mut(&cmd.job_id) {
+                    job.firmware_size = build_result.size;
+                }
+                self.hooks.notify(&completion(true, None));
             }
             Err(error) => {
                 self.update_job_status(
                     &cmd.job_id,
                     JobStatus::Failed,
-                    Some(error),
+                    Some(error.clone()),
                     None,
                     Vec::new(),
                 );
+                self.hooks.notify(&completion(false, Some(error)));
             }
         }
     }
@@ -921,12 +996,15 @@ impl BuildJobManager {
 
     /// Starts a new build job.
     ///
-    /// Returns the created job or an error if the build cannot be started.
+    /// `converter` is passed to `qmk compile` as `CONVERT_TO` and recorded
+    /// on the job. Returns the created job or an error if the build cannot
+    /// be started.
     pub fn start_build(
         self: &Arc<Self>,
         layout_filename: String,
         keyboard: String,
         keymap: String,
+        converter: Option<String>,
     ) -> Result<BuildJob, String> {
         // Trigger artifact cleanup in background (async to avoid blocking)
         let manager = Arc::clone(self);
@@ -951,7 +1029,12 @@ impl BuildJobManager {
         }
 
         // Create job
-        let job = BuildJob::new(layout_filename.clone(), keyboard.clone(), keymap.clone());
+        let job = BuildJob::new(
+            layout_filename.clone(),
+            keyboard.clone(),
+            keymap.clone(),
+            converter.clone(),
+        );
         let job_id = job.id.clone();
 
         // Store job
@@ -978,6 +1061,7 @@ impl BuildJobManager {
             layout_filename,
             keyboard,
             keymap,
+            converter,
             qmk_path,
             log_path,
             output_dir,
@@ -1210,6 +1294,7 @@ mod tests {
             "test.md".to_string(),
             "crkbd".to_string(),
             "default".to_string(),
+            None,
         );
 
         assert!(!job.id.is_empty());
@@ -1232,6 +1317,7 @@ mod tests {
             "test.md".to_string(),
             "crkbd".to_string(),
             "default".to_string(),
+            None,
         );
 
         assert!(result.is_ok());
@@ -1262,6 +1348,7 @@ mod tests {
             "test.md".to_string(),
             "crkbd".to_string(),
             "default".to_string(),
+            None,
         );
 
         assert!(result.is_err());
@@ -1290,6 +1377,7 @@ mod tests {
                 "test.md".to_string(),
                 "crkbd".to_string(),
                 "default".to_string(),
+                None,
             )
             .unwrap();
 
@@ -1313,9 +1401,15 @@ mod tests {
             "a.md".to_string(),
             "crkbd".to_string(),
             "default".to_string(),
+            None,
         );
         thread::sleep(Duration::from_millis(10));
-        let _ = manager.start_build("b.md".to_string(), "crkbd".to_string(), "test".to_string());
+        let _ = manager.start_build(
+            "b.md".to_string(),
+            "crkbd".to_string(),
+            "test".to_string(),
+            None,
+        );
 
         let jobs = manager.list_jobs();
         // First job may still be running, second will be pending
@@ -1357,6 +1451,7 @@ mod tests {
                 "test.md".to_string(),
                 "crkbd".to_string(),
                 "default".to_string(),
+                None,
             )
             .unwrap();
 
@@ -1397,6 +1492,7 @@ mod tests {
                 "test.md".to_string(),
                 "crkbd".to_string(),
                 "default".to_string(),
+                None,
             )
             .unwrap();
 
@@ -1429,6 +1525,7 @@ mod tests {
                 "test.md".to_string(),
                 "crkbd".to_string(),
                 "default".to_string(),
+                None,
             )
             .unwrap();
         thread::sleep(Duration::from_millis(200));
@@ -1459,6 +1556,7 @@ mod tests {
                 "test.md".to_string(),
                 "crkbd".to_string(),
                 "default".to_string(),
+                None,
             )
             .unwrap();
 
@@ -1486,6 +1584,7 @@ mod tests {
                 "test1.md".to_string(),
                 "crkbd".to_string(),
                 "default".to_string(),
+                None,
             )
             .unwrap();
         thread::sleep(Duration::from_millis(200));
@@ -1525,6 +1624,7 @@ mod tests {
                 "running.md".to_string(),
                 "crkbd".to_string(),
                 "default".to_string(),
+                None,
             )
             .unwrap();
 
@@ -0,0 +1,480 @@
+//! Tap dance and category endpoints for a single layout.
+//!
+//! These let the frontend edit one tap dance or category without re-uploading
+//! the whole layout. Every response carries the layout's content hash as a
+//! strong `ETag`. Mutating requests may send it back in `If-Match`; if the file
+//! changed since, the request fails with `412 Precondition Failed` and the UI
+//! can offer to reload. Mutations return the updated collection.
+
+use std::path::PathBuf;
+
+use axum::{
+    extract::{Path, State},
+    http::{header, HeaderMap, HeaderValue, StatusCode},
+    response::{IntoResponse, Response},
+    Json,
+};
+use serde::Serialize;
+
+use super::{validate_filename, ApiError, AppState, TapDanceDto};
+use crate::models::{Category, Layout, TapDanceAction};
+use crate::services::LayoutService;
+
+/// Error response shared by the handlers in this module.
+type ApiFailure = (StatusCode, Json<ApiError>);
+
+/// Strong ETag for a layout's content.
+#[must_use]
+pub fn layout_etag(layout: &Layout) -> String {
+    format!("\"{}\"", layout.content_hash())
+}
+
+/// Returns true unless the request has an `If-Match` header that does not
+/// match `etag`.
+#[must_use]
+pub fn if_match_satisfied(headers: &HeaderMap, etag: &str) -> bool {
+    let mut values = headers
+        .get_all(header::IF_MATCH)
+        .iter()
+        .filter_map(|value| value.to_str().ok())
+        .peekable();
+    if values.peek().is_none() {
+        return true;
+    }
+    values
+        .flat_map(|value| value.split(','))
+        .map(str::trim)
+        .any(|tag| tag == "*" || tag == etag)
+}
+
+/// JSON response with the layout's ETag.
+fn with_etag(status: StatusCode, body: impl Serialize, etag: &str) -> Response {
+    let mut response = (status, Json(body)).into_response();
+    if let Ok(value) = HeaderValue::from_str(etag) {
+        response.headers_mut().insert(header::ETAG, value);
+    }
+    response
+}
+
+fn failure(status: StatusCode, error: impl Into<String>) -> ApiFailure {
+    (status, Json(ApiError::new(error)))
+}
+
+/// Resolves a layout filename in the workspace, adding `.md` if missing.
+fn layout_path(state: &AppState, filename: &str) -> Result<PathBuf, ApiFailure> {
+    let filename = validate_filename(filename).map_err(|e| (StatusCode::BAD_REQUEST, Json(e)))?;
+    let filename = if std::path::Path::new(filename)
+        .extension()
+        .is_some_and(|ext| ext.eq_ignore_ascii_case("md"))
+    {
+        filename.to_string()
+    } else {
+        format!("{filename}.md")
+    };
+
+    let path = state.workspace_root.join(&filename);
+    if !path.exists() {
+        return Err(failure(
+            StatusCode::NOT_FOUND,
+            format!("Layout file not found: {filename}"),
+        ));
+    }
+    Ok(path)
+}
+
+/// Loads the layout at `path`.
+fn load_layout(path: &std::path::Path) -> Result<Layout, ApiFailure> {
+    LayoutService::load(path).map_err(|e| {
+        (
+            StatusCode::INTERNAL_SERVER_ERROR,
+            Json(ApiError::with_details(
+                "Failed to load layout",
+                e.to_string(),
+            )),
+        )
+    })
+}
+
+/// Loads a layout, applies `edit`, validates and saves it.
+///
+/// Edits are serialized so two requests can't both pass the `If-Match` check
+/// and then overwrite each other. Validation failures after the edit are
+/// reported with `invalid_status`.
+async fn edit_layout(
+    state: &AppState,
+    filename: &str,
+    headers: &HeaderMap,
+    invalid_status: StatusCode,
+    edit: impl FnOnce(&mut Layout) -> Result<(), ApiFailure>,
+) -> Result<(Layout, String), ApiFailure> {
+    let path = layout_path(state, filename)?;
+    let _guard = state.layout_edit_lock.lock().await;
+
+    let mut layout = load_layout(&path)?;
+    let etag = layout_etag(&layout);
+    if !if_match_satisfied(headers, &etag) {
+        return Err((
+            StatusCode::PRECONDITION_FAILED,
+            Json(ApiError::with_details(
+                "Layout was modified by someone else",
+                format!("Current ETag is {etag}; reload the layout and retry"),
+            )),
+        ));
+    }
+
+    edit(&mut layout)?;
+    layout.validate().map_err(|e| {
+        (
+            invalid_status,
+            Json(ApiError::with_details("Invalid layout", e.to_string())),
+        )
+    })?;
+
+    LayoutService::save(&layout, &path).map_err(|e| {
+        (
+            StatusCode::INTERNAL_SERVER_ERROR,
+            Json(ApiError::with_details(
+                "Failed to save layout",
+                e.to_string(),
+            )),
+        )
+    })?;
+
+    let etag = layout_etag(&layout);
+    Ok((layout, etag))
+}
+
+// ============================================================================
+// Tap Dances
+// ============================================================================
+
+/// Converts a request body into a validated tap dance.
+fn tap_dance_from_dto(dto: TapDanceDto) -> Result<TapDanceAction, ApiFailure> {
+    let tap_dance = TapDanceAction {
+        name: dto.name,
+        single_tap: dto.single_tap,
+        double_tap: dto.double_tap,
+        hold: dto.hold,
+        tapping_term_ms: dto.tapping_term_ms,
+        on_each_tap: dto.on_each_tap,
+    };
+    tap_dance.validate().map_err(|e| {
+        (
+            StatusCode::BAD_REQUEST,
+            Json(ApiError::with_details("Invalid tap dance", e.to_string())),
+        )
+    })?;
+    Ok(tap_dance)
+}
+
+fn tap_dance_list(layout: &Layout) -> Vec<TapDanceDto> {
+    layout.tap_dances.iter().map(TapDanceDto::from).collect()
+}
+
+fn tap_dance_not_found(name: &str) -> ApiFailure {
+    failure(
+        StatusCode::NOT_FOUND,
+        format!("Tap dance not found: {name}"),
+    )
+}
+
+/// GET /api/layouts/{filename}/tap-dances - List a layout's tap dances.
+pub async fn list_tap_dances(
+    State(state): State<AppState>,
+    Path(filename): Path<String>,
+) -> Result<Response, ApiFailure> {
+    let layout = load_layout(&layout_path(&state, &filename)?)?;
+    Ok(with_etag(
+        StatusCode::OK,
+        tap_dance_list(&layout),
+        &layout_etag(&layout),
+    ))
+}
+
+/// GET /api/layouts/{filename}/tap-dances/{name} - Get one tap dance.
+pub async fn get_tap_dance(
+    State(state): State<AppState>,
+    Path((filename, name)): Path<(String, String)>,
+) -> Result<Response, ApiFailure> {
+    let layout = load_layout(&layout_path(&state, &filename)?)?;
+    let tap_dance = layout
+        .get_tap_dance(&name)
+        .ok_or_else(|| tap_dance_not_found(&name))?;
+    Ok(with_etag(
+        StatusCode::OK,
+        TapDanceDto::from(tap_dance),
+        &layout_etag(&layout),
+    ))
+}
+
+/// POST /api/layouts/{filename}/tap-dances - Add a tap dance.
+pub async fn create_tap_dance(
+    State(state): State<AppState>,
+    Path(filename): Path<String>,
+    headers: HeaderMap,
+    Json(dto): Json<TapDanceDto>,
+) -> Result<Response, ApiFailure> {
+    let tap_dance = tap_dance_from_dto(dto)?;
+    let (layout, etag) = edit_layout(
+        &state,
+        &filename,
+        &headers,
+        StatusCode::BAD_REQUEST,
+        |layout| {
+            if layout.get_tap_dance(&tap_dance.name).is_some() {
+                return Err(failure(
+                    StatusCode::CONFLICT,
+                    format!("Tap dance '{}' already exists", tap_dance.name),
+                ));
+            }
+            layout.add_tap_dance(tap_dance).map_err(|e| {
+                (
+                    StatusCode::BAD_REQUEST,
+                    Json(ApiError::with_details("Invalid tap dance", e.to_string())),
+                )
+            })
+        },
+    )
+    .await?;
+    Ok(with_etag(
+        StatusCode::CREATED,
+        tap_dance_list(&layout),
+        &etag,
+    ))
+}
+
+/// PUT /api/layouts/{filename}/tap-dances/{name} - Replace a tap dance.
+///
+/// A different name in the body renames the tap dance and its `TD()` keys.
+pub async fn update_tap_dance(
+    State(state): State<AppState>,
+    Path((filename, name)): Path<(String, String)>,
+    headers: HeaderMap,
+    Json(dto): Json<TapDanceDto>,
+) -> Result<Response, ApiFailure> {
+    let tap_dance = tap_dance_from_dto(dto)?;
+    let (layout, etag) = edit_layout(
+        &state,
+        &filename,
+        &headers,
+        StatusCode::BAD_REQUEST,
+        |layout| {
+            if tap_dance.name != name && layout.get_tap_dance(&tap_dance.name).is_some() {
+                return Err(failure(
+                    StatusCode::CONFLICT,
+                    format!("Tap dance '{}' already exists", tap_dance.name),
+                ));
+            }
+            let slot = layout
+                .get_tap_dance_mut(&name)
+                .ok_or_else(|| tap_dance_not_found(&name))?;
+            let new_name = tap_dance.name.clone();
+            *slot = tap_dance;
+
+            if new_name != name {
+                let old_keycode = format!("TD({name})");
+                let new_keycode = format!("TD({new_name})");
+                for key in layout.layers.iter_mut().flat_map(|l| l.keys.iter_mut()) {
+                    if key.keycode == old_keycode {
+                        key.keycode.clone_from(&new_keycode);
+                    }
+                }
+            }
+            Ok(())
+        },
+    )
+    .await?;
+    Ok(with_etag(StatusCode::OK, tap_dance_list(&layout), &etag))
+}
+
+/// DELETE /api/layouts/{filename}/tap-dances/{name} - Remove a tap dance.
+///
+/// Fails with `409 Conflict` while a key still uses it.
+pub async fn delete_tap_dance(
+    State(state): State<AppState>,
+    Path((filename, name)): Path<(String, String)>,
+    headers: HeaderMap,
+) -> Result<Response, ApiFailure> {
+    let (layout, etag) = edit_layout(
+        &state,
+        &filename,
+        &headers,
+        StatusCode::CONFLICT,
+        |layout| {
+            layout
+                .remove_tap_dance(&name)
+                .map(|_| ())
+                .ok_or_else(|| tap_dance_not_found(&name))
+        },
+    )
+    .await?;
+    Ok(with_etag(StatusCode::OK, tap_dance_list(&layout), &etag))
+}
+
+// ============================================================================
+// Categories
+// ============================================================================
+
+/// Re-validates a category from a request body.
+fn validated_category(category: Category) -> Result<Category, ApiFailure> {
+    Category::new(category.id, category.name, category.color).map_err(|e| {
+        (
+            StatusCode::BAD_REQUEST,
+            Json(ApiError::with_details("Invalid category", e.to_string())),
+        )
+    })
+}
+
+fn category_not_found(id: &str) -> ApiFailure {
+    failure(StatusCode::NOT_FOUND, format!("Category not found: {id}"))
+}
+
+/// GET /api/layouts/{filename}/categories - List a layout's categories.
+pub async fn list_layout_categories(
+    State(state): State<AppState>,
+    Path(filename): Path<String>,
+) -> Result<Response, ApiFailure> {
+    let layout = load_layout(&layout_path(&state, &filename)?)?;
+    Ok(with_etag(
+        StatusCode::OK,
+        &layout.categories,
+        &layout_etag(&layout),
+    ))
+}
+
+/// GET /api/layouts/{filename}/categories/{id} - Get one category.
+pub async fn get_layout_category(
+    State(state): State<AppState>,
+    Path((filename, id)): Path<(String, String)>,
+) -> Result<Response, ApiFailure> {
+    let layout = load_layout(&layout_path(&state, &filename)?)?;
+    let category = layout
+        .get_category(&id)
+        .ok_or_else(|| category_not_found(&id))?;
+    Ok(with_etag(StatusCode::OK, category, &layout_etag(&layout)))
+}
+
+/// POST /api/layouts/{filename}/categories - Add a category.
+pub async fn create_layout_category(
+    State(state): State<AppState>,
+    Path(filename): Path<String>,
+    headers: HeaderMap,
+    Json(category): Json<Category>,
+) -> Result<Response, ApiFailure> {
+    let category = validated_category(category)?;
+    let (layout, etag) = edit_layout(
+        &state,
+        &filename,
+        &headers,
+        StatusCode::BAD_REQUEST,
+        |layout| {
+            layout
+                .add_category(category)
+                .map_err(|e| failure(StatusCode::CONFLICT, e.to_string()))
+        },
+    )
+    .await?;
+    Ok(with_etag(StatusCode::CREATED, &layout.categories, &etag))
+}
+
+/// PUT /api/layouts/{filename}/categories/{id} - Replace a category.
+///
+/// A different ID in the body renames the category and every reference to it.
+pub async fn update_layout_category(
+    State(state): State<AppState>,
+    Path((filename, id)): Path<(String, String)>,
+    headers: HeaderMap,
+    Json(category): Json<Category>,
+) -> Result<Response, ApiFailure> {
+    let category = validated_category(category)?;
+    let (layout, etag) = edit_layout(
+        &state,
+        &filename,
+        &headers,
+        StatusCode::BAD_REQUEST,
+        |layout| {
+            if category.id != id && layout.get_category(&category.id).is_some() {
+                return Err(failure(
+                    StatusCode::CONFLICT,
+                    format!("Category with ID '{}' already exists", category.id),
+                ));
+            }
+            let slot = layout
+                .get_category_mut(&id)
+                .ok_or_else(|| category_not_found(&id))?;
+            let new_id = category.id.clone();
+            *slot = category;
+
+            if new_id != id {
+                for layer in &mut layout.layers {
+                    if layer.category_id.as_deref() == Some(id.as_str()) {
+                        layer.category_id = Some(new_id.clone());
+                    }
+                    for key in &mut layer.keys {
+                        if key.category_id.as_deref() == Some(id.as_str()) {
+                            key.category_id = Some(new_id.clone());
+                        }
+                    }
+                }
+            }
+            Ok(())
+        },
+    )
+    .await?;
+    Ok(with_etag(StatusCode::OK, &layout.categories, &etag))
+}
+
+/// DELETE /api/layouts/{filename}/categories/{id} - Remove a category.
+///
+/// Fails with `409 Conflict` while a layer or key still uses it.
+pub async fn delete_layout_category(
+    State(state): State<AppState>,
+    Path((filename, id)): Path<(String, String)>,
+    headers: HeaderMap,
+) -> Result<Response, ApiFailure> {
+    let (layout, etag) = edit_layout(
+        &state,
+        &filename,
+        &headers,
+        StatusCode::CONFLICT,
+        |layout| {
+            layout
+                .remove_category(&id)
+                .ok_or_else(|| category_not_found(&id))?;
+            // Loading tolerates missing categories, so reject this explicitly
+            if layout.missing_category_ids().contains(&id) {
+                return Err(failure(
+                    StatusCode::CONFLICT,
+                    format!("Category '{id}' is still assigned to layers or keys"),
+                ));
+            }
+            Ok(())
+        },
+    )
+    .await?;
+    Ok(with_etag(StatusCode::OK, &layout.categories, &etag))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_if_match() {
+        let etag = "\"0123456789abcdef\"";
+        let mut headers = HeaderMap::new();
+        assert!(if_match_satisfied(&headers, etag));
+
+        headers.insert(header::IF_MATCH, HeaderValue::from_static("\"stale\""));
+        assert!(!if_match_satisfied(&headers, etag));
+
+        headers.insert(
+            header::IF_MATCH,
+            HeaderValue::from_static("\"stale\", \"0123456789abcdef\""),
+        );
+        assert!(if_match_satisfied(&headers, etag));
+
+        headers.insert(header::IF_MATCH, HeaderValue::from_static("*"));
+        assert!(if_match_satisfied(&headers, etag));
+    }
+}
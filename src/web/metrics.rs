@@ -0,0 +1,237 @@
+//! Request logging and Prometheus metrics.
+//!
+//! Every request is logged at info level with its method, path, status and
+//! duration, and counted for `GET /metrics` together with latency histograms
+//! and the number of validations and firmware generations. Metrics are keyed
+//! by route template (`/api/layouts/{filename}`), so they never contain
+//! layout names. Logs show the request path unless the server was started
+//! with `--redact-logs`, in which case they show the route template too.
+
+use std::collections::BTreeMap;
+use std::fmt::Write as _;
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::sync::{Arc, Mutex, PoisonError};
+use std::time::{Duration, Instant};
+
+use axum::{
+    extract::{MatchedPath, Request, State},
+    http::header,
+    middleware::Next,
+    response::{IntoResponse, Response},
+};
+use tracing::info;
+
+use super::AppState;
+
+/// Upper bounds of the latency histogram buckets, in seconds
+const LATENCY_BUCKETS: [f64; 11] = [
+    0.005, 0.01, 0.025, 0.05, 0.1, 0.25, 0.5, 1.0, 2.5, 5.0, 10.0,
+];
+
+/// Route label for requests that matched no API route (static files)
+const UNMATCHED_ROUTE: &str = "unmatched";
+
+/// Counters for one method and route.
+#[derive(Debug, Default)]
+struct RouteStats {
+    /// Responses by status code
+    statuses: BTreeMap<u16, u64>,
+    /// Requests at or below each of [`LATENCY_BUCKETS`]
+    buckets: [u64; LATENCY_BUCKETS.len()],
+    /// Total duration of all requests, in seconds
+    duration_sum: f64,
+    /// Number of requests
+    count: u64,
+}
+
+/// Request and operation counters shared by all handlers.
+#[derive(Debug, Default)]
+pub struct Metrics {
+    /// Log route templates instead of request paths
+    redact_paths: bool,
+    /// Counters by (method, route template)
+    routes: Mutex<BTreeMap<(String, String), RouteStats>>,
+    /// Layout validations performed
+    validations: AtomicU64,
+    /// Firmware generations started
+    generations: AtomicU64,
+}
+
+impl Metrics {
+    /// Creates empty metrics. With `redact_paths`, request logs show route
+    /// templates instead of paths, hiding layout names.
+    #[must_use]
+    pub fn new(redact_paths: bool) -> Self {
+        Self {
+            redact_paths,
+            ..Self::default()
+        }
+    }
+
+    /// Counts a handled request.
+    pub fn record_request(&self, method: &str, route: &str, status: u16, duration: Duration) {
+        let seconds = duration.as_secs_f64();
+        let mut routes = self.routes.lock().unwrap_or_else(PoisonError::into_inner);
+        let stats = routes
+            .entry((method.to_string(), route.to_string()))
+            .or_default();
+        *stats.statuses.entry(status).or_default() += 1;
+        for (bucket, bound) in stats.buckets.iter_mut().zip(LATENCY_BUCKETS) {
+            if seconds <= bound {
+                *bucket += 1;
+            }
+        }
+        stats.duration_sum += seconds;
+        stats.count += 1;
+        drop(routes);
+    }
+
+    /// Counts a layout validation.
+    pub fn record_validation(&self) {
+        self.validations.fetch_add(1, Ordering::Relaxed);
+    }
+
+    /// Counts a started firmware generation.
+    pub fn record_generation(&self) {
+        self.generations.fetch_add(1, Ordering::Relaxed);
+    }
+
+    /// Number of requests counted for `method` and `route`.
+    #[must_use]
+    pub fn request_count(&self, method: &str, route: &str) -> u64 {
+        let routes = self.routes.lock().unwrap_or_else(PoisonError::into_inner);
+        routes
+            .get(&(method.to_string(), route.to_string()))
+            .map_or(0, |stats| stats.count)
+    }
+
+    /// Renders all metrics in the Prometheus text exposition format.
+    #[must_use]
+    pub fn render(&self) -> String {
+        let routes = self.routes.lock().unwrap_or_else(PoisonError::into_inner);
+        let mut out = String::new();
+
+        out.push_str("# HELP lazyqmk_http_requests_total HTTP requests handled.\n");
+        out.push_str("# TYPE lazyqmk_http_requests_total counter\n");
+        for ((method, route), stats) in routes.iter() {
+            for (status, count) in &stats.statuses {
+                let _ = writeln!(
+                    out,
+                    "lazyqmk_http_requests_total{{method=\"{method}\",route=\"{route}\",status=\"{status}\"}} {count}"
+                );
+            }
+        }
+
+        out.push_str("# HELP lazyqmk_http_request_duration_seconds HTTP request latency.\n");
+        out.push_str("# TYPE lazyqmk_http_request_duration_seconds histogram\n");
+        for ((method, route), stats) in routes.iter() {
+            let labels = format!("method=\"{method}\",route=\"{route}\"");
+            for (count, bound) in stats.buckets.iter().zip(LATENCY_BUCKETS) {
+                let _ = writeln!(
+                    out,
+                    "lazyqmk_http_request_duration_seconds_bucket{{{labels},le=\"{bound}\"}} {count}"
+                );
+            }
+            let count = stats.count;
+            let _ = writeln!(
+                out,
+                "lazyqmk_http_request_duration_seconds_bucket{{{labels},le=\"+Inf\"}} {count}"
+            );
+            let _ = writeln!(
+                out,
+                "lazyqmk_http_request_duration_seconds_sum{{{labels}}} {}",
+                stats.duration_sum
+            );
+            let _ = writeln!(
+                out,
+                "lazyqmk_http_request_duration_seconds_count{{{labels}}} {count}"
+            );
+        }
+        drop(routes);
+
+        out.push_str("# HELP lazyqmk_validations_total Layout validations performed.\n");
+        out.push_str("# TYPE lazyqmk_validations_total counter\n");
+        let _ = writeln!(
+            out,
+            "lazyqmk_validations_total {}",
+            self.validations.load(Ordering::Relaxed)
+        );
+        out.push_str("# HELP lazyqmk_generations_total Firmware generations started.\n");
+        out.push_str("# TYPE lazyqmk_generations_total counter\n");
+        let _ = writeln!(
+            out,
+            "lazyqmk_generations_total {}",
+            self.generations.load(Ordering::Relaxed)
+        );
+
+        out
+    }
+}
+
+/// Middleware logging each request and counting it in [`Metrics`].
+pub async fn track_requests(
+    State(metrics): State<Arc<Metrics>>,
+    request: Request,
+    next: Next,
+) -> Response {
+    let method = request.method().to_string();
+    let route = request
+        .extensions()
+        .get::<MatchedPath>()
+        .map_or(UNMATCHED_ROUTE, MatchedPath::as_str)
+        .to_string();
+    let path = request.uri().path().to_string();
+    let start = Instant::now();
+
+    let response = next.run(request).await;
+
+    let duration = start.elapsed();
+    let status = response.status().as_u16();
+    let logged_path = if metrics.redact_paths { &route } else { &path };
+    info!(
+        method = %method,
+        path = %logged_path,
+        status,
+        duration_ms = duration.as_secs_f64() * 1000.0,
+        "request"
+    );
+    metrics.record_request(&method, &route, status, duration);
+    response
+}
+
+/// GET /metrics - Request counts, latencies and operation counts in the
+/// Prometheus text format.
+pub async fn get_metrics(State(state): State<AppState>) -> impl IntoResponse {
+    (
+        [(header::CONTENT_TYPE, "text/plain; version=0.0.4")],
+        state.metrics.render(),
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_render_counts_and_histogram() {
+        let metrics = Metrics::new(false);
+        let route = "/api/layouts/{filename}";
+        metrics.record_request("GET", route, 200, Duration::from_millis(20));
+        metrics.record_request("GET", route, 404, Duration::from_secs(3));
+        metrics.record_validation();
+
+        let text = metrics.render();
+        assert!(text.contains(
+            "lazyqmk_http_requests_total{method=\"GET\",route=\"/api/layouts/{filename}\",status=\"404\"} 1"
+        ));
+        assert!(text.contains(
+            "lazyqmk_http_request_duration_seconds_bucket{method=\"GET\",route=\"/api/layouts/{filename}\",le=\"0.025\"} 1"
+        ));
+        assert!(text.contains(
+            "lazyqmk_http_request_duration_seconds_bucket{method=\"GET\",route=\"/api/layouts/{filename}\",le=\"+Inf\"} 2"
+        ));
+        assert!(text.contains("lazyqmk_validations_total 1"));
+        assert!(text.contains("lazyqmk_generations_total 0"));
+        assert_eq!(metrics.request_count("GET", route), 2);
+    }
+}
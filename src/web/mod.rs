@@ -7,9 +7,14 @@
 //! # Endpoints
 //!
 //! - `GET /health` - Health check
+//! - `GET /metrics` - Request and operation counters (Prometheus text format)
 //! - `GET /api/layouts` - List layout markdown files
 //! - `GET /api/layouts/{filename}` - Load and parse a layout file
-//! - `PUT /api/layouts/{filename}` - Save a layout file
+//! - `PUT /api/layouts/{filename}` - Save a layout file (honors `If-Match`)
+//! - `GET/POST /api/layouts/{filename}/tap-dances` - List or add tap dances
+//! - `GET/PUT/DELETE /api/layouts/{filename}/tap-dances/{name}` - Get, replace or remove a tap dance
+//! - `GET/POST /api/layouts/{filename}/categories` - List or add categories
+//! - `GET/PUT/DELETE /api/layouts/{filename}/categories/{id}` - Get, replace or remove a category
 //! - `POST /api/layouts/{filename}/generate` - Generate firmware and start job
 //! - `POST /api/layouts/{filename}/save-as-template` - Save layout as template
 //! - `GET /api/layouts/{filename}/render-metadata` - Get key display metadata for rendering
@@ -22,6 +27,7 @@
 //! - `PUT /api/config` - Update configuration
 //! - `GET /api/preflight` - Check application state for onboarding flow
 //! - `GET /api/keyboards/{keyboard}/geometry/{layout}` - Get keyboard geometry
+//! - `GET /api/keyboards/{keyboard}/geometry?variant=` - Get keyboard geometry (cacheable)
 //! - `POST /api/build/start` - Start a firmware build job
 //! - `GET /api/build/jobs` - List all build jobs
 //! - `GET /api/build/jobs/{job_id}` - Get build job status
@@ -35,18 +41,28 @@
 //! - `POST /api/generate/jobs/{job_id}/cancel` - Cancel a generate job
 //! - `GET /api/generate/jobs/{job_id}/download` - Download generated zip file
 //! - `GET /api/generate/health` - Get generate job system health status
+//! - `GET /api/events` - Stream workspace layout changes and build completions (Server-Sent Events)
 
+pub mod asset_cache;
+pub mod auth;
 pub mod build_jobs;
 pub mod generate_jobs;
+pub mod layout_collections;
+pub mod metrics;
+pub mod workspace_watcher;
 
+use std::convert::Infallible;
 use std::net::SocketAddr;
 use std::path::PathBuf;
 use std::sync::Arc;
 
 use axum::{
     extract::{Path, Query, State},
-    http::{header, StatusCode},
-    response::{Html, IntoResponse, Response},
+    http::{header, HeaderMap, StatusCode},
+    response::{
+        sse::{Event, KeepAlive, Sse},
+        Html, IntoResponse, Response,
+    },
     routing::get,
     Json, Router,
 };
@@ -57,13 +73,20 @@ use tracing::info;
 
 use crate::config::Config;
 use crate::export;
+use crate::firmware::preflight;
 use crate::keycode_db::{KeycodeCategory, KeycodeDb, KeycodeDefinition};
 use crate::models::{IdleEffectSettings, Layout, RgbMatrixEffect, TapDanceAction, TapHoldSettings};
 use crate::parser;
+use crate::services::geometry::{
+    build_geometry_for_layout, detect_geometry_mismatch, GeometryContext, GeometryMismatch,
+};
 use crate::services::LayoutService;
 
+use asset_cache::GeometryCache;
 use build_jobs::BuildJobManager;
 use generate_jobs::GenerateJobManager;
+use metrics::Metrics;
+use workspace_watcher::{WorkspaceEvent, WorkspaceWatcher};
 
 #[cfg(test)]
 use build_jobs::MockFirmwareBuilder;
@@ -144,6 +167,22 @@ async fn static_handler(uri: axum::http::Uri) -> Response {
 // Application State
 // ============================================================================
 
+/// Forwards finished builds to `/api/events` subscribers as `build-finished`
+/// events, flagged for a desktop notification when enabled in the config.
+fn publish_build_events(
+    build_manager: &BuildJobManager,
+    workspace_watcher: &Arc<WorkspaceWatcher>,
+    config: &Config,
+) {
+    let watcher = Arc::downgrade(workspace_watcher);
+    let notify = config.ui.build_notifications;
+    build_manager.on_complete(move |completion| {
+        if let Some(watcher) = watcher.upgrade() {
+            watcher.publish(WorkspaceEvent::build_finished(completion.clone(), notify));
+        }
+    });
+}
+
 /// Shared application state for the web API.
 #[derive(Clone)]
 pub struct AppState {
@@ -157,6 +196,14 @@ pub struct AppState {
     build_manager: Arc<BuildJobManager>,
     /// Generate job manager for firmware generation and zip packaging
     generate_manager: Arc<GenerateJobManager>,
+    /// Cached geometry responses
+    geometry_cache: Arc<GeometryCache>,
+    /// Workspace layout file watcher
+    workspace_watcher: Arc<WorkspaceWatcher>,
+    /// Serializes read-modify-write edits of layout files
+    layout_edit_lock: Arc<tokio::sync::Mutex<()>>,
+    /// Request and operation counters served at `/metrics`
+    metrics: Arc<Metrics>,
 }
 
 impl AppState {
@@ -165,14 +212,23 @@ impl AppState {
         let keycode_db = Arc::new(KeycodeDb::load()?);
 
         // Set up build job manager
-        let logs_dir = workspace_root.join(".lazyqmk").join("build_logs");
-        let output_dir = workspace_root.join(".lazyqmk").join("build_output");
+        let logs_dir = workspace_root
+            .join(crate::paths::STATE_DIR_NAME)
+            .join("build_logs");
+        let output_dir = workspace_root
+            .join(crate::paths::STATE_DIR_NAME)
+            .join("build_output");
         let qmk_path = config.paths.qmk_firmware.clone();
-        let build_manager = BuildJobManager::new(logs_dir, output_dir, qmk_path.clone());
+        let build_manager =
+            BuildJobManager::new(logs_dir, output_dir, qmk_path.clone(), config.build.backend);
 
         // Set up generate job manager
-        let gen_logs_dir = workspace_root.join(".lazyqmk").join("generate_logs");
-        let gen_output_dir = workspace_root.join(".lazyqmk").join("generate_output");
+        let gen_logs_dir = workspace_root
+            .join(crate::paths::STATE_DIR_NAME)
+            .join("generate_logs");
+        let gen_output_dir = workspace_root
+            .join(crate::paths::STATE_DIR_NAME)
+            .join("generate_output");
         let generate_manager = GenerateJobManager::new(
             gen_logs_dir,
             gen_output_dir,
@@ -181,12 +237,19 @@ impl AppState {
             Arc::clone(&keycode_db),
         );
 
+        let workspace_watcher = Arc::new(WorkspaceWatcher::start(workspace_root.clone()));
+        publish_build_events(&build_manager, &workspace_watcher, &config);
+
         Ok(Self {
             config: Arc::new(config),
             keycode_db,
             workspace_root,
             build_manager,
             generate_manager,
+            geometry_cache: Arc::new(GeometryCache::default()),
+            workspace_watcher,
+            layout_edit_lock: Arc::new(tokio::sync::Mutex::new(())),
+            metrics: Arc::new(Metrics::default()),
         })
     }
 
@@ -196,16 +259,24 @@ impl AppState {
         let keycode_db = Arc::new(KeycodeDb::load()?);
 
         // Set up build job manager with mock builder
-        let logs_dir = workspace_root.join(".lazyqmk").join("build_logs");
-        let output_dir = workspace_root.join(".lazyqmk").join("build_output");
+        let logs_dir = workspace_root
+            .join(crate::paths::STATE_DIR_NAME)
+            .join("build_logs");
+        let output_dir = workspace_root
+            .join(crate::paths::STATE_DIR_NAME)
+            .join("build_output");
         let qmk_path = config.paths.qmk_firmware.clone();
         let mock_builder = Arc::new(MockFirmwareBuilder::default());
         let build_manager =
             BuildJobManager::with_builder(logs_dir, output_dir, qmk_path.clone(), mock_builder);
 
         // Set up generate job manager with mock worker
-        let gen_logs_dir = workspace_root.join(".lazyqmk").join("generate_logs");
-        let gen_output_dir = workspace_root.join(".lazyqmk").join("generate_output");
+        let gen_logs_dir = workspace_root
+            .join(crate::paths::STATE_DIR_NAME)
+            .join("generate_logs");
+        let gen_output_dir = workspace_root
+            .join(crate::paths::STATE_DIR_NAME)
+            .join("generate_output");
         let mock_worker = Arc::new(MockGenerateWorker::default());
         let generate_manager = GenerateJobManager::with_worker(
             gen_logs_dir,
@@ -216,20 +287,43 @@ impl AppState {
             mock_worker,
         );
 
+        let workspace_watcher = Arc::new(WorkspaceWatcher::start(workspace_root.clone()));
+        publish_build_events(&build_manager, &workspace_watcher, &config);
+
         Ok(Self {
             config: Arc::new(config),
             keycode_db,
             workspace_root,
             build_manager,
             generate_manager,
+            geometry_cache: Arc::new(GeometryCache::default()),
+            workspace_watcher,
+            layout_edit_lock: Arc::new(tokio::sync::Mutex::new(())),
+            metrics: Arc::new(Metrics::default()),
         })
     }
 
+    /// Logs route templates instead of request paths, so layout names stay
+    /// out of the request log.
+    #[must_use]
+    pub fn with_redacted_logs(mut self) -> Self {
+        self.metrics = Arc::new(Metrics::new(true));
+        self
+    }
+
     /// Returns the workspace root directory.
     #[must_use]
     pub fn workspace_root(&self) -> &PathBuf {
         &self.workspace_root
     }
+
+    /// Number of times keyboard info was parsed to serve geometry requests.
+    ///
+    /// Cached and `304 Not Modified` responses don't count.
+    #[must_use]
+    pub fn geometry_parse_count(&self) -> usize {
+        self.geometry_cache.parse_count()
+    }
 }
 
 // ============================================================================
@@ -263,6 +357,9 @@ pub struct LayoutSummary {
     pub description: String,
     /// Last modified timestamp (RFC 3339 format).
     pub modified: String,
+    /// Filename of an identical layout, if this one is a duplicate.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub duplicate_of: Option<String>,
 }
 
 /// Query parameters for keycode search.
@@ -701,6 +798,12 @@ pub struct TapDanceDto {
     /// Keycode for hold action.
     #[serde(skip_serializing_if = "Option::is_none")]
     pub hold: Option<String>,
+    /// Per-dance tapping term in milliseconds.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub tapping_term_ms: Option<u16>,
+    /// Whether the single tap is sent on every tap.
+    #[serde(default)]
+    pub on_each_tap: bool,
 }
 
 impl From<&TapDanceAction> for TapDanceDto {
@@ -710,6 +813,8 @@ impl From<&TapDanceAction> for TapDanceDto {
             single_tap: td.single_tap.clone(),
             double_tap: td.double_tap.clone(),
             hold: td.hold.clone(),
+            tapping_term_ms: td.tapping_term_ms,
+            on_each_tap: td.on_each_tap,
         }
     }
 }
@@ -751,25 +856,13 @@ fn validate_filename(filename: &str) -> Result<&str, ApiError> {
 
 /// Validates a keyboard path to prevent path traversal attacks.
 fn validate_keyboard_path(keyboard: &str) -> Result<(), ApiError> {
-    if keyboard.is_empty() {
-        return Err(ApiError::new("Keyboard path cannot be empty"));
-    }
-
-    // Reject path traversal attempts
-    if keyboard.contains("..") {
-        return Err(ApiError::new(
-            "Invalid keyboard path: path traversal not allowed",
-        ));
-    }
-
-    // Reject absolute paths
-    if keyboard.starts_with('/') || keyboard.starts_with('\\') {
-        return Err(ApiError::new(
-            "Invalid keyboard path: absolute paths not allowed",
-        ));
-    }
+    crate::firmware::paths::validate_keyboard_path(keyboard)
+        .map_err(|e| ApiError::new(e.to_string()))
+}
 
-    Ok(())
+/// Validates a keymap name so it cannot escape the keyboard's `keymaps/` directory.
+fn validate_keymap_name(keymap: &str) -> Result<(), ApiError> {
+    crate::firmware::paths::validate_keymap_name(keymap).map_err(|e| ApiError::new(e.to_string()))
 }
 
 // ============================================================================
@@ -789,6 +882,7 @@ async fn list_layouts(
     State(state): State<AppState>,
 ) -> Result<Json<LayoutListResponse>, (StatusCode, Json<ApiError>)> {
     let mut layouts = Vec::new();
+    let mut hashes = Vec::new();
 
     // Read directory entries
     let entries = std::fs::read_dir(&state.workspace_root).map_err(|e| {
@@ -818,17 +912,27 @@ async fn list_layouts(
 
             // Try to parse the layout to get metadata
             if let Ok(layout) = LayoutService::load(&path) {
+                hashes.push((PathBuf::from(&filename), layout.content_hash()));
                 layouts.push(LayoutSummary {
                     filename,
                     name: layout.metadata.name.clone(),
                     description: layout.metadata.description.clone(),
                     modified: layout.metadata.modified.to_rfc3339(),
+                    duplicate_of: None,
                 });
             }
             // Skip files that can't be parsed as layouts
         }
     }
 
+    // Flag layouts identical to another one in the workspace
+    let duplicates = crate::services::layouts::find_duplicates(&hashes);
+    for layout in &mut layouts {
+        layout.duplicate_of = duplicates
+            .get(std::path::Path::new(&layout.filename))
+            .map(|original| original.to_string_lossy().into_owned());
+    }
+
     // Sort by modification time (newest first)
     layouts.sort_by(|a, b| b.modified.cmp(&a.modified));
 
@@ -836,10 +940,12 @@ async fn list_layouts(
 }
 
 /// GET /api/layouts/{filename} - Load a specific layout file.
+///
+/// The `ETag` is the layout's content hash, for use with `If-Match`.
 async fn get_layout(
     State(state): State<AppState>,
     Path(filename): Path<String>,
-) -> Result<Json<Layout>, (StatusCode, Json<ApiError>)> {
+) -> Result<Response, (StatusCode, Json<ApiError>)> {
     // Validate filename to prevent path traversal
     let filename = validate_filename(&filename).map_err(|e| (StatusCode::BAD_REQUEST, Json(e)))?;
 
@@ -874,13 +980,47 @@ async fn get_layout(
         )
     })?;
 
-    Ok(Json(layout))
+    let etag = layout_collections::layout_etag(&layout);
+    let geometry_mismatch = layout_geometry_mismatch(&state.config, &layout);
+    let response = LayoutResponse {
+        layout,
+        geometry_mismatch,
+    };
+    Ok(([(header::ETAG, etag)], Json(response)).into_response())
+}
+
+/// Layout returned by `GET /api/layouts/{filename}`.
+#[derive(Serialize)]
+struct LayoutResponse {
+    #[serde(flatten)]
+    layout: Layout,
+    /// Layers with keys outside the keyboard geometry, if any
+    geometry_mismatch: Option<GeometryMismatch>,
+}
+
+/// Compares a layout against its keyboard geometry.
+///
+/// Returns `None` when the layout fits or the geometry can't be built
+/// (no QMK path, keyboard or variant).
+fn layout_geometry_mismatch(config: &Config, layout: &Layout) -> Option<GeometryMismatch> {
+    config.paths.qmk_firmware.as_ref()?;
+    let variant = layout.metadata.layout_variant.as_ref()?;
+    let context = GeometryContext {
+        config,
+        metadata: &layout.metadata,
+    };
+    let result = build_geometry_for_layout(context, variant).ok()?;
+    detect_geometry_mismatch(layout, &result.mapping)
 }
 
 /// PUT /api/layouts/{filename} - Save a layout file.
+///
+/// With `If-Match`, fails with `412 Precondition Failed` if the file on disk
+/// no longer has that content hash.
 async fn save_layout(
     State(state): State<AppState>,
     Path(filename): Path<String>,
+    headers: HeaderMap,
     Json(layout): Json<Layout>,
 ) -> Result<StatusCode, (StatusCode, Json<ApiError>)> {
     // Validate filename to prevent path traversal
@@ -906,6 +1046,29 @@ async fn save_layout(
         )
     })?;
 
+    let _guard = state.layout_edit_lock.lock().await;
+    if headers.contains_key(header::IF_MATCH) && path.exists() {
+        let current = LayoutService::load(&path).map_err(|e| {
+            (
+                StatusCode::INTERNAL_SERVER_ERROR,
+                Json(ApiError::with_details(
+                    "Failed to load layout",
+                    e.to_string(),
+                )),
+            )
+        })?;
+        let etag = layout_collections::layout_etag(&current);
+        if !layout_collections::if_match_satisfied(&headers, &etag) {
+            return Err((
+                StatusCode::PRECONDITION_FAILED,
+                Json(ApiError::with_details(
+                    "Layout was modified by someone else",
+                    format!("Current ETag is {etag}; reload the layout and retry"),
+                )),
+            ));
+        }
+    }
+
     // Save the layout
     LayoutService::save(&layout, &path).map_err(|e| {
         (
@@ -924,8 +1087,16 @@ async fn save_layout(
 async fn list_keycodes(
     State(state): State<AppState>,
     Query(query): Query<KeycodeQuery>,
-) -> Json<KeycodeListResponse> {
+    headers: HeaderMap,
+) -> Response {
     let search = query.search.as_deref().unwrap_or("");
+    let etag = keycodes_etag(
+        &state.keycode_db,
+        &["keycodes", search, query.category.as_deref().unwrap_or("")],
+    );
+    if asset_cache::matches_if_none_match(&headers, &etag) {
+        return asset_cache::not_modified(&etag, asset_cache::KEYCODES_CACHE_CONTROL);
+    }
 
     let keycodes: Vec<KeycodeInfo> = match &query.category {
         Some(cat) => state
@@ -943,11 +1114,20 @@ async fn list_keycodes(
     };
 
     let total = keycodes.len();
-    Json(KeycodeListResponse { keycodes, total })
+    asset_cache::with_cache_headers(
+        Json(KeycodeListResponse { keycodes, total }),
+        &etag,
+        asset_cache::KEYCODES_CACHE_CONTROL,
+    )
 }
 
 /// GET /api/keycodes/categories - List keycode categories.
-async fn list_categories(State(state): State<AppState>) -> Json<CategoryListResponse> {
+async fn list_categories(State(state): State<AppState>, headers: HeaderMap) -> Response {
+    let etag = keycodes_etag(&state.keycode_db, &["categories"]);
+    if asset_cache::matches_if_none_match(&headers, &etag) {
+        return asset_cache::not_modified(&etag, asset_cache::KEYCODES_CACHE_CONTROL);
+    }
+
     let categories = state
         .keycode_db
         .categories()
@@ -955,7 +1135,18 @@ async fn list_categories(State(state): State<AppState>) -> Json<CategoryListResp
         .map(CategoryInfo::from)
         .collect();
 
-    Json(CategoryListResponse { categories })
+    asset_cache::with_cache_headers(
+        Json(CategoryListResponse { categories }),
+        &etag,
+        asset_cache::KEYCODES_CACHE_CONTROL,
+    )
+}
+
+/// ETag for keycode data, which only changes with the embedded database.
+fn keycodes_etag(db: &KeycodeDb, parts: &[&str]) -> String {
+    let mut all = vec![db.version(), db.qmk_version()];
+    all.extend_from_slice(parts);
+    asset_cache::strong_etag(&all)
 }
 
 /// GET /api/config - Get current configuration.
@@ -1102,13 +1293,44 @@ pub struct KeyGeometryInfo {
     pub visual_index: u8,
 }
 
+/// Query parameters for the geometry endpoint.
+#[derive(Debug, Deserialize)]
+pub struct GeometryQuery {
+    /// Layout variant name (e.g., "LAYOUT_split_3x6_3").
+    pub variant: String,
+}
+
 /// GET /api/keyboards/{keyboard}/geometry/{layout} - Get keyboard geometry.
 ///
 /// The keyboard path can contain slashes (e.g., "keebart/corne_choc_pro").
 async fn get_geometry(
     State(state): State<AppState>,
     Path((keyboard, layout)): Path<(String, String)>,
-) -> Result<Json<GeometryResponse>, (StatusCode, Json<ApiError>)> {
+    headers: HeaderMap,
+) -> Result<Response, (StatusCode, Json<ApiError>)> {
+    cached_geometry(&state, keyboard, layout, &headers)
+}
+
+/// GET /api/keyboards/{keyboard}/geometry?variant= - Get keyboard geometry.
+async fn get_geometry_for_variant(
+    State(state): State<AppState>,
+    Path(keyboard): Path<String>,
+    Query(query): Query<GeometryQuery>,
+    headers: HeaderMap,
+) -> Result<Response, (StatusCode, Json<ApiError>)> {
+    cached_geometry(&state, keyboard, query.variant, &headers)
+}
+
+/// Serves geometry with ETag revalidation and a per-variant response cache.
+///
+/// The ETag is computed from the keyboard's config file metadata, so a
+/// matching conditional request is answered without parsing `info.json`.
+fn cached_geometry(
+    state: &AppState,
+    keyboard: String,
+    layout: String,
+    headers: &HeaderMap,
+) -> Result<Response, (StatusCode, Json<ApiError>)> {
     // Validate keyboard path
     validate_keyboard_path(&keyboard).map_err(|e| (StatusCode::BAD_REQUEST, Json(e)))?;
 
@@ -1120,6 +1342,63 @@ async fn get_geometry(
         )
     })?;
 
+    let etag = asset_cache::keyboard_tree_key(qmk_path, &keyboard)
+        .map(|tree_key| asset_cache::strong_etag(&[&keyboard, &layout, &tree_key]));
+
+    if let Some(etag) = &etag {
+        if asset_cache::matches_if_none_match(headers, etag) {
+            return Ok(asset_cache::not_modified(
+                etag,
+                asset_cache::GEOMETRY_CACHE_CONTROL,
+            ));
+        }
+        if let Some(body) = state.geometry_cache.get(&keyboard, &layout, etag) {
+            return Ok(json_body_response(&body, etag));
+        }
+    }
+
+    state.geometry_cache.record_parse();
+    let response = build_geometry_response(qmk_path, keyboard.clone(), layout.clone())?;
+    let body = serde_json::to_string(&response).map_err(|e| {
+        (
+            StatusCode::INTERNAL_SERVER_ERROR,
+            Json(ApiError::with_details(
+                "Failed to serialize geometry",
+                e.to_string(),
+            )),
+        )
+    })?;
+
+    match etag {
+        Some(etag) => {
+            let body = Arc::new(body);
+            state
+                .geometry_cache
+                .insert(&keyboard, &layout, &etag, Arc::clone(&body));
+            Ok(json_body_response(&body, &etag))
+        }
+        None => Ok(([(header::CONTENT_TYPE, "application/json")], body).into_response()),
+    }
+}
+
+/// Builds a JSON response from a pre-serialized geometry body.
+fn json_body_response(body: &str, etag: &str) -> Response {
+    asset_cache::with_cache_headers(
+        (
+            [(header::CONTENT_TYPE, "application/json")],
+            body.to_string(),
+        ),
+        etag,
+        asset_cache::GEOMETRY_CACHE_CONTROL,
+    )
+}
+
+/// Parses keyboard info and builds the geometry response.
+fn build_geometry_response(
+    qmk_path: &std::path::Path,
+    keyboard: String,
+    layout: String,
+) -> Result<GeometryResponse, (StatusCode, Json<ApiError>)> {
     // Parse keyboard info.json
     let keyboard_info = parser::keyboard_json::parse_keyboard_info_json(qmk_path, &keyboard)
         .map_err(|e| {
@@ -1183,15 +1462,14 @@ async fn get_geometry(
         .keys
         .iter()
         .map(|k| {
-            // Quantize visual coordinates to grid position (same logic as VisualLayoutMapping::build)
-            let row = k.visual_y.round() as u8;
-            let col = k.visual_x.round() as u8;
-            let pos_key = format!("{row},{col}");
+            // Same grid position as VisualLayoutMapping::build
+            let pos = k.visual_position();
+            let pos_key = format!("{},{}", pos.row, pos.col);
             (pos_key, k.layout_index)
         })
         .collect();
 
-    Ok(Json(GeometryResponse {
+    Ok(GeometryResponse {
         keyboard,
         layout,
         keys,
@@ -1199,7 +1477,7 @@ async fn get_geometry(
         matrix_cols: geometry.matrix_cols,
         encoder_count: geometry.encoder_count,
         position_to_visual_index,
-    }))
+    })
 }
 
 // ============================================================================
@@ -1255,6 +1533,7 @@ async fn validate_layout(
     }
 
     // Validate the layout
+    state.metrics.record_validation();
     match layout.validate() {
         Ok(()) => Ok(Json(ValidationResponse {
             valid: true,
@@ -1461,9 +1740,8 @@ async fn get_render_metadata(
                         .keys
                         .iter()
                         .map(|k| {
-                            let row = k.visual_y.round() as u8;
-                            let col = k.visual_x.round() as u8;
-                            let pos_key = format!("{row},{col}");
+                            let pos = k.visual_position();
+                            let pos_key = format!("{},{}", pos.row, pos.col);
                             (pos_key, k.layout_index)
                         })
                         .collect()
@@ -1615,7 +1893,13 @@ async fn export_layout(
 
     // Generate markdown (with or without geometry)
     let markdown = if let Some(geom) = geometry {
-        export::export_to_markdown(&layout, &geom, &state.keycode_db).map_err(|e| {
+        export::export_to_markdown(
+            &layout,
+            &geom,
+            &state.keycode_db,
+            state.config.ui.transparent_keys.skips_in_exports(),
+        )
+        .map_err(|e| {
             (
                 StatusCode::INTERNAL_SERVER_ERROR,
                 Json(ApiError::with_details(
@@ -1679,10 +1963,50 @@ fn generate_simple_markdown(layout: &Layout) -> String {
     output
 }
 
+/// Query parameters for firmware generation.
+#[derive(Debug, Deserialize)]
+pub struct GenerateQuery {
+    /// Generate even if the layout's keyboard differs from `build.keyboard`.
+    #[serde(default)]
+    pub allow_keyboard_mismatch: bool,
+}
+
+/// Rejects a layout made for another keyboard than `build.keyboard` with
+/// 409 Conflict, unless the client confirmed the mismatch.
+fn check_keyboard_target(
+    config: &Config,
+    layout: &Layout,
+    allow_mismatch: bool,
+) -> Result<(), (StatusCode, Json<ApiError>)> {
+    if allow_mismatch {
+        return Ok(());
+    }
+    let Ok(target) =
+        crate::firmware::BuildTarget::resolve(None, None, None, &layout.metadata, &config.build)
+    else {
+        return Ok(());
+    };
+    let target_path = preflight::keymap_target_path(config, &target);
+    match preflight::check_keyboard_target(&layout.metadata, None, &config.build, &target_path) {
+        Some(mismatch) => Err((
+            StatusCode::CONFLICT,
+            Json(ApiError::with_details(
+                "Layout keyboard does not match the build target",
+                mismatch.to_string(),
+            )),
+        )),
+        None => Ok(()),
+    }
+}
+
 /// POST /api/layouts/{filename}/generate - Generate firmware and return job info.
+///
+/// Answers 409 Conflict when the layout's keyboard differs from
+/// `build.keyboard`; retry with `?allow_keyboard_mismatch=true` to confirm.
 async fn generate_firmware(
     State(state): State<AppState>,
     Path(filename): Path<String>,
+    Query(query): Query<GenerateQuery>,
 ) -> Result<Json<generate_jobs::StartGenerateResponse>, (StatusCode, Json<ApiError>)> {
     // Validate filename
     let filename = validate_filename(&filename).map_err(|e| (StatusCode::BAD_REQUEST, Json(e)))?;
@@ -1718,6 +2042,8 @@ async fn generate_firmware(
         )
     })?;
 
+    check_keyboard_target(&state.config, &layout, query.allow_keyboard_mismatch)?;
+
     // Get keyboard from layout metadata
     let keyboard = layout.metadata.keyboard.clone().ok_or_else(|| {
         (
@@ -1728,6 +2054,12 @@ async fn generate_firmware(
         )
     })?;
 
+    // Layout metadata arrives over HTTP, so never trust it as a path
+    validate_keyboard_path(&keyboard).map_err(|e| (StatusCode::BAD_REQUEST, Json(e)))?;
+    if let Some(keymap) = &layout.metadata.keymap_name {
+        validate_keymap_name(keymap).map_err(|e| (StatusCode::BAD_REQUEST, Json(e)))?;
+    }
+
     // Get layout variant from layout metadata
     let layout_variant = layout.metadata.layout_variant.ok_or_else(|| {
         (
@@ -1748,6 +2080,7 @@ async fn generate_firmware(
                 Json(ApiError::with_details("Failed to start generation", e)),
             )
         })?;
+    state.metrics.record_generation();
 
     Ok(Json(generate_jobs::StartGenerateResponse {
         status: "started".to_string(),
@@ -1815,25 +2148,28 @@ async fn start_build(
         )
     })?;
 
-    // Get keyboard and keymap from layout metadata
-    let keyboard = layout.metadata.keyboard.clone().ok_or_else(|| {
+    check_keyboard_target(&state.config, &layout, request.allow_keyboard_mismatch)?;
+
+    // Layout metadata first, then the config fallbacks. Metadata arrives over
+    // HTTP, so resolution also rejects names that aren't safe path components.
+    let target = crate::firmware::BuildTarget::resolve(
+        None,
+        None,
+        None,
+        &layout.metadata,
+        &state.config.build,
+    )
+    .map_err(|e| {
         (
             StatusCode::BAD_REQUEST,
-            Json(ApiError::new(
-                "Layout has no keyboard defined - cannot build firmware",
-            )),
+            Json(ApiError::new(format!("Cannot build firmware: {e}"))),
         )
     })?;
 
-    let keymap = layout
-        .metadata
-        .keymap_name
-        .unwrap_or_else(|| "default".to_string());
-
     // Start the build job
     let job = state
         .build_manager
-        .start_build(filename, keyboard, keymap)
+        .start_build(filename, target.keyboard, target.keymap, target.converter)
         .map_err(|e| (StatusCode::SERVICE_UNAVAILABLE, Json(ApiError::new(e))))?;
 
     Ok(Json(build_jobs::StartBuildResponse { job }))
@@ -2194,17 +2530,15 @@ pub struct ApplyTemplateRequest {
 
 /// Get the platform-specific template directory.
 fn get_template_dir() -> Result<PathBuf, (StatusCode, Json<ApiError>)> {
-    Config::config_dir()
-        .map(|dir| dir.join("templates"))
-        .map_err(|e| {
-            (
-                StatusCode::INTERNAL_SERVER_ERROR,
-                Json(ApiError::with_details(
-                    "Failed to get template directory",
-                    e.to_string(),
-                )),
-            )
-        })
+    crate::paths::templates_dir().map_err(|e| {
+        (
+            StatusCode::INTERNAL_SERVER_ERROR,
+            Json(ApiError::with_details(
+                "Failed to get template directory",
+                e.to_string(),
+            )),
+        )
+    })
 }
 
 /// Sanitize a string to be a valid filename.
@@ -2891,8 +3225,12 @@ async fn create_layout(
         id: uuid::Uuid::new_v4().to_string(),
         default_color: RgbColor::new(255, 255, 255),
         category_id: None,
+        description: None,
         keys: base_keys,
+        enabled: true,
         layer_colors_enabled: true,
+        encoders: Vec::new(),
+        modified: Some(chrono::Utc::now()),
     };
 
     let metadata = LayoutMetadata {
@@ -2907,7 +3245,9 @@ async fn create_layout(
         layout_variant: Some(request.layout_variant),
         keyboard: Some(request.keyboard),
         keymap_name: Some("default".to_string()),
+        converter: None,
         output_format: Some("uf2".to_string()),
+        lint: crate::models::layout::LintSettings::default(),
     };
 
     let layout = Layout {
@@ -2922,6 +3262,7 @@ async fn create_layout(
         idle_effect_settings: IdleEffectSettings::default(),
         tap_hold_settings: TapHoldSettings::default(),
         tap_dances: vec![],
+        orphaned_keys: Vec::new(),
     };
 
     // Save the layout
@@ -3109,6 +3450,37 @@ async fn switch_layout_variant(
 // Router Setup
 // ============================================================================
 
+/// GET /api/events - Stream workspace layout changes as Server-Sent Events.
+///
+/// Each event is named after its kind (`layout-added`, `layout-changed`,
+/// `layout-removed`, `build-finished`) and carries the JSON-encoded
+/// [`WorkspaceEvent`].
+///
+/// [`WorkspaceEvent`]: workspace_watcher::WorkspaceEvent
+async fn workspace_events(
+    State(state): State<AppState>,
+) -> Sse<impl futures_util::Stream<Item = Result<Event, Infallible>>> {
+    // The stream holds the watcher so it keeps running while clients are connected
+    let watcher = Arc::clone(&state.workspace_watcher);
+    let receiver = watcher.subscribe();
+    let stream =
+        futures_util::stream::unfold((watcher, receiver), |(watcher, mut receiver)| async move {
+            loop {
+                match receiver.recv().await {
+                    Ok(event) => {
+                        let data = serde_json::to_string(&event).unwrap_or_default();
+                        let sse = Event::default().event(event.kind.as_str()).data(data);
+                        return Some((Ok(sse), (watcher, receiver)));
+                    }
+                    // Slow clients skip missed events rather than disconnect
+                    Err(tokio::sync::broadcast::error::RecvError::Lagged(_)) => {}
+                    Err(tokio::sync::broadcast::error::RecvError::Closed) => return None,
+                }
+            }
+        });
+    Sse::new(stream).keep_alive(KeepAlive::default())
+}
+
 /// Creates the API router with all endpoints.
 pub fn create_router(state: AppState) -> Router {
     // CORS configuration - allow all origins for development
@@ -3121,15 +3493,40 @@ pub fn create_router(state: AppState) -> Router {
         .allow_methods(Any)
         .allow_headers(Any);
 
+    let request_metrics = Arc::clone(&state.metrics);
+
     Router::new()
-        // Health check
+        // Health check and metrics
         .route("/health", get(health_check))
+        .route("/metrics", get(metrics::get_metrics))
         // Layout endpoints
         .route("/api/layouts", get(list_layouts))
         .route("/api/layouts/{filename}", get(get_layout).put(save_layout))
         .route("/api/layouts/{filename}/validate", get(validate_layout))
         .route("/api/layouts/{filename}/inspect", get(inspect_layout))
         .route("/api/layouts/{filename}/export", get(export_layout))
+        .route(
+            "/api/layouts/{filename}/tap-dances",
+            get(layout_collections::list_tap_dances)
+                .post(layout_collections::create_tap_dance),
+        )
+        .route(
+            "/api/layouts/{filename}/tap-dances/{name}",
+            get(layout_collections::get_tap_dance)
+                .put(layout_collections::update_tap_dance)
+                .delete(layout_collections::delete_tap_dance),
+        )
+        .route(
+            "/api/layouts/{filename}/categories",
+            get(layout_collections::list_layout_categories)
+                .post(layout_collections::create_layout_category),
+        )
+        .route(
+            "/api/layouts/{filename}/categories/{id}",
+            get(layout_collections::get_layout_category)
+                .put(layout_collections::update_layout_category)
+                .delete(layout_collections::delete_layout_category),
+        )
         .route(
             "/api/layouts/{filename}/render-metadata",
             get(get_render_metadata),
@@ -3163,6 +3560,10 @@ pub fn create_router(state: AppState) -> Router {
             "/api/keyboards/{keyboard}/geometry/{layout}",
             get(get_geometry),
         )
+        .route(
+            "/api/keyboards/{keyboard}/geometry",
+            get(get_geometry_for_variant),
+        )
         // Keyboard & Setup Wizard endpoints
         .route("/api/keyboards", get(list_keyboards))
         .route(
@@ -3204,7 +3605,12 @@ pub fn create_router(state: AppState) -> Router {
             get(download_generate_zip),
         )
         .route("/api/generate/health", get(get_generate_health))
+        .route("/api/events", get(workspace_events))
         .layer(cors)
+        .layer(axum::middleware::from_fn_with_state(
+            request_metrics,
+            metrics::track_requests,
+        ))
         .layer(TraceLayer::new_for_http())
         .with_state(state)
         // Fallback for static files and SPA routing (must be last)
@@ -3219,21 +3625,43 @@ pub fn create_router(state: AppState) -> Router {
 /// * `config` - Application configuration
 /// * `workspace_root` - Directory containing layout files
 /// * `addr` - Socket address to bind to
+/// * `auth` - Access token options (see [`auth`])
+/// * `redact_logs` - Log route templates instead of request paths, hiding
+///   layout names
 ///
 /// # Errors
 ///
-/// Returns an error if the server fails to start.
+/// Returns an error if `addr` is not a loopback address and no token is set,
+/// if the port is already in use, or if the server fails to start.
 pub async fn run_server(
     config: Config,
     workspace_root: PathBuf,
     addr: SocketAddr,
+    auth: auth::AuthOptions,
+    redact_logs: bool,
 ) -> anyhow::Result<()> {
-    let state = AppState::new(config, workspace_root)?;
-    let app = create_router(state);
+    auth.check_bind(&addr)?;
+
+    let mut state = AppState::new(config, workspace_root)?;
+    if redact_logs {
+        state = state.with_redacted_logs();
+    }
+    let mut app = create_router(state);
+    if let Some(token) = auth.token() {
+        app = auth::protect(app, token);
+        info!("API requests require the access token");
+    }
 
     info!("Starting LazyQMK web server on {}", addr);
 
-    let listener = tokio::net::TcpListener::bind(addr).await?;
+    let listener = tokio::net::TcpListener::bind(addr)
+        .await
+        .map_err(|e| match e.kind() {
+            std::io::ErrorKind::AddrInUse => {
+                anyhow::anyhow!("Port {} is already in use on {}", addr.port(), addr.ip())
+            }
+            _ => anyhow::Error::new(e).context(format!("Failed to bind to {addr}")),
+        })?;
     axum::serve(listener, app).await?;
 
     Ok(())
@@ -0,0 +1,414 @@
+//! Workspace file watching for layout change notifications.
+//!
+//! A background thread polls the workspace directory for `.md` layout files
+//! and broadcasts `layout-added`, `layout-changed`, and `layout-removed`
+//! events. The web API streams them to clients over Server-Sent Events
+//! (`GET /api/events`), along with `build-finished` events published when a
+//! firmware build completes.
+//!
+//! ## Design
+//!
+//! - Files are fingerprinted by modification time and size
+//! - Changes are debounced per file: an event is emitted once the file has
+//!   been quiet for the debounce interval, so an editor's save sequence
+//!   produces a single event
+//! - A file created and deleted within the debounce interval emits nothing
+//! - Events are emitted in the order the files settled
+//! - The thread stops when the watcher is dropped
+
+use std::collections::HashMap;
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::Arc;
+use std::thread;
+use std::time::{Duration, Instant, SystemTime};
+
+use chrono::{DateTime, Utc};
+use serde::Serialize;
+use tokio::sync::broadcast;
+
+use crate::firmware::BuildCompletion;
+
+/// How often the workspace is scanned.
+const POLL_INTERVAL: Duration = Duration::from_millis(500);
+
+/// How long a file must be unchanged before its event is emitted.
+const DEBOUNCE: Duration = Duration::from_millis(250);
+
+/// Number of events buffered per subscriber before it starts lagging.
+const CHANNEL_CAPACITY: usize = 64;
+
+/// Kind of workspace change.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "kebab-case")]
+pub enum WorkspaceEventKind {
+    /// A layout file was created.
+    LayoutAdded,
+    /// A layout file was modified.
+    LayoutChanged,
+    /// A layout file was deleted.
+    LayoutRemoved,
+    /// A firmware build completed or failed.
+    BuildFinished,
+}
+
+impl WorkspaceEventKind {
+    /// Event name used on the wire (e.g., "layout-changed").
+    #[must_use]
+    pub const fn as_str(self) -> &'static str {
+        match self {
+            Self::LayoutAdded => "layout-added",
+            Self::LayoutChanged => "layout-changed",
+            Self::LayoutRemoved => "layout-removed",
+            Self::BuildFinished => "build-finished",
+        }
+    }
+}
+
+/// A change to a layout file in the workspace.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct WorkspaceEvent {
+    /// Kind of change.
+    #[serde(rename = "type")]
+    pub kind: WorkspaceEventKind,
+    /// Layout filename relative to the workspace root.
+    pub filename: String,
+    /// New modification time (RFC 3339), absent for removals.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub modified: Option<String>,
+    /// Build outcome, for `build-finished` events.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub build: Option<BuildCompletion>,
+    /// Whether the desktop app should show a notification.
+    #[serde(skip_serializing_if = "std::ops::Not::not")]
+    pub notify: bool,
+}
+
+impl WorkspaceEvent {
+    /// Event for a finished build of the layout in `completion`.
+    #[must_use]
+    pub fn build_finished(completion: BuildCompletion, notify: bool) -> Self {
+        Self {
+            kind: WorkspaceEventKind::BuildFinished,
+            filename: completion.layout.clone().unwrap_or_default(),
+            modified: None,
+            build: Some(completion),
+            notify,
+        }
+    }
+}
+
+/// Modification time and size of a layout file.
+type Fingerprint = (SystemTime, u64);
+
+/// A change waiting for its file to settle.
+struct PendingChange {
+    kind: WorkspaceEventKind,
+    last_seen: Instant,
+}
+
+/// Watches a workspace directory and broadcasts layout file changes.
+#[derive(Debug)]
+pub struct WorkspaceWatcher {
+    sender: broadcast::Sender<WorkspaceEvent>,
+    stop: Arc<AtomicBool>,
+}
+
+impl WorkspaceWatcher {
+    /// Starts watching `root` with the default poll and debounce intervals.
+    #[must_use]
+    pub fn start(root: PathBuf) -> Self {
+        Self::with_intervals(root, POLL_INTERVAL, DEBOUNCE)
+    }
+
+    /// Starts watching `root` with custom poll and debounce intervals.
+    #[must_use]
+    pub fn with_intervals(root: PathBuf, poll: Duration, debounce: Duration) -> Self {
+        let (sender, _) = broadcast::channel(CHANNEL_CAPACITY);
+        let stop = Arc::new(AtomicBool::new(false));
+
+        // Initial scan happens before returning so later changes are never missed
+        let mut known = scan(&root);
+
+        let thread_sender = sender.clone();
+        let thread_stop = Arc::clone(&stop);
+        thread::spawn(move || {
+            let mut pending: HashMap<String, PendingChange> = HashMap::new();
+
+            while !thread_stop.load(Ordering::Relaxed) {
+                thread::sleep(poll);
+
+                let current = scan(&root);
+                diff(&known, &current, &mut pending, Instant::now());
+                for event in settled(&mut pending, &current, debounce, Instant::now()) {
+                    // No subscribers is not an error
+                    let _ = thread_sender.send(event);
+                }
+                known = current;
+            }
+        });
+
+        Self { sender, stop }
+    }
+
+    /// Sends an event to every subscriber, alongside file changes.
+    pub fn publish(&self, event: WorkspaceEvent) {
+        // No subscribers is not an error
+        let _ = self.sender.send(event);
+    }
+
+    /// Subscribes to workspace events.
+    #[must_use]
+    pub fn subscribe(&self) -> broadcast::Receiver<WorkspaceEvent> {
+        self.sender.subscribe()
+    }
+}
+
+impl Drop for WorkspaceWatcher {
+    fn drop(&mut self) {
+        self.stop.store(true, Ordering::Relaxed);
+    }
+}
+
+/// Fingerprints all `.md` files directly inside `root`.
+fn scan(root: &Path) -> HashMap<String, Fingerprint> {
+    let Ok(entries) = fs::read_dir(root) else {
+        return HashMap::new();
+    };
+
+    entries
+        .filter_map(Result::ok)
+        .filter(|entry| entry.path().extension().is_some_and(|ext| ext == "md"))
+        .filter_map(|entry| {
+            let metadata = entry.metadata().ok()?;
+            if !metadata.is_file() {
+                return None;
+            }
+            let modified = metadata.modified().ok()?;
+            let filename = entry.file_name().to_string_lossy().to_string();
+            Some((filename, (modified, metadata.len())))
+        })
+        .collect()
+}
+
+/// Records changes between two scans as pending events.
+fn diff(
+    known: &HashMap<String, Fingerprint>,
+    current: &HashMap<String, Fingerprint>,
+    pending: &mut HashMap<String, PendingChange>,
+    now: Instant,
+) {
+    for (filename, fingerprint) in current {
+        let kind = match known.get(filename) {
+            None => match pending.get(filename).map(|p| p.kind) {
+                // Deleted and recreated before settling
+                Some(WorkspaceEventKind::LayoutRemoved) => WorkspaceEventKind::LayoutChanged,
+                _ => WorkspaceEventKind::LayoutAdded,
+            },
+            Some(previous) if previous != fingerprint => match pending.get(filename) {
+                Some(p) if p.kind == WorkspaceEventKind::LayoutAdded => p.kind,
+                _ => WorkspaceEventKind::LayoutChanged,
+            },
+            Some(_) => continue,
+        };
+        pending.insert(
+            filename.clone(),
+            PendingChange {
+                kind,
+                last_seen: now,
+            },
+        );
+    }
+
+    for filename in known.keys().filter(|f| !current.contains_key(*f)) {
+        let added_pending = pending
+            .get(filename)
+            .is_some_and(|p| p.kind == WorkspaceEventKind::LayoutAdded);
+        if added_pending {
+            // Created and deleted before settling
+            pending.remove(filename);
+        } else {
+            pending.insert(
+                filename.clone(),
+                PendingChange {
+                    kind: WorkspaceEventKind::LayoutRemoved,
+                    last_seen: now,
+                },
+            );
+        }
+    }
+}
+
+/// Removes and returns events for files that have been quiet for `debounce`.
+fn settled(
+    pending: &mut HashMap<String, PendingChange>,
+    current: &HashMap<String, Fingerprint>,
+    debounce: Duration,
+    now: Instant,
+) -> Vec<WorkspaceEvent> {
+    let mut ready: Vec<(Instant, String)> = pending
+        .iter()
+        .filter(|(_, change)| now.duration_since(change.last_seen) >= debounce)
+        .map(|(filename, change)| (change.last_seen, filename.clone()))
+        .collect();
+    ready.sort();
+
+    ready
+        .into_iter()
+        .filter_map(|(_, filename)| {
+            let change = pending.remove(&filename)?;
+            let modified = current
+                .get(&filename)
+                .map(|(modified, _)| DateTime::<Utc>::from(*modified).to_rfc3339());
+            Some(WorkspaceEvent {
+                kind: change.kind,
+                filename,
+                modified,
+                build: None,
+                notify: false,
+            })
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tempfile::TempDir;
+
+    const TEST_POLL: Duration = Duration::from_millis(20);
+    const TEST_DEBOUNCE: Duration = Duration::from_millis(80);
+
+    /// Waits for the next event, failing after a timeout.
+    fn next_event(rx: &mut broadcast::Receiver<WorkspaceEvent>) -> WorkspaceEvent {
+        let deadline = Instant::now() + Duration::from_secs(5);
+        loop {
+            match rx.try_recv() {
+                Ok(event) => return event,
+                Err(broadcast::error::TryRecvError::Empty) if Instant::now() < deadline => {
+                    thread::sleep(Duration::from_millis(10));
+                }
+                Err(e) => panic!("no workspace event received: {e:?}"),
+            }
+        }
+    }
+
+    /// Asserts that no event arrives within a few debounce intervals.
+    fn assert_quiet(rx: &mut broadcast::Receiver<WorkspaceEvent>) {
+        thread::sleep(TEST_DEBOUNCE * 3);
+        assert!(rx.try_recv().is_err(), "unexpected workspace event");
+    }
+
+    #[test]
+    fn test_create_modify_delete_sequence() {
+        let temp = TempDir::new().unwrap();
+        let watcher =
+            WorkspaceWatcher::with_intervals(temp.path().to_path_buf(), TEST_POLL, TEST_DEBOUNCE);
+        let mut rx = watcher.subscribe();
+        let path = temp.path().join("corne.md");
+
+        fs::write(&path, "# Corne").unwrap();
+        let added = next_event(&mut rx);
+        assert_eq!(added.kind, WorkspaceEventKind::LayoutAdded);
+        assert_eq!(added.filename, "corne.md");
+        assert!(added.modified.is_some());
+
+        fs::write(&path, "# Corne\n\nUpdated").unwrap();
+        let changed = next_event(&mut rx);
+        assert_eq!(changed.kind, WorkspaceEventKind::LayoutChanged);
+        assert_eq!(changed.filename, "corne.md");
+
+        fs::remove_file(&path).unwrap();
+        let removed = next_event(&mut rx);
+        assert_eq!(removed.kind, WorkspaceEventKind::LayoutRemoved);
+        assert_eq!(removed.filename, "corne.md");
+        assert!(removed.modified.is_none());
+    }
+
+    #[test]
+    fn test_rapid_writes_are_debounced() {
+        let temp = TempDir::new().unwrap();
+        let path = temp.path().join("corne.md");
+        fs::write(&path, "# Corne").unwrap();
+
+        let watcher =
+            WorkspaceWatcher::with_intervals(temp.path().to_path_buf(), TEST_POLL, TEST_DEBOUNCE);
+        let mut rx = watcher.subscribe();
+
+        for i in 0..5 {
+            fs::write(&path, format!("# Corne {}", "x".repeat(i + 1))).unwrap();
+            thread::sleep(TEST_POLL);
+        }
+
+        assert_eq!(next_event(&mut rx).kind, WorkspaceEventKind::LayoutChanged);
+        assert_quiet(&mut rx);
+    }
+
+    #[test]
+    fn test_ignores_non_layout_files() {
+        let temp = TempDir::new().unwrap();
+        let watcher =
+            WorkspaceWatcher::with_intervals(temp.path().to_path_buf(), TEST_POLL, TEST_DEBOUNCE);
+        let mut rx = watcher.subscribe();
+
+        fs::write(temp.path().join("notes.txt"), "hello").unwrap();
+        assert_quiet(&mut rx);
+    }
+
+    #[test]
+    fn test_diff_coalesces_pending_changes() {
+        let now = Instant::now();
+        let stamp = (SystemTime::UNIX_EPOCH, 1);
+        let empty = HashMap::new();
+        let one = HashMap::from([("a.md".to_string(), stamp)]);
+        let mut pending = HashMap::new();
+
+        // Added then removed before settling: nothing to report
+        diff(&empty, &one, &mut pending, now);
+        diff(&one, &empty, &mut pending, now);
+        assert!(pending.is_empty());
+
+        // Removed then recreated before settling: reported as a change
+        diff(&one, &empty, &mut pending, now);
+        diff(&empty, &one, &mut pending, now);
+        let events = settled(&mut pending, &one, Duration::ZERO, now);
+        assert_eq!(events.len(), 1);
+        assert_eq!(events[0].kind, WorkspaceEventKind::LayoutChanged);
+    }
+
+    #[test]
+    fn test_event_serialization() {
+        let event = WorkspaceEvent {
+            kind: WorkspaceEventKind::LayoutChanged,
+            filename: "corne.md".to_string(),
+            modified: Some("2024-01-01T00:00:00+00:00".to_string()),
+            build: None,
+            notify: false,
+        };
+        let json = serde_json::to_value(&event).unwrap();
+        assert_eq!(json["type"], "layout-changed");
+        assert_eq!(json["filename"], "corne.md");
+        assert!(json.get("build").is_none());
+        assert!(json.get("notify").is_none());
+    }
+
+    #[test]
+    fn test_published_build_event_reaches_subscribers() {
+        let temp = TempDir::new().unwrap();
+        let watcher = WorkspaceWatcher::start(temp.path().to_path_buf());
+        let mut rx = watcher.subscribe();
+
+        let completion = BuildCompletion::new(true, "crkbd:default", Duration::from_secs(42), None)
+            .with_layout("corne.md");
+        watcher.publish(WorkspaceEvent::build_finished(completion, true));
+
+        let event = next_event(&mut rx);
+        let json = serde_json::to_value(&event).unwrap();
+        assert_eq!(json["type"], "build-finished");
+        assert_eq!(json["filename"], "corne.md");
+        assert_eq!(json["build"]["success"], true);
+        assert_eq!(json["build"]["duration_ms"], 42_000);
+        assert_eq!(json["notify"], true);
+    }
+}
@@ -0,0 +1,137 @@
+#![allow(missing_docs)]
+//! Deleting a category from the category manager and restoring it with undo
+
+use crossterm::event::{KeyCode, KeyEvent, KeyModifiers};
+use lazyqmk::config::Config;
+use lazyqmk::models::{
+    Category, KeyDefinition, KeyboardGeometry, Layer, Layout, Position, RgbColor,
+    VisualLayoutMapping,
+};
+use lazyqmk::shortcuts::Action;
+use lazyqmk::tui::category_manager::ManagerMode;
+use lazyqmk::tui::handlers::{dispatch_action, handle_category_manager_input};
+use lazyqmk::tui::{ActiveComponent, AppState};
+
+/// Two layers with three categories; "nav" is used by keys on both layers
+/// and by the second layer itself.
+fn layout() -> Layout {
+    let mut layout = Layout::new("Categories").unwrap();
+    for (id, name) in [
+        ("alpha", "Alpha"),
+        ("nav", "Navigation"),
+        ("sym", "Symbols"),
+    ] {
+        layout
+            .add_category(Category::new(id, name, RgbColor::new(10, 20, 30)).unwrap())
+            .unwrap();
+    }
+    for index in 0..2u8 {
+        let mut layer = Layer::new(index, format!("Layer {index}"), RgbColor::default()).unwrap();
+        for col in 0..4 {
+            let mut key = KeyDefinition::new(Position::new(0, col), "KC_A");
+            key.category_id = match col {
+                0 | 2 => Some("nav".to_string()),
+                1 => Some("sym".to_string()),
+                _ => None,
+            };
+            layer.add_key(key).unwrap();
+        }
+        layout.add_layer(layer).unwrap();
+    }
+    layout.layers[1].category_id = Some("nav".to_string());
+    layout
+}
+
+fn state() -> AppState {
+    AppState::new(
+        layout(),
+        None,
+        KeyboardGeometry::new("test", "test", 1, 4),
+        VisualLayoutMapping::default(),
+        Config::default(),
+    )
+    .unwrap()
+}
+
+fn press(state: &mut AppState, code: KeyCode, modifiers: KeyModifiers) {
+    handle_category_manager_input(state, KeyEvent::new(code, modifiers)).unwrap();
+}
+
+/// Opens the category manager and deletes the "nav" category.
+fn delete_nav(state: &mut AppState) {
+    state.open_category_manager();
+    press(state, KeyCode::Down, KeyModifiers::NONE);
+    press(state, KeyCode::Char('d'), KeyModifiers::NONE);
+}
+
+#[test]
+fn test_delete_confirmation_counts_affected_keys_and_layers() {
+    let mut state = state();
+    delete_nav(&mut state);
+
+    let Some(ActiveComponent::CategoryManager(manager)) = &state.active_component else {
+        panic!("category manager closed");
+    };
+    let ManagerMode::ConfirmingDelete { category_id, usage } = &manager.state().mode else {
+        panic!("not confirming deletion");
+    };
+    assert_eq!(category_id, "nav");
+    assert_eq!((usage.keys, usage.layers), (4, 1));
+}
+
+#[test]
+fn test_undo_restores_deleted_category_and_assignments() {
+    let mut state = state();
+    let original = state.layout.clone();
+
+    delete_nav(&mut state);
+    press(&mut state, KeyCode::Char('y'), KeyModifiers::NONE);
+    assert!(state.layout.get_category("nav").is_none());
+    assert_eq!(state.layout.category_usage("nav").keys, 0);
+    assert_eq!(state.layout.layers[1].category_id, None);
+
+    // Close the manager and undo from the main view
+    press(&mut state, KeyCode::Esc, KeyModifiers::NONE);
+    dispatch_action(&mut state, Action::Undo).unwrap();
+
+    assert_eq!(state.layout, original);
+    assert!(!state.undo_history.can_undo());
+    assert!(state.undo_history.can_redo());
+}
+
+#[test]
+fn test_undo_inside_category_manager() {
+    let mut state = state();
+    let original = state.layout.clone();
+
+    delete_nav(&mut state);
+    press(&mut state, KeyCode::Char('y'), KeyModifiers::NONE);
+    press(&mut state, KeyCode::Char('z'), KeyModifiers::CONTROL);
+
+    assert_eq!(state.layout, original);
+    assert!(matches!(
+        state.active_component,
+        Some(ActiveComponent::CategoryManager(_))
+    ));
+}
+
+#[test]
+fn test_toggle_rgb_flags_in_category_manager() {
+    let mut state = state();
+    state.open_category_manager();
+    press(&mut state, KeyCode::Down, KeyModifiers::NONE);
+    press(&mut state, KeyCode::Char('o'), KeyModifiers::NONE);
+    press(&mut state, KeyCode::Char('b'), KeyModifiers::NONE);
+
+    let nav = state.layout.get_category("nav").unwrap();
+    assert!(nav.always_on && nav.breathing);
+    assert!(state.dirty);
+    assert!(!state.layout.get_category("alpha").unwrap().always_on);
+
+    press(&mut state, KeyCode::Char('o'), KeyModifiers::NONE);
+    assert!(!state.layout.get_category("nav").unwrap().always_on);
+    assert_eq!(
+        state.action_log.entries().last().unwrap().summary,
+        "Category 'Navigation': always-on off"
+    );
+}
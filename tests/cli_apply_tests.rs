@@ -0,0 +1,210 @@
+//! End-to-end tests for `lazyqmk apply` command.
+
+use lazyqmk::parser::layout::parse_markdown_layout_str;
+use lazyqmk::parser::parse_markdown_layout;
+use serde_json::Value;
+use std::fs;
+use std::path::Path;
+use std::process::{Command, Stdio};
+
+mod fixtures;
+use fixtures::*;
+
+/// Path to the lazyqmk binary (set by cargo at compile time)
+fn lazyqmk_bin() -> &'static str {
+    env!("CARGO_BIN_EXE_lazyqmk")
+}
+
+/// Writes an operations file next to the layout and returns its path
+fn write_ops(dir: &Path, ops: &str) -> std::path::PathBuf {
+    let path = dir.join("ops.json");
+    fs::write(&path, ops).expect("Failed to write ops file");
+    path
+}
+
+#[test]
+fn test_apply_success_json() {
+    let layout = test_layout_basic(2, 3);
+    let (layout_path, temp_dir) = create_temp_layout_file(&layout);
+    let ops_path = write_ops(
+        temp_dir.path(),
+        r##"[
+            {"op": "set_key", "layer": 0, "row": 0, "col": 0, "keycode": "KC_ESC"},
+            {"op": "add_category", "id": "nav", "name": "Navigation", "color": "#00FF00"},
+            {"op": "assign_category", "layer": 1, "category": "nav"},
+            {"op": "add_layer", "name": "Extra", "color": "#123456"}
+        ]"##,
+    );
+
+    let output = Command::new(lazyqmk_bin())
+        .args([
+            "apply",
+            "--layout",
+            layout_path.to_str().unwrap(),
+            "--ops",
+            ops_path.to_str().unwrap(),
+            "--json",
+        ])
+        .output()
+        .expect("Failed to execute command");
+
+    assert_eq!(
+        output.status.code(),
+        Some(0),
+        "stderr: {}",
+        String::from_utf8_lossy(&output.stderr)
+    );
+
+    let response: Value = serde_json::from_slice(&output.stdout).expect("Invalid JSON output");
+    assert_eq!(response["success"], true);
+    assert_eq!(response["applied"], 4);
+    assert_eq!(response["results"][0]["status"], "ok");
+    assert_eq!(response["results"][3]["op"], "add_layer");
+
+    let updated = parse_markdown_layout(&layout_path).unwrap();
+    assert_eq!(updated.layers[0].keys[0].keycode, "KC_ESC");
+    assert_eq!(updated.layers[1].category_id.as_deref(), Some("nav"));
+    assert_eq!(updated.layers.len(), 3);
+    assert_eq!(updated.layers[2].name, "Extra");
+}
+
+#[test]
+fn test_apply_failure_leaves_file_untouched() {
+    let layout = test_layout_basic(2, 3);
+    let (layout_path, temp_dir) = create_temp_layout_file(&layout);
+    let original = fs::read_to_string(&layout_path).unwrap();
+    let ops_path = write_ops(
+        temp_dir.path(),
+        r#"[
+            {"op": "set_key", "layer": 0, "row": 0, "col": 0, "keycode": "KC_ESC"},
+            {"op": "set_key", "layer": 9, "row": 0, "col": 0, "keycode": "KC_A"}
+        ]"#,
+    );
+
+    let output = Command::new(lazyqmk_bin())
+        .args([
+            "apply",
+            "--layout",
+            layout_path.to_str().unwrap(),
+            "--ops",
+            ops_path.to_str().unwrap(),
+            "--json",
+        ])
+        .output()
+        .expect("Failed to execute command");
+
+    assert_eq!(output.status.code(), Some(1));
+
+    let response: Value = serde_json::from_slice(&output.stdout).expect("Invalid JSON output");
+    assert_eq!(response["success"], false);
+    assert_eq!(response["results"][0]["status"], "ok");
+    assert_eq!(response["results"][1]["status"], "error");
+
+    assert_eq!(fs::read_to_string(&layout_path).unwrap(), original);
+}
+
+#[test]
+fn test_apply_invalid_ops_file() {
+    let layout = test_layout_basic(2, 3);
+    let (layout_path, temp_dir) = create_temp_layout_file(&layout);
+    let ops_path = write_ops(temp_dir.path(), "not json");
+
+    let output = Command::new(lazyqmk_bin())
+        .args([
+            "apply",
+            "--layout",
+            layout_path.to_str().unwrap(),
+            "--ops",
+            ops_path.to_str().unwrap(),
+        ])
+        .output()
+        .expect("Failed to execute command");
+
+    assert_eq!(output.status.code(), Some(1));
+    let stderr = String::from_utf8_lossy(&output.stderr);
+    assert!(stderr.contains("JSON array"), "stderr: {stderr}");
+}
+
+#[test]
+fn test_apply_missing_ops_file() {
+    let layout = test_layout_basic(2, 3);
+    let (layout_path, temp_dir) = create_temp_layout_file(&layout);
+
+    let output = Command::new(lazyqmk_bin())
+        .args([
+            "apply",
+            "--layout",
+            layout_path.to_str().unwrap(),
+            "--ops",
+            temp_dir.path().join("missing.json").to_str().unwrap(),
+        ])
+        .output()
+        .expect("Failed to execute command");
+
+    assert_eq!(output.status.code(), Some(2));
+}
+
+#[test]
+fn test_apply_pipe_chain() {
+    let layout = test_layout_basic(2, 3);
+    let (layout_path, temp_dir) = create_temp_layout_file(&layout);
+    let original = fs::read_to_string(&layout_path).unwrap();
+    let first_ops = temp_dir.path().join("first.json");
+    fs::write(
+        &first_ops,
+        r#"[{"op": "set_key", "layer": 0, "row": 0, "col": 0, "keycode": "KC_ESC"}]"#,
+    )
+    .unwrap();
+    let second_ops = temp_dir.path().join("second.json");
+    fs::write(&second_ops, r#"[{"op": "add_layer", "name": "Piped"}]"#).unwrap();
+
+    // lazyqmk apply --layout <file> --output - | lazyqmk apply --layout -
+    let mut first = Command::new(lazyqmk_bin())
+        .args([
+            "apply",
+            "--layout",
+            layout_path.to_str().unwrap(),
+            "--ops",
+            first_ops.to_str().unwrap(),
+            "--output",
+            "-",
+        ])
+        .stdout(Stdio::piped())
+        .stderr(Stdio::piped())
+        .spawn()
+        .expect("Failed to spawn first command");
+
+    let second = Command::new(lazyqmk_bin())
+        .args([
+            "apply",
+            "--layout",
+            "-",
+            "--ops",
+            second_ops.to_str().unwrap(),
+        ])
+        .stdin(first.stdout.take().expect("Missing stdout"))
+        .output()
+        .expect("Failed to execute second command");
+
+    assert!(first.wait().unwrap().success());
+    assert_eq!(
+        second.status.code(),
+        Some(0),
+        "stderr: {}",
+        String::from_utf8_lossy(&second.stderr)
+    );
+
+    let stdout = String::from_utf8(second.stdout).unwrap();
+    let piped = parse_markdown_layout_str(&stdout).expect("Piped output should parse");
+    assert_eq!(piped.layers[0].keys[0].keycode, "KC_ESC");
+    assert_eq!(piped.layers.len(), 3);
+    assert_eq!(piped.layers[2].name, "Piped");
+
+    // Informational output goes to stderr and the source file is untouched
+    let stderr = String::from_utf8_lossy(&second.stderr);
+    assert!(
+        stderr.contains("Applied 1 operation(s) to stdout"),
+        "stderr: {stderr}"
+    );
+    assert_eq!(fs::read_to_string(&layout_path).unwrap(), original);
+}
@@ -0,0 +1,289 @@
+//! End-to-end tests for `lazyqmk build` command.
+//!
+//! These use `--dry-run`, which resolves the keyboard/keymap and build
+//! backend without running `qmk compile` or `make`.
+
+use std::path::Path;
+use std::process::{Command, Output};
+
+mod fixtures;
+use fixtures::*;
+
+/// Path to the lazyqmk binary
+fn lazyqmk_bin() -> &'static str {
+    env!("CARGO_BIN_EXE_lazyqmk")
+}
+
+/// Runs `lazyqmk build --dry-run` with an isolated config directory.
+fn dry_run(layout_path: &Path, config_dir: &Path, extra: &[&str]) -> Output {
+    Command::new(lazyqmk_bin())
+        .env("LAZYQMK_CONFIG_DIR", config_dir)
+        .args([
+            "build",
+            "--dry-run",
+            "--layout",
+            layout_path.to_str().unwrap(),
+        ])
+        .args(extra)
+        .output()
+        .expect("Failed to execute command")
+}
+
+/// Sets build fallbacks in the config in `config_dir` via `config set`.
+fn write_fallback_config(config_dir: &Path) {
+    let output = Command::new(lazyqmk_bin())
+        .env("LAZYQMK_CONFIG_DIR", config_dir)
+        .args([
+            "config",
+            "set",
+            "--build-keyboard",
+            "planck/rev6",
+            "--build-keymap",
+            "global",
+        ])
+        .output()
+        .expect("Failed to execute command");
+    assert_eq!(
+        output.status.code(),
+        Some(0),
+        "stderr: {}",
+        String::from_utf8_lossy(&output.stderr)
+    );
+}
+
+#[test]
+fn test_build_layout_metadata_beats_config() {
+    let layout = test_layout_basic(2, 3);
+    let (layout_path, temp_dir) = create_temp_layout_file(&layout);
+    write_fallback_config(temp_dir.path());
+
+    let output = dry_run(
+        &layout_path,
+        temp_dir.path(),
+        &["--allow-keyboard-mismatch"],
+    );
+
+    let stdout = String::from_utf8_lossy(&output.stdout);
+    assert_eq!(
+        output.status.code(),
+        Some(0),
+        "stderr: {}",
+        String::from_utf8_lossy(&output.stderr)
+    );
+    assert!(stdout.contains("Keyboard: test_keyboard (layout)"));
+    assert!(stdout.contains("Keymap: test_keymap (layout)"));
+
+    let output = dry_run(
+        &layout_path,
+        temp_dir.path(),
+        &["--backend", "qmk", "--allow-keyboard-mismatch"],
+    );
+    let stdout = String::from_utf8_lossy(&output.stdout);
+    assert!(stdout.contains("Backend: qmk CLI (qmk)"));
+    assert!(stdout.contains("qmk compile -kb test_keyboard -km test_keymap"));
+}
+
+#[test]
+fn test_build_backend_from_config_and_flag() {
+    let layout = test_layout_basic(2, 3);
+    let (layout_path, temp_dir) = create_temp_layout_file(&layout);
+    let output = Command::new(lazyqmk_bin())
+        .env("LAZYQMK_CONFIG_DIR", temp_dir.path())
+        .args(["config", "set", "--build-backend", "make"])
+        .output()
+        .expect("Failed to execute command");
+    assert_eq!(output.status.code(), Some(0));
+
+    let output = dry_run(&layout_path, temp_dir.path(), &[]);
+    let stdout = String::from_utf8_lossy(&output.stdout);
+    assert!(stdout.contains("Backend: make (make)"), "{stdout}");
+    assert!(stdout.contains("Command: make test_keyboard:test_keymap -j"));
+
+    let output = dry_run(&layout_path, temp_dir.path(), &["--backend", "qmk"]);
+    let stdout = String::from_utf8_lossy(&output.stdout);
+    assert!(stdout.contains("Command: qmk compile -kb test_keyboard"));
+
+    let output = dry_run(&layout_path, temp_dir.path(), &["--backend", "ninja"]);
+    assert_eq!(output.status.code(), Some(2));
+}
+
+#[test]
+fn test_build_overrides_beat_layout_metadata() {
+    let layout = test_layout_basic(2, 3);
+    let (layout_path, temp_dir) = create_temp_layout_file(&layout);
+    write_fallback_config(temp_dir.path());
+
+    let output = dry_run(
+        &layout_path,
+        temp_dir.path(),
+        &[
+            "--keyboard",
+            "crkbd/rev1",
+            "--keymap",
+            "cli",
+            "--allow-keyboard-mismatch",
+        ],
+    );
+
+    let stdout = String::from_utf8_lossy(&output.stdout);
+    assert_eq!(output.status.code(), Some(0));
+    assert!(stdout.contains("Keyboard: crkbd/rev1 (override)"));
+    assert!(stdout.contains("Keymap: cli (override)"));
+}
+
+#[test]
+fn test_build_refuses_keyboard_mismatch() {
+    let layout = test_layout_basic(2, 3);
+    let (layout_path, temp_dir) = create_temp_layout_file(&layout);
+
+    // Against an override
+    let output = dry_run(&layout_path, temp_dir.path(), &["--keyboard", "crkbd"]);
+    let stderr = String::from_utf8_lossy(&output.stderr);
+    assert_eq!(output.status.code(), Some(1), "stderr: {stderr}");
+    assert!(stderr.contains("'test_keyboard'"), "{stderr}");
+    assert!(stderr.contains("'crkbd' (override)"), "{stderr}");
+    assert!(stderr.contains("--allow-keyboard-mismatch"), "{stderr}");
+
+    // Against a stale build.keyboard in the config
+    write_fallback_config(temp_dir.path());
+    let output = dry_run(&layout_path, temp_dir.path(), &[]);
+    let stderr = String::from_utf8_lossy(&output.stderr);
+    assert_eq!(output.status.code(), Some(1), "stderr: {stderr}");
+    assert!(stderr.contains("'planck/rev6' (config)"), "{stderr}");
+    assert!(
+        stderr.contains("keyboards/test_keyboard/keymaps/test_keymap"),
+        "{stderr}"
+    );
+
+    // Confirmed: warns and continues
+    let output = dry_run(
+        &layout_path,
+        temp_dir.path(),
+        &["--allow-keyboard-mismatch"],
+    );
+    assert_eq!(output.status.code(), Some(0));
+    assert!(String::from_utf8_lossy(&output.stderr).starts_with("Warning: Layout is for keyboard"));
+}
+
+#[test]
+fn test_build_falls_back_to_config_then_default() {
+    let mut layout = test_layout_basic(2, 3);
+    layout.metadata.keyboard = None;
+    layout.metadata.keymap_name = None;
+    let (layout_path, temp_dir) = create_temp_layout_file(&layout);
+    write_fallback_config(temp_dir.path());
+
+    let output = dry_run(&layout_path, temp_dir.path(), &[]);
+    let stdout = String::from_utf8_lossy(&output.stdout);
+    assert_eq!(output.status.code(), Some(0));
+    assert!(stdout.contains("Keyboard: planck/rev6 (config)"));
+    assert!(stdout.contains("Keymap: global (config)"));
+
+    // Without config fallbacks the keymap defaults to "default"
+    std::fs::remove_file(temp_dir.path().join("config.toml")).unwrap();
+    let output = dry_run(&layout_path, temp_dir.path(), &["--keyboard", "crkbd"]);
+    let stdout = String::from_utf8_lossy(&output.stdout);
+    assert_eq!(output.status.code(), Some(0));
+    assert!(stdout.contains("Keymap: default (default)"));
+}
+
+#[test]
+fn test_build_project_file_beats_config_but_not_flags() {
+    let mut layout = test_layout_basic(2, 3);
+    layout.metadata.keyboard = None;
+    layout.metadata.keymap_name = None;
+    let (layout_path, temp_dir) = create_temp_layout_file(&layout);
+    write_fallback_config(temp_dir.path());
+    std::fs::write(
+        temp_dir.path().join(".lazyqmk.toml"),
+        "[build]\nkeymap = \"project\"\n",
+    )
+    .unwrap();
+
+    let output = dry_run(&layout_path, temp_dir.path(), &[]);
+    let stdout = String::from_utf8_lossy(&output.stdout);
+    assert_eq!(
+        output.status.code(),
+        Some(0),
+        "stderr: {}",
+        String::from_utf8_lossy(&output.stderr)
+    );
+    assert!(stdout.contains("Keyboard: planck/rev6 (config)"));
+    assert!(stdout.contains("Keymap: project (config)"));
+
+    let output = dry_run(&layout_path, temp_dir.path(), &["--keymap", "cli"]);
+    let stdout = String::from_utf8_lossy(&output.stdout);
+    assert!(stdout.contains("Keymap: cli (override)"));
+}
+
+#[test]
+fn test_build_without_any_keyboard_fails() {
+    let mut layout = test_layout_basic(2, 3);
+    layout.metadata.keyboard = None;
+    let (layout_path, temp_dir) = create_temp_layout_file(&layout);
+
+    let output = dry_run(&layout_path, temp_dir.path(), &[]);
+
+    assert_eq!(output.status.code(), Some(1));
+    assert!(String::from_utf8_lossy(&output.stderr).contains("No keyboard set"));
+}
+
+#[test]
+fn test_build_rejects_unsafe_keymap_override() {
+    let layout = test_layout_basic(2, 3);
+    let (layout_path, temp_dir) = create_temp_layout_file(&layout);
+
+    let output = dry_run(&layout_path, temp_dir.path(), &["--keymap", "../../evil"]);
+
+    assert_eq!(output.status.code(), Some(1));
+    assert!(String::from_utf8_lossy(&output.stderr).contains("path traversal"));
+}
+
+#[test]
+fn test_build_passes_converter_to_qmk_compile() {
+    let mut layout = test_layout_basic(2, 3);
+    layout.metadata.converter = Some("rp2040_ce".to_string());
+    let (layout_path, temp_dir) = create_temp_layout_file(&layout);
+
+    let output = dry_run(&layout_path, temp_dir.path(), &["--backend", "qmk"]);
+    let stdout = String::from_utf8_lossy(&output.stdout);
+    assert_eq!(output.status.code(), Some(0));
+    assert!(stdout.contains("Converter: rp2040_ce (layout)"));
+    assert!(
+        stdout.contains("qmk compile -kb test_keyboard -km test_keymap -e CONVERT_TO=rp2040_ce")
+    );
+    assert!(String::from_utf8_lossy(&output.stderr).is_empty());
+
+    // Unknown converters warn but still build
+    let output = dry_run(
+        &layout_path,
+        temp_dir.path(),
+        &["--converter", "rp2040_cee"],
+    );
+    assert_eq!(output.status.code(), Some(0));
+    assert!(String::from_utf8_lossy(&output.stdout).contains("Converter: rp2040_cee (override)"));
+    assert!(String::from_utf8_lossy(&output.stderr).contains("Unknown converter 'rp2040_cee'"));
+
+    // Names that can't be passed to make are rejected
+    let output = dry_run(&layout_path, temp_dir.path(), &["--converter", "x y"]);
+    assert_eq!(output.status.code(), Some(1));
+}
+
+#[test]
+fn test_build_fails_fast_without_qmk_tree() {
+    let layout = test_layout_basic(2, 3);
+    let (layout_path, temp_dir) = create_temp_layout_file(&layout);
+    let qmk_path = temp_dir.path().join("qmk_firmware");
+
+    let output = Command::new(lazyqmk_bin())
+        .env("LAZYQMK_CONFIG_DIR", temp_dir.path())
+        .args(["build", "--layout", layout_path.to_str().unwrap()])
+        .args(["--qmk-path", qmk_path.to_str().unwrap()])
+        .output()
+        .expect("Failed to execute command");
+
+    let stderr = String::from_utf8_lossy(&output.stderr);
+    assert_eq!(output.status.code(), Some(1), "stderr: {stderr}");
+    assert!(stderr.contains("is not a QMK firmware tree"), "{stderr}");
+    assert!(stderr.contains("--skip-env-check"), "{stderr}");
+}
@@ -476,6 +476,71 @@ fn test_category_delete_nonexistent_fails() {
     );
 }
 
+// ============================================================================
+// Colorize Command Tests
+// ============================================================================
+
+#[test]
+fn test_category_colorize_okabe_ito() {
+    let layout = test_layout_with_categories();
+    let (layout_path, _temp_dir) = create_temp_layout_file(&layout);
+
+    let output = Command::new(lazyqmk_bin())
+        .args([
+            "category",
+            "colorize",
+            "--layout",
+            layout_path.to_str().unwrap(),
+            "--palette",
+            "okabe-ito",
+        ])
+        .output()
+        .expect("Failed to execute command");
+    assert_eq!(
+        output.status.code(),
+        Some(0),
+        "stderr: {}",
+        String::from_utf8_lossy(&output.stderr)
+    );
+
+    let output = Command::new(lazyqmk_bin())
+        .args([
+            "category",
+            "list",
+            "--layout",
+            layout_path.to_str().unwrap(),
+            "--json",
+        ])
+        .output()
+        .expect("Failed to execute command");
+    let result: ListCategoriesResponse =
+        serde_json::from_slice(&output.stdout).expect("Should parse JSON output");
+    let colors: Vec<&str> = result.categories.iter().map(|c| c.color.as_str()).collect();
+    assert_eq!(colors[0], "#E69F00");
+    assert_eq!(colors[1], "#56B4E9");
+}
+
+#[test]
+fn test_category_colorize_unknown_palette() {
+    let layout = test_layout_with_categories();
+    let (layout_path, _temp_dir) = create_temp_layout_file(&layout);
+
+    let output = Command::new(lazyqmk_bin())
+        .args([
+            "category",
+            "colorize",
+            "--layout",
+            layout_path.to_str().unwrap(),
+            "--palette",
+            "rainbow",
+        ])
+        .output()
+        .expect("Failed to execute command");
+
+    assert_ne!(output.status.code(), Some(0));
+    assert!(String::from_utf8_lossy(&output.stderr).contains("Unknown palette"));
+}
+
 // ============================================================================
 // File Error Tests
 // ============================================================================
@@ -342,6 +342,99 @@ fn test_config_set_multiple_values() {
     );
 }
 
+#[test]
+fn test_config_set_build_converter() {
+    let _lock = CONFIG_TEST_LOCK.lock().unwrap();
+    let temp_dir = tempfile::TempDir::new().expect("Failed to create temp dir");
+    let config_dir = temp_dir.path().to_path_buf();
+
+    let mut cmd = isolated_config_command(
+        &["config", "set", "--build-converter", "rp2040_ce"],
+        &config_dir,
+    );
+    let output = cmd.output().expect("Failed to execute command");
+    assert_eq!(output.status.code(), Some(0));
+    assert!(String::from_utf8_lossy(&output.stderr).is_empty());
+
+    let mut cmd = isolated_config_command(&["config", "show", "--json"], &config_dir);
+    let output = cmd.output().expect("Failed to execute command");
+    let result: serde_json::Value =
+        serde_json::from_str(&String::from_utf8_lossy(&output.stdout)).unwrap();
+    assert_eq!(result["build"]["converter"].as_str(), Some("rp2040_ce"));
+
+    let mut cmd = isolated_config_command(&["config", "show"], &config_dir);
+    let output = cmd.output().expect("Failed to execute command");
+    assert!(String::from_utf8_lossy(&output.stdout).contains("Converter: rp2040_ce"));
+
+    // Unknown converters are saved with a warning
+    let mut cmd = isolated_config_command(
+        &["config", "set", "--build-converter", "my_board"],
+        &config_dir,
+    );
+    let output = cmd.output().expect("Failed to execute command");
+    assert_eq!(output.status.code(), Some(0));
+    assert!(String::from_utf8_lossy(&output.stderr).contains("Unknown converter 'my_board'"));
+}
+
+#[test]
+fn test_config_set_keyboard_defaults() {
+    let temp_dir = tempfile::TempDir::new().unwrap();
+    let config_dir = temp_dir.path().to_path_buf();
+
+    let output = isolated_config_command(
+        &[
+            "config",
+            "set",
+            "--keyboard",
+            "crkbd",
+            "--default-variant",
+            "LAYOUT_split_3x6_3",
+            "--keymap-pattern",
+            "{user}_{layout}",
+            "--layer-count",
+            "4",
+        ],
+        &config_dir,
+    )
+    .output()
+    .expect("Failed to execute command");
+    assert_eq!(
+        output.status.code(),
+        Some(0),
+        "stderr: {}",
+        String::from_utf8_lossy(&output.stderr)
+    );
+
+    let output = isolated_config_command(&["config", "show"], &config_dir)
+        .output()
+        .expect("Failed to execute command");
+    let stdout = String::from_utf8_lossy(&output.stdout);
+    assert!(stdout.contains("Keyboard Defaults:"), "{stdout}");
+    assert!(stdout.contains("crkbd:"));
+    assert!(stdout.contains("Default Variant: LAYOUT_split_3x6_3"));
+    assert!(stdout.contains("Keymap Pattern: {user}_{layout}"));
+    assert!(stdout.contains("Layer Count: 4"));
+
+    let output = isolated_config_command(&["config", "show", "--json"], &config_dir)
+        .output()
+        .expect("Failed to execute command");
+    let json: serde_json::Value = serde_json::from_slice(&output.stdout).unwrap();
+    assert_eq!(
+        json["keyboards"]["crkbd"]["default_variant"],
+        "LAYOUT_split_3x6_3"
+    );
+    assert_eq!(json["keyboards"]["crkbd"]["layer_count"], 4);
+
+    // Keyboard options need a keyboard
+    let output = isolated_config_command(
+        &["config", "set", "--default-variant", "LAYOUT"],
+        &config_dir,
+    )
+    .output()
+    .expect("Failed to execute command");
+    assert_ne!(output.status.code(), Some(0));
+}
+
 // ============================================================================
 // Error Cases
 // ============================================================================
@@ -379,3 +472,139 @@ fn test_config_show_json_valid() {
     let _result: serde_json::Value =
         serde_json::from_str(&stdout).expect("JSON output should be valid");
 }
+
+// ============================================================================
+// Project Configuration Tests
+// ============================================================================
+
+/// A project with a `.lazyqmk.toml` at its root and a layout two levels down
+fn project_dir(project_toml: &str) -> (tempfile::TempDir, PathBuf) {
+    let temp = tempfile::TempDir::new().unwrap();
+    let layouts = temp.path().join("project").join("layouts").join("split");
+    std::fs::create_dir_all(&layouts).unwrap();
+    std::fs::write(
+        temp.path().join("project").join(".lazyqmk.toml"),
+        project_toml,
+    )
+    .unwrap();
+    let layout = layouts.join("corne.md");
+    std::fs::write(&layout, "").unwrap();
+    (temp, layout)
+}
+
+/// Runs `config show --json --layout <layout>` and parses the output.
+fn show_for_layout(
+    config_dir: &PathBuf,
+    layout: &std::path::Path,
+    env: &[(&str, &str)],
+) -> serde_json::Value {
+    let mut cmd = isolated_config_command(
+        &[
+            "config",
+            "show",
+            "--json",
+            "--layout",
+            layout.to_str().unwrap(),
+        ],
+        config_dir,
+    );
+    cmd.env_remove("LAZYQMK_QMK_FIRMWARE")
+        .env_remove("LAZYQMK_OUTPUT_DIR")
+        .env_remove("LAZYQMK_TEMPLATE_DIR");
+    for (key, value) in env {
+        cmd.env(key, value);
+    }
+    let output = cmd.output().expect("Failed to execute command");
+    assert_eq!(
+        output.status.code(),
+        Some(0),
+        "stderr: {}",
+        String::from_utf8_lossy(&output.stderr)
+    );
+    serde_json::from_slice(&output.stdout).expect("Should parse JSON output")
+}
+
+#[test]
+fn test_config_show_project_file_precedence() {
+    let (temp, layout) = project_dir(
+        "[build]\noutput_dir = \"/project/out\"\ntemplate_dir = \"/project/templates\"\nkeymap = \"project\"\n\n[lint]\ndisable = [\"L003\"]\n",
+    );
+    let config_dir = temp.path().join("config");
+    let output = isolated_config_command(
+        &[
+            "config",
+            "set",
+            "--output-dir",
+            temp.path().join("user-out").to_str().unwrap(),
+            "--build-keyboard",
+            "crkbd",
+            "--build-keymap",
+            "user",
+        ],
+        &config_dir,
+    )
+    .output()
+    .unwrap();
+    assert_eq!(output.status.code(), Some(0));
+
+    // Project values override the user config; unset ones are kept
+    let result = show_for_layout(&config_dir, &layout, &[]);
+    assert_eq!(result["build"]["output_dir"], "/project/out");
+    assert_eq!(result["build"]["keymap"], "project");
+    assert_eq!(result["build"]["keyboard"], "crkbd");
+    assert_eq!(result["lint_disable"], serde_json::json!(["L003"]));
+    assert_eq!(result["sources"]["build.output_dir"], "project");
+    assert_eq!(result["sources"]["build.keymap"], "project");
+    assert!(result["sources"]["build.keyboard"].is_null());
+    assert!(result["project_file"]
+        .as_str()
+        .unwrap()
+        .ends_with(".lazyqmk.toml"));
+
+    // The environment overrides the project file
+    let result = show_for_layout(
+        &config_dir,
+        &layout,
+        &[("LAZYQMK_TEMPLATE_DIR", "/env/templates")],
+    );
+    assert_eq!(result["build"]["template_dir"], "/env/templates");
+    assert_eq!(result["sources"]["build.template_dir"], "environment");
+    assert_eq!(result["build"]["output_dir"], "/project/out");
+
+    // Outside the project only the user config applies
+    let result = show_for_layout(&config_dir, temp.path(), &[]);
+    assert_eq!(result["build"]["keymap"], "user");
+    assert!(result.get("sources").is_none());
+}
+
+#[test]
+fn test_config_show_resolves_project_paths_relative_to_file() {
+    let (temp, layout) =
+        project_dir("[build]\noutput_dir = \"firmware\"\ntemplate_dir = \"../shared/templates\"\n");
+    let config_dir = temp.path().join("config");
+    let project = temp.path().join("project");
+
+    let result = show_for_layout(&config_dir, &layout, &[]);
+    assert_eq!(
+        PathBuf::from(result["build"]["output_dir"].as_str().unwrap()),
+        project.join("firmware")
+    );
+    assert_eq!(
+        PathBuf::from(result["build"]["template_dir"].as_str().unwrap()),
+        project.join("../shared/templates")
+    );
+}
+
+#[test]
+fn test_config_show_rejects_unknown_project_keys() {
+    let (temp, layout) = project_dir("[build]\noutput_directory = \"firmware\"\n");
+    let output = isolated_config_command(
+        &["config", "show", "--layout", layout.to_str().unwrap()],
+        &temp.path().join("config"),
+    )
+    .output()
+    .unwrap();
+
+    assert_eq!(output.status.code(), Some(1));
+    assert!(String::from_utf8_lossy(&output.stderr).contains(".lazyqmk.toml"));
+}
@@ -0,0 +1,95 @@
+//! End-to-end tests for `lazyqmk convert`.
+
+use std::path::Path;
+use std::process::{Command, Output};
+
+mod fixtures;
+use fixtures::*;
+use lazyqmk::services::LayoutService;
+
+/// Path to the lazyqmk binary
+fn lazyqmk_bin() -> &'static str {
+    env!("CARGO_BIN_EXE_lazyqmk")
+}
+
+fn convert(input: &Path, output: &Path, args: &[&str]) -> Output {
+    Command::new(lazyqmk_bin())
+        .arg("convert")
+        .arg(input)
+        .arg(output)
+        .args(args)
+        .output()
+        .expect("Failed to execute command")
+}
+
+fn assert_success(output: &Output) {
+    assert_eq!(
+        output.status.code(),
+        Some(0),
+        "stderr: {}",
+        String::from_utf8_lossy(&output.stderr)
+    );
+}
+
+#[test]
+fn test_convert_markdown_to_json_and_back() {
+    let (md_path, temp_dir) = create_temp_layout_file(&test_layout_basic(2, 3));
+    let original = LayoutService::load(&md_path).unwrap();
+    let json_path = temp_dir.path().join("layout.lazyqmk.json");
+
+    let output = convert(&md_path, &json_path, &[]);
+    assert_success(&output);
+    let stdout = String::from_utf8_lossy(&output.stdout);
+    assert!(stdout.contains("(markdown)"), "stdout: {stdout}");
+    assert!(stdout.contains("(json)"), "stdout: {stdout}");
+
+    let content = std::fs::read_to_string(&json_path).unwrap();
+    let json: serde_json::Value = serde_json::from_str(&content).unwrap();
+    assert!(json["layers"].is_array());
+    assert_eq!(
+        LayoutService::load(&json_path).unwrap().layers,
+        original.layers
+    );
+
+    let back_path = temp_dir.path().join("back.md");
+    assert_success(&convert(&json_path, &back_path, &[]));
+    assert_eq!(
+        LayoutService::load(&back_path).unwrap().layers,
+        original.layers
+    );
+}
+
+#[test]
+fn test_convert_format_override() {
+    let (md_path, temp_dir) = create_temp_layout_file(&test_layout_basic(2, 3));
+    let out_path = temp_dir.path().join("layout.txt");
+
+    assert_success(&convert(&md_path, &out_path, &["--to", "json"]));
+    let content = std::fs::read_to_string(&out_path).unwrap();
+    assert!(serde_json::from_str::<serde_json::Value>(&content).is_ok());
+
+    // Content sniffing picks JSON back up despite the extension
+    assert!(LayoutService::load(&out_path).is_ok());
+}
+
+#[test]
+fn test_convert_refuses_to_overwrite() {
+    let (md_path, temp_dir) = create_temp_layout_file(&test_layout_basic(2, 3));
+    let out_path = temp_dir.path().join("existing.lazyqmk.json");
+    std::fs::write(&out_path, "keep me").unwrap();
+
+    let output = convert(&md_path, &out_path, &[]);
+    assert_eq!(output.status.code(), Some(1));
+    assert!(String::from_utf8_lossy(&output.stderr).contains("--force"));
+    assert_eq!(std::fs::read_to_string(&out_path).unwrap(), "keep me");
+
+    assert_success(&convert(&md_path, &out_path, &["--force"]));
+}
+
+#[test]
+fn test_convert_unknown_format() {
+    let (md_path, temp_dir) = create_temp_layout_file(&test_layout_basic(2, 3));
+    let output = convert(&md_path, &temp_dir.path().join("out.md"), &["--to", "yaml"]);
+    assert_eq!(output.status.code(), Some(1));
+    assert!(String::from_utf8_lossy(&output.stderr).contains("Unknown format 'yaml'"));
+}
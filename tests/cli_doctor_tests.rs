@@ -0,0 +1,61 @@
+//! End-to-end tests for `lazyqmk doctor` command.
+
+use std::process::Command;
+
+use tempfile::TempDir;
+
+/// Path to the lazyqmk binary
+fn lazyqmk_bin() -> &'static str {
+    env!("CARGO_BIN_EXE_lazyqmk")
+}
+
+#[test]
+fn test_doctor_reports_missing_qmk_tree_as_json() {
+    let temp_dir = TempDir::new().unwrap();
+    let qmk_path = temp_dir.path().join("qmk_firmware");
+
+    let output = Command::new(lazyqmk_bin())
+        .env("LAZYQMK_CONFIG_DIR", temp_dir.path())
+        .current_dir(temp_dir.path())
+        .args(["doctor", "--json", "--qmk-path", qmk_path.to_str().unwrap()])
+        .output()
+        .expect("Failed to execute command");
+
+    assert_eq!(output.status.code(), Some(1));
+    let json: serde_json::Value = serde_json::from_slice(&output.stdout).unwrap();
+    assert_eq!(json["ok"], false);
+    let tree = &json["checks"][0];
+    assert_eq!(tree["name"], "QMK firmware tree");
+    assert_eq!(tree["ok"], false);
+    assert!(tree["fix"].as_str().unwrap().contains("--qmk-path"));
+    // Without a keyboard, every toolchain is listed but none is required
+    let toolchains: Vec<_> = json["checks"]
+        .as_array()
+        .unwrap()
+        .iter()
+        .filter(|check| check["name"].as_str().unwrap().contains("toolchain"))
+        .collect();
+    assert_eq!(toolchains.len(), 3);
+    assert!(toolchains.iter().all(|check| check["required"] == false));
+}
+
+#[test]
+fn test_doctor_without_configured_qmk_path() {
+    let temp_dir = TempDir::new().unwrap();
+
+    let output = Command::new(lazyqmk_bin())
+        .env("LAZYQMK_CONFIG_DIR", temp_dir.path())
+        .env_remove("LAZYQMK_QMK_FIRMWARE")
+        .current_dir(temp_dir.path())
+        .arg("doctor")
+        .output()
+        .expect("Failed to execute command");
+
+    let stdout = String::from_utf8_lossy(&output.stdout);
+    assert_eq!(output.status.code(), Some(1));
+    assert!(
+        stdout.contains("✗ QMK firmware tree: QMK firmware path not configured"),
+        "{stdout}"
+    );
+    assert!(stdout.contains("To fix: lazyqmk config set --qmk-path <DIR>"));
+}
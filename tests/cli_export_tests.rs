@@ -5,7 +5,10 @@ use std::fs;
 use std::process::Command;
 
 mod fixtures;
+mod golden_helper;
+
 use fixtures::*;
+use golden_helper::*;
 
 /// Path to the lazyqmk binary
 fn lazyqmk_bin() -> &'static str {
@@ -180,3 +183,117 @@ fn test_export_multiple_layers() {
         "Should have default color for layers"
     );
 }
+
+#[test]
+fn test_export_includes_descriptions() {
+    let mut layout = test_layout_basic(2, 3);
+    layout.metadata.description = "Layout for daily use".to_string();
+    layout.layers[0].description = Some("Plain typing".to_string());
+    layout.layers[1].description = Some("Mirrors macOS shortcuts".to_string());
+    let (layout_path, layout_temp) = create_temp_layout_file(&layout);
+    let (config, config_temp) = temp_config_with_qmk(None);
+
+    let output = Command::new(lazyqmk_bin())
+        .args([
+            "export",
+            "--layout",
+            layout_path.to_str().unwrap(),
+            "--qmk-path",
+            config
+                .paths
+                .qmk_firmware
+                .as_ref()
+                .unwrap()
+                .to_str()
+                .unwrap(),
+            "--output",
+            "-",
+        ])
+        .output()
+        .expect("Failed to execute command");
+
+    assert_eq!(output.status.code(), Some(0));
+    let content = String::from_utf8_lossy(&output.stdout);
+    assert!(content.contains("Layout for daily use\n"));
+    assert!(content.contains("## Keyboard Layout\n\nPlain typing\n\n```"));
+    assert!(content.contains("## Layer 1: Function\n\nMirrors macOS shortcuts\n\n```"));
+}
+
+/// Exports `layout` in `format` to stdout.
+fn export_to_stdout(layout: &lazyqmk::models::Layout, format: &str) -> String {
+    let (layout_path, layout_temp) = create_temp_layout_file(layout);
+    let (config, config_temp) = temp_config_with_qmk(None);
+
+    let output = Command::new(lazyqmk_bin())
+        .args([
+            "export",
+            "--layout",
+            layout_path.to_str().unwrap(),
+            "--qmk-path",
+            config
+                .paths
+                .qmk_firmware
+                .as_ref()
+                .unwrap()
+                .to_str()
+                .unwrap(),
+            "--format",
+            format,
+            "--output",
+            "-",
+        ])
+        .output()
+        .expect("Failed to execute command");
+
+    assert_eq!(
+        output.status.code(),
+        Some(0),
+        "Export should succeed. stderr: {}",
+        String::from_utf8_lossy(&output.stderr)
+    );
+    String::from_utf8(output.stdout).unwrap()
+}
+
+/// The basic layout with a layer-tap and a mod-tap key on the base layer
+fn tap_hold_layout() -> lazyqmk::models::Layout {
+    let mut layout = test_layout_basic(2, 3);
+    layout.layers[0].keys[1].keycode = "LT(1, KC_SPC)".to_string();
+    layout.layers[0].keys[4].keycode = "LCTL_T(KC_A)".to_string();
+    layout
+}
+
+#[test]
+fn test_export_keymap_drawer_golden() {
+    let yaml = export_to_stdout(&tap_hold_layout(), "keymap-drawer");
+    assert_golden(&yaml, "tests/golden/keymap_drawer_basic.yaml");
+}
+
+#[test]
+fn test_export_layers_json_golden() {
+    let json = export_to_stdout(&tap_hold_layout(), "layers-json");
+    let parsed: serde_json::Value = serde_json::from_str(&json).expect("Output should be JSON");
+    assert_eq!(parsed["layers"][0]["keys"][1]["legend"]["tap"], "Space");
+    assert_golden(&json, "tests/golden/layers_basic.json");
+}
+
+#[test]
+fn test_export_rejects_unknown_format() {
+    let layout = test_layout_basic(2, 3);
+    let (layout_path, layout_temp) = create_temp_layout_file(&layout);
+
+    let output = Command::new(lazyqmk_bin())
+        .args([
+            "export",
+            "--layout",
+            layout_path.to_str().unwrap(),
+            "--qmk-path",
+            "/nonexistent",
+            "--format",
+            "svg",
+        ])
+        .output()
+        .expect("Failed to execute command");
+
+    assert_ne!(output.status.code(), Some(0));
+    assert!(String::from_utf8_lossy(&output.stderr).contains("Invalid format 'svg'"));
+}
@@ -0,0 +1,115 @@
+//! End-to-end tests for `lazyqmk fix` command.
+
+use std::process::Command;
+
+mod fixtures;
+use fixtures::*;
+
+/// Path to the lazyqmk binary
+fn lazyqmk_bin() -> &'static str {
+    env!("CARGO_BIN_EXE_lazyqmk")
+}
+
+/// Basic layout with transparent keys on the base layer
+fn layout_with_base_trns() -> lazyqmk::models::Layout {
+    let mut layout = test_layout_basic(2, 3);
+    layout.layers[0].keys[1].keycode = "KC_TRNS".to_string();
+    layout.layers[0].keys[4].keycode = "KC_TRANSPARENT".to_string();
+    layout
+}
+
+#[test]
+fn test_validate_json_reports_base_layer_trns() {
+    let (layout_path, _temp_dir) = create_temp_layout_file(&layout_with_base_trns());
+
+    let output = Command::new(lazyqmk_bin())
+        .args(["validate", layout_path.to_str().unwrap(), "--json"])
+        .output()
+        .expect("Failed to execute command");
+
+    let stdout = String::from_utf8_lossy(&output.stdout);
+    let result: serde_json::Value =
+        serde_json::from_str(&stdout).expect("Should parse JSON output");
+    let warnings: Vec<_> = result["errors"]
+        .as_array()
+        .unwrap()
+        .iter()
+        .filter(|w| w["rule"] == "base-layer-trns")
+        .collect();
+
+    assert_eq!(result["valid"], true, "warnings should not fail validation");
+    assert_eq!(warnings.len(), 2, "stdout: {stdout}");
+    assert_eq!(warnings[0]["severity"], "warning");
+    assert_eq!(warnings[0]["location"]["layer"], 0);
+    assert_eq!(warnings[0]["location"]["position"]["row"], 0);
+    assert_eq!(warnings[0]["location"]["position"]["col"], 1);
+}
+
+#[test]
+fn test_fix_base_layer_trns() {
+    let (layout_path, _temp_dir) = create_temp_layout_file(&layout_with_base_trns());
+
+    let output = Command::new(lazyqmk_bin())
+        .args([
+            "fix",
+            "--rule",
+            "base-layer-trns",
+            layout_path.to_str().unwrap(),
+        ])
+        .output()
+        .expect("Failed to execute command");
+
+    assert_eq!(
+        output.status.code(),
+        Some(0),
+        "stderr: {}",
+        String::from_utf8_lossy(&output.stderr)
+    );
+    let stdout = String::from_utf8_lossy(&output.stdout);
+    assert!(
+        stdout.contains("Fixed 2 change(s) across 1 file(s)"),
+        "{stdout}"
+    );
+
+    let layout = lazyqmk::services::LayoutService::load(&layout_path).unwrap();
+    assert_eq!(layout.layers[0].keys[1].keycode, "KC_NO");
+    assert_eq!(layout.layers[0].keys[4].keycode, "KC_NO");
+    // Transparent keys on higher layers are left alone
+    assert_eq!(layout.layers[1].keys[0].keycode, "KC_TRNS");
+}
+
+#[test]
+fn test_fix_dry_run_leaves_file_untouched() {
+    let (layout_path, _temp_dir) = create_temp_layout_file(&layout_with_base_trns());
+    let before = std::fs::read_to_string(&layout_path).unwrap();
+
+    let output = Command::new(lazyqmk_bin())
+        .args([
+            "fix",
+            "--rule",
+            "base-layer-trns",
+            "--dry-run",
+            layout_path.to_str().unwrap(),
+        ])
+        .output()
+        .expect("Failed to execute command");
+
+    assert_eq!(output.status.code(), Some(0));
+    let stdout = String::from_utf8_lossy(&output.stdout);
+    assert!(stdout.contains("Would fix 2 change(s)"), "{stdout}");
+    assert_eq!(std::fs::read_to_string(&layout_path).unwrap(), before);
+}
+
+#[test]
+fn test_fix_unknown_rule() {
+    let (layout_path, _temp_dir) = create_temp_layout_file(&layout_with_base_trns());
+
+    let output = Command::new(lazyqmk_bin())
+        .args(["fix", "--rule", "nope", layout_path.to_str().unwrap()])
+        .output()
+        .expect("Failed to execute command");
+
+    assert_ne!(output.status.code(), Some(0));
+    let stderr = String::from_utf8_lossy(&output.stderr);
+    assert!(stderr.contains("base-layer-trns"), "stderr: {stderr}");
+}
@@ -382,6 +382,72 @@ fn test_generate_idle_effect_off() {
     );
 }
 
+/// Runs `generate --deterministic` and returns (keymap.c, config.h).
+fn generate_deterministic(layout: &lazyqmk::models::Layout) -> (String, String) {
+    let (layout_path, _layout_temp) = create_temp_layout_file(layout);
+    let (config, config_temp) = temp_config_with_qmk(None);
+    let out_dir = config_temp.path().join("output");
+    fs::create_dir_all(&out_dir).expect("Failed to create output dir");
+
+    let output = Command::new(lazyqmk_bin())
+        .args([
+            "generate",
+            "--layout",
+            layout_path.to_str().unwrap(),
+            "--qmk-path",
+            config
+                .paths
+                .qmk_firmware
+                .as_ref()
+                .unwrap()
+                .to_str()
+                .unwrap(),
+            "--out-dir",
+            out_dir.to_str().unwrap(),
+            "--deterministic",
+        ])
+        .output()
+        .expect("Failed to execute command");
+    assert_eq!(
+        output.status.code(),
+        Some(0),
+        "stderr: {}",
+        String::from_utf8_lossy(&output.stderr)
+    );
+
+    (
+        fs::read_to_string(out_dir.join("keymap.c")).expect("Failed to read keymap.c"),
+        fs::read_to_string(out_dir.join("config.h")).expect("Failed to read config.h"),
+    )
+}
+
+#[test]
+fn test_generate_category_rgb_flags_with_timeout() {
+    let (keymap, config_h) = generate_deterministic(&test_layout_with_category_rgb_flags(false));
+
+    assert!(keymap.contains("bool rgb_matrix_indicators_advanced_user("));
+    assert!(keymap.contains("last_input_activity_elapsed() >= LQMK_RGB_TIMEOUT_MS"));
+    // The matrix must keep running for always-on keys
+    assert!(config_h.contains("#define LQMK_RGB_TIMEOUT_MS 90000"));
+    assert!(!config_h.contains("RGB_MATRIX_TIMEOUT"));
+
+    assert_golden(&keymap, "tests/golden/keymap_category_rgb_flags.c");
+    assert_golden(&config_h, "tests/golden/config_category_rgb_flags.h");
+}
+
+#[test]
+fn test_generate_category_rgb_flags_with_idle_effect() {
+    let (keymap, _) = generate_deterministic(&test_layout_with_category_rgb_flags(true));
+
+    assert!(keymap.contains("bool sleeping = idle_state == IDLE_STATE_OFF;"));
+    assert!(
+        !keymap.contains("rgb_matrix_disable_noeeprom()"),
+        "the idle off state must keep always-on keys lit"
+    );
+
+    assert_golden(&keymap, "tests/golden/keymap_category_rgb_flags_idle.c");
+}
+
 #[test]
 fn test_generate_with_tap_dances() {
     let layout = test_layout_with_tap_dances();
@@ -508,12 +574,15 @@ fn test_generate_invalid_layout() {
         .output()
         .expect("Failed to execute command");
 
-    // Generation might still succeed (generator is permissive),
-    // but validation should catch it
-    // The exit code depends on whether generator validates first
+    // Validation runs before generation and fails with exit code 1
+    assert_eq!(
+        output.status.code(),
+        Some(1),
+        "Invalid layout should exit with code 1"
+    );
     assert!(
-        output.status.code() == Some(0) || output.status.code() == Some(1),
-        "Exit code should be 0 (generated) or 1 (validated and failed)"
+        !out_dir.join("keymap.c").exists(),
+        "Nothing should be written when validation fails"
     );
 }
 
@@ -543,3 +612,321 @@ fn test_generate_missing_qmk_path() {
         "Missing QMK path should exit with code 2"
     );
 }
+
+#[test]
+fn test_generate_refuses_keyboard_mismatch() {
+    let layout = test_layout_basic(2, 3);
+    let (layout_path, temp_dir) = create_temp_layout_file(&layout);
+    let out_dir = temp_dir.path().join("out");
+    let output = Command::new(lazyqmk_bin())
+        .env("LAZYQMK_CONFIG_DIR", temp_dir.path())
+        .args(["config", "set", "--build-keyboard", "crkbd"])
+        .output()
+        .expect("Failed to execute command");
+    assert_eq!(output.status.code(), Some(0));
+
+    let output = Command::new(lazyqmk_bin())
+        .env("LAZYQMK_CONFIG_DIR", temp_dir.path())
+        .args([
+            "generate",
+            "--layout",
+            layout_path.to_str().unwrap(),
+            "--qmk-path",
+            "/nonexistent/qmk_firmware",
+            "--out-dir",
+            out_dir.to_str().unwrap(),
+        ])
+        .output()
+        .expect("Failed to execute command");
+
+    let stderr = String::from_utf8_lossy(&output.stderr);
+    assert_eq!(output.status.code(), Some(1), "stderr: {stderr}");
+    assert!(stderr.contains("'test_keyboard'"), "{stderr}");
+    assert!(stderr.contains("'crkbd' (config)"), "{stderr}");
+    assert!(stderr.contains(out_dir.to_str().unwrap()), "{stderr}");
+    assert!(!out_dir.exists(), "nothing is written before confirming");
+}
+
+#[test]
+fn test_generate_missing_layout_reports_not_found() {
+    let temp_dir = tempfile::TempDir::new().unwrap();
+    let layout_path = temp_dir.path().join("missing.md");
+
+    let output = Command::new(lazyqmk_bin())
+        .args([
+            "generate",
+            "--layout",
+            layout_path.to_str().unwrap(),
+            "--qmk-path",
+            "/nonexistent/qmk_firmware",
+            "--out-dir",
+            temp_dir.path().to_str().unwrap(),
+            "--json",
+        ])
+        .output()
+        .expect("Failed to execute command");
+
+    assert_eq!(output.status.code(), Some(2));
+    let error: serde_json::Value =
+        serde_json::from_slice(&output.stderr).expect("stderr should be a JSON error");
+    assert_eq!(error["error"]["kind"], "not_found");
+    assert!(error["error"]["message"]
+        .as_str()
+        .unwrap()
+        .contains("missing.md"));
+}
+
+#[test]
+fn test_generate_rules_mk_enables_tap_dance() {
+    let layout = test_layout_with_tap_dances();
+    let (layout_path, layout_temp) = create_temp_layout_file(&layout);
+    let (config, config_temp) = temp_config_with_qmk(None);
+    let out_dir = config_temp.path().join("output");
+    fs::create_dir_all(&out_dir).expect("Failed to create output dir");
+
+    let output = Command::new(lazyqmk_bin())
+        .args([
+            "generate",
+            "--layout",
+            layout_path.to_str().unwrap(),
+            "--qmk-path",
+            config
+                .paths
+                .qmk_firmware
+                .as_ref()
+                .unwrap()
+                .to_str()
+                .unwrap(),
+            "--out-dir",
+            out_dir.to_str().unwrap(),
+            "--format",
+            "rules",
+        ])
+        .output()
+        .expect("Failed to execute command");
+
+    assert_eq!(
+        output.status.code(),
+        Some(0),
+        "Generation should succeed. stderr: {}",
+        String::from_utf8_lossy(&output.stderr)
+    );
+
+    let rules = fs::read_to_string(out_dir.join("rules.mk")).expect("Failed to read rules.mk");
+    assert!(rules.contains("TAP_DANCE_ENABLE = yes"));
+    assert!(!out_dir.join("keymap.c").exists());
+}
+
+#[test]
+fn test_generate_dump_templates() {
+    let temp = tempfile::TempDir::new().expect("Failed to create temp dir");
+    let dump_dir = temp.path().join("templates");
+
+    let output = Command::new(lazyqmk_bin())
+        .args(["generate", "--dump-templates", dump_dir.to_str().unwrap()])
+        .output()
+        .expect("Failed to execute command");
+
+    assert_eq!(
+        output.status.code(),
+        Some(0),
+        "Dumping templates should succeed. stderr: {}",
+        String::from_utf8_lossy(&output.stderr)
+    );
+
+    for name in ["keymap.c.jinja", "config.h.jinja", "rules.mk.jinja"] {
+        let content = fs::read_to_string(dump_dir.join(name)).expect("Template should be written");
+        assert!(content.contains("{{ generator.name }}"), "{name}");
+    }
+}
+
+/// Runs `generate --json` and returns the exit code and parsed summary.
+fn run_generate_json(
+    layout_path: &std::path::Path,
+    qmk_path: &std::path::Path,
+    out_dir: &std::path::Path,
+    extra_args: &[&str],
+) -> (Option<i32>, serde_json::Value) {
+    let output = Command::new(lazyqmk_bin())
+        .args([
+            "generate",
+            "--layout",
+            layout_path.to_str().unwrap(),
+            "--qmk-path",
+            qmk_path.to_str().unwrap(),
+            "--out-dir",
+            out_dir.to_str().unwrap(),
+            "--deterministic",
+            "--json",
+        ])
+        .args(extra_args)
+        .output()
+        .expect("Failed to execute command");
+
+    let summary = serde_json::from_slice(&output.stdout).unwrap_or_else(|e| {
+        panic!(
+            "stdout should be JSON ({e}). stderr: {}",
+            String::from_utf8_lossy(&output.stderr)
+        )
+    });
+    (output.status.code(), summary)
+}
+
+#[test]
+fn test_generate_json_summary_clean_run() {
+    let layout = test_layout_basic(2, 3);
+    let (layout_path, layout_temp) = create_temp_layout_file(&layout);
+    let (config, config_temp) = temp_config_with_qmk(None);
+    let qmk_path = config.paths.qmk_firmware.unwrap();
+    let out_dir = config_temp.path().join("output");
+
+    let (code, summary) = run_generate_json(&layout_path, &qmk_path, &out_dir, &[]);
+    assert_eq!(code, Some(0));
+    assert_eq!(summary["status"], "success");
+    assert_eq!(summary["exit_code"], 0);
+    assert_eq!(summary["validation"]["valid"], true);
+    assert!(summary["duration_ms"].is_u64());
+    let files = summary["files"].as_array().unwrap();
+    assert_eq!(files.len(), 3);
+    assert!(files.iter().all(|f| f["status"] == "written"));
+    assert_eq!(files[0]["name"], "keymap.c");
+    assert_eq!(
+        files[0]["path"].as_str().unwrap(),
+        out_dir.join("keymap.c").to_str().unwrap()
+    );
+
+    // A second deterministic run leaves every file untouched
+    let (code, summary) = run_generate_json(&layout_path, &qmk_path, &out_dir, &[]);
+    assert_eq!(code, Some(0));
+    let files = summary["files"].as_array().unwrap();
+    assert!(files.iter().all(|f| f["status"] == "unchanged"));
+}
+
+#[test]
+fn test_generate_json_summary_skips_unrequested_files() {
+    let layout = test_layout_basic(2, 3);
+    let (layout_path, layout_temp) = create_temp_layout_file(&layout);
+    let (config, config_temp) = temp_config_with_qmk(None);
+    let qmk_path = config.paths.qmk_firmware.unwrap();
+    let out_dir = config_temp.path().join("output");
+
+    let (code, summary) =
+        run_generate_json(&layout_path, &qmk_path, &out_dir, &["--format", "keymap"]);
+    assert_eq!(code, Some(0));
+    let statuses: Vec<&str> = summary["files"]
+        .as_array()
+        .unwrap()
+        .iter()
+        .map(|f| f["status"].as_str().unwrap())
+        .collect();
+    assert_eq!(statuses, ["written", "skipped", "skipped"]);
+    assert!(!out_dir.join("config.h").exists());
+}
+
+#[test]
+fn test_generate_json_summary_reports_partial_write_failure() {
+    let layout = test_layout_basic(2, 3);
+    let (layout_path, layout_temp) = create_temp_layout_file(&layout);
+    let (config, config_temp) = temp_config_with_qmk(None);
+    let qmk_path = config.paths.qmk_firmware.unwrap();
+    let out_dir = config_temp.path().join("output");
+
+    // A non-empty directory where config.h should go can't be replaced
+    fs::create_dir_all(out_dir.join("config.h").join("inner")).unwrap();
+
+    let (code, summary) = run_generate_json(&layout_path, &qmk_path, &out_dir, &[]);
+    assert_eq!(code, Some(2));
+    assert_eq!(summary["status"], "failed");
+    let files = summary["files"].as_array().unwrap();
+    let statuses: Vec<&str> = files
+        .iter()
+        .map(|f| f["status"].as_str().unwrap())
+        .collect();
+    assert_eq!(statuses, ["written", "failed", "not_written"]);
+    assert!(files[1]["error"].is_string());
+    assert!(files[0].get("error").is_none());
+    assert!(out_dir.join("keymap.c").exists());
+    assert!(!out_dir.join("rules.mk").exists());
+}
+
+#[test]
+fn test_generate_warnings_exit_code() {
+    let mut layout = test_layout_basic(2, 3);
+    // Defined but unused tap dances are a validation warning
+    layout
+        .tap_dances
+        .push(lazyqmk::models::TapDanceAction::new("unused", "KC_A").with_double_tap("KC_B"));
+    let (layout_path, layout_temp) = create_temp_layout_file(&layout);
+    let (config, config_temp) = temp_config_with_qmk(None);
+    let qmk_path = config.paths.qmk_firmware.unwrap();
+    let out_dir = config_temp.path().join("output");
+
+    let (code, summary) = run_generate_json(&layout_path, &qmk_path, &out_dir, &[]);
+    assert_eq!(
+        code,
+        Some(3),
+        "Success with warnings should exit with code 3"
+    );
+    assert_eq!(summary["status"], "warnings");
+    assert_eq!(summary["validation"]["valid"], true);
+    assert_eq!(summary["validation"]["messages"][0]["severity"], "warning");
+    assert!(out_dir.join("keymap.c").exists());
+}
+
+#[test]
+fn test_generate_json_summary_validation_failure() {
+    let layout = test_layout_with_invalid_keycode();
+    let (layout_path, layout_temp) = create_temp_layout_file(&layout);
+    let (config, config_temp) = temp_config_with_qmk(None);
+    let qmk_path = config.paths.qmk_firmware.unwrap();
+    let out_dir = config_temp.path().join("output");
+
+    let (code, summary) = run_generate_json(&layout_path, &qmk_path, &out_dir, &[]);
+    assert_eq!(code, Some(1));
+    assert_eq!(summary["status"], "failed");
+    assert_eq!(summary["validation"]["valid"], false);
+    assert_eq!(summary["validation"]["messages"][0]["severity"], "error");
+    assert!(summary["files"].as_array().unwrap().is_empty());
+}
+
+#[test]
+fn test_generate_json_keymap() {
+    let layout = test_layout_basic(2, 3);
+    let (layout_path, layout_temp) = create_temp_layout_file(&layout);
+    let (config, config_temp) = temp_config_with_qmk(None);
+    let qmk_path = config.paths.qmk_firmware.unwrap();
+    let out_dir = config_temp.path().join("output");
+
+    // Instead of the C sources
+    let (code, summary) = run_generate_json(
+        &layout_path,
+        &qmk_path,
+        &out_dir,
+        &["--format", "json-keymap"],
+    );
+    assert_eq!(code, Some(0));
+    let files = summary["files"].as_array().unwrap();
+    assert_eq!(files.len(), 4);
+    assert_eq!(files[3]["name"], "keymap.json");
+    assert_eq!(files[3]["status"], "written");
+    assert!(!out_dir.join("keymap.c").exists());
+
+    let keymap: serde_json::Value =
+        serde_json::from_str(&fs::read_to_string(out_dir.join("keymap.json")).unwrap()).unwrap();
+    assert_eq!(keymap["keyboard"], "test_keyboard");
+    assert_eq!(keymap["keymap"], "test_keymap");
+    assert_eq!(keymap["layers"].as_array().unwrap().len(), 2);
+    assert_eq!(keymap["layers"][0].as_array().unwrap().len(), 6);
+
+    // Alongside them
+    let (code, summary) =
+        run_generate_json(&layout_path, &qmk_path, &out_dir, &["--with-json-keymap"]);
+    assert_eq!(code, Some(0));
+    let statuses: Vec<&str> = summary["files"]
+        .as_array()
+        .unwrap()
+        .iter()
+        .map(|f| f["status"].as_str().unwrap())
+        .collect();
+    assert_eq!(statuses, ["written", "written", "written", "unchanged"]);
+}
@@ -0,0 +1,102 @@
+//! End-to-end tests for `lazyqmk heatmap` command.
+
+use std::fs;
+use std::process::Command;
+
+mod fixtures;
+use fixtures::*;
+
+/// Path to the lazyqmk binary
+fn lazyqmk_bin() -> &'static str {
+    env!("CARGO_BIN_EXE_lazyqmk")
+}
+
+#[test]
+fn test_heatmap_svg_to_stdout() {
+    let layout = test_layout_basic(2, 3);
+    let (layout_path, temp_dir) = create_temp_layout_file(&layout);
+    let freq_path = temp_dir.path().join("freq.csv");
+    fs::write(&freq_path, "keycode,count\nKC_0,50\nKC_5,10\nq,3\n").unwrap();
+
+    let output = Command::new(lazyqmk_bin())
+        .args([
+            "heatmap",
+            "--layout",
+            layout_path.to_str().unwrap(),
+            "--freq",
+            freq_path.to_str().unwrap(),
+        ])
+        .output()
+        .expect("Failed to execute command");
+
+    assert_eq!(
+        output.status.code(),
+        Some(0),
+        "stderr: {}",
+        String::from_utf8_lossy(&output.stderr)
+    );
+    let svg = String::from_utf8_lossy(&output.stdout);
+    assert!(svg.starts_with("<svg"));
+    assert!(svg.contains("All layers"));
+    // Most used key is drawn in the hottest color
+    assert!(svg.contains("#d73027"));
+}
+
+#[test]
+fn test_heatmap_html_per_layer_with_summary() {
+    let mut layout = test_layout_basic(2, 3);
+    layout.layers[1].description = Some("Function keys & media".to_string());
+    let (layout_path, temp_dir) = create_temp_layout_file(&layout);
+    let freq_path = temp_dir.path().join("freq.csv");
+    fs::write(&freq_path, "KC_0,50\nz,5\n").unwrap();
+    let out_path = temp_dir.path().join("heatmap.html");
+
+    let output = Command::new(lazyqmk_bin())
+        .args([
+            "heatmap",
+            "--layout",
+            layout_path.to_str().unwrap(),
+            "--freq",
+            freq_path.to_str().unwrap(),
+            "--format",
+            "html",
+            "--per-layer",
+            "-o",
+            out_path.to_str().unwrap(),
+        ])
+        .output()
+        .expect("Failed to execute command");
+
+    assert_eq!(output.status.code(), Some(0));
+    let stdout = String::from_utf8_lossy(&output.stdout);
+    assert!(stdout.contains("1 unmatched entries"), "{stdout}");
+
+    let html = fs::read_to_string(&out_path).unwrap();
+    assert!(html.contains("<!DOCTYPE html>"));
+    assert!(html.contains("Layer 0: Base"));
+    assert!(html.contains("Layer 1: Function"));
+    assert!(html.contains("Not in layout: z"));
+    assert!(html.contains("<p>Function keys &amp; media</p>"));
+}
+
+#[test]
+fn test_heatmap_invalid_frequency_file() {
+    let layout = test_layout_basic(2, 3);
+    let (layout_path, temp_dir) = create_temp_layout_file(&layout);
+    let freq_path = temp_dir.path().join("freq.csv");
+    fs::write(&freq_path, "KC_0,50\nKC_1,many\n").unwrap();
+
+    let output = Command::new(lazyqmk_bin())
+        .args([
+            "heatmap",
+            "--layout",
+            layout_path.to_str().unwrap(),
+            "--freq",
+            freq_path.to_str().unwrap(),
+        ])
+        .output()
+        .expect("Failed to execute command");
+
+    assert_eq!(output.status.code(), Some(1));
+    assert!(String::from_utf8_lossy(&output.stderr).contains("Line 2"));
+}
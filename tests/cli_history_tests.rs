@@ -0,0 +1,130 @@
+//! End-to-end tests for `lazyqmk history` and history recording.
+
+use std::fs;
+use std::path::Path;
+use std::process::Command;
+
+mod fixtures;
+use fixtures::*;
+
+/// Path to the lazyqmk binary
+fn lazyqmk_bin() -> &'static str {
+    env!("CARGO_BIN_EXE_lazyqmk")
+}
+
+/// Runs `lazyqmk generate` for the layout and returns the exit code.
+fn generate(layout_path: &Path) -> Option<i32> {
+    let (config, config_temp) = temp_config_with_qmk(None);
+    let out_dir = config_temp.path().join("output");
+    let qmk_path = config.paths.qmk_firmware.unwrap();
+
+    Command::new(lazyqmk_bin())
+        .args([
+            "generate",
+            "--layout",
+            layout_path.to_str().unwrap(),
+            "--qmk-path",
+            qmk_path.to_str().unwrap(),
+            "--out-dir",
+            out_dir.to_str().unwrap(),
+        ])
+        .output()
+        .expect("Failed to execute command")
+        .status
+        .code()
+}
+
+/// Runs `lazyqmk history --json` for the layout.
+fn history_json(layout_path: &Path) -> serde_json::Value {
+    let output = Command::new(lazyqmk_bin())
+        .args([
+            "history",
+            "--layout",
+            layout_path.to_str().unwrap(),
+            "--json",
+        ])
+        .output()
+        .expect("Failed to execute command");
+    assert_eq!(
+        output.status.code(),
+        Some(0),
+        "stderr: {}",
+        String::from_utf8_lossy(&output.stderr)
+    );
+    serde_json::from_slice(&output.stdout).expect("Should parse JSON output")
+}
+
+#[test]
+fn test_generate_records_history() {
+    let (layout_path, _temp_dir) = create_temp_layout_file(&test_layout_basic(2, 3));
+    assert_eq!(generate(&layout_path), Some(0));
+    let (invalid_path, _invalid_dir) = create_temp_layout_file(&test_layout_with_invalid_keycode());
+    assert_eq!(generate(&invalid_path), Some(1));
+
+    let history = history_json(&layout_path);
+    let entries = history["entries"].as_array().unwrap();
+    assert_eq!(entries.len(), 1);
+    assert_eq!(entries[0]["operation"], "generate");
+    assert_eq!(entries[0]["success"], true);
+    assert_eq!(entries[0]["lazyqmk_version"], env!("CARGO_PKG_VERSION"));
+    assert!(history["history_file"]
+        .as_str()
+        .unwrap()
+        .contains(".lazyqmk"));
+
+    let failed = &history_json(&invalid_path)["entries"][0];
+    assert_eq!(failed["success"], false);
+    assert!(failed["error"]
+        .as_str()
+        .unwrap()
+        .contains("validation failed"));
+}
+
+#[test]
+fn test_history_tolerates_corrupt_lines() {
+    let (layout_path, temp_dir) = create_temp_layout_file(&test_layout_basic(2, 3));
+    assert_eq!(generate(&layout_path), Some(0));
+
+    let history_dir = temp_dir.path().join(".lazyqmk").join("history");
+    let history_file = fs::read_dir(&history_dir)
+        .unwrap()
+        .next()
+        .unwrap()
+        .unwrap()
+        .path();
+    let mut content = fs::read_to_string(&history_file).unwrap();
+    content.push_str("not json\n");
+    fs::write(&history_file, content).unwrap();
+    assert_eq!(generate(&layout_path), Some(0));
+
+    let history = history_json(&layout_path);
+    assert_eq!(history["entries"].as_array().unwrap().len(), 2);
+    assert_eq!(history["skipped_lines"], 1);
+
+    let output = Command::new(lazyqmk_bin())
+        .args([
+            "history",
+            "--layout",
+            layout_path.to_str().unwrap(),
+            "-n",
+            "1",
+        ])
+        .output()
+        .expect("Failed to execute command");
+    let stdout = String::from_utf8_lossy(&output.stdout);
+    assert_eq!(stdout.matches("generate ✓").count(), 1, "{stdout}");
+    assert!(String::from_utf8_lossy(&output.stderr).contains("Skipped 1 unreadable line"));
+}
+
+#[test]
+fn test_history_empty() {
+    let (layout_path, _temp_dir) = create_temp_layout_file(&test_layout_basic(2, 3));
+
+    let output = Command::new(lazyqmk_bin())
+        .args(["history", "--layout", layout_path.to_str().unwrap()])
+        .output()
+        .expect("Failed to execute command");
+
+    assert_eq!(output.status.code(), Some(0));
+    assert!(String::from_utf8_lossy(&output.stdout).contains("No generate or build runs"));
+}
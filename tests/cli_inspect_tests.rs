@@ -1,6 +1,7 @@
 //! End-to-end tests for `lazyqmk inspect` command.
 #![allow(unused_variables)] // Temp dirs must be kept alive even if not directly accessed
 
+use std::fs;
 use std::process::Command;
 
 mod fixtures;
@@ -84,7 +85,8 @@ fn test_inspect_metadata_plain() {
 
 #[test]
 fn test_inspect_layers_json() {
-    let layout = test_layout_basic(2, 3);
+    let mut layout = test_layout_basic(2, 3);
+    layout.layers[1].enabled = false;
     let (layout_path, layout_temp) = create_temp_layout_file(&layout);
 
     let output = Command::new(lazyqmk_bin())
@@ -115,11 +117,14 @@ fn test_inspect_layers_json() {
     assert_eq!(layers[0]["number"], 0);
     assert_eq!(layers[0]["name"], "Base");
     assert_eq!(layers[0]["key_count"], 6);
+    assert_eq!(layers[0]["enabled"], true);
+    assert!(layers[0]["modified"].is_string());
 
     // Check second layer
     assert_eq!(layers[1]["number"], 1);
     assert_eq!(layers[1]["name"], "Function");
     assert_eq!(layers[1]["key_count"], 6);
+    assert_eq!(layers[1]["enabled"], false);
 }
 
 #[test]
@@ -146,6 +151,49 @@ fn test_inspect_layers_plain() {
     assert!(stdout.contains("[1] Function (6 keys)"));
 }
 
+#[test]
+fn test_inspect_layers_with_descriptions() {
+    let mut layout = test_layout_basic(2, 3);
+    layout.layers[1].description = Some("Mirrors macOS shortcuts".to_string());
+    let (layout_path, layout_temp) = create_temp_layout_file(&layout);
+
+    let output = Command::new(lazyqmk_bin())
+        .args([
+            "inspect",
+            "--layout",
+            layout_path.to_str().unwrap(),
+            "--section",
+            "layers",
+            "--json",
+        ])
+        .output()
+        .expect("Failed to execute command");
+
+    assert_eq!(output.status.code(), Some(0));
+    let result: serde_json::Value =
+        serde_json::from_slice(&output.stdout).expect("Should parse JSON");
+    assert!(result["layers"][0].get("description").is_none());
+    assert_eq!(
+        result["layers"][1]["description"],
+        "Mirrors macOS shortcuts"
+    );
+
+    let output = Command::new(lazyqmk_bin())
+        .args([
+            "inspect",
+            "--layout",
+            layout_path.to_str().unwrap(),
+            "--section",
+            "layers",
+        ])
+        .output()
+        .expect("Failed to execute command");
+
+    let stdout = String::from_utf8_lossy(&output.stdout);
+    assert!(stdout.contains("[1] Function (6 keys)\n      Mirrors macOS shortcuts"));
+    assert!(stdout.contains("      Last modified: "));
+}
+
 #[test]
 fn test_inspect_categories_json() {
     let layout = test_layout_with_categories();
@@ -373,7 +421,221 @@ fn test_inspect_nonexistent_file() {
 
     let stderr = String::from_utf8_lossy(&output.stderr);
     assert!(
-        stderr.contains("Error:"),
-        "Should have error message on stderr"
+        stderr.contains("Error: [not_found]"),
+        "Should have error message with its kind on stderr: {stderr}"
+    );
+}
+
+/// Runs `inspect --json` and returns the exit code and `error.kind` printed
+/// on stderr.
+fn inspect_error_kind(layout_path: &std::path::Path, section: &str) -> (Option<i32>, String) {
+    let output = Command::new(lazyqmk_bin())
+        .args([
+            "inspect",
+            "--layout",
+            layout_path.to_str().unwrap(),
+            "--section",
+            section,
+            "--json",
+        ])
+        .output()
+        .expect("Failed to execute command");
+
+    let stderr = String::from_utf8_lossy(&output.stderr);
+    let error: serde_json::Value =
+        serde_json::from_str(stderr.trim()).expect("stderr should be a JSON error");
+    (
+        output.status.code(),
+        error["error"]["kind"]
+            .as_str()
+            .unwrap_or_default()
+            .to_string(),
+    )
+}
+
+#[test]
+fn test_inspect_json_error_kinds() {
+    let temp_dir = tempfile::TempDir::new().unwrap();
+
+    let missing = temp_dir.path().join("missing.md");
+    assert_eq!(
+        inspect_error_kind(&missing, "metadata"),
+        (Some(2), "not_found".to_string())
     );
+
+    let broken = temp_dir.path().join("broken.md");
+    fs::write(&broken, "not a layout").unwrap();
+    assert_eq!(
+        inspect_error_kind(&broken, "metadata"),
+        (Some(2), "parse".to_string())
+    );
+
+    let layout = test_layout_basic(2, 3);
+    let (layout_path, _layout_temp) = create_temp_layout_file(&layout);
+    assert_eq!(
+        inspect_error_kind(&layout_path, "invalid_section"),
+        (Some(1), "validation".to_string())
+    );
+}
+
+/// Layout with categories, a note and `LT(` keycodes on both layers:
+/// - layer 0 (0,0): navigation, note; (0,1): numbers, LT(1, KC_SPC)
+/// - layer 1 (1,2): navigation, LT(1, KC_ENT)
+fn keys_layout() -> lazyqmk::models::Layout {
+    let mut layout = test_layout_with_categories();
+    layout.layers[0].keys[0].description = Some("Home key".to_string());
+    layout.layers[0].keys[1].keycode = "LT(1, KC_SPC)".to_string();
+    layout.layers[1].keys[5].keycode = "LT(1, KC_ENT)".to_string();
+    layout.layers[1].keys[5].category_id = Some("navigation".to_string());
+    layout
+}
+
+/// Runs `inspect --section keys --json` with extra filter arguments.
+fn inspect_keys_json(layout_path: &std::path::Path, filters: &[&str]) -> Vec<serde_json::Value> {
+    let output = Command::new(lazyqmk_bin())
+        .args([
+            "inspect",
+            "--layout",
+            layout_path.to_str().unwrap(),
+            "--section",
+            "keys",
+            "--json",
+        ])
+        .args(filters)
+        .output()
+        .expect("Failed to execute command");
+    assert_eq!(
+        output.status.code(),
+        Some(0),
+        "stderr: {}",
+        String::from_utf8_lossy(&output.stderr)
+    );
+    let result: serde_json::Value =
+        serde_json::from_slice(&output.stdout).expect("Should parse JSON");
+    result.as_array().expect("Should be a JSON array").clone()
+}
+
+#[test]
+fn test_inspect_keys_json_schema() {
+    let (layout_path, layout_temp) = create_temp_layout_file(&keys_layout());
+    let keys = inspect_keys_json(&layout_path, &[]);
+    assert_eq!(keys.len(), 12, "2 layers of 2x3 keys");
+
+    let mut fields: Vec<_> = keys[0].as_object().unwrap().keys().cloned().collect();
+    fields.sort();
+    assert_eq!(
+        fields,
+        [
+            "category",
+            "col",
+            "color",
+            "keycode",
+            "layer",
+            "layer_name",
+            "note",
+            "row"
+        ]
+    );
+    assert_eq!(keys[0]["layer"], 0);
+    assert_eq!(keys[0]["layer_name"], "Base");
+    assert_eq!(
+        (keys[0]["row"].as_u64(), keys[0]["col"].as_u64()),
+        (Some(0), Some(0))
+    );
+    assert_eq!(keys[0]["category"], "navigation");
+    assert_eq!(
+        keys[0]["color"], "#00FF00",
+        "Category color wins over layer color"
+    );
+    assert_eq!(keys[0]["note"], "Home key");
+
+    // Unset fields are null rather than missing
+    assert!(keys[2]["category"].is_null());
+    assert!(keys[2]["note"].is_null());
+    assert_eq!(keys[2]["color"], "#FFFFFF");
+
+    // Ordered by layer, then row and column
+    assert_eq!(keys[6]["layer"], 1);
+    assert_eq!(
+        (keys[11]["row"].as_u64(), keys[11]["col"].as_u64()),
+        (Some(1), Some(2))
+    );
+}
+
+#[test]
+fn test_inspect_keys_filter_combinations() {
+    let (layout_path, layout_temp) = create_temp_layout_file(&keys_layout());
+    let keycodes = |filters: &[&str]| -> Vec<(u64, String)> {
+        inspect_keys_json(&layout_path, filters)
+            .iter()
+            .map(|k| {
+                (
+                    k["layer"].as_u64().unwrap(),
+                    k["keycode"].as_str().unwrap().to_string(),
+                )
+            })
+            .collect()
+    };
+
+    assert_eq!(keycodes(&["--layer", "1"]).len(), 6);
+    assert_eq!(
+        keycodes(&["--keycode", "LT(*"]),
+        [
+            (0, "LT(1, KC_SPC)".to_string()),
+            (1, "LT(1, KC_ENT)".to_string())
+        ]
+    );
+    assert_eq!(
+        keycodes(&["--keycode", "LT(*", "--layer", "1"]),
+        [(1, "LT(1, KC_ENT)".to_string())]
+    );
+    assert_eq!(
+        keycodes(&["--category", "navigation"]),
+        [(0, "KC_0".to_string()), (1, "LT(1, KC_ENT)".to_string())]
+    );
+    assert_eq!(
+        keycodes(&["--category", "navigation", "--keycode", "KC_?"]),
+        [(0, "KC_0".to_string())]
+    );
+    assert!(keycodes(&["--category", "numbers", "--layer", "1"]).is_empty());
+}
+
+#[test]
+fn test_inspect_keys_plain_table() {
+    let (layout_path, layout_temp) = create_temp_layout_file(&keys_layout());
+    let output = Command::new(lazyqmk_bin())
+        .args([
+            "inspect",
+            "--layout",
+            layout_path.to_str().unwrap(),
+            "--section",
+            "keys",
+            "--keycode",
+            "LT(*",
+        ])
+        .output()
+        .expect("Failed to execute command");
+
+    assert_eq!(output.status.code(), Some(0));
+    let stdout = String::from_utf8_lossy(&output.stdout);
+    assert!(stdout.starts_with("Keys (2 matching):\n"));
+    assert!(stdout.contains("LAYER"));
+    assert!(stdout.contains("LT(1, KC_SPC)"));
+    assert!(stdout.contains("numbers"));
+}
+
+#[test]
+fn test_inspect_key_filters_rejected_elsewhere() {
+    let (layout_path, layout_temp) = create_temp_layout_file(&keys_layout());
+    for args in [
+        ["--section", "layers", "--layer", "0"],
+        ["--section", "keys", "--layer", "5"],
+    ] {
+        let output = Command::new(lazyqmk_bin())
+            .args(["inspect", "--layout", layout_path.to_str().unwrap()])
+            .args(args)
+            .output()
+            .expect("Failed to execute command");
+        assert_eq!(output.status.code(), Some(1), "{args:?}");
+    }
 }
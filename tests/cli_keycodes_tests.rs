@@ -87,6 +87,26 @@ fn test_keycodes_json_structure() {
     }
 }
 
+#[test]
+fn test_keycodes_json_database_version() {
+    let output = Command::new(lazyqmk_bin())
+        .args(["keycodes", "--json"])
+        .output()
+        .expect("Failed to execute command");
+
+    let result: serde_json::Value =
+        serde_json::from_slice(&output.stdout).expect("Should parse JSON output");
+
+    assert!(result["database"]["version"].is_string());
+    assert!(
+        !result["database"]["qmk_version"]
+            .as_str()
+            .unwrap_or_default()
+            .is_empty(),
+        "Should report the QMK keycode snapshot version"
+    );
+}
+
 // ============================================================================
 // Filter by Category Tests
 // ============================================================================
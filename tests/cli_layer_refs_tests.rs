@@ -59,10 +59,12 @@ fn test_layout_with_all_ref_types() -> Layout {
         let mut layer2 = Layer::new(2, "Adjust", RgbColor::new(255, 0, 255)).unwrap();
         for row in 0..3 {
             for col in 0..3 {
-                layer2.add_key(lazyqmk::models::KeyDefinition::new(
-                    lazyqmk::models::Position::new(row, col),
-                    "KC_TRNS",
-                ));
+                layer2
+                    .add_key(lazyqmk::models::KeyDefinition::new(
+                        lazyqmk::models::Position::new(row, col),
+                        "KC_TRNS",
+                    ))
+                    .unwrap();
             }
         }
         layout.layers.push(layer2);
@@ -0,0 +1,221 @@
+//! End-to-end tests for `lazyqmk layer` commands.
+
+use std::fs;
+use std::process::Command;
+
+mod fixtures;
+use fixtures::*;
+
+/// Path to the lazyqmk binary
+fn lazyqmk_bin() -> &'static str {
+    env!("CARGO_BIN_EXE_lazyqmk")
+}
+
+/// Keycodes of a layer in row order
+fn layer_keycodes(path: &std::path::Path, layer: usize) -> Vec<String> {
+    let layout = lazyqmk::parser::parse_markdown_layout(path).unwrap();
+    let mut keys = layout.layers[layer].keys.clone();
+    keys.sort_by_key(|k| (k.position.row, k.position.col));
+    keys.into_iter().map(|k| k.keycode).collect()
+}
+
+#[test]
+fn test_layer_set_from_grid_file() {
+    let (layout_path, temp_dir) = create_temp_layout_file(&test_layout_basic(2, 3));
+    let grid_path = temp_dir.path().join("grid.txt");
+    fs::write(&grid_path, "KC_Q, KC_W\nLT(1, KC_SPC) KC_ESC\n").unwrap();
+
+    let output = Command::new(lazyqmk_bin())
+        .args(["layer", "set", "--layout"])
+        .arg(&layout_path)
+        .args(["--layer", "1", "--position", "0,1", "--grid"])
+        .arg(&grid_path)
+        .output()
+        .expect("Failed to execute command");
+
+    let stderr = String::from_utf8_lossy(&output.stderr);
+    assert_eq!(output.status.code(), Some(0), "stderr: {stderr}");
+    assert!(String::from_utf8_lossy(&output.stdout).contains("Set 4 key(s) on layer 1"));
+    // Every row starts at column 1
+    assert_eq!(
+        layer_keycodes(&layout_path, 1),
+        [
+            "KC_TRNS",
+            "KC_Q",
+            "KC_W",
+            "KC_F3",
+            "LT(1, KC_SPC)",
+            "KC_ESC"
+        ]
+    );
+}
+
+#[test]
+fn test_layer_set_rejects_invalid_cells() {
+    let (layout_path, temp_dir) = create_temp_layout_file(&test_layout_basic(2, 3));
+    let grid_path = temp_dir.path().join("grid.md");
+    fs::write(&grid_path, "| KC_A | NOT_A_KEY | KC_C | KC_D |\n").unwrap();
+    let before = fs::read_to_string(&layout_path).unwrap();
+
+    let output = Command::new(lazyqmk_bin())
+        .args(["layer", "set", "--layout"])
+        .arg(&layout_path)
+        .args(["--layer", "0", "--grid"])
+        .arg(&grid_path)
+        .output()
+        .expect("Failed to execute command");
+
+    let stderr = String::from_utf8_lossy(&output.stderr);
+    assert_eq!(output.status.code(), Some(1), "stderr: {stderr}");
+    assert!(
+        stderr.contains("NOT_A_KEY (invalid keycode)"),
+        "stderr: {stderr}"
+    );
+    assert!(
+        stderr.contains("KC_D (no key at this position)"),
+        "stderr: {stderr}"
+    );
+    assert_eq!(fs::read_to_string(&layout_path).unwrap(), before);
+
+    let output = Command::new(lazyqmk_bin())
+        .args(["layer", "set", "--skip-invalid", "--layout"])
+        .arg(&layout_path)
+        .args(["--layer", "0", "--grid"])
+        .arg(&grid_path)
+        .output()
+        .expect("Failed to execute command");
+
+    assert_eq!(output.status.code(), Some(0));
+    assert_eq!(
+        layer_keycodes(&layout_path, 0),
+        ["KC_A", "KC_1", "KC_C", "KC_3", "KC_4", "KC_5"]
+    );
+}
+
+#[test]
+fn test_layer_set_dry_run() {
+    let (layout_path, temp_dir) = create_temp_layout_file(&test_layout_basic(2, 3));
+    let grid_path = temp_dir.path().join("grid.txt");
+    fs::write(&grid_path, "KC_A").unwrap();
+    let before = fs::read_to_string(&layout_path).unwrap();
+
+    let output = Command::new(lazyqmk_bin())
+        .args(["layer", "set", "--dry-run", "--layout"])
+        .arg(&layout_path)
+        .args(["--layer", "0", "--grid"])
+        .arg(&grid_path)
+        .output()
+        .expect("Failed to execute command");
+
+    let stdout = String::from_utf8_lossy(&output.stdout);
+    assert_eq!(output.status.code(), Some(0));
+    assert!(stdout.contains("(0, 0)  KC_0 -> KC_A"), "stdout: {stdout}");
+    assert!(stdout.contains("Would set 1 key(s) on layer 0"));
+    assert_eq!(fs::read_to_string(&layout_path).unwrap(), before);
+}
+
+#[test]
+fn test_layer_mirror_copies_left_half() {
+    let (layout_path, _temp_dir) = create_temp_layout_file(&test_layout_basic(2, 3));
+    let (config, _config_temp) = temp_config_with_qmk(None);
+    let qmk_path = config.paths.qmk_firmware.unwrap();
+
+    let output = Command::new(lazyqmk_bin())
+        .args(["layer", "mirror", "--layout"])
+        .arg(&layout_path)
+        .arg("--qmk-path")
+        .arg(&qmk_path)
+        .args(["--layer", "0", "--direction", "left-to-right"])
+        .output()
+        .expect("Failed to execute command");
+
+    let stderr = String::from_utf8_lossy(&output.stderr);
+    assert_eq!(output.status.code(), Some(0), "stderr: {stderr}");
+    assert!(String::from_utf8_lossy(&output.stdout)
+        .contains("Mirrored 2 key(s) on layer 0 left-to-right"));
+    // The middle column sits on the center line and is left alone
+    assert_eq!(
+        layer_keycodes(&layout_path, 0),
+        ["KC_0", "KC_1", "KC_0", "KC_3", "KC_4", "KC_3"]
+    );
+
+    let output = Command::new(lazyqmk_bin())
+        .args(["layer", "mirror", "--layout"])
+        .arg(&layout_path)
+        .arg("--qmk-path")
+        .arg(&qmk_path)
+        .args(["--layer", "0", "--direction", "sideways"])
+        .output()
+        .expect("Failed to execute command");
+    assert_eq!(output.status.code(), Some(2));
+}
+
+#[test]
+fn test_layer_duplicate_appends_copy() {
+    let (layout_path, _temp_dir) = create_temp_layout_file(&test_layout_basic(2, 3));
+
+    let output = Command::new(lazyqmk_bin())
+        .args(["layer", "duplicate", "--layout"])
+        .arg(&layout_path)
+        .args(["--from", "0", "--name", "Base Copy"])
+        .output()
+        .expect("Failed to execute command");
+
+    let stderr = String::from_utf8_lossy(&output.stderr);
+    assert_eq!(output.status.code(), Some(0), "stderr: {stderr}");
+    assert!(String::from_utf8_lossy(&output.stdout)
+        .contains("Duplicated layer 0 as layer 2 'Base Copy'"));
+    let layout = lazyqmk::parser::parse_markdown_layout(&layout_path).unwrap();
+    assert_eq!(layout.layers.len(), 3);
+    assert_eq!(layout.layers[2].number, 2);
+    assert_eq!(layout.layers[2].name, "Base Copy");
+    assert_eq!(
+        layer_keycodes(&layout_path, 2),
+        layer_keycodes(&layout_path, 0)
+    );
+
+    let output = Command::new(lazyqmk_bin())
+        .args(["layer", "duplicate", "--layout"])
+        .arg(&layout_path)
+        .args(["--from", "7"])
+        .output()
+        .expect("Failed to execute command");
+    assert_eq!(output.status.code(), Some(1));
+    assert!(String::from_utf8_lossy(&output.stderr).contains("Layer 7 does not exist"));
+}
+
+#[test]
+fn test_layer_move_rewrites_references() {
+    let mut layout = test_layout_basic(2, 3);
+    layout.duplicate_layer(1, "Sym").unwrap();
+    layout.layers[0].keys[0].keycode = "MO(2)".to_string();
+    layout.layers[0].keys[1].keycode = "LT(1, KC_A)".to_string();
+    let (layout_path, _temp_dir) = create_temp_layout_file(&layout);
+
+    let output = Command::new(lazyqmk_bin())
+        .args(["layer", "move", "--layout"])
+        .arg(&layout_path)
+        .args(["--from", "2", "--to", "1"])
+        .output()
+        .expect("Failed to execute command");
+
+    let stderr = String::from_utf8_lossy(&output.stderr);
+    assert_eq!(output.status.code(), Some(0), "stderr: {stderr}");
+    assert!(String::from_utf8_lossy(&output.stdout)
+        .contains("Moved layer 'Sym' from 2 to 1, updated 2 reference(s)"));
+    let moved = lazyqmk::parser::parse_markdown_layout(&layout_path).unwrap();
+    assert_eq!(moved.layers[1].name, "Sym");
+    assert_eq!(
+        &layer_keycodes(&layout_path, 0)[..2],
+        ["MO(1)", "LT(2, KC_A)"]
+    );
+
+    let output = Command::new(lazyqmk_bin())
+        .args(["layer", "move", "--layout"])
+        .arg(&layout_path)
+        .args(["--from", "0", "--to", "2"])
+        .output()
+        .expect("Failed to execute command");
+    assert_eq!(output.status.code(), Some(1));
+    assert!(String::from_utf8_lossy(&output.stderr).contains("cannot be moved"));
+}
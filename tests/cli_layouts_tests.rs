@@ -0,0 +1,90 @@
+//! End-to-end tests for `lazyqmk layouts list`.
+
+use std::fs;
+use std::path::Path;
+use std::process::Command;
+
+mod fixtures;
+use fixtures::*;
+
+/// Path to the lazyqmk binary
+fn lazyqmk_bin() -> &'static str {
+    env!("CARGO_BIN_EXE_lazyqmk")
+}
+
+/// Runs `lazyqmk layouts list --json` on `dir` with extra arguments.
+fn list_json(dir: &Path, extra: &[&str]) -> serde_json::Value {
+    let output = Command::new(lazyqmk_bin())
+        .args(["layouts", "list", "--dir", dir.to_str().unwrap(), "--json"])
+        .args(extra)
+        .output()
+        .expect("Failed to execute command");
+    assert_eq!(
+        output.status.code(),
+        Some(0),
+        "stderr: {}",
+        String::from_utf8_lossy(&output.stderr)
+    );
+    serde_json::from_slice(&output.stdout).expect("Should parse JSON output")
+}
+
+#[test]
+fn test_layouts_list_reports_layouts_and_problems() {
+    let (layout_path, temp_dir) = create_temp_layout_file(&test_layout_basic(2, 3));
+    fs::write(temp_dir.path().join("broken.md"), "# Broken\n\nno layers").unwrap();
+    fs::write(temp_dir.path().join("readme.txt"), "not a layout").unwrap();
+
+    let all = list_json(temp_dir.path(), &[]);
+    assert_eq!(all["count"], 2);
+    assert_eq!(all["problems"], 1);
+    let good = all["layouts"]
+        .as_array()
+        .unwrap()
+        .iter()
+        .find(|entry| entry["status"] == "ok")
+        .unwrap();
+    assert_eq!(good["path"], layout_path.to_str().unwrap());
+    assert_eq!(good["name"], "Test Layout");
+    assert_eq!(good["layers"], 2);
+    assert!(good["modified"].is_string());
+
+    let problems = list_json(temp_dir.path(), &["--problems"]);
+    assert_eq!(problems["count"], 1);
+    assert_eq!(problems["layouts"][0]["status"], "error");
+    assert!(problems["layouts"][0]["error"].is_string());
+}
+
+#[test]
+fn test_layouts_list_human_output() {
+    let (_layout_path, temp_dir) = create_temp_layout_file(&test_layout_basic(2, 3));
+
+    let output = Command::new(lazyqmk_bin())
+        .args([
+            "layouts",
+            "list",
+            "--dir",
+            temp_dir.path().to_str().unwrap(),
+        ])
+        .output()
+        .expect("Failed to execute command");
+    assert_eq!(output.status.code(), Some(0));
+    let stdout = String::from_utf8_lossy(&output.stdout);
+    assert!(stdout.contains("Test Layout (test_layout.md)"));
+    assert!(stdout.contains("2 layer(s)"));
+}
+
+#[test]
+fn test_layouts_list_missing_directory() {
+    let temp_dir = tempfile::TempDir::new().unwrap();
+    let output = Command::new(lazyqmk_bin())
+        .args([
+            "layouts",
+            "list",
+            "--dir",
+            temp_dir.path().join("missing").to_str().unwrap(),
+        ])
+        .output()
+        .expect("Failed to execute command");
+    assert_ne!(output.status.code(), Some(0));
+    assert!(String::from_utf8_lossy(&output.stderr).contains("not found"));
+}
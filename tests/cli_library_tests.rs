@@ -0,0 +1,128 @@
+//! End-to-end tests for `lazyqmk library export` and `library import`.
+
+use lazyqmk::models::TapDanceAction;
+use lazyqmk::services::LayoutService;
+use std::fs;
+use std::path::Path;
+use std::process::{Command, Output};
+
+mod fixtures;
+use fixtures::*;
+
+/// Path to the lazyqmk binary
+fn lazyqmk_bin() -> &'static str {
+    env!("CARGO_BIN_EXE_lazyqmk")
+}
+
+/// Runs `lazyqmk library` with the given arguments.
+fn library(args: &[&str]) -> Output {
+    Command::new(lazyqmk_bin())
+        .arg("library")
+        .args(args)
+        .output()
+        .expect("Failed to execute command")
+}
+
+/// Exports the tap dances of the standard tap dance fixture to `output`.
+fn export_fixture_library(dir: &Path) -> String {
+    let source = dir.join("source.md");
+    write_layout_file(&test_layout_with_tap_dances(), &source).unwrap();
+    let output_path = dir.join("my-lib.toml");
+    let output = library(&[
+        "export",
+        "--layout",
+        source.to_str().unwrap(),
+        "--output",
+        output_path.to_str().unwrap(),
+    ]);
+    assert_eq!(
+        output.status.code(),
+        Some(0),
+        "stderr: {}",
+        String::from_utf8_lossy(&output.stderr)
+    );
+    output_path.to_str().unwrap().to_string()
+}
+
+#[test]
+fn test_library_export_then_import_selected() {
+    let (target, temp_dir) = create_temp_layout_file(&test_layout_basic(2, 3));
+    let lib = export_fixture_library(temp_dir.path());
+    let content = fs::read_to_string(&lib).unwrap();
+    assert!(content.contains("[[tap_dance]]"));
+    assert!(content.contains("esc_caps"));
+
+    let output = library(&[
+        "import",
+        "--layout",
+        target.to_str().unwrap(),
+        "--from",
+        &lib,
+        "--only",
+        "shift_ctrl",
+    ]);
+    assert_eq!(output.status.code(), Some(0));
+    assert!(String::from_utf8_lossy(&output.stdout).contains("+ shift_ctrl"));
+
+    let layout = LayoutService::load(&target).unwrap();
+    assert_eq!(layout.tap_dances.len(), 1);
+    assert_eq!(
+        layout.get_tap_dance("shift_ctrl").unwrap().hold.as_deref(),
+        Some("KC_LCTL")
+    );
+}
+
+#[test]
+fn test_library_import_refuses_conflicts() {
+    let mut layout = test_layout_basic(2, 3);
+    layout.tap_dances = vec![TapDanceAction::new("esc_caps", "KC_GRV")];
+    let (target, temp_dir) = create_temp_layout_file(&layout);
+    let lib = export_fixture_library(temp_dir.path());
+    let before = fs::read_to_string(&target).unwrap();
+
+    let output = library(&[
+        "import",
+        "--layout",
+        target.to_str().unwrap(),
+        "--from",
+        &lib,
+    ]);
+    assert_eq!(output.status.code(), Some(1));
+    assert!(String::from_utf8_lossy(&output.stderr).contains("esc_caps"));
+    assert_eq!(fs::read_to_string(&target).unwrap(), before);
+
+    let output = library(&[
+        "import",
+        "--layout",
+        target.to_str().unwrap(),
+        "--from",
+        &lib,
+        "--skip-conflicts",
+    ]);
+    assert_eq!(output.status.code(), Some(0));
+    assert!(String::from_utf8_lossy(&output.stdout).contains("! esc_caps"));
+    let layout = LayoutService::load(&target).unwrap();
+    assert_eq!(
+        layout.get_tap_dance("esc_caps").unwrap().single_tap,
+        "KC_GRV"
+    );
+    assert!(layout.get_tap_dance("shift_ctrl").is_some());
+}
+
+#[test]
+fn test_library_import_unknown_name_fails() {
+    let (target, temp_dir) = create_temp_layout_file(&test_layout_basic(2, 3));
+    let lib = export_fixture_library(temp_dir.path());
+
+    let output = library(&[
+        "import",
+        "--layout",
+        target.to_str().unwrap(),
+        "--from",
+        &lib,
+        "--only",
+        "ghost_dance",
+    ]);
+    assert_eq!(output.status.code(), Some(1));
+    assert!(String::from_utf8_lossy(&output.stderr).contains("ghost_dance"));
+}
@@ -0,0 +1,164 @@
+//! End-to-end tests for `lazyqmk lint` command.
+
+use std::process::Command;
+
+mod fixtures;
+use fixtures::*;
+
+/// Path to the lazyqmk binary
+fn lazyqmk_bin() -> &'static str {
+    env!("CARGO_BIN_EXE_lazyqmk")
+}
+
+fn lint_json(layout_path: &std::path::Path) -> (Option<i32>, serde_json::Value) {
+    let output = Command::new(lazyqmk_bin())
+        .args(["lint", "--layout", layout_path.to_str().unwrap(), "--json"])
+        .output()
+        .expect("Failed to execute command");
+    let stdout = String::from_utf8_lossy(&output.stdout);
+    let result = serde_json::from_str(&stdout).expect("Should parse JSON output");
+    (output.status.code(), result)
+}
+
+#[test]
+fn test_lint_warnings_exit_zero() {
+    // Layer 1 is never referenced: a warning, not an error
+    let layout = test_layout_basic(2, 3);
+    let (layout_path, _temp_dir) = create_temp_layout_file(&layout);
+
+    let (code, result) = lint_json(&layout_path);
+
+    assert_eq!(code, Some(0), "Warnings alone should not fail lint");
+    assert_eq!(result["valid"], true);
+    assert_eq!(result["checks"]["L005"], "warning");
+    assert_eq!(result["checks"]["L003"], "passed");
+
+    let finding = result["errors"]
+        .as_array()
+        .unwrap()
+        .iter()
+        .find(|e| e["rule"] == "L005")
+        .expect("Should report unreachable layer");
+    assert_eq!(finding["severity"], "warning");
+    assert_eq!(finding["layer"], 1);
+}
+
+#[test]
+fn test_lint_strict_fails_on_warnings() {
+    let layout = test_layout_basic(2, 3);
+    let (layout_path, _temp_dir) = create_temp_layout_file(&layout);
+
+    let output = Command::new(lazyqmk_bin())
+        .args([
+            "lint",
+            "--layout",
+            layout_path.to_str().unwrap(),
+            "--strict",
+        ])
+        .output()
+        .expect("Failed to execute command");
+
+    assert_eq!(output.status.code(), Some(1));
+    let stdout = String::from_utf8_lossy(&output.stdout);
+    assert!(stdout.contains("L005"), "Human output should list the rule");
+}
+
+#[test]
+fn test_lint_toggle_trap_is_error() {
+    // TG() into a layer with no way back
+    let layout = test_layout_with_layer_refs();
+    let (layout_path, _temp_dir) = create_temp_layout_file(&layout);
+
+    let (code, result) = lint_json(&layout_path);
+
+    assert_eq!(code, Some(1), "Lint errors should exit with code 1");
+    assert_eq!(result["valid"], false);
+    assert_eq!(result["checks"]["L003"], "failed");
+    let finding = result["errors"]
+        .as_array()
+        .unwrap()
+        .iter()
+        .find(|e| e["rule"] == "L003")
+        .unwrap();
+    assert_eq!(finding["severity"], "error");
+}
+
+#[test]
+fn test_lint_respects_frontmatter_disable() {
+    let mut layout = test_layout_with_layer_refs();
+    layout.metadata.lint.disable = vec!["L003".to_string()];
+    let (layout_path, _temp_dir) = create_temp_layout_file(&layout);
+
+    let content = std::fs::read_to_string(&layout_path).unwrap();
+    assert!(
+        content.contains("lint:"),
+        "Settings should be saved in frontmatter"
+    );
+
+    let (code, result) = lint_json(&layout_path);
+
+    assert_eq!(code, Some(0));
+    assert_eq!(result["checks"]["L003"], "disabled");
+    assert!(result["errors"]
+        .as_array()
+        .unwrap()
+        .iter()
+        .all(|e| e["rule"] != "L003"));
+}
+
+#[test]
+fn test_lint_list_rules() {
+    let output = Command::new(lazyqmk_bin())
+        .args(["lint", "--list-rules", "--json"])
+        .output()
+        .expect("Failed to execute command");
+
+    assert_eq!(output.status.code(), Some(0));
+    let rules: serde_json::Value =
+        serde_json::from_str(&String::from_utf8_lossy(&output.stdout)).unwrap();
+    let rules = rules.as_array().unwrap();
+    assert!(rules.len() >= 6);
+    assert!(rules
+        .iter()
+        .all(|r| r["id"].is_string() && r["severity"].is_string() && r["description"].is_string()));
+}
+
+#[test]
+fn test_lint_missing_file() {
+    let output = Command::new(lazyqmk_bin())
+        .args(["lint", "--layout", "/nonexistent/layout.md"])
+        .output()
+        .expect("Failed to execute command");
+
+    assert_ne!(output.status.code(), Some(0));
+}
+
+#[test]
+fn test_lint_fix_rewrites_shifted_forms() {
+    let mut layout = test_layout_basic(1, 3);
+    layout.layers[0].keys[0].keycode = "KC_LPRN".to_string();
+    layout.layers[0].keys[1].keycode = "S(KC_9)".to_string();
+    layout.metadata.lint.shifted_form = Some(lazyqmk::models::ShiftedForm::Named);
+    let (layout_path, _temp_dir) = create_temp_layout_file(&layout);
+
+    let (code, result) = lint_json(&layout_path);
+    assert_eq!(code, Some(0));
+    assert_eq!(result["checks"]["L011"], "warning");
+    assert_eq!(result["checks"]["L012"], "info");
+
+    let output = Command::new(lazyqmk_bin())
+        .args(["lint", "--layout", layout_path.to_str().unwrap(), "--fix"])
+        .output()
+        .expect("Failed to execute command");
+    let stdout = String::from_utf8_lossy(&output.stdout);
+    assert_eq!(output.status.code(), Some(0), "stdout: {stdout}");
+    assert!(stdout.contains("S(KC_9) → KC_LPRN"), "stdout: {stdout}");
+    assert!(stdout.contains("Applied 1 fix(es)"));
+
+    let content = std::fs::read_to_string(&layout_path).unwrap();
+    assert!(!content.contains("S(KC_9)"), "Fix should be saved");
+    let (_, result) = lint_json(&layout_path);
+    assert_eq!(result["checks"]["L012"], "passed");
+    // Still two keys typing "(": the fix makes the duplicate easy to spot
+    assert_eq!(result["checks"]["L011"], "warning");
+}
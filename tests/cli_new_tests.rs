@@ -0,0 +1,145 @@
+//! End-to-end tests for `lazyqmk new`.
+
+use std::process::Command;
+
+mod fixtures;
+use fixtures::*;
+
+/// Runs `lazyqmk new` against the fixture QMK tree with an isolated config directory.
+fn run_new(
+    args: &[&str],
+    qmk_path: &std::path::Path,
+    config_dir: &std::path::Path,
+) -> std::process::Output {
+    Command::new(env!("CARGO_BIN_EXE_lazyqmk"))
+        .env("LAZYQMK_CONFIG_DIR", config_dir)
+        .arg("new")
+        .args(["--keyboard", "test_keyboard", "--qmk-path"])
+        .arg(qmk_path)
+        .args(args)
+        .output()
+        .expect("Failed to execute command")
+}
+
+#[test]
+fn test_new_creates_layout_and_remembers_variant() {
+    let (config, temp_dir) = temp_config_with_qmk(None);
+    let qmk_path = config.paths.qmk_firmware.unwrap();
+    let config_dir = temp_dir.path().join("config");
+    let layout_path = temp_dir.path().join("first.md");
+
+    let output = run_new(
+        &[
+            "-n",
+            "First",
+            "--layers",
+            "3",
+            "-o",
+            layout_path.to_str().unwrap(),
+        ],
+        &qmk_path,
+        &config_dir,
+    );
+    assert_eq!(
+        output.status.code(),
+        Some(0),
+        "stderr: {}",
+        String::from_utf8_lossy(&output.stderr)
+    );
+    let stdout = String::from_utf8_lossy(&output.stdout);
+    assert!(stdout.contains("Variant: LAYOUT_test"), "{stdout}");
+    assert!(stdout.contains("Keymap: first"));
+    assert!(stdout.contains("Layers: 3"));
+
+    let content = std::fs::read_to_string(&layout_path).unwrap();
+    assert!(content.contains("Layer 2"));
+
+    // The variant is stored for the keyboard's next layout
+    let config_toml = std::fs::read_to_string(config_dir.join("config.toml")).unwrap();
+    assert!(
+        config_toml.contains("[keyboards.test_keyboard]"),
+        "{config_toml}"
+    );
+    assert!(config_toml.contains("default_variant = \"LAYOUT_test\""));
+
+    // Refuses to overwrite
+    let output = run_new(
+        &["-o", layout_path.to_str().unwrap()],
+        &qmk_path,
+        &config_dir,
+    );
+    assert_ne!(output.status.code(), Some(0));
+}
+
+#[test]
+fn test_new_uses_keyboard_defaults() {
+    let (config, temp_dir) = temp_config_with_qmk(None);
+    let qmk_path = config.paths.qmk_firmware.unwrap();
+    let config_dir = temp_dir.path().join("config");
+
+    let output = Command::new(env!("CARGO_BIN_EXE_lazyqmk"))
+        .env("LAZYQMK_CONFIG_DIR", &config_dir)
+        .args([
+            "config",
+            "set",
+            "--keyboard",
+            "test_keyboard",
+            "--keymap-pattern",
+            "{keyboard}_{layout}",
+            "--layer-count",
+            "2",
+        ])
+        .output()
+        .expect("Failed to execute command");
+    assert_eq!(output.status.code(), Some(0));
+
+    let layout_path = temp_dir.path().join("mine.md");
+    let output = run_new(
+        &["-n", "Mine", "-o", layout_path.to_str().unwrap()],
+        &qmk_path,
+        &config_dir,
+    );
+    assert_eq!(
+        output.status.code(),
+        Some(0),
+        "stderr: {}",
+        String::from_utf8_lossy(&output.stderr)
+    );
+    let stdout = String::from_utf8_lossy(&output.stdout);
+    assert!(stdout.contains("Keymap: test_keyboard_mine"), "{stdout}");
+    assert!(stdout.contains("Layers: 2"));
+}
+
+#[test]
+fn test_new_in_portable_mode_writes_next_to_working_directory() {
+    let (config, temp_dir) = temp_config_with_qmk(None);
+    let qmk_path = config.paths.qmk_firmware.unwrap();
+    let stick = temp_dir.path().join("stick");
+    std::fs::create_dir_all(&stick).unwrap();
+
+    for portable in [&["--portable"][..], &[]] {
+        let output = Command::new(env!("CARGO_BIN_EXE_lazyqmk"))
+            .current_dir(&stick)
+            .env_remove("LAZYQMK_CONFIG_DIR")
+            .env(
+                "LAZYQMK_PORTABLE",
+                if portable.is_empty() { "1" } else { "0" },
+            )
+            .arg("new")
+            .args(portable)
+            .args(["--keyboard", "test_keyboard", "-n", "Travel", "--qmk-path"])
+            .arg(&qmk_path)
+            .output()
+            .expect("Failed to execute command");
+        assert_eq!(
+            output.status.code(),
+            Some(0),
+            "stderr: {}",
+            String::from_utf8_lossy(&output.stderr)
+        );
+        let state = stick.join(".lazyqmk");
+        assert!(state.join("layouts").join("travel.md").exists());
+        assert!(state.join("config.toml").exists());
+        std::fs::remove_dir_all(state).unwrap();
+    }
+}
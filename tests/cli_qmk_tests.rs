@@ -741,3 +741,40 @@ fn test_geometry_matrix_and_led_indices() {
         }
     }
 }
+
+#[test]
+fn test_geometry_positions_table() {
+    let fixture_path = mock_qmk_fixture();
+    let base_args = [
+        "geometry",
+        "--qmk-path",
+        "dummy",
+        "--keyboard",
+        "crkbd",
+        "--layout-name",
+        "LAYOUT_split_3x6_3",
+        "--positions",
+    ];
+
+    let output = Command::new(lazyqmk_bin())
+        .args(base_args)
+        .env("LAZYQMK_QMK_FIXTURE", &fixture_path)
+        .output()
+        .expect("Failed to execute geometry command");
+    assert_eq!(output.status.code(), Some(0));
+    let stdout = String::from_utf8_lossy(&output.stdout);
+    assert!(stdout.contains("Key Positions:"));
+    assert!(stdout.contains("Position | Matrix"));
+
+    let output = Command::new(lazyqmk_bin())
+        .args(base_args)
+        .arg("--json")
+        .env("LAZYQMK_QMK_FIXTURE", &fixture_path)
+        .output()
+        .expect("Failed to execute geometry command");
+    assert_eq!(output.status.code(), Some(0));
+    let json: serde_json::Value = serde_json::from_slice(&output.stdout).unwrap();
+    let mappings = json["mappings"].as_array().unwrap();
+    assert_eq!(mappings.len(), 42);
+    assert!(mappings.iter().all(|m| m["position"].is_array()));
+}
@@ -0,0 +1,165 @@
+//! End-to-end tests for `lazyqmk remap` command.
+
+use std::fs;
+use std::path::Path;
+use std::process::Command;
+
+mod fixtures;
+use fixtures::*;
+
+/// Path to the lazyqmk binary
+fn lazyqmk_bin() -> &'static str {
+    env!("CARGO_BIN_EXE_lazyqmk")
+}
+
+/// Adds a keyboard with a single layout to the fixture QMK tree.
+fn add_keyboard(qmk_path: &Path, name: &str, variant: &str, keys: &[(u8, u8)]) {
+    let layout: Vec<serde_json::Value> = keys
+        .iter()
+        .enumerate()
+        .map(|(idx, (x, y))| serde_json::json!({"matrix": [0, idx], "x": x, "y": y}))
+        .collect();
+    let info_json = serde_json::json!({
+        "keyboard_name": name,
+        "layouts": { variant: { "layout": layout } }
+    });
+
+    let keyboard_dir = qmk_path.join("keyboards").join(name);
+    fs::create_dir_all(&keyboard_dir).unwrap();
+    fs::write(
+        keyboard_dir.join("info.json"),
+        serde_json::to_string_pretty(&info_json).unwrap(),
+    )
+    .unwrap();
+}
+
+#[test]
+fn test_remap_to_larger_keyboard() {
+    let layout = test_layout_basic(2, 3);
+    let (layout_path, temp_dir) = create_temp_layout_file(&layout);
+    let (config, _config_temp) = temp_config_with_qmk(None);
+    let qmk_path = config.paths.qmk_firmware.unwrap();
+    let grid: Vec<(u8, u8)> = (0..3).flat_map(|y| (0..3).map(move |x| (x, y))).collect();
+    add_keyboard(&qmk_path, "big_keyboard", "LAYOUT_big", &grid);
+    let out_path = temp_dir.path().join("big.md");
+
+    let output = Command::new(lazyqmk_bin())
+        .args([
+            "remap",
+            "--layout",
+            layout_path.to_str().unwrap(),
+            "--qmk-path",
+            qmk_path.to_str().unwrap(),
+            "--to-keyboard",
+            "big_keyboard",
+            "--to-variant",
+            "LAYOUT_big",
+            "--output",
+            out_path.to_str().unwrap(),
+        ])
+        .output()
+        .expect("Failed to execute command");
+
+    let stdout = String::from_utf8_lossy(&output.stdout);
+    assert_eq!(
+        output.status.code(),
+        Some(0),
+        "stderr: {}",
+        String::from_utf8_lossy(&output.stderr)
+    );
+    assert!(stdout.contains("Layer 0 (Base): 6 auto-matched, 0 explicit, 0 orphaned, 3 empty"));
+
+    let remapped = fs::read_to_string(&out_path).unwrap();
+    assert!(remapped.contains("keyboard: big_keyboard"));
+    assert!(remapped.contains("layout_variant: LAYOUT_big"));
+    assert!(!remapped.contains("## Orphaned Keys"));
+}
+
+#[test]
+fn test_remap_with_map_orphans_unmatched_keys() {
+    let layout = test_layout_basic(2, 3);
+    let (layout_path, temp_dir) = create_temp_layout_file(&layout);
+    let (config, _config_temp) = temp_config_with_qmk(None);
+    let qmk_path = config.paths.qmk_firmware.unwrap();
+    add_keyboard(
+        &qmk_path,
+        "small_keyboard",
+        "LAYOUT_small",
+        &[(0, 0), (2, 0)],
+    );
+    let map_path = temp_dir.path().join("map.toml");
+    fs::write(&map_path, "[positions]\n\"1,2\" = \"0,0\"\n").unwrap();
+    let out_path = temp_dir.path().join("small.md");
+
+    let output = Command::new(lazyqmk_bin())
+        .args([
+            "remap",
+            "--layout",
+            layout_path.to_str().unwrap(),
+            "--qmk-path",
+            qmk_path.to_str().unwrap(),
+            "--to-keyboard",
+            "small_keyboard",
+            "--to-variant",
+            "LAYOUT_small",
+            "--output",
+            out_path.to_str().unwrap(),
+            "--map",
+            map_path.to_str().unwrap(),
+        ])
+        .output()
+        .expect("Failed to execute command");
+
+    let stdout = String::from_utf8_lossy(&output.stdout);
+    assert_eq!(
+        output.status.code(),
+        Some(0),
+        "stderr: {}",
+        String::from_utf8_lossy(&output.stderr)
+    );
+    assert!(stdout.contains("Layer 0 (Base): 1 auto-matched, 1 explicit, 4 orphaned, 0 empty"));
+
+    let remapped = fs::read_to_string(&out_path).unwrap();
+    assert!(remapped.contains("## Orphaned Keys"));
+    assert!(remapped.contains("- 0:0:0: KC_0"));
+}
+
+#[test]
+fn test_remap_rejects_unknown_map_position() {
+    let layout = test_layout_basic(2, 3);
+    let (layout_path, temp_dir) = create_temp_layout_file(&layout);
+    let (config, _config_temp) = temp_config_with_qmk(None);
+    let qmk_path = config.paths.qmk_firmware.unwrap();
+    add_keyboard(
+        &qmk_path,
+        "small_keyboard",
+        "LAYOUT_small",
+        &[(0, 0), (2, 0)],
+    );
+    let map_path = temp_dir.path().join("map.toml");
+    fs::write(&map_path, "[positions]\n\"0,0\" = \"5,5\"\n").unwrap();
+    let out_path = temp_dir.path().join("small.md");
+
+    let output = Command::new(lazyqmk_bin())
+        .args([
+            "remap",
+            "--layout",
+            layout_path.to_str().unwrap(),
+            "--qmk-path",
+            qmk_path.to_str().unwrap(),
+            "--to-keyboard",
+            "small_keyboard",
+            "--to-variant",
+            "LAYOUT_small",
+            "--output",
+            out_path.to_str().unwrap(),
+            "--map",
+            map_path.to_str().unwrap(),
+        ])
+        .output()
+        .expect("Failed to execute command");
+
+    assert_eq!(output.status.code(), Some(1));
+    assert!(String::from_utf8_lossy(&output.stderr).contains("not a key on the target keyboard"));
+    assert!(!out_path.exists());
+}
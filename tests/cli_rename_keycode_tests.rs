@@ -0,0 +1,86 @@
+//! End-to-end tests for `lazyqmk rename-keycode`.
+
+use std::path::Path;
+use std::process::{Command, Output};
+
+mod fixtures;
+use fixtures::*;
+use lazyqmk::models::Layout;
+use lazyqmk::services::LayoutService;
+
+/// Path to the lazyqmk binary
+fn lazyqmk_bin() -> &'static str {
+    env!("CARGO_BIN_EXE_lazyqmk")
+}
+
+fn rename(args: &[&str], files: &[&Path]) -> Output {
+    Command::new(lazyqmk_bin())
+        .arg("rename-keycode")
+        .args(args)
+        .args(files)
+        .output()
+        .expect("Failed to execute command")
+}
+
+/// Layout whose first two keys use the custom keycode `MY_EMOJI`.
+fn layout_with_custom_keycode() -> Layout {
+    let mut layout = test_layout_basic(2, 3);
+    layout.layers[0].keys[0].keycode = "MY_EMOJI".to_string();
+    layout.layers[0].keys[1].keycode = "MT(MOD_LSFT, MY_EMOJI)".to_string();
+    layout
+}
+
+#[test]
+fn test_rename_keycode_across_files() {
+    let (first, _first_dir) = create_temp_layout_file(&layout_with_custom_keycode());
+    let (second, _second_dir) = create_temp_layout_file(&test_layout_basic(2, 3));
+
+    let output = rename(
+        &["--from", "MY_EMOJI", "--to", "EMOJI_PICK"],
+        &[&first, &second],
+    );
+
+    let stdout = String::from_utf8_lossy(&output.stdout);
+    assert_eq!(
+        output.status.code(),
+        Some(0),
+        "stderr: {}",
+        String::from_utf8_lossy(&output.stderr)
+    );
+    assert!(stdout.contains("2 keys"), "stdout: {stdout}");
+    assert!(stdout.contains("no matches"));
+    assert!(stdout.contains("in 2 place(s) across 2 file(s)"));
+
+    let layout = LayoutService::load(&first).unwrap();
+    assert_eq!(layout.layers[0].keys[0].keycode, "EMOJI_PICK");
+    assert_eq!(layout.layers[0].keys[1].keycode, "MT(MOD_LSFT, EMOJI_PICK)");
+}
+
+#[test]
+fn test_rename_keycode_dry_run_leaves_files_untouched() {
+    let (path, _temp_dir) = create_temp_layout_file(&layout_with_custom_keycode());
+    let before = std::fs::read_to_string(&path).unwrap();
+
+    let output = rename(
+        &["--from", "MY_EMOJI", "--to", "EMOJI_PICK", "--dry-run"],
+        &[&path],
+    );
+
+    assert_eq!(output.status.code(), Some(0));
+    assert!(String::from_utf8_lossy(&output.stdout).contains("Would rename"));
+    assert_eq!(std::fs::read_to_string(&path).unwrap(), before);
+}
+
+#[test]
+fn test_rename_keycode_refuses_existing_keycode_without_force() {
+    let (path, _temp_dir) = create_temp_layout_file(&layout_with_custom_keycode());
+
+    let output = rename(&["--from", "MY_EMOJI", "--to", "KC_A"], &[&path]);
+    assert_eq!(output.status.code(), Some(1));
+    assert!(String::from_utf8_lossy(&output.stderr).contains("--force"));
+
+    let output = rename(&["--from", "MY_EMOJI", "--to", "KC_A", "--force"], &[&path]);
+    assert_eq!(output.status.code(), Some(0));
+    let layout = LayoutService::load(&path).unwrap();
+    assert_eq!(layout.layers[0].keys[0].keycode, "KC_A");
+}
@@ -211,6 +211,62 @@ fn test_tap_dance_add_three_way() {
     assert!(content.contains("Hold: MO(1)"));
 }
 
+#[test]
+fn test_tap_dance_add_with_options() {
+    let layout = test_layout_basic(2, 3);
+    let (layout_path, _temp_dir) = create_temp_layout_file(&layout);
+
+    let output = Command::new(lazyqmk_bin())
+        .args([
+            "tap-dance",
+            "add",
+            "--layout",
+            layout_path.to_str().unwrap(),
+            "--name",
+            "slow_esc",
+            "--single",
+            "KC_ESC",
+            "--hold",
+            "KC_LCTL",
+            "--tapping-term",
+            "300",
+            "--on-each-tap",
+        ])
+        .output()
+        .expect("Failed to execute command");
+
+    assert_eq!(
+        output.status.code(),
+        Some(0),
+        "stderr: {}",
+        String::from_utf8_lossy(&output.stderr)
+    );
+
+    let content = fs::read_to_string(&layout_path).expect("Should read file");
+    assert!(content.contains("Tapping Term: 300ms"));
+    assert!(content.contains("On Each Tap: true"));
+
+    // Out-of-range terms are rejected
+    let output = Command::new(lazyqmk_bin())
+        .args([
+            "tap-dance",
+            "add",
+            "--layout",
+            layout_path.to_str().unwrap(),
+            "--name",
+            "too_fast",
+            "--single",
+            "KC_A",
+            "--tapping-term",
+            "10",
+        ])
+        .output()
+        .expect("Failed to execute command");
+
+    assert_eq!(output.status.code(), Some(1));
+    assert!(String::from_utf8_lossy(&output.stderr).contains("between 50 and 2000ms"));
+}
+
 #[test]
 fn test_tap_dance_add_duplicate_name() {
     let layout = test_layout_with_tap_dances();
@@ -714,6 +770,37 @@ fn test_tap_dance_validate_json() {
     );
 }
 
+#[test]
+fn test_tap_dance_json_error_kinds() {
+    let temp_dir = tempfile::TempDir::new().unwrap();
+    let missing = temp_dir.path().join("missing.md");
+    let broken = temp_dir.path().join("broken.md");
+    fs::write(&broken, "not a layout").unwrap();
+
+    for (subcommand, path, kind) in [
+        ("list", &missing, "not_found"),
+        ("list", &broken, "parse"),
+        ("validate", &missing, "not_found"),
+        ("validate", &broken, "parse"),
+    ] {
+        let output = Command::new(lazyqmk_bin())
+            .args([
+                "tap-dance",
+                subcommand,
+                "--layout",
+                path.to_str().unwrap(),
+                "--json",
+            ])
+            .output()
+            .expect("Failed to execute command");
+
+        assert_eq!(output.status.code(), Some(2), "{subcommand} {kind}");
+        let error: serde_json::Value =
+            serde_json::from_slice(&output.stderr).expect("stderr should be a JSON error");
+        assert_eq!(error["error"]["kind"], kind, "{subcommand}");
+    }
+}
+
 // ============================================================================
 // End-to-end flows
 // ============================================================================
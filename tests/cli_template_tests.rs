@@ -18,8 +18,8 @@ fn lazyqmk_bin() -> &'static str {
 
 /// Get the template directory path
 fn get_template_dir() -> PathBuf {
-    let config_dir = dirs::config_dir().expect("Failed to get config directory");
-    config_dir.join("LazyQMK").join("templates")
+    let data_dir = dirs::data_dir().expect("Failed to get data directory");
+    data_dir.join("LazyQMK").join("templates")
 }
 
 /// Clean up all templates in the template directory to ensure test isolation
@@ -44,6 +44,9 @@ fn cleanup_templates() {
 
 #[test]
 fn test_template_list_empty_directory() {
+    let _lock = TEMPLATE_TEST_LOCK.lock().unwrap();
+    cleanup_templates();
+
     let output = Command::new(lazyqmk_bin())
         .args(["template", "list"])
         .output()
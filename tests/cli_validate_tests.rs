@@ -1,6 +1,8 @@
 //! End-to-end tests for `lazyqmk validate` command.
 
+use std::path::PathBuf;
 use std::process::Command;
+use tempfile::TempDir;
 
 mod fixtures;
 use fixtures::*;
@@ -109,6 +111,40 @@ fn test_validate_invalid_keycode() {
     );
 }
 
+#[test]
+fn test_validate_prints_edit_command() {
+    let layout = test_layout_with_invalid_keycode();
+    let (layout_path, _temp_dir) = create_temp_layout_file(&layout);
+
+    let output = Command::new(lazyqmk_bin())
+        .args(["validate", layout_path.to_str().unwrap()])
+        .output()
+        .expect("Failed to execute command");
+
+    assert_eq!(output.status.code(), Some(1));
+    let stdout = String::from_utf8_lossy(&output.stdout);
+    let expected = format!(
+        "lazyqmk edit {} --layer 0 --position 0,0",
+        layout_path.display()
+    );
+    assert!(
+        stdout.contains(&expected),
+        "Output should suggest the edit command, got: {stdout}"
+    );
+}
+
+#[test]
+fn test_edit_focus_flags_require_a_file() {
+    let output = Command::new(lazyqmk_bin())
+        .args(["edit", "--layer", "1"])
+        .output()
+        .expect("Failed to execute command");
+
+    assert_eq!(output.status.code(), Some(2));
+    let stderr = String::from_utf8_lossy(&output.stderr);
+    assert!(stderr.contains("FILE"), "stderr: {stderr}");
+}
+
 // Note: "Missing position" test removed - sparse layouts (layouts with fewer keys than
 // positions) are actually valid and should not fail validation. A true "missing position"
 // error would be if a key references a position outside the geometry bounds, which is
@@ -172,6 +208,30 @@ fn test_validate_nonexistent_file() {
     );
 }
 
+#[test]
+fn test_validate_error_kinds() {
+    let temp_dir = TempDir::new().unwrap();
+    let broken = temp_dir.path().join("broken.md");
+    std::fs::write(&broken, "not a layout").unwrap();
+
+    let output = Command::new(lazyqmk_bin())
+        .args(["validate", "--layout", "/nonexistent/file.md", "--json"])
+        .output()
+        .expect("Failed to execute command");
+    assert_eq!(output.status.code(), Some(2));
+    let error: serde_json::Value =
+        serde_json::from_slice(&output.stderr).expect("stderr should be a JSON error");
+    assert_eq!(error["error"]["kind"], "not_found");
+
+    let output = Command::new(lazyqmk_bin())
+        .args(["validate", "--layout", broken.to_str().unwrap()])
+        .output()
+        .expect("Failed to execute command");
+    assert_eq!(output.status.code(), Some(2));
+    let stderr = String::from_utf8_lossy(&output.stderr);
+    assert!(stderr.starts_with("Error: [parse]"), "stderr: {stderr}");
+}
+
 #[test]
 fn test_validate_json_structure() {
     let layout = test_layout_basic(2, 3);
@@ -281,3 +341,168 @@ fn test_validate_with_layer_refs() {
         "Layer refs check should pass"
     );
 }
+
+/// Writes layouts into one temp directory and returns their paths.
+fn create_layout_dir(layouts: &[(&str, lazyqmk::models::Layout)]) -> (Vec<PathBuf>, TempDir) {
+    let temp_dir = TempDir::new().expect("Failed to create temp dir");
+    let paths = layouts
+        .iter()
+        .map(|(name, layout)| {
+            let path = temp_dir.path().join(name);
+            write_layout_file(layout, &path).expect("Failed to write layout");
+            path
+        })
+        .collect();
+    (paths, temp_dir)
+}
+
+#[test]
+fn test_validate_multiple_layout_flags_json() {
+    let (paths, _temp_dir) = create_layout_dir(&[
+        ("a.md", test_layout_basic(2, 3)),
+        ("b.md", test_layout_with_invalid_keycode()),
+    ]);
+
+    let output = Command::new(lazyqmk_bin())
+        .args([
+            "validate",
+            "--layout",
+            paths[0].to_str().unwrap(),
+            "--layout",
+            paths[1].to_str().unwrap(),
+            "--json",
+        ])
+        .output()
+        .expect("Failed to execute command");
+
+    assert_eq!(output.status.code(), Some(1));
+
+    let result: serde_json::Value =
+        serde_json::from_slice(&output.stdout).expect("Should parse JSON output");
+    assert_eq!(result["valid"], false);
+    assert_eq!(result["checked"], 2);
+    assert_eq!(result["failed"], 1);
+    assert_eq!(result["files"][paths[0].to_str().unwrap()]["valid"], true);
+    assert_eq!(result["files"][paths[1].to_str().unwrap()]["valid"], false);
+}
+
+#[test]
+fn test_validate_glob_pattern() {
+    let (_paths, temp_dir) = create_layout_dir(&[
+        ("a.md", test_layout_basic(2, 3)),
+        ("b.md", test_layout_basic(2, 3)),
+    ]);
+    std::fs::write(temp_dir.path().join("notes.txt"), "not a layout").unwrap();
+    let pattern = temp_dir.path().join("*.md");
+
+    let output = Command::new(lazyqmk_bin())
+        .args(["validate", pattern.to_str().unwrap()])
+        .output()
+        .expect("Failed to execute command");
+
+    assert_eq!(
+        output.status.code(),
+        Some(0),
+        "stderr: {}",
+        String::from_utf8_lossy(&output.stderr)
+    );
+    let stdout = String::from_utf8_lossy(&output.stdout);
+    assert!(stdout.contains("a.md"));
+    assert!(stdout.contains("b.md"));
+    assert!(!stdout.contains("notes.txt"));
+    assert!(stdout.contains("2 of 2 layouts passed"));
+}
+
+#[test]
+fn test_validate_multiple_worst_exit_code() {
+    let (paths, temp_dir) = create_layout_dir(&[
+        ("a.md", test_layout_with_invalid_keycode()),
+        ("b.md", test_layout_basic(2, 3)),
+    ]);
+    let missing = temp_dir.path().join("missing.md");
+
+    let output = Command::new(lazyqmk_bin())
+        .args([
+            "validate",
+            paths[0].to_str().unwrap(),
+            missing.to_str().unwrap(),
+            paths[1].to_str().unwrap(),
+        ])
+        .output()
+        .expect("Failed to execute command");
+
+    // Load failure (2) outranks validation failure (1)
+    assert_eq!(output.status.code(), Some(2));
+    let stdout = String::from_utf8_lossy(&output.stdout);
+    assert!(stdout.contains("1 of 3 layouts passed"));
+}
+
+#[test]
+fn test_validate_fail_fast() {
+    let (paths, _temp_dir) = create_layout_dir(&[
+        ("a.md", test_layout_with_invalid_keycode()),
+        ("b.md", test_layout_basic(2, 3)),
+    ]);
+
+    let output = Command::new(lazyqmk_bin())
+        .args([
+            "validate",
+            "--fail-fast",
+            "--json",
+            paths[0].to_str().unwrap(),
+            paths[1].to_str().unwrap(),
+        ])
+        .output()
+        .expect("Failed to execute command");
+
+    assert_eq!(output.status.code(), Some(1));
+    let result: serde_json::Value =
+        serde_json::from_slice(&output.stdout).expect("Should parse JSON output");
+    assert_eq!(result["checked"], 1);
+    assert!(result["files"].get(paths[1].to_str().unwrap()).is_none());
+}
+
+#[test]
+fn test_validate_requires_a_file() {
+    let output = Command::new(lazyqmk_bin())
+        .args(["validate"])
+        .output()
+        .expect("Failed to execute command");
+
+    assert_eq!(output.status.code(), Some(1));
+}
+
+#[test]
+fn test_validate_fix_applies_and_revalidates() {
+    let mut layout = test_layout_basic(2, 3);
+    layout.layers[0].keys[0].keycode = "RESET".to_string();
+    layout.layers[0].keys[1].keycode = "KC_TRNS".to_string();
+    let (layout_path, _temp_dir) = create_temp_layout_file(&layout);
+    let before = std::fs::read_to_string(&layout_path).unwrap();
+
+    let dry_run = Command::new(lazyqmk_bin())
+        .args(["validate", layout_path.to_str().unwrap(), "--fix-dry-run"])
+        .output()
+        .expect("Failed to execute command");
+    let stdout = String::from_utf8_lossy(&dry_run.stdout);
+    assert!(stdout.contains("RESET → QK_BOOT"), "{stdout}");
+    assert!(stdout.contains("Would apply 2 fix(es)"), "{stdout}");
+    assert_eq!(std::fs::read_to_string(&layout_path).unwrap(), before);
+
+    let output = Command::new(lazyqmk_bin())
+        .args([
+            "validate",
+            layout_path.to_str().unwrap(),
+            "--fix",
+            "--strict",
+        ])
+        .output()
+        .expect("Failed to execute command");
+    let stdout = String::from_utf8_lossy(&output.stdout);
+    assert_eq!(output.status.code(), Some(0), "{stdout}");
+    assert!(stdout.contains("Applied 2 fix(es)"), "{stdout}");
+    assert!(stdout.contains("✓ Validation passed"), "{stdout}");
+
+    let fixed = std::fs::read_to_string(&layout_path).unwrap();
+    assert!(fixed.contains("QK_BOOT") && !fixed.contains("RESET"));
+}
@@ -7,11 +7,13 @@
 //! 4. Coordinate system transformations (visual -> matrix -> LED)
 
 use chrono::Utc;
-use lazyqmk::config::{BuildConfig, Config, PathConfig, UiConfig};
-use lazyqmk::firmware::{FirmwareGenerator, FirmwareValidator};
+use lazyqmk::config::{BuildBackend, BuildConfig, Config, PathConfig, UiConfig};
+use lazyqmk::firmware::{
+    FileStatus, FirmwareGenerator, FirmwareValidator, GenerationError, GenerationOutcome,
+};
 use lazyqmk::keycode_db::KeycodeDb;
 use lazyqmk::models::{
-    Category, KeyDefinition, KeyGeometry, KeyboardGeometry, Layer, Layout, LayoutMetadata,
+    Category, KeyDefinition, KeyGeometry, KeyRole, KeyboardGeometry, Layer, Layout, LayoutMetadata,
     Position, RgbColor, VisualLayoutMapping,
 };
 use std::collections::HashMap;
@@ -19,6 +21,12 @@ use std::fs;
 use std::path::PathBuf;
 use tempfile::TempDir;
 
+/// Archive paths of keymap.c and config.h from a generation outcome.
+fn archive_paths(outcome: &GenerationOutcome) -> (PathBuf, PathBuf) {
+    let path = |name| outcome.file(name).unwrap().archive_path.clone();
+    (path("keymap.c"), path("config.h"))
+}
+
 // === Parameterized Keycode Tests ===
 
 /// Test that LT() keycodes with layer references are resolved correctly
@@ -135,7 +143,7 @@ fn test_idle_effect_enabled_in_keymap() {
 
     assert!(result.is_ok(), "Generation with idle effect should succeed");
 
-    let (keymap_path, config_path) = result.unwrap();
+    let (keymap_path, config_path) = archive_paths(&result.unwrap());
     let keymap_content = fs::read_to_string(&keymap_path).expect("Should read keymap.c");
     let config_content = fs::read_to_string(&config_path).expect("Should read config.h");
 
@@ -202,7 +210,7 @@ fn test_idle_effect_disabled_no_code() {
 
     assert!(result.is_ok());
 
-    let (keymap_path, config_path) = result.unwrap();
+    let (keymap_path, config_path) = archive_paths(&result.unwrap());
     let keymap_content = fs::read_to_string(&keymap_path).expect("Should read keymap.c");
     let config_content = fs::read_to_string(&config_path).expect("Should read config.h");
 
@@ -238,7 +246,7 @@ fn test_idle_effect_no_rgb_matrix_timeout_conflict() {
 
     assert!(result.is_ok());
 
-    let (_, config_path) = result.unwrap();
+    let (_, config_path) = archive_paths(&result.unwrap());
     let config_content = fs::read_to_string(&config_path).expect("Should read config.h");
 
     // Should have idle effect defines
@@ -273,7 +281,7 @@ fn test_rgb_matrix_timeout_when_idle_disabled() {
 
     assert!(result.is_ok());
 
-    let (_, config_path) = result.unwrap();
+    let (_, config_path) = archive_paths(&result.unwrap());
     let config_content = fs::read_to_string(&config_path).expect("Should read config.h");
 
     // Should have RGB_MATRIX_TIMEOUT when idle effect is disabled
@@ -328,7 +336,7 @@ fn test_idle_effect_different_modes() {
             effect
         );
 
-        let (_, config_path) = result.unwrap();
+        let (_, config_path) = archive_paths(&result.unwrap());
         let config_content = fs::read_to_string(&config_path).expect("Should read config.h");
 
         assert!(
@@ -365,13 +373,13 @@ fn test_generation_with_parameterized_keycodes() {
         "Generation with parameterized keycodes should succeed"
     );
 
-    let (keymap_path, _) = result.unwrap();
+    let (keymap_path, _) = archive_paths(&result.unwrap());
     let content = fs::read_to_string(&keymap_path).expect("Should be able to read keymap.c");
 
-    // LT with @uuid should be resolved to index
+    // LT with @uuid should be resolved to the layer's enum name
     assert!(
-        content.contains("LT(1, KC_SPC)"),
-        "LT should be resolved to layer index: {}",
+        content.contains("LT(_FUNCTION, KC_SPC)"),
+        "LT should be resolved to layer enum name: {}",
         content
     );
     // MT and SH_T should pass through as-is
@@ -396,7 +404,9 @@ fn create_test_layout() -> Layout {
         layout_variant: Some("LAYOUT_test".to_string()),
         keyboard: Some("test_kb".to_string()),
         keymap_name: Some("test_keymap".to_string()),
+        converter: None,
         output_format: Some("uf2".to_string()),
+        lint: lazyqmk::models::layout::LintSettings::default(),
     };
 
     // Create a simple 2x3 layout (6 keys)
@@ -421,8 +431,12 @@ fn create_test_layout() -> Layout {
         id: uuid::Uuid::new_v4().to_string(),
         default_color: RgbColor::new(255, 255, 255),
         category_id: None,
+        description: None,
         keys: keys.clone(),
+        enabled: true,
         layer_colors_enabled: true,
+        encoders: Vec::new(),
+        modified: None,
     };
 
     // Second layer with some transparent keys
@@ -436,8 +450,12 @@ fn create_test_layout() -> Layout {
         id: uuid::Uuid::new_v4().to_string(),
         default_color: RgbColor::new(100, 100, 255),
         category_id: None,
+        description: None,
         keys: layer1_keys,
+        enabled: true,
         layer_colors_enabled: true,
+        encoders: Vec::new(),
+        modified: None,
     };
 
     Layout {
@@ -452,6 +470,7 @@ fn create_test_layout() -> Layout {
         rgb_saturation: lazyqmk::models::RgbSaturation::default(),
         rgb_timeout_ms: 0,
         tap_dances: vec![],
+        orphaned_keys: Vec::new(),
     }
 }
 
@@ -472,6 +491,8 @@ fn create_test_geometry() -> KeyboardGeometry {
                 width: 1.0,
                 height: 1.0,
                 rotation: 0.0,
+                rotation_origin: None,
+                role: KeyRole::default(),
             };
             keys.push(key_geo);
         }
@@ -484,6 +505,7 @@ fn create_test_geometry() -> KeyboardGeometry {
         matrix_cols: 3,
         keys,
         encoder_count: 0,
+        encoder_buttons: std::collections::BTreeMap::new(),
     }
 }
 
@@ -522,6 +544,8 @@ fn create_test_mapping() -> VisualLayoutMapping {
         matrix_to_visual,
         visual_to_matrix,
         max_col: 2, // 3 columns means max_col = 2
+        roles: HashMap::new(),
+        encoder_positions: Vec::new(),
     }
 }
 
@@ -541,11 +565,19 @@ fn create_test_config(temp_dir: &TempDir) -> Config {
     Config {
         paths: PathConfig {
             qmk_firmware: Some(qmk_path),
+            library: None,
+            info_cache: None,
         },
         build: BuildConfig {
             output_dir: temp_dir.path().to_path_buf(),
+            template_dir: None,
+            keyboard: None,
+            keymap: None,
+            converter: None,
+            backend: BuildBackend::Auto,
         },
         ui: UiConfig::default(),
+        ..Config::default()
     }
 }
 
@@ -632,13 +664,99 @@ fn test_generation_creates_files() {
         result.err()
     );
 
-    let (keymap_path, _) = result.unwrap();
+    let (keymap_path, _) = archive_paths(&result.unwrap());
     assert!(
         PathBuf::from(&keymap_path).exists(),
         "keymap.c should be created"
     );
 }
 
+#[test]
+fn test_generate_reports_each_file_and_its_status() {
+    let temp_dir = TempDir::new().expect("Failed to create temp dir");
+    let layout = create_test_layout();
+    let geometry = create_test_geometry();
+    let mapping = create_test_mapping();
+    let config = create_test_config(&temp_dir);
+    let keycode_db = KeycodeDb::load().expect("Failed to load keycode database");
+    let generator = FirmwareGenerator::new(&layout, &geometry, &mapping, &config, &keycode_db);
+
+    let report = generator.generate().expect("Generation should succeed");
+    let names: Vec<_> = report.files.iter().map(|file| file.name).collect();
+    assert_eq!(names, ["config.h", "keymap.c", "rules.mk"]);
+    for file in &report.files {
+        assert_eq!(file.status, FileStatus::Written);
+        assert_eq!(fs::read(&file.keymap_path).unwrap().len(), file.size);
+        assert!(file.archive_path.starts_with(&report.archive_dir));
+    }
+
+    // keymap.c carries no timestamp, so regenerating leaves it untouched
+    let report = generator.generate().expect("Generation should succeed");
+    assert_eq!(
+        report.file("keymap.c").unwrap().status,
+        FileStatus::Unchanged
+    );
+
+    // A hand-written rules.mk in the keymap directory is kept
+    fs::write(
+        report.keymap_dir.join("rules.mk"),
+        "TAP_DANCE_ENABLE = yes\n",
+    )
+    .unwrap();
+    let report = generator.generate().expect("Generation should succeed");
+    assert_eq!(
+        report.file("rules.mk").unwrap().status,
+        FileStatus::HandWritten
+    );
+}
+
+#[test]
+fn test_generate_reports_files_written_before_a_failure() {
+    let temp_dir = TempDir::new().expect("Failed to create temp dir");
+    let layout = create_test_layout();
+    let geometry = create_test_geometry();
+    let mapping = create_test_mapping();
+    let config = create_test_config(&temp_dir);
+    let keycode_db = KeycodeDb::load().expect("Failed to load keycode database");
+
+    // A non-empty directory where keymap.c should go can't be replaced
+    let blocked = temp_dir
+        .path()
+        .join("qmk_firmware/keyboards/test_kb/keymaps/test_keymap/keymap.c");
+    fs::create_dir_all(blocked.join("inner")).unwrap();
+
+    let generator = FirmwareGenerator::new(&layout, &geometry, &mapping, &config, &keycode_db);
+    let error = generator.generate().unwrap_err();
+    let outcome = &error
+        .downcast_ref::<GenerationError>()
+        .expect("Should carry the partial outcome")
+        .outcome;
+
+    let statuses: Vec<_> = outcome
+        .files
+        .iter()
+        .map(|file| (file.name, file.status))
+        .collect();
+    assert_eq!(
+        statuses,
+        [
+            ("config.h", FileStatus::Written),
+            ("keymap.c", FileStatus::Failed),
+            ("rules.mk", FileStatus::NotWritten),
+        ]
+    );
+    assert!(outcome.file("config.h").unwrap().keymap_path.exists());
+    assert!(!outcome.keymap_dir.join("rules.mk").exists());
+    assert_eq!(outcome.stale_files(), ["keymap.c", "rules.mk"]);
+
+    let write_error = outcome.failed().unwrap().error.as_ref().unwrap();
+    assert_eq!(write_error.path, blocked);
+    assert!(error.to_string().starts_with(&write_error.to_string()));
+    assert!(error
+        .to_string()
+        .ends_with("(keymap.c, rules.mk were not updated)"));
+}
+
 #[test]
 fn test_generation_keymap_c_structure() {
     // Arrange
@@ -655,7 +773,7 @@ fn test_generation_keymap_c_structure() {
     let result = generator.generate();
     assert!(result.is_ok(), "Generation should succeed");
 
-    let (keymap_path, _) = result.unwrap();
+    let (keymap_path, _) = archive_paths(&result.unwrap());
     let content = fs::read_to_string(&keymap_path).expect("Should be able to read keymap.c");
 
     // Assert - Check for expected C code structure
@@ -707,7 +825,7 @@ fn test_generation_led_ordering() {
     let result = generator.generate();
     assert!(result.is_ok(), "Generation should succeed");
 
-    let (keymap_path, _) = result.unwrap();
+    let (keymap_path, _) = archive_paths(&result.unwrap());
     let content = fs::read_to_string(&keymap_path).expect("Should be able to read keymap.c");
 
     // Assert - Keys should be ordered by LED index (0, 1, 2, 3, 4, 5)
@@ -758,7 +876,7 @@ fn test_generation_with_categories() {
     // Assert
     assert!(result.is_ok(), "Generation with categories should succeed");
 
-    let (keymap_path, _) = result.unwrap();
+    let (keymap_path, _) = archive_paths(&result.unwrap());
     let content = fs::read_to_string(&keymap_path).expect("Should be able to read keymap.c");
 
     // Categories don't affect C code generation, but should not cause errors
@@ -787,7 +905,7 @@ fn test_generation_atomic_write() {
     );
 
     // Assert - Files should exist and be readable
-    let (keymap_path, _) = result2.unwrap();
+    let (keymap_path, _) = archive_paths(&result2.unwrap());
     let keymap_content =
         fs::read_to_string(&keymap_path).expect("Should read keymap.c after overwrite");
 
@@ -825,7 +943,7 @@ fn test_full_pipeline_validation_to_generation() {
         result.err()
     );
 
-    let (keymap_path, _) = result.unwrap();
+    let (keymap_path, _) = archive_paths(&result.unwrap());
     assert!(
         PathBuf::from(&keymap_path).exists(),
         "keymap.c should exist"
@@ -869,7 +987,7 @@ fn test_tap_dance_two_way_generation() {
         "Generation with 2-way tap dance should succeed"
     );
 
-    let (keymap_path, _) = result.unwrap();
+    let (keymap_path, _) = archive_paths(&result.unwrap());
     let content = fs::read_to_string(&keymap_path).expect("Should read keymap.c");
 
     // Check enum
@@ -925,7 +1043,7 @@ fn test_tap_dance_three_way_generation() {
         "Generation with 3-way tap dance should succeed"
     );
 
-    let (keymap_path, _) = result.unwrap();
+    let (keymap_path, _) = archive_paths(&result.unwrap());
     let content = fs::read_to_string(&keymap_path).expect("Should read keymap.c");
 
     // Check enum
@@ -981,6 +1099,58 @@ fn test_tap_dance_three_way_generation() {
     );
 }
 
+#[test]
+fn test_tap_dance_options_generation() {
+    use lazyqmk::models::TapDanceAction;
+
+    let temp_dir = TempDir::new().expect("Failed to create temp dir");
+    let mut layout = create_test_layout();
+
+    // A slow "tap then hold" dance and a plain 2-way dance with a custom term
+    layout.tap_dances.push(
+        TapDanceAction::new("slow", "KC_ESC")
+            .with_hold("KC_LCTL")
+            .with_tapping_term(300)
+            .with_on_each_tap(),
+    );
+    layout.tap_dances.push(
+        TapDanceAction::new("quick", "KC_A")
+            .with_double_tap("KC_B")
+            .with_tapping_term(120),
+    );
+    layout.layers[0].keys[0].keycode = "TD(slow)".to_string();
+    layout.layers[0].keys[1].keycode = "TD(quick)".to_string();
+
+    let geometry = create_test_geometry();
+    let mapping = create_test_mapping();
+    let config = create_test_config(&temp_dir);
+    let keycode_db = KeycodeDb::load().expect("Failed to load keycode database");
+
+    let generator = FirmwareGenerator::new(&layout, &geometry, &mapping, &config, &keycode_db);
+    let (keymap_path, config_path) =
+        archive_paths(&generator.generate().expect("Generation should succeed"));
+    let content = fs::read_to_string(&keymap_path).expect("Should read keymap.c");
+    let config_h = fs::read_to_string(&config_path).expect("Should read config.h");
+
+    // On-each-tap dance taps the single keycode immediately
+    assert!(content.contains("void td_slow_each"));
+    assert!(content.contains("tap_code16(KC_ESC)"));
+    assert!(content
+        .contains("ACTION_TAP_DANCE_FN_ADVANCED(td_slow_each, td_slow_finished, td_slow_reset)"));
+
+    // Options force the advanced form even without a hold
+    assert!(
+        content.contains("ACTION_TAP_DANCE_FN_ADVANCED(NULL, td_quick_finished, td_quick_reset)")
+    );
+    assert!(!content.contains("ACTION_TAP_DANCE_DOUBLE"));
+
+    // Per-dance terms
+    assert!(content.contains("uint16_t get_tapping_term(uint16_t keycode, keyrecord_t *record)"));
+    assert!(content.contains("case TD(TD_SLOW):\n            return 300;"));
+    assert!(content.contains("case TD(TD_QUICK):\n            return 120;"));
+    assert!(config_h.contains("#define TAPPING_TERM_PER_KEY"));
+}
+
 #[test]
 fn test_tap_dance_multiple_stable_ordering() {
     use lazyqmk::models::TapDanceAction;
@@ -1018,7 +1188,7 @@ fn test_tap_dance_multiple_stable_ordering() {
         "Generation with multiple tap dances should succeed"
     );
 
-    let (keymap_path, _) = result.unwrap();
+    let (keymap_path, _) = archive_paths(&result.unwrap());
     let content = fs::read_to_string(&keymap_path).expect("Should read keymap.c");
 
     // Verify alphabetical ordering in enum (alpha, beta, zebra)
@@ -1060,7 +1230,7 @@ fn test_tap_dance_empty_no_code() {
         "Generation with no tap dances should succeed"
     );
 
-    let (keymap_path, _) = result.unwrap();
+    let (keymap_path, _) = archive_paths(&result.unwrap());
     let content = fs::read_to_string(&keymap_path).expect("Should read keymap.c");
 
     // Should not contain tap dance code
@@ -1100,7 +1270,7 @@ fn test_tap_dance_invalid_reference_passthrough() {
         "Generation should not fail on missing tap dance ref"
     );
 
-    let (keymap_path, _) = result.unwrap();
+    let (keymap_path, _) = archive_paths(&result.unwrap());
     let content = fs::read_to_string(&keymap_path).expect("Should read keymap.c");
 
     // Should pass through unchanged (validator will catch the error)
@@ -1142,7 +1312,7 @@ fn test_tap_dance_mixed_two_and_three_way() {
         "Generation with mixed tap dances should succeed"
     );
 
-    let (keymap_path, _) = result.unwrap();
+    let (keymap_path, _) = archive_paths(&result.unwrap());
     let content = fs::read_to_string(&keymap_path).expect("Should read keymap.c");
 
     // Check both are in enum
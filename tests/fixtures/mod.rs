@@ -2,10 +2,10 @@
 #![allow(dead_code)] // Some fixtures reserved for future tests
 
 use chrono::{TimeZone, Utc};
-use lazyqmk::config::{BuildConfig, Config, PathConfig, UiConfig};
+use lazyqmk::config::{BuildBackend, BuildConfig, Config, PathConfig, UiConfig};
 use lazyqmk::models::{
-    Category, IdleEffectSettings, KeyDefinition, KeyGeometry, KeyboardGeometry, Layer, Layout,
-    LayoutMetadata, Position, RgbBrightness, RgbColor, RgbMatrixEffect, RgbSaturation,
+    Category, IdleEffectSettings, KeyDefinition, KeyGeometry, KeyRole, KeyboardGeometry, Layer,
+    Layout, LayoutMetadata, Position, RgbBrightness, RgbColor, RgbMatrixEffect, RgbSaturation,
     TapDanceAction, TapHoldSettings, UncoloredKeyBehavior, VisualLayoutMapping,
 };
 use std::collections::HashMap;
@@ -39,7 +39,9 @@ pub fn test_layout_basic(rows: usize, cols: usize) -> Layout {
         layout_variant: Some("LAYOUT_test".to_string()),
         keyboard: Some("test_keyboard".to_string()),
         keymap_name: Some("test_keymap".to_string()),
+        converter: None,
         output_format: Some("uf2".to_string()),
+        lint: lazyqmk::models::layout::LintSettings::default(),
     };
 
     // Layer 0: Base layer with simple keycodes
@@ -68,8 +70,12 @@ pub fn test_layout_basic(rows: usize, cols: usize) -> Layout {
         id: "00000000-0000-0000-0000-000000000000".to_string(), // Deterministic UUID
         default_color: RgbColor::new(255, 255, 255),
         category_id: None,
+        description: None,
         keys: base_keys,
+        enabled: true,
         layer_colors_enabled: true,
+        encoders: Vec::new(),
+        modified: None,
     };
 
     // Layer 1: Function layer with some transparent keys
@@ -102,8 +108,12 @@ pub fn test_layout_basic(rows: usize, cols: usize) -> Layout {
         id: "11111111-1111-1111-1111-111111111111".to_string(), // Deterministic UUID
         default_color: RgbColor::new(100, 100, 255),
         category_id: None,
+        description: None,
         keys: func_keys,
+        enabled: true,
         layer_colors_enabled: true,
+        encoders: Vec::new(),
+        modified: None,
     };
 
     Layout {
@@ -118,6 +128,7 @@ pub fn test_layout_basic(rows: usize, cols: usize) -> Layout {
         idle_effect_settings: IdleEffectSettings::default(),
         tap_hold_settings: TapHoldSettings::default(),
         tap_dances: vec![],
+        orphaned_keys: Vec::new(),
     }
 }
 
@@ -131,6 +142,8 @@ pub fn test_layout_with_tap_dances() -> Layout {
         single_tap: "KC_ESC".to_string(),
         double_tap: Some("KC_CAPS".to_string()),
         hold: None,
+        tapping_term_ms: None,
+        on_each_tap: false,
     };
 
     // Add 3-way tap dance
@@ -139,6 +152,8 @@ pub fn test_layout_with_tap_dances() -> Layout {
         single_tap: "KC_LSFT".to_string(),
         double_tap: Some("KC_CAPS".to_string()),
         hold: Some("KC_LCTL".to_string()),
+        tapping_term_ms: None,
+        on_each_tap: false,
     };
 
     layout.tap_dances = vec![td_esc_caps, td_shift];
@@ -189,6 +204,25 @@ pub fn test_layout_with_categories() -> Layout {
     layout
 }
 
+/// Creates a test layout whose categories use both firmware RGB flags:
+/// "navigation" keys stay lit through the timeout and layer 1 breathes.
+pub fn test_layout_with_category_rgb_flags(idle_effect: bool) -> Layout {
+    let mut layout = test_layout_with_idle_effect(idle_effect);
+
+    let mut nav_category = Category::new("navigation", "Navigation", RgbColor::new(0, 255, 0))
+        .expect("Should create category");
+    nav_category.always_on = true;
+    let mut num_category = Category::new("numbers", "Numbers", RgbColor::new(255, 128, 0))
+        .expect("Should create category");
+    num_category.breathing = true;
+    layout.categories = vec![nav_category, num_category];
+
+    layout.layers[0].keys[0].category_id = Some("navigation".to_string());
+    layout.layers[1].category_id = Some("numbers".to_string());
+
+    layout
+}
+
 /// Creates a test layout with layer references (LT, MO, etc).
 pub fn test_layout_with_layer_refs() -> Layout {
     let mut layout = test_layout_basic(2, 3);
@@ -230,6 +264,8 @@ pub fn test_geometry_basic(rows: usize, cols: usize) -> KeyboardGeometry {
                 width: 1.0,
                 height: 1.0,
                 rotation: 0.0,
+                rotation_origin: None,
+                role: KeyRole::default(),
             };
             keys.push(key_geo);
         }
@@ -242,6 +278,7 @@ pub fn test_geometry_basic(rows: usize, cols: usize) -> KeyboardGeometry {
         matrix_cols: cols as u8,
         keys,
         encoder_count: 0,
+        encoder_buttons: std::collections::BTreeMap::new(),
     }
 }
 
@@ -287,6 +324,8 @@ pub fn test_mapping_basic(rows: usize, cols: usize) -> VisualLayoutMapping {
         matrix_to_visual,
         visual_to_matrix,
         max_col: (cols - 1) as u8,
+        roles: HashMap::new(),
+        encoder_positions: Vec::new(),
     }
 }
 
@@ -356,11 +395,19 @@ pub fn temp_config_with_qmk(qmk_path: Option<PathBuf>) -> (Config, TempDir) {
     let config = Config {
         paths: PathConfig {
             qmk_firmware: Some(qmk_path),
+            library: None,
+            info_cache: None,
         },
         build: BuildConfig {
             output_dir: temp_dir.path().to_path_buf(),
+            template_dir: None,
+            keyboard: None,
+            keymap: None,
+            converter: None,
+            backend: BuildBackend::Auto,
         },
         ui: UiConfig::default(),
+        ..Config::default()
     };
 
     (config, temp_dir)
@@ -0,0 +1,17 @@
+// Generated by lazyqmk
+// Layout: Test Layout
+// Generated: <timestamp>
+
+#pragma once
+
+// Add keymap-specific configuration here
+
+// RGB Timeout (other keys go dark, always-on keys stay lit)
+#define LQMK_RGB_TIMEOUT_MS 90000
+
+// Default to TUI layer-aware RGB colors when available
+#ifdef RGB_MATRIX_ENABLE
+#    undef RGB_MATRIX_DEFAULT_MODE
+#    define RGB_MATRIX_DEFAULT_MODE RGB_MATRIX_TUI_LAYER_COLORS
+#    define LAYER_BASE_COLORS_LAYER_COUNT 2
+#endif
\ No newline at end of file
@@ -0,0 +1,82 @@
+// Generated by lazyqmk
+// Layout: Test Layout
+// Keyboard: test_keyboard
+// Layout Variant: LAYOUT_test
+
+#include QMK_KEYBOARD_H
+
+enum layers {
+    _BASE = 0,
+    _FUNCTION,
+};
+
+const uint16_t PROGMEM keymaps[][MATRIX_ROWS][MATRIX_COLS] = {
+    // Layer 0: Base
+[_BASE] = LAYOUT_test(KC_0, KC_1, KC_2, KC_3, KC_4, KC_5),
+    // Layer 1: Function
+[_FUNCTION] = LAYOUT_test(KC_TRNS, KC_F1, KC_F2, KC_F3, KC_F4, KC_F5)
+};
+
+#ifdef ENCODER_MAP_ENABLE
+const uint16_t PROGMEM encoder_map[][NUM_ENCODERS][NUM_DIRECTIONS] = {
+    [_BASE] = {
+    },
+    [_FUNCTION] = {
+    },
+};
+#endif
+
+#ifdef RGB_MATRIX_ENABLE
+const uint8_t PROGMEM layer_base_colors[2][6][3] = {
+    [_BASE] = {
+        {  0, 255,   0},
+        {255, 255, 255},
+        {255, 255, 255},
+        {255, 255, 255},
+        {255, 255, 255},
+        {255, 255, 255}
+    },
+    [_FUNCTION] = {
+        {255, 128,   0},
+        {255, 128,   0},
+        {255, 128,   0},
+        {255, 128,   0},
+        {255, 128,   0},
+        {255, 128,   0}
+    }
+};
+const uint8_t PROGMEM layer_base_colors_layer_count = 2;
+#endif
+
+
+#ifdef RGB_MATRIX_ENABLE
+// Category RGB flags
+#define LQMK_LED_ALWAYS_ON 0x01
+#define LQMK_LED_BREATHING 0x02
+
+const uint8_t PROGMEM lqmk_led_flags[2][6] = {
+    [_BASE] = {0x01, 0x00, 0x00, 0x00, 0x00, 0x00},
+    [_FUNCTION] = {0x02, 0x02, 0x02, 0x02, 0x02, 0x02}
+};
+
+bool rgb_matrix_indicators_advanced_user(uint8_t led_min, uint8_t led_max) {
+    uint8_t layer = get_highest_layer(layer_state | default_layer_state);
+    if (layer >= 2) {
+        return false;
+    }
+    bool sleeping = last_input_activity_elapsed() >= LQMK_RGB_TIMEOUT_MS;
+    uint8_t breath = sin8((uint8_t)(timer_read() / 8));
+    for (uint8_t i = led_min; i < led_max && i < 6; i++) {
+        uint8_t flags = pgm_read_byte(&lqmk_led_flags[layer][i]);
+        if (sleeping && !(flags & LQMK_LED_ALWAYS_ON)) {
+            rgb_matrix_set_color(i, 0, 0, 0);
+        } else if (flags & LQMK_LED_BREATHING) {
+            rgb_matrix_set_color(i,
+                scale8(pgm_read_byte(&layer_base_colors[layer][i][0]), breath),
+                scale8(pgm_read_byte(&layer_base_colors[layer][i][1]), breath),
+                scale8(pgm_read_byte(&layer_base_colors[layer][i][2]), breath));
+        }
+    }
+    return false;
+}
+#endif
\ No newline at end of file
@@ -6,6 +6,11 @@
 #include QMK_KEYBOARD_H
 #include "process_keycode/process_tap_dance.h"
 
+enum layers {
+    _BASE = 0,
+    _FUNCTION,
+};
+
 // Tap Dance Configuration
 enum tap_dance_ids {
     TD_ESC_CAPS,
@@ -41,23 +46,23 @@ tap_dance_action_t tap_dance_actions[] = {
 
 const uint16_t PROGMEM keymaps[][MATRIX_ROWS][MATRIX_COLS] = {
     // Layer 0: Base
-[0] = LAYOUT_test(TD(TD_ESC_CAPS), TD(TD_SHIFT_CTRL), KC_2, KC_3, KC_4, KC_5),
+[_BASE] = LAYOUT_test(TD(TD_ESC_CAPS), TD(TD_SHIFT_CTRL), KC_2, KC_3, KC_4, KC_5),
     // Layer 1: Function
-[1] = LAYOUT_test(KC_TRNS, KC_F1, KC_F2, KC_F3, KC_F4, KC_F5)
+[_FUNCTION] = LAYOUT_test(KC_TRNS, KC_F1, KC_F2, KC_F3, KC_F4, KC_F5)
 };
 
 #ifdef ENCODER_MAP_ENABLE
 const uint16_t PROGMEM encoder_map[][NUM_ENCODERS][NUM_DIRECTIONS] = {
-    [0] = {
+    [_BASE] = {
     },
-    [1] = {
+    [_FUNCTION] = {
     },
 };
 #endif
 
 #ifdef RGB_MATRIX_ENABLE
 const uint8_t PROGMEM layer_base_colors[2][6][3] = {
-    {
+    [_BASE] = {
         {255, 255, 255},
         {255, 255, 255},
         {255, 255, 255},
@@ -65,7 +70,7 @@ const uint8_t PROGMEM layer_base_colors[2][6][3] = {
         {255, 255, 255},
         {255, 255, 255}
     },
-    {
+    [_FUNCTION] = {
         {100, 100, 255},
         {100, 100, 255},
         {100, 100, 255},
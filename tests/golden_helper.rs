@@ -4,6 +4,7 @@
 //! - Automatic normalization of timestamps, UUIDs, and paths
 //! - `UPDATE_GOLDEN=1` env var to regenerate expected files
 //! - Platform-independent line ending handling
+#![allow(dead_code)] // Each test binary uses only some of the helpers
 
 use std::env;
 use std::fs;
@@ -56,6 +57,7 @@ pub fn assert_golden(actual: &str, golden_path: &str) {
 ///
 /// Transformations applied:
 /// - Strips timestamp comments like `// Generated at: 2025-01-16...`
+/// - Replaces per-layer `**Modified**:` timestamps with `<TIMESTAMP>`
 /// - Replaces UUIDs with `<UUID>`
 /// - Replaces absolute paths with `<PATH>`
 /// - Normalizes line endings to `\n`
@@ -70,6 +72,9 @@ pub fn normalize_output(content: &str) -> String {
             if line.contains("Generated at:") || line.contains("Last modified:") {
                 return "// Generated at: <TIMESTAMP>".to_string();
             }
+            if line.starts_with("**Modified**:") {
+                return "**Modified**: <TIMESTAMP>".to_string();
+            }
 
             // Replace UUIDs (simple pattern matching)
             let line = replace_uuids(line);
@@ -133,6 +138,13 @@ mod tests {
         assert_eq!(normalize_output(input), expected);
     }
 
+    #[test]
+    fn test_normalize_layer_modified() {
+        let input = "**Modified**: 2026-10-17T09:12:03.123456Z";
+        let expected = "**Modified**: <TIMESTAMP>";
+        assert_eq!(normalize_output(input), expected);
+    }
+
     #[test]
     fn test_normalize_uuid() {
         let input = "layer_id: 12345678-1234-1234-1234-123456789abc";
@@ -0,0 +1,67 @@
+#![allow(missing_docs)]
+//! Per-layer `modified` timestamps: stamped by TUI edits and by saves,
+//! persisted in markdown
+
+use lazyqmk::config::Config;
+use lazyqmk::models::{KeyboardGeometry, Layout, Position, VisualLayoutMapping};
+use lazyqmk::services::LayoutService;
+use lazyqmk::shortcuts::Action;
+use lazyqmk::tui::handlers::dispatch_action;
+use lazyqmk::tui::AppState;
+use tempfile::TempDir;
+
+mod fixtures;
+use fixtures::test_layout_basic;
+
+/// Two layers of 2x3 keys, neither with a timestamp (as in an older file)
+fn layout() -> Layout {
+    let mut layout = test_layout_basic(2, 3);
+    for layer in &mut layout.layers {
+        layer.modified = None;
+    }
+    layout
+}
+
+#[test]
+fn test_tui_edit_stamps_only_the_edited_layer() {
+    let mut state = AppState::new(
+        layout(),
+        None,
+        KeyboardGeometry::new("test", "test", 2, 3),
+        VisualLayoutMapping::default(),
+        Config::default(),
+    )
+    .unwrap();
+    state.selected_position = Position::new(0, 0);
+
+    dispatch_action(&mut state, Action::ClearKey).unwrap();
+
+    assert!(state.dirty);
+    assert!(state.layout.layers[0].modified.is_some());
+    assert!(state.layout.layers[1].modified.is_none());
+}
+
+#[test]
+fn test_save_stamps_layers_changed_since_the_file_was_written() {
+    let temp = TempDir::new().unwrap();
+    let path = temp.path().join("layout.md");
+    let mut layout = layout();
+    LayoutService::save(&layout, &path).unwrap();
+
+    // A new file gets a timestamp on every layer
+    let saved = LayoutService::load(&path).unwrap();
+    let first = saved.layers[0].modified.unwrap();
+    let second = saved.layers[1].modified.unwrap();
+
+    // Editing only the first layer restamps it and leaves the second alone
+    layout = saved;
+    layout.layers[0].keys[0].keycode = "KC_Z".to_string();
+    LayoutService::save(&layout, &path).unwrap();
+    let saved = LayoutService::load(&path).unwrap();
+    assert!(saved.layers[0].modified.unwrap() > first);
+    assert_eq!(saved.layers[1].modified, Some(second));
+
+    // Saving unchanged content keeps both timestamps
+    LayoutService::save(&saved, &path).unwrap();
+    assert_eq!(LayoutService::load(&path).unwrap().layers, saved.layers);
+}
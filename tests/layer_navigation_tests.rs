@@ -5,9 +5,9 @@
 //! - Shift+Tab cycles backward through layers (0 -> 2 -> 1 -> 0)
 
 use chrono::Utc;
-use lazyqmk::config::{BuildConfig, Config, PathConfig, UiConfig};
+use lazyqmk::config::{BuildBackend, BuildConfig, Config, PathConfig, UiConfig};
 use lazyqmk::models::{
-    KeyDefinition, KeyGeometry, KeyboardGeometry, Layer, Layout, LayoutMetadata, Position,
+    KeyDefinition, KeyGeometry, KeyRole, KeyboardGeometry, Layer, Layout, LayoutMetadata, Position,
     RgbColor, VisualLayoutMapping,
 };
 use lazyqmk::tui::AppState;
@@ -27,7 +27,9 @@ fn create_test_layout_with_layers() -> Layout {
         layout_variant: Some("LAYOUT_test".to_string()),
         keyboard: Some("test_kb".to_string()),
         keymap_name: Some("test_keymap".to_string()),
+        converter: None,
         output_format: Some("uf2".to_string()),
+        lint: lazyqmk::models::layout::LintSettings::default(),
     };
 
     // Create a simple 2x3 layout (6 keys)
@@ -53,8 +55,12 @@ fn create_test_layout_with_layers() -> Layout {
         id: uuid::Uuid::new_v4().to_string(),
         default_color: RgbColor::new(255, 255, 255),
         category_id: None,
+        description: None,
         keys: keys.clone(),
+        enabled: true,
         layer_colors_enabled: true,
+        encoders: Vec::new(),
+        modified: None,
     };
 
     let layer1 = Layer {
@@ -63,8 +69,12 @@ fn create_test_layout_with_layers() -> Layout {
         id: uuid::Uuid::new_v4().to_string(),
         default_color: RgbColor::new(100, 100, 255),
         category_id: None,
+        description: None,
         keys: keys.clone(),
+        enabled: true,
         layer_colors_enabled: true,
+        encoders: Vec::new(),
+        modified: None,
     };
 
     let layer2 = Layer {
@@ -73,8 +83,12 @@ fn create_test_layout_with_layers() -> Layout {
         id: uuid::Uuid::new_v4().to_string(),
         default_color: RgbColor::new(255, 100, 100),
         category_id: None,
+        description: None,
         keys: keys.clone(),
+        enabled: true,
         layer_colors_enabled: true,
+        encoders: Vec::new(),
+        modified: None,
     };
 
     Layout {
@@ -89,6 +103,7 @@ fn create_test_layout_with_layers() -> Layout {
         rgb_saturation: lazyqmk::models::RgbSaturation::default(),
         rgb_timeout_ms: 0,
         tap_dances: vec![],
+        orphaned_keys: Vec::new(),
     }
 }
 
@@ -108,6 +123,8 @@ fn create_test_geometry() -> KeyboardGeometry {
                 width: 1.0,
                 height: 1.0,
                 rotation: 0.0,
+                rotation_origin: None,
+                role: KeyRole::default(),
             };
             keys.push(key_geo);
         }
@@ -120,6 +137,7 @@ fn create_test_geometry() -> KeyboardGeometry {
         matrix_cols: 3,
         keys,
         encoder_count: 0,
+        encoder_buttons: std::collections::BTreeMap::new(),
     }
 }
 
@@ -157,17 +175,29 @@ fn create_test_mapping() -> VisualLayoutMapping {
         matrix_to_visual,
         visual_to_matrix,
         max_col: 2, // 3 columns means max_col = 2
+        roles: HashMap::new(),
+        encoder_positions: Vec::new(),
     }
 }
 
 /// Creates a test config
 fn create_test_config() -> Config {
     Config {
-        paths: PathConfig { qmk_firmware: None },
+        paths: PathConfig {
+            qmk_firmware: None,
+            library: None,
+            info_cache: None,
+        },
         build: BuildConfig {
             output_dir: std::env::temp_dir(),
+            template_dir: None,
+            keyboard: None,
+            keymap: None,
+            converter: None,
+            backend: BuildBackend::Auto,
         },
         ui: UiConfig::default(),
+        ..Config::default()
     }
 }
 
@@ -319,3 +349,29 @@ fn test_layer_cycling_with_two_layers() {
     navigation::handle_previous_layer(&mut state).expect("Should handle previous layer");
     assert_eq!(state.current_layer, 0);
 }
+
+#[test]
+fn test_base_overlay_precomputes_base_legends() {
+    use lazyqmk::tui::handlers::action_handlers::navigation;
+
+    let mut state = create_test_app_state();
+    state.layout.layers[0].keys[0].keycode = "KC_A".to_string();
+    state.layout.layers[0].keys[1].keycode = "LT(1, KC_SPC)".to_string();
+    navigation::handle_next_layer(&mut state).expect("Should handle next layer");
+
+    navigation::handle_toggle_base_overlay(&mut state).expect("Should toggle overlay");
+    let legends = state.base_overlay.as_ref().expect("Overlay should be on");
+    assert_eq!(legends.get(&Position { row: 0, col: 0 }).unwrap(), "A");
+    assert_eq!(legends.get(&Position { row: 0, col: 1 }).unwrap(), "Spc/L1");
+    // Transparent base keys have no legend
+    assert!(!legends.contains_key(&Position { row: 0, col: 2 }));
+
+    // Switching layers picks up base-layer edits
+    state.layout.layers[0].keys[2].keycode = "KC_B".to_string();
+    navigation::handle_next_layer(&mut state).expect("Should handle next layer");
+    let legends = state.base_overlay.as_ref().expect("Overlay should stay on");
+    assert_eq!(legends.get(&Position { row: 0, col: 2 }).unwrap(), "B");
+
+    navigation::handle_toggle_base_overlay(&mut state).expect("Should toggle overlay");
+    assert!(state.base_overlay.is_none());
+}
@@ -10,14 +10,26 @@ use lazyqmk::services::layer_refs::{
 fn test_layer_refs_basic_flow() {
     // Create a simple 2-layer layout
     let mut layer0 = Layer::new(0, "Base", RgbColor::new(255, 0, 0)).unwrap();
-    layer0.add_key(KeyDefinition::new(Position::new(0, 0), "KC_A"));
-    layer0.add_key(KeyDefinition::new(Position::new(0, 1), "MO(1)")); // Momentary to layer 1
-    layer0.add_key(KeyDefinition::new(Position::new(0, 2), "LT(1, KC_SPC)")); // Layer-tap to layer 1
+    layer0
+        .add_key(KeyDefinition::new(Position::new(0, 0), "KC_A"))
+        .unwrap();
+    layer0
+        .add_key(KeyDefinition::new(Position::new(0, 1), "MO(1)"))
+        .unwrap(); // Momentary to layer 1
+    layer0
+        .add_key(KeyDefinition::new(Position::new(0, 2), "LT(1, KC_SPC)"))
+        .unwrap(); // Layer-tap to layer 1
 
     let mut layer1 = Layer::new(1, "Lower", RgbColor::new(0, 255, 0)).unwrap();
-    layer1.add_key(KeyDefinition::new(Position::new(0, 0), "KC_1"));
-    layer1.add_key(KeyDefinition::new(Position::new(0, 1), "KC_TRNS")); // Transparent
-    layer1.add_key(KeyDefinition::new(Position::new(0, 2), "KC_2"));
+    layer1
+        .add_key(KeyDefinition::new(Position::new(0, 0), "KC_1"))
+        .unwrap();
+    layer1
+        .add_key(KeyDefinition::new(Position::new(0, 1), "KC_TRNS"))
+        .unwrap(); // Transparent
+    layer1
+        .add_key(KeyDefinition::new(Position::new(0, 2), "KC_2"))
+        .unwrap();
 
     let layers = vec![layer0, layer1];
 
@@ -42,7 +54,9 @@ fn test_layer_refs_basic_flow() {
 fn test_transparency_warning() {
     // Create a layout with a hold-like reference
     let mut layer0 = Layer::new(0, "Base", RgbColor::new(255, 0, 0)).unwrap();
-    layer0.add_key(KeyDefinition::new(Position::new(1, 0), "LT(1, KC_SPACE)"));
+    layer0
+        .add_key(KeyDefinition::new(Position::new(1, 0), "LT(1, KC_SPACE)"))
+        .unwrap();
 
     let layer1 = Layer::new(1, "Lower", RgbColor::new(0, 255, 0)).unwrap();
 
@@ -117,7 +131,9 @@ fn test_parse_all_layer_keycodes() {
 fn test_layer_refs_self_reference() {
     // Layer can reference itself
     let mut layer0 = Layer::new(0, "Base", RgbColor::new(255, 0, 0)).unwrap();
-    layer0.add_key(KeyDefinition::new(Position::new(0, 0), "TG(0)")); // Toggle self
+    layer0
+        .add_key(KeyDefinition::new(Position::new(0, 0), "TG(0)"))
+        .unwrap(); // Toggle self
 
     let layers = vec![layer0];
     let layer_refs = build_layer_ref_index(&layers);
@@ -133,7 +149,9 @@ fn test_layer_refs_self_reference() {
 fn test_layer_refs_ignores_out_of_bounds() {
     // References to non-existent layers should be ignored
     let mut layer0 = Layer::new(0, "Base", RgbColor::new(255, 0, 0)).unwrap();
-    layer0.add_key(KeyDefinition::new(Position::new(0, 0), "MO(99)")); // Layer 99 doesn't exist
+    layer0
+        .add_key(KeyDefinition::new(Position::new(0, 0), "MO(99)"))
+        .unwrap(); // Layer 99 doesn't exist
 
     let layers = vec![layer0];
     let layer_refs = build_layer_ref_index(&layers);
@@ -146,10 +164,14 @@ fn test_layer_refs_ignores_out_of_bounds() {
 fn test_multiple_hold_like_refs_at_same_position() {
     // Multiple layers can reference the same position on another layer
     let mut layer0 = Layer::new(0, "Base", RgbColor::new(255, 0, 0)).unwrap();
-    layer0.add_key(KeyDefinition::new(Position::new(0, 0), "MO(2)"));
+    layer0
+        .add_key(KeyDefinition::new(Position::new(0, 0), "MO(2)"))
+        .unwrap();
 
     let mut layer1 = Layer::new(1, "Lower", RgbColor::new(0, 255, 0)).unwrap();
-    layer1.add_key(KeyDefinition::new(Position::new(0, 0), "LT(2, KC_SPC)"));
+    layer1
+        .add_key(KeyDefinition::new(Position::new(0, 0), "LT(2, KC_SPC)"))
+        .unwrap();
 
     let layer2 = Layer::new(2, "Raise", RgbColor::new(0, 0, 255)).unwrap();
 
@@ -0,0 +1,109 @@
+#![allow(missing_docs)]
+//! Geometry, navigation and rendering of rotated keys, using the thumb arcs
+//! of a Kyria-style info.json
+
+use std::path::PathBuf;
+
+use lazyqmk::config::Config;
+use lazyqmk::models::{
+    KeyDefinition, KeyboardGeometry, Layer, Layout, Position, RgbColor, VisualLayoutMapping,
+};
+use lazyqmk::parser::keyboard_json::{build_keyboard_geometry, parse_keyboard_info_json};
+use lazyqmk::tui::handlers::action_handlers::navigation::handle_click;
+use lazyqmk::tui::keyboard::KeyboardWidget;
+use lazyqmk::tui::{render_frame, AppState};
+use ratatui::backend::TestBackend;
+use ratatui::layout::Rect;
+use ratatui::Terminal;
+
+fn kyria() -> KeyboardGeometry {
+    let qmk_path = PathBuf::from(env!("CARGO_MANIFEST_DIR")).join("tests/fixtures/mock_qmk");
+    let info = parse_keyboard_info_json(&qmk_path, "splitkb/kyria/rev1").unwrap();
+    build_keyboard_geometry(&info, "splitkb/kyria/rev1", "LAYOUT_split_3x6_5").unwrap()
+}
+
+fn state() -> AppState {
+    let geometry = kyria();
+    let mapping = VisualLayoutMapping::build(&geometry);
+    let mut layer = Layer::new(0, "Base", RgbColor::default()).unwrap();
+    for position in mapping.get_all_visual_positions() {
+        layer.add_key(KeyDefinition::new(position, "KC_A")).unwrap();
+    }
+    let mut layout = Layout::new("Kyria").unwrap();
+    layout.add_layer(layer).unwrap();
+    AppState::new(layout, None, geometry, mapping, Config::default()).unwrap()
+}
+
+#[test]
+fn test_kyria_rotated_thumbs_parse_with_origin() {
+    let geometry = kyria();
+    let thumb = geometry.get_key_by_matrix((3, 7)).unwrap();
+    assert!(thumb.is_rotated());
+    assert_eq!(thumb.rotation_origin, Some((4.0, 4.5)));
+
+    // Turned 45 degrees around the arc's pivot, the outer thumb key sits a
+    // row lower than its unrotated coordinates suggest
+    let (x, y) = thumb.center();
+    assert!((x - 6.12).abs() < 0.01 && (y - 5.91).abs() < 0.01);
+    assert_eq!(thumb.visual_position(), Position::new(5, 6));
+
+    // Mirrored arc on the right half
+    let right = geometry.get_key_by_matrix((7, 7)).unwrap();
+    assert_eq!(right.visual_position(), Position::new(5, 10));
+}
+
+#[test]
+fn test_kyria_keys_get_distinct_grid_cells() {
+    let geometry = kyria();
+    let mapping = VisualLayoutMapping::build(&geometry);
+    assert_eq!(geometry.key_count(), 50);
+    assert_eq!(mapping.get_all_visual_positions().len(), 50);
+}
+
+#[test]
+fn test_navigation_follows_rotated_centers() {
+    let mapping = VisualLayoutMapping::build(&kyria());
+    // Left thumb arc: 15 and 30 degree keys on row 4, the 45 degree key below
+    assert_eq!(
+        mapping.find_position_right(Position::new(4, 4)),
+        Some(Position::new(4, 5))
+    );
+    assert_eq!(
+        mapping.find_position_down(Position::new(4, 5)),
+        Some(Position::new(5, 6))
+    );
+    assert_eq!(
+        mapping.find_position_up(Position::new(5, 6)),
+        Some(Position::new(4, 5))
+    );
+}
+
+#[test]
+fn test_rotated_keys_render_with_distinct_border_and_hit_test() {
+    let mut state = state();
+    let mut terminal = Terminal::new(TestBackend::new(200, 60)).unwrap();
+    terminal.draw(|f| render_frame(f, &state)).unwrap();
+
+    let area = state.keyboard_area.get();
+    assert_ne!(area, Rect::default());
+    let rotated = KeyboardWidget::key_rect(area, &state, Position::new(5, 6)).unwrap();
+    let upright = KeyboardWidget::key_rect(area, &state, Position::new(0, 0)).unwrap();
+
+    let buffer = terminal.backend().buffer();
+    assert_eq!(buffer[(rotated.x, rotated.y)].symbol(), "╭");
+    assert_eq!(buffer[(upright.x, upright.y)].symbol(), "┌");
+    // The bounding box of a 45 degree key is wider than the key itself
+    assert!(rotated.width > upright.width);
+
+    // Clicking the middle of the rotated key selects it
+    let (column, row) = (
+        rotated.x + rotated.width / 2,
+        rotated.y + rotated.height / 2,
+    );
+    assert_eq!(
+        KeyboardWidget::key_at(area, &state, column, row),
+        Some(Position::new(5, 6))
+    );
+    handle_click(&mut state, column, row);
+    assert_eq!(state.selected_position, Position::new(5, 6));
+}
@@ -14,7 +14,9 @@ fn test_selecting_td_from_picker_opens_form() {
     // Setup test state
     let mut layout = Layout::new("Test Layout").unwrap();
     let mut layer = Layer::new(0, "Base".to_string(), RgbColor::new(212, 212, 212)).unwrap();
-    layer.add_key(KeyDefinition::new(Position::new(0, 0), "KC_TRNS"));
+    layer
+        .add_key(KeyDefinition::new(Position::new(0, 0), "KC_TRNS"))
+        .unwrap();
     layout.add_layer(layer).unwrap();
 
     let geometry = KeyboardGeometry::new("test", "test", 4, 12);
@@ -69,7 +71,9 @@ fn test_td_form_initialization() {
     // Setup test state
     let mut layout = Layout::new("Test Layout").unwrap();
     let mut layer = Layer::new(0, "Base".to_string(), RgbColor::new(212, 212, 212)).unwrap();
-    layer.add_key(KeyDefinition::new(Position::new(0, 0), "KC_TRNS"));
+    layer
+        .add_key(KeyDefinition::new(Position::new(0, 0), "KC_TRNS"))
+        .unwrap();
     layout.add_layer(layer).unwrap();
 
     let geometry = KeyboardGeometry::new("test", "test", 4, 12);
@@ -114,7 +118,9 @@ fn test_reproduction_td_selection_flow() {
     // Setup test state
     let mut layout = Layout::new("Test Layout").unwrap();
     let mut layer = Layer::new(0, "Base".to_string(), RgbColor::new(212, 212, 212)).unwrap();
-    layer.add_key(KeyDefinition::new(Position::new(0, 0), "KC_TRNS"));
+    layer
+        .add_key(KeyDefinition::new(Position::new(0, 0), "KC_TRNS"))
+        .unwrap();
     layout.add_layer(layer).unwrap();
 
     let geometry = KeyboardGeometry::new("test", "test", 4, 12);
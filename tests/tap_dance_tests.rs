@@ -178,15 +178,21 @@ fn test_validate_tap_dances_with_td_keycodes() {
 
     // Add a layer with TD() keycodes
     let mut layer = Layer::new(0, "Base".to_string(), RgbColor::new(212, 212, 212)).unwrap();
-    layer.add_key(KeyDefinition::new(Position { row: 0, col: 0 }, "KC_Q"));
-    layer.add_key(KeyDefinition::new(
-        Position { row: 0, col: 1 },
-        "TD(esc_caps)",
-    ));
-    layer.add_key(KeyDefinition::new(
-        Position { row: 0, col: 2 },
-        "TD(shift_caps)",
-    ));
+    layer
+        .add_key(KeyDefinition::new(Position { row: 0, col: 0 }, "KC_Q"))
+        .unwrap();
+    layer
+        .add_key(KeyDefinition::new(
+            Position { row: 0, col: 1 },
+            "TD(esc_caps)",
+        ))
+        .unwrap();
+    layer
+        .add_key(KeyDefinition::new(
+            Position { row: 0, col: 2 },
+            "TD(shift_caps)",
+        ))
+        .unwrap();
 
     layout.add_layer(layer).unwrap();
 
@@ -207,12 +213,18 @@ fn test_validate_tap_dances_with_missing_definition() {
 
     // Add a layer that references a non-existent tap dance
     let mut layer = Layer::new(0, "Base".to_string(), RgbColor::new(212, 212, 212)).unwrap();
-    layer.add_key(KeyDefinition::new(Position { row: 0, col: 0 }, "KC_Q"));
-    layer.add_key(KeyDefinition::new(
-        Position { row: 0, col: 1 },
-        "TD(nonexistent)",
-    )); // This doesn't exist!
-    layer.add_key(KeyDefinition::new(Position { row: 0, col: 2 }, "KC_W"));
+    layer
+        .add_key(KeyDefinition::new(Position { row: 0, col: 0 }, "KC_Q"))
+        .unwrap();
+    layer
+        .add_key(KeyDefinition::new(
+            Position { row: 0, col: 1 },
+            "TD(nonexistent)",
+        ))
+        .unwrap(); // This doesn't exist!
+    layer
+        .add_key(KeyDefinition::new(Position { row: 0, col: 2 }, "KC_W"))
+        .unwrap();
 
     layout.add_layer(layer).unwrap();
 
@@ -240,12 +252,18 @@ fn test_get_orphaned_tap_dances() {
 
     // Add a layer that only uses esc_caps
     let mut layer = Layer::new(0, "Base".to_string(), RgbColor::new(212, 212, 212)).unwrap();
-    layer.add_key(KeyDefinition::new(Position { row: 0, col: 0 }, "KC_Q"));
-    layer.add_key(KeyDefinition::new(
-        Position { row: 0, col: 1 },
-        "TD(esc_caps)",
-    ));
-    layer.add_key(KeyDefinition::new(Position { row: 0, col: 2 }, "KC_W"));
+    layer
+        .add_key(KeyDefinition::new(Position { row: 0, col: 0 }, "KC_Q"))
+        .unwrap();
+    layer
+        .add_key(KeyDefinition::new(
+            Position { row: 0, col: 1 },
+            "TD(esc_caps)",
+        ))
+        .unwrap();
+    layer
+        .add_key(KeyDefinition::new(Position { row: 0, col: 2 }, "KC_W"))
+        .unwrap();
 
     layout.add_layer(layer).unwrap();
 
@@ -270,7 +288,9 @@ fn test_td_inline_wizard_two_way_flow() {
     // Setup test state
     let mut layout = Layout::new("Test Layout").unwrap();
     let mut layer = Layer::new(0, "Base".to_string(), RgbColor::new(212, 212, 212)).unwrap();
-    layer.add_key(KeyDefinition::new(Position::new(0, 0), "KC_TRNS"));
+    layer
+        .add_key(KeyDefinition::new(Position::new(0, 0), "KC_TRNS"))
+        .unwrap();
     layout.add_layer(layer).unwrap();
 
     let geometry = KeyboardGeometry::new("test", "test", 4, 12);
@@ -315,7 +335,9 @@ fn test_td_inline_wizard_three_way_flow() {
     // Setup test state
     let mut layout = Layout::new("Test Layout").unwrap();
     let mut layer = Layer::new(0, "Base".to_string(), RgbColor::new(212, 212, 212)).unwrap();
-    layer.add_key(KeyDefinition::new(Position::new(0, 0), "KC_TRNS"));
+    layer
+        .add_key(KeyDefinition::new(Position::new(0, 0), "KC_TRNS"))
+        .unwrap();
     layout.add_layer(layer).unwrap();
 
     let geometry = KeyboardGeometry::new("test", "test", 4, 12);
@@ -356,7 +378,9 @@ fn test_lt_flow_regression() {
     // Setup test state
     let mut layout = Layout::new("Test Layout").unwrap();
     let mut layer = Layer::new(0, "Base".to_string(), RgbColor::new(212, 212, 212)).unwrap();
-    layer.add_key(KeyDefinition::new(Position::new(0, 0), "KC_TRNS"));
+    layer
+        .add_key(KeyDefinition::new(Position::new(0, 0), "KC_TRNS"))
+        .unwrap();
     layout.add_layer(layer).unwrap();
 
     let geometry = KeyboardGeometry::new("test", "test", 4, 12);
@@ -0,0 +1,100 @@
+#![allow(missing_docs)]
+//! Multi-level undo (`u`, `Ctrl+Z`) and redo (`Ctrl+R`) in the TUI editor
+
+use crossterm::event::{KeyCode, KeyEvent, KeyModifiers};
+use lazyqmk::config::Config;
+use lazyqmk::models::{KeyboardGeometry, Layout, Position, VisualLayoutMapping};
+use lazyqmk::shortcuts::Action;
+use lazyqmk::tui::handlers::{dispatch_action, handle_main_input};
+use lazyqmk::tui::AppState;
+
+mod fixtures;
+use fixtures::test_layout_basic;
+
+fn state() -> AppState {
+    AppState::new(
+        test_layout_basic(2, 3),
+        None,
+        KeyboardGeometry::new("test", "test", 2, 3),
+        VisualLayoutMapping::default(),
+        Config::default(),
+    )
+    .unwrap()
+}
+
+fn press(state: &mut AppState, code: KeyCode, modifiers: KeyModifiers) {
+    handle_main_input(state, KeyEvent::new(code, modifiers)).unwrap();
+}
+
+fn keycode(layout: &Layout, layer: usize, position: Position) -> &str {
+    &layout.layers[layer].get_key(position).unwrap().keycode
+}
+
+#[test]
+fn test_undo_and_redo_across_layers() {
+    let mut state = state();
+    let original = state.layout.clone();
+    let first = Position::new(0, 0);
+    let second = Position::new(0, 1);
+
+    state.selected_position = first;
+    dispatch_action(&mut state, Action::ClearKey).unwrap();
+    dispatch_action(&mut state, Action::NextLayer).unwrap();
+    state.selected_position = second;
+    dispatch_action(&mut state, Action::ClearKey).unwrap();
+    assert_eq!(state.undo_history.undo_count(), 2);
+
+    // Each undo returns to the layer the edit was made on
+    press(&mut state, KeyCode::Char('u'), KeyModifiers::NONE);
+    assert_eq!(state.current_layer, 1);
+    assert_eq!(
+        keycode(&state.layout, 1, second),
+        keycode(&original, 1, second)
+    );
+    assert_eq!(keycode(&state.layout, 0, first), "KC_TRNS");
+
+    press(&mut state, KeyCode::Char('z'), KeyModifiers::CONTROL);
+    assert_eq!(state.current_layer, 0);
+    assert_eq!(state.layout, original);
+    assert_eq!(state.status_message, "Undone: Key cleared (KC_TRNS)");
+
+    press(&mut state, KeyCode::Char('u'), KeyModifiers::NONE);
+    assert_eq!(state.error_message.as_deref(), Some("Nothing to undo"));
+    state.clear_error();
+
+    press(&mut state, KeyCode::Char('r'), KeyModifiers::CONTROL);
+    assert_eq!(keycode(&state.layout, 0, first), "KC_TRNS");
+    assert_eq!(
+        keycode(&state.layout, 1, second),
+        keycode(&original, 1, second)
+    );
+    assert!(state.undo_history.can_redo());
+
+    // A new edit drops the edit that could still have been redone
+    state.selected_position = Position::new(1, 2);
+    dispatch_action(&mut state, Action::ClearKey).unwrap();
+    assert!(!state.undo_history.can_redo());
+    assert_eq!(state.undo_history.undo_count(), 2);
+}
+
+#[test]
+fn test_cut_and_paste_is_one_undo_step() {
+    let mut state = state();
+    let original = state.layout.clone();
+    let source = Position::new(0, 0);
+    let target = Position::new(1, 1);
+
+    state.selected_position = source;
+    press(&mut state, KeyCode::Char('d'), KeyModifiers::NONE);
+    state.selected_position = target;
+    press(&mut state, KeyCode::Char('p'), KeyModifiers::NONE);
+    assert_eq!(
+        keycode(&state.layout, 0, target),
+        keycode(&original, 0, source)
+    );
+    assert_eq!(keycode(&state.layout, 0, source), "KC_TRNS");
+
+    press(&mut state, KeyCode::Char('u'), KeyModifiers::NONE);
+    assert_eq!(state.layout, original);
+    assert!(!state.undo_history.can_undo());
+}
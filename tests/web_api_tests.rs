@@ -17,11 +17,13 @@ use std::fs;
 use tempfile::TempDir;
 use tower::ServiceExt;
 
-use lazyqmk::config::{BuildConfig, Config, PathConfig, UiConfig};
+use lazyqmk::config::{BuildBackend, BuildConfig, Config, PathConfig, UiConfig};
 use lazyqmk::web::{create_router, AppState};
 
 mod fixtures;
-use fixtures::{test_layout_basic, write_layout_file};
+use fixtures::{
+    test_layout_basic, test_layout_with_categories, test_layout_with_tap_dances, write_layout_file,
+};
 
 /// Creates a test layout marked as a template.
 fn test_template_basic(rows: usize, cols: usize, name: &str) -> lazyqmk::models::Layout {
@@ -38,11 +40,21 @@ fn create_test_state() -> (AppState, TempDir) {
     let temp_dir = TempDir::new().expect("Failed to create temp dir");
 
     let config = Config {
-        paths: PathConfig { qmk_firmware: None },
+        paths: PathConfig {
+            qmk_firmware: None,
+            library: None,
+            info_cache: None,
+        },
         build: BuildConfig {
             output_dir: temp_dir.path().to_path_buf(),
+            template_dir: None,
+            keyboard: None,
+            keymap: None,
+            converter: None,
+            backend: BuildBackend::Auto,
         },
         ui: UiConfig::default(),
+        ..Config::default()
     };
 
     let state =
@@ -103,11 +115,19 @@ fn create_test_state_with_qmk() -> (AppState, TempDir) {
     let config = Config {
         paths: PathConfig {
             qmk_firmware: Some(qmk_path),
+            library: None,
+            info_cache: None,
         },
         build: BuildConfig {
             output_dir: temp_dir.path().to_path_buf(),
+            template_dir: None,
+            keyboard: None,
+            keymap: None,
+            converter: None,
+            backend: BuildBackend::Auto,
         },
         ui: UiConfig::default(),
+        ..Config::default()
     };
 
     let state =
@@ -193,6 +213,39 @@ async fn test_health_check() {
     assert!(json["version"].is_string());
 }
 
+#[tokio::test]
+async fn test_metrics_counts_requests_by_route() {
+    let (state, temp_dir) = create_test_state();
+    let layout_path = temp_dir.path().join("secret_name.md");
+    write_layout_file(&test_layout_basic(2, 3), &layout_path).expect("Failed to write layout");
+    let app = create_router(state);
+
+    let (status, _) = get_json(&app, "/api/layouts/secret_name").await;
+    assert_eq!(status, StatusCode::OK);
+    let (status, _) = get_json(&app, "/api/layouts/secret_name/validate").await;
+    assert_eq!(status, StatusCode::OK);
+
+    let response = app
+        .clone()
+        .oneshot(
+            Request::builder()
+                .uri("/metrics")
+                .body(Body::empty())
+                .unwrap(),
+        )
+        .await
+        .unwrap();
+    assert_eq!(response.status(), StatusCode::OK);
+    let body = response.into_body().collect().await.unwrap().to_bytes();
+    let text = String::from_utf8(body.to_vec()).unwrap();
+
+    assert!(text.contains(
+        "lazyqmk_http_requests_total{method=\"GET\",route=\"/api/layouts/{filename}\",status=\"200\"} 1"
+    ));
+    assert!(text.contains("lazyqmk_validations_total 1"));
+    assert!(!text.contains("secret_name"));
+}
+
 // ============================================================================
 // Layout Endpoint Tests
 // ============================================================================
@@ -246,6 +299,31 @@ async fn test_get_layout_success() {
     assert_eq!(json["layers"].as_array().unwrap().len(), 2);
 }
 
+#[tokio::test]
+async fn test_get_layout_reports_geometry_mismatch() {
+    let (state, temp_dir) = create_test_state_with_qmk();
+
+    // LAYOUT_test is 2x3; a third row doesn't fit
+    write_layout_file(&test_layout_basic(3, 3), &temp_dir.path().join("big.md"))
+        .expect("Failed to write layout");
+    write_layout_file(&test_layout_basic(2, 3), &temp_dir.path().join("fits.md"))
+        .expect("Failed to write layout");
+
+    let app = create_router(state);
+
+    let (status, json) = get_json(&app, "/api/layouts/big.md").await;
+    assert_eq!(status, StatusCode::OK);
+    assert_eq!(json["metadata"]["name"], "Test Layout");
+    let mismatch = &json["geometry_mismatch"];
+    assert_eq!(mismatch["geometry_keys"], 6);
+    assert_eq!(mismatch["layers"][0]["key_count"], 9);
+    assert_eq!(mismatch["layers"][0]["extra"].as_array().unwrap().len(), 3);
+
+    let (status, json) = get_json(&app, "/api/layouts/fits.md").await;
+    assert_eq!(status, StatusCode::OK);
+    assert!(json["geometry_mismatch"].is_null());
+}
+
 #[tokio::test]
 async fn test_get_layout_not_found() {
     let (state, _temp_dir) = create_test_state();
@@ -304,6 +382,268 @@ async fn test_save_layout_path_traversal_rejected() {
     assert_eq!(status, StatusCode::BAD_REQUEST);
 }
 
+// ============================================================================
+// Tap Dance & Category Endpoint Tests
+// ============================================================================
+
+/// Sends a request with an optional JSON body and `If-Match` header.
+///
+/// Returns the status, the `ETag` header, and the JSON body.
+async fn send_json(
+    app: &axum::Router,
+    method: &str,
+    uri: &str,
+    body: Option<Value>,
+    if_match: Option<&str>,
+) -> (StatusCode, Option<String>, Value) {
+    let mut request = Request::builder()
+        .method(method)
+        .uri(uri)
+        .header("Content-Type", "application/json");
+    if let Some(etag) = if_match {
+        request = request.header("If-Match", etag);
+    }
+    let body = body.map_or_else(Body::empty, |b| Body::from(serde_json::to_vec(&b).unwrap()));
+    let response = app
+        .clone()
+        .oneshot(request.body(body).unwrap())
+        .await
+        .unwrap();
+
+    let status = response.status();
+    let etag = response
+        .headers()
+        .get("ETag")
+        .map(|v| v.to_str().unwrap().to_string());
+    let body = response.into_body().collect().await.unwrap().to_bytes();
+    let json: Value = serde_json::from_slice(&body).unwrap_or(Value::Null);
+
+    (status, etag, json)
+}
+
+#[tokio::test]
+async fn test_tap_dance_crud() {
+    let (state, temp_dir) = create_test_state();
+    let path = temp_dir.path().join("td.md");
+    write_layout_file(&test_layout_with_tap_dances(), &path).unwrap();
+    let app = create_router(state);
+
+    let (status, etag, json) =
+        send_json(&app, "GET", "/api/layouts/td/tap-dances", None, None).await;
+    assert_eq!(status, StatusCode::OK);
+    assert!(etag.is_some());
+    assert_eq!(json.as_array().unwrap().len(), 2);
+
+    let (status, _, json) = send_json(
+        &app,
+        "POST",
+        "/api/layouts/td/tap-dances",
+        Some(json!({"name": "quote", "single_tap": "KC_QUOT", "double_tap": "KC_DQUO"})),
+        etag.as_deref(),
+    )
+    .await;
+    assert_eq!(status, StatusCode::CREATED);
+    assert_eq!(json[2]["name"], "quote");
+
+    // Duplicate names conflict
+    let (status, _, _) = send_json(
+        &app,
+        "POST",
+        "/api/layouts/td/tap-dances",
+        Some(json!({"name": "quote", "single_tap": "KC_A"})),
+        None,
+    )
+    .await;
+    assert_eq!(status, StatusCode::CONFLICT);
+
+    // Invalid tap dances are rejected by model validation
+    let (status, _, json) = send_json(
+        &app,
+        "POST",
+        "/api/layouts/td/tap-dances",
+        Some(json!({"name": "bad name", "single_tap": "KC_A"})),
+        None,
+    )
+    .await;
+    assert_eq!(status, StatusCode::BAD_REQUEST);
+    assert_eq!(json["error"], "Invalid tap dance");
+
+    // Renaming updates the TD() keys that use it
+    let (status, _, json) = send_json(
+        &app,
+        "PUT",
+        "/api/layouts/td/tap-dances/esc_caps",
+        Some(json!({"name": "escape", "single_tap": "KC_ESC", "hold": "KC_LCTL"})),
+        None,
+    )
+    .await;
+    assert_eq!(status, StatusCode::OK);
+    assert_eq!(json[0]["name"], "escape");
+    let layout = lazyqmk::services::LayoutService::load(&path).unwrap();
+    assert_eq!(layout.layers[0].keys[0].keycode, "TD(escape)");
+
+    // A tap dance still used by a key can't be deleted
+    let (status, _, _) = send_json(
+        &app,
+        "DELETE",
+        "/api/layouts/td/tap-dances/escape",
+        None,
+        None,
+    )
+    .await;
+    assert_eq!(status, StatusCode::CONFLICT);
+
+    let (status, _, json) = send_json(
+        &app,
+        "DELETE",
+        "/api/layouts/td/tap-dances/quote",
+        None,
+        None,
+    )
+    .await;
+    assert_eq!(status, StatusCode::OK);
+    assert_eq!(json.as_array().unwrap().len(), 2);
+
+    let (status, _, _) =
+        send_json(&app, "GET", "/api/layouts/td/tap-dances/quote", None, None).await;
+    assert_eq!(status, StatusCode::NOT_FOUND);
+}
+
+#[tokio::test]
+async fn test_category_crud() {
+    let (state, temp_dir) = create_test_state();
+    let path = temp_dir.path().join("cats.md");
+    write_layout_file(&test_layout_with_categories(), &path).unwrap();
+    let app = create_router(state);
+
+    let (status, _, json) = send_json(
+        &app,
+        "POST",
+        "/api/layouts/cats.md/categories",
+        Some(json!({"id": "media", "name": "Media", "color": {"r": 0, "g": 0, "b": 255}})),
+        None,
+    )
+    .await;
+    assert_eq!(status, StatusCode::CREATED);
+    assert_eq!(json.as_array().unwrap().len(), 3);
+
+    let (status, _, json) = send_json(
+        &app,
+        "POST",
+        "/api/layouts/cats.md/categories",
+        Some(json!({"id": "Not Kebab", "name": "Bad", "color": {"r": 0, "g": 0, "b": 0}})),
+        None,
+    )
+    .await;
+    assert_eq!(status, StatusCode::BAD_REQUEST);
+    assert_eq!(json["error"], "Invalid category");
+
+    // Renaming the ID moves key assignments along with it
+    let (status, _, _) = send_json(
+        &app,
+        "PUT",
+        "/api/layouts/cats.md/categories/navigation",
+        Some(json!({"id": "nav", "name": "Nav", "color": {"r": 0, "g": 200, "b": 0}})),
+        None,
+    )
+    .await;
+    assert_eq!(status, StatusCode::OK);
+    let layout = lazyqmk::services::LayoutService::load(&path).unwrap();
+    assert_eq!(layout.layers[0].keys[0].category_id.as_deref(), Some("nav"));
+
+    let (status, _, json) = send_json(
+        &app,
+        "GET",
+        "/api/layouts/cats.md/categories/nav",
+        None,
+        None,
+    )
+    .await;
+    assert_eq!(status, StatusCode::OK);
+    assert_eq!(json["name"], "Nav");
+
+    // In use by a key
+    let (status, _, _) = send_json(
+        &app,
+        "DELETE",
+        "/api/layouts/cats.md/categories/nav",
+        None,
+        None,
+    )
+    .await;
+    assert_eq!(status, StatusCode::CONFLICT);
+
+    let (status, _, json) = send_json(
+        &app,
+        "DELETE",
+        "/api/layouts/cats.md/categories/media",
+        None,
+        None,
+    )
+    .await;
+    assert_eq!(status, StatusCode::OK);
+    assert_eq!(json.as_array().unwrap().len(), 2);
+}
+
+#[tokio::test]
+async fn test_stale_if_match_is_rejected() {
+    let (state, temp_dir) = create_test_state();
+    let path = temp_dir.path().join("shared.md");
+    write_layout_file(&test_layout_with_tap_dances(), &path).unwrap();
+    let app = create_router(state);
+
+    let (_, stale, _) = send_json(&app, "GET", "/api/layouts/shared", None, None).await;
+    let stale = stale.expect("layout has an ETag");
+
+    // Someone else edits the layout
+    let (status, fresh, _) = send_json(
+        &app,
+        "POST",
+        "/api/layouts/shared/categories",
+        Some(json!({"id": "media", "name": "Media", "color": {"r": 0, "g": 0, "b": 255}})),
+        Some(&stale),
+    )
+    .await;
+    assert_eq!(status, StatusCode::CREATED);
+    let fresh = fresh.unwrap();
+    assert_ne!(fresh, stale);
+    let before = fs::read_to_string(&path).unwrap();
+
+    let (status, _, json) = send_json(
+        &app,
+        "DELETE",
+        "/api/layouts/shared/tap-dances/shift_ctrl",
+        None,
+        Some(&stale),
+    )
+    .await;
+    assert_eq!(status, StatusCode::PRECONDITION_FAILED);
+    assert!(json["details"].as_str().unwrap().contains(&fresh));
+
+    let mut layout = test_layout_basic(2, 3);
+    layout.metadata.name = "Overwritten".to_string();
+    let (status, _, _) = send_json(
+        &app,
+        "PUT",
+        "/api/layouts/shared",
+        Some(serde_json::to_value(&layout).unwrap()),
+        Some(&stale),
+    )
+    .await;
+    assert_eq!(status, StatusCode::PRECONDITION_FAILED);
+    assert_eq!(fs::read_to_string(&path).unwrap(), before);
+
+    let (status, _, _) = send_json(
+        &app,
+        "PUT",
+        "/api/layouts/shared",
+        Some(serde_json::to_value(&layout).unwrap()),
+        Some(&fresh),
+    )
+    .await;
+    assert_eq!(status, StatusCode::NO_CONTENT);
+}
+
 // ============================================================================
 // Keycode Endpoint Tests
 // ============================================================================
@@ -486,6 +826,120 @@ async fn test_get_geometry_layout_not_found() {
     assert!(json["error"].as_str().unwrap().contains("not found"));
 }
 
+/// Sends a GET request with an optional `If-None-Match` header.
+async fn get_conditional(
+    app: &axum::Router,
+    uri: &str,
+    etag: Option<&str>,
+) -> axum::response::Response {
+    let mut request = Request::builder().method("GET").uri(uri);
+    if let Some(etag) = etag {
+        request = request.header("if-none-match", etag);
+    }
+    app.clone()
+        .oneshot(request.body(Body::empty()).unwrap())
+        .await
+        .unwrap()
+}
+
+#[tokio::test]
+async fn test_get_geometry_variant_query() {
+    let (state, _temp_dir) = create_test_state_with_qmk();
+    let app = create_router(state);
+
+    let (status, json) = get_json(
+        &app,
+        "/api/keyboards/test_keyboard/geometry?variant=LAYOUT_test",
+    )
+    .await;
+
+    assert_eq!(status, StatusCode::OK);
+    assert_eq!(json["layout"], "LAYOUT_test");
+    assert_eq!(json["keys"].as_array().unwrap().len(), 6);
+}
+
+#[tokio::test]
+async fn test_get_geometry_conditional_request_skips_parse() {
+    let (state, _temp_dir) = create_test_state_with_qmk();
+    let app = create_router(state.clone());
+    let uri = "/api/keyboards/test_keyboard/geometry?variant=LAYOUT_test";
+
+    let first = get_conditional(&app, uri, None).await;
+    assert_eq!(first.status(), StatusCode::OK);
+    assert_eq!(first.headers()["cache-control"], "no-cache");
+    let etag = first.headers()["etag"].to_str().unwrap().to_string();
+    assert!(etag.starts_with('"'));
+    assert_eq!(state.geometry_parse_count(), 1);
+
+    let second = get_conditional(&app, uri, Some(&etag)).await;
+    assert_eq!(second.status(), StatusCode::NOT_MODIFIED);
+    assert_eq!(second.headers()["etag"], etag.as_str());
+    assert_eq!(state.geometry_parse_count(), 1);
+
+    // Unconditional repeat is served from the cache
+    let third = get_conditional(&app, uri, None).await;
+    assert_eq!(third.status(), StatusCode::OK);
+    assert_eq!(state.geometry_parse_count(), 1);
+
+    // Legacy path route shares the same ETag
+    let legacy = get_conditional(
+        &app,
+        "/api/keyboards/test_keyboard/geometry/LAYOUT_test",
+        Some(&etag),
+    )
+    .await;
+    assert_eq!(legacy.status(), StatusCode::NOT_MODIFIED);
+}
+
+#[tokio::test]
+async fn test_get_geometry_etag_changes_with_info_json() {
+    let (state, temp_dir) = create_test_state_with_qmk();
+    let app = create_router(state.clone());
+    let uri = "/api/keyboards/test_keyboard/geometry?variant=LAYOUT_test";
+
+    let first = get_conditional(&app, uri, None).await;
+    let etag = first.headers()["etag"].to_str().unwrap().to_string();
+
+    let info_path = temp_dir
+        .path()
+        .join("qmk_firmware/keyboards/test_keyboard/info.json");
+    let mut content = fs::read_to_string(&info_path).unwrap();
+    content.push('\n');
+    fs::write(&info_path, content).unwrap();
+
+    let second = get_conditional(&app, uri, Some(&etag)).await;
+    assert_eq!(second.status(), StatusCode::OK);
+    assert_ne!(second.headers()["etag"], etag.as_str());
+    assert_eq!(state.geometry_parse_count(), 2);
+}
+
+#[tokio::test]
+async fn test_list_keycodes_conditional_request() {
+    let (state, _temp_dir) = create_test_state();
+    let app = create_router(state);
+
+    let first = get_conditional(&app, "/api/keycodes?search=KC_A", None).await;
+    assert_eq!(first.status(), StatusCode::OK);
+    assert!(first.headers()["cache-control"]
+        .to_str()
+        .unwrap()
+        .contains("max-age"));
+    let etag = first.headers()["etag"].to_str().unwrap().to_string();
+
+    let second = get_conditional(&app, "/api/keycodes?search=KC_A", Some(&etag)).await;
+    assert_eq!(second.status(), StatusCode::NOT_MODIFIED);
+
+    // Different query, different ETag
+    let other = get_conditional(&app, "/api/keycodes?search=KC_B", Some(&etag)).await;
+    assert_eq!(other.status(), StatusCode::OK);
+
+    let categories = get_conditional(&app, "/api/keycodes/categories", Some(&etag)).await;
+    assert_eq!(categories.status(), StatusCode::OK);
+    let category_etag = categories.headers()["etag"].to_str().unwrap().to_string();
+    let again = get_conditional(&app, "/api/keycodes/categories", Some(&category_etag)).await;
+    assert_eq!(again.status(), StatusCode::NOT_MODIFIED);
+}
+
 #[tokio::test]
 async fn test_get_geometry_path_traversal_rejected() {
     let (state, _temp_dir) = create_test_state_with_qmk();
@@ -514,20 +968,28 @@ async fn test_get_geometry_path_traversal_rejected() {
 fn create_test_state_with_template_dir() -> (AppState, TempDir, std::path::PathBuf) {
     let temp_dir = TempDir::new().expect("Failed to create temp dir");
 
-    // Use the platform config directory for templates (what the endpoints use)
-    let template_dir = lazyqmk::config::Config::config_dir()
-        .expect("Failed to get config dir")
-        .join("templates");
+    // Use the platform templates directory (what the endpoints use)
+    let template_dir = lazyqmk::paths::templates_dir().expect("Failed to get templates dir");
 
     // Ensure template directory exists
     fs::create_dir_all(&template_dir).expect("Failed to create template dir");
 
     let config = Config {
-        paths: PathConfig { qmk_firmware: None },
+        paths: PathConfig {
+            qmk_firmware: None,
+            library: None,
+            info_cache: None,
+        },
         build: BuildConfig {
             output_dir: temp_dir.path().to_path_buf(),
+            template_dir: None,
+            keyboard: None,
+            keymap: None,
+            converter: None,
+            backend: BuildBackend::Auto,
         },
         ui: UiConfig::default(),
+        ..Config::default()
     };
 
     let state =
@@ -1400,10 +1862,30 @@ async fn test_start_build_no_keyboard_in_layout() {
     let (status, json) = post_json(&app, "/api/build/start", request).await;
 
     assert_eq!(status, StatusCode::BAD_REQUEST);
-    assert!(json["error"]
-        .as_str()
-        .unwrap()
-        .contains("no keyboard defined"));
+    assert!(json["error"].as_str().unwrap().contains("No keyboard set"));
+}
+
+#[tokio::test]
+async fn test_start_build_keymap_traversal_rejected() {
+    let (state, temp_dir) = create_test_state();
+
+    let mut layout = test_layout_basic(2, 3);
+    layout.metadata.keyboard = Some("test_keyboard".to_string());
+    layout.metadata.keymap_name = Some("../../../tmp/evil".to_string());
+    let filename = "traversal_keymap.md";
+    write_layout_file(&layout, &temp_dir.path().join(filename)).expect("Failed to write layout");
+
+    let app = create_router(state);
+
+    let (status, json) = post_json(
+        &app,
+        "/api/build/start",
+        json!({ "layout_filename": filename }),
+    )
+    .await;
+
+    assert_eq!(status, StatusCode::BAD_REQUEST);
+    assert!(json["error"].as_str().unwrap().contains("path traversal"));
 }
 
 #[tokio::test]
@@ -1470,6 +1952,54 @@ async fn test_start_build_no_qmk_path() {
         .contains("QMK firmware path not configured"));
 }
 
+#[tokio::test]
+async fn test_build_and_generate_refuse_keyboard_mismatch() {
+    let temp_dir = TempDir::new().expect("Failed to create temp dir");
+    let config = Config {
+        build: BuildConfig {
+            output_dir: temp_dir.path().to_path_buf(),
+            keyboard: Some("crkbd".to_string()),
+            ..BuildConfig::default()
+        },
+        ..Config::default()
+    };
+    let state =
+        AppState::new(config, temp_dir.path().to_path_buf()).expect("Failed to create app state");
+    let layout = test_layout_basic(2, 3);
+    write_layout_file(&layout, &temp_dir.path().join("lily.md")).expect("Failed to write layout");
+    let app = create_router(state);
+
+    let (status, json) = post_json(
+        &app,
+        "/api/build/start",
+        json!({ "layout_filename": "lily.md" }),
+    )
+    .await;
+    assert_eq!(status, StatusCode::CONFLICT);
+    let details = json["details"].as_str().unwrap();
+    assert!(details.contains("'test_keyboard'"), "{details}");
+    assert!(details.contains("'crkbd' (config)"), "{details}");
+
+    let (status, _) = post_json(&app, "/api/layouts/lily.md/generate", json!({})).await;
+    assert_eq!(status, StatusCode::CONFLICT);
+
+    // Confirmed: the check passes and the missing QMK path is reported instead
+    let (status, _) = post_json(
+        &app,
+        "/api/build/start",
+        json!({ "layout_filename": "lily.md", "allow_keyboard_mismatch": true }),
+    )
+    .await;
+    assert_eq!(status, StatusCode::SERVICE_UNAVAILABLE);
+    let (status, _) = post_json(
+        &app,
+        "/api/layouts/lily.md/generate?allow_keyboard_mismatch=true",
+        json!({}),
+    )
+    .await;
+    assert_ne!(status, StatusCode::CONFLICT);
+}
+
 // ============================================================================
 // Preflight Endpoint Tests
 // ============================================================================
@@ -1590,6 +2120,36 @@ async fn test_generate_firmware_no_keyboard() {
     assert!(json["error"].as_str().unwrap().contains("no keyboard"));
 }
 
+#[tokio::test]
+async fn test_generate_firmware_keymap_traversal_rejected() {
+    let (state, temp_dir) = create_test_state_with_qmk();
+    let app = create_router(state);
+
+    for (index, keymap) in ["../../something", "..\\..\\something", "C:\\evil"]
+        .iter()
+        .enumerate()
+    {
+        let mut layout = test_layout_basic(2, 3);
+        layout.metadata.keymap_name = Some((*keymap).to_string());
+        let filename = format!("traversal_{index}.md");
+        write_layout_file(&layout, &temp_dir.path().join(&filename))
+            .expect("Failed to write layout");
+
+        let (status, json) = post_json(
+            &app,
+            &format!("/api/layouts/{filename}/generate"),
+            json!({}),
+        )
+        .await;
+
+        assert_eq!(status, StatusCode::BAD_REQUEST, "keymap {keymap:?}");
+        assert!(json["error"]
+            .as_str()
+            .unwrap()
+            .contains("Invalid keymap name"));
+    }
+}
+
 #[tokio::test]
 async fn test_generate_firmware_no_layout_variant() {
     let (state, temp_dir) = create_test_state_with_qmk();
@@ -1754,3 +2314,45 @@ async fn test_generate_download_job_not_found() {
     assert_eq!(status, StatusCode::NOT_FOUND);
     assert!(json["error"].as_str().unwrap().contains("not found"));
 }
+
+// ============================================================================
+// Workspace Event Tests
+// ============================================================================
+
+#[tokio::test]
+async fn test_workspace_events_stream_layout_added() {
+    let (state, temp_dir) = create_test_state();
+    let app = create_router(state);
+
+    let response = app
+        .oneshot(
+            Request::builder()
+                .method("GET")
+                .uri("/api/events")
+                .body(Body::empty())
+                .unwrap(),
+        )
+        .await
+        .unwrap();
+
+    assert_eq!(response.status(), StatusCode::OK);
+    assert!(response.headers()["content-type"]
+        .to_str()
+        .unwrap()
+        .starts_with("text/event-stream"));
+
+    let layout = test_layout_basic(2, 3);
+    write_layout_file(&layout, &temp_dir.path().join("watched.md"))
+        .expect("Failed to write layout");
+
+    let mut body = response.into_body();
+    let frame = tokio::time::timeout(std::time::Duration::from_secs(10), body.frame())
+        .await
+        .expect("no event received")
+        .unwrap()
+        .unwrap();
+    let text = String::from_utf8(frame.into_data().unwrap().to_vec()).unwrap();
+
+    assert!(text.contains("event: layout-added"));
+    assert!(text.contains("\"filename\":\"watched.md\""));
+}
@@ -1,91 +1,354 @@
 //! Backend spawning and management for Tauri desktop app.
 //!
 //! This module handles spawning the LazyQMK backend server as a child process
-//! and managing its lifecycle.
+//! and managing its lifecycle. Backends left running by a crashed app are
+//! found through their pidfiles (see [`crate::pidfile`]) and adopted or
+//! terminated before a new one is spawned.
 
 use anyhow::{Context, Result};
+use std::net::{IpAddr, SocketAddr};
+use std::path::{Path, PathBuf};
 use std::process::Stdio;
-use tokio::io::{AsyncBufReadExt, BufReader};
+use std::time::Duration;
+use tokio::io::{AsyncReadExt, AsyncWriteExt};
+use tokio::net::TcpStream;
 use tokio::process::{Child, Command};
 
-/// Handle to a spawned backend process
+use crate::logs::BackendLogs;
+use crate::pidfile::{self, BackendRecord};
+use crate::settings::{self, BackendSettings};
+
+/// Environment variable passing the access token to the backend, so it
+/// doesn't show up in the process list
+const AUTH_TOKEN_ENV: &str = "LAZYQMK_AUTH_TOKEN";
+
+/// Name of the backend binary, as reported by the process table
+const BACKEND_PROCESS_NAME: &str = "lazyqmk-web";
+
+/// How long a health check may take before the backend counts as hung
+const HEALTH_TIMEOUT: Duration = Duration::from_secs(2);
+
+/// Time given to a backend to shut down before it is killed
+const GRACEFUL_SHUTDOWN: Duration = Duration::from_millis(500);
+
+/// The configured fixed port is taken by another program
+#[derive(Debug)]
+pub struct PortInUse(pub u16);
+
+impl std::fmt::Display for PortInUse {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "Port {} is already in use by another program", self.0)
+    }
+}
+
+impl std::error::Error for PortInUse {}
+
+/// How the backend process is owned
+enum BackendProcess {
+    /// Spawned by this app instance
+    Spawned(Child),
+    /// Left running by a previous app instance and adopted
+    Adopted(u32),
+}
+
+/// Handle to a running backend process
 pub struct BackendHandle {
-    /// The child process
-    child: Child,
+    /// The backend process
+    process: BackendProcess,
     /// Port the backend is listening on
     pub port: u16,
+    /// Address for reaching the backend from this machine
+    pub host: IpAddr,
+    /// Pidfile recording the process, removed when it stops
+    pidfile: Option<PathBuf>,
+    /// Recent output of a spawned backend; empty for adopted ones, whose
+    /// output went to the app instance that started them
+    pub logs: BackendLogs,
 }
 
 impl BackendHandle {
+    /// Process ID of the backend, if it is still known
+    fn pid(&self) -> Option<u32> {
+        match &self.process {
+            BackendProcess::Spawned(child) => child.id(),
+            BackendProcess::Adopted(pid) => Some(*pid),
+        }
+    }
+
+    /// Whether the backend was left running by a previous app instance
+    pub fn is_adopted(&self) -> bool {
+        matches!(self.process, BackendProcess::Adopted(_))
+    }
+
+    /// Address for reaching the backend from this machine
+    pub fn local_addr(&self) -> SocketAddr {
+        SocketAddr::new(self.host, self.port)
+    }
+
     /// Stop the backend server
-    pub async fn stop(mut self) -> Result<()> {
-        // Try graceful shutdown first
-        #[cfg(unix)]
-        {
-            use tokio::process::Command;
-            if let Some(pid) = self.child.id() {
-                let _ = Command::new("kill")
-                    .args(["-TERM", &pid.to_string()])
-                    .status()
-                    .await;
+    pub async fn stop(self) -> Result<()> {
+        match self.process {
+            BackendProcess::Spawned(mut child) => {
+                // Try graceful shutdown first
+                if let Some(pid) = child.id() {
+                    request_shutdown(pid).await;
+                }
+
+                // Wait a bit for graceful shutdown
+                tokio::time::sleep(GRACEFUL_SHUTDOWN).await;
+
+                // Force kill if still running
+                let _ = child.kill().await;
+                let _ = child.wait().await;
             }
+            BackendProcess::Adopted(pid) => terminate_process(pid).await,
         }
 
-        // Wait a bit for graceful shutdown
-        tokio::time::sleep(std::time::Duration::from_millis(500)).await;
+        if let Some(path) = &self.pidfile {
+            pidfile::remove(path);
+        }
+        Ok(())
+    }
+}
 
-        // Force kill if still running
-        let _ = self.child.kill().await;
-        let _ = self.child.wait().await;
+/// Reuse a backend already serving `workspace_path`, or spawn a new one
+///
+/// Pidfiles in `app_data_dir` left by earlier app instances are checked
+/// first: records of exited processes are removed, a healthy backend serving
+/// the same workspace is adopted, and hung or duplicate backends are
+/// terminated so they release the workspace lock. Healthy backends serving
+/// other workspaces may belong to another window and are left alone.
+///
+/// The new backend listens where `backend_settings` say, or on a random port
+/// if `random_port` is set. A fixed port that is taken fails with
+/// [`PortInUse`].
+pub async fn adopt_or_spawn(
+    app_data_dir: &Path,
+    workspace_path: &str,
+    backend_settings: &BackendSettings,
+    random_port: bool,
+) -> Result<BackendHandle> {
+    backend_settings.validate()?;
+    let bind_ip = backend_settings.bind_ip()?;
+    let fixed_port = backend_settings.port.filter(|_| !random_port);
+    let dir = pidfile::backends_dir(app_data_dir);
+    let workspace = Path::new(workspace_path);
+    let mut adopted = None;
 
-        Ok(())
+    for (path, record) in pidfile::read_all(&dir) {
+        if !is_backend_process(record.pid).await {
+            // Exited, or the PID now belongs to another program
+            pidfile::remove(&path);
+            continue;
+        }
+
+        let healthy = is_healthy(record.local_addr()).await;
+        // A backend started with other network settings must be restarted
+        let matches_settings =
+            record.bind == bind_ip && fixed_port.unwrap_or(record.port) == record.port;
+        if healthy && matches_settings && record.serves(workspace) && adopted.is_none() {
+            adopted = Some(BackendHandle {
+                process: BackendProcess::Adopted(record.pid),
+                port: record.port,
+                host: settings::local_ip(record.bind),
+                pidfile: Some(path),
+                logs: BackendLogs::default(),
+            });
+        } else if !healthy || record.serves(workspace) {
+            terminate_process(record.pid).await;
+            pidfile::remove(&path);
+        }
+    }
+
+    if let Some(handle) = adopted {
+        return Ok(handle);
+    }
+
+    let port = match fixed_port {
+        Some(port) => check_port_free(bind_ip, port)?,
+        None => find_available_port(bind_ip)?,
+    };
+    let mut handle = spawn_backend(workspace_path, bind_ip, port, backend_settings).await?;
+    if let Some(pid) = handle.pid() {
+        let record = BackendRecord {
+            pid,
+            port: handle.port,
+            bind: bind_ip,
+            workspace: workspace.to_path_buf(),
+        };
+        match pidfile::write(&dir, &record) {
+            Ok(path) => handle.pidfile = Some(path),
+            // The backend works without it; only orphan cleanup is lost
+            Err(e) => eprintln!("Failed to record backend process: {e}"),
+        }
+    }
+    Ok(handle)
+}
+
+/// Check that a backend answers `GET /health` at `addr`
+pub async fn is_healthy(addr: SocketAddr) -> bool {
+    tokio::time::timeout(HEALTH_TIMEOUT, health_check(addr))
+        .await
+        .unwrap_or(false)
+}
+
+async fn health_check(addr: SocketAddr) -> bool {
+    let Ok(mut stream) = TcpStream::connect(addr).await else {
+        return false;
+    };
+
+    // HTTP/1.0 closes the connection after the response
+    if stream
+        .write_all(b"GET /health HTTP/1.0\r\nHost: 127.0.0.1\r\n\r\n")
+        .await
+        .is_err()
+    {
+        return false;
+    }
+    let mut response = String::new();
+    if stream.read_to_string(&mut response).await.is_err() {
+        return false;
+    }
+
+    let status_ok = response
+        .lines()
+        .next()
+        .is_some_and(|status| status.split_whitespace().nth(1) == Some("200"));
+    status_ok && response.contains("\"healthy\"")
+}
+
+/// Whether `pid` is a running backend process
+async fn is_backend_process(pid: u32) -> bool {
+    #[cfg(unix)]
+    let output = Command::new("ps")
+        .args(["-p", &pid.to_string(), "-o", "comm="])
+        .output()
+        .await;
+    #[cfg(windows)]
+    let output = Command::new("tasklist")
+        .args(["/FI", &format!("PID eq {pid}"), "/FO", "CSV", "/NH"])
+        .output()
+        .await;
+
+    output.is_ok_and(|output| {
+        output.status.success()
+            && String::from_utf8_lossy(&output.stdout).contains(BACKEND_PROCESS_NAME)
+    })
+}
+
+/// Ask a process to shut down gracefully
+async fn request_shutdown(pid: u32) {
+    #[cfg(unix)]
+    let _ = Command::new("kill")
+        .args(["-TERM", &pid.to_string()])
+        .status()
+        .await;
+    #[cfg(windows)]
+    let _ = Command::new("taskkill")
+        .args(["/PID", &pid.to_string()])
+        .status()
+        .await;
+}
+
+/// Stop a backend that is not a child of this app
+async fn terminate_process(pid: u32) {
+    request_shutdown(pid).await;
+    tokio::time::sleep(GRACEFUL_SHUTDOWN).await;
+
+    if is_backend_process(pid).await {
+        #[cfg(unix)]
+        let _ = Command::new("kill")
+            .args(["-KILL", &pid.to_string()])
+            .status()
+            .await;
+        #[cfg(windows)]
+        let _ = Command::new("taskkill")
+            .args(["/PID", &pid.to_string(), "/F"])
+            .status()
+            .await;
     }
 }
 
 /// Find an available port for the backend
-fn find_available_port() -> Result<u16> {
+fn find_available_port(bind_ip: IpAddr) -> Result<u16> {
     // Try to bind to port 0 to get an available port
     let listener =
-        std::net::TcpListener::bind("127.0.0.1:0").context("Failed to find available port")?;
+        std::net::TcpListener::bind((bind_ip, 0)).context("Failed to find available port")?;
     let port = listener.local_addr()?.port();
     drop(listener);
     Ok(port)
 }
 
+/// Check that the configured fixed port can be bound
+fn check_port_free(bind_ip: IpAddr, port: u16) -> Result<u16> {
+    match std::net::TcpListener::bind((bind_ip, port)) {
+        Ok(_) => Ok(port),
+        Err(e) if e.kind() == std::io::ErrorKind::AddrInUse => Err(PortInUse(port).into()),
+        Err(e) => Err(e).context(format!("Failed to bind to {bind_ip} port {port}")),
+    }
+}
+
 /// Spawn the backend server as a child process
 ///
+/// Prefer [`adopt_or_spawn`], which also records the process so it can be
+/// cleaned up after a crash.
+///
 /// This looks for the `lazyqmk-web` binary in several locations:
 /// 1. Bundled with the app (in Resources on macOS, etc.)
 /// 2. In PATH
 /// 3. Built locally in target/release or target/debug
-pub async fn spawn_backend(workspace_path: &str) -> Result<BackendHandle> {
-    let port = find_available_port()?;
-
+async fn spawn_backend(
+    workspace_path: &str,
+    bind_ip: IpAddr,
+    port: u16,
+    backend_settings: &BackendSettings,
+) -> Result<BackendHandle> {
     // Find the backend binary
     let binary_path = find_backend_binary()?;
 
     // Spawn the backend process
-    let mut child = Command::new(&binary_path)
+    let mut command = Command::new(&binary_path);
+    command
         .args([
             "--host",
-            "127.0.0.1",
+            &bind_ip.to_string(),
             "--port",
             &port.to_string(),
             "--workspace",
             workspace_path,
         ])
         .stdout(Stdio::piped())
-        .stderr(Stdio::piped())
-        .spawn()
-        .context(format!(
-            "Failed to spawn backend process: {}",
-            binary_path.display()
-        ))?;
+        .stderr(Stdio::piped());
+    if let Some(token) = backend_settings.token() {
+        command.env(AUTH_TOKEN_ENV, token);
+    }
+    if backend_settings.redact_logs {
+        command.arg("--redact-logs");
+    }
+    let mut child = command.spawn().context(format!(
+        "Failed to spawn backend process: {}",
+        binary_path.display()
+    ))?;
+
+    let logs = BackendLogs::default();
+    if let Some(stdout) = child.stdout.take() {
+        logs.drain(stdout);
+    }
+    if let Some(stderr) = child.stderr.take() {
+        logs.drain(stderr);
+    }
 
     // Wait for the backend to be ready
-    wait_for_backend_ready(&mut child, port).await?;
+    let host = settings::local_ip(bind_ip);
+    wait_for_backend_ready(&mut child, SocketAddr::new(host, port)).await?;
 
-    Ok(BackendHandle { child, port })
+    Ok(BackendHandle {
+        process: BackendProcess::Spawned(child),
+        port,
+        host,
+        pidfile: None,
+        logs,
+    })
 }
 
 /// Find the backend binary in various locations
@@ -157,50 +420,21 @@ fn find_backend_binary() -> Result<std::path::PathBuf> {
 }
 
 /// Wait for the backend to be ready to accept connections
-async fn wait_for_backend_ready(child: &mut Child, port: u16) -> Result<()> {
-    let stderr = child.stderr.take().context("No stderr available")?;
-    let mut reader = BufReader::new(stderr).lines();
-
-    let timeout = std::time::Duration::from_secs(10);
+async fn wait_for_backend_ready(child: &mut Child, addr: SocketAddr) -> Result<()> {
+    let timeout = Duration::from_secs(10);
     let start = std::time::Instant::now();
 
-    // Read stderr looking for startup message
     while start.elapsed() < timeout {
-        tokio::select! {
-            line = reader.next_line() => {
-                match line {
-                    Ok(Some(line)) => {
-                        // Look for the "Starting" message
-                        if line.contains("Starting") || line.contains(&format!(":{port}")) {
-                            // Give it a moment to actually bind
-                            tokio::time::sleep(std::time::Duration::from_millis(100)).await;
-                            return Ok(());
-                        }
-                    }
-                    Ok(None) => break,
-                    Err(_) => break,
-                }
-            }
-            _ = tokio::time::sleep(std::time::Duration::from_millis(100)) => {
-                // Check if process is still running
-                if child.try_wait().ok().flatten().is_some() {
-                    anyhow::bail!("Backend process exited unexpectedly");
-                }
-
-                // Try to connect to see if it's ready
-                if tokio::net::TcpStream::connect(format!("127.0.0.1:{port}")).await.is_ok() {
-                    return Ok(());
-                }
-            }
+        // Check if process is still running
+        if child.try_wait().ok().flatten().is_some() {
+            anyhow::bail!("Backend process exited unexpectedly");
         }
-    }
 
-    // Final check - try to connect
-    if tokio::net::TcpStream::connect(format!("127.0.0.1:{port}"))
-        .await
-        .is_ok()
-    {
-        return Ok(());
+        // Try to connect to see if it's ready
+        if TcpStream::connect(addr).await.is_ok() {
+            return Ok(());
+        }
+        tokio::time::sleep(Duration::from_millis(100)).await;
     }
 
     anyhow::bail!("Backend failed to start within timeout")
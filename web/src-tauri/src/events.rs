@@ -0,0 +1,119 @@
+//! Forwarding of backend workspace events to the desktop window.
+//!
+//! The backend streams layout file changes as Server-Sent Events from
+//! `GET /api/events`. This module subscribes to that stream and re-emits each
+//! event (`layout-added`, `layout-changed`, `layout-removed`,
+//! `build-finished`) as a Tauri event with the same name and JSON payload.
+//! A `build-finished` event flagged with `notify` (the backend's
+//! `ui.build_notifications` setting) also shows a native notification.
+
+use anyhow::{Context, Result};
+use std::net::SocketAddr;
+use tauri::{AppHandle, Emitter};
+use tauri_plugin_notification::NotificationExt;
+use tokio::io::{AsyncBufReadExt, AsyncWriteExt, BufReader};
+use tokio::net::TcpStream;
+use tokio::task::JoinHandle;
+
+/// Delay before reconnecting after the event stream drops.
+const RECONNECT_DELAY: std::time::Duration = std::time::Duration::from_secs(1);
+
+/// Spawns a task that forwards backend workspace events to the app's windows.
+///
+/// The task reconnects if the stream drops and runs until aborted.
+pub fn forward_workspace_events(
+    app: AppHandle,
+    addr: SocketAddr,
+    auth_token: Option<String>,
+) -> JoinHandle<()> {
+    tokio::spawn(async move {
+        loop {
+            if let Err(e) = stream_events(&app, addr, auth_token.as_deref()).await {
+                eprintln!("Workspace event stream error: {e}");
+            }
+            tokio::time::sleep(RECONNECT_DELAY).await;
+        }
+    })
+}
+
+/// Reads the event stream until it closes, emitting each event.
+async fn stream_events(app: &AppHandle, addr: SocketAddr, auth_token: Option<&str>) -> Result<()> {
+    let mut stream = TcpStream::connect(addr)
+        .await
+        .context("Failed to connect to backend event stream")?;
+
+    // HTTP/1.0 keeps the body unchunked, so it can be read line by line
+    let authorization = auth_token
+        .map(|token| format!("Authorization: Bearer {token}\r\n"))
+        .unwrap_or_default();
+    let request = format!(
+        "GET /api/events HTTP/1.0\r\nHost: {addr}\r\nAccept: text/event-stream\r\n{authorization}\r\n"
+    );
+    stream.write_all(request.as_bytes()).await?;
+
+    let mut lines = BufReader::new(stream).lines();
+
+    // Skip response headers
+    while let Some(line) = lines.next_line().await? {
+        if line.is_empty() {
+            break;
+        }
+    }
+
+    let mut event_name = String::new();
+    let mut data = String::new();
+    while let Some(line) = lines.next_line().await? {
+        if line.is_empty() {
+            // Blank line terminates an event
+            if !event_name.is_empty() {
+                if let Ok(payload) = serde_json::from_str::<serde_json::Value>(&data) {
+                    if event_name == "build-finished" {
+                        notify_build_finished(app, &payload);
+                    }
+                    let _ = app.emit(&event_name, payload);
+                }
+            }
+            event_name.clear();
+            data.clear();
+        } else if let Some(name) = line.strip_prefix("event:") {
+            event_name = name.trim().to_string();
+        } else if let Some(value) = line.strip_prefix("data:") {
+            data.push_str(value.trim_start());
+        }
+        // Comments (keep-alive) and other fields are ignored
+    }
+
+    Ok(())
+}
+
+/// Shows a native notification for a `build-finished` event, if the backend
+/// flagged it for one.
+fn notify_build_finished(app: &AppHandle, payload: &serde_json::Value) {
+    if payload["notify"].as_bool() != Some(true) {
+        return;
+    }
+    let Some((title, body)) = build_notification_text(&payload["build"]) else {
+        return;
+    };
+    if let Err(e) = app.notification().builder().title(title).body(body).show() {
+        eprintln!("Failed to show build notification: {e}");
+    }
+}
+
+/// Title and body for a build outcome, e.g. ("Build failed",
+/// "crkbd:default failed after 1m 05s").
+fn build_notification_text(build: &serde_json::Value) -> Option<(&'static str, String)> {
+    let success = build["success"].as_bool()?;
+    let target = build["target"].as_str().unwrap_or("firmware");
+    let secs = build["duration_ms"].as_u64().unwrap_or(0) / 1000;
+    let duration = if secs < 60 {
+        format!("{secs}s")
+    } else {
+        format!("{}m {:02}s", secs / 60, secs % 60)
+    };
+    Some(if success {
+        ("Build succeeded", format!("{target} built in {duration}"))
+    } else {
+        ("Build failed", format!("{target} failed after {duration}"))
+    })
+}
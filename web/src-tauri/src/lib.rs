@@ -10,8 +10,16 @@
 )]
 
 mod backend;
+mod events;
+mod logs;
+mod menu;
+mod pidfile;
+mod recent;
+mod settings;
 
-#[cfg(debug_assertions)]
+use recent::RecentLayout;
+use serde::Serialize;
+use settings::BackendSettings;
 use tauri::Manager;
 use tokio::sync::Mutex;
 
@@ -19,36 +27,101 @@ use tokio::sync::Mutex;
 pub struct AppState {
     /// Handle to the spawned backend process (if running)
     backend_handle: Mutex<Option<backend::BackendHandle>>,
+    /// Task forwarding backend workspace events to the window
+    events_task: Mutex<Option<tokio::task::JoinHandle<()>>>,
 }
 
 impl Default for AppState {
     fn default() -> Self {
         Self {
             backend_handle: Mutex::new(None),
+            events_task: Mutex::new(None),
         }
     }
 }
 
+/// Why the backend could not be started
+///
+/// Serialized as `{ "kind": "port-in-use", "port": 8080, "message": "..." }`
+/// so the frontend can offer to retry on a random port.
+#[derive(Debug, Serialize)]
+#[serde(tag = "kind", rename_all = "kebab-case")]
+enum StartBackendError {
+    /// The configured fixed port is taken
+    PortInUse { port: u16, message: String },
+    /// Any other failure
+    Failed { message: String },
+}
+
+impl From<anyhow::Error> for StartBackendError {
+    fn from(error: anyhow::Error) -> Self {
+        match error.downcast_ref::<backend::PortInUse>() {
+            Some(backend::PortInUse(port)) => Self::PortInUse {
+                port: *port,
+                message: format!(
+                    "{error}. Free the port, choose another one in the settings, \
+                     or start on a random port instead."
+                ),
+            },
+            None => Self::Failed {
+                message: format!("{error:#}"),
+            },
+        }
+    }
+}
+
+/// Load the backend settings from the app config directory
+fn load_backend_settings(app: &tauri::AppHandle) -> Result<BackendSettings, String> {
+    let config_dir = app.path().app_config_dir().map_err(|e| e.to_string())?;
+    Ok(BackendSettings::load(&config_dir))
+}
+
 /// Start the backend server
+///
+/// With `random_port`, a configured fixed port is ignored for this start
+/// (the fall-back offered when the port is taken).
 #[tauri::command]
 async fn start_backend(
+    app: tauri::AppHandle,
     state: tauri::State<'_, AppState>,
     workspace_path: String,
-) -> Result<String, String> {
+    random_port: Option<bool>,
+) -> Result<String, StartBackendError> {
     let mut handle = state.backend_handle.lock().await;
 
     if handle.is_some() {
         return Ok("Backend already running".to_string());
     }
 
-    let new_handle = backend::spawn_backend(&workspace_path)
-        .await
-        .map_err(|e| e.to_string())?;
+    let failed = |message: String| StartBackendError::Failed { message };
+    let backend_settings = load_backend_settings(&app).map_err(failed)?;
+    let app_data_dir = app
+        .path()
+        .app_data_dir()
+        .map_err(|e| failed(e.to_string()))?;
+    let new_handle = backend::adopt_or_spawn(
+        &app_data_dir,
+        &workspace_path,
+        &backend_settings,
+        random_port.unwrap_or(false),
+    )
+    .await?;
 
     let port = new_handle.port;
+    let adopted = new_handle.is_adopted();
+    let events_task = events::forward_workspace_events(
+        app,
+        new_handle.local_addr(),
+        backend_settings.token().map(str::to_string),
+    );
     *handle = Some(new_handle);
+    *state.events_task.lock().await = Some(events_task);
 
-    Ok(format!("Backend started on port {port}"))
+    if adopted {
+        Ok(format!("Reusing backend already running on port {port}"))
+    } else {
+        Ok(format!("Backend started on port {port}"))
+    }
 }
 
 /// Stop the backend server
@@ -56,6 +129,10 @@ async fn start_backend(
 async fn stop_backend(state: tauri::State<'_, AppState>) -> Result<String, String> {
     let mut handle = state.backend_handle.lock().await;
 
+    if let Some(task) = state.events_task.lock().await.take() {
+        task.abort();
+    }
+
     if let Some(backend) = handle.take() {
         backend.stop().await.map_err(|e| e.to_string())?;
         Ok("Backend stopped".to_string())
@@ -64,38 +141,124 @@ async fn stop_backend(state: tauri::State<'_, AppState>) -> Result<String, Strin
     }
 }
 
-/// Check if backend is running
+/// Check if backend is running and answering health checks
+///
+/// Covers both spawned backends and ones adopted from a previous session.
 #[tauri::command]
 async fn is_backend_running(state: tauri::State<'_, AppState>) -> Result<bool, String> {
     let handle = state.backend_handle.lock().await;
-    Ok(handle.is_some())
+    match handle.as_ref() {
+        Some(backend) => Ok(backend::is_healthy(backend.local_addr()).await),
+        None => Ok(false),
+    }
 }
 
 /// Get the backend URL
+///
+/// When the backend is bound to a non-loopback address, this is the URL
+/// other devices use: the configured public URL, or the machine's address.
+#[tauri::command]
+async fn get_backend_url(
+    app: tauri::AppHandle,
+    state: tauri::State<'_, AppState>,
+) -> Result<Option<String>, String> {
+    let handle = state.backend_handle.lock().await;
+    let Some(backend) = handle.as_ref() else {
+        return Ok(None);
+    };
+    let backend_settings = load_backend_settings(&app)?;
+    if backend_settings.is_exposed() {
+        Ok(Some(backend_settings.external_url(backend.port)))
+    } else {
+        Ok(Some(format!("http://{}", backend.local_addr())))
+    }
+}
+
+/// Get the most recent lines the backend logged, oldest first
+///
+/// Empty when no backend is running or it was adopted from a previous session.
 #[tauri::command]
-async fn get_backend_url(state: tauri::State<'_, AppState>) -> Result<Option<String>, String> {
+async fn get_backend_logs(state: tauri::State<'_, AppState>) -> Result<Vec<String>, String> {
     let handle = state.backend_handle.lock().await;
     Ok(handle
         .as_ref()
-        .map(|b| format!("http://127.0.0.1:{}", b.port)))
+        .map(|backend| backend.logs.snapshot())
+        .unwrap_or_default())
+}
+
+/// Get the persisted backend settings
+#[tauri::command]
+async fn get_backend_settings(app: tauri::AppHandle) -> Result<BackendSettings, String> {
+    load_backend_settings(&app)
+}
+
+/// Validate and persist backend settings; they apply on the next start
+#[tauri::command]
+async fn set_backend_settings(
+    app: tauri::AppHandle,
+    settings: BackendSettings,
+) -> Result<(), String> {
+    let config_dir = app.path().app_config_dir().map_err(|e| e.to_string())?;
+    settings.save(&config_dir).map_err(|e| e.to_string())
+}
+
+/// Get the recently opened layouts, newest first
+#[tauri::command]
+async fn get_recent_layouts(app: tauri::AppHandle) -> Result<Vec<RecentLayout>, String> {
+    let config_dir = app.path().app_config_dir().map_err(|e| e.to_string())?;
+    Ok(recent::load(&config_dir))
+}
+
+/// Record that a layout was opened and update File > Open Recent
+#[tauri::command]
+async fn add_recent_layout(
+    app: tauri::AppHandle,
+    filename: String,
+    name: String,
+) -> Result<Vec<RecentLayout>, String> {
+    let config_dir = app.path().app_config_dir().map_err(|e| e.to_string())?;
+    let recent =
+        recent::add(&config_dir, RecentLayout { filename, name }).map_err(|e| e.to_string())?;
+    menu::refresh(&app, &recent).map_err(|e| e.to_string())?;
+    Ok(recent)
+}
+
+/// Forget the recently opened layouts and empty File > Open Recent
+#[tauri::command]
+async fn clear_recent_layouts(app: tauri::AppHandle) -> Result<(), String> {
+    let config_dir = app.path().app_config_dir().map_err(|e| e.to_string())?;
+    recent::clear(&config_dir).map_err(|e| e.to_string())?;
+    menu::refresh(&app, &[]).map_err(|e| e.to_string())
 }
 
 #[cfg_attr(mobile, tauri::mobile_entry_point)]
 pub fn run() {
     tauri::Builder::default()
         .plugin(tauri_plugin_shell::init())
+        .plugin(tauri_plugin_notification::init())
         .manage(AppState::default())
         .invoke_handler(tauri::generate_handler![
             start_backend,
             stop_backend,
             is_backend_running,
             get_backend_url,
+            get_backend_logs,
+            get_backend_settings,
+            set_backend_settings,
+            get_recent_layouts,
+            add_recent_layout,
+            clear_recent_layouts,
         ])
-        .setup(|_app| {
+        .on_menu_event(menu::handle_event)
+        .setup(|app| {
+            let config_dir = app.path().app_config_dir()?;
+            let handle = app.handle();
+            handle.set_menu(menu::build(handle, &recent::load(&config_dir))?)?;
+
             // Open devtools in debug builds
             #[cfg(debug_assertions)]
             {
-                let window = _app.get_webview_window("main").unwrap();
+                let window = app.get_webview_window("main").unwrap();
                 window.open_devtools();
             }
             Ok(())
@@ -0,0 +1,68 @@
+//! Recent backend output for the diagnostics panel.
+//!
+//! The backend's stdout and stderr are drained into a fixed-size ring buffer.
+//! Draining also keeps the pipes from filling up, which would otherwise block
+//! the backend once it logs every request.
+
+use std::collections::VecDeque;
+use std::sync::{Arc, Mutex};
+use tokio::io::{AsyncBufReadExt, AsyncRead, BufReader};
+
+/// Number of lines kept; older lines are dropped
+const CAPACITY: usize = 500;
+
+/// Shared ring buffer of the most recent backend log lines
+#[derive(Clone, Default)]
+pub struct BackendLogs {
+    lines: Arc<Mutex<VecDeque<String>>>,
+}
+
+impl BackendLogs {
+    /// Append a line, dropping the oldest one when full
+    pub fn push(&self, line: &str) {
+        let mut lines = self.lines.lock().unwrap_or_else(|e| e.into_inner());
+        if lines.len() == CAPACITY {
+            lines.pop_front();
+        }
+        lines.push_back(strip_ansi(line));
+    }
+
+    /// The buffered lines, oldest first
+    pub fn snapshot(&self) -> Vec<String> {
+        let lines = self.lines.lock().unwrap_or_else(|e| e.into_inner());
+        lines.iter().cloned().collect()
+    }
+
+    /// Read `stream` line by line into the buffer until it closes
+    pub fn drain<R>(&self, stream: R)
+    where
+        R: AsyncRead + Unpin + Send + 'static,
+    {
+        let logs = self.clone();
+        tokio::spawn(async move {
+            let mut reader = BufReader::new(stream).lines();
+            while let Ok(Some(line)) = reader.next_line().await {
+                logs.push(&line);
+            }
+        });
+    }
+}
+
+/// Remove terminal color codes (`ESC [ ... m`) from a log line
+fn strip_ansi(line: &str) -> String {
+    let mut out = String::with_capacity(line.len());
+    let mut chars = line.chars();
+    while let Some(c) = chars.next() {
+        if c == '\u{1b}' {
+            // Skip the escape sequence up to and including its final letter
+            for c in chars.by_ref() {
+                if c.is_ascii_alphabetic() {
+                    break;
+                }
+            }
+        } else {
+            out.push(c);
+        }
+    }
+    out
+}
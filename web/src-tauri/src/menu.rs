@@ -0,0 +1,182 @@
+//! Native menu bar.
+//!
+//! Items that act on the open layout emit a Tauri event the frontend handles:
+//! `menu:open-layout`, `menu:save`, `menu:save-as`, `menu:undo`, `menu:redo`,
+//! `menu:about`, and `menu:open-recent` with the layout filename as payload.
+//! Clipboard items are native so they reach whatever has focus in the
+//! webview; window and view items are handled here. The File > Open Recent
+//! submenu is rebuilt whenever the recent list changes.
+
+use tauri::menu::{Menu, MenuBuilder, MenuEvent, MenuItemBuilder, Submenu, SubmenuBuilder};
+use tauri::{AppHandle, Emitter, Manager};
+
+use crate::recent::{self, RecentLayout};
+
+/// ID prefix of the Open Recent items; the rest is the layout filename
+const RECENT_PREFIX: &str = "recent:";
+
+/// ID of the Clear Recent item
+const CLEAR_RECENT: &str = "clear-recent";
+
+/// Items forwarded to the frontend as `menu:<id>` events
+const FRONTEND_ITEMS: &[&str] = &["open-layout", "save", "save-as", "undo", "redo", "about"];
+
+/// Builds the menu bar with `recent` in File > Open Recent
+pub fn build(app: &AppHandle, recent: &[RecentLayout]) -> tauri::Result<Menu<tauri::Wry>> {
+    let file = SubmenuBuilder::new(app, "File")
+        .item(
+            &MenuItemBuilder::with_id("open-layout", "Open Layout…")
+                .accelerator("CmdOrCtrl+O")
+                .build(app)?,
+        )
+        .item(&recent_submenu(app, recent)?)
+        .separator()
+        .item(
+            &MenuItemBuilder::with_id("save", "Save")
+                .accelerator("CmdOrCtrl+S")
+                .build(app)?,
+        )
+        .item(
+            &MenuItemBuilder::with_id("save-as", "Save As…")
+                .accelerator("CmdOrCtrl+Shift+S")
+                .build(app)?,
+        )
+        .separator()
+        .item(
+            &MenuItemBuilder::with_id("close-window", "Close Window")
+                .accelerator("CmdOrCtrl+W")
+                .build(app)?,
+        )
+        .item(
+            &MenuItemBuilder::with_id("quit", "Quit")
+                .accelerator("CmdOrCtrl+Q")
+                .build(app)?,
+        )
+        .build()?;
+
+    let edit = SubmenuBuilder::new(app, "Edit")
+        .item(
+            &MenuItemBuilder::with_id("undo", "Undo")
+                .accelerator("CmdOrCtrl+Z")
+                .build(app)?,
+        )
+        .item(
+            &MenuItemBuilder::with_id("redo", "Redo")
+                .accelerator("CmdOrCtrl+Shift+Z")
+                .build(app)?,
+        )
+        .separator()
+        .cut()
+        .copy()
+        .paste()
+        .select_all()
+        .build()?;
+
+    let mut view = SubmenuBuilder::new(app, "View").item(
+        &MenuItemBuilder::with_id("reload", "Reload")
+            .accelerator("CmdOrCtrl+R")
+            .build(app)?,
+    );
+    if cfg!(debug_assertions) {
+        view = view.item(
+            &MenuItemBuilder::with_id("toggle-devtools", "Toggle Developer Tools")
+                .accelerator("Alt+CmdOrCtrl+I")
+                .build(app)?,
+        );
+    }
+    let view = view.build()?;
+
+    let help = SubmenuBuilder::new(app, "Help")
+        .item(&MenuItemBuilder::with_id("about", "About LazyQMK").build(app)?)
+        .build()?;
+
+    let menu = MenuBuilder::new(app);
+    // macOS puts the first submenu under the application name
+    #[cfg(target_os = "macos")]
+    let menu = menu.item(
+        &SubmenuBuilder::new(app, "LazyQMK")
+            .item(&MenuItemBuilder::with_id("about", "About LazyQMK").build(app)?)
+            .separator()
+            .services()
+            .separator()
+            .hide()
+            .hide_others()
+            .show_all()
+            .build()?,
+    );
+    menu.items(&[&file, &edit, &view, &help]).build()
+}
+
+/// File > Open Recent, newest first, with an item to clear the list
+fn recent_submenu(app: &AppHandle, recent: &[RecentLayout]) -> tauri::Result<Submenu<tauri::Wry>> {
+    let mut submenu = SubmenuBuilder::new(app, "Open Recent");
+    if recent.is_empty() {
+        submenu = submenu.item(
+            &MenuItemBuilder::new("No Recent Layouts")
+                .enabled(false)
+                .build(app)?,
+        );
+    } else {
+        for layout in recent {
+            let id = format!("{RECENT_PREFIX}{}", layout.filename);
+            submenu = submenu.item(&MenuItemBuilder::with_id(id, &layout.name).build(app)?);
+        }
+        submenu = submenu
+            .separator()
+            .item(&MenuItemBuilder::with_id(CLEAR_RECENT, "Clear Recent").build(app)?);
+    }
+    submenu.build()
+}
+
+/// Rebuilds the menu bar after the recent list changed
+pub fn refresh(app: &AppHandle, recent: &[RecentLayout]) -> tauri::Result<()> {
+    app.set_menu(build(app, recent)?)?;
+    Ok(())
+}
+
+/// Handles a click on a menu item
+pub fn handle_event(app: &AppHandle, event: MenuEvent) {
+    let id = event.id().as_ref();
+    let window = app.get_webview_window("main");
+
+    let result = if FRONTEND_ITEMS.contains(&id) {
+        app.emit(&format!("menu:{id}"), ())
+    } else if let Some(filename) = id.strip_prefix(RECENT_PREFIX) {
+        app.emit("menu:open-recent", filename)
+    } else {
+        match id {
+            CLEAR_RECENT => clear_recent(app),
+            "close-window" => window.map_or(Ok(()), |window| window.close()),
+            "quit" => {
+                app.exit(0);
+                Ok(())
+            }
+            "reload" => window.map_or(Ok(()), |window| window.eval("window.location.reload()")),
+            "toggle-devtools" => {
+                if let Some(window) = window {
+                    if window.is_devtools_open() {
+                        window.close_devtools();
+                    } else {
+                        window.open_devtools();
+                    }
+                }
+                Ok(())
+            }
+            _ => Ok(()),
+        }
+    };
+
+    if let Err(e) = result {
+        eprintln!("Menu action '{id}' failed: {e}");
+    }
+}
+
+/// Forgets the recent layouts and tells the frontend to do the same
+fn clear_recent(app: &AppHandle) -> tauri::Result<()> {
+    let config_dir = app.path().app_config_dir()?;
+    if let Err(e) = recent::clear(&config_dir) {
+        eprintln!("Failed to clear recent layouts: {e}");
+    }
+    refresh(app, &[])?;
+    app.emit("menu:recent-cleared", ())
+}
@@ -0,0 +1,89 @@
+//! Records of spawned backend processes.
+//!
+//! Each spawned `lazyqmk-web` gets a pidfile (`backends/<pid>.json` in the
+//! app data directory) holding its port and workspace. If the app crashes,
+//! the next launch finds the records and adopts or terminates the orphans
+//! instead of spawning another backend next to them.
+
+use anyhow::{Context, Result};
+use serde::{Deserialize, Serialize};
+use std::fs;
+use std::net::{IpAddr, Ipv4Addr, SocketAddr};
+use std::path::{Path, PathBuf};
+
+use crate::settings;
+
+/// A backend process spawned by this app.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct BackendRecord {
+    /// Process ID
+    pub pid: u32,
+    /// Port the backend listens on
+    pub port: u16,
+    /// Address the backend is bound to
+    #[serde(default = "loopback")]
+    pub bind: IpAddr,
+    /// Workspace the backend serves
+    pub workspace: PathBuf,
+}
+
+impl BackendRecord {
+    /// Whether this backend serves `workspace`.
+    pub fn serves(&self, workspace: &Path) -> bool {
+        canonical(&self.workspace) == canonical(workspace)
+    }
+
+    /// Address for reaching the backend from this machine
+    pub fn local_addr(&self) -> SocketAddr {
+        SocketAddr::new(settings::local_ip(self.bind), self.port)
+    }
+}
+
+const fn loopback() -> IpAddr {
+    IpAddr::V4(Ipv4Addr::LOCALHOST)
+}
+
+/// Directory holding the pidfiles.
+pub fn backends_dir(app_data_dir: &Path) -> PathBuf {
+    app_data_dir.join("backends")
+}
+
+/// Writes the pidfile for `record`, returning its path.
+pub fn write(dir: &Path, record: &BackendRecord) -> Result<PathBuf> {
+    fs::create_dir_all(dir).context("Failed to create backend pidfile directory")?;
+    let path = dir.join(format!("{}.json", record.pid));
+    let json = serde_json::to_string_pretty(record)?;
+    fs::write(&path, json).context(format!("Failed to write pidfile: {}", path.display()))?;
+    Ok(path)
+}
+
+/// Reads every pidfile in `dir`; unreadable files are removed.
+pub fn read_all(dir: &Path) -> Vec<(PathBuf, BackendRecord)> {
+    let Ok(entries) = fs::read_dir(dir) else {
+        return Vec::new();
+    };
+
+    let mut records = Vec::new();
+    for path in entries.flatten().map(|entry| entry.path()) {
+        if path.extension().and_then(|ext| ext.to_str()) != Some("json") {
+            continue;
+        }
+        let record = fs::read_to_string(&path)
+            .ok()
+            .and_then(|content| serde_json::from_str::<BackendRecord>(&content).ok());
+        match record {
+            Some(record) => records.push((path, record)),
+            None => remove(&path),
+        }
+    }
+    records
+}
+
+/// Removes a pidfile, ignoring files that are already gone.
+pub fn remove(path: &Path) {
+    let _ = fs::remove_file(path);
+}
+
+fn canonical(path: &Path) -> PathBuf {
+    path.canonicalize().unwrap_or_else(|_| path.to_path_buf())
+}
@@ -0,0 +1,58 @@
+//! Recently opened layouts, listed in the File > Open Recent menu.
+//!
+//! The frontend reports each layout it opens; the list is kept newest first
+//! in the app config directory so the menu survives restarts.
+
+use anyhow::{Context, Result};
+use serde::{Deserialize, Serialize};
+use std::fs;
+use std::path::{Path, PathBuf};
+
+/// File holding the list, in the app config directory
+const RECENT_FILE: &str = "recent_layouts.json";
+
+/// Most layouts kept in the list
+const MAX_RECENT: usize = 10;
+
+/// A layout the user opened
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct RecentLayout {
+    /// Layout filename in the workspace, as used in `/layouts/{filename}`
+    pub filename: String,
+    /// Display name from the layout's metadata
+    pub name: String,
+}
+
+/// Load the list, newest first; empty if none is saved
+pub fn load(config_dir: &Path) -> Vec<RecentLayout> {
+    fs::read_to_string(recent_path(config_dir))
+        .ok()
+        .and_then(|content| serde_json::from_str(&content).ok())
+        .unwrap_or_default()
+}
+
+/// Move a layout to the front of the list and save it
+pub fn add(config_dir: &Path, layout: RecentLayout) -> Result<Vec<RecentLayout>> {
+    let mut recent = load(config_dir);
+    recent.retain(|item| item.filename != layout.filename);
+    recent.insert(0, layout);
+    recent.truncate(MAX_RECENT);
+    save(config_dir, &recent)?;
+    Ok(recent)
+}
+
+/// Forget every layout
+pub fn clear(config_dir: &Path) -> Result<()> {
+    save(config_dir, &[])
+}
+
+fn save(config_dir: &Path, recent: &[RecentLayout]) -> Result<()> {
+    fs::create_dir_all(config_dir).context("Failed to create app config directory")?;
+    let json = serde_json::to_string_pretty(recent)?;
+    fs::write(recent_path(config_dir), json).context("Failed to write recent layouts")
+}
+
+fn recent_path(config_dir: &Path) -> PathBuf {
+    config_dir.join(RECENT_FILE)
+}
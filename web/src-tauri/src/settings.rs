@@ -0,0 +1,130 @@
+//! Persisted desktop settings for the backend server.
+//!
+//! By default the backend listens on a random free port on loopback, reachable
+//! only from the desktop window. Advanced users can fix the port and bind
+//! address, e.g. to put the backend behind a reverse proxy and edit from a
+//! tablet. Binding to a non-loopback address requires an access token, which
+//! the backend then demands on every API request.
+
+use anyhow::{Context, Result};
+use serde::{Deserialize, Serialize};
+use std::fs;
+use std::net::{IpAddr, Ipv4Addr, SocketAddr, UdpSocket};
+use std::path::{Path, PathBuf};
+
+/// File holding the settings, in the app config directory
+const SETTINGS_FILE: &str = "settings.json";
+
+/// How the backend server is started
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(default, rename_all = "camelCase")]
+pub struct BackendSettings {
+    /// Fixed port; a random free port when unset
+    pub port: Option<u16>,
+    /// Address to bind to; loopback when unset
+    pub bind_address: Option<String>,
+    /// Access token, required when binding to a non-loopback address
+    pub auth_token: Option<String>,
+    /// URL other devices use to reach the backend (e.g., a reverse proxy);
+    /// derived from the bind address when unset
+    pub public_url: Option<String>,
+    /// Log route templates instead of request paths, keeping layout names
+    /// out of the backend log
+    pub redact_logs: bool,
+}
+
+impl BackendSettings {
+    /// Load the settings, falling back to defaults if none are saved
+    pub fn load(config_dir: &Path) -> Self {
+        fs::read_to_string(settings_path(config_dir))
+            .ok()
+            .and_then(|content| serde_json::from_str(&content).ok())
+            .unwrap_or_default()
+    }
+
+    /// Validate and save the settings
+    pub fn save(&self, config_dir: &Path) -> Result<()> {
+        self.validate()?;
+        fs::create_dir_all(config_dir).context("Failed to create app config directory")?;
+        let json = serde_json::to_string_pretty(self)?;
+        fs::write(settings_path(config_dir), json).context("Failed to write desktop settings")
+    }
+
+    /// Check that the backend can be started with these settings
+    pub fn validate(&self) -> Result<()> {
+        let ip = self.bind_ip()?;
+        if !ip.is_loopback() && self.token().is_none() {
+            anyhow::bail!(
+                "Binding to {ip} exposes the editor to the network; set an access token first"
+            );
+        }
+        if self.port == Some(0) {
+            anyhow::bail!("Port must be between 1 and 65535");
+        }
+        if let Some(url) = &self.public_url {
+            if !url.starts_with("http://") && !url.starts_with("https://") {
+                anyhow::bail!("Public URL must start with http:// or https://");
+            }
+        }
+        Ok(())
+    }
+
+    /// Address the backend binds to
+    pub fn bind_ip(&self) -> Result<IpAddr> {
+        match self.bind_address.as_deref().map(str::trim) {
+            None | Some("") => Ok(IpAddr::V4(Ipv4Addr::LOCALHOST)),
+            Some(address) => address
+                .parse()
+                .context(format!("Invalid bind address '{address}'")),
+        }
+    }
+
+    /// The access token, if one is set
+    pub fn token(&self) -> Option<&str> {
+        self.auth_token
+            .as_deref()
+            .map(str::trim)
+            .filter(|token| !token.is_empty())
+    }
+
+    /// Whether the backend is reachable from other devices
+    pub fn is_exposed(&self) -> bool {
+        self.bind_ip().is_ok_and(|ip| !ip.is_loopback())
+    }
+
+    /// URL other devices use to reach a backend listening on `port`
+    pub fn external_url(&self, port: u16) -> String {
+        if let Some(url) = &self.public_url {
+            return url.trim_end_matches('/').to_string();
+        }
+        let ip = match self.bind_ip() {
+            Ok(ip) if ip.is_unspecified() => lan_ip().unwrap_or(ip),
+            Ok(ip) => ip,
+            Err(_) => IpAddr::V4(Ipv4Addr::LOCALHOST),
+        };
+        format!("http://{}", SocketAddr::new(ip, port))
+    }
+}
+
+/// Address for connecting to a backend bound to `bind_ip` from this machine
+pub fn local_ip(bind_ip: IpAddr) -> IpAddr {
+    match bind_ip {
+        IpAddr::V4(ip) if ip.is_unspecified() => IpAddr::V4(Ipv4Addr::LOCALHOST),
+        IpAddr::V6(ip) if ip.is_unspecified() => IpAddr::V6(std::net::Ipv6Addr::LOCALHOST),
+        ip => ip,
+    }
+}
+
+/// This machine's address on the local network.
+///
+/// Connecting a UDP socket sends nothing; it only picks the outgoing
+/// interface.
+fn lan_ip() -> Option<IpAddr> {
+    let socket = UdpSocket::bind("0.0.0.0:0").ok()?;
+    socket.connect("192.0.2.1:80").ok()?;
+    socket.local_addr().ok().map(|addr| addr.ip())
+}
+
+fn settings_path(config_dir: &Path) -> PathBuf {
+    config_dir.join(SETTINGS_FILE)
+}
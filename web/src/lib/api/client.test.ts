@@ -33,6 +33,27 @@ describe('ApiClient', () => {
 		});
 	});
 
+	describe('access token', () => {
+		it('sends the token as a bearer header and on download URLs', async () => {
+			client.setAuthToken('s3cret');
+			(global.fetch as any).mockResolvedValueOnce({
+				ok: true,
+				json: async () => ({ layouts: [] })
+			});
+
+			await client.listLayouts();
+			expect(global.fetch).toHaveBeenCalledWith(
+				'http://localhost:3000/api/layouts',
+				expect.objectContaining({
+					headers: expect.objectContaining({ Authorization: 'Bearer s3cret' })
+				})
+			);
+			expect(client.getGenerateDownloadUrl('job1')).toBe(
+				'http://localhost:3000/api/generate/jobs/job1/download?token=s3cret'
+			);
+		});
+	});
+
 	describe('listLayouts', () => {
 		it('fetches layout list', async () => {
 			const mockResponse = {
@@ -193,6 +214,21 @@ describe('ApiClient', () => {
 				})
 			);
 		});
+
+		it('confirms a keyboard mismatch when asked to', async () => {
+			(global.fetch as any).mockResolvedValueOnce({
+				ok: true,
+				json: async () => ({ job: {} })
+			});
+
+			await client.startBuild('test.md', true);
+			expect(global.fetch).toHaveBeenCalledWith(
+				'http://localhost:3000/api/build/start',
+				expect.objectContaining({
+					body: JSON.stringify({ layout_filename: 'test.md', allow_keyboard_mismatch: true })
+				})
+			);
+		});
 	});
 
 	describe('listBuildJobs', () => {
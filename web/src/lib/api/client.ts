@@ -33,15 +33,53 @@ import type {
 	CancelJobResponse,
 	BuildJob,
 	BuildArtifactsResponse,
-	RenderMetadataResponse
+	RenderMetadataResponse,
+	WorkspaceEvent,
+	WorkspaceEventType
 } from './types';
 
+const WORKSPACE_EVENT_TYPES: WorkspaceEventType[] = [
+	'layout-added',
+	'layout-changed',
+	'layout-removed'
+];
+
+const AUTH_TOKEN_STORAGE_KEY = 'lazyqmk-auth-token';
+
+/**
+ * Reads the access token for a server exposed to the network.
+ * A `?token=` in the page URL is remembered for later visits.
+ */
+function readAuthToken(): string | null {
+	if (typeof window === 'undefined') return null;
+	const fromUrl = new URLSearchParams(window.location.search).get('token');
+	if (fromUrl) {
+		window.localStorage.setItem(AUTH_TOKEN_STORAGE_KEY, fromUrl);
+		return fromUrl;
+	}
+	return window.localStorage.getItem(AUTH_TOKEN_STORAGE_KEY);
+}
+
 export class ApiClient {
 	private baseUrl: string;
+	private authToken: string | null;
 
-	constructor(baseUrl?: string) {
+	constructor(baseUrl?: string, authToken?: string | null) {
 		// Default to current origin, configurable for testing
 		this.baseUrl = baseUrl || '';
+		this.authToken = authToken ?? null;
+	}
+
+	/** Sets the access token sent with API requests (`null` to stop sending one). */
+	setAuthToken(token: string | null): void {
+		this.authToken = token;
+	}
+
+	/** Adds the access token to URLs fetched without custom headers. */
+	private withToken(url: string): string {
+		if (!this.authToken) return url;
+		const separator = url.includes('?') ? '&' : '?';
+		return `${url}${separator}token=${encodeURIComponent(this.authToken)}`;
 	}
 
 	private async request<T>(endpoint: string, options?: RequestInit): Promise<T> {
@@ -50,6 +88,7 @@ export class ApiClient {
 			...options,
 			headers: {
 				'Content-Type': 'application/json',
+				...(this.authToken ? { Authorization: `Bearer ${this.authToken}` } : {}),
 				...options?.headers
 			}
 		});
@@ -109,9 +148,13 @@ export class ApiClient {
 		return this.request<ExportResponse>(`/api/layouts/${encodeURIComponent(filename)}/export`);
 	}
 
-	async generateFirmware(filename: string): Promise<GenerateResponse> {
+	async generateFirmware(
+		filename: string,
+		allowKeyboardMismatch = false
+	): Promise<GenerateResponse> {
+		const query = allowKeyboardMismatch ? '?allow_keyboard_mismatch=true' : '';
 		return this.request<GenerateResponse>(
-			`/api/layouts/${encodeURIComponent(filename)}/generate`,
+			`/api/layouts/${encodeURIComponent(filename)}/generate${query}`,
 			{
 				method: 'POST'
 			}
@@ -163,7 +206,7 @@ export class ApiClient {
 	// Geometry Operations
 	async getGeometry(keyboard: string, layout: string): Promise<GeometryResponse> {
 		return this.request<GeometryResponse>(
-			`/api/keyboards/${encodeURIComponent(keyboard)}/geometry/${encodeURIComponent(layout)}`
+			`/api/keyboards/${encodeURIComponent(keyboard)}/geometry?variant=${encodeURIComponent(layout)}`
 		);
 	}
 
@@ -228,8 +271,14 @@ export class ApiClient {
 	}
 
 	// Build Job Operations
-	async startBuild(layoutFilename: string): Promise<StartBuildResponse> {
-		const request: StartBuildRequest = { layout_filename: layoutFilename };
+	async startBuild(
+		layoutFilename: string,
+		allowKeyboardMismatch = false
+	): Promise<StartBuildResponse> {
+		const request: StartBuildRequest = {
+			layout_filename: layoutFilename,
+			...(allowKeyboardMismatch ? { allow_keyboard_mismatch: true } : {})
+		};
 		return this.request<StartBuildResponse>('/api/build/start', {
 			method: 'POST',
 			body: JSON.stringify(request)
@@ -268,7 +317,9 @@ export class ApiClient {
 	 * @returns Full URL to download the artifact
 	 */
 	getBuildArtifactDownloadUrl(jobId: string, artifactId: string): string {
-		return `${this.baseUrl}/api/build/jobs/${encodeURIComponent(jobId)}/artifacts/${encodeURIComponent(artifactId)}/download`;
+		return this.withToken(
+			`${this.baseUrl}/api/build/jobs/${encodeURIComponent(jobId)}/artifacts/${encodeURIComponent(artifactId)}/download`
+		);
 	}
 
 	// Generate Job Operations
@@ -299,9 +350,26 @@ export class ApiClient {
 	 * @returns Full URL to download the generated zip file
 	 */
 	getGenerateDownloadUrl(jobId: string): string {
-		return `${this.baseUrl}/api/generate/jobs/${encodeURIComponent(jobId)}/download`;
+		return this.withToken(
+			`${this.baseUrl}/api/generate/jobs/${encodeURIComponent(jobId)}/download`
+		);
+	}
+
+	/**
+	 * Subscribes to layout file changes in the workspace.
+	 * @param onEvent Called for each added, changed, or removed layout
+	 * @returns Function that closes the subscription
+	 */
+	subscribeWorkspaceEvents(onEvent: (event: WorkspaceEvent) => void): () => void {
+		const source = new EventSource(this.withToken(`${this.baseUrl}/api/events`));
+		for (const type of WORKSPACE_EVENT_TYPES) {
+			source.addEventListener(type, (message) => {
+				onEvent(JSON.parse((message as MessageEvent).data) as WorkspaceEvent);
+			});
+		}
+		return () => source.close();
 	}
 }
 
 // Default instance
-export const apiClient = new ApiClient();
+export const apiClient = new ApiClient(undefined, readAuthToken());
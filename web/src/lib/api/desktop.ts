@@ -0,0 +1,119 @@
+import { invoke } from '@tauri-apps/api/core';
+import { listen, type UnlistenFn } from '@tauri-apps/api/event';
+import type { BackendSettings, StartBackendError } from './types';
+
+/** Whether the app runs inside the desktop (Tauri) shell. */
+export function isDesktop(): boolean {
+	return typeof window !== 'undefined' && '__TAURI_INTERNALS__' in window;
+}
+
+/** Whether an error from `startBackend` means the fixed port is taken. */
+export function isPortInUse(
+	error: unknown
+): error is Extract<StartBackendError, { kind: 'port-in-use' }> {
+	return (
+		typeof error === 'object' &&
+		error !== null &&
+		(error as StartBackendError).kind === 'port-in-use'
+	);
+}
+
+/**
+ * Starts (or adopts) the desktop backend for a workspace.
+ * Rejects with a `StartBackendError`; when the configured port is taken,
+ * offer `startBackend(path, { randomPort: true })` as the fall-back.
+ */
+export async function startBackend(
+	workspacePath: string,
+	options: { randomPort?: boolean } = {}
+): Promise<string> {
+	return invoke<string>('start_backend', {
+		workspacePath,
+		randomPort: options.randomPort ?? false
+	});
+}
+
+/** URL of the running backend, as other devices reach it when exposed. */
+export async function getBackendUrl(): Promise<string | null> {
+	return invoke<string | null>('get_backend_url');
+}
+
+/**
+ * Most recent lines the desktop backend logged, oldest first.
+ * Empty when the backend was adopted from a previous session.
+ */
+export async function getBackendLogs(): Promise<string[]> {
+	return invoke<string[]>('get_backend_logs');
+}
+
+export async function getBackendSettings(): Promise<BackendSettings> {
+	return invoke<BackendSettings>('get_backend_settings');
+}
+
+/** Validates and saves backend settings; they apply on the next start. */
+export async function setBackendSettings(settings: BackendSettings): Promise<void> {
+	return invoke<void>('set_backend_settings', { settings });
+}
+
+/** A layout listed in the desktop File > Open Recent menu. */
+export interface DesktopRecentLayout {
+	filename: string;
+	name: string;
+}
+
+/** Recently opened layouts, newest first. */
+export async function getRecentLayouts(): Promise<DesktopRecentLayout[]> {
+	return invoke<DesktopRecentLayout[]>('get_recent_layouts');
+}
+
+/** Records an opened layout so it appears in File > Open Recent. */
+export async function addRecentLayout(filename: string, name: string): Promise<void> {
+	await invoke('add_recent_layout', { filename, name });
+}
+
+/** Empties File > Open Recent. */
+export async function clearRecentLayouts(): Promise<void> {
+	return invoke<void>('clear_recent_layouts');
+}
+
+/** Native menu items the frontend handles, emitted as `menu:<action>`. */
+export type MenuAction =
+	| 'open-layout'
+	| 'save'
+	| 'save-as'
+	| 'undo'
+	| 'redo'
+	| 'about'
+	| 'recent-cleared';
+
+const MENU_ACTIONS: MenuAction[] = [
+	'open-layout',
+	'save',
+	'save-as',
+	'undo',
+	'redo',
+	'about',
+	'recent-cleared'
+];
+
+/**
+ * Window event carrying a layout menu action (`save`, `save-as`, `undo`,
+ * `redo`) as its `detail`, for the open layout editor to handle.
+ */
+export const MENU_EVENT = 'lazyqmk:menu';
+
+/**
+ * Calls `onAction` for each native menu click the frontend handles, and
+ * `onOpenRecent` with the filename of a File > Open Recent item.
+ * Resolves to a function that stops listening.
+ */
+export async function listenToMenu(
+	onAction: (action: MenuAction) => void,
+	onOpenRecent: (filename: string) => void
+): Promise<UnlistenFn> {
+	const unlisteners = await Promise.all([
+		...MENU_ACTIONS.map((action) => listen(`menu:${action}`, () => onAction(action))),
+		listen<string>('menu:open-recent', (event) => onOpenRecent(event.payload))
+	]);
+	return () => unlisteners.forEach((unlisten) => unlisten());
+}
@@ -10,6 +10,7 @@ export interface LayoutSummary {
 	name: string;
 	description: string;
 	modified: string;
+	duplicate_of?: string;
 }
 
 export interface LayoutListResponse {
@@ -33,6 +34,20 @@ export interface Layout {
 	tap_hold_settings?: TapHoldSettings;
 	// Categories
 	categories?: Category[];
+	// Keys outside the keyboard geometry (GET only)
+	geometry_mismatch?: GeometryMismatch | null;
+}
+
+export interface GeometryMismatch {
+	geometry_keys: number;
+	layers: LayerMismatch[];
+}
+
+export interface LayerMismatch {
+	layer: number;
+	name: string;
+	key_count: number;
+	extra: { row: number; col: number }[];
 }
 
 export interface LayoutMetadata {
@@ -46,6 +61,7 @@ export interface LayoutMetadata {
 	idle_effect?: IdleEffect;
 	layout_variant?: string;
 	keymap_name?: string;
+	converter?: string;
 	output_format?: string;
 	tags?: string[];
 	is_template?: boolean;
@@ -81,6 +97,8 @@ export interface Category {
 	name: string;
 	color: RgbColor;
 	description?: string;
+	always_on?: boolean;
+	breathing?: boolean;
 }
 
 export interface RgbColor {
@@ -119,6 +137,8 @@ export interface TapDance {
 	hold?: string;
 	double_tap?: string;
 	tap_hold?: string;
+	tapping_term_ms?: number;
+	on_each_tap?: boolean;
 }
 
 export interface Combo {
@@ -387,16 +407,27 @@ export interface BuildJob {
 	layout_filename: string;
 	keyboard: string;
 	keymap: string;
+	converter?: string;
 	created_at: string;
 	started_at?: string;
 	completed_at?: string;
 	error?: string;
 	firmware_path?: string;
 	progress: number;
+	firmware_size?: FirmwareSize;
+}
+
+export interface FirmwareSize {
+	flash_used: number;
+	flash_max?: number;
+	ram_used?: number;
+	ram_max?: number;
 }
 
 export interface StartBuildRequest {
 	layout_filename: string;
+	/** Build even if the layout's keyboard differs from build.keyboard */
+	allow_keyboard_mismatch?: boolean;
 }
 
 export interface StartBuildResponse {
@@ -491,3 +522,33 @@ export interface RenderMetadataResponse {
 	/** Layer-indexed key metadata */
 	layers: LayerRenderMetadata[];
 }
+
+// Workspace Events
+export type WorkspaceEventType = 'layout-added' | 'layout-changed' | 'layout-removed';
+
+export interface WorkspaceEvent {
+	type: WorkspaceEventType;
+	filename: string;
+	modified?: string;
+}
+
+// Desktop (Tauri) backend settings
+
+/** Network settings for the desktop app's backend (`get_backend_settings`) */
+export interface BackendSettings {
+	/** Fixed port; a random free port when unset */
+	port?: number | null;
+	/** Address to bind to; loopback when unset */
+	bindAddress?: string | null;
+	/** Access token, required when binding to a non-loopback address */
+	authToken?: string | null;
+	/** URL other devices use to reach the backend (e.g., a reverse proxy) */
+	publicUrl?: string | null;
+	/** Log route templates instead of request paths, hiding layout names */
+	redactLogs?: boolean;
+}
+
+/** Error returned by the desktop `start_backend` command */
+export type StartBackendError =
+	| { kind: 'port-in-use'; port: number; message: string }
+	| { kind: 'failed'; message: string };
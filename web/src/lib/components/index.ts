@@ -2,6 +2,8 @@ export { default as Button } from './Button.svelte';
 export { default as Card } from './Card.svelte';
 export { default as CategoryManager } from './CategoryManager.svelte';
 export { default as ColorPicker } from './ColorPicker.svelte';
+export { default as DesktopMenu } from './DesktopMenu.svelte';
+export { default as DiagnosticsPanel } from './DiagnosticsPanel.svelte';
 export { default as Input } from './Input.svelte';
 export { default as KeyboardPreview } from './KeyboardPreview.svelte';
 export { default as KeycodePicker } from './KeycodePicker.svelte';
@@ -2,6 +2,7 @@ import { error } from '@sveltejs/kit';
 import type { PageLoad } from './$types';
 import type { Layout, ApiError } from '$api/types';
 import { addRecentLayout } from '$lib/utils/recentLayouts';
+import { addRecentLayout as addDesktopRecentLayout, isDesktop } from '$api/desktop';
 
 // Disable SSR for this page to allow proper API mocking in tests
 // and client-side rendering with live geometry updates
@@ -28,6 +29,12 @@ export const load: PageLoad = async ({ params, fetch }) => {
 		
 		// Track this layout as recently opened
 		addRecentLayout(params.name, layout.metadata.name);
+		if (isDesktop()) {
+			// Listed in the native File > Open Recent menu
+			addDesktopRecentLayout(params.name, layout.metadata.name).catch((e) =>
+				console.warn('Failed to update recent layouts menu:', e)
+			);
+		}
 		
 		return {
 			layout,